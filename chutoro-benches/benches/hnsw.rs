@@ -24,12 +24,16 @@ use chutoro_benches::{
         measure_peak_resident_set_size, write_hnsw_memory_report,
     },
     source::{
-        Anisotropy, GaussianBlobConfig, ManifoldConfig, ManifoldPattern, MnistConfig,
-        SyntheticSource, SyntheticTextConfig,
+        Anisotropy, GaussianBlobConfig, ManifoldConfig, ManifoldPattern, SyntheticSource,
+        SyntheticTextConfig,
     },
 };
 use chutoro_core::{CpuHnsw, DataSource, HnswError, HnswParams};
 
+#[path = "internal/real_datasets.rs"]
+mod real_datasets;
+use real_datasets::bench_real_datasets;
+
 /// Dataset sizes to benchmark.
 const POINT_COUNTS: &[usize] = &[100, 500, 1_000, 5_000];
 
@@ -368,19 +372,7 @@ fn hnsw_build_diverse_sources_impl(c: &mut Criterion) -> Result<(), BenchSetupEr
         &params,
     );
 
-    if std::env::var("CHUTORO_BENCH_ENABLE_MNIST").as_deref() == Ok("1") {
-        let mnist = SyntheticSource::load_mnist(&MnistConfig::default())?;
-        bench_build_source(
-            &mut group,
-            SourceBenchSpec {
-                bench_label: "mnist_baseline",
-                fail_label: "MNIST source",
-                point_count: mnist.len(),
-            },
-            &mnist,
-            &params,
-        );
-    }
+    bench_real_datasets(&mut group, &params)?;
 
     group.finish();
     Ok(())
@@ -3,21 +3,20 @@
 //! Varies `ef_construction` independently of `M` to show build-time versus
 //! recall trade-offs. Complements the main HNSW benchmarks in `hnsw.rs`
 //! which hold `ef_construction = M * 2` fixed.
-use std::{num::NonZeroUsize, path::PathBuf, time::Duration, time::Instant};
+use std::{path::PathBuf, time::Duration};
 
 use criterion::{BatchSize, BenchmarkId, Criterion, criterion_main};
 
 use chutoro_benches::{
     ef_sweep::{
         BENCH_SEED, EF_CONSTRUCTION_VALUES, EF_SWEEP_MAX_CONNECTIONS, EF_SWEEP_POINT_COUNTS,
-        make_bench_source, make_hnsw_params_with_ef, resolve_ef_construction,
+        RecallSweep, make_bench_source, make_hnsw_params_with_ef, resolve_ef_construction,
     },
     error::BenchSetupError,
     params::HnswBenchParams,
-    recall::{RecallMeasurement, RecallScore, brute_force_top_k, recall_at_k, write_recall_report},
-    source::SyntheticSource,
+    recall::{RecallMeasurement, write_recall_report},
 };
-use chutoro_core::{CpuHnsw, DataSource};
+use chutoro_core::CpuHnsw;
 
 #[path = "internal/quality_pass.rs"]
 mod quality_pass;
@@ -26,7 +25,7 @@ use quality_pass::measure_clustering_quality_vs_ef_impl;
 /// Dataset size for recall measurement.
 const RECALL_POINT_COUNT: usize = 1_000;
 
-/// Number of deterministic query points for recall measurement.
+/// Number of sampled query points for recall measurement.
 const RECALL_QUERY_COUNT: usize = 50;
 
 /// Number of nearest neighbours compared for recall@k.
@@ -142,78 +141,31 @@ fn cluster_quality_report_path() -> PathBuf {
     )
 }
 
-/// Returns an evenly-spaced query index for deterministic recall sampling.
-///
-/// Maps query ordinal `qi` in `0..RECALL_QUERY_COUNT` to an index in
-/// `0..len` using `(qi + 1) * len / (count + 1)`, which avoids bias
-/// towards lower indices and never returns index 0 or `len - 1` at the
-/// extremes (unless the dataset is very small).
-#[expect(
-    clippy::integer_division,
-    clippy::integer_division_remainder_used,
-    reason = "Intentional truncating division to produce evenly-spaced indices."
-)]
-const fn query_index(qi: usize, len: usize) -> usize {
-    qi.saturating_add(1).saturating_mul(len) / RECALL_QUERY_COUNT.saturating_add(1)
-}
-
-fn collect_recall_over_queries(
-    source: &SyntheticSource,
-    index: &CpuHnsw,
-    ef_search: NonZeroUsize,
-) -> Result<RecallScore, BenchSetupError> {
-    let len = source.len();
-    let mut total_hits: usize = 0;
-    let mut total_targets: usize = 0;
-
-    for qi in 0..RECALL_QUERY_COUNT {
-        let query = query_index(qi, len);
-        let mut hnsw_result = index.search(source, query, ef_search)?;
-        hnsw_result.retain(|n| n.id != query);
-        hnsw_result.truncate(RECALL_K);
-        let oracle = brute_force_top_k(source, query, RECALL_K)?;
-        let score = recall_at_k(&oracle, &hnsw_result, RECALL_K);
-        total_hits = total_hits.saturating_add(score.hits);
-        total_targets = total_targets.saturating_add(score.total);
-    }
-
-    Ok(RecallScore {
-        hits: total_hits,
-        total: total_targets,
-    })
-}
-
 fn measure_recall_vs_ef_impl() -> Result<Option<PathBuf>, BenchSetupError> {
     if !should_collect_recall_report() {
         return Ok(None);
     }
 
     let source = make_bench_source(RECALL_POINT_COUNT)?;
-    let ef_search = NonZeroUsize::new(RECALL_EF_SEARCH).ok_or(BenchSetupError::ZeroValue {
-        context: "RECALL_EF_SEARCH",
-    })?;
-    let mut records = Vec::new();
-
-    for &m in EF_SWEEP_MAX_CONNECTIONS {
-        for &ef_raw in EF_CONSTRUCTION_VALUES {
-            let ef = resolve_ef_construction(m, ef_raw);
-            let params = make_hnsw_params_with_ef(m, ef, BENCH_SEED)?;
-
-            let started = Instant::now();
-            let index = CpuHnsw::build(&source, params)?;
-            let build_time = started.elapsed();
-
-            let recall = collect_recall_over_queries(&source, &index, ef_search)?;
-
-            records.push(RecallMeasurement {
-                point_count: RECALL_POINT_COUNT,
-                max_connections: m,
-                ef_construction: ef,
-                recall,
-                build_time_millis: build_time.as_millis(),
-            });
-        }
-    }
+    let sweep = RecallSweep::new()
+        .with_max_connections(EF_SWEEP_MAX_CONNECTIONS.to_vec())
+        .with_ef_construction(EF_CONSTRUCTION_VALUES.to_vec())
+        .with_ef_search(vec![RECALL_EF_SEARCH])
+        .with_k(RECALL_K)
+        .with_query_count(RECALL_QUERY_COUNT)
+        .with_seed(BENCH_SEED)
+        .run(&source)?;
+    let records: Vec<_> = sweep
+        .points
+        .into_iter()
+        .map(|point| RecallMeasurement {
+            point_count: RECALL_POINT_COUNT,
+            max_connections: point.max_connections,
+            ef_construction: point.ef_construction,
+            recall: point.recall,
+            build_time_millis: point.build_time_millis,
+        })
+        .collect();
 
     write_recall_report(recall_report_path(), &records)
         .map(Some)
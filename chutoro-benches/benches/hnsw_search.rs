@@ -0,0 +1,139 @@
+//! HNSW search throughput benchmarks for the live and frozen graph layouts.
+//!
+//! Builds each index once, then measures a fixed batch of queries against
+//! the mutable graph that insertion uses (`CpuHnsw::search`) and against its
+//! compressed sparse row snapshot (`FrozenHnsw::search`). Both layouts walk
+//! the same lists and return the same neighbours, so the difference is the
+//! cost of reaching each neighbour list.
+#![expect(
+    missing_docs,
+    reason = "Criterion macros generate items without doc comments"
+)]
+use std::num::NonZeroUsize;
+
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+
+use chutoro_benches::{
+    criterion_support::{
+        configure_short_measurement_group, is_benchmark_discovery, is_exact_benchmark_probe,
+        is_nextest_exact_benchmark_probe, register_noop_benches,
+    },
+    ef_sweep::{BENCH_SEED, make_bench_source, make_hnsw_params_with_ef},
+    error::BenchSetupError,
+    params::HnswBenchParams,
+    source::SyntheticSource,
+};
+use chutoro_core::{CpuHnsw, HnswError, Neighbour};
+
+/// Dataset sizes to benchmark.
+const POINT_COUNTS: &[usize] = &[1_000, 10_000];
+
+/// HNSW M parameter used for every index.
+const M: usize = 16;
+
+/// Construction search width used for every index.
+const EF_CONSTRUCTION: usize = 64;
+
+/// Search beam width for the measured queries.
+const EF_SEARCH: usize = 64;
+
+/// Queries per measured iteration, spread evenly over the dataset.
+const QUERY_COUNT: usize = 256;
+
+/// Layout labels, in the order each dataset is measured.
+const LAYOUTS: &[&str] = &["live", "frozen"];
+
+const fn bench_params(point_count: usize) -> HnswBenchParams {
+    HnswBenchParams {
+        point_count,
+        max_connections: M,
+        ef_construction: EF_CONSTRUCTION,
+    }
+}
+
+fn queries(point_count: usize) -> Vec<usize> {
+    let stride = point_count.div_ceil(QUERY_COUNT).max(1);
+    (0..point_count).step_by(stride).take(QUERY_COUNT).collect()
+}
+
+/// Runs every query through `search`, panicking on the first failure.
+///
+/// Exists as a separate function because Criterion measurement closures
+/// cannot propagate errors.
+fn run_queries(
+    source: &SyntheticSource,
+    queries: &[usize],
+    search: impl Fn(&SyntheticSource, usize) -> Result<Vec<Neighbour>, HnswError>,
+) {
+    for &query in queries {
+        match search(source, query) {
+            Ok(neighbours) => {
+                black_box(neighbours);
+            }
+            Err(err) => panic!("search for {query} failed during benchmark: {err}"),
+        }
+    }
+}
+
+fn hnsw_search_layout_impl(c: &mut Criterion) -> Result<(), BenchSetupError> {
+    if is_benchmark_discovery() || is_nextest_exact_benchmark_probe() {
+        register_layout_discovery_benches(c);
+        return Ok(());
+    }
+
+    let mut group = c.benchmark_group("hnsw_search_layout");
+    configure_short_measurement_group(&mut group, 20, is_exact_benchmark_probe());
+    let ef = NonZeroUsize::new(EF_SEARCH).unwrap_or(NonZeroUsize::MIN);
+
+    for &point_count in POINT_COUNTS {
+        let source = make_bench_source(point_count)?;
+        let params = make_hnsw_params_with_ef(M, EF_CONSTRUCTION, BENCH_SEED)?;
+        let index = CpuHnsw::build(&source, params)?;
+        let frozen = index.freeze()?;
+        let queries = queries(point_count);
+        let bench_params = bench_params(point_count);
+        group.throughput(Throughput::Elements(queries.len() as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("live", &bench_params),
+            &queries,
+            |b, batch| {
+                b.iter(|| {
+                    run_queries(&source, batch, |data, query| index.search(data, query, ef));
+                });
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("frozen", &bench_params),
+            &queries,
+            |b, batch| {
+                b.iter(|| {
+                    run_queries(&source, batch, |data, query| frozen.search(data, query, ef));
+                });
+            },
+        );
+    }
+
+    group.finish();
+    Ok(())
+}
+
+fn register_layout_discovery_benches(c: &mut Criterion) {
+    let params = POINT_COUNTS.iter().flat_map(|&point_count| {
+        LAYOUTS
+            .iter()
+            .map(move |layout| format!("{layout}/{}", bench_params(point_count)))
+    });
+    register_noop_benches(c, "hnsw_search_layout", params, |group| {
+        configure_short_measurement_group(group, 20, is_exact_benchmark_probe());
+    });
+}
+
+fn hnsw_search_layout(c: &mut Criterion) {
+    if let Err(err) = hnsw_search_layout_impl(c) {
+        panic!("hnsw_search_layout benchmark setup failed: {err}");
+    }
+}
+
+criterion_group!(benches, hnsw_search_layout);
+criterion_main!(benches);
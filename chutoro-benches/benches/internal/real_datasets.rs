@@ -0,0 +1,70 @@
+//! Opt-in real-world dataset cases for the HNSW diverse-source benchmark.
+//!
+//! Each dataset downloads on first use and is then read from its local
+//! cache, so every case stays behind its own environment flag to keep the
+//! default developer loop offline.
+
+use criterion::{BenchmarkGroup, measurement::WallTime};
+
+use chutoro_benches::{
+    error::BenchSetupError,
+    source::{
+        FashionMnistConfig, MnistConfig, NewsgroupsConfig, NewsgroupsCorpus, SyntheticSource,
+    },
+};
+use chutoro_core::{DataSource, HnswParams};
+
+use crate::{SourceBenchSpec, bench_build_source};
+
+fn enabled(flag: &str) -> bool {
+    std::env::var(flag).as_deref() == Ok("1")
+}
+
+/// Benchmarks every real-world dataset whose flag is set to `1`:
+/// `CHUTORO_BENCH_ENABLE_MNIST`, `CHUTORO_BENCH_ENABLE_FASHION_MNIST`, and
+/// `CHUTORO_BENCH_ENABLE_NEWSGROUPS`.
+pub(crate) fn bench_real_datasets(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    params: &HnswParams,
+) -> Result<(), BenchSetupError> {
+    if enabled("CHUTORO_BENCH_ENABLE_MNIST") {
+        let mnist = SyntheticSource::load_mnist(&MnistConfig::default())?;
+        bench_build_source(
+            group,
+            SourceBenchSpec {
+                bench_label: "mnist_baseline",
+                fail_label: "MNIST source",
+                point_count: mnist.len(),
+            },
+            &mnist,
+            params,
+        );
+    }
+    if enabled("CHUTORO_BENCH_ENABLE_FASHION_MNIST") {
+        let fashion = SyntheticSource::load_fashion_mnist(&FashionMnistConfig::default())?;
+        bench_build_source(
+            group,
+            SourceBenchSpec {
+                bench_label: "fashion_mnist_baseline",
+                fail_label: "Fashion-MNIST source",
+                point_count: fashion.len(),
+            },
+            &fashion,
+            params,
+        );
+    }
+    if enabled("CHUTORO_BENCH_ENABLE_NEWSGROUPS") {
+        let newsgroups = NewsgroupsCorpus::load(&NewsgroupsConfig::default())?.into_text_source();
+        bench_build_source(
+            group,
+            SourceBenchSpec {
+                bench_label: "newsgroups_levenshtein",
+                fail_label: "20-newsgroups source",
+                point_count: newsgroups.len(),
+            },
+            &newsgroups,
+            params,
+        );
+    }
+    Ok(())
+}
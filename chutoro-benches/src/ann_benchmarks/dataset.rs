@@ -0,0 +1,351 @@
+//! Loading ann-benchmarks datasets and exposing them as a [`DataSource`].
+
+use std::path::Path;
+
+use chutoro_core::{DataSource, DataSourceError, MetricDescriptor};
+
+use super::{
+    AnnError, AnnMetric,
+    hdf5::{Dataset, Hdf5Error, Hdf5File},
+};
+
+/// The datasets every ann-benchmarks file holds, in load order.
+const DATASETS: [&str; 4] = ["train", "test", "neighbors", "distances"];
+
+/// An ann-benchmarks dataset held in memory.
+///
+/// Training vectors come first and test queries follow, so query `q` is row
+/// `train_len() + q` of the [`DataSource`] views.
+#[derive(Clone, Debug)]
+pub struct AnnDataset {
+    name: String,
+    metric: AnnMetric,
+    dimensions: usize,
+    train: usize,
+    queries: usize,
+    vectors: Vec<f32>,
+    norms: Vec<f32>,
+    depth: usize,
+    neighbours: Vec<usize>,
+    distances: Vec<f32>,
+}
+
+impl AnnDataset {
+    /// Reads the `train`, `test`, `neighbors`, and `distances` datasets from
+    /// the HDF5 file at `path`.
+    ///
+    /// The metric is `metric` when given, otherwise the file's `distance`
+    /// attribute, otherwise the `-euclidean` or `-angular` suffix that every
+    /// published file name carries.
+    ///
+    /// # Errors
+    /// Returns [`AnnError::Hdf5`] when the file cannot be read,
+    /// [`AnnError::InvalidDataset`] when a dataset is missing or the shapes
+    /// disagree, and [`AnnError::UnknownMetric`] when no supported metric can
+    /// be determined.
+    pub fn load(path: &Path, metric_override: Option<AnnMetric>) -> Result<Self, AnnError> {
+        let hdf5 = |source| AnnError::Hdf5 {
+            path: path.to_path_buf(),
+            source,
+        };
+        let mut file = Hdf5File::open(path).map_err(hdf5)?;
+        let metric = match metric_override {
+            Some(metric) => metric,
+            None => metric_for(path, file.string_attribute("distance").map_err(hdf5)?)?,
+        };
+        let mut datasets = Vec::with_capacity(DATASETS.len());
+        for name in DATASETS {
+            let Some(dataset) = file.dataset(name).map_err(hdf5)? else {
+                let members = file.members().map_err(hdf5)?;
+                return Err(invalid(format!(
+                    "the file has no `{name}` dataset; it holds {}",
+                    members.join(", ")
+                )));
+            };
+            datasets.push(dataset);
+        }
+        let [train, test, neighbours, distances] = <[Dataset; 4]>::try_from(datasets)
+            .map_err(|_| invalid("the file is missing a dataset".to_owned()))?;
+        let shape = Shape::check(&train, &test, &neighbours, &distances)?;
+        let name = path.file_stem().map_or_else(
+            || path.display().to_string(),
+            |stem| stem.to_string_lossy().into_owned(),
+        );
+        let decoded = Decoded::read(&train, &test, &neighbours, &distances).map_err(hdf5)?;
+        Self::new(name, metric, &shape, decoded)
+    }
+
+    fn new(
+        name: String,
+        metric: AnnMetric,
+        shape: &Shape,
+        decoded: Decoded,
+    ) -> Result<Self, AnnError> {
+        if let Some(index) = decoded
+            .neighbours
+            .iter()
+            .find(|neighbour| **neighbour >= shape.train)
+        {
+            return Err(invalid(format!(
+                "`neighbors` names row {index} but `train` has {} rows",
+                shape.train
+            )));
+        }
+        let norms = match metric {
+            AnnMetric::Angular => decoded
+                .vectors
+                .chunks_exact(shape.dimensions)
+                .map(norm)
+                .collect(),
+            AnnMetric::Euclidean => Vec::new(),
+        };
+        Ok(Self {
+            name,
+            metric,
+            dimensions: shape.dimensions,
+            train: shape.train,
+            queries: shape.queries,
+            vectors: decoded.vectors,
+            norms,
+            depth: shape.depth,
+            neighbours: decoded.neighbours,
+            distances: decoded.distances,
+        })
+    }
+
+    /// Returns the file stem the dataset was loaded from.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the metric distances are computed with.
+    #[must_use]
+    pub const fn metric(&self) -> AnnMetric {
+        self.metric
+    }
+
+    /// Returns the dimensionality of every vector.
+    #[must_use]
+    pub const fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// Returns the number of training vectors.
+    #[must_use]
+    pub const fn train_len(&self) -> usize {
+        self.train
+    }
+
+    /// Returns the number of test queries.
+    #[must_use]
+    pub const fn query_len(&self) -> usize {
+        self.queries
+    }
+
+    /// Returns how many ground-truth neighbours are stored per query.
+    #[must_use]
+    pub const fn ground_truth_depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the ground-truth neighbour rows of `query`, closest first.
+    #[must_use]
+    pub fn ground_truth(&self, query: usize) -> Option<&[usize]> {
+        let start = query.checked_mul(self.depth)?;
+        self.neighbours.get(start..start.checked_add(self.depth)?)
+    }
+
+    /// Returns the distance from `query` to its `k`th true neighbour.
+    pub(super) fn kth_distance(&self, query: usize, k: usize) -> Option<f32> {
+        if k == 0 || k > self.depth {
+            return None;
+        }
+        let offset = query.checked_mul(self.depth)?.checked_add(k - 1)?;
+        self.distances.get(offset).copied()
+    }
+
+    /// Returns a view of the training vectors, for building the index.
+    pub(super) const fn training(&self) -> AnnSource<'_> {
+        AnnSource {
+            dataset: self,
+            len: self.train,
+        }
+    }
+
+    /// Returns a view of the training vectors followed by the queries.
+    pub(super) const fn with_queries(&self) -> AnnSource<'_> {
+        AnnSource {
+            dataset: self,
+            len: self.train + self.queries,
+        }
+    }
+
+    fn row(&self, index: usize) -> Result<&[f32], DataSourceError> {
+        let start = index
+            .checked_mul(self.dimensions)
+            .ok_or(DataSourceError::OutOfBounds { index })?;
+        start
+            .checked_add(self.dimensions)
+            .and_then(|end| self.vectors.get(start..end))
+            .ok_or(DataSourceError::OutOfBounds { index })
+    }
+
+    #[expect(
+        clippy::float_arithmetic,
+        reason = "vector distances require floating-point arithmetic"
+    )]
+    fn distance(&self, left: usize, right: usize) -> Result<f32, DataSourceError> {
+        let a = self.row(left)?;
+        let b = self.row(right)?;
+        match self.metric {
+            AnnMetric::Euclidean => Ok(a
+                .iter()
+                .zip(b)
+                .fold(0.0_f32, |sum, (x, y)| sum + (x - y) * (x - y))
+                .sqrt()),
+            AnnMetric::Angular => {
+                let dot = a.iter().zip(b).fold(0.0_f32, |sum, (x, y)| sum + x * y);
+                let scale = self.norms.get(left).copied().unwrap_or_default()
+                    * self.norms.get(right).copied().unwrap_or_default();
+                if scale == 0.0 {
+                    return Ok(1.0);
+                }
+                Ok((1.0 - dot / scale).max(0.0))
+            }
+        }
+    }
+}
+
+/// Row counts and widths shared by the four datasets.
+struct Shape {
+    train: usize,
+    queries: usize,
+    dimensions: usize,
+    depth: usize,
+}
+
+impl Shape {
+    fn check(
+        train: &Dataset,
+        test: &Dataset,
+        neighbours: &Dataset,
+        distances: &Dataset,
+    ) -> Result<Self, AnnError> {
+        let (train_rows, dimensions) = matrix_shape("train", train)?;
+        let (queries, test_dimensions) = matrix_shape("test", test)?;
+        let (truth_rows, depth) = matrix_shape("neighbors", neighbours)?;
+        if dimensions == 0 || train_rows == 0 {
+            return Err(invalid("`train` must hold at least one vector".to_owned()));
+        }
+        if test_dimensions != dimensions {
+            return Err(invalid(format!(
+                "`test` has {test_dimensions} dimensions but `train` has {dimensions}"
+            )));
+        }
+        if truth_rows != queries || distances.shape() != neighbours.shape() {
+            return Err(invalid(
+                "`neighbors` and `distances` must hold one row per test query".to_owned(),
+            ));
+        }
+        Ok(Self {
+            train: train_rows,
+            queries,
+            dimensions,
+            depth,
+        })
+    }
+}
+
+/// The decoded contents of the four datasets.
+struct Decoded {
+    vectors: Vec<f32>,
+    neighbours: Vec<usize>,
+    distances: Vec<f32>,
+}
+
+impl Decoded {
+    fn read(
+        train: &Dataset,
+        test: &Dataset,
+        neighbours: &Dataset,
+        distances: &Dataset,
+    ) -> Result<Self, Hdf5Error> {
+        let mut vectors = train.to_f32()?;
+        vectors.extend(test.to_f32()?);
+        Ok(Self {
+            vectors,
+            neighbours: neighbours.to_indices()?,
+            distances: distances.to_f32()?,
+        })
+    }
+}
+
+/// A [`DataSource`] over the first `len` rows of an [`AnnDataset`].
+#[derive(Clone, Copy, Debug)]
+pub(super) struct AnnSource<'a> {
+    dataset: &'a AnnDataset,
+    len: usize,
+}
+
+impl DataSource for AnnSource<'_> {
+    #[rustfmt::skip]
+    fn len(&self) -> usize { self.len }
+
+    #[rustfmt::skip]
+    fn name(&self) -> &str { &self.dataset.name }
+
+    fn metric_descriptor(&self) -> MetricDescriptor {
+        MetricDescriptor::new(self.dataset.metric.as_str())
+    }
+
+    fn distance(&self, left: usize, right: usize) -> Result<f32, DataSourceError> {
+        if left >= self.len {
+            return Err(DataSourceError::OutOfBounds { index: left });
+        }
+        if right >= self.len {
+            return Err(DataSourceError::OutOfBounds { index: right });
+        }
+        self.dataset.distance(left, right)
+    }
+}
+
+/// Chooses the metric from the `distance` attribute or the file name.
+fn metric_for(path: &Path, attribute: Option<String>) -> Result<AnnMetric, AnnError> {
+    let suffix = || {
+        path.file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.rsplit_once('-'))
+            .map(|(_, suffix)| suffix.to_owned())
+    };
+    attribute.or_else(suffix).map_or_else(
+        || {
+            Err(AnnError::UnknownMetric {
+                name: String::new(),
+            })
+        },
+        |name| name.parse(),
+    )
+}
+
+fn matrix_shape(name: &str, dataset: &Dataset) -> Result<(usize, usize), AnnError> {
+    match dataset.shape() {
+        [rows, columns] => Ok((*rows, *columns)),
+        shape => Err(invalid(format!(
+            "`{name}` must be two-dimensional, found {} dimensions",
+            shape.len()
+        ))),
+    }
+}
+
+#[expect(
+    clippy::float_arithmetic,
+    reason = "vector norms require floating-point arithmetic"
+)]
+fn norm(vector: &[f32]) -> f32 {
+    vector.iter().fold(0.0_f32, |sum, x| sum + x * x).sqrt()
+}
+
+const fn invalid(reason: String) -> AnnError {
+    AnnError::InvalidDataset { reason }
+}
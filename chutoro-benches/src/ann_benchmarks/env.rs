@@ -0,0 +1,87 @@
+//! Environment-variable configuration for the `chutoro-ann-benchmarks`
+//! binary.
+//!
+//! `CHUTORO_ANN_DATASET` is required; every other [`AnnConfig`] field can be
+//! overridden by a `CHUTORO_ANN_*` variable, and unset variables keep the
+//! [`AnnConfig::new`] value.
+
+use std::path::PathBuf;
+
+use super::{AnnConfig, AnnError};
+use crate::settings::SettingReader;
+
+/// Prefix shared by every ann-benchmarks configuration variable.
+pub const ANN_ENV_PREFIX: &str = "CHUTORO_ANN_";
+
+/// Reads the adapter configuration from the process environment.
+///
+/// # Errors
+/// Returns [`AnnError::MissingSetting`] when `CHUTORO_ANN_DATASET` is unset
+/// and [`AnnError::InvalidSetting`] when a variable does not parse.
+///
+/// # Examples
+/// ```
+/// use chutoro_benches::ann_benchmarks::config_from_env;
+///
+/// let _config = config_from_env();
+/// ```
+pub fn config_from_env() -> Result<AnnConfig, AnnError> {
+    config_from_lookup(|name| std::env::var(name).ok())
+}
+
+/// Builds an adapter configuration from `lookup`, which maps a variable name
+/// to its value.
+///
+/// | Variable                       | Field             |
+/// | ------------------------------ | ----------------- |
+/// | `CHUTORO_ANN_DATASET`          | `dataset`         |
+/// | `CHUTORO_ANN_METRIC`           | `metric`          |
+/// | `CHUTORO_ANN_K`                | `k`               |
+/// | `CHUTORO_ANN_MAX_CONNECTIONS`  | `max_connections` |
+/// | `CHUTORO_ANN_EF_CONSTRUCTION`  | `ef_construction` |
+/// | `CHUTORO_ANN_EF_SEARCH`        | `ef_search`       |
+/// | `CHUTORO_ANN_QUERIES`          | `query_limit`     |
+/// | `CHUTORO_ANN_SEED`             | `seed`            |
+/// | `CHUTORO_ANN_OUTPUT`           | `output`          |
+///
+/// `CHUTORO_ANN_EF_SEARCH` is a comma-separated list, and
+/// `CHUTORO_ANN_METRIC` is `euclidean` or `angular`.
+///
+/// # Errors
+/// Returns [`AnnError::MissingSetting`] when `CHUTORO_ANN_DATASET` is unset
+/// and [`AnnError::InvalidSetting`] when a variable does not parse.
+///
+/// # Examples
+/// ```
+/// use chutoro_benches::ann_benchmarks::config_from_lookup;
+///
+/// let config = config_from_lookup(|name| match name {
+///     "CHUTORO_ANN_DATASET" => Some("glove-25-angular.hdf5".to_owned()),
+///     "CHUTORO_ANN_EF_SEARCH" => Some("16, 64".to_owned()),
+///     _ => None,
+/// })
+/// .expect("settings must parse");
+/// assert_eq!(config.ef_search.len(), 2);
+/// ```
+pub fn config_from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<AnnConfig, AnnError> {
+    let read = SettingReader::new(ANN_ENV_PREFIX, lookup);
+    let dataset = read
+        .value("DATASET")
+        .ok_or_else(|| AnnError::MissingSetting {
+            name: read.name("DATASET"),
+        })?;
+    let defaults = AnnConfig::new(dataset);
+    Ok(AnnConfig {
+        metric: read.optional("METRIC", "euclidean or angular")?,
+        k: read.parsed("K", defaults.k, "a positive integer")?,
+        max_connections: read.parsed("MAX_CONNECTIONS", defaults.max_connections, "an integer")?,
+        ef_construction: read.parsed("EF_CONSTRUCTION", defaults.ef_construction, "an integer")?,
+        ef_search: read.list("EF_SEARCH", defaults.ef_search)?,
+        query_limit: read.optional("QUERIES", "a positive integer")?,
+        seed: read.parsed("SEED", defaults.seed, "an unsigned 64-bit integer")?,
+        output: read
+            .optional::<String>("OUTPUT", "a path")?
+            .map(PathBuf::from),
+        ..defaults
+    })
+}
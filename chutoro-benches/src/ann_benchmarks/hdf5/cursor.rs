@@ -0,0 +1,93 @@
+//! Sequential decoding of the little-endian structures in an HDF5 file.
+
+use super::{Hdf5Error, UNDEFINED_ADDRESS, Widths, le_u64, malformed};
+
+/// Sequential little-endian decoding over a byte slice.
+pub(super) struct Cursor<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    widths: Widths,
+}
+
+impl<'a> Cursor<'a> {
+    pub(super) const fn new(bytes: &'a [u8], widths: Widths) -> Self {
+        Self {
+            bytes,
+            position: 0,
+            widths,
+        }
+    }
+
+    pub(super) const fn remaining(&self) -> usize {
+        self.bytes.len().saturating_sub(self.position)
+    }
+
+    pub(super) fn take(&mut self, count: usize) -> Result<&'a [u8], Hdf5Error> {
+        let end = self
+            .position
+            .checked_add(count)
+            .ok_or_else(|| malformed("structure length overflows"))?;
+        let slice = self
+            .bytes
+            .get(self.position..end)
+            .ok_or_else(|| malformed("structure is truncated"))?;
+        self.position = end;
+        Ok(slice)
+    }
+
+    pub(super) fn skip(&mut self, count: usize) -> Result<(), Hdf5Error> {
+        self.take(count).map(|_| ())
+    }
+
+    /// Skips to the next multiple of eight from the start of the slice.
+    pub(super) fn align8(&mut self) -> Result<(), Hdf5Error> {
+        let padding = self.position.next_multiple_of(8) - self.position;
+        self.skip(padding.min(self.remaining()))
+    }
+
+    pub(super) fn uint(&mut self, width: usize) -> Result<u64, Hdf5Error> {
+        self.take(width).map(le_u64)
+    }
+
+    pub(super) fn u8(&mut self) -> Result<u8, Hdf5Error> {
+        self.take(1)?
+            .first()
+            .copied()
+            .ok_or_else(|| malformed("structure is truncated"))
+    }
+
+    pub(super) fn u16(&mut self) -> Result<u16, Hdf5Error> {
+        u16::try_from(self.uint(2)?).map_err(|_| malformed("invalid 16-bit field"))
+    }
+
+    pub(super) fn u32(&mut self) -> Result<u32, Hdf5Error> {
+        u32::try_from(self.uint(4)?).map_err(|_| malformed("invalid 32-bit field"))
+    }
+
+    pub(super) fn address(&mut self) -> Result<u64, Hdf5Error> {
+        let address = self.uint(self.widths.offset)?;
+        Ok(
+            if self.widths.offset < 8 && address == (1_u64 << (self.widths.offset * 8)) - 1 {
+                UNDEFINED_ADDRESS
+            } else {
+                address
+            },
+        )
+    }
+
+    pub(super) fn length(&mut self) -> Result<u64, Hdf5Error> {
+        self.uint(self.widths.length)
+    }
+
+    pub(super) fn signature(
+        &mut self,
+        expected: [u8; 4],
+        structure: &str,
+    ) -> Result<(), Hdf5Error> {
+        if self.take(4)? == expected {
+            Ok(())
+        } else {
+            Err(malformed(format!("missing {structure} signature")))
+        }
+    }
+}
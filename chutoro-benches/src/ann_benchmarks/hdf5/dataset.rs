@@ -0,0 +1,203 @@
+//! Numeric datasets: their dataspace, element type, and contiguous layout.
+
+use super::{
+    Cursor, Hdf5Error, Hdf5File, UNDEFINED_ADDRESS, le_u64, malformed,
+    object_header::{MESSAGE_DATASPACE, MESSAGE_DATATYPE, MESSAGE_FILTERS, MESSAGE_LAYOUT},
+    size_to_usize, unsupported,
+};
+
+/// Element types the reader can convert.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Element {
+    F32,
+    F64,
+    I32,
+    I64,
+}
+
+impl Element {
+    const fn size(self) -> usize {
+        match self {
+            Self::F32 | Self::I32 => 4,
+            Self::F64 | Self::I64 => 8,
+        }
+    }
+}
+
+/// A numeric dataset read in full.
+#[derive(Debug)]
+pub struct Dataset {
+    shape: Vec<usize>,
+    element: Element,
+    bytes: Vec<u8>,
+}
+
+impl Dataset {
+    /// Returns the extent of each dimension.
+    #[must_use]
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// Returns the values as `f32`, row-major.
+    ///
+    /// # Errors
+    /// Returns [`Hdf5Error::Unsupported`] when the dataset is not floating
+    /// point.
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "double-precision vectors are narrowed to the f32 the index stores"
+    )]
+    pub fn to_f32(&self) -> Result<Vec<f32>, Hdf5Error> {
+        let words = self.bytes.chunks_exact(self.element.size()).map(le_u64);
+        match self.element {
+            Element::F32 => Ok(words
+                .map(|word| f32::from_bits(u32::try_from(word).unwrap_or_default()))
+                .collect()),
+            Element::F64 => Ok(words.map(|word| f64::from_bits(word) as f32).collect()),
+            Element::I32 | Element::I64 => Err(unsupported("expected a floating-point dataset")),
+        }
+    }
+
+    /// Returns the values as non-negative indices, row-major.
+    ///
+    /// # Errors
+    /// Returns [`Hdf5Error::Unsupported`] when the dataset is not integral
+    /// and [`Hdf5Error::Malformed`] when a value is negative.
+    pub fn to_indices(&self) -> Result<Vec<usize>, Hdf5Error> {
+        let width = self.element.size();
+        let sign_bit = match self.element {
+            Element::I32 => 1_u64 << 31,
+            Element::I64 => 1_u64 << 63,
+            Element::F32 | Element::F64 => {
+                return Err(unsupported("expected an integer dataset"));
+            }
+        };
+        self.bytes
+            .chunks_exact(width)
+            .map(|chunk| {
+                let word = le_u64(chunk);
+                if word & sign_bit != 0 {
+                    return Err(malformed("negative index in an index dataset"));
+                }
+                usize::try_from(word).map_err(|_| malformed("index exceeds usize"))
+            })
+            .collect()
+    }
+}
+
+/// The parts of a dataset's object header the reader needs.
+#[derive(Default)]
+struct DatasetHeader {
+    shape: Option<Vec<usize>>,
+    element: Option<Element>,
+    address: Option<u64>,
+}
+
+impl Hdf5File {
+    /// Reads the root-group dataset called `name`, if there is one.
+    ///
+    /// # Errors
+    /// Returns [`Hdf5Error`] when the dataset is compressed, chunked, or of
+    /// an unsupported element type, or when its structures are malformed.
+    pub fn dataset(&mut self, name: &str) -> Result<Option<Dataset>, Hdf5Error> {
+        let Some(member) = self
+            .root_members()?
+            .into_iter()
+            .find(|member| member.name == name)
+        else {
+            return Ok(None);
+        };
+        let header = self.dataset_header(member.header, name)?;
+        let (Some(shape), Some(element)) = (header.shape, header.element) else {
+            return Err(malformed(format!("`{name}` is not a dataset")));
+        };
+        let count = shape
+            .iter()
+            .try_fold(1_usize, |total, extent| total.checked_mul(*extent))
+            .and_then(|total| total.checked_mul(element.size()))
+            .ok_or_else(|| malformed(format!("`{name}` is too large")))?;
+        let bytes = match header.address {
+            _ if count == 0 => Vec::new(),
+            Some(address) if address != UNDEFINED_ADDRESS => self.read_at(address, count)?,
+            _ => return Err(malformed(format!("`{name}` has no stored data"))),
+        };
+        Ok(Some(Dataset {
+            shape,
+            element,
+            bytes,
+        }))
+    }
+
+    fn dataset_header(&mut self, address: u64, name: &str) -> Result<DatasetHeader, Hdf5Error> {
+        let mut header = DatasetHeader::default();
+        for message in self.messages(address)? {
+            let mut cursor = Cursor::new(&message.body, self.widths);
+            match message.kind {
+                MESSAGE_DATASPACE => header.shape = Some(read_dataspace(&mut cursor)?),
+                MESSAGE_DATATYPE => header.element = Some(read_element(&mut cursor, name)?),
+                MESSAGE_LAYOUT => header.address = Some(read_layout(&mut cursor, name)?),
+                MESSAGE_FILTERS => {
+                    return Err(unsupported(format!(
+                        "`{name}` is filtered or compressed; repack it with `h5repack -l CONTI`"
+                    )));
+                }
+                _ => {}
+            }
+        }
+        Ok(header)
+    }
+}
+
+fn read_dataspace(cursor: &mut Cursor<'_>) -> Result<Vec<usize>, Hdf5Error> {
+    let version = cursor.u8()?;
+    let rank = usize::from(cursor.u8()?);
+    cursor.skip(1)?;
+    match version {
+        1 => cursor.skip(5)?,
+        2 => cursor.skip(1)?,
+        other => return Err(unsupported(format!("dataspace version {other}"))),
+    }
+    (0..rank)
+        .map(|_| cursor.length().and_then(size_to_usize))
+        .collect()
+}
+
+fn read_element(cursor: &mut Cursor<'_>, name: &str) -> Result<Element, Hdf5Error> {
+    let class = cursor.u8()? & 0x0f;
+    let bits = cursor.u8()?;
+    cursor.skip(2)?;
+    let size = cursor.u32()?;
+    if bits & 0x01 != 0 {
+        return Err(unsupported(format!("`{name}` is stored big-endian")));
+    }
+    match (class, size) {
+        (0, 4) if bits & 0x08 != 0 => Ok(Element::I32),
+        (0, 8) if bits & 0x08 != 0 => Ok(Element::I64),
+        (1, 4) => Ok(Element::F32),
+        (1, 8) => Ok(Element::F64),
+        _ => Err(unsupported(format!(
+            "`{name}` has element class {class} of {size} bytes; expected f32, f64, i32, or i64"
+        ))),
+    }
+}
+
+fn read_layout(cursor: &mut Cursor<'_>, name: &str) -> Result<u64, Hdf5Error> {
+    let version = cursor.u8()?;
+    let class = match version {
+        1 | 2 => {
+            cursor.skip(1)?;
+            let class = cursor.u8()?;
+            cursor.skip(5)?;
+            class
+        }
+        3 | 4 => cursor.u8()?,
+        other => return Err(unsupported(format!("data layout version {other}"))),
+    };
+    if class != 1 {
+        return Err(unsupported(format!(
+            "`{name}` is not stored contiguously; repack it with `h5repack -l CONTI`"
+        )));
+    }
+    cursor.address()
+}
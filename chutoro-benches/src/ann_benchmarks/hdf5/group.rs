@@ -0,0 +1,119 @@
+//! The root group's symbol table: a B-tree of symbol table nodes whose link
+//! names live in a local heap.
+
+use super::{
+    Cursor, Hdf5Error, Hdf5File, Widths, malformed, object_header::MESSAGE_SYMBOL_TABLE,
+    size_to_usize, trim_nul, unsupported,
+};
+
+/// A link from a group to one of its members.
+pub(super) struct Member {
+    pub(super) name: String,
+    pub(super) header: u64,
+}
+
+impl Hdf5File {
+    pub(super) fn root_members(&mut self) -> Result<Vec<Member>, Hdf5Error> {
+        let messages = self.messages(self.root)?;
+        let table = messages
+            .iter()
+            .find(|message| message.kind == MESSAGE_SYMBOL_TABLE)
+            .ok_or_else(|| {
+                unsupported("root group without a symbol table; repack the file with `h5repack`")
+            })?;
+        let mut cursor = Cursor::new(&table.body, self.widths);
+        let tree = cursor.address()?;
+        let heap = cursor.address()?;
+        let names = self.local_heap(heap)?;
+        let mut entries = Vec::new();
+        self.collect_entries(tree, &mut entries)?;
+        let mut members = entries
+            .into_iter()
+            .map(|(offset, header)| {
+                Ok(Member {
+                    name: heap_string(&names, offset)?,
+                    header,
+                })
+            })
+            .collect::<Result<Vec<_>, Hdf5Error>>()?;
+        members.sort_by(|left, right| left.name.cmp(&right.name));
+        Ok(members)
+    }
+
+    /// Reads the data segment of the local heap at `address`.
+    fn local_heap(&mut self, address: u64) -> Result<Vec<u8>, Hdf5Error> {
+        let header = self.read_at(address, 8 + 2 * self.widths.length + self.widths.offset)?;
+        let mut cursor = Cursor::new(&header, self.widths);
+        cursor.signature(*b"HEAP", "local heap")?;
+        cursor.skip(4)?;
+        let size = size_to_usize(cursor.length()?)?;
+        cursor.skip(self.widths.length)?;
+        let data = cursor.address()?;
+        self.read_at(data, size)
+    }
+
+    /// Appends the `(name offset, header address)` pairs below the group
+    /// B-tree node at `address`.
+    fn collect_entries(
+        &mut self,
+        address: u64,
+        entries: &mut Vec<(usize, u64)>,
+    ) -> Result<(), Hdf5Error> {
+        let widths = self.widths;
+        let header = self.read_at(address, 8 + 2 * widths.offset)?;
+        let mut cursor = Cursor::new(&header, widths);
+        cursor.signature(*b"TREE", "B-tree node")?;
+        if cursor.u8()? != 0 {
+            return Err(malformed("group B-tree has a non-group node"));
+        }
+        let level = cursor.u8()?;
+        let used = usize::from(cursor.u16()?);
+        let body_length = (used + 1) * widths.length + used * widths.offset;
+        let body = self.read_at(address + 8 + 2 * widths.offset as u64, body_length)?;
+        let mut children = Cursor::new(&body, widths);
+        for _ in 0..used {
+            children.skip(widths.length)?;
+            let child = children.address()?;
+            if level > 0 {
+                self.collect_entries(child, entries)?;
+            } else {
+                self.collect_symbols(child, entries)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn collect_symbols(
+        &mut self,
+        address: u64,
+        entries: &mut Vec<(usize, u64)>,
+    ) -> Result<(), Hdf5Error> {
+        let header = self.read_at(address, 8)?;
+        let mut cursor = Cursor::new(&header, self.widths);
+        cursor.signature(*b"SNOD", "symbol table node")?;
+        cursor.skip(2)?;
+        let count = usize::from(cursor.u16()?);
+        let entry_size = symbol_entry_size(self.widths);
+        let body = self.read_at(address + 8, count * entry_size)?;
+        let mut symbols = Cursor::new(&body, self.widths);
+        for _ in 0..count {
+            // The link-name offset is stored at address width.
+            let name = size_to_usize(symbols.uint(self.widths.offset)?)?;
+            let object = symbols.address()?;
+            symbols.skip(entry_size - 2 * self.widths.offset)?;
+            entries.push((name, object));
+        }
+        Ok(())
+    }
+}
+
+pub(super) const fn symbol_entry_size(widths: Widths) -> usize {
+    2 * widths.offset + 24
+}
+
+fn heap_string(heap: &[u8], offset: usize) -> Result<String, Hdf5Error> {
+    let tail = heap
+        .get(offset..)
+        .ok_or_else(|| malformed("link name lies outside the local heap"))?;
+    String::from_utf8(trim_nul(tail).to_vec()).map_err(|_| malformed("link name is not UTF-8"))
+}
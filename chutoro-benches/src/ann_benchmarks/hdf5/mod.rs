@@ -0,0 +1,130 @@
+//! Minimal reader for the HDF5 files published by ann-benchmarks.
+//!
+//! The published datasets are written by `h5py` with the library's earliest
+//! file-format settings: a version 0 superblock, version 1 object headers,
+//! a root group indexed by a symbol table, and uncompressed contiguous
+//! datasets. This reader understands exactly that subset — enough to list
+//! the root group, read a numeric dataset, and read a string attribute — and
+//! reports anything else as unsupported rather than guessing. Files
+//! rewritten with newer format settings can be converted back with
+//! `h5repack`.
+
+mod cursor;
+mod dataset;
+mod group;
+mod object_header;
+mod superblock;
+
+use std::{
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+};
+
+use thiserror::Error;
+
+pub use self::dataset::Dataset;
+use self::{cursor::Cursor, object_header::MESSAGE_ATTRIBUTE};
+
+/// Address value HDF5 uses for "not allocated".
+const UNDEFINED_ADDRESS: u64 = u64::MAX;
+
+/// Errors raised while reading an HDF5 file.
+#[derive(Debug, Error)]
+pub enum Hdf5Error {
+    /// Reading the file failed.
+    #[error("read failed: {0}")]
+    Io(#[from] io::Error),
+    /// The file is not HDF5 or a structure is truncated or inconsistent.
+    #[error("malformed HDF5: {0}")]
+    Malformed(String),
+    /// The file uses a format feature outside the supported subset.
+    #[error("unsupported HDF5 feature: {0}")]
+    Unsupported(String),
+}
+
+fn malformed(reason: impl Into<String>) -> Hdf5Error {
+    Hdf5Error::Malformed(reason.into())
+}
+
+fn unsupported(reason: impl Into<String>) -> Hdf5Error {
+    Hdf5Error::Unsupported(reason.into())
+}
+
+/// An open HDF5 file and the location of its root group.
+#[derive(Debug)]
+pub struct Hdf5File {
+    file: File,
+    widths: Widths,
+    base: u64,
+    root: u64,
+}
+
+/// Byte widths of file addresses and lengths, fixed by the superblock.
+#[derive(Clone, Copy, Debug)]
+struct Widths {
+    offset: usize,
+    length: usize,
+}
+
+impl Hdf5File {
+    /// Returns the names of the root group's members, sorted.
+    ///
+    /// # Errors
+    /// Returns [`Hdf5Error`] when the root group cannot be read.
+    pub fn members(&mut self) -> Result<Vec<String>, Hdf5Error> {
+        Ok(self
+            .root_members()?
+            .into_iter()
+            .map(|member| member.name)
+            .collect())
+    }
+
+    /// Reads the root group's string attribute called `name`, if there is one.
+    ///
+    /// # Errors
+    /// Returns [`Hdf5Error`] when the attribute is not a string or its
+    /// structures are malformed.
+    pub fn string_attribute(&mut self, name: &str) -> Result<Option<String>, Hdf5Error> {
+        for message in self.messages(self.root)? {
+            if message.kind != MESSAGE_ATTRIBUTE {
+                continue;
+            }
+            if let Some(value) = self.attribute_if_named(&message.body, name)? {
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+
+    fn read_at(&mut self, address: u64, length: usize) -> Result<Vec<u8>, Hdf5Error> {
+        let position = self
+            .base
+            .checked_add(address)
+            .ok_or_else(|| malformed("address overflows"))?;
+        self.file.seek(SeekFrom::Start(position))?;
+        let mut buffer = vec![0; length];
+        self.file.read_exact(&mut buffer)?;
+        Ok(buffer)
+    }
+}
+
+/// Decodes a little-endian unsigned integer of up to eight bytes.
+fn le_u64(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .rev()
+        .fold(0_u64, |acc, byte| (acc << 8) | u64::from(*byte))
+}
+
+fn size_to_usize(size: u64) -> Result<usize, Hdf5Error> {
+    usize::try_from(size).map_err(|_| malformed("size exceeds usize"))
+}
+
+/// Returns `bytes` up to the first NUL.
+fn trim_nul(bytes: &[u8]) -> &[u8] {
+    bytes
+        .iter()
+        .position(|byte| *byte == 0)
+        .and_then(|end| bytes.get(..end))
+        .unwrap_or(bytes)
+}
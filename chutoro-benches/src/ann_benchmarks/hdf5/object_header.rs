@@ -0,0 +1,156 @@
+//! Version 1 object headers and the attribute messages they carry.
+
+use super::{Cursor, Hdf5Error, Hdf5File, Widths, malformed, size_to_usize, trim_nul, unsupported};
+
+pub(super) const MESSAGE_DATASPACE: u16 = 0x0001;
+pub(super) const MESSAGE_DATATYPE: u16 = 0x0003;
+pub(super) const MESSAGE_LAYOUT: u16 = 0x0008;
+pub(super) const MESSAGE_FILTERS: u16 = 0x000B;
+pub(super) const MESSAGE_ATTRIBUTE: u16 = 0x000C;
+const MESSAGE_CONTINUATION: u16 = 0x0010;
+pub(super) const MESSAGE_SYMBOL_TABLE: u16 = 0x0011;
+
+/// A header message's type and body.
+pub(super) struct Message {
+    pub(super) kind: u16,
+    pub(super) body: Vec<u8>,
+}
+
+impl Hdf5File {
+    /// Reads every message of the version 1 object header at `address`,
+    /// following continuation blocks.
+    pub(super) fn messages(&mut self, address: u64) -> Result<Vec<Message>, Hdf5Error> {
+        let prefix = self.read_at(address, 16)?;
+        let mut cursor = Cursor::new(&prefix, self.widths);
+        let version = cursor.u8()?;
+        if version != 1 {
+            return Err(unsupported(format!(
+                "object header version {version}; repack the file with `h5repack` using the earliest format"
+            )));
+        }
+        cursor.skip(1)?;
+        let count = usize::from(cursor.u16()?);
+        cursor.skip(4)?;
+        let size = cursor.u32()?;
+        let mut blocks = vec![(address + 16, size_to_usize(u64::from(size))?)];
+        let mut messages = Vec::with_capacity(count);
+        while let Some((block, length)) = blocks.pop() {
+            let data = self.read_at(block, length)?;
+            let found = read_messages(&data, self.widths, count - messages.len())?;
+            for message in found
+                .iter()
+                .filter(|message| message.kind == MESSAGE_CONTINUATION)
+            {
+                let mut body = Cursor::new(&message.body, self.widths);
+                blocks.push((body.address()?, size_to_usize(body.length()?)?));
+            }
+            messages.extend(found);
+        }
+        Ok(messages)
+    }
+
+    /// Returns the attribute's string value when it is called `name`.
+    pub(super) fn attribute_if_named(
+        &mut self,
+        body: &[u8],
+        name: &str,
+    ) -> Result<Option<String>, Hdf5Error> {
+        let mut cursor = Cursor::new(body, self.widths);
+        let version = cursor.u8()?;
+        if !(1..=3).contains(&version) {
+            return Err(unsupported(format!("attribute message version {version}")));
+        }
+        cursor.skip(1)?;
+        let name_size = usize::from(cursor.u16()?);
+        let datatype_size = usize::from(cursor.u16()?);
+        let dataspace_size = usize::from(cursor.u16()?);
+        if version == 3 {
+            cursor.skip(1)?;
+        }
+        let padded = |size: usize| {
+            if version == 1 {
+                size.next_multiple_of(8)
+            } else {
+                size
+            }
+        };
+        let attribute = cursor.take(padded(name_size))?;
+        if trim_nul(attribute) != name.as_bytes() {
+            return Ok(None);
+        }
+        let datatype = cursor.take(padded(datatype_size))?;
+        cursor.skip(padded(dataspace_size))?;
+        let value = self.string_value(datatype, &mut cursor, name)?;
+        Ok(Some(value))
+    }
+
+    /// Decodes a fixed-length or variable-length string attribute value.
+    fn string_value(
+        &mut self,
+        datatype: &[u8],
+        data: &mut Cursor<'_>,
+        name: &str,
+    ) -> Result<String, Hdf5Error> {
+        let mut cursor = Cursor::new(datatype, self.widths);
+        let class = cursor.u8()? & 0x0f;
+        let bits = cursor.u8()?;
+        cursor.skip(2)?;
+        let size = size_to_usize(u64::from(cursor.u32()?))?;
+        let bytes = match class {
+            3 => data.take(size)?.to_vec(),
+            9 if bits & 0x0f == 1 => {
+                let length = size_to_usize(u64::from(data.u32()?))?;
+                let collection = data.address()?;
+                let index = data.u32()?;
+                let object = self.global_heap_object(collection, index)?;
+                object
+                    .get(..length)
+                    .ok_or_else(|| malformed("global heap object is truncated"))?
+                    .to_vec()
+            }
+            _ => return Err(unsupported(format!("attribute `{name}` is not a string"))),
+        };
+        String::from_utf8(trim_nul(&bytes).to_vec())
+            .map_err(|_| malformed(format!("attribute `{name}` is not UTF-8")))
+    }
+
+    fn global_heap_object(&mut self, collection: u64, index: u32) -> Result<Vec<u8>, Hdf5Error> {
+        let widths = self.widths;
+        let header = self.read_at(collection, 8 + widths.length)?;
+        let mut cursor = Cursor::new(&header, widths);
+        cursor.signature(*b"GCOL", "global heap")?;
+        cursor.skip(4)?;
+        let size = size_to_usize(cursor.length()?)?;
+        let data = self.read_at(collection, size)?;
+        let mut objects = Cursor::new(&data, widths);
+        objects.skip(8 + widths.length)?;
+        while objects.remaining() >= 8 + widths.length {
+            let object = objects.u16()?;
+            objects.skip(6)?;
+            let length = size_to_usize(objects.length()?)?;
+            if object == 0 {
+                break;
+            }
+            let body = objects.take(length)?;
+            if u32::from(object) == index {
+                return Ok(body.to_vec());
+            }
+            objects.align8()?;
+        }
+        Err(malformed(format!("global heap object {index} not found")))
+    }
+}
+
+/// Reads up to `limit` messages from one object header block.
+fn read_messages(block: &[u8], widths: Widths, limit: usize) -> Result<Vec<Message>, Hdf5Error> {
+    let mut cursor = Cursor::new(block, widths);
+    let mut messages = Vec::new();
+    while cursor.remaining() >= 8 && messages.len() < limit {
+        let kind = cursor.u16()?;
+        let size = usize::from(cursor.u16()?);
+        cursor.skip(4)?;
+        let body = cursor.take(size)?.to_vec();
+        messages.push(Message { kind, body });
+    }
+    Ok(messages)
+}
@@ -0,0 +1,59 @@
+//! The superblock, which fixes the file's address widths and points at the
+//! root group.
+
+use std::{fs::File, io::Read, path::Path};
+
+use super::{
+    Cursor, Hdf5Error, Hdf5File, Widths, group::symbol_entry_size, malformed, unsupported,
+};
+
+/// Signature at the start of every HDF5 superblock.
+const SIGNATURE: [u8; 8] = [0x89, b'H', b'D', b'F', b'\r', b'\n', 0x1a, b'\n'];
+
+impl Hdf5File {
+    /// Opens `path` and locates the root group.
+    ///
+    /// # Errors
+    /// Returns [`Hdf5Error`] when the file cannot be read, is not HDF5, or
+    /// uses a superblock other than versions 0 and 1.
+    pub fn open(path: &Path) -> Result<Self, Hdf5Error> {
+        let mut file = File::open(path)?;
+        let mut prefix = [0_u8; 24];
+        file.read_exact(&mut prefix)?;
+        if prefix.get(..8) != Some(SIGNATURE.as_slice()) {
+            return Err(malformed("missing HDF5 signature at the start of the file"));
+        }
+        let field = |index: usize| prefix.get(index).copied().unwrap_or_default();
+        let version = field(8);
+        if version > 1 {
+            return Err(unsupported(format!(
+                "superblock version {version}; repack the file with `h5repack` using the earliest format"
+            )));
+        }
+        let widths = Widths {
+            offset: usize::from(field(13)),
+            length: usize::from(field(14)),
+        };
+        if !(1..=8).contains(&widths.offset) || !(1..=8).contains(&widths.length) {
+            return Err(malformed("invalid address or length width"));
+        }
+        // Version 1 adds a 16-bit B-tree K value and two reserved bytes.
+        let fixed: u64 = if version == 1 { 28 } else { 24 };
+        let addresses = 4 * widths.offset;
+        let entry = symbol_entry_size(widths);
+        let mut reader = Self {
+            file,
+            widths,
+            base: 0,
+            root: 0,
+        };
+        let tail = reader.read_at(fixed, addresses + entry)?;
+        let mut cursor = Cursor::new(&tail, widths);
+        reader.base = cursor.address()?;
+        // Skip the free-space, end-of-file, and driver addresses, then the
+        // root entry's link-name offset.
+        cursor.skip(4 * widths.offset)?;
+        reader.root = cursor.address()?;
+        Ok(reader)
+    }
+}
@@ -0,0 +1,274 @@
+//! Recall and throughput of `CpuHnsw` on ann-benchmarks datasets.
+//!
+//! [ann-benchmarks](https://ann-benchmarks.com) publishes HDF5 files with
+//! `train` vectors to index, `test` queries, and the true `neighbors` and
+//! `distances` of every query. Reporting chutoro's index on the same files,
+//! with the same recall definition, makes its numbers directly comparable
+//! with the hnswlib and FAISS results published there.
+//!
+//! [`run_ann_benchmark`] builds one index over the training vectors, then
+//! answers every query once per `ef_search` value on a single thread, the
+//! way ann-benchmarks measures queries per second. A returned neighbour
+//! counts towards recall@k when it is no further than the query's `k`th
+//! true neighbour plus `1e-3`, which is the ann-benchmarks definition and
+//! credits ties at the boundary.
+
+mod dataset;
+mod env;
+mod hdf5;
+mod report;
+
+use std::{fmt, num::NonZeroUsize, path::PathBuf, str::FromStr, time::Instant};
+
+use chutoro_core::{CpuHnsw, HnswError, HnswParams};
+use thiserror::Error;
+
+use crate::{recall::RecallScore, settings::InvalidSetting};
+
+pub use dataset::AnnDataset;
+pub use env::{ANN_ENV_PREFIX, config_from_env, config_from_lookup};
+pub use hdf5::Hdf5Error;
+pub use report::{AnnMeasurement, AnnReport, queries_per_second};
+
+/// Slack added to the `k`th true distance, as in ann-benchmarks.
+const RECALL_EPSILON: f32 = 1e-3;
+
+/// Distance functions used by ann-benchmarks datasets.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AnnMetric {
+    /// Straight-line distance.
+    Euclidean,
+    /// One minus the cosine similarity.
+    Angular,
+}
+
+impl AnnMetric {
+    /// Returns the name ann-benchmarks uses for the metric.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Euclidean => "euclidean",
+            Self::Angular => "angular",
+        }
+    }
+}
+
+impl fmt::Display for AnnMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AnnMetric {
+    type Err = AnnError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.trim() {
+            "euclidean" => Ok(Self::Euclidean),
+            "angular" | "cosine" => Ok(Self::Angular),
+            other => Err(AnnError::UnknownMetric {
+                name: other.to_owned(),
+            }),
+        }
+    }
+}
+
+/// Errors raised by the ann-benchmarks adapter.
+#[derive(Debug, Error)]
+pub enum AnnError {
+    /// A configuration variable held a value that could not be used.
+    #[error(transparent)]
+    InvalidSetting(#[from] InvalidSetting),
+    /// A required configuration variable was not set.
+    #[error("{name} must name an ann-benchmarks HDF5 file")]
+    MissingSetting {
+        /// Name of the environment variable.
+        name: String,
+    },
+    /// The dataset file could not be read.
+    #[error("cannot read {}: {source}", path.display())]
+    Hdf5 {
+        /// The dataset file.
+        path: PathBuf,
+        /// The underlying failure.
+        source: Hdf5Error,
+    },
+    /// The file's datasets do not form an ann-benchmarks dataset.
+    #[error("invalid ann-benchmarks dataset: {reason}")]
+    InvalidDataset {
+        /// Why the dataset was rejected.
+        reason: String,
+    },
+    /// The dataset's metric is missing or not supported.
+    #[error("unsupported metric `{name}`: expected euclidean or angular")]
+    UnknownMetric {
+        /// The metric name found, empty when none was.
+        name: String,
+    },
+    /// More neighbours were requested than the ground truth holds.
+    #[error("k = {k} exceeds the {depth} ground-truth neighbours per query")]
+    KExceedsGroundTruth {
+        /// The requested neighbour count.
+        k: usize,
+        /// Ground-truth neighbours stored per query.
+        depth: usize,
+    },
+    /// Building or searching the index failed.
+    #[error("HNSW operation failed: {0}")]
+    Hnsw(#[from] HnswError),
+    /// Writing the report failed.
+    #[error("ann-benchmarks report failed: {0}")]
+    Report(std::io::Error),
+}
+
+/// Configuration for [`run_ann_benchmark`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AnnConfig {
+    /// The ann-benchmarks HDF5 file.
+    pub dataset: PathBuf,
+    /// Metric override; `None` reads it from the file.
+    pub metric: Option<AnnMetric>,
+    /// Neighbour count scored by recall@k.
+    pub k: NonZeroUsize,
+    /// HNSW maximum connections per node (M).
+    pub max_connections: usize,
+    /// HNSW search width during construction.
+    pub ef_construction: usize,
+    /// Search widths to measure, in order.
+    pub ef_search: Vec<NonZeroUsize>,
+    /// Caps the number of test queries; `None` answers all of them.
+    pub query_limit: Option<NonZeroUsize>,
+    /// Seed for the index's level sampling.
+    pub seed: u64,
+    /// Where to write the CSV report, if anywhere.
+    pub output: Option<PathBuf>,
+}
+
+impl AnnConfig {
+    /// Returns the default configuration for `dataset`: recall@10, M = 16,
+    /// `ef_construction` = 200, and the `ef_search` values ann-benchmarks
+    /// sweeps for hnswlib.
+    #[must_use]
+    pub fn new(dataset: impl Into<PathBuf>) -> Self {
+        Self {
+            dataset: dataset.into(),
+            metric: None,
+            k: NonZeroUsize::MIN.saturating_add(9),
+            max_connections: 16,
+            ef_construction: 200,
+            ef_search: [10, 20, 40, 80, 120, 200, 400, 800]
+                .into_iter()
+                .filter_map(NonZeroUsize::new)
+                .collect(),
+            query_limit: None,
+            seed: 0x5EED,
+            output: None,
+        }
+    }
+}
+
+/// Loads `config.dataset` and measures it with [`benchmark_dataset`].
+///
+/// # Errors
+/// Returns [`AnnError`] when the dataset cannot be loaded or measured.
+pub fn run_ann_benchmark(config: &AnnConfig) -> Result<AnnReport, AnnError> {
+    let dataset = AnnDataset::load(&config.dataset, config.metric)?;
+    benchmark_dataset(&dataset, config)
+}
+
+/// Builds an index over `dataset`'s training vectors and measures recall@k
+/// and query time at each `config.ef_search`.
+///
+/// Searches use a width of at least `k`, as hnswlib does, so small
+/// `ef_search` values still return `k` neighbours.
+///
+/// # Errors
+/// Returns [`AnnError::KExceedsGroundTruth`] when `config.k` exceeds the
+/// stored ground truth and [`AnnError::Hnsw`] when the parameters are
+/// invalid or an index operation fails.
+pub fn benchmark_dataset(dataset: &AnnDataset, config: &AnnConfig) -> Result<AnnReport, AnnError> {
+    let k = config.k.get();
+    let depth = dataset.ground_truth_depth();
+    if k > depth {
+        return Err(AnnError::KExceedsGroundTruth { k, depth });
+    }
+    let params =
+        HnswParams::new(config.max_connections, config.ef_construction)?.with_rng_seed(config.seed);
+    let started = Instant::now();
+    let index = CpuHnsw::build(&dataset.training(), params)?;
+    let build_time_millis = started.elapsed().as_millis();
+    let query_count = config.query_limit.map_or_else(
+        || dataset.query_len(),
+        |limit| limit.get().min(dataset.query_len()),
+    );
+    let measurements = config
+        .ef_search
+        .iter()
+        .map(|&ef| measure(&index, dataset, QueryPlan { query_count, k, ef }))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(AnnReport {
+        dataset: dataset.name().to_owned(),
+        metric: dataset.metric(),
+        point_count: dataset.train_len(),
+        query_count,
+        k,
+        max_connections: config.max_connections,
+        ef_construction: config.ef_construction,
+        build_time_millis,
+        measurements,
+    })
+}
+
+/// The queries to answer at one search width.
+#[derive(Clone, Copy)]
+struct QueryPlan {
+    query_count: usize,
+    k: usize,
+    ef: NonZeroUsize,
+}
+
+fn measure(
+    index: &CpuHnsw,
+    dataset: &AnnDataset,
+    plan: QueryPlan,
+) -> Result<AnnMeasurement, AnnError> {
+    let source = dataset.with_queries();
+    let width = plan.ef.max(NonZeroUsize::new(plan.k).unwrap_or(plan.ef));
+    let mut hits = 0;
+    let mut search_time = std::time::Duration::ZERO;
+    for query in 0..plan.query_count {
+        let row = dataset.train_len() + query;
+        let started = Instant::now();
+        // Detailed search returns the traversal as it ran, without adding
+        // the unindexed query row to its own results.
+        let found = index.search_detailed(&source, row, width)?;
+        search_time += started.elapsed();
+        let Some(kth) = dataset.kth_distance(query, plan.k) else {
+            continue;
+        };
+        hits += found
+            .iter()
+            .take(plan.k)
+            .filter(|neighbour| within(neighbour.distance, kth))
+            .count();
+    }
+    Ok(AnnMeasurement {
+        ef_search: plan.ef.get(),
+        recall: RecallScore {
+            hits,
+            total: plan.query_count * plan.k,
+        },
+        search_time_micros: search_time.as_micros(),
+    })
+}
+
+#[expect(
+    clippy::float_arithmetic,
+    reason = "the recall threshold adds the ann-benchmarks epsilon to a distance"
+)]
+fn within(distance: f32, kth: f32) -> bool {
+    distance <= kth + RECALL_EPSILON
+}
+
+#[cfg(test)]
+mod tests;
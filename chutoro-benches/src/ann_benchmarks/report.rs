@@ -0,0 +1,137 @@
+//! CSV reporting for ann-benchmarks measurements.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use super::AnnMetric;
+use crate::recall::{RecallScore, recall_fraction};
+
+/// Recall and throughput at one search width.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AnnMeasurement {
+    /// HNSW search width requested for the queries.
+    pub ef_search: usize,
+    /// Aggregated recall score across the queries.
+    pub recall: RecallScore,
+    /// Wall-clock time for all queries in microseconds.
+    pub search_time_micros: u128,
+}
+
+/// The outcome of [`super::run_ann_benchmark`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AnnReport {
+    /// Dataset name, taken from the file stem.
+    pub dataset: String,
+    /// Metric the index was built with.
+    pub metric: AnnMetric,
+    /// Number of indexed training vectors.
+    pub point_count: usize,
+    /// Number of queries behind every measurement.
+    pub query_count: usize,
+    /// Neighbour count scored by recall@k.
+    pub k: usize,
+    /// HNSW maximum connections per node (M).
+    pub max_connections: usize,
+    /// HNSW search width during construction.
+    pub ef_construction: usize,
+    /// Wall-clock index build time in milliseconds.
+    pub build_time_millis: u128,
+    /// One measurement per `ef_search` value, in sweep order.
+    pub measurements: Vec<AnnMeasurement>,
+}
+
+impl AnnReport {
+    const fn csv_header() -> &'static str {
+        "dataset,metric,point_count,query_count,k,max_connections,ef_construction,ef_search,recall_hits,recall_total,recall_fraction,build_time_ms,search_time_us,queries_per_second\n"
+    }
+
+    /// Renders the report as CSV with one row per measurement.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_benches::{
+    ///     ann_benchmarks::{AnnMeasurement, AnnMetric, AnnReport},
+    ///     recall::RecallScore,
+    /// };
+    ///
+    /// let report = AnnReport {
+    ///     dataset: "tiny-2-euclidean".to_owned(),
+    ///     metric: AnnMetric::Euclidean,
+    ///     point_count: 100,
+    ///     query_count: 10,
+    ///     k: 10,
+    ///     max_connections: 16,
+    ///     ef_construction: 200,
+    ///     build_time_millis: 3,
+    ///     measurements: vec![AnnMeasurement {
+    ///         ef_search: 10,
+    ///         recall: RecallScore { hits: 95, total: 100 },
+    ///         search_time_micros: 500,
+    ///     }],
+    /// };
+    /// let csv = report.to_csv();
+    /// assert!(csv.ends_with(",0.950000,3,500,20000.0\n"));
+    /// ```
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let mut output = String::from(Self::csv_header());
+        for measurement in &self.measurements {
+            output.push_str(&self.to_csv_row(measurement));
+        }
+        output
+    }
+
+    fn to_csv_row(&self, measurement: &AnnMeasurement) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+            self.dataset,
+            self.metric,
+            self.point_count,
+            self.query_count,
+            self.k,
+            self.max_connections,
+            self.ef_construction,
+            measurement.ef_search,
+            measurement.recall.hits,
+            measurement.recall.total,
+            recall_fraction(measurement.recall),
+            self.build_time_millis,
+            measurement.search_time_micros,
+            queries_per_second(self.query_count, measurement.search_time_micros),
+        )
+    }
+
+    /// Writes [`Self::to_csv`] to `report_path`, creating parent
+    /// directories, and returns the written path.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`std::io::Error`] if directory creation or file writing fails.
+    pub fn write_csv(&self, report_path: impl AsRef<Path>) -> Result<PathBuf, std::io::Error> {
+        let report_file_path = report_path.as_ref().to_path_buf();
+        if let Some(parent) = report_file_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&report_file_path, self.to_csv())?;
+        Ok(report_file_path)
+    }
+}
+
+/// Formats the query rate with one decimal place.
+///
+/// Returns `"inf"` when the queries took no measurable time.
+#[expect(
+    clippy::float_arithmetic,
+    clippy::cast_precision_loss,
+    reason = "queries per second is a float ratio, only used for human-readable output"
+)]
+#[must_use]
+pub fn queries_per_second(query_count: usize, search_time_micros: u128) -> String {
+    if search_time_micros == 0 {
+        return "inf".to_owned();
+    }
+    let seconds = search_time_micros as f64 / 1_000_000.0;
+    format!("{:.1}", query_count as f64 / seconds)
+}
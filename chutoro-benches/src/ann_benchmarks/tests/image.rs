@@ -0,0 +1,274 @@
+//! Hand-assembled HDF5 images in the layout `h5py` writes with the earliest
+//! file format.
+
+/// Undefined address in an eight-byte offset field.
+const UNDEFINED: u64 = u64::MAX;
+
+/// Element encodings the fixture writer can store.
+pub(super) enum Values<'a> {
+    F32(&'a [f32]),
+    I32(&'a [i32]),
+}
+
+pub(super) struct Member<'a> {
+    pub(super) name: &'a str,
+    pub(super) shape: [u64; 2],
+    pub(super) values: Values<'a>,
+}
+
+/// Appends little-endian fields to a growing file image.
+#[derive(Default)]
+struct Image {
+    bytes: Vec<u8>,
+}
+
+impl Image {
+    fn address(&self) -> u64 {
+        u64::try_from(self.bytes.len()).expect("fixture fits in u64")
+    }
+
+    fn put(&mut self, value: u64, width: usize) {
+        self.bytes
+            .extend((0..width).map(|byte| u8::try_from((value >> (8 * byte)) & 0xff).unwrap_or(0)));
+    }
+
+    fn raw(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    fn pad8(&mut self) {
+        self.bytes.resize(padded(self.bytes.len()), 0);
+    }
+
+    fn patch(&mut self, at: usize, value: u64) {
+        for (offset, byte) in (0..8).zip(at..) {
+            if let Some(slot) = self.bytes.get_mut(byte) {
+                *slot = u8::try_from((value >> (8 * offset)) & 0xff).unwrap_or(0);
+            }
+        }
+    }
+
+    /// Writes a version 1 object header holding `messages`.
+    fn object_header(&mut self, messages: &[(u16, Vec<u8>)]) -> u64 {
+        self.pad8();
+        let address = self.address();
+        let size: usize = messages
+            .iter()
+            .map(|(_, body)| 8 + padded(body.len()))
+            .sum();
+        self.put(1, 1);
+        self.put(0, 1);
+        self.put(messages.len() as u64, 2);
+        self.put(1, 4);
+        self.put(size as u64, 4);
+        self.put(0, 4);
+        for (kind, body) in messages {
+            self.put(u64::from(*kind), 2);
+            self.put(padded(body.len()) as u64, 2);
+            self.put(0, 4);
+            self.raw(body);
+            self.pad8();
+        }
+        address
+    }
+}
+
+const fn padded(length: usize) -> usize {
+    length.next_multiple_of(8)
+}
+
+fn field(value: u64, width: usize) -> Vec<u8> {
+    let mut image = Image::default();
+    image.put(value, width);
+    image.bytes
+}
+
+fn dataspace(dims: &[u64]) -> Vec<u8> {
+    let mut body = vec![
+        1,
+        u8::try_from(dims.len()).expect("rank fits"),
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+    ];
+    for dim in dims {
+        body.extend(field(*dim, 8));
+    }
+    body
+}
+
+fn datatype(values: &Values<'_>) -> Vec<u8> {
+    match values {
+        Values::F32(_) => {
+            let mut body = vec![0x11, 0x20, 0x1f, 0x00];
+            body.extend(field(4, 4));
+            body.extend(field(0, 2));
+            body.extend(field(32, 2));
+            body.extend([23, 8, 0, 23]);
+            body.extend(field(127, 4));
+            body
+        }
+        Values::I32(_) => {
+            let mut body = vec![0x10, 0x08, 0x00, 0x00];
+            body.extend(field(4, 4));
+            body.extend(field(0, 2));
+            body.extend(field(32, 2));
+            body
+        }
+    }
+}
+
+fn encode(values: &Values<'_>) -> Vec<u8> {
+    match values {
+        Values::F32(floats) => floats
+            .iter()
+            .flat_map(|value| field(u64::from(value.to_bits()), 4))
+            .collect(),
+        Values::I32(integers) => integers
+            .iter()
+            .flat_map(|value| field(u64::from(value.cast_unsigned()), 4))
+            .collect(),
+    }
+}
+
+/// Writes an ann-benchmarks style file with an optional `distance`
+/// attribute.
+pub(super) fn write_hdf5(members: &[Member<'_>], distance: Option<&str>) -> Vec<u8> {
+    let mut image = Image::default();
+    image.raw(&[0; 96]);
+    let mut links = Vec::new();
+    for member in members {
+        let data = encode(&member.values);
+        image.pad8();
+        let data_address = image.address();
+        image.raw(&data);
+        let mut layout = vec![3, 1];
+        layout.extend(field(data_address, 8));
+        layout.extend(field(data.len() as u64, 8));
+        let header = image.object_header(&[
+            (0x0001, dataspace(&member.shape)),
+            (0x0003, datatype(&member.values)),
+            (0x0008, layout),
+        ]);
+        links.push((member.name, header));
+    }
+    links.sort_by(|left, right| left.0.cmp(right.0));
+
+    let mut root_messages = Vec::new();
+    if let Some(metric) = distance {
+        root_messages.push((0x000C, string_attribute(&mut image, "distance", metric)));
+    }
+    let (heap, tree) = write_group(&mut image, &links);
+    let mut table = field(tree, 8);
+    table.extend(field(heap, 8));
+    root_messages.insert(0, (0x0011, table));
+    let root = image.object_header(&root_messages);
+
+    let end = image.address();
+    image.bytes.splice(0..24, superblock_prefix());
+    for (slot, value) in [(24, 0), (32, UNDEFINED), (40, end), (48, UNDEFINED)] {
+        image.patch(slot, value);
+    }
+    for (slot, value) in [(56, 0), (64, root), (80, tree), (88, heap)] {
+        image.patch(slot, value);
+    }
+    image.patch(72, 1);
+    image.bytes
+}
+
+/// Writes the root group's local heap, symbol node, and B-tree, returning
+/// the heap and B-tree addresses.
+fn write_group(image: &mut Image, links: &[(&str, u64)]) -> (u64, u64) {
+    // Local heap: an empty name at offset 0, then each link name.
+    let mut names = vec![0_u8; 8];
+    let mut offsets = Vec::new();
+    for (name, _) in links {
+        offsets.push(names.len() as u64);
+        names.extend(name.as_bytes());
+        names.push(0);
+        names.resize(padded(names.len()), 0);
+    }
+    image.pad8();
+    let heap = image.address();
+    image.raw(b"HEAP");
+    image.raw(&[0, 0, 0, 0]);
+    image.put(names.len() as u64, 8);
+    image.put(UNDEFINED, 8);
+    image.put(heap + 32, 8);
+    image.raw(&names);
+
+    let symbols = image.address();
+    image.raw(b"SNOD");
+    image.raw(&[1, 0]);
+    image.put(links.len() as u64, 2);
+    for ((_, header), offset) in links.iter().zip(&offsets) {
+        image.put(*offset, 8);
+        image.put(*header, 8);
+        image.put(0, 4);
+        image.put(0, 4);
+        image.raw(&[0; 16]);
+    }
+
+    let tree = image.address();
+    image.raw(b"TREE");
+    image.raw(&[0, 0]);
+    image.put(1, 2);
+    image.put(UNDEFINED, 8);
+    image.put(UNDEFINED, 8);
+    image.put(0, 8);
+    image.put(symbols, 8);
+    image.put(offsets.last().copied().unwrap_or(0), 8);
+    (heap, tree)
+}
+
+fn superblock_prefix() -> Vec<u8> {
+    let mut prefix = vec![0x89, b'H', b'D', b'F', b'\r', b'\n', 0x1a, b'\n'];
+    prefix.extend([0, 0, 0, 0, 0, 8, 8, 0]);
+    prefix.extend(field(4, 2));
+    prefix.extend(field(16, 2));
+    prefix.extend(field(0, 4));
+    prefix
+}
+
+/// Stores `value` in a global heap and returns a version 1 attribute message
+/// body holding a variable-length string that refers to it.
+fn string_attribute(image: &mut Image, name: &str, value: &str) -> Vec<u8> {
+    image.pad8();
+    let collection = image.address();
+    let object = padded(value.len());
+    image.raw(b"GCOL");
+    image.raw(&[1, 0, 0, 0]);
+    image.put((16 + 16 + object + 16) as u64, 8);
+    image.put(1, 2);
+    image.put(1, 2);
+    image.put(0, 4);
+    image.put(value.len() as u64, 8);
+    image.raw(value.as_bytes());
+    image.pad8();
+    image.raw(&[0; 16]);
+
+    let mut datatype = vec![0x19, 0x01, 0x01, 0x00];
+    datatype.extend(field(16, 4));
+    datatype.extend([0x10, 0x00, 0x00, 0x00]);
+    datatype.extend(field(1, 4));
+    datatype.extend(field(0, 2));
+    datatype.extend(field(8, 2));
+    let dataspace = vec![1, 0, 0, 0, 0, 0, 0, 0];
+
+    let mut body = vec![1, 0];
+    body.extend(field(name.len() as u64 + 1, 2));
+    body.extend(field(datatype.len() as u64, 2));
+    body.extend(field(dataspace.len() as u64, 2));
+    for part in [[name.as_bytes(), &[0]].concat(), datatype, dataspace] {
+        let length = padded(part.len());
+        body.extend(&part);
+        body.resize(body.len() + length - part.len(), 0);
+    }
+    body.extend(field(value.len() as u64, 4));
+    body.extend(field(collection, 8));
+    body.extend(field(1, 4));
+    body
+}
@@ -0,0 +1,227 @@
+//! Unit tests for the ann-benchmarks adapter.
+//!
+//! Fixtures are written in the layout `h5py` produces with the earliest file
+//! format: a version 0 superblock, a symbol-table root group, contiguous
+//! datasets, and a variable-length string `distance` attribute stored in a
+//! global heap. These images exercise the adapter's edge cases; the file
+//! written by h5py in `tests/ann_benchmarks_fixture.rs` checks the layout
+//! itself.
+
+use std::{fs, num::NonZeroUsize, path::PathBuf};
+
+use rstest::{fixture, rstest};
+use tempfile::TempDir;
+
+mod image;
+
+use self::image::{Member, Values, write_hdf5};
+use super::{
+    AnnConfig, AnnDataset, AnnError, AnnMetric, Hdf5Error, benchmark_dataset, config_from_lookup,
+    hdf5::Hdf5File, run_ann_benchmark,
+};
+
+/// Brute-force ground truth for `queries` against `train`, both 2-D.
+fn ground_truth(train: &[[f32; 2]], queries: &[[f32; 2]], depth: usize) -> (Vec<i32>, Vec<f32>) {
+    let mut neighbours = Vec::new();
+    let mut distances = Vec::new();
+    for query in queries {
+        let mut ranked: Vec<(f32, usize)> = train
+            .iter()
+            .enumerate()
+            .map(|(row, point)| (euclidean(*point, *query), row))
+            .collect();
+        ranked.sort_by(|left, right| left.0.total_cmp(&right.0).then(left.1.cmp(&right.1)));
+        for (distance, row) in ranked.into_iter().take(depth) {
+            neighbours.push(i32::try_from(row).expect("row fits"));
+            distances.push(distance);
+        }
+    }
+    (neighbours, distances)
+}
+
+#[expect(
+    clippy::float_arithmetic,
+    reason = "ground-truth distances require floating-point arithmetic"
+)]
+fn euclidean(left: [f32; 2], right: [f32; 2]) -> f32 {
+    left.iter()
+        .zip(right)
+        .map(|(a, b)| (a - b) * (a - b))
+        .sum::<f32>()
+        .sqrt()
+}
+
+struct Fixture {
+    _dir: TempDir,
+    path: PathBuf,
+}
+
+/// A 10x10 grid of training points and five off-grid queries.
+fn grid_file(file_name: &str, distance: Option<&str>) -> Fixture {
+    let train: Vec<[f32; 2]> = (0..10_u8)
+        .flat_map(|y| (0..10_u8).map(move |x| [f32::from(x), f32::from(y)]))
+        .collect();
+    let queries = vec![[0.3, 0.6], [2.0, 1.9], [3.7, 3.2], [5.4, 4.5], [7.1, 5.8]];
+    let (neighbours, distances) = ground_truth(&train, &queries, 20);
+    let flat = |points: &[[f32; 2]]| points.iter().flatten().copied().collect::<Vec<_>>();
+    let (train_flat, test_flat) = (flat(&train), flat(&queries));
+    let bytes = write_hdf5(
+        &[
+            Member {
+                name: "train",
+                shape: [100, 2],
+                values: Values::F32(&train_flat),
+            },
+            Member {
+                name: "test",
+                shape: [5, 2],
+                values: Values::F32(&test_flat),
+            },
+            Member {
+                name: "neighbors",
+                shape: [5, 20],
+                values: Values::I32(&neighbours),
+            },
+            Member {
+                name: "distances",
+                shape: [5, 20],
+                values: Values::F32(&distances),
+            },
+        ],
+        distance,
+    );
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join(file_name);
+    fs::write(&path, bytes).expect("fixture must be written");
+    Fixture { _dir: dir, path }
+}
+
+#[fixture]
+fn grid() -> Fixture {
+    grid_file("grid-2-euclidean.hdf5", Some("euclidean"))
+}
+
+fn config(path: PathBuf) -> AnnConfig {
+    AnnConfig {
+        k: NonZeroUsize::new(5).expect("non-zero"),
+        ef_search: [4, 32].into_iter().filter_map(NonZeroUsize::new).collect(),
+        ..AnnConfig::new(path)
+    }
+}
+
+#[rstest]
+fn reader_lists_members_and_reads_datasets(grid: Fixture) {
+    let mut file = Hdf5File::open(&grid.path).expect("fixture must open");
+
+    assert_eq!(
+        file.members().expect("root group"),
+        ["distances", "neighbors", "test", "train"]
+    );
+    let train = file.dataset("train").expect("read").expect("train exists");
+    assert_eq!(train.shape(), [100, 2]);
+    let values = train.to_f32().expect("floats");
+    assert_eq!(values.get(..4), Some([0.0, 0.0, 1.0, 0.0].as_slice()));
+    let neighbours = file
+        .dataset("neighbors")
+        .expect("read")
+        .expect("neighbours exist");
+    assert_eq!(neighbours.to_indices().expect("indices").len(), 100);
+    assert!(file.dataset("missing").expect("read").is_none());
+    assert_eq!(
+        file.string_attribute("distance").expect("attribute"),
+        Some("euclidean".to_owned())
+    );
+}
+
+#[rstest]
+fn datasets_load_with_their_shapes(grid: Fixture) {
+    let dataset = AnnDataset::load(&grid.path, None).expect("dataset must load");
+
+    assert_eq!(dataset.name(), "grid-2-euclidean");
+    assert_eq!(dataset.metric(), AnnMetric::Euclidean);
+    assert_eq!(dataset.dimensions(), 2);
+    assert_eq!(dataset.train_len(), 100);
+    assert_eq!(dataset.query_len(), 5);
+    assert_eq!(dataset.ground_truth_depth(), 20);
+    assert_eq!(dataset.ground_truth(0).map(<[usize]>::len), Some(20));
+}
+
+#[rstest]
+fn wide_searches_recall_every_neighbour(grid: Fixture) {
+    let report = run_ann_benchmark(&config(grid.path.clone())).expect("benchmark must run");
+
+    assert_eq!(report.point_count, 100);
+    assert_eq!(report.query_count, 5);
+    assert_eq!(report.measurements.len(), 2);
+    let wide = report.measurements.last().expect("two measurements");
+    assert_eq!(wide.ef_search, 32);
+    assert_eq!(wide.recall.total, 25);
+    assert_eq!(wide.recall.hits, 25);
+    let csv = report.to_csv();
+    assert_eq!(csv.lines().count(), 3);
+    assert!(csv.contains("grid-2-euclidean,euclidean,100,5,5,16,200,32,25,25,1.000000"));
+}
+
+#[rstest]
+fn the_metric_falls_back_to_the_file_name() {
+    let fixture = grid_file("grid-2-angular.hdf5", None);
+
+    let dataset = AnnDataset::load(&fixture.path, None).expect("dataset must load");
+    assert_eq!(dataset.metric(), AnnMetric::Angular);
+    let overridden =
+        AnnDataset::load(&fixture.path, Some(AnnMetric::Euclidean)).expect("dataset must load");
+    assert_eq!(overridden.metric(), AnnMetric::Euclidean);
+}
+
+#[rstest]
+fn k_beyond_the_ground_truth_is_rejected(grid: Fixture) {
+    let dataset = AnnDataset::load(&grid.path, None).expect("dataset must load");
+    let config = AnnConfig {
+        k: NonZeroUsize::new(21).expect("non-zero"),
+        ..config(grid.path.clone())
+    };
+
+    let err = benchmark_dataset(&dataset, &config).expect_err("k must be rejected");
+    assert!(matches!(
+        err,
+        AnnError::KExceedsGroundTruth { k: 21, depth: 20 }
+    ));
+}
+
+#[rstest]
+fn non_hdf5_files_are_rejected() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("plain-2-euclidean.hdf5");
+    fs::write(&path, b"not an HDF5 file at all, just text").expect("write");
+
+    let err = AnnDataset::load(&path, None).expect_err("file must be rejected");
+    assert!(matches!(
+        err,
+        AnnError::Hdf5 {
+            source: Hdf5Error::Malformed(_),
+            ..
+        }
+    ));
+}
+
+#[rstest]
+#[case::k("CHUTORO_ANN_K", "0")]
+#[case::ef_search("CHUTORO_ANN_EF_SEARCH", "10,,20")]
+#[case::metric("CHUTORO_ANN_METRIC", "hamming")]
+fn invalid_settings_are_rejected(#[case] name: &str, #[case] value: &str) {
+    let err = config_from_lookup(|key| match key {
+        "CHUTORO_ANN_DATASET" => Some("data.hdf5".to_owned()),
+        _ => (key == name).then(|| value.to_owned()),
+    })
+    .expect_err("setting must be rejected");
+
+    assert!(matches!(err, AnnError::InvalidSetting { .. }));
+    assert!(err.to_string().contains(name), "unexpected error: {err}");
+}
+
+#[rstest]
+fn the_dataset_setting_is_required() {
+    let err = config_from_lookup(|_| None).expect_err("dataset must be required");
+
+    assert!(matches!(err, AnnError::MissingSetting { .. }));
+}
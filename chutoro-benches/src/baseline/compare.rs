@@ -0,0 +1,190 @@
+//! Comparison of a fresh baseline against a stored one.
+
+use std::fmt;
+
+use super::{Baseline, BaselineRecord};
+
+/// How far a measurement may move in the wrong direction before it counts
+/// as a regression.
+///
+/// Runtime and peak RSS tolerances are percentages of the stored value, so
+/// `runtime_percent = 25` accepts runs up to 25% slower. Quality tolerances
+/// are absolute drops, so `recall_drop = 0.02` accepts recall falling from
+/// `0.95` to `0.93`. Improvements never count as regressions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tolerance {
+    /// Allowed runtime growth, in percent.
+    pub runtime_percent: u64,
+    /// Allowed peak RSS growth, in percent.
+    pub rss_percent: u64,
+    /// Allowed absolute fall in recall.
+    pub recall_drop: f64,
+    /// Allowed absolute fall in ARI.
+    pub ari_drop: f64,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self {
+            runtime_percent: 25,
+            rss_percent: 25,
+            recall_drop: 0.02,
+            ari_drop: 0.02,
+        }
+    }
+}
+
+/// The measurement that regressed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RegressionMetric {
+    /// Pipeline wall-clock time.
+    Runtime,
+    /// Peak resident-set growth.
+    PeakRss,
+    /// HNSW recall.
+    Recall,
+    /// Adjusted Rand Index.
+    Ari,
+    /// The case is in the stored baseline but was not measured.
+    Missing,
+}
+
+/// A measurement that moved beyond its tolerance.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Regression {
+    /// Name of the regressed case.
+    pub case: String,
+    /// Which measurement regressed.
+    pub metric: RegressionMetric,
+    /// The stored value.
+    pub baseline: f64,
+    /// The freshly measured value.
+    pub current: f64,
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.metric {
+            RegressionMetric::Missing => write!(f, "{}: case was not measured", self.case),
+            metric => write!(
+                f,
+                "{}: {metric:?} regressed from {} to {}",
+                self.case, self.baseline, self.current
+            ),
+        }
+    }
+}
+
+/// Returns every regression of `current` against `stored`.
+///
+/// Cases only present in `current` are new and never regress. Peak RSS is
+/// compared only when both baselines recorded it.
+///
+/// # Examples
+/// ```
+/// use chutoro_benches::baseline::{
+///     BASELINE_VERSION, Baseline, BaselineRecord, RegressionMetric, Tolerance, compare,
+/// };
+///
+/// let record = |runtime_millis| BaselineRecord {
+///     case: "small".to_owned(),
+///     point_count: 100,
+///     max_connections: 8,
+///     ef_construction: 100,
+///     runtime_millis,
+///     peak_rss_bytes: None,
+///     recall: 0.9,
+///     ari: 0.8,
+/// };
+/// let baseline = |runtime| Baseline { version: BASELINE_VERSION, records: vec![record(runtime)] };
+///
+/// let regressions = compare(&baseline(100), &baseline(130), Tolerance::default());
+/// assert_eq!(regressions.len(), 1);
+/// assert_eq!(regressions[0].metric, RegressionMetric::Runtime);
+/// assert!(compare(&baseline(100), &baseline(120), Tolerance::default()).is_empty());
+/// ```
+#[must_use]
+pub fn compare(stored: &Baseline, current: &Baseline, tolerance: Tolerance) -> Vec<Regression> {
+    stored
+        .records
+        .iter()
+        .flat_map(|expected| {
+            current.record(&expected.case).map_or_else(
+                || vec![regression(expected, RegressionMetric::Missing, 0.0, 0.0)],
+                |actual| compare_record(expected, actual, tolerance),
+            )
+        })
+        .collect()
+}
+
+#[expect(
+    clippy::float_arithmetic,
+    clippy::cast_precision_loss,
+    reason = "Quality scores are fractions and reported values are for display only."
+)]
+fn compare_record(
+    expected: &BaselineRecord,
+    actual: &BaselineRecord,
+    tolerance: Tolerance,
+) -> Vec<Regression> {
+    let mut regressions = Vec::new();
+    if grew_beyond(
+        expected.runtime_millis,
+        actual.runtime_millis,
+        tolerance.runtime_percent,
+    ) {
+        regressions.push(regression(
+            expected,
+            RegressionMetric::Runtime,
+            expected.runtime_millis as f64,
+            actual.runtime_millis as f64,
+        ));
+    }
+    if let (Some(before), Some(after)) = (expected.peak_rss_bytes, actual.peak_rss_bytes)
+        && grew_beyond(before, after, tolerance.rss_percent)
+    {
+        regressions.push(regression(
+            expected,
+            RegressionMetric::PeakRss,
+            before as f64,
+            after as f64,
+        ));
+    }
+    if actual.recall < expected.recall - tolerance.recall_drop {
+        regressions.push(regression(
+            expected,
+            RegressionMetric::Recall,
+            expected.recall,
+            actual.recall,
+        ));
+    }
+    if actual.ari < expected.ari - tolerance.ari_drop {
+        regressions.push(regression(
+            expected,
+            RegressionMetric::Ari,
+            expected.ari,
+            actual.ari,
+        ));
+    }
+    regressions
+}
+
+/// Returns whether `after` exceeds `before` by more than `percent`.
+fn grew_beyond(before: u64, after: u64, percent: u64) -> bool {
+    let limit = u128::from(before).saturating_mul(u128::from(percent).saturating_add(100));
+    u128::from(after).saturating_mul(100) > limit
+}
+
+fn regression(
+    expected: &BaselineRecord,
+    metric: RegressionMetric,
+    baseline: f64,
+    current: f64,
+) -> Regression {
+    Regression {
+        case: expected.case.clone(),
+        metric,
+        baseline,
+        current,
+    }
+}
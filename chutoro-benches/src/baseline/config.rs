@@ -0,0 +1,105 @@
+//! Environment-variable configuration for the `baseline` binary.
+
+use std::path::PathBuf;
+
+use super::{BaselineError, Tolerance};
+use crate::settings::SettingReader;
+
+/// Prefix shared by every baseline configuration variable.
+pub const BASELINE_ENV_PREFIX: &str = "CHUTORO_BASELINE_";
+
+/// Accepted values of every tolerance variable.
+const NON_NEGATIVE: &str = "a non-negative number";
+
+/// Default destination for freshly measured baselines.
+const DEFAULT_OUTPUT: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../target/benchmarks/baseline.json"
+);
+
+/// Where the `baseline` binary writes its results and what it compares them
+/// against.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BaselineConfig {
+    /// Destination for the freshly measured baseline.
+    pub output: PathBuf,
+    /// Stored baseline to compare against, if any.
+    pub compare_with: Option<PathBuf>,
+    /// Regression tolerances used for the comparison.
+    pub tolerance: Tolerance,
+}
+
+impl Default for BaselineConfig {
+    fn default() -> Self {
+        Self {
+            output: PathBuf::from(DEFAULT_OUTPUT),
+            compare_with: None,
+            tolerance: Tolerance::default(),
+        }
+    }
+}
+
+/// Reads the baseline configuration from the process environment.
+///
+/// # Errors
+/// Returns [`BaselineError::InvalidSetting`] when a tolerance variable does
+/// not parse.
+///
+/// # Examples
+/// ```
+/// use chutoro_benches::baseline::config_from_env;
+///
+/// let _config = config_from_env();
+/// ```
+pub fn config_from_env() -> Result<BaselineConfig, BaselineError> {
+    config_from_lookup(|name| std::env::var(name).ok())
+}
+
+/// Builds a baseline configuration from `lookup`, which maps a variable
+/// name to its value.
+///
+/// | Variable                                  | Field                       |
+/// | ----------------------------------------- | --------------------------- |
+/// | `CHUTORO_BASELINE_OUTPUT`                 | `output`                    |
+/// | `CHUTORO_BASELINE_COMPARE`                | `compare_with`              |
+/// | `CHUTORO_BASELINE_RUNTIME_TOLERANCE_PCT`  | `tolerance.runtime_percent` |
+/// | `CHUTORO_BASELINE_RSS_TOLERANCE_PCT`      | `tolerance.rss_percent`     |
+/// | `CHUTORO_BASELINE_RECALL_TOLERANCE`       | `tolerance.recall_drop`     |
+/// | `CHUTORO_BASELINE_ARI_TOLERANCE`          | `tolerance.ari_drop`        |
+///
+/// # Errors
+/// Returns [`BaselineError::InvalidSetting`] when a tolerance variable does
+/// not parse.
+///
+/// # Examples
+/// ```
+/// use chutoro_benches::baseline::config_from_lookup;
+///
+/// let config = config_from_lookup(|name| {
+///     (name == "CHUTORO_BASELINE_RUNTIME_TOLERANCE_PCT").then(|| "10".to_owned())
+/// })
+/// .expect("settings must parse");
+/// assert_eq!(config.tolerance.runtime_percent, 10);
+/// assert!(config.compare_with.is_none());
+/// ```
+pub fn config_from_lookup(
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Result<BaselineConfig, BaselineError> {
+    let read = SettingReader::new(BASELINE_ENV_PREFIX, lookup);
+    let defaults = BaselineConfig::default();
+    let tolerance = defaults.tolerance;
+    Ok(BaselineConfig {
+        output: read.value("OUTPUT").map_or(defaults.output, PathBuf::from),
+        compare_with: read.value("COMPARE").map(PathBuf::from),
+        tolerance: Tolerance {
+            runtime_percent: read.parsed(
+                "RUNTIME_TOLERANCE_PCT",
+                tolerance.runtime_percent,
+                NON_NEGATIVE,
+            )?,
+            rss_percent: read.parsed("RSS_TOLERANCE_PCT", tolerance.rss_percent, NON_NEGATIVE)?,
+            recall_drop: read.parsed("RECALL_TOLERANCE", tolerance.recall_drop, NON_NEGATIVE)?,
+            ari_drop: read.parsed("ARI_TOLERANCE", tolerance.ari_drop, NON_NEGATIVE)?,
+        },
+    })
+}
@@ -0,0 +1,345 @@
+//! Regression baselines for the CPU pipeline.
+//!
+//! The `baseline` binary runs a fixed matrix of dataset × parameter cases,
+//! records runtime, peak resident-set growth, HNSW recall, and Adjusted Rand
+//! Index (ARI) for each, and writes the results as a JSON [`Baseline`]. When
+//! given a stored baseline it also [`compare`]s the two and fails on any
+//! regression beyond the configured [`Tolerance`], so the tool can serve as a
+//! performance gate in CI.
+//!
+//! Cases cluster labelled Gaussian blobs so ARI is measured against ground
+//! truth, and every case is seeded so repeated runs measure the same work.
+//! Parallel HNSW construction still orders inserts nondeterministically, so
+//! recall and ARI vary slightly between runs; the default tolerances absorb
+//! that jitter.
+
+mod compare;
+mod config;
+
+use std::{
+    fs,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use chutoro_core::{
+    ChutoroBuilder, ChutoroError, ClusteringQualityError, CpuHnsw, DataSourceError, HnswError,
+    HnswParams,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    clustering_quality::clustering_quality_score,
+    ef_sweep::{BENCH_DIMENSIONS, BENCH_SEED},
+    profiling::{ProfilingError, measure_peak_resident_set_size},
+    recall::{RecallScore, brute_force_top_k, recall_at_k},
+    settings::InvalidSetting,
+    source::{Anisotropy, GaussianBlobConfig, SyntheticConfig, SyntheticError, SyntheticSource},
+};
+
+pub use compare::{Regression, RegressionMetric, Tolerance, compare};
+pub use config::{BASELINE_ENV_PREFIX, BaselineConfig, config_from_env, config_from_lookup};
+
+/// Version written to, and required of, baseline files.
+pub const BASELINE_VERSION: u32 = 1;
+
+/// Neighbours compared per query when measuring recall.
+const RECALL_K: usize = 10;
+/// Queries sampled per case when measuring recall.
+const RECALL_QUERIES: usize = 50;
+/// Interval between resident-set samples.
+const RSS_SAMPLE_INTERVAL: Duration = Duration::from_millis(5);
+
+/// One dataset × parameter combination measured by the baseline tool.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BaselineCase {
+    /// Stable identifier used to match records across baselines.
+    pub name: &'static str,
+    /// Number of points in the Gaussian-blob dataset.
+    pub point_count: usize,
+    /// Number of blobs, and so of ground-truth clusters.
+    pub cluster_count: usize,
+    /// HNSW maximum connections per node (M).
+    pub max_connections: usize,
+    /// HNSW search width during construction.
+    pub ef_construction: usize,
+    /// Minimum cluster size passed to the pipeline.
+    pub min_cluster_size: usize,
+}
+
+/// The fixed matrix run by the `baseline` binary.
+pub const BASELINE_MATRIX: &[BaselineCase] = &[
+    case("blobs-n1000-m8", 1_000, 8),
+    case("blobs-n1000-m16", 1_000, 16),
+    case("blobs-n5000-m8", 5_000, 8),
+    case("blobs-n5000-m16", 5_000, 16),
+];
+
+const fn case(name: &'static str, point_count: usize, max_connections: usize) -> BaselineCase {
+    BaselineCase {
+        name,
+        point_count,
+        cluster_count: 8,
+        max_connections,
+        ef_construction: 100,
+        min_cluster_size: 10,
+    }
+}
+
+/// Measurements recorded for one [`BaselineCase`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BaselineRecord {
+    /// Name of the measured case.
+    pub case: String,
+    /// Number of points clustered.
+    pub point_count: usize,
+    /// HNSW maximum connections per node (M).
+    pub max_connections: usize,
+    /// HNSW search width during construction.
+    pub ef_construction: usize,
+    /// Wall-clock time of the full pipeline run in milliseconds.
+    pub runtime_millis: u64,
+    /// Peak resident-set growth during the run, when the platform reports it.
+    pub peak_rss_bytes: Option<u64>,
+    /// HNSW recall@10 over sampled queries of a uniform dataset of the same
+    /// size and parameters.
+    pub recall: f64,
+    /// ARI of the pipeline's labels against the ground truth.
+    pub ari: f64,
+}
+
+/// A set of baseline records, as stored on disk.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    /// File format version; see [`BASELINE_VERSION`].
+    pub version: u32,
+    /// One record per measured case.
+    pub records: Vec<BaselineRecord>,
+}
+
+/// Errors raised while measuring, storing, or loading baselines.
+#[derive(Debug, Error)]
+pub enum BaselineError {
+    /// Synthetic data generation failed.
+    #[error("synthetic source generation failed: {0}")]
+    Synthetic(#[from] SyntheticError),
+    /// HNSW parameter validation, build, or search failed.
+    #[error("HNSW operation failed: {0}")]
+    Hnsw(#[from] HnswError),
+    /// The clustering pipeline failed.
+    #[error("pipeline run failed: {0}")]
+    Pipeline(#[from] ChutoroError),
+    /// A distance computation for the recall oracle failed.
+    #[error("data source error: {0}")]
+    DataSource(#[from] DataSourceError),
+    /// ARI computation failed.
+    #[error("clustering quality computation failed: {0}")]
+    ClusteringQuality(#[from] ClusteringQualityError),
+    /// Peak resident-set sampling failed.
+    #[error("memory profiling failed: {0}")]
+    Profiling(#[from] ProfilingError),
+    /// A case parameter was zero where a positive value is required.
+    #[error("case {case} has a zero {parameter}")]
+    ZeroParameter {
+        /// Name of the offending case.
+        case: &'static str,
+        /// The parameter that was zero.
+        parameter: &'static str,
+    },
+    /// Reading or writing a baseline file failed.
+    #[error("failed to access baseline {path}: {source}")]
+    Io {
+        /// The baseline file.
+        path: PathBuf,
+        /// The underlying I/O error.
+        source: std::io::Error,
+    },
+    /// A baseline file did not hold valid baseline JSON.
+    #[error("failed to parse baseline {path}: {source}")]
+    Json {
+        /// The baseline file.
+        path: PathBuf,
+        /// The underlying parse error.
+        source: serde_json::Error,
+    },
+    /// A baseline file was written by an incompatible version of the tool.
+    #[error("baseline {path} has unsupported version {version}")]
+    UnsupportedVersion {
+        /// The baseline file.
+        path: PathBuf,
+        /// The version it declares.
+        version: u32,
+    },
+    /// A configuration variable held a value that could not be used.
+    #[error(transparent)]
+    InvalidSetting(#[from] InvalidSetting),
+}
+
+impl Baseline {
+    /// Measures every case in `cases`, in order.
+    ///
+    /// # Errors
+    /// Returns [`BaselineError`] when any case fails to run.
+    pub fn measure(cases: &[BaselineCase]) -> Result<Self, BaselineError> {
+        let records = cases.iter().map(measure_case).collect::<Result<_, _>>()?;
+        Ok(Self {
+            version: BASELINE_VERSION,
+            records,
+        })
+    }
+
+    /// Returns the record for the case called `name`.
+    #[must_use]
+    pub fn record(&self, name: &str) -> Option<&BaselineRecord> {
+        self.records.iter().find(|record| record.case == name)
+    }
+
+    /// Loads a baseline, rejecting files from other format versions.
+    ///
+    /// # Errors
+    /// Returns [`BaselineError`] when the file cannot be read or parsed, or
+    /// declares an unsupported version.
+    pub fn load(path: &Path) -> Result<Self, BaselineError> {
+        let contents = fs::read_to_string(path).map_err(|source| BaselineError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let baseline: Self =
+            serde_json::from_str(&contents).map_err(|source| BaselineError::Json {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        if baseline.version != BASELINE_VERSION {
+            return Err(BaselineError::UnsupportedVersion {
+                path: path.to_path_buf(),
+                version: baseline.version,
+            });
+        }
+        Ok(baseline)
+    }
+
+    /// Writes the baseline as pretty-printed JSON, creating parent
+    /// directories as needed.
+    ///
+    /// # Errors
+    /// Returns [`BaselineError::Io`] when the file cannot be written.
+    pub fn write(&self, path: &Path) -> Result<(), BaselineError> {
+        let io_error = |source| BaselineError::Io {
+            path: path.to_path_buf(),
+            source,
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(io_error)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|source| BaselineError::Json {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        fs::write(path, json).map_err(io_error)
+    }
+}
+
+/// Runs one case: the full pipeline over labelled blobs under the RSS
+/// sampler, then a recall probe against a brute-force oracle.
+///
+/// Peak RSS is recorded as `None` on platforms the sampler does not support.
+///
+/// # Errors
+/// Returns [`BaselineError`] when data generation, the pipeline, the recall
+/// probe, or scoring fails.
+pub fn measure_case(case: &BaselineCase) -> Result<BaselineRecord, BaselineError> {
+    let (source, truth) =
+        SyntheticSource::generate_gaussian_blobs_with_labels(&GaussianBlobConfig {
+            point_count: case.point_count,
+            dimensions: BENCH_DIMENSIONS,
+            cluster_count: case.cluster_count,
+            separation: 6.0,
+            anisotropy: Anisotropy::Isotropic(0.35),
+            seed: BENCH_SEED,
+        })?;
+    let params =
+        HnswParams::new(case.max_connections, case.ef_construction)?.with_rng_seed(BENCH_SEED);
+    let chutoro = ChutoroBuilder::new()
+        .with_min_cluster_size(case.min_cluster_size)
+        .with_hnsw_params(params.clone())
+        .build()?;
+
+    let started = Instant::now();
+    let (result, peak_rss_bytes) =
+        match measure_peak_resident_set_size(RSS_SAMPLE_INTERVAL, || chutoro.run(&source)) {
+            Ok((result, measurement)) => (result, Some(measurement.peak_rss_bytes)),
+            Err(ProfilingError::UnsupportedPlatform { .. }) => (chutoro.run(&source), None),
+            Err(err) => return Err(err.into()),
+        };
+    let runtime = started.elapsed();
+    let labels: Vec<usize> = result?
+        .assignments()
+        .iter()
+        .map(|id| usize::try_from(id.get()).unwrap_or(usize::MAX))
+        .collect();
+
+    Ok(BaselineRecord {
+        case: case.name.to_owned(),
+        point_count: case.point_count,
+        max_connections: case.max_connections,
+        ef_construction: case.ef_construction,
+        runtime_millis: u64::try_from(runtime.as_millis()).unwrap_or(u64::MAX),
+        peak_rss_bytes,
+        recall: fraction(measure_recall(case, params)?),
+        ari: clustering_quality_score(&truth, &labels)?.ari,
+    })
+}
+
+/// Sums recall@[`RECALL_K`] over evenly spaced queries of a uniform dataset
+/// the size of the case.
+///
+/// Uniform data is used, as in the `ef_construction` sweep's recall report,
+/// because searches over well-separated blobs rarely leave the entry point's
+/// blob and would measure the dataset's separation rather than the index.
+fn measure_recall(case: &BaselineCase, params: HnswParams) -> Result<RecallScore, BaselineError> {
+    let source = SyntheticSource::generate(&SyntheticConfig {
+        point_count: case.point_count,
+        dimensions: BENCH_DIMENSIONS,
+        seed: BENCH_SEED,
+    })?;
+    let ef = NonZeroUsize::new(case.ef_construction.max(RECALL_K)).ok_or(
+        BaselineError::ZeroParameter {
+            case: case.name,
+            parameter: "ef_construction",
+        },
+    )?;
+    let step = NonZeroUsize::new(case.point_count.div_ceil(RECALL_QUERIES)).ok_or(
+        BaselineError::ZeroParameter {
+            case: case.name,
+            parameter: "point_count",
+        },
+    )?;
+    let index = CpuHnsw::build(&source, params)?;
+    let mut total = RecallScore { hits: 0, total: 0 };
+    for query in (0..case.point_count).step_by(step.get()) {
+        let oracle = brute_force_top_k(&source, query, RECALL_K)?;
+        let observed: Vec<_> = index
+            .search(&source, query, ef)?
+            .into_iter()
+            .filter(|neighbour| neighbour.id != query)
+            .collect();
+        let score = recall_at_k(&oracle, &observed, RECALL_K);
+        total.hits = total.hits.saturating_add(score.hits);
+        total.total = total.total.saturating_add(score.total);
+    }
+    Ok(total)
+}
+
+#[expect(
+    clippy::float_arithmetic,
+    clippy::cast_precision_loss,
+    reason = "Recall is stored as a fraction so baselines stay readable."
+)]
+fn fraction(score: RecallScore) -> f64 {
+    if score.total == 0 {
+        return 0.0;
+    }
+    score.hits as f64 / score.total as f64
+}
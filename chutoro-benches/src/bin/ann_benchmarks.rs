@@ -0,0 +1,67 @@
+//! Reports `CpuHnsw` recall and throughput on an ann-benchmarks dataset.
+//!
+//! Loads the HDF5 file named by `CHUTORO_ANN_DATASET`, builds one index over
+//! its training vectors, and prints recall@k and queries per second for each
+//! `ef_search` value, in the form ann-benchmarks publishes for other
+//! libraries. When `CHUTORO_ANN_OUTPUT` is set, the rows are also written
+//! there as CSV. See
+//! [`chutoro_benches::ann_benchmarks::config_from_lookup`] for the settings.
+
+use std::process::ExitCode;
+
+use chutoro_benches::{
+    ann_benchmarks::{AnnError, config_from_env, queries_per_second, run_ann_benchmark},
+    recall::RecallScore,
+};
+
+#[expect(
+    clippy::print_stderr,
+    reason = "the adapter binary reports failures on the terminal"
+)]
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("ann-benchmarks adapter failed: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Runs the configured benchmark and prints one line per search width.
+#[expect(
+    clippy::print_stdout,
+    reason = "the adapter binary reports its results on the terminal"
+)]
+fn run() -> Result<(), AnnError> {
+    let config = config_from_env()?;
+    let report = run_ann_benchmark(&config)?;
+    println!(
+        "{} ({}): {} points, {} queries, M={} ef_construction={} build={}ms",
+        report.dataset,
+        report.metric,
+        report.point_count,
+        report.query_count,
+        report.max_connections,
+        report.ef_construction,
+        report.build_time_millis,
+    );
+    for measurement in &report.measurements {
+        println!(
+            "ef_search={}: recall@{}={} qps={}",
+            measurement.ef_search,
+            report.k,
+            fraction(measurement.recall),
+            queries_per_second(report.query_count, measurement.search_time_micros),
+        );
+    }
+    if let Some(output) = &config.output {
+        let written = report.write_csv(output).map_err(AnnError::Report)?;
+        println!("report written to {}", written.display());
+    }
+    Ok(())
+}
+
+fn fraction(score: RecallScore) -> String {
+    format!("{}/{}", score.hits, score.total)
+}
@@ -0,0 +1,60 @@
+//! Measures the regression-baseline matrix and optionally gates on it.
+//!
+//! Runs every case in [`chutoro_benches::baseline::BASELINE_MATRIX`], writes
+//! the results to `CHUTORO_BASELINE_OUTPUT`, and, when
+//! `CHUTORO_BASELINE_COMPARE` names a stored baseline, exits unsuccessfully
+//! if any case regressed beyond the configured tolerance. See
+//! [`chutoro_benches::baseline::config_from_lookup`] for the settings.
+
+use std::process::ExitCode;
+
+use chutoro_benches::baseline::{
+    BASELINE_MATRIX, Baseline, BaselineError, compare, config_from_env,
+};
+
+#[expect(
+    clippy::print_stderr,
+    reason = "the baseline binary reports failures on the terminal"
+)]
+fn main() -> ExitCode {
+    match run() {
+        Ok(0) => ExitCode::SUCCESS,
+        Ok(count) => {
+            eprintln!("baseline failed: {count} regression(s)");
+            ExitCode::FAILURE
+        }
+        Err(err) => {
+            eprintln!("baseline failed: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Measures, writes, and compares the baseline, returning the number of
+/// regressions found.
+#[expect(
+    clippy::print_stdout,
+    reason = "the baseline binary reports its results on the terminal"
+)]
+fn run() -> Result<usize, BaselineError> {
+    let config = config_from_env()?;
+    let current = Baseline::measure(BASELINE_MATRIX)?;
+    for record in &current.records {
+        println!(
+            "{}: runtime={}ms peak_rss={:?} recall={:.4} ari={:.4}",
+            record.case, record.runtime_millis, record.peak_rss_bytes, record.recall, record.ari,
+        );
+    }
+    current.write(&config.output)?;
+    println!("baseline written to {}", config.output.display());
+
+    let Some(stored_path) = config.compare_with else {
+        return Ok(0);
+    };
+    let stored = Baseline::load(&stored_path)?;
+    let regressions = compare(&stored, &current, config.tolerance);
+    for regression in &regressions {
+        println!("regression: {regression}");
+    }
+    Ok(regressions.len())
+}
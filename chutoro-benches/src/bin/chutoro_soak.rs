@@ -0,0 +1,38 @@
+//! Concurrency soak for the CPU HNSW index.
+//!
+//! Interleaves parallel inserts, searches, and invariant checks over growing
+//! synthetic datasets until `CHUTORO_SOAK_DURATION_SECS` elapses, then prints
+//! the violation statistics. Exits unsuccessfully when a settled graph breaks
+//! an invariant, a round stalls, or an operation fails. See
+//! [`chutoro_benches::soak::config_from_lookup`] for the settings.
+
+use std::process::ExitCode;
+
+use chutoro_benches::soak::{config_from_env, run_soak};
+
+#[expect(
+    clippy::print_stdout,
+    clippy::print_stderr,
+    reason = "the soak binary reports its statistics on the terminal"
+)]
+fn main() -> ExitCode {
+    let outcome = config_from_env().and_then(|config| {
+        println!("soak configuration: {config:?}");
+        run_soak(&config)
+    });
+    match outcome {
+        Ok(stats) => {
+            println!("{stats}");
+            if stats.passed() {
+                ExitCode::SUCCESS
+            } else {
+                eprintln!("soak failed: settled graphs violated HNSW invariants");
+                ExitCode::FAILURE
+            }
+        }
+        Err(err) => {
+            eprintln!("soak failed: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -1,11 +1,21 @@
-//! `ef_construction` sweep constants and parameter helpers.
+//! `ef_construction` sweep constants, parameter helpers, and recall sweeps.
 //!
 //! Provides the parameter matrix for the `hnsw_build_ef_sweep` benchmark
 //! group, which varies `ef_construction` independently of `M` to reveal
-//! build-time versus recall trade-offs.
+//! build-time versus recall trade-offs, and [`RecallSweep`], which scores an
+//! arbitrary parameter grid on any data source and reports its Pareto
+//! frontier.
 
 use chutoro_core::HnswParams;
 
+mod pareto;
+mod recall_sweep;
+
+pub use pareto::{RecallSweepPoint, RecallSweepReport};
+pub use recall_sweep::{
+    DEFAULT_EF_SEARCH_VALUES, DEFAULT_QUERY_COUNT, DEFAULT_RECALL_K, RecallSweep,
+};
+
 use crate::{
     error::BenchSetupError,
     source::{SyntheticConfig, SyntheticSource},
@@ -140,3 +150,6 @@ mod tests {
         assert!(make_hnsw_params_with_ef(m, ef, 42).is_err());
     }
 }
+
+#[cfg(test)]
+mod recall_sweep_tests;
@@ -0,0 +1,195 @@
+//! Recall sweep results, CSV output, and the Pareto frontier table.
+//!
+//! A grid point is on the frontier when no other point matches or beats it
+//! on recall, build time, and search time at once while strictly beating it
+//! on at least one. The frontier is the short list worth choosing from;
+//! every other point pays more for the same or worse recall.
+
+use std::{
+    cmp::Ordering,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::recall::{RecallScore, recall_fraction};
+
+/// One scored `(M, ef_construction, ef_search)` combination.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecallSweepPoint {
+    /// HNSW maximum connections per node (M).
+    pub max_connections: usize,
+    /// HNSW search width during construction, after sentinel resolution.
+    pub ef_construction: usize,
+    /// HNSW search width used for the sampled queries.
+    pub ef_search: usize,
+    /// Aggregated recall score across the sampled queries.
+    pub recall: RecallScore,
+    /// Wall-clock index build time in milliseconds.
+    pub build_time_millis: u128,
+    /// Wall-clock time for all sampled queries in microseconds.
+    pub search_time_micros: u128,
+}
+
+impl RecallSweepPoint {
+    /// Returns whether `self` is at least as good as `other` on every axis
+    /// and strictly better on one.
+    fn dominates(&self, other: &Self) -> bool {
+        let recall = compare_recall(self.recall, other.recall);
+        let build = other.build_time_millis.cmp(&self.build_time_millis);
+        let search = other.search_time_micros.cmp(&self.search_time_micros);
+        let axes = [recall, build, search];
+        axes.iter().all(|axis| axis.is_ge()) && axes.iter().any(|axis| axis.is_gt())
+    }
+}
+
+/// Compares two recall scores as fractions without float arithmetic.
+fn compare_recall(left: RecallScore, right: RecallScore) -> Ordering {
+    let widen = |value: usize| u128::try_from(value).unwrap_or(u128::MAX);
+    let left_scaled = widen(left.hits).saturating_mul(widen(right.total));
+    let right_scaled = widen(right.hits).saturating_mul(widen(left.total));
+    left_scaled.cmp(&right_scaled)
+}
+
+/// The outcome of a [`super::RecallSweep`] run.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecallSweepReport {
+    /// Number of points in the swept data source.
+    pub point_count: usize,
+    /// Neighbour count scored by recall@k.
+    pub k: usize,
+    /// Number of sampled queries behind every score.
+    pub query_count: usize,
+    /// Scored grid points in sweep order.
+    pub points: Vec<RecallSweepPoint>,
+}
+
+impl RecallSweepReport {
+    const fn csv_header() -> &'static str {
+        "point_count,k,query_count,max_connections,ef_construction,ef_search,recall_hits,recall_total,recall_fraction,build_time_ms,search_time_us,pareto\n"
+    }
+
+    /// Renders every grid point as CSV, marking frontier members in the
+    /// `pareto` column.
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let mut output = String::from(Self::csv_header());
+        for point in &self.points {
+            output.push_str(&self.csv_row(point));
+        }
+        output
+    }
+
+    fn csv_row(&self, point: &RecallSweepPoint) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{},{},{},{},{}\n",
+            self.point_count,
+            self.k,
+            self.query_count,
+            point.max_connections,
+            point.ef_construction,
+            point.ef_search,
+            point.recall.hits,
+            point.recall.total,
+            recall_fraction(point.recall),
+            point.build_time_millis,
+            point.search_time_micros,
+            self.is_on_frontier(point),
+        )
+    }
+
+    /// Writes [`Self::to_csv`] to `report_path`, creating parent
+    /// directories, and returns the written path.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`std::io::Error`] if directory creation or file writing fails.
+    pub fn write_csv(&self, report_path: impl AsRef<Path>) -> Result<PathBuf, std::io::Error> {
+        let report_file_path = report_path.as_ref().to_path_buf();
+        if let Some(parent) = report_file_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&report_file_path, self.to_csv())?;
+        Ok(report_file_path)
+    }
+
+    /// Returns the non-dominated grid points, cheapest build first.
+    ///
+    /// Ties on build time are broken by search time, then by descending
+    /// recall.
+    #[must_use]
+    pub fn pareto_frontier(&self) -> Vec<&RecallSweepPoint> {
+        let mut frontier: Vec<_> = self
+            .points
+            .iter()
+            .filter(|point| self.is_on_frontier(point))
+            .collect();
+        frontier.sort_by(|left, right| {
+            left.build_time_millis
+                .cmp(&right.build_time_millis)
+                .then(left.search_time_micros.cmp(&right.search_time_micros))
+                .then(compare_recall(right.recall, left.recall))
+        });
+        frontier
+    }
+
+    /// Renders the Pareto frontier as a fixed-width ASCII table.
+    #[must_use]
+    pub fn pareto_table(&self) -> String {
+        let recall_heading = format!("recall@{}", self.k);
+        let headings = [
+            "M",
+            "ef_construction",
+            "ef_search",
+            recall_heading.as_str(),
+            "build_ms",
+            "search_us",
+        ];
+        let rows: Vec<[String; 6]> = self
+            .pareto_frontier()
+            .into_iter()
+            .map(|point| {
+                [
+                    point.max_connections.to_string(),
+                    point.ef_construction.to_string(),
+                    point.ef_search.to_string(),
+                    recall_fraction(point.recall),
+                    point.build_time_millis.to_string(),
+                    point.search_time_micros.to_string(),
+                ]
+            })
+            .collect();
+        let widths: [usize; 6] = std::array::from_fn(|column| {
+            rows.iter()
+                .filter_map(|row| row.get(column).map(String::len))
+                .chain(headings.get(column).map(|heading| heading.len()))
+                .max()
+                .unwrap_or(0)
+        });
+
+        let rule = widths
+            .iter()
+            .map(|&width| "-".repeat(width.saturating_add(2)))
+            .fold(String::from("+"), |line, cell| line + &cell + "+");
+        let mut table = format!("{rule}\n{}\n{rule}\n", table_row(&headings, &widths));
+        for row in &rows {
+            table.push_str(&table_row(&row.each_ref().map(String::as_str), &widths));
+            table.push('\n');
+        }
+        table.push_str(&rule);
+        table.push('\n');
+        table
+    }
+
+    fn is_on_frontier(&self, point: &RecallSweepPoint) -> bool {
+        !self.points.iter().any(|other| other.dominates(point))
+    }
+}
+
+/// Right-aligns `cells` into `|`-separated columns of the given widths.
+fn table_row(cells: &[&str; 6], widths: &[usize; 6]) -> String {
+    cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, &width)| format!(" {cell:>width$} |"))
+        .fold(String::from("|"), |line, cell| line + &cell)
+}
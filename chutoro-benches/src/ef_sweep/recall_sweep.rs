@@ -0,0 +1,286 @@
+//! Grid sweep of HNSW parameters against a sampled exact recall baseline.
+//!
+//! [`RecallSweep`] builds one index per `(M, ef_construction)` pair of its
+//! grid, queries it once per search width, and scores each search against
+//! brute-force neighbours computed once for a seeded sample of query points.
+//! It accepts any [`DataSource`], so anisotropic, high-dimensional, and
+//! real-world datasets can be swept with the same evidence as the uniform
+//! benchmark data.
+
+use std::{num::NonZeroUsize, time::Instant};
+
+use chutoro_core::{CpuHnsw, DataSource, Neighbour};
+use rand::{SeedableRng, rngs::SmallRng, seq::index};
+
+use super::{
+    BENCH_SEED, EF_CONSTRUCTION_VALUES, EF_SWEEP_MAX_CONNECTIONS, make_hnsw_params_with_ef,
+    resolve_ef_construction,
+};
+use crate::{
+    error::BenchSetupError,
+    recall::{RecallScore, brute_force_top_k, recall_at_k},
+};
+
+use super::pareto::{RecallSweepPoint, RecallSweepReport};
+
+/// Default search widths swept by [`RecallSweep::new`].
+pub const DEFAULT_EF_SEARCH_VALUES: &[usize] = &[16, 32, 64, 128];
+
+/// Default neighbour count scored by [`RecallSweep::new`].
+pub const DEFAULT_RECALL_K: usize = 10;
+
+/// Default number of sampled queries used by [`RecallSweep::new`].
+pub const DEFAULT_QUERY_COUNT: usize = 50;
+
+/// A grid of HNSW parameters to score by recall@k.
+///
+/// Each grid axis is swept exhaustively. `ef_construction` accepts the `0`
+/// sentinel, resolved to `M * 2` as in [`EF_CONSTRUCTION_VALUES`]. Pairs
+/// whose resolved `ef_construction` is below `M` are skipped because
+/// [`chutoro_core::HnswParams`] rejects them.
+///
+/// # Examples
+///
+/// ```
+/// use chutoro_benches::{
+///     ef_sweep::RecallSweep,
+///     source::{SyntheticConfig, SyntheticSource},
+/// };
+///
+/// let source = SyntheticSource::generate(&SyntheticConfig {
+///     point_count: 200,
+///     dimensions: 8,
+///     seed: 7,
+/// })
+/// .expect("generation should succeed");
+/// let report = RecallSweep::new()
+///     .with_max_connections(vec![8])
+///     .with_ef_construction(vec![0, 64])
+///     .with_ef_search(vec![16, 32])
+///     .with_query_count(20)
+///     .run(&source)
+///     .expect("sweep should succeed");
+/// assert_eq!(report.points.len(), 4);
+/// assert!(!report.pareto_frontier().is_empty());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecallSweep {
+    max_connections: Vec<usize>,
+    ef_construction: Vec<usize>,
+    ef_search: Vec<usize>,
+    k: usize,
+    query_count: usize,
+    seed: u64,
+}
+
+impl Default for RecallSweep {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RecallSweep {
+    /// Creates a sweep over the benchmark `M` and `ef_construction` values,
+    /// [`DEFAULT_EF_SEARCH_VALUES`], recall@[`DEFAULT_RECALL_K`], and
+    /// [`DEFAULT_QUERY_COUNT`] queries sampled with [`BENCH_SEED`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            max_connections: EF_SWEEP_MAX_CONNECTIONS.to_vec(),
+            ef_construction: EF_CONSTRUCTION_VALUES.to_vec(),
+            ef_search: DEFAULT_EF_SEARCH_VALUES.to_vec(),
+            k: DEFAULT_RECALL_K,
+            query_count: DEFAULT_QUERY_COUNT,
+            seed: BENCH_SEED,
+        }
+    }
+
+    /// Sets the `M` (`max_connections`) values to sweep.
+    #[must_use]
+    pub fn with_max_connections(mut self, values: Vec<usize>) -> Self {
+        self.max_connections = values;
+        self
+    }
+
+    /// Sets the `ef_construction` values to sweep; `0` means `M * 2`.
+    #[must_use]
+    pub fn with_ef_construction(mut self, values: Vec<usize>) -> Self {
+        self.ef_construction = values;
+        self
+    }
+
+    /// Sets the search widths to query each index with.
+    ///
+    /// Every value must exceed `k` so the query point, which the search
+    /// returns as its own nearest neighbour, can be dropped without leaving
+    /// fewer than `k` results to score.
+    #[must_use]
+    pub fn with_ef_search(mut self, values: Vec<usize>) -> Self {
+        self.ef_search = values;
+        self
+    }
+
+    /// Sets the neighbour count scored by recall@k.
+    #[must_use]
+    pub const fn with_k(mut self, k: usize) -> Self {
+        self.k = k;
+        self
+    }
+
+    /// Sets how many query points are sampled; capped at the source length.
+    #[must_use]
+    pub const fn with_query_count(mut self, query_count: usize) -> Self {
+        self.query_count = query_count;
+        self
+    }
+
+    /// Sets the seed for both query sampling and index construction.
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Runs the sweep over `source`.
+    ///
+    /// The exact baseline is computed once for the sampled queries and
+    /// shared by every grid point. Build times cover index construction
+    /// only; search times sum the sampled queries.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BenchSetupError::InvalidRecallSweep`] when a grid axis is
+    /// empty, `k` or the query count is zero, a search width does not exceed
+    /// `k`, or the source has fewer than two points. Returns
+    /// [`BenchSetupError::Hnsw`] or [`BenchSetupError::DataSource`] when
+    /// building, searching, or the exact baseline fails.
+    pub fn run<D: DataSource + Sync>(
+        &self,
+        source: &D,
+    ) -> Result<RecallSweepReport, BenchSetupError> {
+        let ef_search = self.validate(source.len())?;
+        let baseline = SampledBaseline::new(source, self.sample_queries(source.len()), self.k)?;
+
+        let mut points = Vec::new();
+        for (m, ef_construction) in self.grid() {
+            let params = make_hnsw_params_with_ef(m, ef_construction, self.seed)?;
+            let started = Instant::now();
+            let index = CpuHnsw::build(source, params)?;
+            let build_time_millis = started.elapsed().as_millis();
+
+            for &ef in &ef_search {
+                let started_search = Instant::now();
+                let recall = baseline.score(source, &index, ef)?;
+                points.push(RecallSweepPoint {
+                    max_connections: m,
+                    ef_construction,
+                    ef_search: ef.get(),
+                    recall,
+                    build_time_millis,
+                    search_time_micros: started_search.elapsed().as_micros(),
+                });
+            }
+        }
+
+        Ok(RecallSweepReport {
+            point_count: source.len(),
+            k: self.k,
+            query_count: baseline.queries.len(),
+            points,
+        })
+    }
+
+    /// Yields the `(M, ef_construction)` pairs to build, with sentinels
+    /// resolved and pairs rejected by `HnswParams` skipped.
+    fn grid(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.max_connections.iter().flat_map(move |&m| {
+            self.ef_construction
+                .iter()
+                .map(move |&ef_raw| (m, resolve_ef_construction(m, ef_raw)))
+                .filter(|&(max_connections, ef)| ef >= max_connections)
+        })
+    }
+
+    fn validate(&self, len: usize) -> Result<Vec<NonZeroUsize>, BenchSetupError> {
+        let invalid = |parameter, reason| BenchSetupError::InvalidRecallSweep { parameter, reason };
+        if self.max_connections.is_empty() {
+            return Err(invalid("max_connections", "grid is empty"));
+        }
+        if self.ef_construction.is_empty() {
+            return Err(invalid("ef_construction", "grid is empty"));
+        }
+        if self.k == 0 {
+            return Err(invalid("k", "must be non-zero"));
+        }
+        if self.query_count == 0 {
+            return Err(invalid("query_count", "must be non-zero"));
+        }
+        if len < 2 {
+            return Err(invalid("source", "needs at least two points"));
+        }
+        if self.ef_search.is_empty() {
+            return Err(invalid("ef_search", "grid is empty"));
+        }
+        self.ef_search
+            .iter()
+            .map(|&ef| {
+                NonZeroUsize::new(ef)
+                    .filter(|width| width.get() > self.k)
+                    .ok_or_else(|| invalid("ef_search", "every value must exceed k"))
+            })
+            .collect()
+    }
+
+    /// Draws distinct query indices, sorted for stable report order.
+    fn sample_queries(&self, len: usize) -> Vec<usize> {
+        let mut rng = SmallRng::seed_from_u64(self.seed);
+        let mut queries = index::sample(&mut rng, len, self.query_count.min(len)).into_vec();
+        queries.sort_unstable();
+        queries
+    }
+}
+
+/// Exact neighbours for the sampled queries, shared by every grid point.
+struct SampledBaseline {
+    k: usize,
+    queries: Vec<usize>,
+    oracles: Vec<Vec<Neighbour>>,
+}
+
+impl SampledBaseline {
+    fn new<D: DataSource>(
+        source: &D,
+        queries: Vec<usize>,
+        k: usize,
+    ) -> Result<Self, BenchSetupError> {
+        let oracles = queries
+            .iter()
+            .map(|&query| brute_force_top_k(source, query, k))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            k,
+            queries,
+            oracles,
+        })
+    }
+
+    fn score<D: DataSource + Sync>(
+        &self,
+        source: &D,
+        index: &CpuHnsw,
+        ef: NonZeroUsize,
+    ) -> Result<RecallScore, BenchSetupError> {
+        let mut total = RecallScore { hits: 0, total: 0 };
+        for (&query, oracle) in self.queries.iter().zip(&self.oracles) {
+            let mut observed = index.search(source, query, ef)?;
+            observed.retain(|neighbour| neighbour.id != query);
+            observed.truncate(self.k);
+            // Short result lists count as misses rather than shrinking the
+            // target, so a fragmented graph cannot inflate its recall.
+            let hits = recall_at_k(oracle, &observed, self.k).hits;
+            total.hits = total.hits.saturating_add(hits);
+            total.total = total.total.saturating_add(oracle.len().min(self.k));
+        }
+        Ok(total)
+    }
+}
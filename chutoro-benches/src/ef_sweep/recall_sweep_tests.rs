@@ -0,0 +1,193 @@
+//! Unit tests for the recall sweep runner and Pareto frontier.
+
+use super::*;
+use crate::{
+    error::BenchSetupError,
+    recall::RecallScore,
+    source::{Anisotropy, GaussianBlobConfig, SyntheticConfig, SyntheticSource},
+};
+use rstest::{fixture, rstest};
+
+#[fixture]
+fn uniform_source() -> SyntheticSource {
+    SyntheticSource::generate(&SyntheticConfig {
+        point_count: 300,
+        dimensions: 64,
+        seed: 11,
+    })
+    .expect("uniform generation should succeed")
+}
+
+fn small_sweep() -> RecallSweep {
+    RecallSweep::new()
+        .with_max_connections(vec![8, 16])
+        .with_ef_construction(vec![0, 12, 64])
+        .with_ef_search(vec![16, 96])
+        .with_query_count(25)
+}
+
+fn point(hits: usize, build_time_millis: u128, search_time_micros: u128) -> RecallSweepPoint {
+    RecallSweepPoint {
+        max_connections: 8,
+        ef_construction: 16,
+        ef_search: 32,
+        recall: RecallScore { hits, total: 100 },
+        build_time_millis,
+        search_time_micros,
+    }
+}
+
+fn report(points: Vec<RecallSweepPoint>) -> RecallSweepReport {
+    RecallSweepReport {
+        point_count: 1_000,
+        k: 10,
+        query_count: 10,
+        points,
+    }
+}
+
+#[rstest]
+fn sweep_scores_every_valid_grid_point(uniform_source: SyntheticSource) {
+    let report = small_sweep()
+        .run(&uniform_source)
+        .expect("sweep should succeed");
+
+    // ef_construction 12 is below M = 16, so that pair is skipped.
+    let pairs: Vec<_> = report
+        .points
+        .iter()
+        .map(|point| {
+            (
+                point.max_connections,
+                point.ef_construction,
+                point.ef_search,
+            )
+        })
+        .collect();
+    assert_eq!(
+        pairs,
+        [
+            (8, 16, 16),
+            (8, 16, 96),
+            (8, 12, 16),
+            (8, 12, 96),
+            (8, 64, 16),
+            (8, 64, 96),
+            (16, 32, 16),
+            (16, 32, 96),
+            (16, 64, 16),
+            (16, 64, 96),
+        ]
+    );
+    assert_eq!(report.query_count, 25);
+    for scored in &report.points {
+        assert_eq!(scored.recall.total, 25 * DEFAULT_RECALL_K);
+        assert!(scored.recall.hits <= scored.recall.total);
+    }
+}
+
+#[rstest]
+fn wider_search_does_not_lose_recall_on_anisotropic_blobs() {
+    let source = SyntheticSource::generate_gaussian_blobs(&GaussianBlobConfig {
+        point_count: 400,
+        dimensions: 8,
+        cluster_count: 4,
+        separation: 6.0,
+        anisotropy: Anisotropy::AxisScales(vec![4.0, 0.1, 2.0, 0.1, 1.0, 0.1, 0.5, 0.1]),
+        seed: 3,
+    })
+    .expect("blob generation should succeed");
+
+    let report = RecallSweep::new()
+        .with_max_connections(vec![16])
+        .with_ef_construction(vec![100])
+        .with_ef_search(vec![11, 200])
+        .run(&source)
+        .expect("sweep should succeed");
+
+    let [narrow, wide] = report.points.as_slice() else {
+        panic!("expected two grid points, got {:?}", report.points);
+    };
+    assert!(wide.recall.hits >= narrow.recall.hits);
+    assert!(wide.recall.hits * 10 >= wide.recall.total * 9, "{wide:?}");
+}
+
+#[rstest]
+#[case::empty_m(RecallSweep::new().with_max_connections(vec![]), "max_connections")]
+#[case::empty_ef_construction(RecallSweep::new().with_ef_construction(vec![]), "ef_construction")]
+#[case::empty_ef_search(RecallSweep::new().with_ef_search(vec![]), "ef_search")]
+#[case::ef_search_not_above_k(RecallSweep::new().with_ef_search(vec![32, 10]), "ef_search")]
+#[case::zero_k(RecallSweep::new().with_k(0), "k")]
+#[case::zero_queries(RecallSweep::new().with_query_count(0), "query_count")]
+fn sweep_rejects_invalid_configuration(
+    uniform_source: SyntheticSource,
+    #[case] sweep: RecallSweep,
+    #[case] expected: &str,
+) {
+    let error = sweep
+        .run(&uniform_source)
+        .expect_err("invalid sweep must fail");
+
+    assert!(matches!(
+        error,
+        BenchSetupError::InvalidRecallSweep { parameter, .. } if parameter == expected
+    ));
+}
+
+#[rstest]
+fn frontier_drops_dominated_points() {
+    let sweep = report(vec![
+        point(80, 10, 500),
+        point(90, 20, 900),
+        point(85, 30, 900),
+        point(95, 40, 400),
+        point(80, 10, 500),
+    ]);
+
+    let frontier: Vec<_> = sweep
+        .pareto_frontier()
+        .into_iter()
+        .map(|kept| kept.recall.hits)
+        .collect();
+
+    // 85 costs more than 90 on both axes; exact duplicates both survive.
+    assert_eq!(frontier, [80, 80, 90, 95]);
+    let pareto_column: Vec<_> = sweep
+        .to_csv()
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.rsplit(',').next().map(str::to_owned))
+        .collect();
+    assert_eq!(pareto_column, ["true", "true", "false", "true", "true"]);
+}
+
+#[rstest]
+fn pareto_table_aligns_frontier_rows() {
+    let table = report(vec![point(80, 5, 1_234), point(100, 250, 90)]).pareto_table();
+
+    let lines: Vec<_> = table.lines().collect();
+    assert_eq!(lines.len(), 6, "{table}");
+    assert!(
+        lines
+            .iter()
+            .all(|line| line.len() == table.find('\n').unwrap_or(0))
+    );
+    let row = |index: usize| lines.get(index).copied().unwrap_or_default();
+    assert!(row(1).contains("recall@10"));
+    assert!(row(3).contains("0.800000"));
+    assert!(row(4).contains("1.000000"));
+}
+
+#[rstest]
+fn write_csv_creates_parent_directories() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = dir.path().join("nested").join("sweep.csv");
+
+    let written = report(vec![point(50, 1, 1)])
+        .write_csv(&path)
+        .expect("report should be written");
+
+    let contents = std::fs::read_to_string(written).expect("report should be readable");
+    assert!(contents.starts_with("point_count,k,query_count,"));
+    assert_eq!(contents.lines().count(), 2);
+}
@@ -33,6 +33,14 @@ pub enum BenchSetupError {
     /// Memory profiling failed.
     #[error("memory profiling failed: {0}")]
     Profiling(#[from] ProfilingError),
+    /// A recall sweep was configured with an unusable grid or sample.
+    #[error("invalid recall sweep {parameter}: {reason}")]
+    InvalidRecallSweep {
+        /// The sweep setting that was rejected.
+        parameter: &'static str,
+        /// Why the setting was rejected.
+        reason: &'static str,
+    },
     /// Recall report I/O failed.
     #[error("recall report failed: {0}")]
     RecallReport(std::io::Error),
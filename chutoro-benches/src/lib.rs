@@ -2,8 +2,11 @@
 //!
 //! Provides synthetic data sources and parameter types used by Criterion
 //! benchmarks for the four CPU pipeline stages: HNSW build, edge harvest,
-//! MST computation, and hierarchy extraction.
+//! MST computation, and hierarchy extraction, and an adapter that scores the
+//! HNSW index on ann-benchmarks datasets.
 
+pub mod ann_benchmarks;
+pub mod baseline;
 pub mod clustering_quality;
 pub mod criterion_support;
 pub mod ef_sweep;
@@ -12,4 +15,6 @@ pub mod neighbour_scoring;
 pub mod params;
 pub mod profiling;
 pub mod recall;
+pub mod settings;
+pub mod soak;
 pub mod source;
@@ -91,6 +91,10 @@ impl<S: DataSource> DataSource for ProfilingSource<S> {
         self.inner.metric_descriptor()
     }
 
+    fn dimension_hint(&self) -> Option<usize> {
+        self.inner.dimension_hint()
+    }
+
     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
         let result = self.inner.distance(i, j);
         self.record_scalar();
@@ -1,9 +1,9 @@
-//! Linux process resident-set sampling utilities.
+//! Process resident-set sampling utilities.
 //!
-//! These helpers track peak resident set size (RSS) while an operation runs.
+//! These helpers track peak resident set size (RSS) while an operation runs,
+//! polling the platform reader in `resident_set` on Linux, macOS, and Windows.
 
 use std::{
-    fs,
     sync::{
         Arc, Mutex,
         atomic::{AtomicBool, AtomicU64, Ordering},
@@ -13,6 +13,8 @@ use std::{
 };
 
 use super::ProfilingError;
+#[cfg(any(target_os = "linux", target_os = "macos", windows))]
+use super::resident_set::read_resident_set_bytes;
 
 /// Memory sampling output captured while running a benchmark operation.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -27,8 +29,10 @@ pub struct PeakRssMeasurement {
 ///
 /// # Errors
 ///
-/// Returns [`ProfilingError`] when sampling cannot be started, `/proc` values
-/// cannot be parsed, or sampler thread coordination fails.
+/// Returns [`ProfilingError`] when sampling cannot be started, the platform
+/// memory query fails, or sampler thread coordination fails. Targets other
+/// than Linux, macOS, and Windows report
+/// [`ProfilingError::UnsupportedPlatform`].
 pub fn measure_peak_resident_set_size<T>(
     sample_interval: Duration,
     operation: impl FnOnce() -> T,
@@ -36,11 +40,11 @@ pub fn measure_peak_resident_set_size<T>(
     if sample_interval.is_zero() {
         return Err(ProfilingError::ZeroSamplingInterval);
     }
-    #[cfg(target_os = "linux")]
+    #[cfg(any(target_os = "linux", target_os = "macos", windows))]
     {
-        measure_peak_resident_set_size_linux(sample_interval, operation)
+        sample_peak_resident_set_size(sample_interval, operation)
     }
-    #[cfg(not(target_os = "linux"))]
+    #[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
     {
         let _ = operation;
         Err(ProfilingError::UnsupportedPlatform {
@@ -49,13 +53,13 @@ pub fn measure_peak_resident_set_size<T>(
     }
 }
 
-#[cfg(target_os = "linux")]
-fn measure_peak_resident_set_size_linux<T>(
+#[cfg(any(target_os = "linux", target_os = "macos", windows))]
+fn sample_peak_resident_set_size<T>(
     sample_interval: Duration,
     operation: impl FnOnce() -> T,
 ) -> Result<(T, PeakRssMeasurement), ProfilingError> {
     let running = Arc::new(AtomicBool::new(true));
-    let starting_rss_bytes = read_vm_rss_bytes()?;
+    let starting_rss_bytes = read_resident_set_bytes()?;
     let peak_bytes = Arc::new(AtomicU64::new(starting_rss_bytes));
     let background_error = Arc::new(Mutex::new(None::<ProfilingError>));
 
@@ -64,7 +68,7 @@ fn measure_peak_resident_set_size_linux<T>(
     let error_handle = Arc::clone(&background_error);
     let sampler = thread::spawn(move || {
         while running_handle.load(Ordering::Relaxed) {
-            match read_vm_rss_bytes() {
+            match read_resident_set_bytes() {
                 Ok(bytes) => {
                     peak_handle.fetch_max(bytes, Ordering::Relaxed);
                 }
@@ -86,7 +90,7 @@ fn measure_peak_resident_set_size_linux<T>(
         .join()
         .map_err(|_| ProfilingError::SamplerThreadPanicked)?;
 
-    peak_bytes.fetch_max(read_vm_rss_bytes()?, Ordering::Relaxed);
+    peak_bytes.fetch_max(read_resident_set_bytes()?, Ordering::Relaxed);
     let maybe_background_error = background_error
         .lock()
         .map_err(|_| ProfilingError::SamplerLockPoisoned)?
@@ -106,12 +110,12 @@ fn measure_peak_resident_set_size_linux<T>(
     ))
 }
 
-#[cfg(target_os = "linux")]
+#[cfg(any(target_os = "linux", target_os = "macos", windows))]
 const fn compute_peak_rss_delta_bytes(starting_rss_bytes: u64, peak_rss_bytes: u64) -> u64 {
     peak_rss_bytes.saturating_sub(starting_rss_bytes)
 }
 
-#[cfg(target_os = "linux")]
+#[cfg(any(target_os = "linux", target_os = "macos", windows))]
 fn store_background_error(error_slot: &Mutex<Option<ProfilingError>>, error: ProfilingError) {
     if let Ok(mut guard) = error_slot.lock()
         && guard.is_none()
@@ -120,49 +124,6 @@ fn store_background_error(error_slot: &Mutex<Option<ProfilingError>>, error: Pro
     }
 }
 
-#[cfg(target_os = "linux")]
-fn read_vm_rss_bytes() -> Result<u64, ProfilingError> {
-    let status = fs::read_to_string("/proc/self/status")?;
-    parse_vm_rss_bytes(&status)
-}
-
-#[cfg(target_os = "linux")]
-fn parse_vm_rss_bytes(status: &str) -> Result<u64, ProfilingError> {
-    let field = "VmRSS";
-    let line = status
-        .lines()
-        .find(|candidate| candidate.starts_with("VmRSS:"))
-        .ok_or(ProfilingError::MissingProcField { field })?;
-    parse_kibibyte_proc_field(line, field)
-}
-
-#[cfg(target_os = "linux")]
-fn parse_kibibyte_proc_field(line: &str, field: &'static str) -> Result<u64, ProfilingError> {
-    let mut parts = line.split_whitespace();
-    let _label = parts
-        .next()
-        .ok_or(ProfilingError::MissingProcField { field })?;
-    let value_raw = parts
-        .next()
-        .ok_or(ProfilingError::MissingProcField { field })?;
-    let unit = parts.next().unwrap_or("kB");
-    if unit != "kB" {
-        return Err(ProfilingError::UnsupportedProcUnit {
-            field,
-            unit: unit.to_owned(),
-        });
-    }
-    let kibibytes = value_raw
-        .parse::<u64>()
-        .map_err(|_| ProfilingError::InvalidProcField {
-            field,
-            value: value_raw.to_owned(),
-        })?;
-    kibibytes.checked_mul(1024).ok_or(ProfilingError::Overflow {
-        context: "vm_rss_kibibytes_to_bytes",
-    })
-}
-
 #[cfg(test)]
 mod tests {
     //! Unit tests for the memory sampler.
@@ -170,41 +131,6 @@ mod tests {
     use super::*;
     use rstest::rstest;
 
-    #[cfg(target_os = "linux")]
-    #[rstest]
-    #[case("VmRSS:\t1234 kB", 1_263_616)]
-    #[case("VmRSS: 42 kB", 43_008)]
-    fn parse_vm_rss_bytes_accepts_valid_lines(#[case] vmrss_line: &str, #[case] expected: u64) {
-        let status = format!("{vmrss_line}\nName:\tchutoro\n");
-        assert_eq!(
-            parse_vm_rss_bytes(&status).expect("valid VmRSS field must parse"),
-            expected
-        );
-    }
-
-    #[cfg(target_os = "linux")]
-    #[rstest]
-    #[case::missing_field("Name:\tchutoro\n", "VmRSS")]
-    #[case::invalid_numeric("VmRSS:\tnot-a-number kB\n", "VmRSS")]
-    fn parse_vm_rss_bytes_rejects_invalid_input(#[case] status: &str, #[case] field: &'static str) {
-        let err = parse_vm_rss_bytes(status).expect_err("invalid status must fail");
-        assert!(
-            matches!(err, ProfilingError::MissingProcField { field: actual } if actual == field)
-                || matches!(err, ProfilingError::InvalidProcField { field: actual, .. } if actual == field),
-        );
-    }
-
-    #[cfg(target_os = "linux")]
-    #[rstest]
-    fn parse_vm_rss_bytes_rejects_unexpected_unit() {
-        let status = "VmRSS:\t200 MB\n";
-        let err = parse_vm_rss_bytes(status).expect_err("unexpected unit must fail");
-        assert!(matches!(
-            err,
-            ProfilingError::UnsupportedProcUnit { field: "VmRSS", .. }
-        ));
-    }
-
     #[rstest]
     fn measure_peak_resident_set_size_rejects_zero_interval() {
         let err = measure_peak_resident_set_size(Duration::from_secs(0), || 1_u8)
@@ -212,7 +138,16 @@ mod tests {
         assert!(matches!(err, ProfilingError::ZeroSamplingInterval));
     }
 
-    #[cfg(target_os = "linux")]
+    #[cfg(any(target_os = "linux", target_os = "macos", windows))]
+    #[rstest]
+    fn measure_peak_resident_set_size_samples_supported_platforms() {
+        let (value, _measurement) =
+            measure_peak_resident_set_size(Duration::from_millis(1), || vec![0_u8; 1 << 20])
+                .expect("supported platforms must sample resident-set size");
+        assert_eq!(value.len(), 1 << 20);
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos", windows))]
     #[rstest]
     #[case::grows(1_024, 4_096, 3_072)]
     #[case::unchanged(4_096, 4_096, 0)]
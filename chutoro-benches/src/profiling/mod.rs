@@ -1,9 +1,11 @@
 //! Memory profiling support for HNSW benchmarks.
 //!
-//! Provides a Linux resident-set sampler plus report helpers that compute
-//! memory-per-point and memory-per-edge metrics for benchmark runs.
+//! Provides a resident-set sampler for Linux, macOS, and Windows plus report
+//! helpers that compute memory-per-point and memory-per-edge metrics for
+//! benchmark runs.
 
 mod memory_sampler;
+mod resident_set;
 
 use std::{
     fs,
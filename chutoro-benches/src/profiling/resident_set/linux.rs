@@ -0,0 +1,86 @@
+//! Linux resident-set reader backed by `/proc/self/status`.
+
+use std::fs;
+
+use crate::profiling::ProfilingError;
+
+/// Returns the current `VmRSS` of this process in bytes.
+pub(crate) fn read_resident_set_bytes() -> Result<u64, ProfilingError> {
+    let status = fs::read_to_string("/proc/self/status")?;
+    parse_vm_rss_bytes(&status)
+}
+
+fn parse_vm_rss_bytes(status: &str) -> Result<u64, ProfilingError> {
+    let field = "VmRSS";
+    let line = status
+        .lines()
+        .find(|candidate| candidate.starts_with("VmRSS:"))
+        .ok_or(ProfilingError::MissingProcField { field })?;
+    parse_kibibyte_proc_field(line, field)
+}
+
+fn parse_kibibyte_proc_field(line: &str, field: &'static str) -> Result<u64, ProfilingError> {
+    let mut parts = line.split_whitespace();
+    let _label = parts
+        .next()
+        .ok_or(ProfilingError::MissingProcField { field })?;
+    let value_raw = parts
+        .next()
+        .ok_or(ProfilingError::MissingProcField { field })?;
+    let unit = parts.next().unwrap_or("kB");
+    if unit != "kB" {
+        return Err(ProfilingError::UnsupportedProcUnit {
+            field,
+            unit: unit.to_owned(),
+        });
+    }
+    let kibibytes = value_raw
+        .parse::<u64>()
+        .map_err(|_| ProfilingError::InvalidProcField {
+            field,
+            value: value_raw.to_owned(),
+        })?;
+    kibibytes.checked_mul(1024).ok_or(ProfilingError::Overflow {
+        context: "vm_rss_kibibytes_to_bytes",
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for the `/proc/self/status` parser.
+
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("VmRSS:\t1234 kB", 1_263_616)]
+    #[case("VmRSS: 42 kB", 43_008)]
+    fn parse_vm_rss_bytes_accepts_valid_lines(#[case] vmrss_line: &str, #[case] expected: u64) {
+        let status = format!("{vmrss_line}\nName:\tchutoro\n");
+        assert_eq!(
+            parse_vm_rss_bytes(&status).expect("valid VmRSS field must parse"),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case::missing_field("Name:\tchutoro\n", "VmRSS")]
+    #[case::invalid_numeric("VmRSS:\tnot-a-number kB\n", "VmRSS")]
+    fn parse_vm_rss_bytes_rejects_invalid_input(#[case] status: &str, #[case] field: &'static str) {
+        let err = parse_vm_rss_bytes(status).expect_err("invalid status must fail");
+        assert!(
+            matches!(err, ProfilingError::MissingProcField { field: actual } if actual == field)
+                || matches!(err, ProfilingError::InvalidProcField { field: actual, .. } if actual == field),
+        );
+    }
+
+    #[rstest]
+    fn parse_vm_rss_bytes_rejects_unexpected_unit() {
+        let status = "VmRSS:\t200 MB\n";
+        let err = parse_vm_rss_bytes(status).expect_err("unexpected unit must fail");
+        assert!(matches!(
+            err,
+            ProfilingError::UnsupportedProcUnit { field: "VmRSS", .. }
+        ));
+    }
+}
@@ -0,0 +1,26 @@
+//! macOS resident-set reader backed by `proc_pid_rusage`.
+
+use std::{io, mem::MaybeUninit};
+
+use crate::profiling::ProfilingError;
+
+/// Returns the current resident size of this process in bytes.
+pub(crate) fn read_resident_set_bytes() -> Result<u64, ProfilingError> {
+    let mut info = MaybeUninit::<libc::rusage_info_v2>::uninit();
+    // SAFETY: `proc_pid_rusage` writes a complete `rusage_info_v2` for the
+    // `RUSAGE_INFO_V2` flavour into the buffer, which is sized and aligned
+    // for that struct; `getpid` cannot fail.
+    let status = unsafe {
+        libc::proc_pid_rusage(
+            libc::getpid(),
+            libc::RUSAGE_INFO_V2,
+            info.as_mut_ptr().cast::<libc::rusage_info_t>(),
+        )
+    };
+    if status != 0 {
+        return Err(ProfilingError::Io(io::Error::last_os_error()));
+    }
+    // SAFETY: a zero status means the kernel initialised the buffer.
+    let info = unsafe { info.assume_init() };
+    Ok(info.ri_resident_size)
+}
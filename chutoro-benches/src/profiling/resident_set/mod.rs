@@ -0,0 +1,25 @@
+//! Per-platform readers for the current process's resident-set size.
+//!
+//! Each backend returns the resident (working-set) bytes of the calling
+//! process at the moment of the call; the sampler in `memory_sampler`
+//! polls whichever backend the target provides.
+//!
+//! | Target  | Source                                           |
+//! | ------- | ------------------------------------------------ |
+//! | Linux   | `VmRSS` in `/proc/self/status`                   |
+//! | macOS   | `ri_resident_size` from `proc_pid_rusage`        |
+//! | Windows | `WorkingSetSize` from `GetProcessMemoryInfo`     |
+
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(windows)]
+mod windows;
+
+#[cfg(target_os = "linux")]
+pub(crate) use linux::read_resident_set_bytes;
+#[cfg(target_os = "macos")]
+pub(crate) use macos::read_resident_set_bytes;
+#[cfg(windows)]
+pub(crate) use windows::read_resident_set_bytes;
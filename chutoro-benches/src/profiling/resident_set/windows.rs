@@ -0,0 +1,32 @@
+//! Windows resident-set reader backed by `GetProcessMemoryInfo`.
+
+use std::{io, mem};
+
+use windows_sys::Win32::System::{
+    ProcessStatus::{GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS},
+    Threading::GetCurrentProcess,
+};
+
+use crate::profiling::ProfilingError;
+
+/// Returns the current working-set size of this process in bytes.
+pub(crate) fn read_resident_set_bytes() -> Result<u64, ProfilingError> {
+    let size = u32::try_from(mem::size_of::<PROCESS_MEMORY_COUNTERS>()).map_err(|_| {
+        ProfilingError::Overflow {
+            context: "process_memory_counters_size",
+        }
+    })?;
+    // SAFETY: `PROCESS_MEMORY_COUNTERS` is plain data, so the all-zero value
+    // is valid.
+    let mut counters: PROCESS_MEMORY_COUNTERS = unsafe { mem::zeroed() };
+    counters.cb = size;
+    // SAFETY: `GetCurrentProcess` returns a pseudo-handle that needs no
+    // closing, and `counters` is a writable buffer of the advertised size.
+    let succeeded = unsafe { GetProcessMemoryInfo(GetCurrentProcess(), &raw mut counters, size) };
+    if succeeded == 0 {
+        return Err(ProfilingError::Io(io::Error::last_os_error()));
+    }
+    u64::try_from(counters.WorkingSetSize).map_err(|_| ProfilingError::Overflow {
+        context: "working_set_size_to_bytes",
+    })
+}
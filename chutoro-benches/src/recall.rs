@@ -155,7 +155,7 @@ impl RecallMeasurement {
     clippy::cast_precision_loss,
     reason = "Recall fraction is inherently a float ratio; only used for human-readable CSV output."
 )]
-fn recall_fraction(score: RecallScore) -> String {
+pub(crate) fn recall_fraction(score: RecallScore) -> String {
     if score.total == 0 {
         return "0.000000".to_owned();
     }
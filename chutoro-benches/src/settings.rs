@@ -0,0 +1,113 @@
+//! Environment-variable settings shared by the benchmark binaries.
+//!
+//! The soak, baseline, and ann-benchmarks configurations each read a family
+//! of prefixed variables through a lookup function, so tests can supply
+//! settings without touching the process environment. `SettingReader`
+//! parses those variables and reports unusable values as [`InvalidSetting`].
+
+use std::{num::NonZeroUsize, str::FromStr, time::Duration};
+
+use thiserror::Error;
+
+/// A configuration variable held a value that could not be used.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[error("invalid value `{value}` for {name}: expected {expected}")]
+pub struct InvalidSetting {
+    /// Name of the environment variable.
+    pub name: String,
+    /// The rejected value.
+    pub value: String,
+    /// Description of the accepted values.
+    pub expected: &'static str,
+}
+
+/// Reads the variables named `prefix` followed by a suffix from `lookup`.
+pub(crate) struct SettingReader<F> {
+    prefix: &'static str,
+    lookup: F,
+}
+
+impl<F: Fn(&str) -> Option<String>> SettingReader<F> {
+    pub(crate) const fn new(prefix: &'static str, lookup: F) -> Self {
+        Self { prefix, lookup }
+    }
+
+    /// Returns the full variable name for `suffix`.
+    pub(crate) fn name(&self, suffix: &str) -> String {
+        format!("{}{suffix}", self.prefix)
+    }
+
+    /// Returns the raw value of the variable, if set.
+    pub(crate) fn value(&self, suffix: &str) -> Option<String> {
+        (self.lookup)(&self.name(suffix))
+    }
+
+    /// Parses the variable when it is set.
+    pub(crate) fn optional<T: FromStr>(
+        &self,
+        suffix: &str,
+        expected: &'static str,
+    ) -> Result<Option<T>, InvalidSetting> {
+        let name = self.name(suffix);
+        let Some(value) = (self.lookup)(&name) else {
+            return Ok(None);
+        };
+        value.trim().parse().map(Some).map_err(|_| InvalidSetting {
+            name,
+            value,
+            expected,
+        })
+    }
+
+    /// Parses the variable, or returns `default` when it is unset.
+    pub(crate) fn parsed<T: FromStr>(
+        &self,
+        suffix: &str,
+        default: T,
+        expected: &'static str,
+    ) -> Result<T, InvalidSetting> {
+        Ok(self.optional(suffix, expected)?.unwrap_or(default))
+    }
+
+    pub(crate) fn non_zero(
+        &self,
+        suffix: &str,
+        default: NonZeroUsize,
+    ) -> Result<NonZeroUsize, InvalidSetting> {
+        self.parsed(suffix, default, "a positive integer")
+    }
+
+    pub(crate) fn seconds(
+        &self,
+        suffix: &str,
+        default: Duration,
+    ) -> Result<Duration, InvalidSetting> {
+        self.parsed(suffix, default.as_secs(), "a whole number of seconds")
+            .map(Duration::from_secs)
+    }
+
+    /// Parses a comma-separated list of positive integers, or returns
+    /// `default` when the variable is unset.
+    pub(crate) fn list(
+        &self,
+        suffix: &str,
+        default: Vec<NonZeroUsize>,
+    ) -> Result<Vec<NonZeroUsize>, InvalidSetting> {
+        let name = self.name(suffix);
+        let Some(value) = (self.lookup)(&name) else {
+            return Ok(default);
+        };
+        let parsed: Option<Vec<NonZeroUsize>> = value
+            .split(',')
+            .map(|item| item.trim().parse().ok())
+            .collect();
+        match parsed {
+            Some(values) if !values.is_empty() => Ok(values),
+            _ => Err(InvalidSetting {
+                name,
+                value,
+                expected: "a comma-separated list of positive integers",
+            }),
+        }
+    }
+}
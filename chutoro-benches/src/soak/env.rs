@@ -0,0 +1,75 @@
+//! Environment-variable configuration for the `chutoro-soak` binary.
+//!
+//! Every [`SoakConfig`] field can be overridden by a `CHUTORO_SOAK_*`
+//! variable; unset variables keep the [`SoakConfig::default`] value.
+
+use super::{SoakConfig, SoakError};
+use crate::settings::SettingReader;
+
+/// Prefix shared by every soak configuration variable.
+pub const SOAK_ENV_PREFIX: &str = "CHUTORO_SOAK_";
+
+/// Reads the soak configuration from the process environment.
+///
+/// # Errors
+/// Returns [`SoakError::InvalidSetting`] when a variable is set to a value
+/// that does not parse.
+///
+/// # Examples
+/// ```
+/// use chutoro_benches::soak::config_from_env;
+///
+/// let _config = config_from_env();
+/// ```
+pub fn config_from_env() -> Result<SoakConfig, SoakError> {
+    config_from_lookup(|name| std::env::var(name).ok())
+}
+
+/// Builds a soak configuration from `lookup`, which maps a variable name to
+/// its value.
+///
+/// | Variable                            | Field             |
+/// | ----------------------------------- | ----------------- |
+/// | `CHUTORO_SOAK_DURATION_SECS`        | `duration`        |
+/// | `CHUTORO_SOAK_INITIAL_POINTS`       | `initial_points`  |
+/// | `CHUTORO_SOAK_MAX_POINTS`           | `max_points`      |
+/// | `CHUTORO_SOAK_DIMENSIONS`           | `dimensions`      |
+/// | `CHUTORO_SOAK_WORKERS`              | `workers`         |
+/// | `CHUTORO_SOAK_MAX_CONNECTIONS`      | `max_connections` |
+/// | `CHUTORO_SOAK_EF_CONSTRUCTION`      | `ef_construction` |
+/// | `CHUTORO_SOAK_SEED`                 | `seed`            |
+/// | `CHUTORO_SOAK_STALL_TIMEOUT_SECS`   | `stall_timeout`   |
+///
+/// # Errors
+/// Returns [`SoakError::InvalidSetting`] when a variable is set to a value
+/// that does not parse.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+///
+/// use chutoro_benches::soak::config_from_lookup;
+///
+/// let config = config_from_lookup(|name| {
+///     (name == "CHUTORO_SOAK_DURATION_SECS").then(|| "5".to_owned())
+/// })
+/// .expect("settings must parse");
+/// assert_eq!(config.duration, Duration::from_secs(5));
+/// ```
+pub fn config_from_lookup(
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Result<SoakConfig, SoakError> {
+    let read = SettingReader::new(SOAK_ENV_PREFIX, lookup);
+    let defaults = SoakConfig::default();
+    Ok(SoakConfig {
+        duration: read.seconds("DURATION_SECS", defaults.duration)?,
+        initial_points: read.non_zero("INITIAL_POINTS", defaults.initial_points)?,
+        max_points: read.non_zero("MAX_POINTS", defaults.max_points)?,
+        dimensions: read.non_zero("DIMENSIONS", defaults.dimensions)?,
+        workers: read.non_zero("WORKERS", defaults.workers)?,
+        max_connections: read.parsed("MAX_CONNECTIONS", defaults.max_connections, "an integer")?,
+        ef_construction: read.parsed("EF_CONSTRUCTION", defaults.ef_construction, "an integer")?,
+        seed: read.parsed("SEED", defaults.seed, "an unsigned 64-bit integer")?,
+        stall_timeout: read.seconds("STALL_TIMEOUT_SECS", defaults.stall_timeout)?,
+    })
+}
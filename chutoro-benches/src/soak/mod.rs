@@ -0,0 +1,289 @@
+//! Long-running concurrency soak for the CPU HNSW index.
+//!
+//! Each round builds a fresh index over a synthetic dataset and has a set of
+//! worker threads insert disjoint stripes of points concurrently, searching
+//! for every point straight after inserting it, while a checker thread runs
+//! the scoped invariant checks over the neighbourhood of each inserted point.
+//! Once the workers finish, the full invariants are checked once on the
+//! settled graph. Datasets double each
+//! round up to a ceiling and then restart, so lock contention is exercised
+//! at several graph sizes for as long as the soak runs.
+//!
+//! Rounds run on a supervisor thread with a stall deadline, so a deadlock
+//! caused by a lock-ordering regression surfaces as [`SoakError::Stalled`]
+//! instead of hanging the process. Plain `std` threads are used throughout
+//! so the soak runs cleanly under `ThreadSanitizer`.
+
+mod env;
+mod stats;
+
+use std::{
+    num::NonZeroUsize,
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use chutoro_core::{CpuHnsw, DataSource, HnswError, HnswParams};
+use thiserror::Error;
+
+use crate::{
+    settings::InvalidSetting,
+    source::{SyntheticConfig, SyntheticError, SyntheticSource},
+};
+
+pub use env::{SOAK_ENV_PREFIX, config_from_env, config_from_lookup};
+pub use stats::{SoakStats, ViolationCounts};
+
+/// Search width used for the searches interleaved with inserts.
+const SEARCH_EF: NonZeroUsize = non_zero(16);
+
+/// Unwraps a non-zero literal; only evaluated in constant contexts.
+const fn non_zero(value: usize) -> NonZeroUsize {
+    match NonZeroUsize::new(value) {
+        Some(non_zero) => non_zero,
+        None => panic!("soak constants must be non-zero"),
+    }
+}
+
+/// Configuration for [`run_soak`].
+#[derive(Clone, Debug)]
+pub struct SoakConfig {
+    /// How long to keep starting rounds. At least one round always runs.
+    pub duration: Duration,
+    /// Points in the first round's dataset.
+    pub initial_points: NonZeroUsize,
+    /// Largest dataset before the size restarts at `initial_points`.
+    pub max_points: NonZeroUsize,
+    /// Dimensionality of the synthetic vectors.
+    pub dimensions: NonZeroUsize,
+    /// Number of concurrent insert workers.
+    pub workers: NonZeroUsize,
+    /// HNSW maximum connections per node (M).
+    pub max_connections: usize,
+    /// HNSW search width during construction.
+    pub ef_construction: usize,
+    /// Seed from which each round's data and index seeds are derived.
+    pub seed: u64,
+    /// How long a single round may run before it is reported as stalled.
+    pub stall_timeout: Duration,
+}
+
+impl Default for SoakConfig {
+    fn default() -> Self {
+        Self {
+            duration: Duration::from_mins(1),
+            initial_points: non_zero(64),
+            max_points: non_zero(4_096),
+            dimensions: non_zero(8),
+            workers: non_zero(4),
+            max_connections: 8,
+            ef_construction: 32,
+            seed: 0x5EED,
+            stall_timeout: Duration::from_mins(2),
+        }
+    }
+}
+
+/// Errors that abort a soak run.
+#[derive(Debug, Error)]
+pub enum SoakError {
+    /// A configuration variable held a value that could not be used.
+    #[error(transparent)]
+    InvalidSetting(#[from] InvalidSetting),
+    /// Synthetic data generation failed.
+    #[error("synthetic source generation failed: {0}")]
+    Synthetic(#[from] SyntheticError),
+    /// An insert or search returned an error.
+    #[error("HNSW operation failed: {0}")]
+    Hnsw(#[from] HnswError),
+    /// A round exceeded the stall timeout, which usually means a deadlock.
+    #[error("round {round} over {points} points made no progress within {timeout:?}")]
+    Stalled {
+        /// Zero-based index of the stalled round.
+        round: u64,
+        /// Dataset size of the stalled round.
+        points: usize,
+        /// The configured stall timeout.
+        timeout: Duration,
+    },
+    /// A worker or checker thread panicked.
+    #[error("a soak thread panicked during round {round}")]
+    Panicked {
+        /// Zero-based index of the round whose thread panicked.
+        round: u64,
+    },
+}
+
+/// Runs rounds until `config.duration` has elapsed and returns the totals.
+///
+/// # Errors
+/// Returns [`SoakError`] when data generation, an index operation, or a
+/// thread fails, or when a round stalls.
+///
+/// # Examples
+/// ```
+/// use std::{num::NonZeroUsize, time::Duration};
+///
+/// use chutoro_benches::soak::{SoakConfig, run_soak};
+///
+/// let config = SoakConfig {
+///     duration: Duration::ZERO,
+///     initial_points: NonZeroUsize::new(32).expect("non-zero"),
+///     ..SoakConfig::default()
+/// };
+/// let stats = run_soak(&config).expect("soak must run");
+/// assert_eq!(stats.rounds, 1);
+/// assert!(stats.passed());
+/// ```
+pub fn run_soak(config: &SoakConfig) -> Result<SoakStats, SoakError> {
+    let deadline = Instant::now() + config.duration;
+    let mut totals = SoakStats::default();
+    let mut points = config.initial_points.get();
+    loop {
+        let round = run_supervised(config, totals.rounds, points)?;
+        totals.absorb(&round);
+        if Instant::now() >= deadline {
+            return Ok(totals);
+        }
+        points = next_size(points, config);
+    }
+}
+
+const fn next_size(points: usize, config: &SoakConfig) -> usize {
+    let doubled = points.saturating_mul(2);
+    if doubled > config.max_points.get() {
+        config.initial_points.get()
+    } else {
+        doubled
+    }
+}
+
+/// Runs one round on a supervisor thread so a deadlock cannot hang the caller.
+///
+/// A stalled round's threads are left blocked; the caller is expected to
+/// report the stall and exit.
+fn run_supervised(config: &SoakConfig, round: u64, points: usize) -> Result<SoakStats, SoakError> {
+    let (sender, receiver) = mpsc::channel();
+    let round_config = config.clone();
+    thread::spawn(move || {
+        // The receiver is gone only when the supervisor gave up on a stall.
+        sender.send(run_round(&round_config, round, points)).ok();
+    });
+    match receiver.recv_timeout(config.stall_timeout) {
+        Ok(result) => result,
+        Err(mpsc::RecvTimeoutError::Timeout) => Err(SoakError::Stalled {
+            round,
+            points,
+            timeout: config.stall_timeout,
+        }),
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err(SoakError::Panicked { round }),
+    }
+}
+
+fn run_round(config: &SoakConfig, round: u64, points: usize) -> Result<SoakStats, SoakError> {
+    let seed = config.seed.wrapping_add(round);
+    let source = SyntheticSource::generate(&SyntheticConfig {
+        point_count: points,
+        dimensions: config.dimensions.get(),
+        seed,
+    })?;
+    let params =
+        HnswParams::new(config.max_connections, config.ef_construction)?.with_rng_seed(seed);
+    let index = CpuHnsw::with_capacity(params, points)?;
+    index.insert(0, &source)?;
+
+    let (inserted, in_flight, checks) = run_workers(&index, &source, config.workers)
+        .map_err(|panicked| panicked.unwrap_or(SoakError::Panicked { round }))?;
+    let mut settled = ViolationCounts::default();
+    for violation in index.invariants().collect_all() {
+        settled.record(&violation);
+    }
+    Ok(SoakStats {
+        rounds: 1,
+        inserts: inserted.saturating_add(1),
+        searches: inserted,
+        checks: checks.saturating_add(1),
+        max_points: points,
+        in_flight,
+        settled,
+    })
+}
+
+/// Inserts every point after the first across `workers` threads while a
+/// checker thread validates the neighbourhood of each inserted point,
+/// returning the insert count, the in-flight violations, and the number of
+/// concurrent checks.
+///
+/// Fails with `None` when a thread panicked.
+fn run_workers(
+    index: &CpuHnsw,
+    source: &SyntheticSource,
+    workers: NonZeroUsize,
+) -> Result<(u64, ViolationCounts, u64), Option<SoakError>> {
+    let points = source.len();
+    let step = workers.get();
+    let (report, inserted_nodes) = mpsc::channel();
+    thread::scope(|scope| {
+        let checker = scope.spawn(move || check_touched_until_done(index, &inserted_nodes));
+        let inserters: Vec<_> = (0..step)
+            .map(|worker| {
+                let stripe = (worker.saturating_add(1)..points).step_by(step);
+                let touched = report.clone();
+                scope.spawn(move || insert_stripe(index, source, stripe, &touched))
+            })
+            .collect();
+        // The checker stops once every inserter has dropped its sender.
+        drop(report);
+        let inserted: Result<u64, Option<SoakError>> =
+            inserters.into_iter().try_fold(0_u64, |sum, inserter| {
+                let count = inserter.join().map_err(|_| None)?.map_err(Some)?;
+                Ok(sum.saturating_add(count))
+            });
+        let (in_flight, checks) = checker.join().map_err(|_| None)?;
+        Ok((inserted?, in_flight, checks))
+    })
+}
+
+/// Inserts each point of `stripe`, searching for it as soon as it lands and
+/// passing it to the checker.
+fn insert_stripe(
+    index: &CpuHnsw,
+    source: &SyntheticSource,
+    stripe: impl Iterator<Item = usize>,
+    touched: &mpsc::Sender<usize>,
+) -> Result<u64, SoakError> {
+    let mut inserted = 0_u64;
+    for node in stripe {
+        index.insert(node, source)?;
+        index.search(source, node, SEARCH_EF)?;
+        // The receiver is gone only when the checker panicked, which the
+        // round reports when it joins the checker.
+        touched.send(node).ok();
+        inserted = inserted.saturating_add(1);
+    }
+    Ok(inserted)
+}
+
+/// Runs the scoped invariant checks over each batch of inserted points until
+/// every inserter has finished, returning the violations and the number of
+/// passes.
+///
+/// Each pass covers every point reported since the previous one, so the
+/// cost follows the inserts' neighbourhoods rather than the graph size.
+fn check_touched_until_done(
+    index: &CpuHnsw,
+    inserted: &mpsc::Receiver<usize>,
+) -> (ViolationCounts, u64) {
+    let mut counts = ViolationCounts::default();
+    let mut checks = 0_u64;
+    while let Ok(first) = inserted.recv() {
+        let mut batch = vec![first];
+        batch.extend(inserted.try_iter());
+        for violation in index.invariants().collect_touched(&batch) {
+            counts.record(&violation);
+        }
+        checks = checks.saturating_add(1);
+    }
+    (counts, checks)
+}
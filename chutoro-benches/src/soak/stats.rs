@@ -0,0 +1,132 @@
+//! Counters accumulated by a soak run.
+
+use std::fmt;
+
+use chutoro_core::HnswInvariantViolation;
+
+/// Invariant violations tallied by the invariant they breach.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ViolationCounts {
+    /// Neighbours referenced at a layer they do not expose.
+    pub layer_consistency: u64,
+    /// Adjacency lists longer than the configured degree bound.
+    pub degree_bounds: u64,
+    /// Nodes unreachable from the entry point, or a missing entry point.
+    pub reachability: u64,
+    /// Edges lacking their reverse link.
+    pub bidirectional_links: u64,
+    /// Checks that could not run because the configuration was invalid.
+    pub config: u64,
+}
+
+impl ViolationCounts {
+    /// Counts one violation against the invariant it breaches.
+    pub const fn record(&mut self, violation: &HnswInvariantViolation) {
+        let counter = match violation {
+            HnswInvariantViolation::LayerConsistency { .. } => &mut self.layer_consistency,
+            HnswInvariantViolation::DegreeBounds { .. } => &mut self.degree_bounds,
+            HnswInvariantViolation::MissingEntryPoint
+            | HnswInvariantViolation::UnreachableNode { .. } => &mut self.reachability,
+            HnswInvariantViolation::MissingBacklink { .. } => &mut self.bidirectional_links,
+            HnswInvariantViolation::ConfigError { .. } => &mut self.config,
+        };
+        *counter = counter.saturating_add(1);
+    }
+
+    /// Returns the number of violations across every invariant.
+    #[must_use]
+    pub const fn total(&self) -> u64 {
+        self.layer_consistency
+            .saturating_add(self.degree_bounds)
+            .saturating_add(self.reachability)
+            .saturating_add(self.bidirectional_links)
+            .saturating_add(self.config)
+    }
+}
+
+impl fmt::Display for ViolationCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "layer_consistency={} degree_bounds={} reachability={} \
+             bidirectional_links={} config={}",
+            self.layer_consistency,
+            self.degree_bounds,
+            self.reachability,
+            self.bidirectional_links,
+            self.config,
+        )
+    }
+}
+
+/// Totals reported at the end of a soak run.
+///
+/// `in_flight` violations are observed by the checker thread while inserts
+/// are still running. It reads the graph between an insertion's link and
+/// trim phases, so transient degree overflows can legitimately appear there.
+/// `settled` violations are observed once every insert of a round has
+/// returned and indicate a genuine defect.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SoakStats {
+    /// Rounds completed, each on a freshly built index.
+    pub rounds: u64,
+    /// Points inserted across all rounds.
+    pub inserts: u64,
+    /// Searches issued between inserts.
+    pub searches: u64,
+    /// Invariant passes run: scoped passes over inserted points while inserts
+    /// are in flight, and one full pass per settled round.
+    pub checks: u64,
+    /// Largest dataset a round indexed.
+    pub max_points: usize,
+    /// Violations observed while inserts were in flight.
+    pub in_flight: ViolationCounts,
+    /// Violations observed after each round's inserts completed.
+    pub settled: ViolationCounts,
+}
+
+impl SoakStats {
+    /// Returns whether the run surfaced no settled violations.
+    #[must_use]
+    pub const fn passed(&self) -> bool {
+        self.settled.total() == 0
+    }
+
+    pub(super) const fn absorb(&mut self, round: &Self) {
+        self.rounds = self.rounds.saturating_add(round.rounds);
+        self.inserts = self.inserts.saturating_add(round.inserts);
+        self.searches = self.searches.saturating_add(round.searches);
+        self.checks = self.checks.saturating_add(round.checks);
+        if round.max_points > self.max_points {
+            self.max_points = round.max_points;
+        }
+        self.in_flight = add_counts(self.in_flight, round.in_flight);
+        self.settled = add_counts(self.settled, round.settled);
+    }
+}
+
+const fn add_counts(left: ViolationCounts, right: ViolationCounts) -> ViolationCounts {
+    ViolationCounts {
+        layer_consistency: left
+            .layer_consistency
+            .saturating_add(right.layer_consistency),
+        degree_bounds: left.degree_bounds.saturating_add(right.degree_bounds),
+        reachability: left.reachability.saturating_add(right.reachability),
+        bidirectional_links: left
+            .bidirectional_links
+            .saturating_add(right.bidirectional_links),
+        config: left.config.saturating_add(right.config),
+    }
+}
+
+impl fmt::Display for SoakStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "rounds={} inserts={} searches={} checks={} max_points={}",
+            self.rounds, self.inserts, self.searches, self.checks, self.max_points,
+        )?;
+        writeln!(f, "in-flight violations: {}", self.in_flight)?;
+        write!(f, "settled violations: {}", self.settled)
+    }
+}
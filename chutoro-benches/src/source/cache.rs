@@ -0,0 +1,207 @@
+//! Download-and-cache helpers shared by the real-world dataset loaders.
+//!
+//! Each loader downloads its files once into a local cache directory and
+//! reuses them on later runs without touching the network. When a SHA-256
+//! digest is configured, cached files that no longer match are downloaded
+//! again and fresh downloads that do not match are rejected before they
+//! reach the cache.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use sha2::{Digest, Sha256};
+
+use crate::source::SyntheticError;
+
+/// Download client abstraction for dataset loaders.
+pub(crate) trait DownloadClient {
+    /// Downloads URL contents as bytes.
+    ///
+    /// # Errors
+    /// Returns [`SyntheticError`] if the request fails.
+    fn download_bytes(&self, url: &str) -> Result<Vec<u8>, SyntheticError>;
+}
+
+/// Production client that fetches over HTTP(S) with `ureq`.
+pub(crate) struct UreqDownloadClient;
+
+impl DownloadClient for UreqDownloadClient {
+    fn download_bytes(&self, url: &str) -> Result<Vec<u8>, SyntheticError> {
+        let mut response = ureq::get(url)
+            .call()
+            .map_err(|error| SyntheticError::Download {
+                url: url.to_owned(),
+                message: error.to_string(),
+            })?;
+
+        response
+            .body_mut()
+            .read_to_vec()
+            .map_err(|error| SyntheticError::Download {
+                url: url.to_owned(),
+                message: error.to_string(),
+            })
+    }
+}
+
+/// Returns the cached bytes at `path`, downloading them from `url` first
+/// when the cache is empty or fails `expected_sha256`.
+pub(crate) fn ensure_cached_bytes(
+    path: &Path,
+    url: &str,
+    expected_sha256: Option<&str>,
+    client: &dyn DownloadClient,
+) -> Result<Vec<u8>, SyntheticError> {
+    if path.exists() {
+        let cached = fs::read(path)?;
+        if expected_sha256.is_none_or(|expected| digest_matches(&cached, expected)) {
+            return Ok(cached);
+        }
+    }
+
+    let payload = client.download_bytes(url)?;
+    if let Some(expected) = expected_sha256 {
+        let actual = sha256_hex(&payload);
+        if !actual.eq_ignore_ascii_case(expected) {
+            return Err(SyntheticError::ChecksumMismatch {
+                path: path.to_path_buf(),
+                expected: expected.to_owned(),
+                actual,
+            });
+        }
+    }
+    write_atomic(path, &payload)?;
+    Ok(payload)
+}
+
+/// Returns the lowercase hexadecimal SHA-256 digest of `bytes`.
+pub(crate) fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .flat_map(|byte| [byte >> 4, byte & 0x0f])
+        .filter_map(|nibble| char::from_digit(u32::from(nibble), 16))
+        .collect()
+}
+
+fn digest_matches(bytes: &[u8], expected: &str) -> bool {
+    sha256_hex(bytes).eq_ignore_ascii_case(expected)
+}
+
+fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), SyntheticError> {
+    let mut part_path = path.to_path_buf();
+    part_path.set_extension("part");
+    if part_path.exists() {
+        fs::remove_file(&part_path)?;
+    }
+    fs::write(&part_path, bytes)?;
+    fs::rename(&part_path, path)?;
+    Ok(())
+}
+
+/// Joins `base_url` and `file_name` with exactly one slash.
+pub(crate) fn file_url(base_url: &str, file_name: &str) -> String {
+    format!("{}/{}", base_url.trim_end_matches('/'), file_name)
+}
+
+/// Resolves a dataset cache directory.
+///
+/// `override_var` wins when set; otherwise the directory is
+/// `<cache root>/chutoro/<dataset>`, where the cache root is
+/// `XDG_CACHE_HOME`, then `~/.cache`, then the system temporary directory.
+pub(crate) fn default_cache_dir(override_var: &str, dataset: &str) -> PathBuf {
+    if let Some(explicit) = env::var_os(override_var) {
+        return PathBuf::from(explicit);
+    }
+
+    if let Some(xdg_cache) = env::var_os("XDG_CACHE_HOME") {
+        return PathBuf::from(xdg_cache).join("chutoro").join(dataset);
+    }
+
+    if let Some(home) = env::var_os("HOME") {
+        return PathBuf::from(home)
+            .join(".cache")
+            .join("chutoro")
+            .join(dataset);
+    }
+
+    env::temp_dir().join("chutoro").join(dataset)
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for cache reuse and checksum validation.
+
+    use super::*;
+    use rstest::rstest;
+    use std::cell::Cell;
+
+    struct CountingClient {
+        payload: Vec<u8>,
+        calls: Cell<usize>,
+    }
+
+    impl DownloadClient for CountingClient {
+        fn download_bytes(&self, _url: &str) -> Result<Vec<u8>, SyntheticError> {
+            self.calls.set(self.calls.get() + 1);
+            Ok(self.payload.clone())
+        }
+    }
+
+    fn client(payload: &[u8]) -> CountingClient {
+        CountingClient {
+            payload: payload.to_vec(),
+            calls: Cell::new(0),
+        }
+    }
+
+    #[rstest]
+    fn sha256_hex_matches_known_digest() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[rstest]
+    fn ensure_cached_bytes_redownloads_corrupt_cache() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("payload.bin");
+        fs::write(&path, b"corrupt").expect("seed cache");
+        let fresh = client(b"abc");
+
+        let bytes = ensure_cached_bytes(&path, "u", Some(&sha256_hex(b"abc")), &fresh)
+            .expect("mismatched cache should be replaced");
+
+        assert_eq!(bytes, b"abc");
+        assert_eq!(fresh.calls.get(), 1);
+        assert_eq!(fs::read(&path).expect("cache"), b"abc");
+    }
+
+    #[rstest]
+    fn ensure_cached_bytes_rejects_mismatched_download() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("payload.bin");
+        let tampered = client(b"tampered");
+
+        let error = ensure_cached_bytes(&path, "u", Some(&sha256_hex(b"abc")), &tampered)
+            .expect_err("mismatched download must fail");
+
+        assert!(matches!(error, SyntheticError::ChecksumMismatch { .. }));
+        assert!(!path.exists(), "rejected payloads must not be cached");
+    }
+
+    #[rstest]
+    fn ensure_cached_bytes_reuses_cache_without_checksum() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("payload.bin");
+        fs::write(&path, b"cached").expect("seed cache");
+        let unused = client(b"fresh");
+
+        let bytes = ensure_cached_bytes(&path, "u", None, &unused).expect("cache hit");
+
+        assert_eq!(bytes, b"cached");
+        assert_eq!(unused.calls.get(), 0);
+    }
+}
@@ -50,6 +50,14 @@ pub enum SyntheticError {
         /// Number of points requested.
         point_count: usize,
     },
+    /// Ground-truth labels did not match the source's point count.
+    #[error("expected {expected} labels, got {actual}")]
+    LabelCountMismatch {
+        /// Number of points in the source.
+        expected: usize,
+        /// Number of labels supplied.
+        actual: usize,
+    },
     /// The requested `point_count * dimensions` overflowed `usize`.
     #[error("point_count * dimensions overflows usize")]
     Overflow,
@@ -102,6 +110,24 @@ pub enum SyntheticError {
         /// Human-readable validation failure.
         message: String,
     },
+    /// A downloaded dataset file did not match its expected SHA-256 digest.
+    #[error("checksum mismatch for `{path}`: expected sha256 {expected}, got {actual}")]
+    ChecksumMismatch {
+        /// Cache path the download was destined for.
+        path: PathBuf,
+        /// Expected hexadecimal digest.
+        expected: String,
+        /// Digest of the downloaded bytes.
+        actual: String,
+    },
+    /// The 20-newsgroups archive was malformed.
+    #[error("invalid 20-newsgroups archive `{path}`: {message}")]
+    InvalidNewsgroupsArchive {
+        /// Path of the malformed archive.
+        path: PathBuf,
+        /// Human-readable validation failure.
+        message: String,
+    },
     /// Two MNIST image files had mismatched dimensions.
     #[error("MNIST image dimensions mismatch between train and test: train={train}, test={test}")]
     MnistDimensionMismatch {
@@ -0,0 +1,73 @@
+//! Fashion-MNIST download-and-cache helper.
+//!
+//! Fashion-MNIST is a drop-in replacement for MNIST (same IDX layout and
+//! 70,000 x 784 shape) whose clothing classes overlap far more than digits
+//! do, so it is a harder real-world baseline for the same pipeline.
+
+use std::path::PathBuf;
+
+use super::{IdxPair, load_idx_pair};
+use crate::source::{
+    SyntheticError,
+    cache::{DownloadClient, UreqDownloadClient, default_cache_dir},
+    numeric::SyntheticSource,
+};
+
+/// SHA-256 of the published `train-images-idx3-ubyte.gz`.
+const TRAIN_IMAGES_SHA256: &str =
+    "3aede38d61863908ad78613f6a32ed271626dd12800ba2636569512369268a84";
+/// SHA-256 of the published `t10k-images-idx3-ubyte.gz`.
+const TEST_IMAGES_SHA256: &str = "346e55b948d973a97e58d2351dde16a484bd415d4595297633bb08f03db6a073";
+
+/// Configuration for Fashion-MNIST download and cache behaviour.
+#[derive(Clone, Debug)]
+pub struct FashionMnistConfig {
+    /// Local directory where compressed Fashion-MNIST files are cached.
+    pub cache_dir: PathBuf,
+    /// Base URL that hosts the Fashion-MNIST gzip IDX files.
+    pub base_url: String,
+    /// Expected SHA-256 of the training images, or `None` to skip the check
+    /// (for example, for a re-packed mirror).
+    pub train_sha256: Option<String>,
+    /// Expected SHA-256 of the test images, or `None` to skip the check.
+    pub test_sha256: Option<String>,
+}
+
+impl Default for FashionMnistConfig {
+    fn default() -> Self {
+        Self {
+            cache_dir: default_cache_dir("CHUTORO_FASHION_MNIST_CACHE_DIR", "fashion-mnist"),
+            base_url: "http://fashion-mnist.s3-website.eu-central-1.amazonaws.com".to_owned(),
+            train_sha256: Some(TRAIN_IMAGES_SHA256.to_owned()),
+            test_sha256: Some(TEST_IMAGES_SHA256.to_owned()),
+        }
+    }
+}
+
+impl SyntheticSource {
+    /// Loads Fashion-MNIST vectors (70,000 x 784) using a download-and-cache
+    /// helper, validating each file against its configured SHA-256.
+    ///
+    /// # Errors
+    /// Returns [`SyntheticError`] when downloading, checksum validation,
+    /// parsing, or shape validation fails.
+    pub fn load_fashion_mnist(config: &FashionMnistConfig) -> Result<Self, SyntheticError> {
+        load_fashion_mnist_with_client(config, &UreqDownloadClient)
+    }
+}
+
+pub(super) fn load_fashion_mnist_with_client(
+    config: &FashionMnistConfig,
+    client: &dyn DownloadClient,
+) -> Result<SyntheticSource, SyntheticError> {
+    load_idx_pair(
+        &IdxPair {
+            name: "fashion-mnist",
+            cache_dir: &config.cache_dir,
+            base_url: &config.base_url,
+            train_sha256: config.train_sha256.as_deref(),
+            test_sha256: config.test_sha256.as_deref(),
+        },
+        client,
+    )
+}
@@ -1,12 +1,20 @@
-//! MNIST download-and-cache helper for benchmark baselines.
+//! MNIST and Fashion-MNIST download-and-cache helpers for benchmark
+//! baselines.
 
-use crate::source::{SyntheticError, numeric::SyntheticSource};
+mod fashion;
+
+use crate::source::{
+    SyntheticError,
+    cache::{DownloadClient, UreqDownloadClient, default_cache_dir, ensure_cached_bytes, file_url},
+    numeric::SyntheticSource,
+};
 use flate2::read::GzDecoder;
-use std::env;
 use std::fs;
 use std::io::Read;
 use std::path::{Path, PathBuf};
 
+pub use fashion::FashionMnistConfig;
+
 const TRAIN_IMAGES_FILE: &str = "train-images-idx3-ubyte.gz";
 const TEST_IMAGES_FILE: &str = "t10k-images-idx3-ubyte.gz";
 const IDX_IMAGE_MAGIC: u32 = 2_051;
@@ -27,42 +35,12 @@ pub struct MnistConfig {
 impl Default for MnistConfig {
     fn default() -> Self {
         Self {
-            cache_dir: default_cache_dir(),
+            cache_dir: default_cache_dir("CHUTORO_MNIST_CACHE_DIR", "mnist"),
             base_url: "https://storage.googleapis.com/cvdf-datasets/mnist".to_owned(),
         }
     }
 }
 
-/// Download client abstraction for MNIST helpers.
-pub trait MnistDownloadClient {
-    /// Downloads URL contents as bytes.
-    ///
-    /// # Errors
-    /// Returns [`SyntheticError`] if the request fails.
-    fn download_bytes(&self, url: &str) -> Result<Vec<u8>, SyntheticError>;
-}
-
-struct UreqMnistDownloadClient;
-
-impl MnistDownloadClient for UreqMnistDownloadClient {
-    fn download_bytes(&self, url: &str) -> Result<Vec<u8>, SyntheticError> {
-        let mut response = ureq::get(url)
-            .call()
-            .map_err(|error| SyntheticError::Download {
-                url: url.to_owned(),
-                message: error.to_string(),
-            })?;
-
-        response
-            .body_mut()
-            .read_to_vec()
-            .map_err(|error| SyntheticError::Download {
-                url: url.to_owned(),
-                message: error.to_string(),
-            })
-    }
-}
-
 impl SyntheticSource {
     /// Loads MNIST vectors (70,000 x 784) using a download-and-cache helper.
     ///
@@ -70,22 +48,58 @@ impl SyntheticSource {
     /// Returns [`SyntheticError`] when downloading, parsing, or validating
     /// cached MNIST files fails.
     pub fn load_mnist(config: &MnistConfig) -> Result<Self, SyntheticError> {
-        load_mnist_with_client(config, &UreqMnistDownloadClient)
+        load_mnist_with_client(config, &UreqDownloadClient)
     }
 }
 
 fn load_mnist_with_client(
     config: &MnistConfig,
-    client: &dyn MnistDownloadClient,
+    client: &dyn DownloadClient,
 ) -> Result<SyntheticSource, SyntheticError> {
-    fs::create_dir_all(&config.cache_dir)?;
-
-    let train_path = config.cache_dir.join(TRAIN_IMAGES_FILE);
-    let test_path = config.cache_dir.join(TEST_IMAGES_FILE);
-
-    let train_bytes =
-        ensure_cached_bytes(&train_path, &file_url(config, TRAIN_IMAGES_FILE), client)?;
-    let test_bytes = ensure_cached_bytes(&test_path, &file_url(config, TEST_IMAGES_FILE), client)?;
+    load_idx_pair(
+        &IdxPair {
+            name: "mnist",
+            cache_dir: &config.cache_dir,
+            base_url: &config.base_url,
+            train_sha256: None,
+            test_sha256: None,
+        },
+        client,
+    )
+}
+
+/// One train/test pair of gzip IDX image files and where to fetch them.
+struct IdxPair<'a> {
+    name: &'static str,
+    cache_dir: &'a Path,
+    base_url: &'a str,
+    train_sha256: Option<&'a str>,
+    test_sha256: Option<&'a str>,
+}
+
+/// Loads and concatenates an MNIST-format train/test pair, checking it has
+/// the 70,000 x 784 shape MNIST and its drop-in replacements share.
+fn load_idx_pair(
+    pair: &IdxPair<'_>,
+    client: &dyn DownloadClient,
+) -> Result<SyntheticSource, SyntheticError> {
+    fs::create_dir_all(pair.cache_dir)?;
+
+    let train_path = pair.cache_dir.join(TRAIN_IMAGES_FILE);
+    let test_path = pair.cache_dir.join(TEST_IMAGES_FILE);
+
+    let train_bytes = ensure_cached_bytes(
+        &train_path,
+        &file_url(pair.base_url, TRAIN_IMAGES_FILE),
+        pair.train_sha256,
+        client,
+    )?;
+    let test_bytes = ensure_cached_bytes(
+        &test_path,
+        &file_url(pair.base_url, TEST_IMAGES_FILE),
+        pair.test_sha256,
+        client,
+    )?;
 
     let train = parse_idx_images(&train_path, &train_bytes)?;
     let test = parse_idx_images(&test_path, &test_bytes)?;
@@ -105,7 +119,7 @@ fn load_mnist_with_client(
 
     if point_count != MNIST_POINT_COUNT || dimensions != MNIST_DIMENSIONS {
         return Err(SyntheticError::InvalidMnistFile {
-            path: config.cache_dir.clone(),
+            path: pair.cache_dir.to_path_buf(),
             message: format!(
                 "expected {MNIST_POINT_COUNT}x{MNIST_DIMENSIONS}, got {point_count}x{dimensions}"
             ),
@@ -115,55 +129,7 @@ fn load_mnist_with_client(
     let mut data = train.data;
     data.extend(test.data);
 
-    SyntheticSource::from_parts("mnist", data, point_count, dimensions)
-}
-
-fn ensure_cached_bytes(
-    path: &Path,
-    url: &str,
-    client: &dyn MnistDownloadClient,
-) -> Result<Vec<u8>, SyntheticError> {
-    if path.exists() {
-        return fs::read(path).map_err(SyntheticError::from);
-    }
-
-    let payload = client.download_bytes(url)?;
-    write_atomic(path, &payload)?;
-    Ok(payload)
-}
-
-fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), SyntheticError> {
-    let mut part_path = path.to_path_buf();
-    part_path.set_extension("part");
-    if part_path.exists() {
-        fs::remove_file(&part_path)?;
-    }
-    fs::write(&part_path, bytes)?;
-    fs::rename(&part_path, path)?;
-    Ok(())
-}
-
-fn file_url(config: &MnistConfig, file_name: &str) -> String {
-    format!("{}/{}", config.base_url.trim_end_matches('/'), file_name)
-}
-
-fn default_cache_dir() -> PathBuf {
-    if let Some(explicit) = env::var_os("CHUTORO_MNIST_CACHE_DIR") {
-        return PathBuf::from(explicit);
-    }
-
-    if let Some(xdg_cache) = env::var_os("XDG_CACHE_HOME") {
-        return PathBuf::from(xdg_cache).join("chutoro").join("mnist");
-    }
-
-    if let Some(home) = env::var_os("HOME") {
-        return PathBuf::from(home)
-            .join(".cache")
-            .join("chutoro")
-            .join("mnist");
-    }
-
-    env::temp_dir().join("chutoro").join("mnist")
+    SyntheticSource::from_parts(pair.name, data, point_count, dimensions)
 }
 
 #[derive(Debug)]
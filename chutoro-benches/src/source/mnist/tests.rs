@@ -1,12 +1,15 @@
 //! Unit tests for MNIST parsing and cache helpers.
 
+use super::fashion::load_fashion_mnist_with_client;
 use super::*;
+use crate::source::cache::sha256_hex;
 use chutoro_core::DataSource;
 use flate2::Compression;
 use flate2::write::GzEncoder;
 use rstest::rstest;
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::env;
 use std::io::Write;
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -28,7 +31,7 @@ impl FakeClient {
     }
 }
 
-impl MnistDownloadClient for FakeClient {
+impl DownloadClient for FakeClient {
     fn download_bytes(&self, url: &str) -> Result<Vec<u8>, SyntheticError> {
         *self.call_count.borrow_mut() += 1;
         self.payloads
@@ -83,8 +86,8 @@ fn load_mnist_uses_cache_after_first_download() {
         base_url: "https://example.test/mnist".to_owned(),
     };
 
-    let train_url = file_url(&config, TRAIN_IMAGES_FILE);
-    let test_url = file_url(&config, TEST_IMAGES_FILE);
+    let train_url = file_url(&config.base_url, TRAIN_IMAGES_FILE);
+    let test_url = file_url(&config.base_url, TEST_IMAGES_FILE);
     let train_payload = gzip_idx_images(60_000, 28, 28, 3_u8);
     let test_payload = gzip_idx_images(10_000, 28, 28, 9_u8);
 
@@ -106,6 +109,54 @@ fn load_mnist_uses_cache_after_first_download() {
     fs::remove_dir_all(cache_dir).expect("test cache dir cleanup must succeed");
 }
 
+#[rstest]
+#[case::pinned_digests(true)]
+#[case::unchecked(false)]
+fn load_fashion_mnist_accepts_matching_or_unchecked_files(#[case] pin: bool) {
+    let cache_dir = test_cache_dir();
+    let base_url = "https://example.test/fashion";
+    let train_payload = gzip_idx_images(60_000, 28, 28, 5_u8);
+    let test_payload = gzip_idx_images(10_000, 28, 28, 7_u8);
+    let config = FashionMnistConfig {
+        cache_dir: cache_dir.clone(),
+        base_url: base_url.to_owned(),
+        train_sha256: pin.then(|| sha256_hex(&train_payload)),
+        test_sha256: pin.then(|| sha256_hex(&test_payload)),
+    };
+    let client = FakeClient::new(HashMap::from([
+        (file_url(base_url, TRAIN_IMAGES_FILE), train_payload),
+        (file_url(base_url, TEST_IMAGES_FILE), test_payload),
+    ]));
+
+    let source =
+        load_fashion_mnist_with_client(&config, &client).expect("fashion load should succeed");
+    assert_eq!(source.len(), MNIST_POINT_COUNT);
+    assert_eq!(source.name(), "fashion-mnist");
+    fs::remove_dir_all(cache_dir).expect("test cache dir cleanup must succeed");
+}
+
+#[rstest]
+fn load_fashion_mnist_rejects_tampered_download() {
+    let cache_dir = test_cache_dir();
+    let base_url = "https://example.test/fashion";
+    let config = FashionMnistConfig {
+        cache_dir: cache_dir.clone(),
+        base_url: base_url.to_owned(),
+        ..FashionMnistConfig::default()
+    };
+    let client = FakeClient::new(HashMap::from([(
+        file_url(base_url, TRAIN_IMAGES_FILE),
+        gzip_idx_images(2, 28, 28, 1_u8),
+    )]));
+
+    let error = load_fashion_mnist_with_client(&config, &client)
+        .expect_err("payload not matching the published digest must fail");
+
+    assert!(matches!(error, SyntheticError::ChecksumMismatch { .. }));
+    assert!(!cache_dir.join(TRAIN_IMAGES_FILE).exists());
+    fs::remove_dir_all(cache_dir).expect("test cache dir cleanup must succeed");
+}
+
 fn test_cache_dir() -> PathBuf {
     // A clock before the epoch degrades to zero nanoseconds; the prefix
     // still keeps the path unique enough for test scratch space.
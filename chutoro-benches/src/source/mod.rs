@@ -1,21 +1,26 @@
 //! Synthetic benchmark data sources.
 //!
 //! This module provides configurable generators for numeric and text
-//! benchmarking datasets, together with a download-and-cache helper for
-//! MNIST.
+//! benchmarking datasets, together with download-and-cache helpers for
+//! MNIST, Fashion-MNIST, and the 20-newsgroups text corpus.
 
+mod cache;
 mod errors;
 mod mnist;
+mod newsgroups;
 mod numeric;
 mod text;
 
 pub use errors::SyntheticError;
-pub use mnist::{MNIST_DIMENSIONS, MNIST_POINT_COUNT, MnistConfig};
+pub use mnist::{FashionMnistConfig, MNIST_DIMENSIONS, MNIST_POINT_COUNT, MnistConfig};
+pub use newsgroups::{NewsgroupsConfig, NewsgroupsCorpus};
 pub use numeric::{
-    Anisotropy, GaussianBlobConfig, ManifoldConfig, ManifoldPattern, SyntheticConfig,
-    SyntheticSource,
+    Anisotropy, DensityRampConfig, GaussianBlobConfig, ManifoldConfig, ManifoldPattern,
+    NestedClusterConfig, SyntheticConfig, SyntheticSource,
 };
 pub use text::{SyntheticTextConfig, SyntheticTextSource};
 
 #[cfg(test)]
 mod tests;
+#[cfg(test)]
+mod variable_density_tests;
@@ -0,0 +1,205 @@
+//! 20-newsgroups download-and-cache helper for text benchmarks.
+//!
+//! The corpus is the "bydate" split (about 18,800 posts across 20 groups)
+//! distributed as a single gzip tarball. Each post is reduced to one line of
+//! lowercase alphanumeric tokens with its mail headers stripped, so the
+//! corpus can feed the Levenshtein text path directly or be written out one
+//! document per line for the CLI text provider. The group each post came
+//! from is kept as its ground-truth label.
+
+use std::{
+    fs,
+    io::Read,
+    path::{Component, Path, PathBuf},
+};
+
+use flate2::read::GzDecoder;
+
+use crate::source::{
+    SyntheticError, SyntheticTextSource,
+    cache::{DownloadClient, UreqDownloadClient, default_cache_dir, ensure_cached_bytes},
+};
+
+const ARCHIVE_FILE: &str = "20news-bydate.tar.gz";
+/// SHA-256 of the published `20news-bydate.tar.gz`.
+const ARCHIVE_SHA256: &str = "8f1b2514ca22a5ade8fbb9cfa5727df95fa587f4c87b786e15c759fa66d95610";
+/// Prefix shared by the archive's train and test split directories.
+const SPLIT_PREFIX: &str = "20news-bydate-";
+
+/// Configuration for 20-newsgroups download, cache, and tokenisation.
+#[derive(Clone, Debug)]
+pub struct NewsgroupsConfig {
+    /// Local directory where the compressed archive is cached.
+    pub cache_dir: PathBuf,
+    /// URL of the `20news-bydate.tar.gz` archive.
+    pub url: String,
+    /// Expected SHA-256 of the archive, or `None` to skip the check.
+    pub sha256: Option<String>,
+    /// Maximum tokens kept per document; bounds Levenshtein cost on long
+    /// posts.
+    pub max_tokens: usize,
+}
+
+impl Default for NewsgroupsConfig {
+    fn default() -> Self {
+        Self {
+            cache_dir: default_cache_dir("CHUTORO_NEWSGROUPS_CACHE_DIR", "20newsgroups"),
+            url: "https://ndownloader.figshare.com/files/5975967".to_owned(),
+            sha256: Some(ARCHIVE_SHA256.to_owned()),
+            max_tokens: 32,
+        }
+    }
+}
+
+/// A tokenised 20-newsgroups corpus with per-document group labels.
+#[derive(Clone, Debug)]
+pub struct NewsgroupsCorpus {
+    documents: Vec<String>,
+    labels: Vec<usize>,
+    groups: Vec<String>,
+}
+
+impl NewsgroupsCorpus {
+    /// Loads the train and test splits using a download-and-cache helper.
+    ///
+    /// Documents are ordered by group, split, and post id so repeated loads
+    /// are identical. Posts with no tokens after their headers are skipped.
+    ///
+    /// # Errors
+    /// Returns [`SyntheticError`] when downloading, checksum validation, or
+    /// archive parsing fails, or when `max_tokens` is zero.
+    pub fn load(config: &NewsgroupsConfig) -> Result<Self, SyntheticError> {
+        load_newsgroups_with_client(config, &UreqDownloadClient)
+    }
+
+    /// Returns one space-separated token line per document.
+    #[must_use]
+    pub fn documents(&self) -> &[String] {
+        &self.documents
+    }
+
+    /// Returns each document's group index into [`Self::groups`].
+    #[must_use]
+    pub fn labels(&self) -> &[usize] {
+        &self.labels
+    }
+
+    /// Returns the group names in label order.
+    #[must_use]
+    pub fn groups(&self) -> &[String] {
+        &self.groups
+    }
+
+    /// Converts the documents into a Levenshtein-distance text source.
+    #[must_use]
+    pub fn into_text_source(self) -> SyntheticTextSource {
+        SyntheticTextSource::from_lines("20-newsgroups", self.documents)
+    }
+}
+
+fn load_newsgroups_with_client(
+    config: &NewsgroupsConfig,
+    client: &dyn DownloadClient,
+) -> Result<NewsgroupsCorpus, SyntheticError> {
+    if config.max_tokens == 0 {
+        return Err(SyntheticError::ZeroTextLength);
+    }
+    fs::create_dir_all(&config.cache_dir)?;
+    let path = config.cache_dir.join(ARCHIVE_FILE);
+    let bytes = ensure_cached_bytes(&path, &config.url, config.sha256.as_deref(), client)?;
+    parse_archive(&path, &bytes, config.max_tokens)
+}
+
+/// A post located in the archive, keyed for deterministic ordering.
+struct Post {
+    group: String,
+    key: String,
+    text: String,
+}
+
+fn parse_archive(
+    path: &Path,
+    gzipped_bytes: &[u8],
+    max_tokens: usize,
+) -> Result<NewsgroupsCorpus, SyntheticError> {
+    let invalid = |message: String| SyntheticError::InvalidNewsgroupsArchive {
+        path: path.to_path_buf(),
+        message,
+    };
+    let mut archive = tar::Archive::new(GzDecoder::new(gzipped_bytes));
+    let mut posts = Vec::new();
+    for item in archive
+        .entries()
+        .map_err(|error| invalid(error.to_string()))?
+    {
+        let mut entry = item.map_err(|error| invalid(error.to_string()))?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let entry_path = entry.path().map_err(|error| invalid(error.to_string()))?;
+        let Some((split, group, id)) = post_location(&entry_path) else {
+            continue;
+        };
+        let key = format!("{split}/{id:0>8}");
+        let mut raw = Vec::new();
+        entry
+            .read_to_end(&mut raw)
+            .map_err(|error| invalid(error.to_string()))?;
+        posts.push(Post {
+            group,
+            key,
+            text: tokenise(&String::from_utf8_lossy(&raw), max_tokens),
+        });
+    }
+    if posts.is_empty() {
+        return Err(invalid("archive contains no posts".to_owned()));
+    }
+    posts.sort_unstable_by(|left, right| (&left.group, &left.key).cmp(&(&right.group, &right.key)));
+    Ok(collect_corpus(posts))
+}
+
+/// Splits `<20news-bydate-split>/<group>/<id>` into its parts.
+fn post_location(path: &Path) -> Option<(String, String, String)> {
+    let mut parts = path.components().filter_map(|component| match component {
+        Component::Normal(part) => part.to_str(),
+        _ => None,
+    });
+    let split = parts.next()?.strip_prefix(SPLIT_PREFIX)?.to_owned();
+    let group = parts.next()?.to_owned();
+    let id = parts.next()?.to_owned();
+    parts.next().is_none().then_some((split, group, id))
+}
+
+fn collect_corpus(posts: Vec<Post>) -> NewsgroupsCorpus {
+    let mut corpus = NewsgroupsCorpus {
+        documents: Vec::with_capacity(posts.len()),
+        labels: Vec::with_capacity(posts.len()),
+        groups: Vec::new(),
+    };
+    for post in posts.into_iter().filter(|post| !post.text.is_empty()) {
+        if corpus.groups.last() != Some(&post.group) {
+            corpus.groups.push(post.group);
+        }
+        corpus.labels.push(corpus.groups.len().saturating_sub(1));
+        corpus.documents.push(post.text);
+    }
+    corpus
+}
+
+/// Drops the mail headers (everything before the first blank line) and
+/// keeps at most `max_tokens` lowercase alphanumeric tokens.
+fn tokenise(raw: &str, max_tokens: usize) -> String {
+    let body = raw
+        .replace("\r\n", "\n")
+        .split_once("\n\n")
+        .map_or_else(String::new, |(_, body)| body.to_owned());
+    body.split(|character: char| !character.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .take(max_tokens)
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,167 @@
+//! Unit tests for 20-newsgroups parsing and cache helpers.
+
+use super::*;
+use crate::source::cache::sha256_hex;
+use chutoro_core::DataSource;
+use flate2::{Compression, write::GzEncoder};
+use rstest::rstest;
+use std::cell::Cell;
+
+struct ArchiveClient {
+    archive: Vec<u8>,
+    calls: Cell<usize>,
+}
+
+impl DownloadClient for ArchiveClient {
+    fn download_bytes(&self, _url: &str) -> Result<Vec<u8>, SyntheticError> {
+        self.calls.set(self.calls.get() + 1);
+        Ok(self.archive.clone())
+    }
+}
+
+/// Builds a gzip tarball holding `(path, contents)` files.
+fn archive(files: &[(&str, &str)]) -> Vec<u8> {
+    let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+    for (path, contents) in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, path, contents.as_bytes())
+            .expect("appending to an in-memory archive must succeed");
+    }
+    builder
+        .into_inner()
+        .and_then(GzEncoder::finish)
+        .expect("finishing an in-memory archive must succeed")
+}
+
+fn sample_archive() -> Vec<u8> {
+    archive(&[
+        (
+            "20news-bydate-train/sci.space/61000",
+            "From: a@b\nSubject: orbit\n\nThe Shuttle reached ORBIT, twice!\n",
+        ),
+        (
+            "20news-bydate-test/alt.atheism/53000",
+            "From: c@d\n\nFaith and reason.\n",
+        ),
+        (
+            "20news-bydate-train/alt.atheism/49960",
+            "Subject: headers only\n",
+        ),
+        ("README", "not a post"),
+        (
+            "20news-bydate-train/alt.atheism/51060",
+            "From: e@f\n\nReason\n",
+        ),
+    ])
+}
+
+fn config(cache_dir: &Path, sha256: Option<String>) -> NewsgroupsConfig {
+    NewsgroupsConfig {
+        cache_dir: cache_dir.to_path_buf(),
+        url: "https://example.test/20news-bydate.tar.gz".to_owned(),
+        sha256,
+        max_tokens: 4,
+    }
+}
+
+#[rstest]
+#[case::headers_stripped("From: x\nSubject: y\n\nHello, World", 8, "hello world")]
+#[case::crlf_headers("From: x\r\n\r\nHello", 8, "hello")]
+#[case::truncated("From: x\n\none two three four five", 3, "one two three")]
+#[case::headers_only("From: x\nSubject: y\n", 8, "")]
+fn tokenise_strips_headers_and_normalises(
+    #[case] raw: &str,
+    #[case] max_tokens: usize,
+    #[case] expected: &str,
+) {
+    assert_eq!(tokenise(raw, max_tokens), expected);
+}
+
+#[rstest]
+fn load_orders_documents_and_labels_by_group() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let client = ArchiveClient {
+        archive: sample_archive(),
+        calls: Cell::new(0),
+    };
+
+    let corpus = load_newsgroups_with_client(&config(dir.path(), None), &client)
+        .expect("sample archive should load");
+
+    assert_eq!(corpus.groups(), ["alt.atheism", "sci.space"]);
+    assert_eq!(
+        corpus.documents(),
+        ["faith and reason", "reason", "the shuttle reached orbit"]
+    );
+    assert_eq!(corpus.labels(), [0, 0, 1]);
+    let source = corpus.into_text_source();
+    assert_eq!(source.len(), 3);
+    assert_eq!(source.name(), "20-newsgroups");
+}
+
+#[rstest]
+fn load_reuses_verified_cache() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let payload = sample_archive();
+    let digest = sha256_hex(&payload);
+    let client = ArchiveClient {
+        archive: payload,
+        calls: Cell::new(0),
+    };
+    let pinned = config(dir.path(), Some(digest));
+
+    load_newsgroups_with_client(&pinned, &client).expect("first load should download");
+    load_newsgroups_with_client(&pinned, &client).expect("second load should hit the cache");
+
+    assert_eq!(client.calls.get(), 1);
+}
+
+#[rstest]
+fn load_rejects_archive_failing_checksum() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let client = ArchiveClient {
+        archive: sample_archive(),
+        calls: Cell::new(0),
+    };
+
+    let error = load_newsgroups_with_client(&config(dir.path(), Some("00".repeat(32))), &client)
+        .expect_err("archive not matching its digest must fail");
+
+    assert!(matches!(error, SyntheticError::ChecksumMismatch { .. }));
+}
+
+#[rstest]
+#[case::no_posts(archive(&[("README", "nothing here")]), "no posts")]
+#[case::not_gzip(b"plain bytes".to_vec(), "")]
+fn parse_archive_rejects_malformed_input(#[case] bytes: Vec<u8>, #[case] fragment: &str) {
+    let error =
+        parse_archive(Path::new("bad.tar.gz"), &bytes, 8).expect_err("malformed archive must fail");
+
+    let SyntheticError::InvalidNewsgroupsArchive { message, .. } = error else {
+        panic!("expected InvalidNewsgroupsArchive, got {error:?}");
+    };
+    assert!(message.contains(fragment));
+}
+
+#[rstest]
+fn load_rejects_zero_token_budget() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let client = ArchiveClient {
+        archive: sample_archive(),
+        calls: Cell::new(0),
+    };
+    let zero_budget = NewsgroupsConfig {
+        max_tokens: 0,
+        ..config(dir.path(), None)
+    };
+
+    let error = load_newsgroups_with_client(&zero_budget, &client)
+        .expect_err("a zero token budget must fail");
+
+    assert!(matches!(error, SyntheticError::ZeroTextLength));
+    assert_eq!(client.calls.get(), 0);
+}
@@ -32,21 +32,23 @@ pub(super) fn validate_blob_config(config: &GaussianBlobConfig) -> Result<(), Sy
     reason = "centroid placement uses trigonometric expressions"
 )]
 pub(super) fn build_blob_centroids(
-    config: &GaussianBlobConfig,
+    cluster_count: usize,
+    dimensions: usize,
+    separation: f32,
     rng: &mut SmallRng,
 ) -> Vec<Vec<f32>> {
-    (0..config.cluster_count)
+    (0..cluster_count)
         .map(|cluster_index| {
-            let angle = (cluster_index as f32 / config.cluster_count as f32) * (2.0 * PI);
-            let mut centroid = vec![0.0_f32; config.dimensions];
+            let angle = (cluster_index as f32 / cluster_count as f32) * (2.0 * PI);
+            let mut centroid = vec![0.0_f32; dimensions];
             if let Some(value) = centroid.get_mut(0) {
-                *value = config.separation * angle.cos();
+                *value = separation * angle.cos();
             }
             if let Some(value) = centroid.get_mut(1) {
-                *value = config.separation * angle.sin();
+                *value = separation * angle.sin();
             }
             for value in centroid.iter_mut().skip(2) {
-                *value = rng.gen_range((-0.2 * config.separation)..(0.2 * config.separation));
+                *value = rng.gen_range((-0.2 * separation)..(0.2 * separation));
             }
             centroid
         })
@@ -87,7 +89,7 @@ fn validate_axis_scales(scales: &[f32], dimensions: usize) -> Result<Vec<f32>, S
     Ok(scales.to_vec())
 }
 
-fn validate_float_param(
+pub(super) fn validate_float_param(
     value: f32,
     parameter: &'static str,
     allow_zero: bool,
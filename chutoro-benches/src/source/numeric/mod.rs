@@ -1,6 +1,7 @@
 //! Numeric synthetic data generators for benchmarking.
 
 mod generation;
+mod variable_density;
 
 use crate::source::{SyntheticError, text::SyntheticTextConfig, text::SyntheticTextSource};
 use chutoro_core::{DataSource, DataSourceError, MetricDescriptor};
@@ -9,6 +10,7 @@ use generation::{
     validate_manifold_config,
 };
 use rand::{Rng, SeedableRng, rngs::SmallRng};
+pub use variable_density::{DensityRampConfig, NestedClusterConfig};
 
 /// Legacy uniform random vector configuration.
 #[derive(Clone, Debug)]
@@ -148,7 +150,12 @@ impl SyntheticSource {
         validate_blob_config(config)?;
 
         let scales = resolve_axis_scales(&config.anisotropy, config.dimensions)?;
-        let centroids = build_blob_centroids(config, &mut SmallRng::seed_from_u64(config.seed));
+        let centroids = build_blob_centroids(
+            config.cluster_count,
+            config.dimensions,
+            config.separation,
+            &mut SmallRng::seed_from_u64(config.seed),
+        );
         let mut rng = SmallRng::seed_from_u64(config.seed ^ 0xA5A5_A5A5_A5A5_A5A5_u64);
         let total = checked_total(config.point_count, config.dimensions)?;
         let mut data = Vec::with_capacity(total);
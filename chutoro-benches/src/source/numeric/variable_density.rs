@@ -0,0 +1,361 @@
+//! Variable-density generators: nested clusters, density-ramped blobs, and
+//! uniform background noise.
+//!
+//! These cover the cases density-based clustering is built for and that
+//! well-separated equal-width blobs never exercise: clusters that only
+//! separate at a finer density level, neighbouring clusters whose densities
+//! differ by an order of magnitude, and sparse noise that should not attach
+//! to any cluster. Every generator returns ground-truth labels alongside the
+//! source so ARI can be scored against them.
+
+use rand::{Rng, SeedableRng, rngs::SmallRng, seq::index};
+
+use super::{
+    SyntheticSource, checked_total,
+    generation::{build_blob_centroids, standard_normal_sample, validate_float_param},
+    validate_basic_numeric_config,
+};
+use crate::source::SyntheticError;
+
+/// Configuration for clusters nested inside parent clusters.
+///
+/// Parents sit `parent_separation` from the origin and each holds
+/// `children_per_parent` tight clusters `child_separation` from its centre,
+/// so the hierarchy splits twice: once between parents and again within
+/// each parent.
+#[derive(Clone, Debug)]
+pub struct NestedClusterConfig {
+    /// Number of points to generate.
+    pub point_count: usize,
+    /// Dimensionality of each vector.
+    pub dimensions: usize,
+    /// Number of top-level clusters.
+    pub parent_count: usize,
+    /// Number of child clusters inside each parent.
+    pub children_per_parent: usize,
+    /// Distance of each parent centre from the origin.
+    pub parent_separation: f32,
+    /// Distance of each child centre from its parent centre; must be smaller
+    /// than `parent_separation`.
+    pub child_separation: f32,
+    /// Standard deviation of each child cluster.
+    pub spread: f32,
+    /// RNG seed for reproducibility.
+    pub seed: u64,
+}
+
+impl NestedClusterConfig {
+    /// Returns the parent of a child-cluster label produced by
+    /// [`SyntheticSource::generate_nested_clusters_with_labels`].
+    ///
+    /// Returns `None` when `children_per_parent` is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_benches::source::NestedClusterConfig;
+    ///
+    /// let config = NestedClusterConfig {
+    ///     point_count: 60,
+    ///     dimensions: 2,
+    ///     parent_count: 2,
+    ///     children_per_parent: 3,
+    ///     parent_separation: 20.0,
+    ///     child_separation: 3.0,
+    ///     spread: 0.3,
+    ///     seed: 7,
+    /// };
+    /// assert_eq!(config.parent_label(4), Some(1));
+    /// ```
+    #[must_use]
+    pub const fn parent_label(&self, label: usize) -> Option<usize> {
+        label.checked_div(self.children_per_parent)
+    }
+}
+
+/// Configuration for Gaussian blobs whose spread ramps across clusters.
+///
+/// Every cluster receives the same number of points, so cluster `0`, with
+/// `min_spread`, is the densest and the last cluster, with `max_spread`, the
+/// sparsest; spreads in between are linearly interpolated.
+#[derive(Clone, Debug)]
+pub struct DensityRampConfig {
+    /// Number of points to generate.
+    pub point_count: usize,
+    /// Dimensionality of each vector.
+    pub dimensions: usize,
+    /// Number of Gaussian clusters.
+    pub cluster_count: usize,
+    /// Distance of each cluster centre from the origin.
+    pub separation: f32,
+    /// Standard deviation of the densest cluster.
+    pub min_spread: f32,
+    /// Standard deviation of the sparsest cluster.
+    pub max_spread: f32,
+    /// RNG seed for reproducibility.
+    pub seed: u64,
+}
+
+impl SyntheticSource {
+    /// Generates clusters nested inside parent clusters and returns the
+    /// child-cluster label of each point.
+    ///
+    /// Labels number child clusters `parent * children_per_parent + child`
+    /// in round-robin order; [`NestedClusterConfig::parent_label`] recovers
+    /// the parent for coarse-level scoring.
+    ///
+    /// # Errors
+    /// Returns [`SyntheticError`] when the configuration is invalid.
+    #[expect(
+        clippy::float_arithmetic,
+        reason = "child centres are offsets from their parent centre"
+    )]
+    pub fn generate_nested_clusters_with_labels(
+        config: &NestedClusterConfig,
+    ) -> Result<(Self, Vec<usize>), SyntheticError> {
+        validate_basic_numeric_config(config.point_count, config.dimensions)?;
+        validate_nested_config(config)?;
+
+        let mut rng = SmallRng::seed_from_u64(config.seed);
+        let parents = build_blob_centroids(
+            config.parent_count,
+            config.dimensions,
+            config.parent_separation,
+            &mut rng,
+        );
+        let offsets = build_blob_centroids(
+            config.children_per_parent,
+            config.dimensions,
+            config.child_separation,
+            &mut rng,
+        );
+        let centroids: Vec<Vec<f32>> = parents
+            .iter()
+            .flat_map(|parent| {
+                offsets.iter().map(move |offset| {
+                    parent
+                        .iter()
+                        .zip(offset)
+                        .map(|(centre, shift)| centre + shift)
+                        .collect()
+                })
+            })
+            .collect();
+        let spreads = vec![config.spread; centroids.len()];
+        let (data, labels) =
+            sample_round_robin(&centroids, &spreads, config.point_count, &mut rng)?;
+        let source = Self::from_parts(
+            "synthetic-nested-clusters",
+            data,
+            config.point_count,
+            config.dimensions,
+        )?;
+        Ok((source, labels))
+    }
+
+    /// Generates blobs of increasing spread and returns the cluster label of
+    /// each point in round-robin order.
+    ///
+    /// # Errors
+    /// Returns [`SyntheticError`] when the configuration is invalid.
+    pub fn generate_density_ramp_with_labels(
+        config: &DensityRampConfig,
+    ) -> Result<(Self, Vec<usize>), SyntheticError> {
+        validate_basic_numeric_config(config.point_count, config.dimensions)?;
+        validate_ramp_config(config)?;
+
+        let mut rng = SmallRng::seed_from_u64(config.seed);
+        let centroids = build_blob_centroids(
+            config.cluster_count,
+            config.dimensions,
+            config.separation,
+            &mut rng,
+        );
+        let spreads = ramp_spreads(config);
+        let (data, labels) =
+            sample_round_robin(&centroids, &spreads, config.point_count, &mut rng)?;
+        let source = Self::from_parts(
+            "synthetic-density-ramp",
+            data,
+            config.point_count,
+            config.dimensions,
+        )?;
+        Ok((source, labels))
+    }
+
+    /// Replaces a `noise_fraction` share of the points with uniform noise
+    /// drawn from the data's bounding box.
+    ///
+    /// `labels` must hold one ground-truth label per point, as returned by
+    /// the labelled generators. Replaced points are relabelled with one more
+    /// than the largest existing label, so ARI treats noise as its own
+    /// cluster, matching how a clustering run reports its noise label.
+    ///
+    /// # Errors
+    /// Returns [`SyntheticError::LabelCountMismatch`] when `labels` does not
+    /// match the point count, and [`SyntheticError::InvalidFloatParameter`]
+    /// when `noise_fraction` is not in `[0.0, 1.0)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_benches::source::{Anisotropy, GaussianBlobConfig, SyntheticSource};
+    ///
+    /// let (blobs, labels) = SyntheticSource::generate_gaussian_blobs_with_labels(
+    ///     &GaussianBlobConfig {
+    ///         point_count: 100,
+    ///         dimensions: 4,
+    ///         cluster_count: 2,
+    ///         separation: 5.0,
+    ///         anisotropy: Anisotropy::Isotropic(0.5),
+    ///         seed: 3,
+    ///     },
+    /// )?;
+    /// let (_noisy, noisy_labels) = blobs.with_background_noise(labels, 0.1, 3)?;
+    /// assert_eq!(noisy_labels.iter().filter(|&&label| label == 2).count(), 10);
+    /// # Ok::<(), chutoro_benches::source::SyntheticError>(())
+    /// ```
+    pub fn with_background_noise(
+        mut self,
+        mut labels: Vec<usize>,
+        noise_fraction: f32,
+        seed: u64,
+    ) -> Result<(Self, Vec<usize>), SyntheticError> {
+        if labels.len() != self.point_count {
+            return Err(SyntheticError::LabelCountMismatch {
+                expected: self.point_count,
+                actual: labels.len(),
+            });
+        }
+        let noise_count = noise_count(self.point_count, noise_fraction)?;
+        let noise_label = labels
+            .iter()
+            .max()
+            .map_or(0, |label| label.saturating_add(1));
+        let bounds = bounding_box(&self.data, self.dimensions);
+        let mut rng = SmallRng::seed_from_u64(seed);
+        for point in index::sample(&mut rng, self.point_count, noise_count) {
+            if let Some(label) = labels.get_mut(point) {
+                *label = noise_label;
+            }
+            let start = point.saturating_mul(self.dimensions);
+            let coordinates = self.data.iter_mut().skip(start).take(self.dimensions);
+            for (value, &(low, high)) in coordinates.zip(&bounds) {
+                *value = rng.gen_range(low..=high);
+            }
+        }
+        Ok((self, labels))
+    }
+}
+
+fn validate_nested_config(config: &NestedClusterConfig) -> Result<(), SyntheticError> {
+    let cluster_count = config
+        .parent_count
+        .checked_mul(config.children_per_parent)
+        .ok_or(SyntheticError::Overflow)?;
+    validate_cluster_count(cluster_count, config.point_count)?;
+    validate_float_param(config.parent_separation, "parent_separation", false)?;
+    validate_float_param(config.child_separation, "child_separation", false)?;
+    validate_float_param(config.spread, "spread", false)?;
+    if config.child_separation >= config.parent_separation {
+        return Err(SyntheticError::InvalidFloatParameter {
+            parameter: "child_separation",
+        });
+    }
+    Ok(())
+}
+
+fn validate_ramp_config(config: &DensityRampConfig) -> Result<(), SyntheticError> {
+    validate_cluster_count(config.cluster_count, config.point_count)?;
+    validate_float_param(config.separation, "separation", false)?;
+    validate_float_param(config.min_spread, "min_spread", false)?;
+    validate_float_param(config.max_spread, "max_spread", false)?;
+    if config.max_spread < config.min_spread {
+        return Err(SyntheticError::InvalidFloatParameter {
+            parameter: "max_spread",
+        });
+    }
+    Ok(())
+}
+
+const fn validate_cluster_count(
+    cluster_count: usize,
+    point_count: usize,
+) -> Result<(), SyntheticError> {
+    if cluster_count == 0 {
+        return Err(SyntheticError::ZeroClusters);
+    }
+    if cluster_count > point_count {
+        return Err(SyntheticError::ClusterCountExceedsPointCount {
+            cluster_count,
+            point_count,
+        });
+    }
+    Ok(())
+}
+
+#[expect(
+    clippy::float_arithmetic,
+    clippy::cast_precision_loss,
+    reason = "spreads are linearly interpolated by cluster index"
+)]
+fn ramp_spreads(config: &DensityRampConfig) -> Vec<f32> {
+    let steps = config.cluster_count.saturating_sub(1).max(1) as f32;
+    (0..config.cluster_count)
+        .map(|cluster| {
+            let position = cluster as f32 / steps;
+            config.min_spread + (config.max_spread - config.min_spread) * position
+        })
+        .collect()
+}
+
+/// Draws points around `centroids` in round-robin order, using the matching
+/// entry of `spreads` as each cluster's standard deviation.
+#[expect(
+    clippy::float_arithmetic,
+    reason = "Gaussian data generation requires floating-point arithmetic"
+)]
+fn sample_round_robin(
+    centroids: &[Vec<f32>],
+    spreads: &[f32],
+    point_count: usize,
+    rng: &mut SmallRng,
+) -> Result<(Vec<f32>, Vec<usize>), SyntheticError> {
+    let dimensions = centroids.first().map_or(0, Vec::len);
+    let mut data = Vec::with_capacity(checked_total(point_count, dimensions)?);
+    let mut labels = Vec::with_capacity(point_count);
+    let clusters = centroids.iter().zip(spreads).enumerate().cycle();
+    for (label, (centroid, spread)) in clusters.take(point_count) {
+        labels.push(label);
+        for centre in centroid {
+            data.push(*centre + standard_normal_sample(rng)? * *spread);
+        }
+    }
+    Ok((data, labels))
+}
+
+#[expect(
+    clippy::float_arithmetic,
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    reason = "the noise share is a fraction of the point count, rounded to a whole point"
+)]
+fn noise_count(point_count: usize, noise_fraction: f32) -> Result<usize, SyntheticError> {
+    if !(0.0..1.0).contains(&noise_fraction) {
+        return Err(SyntheticError::InvalidFloatParameter {
+            parameter: "noise_fraction",
+        });
+    }
+    Ok(((point_count as f64) * f64::from(noise_fraction)).round() as usize)
+}
+
+/// Returns the `(min, max)` of each coordinate across all points.
+fn bounding_box(data: &[f32], dimensions: usize) -> Vec<(f32, f32)> {
+    let mut bounds = vec![(f32::INFINITY, f32::NEG_INFINITY); dimensions];
+    for point in data.chunks_exact(dimensions.max(1)) {
+        for (value, (low, high)) in point.iter().zip(&mut bounds) {
+            *low = low.min(*value);
+            *high = high.max(*value);
+        }
+    }
+    bounds
+}
@@ -67,6 +67,11 @@ impl SyntheticTextSource {
         })
     }
 
+    /// Wraps already-prepared strings, such as a tokenised real-world corpus.
+    pub(crate) const fn from_lines(name: &'static str, data: Vec<String>) -> Self {
+        Self { data, name }
+    }
+
     /// Returns a read-only view of generated strings.
     #[must_use]
     pub fn lines(&self) -> &[String] {
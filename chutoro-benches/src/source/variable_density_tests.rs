@@ -0,0 +1,270 @@
+//! Unit tests for the nested, density-ramped, and noisy generators.
+
+use super::{
+    Anisotropy, DensityRampConfig, GaussianBlobConfig, NestedClusterConfig, SyntheticError,
+    SyntheticSource,
+};
+use chutoro_core::DataSource;
+use rstest::{fixture, rstest};
+
+#[fixture]
+fn nested_config() -> NestedClusterConfig {
+    NestedClusterConfig {
+        point_count: 120,
+        dimensions: 4,
+        parent_count: 2,
+        children_per_parent: 3,
+        parent_separation: 40.0,
+        child_separation: 4.0,
+        spread: 0.3,
+        seed: 17,
+    }
+}
+
+#[fixture]
+fn ramp_config() -> DensityRampConfig {
+    DensityRampConfig {
+        point_count: 300,
+        dimensions: 4,
+        cluster_count: 3,
+        separation: 30.0,
+        min_spread: 0.2,
+        max_spread: 2.0,
+        seed: 23,
+    }
+}
+
+/// Returns the mean distance from each cluster's first point to the rest of
+/// that cluster, indexed by label.
+#[expect(
+    clippy::float_arithmetic,
+    clippy::cast_precision_loss,
+    reason = "test averages distances per cluster"
+)]
+fn mean_intra_cluster_distance(source: &SyntheticSource, labels: &[usize]) -> Vec<f32> {
+    let cluster_count = labels.iter().max().map_or(0, |label| label + 1);
+    (0..cluster_count)
+        .map(|cluster| {
+            let members: Vec<_> = (0..labels.len())
+                .filter(|&point| labels.get(point) == Some(&cluster))
+                .collect();
+            let anchor = *members.first().expect("every cluster has members");
+            let total: f32 = members
+                .iter()
+                .skip(1)
+                .map(|&point| source.distance(anchor, point).expect("distance"))
+                .sum();
+            total / (members.len() - 1) as f32
+        })
+        .collect()
+}
+
+#[rstest]
+fn nested_generator_labels_every_child_cluster(nested_config: NestedClusterConfig) {
+    let (source, labels) = SyntheticSource::generate_nested_clusters_with_labels(&nested_config)
+        .expect("nested generation should succeed");
+
+    assert_eq!(source.len(), nested_config.point_count);
+    assert_eq!(source.dimensions(), nested_config.dimensions);
+    let expected: Vec<_> = (0..6).cycle().take(nested_config.point_count).collect();
+    assert_eq!(labels, expected);
+}
+
+#[rstest]
+fn nested_children_sit_closer_to_siblings_than_to_other_parents(
+    nested_config: NestedClusterConfig,
+) {
+    let (source, labels) = SyntheticSource::generate_nested_clusters_with_labels(&nested_config)
+        .expect("nested generation should succeed");
+
+    // Points 0..6 are one from each child cluster: 0-2 under parent 0 and
+    // 3-5 under parent 1.
+    let sibling = source.distance(0, 1).expect("distance");
+    let cousin = source.distance(0, 3).expect("distance");
+    let parent_of = |point: usize| {
+        labels
+            .get(point)
+            .and_then(|&label| nested_config.parent_label(label))
+    };
+    assert_eq!(parent_of(1), Some(0));
+    assert_eq!(parent_of(3), Some(1));
+    assert!(sibling < cousin, "sibling {sibling} vs cousin {cousin}");
+}
+
+#[rstest]
+#[case::child_wider_than_parent(40.0, "child_separation")]
+#[case::child_equal_to_parent(4.0, "child_separation")]
+fn nested_generator_rejects_children_outside_parents(
+    nested_config: NestedClusterConfig,
+    #[case] parent_separation: f32,
+    #[case] parameter: &'static str,
+) {
+    let error = SyntheticSource::generate_nested_clusters_with_labels(&NestedClusterConfig {
+        parent_separation,
+        child_separation: 40.0,
+        ..nested_config
+    })
+    .expect_err("children must fit inside their parents");
+
+    assert!(matches!(
+        error,
+        SyntheticError::InvalidFloatParameter { parameter: actual } if actual == parameter
+    ));
+}
+
+#[rstest]
+fn nested_generator_rejects_more_clusters_than_points(nested_config: NestedClusterConfig) {
+    let error = SyntheticSource::generate_nested_clusters_with_labels(&NestedClusterConfig {
+        point_count: 5,
+        ..nested_config
+    })
+    .expect_err("six clusters cannot fit in five points");
+
+    assert!(matches!(
+        error,
+        SyntheticError::ClusterCountExceedsPointCount {
+            cluster_count: 6,
+            point_count: 5,
+        }
+    ));
+}
+
+#[rstest]
+fn density_ramp_spreads_increase_across_clusters(ramp_config: DensityRampConfig) {
+    let (source, labels) = SyntheticSource::generate_density_ramp_with_labels(&ramp_config)
+        .expect("density ramp generation should succeed");
+
+    let spreads = mean_intra_cluster_distance(&source, &labels);
+    assert_eq!(spreads.len(), ramp_config.cluster_count);
+    assert!(
+        spreads.is_sorted_by(|narrower, wider| narrower < wider),
+        "cluster spreads should increase: {spreads:?}"
+    );
+}
+
+#[rstest]
+fn density_ramp_rejects_inverted_spreads(ramp_config: DensityRampConfig) {
+    let error = SyntheticSource::generate_density_ramp_with_labels(&DensityRampConfig {
+        min_spread: 3.0,
+        ..ramp_config
+    })
+    .expect_err("min spread above max spread must fail");
+
+    assert!(matches!(
+        error,
+        SyntheticError::InvalidFloatParameter {
+            parameter: "max_spread"
+        }
+    ));
+}
+
+#[rstest]
+fn density_ramp_is_deterministic(ramp_config: DensityRampConfig) {
+    let (left, left_labels) = SyntheticSource::generate_density_ramp_with_labels(&ramp_config)
+        .expect("first generation should succeed");
+    let (right, right_labels) = SyntheticSource::generate_density_ramp_with_labels(&ramp_config)
+        .expect("second generation should succeed");
+
+    assert_eq!(left.raw_data(), right.raw_data());
+    assert_eq!(left_labels, right_labels);
+}
+
+#[rstest]
+#[case::none(0.0, 0)]
+#[case::tenth(0.1, 30)]
+#[case::most(0.9, 270)]
+fn background_noise_relabels_the_requested_share(
+    ramp_config: DensityRampConfig,
+    #[case] noise_fraction: f32,
+    #[case] expected_noise: usize,
+) {
+    let (source, labels) = SyntheticSource::generate_density_ramp_with_labels(&ramp_config)
+        .expect("density ramp generation should succeed");
+    let (noisy, noisy_labels) = source
+        .with_background_noise(labels.clone(), noise_fraction, 5)
+        .expect("noise injection should succeed");
+
+    let noise_label = ramp_config.cluster_count;
+    assert_eq!(noisy.len(), ramp_config.point_count);
+    assert_eq!(
+        noisy_labels
+            .iter()
+            .filter(|&&label| label == noise_label)
+            .count(),
+        expected_noise
+    );
+    for (before, after) in labels.iter().zip(&noisy_labels) {
+        assert!(after == before || *after == noise_label);
+    }
+}
+
+#[rstest]
+fn background_noise_stays_inside_the_bounding_box() {
+    let (source, labels) =
+        SyntheticSource::generate_gaussian_blobs_with_labels(&GaussianBlobConfig {
+            point_count: 200,
+            dimensions: 3,
+            cluster_count: 4,
+            separation: 10.0,
+            anisotropy: Anisotropy::Isotropic(0.5),
+            seed: 31,
+        })
+        .expect("blob generation should succeed");
+    let bounds: Vec<_> = (0..3)
+        .map(|axis| {
+            let values = source.raw_data().iter().skip(axis).step_by(3);
+            let low = values.clone().copied().fold(f32::INFINITY, f32::min);
+            let high = values.copied().fold(f32::NEG_INFINITY, f32::max);
+            (low, high)
+        })
+        .collect();
+
+    let (noisy, _labels) = source
+        .with_background_noise(labels, 0.5, 9)
+        .expect("noise injection should succeed");
+
+    for point in noisy.raw_data().chunks_exact(3) {
+        for (value, (low, high)) in point.iter().zip(&bounds) {
+            assert!((low..=high).contains(&value));
+        }
+    }
+}
+
+#[rstest]
+#[case::negative(-0.1)]
+#[case::all_noise(1.0)]
+#[case::not_a_number(f32::NAN)]
+fn background_noise_rejects_invalid_fractions(
+    ramp_config: DensityRampConfig,
+    #[case] noise_fraction: f32,
+) {
+    let (source, labels) = SyntheticSource::generate_density_ramp_with_labels(&ramp_config)
+        .expect("density ramp generation should succeed");
+    let error = source
+        .with_background_noise(labels, noise_fraction, 5)
+        .expect_err("invalid noise fraction must fail");
+
+    assert!(matches!(
+        error,
+        SyntheticError::InvalidFloatParameter {
+            parameter: "noise_fraction"
+        }
+    ));
+}
+
+#[rstest]
+fn background_noise_rejects_mismatched_labels(ramp_config: DensityRampConfig) {
+    let (source, _labels) = SyntheticSource::generate_density_ramp_with_labels(&ramp_config)
+        .expect("density ramp generation should succeed");
+    let error = source
+        .with_background_noise(vec![0; 3], 0.1, 5)
+        .expect_err("label count must match point count");
+
+    assert!(matches!(
+        error,
+        SyntheticError::LabelCountMismatch {
+            expected: 300,
+            actual: 3,
+        }
+    ));
+}
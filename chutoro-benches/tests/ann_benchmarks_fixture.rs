@@ -0,0 +1,49 @@
+//! Tests that the ann-benchmarks reader understands a file written by h5py.
+//!
+//! The fixture comes from `scripts/make_ann_fixture.py`, which writes it the
+//! way ann-benchmarks writes its published datasets, so a misreading of the
+//! format cannot hide behind a writer that shares the reader's assumptions.
+
+use std::{num::NonZeroUsize, path::PathBuf};
+
+use chutoro_benches::ann_benchmarks::{AnnConfig, AnnDataset, AnnMetric, benchmark_dataset};
+use rstest::{fixture, rstest};
+
+#[fixture]
+fn fixture_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/ann/grid-2-euclidean.hdf5")
+}
+
+#[rstest]
+#[ignore = "needs the fixture written by scripts/make_ann_fixture.py, which requires h5py"]
+fn h5py_files_load_with_their_shapes_and_metric(fixture_path: PathBuf) {
+    let dataset = AnnDataset::load(&fixture_path, None).expect("h5py fixture must load");
+
+    assert_eq!(dataset.name(), "grid-2-euclidean");
+    assert_eq!(dataset.metric(), AnnMetric::Euclidean);
+    assert_eq!(dataset.dimensions(), 2);
+    assert_eq!(dataset.train_len(), 100);
+    assert_eq!(dataset.query_len(), 5);
+    assert_eq!(dataset.ground_truth_depth(), 20);
+    // The query at (0.3, 0.6) is closest to the grid point (0, 1).
+    let nearest = dataset.ground_truth(0).expect("query 0 exists");
+    assert_eq!(nearest.first(), Some(&10));
+}
+
+#[rstest]
+#[ignore = "needs the fixture written by scripts/make_ann_fixture.py, which requires h5py"]
+fn h5py_files_benchmark_with_full_recall(fixture_path: PathBuf) {
+    let dataset = AnnDataset::load(&fixture_path, None).expect("h5py fixture must load");
+    let config = AnnConfig {
+        k: NonZeroUsize::new(5).expect("non-zero"),
+        ef_search: vec![NonZeroUsize::new(32).expect("non-zero")],
+        ..AnnConfig::new(fixture_path)
+    };
+
+    let report = benchmark_dataset(&dataset, &config).expect("benchmark must run");
+
+    let [measurement] = report.measurements.as_slice() else {
+        panic!("one search width was requested");
+    };
+    assert_eq!(measurement.recall.hits, measurement.recall.total);
+}
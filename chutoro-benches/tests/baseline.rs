@@ -0,0 +1,104 @@
+//! Tests for measuring, storing, and comparing regression baselines.
+
+use chutoro_benches::baseline::{
+    BASELINE_VERSION, Baseline, BaselineCase, BaselineError, RegressionMetric, Tolerance, compare,
+};
+use rstest::rstest;
+use tempfile::TempDir;
+
+const SMALL_CASE: BaselineCase = BaselineCase {
+    name: "small",
+    point_count: 200,
+    cluster_count: 4,
+    max_connections: 8,
+    ef_construction: 32,
+    min_cluster_size: 5,
+};
+
+fn measure_small() -> Result<Baseline, BaselineError> {
+    Baseline::measure(&[SMALL_CASE])
+}
+
+#[rstest]
+fn measurement_records_every_metric() {
+    let measured = measure_small().expect("small case must run");
+    let record = measured.record("small").expect("case must be recorded");
+
+    assert_eq!(measured.version, BASELINE_VERSION);
+    assert_eq!(record.point_count, 200);
+    assert!(
+        (0.0..=1.0).contains(&record.recall),
+        "recall {}",
+        record.recall
+    );
+    assert!(record.recall > 0.5, "recall {}", record.recall);
+    assert!(record.ari > 0.5, "ari {}", record.ari);
+    if cfg!(target_os = "linux") {
+        assert!(record.peak_rss_bytes.is_some());
+    }
+}
+
+#[rstest]
+fn baselines_round_trip_through_json() {
+    let measured = measure_small().expect("small case must run");
+    let dir = TempDir::new().expect("temp dir must be created");
+    let path = dir.path().join("nested").join("baseline.json");
+
+    measured.write(&path).expect("baseline must be written");
+    let loaded = Baseline::load(&path).expect("baseline must load");
+
+    assert_eq!(loaded, measured);
+    assert!(compare(&loaded, &measured, Tolerance::default()).is_empty());
+}
+
+#[rstest]
+fn comparison_flags_each_regressed_metric() {
+    let measured = measure_small().expect("small case must run");
+    let mut current = measured.clone();
+    let record = current.records.first_mut().expect("one record");
+    record.runtime_millis = record.runtime_millis.saturating_mul(2).saturating_add(10);
+    record.peak_rss_bytes = record.peak_rss_bytes.map(|bytes| bytes.saturating_mul(2));
+    record.recall = 0.0;
+    record.ari = -1.0;
+
+    let metrics: Vec<_> = compare(&measured, &current, Tolerance::default())
+        .into_iter()
+        .map(|regression| regression.metric)
+        .collect();
+
+    assert!(metrics.contains(&RegressionMetric::Runtime));
+    assert!(metrics.contains(&RegressionMetric::Recall));
+    assert!(metrics.contains(&RegressionMetric::Ari));
+}
+
+#[rstest]
+fn cases_missing_from_the_new_run_regress() {
+    let measured = measure_small().expect("small case must run");
+    let empty = Baseline {
+        version: BASELINE_VERSION,
+        records: Vec::new(),
+    };
+
+    let regressions = compare(&measured, &empty, Tolerance::default());
+
+    assert_eq!(regressions.len(), 1);
+    assert_eq!(
+        regressions.first().map(|regression| regression.metric),
+        Some(RegressionMetric::Missing)
+    );
+    assert!(compare(&empty, &measured, Tolerance::default()).is_empty());
+}
+
+#[rstest]
+fn load_rejects_other_versions() {
+    let dir = TempDir::new().expect("temp dir must be created");
+    let path = dir.path().join("baseline.json");
+    std::fs::write(&path, r#"{"version": 99, "records": []}"#).expect("file must be written");
+
+    let err = Baseline::load(&path).expect_err("version must be rejected");
+
+    assert!(matches!(
+        err,
+        BaselineError::UnsupportedVersion { version: 99, .. }
+    ));
+}
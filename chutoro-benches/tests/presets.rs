@@ -0,0 +1,65 @@
+//! Tests that the parameter presets keep their documented ordering on the
+//! regression datasets.
+//!
+//! Each case runs on a one-thread Rayon pool. The HNSW build is seeded, but
+//! parallel insertion reorders links between runs and moved `Balanced`
+//! recall below its threshold. Sequential runs vary by a few thousandths,
+//! well inside the margins asserted here. Run time is not asserted because
+//! it depends on the machine; the measured ratios are recorded in the
+//! `Preset` docs.
+
+use chutoro_benches::baseline::{Baseline, BaselineCase, BaselineError, BaselineRecord};
+use chutoro_core::{HierarchyConfig, HnswParams, Preset};
+use rstest::rstest;
+
+const PRESETS: [(Preset, &str); 3] = [
+    (Preset::FastApproximate, "fast"),
+    (Preset::Balanced, "balanced"),
+    (Preset::HighRecall, "high-recall"),
+];
+
+fn measure(preset: Preset, name: &'static str) -> Result<Baseline, BaselineError> {
+    let params = HnswParams::preset(preset);
+    let case = BaselineCase {
+        name,
+        point_count: 1_000,
+        cluster_count: 8,
+        max_connections: params.max_connections(),
+        ef_construction: params.ef_construction(),
+        min_cluster_size: HierarchyConfig::preset(preset).min_cluster_size().get(),
+    };
+    Baseline::measure(&[case])
+}
+
+fn measure_sequentially(
+    preset: Preset,
+    name: &'static str,
+) -> Result<Option<BaselineRecord>, Box<dyn std::error::Error>> {
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(1).build()?;
+    let measured = pool.install(|| measure(preset, name))?;
+    Ok(measured.records.into_iter().next())
+}
+
+#[rstest]
+fn presets_keep_their_recall_ordering() {
+    let [fast, balanced, high] = PRESETS.map(|(preset, name)| {
+        measure_sequentially(preset, name)
+            .expect("preset case must run")
+            .expect("one record")
+    });
+
+    assert!(
+        fast.recall <= balanced.recall && balanced.recall <= high.recall,
+        "recall must not fall as presets get more thorough"
+    );
+    assert!(fast.recall >= 0.9, "fast recall {}", fast.recall);
+    assert!(
+        balanced.recall >= 0.98,
+        "balanced recall {}",
+        balanced.recall
+    );
+    assert!(fast.ari > 0.8, "fast ari {}", fast.ari);
+    for record in [&balanced, &high] {
+        assert!(record.ari > 0.95, "{} ari {}", record.case, record.ari);
+    }
+}
@@ -0,0 +1,56 @@
+//! Short soak runs exercising concurrent inserts and invariant checks.
+
+use std::{num::NonZeroUsize, time::Duration};
+
+use chutoro_benches::soak::{SoakConfig, SoakError, config_from_lookup, run_soak};
+use rstest::rstest;
+
+/// Returns a single-round configuration, or `None` for a zero size.
+fn short_config(initial_points: usize, max_points: usize) -> Option<SoakConfig> {
+    Some(SoakConfig {
+        duration: Duration::ZERO,
+        initial_points: NonZeroUsize::new(initial_points)?,
+        max_points: NonZeroUsize::new(max_points)?,
+        ..SoakConfig::default()
+    })
+}
+
+#[rstest]
+fn a_round_inserts_and_searches_every_point() {
+    let config = short_config(48, 48).expect("test sizes are non-zero");
+
+    let stats = run_soak(&config).expect("soak must run");
+
+    assert_eq!(stats.rounds, 1);
+    assert_eq!(stats.inserts, 48);
+    assert_eq!(stats.searches, 47);
+    assert_eq!(stats.max_points, 48);
+    assert!(stats.checks >= 1);
+    assert!(stats.passed(), "settled violations: {}", stats.settled);
+}
+
+#[rstest]
+fn rounds_grow_until_the_duration_elapses() {
+    let config = SoakConfig {
+        duration: Duration::from_millis(200),
+        ..short_config(16, 64).expect("test sizes are non-zero")
+    };
+
+    let stats = run_soak(&config).expect("soak must run");
+
+    assert!(stats.rounds >= 2, "expected several rounds, got {stats:?}");
+    assert!(stats.max_points > 16);
+    assert!(stats.max_points <= 64);
+    assert!(stats.passed(), "settled violations: {}", stats.settled);
+}
+
+#[rstest]
+#[case::workers("CHUTORO_SOAK_WORKERS", "0")]
+#[case::duration("CHUTORO_SOAK_DURATION_SECS", "soon")]
+fn invalid_settings_are_rejected(#[case] name: &str, #[case] value: &str) {
+    let err = config_from_lookup(|key| (key == name).then(|| value.to_owned()))
+        .expect_err("setting must be rejected");
+
+    assert!(matches!(err, SoakError::InvalidSetting { .. }));
+    assert!(err.to_string().contains(name), "unexpected error: {err}");
+}
@@ -0,0 +1,384 @@
+//! Argument definitions for the chutoro CLI.
+//!
+//! Run options are optional at parse time so values loaded from a `--config`
+//! file can fill the gaps; defaults are applied only after that merge.
+
+use std::path::PathBuf;
+
+use chutoro_core::{HnswError, HnswParams};
+use chutoro_providers_image::ImageFeature;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
+
+use super::batch::BatchCommand;
+use super::config::parse_byte_size;
+use super::details::SummaryDetail;
+use super::render::OutputArgs;
+use super::stability::StabilityCommand;
+
+/// Minimum cluster size applied when neither flags nor config set one.
+pub(super) const DEFAULT_MIN_CLUSTER_SIZE: usize = 5;
+
+/// Thumbnail side used by `--feature pixels` when none is given.
+pub(super) const DEFAULT_PIXEL_SIDE: u32 = 16;
+
+/// Top-level CLI options parsed by [`clap`].
+#[derive(Debug, Parser, Clone)]
+#[command(name = "chutoro", about = "Execute the chutoro clustering pipeline.")]
+pub struct Cli {
+    /// Command to execute.
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+/// Supported CLI commands.
+#[derive(Debug, Subcommand, Clone)]
+pub enum Command {
+    /// Execute the clustering pipeline.
+    Run(RunCommand),
+    /// Manage run configuration files.
+    Config(ConfigCommand),
+    /// Summarize a Parquet input and estimate the cost of clustering it.
+    Inspect(InspectCommand),
+    /// Cluster an input under several seeds and report how much the runs agree.
+    Stability(StabilityCommand),
+    /// Run every job listed in a jobs file and report their outcomes.
+    Batch(BatchCommand),
+}
+
+impl Command {
+    pub(super) fn name(&self) -> &'static str {
+        match self {
+            Command::Run(_) => "run",
+            Command::Config(_) => "config",
+            Command::Inspect(_) => "inspect",
+            Command::Stability(_) => "stability",
+            Command::Batch(_) => "batch",
+        }
+    }
+}
+
+/// Options accepted by the `run` command.
+#[derive(Debug, Args, Clone, Default)]
+pub struct RunCommand {
+    /// TOML file supplying run parameters; flags override its values.
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+
+    /// Minimum number of items per cluster [default: 5].
+    #[arg(long = "min-cluster-size", value_parser = clap::value_parser!(usize))]
+    pub min_cluster_size: Option<usize>,
+
+    /// Maximum estimated memory (in bytes) allowed for the pipeline.
+    ///
+    /// Supports human-readable suffixes: K, M, G, T (case-insensitive).
+    /// Example: `--max-bytes 2G` or `--max-bytes 2147483648`.
+    #[arg(long = "max-bytes", value_parser = parse_byte_size)]
+    pub max_bytes: Option<u64>,
+
+    /// HNSW index construction options.
+    #[command(flatten)]
+    pub hnsw: HnswArgs,
+
+    /// Summary output options.
+    #[command(flatten)]
+    pub output: OutputArgs,
+
+    /// Add a section to the summary; `clusters` prints one row per cluster
+    /// with its size, share of the data, exemplar row, and mean
+    /// intra-cluster distance.
+    #[arg(long, value_enum)]
+    pub detail: Option<SummaryDetail>,
+
+    /// Write a run manifest describing this run to the given JSON file.
+    #[arg(long)]
+    pub manifest: Option<PathBuf>,
+
+    /// Validate the parameters and data, probe distances, and estimate
+    /// memory without clustering.
+    #[arg(long = "dry-run")]
+    pub dry_run: bool,
+
+    /// Data source configuration; required unless supplied by `--config`.
+    #[command(subcommand)]
+    pub source: Option<RunSource>,
+}
+
+impl RunCommand {
+    /// Returns the minimum cluster size after applying the default.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_cli::cli::RunCommand;
+    ///
+    /// assert_eq!(RunCommand::default().effective_min_cluster_size(), 5);
+    /// ```
+    #[must_use]
+    pub fn effective_min_cluster_size(&self) -> usize {
+        self.min_cluster_size.unwrap_or(DEFAULT_MIN_CLUSTER_SIZE)
+    }
+}
+
+/// HNSW construction parameters exposed on the command line.
+#[derive(Debug, Args, Clone, Default)]
+pub struct HnswArgs {
+    /// Maximum neighbours per node on upper HNSW layers [default: 16].
+    #[arg(long = "hnsw-max-connections")]
+    pub max_connections: Option<usize>,
+
+    /// Candidate list width while building the HNSW index [default: 64].
+    #[arg(long = "hnsw-ef-construction")]
+    pub ef_construction: Option<usize>,
+
+    /// Seed for HNSW level sampling; fixes the index layout across runs.
+    #[arg(long = "hnsw-seed")]
+    pub seed: Option<u64>,
+}
+
+impl HnswArgs {
+    /// Builds [`HnswParams`] from the supplied values and library defaults.
+    ///
+    /// When only `max_connections` is given, `ef_construction` is raised to
+    /// at least that value so the pair stays valid.
+    ///
+    /// # Errors
+    /// Returns [`HnswError::InvalidParameters`] when the combination is
+    /// rejected by [`HnswParams::new`].
+    pub fn to_params(&self) -> Result<HnswParams, HnswError> {
+        let defaults = HnswParams::default();
+        let max_connections = self.max_connections.unwrap_or(defaults.max_connections());
+        let ef_construction = self
+            .ef_construction
+            .unwrap_or_else(|| defaults.ef_construction().max(max_connections));
+        let params = HnswParams::new(max_connections, ef_construction)?;
+        Ok(match self.seed {
+            Some(seed) => params.with_rng_seed(seed),
+            None => params,
+        })
+    }
+}
+
+/// Input data sources supported by the CLI.
+#[derive(Debug, Subcommand, Clone)]
+pub enum RunSource {
+    /// Execute against Parquet feature columns of floats or float lists.
+    Parquet(ParquetArgs),
+    /// Execute against a UTF-8 text corpus, one string per line.
+    Text(TextArgs),
+    /// Execute against a directory of images, clustering near-duplicates.
+    Images(ImageArgs),
+}
+
+impl RunSource {
+    pub(super) fn kind(&self) -> &'static str {
+        match self {
+            RunSource::Parquet(_) => "parquet",
+            RunSource::Text(_) => "text",
+            RunSource::Images(_) => "images",
+        }
+    }
+}
+
+/// Parquet ingestion arguments.
+#[derive(Debug, Args, Clone)]
+pub struct ParquetArgs {
+    /// Path to the Parquet file containing feature vectors.
+    pub path: PathBuf,
+
+    /// Feature columns, concatenated per row in the order given.
+    ///
+    /// Each column must be a `FixedSizeList<F, D>` or a scalar `F` column,
+    /// where `F` is `Float16` or `Float32` (or `Float64` with `--lossy-f64`).
+    /// Accepts a comma-separated list or repeated flags.
+    #[arg(
+        long = "columns",
+        visible_alias = "column",
+        value_delimiter = ',',
+        required = true
+    )]
+    pub columns: Vec<String>,
+
+    /// Override name for the data source (defaults to the file name).
+    #[arg(long)]
+    pub name: Option<String>,
+
+    /// Accept `Float64` feature columns, narrowing each value to `f32`.
+    #[arg(long = "lossy-f64")]
+    pub lossy_f64: bool,
+
+    /// Column copied into `--output` to identify each row (defaults to the
+    /// row index).
+    #[arg(long = "id-column", requires = "output")]
+    pub id_column: Option<String>,
+
+    /// Column of ground-truth labels to score the clustering against. The
+    /// summary then reports the adjusted Rand index and normalized mutual
+    /// information of the assignments.
+    #[arg(long = "label-column")]
+    pub label_column: Option<String>,
+
+    /// Write per-row cluster assignments and scores to this Parquet file.
+    #[arg(long)]
+    pub output: Option<PathBuf>,
+}
+
+/// Text ingestion arguments.
+#[derive(Debug, Args, Clone)]
+pub struct TextArgs {
+    /// Path to a UTF-8 text file with one string per line, or `-` for stdin.
+    ///
+    /// Files ending in `.gz` or `.zst` are decompressed transparently.
+    pub path: PathBuf,
+
+    /// Distance metric to use when comparing lines.
+    #[arg(long, value_enum)]
+    pub metric: TextMetric,
+
+    /// Override name for the data source (defaults to the file name).
+    #[arg(long)]
+    pub name: Option<String>,
+}
+
+/// Supported text metrics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TextMetric {
+    /// Compute Levenshtein edit distance between lines.
+    Levenshtein,
+}
+
+impl TextMetric {
+    pub(super) fn label(self) -> &'static str {
+        match self {
+            TextMetric::Levenshtein => "levenshtein",
+        }
+    }
+}
+
+/// Image folder ingestion arguments.
+#[derive(Debug, Args, Clone)]
+pub struct ImageArgs {
+    /// Directory searched recursively for BMP, GIF, JPEG, PNG, and WebP files.
+    pub path: PathBuf,
+
+    /// Feature extracted from each image; hashes use Hamming distance and
+    /// pixels use Euclidean distance.
+    #[arg(long, value_enum, default_value_t = ImageFeatureKind::Dhash)]
+    pub feature: ImageFeatureKind,
+
+    /// Thumbnail width and height for `--feature pixels`.
+    #[arg(long = "pixel-side", default_value_t = DEFAULT_PIXEL_SIDE)]
+    pub pixel_side: u32,
+
+    /// Override name for the data source (defaults to the directory name).
+    #[arg(long)]
+    pub name: Option<String>,
+}
+
+impl ImageArgs {
+    /// Returns the provider feature selected by `--feature` and
+    /// `--pixel-side`.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_cli::cli::{ImageArgs, ImageFeatureKind};
+    /// use chutoro_providers_image::ImageFeature;
+    ///
+    /// let args = ImageArgs {
+    ///     path: "photos".into(),
+    ///     feature: ImageFeatureKind::Pixels,
+    ///     pixel_side: 8,
+    ///     name: None,
+    /// };
+    /// assert_eq!(args.image_feature(), ImageFeature::Pixels { side: 8 });
+    /// ```
+    #[must_use]
+    pub fn image_feature(&self) -> ImageFeature {
+        match self.feature {
+            ImageFeatureKind::Dhash => ImageFeature::DHash,
+            ImageFeatureKind::Phash => ImageFeature::PHash,
+            ImageFeatureKind::Pixels => ImageFeature::Pixels {
+                side: self.pixel_side,
+            },
+        }
+    }
+}
+
+/// Supported image features.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageFeatureKind {
+    /// 64-bit difference hash; fast and robust to rescaling.
+    #[default]
+    Dhash,
+    /// 64-bit DCT perceptual hash; tolerates brightness and contrast changes.
+    Phash,
+    /// Downsampled greyscale pixels.
+    Pixels,
+}
+
+impl ImageFeatureKind {
+    pub(super) fn label(self) -> &'static str {
+        match self {
+            ImageFeatureKind::Dhash => "dhash",
+            ImageFeatureKind::Phash => "phash",
+            ImageFeatureKind::Pixels => "pixels",
+        }
+    }
+}
+
+/// Options accepted by the `inspect` command.
+#[derive(Debug, Args, Clone)]
+pub struct InspectCommand {
+    /// Path to the Parquet file containing feature vectors.
+    #[arg(long)]
+    pub parquet: PathBuf,
+
+    /// Feature columns to inspect, as accepted by `run parquet --columns`.
+    #[arg(
+        long = "columns",
+        visible_alias = "column",
+        value_delimiter = ',',
+        required = true
+    )]
+    pub columns: Vec<String>,
+
+    /// Memory limit the estimate is checked against, as for `run`.
+    #[arg(long = "max-bytes", value_parser = parse_byte_size)]
+    pub max_bytes: Option<u64>,
+
+    /// HNSW options the estimate should assume.
+    #[command(flatten)]
+    pub hnsw: HnswArgs,
+
+    /// Report output options.
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+/// Options accepted by the `config` command.
+#[derive(Debug, Args, Clone)]
+pub struct ConfigCommand {
+    /// Configuration action to perform.
+    #[command(subcommand)]
+    pub action: ConfigAction,
+}
+
+/// Configuration file actions.
+#[derive(Debug, Subcommand, Clone)]
+pub enum ConfigAction {
+    /// Emit a commented configuration template.
+    Init(ConfigInitArgs),
+}
+
+/// Options for `chutoro config init`.
+#[derive(Debug, Args, Clone, Default)]
+pub struct ConfigInitArgs {
+    /// Write the template to this path instead of standard output.
+    #[arg(long)]
+    pub output: Option<PathBuf>,
+
+    /// Overwrite `--output` if it already exists.
+    #[arg(long, requires = "output")]
+    pub force: bool,
+}
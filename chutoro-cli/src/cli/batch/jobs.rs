@@ -0,0 +1,72 @@
+//! Running the jobs of a batch on worker threads.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use tracing::{info, warn};
+
+use super::super::args::RunCommand;
+use super::super::commands::{CliError, ExecutionSummary, run_command};
+use super::super::json::{render_failure_json, render_summary_json};
+use super::{BatchJob, BatchPlan, JobReport, JobResult};
+
+/// Claims and runs jobs until none remain, returning each report with its
+/// position in the plan.
+pub(super) fn work(plan: &BatchPlan, next: &AtomicUsize) -> Vec<(usize, JobReport)> {
+    let mut reports = Vec::new();
+    loop {
+        let index = next.fetch_add(1, Ordering::Relaxed);
+        let Some(job) = plan.jobs.get(index) else {
+            return reports;
+        };
+        reports.push((index, run_job(job, &plan.output_dir)));
+    }
+}
+
+fn run_job(job: &BatchJob, output_dir: &Path) -> JobReport {
+    let started = Instant::now();
+    let output = output_dir.join(format!("{}.json", job.name));
+    let (command, outcome) = match job.run.clone().resolve() {
+        Ok(resolved) => (resolved.clone(), run_command(resolved)),
+        Err(err) => (job.run.clone(), Err(err)),
+    };
+    let written = write_job_output(&output, &command, &outcome);
+    let outcome = match (outcome, written) {
+        (Ok(summary), Ok(())) => Ok(JobResult::from(&summary)),
+        (Ok(_), Err(source)) => Err(CliError::Io {
+            path: output.clone(),
+            source,
+        }),
+        (Err(err), _) => Err(err),
+    };
+    match &outcome {
+        Ok(result) => info!(
+            job = job.name.as_str(),
+            clusters = result.clusters,
+            "job completed"
+        ),
+        Err(err) => warn!(job = job.name.as_str(), error = %err, "job failed"),
+    }
+    JobReport {
+        name: job.name.clone(),
+        output,
+        elapsed: started.elapsed(),
+        outcome,
+    }
+}
+
+fn write_job_output(
+    path: &Path,
+    command: &RunCommand,
+    outcome: &Result<ExecutionSummary, CliError>,
+) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    match outcome {
+        Ok(summary) => render_summary_json(summary, command, &mut writer)?,
+        Err(err) => render_failure_json(err, command, &mut writer)?,
+    }
+    writer.flush()
+}
@@ -0,0 +1,144 @@
+//! Loading and validating `batch` jobs files.
+
+use std::collections::HashSet;
+use std::fs;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use super::super::args::{HnswArgs, RunCommand};
+use super::super::commands::CliError;
+use super::super::details::SummaryDetail;
+use super::super::render::{OutputArgs, SummaryFormat};
+use super::{BatchCommand, BatchJob, BatchPlan};
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BatchFile {
+    output_dir: Option<PathBuf>,
+    parallel: Option<NonZeroUsize>,
+    #[serde(default, rename = "job")]
+    jobs: Vec<JobConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JobConfig {
+    name: String,
+    config: PathBuf,
+    min_cluster_size: Option<usize>,
+    max_connections: Option<usize>,
+    ef_construction: Option<usize>,
+    seed: Option<u64>,
+    detail: Option<SummaryDetail>,
+}
+
+impl BatchCommand {
+    /// Loads the jobs file and applies `--parallel` and `--output-dir`.
+    ///
+    /// Relative paths in the file resolve against its directory.
+    ///
+    /// # Errors
+    /// Returns [`CliError::Io`] when the file cannot be read,
+    /// [`CliError::ConfigParse`] when it is not a valid jobs file, and
+    /// [`CliError::InvalidConfig`] when it lists no jobs or a job name is
+    /// empty, repeated, or not a plain file name.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::error::Error;
+    /// # use chutoro_cli::cli::{BatchCommand, OutputArgs};
+    /// # use tempfile::TempDir;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let dir = TempDir::new()?;
+    /// let manifest = dir.path().join("jobs.toml");
+    /// std::fs::write(&manifest, "[[job]]\nname = \"a\"\nconfig = \"a.toml\"\n")?;
+    /// let command = BatchCommand {
+    ///     manifest,
+    ///     parallel: None,
+    ///     output_dir: None,
+    ///     output: OutputArgs::default(),
+    /// };
+    /// let plan = command.plan()?;
+    /// assert_eq!(plan.jobs[0].run.config, Some(dir.path().join("a.toml")));
+    /// assert_eq!(plan.parallel.get(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn plan(&self) -> Result<BatchPlan, CliError> {
+        let path = &self.manifest;
+        let text = fs::read_to_string(path).map_err(|source| CliError::Io {
+            path: path.clone(),
+            source,
+        })?;
+        let file: BatchFile = toml::from_str(&text).map_err(|source| CliError::ConfigParse {
+            path: path.clone(),
+            source: Box::new(source),
+        })?;
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+        validate_names(path, &file.jobs)?;
+        Ok(BatchPlan {
+            parallel: self.parallel.or(file.parallel).unwrap_or(NonZeroUsize::MIN),
+            output_dir: self
+                .output_dir
+                .clone()
+                .or_else(|| file.output_dir.map(|dir| base.join(dir)))
+                .unwrap_or_else(|| base.to_path_buf()),
+            jobs: file
+                .jobs
+                .into_iter()
+                .map(|job| job.into_batch_job(base))
+                .collect(),
+        })
+    }
+}
+
+fn validate_names(path: &Path, jobs: &[JobConfig]) -> Result<(), CliError> {
+    let invalid = |message: String| CliError::InvalidConfig {
+        path: path.to_path_buf(),
+        key: "job.name",
+        message,
+    };
+    if jobs.is_empty() {
+        return Err(CliError::InvalidConfig {
+            path: path.to_path_buf(),
+            key: "job",
+            message: "the jobs file lists no jobs".to_owned(),
+        });
+    }
+    let mut seen = HashSet::new();
+    for JobConfig { name, .. } in jobs {
+        if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+            return Err(invalid(format!("`{name}` is not a plain file name")));
+        }
+        if !seen.insert(name.as_str()) {
+            return Err(invalid(format!("job `{name}` is listed more than once")));
+        }
+    }
+    Ok(())
+}
+
+impl JobConfig {
+    fn into_batch_job(self, base: &Path) -> BatchJob {
+        BatchJob {
+            name: self.name,
+            run: RunCommand {
+                config: Some(base.join(self.config)),
+                min_cluster_size: self.min_cluster_size,
+                hnsw: HnswArgs {
+                    max_connections: self.max_connections,
+                    ef_construction: self.ef_construction,
+                    seed: self.seed,
+                },
+                output: OutputArgs {
+                    format: Some(SummaryFormat::Json),
+                    json: false,
+                },
+                detail: self.detail,
+                ..RunCommand::default()
+            },
+        }
+    }
+}
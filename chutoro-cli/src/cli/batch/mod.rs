@@ -0,0 +1,179 @@
+//! The `batch` command: many runs described by one jobs file.
+//!
+//! Each job names a run configuration file, as accepted by `run --config`,
+//! and may override its parameters. Jobs are claimed in file order by up to
+//! `--parallel` worker threads. Every job writes its JSON summary, or its JSON
+//! failure document, to `<output_dir>/<name>.json`, and a failed job does not
+//! stop the others; the consolidated report lists each job's outcome.
+
+mod jobs;
+mod manifest;
+mod report;
+
+use std::fs;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicUsize;
+use std::thread;
+use std::time::Duration;
+
+use clap::Args;
+use tracing::{info, instrument};
+
+use super::args::RunCommand;
+use super::commands::{CliError, ExecutionSummary, path_label};
+use super::failure::ExitStatus;
+use super::render::OutputArgs;
+
+pub use self::report::{render_batch, render_batch_json};
+
+/// Options accepted by the `batch` command.
+#[derive(Debug, Args, Clone)]
+pub struct BatchCommand {
+    /// TOML file listing the jobs to run.
+    #[arg(long)]
+    pub manifest: PathBuf,
+
+    /// Maximum number of jobs run at once [default: `parallel` in the jobs
+    /// file, or 1].
+    #[arg(long)]
+    pub parallel: Option<NonZeroUsize>,
+
+    /// Directory receiving each job's JSON summary [default: `output_dir` in
+    /// the jobs file, or the jobs file's directory].
+    #[arg(long = "output-dir")]
+    pub output_dir: Option<PathBuf>,
+
+    /// Report output options.
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+/// One job of a batch: a name and the `run` command it executes.
+#[derive(Debug, Clone)]
+pub struct BatchJob {
+    /// Name of the job, unique within the batch; also the stem of its output
+    /// file.
+    pub name: String,
+    /// The run, before its `--config` file is applied.
+    pub run: RunCommand,
+}
+
+/// A jobs file resolved against the command-line flags.
+#[derive(Debug, Clone)]
+pub struct BatchPlan {
+    /// Jobs in file order.
+    pub jobs: Vec<BatchJob>,
+    /// Maximum number of jobs run at once.
+    pub parallel: NonZeroUsize,
+    /// Directory receiving each job's JSON summary.
+    pub output_dir: PathBuf,
+}
+
+/// Statistics of a job that completed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobResult {
+    /// Name reported by the data source implementation.
+    pub data_source: String,
+    /// Number of points clustered.
+    pub points: usize,
+    /// Number of clusters, including the noise label when present.
+    pub clusters: usize,
+    /// Share of points labelled noise.
+    pub noise_fraction: f64,
+}
+
+impl From<&ExecutionSummary> for JobResult {
+    fn from(summary: &ExecutionSummary) -> Self {
+        Self {
+            data_source: summary.data_source.clone(),
+            points: summary.result.assignments().len(),
+            clusters: summary.result.cluster_count(),
+            noise_fraction: summary.result.noise_fraction(),
+        }
+    }
+}
+
+/// Outcome of one job of a batch.
+#[derive(Debug)]
+pub struct JobReport {
+    /// Name of the job.
+    pub name: String,
+    /// File holding the job's JSON summary or failure document.
+    pub output: PathBuf,
+    /// Wall-clock time the job took, including writing its output.
+    pub elapsed: Duration,
+    /// The job's statistics, or the error that stopped it.
+    pub outcome: Result<JobResult, CliError>,
+}
+
+/// Outcome of `chutoro batch`.
+#[derive(Debug)]
+pub struct BatchSummary {
+    /// One report per job, in file order.
+    pub jobs: Vec<JobReport>,
+}
+
+impl BatchSummary {
+    /// Returns the number of jobs that failed.
+    #[must_use]
+    pub fn failed(&self) -> usize {
+        self.jobs.iter().filter(|job| job.outcome.is_err()).count()
+    }
+
+    /// Returns the exit status of the batch: [`ExitStatus::Failure`] when
+    /// any job failed.
+    #[must_use]
+    pub fn exit_status(&self) -> ExitStatus {
+        if self.failed() == 0 {
+            ExitStatus::Success
+        } else {
+            ExitStatus::Failure
+        }
+    }
+}
+
+/// Runs every job of the batch described by `command`.
+///
+/// # Errors
+/// Returns the errors of [`BatchCommand::plan`], and [`CliError::Io`] when
+/// the output directory cannot be created. Failures of individual jobs are
+/// reported in the summary instead.
+#[instrument(
+    name = "cli.batch",
+    err,
+    skip(command),
+    fields(manifest = %path_label(&command.manifest)),
+)]
+pub fn batch_command(command: &BatchCommand) -> Result<BatchSummary, CliError> {
+    let plan = command.plan()?;
+    fs::create_dir_all(&plan.output_dir).map_err(|source| CliError::Io {
+        path: plan.output_dir.clone(),
+        source,
+    })?;
+    let next = AtomicUsize::new(0);
+    let workers = plan.parallel.get().min(plan.jobs.len());
+    let mut reports: Vec<(usize, JobReport)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| scope.spawn(|| jobs::work(&plan, &next)))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    });
+    reports.sort_by_key(|(index, _)| *index);
+    let summary = BatchSummary {
+        jobs: reports.into_iter().map(|(_, report)| report).collect(),
+    };
+    info!(
+        jobs = summary.jobs.len(),
+        failed = summary.failed(),
+        "batch completed"
+    );
+    Ok(summary)
+}
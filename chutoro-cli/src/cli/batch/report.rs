@@ -0,0 +1,116 @@
+//! Rendering the consolidated report of a batch.
+
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use super::super::json::{millis, write_document};
+use super::{BatchSummary, JobReport};
+
+/// Renders `summary` to `writer` as human-readable text.
+///
+/// Each job is printed on a `job <name>:` line with its statistics or error
+/// and its output file, followed by a `jobs:` line counting the successes and
+/// failures.
+///
+/// # Errors
+/// Returns [`io::Error`] if writing to the supplied writer fails.
+pub fn render_batch(summary: &BatchSummary, mut writer: impl Write) -> io::Result<()> {
+    for job in &summary.jobs {
+        let output = job.output.display();
+        match &job.outcome {
+            Ok(result) => writeln!(
+                writer,
+                "job {}: ok data_source={} points={} clusters={} noise_fraction={:.4} \
+                 elapsed={:.3?} output={output}",
+                job.name,
+                result.data_source,
+                result.points,
+                result.clusters,
+                result.noise_fraction,
+                job.elapsed,
+            )?,
+            Err(err) => writeln!(
+                writer,
+                "job {}: error exit_code={} {err} output={output}",
+                job.name,
+                err.exit_status().code(),
+            )?,
+        }
+    }
+    let failed = summary.failed();
+    writeln!(
+        writer,
+        "jobs: {} succeeded, {failed} failed",
+        summary.jobs.len() - failed
+    )
+}
+
+/// Renders `summary` to `writer` as JSON.
+///
+/// `status` is `ok` when every job succeeded and `error` otherwise. Each
+/// entry of `jobs` carries the job's `name`, `status`, `output` file, and
+/// `elapsed_ms`, with the run statistics for a success or an `error` object
+/// shaped like a failure document's for a failure.
+///
+/// # Errors
+/// Returns [`io::Error`] if serialization or writing fails.
+pub fn render_batch_json(summary: &BatchSummary, writer: impl Write) -> io::Result<()> {
+    let failed = summary.failed();
+    let document = BatchDocument {
+        status: if failed == 0 { "ok" } else { "error" },
+        succeeded: summary.jobs.len() - failed,
+        failed,
+        jobs: summary.jobs.iter().map(JsonJob::from).collect(),
+    };
+    write_document(&document, writer)
+}
+
+#[derive(Serialize)]
+struct BatchDocument<'a> {
+    status: &'static str,
+    succeeded: usize,
+    failed: usize,
+    jobs: Vec<JsonJob<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonJob<'a> {
+    name: &'a str,
+    status: &'static str,
+    output: String,
+    elapsed_ms: f64,
+    data_source: Option<&'a str>,
+    points: Option<usize>,
+    clusters: Option<usize>,
+    noise_fraction: Option<f64>,
+    error: Option<JsonJobError>,
+}
+
+#[derive(Serialize)]
+struct JsonJobError {
+    message: String,
+    code: Option<&'static str>,
+    exit_code: u8,
+}
+
+impl<'a> From<&'a JobReport> for JsonJob<'a> {
+    fn from(job: &'a JobReport) -> Self {
+        let result = job.outcome.as_ref().ok();
+        Self {
+            name: &job.name,
+            status: if result.is_some() { "ok" } else { "error" },
+            output: job.output.to_string_lossy().into_owned(),
+            elapsed_ms: millis(job.elapsed),
+            data_source: result.map(|result| result.data_source.as_str()),
+            points: result.map(|result| result.points),
+            clusters: result.map(|result| result.clusters),
+            noise_fraction: result.map(|result| result.noise_fraction),
+            error: job.outcome.as_ref().err().map(|err| JsonJobError {
+                message: err.to_string(),
+                code: err.code().map(|code| code.as_str()),
+                exit_code: err.exit_status().code(),
+            }),
+        }
+    }
+}
@@ -1,127 +1,26 @@
-//! Command implementations and argument parsing for the chutoro CLI.
+//! Command implementations for the chutoro CLI.
 
-use std::fs::File;
-use std::io::{self, BufReader, Write};
+use std::io;
 use std::path::{Path, PathBuf};
 
-use chutoro_core::{Chutoro, ChutoroBuilder, ChutoroError, ClusteringResult, DataSource};
-use chutoro_providers_dense::{DenseMatrixProvider, DenseMatrixProviderError};
-use chutoro_providers_text::{TextProvider, TextProviderError};
-use clap::{Args, Parser, Subcommand, ValueEnum};
+use arrow_schema::DataType;
+use chutoro_core::{
+    Chutoro, ChutoroBuilder, ChutoroError, ChutoroErrorCode, ClusteringQualityError,
+    ClusteringQualityScore, ClusteringResult, DataSource, DataSourceErrorCode, ExemplarError,
+    HnswError,
+};
+use chutoro_providers_dense::DenseMatrixProviderError;
+use chutoro_providers_image::ImageProviderError;
+use chutoro_providers_text::TextProviderError;
+use parquet::errors::ParquetError;
 use thiserror::Error;
 use tracing::{info, instrument};
 
-const DEFAULT_MIN_CLUSTER_SIZE: usize = 5;
-
-/// Top-level CLI options parsed by [`clap`].
-#[derive(Debug, Parser, Clone)]
-#[command(name = "chutoro", about = "Execute the chutoro clustering pipeline.")]
-pub struct Cli {
-    /// Command to execute.
-    #[command(subcommand)]
-    pub command: Command,
-}
-
-/// Supported CLI commands.
-#[derive(Debug, Subcommand, Clone)]
-pub enum Command {
-    /// Execute the clustering pipeline.
-    Run(RunCommand),
-}
-
-impl Command {
-    fn name(&self) -> &'static str {
-        match self {
-            Command::Run(_) => "run",
-        }
-    }
-}
-
-/// Options accepted by the `run` command.
-#[derive(Debug, Args, Clone)]
-pub struct RunCommand {
-    /// Minimum number of items per cluster.
-    #[arg(
-        long = "min-cluster-size",
-        default_value_t = DEFAULT_MIN_CLUSTER_SIZE,
-        value_parser = clap::value_parser!(usize),
-    )]
-    pub min_cluster_size: usize,
-
-    /// Maximum estimated memory (in bytes) allowed for the pipeline.
-    ///
-    /// Supports human-readable suffixes: K, M, G, T (case-insensitive).
-    /// Example: `--max-bytes 2G` or `--max-bytes 2147483648`.
-    #[arg(long = "max-bytes", value_parser = parse_byte_size)]
-    pub max_bytes: Option<u64>,
-
-    /// Data source configuration.
-    #[command(subcommand)]
-    pub source: RunSource,
-}
-
-/// Input data sources supported by the CLI.
-#[derive(Debug, Subcommand, Clone)]
-pub enum RunSource {
-    /// Execute against a Parquet file containing a `FixedSizeList<Float32, D>` column.
-    Parquet(ParquetArgs),
-    /// Execute against a UTF-8 text corpus, one string per line.
-    Text(TextArgs),
-}
-
-impl RunSource {
-    fn kind(&self) -> &'static str {
-        match self {
-            RunSource::Parquet(_) => "parquet",
-            RunSource::Text(_) => "text",
-        }
-    }
-}
-
-/// Parquet ingestion arguments.
-#[derive(Debug, Args, Clone)]
-pub struct ParquetArgs {
-    /// Path to the Parquet file containing feature vectors.
-    pub path: PathBuf,
-
-    /// Column containing `FixedSizeList<Float32, D>` rows.
-    #[arg(long)]
-    pub column: String,
-
-    /// Override name for the data source (defaults to the file name).
-    #[arg(long)]
-    pub name: Option<String>,
-}
-
-/// Text ingestion arguments.
-#[derive(Debug, Args, Clone)]
-pub struct TextArgs {
-    /// Path to a UTF-8 text file with one string per line.
-    pub path: PathBuf,
-
-    /// Distance metric to use when comparing lines.
-    #[arg(long, value_enum)]
-    pub metric: TextMetric,
-
-    /// Override name for the data source (defaults to the file name).
-    #[arg(long)]
-    pub name: Option<String>,
-}
-
-/// Supported text metrics.
-#[derive(Debug, Clone, Copy, ValueEnum)]
-pub enum TextMetric {
-    /// Compute Levenshtein edit distance between lines.
-    Levenshtein,
-}
-
-impl TextMetric {
-    fn label(self) -> &'static str {
-        match self {
-            TextMetric::Levenshtein => "levenshtein",
-        }
-    }
-}
+use super::args::{Cli, Command, RunCommand, RunSource};
+use super::details::{ClusterDetail, SummaryDetail, cluster_details};
+use super::images::run_images;
+use super::manifest::RunManifest;
+use super::sources::{run_parquet, run_text};
 
 /// Errors surfaced while executing CLI commands.
 #[derive(Debug, Error)]
@@ -135,15 +34,158 @@ pub enum CliError {
         #[source]
         source: io::Error,
     },
+    /// A compressed input requires a decoder that was not compiled in.
+    #[error("`{path}` is {format}-compressed but the `{format}` feature is disabled")]
+    UnsupportedCompression {
+        /// Path of the compressed input.
+        path: PathBuf,
+        /// Compression format detected from the file extension.
+        format: &'static str,
+    },
+    /// The configuration file is not valid TOML or has unexpected fields.
+    #[error("failed to parse config `{path}`: {source}")]
+    ConfigParse {
+        /// Path of the configuration file.
+        path: PathBuf,
+        /// Underlying TOML error, including the offending location.
+        #[source]
+        source: Box<toml::de::Error>,
+    },
+    /// A configuration value parsed but could not be interpreted.
+    #[error("invalid value for `{key}` in config `{path}`: {message}")]
+    InvalidConfig {
+        /// Path of the configuration file.
+        path: PathBuf,
+        /// Dotted key of the rejected value.
+        key: &'static str,
+        /// Description of the problem.
+        message: String,
+    },
+    /// Neither the command line nor the configuration named a data source.
+    #[error("no data source given; pass a source subcommand or set [source] in --config")]
+    MissingSource,
+    /// The command does not execute the clustering pipeline.
+    #[error("`{command}` does not run the clustering pipeline")]
+    NotARun {
+        /// Name of the command that was supplied.
+        command: &'static str,
+    },
+    /// The `--id-column` requested for Parquet output does not exist.
+    #[error("id column `{column}` not found in Parquet schema")]
+    IdColumnNotFound {
+        /// Name of the missing column.
+        column: String,
+        /// Columns the input does contain, in schema order.
+        available: Vec<String>,
+    },
+    /// The id column and the clustered data have different row counts.
+    #[error("id column has {actual} rows but {expected} rows were clustered")]
+    IdRowCount {
+        /// Number of clustered rows.
+        expected: usize,
+        /// Number of rows read from the id column.
+        actual: usize,
+    },
+    /// The `--label-column` to score against does not exist.
+    #[error("label column `{column}` not found in Parquet schema")]
+    LabelColumnNotFound {
+        /// Name of the missing column.
+        column: String,
+        /// Columns the input does contain, in schema order.
+        available: Vec<String>,
+    },
+    /// The `--label-column` holds values that cannot name classes.
+    #[error("label column `{column}` has unsupported type {data_type}")]
+    UnsupportedLabelColumn {
+        /// Name of the label column.
+        column: String,
+        /// Arrow type of the column.
+        data_type: DataType,
+    },
+    /// The assignments could not be scored against the ground-truth labels.
+    #[error("failed to score against labels: {0}")]
+    Quality(#[source] ClusteringQualityError),
+    /// Reading or writing Parquet data for `--output` failed.
+    #[error("Parquet I/O failed for `{path}`: {source}")]
+    Parquet {
+        /// Path of the Parquet file being read or written.
+        path: PathBuf,
+        /// Underlying Parquet or Arrow error.
+        #[source]
+        source: ParquetError,
+    },
+    /// A run manifest is not valid JSON or has unexpected fields.
+    #[error("failed to parse manifest `{path}`: {source}")]
+    ManifestParse {
+        /// Path of the manifest file.
+        path: PathBuf,
+        /// Underlying JSON error.
+        #[source]
+        source: serde_json::Error,
+    },
+    /// A run manifest uses a format version this build cannot replay.
+    #[error("manifest `{path}` has unsupported version {version}")]
+    UnsupportedManifest {
+        /// Path of the manifest file.
+        path: PathBuf,
+        /// Version recorded in the manifest.
+        version: u32,
+    },
+    /// The dataset named by a manifest changed since the manifest was written.
+    #[error("dataset `{path}` has hash {actual} but the manifest recorded {expected}")]
+    DatasetMismatch {
+        /// Path of the dataset.
+        path: PathBuf,
+        /// Hash recorded in the manifest.
+        expected: String,
+        /// Hash of the dataset as it is now.
+        actual: String,
+    },
+    /// Writing command output failed.
+    #[error("failed to write output: {0}")]
+    Write(#[source] io::Error),
+    /// The requested HNSW parameters are inconsistent.
+    #[error("invalid HNSW parameters: {0}")]
+    Hnsw(#[source] HnswError),
     /// Dense matrix ingestion failed.
     #[error(transparent)]
     Dense(#[from] DenseMatrixProviderError),
     /// Text ingestion failed.
     #[error(transparent)]
     Text(#[from] TextProviderError),
+    /// Image folder ingestion failed.
+    #[error(transparent)]
+    Image(#[from] ImageProviderError),
     /// Core orchestration failed.
     #[error(transparent)]
     Core(#[from] ChutoroError),
+    /// The runs of a stability comparison could not be compared.
+    #[error("failed to compare runs: {0}")]
+    Stability(#[source] ClusteringQualityError),
+    /// The `--detail clusters` table could not be computed.
+    #[error("failed to summarize clusters: {0}")]
+    ClusterDetail(#[source] ExemplarError),
+}
+
+impl CliError {
+    /// Returns the stable core error code when the failure originated in the
+    /// clustering pipeline.
+    #[must_use]
+    pub fn code(&self) -> Option<ChutoroErrorCode> {
+        match self {
+            CliError::Core(core) => Some(core.code()),
+            _ => None,
+        }
+    }
+
+    /// Returns the data source error code carried by a core failure, if any.
+    #[must_use]
+    pub fn data_source_code(&self) -> Option<DataSourceErrorCode> {
+        match self {
+            CliError::Core(core) => core.data_source_code(),
+            _ => None,
+        }
+    }
 }
 
 /// Summarizes the outcome of executing a CLI command.
@@ -153,12 +195,20 @@ pub struct ExecutionSummary {
     pub data_source: String,
     /// Cluster assignments produced by the clustering pipeline.
     pub result: ClusteringResult,
+    /// Agreement with the `--label-column` ground truth, when one was given.
+    pub quality: Option<ClusteringQualityScore>,
+    /// One row per cluster, when `--detail clusters` was given.
+    pub clusters: Option<Vec<ClusterDetail>>,
 }
 
 /// Executes the CLI command represented by `cli`.
 ///
+/// Run commands are first resolved against any `--config` file, so flags
+/// override configured values.
+///
 /// # Errors
-/// Returns [`CliError`] when parsing or execution fails.
+/// Returns [`CliError`] when configuration loading or execution fails, and
+/// [`CliError::NotARun`] for commands that do not run the pipeline.
 ///
 /// # Examples
 /// ```
@@ -171,13 +221,13 @@ pub struct ExecutionSummary {
 /// std::fs::write(file.path(), "alpha\nbeta\n")?;
 /// let cli = Cli {
 ///     command: Command::Run(RunCommand {
-///         min_cluster_size: 1,
-///         max_bytes: None,
-///         source: RunSource::Text(TextArgs {
+///         min_cluster_size: Some(1),
+///         source: Some(RunSource::Text(TextArgs {
 ///             path: file.path().to_path_buf(),
 ///             metric: TextMetric::Levenshtein,
 ///             name: None,
-///         }),
+///         })),
+///         ..RunCommand::default()
 ///     }),
 /// };
 /// let summary = run_cli(cli)?;
@@ -188,30 +238,46 @@ pub struct ExecutionSummary {
 #[instrument(name = "cli.run", err, skip(cli), fields(command = %cli.command.name()))]
 pub fn run_cli(cli: Cli) -> Result<ExecutionSummary, CliError> {
     match cli.command {
-        Command::Run(run) => run_command(run),
+        Command::Run(run) => run_command(run.resolve()?),
+        command @ (Command::Config(_)
+        | Command::Inspect(_)
+        | Command::Stability(_)
+        | Command::Batch(_)) => Err(CliError::NotARun {
+            command: command.name(),
+        }),
     }
 }
 
+/// Executes an already resolved `run` command.
+///
+/// Unlike [`run_cli`], this does not consult `command.config`; call
+/// [`RunCommand::resolve`] first when a configuration file may be set. When
+/// `command.manifest` is set, a [`RunManifest`] is written after the run.
+///
+/// # Errors
+/// Returns [`CliError::MissingSource`] when no data source is set, and other
+/// [`CliError`] variants when parameter validation or execution fails.
 #[instrument(
     name = "cli.execute",
     err,
     skip(command),
     fields(
-        min_cluster_size = command.min_cluster_size,
-        source = %command.source.kind()
+        min_cluster_size = command.effective_min_cluster_size(),
+        source = %command.source.as_ref().map_or("<none>", RunSource::kind)
     ),
 )]
-pub(super) fn run_command(command: RunCommand) -> Result<ExecutionSummary, CliError> {
-    let mut builder = ChutoroBuilder::new().with_min_cluster_size(command.min_cluster_size);
-    if let Some(bytes) = command.max_bytes {
-        builder = builder.with_max_bytes(bytes);
-    }
-    let chutoro = builder.build()?;
-
-    let summary = match command.source {
-        RunSource::Parquet(args) => run_parquet(&chutoro, args)?,
-        RunSource::Text(args) => run_text(&chutoro, args)?,
+pub fn run_command(command: RunCommand) -> Result<ExecutionSummary, CliError> {
+    let chutoro = build_chutoro(&command)?;
+    let source = command.source.clone().ok_or(CliError::MissingSource)?;
+
+    let summary = match source {
+        RunSource::Parquet(args) => run_parquet(&chutoro, args, command.detail)?,
+        RunSource::Text(args) => run_text(&chutoro, args, command.detail)?,
+        RunSource::Images(args) => run_images(&chutoro, args, command.detail)?,
     };
+    if let Some(path) = &command.manifest {
+        RunManifest::capture(&command, &summary)?.write(path)?;
+    }
 
     info!(
         data_source = summary.data_source.as_str(),
@@ -221,156 +287,52 @@ pub(super) fn run_command(command: RunCommand) -> Result<ExecutionSummary, CliEr
     Ok(summary)
 }
 
-#[instrument(
-    name = "cli.run_parquet",
-    err,
-    skip(chutoro, args),
-    fields(
-        path = %path_label(&args.path),
-        column = %args.column,
-        override_name = %args.name.as_deref().unwrap_or("<derived>")
-    ),
-)]
-pub(super) fn run_parquet(
-    chutoro: &Chutoro,
-    args: ParquetArgs,
-) -> Result<ExecutionSummary, CliError> {
-    let ParquetArgs { path, column, name } = args;
-    let chosen_name = derive_data_source_name(&path, name.as_deref());
-    let provider = DenseMatrixProvider::try_from_parquet_path(chosen_name, &path, &column)?;
-    execute_with_provider(chutoro, provider)
-}
-
-#[instrument(
-    name = "cli.run_text",
-    err,
-    skip(chutoro, args),
-    fields(
-        path = %path_label(&args.path),
-        metric = args.metric.label(),
-        override_name = %args.name.as_deref().unwrap_or("<derived>")
-    ),
-)]
-pub(super) fn run_text(chutoro: &Chutoro, args: TextArgs) -> Result<ExecutionSummary, CliError> {
-    let TextArgs { path, metric, name } = args;
-    let chosen_name = derive_data_source_name(&path, name.as_deref());
-    let reader = open_text_reader(&path)?;
-    let provider = match metric {
-        TextMetric::Levenshtein => TextProvider::try_from_reader(chosen_name, reader)?,
-    };
-    execute_with_provider(chutoro, provider)
-}
-
-#[instrument(
-    name = "cli.open_text_reader",
-    err,
-    skip(path),
-    fields(path = %path_label(path))
-)]
-pub(super) fn open_text_reader(path: &Path) -> Result<BufReader<File>, CliError> {
-    let file = File::open(path).map_err(|source| CliError::Io {
-        path: path.to_path_buf(),
-        source,
-    })?;
-    Ok(BufReader::new(file))
+/// Builds the pipeline configured by `command`'s flags.
+pub(super) fn build_chutoro(command: &RunCommand) -> Result<Chutoro, CliError> {
+    Ok(configure_builder(command)?.build()?)
 }
 
-pub(super) fn derive_data_source_name(path: &Path, override_name: Option<&str>) -> String {
-    if let Some(name) = override_name {
-        return name.to_owned();
-    }
-
-    path.file_stem()
-        .and_then(|value| value.to_str())
-        .map(ToOwned::to_owned)
-        .unwrap_or_else(|| "data_source".to_owned())
-}
-
-/// Parses a human-readable byte size such as `"512M"` or `"2G"` into a `u64`.
-///
-/// Recognized suffixes (case-insensitive): `K`/`KB`/`KiB`, `M`/`MB`/`MiB`,
-/// `G`/`GB`/`GiB`, `T`/`TB`/`TiB`.  Plain integers are treated as bytes.
-pub(super) fn parse_byte_size(s: &str) -> Result<u64, String> {
-    let s = s.trim();
-    if s.is_empty() {
-        return Err("byte size must not be empty".to_owned());
-    }
-
-    // Split into leading digits and trailing suffix.
-    let split = s.find(|ch: char| !ch.is_ascii_digit()).unwrap_or(s.len());
-    let (num_part, suffix) = s.split_at(split);
-
-    let base: u64 = num_part
-        .parse()
-        .map_err(|err| format!("invalid byte size `{num_part}`: {err}"))?;
-
-    let multiplier = suffix_multiplier(suffix)?;
-
-    base.checked_mul(multiplier)
-        .ok_or_else(|| "byte size overflows u64".to_owned())
-}
-
-/// Maps a byte-size suffix to its multiplier (in bytes).
-fn suffix_multiplier(suffix: &str) -> Result<u64, String> {
-    match suffix.trim().to_ascii_lowercase().as_str() {
-        "" => Ok(1_u64),
-        "k" | "kb" | "kib" => Ok(1024),
-        "m" | "mb" | "mib" => Ok(1024 * 1024),
-        "g" | "gb" | "gib" => Ok(1024 * 1024 * 1024),
-        "t" | "tb" | "tib" => Ok(1024_u64 * 1024 * 1024 * 1024),
-        other => Err(format!("unknown size suffix: `{other}`")),
+/// Returns a builder configured by `command`'s flags, for callers that vary
+/// further options between runs.
+pub(super) fn configure_builder(command: &RunCommand) -> Result<ChutoroBuilder, CliError> {
+    let hnsw = command.hnsw.to_params().map_err(CliError::Hnsw)?;
+    let mut builder = ChutoroBuilder::new()
+        .with_min_cluster_size(command.effective_min_cluster_size())
+        .with_hnsw_params(hnsw);
+    if let Some(bytes) = command.max_bytes {
+        builder = builder.with_max_bytes(bytes);
     }
+    Ok(builder)
 }
 
 /// Produce a redacted label for a path that avoids leaking absolute directories.
-fn path_label(path: &Path) -> String {
+pub(super) fn path_label(path: &Path) -> String {
     path.file_name()
         .map(|name| name.to_string_lossy().into_owned())
         .unwrap_or_else(|| "<unknown>".to_owned())
 }
 
-fn execute_with_provider<D>(chutoro: &Chutoro, provider: D) -> Result<ExecutionSummary, CliError>
+/// Clusters `provider`, adding the per-cluster table when `detail` asks for
+/// it while the provider is still loaded.
+pub(super) fn execute_with_provider<D>(
+    chutoro: &Chutoro,
+    provider: D,
+    detail: Option<SummaryDetail>,
+) -> Result<ExecutionSummary, CliError>
 where
     D: DataSource + Sync,
 {
     let result = chutoro.run(&provider)?;
+    let clusters = match detail {
+        Some(SummaryDetail::Clusters) => {
+            Some(cluster_details(&result, &provider).map_err(CliError::ClusterDetail)?)
+        }
+        None => None,
+    };
     Ok(ExecutionSummary {
         data_source: provider.name().to_owned(),
         result,
+        quality: None,
+        clusters,
     })
 }
-
-/// Renders `summary` to `writer` in a human-readable text format.
-///
-/// # Errors
-/// Returns [`io::Error`] if writing to the supplied writer fails.
-///
-/// # Examples
-/// ```
-/// # use std::error::Error;
-/// # use std::io::Cursor;
-/// # use chutoro_cli::cli::{ExecutionSummary, render_summary};
-/// # use chutoro_core::{ClusteringResult, ClusterId};
-/// #
-/// # fn main() -> Result<(), Box<dyn Error>> {
-/// let summary = ExecutionSummary {
-///     data_source: "demo".into(),
-///     result: ClusteringResult::from_assignments(vec![
-///         ClusterId::new(0),
-///         ClusterId::new(1),
-///     ]),
-/// };
-/// let mut buffer = Cursor::new(Vec::new());
-/// render_summary(&summary, &mut buffer)?;
-/// assert_eq!(buffer.into_inner().len(), 38);
-/// # Ok(())
-/// # }
-/// ```
-pub fn render_summary(summary: &ExecutionSummary, mut writer: impl Write) -> io::Result<()> {
-    writeln!(writer, "data source: {}", summary.data_source)?;
-    writeln!(writer, "clusters: {}", summary.result.cluster_count())?;
-    for (index, cluster) in summary.result.assignments().iter().enumerate() {
-        writeln!(writer, "{index}\t{}", cluster.get())?;
-    }
-    Ok(())
-}
@@ -0,0 +1,394 @@
+//! TOML configuration files for `chutoro run --config` and `chutoro config`.
+//!
+//! A configuration file supplies any subset of the run parameters. Values set
+//! on the command line take precedence field by field, except the data source:
+//! a source subcommand replaces the configured `[source]` table wholesale so
+//! flags from two different sources are never mixed.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use tracing::instrument;
+
+use super::args::{
+    ConfigAction, ConfigCommand, ConfigInitArgs, DEFAULT_PIXEL_SIDE, ImageArgs, ImageFeatureKind,
+    ParquetArgs, RunCommand, RunSource, TextArgs, TextMetric,
+};
+use super::commands::{CliError, path_label};
+use super::details::SummaryDetail;
+use super::input::is_stdin;
+use super::render::SummaryFormat;
+
+/// Commented configuration template emitted by `chutoro config init`.
+pub const CONFIG_TEMPLATE: &str = r#"# chutoro run configuration.
+#
+# Use with `chutoro run --config chutoro.toml`. Command-line flags override
+# the values below; a source subcommand replaces the whole [source] table.
+
+[source]
+# Input kind: "text" (one string per line), "parquet" (dense vectors), or
+# "images" (a directory of pictures).
+kind = "text"
+# Relative paths resolve against this file's directory; "-" reads stdin.
+path = "data.txt"
+# Distance metric for text sources.
+metric = "levenshtein"
+# Parquet sources name their feature column instead, or an array of
+# FixedSizeList<F, D> and F columns concatenated per row, where F is Float16 or
+# Float32. Float64 columns are narrowed to Float32 only when lossy_f64 is set.
+# Parquet sources may also write per-row assignments keyed by an identifier
+# column:
+# column = "features"
+# columns = ["embedding", "price", "rating"]
+# lossy_f64 = false
+# output = "clusters.parquet"
+# id_column = "id"
+# Parquet sources may score the run against a column of ground-truth labels:
+# label_column = "ground_truth"
+# Image sources name a directory and the feature to extract: "dhash",
+# "phash", or "pixels" (a pixel_side x pixel_side greyscale thumbnail):
+# feature = "phash"
+# pixel_side = 16
+# Override the data source name reported in summaries.
+# name = "corpus"
+
+[hierarchy]
+# Minimum number of items per cluster.
+min_cluster_size = 5
+
+[hnsw]
+# Maximum neighbours per node on upper layers.
+max_connections = 16
+# Candidate list width while building the index.
+ef_construction = 64
+# Seed for level sampling; omit to use the library default.
+# seed = 42
+
+[limits]
+# Maximum estimated memory, in bytes or with a K, M, G, or T suffix.
+# max_bytes = "2G"
+
+[output]
+# Summary format: "text" or "json".
+format = "text"
+# Extra summary section; "clusters" adds a per-cluster table.
+# detail = "clusters"
+"#;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RunConfig {
+    source: Option<SourceConfig>,
+    #[serde(default)]
+    hierarchy: HierarchyConfig,
+    #[serde(default)]
+    hnsw: HnswConfig,
+    #[serde(default)]
+    limits: LimitsConfig,
+    #[serde(default)]
+    output: OutputConfig,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase", deny_unknown_fields)]
+enum SourceConfig {
+    Parquet {
+        path: PathBuf,
+        #[serde(alias = "columns")]
+        column: FeatureColumns,
+        name: Option<String>,
+        #[serde(default)]
+        lossy_f64: bool,
+        id_column: Option<String>,
+        label_column: Option<String>,
+        output: Option<PathBuf>,
+    },
+    Text {
+        path: PathBuf,
+        metric: TextMetric,
+        name: Option<String>,
+    },
+    Images {
+        path: PathBuf,
+        #[serde(default)]
+        feature: ImageFeatureKind,
+        pixel_side: Option<u32>,
+        name: Option<String>,
+    },
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct HierarchyConfig {
+    min_cluster_size: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct HnswConfig {
+    max_connections: Option<usize>,
+    ef_construction: Option<usize>,
+    seed: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LimitsConfig {
+    max_bytes: Option<ByteSize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OutputConfig {
+    format: Option<SummaryFormat>,
+    detail: Option<SummaryDetail>,
+}
+
+/// Parquet sources may name one feature column or an array of them.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum FeatureColumns {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl From<FeatureColumns> for Vec<String> {
+    fn from(columns: FeatureColumns) -> Self {
+        match columns {
+            FeatureColumns::One(column) => vec![column],
+            FeatureColumns::Many(columns) => columns,
+        }
+    }
+}
+
+/// Byte sizes may be written as integers or as suffixed strings like `"2G"`.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ByteSize {
+    Bytes(u64),
+    Text(String),
+}
+
+impl RunCommand {
+    /// Merges the parameters from `self.config`, if set, beneath the flags.
+    ///
+    /// Relative source paths in the file resolve against the directory that
+    /// contains it. Without a configuration file the command is returned
+    /// unchanged.
+    ///
+    /// # Errors
+    /// Returns [`CliError::Io`] when the file cannot be read,
+    /// [`CliError::ConfigParse`] when it is not a valid configuration, and
+    /// [`CliError::InvalidConfig`] when a value cannot be interpreted.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::error::Error;
+    /// # use chutoro_cli::cli::RunCommand;
+    /// # use tempfile::NamedTempFile;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let file = NamedTempFile::new()?;
+    /// std::fs::write(file.path(), "[hierarchy]\nmin_cluster_size = 8\n")?;
+    /// let command = RunCommand {
+    ///     config: Some(file.path().to_path_buf()),
+    ///     ..RunCommand::default()
+    /// };
+    /// assert_eq!(command.resolve()?.min_cluster_size, Some(8));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(name = "cli.resolve_config", err, skip(self), fields(
+        config = %self.config.as_deref().map_or_else(|| "<none>".to_owned(), path_label)
+    ))]
+    pub fn resolve(self) -> Result<Self, CliError> {
+        let Some(path) = self.config.clone() else {
+            return Ok(self);
+        };
+        let config = load_config(&path)?;
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+        let max_bytes = config
+            .limits
+            .max_bytes
+            .map(|size| size.to_bytes(&path))
+            .transpose()?;
+        let mut merged = self;
+        merged.source = merged
+            .source
+            .or_else(|| config.source.map(|source| source.into_run_source(base)));
+        merged.min_cluster_size = merged
+            .min_cluster_size
+            .or(config.hierarchy.min_cluster_size);
+        merged.max_bytes = merged.max_bytes.or(max_bytes);
+        merged.hnsw.max_connections = merged.hnsw.max_connections.or(config.hnsw.max_connections);
+        merged.hnsw.ef_construction = merged.hnsw.ef_construction.or(config.hnsw.ef_construction);
+        merged.hnsw.seed = merged.hnsw.seed.or(config.hnsw.seed);
+        merged.detail = merged.detail.or(config.output.detail);
+        if !merged.output.json {
+            merged.output.format = merged.output.format.or(config.output.format);
+        }
+        Ok(merged)
+    }
+}
+
+fn load_config(path: &Path) -> Result<RunConfig, CliError> {
+    let text = fs::read_to_string(path).map_err(|source| CliError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    toml::from_str(&text).map_err(|source| CliError::ConfigParse {
+        path: path.to_path_buf(),
+        source: Box::new(source),
+    })
+}
+
+impl SourceConfig {
+    fn into_run_source(self, base: &Path) -> RunSource {
+        match self {
+            Self::Parquet {
+                path,
+                column,
+                name,
+                lossy_f64,
+                id_column,
+                label_column,
+                output,
+            } => RunSource::Parquet(ParquetArgs {
+                path: relative_to(base, path),
+                columns: column.into(),
+                name,
+                lossy_f64,
+                id_column,
+                label_column,
+                output: output.map(|output| relative_to(base, output)),
+            }),
+            Self::Text { path, metric, name } => RunSource::Text(TextArgs {
+                path: relative_to(base, path),
+                metric,
+                name,
+            }),
+            Self::Images {
+                path,
+                feature,
+                pixel_side,
+                name,
+            } => RunSource::Images(ImageArgs {
+                path: relative_to(base, path),
+                feature,
+                pixel_side: pixel_side.unwrap_or(DEFAULT_PIXEL_SIDE),
+                name,
+            }),
+        }
+    }
+}
+
+fn relative_to(base: &Path, path: PathBuf) -> PathBuf {
+    if is_stdin(&path) || path.is_absolute() {
+        path
+    } else {
+        base.join(path)
+    }
+}
+
+impl ByteSize {
+    fn to_bytes(&self, config_path: &Path) -> Result<u64, CliError> {
+        match self {
+            Self::Bytes(bytes) => Ok(*bytes),
+            Self::Text(text) => parse_byte_size(text).map_err(|message| CliError::InvalidConfig {
+                path: config_path.to_path_buf(),
+                key: "limits.max_bytes",
+                message,
+            }),
+        }
+    }
+}
+
+/// Parses a human-readable byte size such as `"512M"` or `"2G"` into a `u64`.
+///
+/// Recognized suffixes (case-insensitive): `K`/`KB`/`KiB`, `M`/`MB`/`MiB`,
+/// `G`/`GB`/`GiB`, `T`/`TB`/`TiB`.  Plain integers are treated as bytes.
+pub(super) fn parse_byte_size(s: &str) -> Result<u64, String> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err("byte size must not be empty".to_owned());
+    }
+
+    // Split into leading digits and trailing suffix.
+    let split = s.find(|ch: char| !ch.is_ascii_digit()).unwrap_or(s.len());
+    let (num_part, suffix) = s.split_at(split);
+
+    let base: u64 = num_part
+        .parse()
+        .map_err(|err| format!("invalid byte size `{num_part}`: {err}"))?;
+
+    let multiplier = suffix_multiplier(suffix)?;
+
+    base.checked_mul(multiplier)
+        .ok_or_else(|| "byte size overflows u64".to_owned())
+}
+
+/// Maps a byte-size suffix to its multiplier (in bytes).
+fn suffix_multiplier(suffix: &str) -> Result<u64, String> {
+    match suffix.trim().to_ascii_lowercase().as_str() {
+        "" => Ok(1_u64),
+        "k" | "kb" | "kib" => Ok(1024),
+        "m" | "mb" | "mib" => Ok(1024 * 1024),
+        "g" | "gb" | "gib" => Ok(1024 * 1024 * 1024),
+        "t" | "tb" | "tib" => Ok(1024_u64 * 1024 * 1024 * 1024),
+        other => Err(format!("unknown size suffix: `{other}`")),
+    }
+}
+
+/// Executes a `config` subcommand, writing any output to `writer`.
+///
+/// `config init` writes [`CONFIG_TEMPLATE`] to `writer`, or to `--output`
+/// when given. Existing files are only replaced with `--force`.
+///
+/// # Errors
+/// Returns [`CliError::Write`] when `writer` fails, and [`CliError::Io`] when
+/// `--output` cannot be written, including when it already exists and
+/// `--force` was not supplied.
+///
+/// # Examples
+/// ```
+/// # use std::error::Error;
+/// # use chutoro_cli::cli::{ConfigAction, ConfigCommand, ConfigInitArgs, run_config};
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let command = ConfigCommand {
+///     action: ConfigAction::Init(ConfigInitArgs::default()),
+/// };
+/// let mut buffer = Vec::new();
+/// run_config(&command, &mut buffer)?;
+/// assert!(String::from_utf8(buffer)?.contains("[hierarchy]"));
+/// # Ok(())
+/// # }
+/// ```
+pub fn run_config(command: &ConfigCommand, mut writer: impl Write) -> Result<(), CliError> {
+    match &command.action {
+        ConfigAction::Init(args) => match &args.output {
+            None => writer
+                .write_all(CONFIG_TEMPLATE.as_bytes())
+                .map_err(CliError::Write),
+            Some(path) => write_template(path, args),
+        },
+    }
+}
+
+fn write_template(path: &Path, args: &ConfigInitArgs) -> Result<(), CliError> {
+    let io_error = |source: io::Error| CliError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    let mut options = OpenOptions::new();
+    options.write(true);
+    if args.force {
+        options.create(true).truncate(true);
+    } else {
+        options.create_new(true);
+    }
+    let mut file = options.open(path).map_err(io_error)?;
+    file.write_all(CONFIG_TEMPLATE.as_bytes()).map_err(io_error)
+}
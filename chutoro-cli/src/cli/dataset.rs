@@ -0,0 +1,86 @@
+//! Content hashes that let a run manifest detect a changed dataset.
+//!
+//! Files are hashed byte for byte. Directories, which back image sources, are
+//! hashed over the images the provider would load: each one contributes its
+//! path relative to the directory, its length, and its bytes, in path order,
+//! so renaming, adding, removing, or editing an image changes the hash.
+
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+
+use chutoro_providers_image::image_paths;
+
+use super::commands::CliError;
+use super::input::is_stdin;
+
+/// Hashes the dataset at `path` with 64-bit FNV-1a, which is stable across
+/// platforms and toolchains. Standard input cannot be re-read, so it has no
+/// hash.
+pub(super) fn dataset_hash(path: &Path) -> Result<Option<String>, CliError> {
+    if is_stdin(path) {
+        return Ok(None);
+    }
+    let mut hasher = Fnv1a::default();
+    if path.is_dir() {
+        for image in image_paths(path)? {
+            let relative = image.strip_prefix(path).unwrap_or(&image);
+            // Separators are normalised so a manifest replays on any
+            // platform, and the length keeps file boundaries unambiguous.
+            let name = relative.to_string_lossy().replace('\\', "/");
+            let length = image
+                .metadata()
+                .map_err(|source| CliError::Io {
+                    path: image.clone(),
+                    source,
+                })?
+                .len();
+            hasher.update(name.as_bytes());
+            hasher.update(&[0]);
+            hasher.update(&length.to_le_bytes());
+            hash_file(&image, &mut hasher)?;
+        }
+    } else {
+        hash_file(path, &mut hasher)?;
+    }
+    Ok(Some(format!("fnv1a64:{:016x}", hasher.0)))
+}
+
+fn hash_file(path: &Path, hasher: &mut Fnv1a) -> Result<(), CliError> {
+    let io_error = |source| CliError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    let mut file = File::open(path).map_err(io_error)?;
+    io::copy(&mut file, hasher).map_err(io_error)?;
+    Ok(())
+}
+
+/// Streaming 64-bit FNV-1a hasher fed through [`io::copy`].
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn update(&mut self, bytes: &[u8]) {
+        const PRIME: u64 = 0x0000_0100_0000_01b3;
+        for &byte in bytes {
+            self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(PRIME);
+        }
+    }
+}
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Write for Fnv1a {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.update(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+//! Per-cluster detail for `chutoro run --detail clusters`.
+//!
+//! Automation only needs the assignments, but exploring a dataset by hand
+//! starts with how big each cluster is and what a typical member looks like.
+//! The table is computed while the data source is still loaded, since
+//! exemplars and intra-cluster distances need it, and rendered by the text
+//! and JSON summaries.
+
+use chutoro_core::{ClusteringResult, DataSource, ExemplarError};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Clusters with more members than this report no mean intra-cluster
+/// distance, which costs one distance evaluation per pair of members.
+pub const MEAN_DISTANCE_MEMBER_LIMIT: usize = 512;
+
+/// Optional sections added to the run summary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SummaryDetail {
+    /// A table with one row per cluster.
+    Clusters,
+}
+
+/// One row of the per-cluster table.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ClusterDetail {
+    /// The cluster label.
+    pub cluster: u64,
+    /// Whether the label holds noise points.
+    pub noise: bool,
+    /// Number of points with the label.
+    pub size: usize,
+    /// Share of all points with the label, in `[0, 1]`.
+    pub fraction: f64,
+    /// Row index of the cluster's most representative point; `None` for
+    /// noise.
+    pub exemplar: Option<usize>,
+    /// Mean distance between pairs of members; `None` for noise, singleton
+    /// clusters, and clusters above [`MEAN_DISTANCE_MEMBER_LIMIT`].
+    pub mean_distance: Option<f64>,
+}
+
+/// Summarizes each cluster of `result`, in label order.
+///
+/// `source` must be the data that was clustered.
+///
+/// # Errors
+/// Returns [`ExemplarError`] when `source` does not match `result` or a
+/// distance cannot be evaluated.
+///
+/// # Examples
+/// ```
+/// use chutoro_cli::cli::cluster_details;
+/// use chutoro_core::{ClusterId, ClusteringResult, DataSource, DataSourceError};
+///
+/// struct Line(Vec<f32>);
+///
+/// impl DataSource for Line {
+///     fn len(&self) -> usize { self.0.len() }
+///     fn name(&self) -> &str { "line" }
+///     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+///         Ok((self.0[i] - self.0[j]).abs())
+///     }
+/// }
+///
+/// let source = Line(vec![0.0, 1.0, 2.0, 10.0]);
+/// let result = ClusteringResult::from_assignments([0, 0, 0, 1].map(ClusterId::new).to_vec());
+///
+/// let details = cluster_details(&result, &source)?;
+/// assert_eq!(details[0].size, 3);
+/// assert_eq!(details[0].exemplar, Some(1));
+/// assert_eq!(details[1].mean_distance, None);
+/// # Ok::<(), chutoro_core::ExemplarError>(())
+/// ```
+pub fn cluster_details<D: DataSource + ?Sized>(
+    result: &ClusteringResult,
+    source: &D,
+) -> Result<Vec<ClusterDetail>, ExemplarError> {
+    let mut members = vec![Vec::new(); result.cluster_count()];
+    for (point, cluster) in result.assignments().iter().enumerate() {
+        if let Some(list) = usize::try_from(cluster.get())
+            .ok()
+            .and_then(|label| members.get_mut(label))
+        {
+            list.push(point);
+        }
+    }
+    let mut exemplars = vec![None; members.len()];
+    for cluster in result.exemplars(source, 1)? {
+        let slot = usize::try_from(cluster.cluster.get())
+            .ok()
+            .and_then(|label| exemplars.get_mut(label));
+        if let Some(slot) = slot {
+            *slot = cluster.points.first().copied();
+        }
+    }
+    let total = result.assignments().len().max(1) as f64;
+    let noise = result.noise_label().map(|label| label.get());
+    members
+        .iter()
+        .zip(exemplars)
+        .zip(0_u64..)
+        .map(|((points, exemplar), cluster)| {
+            let is_noise = noise == Some(cluster);
+            let mean_distance = if is_noise {
+                None
+            } else {
+                mean_distance(source, points)?
+            };
+            Ok(ClusterDetail {
+                cluster,
+                noise: is_noise,
+                size: points.len(),
+                fraction: points.len() as f64 / total,
+                exemplar,
+                mean_distance,
+            })
+        })
+        .collect()
+}
+
+/// Returns the mean distance between pairs of `points`, or `None` when there
+/// are fewer than two or more than [`MEAN_DISTANCE_MEMBER_LIMIT`].
+fn mean_distance<D: DataSource + ?Sized>(
+    source: &D,
+    points: &[usize],
+) -> Result<Option<f64>, ExemplarError> {
+    if !(2..=MEAN_DISTANCE_MEMBER_LIMIT).contains(&points.len()) {
+        return Ok(None);
+    }
+    let pairs: Vec<(usize, usize)> = points
+        .iter()
+        .enumerate()
+        .flat_map(|(offset, &left)| points[offset + 1..].iter().map(move |&right| (left, right)))
+        .collect();
+    let mut distances = vec![0.0; pairs.len()];
+    source.distance_batch(&pairs, &mut distances)?;
+    let sum: f64 = distances.iter().copied().map(f64::from).sum();
+    Ok(Some(sum / pairs.len() as f64))
+}
@@ -0,0 +1,183 @@
+//! The `run --dry-run` mode: pre-flight validation without clustering.
+//!
+//! A dry run loads the source exactly as `run` would, then asks the core for a
+//! [`DryRunReport`]: the parameters are validated, a seeded sample of
+//! distances is probed for non-finite, negative, and asymmetric values, and
+//! peak memory is estimated. No index is built and nothing is written, so
+//! `--output` and `--manifest` are ignored.
+
+use std::io::{self, Write};
+
+use chutoro_core::{Chutoro, DataSource, DryRunReport};
+use serde::Serialize;
+use tracing::{info, instrument};
+
+use super::args::{RunCommand, RunSource};
+use super::commands::{CliError, build_chutoro};
+use super::failure::ExitStatus;
+use super::images::image_provider;
+use super::inspect::{EstimateReport, render_estimate};
+use super::json::{JsonParameters, write_document};
+use super::sources::{parquet_provider, text_provider};
+
+/// Outcome of `chutoro run --dry-run`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DryRunSummary {
+    /// Name reported by the data source implementation.
+    pub data_source: String,
+    /// Number of points the source holds.
+    pub points: usize,
+    /// Findings of the dry run.
+    pub report: DryRunReport,
+}
+
+impl DryRunSummary {
+    /// Returns the exit status the `chutoro` binary reports for this summary.
+    ///
+    /// An estimate over `--max-bytes` maps to [`ExitStatus::ResourceLimit`],
+    /// any other failed check to [`ExitStatus::Data`].
+    #[must_use]
+    pub fn exit_status(&self) -> ExitStatus {
+        match (self.report.passed(), self.report.fits_memory()) {
+            (true, _) => ExitStatus::Success,
+            (false, Some(false)) => ExitStatus::ResourceLimit,
+            (false, _) => ExitStatus::Data,
+        }
+    }
+}
+
+/// Validates an already resolved `run` command without clustering.
+///
+/// # Errors
+/// Returns [`CliError::MissingSource`] when no data source is set, and the
+/// errors `run` would raise while validating parameters, loading the source,
+/// or evaluating distances.
+///
+/// # Examples
+/// ```
+/// # use std::error::Error;
+/// # use chutoro_cli::cli::{RunCommand, RunSource, TextArgs, TextMetric, dry_run_command};
+/// # use tempfile::NamedTempFile;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let file = NamedTempFile::new()?;
+/// std::fs::write(file.path(), "alpha\nbeta\ngamma\n")?;
+/// let command = RunCommand {
+///     min_cluster_size: Some(2),
+///     dry_run: true,
+///     source: Some(RunSource::Text(TextArgs {
+///         path: file.path().to_path_buf(),
+///         metric: TextMetric::Levenshtein,
+///         name: None,
+///     })),
+///     ..RunCommand::default()
+/// };
+/// let summary = dry_run_command(&command)?;
+/// assert_eq!(summary.points, 3);
+/// assert!(summary.report.passed());
+/// # Ok(())
+/// # }
+/// ```
+#[instrument(
+    name = "cli.dry_run",
+    err,
+    skip(command),
+    fields(source = %command.source.as_ref().map_or("<none>", RunSource::kind)),
+)]
+pub fn dry_run_command(command: &RunCommand) -> Result<DryRunSummary, CliError> {
+    let chutoro = build_chutoro(command)?;
+    let summary = match command.source.as_ref().ok_or(CliError::MissingSource)? {
+        RunSource::Parquet(args) => probe(&chutoro, &parquet_provider(args)?)?,
+        RunSource::Text(args) => probe(&chutoro, &text_provider(args)?)?,
+        RunSource::Images(args) => probe(&chutoro, &image_provider(args)?)?,
+    };
+    info!(
+        data_source = summary.data_source.as_str(),
+        passed = summary.report.passed(),
+        "dry run completed"
+    );
+    Ok(summary)
+}
+
+fn probe<D: DataSource>(chutoro: &Chutoro, provider: &D) -> Result<DryRunSummary, CliError> {
+    let report = chutoro.dry_run(provider)?;
+    Ok(DryRunSummary {
+        data_source: provider.name().to_owned(),
+        points: provider.len(),
+        report,
+    })
+}
+
+/// Renders `summary` to `writer` as human-readable text.
+///
+/// # Errors
+/// Returns [`io::Error`] if writing to the supplied writer fails.
+pub fn render_dry_run(summary: &DryRunSummary, mut writer: impl Write) -> io::Result<()> {
+    let report = &summary.report;
+    writeln!(writer, "data source: {}", summary.data_source)?;
+    writeln!(writer, "points: {}", summary.points)?;
+    writeln!(
+        writer,
+        "probes: pairs={} non_finite={} negative={} identity={} asymmetric={}",
+        report.probed_pairs(),
+        report.non_finite_pairs(),
+        report.negative_pairs(),
+        report.identity_pairs(),
+        report.asymmetric_pairs(),
+    )?;
+    let estimate = EstimateReport::new(&report.estimate(), report.max_bytes());
+    render_estimate(&estimate, &mut writer)?;
+    let verdict = if report.passed() { "passed" } else { "failed" };
+    writeln!(writer, "dry run: {verdict}")
+}
+
+/// Renders `summary` for `command` to `writer` as JSON.
+///
+/// `status` is `"ok"` when every check passed and `"failed"` otherwise; the
+/// parameters match those of a `run` document.
+///
+/// # Errors
+/// Returns [`io::Error`] if serialization or writing fails.
+pub fn render_dry_run_json(
+    summary: &DryRunSummary,
+    command: &RunCommand,
+    writer: impl Write,
+) -> io::Result<()> {
+    let report = &summary.report;
+    let document = DryRunDocument {
+        status: if report.passed() { "ok" } else { "failed" },
+        dry_run: true,
+        data_source: &summary.data_source,
+        points: summary.points,
+        probes: JsonProbes {
+            pairs: report.probed_pairs(),
+            non_finite: report.non_finite_pairs(),
+            negative: report.negative_pairs(),
+            identity: report.identity_pairs(),
+            asymmetric: report.asymmetric_pairs(),
+        },
+        estimate: EstimateReport::new(&report.estimate(), report.max_bytes()),
+        parameters: JsonParameters::from(command),
+    };
+    write_document(&document, writer)
+}
+
+#[derive(Serialize)]
+struct DryRunDocument<'a> {
+    status: &'static str,
+    dry_run: bool,
+    data_source: &'a str,
+    points: usize,
+    probes: JsonProbes,
+    estimate: EstimateReport,
+    parameters: JsonParameters<'a>,
+}
+
+#[derive(Serialize)]
+struct JsonProbes {
+    pairs: usize,
+    non_finite: usize,
+    negative: usize,
+    identity: usize,
+    asymmetric: usize,
+}
@@ -0,0 +1,273 @@
+//! Classification of CLI failures into exit statuses and remediation hints.
+//!
+//! Orchestrators branch on the process exit status, so every [`CliError`] maps
+//! onto one documented [`ExitStatus`]. Core failures are classified by their
+//! stable `ChutoroErrorCode`, `DataSourceErrorCode`, and `HnswErrorCode`
+//! identifiers rather than their messages. Hints suggest the flag or file to
+//! change and are attached to the logged error and to JSON failure documents.
+
+use std::process::ExitCode;
+
+use chutoro_core::{
+    ChutoroError, ChutoroErrorCode, DataSourceErrorCode, ExemplarError, HnswErrorCode,
+};
+use chutoro_providers_dense::DenseMatrixProviderError;
+use chutoro_providers_image::ImageProviderError;
+use chutoro_providers_text::TextProviderError;
+
+use super::commands::CliError;
+
+/// Process exit statuses reported by the `chutoro` binary.
+///
+/// # Examples
+/// ```
+/// use chutoro_cli::cli::{CliError, ExitStatus};
+///
+/// assert_eq!(CliError::MissingSource.exit_status(), ExitStatus::Config);
+/// assert_eq!(ExitStatus::Config.code(), 3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExitStatus {
+    /// The command completed.
+    Success,
+    /// An unclassified or internal failure, such as a broken graph invariant.
+    Failure,
+    /// The command line could not be parsed; reported by `clap` itself.
+    Usage,
+    /// Flags or the configuration file are invalid or incomplete.
+    Config,
+    /// An input or output file could not be read or written.
+    Io,
+    /// The input data is unusable: a missing or mistyped column, nulls, no
+    /// rows, invalid text, or non-finite distances.
+    Data,
+    /// The run would exceed `--max-bytes` or its distance budget.
+    ResourceLimit,
+    /// A run manifest is unreadable or no longer matches its dataset.
+    Manifest,
+    /// Distance evaluation was cancelled.
+    Cancelled,
+}
+
+impl ExitStatus {
+    /// Returns the numeric process exit code.
+    #[must_use]
+    pub const fn code(self) -> u8 {
+        match self {
+            Self::Success => 0,
+            Self::Failure => 1,
+            Self::Usage => 2,
+            Self::Config => 3,
+            Self::Io => 4,
+            Self::Data => 5,
+            Self::ResourceLimit => 6,
+            Self::Manifest => 7,
+            Self::Cancelled => 8,
+        }
+    }
+}
+
+impl From<ExitStatus> for ExitCode {
+    fn from(status: ExitStatus) -> Self {
+        Self::from(status.code())
+    }
+}
+
+impl CliError {
+    /// Returns the exit status the `chutoro` binary reports for this error.
+    #[must_use]
+    pub fn exit_status(&self) -> ExitStatus {
+        match self {
+            CliError::Io { .. } | CliError::Parquet { .. } | CliError::Write(_) => ExitStatus::Io,
+            CliError::UnsupportedCompression { .. }
+            | CliError::ConfigParse { .. }
+            | CliError::InvalidConfig { .. }
+            | CliError::MissingSource
+            | CliError::NotARun { .. } => ExitStatus::Config,
+            CliError::IdColumnNotFound { .. }
+            | CliError::IdRowCount { .. }
+            | CliError::LabelColumnNotFound { .. }
+            | CliError::UnsupportedLabelColumn { .. }
+            | CliError::Quality(_) => ExitStatus::Data,
+            CliError::ManifestParse { .. }
+            | CliError::UnsupportedManifest { .. }
+            | CliError::DatasetMismatch { .. } => ExitStatus::Manifest,
+            CliError::Hnsw(error) => hnsw_status(error.code()),
+            CliError::Dense(error) => dense_status(error),
+            CliError::Text(TextProviderError::Io(_))
+            | CliError::Image(ImageProviderError::Io { .. }) => ExitStatus::Io,
+            CliError::Image(ImageProviderError::ZeroPixelSide) => ExitStatus::Config,
+            CliError::Text(_) | CliError::Image(_) => ExitStatus::Data,
+            CliError::Core(error) => core_status(error),
+            CliError::Stability(_) => ExitStatus::Failure,
+            CliError::ClusterDetail(ExemplarError::DataSource(error)) => {
+                data_source_status(error.code())
+            }
+            CliError::ClusterDetail(_) => ExitStatus::Failure,
+        }
+    }
+
+    /// Returns a suggestion for fixing the failure, when one is known.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_cli::cli::CliError;
+    ///
+    /// let error = CliError::IdColumnNotFound {
+    ///     column: "uuid".into(),
+    ///     available: vec!["features".into(), "id".into()],
+    /// };
+    /// assert_eq!(
+    ///     error.hint().as_deref(),
+    ///     Some("available columns are: features, id")
+    /// );
+    /// ```
+    #[must_use]
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            CliError::Io { path, .. } => Some(format!(
+                "check that `{}` exists and is readable",
+                path.display()
+            )),
+            CliError::UnsupportedCompression { format, .. } => Some(format!(
+                "decompress the file first, or build chutoro-cli with the `{format}` feature"
+            )),
+            CliError::ConfigParse { .. } | CliError::InvalidConfig { .. } => Some(
+                "`chutoro config init` prints a template listing every accepted key".to_owned(),
+            ),
+            CliError::MissingSource => Some(
+                "add a source such as `parquet <path> --columns <name>`, or a [source] table \
+                 in --config"
+                    .to_owned(),
+            ),
+            CliError::IdColumnNotFound { available, .. }
+            | CliError::LabelColumnNotFound { available, .. } => Some(available_columns(available)),
+            CliError::UnsupportedLabelColumn { .. } => {
+                Some("label columns must hold strings, integers, or booleans".to_owned())
+            }
+            CliError::UnsupportedManifest { .. } | CliError::DatasetMismatch { .. } => {
+                Some("record a new manifest by re-running with `--manifest <path>`".to_owned())
+            }
+            CliError::Hnsw(_) => Some(
+                "--hnsw-max-connections must be positive and --hnsw-ef-construction at least \
+                 as large"
+                    .to_owned(),
+            ),
+            CliError::Dense(error) => dense_hint(error),
+            CliError::Core(error) => core_hint(error),
+            _ => None,
+        }
+    }
+}
+
+fn hnsw_status(code: HnswErrorCode) -> ExitStatus {
+    match code {
+        HnswErrorCode::InvalidParameters => ExitStatus::Config,
+        HnswErrorCode::EmptyBuild
+        | HnswErrorCode::NonFiniteDistance
+        | HnswErrorCode::DataSource => ExitStatus::Data,
+        _ => ExitStatus::Failure,
+    }
+}
+
+fn dense_status(error: &DenseMatrixProviderError) -> ExitStatus {
+    match error {
+        DenseMatrixProviderError::Io(_) | DenseMatrixProviderError::Parquet(_) => ExitStatus::Io,
+        _ => ExitStatus::Data,
+    }
+}
+
+fn data_source_status(code: DataSourceErrorCode) -> ExitStatus {
+    match code {
+        DataSourceErrorCode::DistanceBudgetExhausted => ExitStatus::ResourceLimit,
+        DataSourceErrorCode::Cancelled => ExitStatus::Cancelled,
+        _ => ExitStatus::Data,
+    }
+}
+
+fn core_status(error: &ChutoroError) -> ExitStatus {
+    if let Some(code) = error.data_source_code() {
+        return data_source_status(code);
+    }
+    if let ChutoroError::CpuHnswFailure { code, .. } = error {
+        // Core keeps the HNSW code as its string identifier.
+        return [
+            HnswErrorCode::InvalidParameters,
+            HnswErrorCode::EmptyBuild,
+            HnswErrorCode::NonFiniteDistance,
+        ]
+        .into_iter()
+        .find(|candidate| candidate.as_str() == &**code)
+        .map_or(ExitStatus::Failure, hnsw_status);
+    }
+    match error.code() {
+        ChutoroErrorCode::InvalidMinClusterSize
+        | ChutoroErrorCode::BackendUnavailable
+        | ChutoroErrorCode::InvalidSample
+        | ChutoroErrorCode::InvalidReassignPolicy
+        | ChutoroErrorCode::InvalidOnlineConfig
+        | ChutoroErrorCode::InvalidCoreDistances
+        | ChutoroErrorCode::InvalidDeduplication
+        | ChutoroErrorCode::InvalidGraphBuilder
+        | ChutoroErrorCode::PrebuiltIndexMismatch => ExitStatus::Config,
+        ChutoroErrorCode::EmptySource
+        | ChutoroErrorCode::InsufficientItems
+        | ChutoroErrorCode::InvalidKnnGraph => ExitStatus::Data,
+        ChutoroErrorCode::MemoryLimitExceeded | ChutoroErrorCode::DistanceBudgetExceeded => {
+            ExitStatus::ResourceLimit
+        }
+        ChutoroErrorCode::SpillFailure
+        | ChutoroErrorCode::EventLogFailure
+        | ChutoroErrorCode::CheckpointFailure => ExitStatus::Io,
+        _ => ExitStatus::Failure,
+    }
+}
+
+fn dense_hint(error: &DenseMatrixProviderError) -> Option<String> {
+    match error {
+        DenseMatrixProviderError::ColumnNotFound { available, .. } => {
+            Some(available_columns(available))
+        }
+        DenseMatrixProviderError::InvalidColumnType { .. }
+        | DenseMatrixProviderError::InvalidListValueType { .. } => Some(
+            "feature columns must be Float16, Float32, or Float64, or FixedSizeLists of them; \
+             `chutoro inspect` shows each column's type"
+                .to_owned(),
+        ),
+        DenseMatrixProviderError::NullableField { .. }
+        | DenseMatrixProviderError::NullRow { .. }
+        | DenseMatrixProviderError::NullValue { .. } => Some(
+            "drop or impute nulls before clustering; `chutoro inspect` counts them per column"
+                .to_owned(),
+        ),
+        DenseMatrixProviderError::LossyNarrowingDisabled => Some(
+            "pass --lossy-f64 (or set lossy_f64 = true) to narrow Float64 columns to f32"
+                .to_owned(),
+        ),
+        _ => None,
+    }
+}
+
+fn core_hint(error: &ChutoroError) -> Option<String> {
+    match error {
+        ChutoroError::InvalidMinClusterSize { .. } => {
+            Some("--min-cluster-size must be at least 1".to_owned())
+        }
+        ChutoroError::InsufficientItems { .. } => {
+            Some("lower --min-cluster-size to at most the number of rows in the input".to_owned())
+        }
+        ChutoroError::MemoryLimitExceeded { .. } => Some(
+            "raise --max-bytes or lower --hnsw-max-connections; `chutoro inspect` shows the \
+             estimate"
+                .to_owned(),
+        ),
+        _ => None,
+    }
+}
+
+fn available_columns(available: &[String]) -> String {
+    if available.is_empty() {
+        return "the input has no columns".to_owned();
+    }
+    format!("available columns are: {}", available.join(", "))
+}
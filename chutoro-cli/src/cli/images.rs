@@ -0,0 +1,53 @@
+//! Image folder execution for `chutoro run images`.
+
+use std::path::Path;
+
+use chutoro_core::Chutoro;
+use chutoro_providers_image::ImageFolderProvider;
+use tracing::instrument;
+
+use super::args::ImageArgs;
+use super::commands::{CliError, ExecutionSummary, execute_with_provider, path_label};
+use super::details::SummaryDetail;
+
+#[instrument(
+    name = "cli.run_images",
+    err,
+    skip(chutoro, args),
+    fields(
+        path = %path_label(&args.path),
+        feature = args.feature.label(),
+        override_name = %args.name.as_deref().unwrap_or("<derived>")
+    ),
+)]
+pub(super) fn run_images(
+    chutoro: &Chutoro,
+    args: ImageArgs,
+    detail: Option<SummaryDetail>,
+) -> Result<ExecutionSummary, CliError> {
+    execute_with_provider(chutoro, image_provider(&args)?, detail)
+}
+
+/// Loads the image folder named by `args`.
+pub(super) fn image_provider(args: &ImageArgs) -> Result<ImageFolderProvider, CliError> {
+    let feature = args.image_feature();
+    let chosen_name = args
+        .name
+        .clone()
+        .unwrap_or_else(|| directory_name(&args.path));
+    Ok(ImageFolderProvider::try_from_dir(
+        chosen_name,
+        &args.path,
+        feature,
+    )?)
+}
+
+/// Names the source after its directory. Directory names are kept whole,
+/// unlike file stems, and `.` resolves to the working directory's name.
+fn directory_name(path: &Path) -> String {
+    let resolved = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    resolved
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map_or_else(|| "images".to_owned(), ToOwned::to_owned)
+}
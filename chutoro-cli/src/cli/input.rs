@@ -0,0 +1,135 @@
+//! Opening text inputs from files, standard input, and compressed archives.
+//!
+//! The text source accepts `-` to read standard input so log pipelines such as
+//! `zcat logs.gz | chutoro run text - --metric levenshtein` work without
+//! temporary files. Files ending in `.gz` or `.zst` are decompressed
+//! transparently when the matching `gzip` or `zstd` feature is enabled.
+
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use tracing::instrument;
+
+use super::commands::{CliError, path_label};
+
+/// Path argument that selects standard input instead of a file.
+pub(super) const STDIN_PATH: &str = "-";
+
+/// Compression formats recognised from a text input's file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Compression {
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    /// Detects the compression format from the extension of `path`.
+    pub(super) fn from_path(path: &Path) -> Self {
+        let extension = path
+            .extension()
+            .and_then(|value| value.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("gz") => Self::Gzip,
+            Some("zst" | "zstd") => Self::Zstd,
+            _ => Self::None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Gzip => "gzip",
+            Self::Zstd => "zstd",
+        }
+    }
+}
+
+/// Returns whether `path` selects standard input.
+pub(super) fn is_stdin(path: &Path) -> bool {
+    path == Path::new(STDIN_PATH)
+}
+
+/// Strips a compression extension so `logs.txt.gz` names like `logs.txt`.
+pub(super) fn logical_path(path: &Path) -> Cow<'_, Path> {
+    match Compression::from_path(path) {
+        Compression::None => Cow::Borrowed(path),
+        Compression::Gzip | Compression::Zstd => Cow::Owned(path.with_extension("")),
+    }
+}
+
+/// Opens `path` for line-oriented reading, decompressing it when required.
+///
+/// # Errors
+/// Returns [`CliError::Io`] when the file cannot be opened or the decoder
+/// cannot be initialized, and [`CliError::UnsupportedCompression`] when the
+/// file is compressed with a format whose feature is disabled.
+#[instrument(
+    name = "cli.open_text_reader",
+    err,
+    skip(path),
+    fields(
+        path = %path_label(path),
+        compression = Compression::from_path(path).label()
+    )
+)]
+pub(super) fn open_text_reader(path: &Path) -> Result<Box<dyn BufRead>, CliError> {
+    if is_stdin(path) {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+    let file = File::open(path).map_err(|source| CliError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    decompress(Compression::from_path(path), path, file)
+}
+
+fn decompress(
+    compression: Compression,
+    path: &Path,
+    file: File,
+) -> Result<Box<dyn BufRead>, CliError> {
+    match compression {
+        Compression::None => Ok(Box::new(BufReader::new(file))),
+        #[cfg(feature = "gzip")]
+        Compression::Gzip => {
+            // Multi-member decoding handles concatenated archives such as
+            // rotated logs joined with `cat`.
+            let decoder = flate2::bufread::MultiGzDecoder::new(BufReader::new(file));
+            Ok(Box::new(BufReader::new(decoder)))
+        }
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => {
+            let decoder = zstd::Decoder::new(file).map_err(|source| CliError::Io {
+                path: path.to_path_buf(),
+                source,
+            })?;
+            Ok(Box::new(BufReader::new(decoder)))
+        }
+        #[cfg(not(all(feature = "gzip", feature = "zstd")))]
+        unsupported => Err(CliError::UnsupportedCompression {
+            path: path.to_path_buf(),
+            format: unsupported.label(),
+        }),
+    }
+}
+
+/// Returns `override_name` if given, else the file stem of `path`, or
+/// `stdin` when reading standard input.
+pub(super) fn derive_data_source_name(path: &Path, override_name: Option<&str>) -> String {
+    if let Some(name) = override_name {
+        return name.to_owned();
+    }
+    if is_stdin(path) {
+        return "stdin".to_owned();
+    }
+
+    logical_path(path)
+        .file_stem()
+        .and_then(|value| value.to_str())
+        .map(ToOwned::to_owned)
+        .unwrap_or_else(|| "data_source".to_owned())
+}
@@ -0,0 +1,363 @@
+//! The `inspect` command: a pre-flight summary of a Parquet input.
+//!
+//! Inspection streams the requested feature columns batch by batch and
+//! reports what a `run` would see — row count, dimensionality, nulls, value
+//! ranges, the metric — together with the pipeline's estimated peak memory.
+//! Rows are never copied into a dense matrix, so nulls are counted rather
+//! than rejected and large files can be checked before a long run is queued.
+
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+
+use arrow_array::{
+    Array, ArrayRef, RecordBatchReader,
+    cast::AsArray,
+    types::{Float16Type, Float32Type, Float64Type},
+};
+use arrow_schema::{DataType, Schema};
+use chutoro_core::{ChutoroBuilder, DataSource, DataSourceError, ResourceEstimate, format_bytes};
+use chutoro_providers_dense::{DenseMatrixProvider, DenseMatrixProviderError};
+use parquet::arrow::{ProjectionMask, arrow_reader::ParquetRecordBatchReaderBuilder};
+use parquet::errors::ParquetError;
+use serde::Serialize;
+use tracing::instrument;
+
+use super::args::InspectCommand;
+use super::commands::{CliError, path_label};
+use super::json::write_document;
+use super::render::SummaryFormat;
+
+/// Summary of a Parquet input produced by `chutoro inspect`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InspectReport {
+    /// File name of the inspected input.
+    pub file: String,
+    /// Number of rows in the file.
+    pub rows: usize,
+    /// Feature dimensionality once the columns are concatenated per row.
+    pub dimension: usize,
+    /// Metric a Parquet `run` compares rows with.
+    pub metric: String,
+    /// Statistics for each inspected column, in the order requested.
+    pub columns: Vec<ColumnReport>,
+    /// Estimated memory needed to cluster every row.
+    pub estimate: EstimateReport,
+}
+
+/// Statistics for one feature column.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ColumnReport {
+    /// Column name.
+    pub name: String,
+    /// Arrow data type of the column.
+    pub data_type: String,
+    /// Features the column contributes to each row.
+    pub dimension: usize,
+    /// Rows where the column itself is null.
+    pub null_rows: usize,
+    /// Null elements inside non-null list rows.
+    pub null_values: usize,
+    /// NaN or infinite values.
+    pub non_finite_values: usize,
+    /// Smallest finite value, if any.
+    pub min: Option<f64>,
+    /// Largest finite value, if any.
+    pub max: Option<f64>,
+}
+
+/// Estimated pipeline memory, mirroring [`ResourceEstimate`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EstimateReport {
+    /// Estimated peak bytes; the figure `--max-bytes` is checked against.
+    pub peak_bytes: u64,
+    /// Bytes held by the HNSW index.
+    pub index_bytes: u64,
+    /// Bytes held by harvested candidate edges.
+    pub edge_harvest_bytes: u64,
+    /// Bytes held by the minimum spanning tree.
+    pub mst_bytes: u64,
+    /// Bytes held while extracting the hierarchy.
+    pub hierarchy_bytes: u64,
+    /// Bytes of the loaded feature matrix.
+    pub source_bytes: Option<u64>,
+    /// The `--max-bytes` limit, when given.
+    pub max_bytes: Option<u64>,
+    /// Whether the peak fits within `max_bytes`, when a limit is given.
+    pub fits: Option<bool>,
+}
+
+impl EstimateReport {
+    pub(super) fn new(estimate: &ResourceEstimate, max_bytes: Option<u64>) -> Self {
+        Self {
+            peak_bytes: estimate.peak_bytes(),
+            index_bytes: estimate.index_bytes(),
+            edge_harvest_bytes: estimate.edge_harvest_bytes(),
+            mst_bytes: estimate.mst_bytes(),
+            hierarchy_bytes: estimate.hierarchy_bytes(),
+            source_bytes: estimate.source_bytes(),
+            max_bytes,
+            fits: max_bytes.map(|limit| estimate.fits_within(limit)),
+        }
+    }
+}
+
+/// Inspects the Parquet input named by `command`.
+///
+/// # Errors
+/// Returns [`CliError::Io`] or [`CliError::Parquet`] when the file cannot be
+/// read, [`CliError::Dense`] when a column is missing or is not a float or
+/// `FixedSizeList` of floats, and [`CliError::Hnsw`] or [`CliError::Core`]
+/// when the HNSW options or memory limit are invalid.
+#[instrument(
+    name = "cli.inspect",
+    err,
+    skip(command),
+    fields(path = %path_label(&command.parquet), columns = %command.columns.join(","))
+)]
+pub fn inspect_parquet(command: &InspectCommand) -> Result<InspectReport, CliError> {
+    let hnsw = command.hnsw.to_params().map_err(CliError::Hnsw)?;
+    let mut builder = ChutoroBuilder::new().with_hnsw_params(hnsw);
+    if let Some(bytes) = command.max_bytes {
+        builder = builder.with_max_bytes(bytes);
+    }
+    let chutoro = builder.build()?;
+
+    let (rows, columns) = scan_columns(&command.parquet, &command.columns)?;
+    let shape = Shape {
+        name: path_label(&command.parquet),
+        rows,
+        dimension: columns.iter().map(|column| column.dimension).sum(),
+    };
+    let estimate = chutoro.estimate_resources(&shape);
+    Ok(InspectReport {
+        rows,
+        dimension: shape.dimension,
+        metric: DenseMatrixProvider::METRIC.to_owned(),
+        columns,
+        estimate: EstimateReport::new(&estimate, command.max_bytes),
+        file: shape.name,
+    })
+}
+
+/// Inspects the input named by `command` and renders the report to `writer`
+/// in the format selected by `--format` or `--json`.
+///
+/// # Errors
+/// Returns the errors of [`inspect_parquet`], and [`CliError::Write`] when
+/// writing the report fails.
+pub fn run_inspect(command: &InspectCommand, writer: impl Write) -> Result<(), CliError> {
+    let report = inspect_parquet(command)?;
+    let rendered = match command.output.summary_format() {
+        SummaryFormat::Text => render_inspect(&report, writer),
+        SummaryFormat::Json => write_document(&report, writer),
+    };
+    rendered.map_err(CliError::Write)
+}
+
+/// Renders `report` to `writer` as human-readable text.
+///
+/// # Errors
+/// Returns [`io::Error`] if writing to the supplied writer fails.
+pub fn render_inspect(report: &InspectReport, mut writer: impl Write) -> io::Result<()> {
+    writeln!(writer, "file: {}", report.file)?;
+    writeln!(writer, "rows: {}", report.rows)?;
+    writeln!(writer, "dimension: {}", report.dimension)?;
+    writeln!(writer, "metric: {}", report.metric)?;
+    for column in &report.columns {
+        let range = match (column.min, column.max) {
+            (Some(min), Some(max)) => format!("[{min}, {max}]"),
+            _ => "none".to_owned(),
+        };
+        writeln!(
+            writer,
+            "column {}: type={} dimension={} null_rows={} null_values={} non_finite={} range={range}",
+            column.name,
+            column.data_type,
+            column.dimension,
+            column.null_rows,
+            column.null_values,
+            column.non_finite_values,
+        )?;
+    }
+    render_estimate(&report.estimate, writer)
+}
+
+pub(super) fn render_estimate(estimate: &EstimateReport, mut writer: impl Write) -> io::Result<()> {
+    writeln!(
+        writer,
+        "estimate: peak={} index={} edge_harvest={} mst={} hierarchy={} source={}",
+        format_bytes(estimate.peak_bytes),
+        format_bytes(estimate.index_bytes),
+        format_bytes(estimate.edge_harvest_bytes),
+        format_bytes(estimate.mst_bytes),
+        format_bytes(estimate.hierarchy_bytes),
+        estimate
+            .source_bytes
+            .map_or_else(|| "unknown".to_owned(), format_bytes),
+    )?;
+    if let (Some(limit), Some(fits)) = (estimate.max_bytes, estimate.fits) {
+        let verdict = if fits { "fits" } else { "exceeds" };
+        writeln!(writer, "max_bytes: {} ({verdict})", format_bytes(limit))?;
+    }
+    Ok(())
+}
+
+/// Reads `columns` from `path`, returning the row count and the statistics
+/// of each column.
+fn scan_columns(path: &Path, columns: &[String]) -> Result<(usize, Vec<ColumnReport>), CliError> {
+    let parquet_error = |source| CliError::Parquet {
+        path: path.to_path_buf(),
+        source,
+    };
+    let file = File::open(path).map_err(|source| CliError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let builder = ParquetRecordBatchReaderBuilder::try_new(file).map_err(parquet_error)?;
+    // Validate against the full schema so a missing column can be reported
+    // alongside the columns the file does contain.
+    let mut scans = columns
+        .iter()
+        .map(|column| ColumnScan::new(builder.schema(), column))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mask =
+        ProjectionMask::columns(builder.parquet_schema(), columns.iter().map(String::as_str));
+    let reader = builder
+        .with_projection(mask)
+        .build()
+        .map_err(parquet_error)?;
+    let schema = reader.schema();
+    for scan in &mut scans {
+        scan.index = schema
+            .index_of(&scan.report.name)
+            .map_err(|err| parquet_error(ParquetError::from(err)))?;
+    }
+
+    let mut rows = 0;
+    for batch in reader {
+        let batch = batch.map_err(|err| parquet_error(ParquetError::from(err)))?;
+        rows += batch.num_rows();
+        for scan in &mut scans {
+            scan.add(batch.column(scan.index));
+        }
+    }
+    Ok((rows, scans.into_iter().map(|scan| scan.report).collect()))
+}
+
+/// Running statistics for one column.
+struct ColumnScan {
+    /// Position of the column in the projected record batches.
+    index: usize,
+    report: ColumnReport,
+}
+
+impl ColumnScan {
+    /// Locates `column` in `schema` and checks that it holds float features.
+    fn new(schema: &Schema, column: &str) -> Result<Self, DenseMatrixProviderError> {
+        let index =
+            schema
+                .index_of(column)
+                .map_err(|_| DenseMatrixProviderError::ColumnNotFound {
+                    column: column.to_owned(),
+                    available: schema
+                        .fields()
+                        .iter()
+                        .map(|field| field.name().clone())
+                        .collect(),
+                })?;
+        let data_type = schema.field(index).data_type();
+        let (element, dimension) = match data_type {
+            DataType::FixedSizeList(item, width) => (item.data_type(), *width),
+            other => (other, 1),
+        };
+        if !matches!(
+            element,
+            DataType::Float16 | DataType::Float32 | DataType::Float64
+        ) {
+            return Err(DenseMatrixProviderError::InvalidColumnType {
+                column: column.to_owned(),
+                actual: data_type.clone(),
+            });
+        }
+        let dimension = usize::try_from(dimension)
+            .ok()
+            .filter(|&width| width > 0)
+            .ok_or(DenseMatrixProviderError::InvalidDimension { actual: dimension })?;
+        Ok(Self {
+            index,
+            report: ColumnReport {
+                name: column.to_owned(),
+                data_type: data_type.to_string(),
+                dimension,
+                null_rows: 0,
+                null_values: 0,
+                non_finite_values: 0,
+                min: None,
+                max: None,
+            },
+        })
+    }
+
+    fn add(&mut self, array: &ArrayRef) {
+        self.report.null_rows += array.null_count();
+        let Some(list) = array.as_fixed_size_list_opt() else {
+            self.add_values(array.as_ref());
+            return;
+        };
+        for row in (0..list.len()).filter(|&row| list.is_valid(row)) {
+            let values = list.value(row);
+            self.report.null_values += values.null_count();
+            self.add_values(values.as_ref());
+        }
+    }
+
+    /// Folds the non-null values of a float array into the statistics.
+    fn add_values(&mut self, array: &dyn Array) {
+        match array.data_type() {
+            DataType::Float16 => self.fold(array.as_primitive::<Float16Type>().iter()),
+            DataType::Float32 => self.fold(array.as_primitive::<Float32Type>().iter()),
+            DataType::Float64 => self.fold(array.as_primitive::<Float64Type>().iter()),
+            _ => {}
+        }
+    }
+
+    fn fold<T: Into<f64>>(&mut self, values: impl Iterator<Item = Option<T>>) {
+        for value in values.flatten().map(Into::into) {
+            if !value.is_finite() {
+                self.report.non_finite_values += 1;
+                continue;
+            }
+            let report = &mut self.report;
+            report.min = Some(report.min.map_or(value, |min| min.min(value)));
+            report.max = Some(report.max.map_or(value, |max| max.max(value)));
+        }
+    }
+}
+
+/// Row count and dimensionality of an inspected file, standing in for the
+/// provider a `run` would load so the estimate honours the same settings.
+struct Shape {
+    name: String,
+    rows: usize,
+    dimension: usize,
+}
+
+impl DataSource for Shape {
+    fn len(&self) -> usize {
+        self.rows
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn dimension_hint(&self) -> Option<usize> {
+        Some(self.dimension)
+    }
+
+    fn distance(&self, i: usize, _: usize) -> Result<f32, DataSourceError> {
+        // Estimation never evaluates distances, and the rows are not loaded.
+        Err(DataSourceError::OutOfBounds { index: i })
+    }
+}
@@ -0,0 +1,296 @@
+//! Machine-readable JSON summaries for `chutoro run --format json`.
+//!
+//! Each invocation writes exactly one JSON document terminated by a newline.
+//! Successful runs report the dataset, clustering statistics, timings, and the
+//! parameters used; failures report the error message and any stable error
+//! codes so CI jobs and orchestrators can branch on them without parsing logs.
+
+use std::io::{self, Write};
+use std::time::Duration;
+
+use chutoro_core::{StageTimings, Warning};
+use serde::Serialize;
+
+use super::args::{ImageFeatureKind, RunCommand, RunSource};
+use super::commands::{CliError, ExecutionSummary};
+use super::details::ClusterDetail;
+
+/// Renders a successful `summary` for `command` to `writer` as JSON.
+///
+/// Durations are reported in fractional milliseconds. `timings` is `null`
+/// when the run did not record them. `warnings` lists each non-fatal event
+/// the run raised as its stable code and message. `quality` holds the `ari`
+/// and `nmi` scores against `--label-column`, or `null` without one.
+/// `cluster_details` lists the `--detail clusters` table, with each row's
+/// `cluster`, `noise` flag, `size`, `fraction`, `exemplar`, and
+/// `mean_distance`, or is `null` without it. Pass the resolved command so the
+/// reported parameters include values loaded from `--config`.
+///
+/// # Errors
+/// Returns [`io::Error`] if serialization or writing fails.
+///
+/// # Examples
+/// ```
+/// # use std::error::Error;
+/// # use chutoro_cli::cli::{
+/// #     ExecutionSummary, RunCommand, RunSource, TextArgs, TextMetric, render_summary_json,
+/// # };
+/// # use chutoro_core::{ClusteringResult, ClusterId};
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let command = RunCommand {
+///     min_cluster_size: Some(2),
+///     source: Some(RunSource::Text(TextArgs {
+///         path: "demo.txt".into(),
+///         metric: TextMetric::Levenshtein,
+///         name: None,
+///     })),
+///     ..RunCommand::default()
+/// };
+/// let summary = ExecutionSummary {
+///     data_source: "demo".into(),
+///     result: ClusteringResult::from_assignments(vec![ClusterId::new(0)]),
+///     quality: None,
+///     clusters: None,
+/// };
+/// let mut buffer = Vec::new();
+/// render_summary_json(&summary, &command, &mut buffer)?;
+/// let text = String::from_utf8(buffer)?;
+/// assert!(text.starts_with(r#"{"status":"ok","data_source":"demo","points":1"#));
+/// # Ok(())
+/// # }
+/// ```
+pub fn render_summary_json(
+    summary: &ExecutionSummary,
+    command: &RunCommand,
+    writer: impl Write,
+) -> io::Result<()> {
+    let result = &summary.result;
+    let document = SuccessDocument {
+        status: "ok",
+        data_source: &summary.data_source,
+        points: result.assignments().len(),
+        clusters: result.cluster_count(),
+        noise_fraction: result.noise_fraction(),
+        timings: result.timings().map(JsonTimings::from),
+        warnings: result.warnings().iter().map(JsonWarning::from).collect(),
+        quality: summary.quality.map(|score| JsonQuality {
+            ari: score.ari,
+            nmi: score.nmi,
+        }),
+        cluster_details: summary.clusters.as_deref(),
+        parameters: JsonParameters::from(command),
+        assignments: result.assignments().iter().map(|id| id.get()).collect(),
+    };
+    write_document(&document, writer)
+}
+
+/// Renders a failed run of `command` to `writer` as JSON.
+///
+/// `code` and `data_source_code` carry the stable identifiers exposed by
+/// [`CliError::code`] and [`CliError::data_source_code`], or `null` when the
+/// failure has none. `hint` carries [`CliError::hint`], and `exit_code` the
+/// process exit status from [`CliError::exit_status`]. Parameters that were
+/// never supplied, such as a missing source, are reported as `null`.
+///
+/// # Errors
+/// Returns [`io::Error`] if serialization or writing fails.
+pub fn render_failure_json(
+    error: &CliError,
+    command: &RunCommand,
+    writer: impl Write,
+) -> io::Result<()> {
+    let document = FailureDocument {
+        status: "error",
+        error: JsonError {
+            message: error.to_string(),
+            code: error.code().map(|code| code.as_str()),
+            data_source_code: error.data_source_code().map(|code| code.as_str()),
+            hint: error.hint(),
+            exit_code: error.exit_status().code(),
+        },
+        parameters: JsonParameters::from(command),
+    };
+    write_document(&document, writer)
+}
+
+pub(super) fn write_document(document: &impl Serialize, mut writer: impl Write) -> io::Result<()> {
+    serde_json::to_writer(&mut writer, document)?;
+    writeln!(writer)
+}
+
+#[derive(Serialize)]
+struct SuccessDocument<'a> {
+    status: &'static str,
+    data_source: &'a str,
+    points: usize,
+    clusters: usize,
+    noise_fraction: f64,
+    timings: Option<JsonTimings>,
+    warnings: Vec<JsonWarning>,
+    quality: Option<JsonQuality>,
+    cluster_details: Option<&'a [ClusterDetail]>,
+    parameters: JsonParameters<'a>,
+    assignments: Vec<u64>,
+}
+
+#[derive(Serialize)]
+struct JsonQuality {
+    ari: f64,
+    nmi: f64,
+}
+
+#[derive(Serialize)]
+struct JsonWarning {
+    code: &'static str,
+    message: String,
+}
+
+impl From<&Warning> for JsonWarning {
+    fn from(warning: &Warning) -> Self {
+        Self {
+            code: warning.code().as_str(),
+            message: warning.to_string(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct FailureDocument<'a> {
+    status: &'static str,
+    error: JsonError,
+    parameters: JsonParameters<'a>,
+}
+
+#[derive(Serialize)]
+struct JsonError {
+    message: String,
+    code: Option<&'static str>,
+    data_source_code: Option<&'static str>,
+    hint: Option<String>,
+    exit_code: u8,
+}
+
+#[derive(Serialize)]
+pub(super) struct JsonParameters<'a> {
+    command: &'static str,
+    config: Option<String>,
+    min_cluster_size: usize,
+    max_bytes: Option<u64>,
+    hnsw: JsonHnsw,
+    source: Option<&'static str>,
+    path: Option<String>,
+    columns: Option<&'a [String]>,
+    metric: Option<&'static str>,
+    feature: Option<&'static str>,
+    pixel_side: Option<u32>,
+    name: Option<&'a str>,
+    lossy_f64: Option<bool>,
+    id_column: Option<&'a str>,
+    label_column: Option<&'a str>,
+    output: Option<String>,
+}
+
+#[derive(Serialize)]
+struct JsonHnsw {
+    max_connections: Option<usize>,
+    ef_construction: Option<usize>,
+}
+
+impl<'a> From<&'a RunCommand> for JsonParameters<'a> {
+    fn from(run: &'a RunCommand) -> Self {
+        let (path, columns, metric, name) = match &run.source {
+            Some(RunSource::Parquet(args)) => (
+                Some(&args.path),
+                Some(args.columns.as_slice()),
+                None,
+                args.name.as_deref(),
+            ),
+            Some(RunSource::Text(args)) => (
+                Some(&args.path),
+                None,
+                Some(args.metric.label()),
+                args.name.as_deref(),
+            ),
+            Some(RunSource::Images(args)) => (
+                Some(&args.path),
+                None,
+                Some(args.image_feature().metric()),
+                args.name.as_deref(),
+            ),
+            None => (None, None, None, None),
+        };
+        let (feature, pixel_side) = match &run.source {
+            Some(RunSource::Images(args)) => (
+                Some(args.feature.label()),
+                matches!(args.feature, ImageFeatureKind::Pixels).then_some(args.pixel_side),
+            ),
+            _ => (None, None),
+        };
+        let (lossy_f64, id_column, label_column, output) = match &run.source {
+            Some(RunSource::Parquet(args)) => (
+                Some(args.lossy_f64),
+                args.id_column.as_deref(),
+                args.label_column.as_deref(),
+                args.output.as_ref(),
+            ),
+            _ => (None, None, None, None),
+        };
+        // Report the HNSW parameters the pipeline used; when the supplied
+        // values are invalid, echo them back unchanged instead.
+        let params = run.hnsw.to_params().ok();
+        Self {
+            command: "run",
+            config: run
+                .config
+                .as_ref()
+                .map(|config| config.to_string_lossy().into_owned()),
+            min_cluster_size: run.effective_min_cluster_size(),
+            max_bytes: run.max_bytes,
+            hnsw: JsonHnsw {
+                max_connections: params
+                    .as_ref()
+                    .map_or(run.hnsw.max_connections, |p| Some(p.max_connections())),
+                ef_construction: params
+                    .as_ref()
+                    .map_or(run.hnsw.ef_construction, |p| Some(p.ef_construction())),
+            },
+            source: run.source.as_ref().map(RunSource::kind),
+            path: path.map(|path| path.to_string_lossy().into_owned()),
+            columns,
+            metric,
+            feature,
+            pixel_side,
+            name,
+            lossy_f64,
+            id_column,
+            label_column,
+            output: output.map(|output| output.to_string_lossy().into_owned()),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonTimings {
+    hnsw_build_ms: f64,
+    edge_harvest_ms: f64,
+    mst_ms: f64,
+    hierarchy_ms: f64,
+    total_ms: f64,
+}
+
+impl From<&StageTimings> for JsonTimings {
+    fn from(timings: &StageTimings) -> Self {
+        Self {
+            hnsw_build_ms: millis(timings.hnsw_build()),
+            edge_harvest_ms: millis(timings.edge_harvest()),
+            mst_ms: millis(timings.mst()),
+            hierarchy_ms: millis(timings.hierarchy()),
+            total_ms: millis(timings.total()),
+        }
+    }
+}
+
+pub(super) fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1_000.0
+}
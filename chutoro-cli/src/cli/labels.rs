@@ -0,0 +1,123 @@
+//! Ground-truth scoring for `chutoro run parquet --label-column`.
+//!
+//! The labels are read from a column of the Parquet input and compared with
+//! the run's assignments by the adjusted Rand index (ARI) and normalized
+//! mutual information (NMI). Every distinct value, null included, is a class
+//! of its own. Noise points form one predicted cluster, so a run that leaves
+//! a class as noise scores like one that merges it into another cluster.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::path::Path;
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    Int8Type, Int16Type, Int32Type, Int64Type, UInt8Type, UInt16Type, UInt32Type, UInt64Type,
+};
+use arrow_array::{Array, ArrowPrimitiveType};
+use arrow_schema::DataType;
+use chutoro_core::{ClusteringQualityScore, ClusteringResult, clustering_quality_score};
+use tracing::instrument;
+
+use super::commands::{CliError, path_label};
+use super::parquet_output::read_column;
+
+/// Scores `result` against the labels in `column` of the `input` Parquet file.
+///
+/// # Errors
+/// Returns [`CliError::LabelColumnNotFound`] when `column` is missing from the
+/// input, [`CliError::UnsupportedLabelColumn`] when it holds neither strings,
+/// integers, nor booleans, [`CliError::Quality`] when it has a different
+/// number of rows than were clustered, and [`CliError::Io`] or
+/// [`CliError::Parquet`] when the input cannot be read.
+#[instrument(
+    name = "cli.score_labels",
+    err,
+    skip(input, result),
+    fields(input = %path_label(input))
+)]
+pub(super) fn score_label_column(
+    input: &Path,
+    column: &str,
+    result: &ClusteringResult,
+) -> Result<ClusteringQualityScore, CliError> {
+    let (field, chunks) = read_column(input, column, |available| CliError::LabelColumnNotFound {
+        column: column.to_owned(),
+        available,
+    })?;
+    let mut labels = Vec::with_capacity(result.assignments().len());
+    for chunk in &chunks {
+        let keys = label_keys(chunk.as_ref()).ok_or_else(|| CliError::UnsupportedLabelColumn {
+            column: column.to_owned(),
+            data_type: field.data_type().clone(),
+        })?;
+        labels.extend(keys);
+    }
+    let ground_truth = dense_labels(labels);
+    let predicted = dense_labels(result.assignments().iter().map(|id| id.get()));
+    clustering_quality_score(&ground_truth, &predicted).map_err(CliError::Quality)
+}
+
+/// Numbers the distinct values of `keys` in order of first appearance.
+fn dense_labels<K: Eq + Hash>(keys: impl IntoIterator<Item = K>) -> Vec<usize> {
+    let mut classes = HashMap::new();
+    keys.into_iter()
+        .map(|key| {
+            let next = classes.len();
+            *classes.entry(key).or_insert(next)
+        })
+        .collect()
+}
+
+/// Returns a comparable key for every row of `array`, or `None` when its
+/// type cannot hold class labels.
+fn label_keys(array: &dyn Array) -> Option<Vec<Option<String>>> {
+    let owned = |value: Option<&str>| value.map(ToOwned::to_owned);
+    Some(match array.data_type() {
+        DataType::Utf8 => array.as_string::<i32>().iter().map(owned).collect(),
+        DataType::LargeUtf8 => array.as_string::<i64>().iter().map(owned).collect(),
+        DataType::Utf8View => array.as_string_view().iter().map(owned).collect(),
+        DataType::Boolean => displayed(array.as_boolean().iter()),
+        DataType::Int8 => primitive_keys::<Int8Type>(array),
+        DataType::Int16 => primitive_keys::<Int16Type>(array),
+        DataType::Int32 => primitive_keys::<Int32Type>(array),
+        DataType::Int64 => primitive_keys::<Int64Type>(array),
+        DataType::UInt8 => primitive_keys::<UInt8Type>(array),
+        DataType::UInt16 => primitive_keys::<UInt16Type>(array),
+        DataType::UInt32 => primitive_keys::<UInt32Type>(array),
+        DataType::UInt64 => primitive_keys::<UInt64Type>(array),
+        DataType::Dictionary(_, _) => dictionary_keys(array)?,
+        _ => return None,
+    })
+}
+
+fn primitive_keys<T: ArrowPrimitiveType>(array: &dyn Array) -> Vec<Option<String>>
+where
+    T::Native: Display,
+{
+    displayed(array.as_primitive::<T>().iter())
+}
+
+fn displayed<V: Display>(values: impl Iterator<Item = Option<V>>) -> Vec<Option<String>> {
+    values.map(|value| value.map(|v| v.to_string())).collect()
+}
+
+/// Resolves each row of a dictionary-encoded column to its value's key.
+fn dictionary_keys(array: &dyn Array) -> Option<Vec<Option<String>>> {
+    let dictionary = array.as_any_dictionary();
+    let values = label_keys(dictionary.values().as_ref())?;
+    let keys = dictionary
+        .normalized_keys()
+        .into_iter()
+        .enumerate()
+        .map(|(row, key)| {
+            if array.is_null(row) {
+                None
+            } else {
+                values.get(key).cloned().flatten()
+            }
+        })
+        .collect();
+    Some(keys)
+}
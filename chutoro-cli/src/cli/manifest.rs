@@ -0,0 +1,392 @@
+//! Persistable run manifests for `chutoro run --manifest`.
+//!
+//! A manifest records everything needed to repeat a run: the chutoro version,
+//! the resolved parameters (including the HNSW seed), the data source, a hash
+//! of the dataset, the stage timings, and a summary of the result. Manifests
+//! are versioned JSON documents; [`RunManifest::replay`] re-executes one after
+//! checking that the dataset has not changed.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use chutoro_core::StageTimings;
+use serde::{Deserialize, Serialize};
+
+use super::args::{
+    HnswArgs, ImageArgs, ImageFeatureKind, ParquetArgs, RunCommand, RunSource, TextArgs, TextMetric,
+};
+use super::commands::{CliError, ExecutionSummary, run_command};
+use super::dataset::dataset_hash;
+use super::json::millis;
+use super::render::OutputArgs;
+
+/// Manifest format version written by this build.
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// A reproducible record of one `chutoro run`.
+///
+/// # Examples
+/// ```
+/// # use std::error::Error;
+/// # use chutoro_cli::cli::{RunCommand, RunManifest, RunSource, TextArgs, TextMetric, run_command};
+/// # use tempfile::tempdir;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let dir = tempdir()?;
+/// let data = dir.path().join("words.txt");
+/// std::fs::write(&data, "alpha\nalpine\nbeta\nbetamax\n")?;
+/// let manifest_path = dir.path().join("run.json");
+/// let first = run_command(RunCommand {
+///     min_cluster_size: Some(2),
+///     manifest: Some(manifest_path.clone()),
+///     source: Some(RunSource::Text(TextArgs {
+///         path: data,
+///         metric: TextMetric::Levenshtein,
+///         name: None,
+///     })),
+///     ..RunCommand::default()
+/// })?;
+///
+/// let manifest = RunManifest::load(&manifest_path)?;
+/// assert_eq!(manifest.dataset.name, "words");
+/// let replayed = manifest.replay()?;
+/// assert_eq!(replayed.result.assignments(), first.result.assignments());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RunManifest {
+    /// Manifest format version; see [`MANIFEST_VERSION`].
+    pub version: u32,
+    /// Version of chutoro that produced the run.
+    pub chutoro_version: String,
+    /// Resolved clustering parameters.
+    pub parameters: ManifestParameters,
+    /// Data source the run read.
+    pub source: ManifestSource,
+    /// Name and content hash of the dataset.
+    pub dataset: ManifestDataset,
+    /// Stage timings, when the run recorded them.
+    pub timings: Option<ManifestTimings>,
+    /// Summary of the clustering result.
+    pub result: ManifestResult,
+}
+
+/// Clustering parameters recorded in a [`RunManifest`], with defaults applied.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestParameters {
+    /// Minimum number of items per cluster.
+    pub min_cluster_size: usize,
+    /// Memory limit in bytes, if one was set.
+    pub max_bytes: Option<u64>,
+    /// HNSW index construction parameters.
+    pub hnsw: ManifestHnsw,
+}
+
+/// HNSW parameters recorded in a [`RunManifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestHnsw {
+    /// Maximum neighbours per node on upper layers.
+    pub max_connections: usize,
+    /// Candidate list width while building the index.
+    pub ef_construction: usize,
+    /// Seed for level sampling.
+    pub seed: u64,
+}
+
+/// Data source recorded in a [`RunManifest`].
+///
+/// Paths are stored as given on the command line or resolved from the
+/// configuration file, so relative paths replay against the working
+/// directory. Per-row `--output` files are not recorded and are not
+/// rewritten by a replay.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase", deny_unknown_fields)]
+pub enum ManifestSource {
+    /// Parquet feature columns.
+    Parquet {
+        /// Path of the Parquet file.
+        path: PathBuf,
+        /// Feature columns, in concatenation order.
+        columns: Vec<String>,
+        /// Whether `Float64` columns were narrowed to `f32`.
+        lossy_f64: bool,
+    },
+    /// A UTF-8 text corpus.
+    Text {
+        /// Path of the text file, or `-` for standard input.
+        path: PathBuf,
+        /// Distance metric used to compare lines.
+        metric: TextMetric,
+    },
+    /// A directory of images.
+    Images {
+        /// Path of the image directory.
+        path: PathBuf,
+        /// Feature extracted from each image.
+        feature: ImageFeatureKind,
+        /// Thumbnail side used by pixel features.
+        pixel_side: u32,
+    },
+}
+
+impl ManifestSource {
+    /// Returns the path of the dataset.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Parquet { path, .. } | Self::Text { path, .. } | Self::Images { path, .. } => {
+                path
+            }
+        }
+    }
+}
+
+/// Dataset identity recorded in a [`RunManifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestDataset {
+    /// Data source name reported in summaries.
+    pub name: String,
+    /// `fnv1a64:`-prefixed hash of the file bytes or, for image sources, of
+    /// every image's relative path and bytes; `None` for standard input.
+    pub hash: Option<String>,
+}
+
+/// Stage timings recorded in a [`RunManifest`], in fractional milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestTimings {
+    /// Time spent building the HNSW index.
+    pub hnsw_build_ms: f64,
+    /// Time spent deriving mutual-reachability edges.
+    pub edge_harvest_ms: f64,
+    /// Time spent constructing the minimum spanning forest.
+    pub mst_ms: f64,
+    /// Time spent extracting the hierarchy and labels.
+    pub hierarchy_ms: f64,
+    /// Wall-clock duration of the whole pipeline.
+    pub total_ms: f64,
+}
+
+impl From<&StageTimings> for ManifestTimings {
+    fn from(timings: &StageTimings) -> Self {
+        Self {
+            hnsw_build_ms: millis(timings.hnsw_build()),
+            edge_harvest_ms: millis(timings.edge_harvest()),
+            mst_ms: millis(timings.mst()),
+            hierarchy_ms: millis(timings.hierarchy()),
+            total_ms: millis(timings.total()),
+        }
+    }
+}
+
+/// Result summary recorded in a [`RunManifest`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestResult {
+    /// Number of clustered points.
+    pub points: usize,
+    /// Number of clusters found.
+    pub clusters: usize,
+    /// Fraction of points labelled as noise.
+    pub noise_fraction: f64,
+}
+
+/// Reads only the version so newer manifests fail with a clear error rather
+/// than an unknown-field complaint.
+#[derive(Deserialize)]
+struct VersionProbe {
+    version: u32,
+}
+
+impl RunManifest {
+    /// Describes the run of the resolved `command` that produced `summary`.
+    ///
+    /// # Errors
+    /// Returns [`CliError::MissingSource`] when `command` has no source,
+    /// [`CliError::Hnsw`] when its HNSW parameters are invalid, and
+    /// [`CliError::Io`] or [`CliError::Image`] when the dataset cannot be
+    /// read for hashing.
+    pub fn capture(command: &RunCommand, summary: &ExecutionSummary) -> Result<Self, CliError> {
+        let hnsw = command.hnsw.to_params().map_err(CliError::Hnsw)?;
+        let source = match command.source.as_ref().ok_or(CliError::MissingSource)? {
+            RunSource::Parquet(args) => ManifestSource::Parquet {
+                path: args.path.clone(),
+                columns: args.columns.clone(),
+                lossy_f64: args.lossy_f64,
+            },
+            RunSource::Text(args) => ManifestSource::Text {
+                path: args.path.clone(),
+                metric: args.metric,
+            },
+            RunSource::Images(args) => ManifestSource::Images {
+                path: args.path.clone(),
+                feature: args.feature,
+                pixel_side: args.pixel_side,
+            },
+        };
+        let hash = dataset_hash(source.path())?;
+        let result = &summary.result;
+        Ok(Self {
+            version: MANIFEST_VERSION,
+            chutoro_version: env!("CARGO_PKG_VERSION").to_owned(),
+            parameters: ManifestParameters {
+                min_cluster_size: command.effective_min_cluster_size(),
+                max_bytes: command.max_bytes,
+                hnsw: ManifestHnsw {
+                    max_connections: hnsw.max_connections(),
+                    ef_construction: hnsw.ef_construction(),
+                    seed: hnsw.rng_seed(),
+                },
+            },
+            source,
+            dataset: ManifestDataset {
+                name: summary.data_source.clone(),
+                hash,
+            },
+            timings: result.timings().map(ManifestTimings::from),
+            result: ManifestResult {
+                points: result.assignments().len(),
+                clusters: result.cluster_count(),
+                noise_fraction: result.noise_fraction(),
+            },
+        })
+    }
+
+    /// Loads a manifest written by [`Self::write`].
+    ///
+    /// # Errors
+    /// Returns [`CliError::Io`] when the file cannot be read,
+    /// [`CliError::ManifestParse`] when it is not a valid manifest, and
+    /// [`CliError::UnsupportedManifest`] when its version differs from
+    /// [`MANIFEST_VERSION`].
+    pub fn load(path: &Path) -> Result<Self, CliError> {
+        let text = std::fs::read_to_string(path).map_err(|source| CliError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let parse_error = |source| CliError::ManifestParse {
+            path: path.to_path_buf(),
+            source,
+        };
+        let probe: VersionProbe = serde_json::from_str(&text).map_err(parse_error)?;
+        if probe.version != MANIFEST_VERSION {
+            return Err(CliError::UnsupportedManifest {
+                path: path.to_path_buf(),
+                version: probe.version,
+            });
+        }
+        serde_json::from_str(&text).map_err(parse_error)
+    }
+
+    /// Writes the manifest to `path` as pretty-printed JSON, replacing any
+    /// existing file.
+    ///
+    /// # Errors
+    /// Returns [`CliError::Io`] when the file cannot be written.
+    pub fn write(&self, path: &Path) -> Result<(), CliError> {
+        let io_error = |source: io::Error| CliError::Io {
+            path: path.to_path_buf(),
+            source,
+        };
+        let mut writer = BufWriter::new(File::create(path).map_err(io_error)?);
+        serde_json::to_writer_pretty(&mut writer, self).map_err(|err| io_error(err.into()))?;
+        writeln!(writer).map_err(io_error)?;
+        writer.flush().map_err(io_error)
+    }
+
+    /// Rebuilds the resolved `run` command the manifest describes.
+    ///
+    /// The recorded dataset name is passed as the source name so summaries
+    /// match the original run. The command writes no summary file, manifest,
+    /// or per-row output.
+    #[must_use]
+    pub fn to_run_command(&self) -> RunCommand {
+        let name = Some(self.dataset.name.clone());
+        let source = match &self.source {
+            ManifestSource::Parquet {
+                path,
+                columns,
+                lossy_f64,
+            } => RunSource::Parquet(ParquetArgs {
+                path: path.clone(),
+                columns: columns.clone(),
+                name,
+                lossy_f64: *lossy_f64,
+                id_column: None,
+                label_column: None,
+                output: None,
+            }),
+            ManifestSource::Text { path, metric } => RunSource::Text(TextArgs {
+                path: path.clone(),
+                metric: *metric,
+                name,
+            }),
+            ManifestSource::Images {
+                path,
+                feature,
+                pixel_side,
+            } => RunSource::Images(ImageArgs {
+                path: path.clone(),
+                feature: *feature,
+                pixel_side: *pixel_side,
+                name,
+            }),
+        };
+        let hnsw = &self.parameters.hnsw;
+        RunCommand {
+            config: None,
+            min_cluster_size: Some(self.parameters.min_cluster_size),
+            max_bytes: self.parameters.max_bytes,
+            hnsw: HnswArgs {
+                max_connections: Some(hnsw.max_connections),
+                ef_construction: Some(hnsw.ef_construction),
+                seed: Some(hnsw.seed),
+            },
+            output: OutputArgs::default(),
+            detail: None,
+            manifest: None,
+            dry_run: false,
+            source: Some(source),
+        }
+    }
+
+    /// Checks that the dataset still has the recorded hash.
+    ///
+    /// Manifests of standard-input runs carry no hash and always pass.
+    ///
+    /// # Errors
+    /// Returns [`CliError::DatasetMismatch`] when the hashes differ, and
+    /// [`CliError::Io`] or [`CliError::Image`] when the dataset cannot be
+    /// read.
+    pub fn verify_dataset(&self) -> Result<(), CliError> {
+        let Some(expected) = &self.dataset.hash else {
+            return Ok(());
+        };
+        let path = self.source.path();
+        let actual = dataset_hash(path)?.unwrap_or_default();
+        if actual == *expected {
+            Ok(())
+        } else {
+            Err(CliError::DatasetMismatch {
+                path: path.to_path_buf(),
+                expected: expected.clone(),
+                actual,
+            })
+        }
+    }
+
+    /// Re-executes the run after [`Self::verify_dataset`] succeeds.
+    ///
+    /// # Errors
+    /// Returns the errors of [`Self::verify_dataset`] and [`run_command`].
+    pub fn replay(&self) -> Result<ExecutionSummary, CliError> {
+        self.verify_dataset()?;
+        run_command(self.to_run_command())
+    }
+}
@@ -1,14 +1,58 @@
 //! Command-line interface orchestration for the chutoro CPU pipeline.
 //!
-//! The CLI currently offers a minimal `run` command that loads either a Parquet
-//! dense matrix or a line-based UTF-8 text corpus and executes the CPU
-//! clustering pipeline.
+//! The `run` command loads a Parquet dense matrix, a line-based UTF-8 text
+//! corpus (from a file, a compressed archive, or standard input), or a
+//! directory of images and executes the CPU clustering pipeline, optionally taking its parameters from
+//! a TOML file, and can record a replayable manifest of the run or, with
+//! `--dry-run`, validate the input without clustering it. The `config`
+//! command emits a template for that file, `inspect` summarizes a Parquet
+//! input and its estimated cost before a run is launched, `stability`
+//! clusters an input under several seeds to measure how much the runs agree,
+//! and `batch` runs every job listed in a jobs file.
 
+mod args;
+mod batch;
 mod commands;
+mod config;
+mod dataset;
+mod details;
+mod dry_run;
+mod failure;
+mod images;
+mod input;
+mod inspect;
+mod json;
+mod labels;
+mod manifest;
+mod parquet_output;
+mod render;
+mod sources;
+mod stability;
 
-pub use commands::{
-    Cli, CliError, Command, ExecutionSummary, ParquetArgs, RunCommand, RunSource, TextArgs,
-    TextMetric, render_summary, run_cli,
+pub use args::{
+    Cli, Command, ConfigAction, ConfigCommand, ConfigInitArgs, HnswArgs, ImageArgs,
+    ImageFeatureKind, InspectCommand, ParquetArgs, RunCommand, RunSource, TextArgs, TextMetric,
+};
+pub use batch::{
+    BatchCommand, BatchJob, BatchPlan, BatchSummary, JobReport, JobResult, batch_command,
+    render_batch, render_batch_json,
+};
+pub use commands::{CliError, ExecutionSummary, run_cli, run_command};
+pub use config::{CONFIG_TEMPLATE, run_config};
+pub use details::{ClusterDetail, MEAN_DISTANCE_MEMBER_LIMIT, SummaryDetail, cluster_details};
+pub use dry_run::{DryRunSummary, dry_run_command, render_dry_run, render_dry_run_json};
+pub use failure::ExitStatus;
+pub use inspect::{
+    ColumnReport, EstimateReport, InspectReport, inspect_parquet, render_inspect, run_inspect,
+};
+pub use json::{render_failure_json, render_summary_json};
+pub use manifest::{
+    MANIFEST_VERSION, ManifestDataset, ManifestHnsw, ManifestParameters, ManifestResult,
+    ManifestSource, ManifestTimings, RunManifest,
+};
+pub use render::{OutputArgs, SummaryFormat, render_summary};
+pub use stability::{
+    StabilityCommand, StabilitySummary, render_stability, render_stability_json, stability_command,
 };
 
 #[cfg(test)]
@@ -0,0 +1,166 @@
+//! Parquet output of cluster assignments for `chutoro run parquet --output`.
+//!
+//! The output carries an identifier for every input row alongside its
+//! `cluster_id`, membership `probability`, and GLOSH `outlier_score`, so
+//! downstream jobs can join on the identifier instead of relying on row order.
+//! The identifier is copied from `--id-column` when given and is otherwise the
+//! zero-based `row` index.
+
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, Float32Array, RecordBatch, RecordBatchReader, UInt64Array};
+use arrow_schema::{DataType, Field, Schema};
+use chutoro_core::ClusteringResult;
+use parquet::arrow::{ArrowWriter, ProjectionMask, arrow_reader::ParquetRecordBatchReaderBuilder};
+use parquet::errors::ParquetError;
+use tracing::instrument;
+
+use super::commands::{CliError, path_label};
+
+/// Name of the identifier column written when no `--id-column` is given.
+const ROW_COLUMN: &str = "row";
+
+/// Writes the assignments in `result` to `output` as Parquet.
+///
+/// `id_column` names a column of the `input` Parquet file to copy into the
+/// output unchanged. Noise points have a null `cluster_id`.
+///
+/// # Errors
+/// Returns [`CliError::IdColumnNotFound`] when `id_column` is missing from the
+/// input, [`CliError::IdRowCount`] when it has a different number of rows than
+/// were clustered, [`CliError::Io`] when `output` cannot be created, and
+/// [`CliError::Parquet`] when reading or writing Parquet data fails.
+#[instrument(
+    name = "cli.write_parquet_output",
+    err,
+    skip(input, result, output),
+    fields(output = %path_label(output), id_column = id_column.unwrap_or(ROW_COLUMN))
+)]
+pub(super) fn write_cluster_parquet(
+    input: &Path,
+    id_column: Option<&str>,
+    result: &ClusteringResult,
+    output: &Path,
+) -> Result<(), CliError> {
+    let rows = result.assignments().len();
+    let (id_field, id_chunks) = match id_column {
+        Some(column) => read_column(input, column, |available| CliError::IdColumnNotFound {
+            column: column.to_owned(),
+            available,
+        })?,
+        None => row_index_column(rows),
+    };
+    let id_rows: usize = id_chunks.iter().map(|chunk| chunk.len()).sum();
+    if id_rows != rows {
+        return Err(CliError::IdRowCount {
+            expected: rows,
+            actual: id_rows,
+        });
+    }
+
+    let schema = Arc::new(Schema::new(vec![
+        id_field,
+        Field::new("cluster_id", DataType::UInt64, true),
+        Field::new("probability", DataType::Float32, true),
+        Field::new("outlier_score", DataType::Float32, true),
+    ]));
+    let file = File::create(output).map_err(|source| CliError::Io {
+        path: output.to_path_buf(),
+        source,
+    })?;
+    let parquet_error = |source| CliError::Parquet {
+        path: output.to_path_buf(),
+        source,
+    };
+    let mut writer =
+        ArrowWriter::try_new(file, Arc::clone(&schema), None).map_err(parquet_error)?;
+    let mut offset = 0;
+    for ids in id_chunks {
+        let len = ids.len();
+        let batch = RecordBatch::try_new(Arc::clone(&schema), result_columns(ids, result, offset))
+            .map_err(|err| parquet_error(ParquetError::from(err)))?;
+        writer.write(&batch).map_err(parquet_error)?;
+        offset += len;
+    }
+    writer.close().map_err(parquet_error)?;
+    Ok(())
+}
+
+/// Reads `column` from `input`, keeping the input's record batch boundaries.
+///
+/// `missing` builds the error reported when the column does not exist, given
+/// the columns the input does contain.
+pub(super) fn read_column(
+    input: &Path,
+    column: &str,
+    missing: impl FnOnce(Vec<String>) -> CliError,
+) -> Result<(Field, Vec<ArrayRef>), CliError> {
+    let parquet_error = |source| CliError::Parquet {
+        path: input.to_path_buf(),
+        source,
+    };
+    let file = File::open(input).map_err(|source| CliError::Io {
+        path: input.to_path_buf(),
+        source,
+    })?;
+    let builder = ParquetRecordBatchReaderBuilder::try_new(file).map_err(parquet_error)?;
+    let schema = builder.schema();
+    if schema.index_of(column).is_err() {
+        return Err(missing(
+            schema
+                .fields()
+                .iter()
+                .map(|field| field.name().clone())
+                .collect(),
+        ));
+    }
+    let mask = ProjectionMask::columns(builder.parquet_schema(), [column]);
+    let reader = builder
+        .with_projection(mask)
+        .build()
+        .map_err(parquet_error)?;
+    let schema = reader.schema();
+    let index = schema
+        .index_of(column)
+        .map_err(|err| parquet_error(ParquetError::from(err)))?;
+    let field = schema.field(index).clone();
+    let chunks = reader
+        .map(|batch| batch.map(|batch| Arc::clone(batch.column(index))))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| parquet_error(ParquetError::from(err)))?;
+    Ok((field, chunks))
+}
+
+fn row_index_column(rows: usize) -> (Field, Vec<ArrayRef>) {
+    let ids: UInt64Array = (0..rows as u64).collect();
+    (
+        Field::new(ROW_COLUMN, DataType::UInt64, false),
+        vec![Arc::new(ids)],
+    )
+}
+
+/// Builds the output columns for the rows `offset..offset + ids.len()`.
+fn result_columns(ids: ArrayRef, result: &ClusteringResult, offset: usize) -> Vec<ArrayRef> {
+    let range = offset..offset + ids.len();
+    let noise = result.noise_label();
+    let cluster_ids: UInt64Array = result.assignments()[range.clone()]
+        .iter()
+        .map(|&id| (Some(id) != noise).then_some(id.get()))
+        .collect();
+    let scores = result.membership();
+    let score_column = |values: Option<&[f32]>| -> ArrayRef {
+        let array: Float32Array = match values {
+            Some(values) => values[range.clone()].iter().copied().map(Some).collect(),
+            None => std::iter::repeat_n(None, range.len()).collect(),
+        };
+        Arc::new(array)
+    };
+    vec![
+        ids,
+        Arc::new(cluster_ids),
+        score_column(scores.map(|scores| scores.probabilities())),
+        score_column(scores.map(|scores| scores.outlier_scores())),
+    ]
+}
@@ -0,0 +1,143 @@
+//! Summary output options and the human-readable summary renderer.
+//!
+//! The text renderer prints one assignment per line for quick inspection.
+//! Machine-readable output lives in the sibling `json` module.
+
+use std::io::{self, Write};
+
+use clap::{Args, ValueEnum};
+use serde::Deserialize;
+
+use super::commands::ExecutionSummary;
+use super::details::ClusterDetail;
+
+/// Formats available for the run summary written to standard output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SummaryFormat {
+    /// Human-readable text with one assignment per line.
+    #[default]
+    Text,
+    /// A single JSON document describing the run or its failure.
+    Json,
+}
+
+/// Options controlling how the run summary is rendered.
+#[derive(Debug, Args, Clone, Default)]
+pub struct OutputArgs {
+    /// Format of the summary written to standard output [default: text].
+    #[arg(long, value_enum)]
+    pub format: Option<SummaryFormat>,
+
+    /// Shorthand for `--format json`.
+    #[arg(long, conflicts_with = "format")]
+    pub json: bool,
+}
+
+impl OutputArgs {
+    /// Returns the effective summary format, honouring the `--json` shorthand.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_cli::cli::{OutputArgs, SummaryFormat};
+    ///
+    /// let output = OutputArgs { json: true, ..OutputArgs::default() };
+    /// assert_eq!(output.summary_format(), SummaryFormat::Json);
+    /// ```
+    #[must_use]
+    pub fn summary_format(&self) -> SummaryFormat {
+        if self.json {
+            SummaryFormat::Json
+        } else {
+            self.format.unwrap_or_default()
+        }
+    }
+}
+
+/// Renders `summary` to `writer` in a human-readable text format.
+///
+/// When the run recorded stage timings, a `timings:` line follows the cluster
+/// count, and each warning the run raised follows on a `warning:` line with
+/// its stable code. Runs scored against `--label-column` report the ARI and
+/// NMI on a `quality:` line. With `--detail clusters`, a tab-separated table
+/// with one row per cluster precedes the assignments; the noise label is
+/// marked `(noise)` and columns that do not apply hold `-`.
+///
+/// # Errors
+/// Returns [`io::Error`] if writing to the supplied writer fails.
+///
+/// # Examples
+/// ```
+/// # use std::error::Error;
+/// # use std::io::Cursor;
+/// # use chutoro_cli::cli::{ExecutionSummary, render_summary};
+/// # use chutoro_core::{ClusteringResult, ClusterId};
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let summary = ExecutionSummary {
+///     data_source: "demo".into(),
+///     result: ClusteringResult::from_assignments(vec![
+///         ClusterId::new(0),
+///         ClusterId::new(1),
+///     ]),
+///     quality: None,
+///     clusters: None,
+/// };
+/// let mut buffer = Cursor::new(Vec::new());
+/// render_summary(&summary, &mut buffer)?;
+/// assert_eq!(buffer.into_inner().len(), 38);
+/// # Ok(())
+/// # }
+/// ```
+pub fn render_summary(summary: &ExecutionSummary, mut writer: impl Write) -> io::Result<()> {
+    writeln!(writer, "data source: {}", summary.data_source)?;
+    writeln!(writer, "clusters: {}", summary.result.cluster_count())?;
+    if let Some(timings) = summary.result.timings() {
+        writeln!(
+            writer,
+            "timings: hnsw_build={:.3?} edge_harvest={:.3?} mst={:.3?} hierarchy={:.3?} total={:.3?}",
+            timings.hnsw_build(),
+            timings.edge_harvest(),
+            timings.mst(),
+            timings.hierarchy(),
+            timings.total(),
+        )?;
+    }
+    if let Some(score) = summary.quality {
+        writeln!(writer, "quality: ari={:.4} nmi={:.4}", score.ari, score.nmi)?;
+    }
+    for warning in summary.result.warnings() {
+        writeln!(writer, "warning: {} {warning}", warning.code())?;
+    }
+    if let Some(clusters) = &summary.clusters {
+        render_cluster_table(clusters, &mut writer)?;
+    }
+    for (index, cluster) in summary.result.assignments().iter().enumerate() {
+        writeln!(writer, "{index}\t{}", cluster.get())?;
+    }
+    Ok(())
+}
+
+/// Writes the `--detail clusters` table: a header, then the label, size,
+/// percentage of points, exemplar row, and mean intra-cluster distance of
+/// each cluster.
+fn render_cluster_table(clusters: &[ClusterDetail], mut writer: impl Write) -> io::Result<()> {
+    writeln!(writer, "cluster\tsize\tpercent\texemplar\tmean_distance")?;
+    for detail in clusters {
+        let noise = if detail.noise { " (noise)" } else { "" };
+        let exemplar = detail
+            .exemplar
+            .map_or_else(|| "-".to_owned(), |row| row.to_string());
+        let mean = detail
+            .mean_distance
+            .map_or_else(|| "-".to_owned(), |mean| format!("{mean:.4}"));
+        writeln!(
+            writer,
+            "{}{noise}\t{}\t{:.1}%\t{exemplar}\t{mean}",
+            detail.cluster,
+            detail.size,
+            detail.fraction * 100.0,
+        )?;
+    }
+    Ok(())
+}
@@ -0,0 +1,86 @@
+//! Parquet and text execution for `chutoro run parquet` and `chutoro run text`.
+
+use chutoro_core::Chutoro;
+use chutoro_providers_dense::{DenseIngestOptions, DenseMatrixProvider};
+use chutoro_providers_text::TextProvider;
+use tracing::instrument;
+
+use super::args::{ParquetArgs, TextArgs, TextMetric};
+use super::commands::{CliError, ExecutionSummary, execute_with_provider, path_label};
+use super::details::SummaryDetail;
+use super::input::{derive_data_source_name, open_text_reader};
+use super::labels::score_label_column;
+use super::parquet_output::write_cluster_parquet;
+
+#[instrument(
+    name = "cli.run_parquet",
+    err,
+    skip(chutoro, args),
+    fields(
+        path = %path_label(&args.path),
+        columns = %args.columns.join(","),
+        override_name = %args.name.as_deref().unwrap_or("<derived>"),
+        output = %args.output.as_deref().map_or_else(|| "<none>".to_owned(), path_label),
+        label_column = args.label_column.as_deref().unwrap_or("<none>")
+    ),
+)]
+pub(super) fn run_parquet(
+    chutoro: &Chutoro,
+    args: ParquetArgs,
+    detail: Option<SummaryDetail>,
+) -> Result<ExecutionSummary, CliError> {
+    let provider = parquet_provider(&args)?;
+    let mut summary = execute_with_provider(chutoro, provider, detail)?;
+    if let Some(column) = &args.label_column {
+        summary.quality = Some(score_label_column(&args.path, column, &summary.result)?);
+    }
+    if let Some(output) = &args.output {
+        write_cluster_parquet(
+            &args.path,
+            args.id_column.as_deref(),
+            &summary.result,
+            output,
+        )?;
+    }
+    Ok(summary)
+}
+
+/// Loads the dense matrix named by `args`.
+pub(super) fn parquet_provider(args: &ParquetArgs) -> Result<DenseMatrixProvider, CliError> {
+    let chosen_name = derive_data_source_name(&args.path, args.name.as_deref());
+    let columns: Vec<&str> = args.columns.iter().map(String::as_str).collect();
+    let options = DenseIngestOptions::default().with_lossy_f64(args.lossy_f64);
+    Ok(DenseMatrixProvider::try_from_parquet_columns_with_options(
+        chosen_name,
+        &args.path,
+        &columns,
+        options,
+    )?)
+}
+
+#[instrument(
+    name = "cli.run_text",
+    err,
+    skip(chutoro, args),
+    fields(
+        path = %path_label(&args.path),
+        metric = args.metric.label(),
+        override_name = %args.name.as_deref().unwrap_or("<derived>")
+    ),
+)]
+pub(super) fn run_text(
+    chutoro: &Chutoro,
+    args: TextArgs,
+    detail: Option<SummaryDetail>,
+) -> Result<ExecutionSummary, CliError> {
+    execute_with_provider(chutoro, text_provider(&args)?, detail)
+}
+
+/// Loads the text corpus named by `args`.
+pub(super) fn text_provider(args: &TextArgs) -> Result<TextProvider, CliError> {
+    let chosen_name = derive_data_source_name(&args.path, args.name.as_deref());
+    let reader = open_text_reader(&args.path)?;
+    Ok(match args.metric {
+        TextMetric::Levenshtein => TextProvider::try_from_reader(chosen_name, reader)?,
+    })
+}
@@ -0,0 +1,321 @@
+//! The `stability` command: one input clustered under several seeds.
+//!
+//! The source is loaded once and clustered with consecutive master seeds, so
+//! the runs differ only in their HNSW level sampling. The first run is the
+//! reference of the core [`StabilityReport`]: every pair of runs is scored
+//! with ARI and NMI, and each of the reference run's clusters is reported
+//! with how often the other runs recover it.
+
+use std::io::{self, Write};
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+
+use chutoro_core::{ChutoroBuilder, ClusteringResult, DataSource, StabilityReport};
+use clap::Args;
+use serde::Serialize;
+use tracing::{info, instrument};
+
+use super::args::{HnswArgs, RunCommand, RunSource};
+use super::commands::{CliError, configure_builder};
+use super::config::parse_byte_size;
+use super::images::image_provider;
+use super::json::{JsonParameters, write_document};
+use super::render::OutputArgs;
+use super::sources::{parquet_provider, text_provider};
+
+/// Number of runs compared when `--runs` is not given.
+const DEFAULT_RUNS: NonZeroUsize = match NonZeroUsize::new(5) {
+    Some(runs) => runs,
+    None => panic!("the default compares at least one run"),
+};
+
+/// Options accepted by the `stability` command.
+///
+/// The pipeline options match `run`; `--hnsw-seed` is replaced by each run's
+/// master seed.
+#[derive(Debug, Args, Clone)]
+pub struct StabilityCommand {
+    /// Number of runs to compare, each under its own master seed.
+    #[arg(long, default_value_t = DEFAULT_RUNS)]
+    pub runs: NonZeroUsize,
+
+    /// Master seed of the first run; each later run adds one.
+    #[arg(long = "seed", default_value_t = 0)]
+    pub first_seed: u64,
+
+    /// TOML file supplying run parameters; flags override its values.
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+
+    /// Minimum number of items per cluster [default: 5].
+    #[arg(long = "min-cluster-size", value_parser = clap::value_parser!(usize))]
+    pub min_cluster_size: Option<usize>,
+
+    /// Maximum estimated memory (in bytes) allowed for each run.
+    #[arg(long = "max-bytes", value_parser = parse_byte_size)]
+    pub max_bytes: Option<u64>,
+
+    /// HNSW index construction options.
+    #[command(flatten)]
+    pub hnsw: HnswArgs,
+
+    /// Report output options.
+    #[command(flatten)]
+    pub output: OutputArgs,
+
+    /// Data source configuration; required unless supplied by `--config`.
+    #[command(subcommand)]
+    pub source: Option<RunSource>,
+}
+
+impl StabilityCommand {
+    /// Returns the `run` command each run executes, before `--config` is
+    /// applied.
+    #[must_use]
+    pub fn to_run(&self) -> RunCommand {
+        RunCommand {
+            config: self.config.clone(),
+            min_cluster_size: self.min_cluster_size,
+            max_bytes: self.max_bytes,
+            hnsw: self.hnsw.clone(),
+            output: self.output.clone(),
+            source: self.source.clone(),
+            ..RunCommand::default()
+        }
+    }
+
+    /// Returns the master seed of each run, in order.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_cli::cli::{Cli, Command};
+    /// use clap::Parser;
+    ///
+    /// let cli = Cli::parse_from(["chutoro", "stability", "--runs", "3", "--seed", "7"]);
+    /// let Command::Stability(command) = cli.command else { unreachable!() };
+    /// assert_eq!(command.seeds(), [7, 8, 9]);
+    /// ```
+    #[must_use]
+    pub fn seeds(&self) -> Vec<u64> {
+        (0..self.runs.get() as u64)
+            .map(|run| self.first_seed.wrapping_add(run))
+            .collect()
+    }
+}
+
+/// Outcome of `chutoro stability`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StabilitySummary {
+    /// Name reported by the data source implementation.
+    pub data_source: String,
+    /// Master seed of each run.
+    pub seeds: Vec<u64>,
+    /// Result of each run, in seed order.
+    pub results: Vec<ClusteringResult>,
+    /// Agreement between the runs, with the first as reference.
+    pub report: StabilityReport,
+}
+
+/// Clusters the input of `command` once per seed and compares the runs.
+///
+/// `--config` is applied first, as for `run`.
+///
+/// # Errors
+/// Returns [`CliError::MissingSource`] when no data source is set, and the
+/// errors `run` would raise while validating parameters, loading the source,
+/// or clustering it.
+#[instrument(
+    name = "cli.stability",
+    err,
+    skip(command),
+    fields(
+        runs = command.runs.get(),
+        source = %command.source.as_ref().map_or("<none>", RunSource::kind)
+    ),
+)]
+pub fn stability_command(command: &StabilityCommand) -> Result<StabilitySummary, CliError> {
+    let run = command.to_run().resolve()?;
+    let builder = configure_builder(&run)?;
+    let summary = match run.source.as_ref().ok_or(CliError::MissingSource)? {
+        RunSource::Parquet(args) => compare(&builder, &parquet_provider(args)?, command)?,
+        RunSource::Text(args) => compare(&builder, &text_provider(args)?, command)?,
+        RunSource::Images(args) => compare(&builder, &image_provider(args)?, command)?,
+    };
+    info!(
+        data_source = summary.data_source.as_str(),
+        mean_ari = summary.report.mean_ari(),
+        "stability comparison completed"
+    );
+    Ok(summary)
+}
+
+fn compare<D: DataSource + Sync>(
+    builder: &ChutoroBuilder,
+    provider: &D,
+    command: &StabilityCommand,
+) -> Result<StabilitySummary, CliError> {
+    let seeds = command.seeds();
+    let cluster = |seed| -> Result<ClusteringResult, CliError> {
+        Ok(builder.clone().with_seed(seed).build()?.run(provider)?)
+    };
+    // `--runs` is non-zero, so the reference run always exists.
+    let reference = cluster(command.first_seed)?;
+    let others = seeds
+        .iter()
+        .skip(1)
+        .map(|&seed| cluster(seed))
+        .collect::<Result<Vec<_>, _>>()?;
+    let report = reference
+        .stability_report(&others)
+        .map_err(CliError::Stability)?;
+    Ok(StabilitySummary {
+        data_source: provider.name().to_owned(),
+        seeds,
+        results: std::iter::once(reference).chain(others).collect(),
+        report,
+    })
+}
+
+/// Renders `summary` to `writer` as human-readable text.
+///
+/// Pairwise scores are printed as `pair <left>-<right>` lines and reference
+/// clusters as `cluster <id>` lines; means read `n/a` for a single run.
+///
+/// # Errors
+/// Returns [`io::Error`] if writing to the supplied writer fails.
+pub fn render_stability(summary: &StabilitySummary, mut writer: impl Write) -> io::Result<()> {
+    let report = &summary.report;
+    writeln!(writer, "data source: {}", summary.data_source)?;
+    for (run, (seed, result)) in summary.seeds.iter().zip(&summary.results).enumerate() {
+        writeln!(
+            writer,
+            "run {run}: seed={seed} clusters={} noise_fraction={:.4}",
+            result.cluster_count(),
+            result.noise_fraction(),
+        )?;
+    }
+    writeln!(
+        writer,
+        "agreement: mean_ari={} min_ari={} mean_nmi={}",
+        score(report.mean_ari()),
+        score(report.min_ari()),
+        score(report.mean_nmi()),
+    )?;
+    for agreement in report.agreements() {
+        writeln!(
+            writer,
+            "pair {}-{}: ari={:.4} nmi={:.4}",
+            agreement.left, agreement.right, agreement.score.ari, agreement.score.nmi,
+        )?;
+    }
+    for cluster in report.clusters() {
+        writeln!(
+            writer,
+            "cluster {}: size={} persistence={:.2} mean_jaccard={:.4}",
+            cluster.cluster.get(),
+            cluster.size,
+            cluster.frequency,
+            cluster.mean_jaccard,
+        )?;
+    }
+    Ok(())
+}
+
+fn score(value: Option<f64>) -> String {
+    value.map_or_else(|| "n/a".to_owned(), |value| format!("{value:.4}"))
+}
+
+/// Renders `summary` for `command` to `writer` as JSON.
+///
+/// The means are `null` for a single run; the parameters match those of a
+/// `run` document.
+///
+/// # Errors
+/// Returns [`io::Error`] if serialization or writing fails.
+pub fn render_stability_json(
+    summary: &StabilitySummary,
+    command: &RunCommand,
+    writer: impl Write,
+) -> io::Result<()> {
+    let report = &summary.report;
+    let document = StabilityDocument {
+        status: "ok",
+        data_source: &summary.data_source,
+        points: summary
+            .results
+            .first()
+            .map_or(0, |result| result.assignments().len()),
+        runs: summary
+            .seeds
+            .iter()
+            .zip(&summary.results)
+            .map(|(&seed, result)| JsonRun {
+                seed,
+                clusters: result.cluster_count(),
+                noise_fraction: result.noise_fraction(),
+            })
+            .collect(),
+        mean_ari: report.mean_ari(),
+        min_ari: report.min_ari(),
+        mean_nmi: report.mean_nmi(),
+        pairs: report
+            .agreements()
+            .iter()
+            .map(|agreement| JsonPair {
+                left: agreement.left,
+                right: agreement.right,
+                ari: agreement.score.ari,
+                nmi: agreement.score.nmi,
+            })
+            .collect(),
+        clusters: report
+            .clusters()
+            .iter()
+            .map(|cluster| JsonPersistence {
+                cluster: cluster.cluster.get(),
+                size: cluster.size,
+                frequency: cluster.frequency,
+                mean_jaccard: cluster.mean_jaccard,
+            })
+            .collect(),
+        parameters: JsonParameters::from(command),
+    };
+    write_document(&document, writer)
+}
+
+#[derive(Serialize)]
+struct StabilityDocument<'a> {
+    status: &'static str,
+    data_source: &'a str,
+    points: usize,
+    runs: Vec<JsonRun>,
+    mean_ari: Option<f64>,
+    min_ari: Option<f64>,
+    mean_nmi: Option<f64>,
+    pairs: Vec<JsonPair>,
+    clusters: Vec<JsonPersistence>,
+    parameters: JsonParameters<'a>,
+}
+
+#[derive(Serialize)]
+struct JsonRun {
+    seed: u64,
+    clusters: usize,
+    noise_fraction: f64,
+}
+
+#[derive(Serialize)]
+struct JsonPair {
+    left: usize,
+    right: usize,
+    ari: f64,
+    nmi: f64,
+}
+
+#[derive(Serialize)]
+struct JsonPersistence {
+    cluster: u64,
+    size: usize,
+    frequency: f64,
+    mean_jaccard: f64,
+}
@@ -0,0 +1,187 @@
+//! Tests for `chutoro batch`.
+
+use std::fs;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+
+use super::super::{
+    BatchCommand, Cli, CliError, Command, ExitStatus, OutputArgs, batch_command, render_batch,
+    render_batch_json,
+};
+
+use clap::Parser;
+use rstest::rstest;
+use serde_json::Value;
+use tempfile::TempDir;
+
+use super::helpers::{create_text_file, temp_dir};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+fn batch(manifest: PathBuf) -> BatchCommand {
+    BatchCommand {
+        manifest,
+        parallel: None,
+        output_dir: None,
+        output: OutputArgs::default(),
+    }
+}
+
+/// Writes a jobs file over two text datasets and one missing dataset.
+fn tenants(dir: &TempDir) -> Result<PathBuf, std::io::Error> {
+    create_text_file(dir, "a.txt", "alpha\nalpine\nalps\nbeta\nbetting\nbetter\n")?;
+    create_text_file(dir, "b.txt", "gamma\ngammas\ngrammar\n")?;
+    for (name, data) in [("a", "a.txt"), ("b", "b.txt"), ("missing", "missing.txt")] {
+        create_text_file(
+            dir,
+            &format!("{name}.toml"),
+            &format!("[source]\nkind = \"text\"\npath = \"{data}\"\nmetric = \"levenshtein\"\n"),
+        )?;
+    }
+    create_text_file(
+        dir,
+        "jobs.toml",
+        concat!(
+            "output_dir = \"results\"\nparallel = 2\n",
+            "[[job]]\nname = \"tenant-a\"\nconfig = \"a.toml\"\nmin_cluster_size = 2\n",
+            "[[job]]\nname = \"tenant-missing\"\nconfig = \"missing.toml\"\n",
+            "[[job]]\nname = \"tenant-b\"\nconfig = \"b.toml\"\nmin_cluster_size = 2\nseed = 3\n",
+        ),
+    )
+}
+
+#[rstest]
+fn plan_resolves_the_jobs_file_beneath_the_flags() -> TestResult {
+    let dir = temp_dir();
+    let manifest = tenants(&dir)?;
+
+    let plan = batch(manifest.clone()).plan()?;
+    assert_eq!(plan.parallel.get(), 2);
+    assert_eq!(plan.output_dir, dir.path().join("results"));
+    let names: Vec<_> = plan.jobs.iter().map(|job| job.name.as_str()).collect();
+    assert_eq!(names, ["tenant-a", "tenant-missing", "tenant-b"]);
+    let b = &plan.jobs[2].run;
+    assert_eq!(b.config, Some(dir.path().join("b.toml")));
+    assert_eq!(b.min_cluster_size, Some(2));
+    assert_eq!(b.hnsw.seed, Some(3));
+
+    let overridden = BatchCommand {
+        parallel: NonZeroUsize::new(4),
+        output_dir: Some(PathBuf::from("elsewhere")),
+        ..batch(manifest)
+    }
+    .plan()?;
+    assert_eq!(overridden.parallel.get(), 4);
+    assert_eq!(overridden.output_dir, PathBuf::from("elsewhere"));
+    Ok(())
+}
+
+#[rstest]
+#[case::no_jobs("output_dir = \"out\"\n", "job")]
+#[case::repeated_name(
+    "[[job]]\nname = \"a\"\nconfig = \"a.toml\"\n[[job]]\nname = \"a\"\nconfig = \"b.toml\"\n",
+    "job.name"
+)]
+#[case::path_name("[[job]]\nname = \"../a\"\nconfig = \"a.toml\"\n", "job.name")]
+fn invalid_jobs_files_are_rejected(
+    #[case] contents: &str,
+    #[case] expected_key: &str,
+) -> TestResult {
+    let dir = temp_dir();
+    let manifest = create_text_file(&dir, "jobs.toml", contents)?;
+    let err = batch(manifest)
+        .plan()
+        .expect_err("jobs file must be rejected");
+    assert!(
+        matches!(&err, CliError::InvalidConfig { key, .. } if *key == expected_key),
+        "unexpected error: {err:?}"
+    );
+    Ok(())
+}
+
+#[rstest]
+fn unknown_job_keys_are_rejected() -> TestResult {
+    let dir = temp_dir();
+    let manifest = create_text_file(
+        &dir,
+        "jobs.toml",
+        "[[job]]\nname = \"a\"\nconfig = \"a.toml\"\nmetric = \"levenshtein\"\n",
+    )?;
+    let err = batch(manifest).plan().expect_err("unknown key must fail");
+    assert!(matches!(err, CliError::ConfigParse { .. }));
+    Ok(())
+}
+
+#[rstest]
+fn batch_runs_every_job_and_writes_their_summaries() -> TestResult {
+    let dir = temp_dir();
+    let summary = batch_command(&batch(tenants(&dir)?))?;
+
+    let names: Vec<_> = summary.jobs.iter().map(|job| job.name.as_str()).collect();
+    assert_eq!(names, ["tenant-a", "tenant-missing", "tenant-b"]);
+    assert_eq!(summary.failed(), 1);
+    assert_eq!(summary.exit_status(), ExitStatus::Failure);
+
+    let a = summary.jobs[0]
+        .outcome
+        .as_ref()
+        .expect("tenant-a must succeed");
+    assert_eq!((a.data_source.as_str(), a.points), ("a", 6));
+    assert!(matches!(summary.jobs[1].outcome, Err(CliError::Io { .. })));
+
+    let results = dir.path().join("results");
+    for (name, status) in [
+        ("tenant-a", "ok"),
+        ("tenant-missing", "error"),
+        ("tenant-b", "ok"),
+    ] {
+        let document: Value =
+            serde_json::from_str(&fs::read_to_string(results.join(format!("{name}.json")))?)?;
+        assert_eq!(document["status"], status, "summary of {name}");
+    }
+    Ok(())
+}
+
+#[rstest]
+fn batch_reports_render_each_job() -> TestResult {
+    let dir = temp_dir();
+    let summary = batch_command(&batch(tenants(&dir)?))?;
+
+    let mut text = Vec::new();
+    render_batch(&summary, &mut text)?;
+    let text = String::from_utf8(text)?;
+    let lines: Vec<_> = text.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].starts_with("job tenant-a: ok data_source=a points=6 "));
+    assert!(lines[1].starts_with("job tenant-missing: error exit_code=4 "));
+    assert_eq!(lines[3], "jobs: 2 succeeded, 1 failed");
+
+    let mut json = Vec::new();
+    render_batch_json(&summary, &mut json)?;
+    let document: Value = serde_json::from_slice(&json)?;
+    assert_eq!(document["status"], "error");
+    assert_eq!(document["succeeded"], 2);
+    assert_eq!(document["jobs"][1]["error"]["exit_code"], 4);
+    assert_eq!(document["jobs"][2]["points"], 3);
+    Ok(())
+}
+
+#[rstest]
+fn clap_parses_batch_options() {
+    let cli = Cli::try_parse_from([
+        "chutoro",
+        "batch",
+        "--manifest",
+        "jobs.toml",
+        "--parallel",
+        "3",
+        "--json",
+    ])
+    .expect("arguments must parse");
+    let Command::Batch(command) = cli.command else {
+        panic!("expected a batch command");
+    };
+    assert_eq!(command.manifest, PathBuf::from("jobs.toml"));
+    assert_eq!(command.parallel, NonZeroUsize::new(3));
+    assert!(command.output.json);
+}
@@ -0,0 +1,279 @@
+//! Tests for `--config` loading and `chutoro config init`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::super::{
+    CONFIG_TEMPLATE, Cli, CliError, Command, ConfigAction, ConfigCommand, ConfigInitArgs,
+    RunCommand, RunSource, SummaryFormat, run_cli, run_config,
+};
+
+use clap::Parser;
+use rstest::rstest;
+use tempfile::TempDir;
+
+use super::helpers::{create_text_file, temp_dir};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+fn parse_run(args: &[&str]) -> RunCommand {
+    match Cli::try_parse_from(args) {
+        Ok(Cli {
+            command: Command::Run(run),
+        }) => run,
+        Ok(other) => panic!("expected a run command, got {other:?}"),
+        Err(err) => panic!("arguments must parse: {err}"),
+    }
+}
+
+fn write_config(dir: &TempDir, contents: &str) -> Result<PathBuf, std::io::Error> {
+    create_text_file(dir, "chutoro.toml", contents)
+}
+
+fn config_command(path: &Path) -> RunCommand {
+    RunCommand {
+        config: Some(path.to_path_buf()),
+        ..RunCommand::default()
+    }
+}
+
+fn source_path(run: &RunCommand) -> &Path {
+    match &run.source {
+        Some(RunSource::Text(args)) => &args.path,
+        Some(RunSource::Parquet(args)) => &args.path,
+        Some(RunSource::Images(args)) => &args.path,
+        None => panic!("source must be resolved"),
+    }
+}
+
+#[rstest]
+fn template_resolves_to_documented_defaults() -> TestResult {
+    let dir = temp_dir();
+    let config = write_config(&dir, CONFIG_TEMPLATE)?;
+    let run = config_command(&config).resolve()?;
+
+    assert_eq!(run.min_cluster_size, Some(5));
+    assert_eq!(run.hnsw.max_connections, Some(16));
+    assert_eq!(run.hnsw.ef_construction, Some(64));
+    assert_eq!(run.max_bytes, None);
+    assert_eq!(run.output.summary_format(), SummaryFormat::Text);
+    assert_eq!(run.detail, None);
+    assert_eq!(source_path(&run), dir.path().join("data.txt"));
+    Ok(())
+}
+
+#[rstest]
+fn flags_take_precedence_over_config() -> TestResult {
+    let dir = temp_dir();
+    let config = write_config(
+        &dir,
+        concat!(
+            "[source]\nkind = \"parquet\"\npath = \"vectors.parquet\"\ncolumn = \"features\"\n",
+            "[hierarchy]\nmin_cluster_size = 8\n",
+            "[hnsw]\nmax_connections = 24\nef_construction = 200\nseed = 7\n",
+            "[limits]\nmax_bytes = \"2K\"\n",
+            "[output]\nformat = \"text\"\n",
+        ),
+    )?;
+    let config_arg = config.to_string_lossy().into_owned();
+    let run = parse_run(&[
+        "chutoro",
+        "run",
+        "--config",
+        &config_arg,
+        "--min-cluster-size",
+        "3",
+        "--hnsw-ef-construction",
+        "96",
+        "--json",
+        "text",
+        "lines.txt",
+        "--metric",
+        "levenshtein",
+    ])
+    .resolve()?;
+
+    assert_eq!(run.min_cluster_size, Some(3));
+    assert_eq!(run.hnsw.max_connections, Some(24));
+    assert_eq!(run.hnsw.ef_construction, Some(96));
+    assert_eq!(run.hnsw.seed, Some(7));
+    assert_eq!(run.max_bytes, Some(2048));
+    assert_eq!(run.output.summary_format(), SummaryFormat::Json);
+    assert!(matches!(run.source, Some(RunSource::Text(_))));
+    assert_eq!(source_path(&run), Path::new("lines.txt"));
+    Ok(())
+}
+
+#[rstest]
+#[case::stdin("-", "-")]
+#[case::relative("corpus/lines.txt", "corpus/lines.txt")]
+fn config_paths_resolve_against_config_directory(
+    #[case] configured: &str,
+    #[case] expected: &str,
+) -> TestResult {
+    let dir = temp_dir();
+    let config = write_config(
+        &dir,
+        &format!("[source]\nkind = \"text\"\npath = \"{configured}\"\nmetric = \"levenshtein\"\n"),
+    )?;
+    let run = config_command(&config).resolve()?;
+    let expected = if expected == "-" {
+        PathBuf::from("-")
+    } else {
+        dir.path().join(expected)
+    };
+    assert_eq!(source_path(&run), expected);
+    Ok(())
+}
+
+#[rstest]
+#[case::single("column = \"features\"", &["features"])]
+#[case::array("columns = [\"embedding\", \"price\"]", &["embedding", "price"])]
+fn parquet_columns_accept_a_name_or_an_array(
+    #[case] entry: &str,
+    #[case] expected: &[&str],
+) -> TestResult {
+    let dir = temp_dir();
+    let config = write_config(
+        &dir,
+        &format!("[source]\nkind = \"parquet\"\npath = \"v.parquet\"\n{entry}\n"),
+    )?;
+    let run = config_command(&config).resolve()?;
+    match run.source {
+        Some(RunSource::Parquet(args)) => assert_eq!(args.columns, expected),
+        other => panic!("expected a parquet source, got {other:?}"),
+    }
+    Ok(())
+}
+
+#[rstest]
+#[case::unknown_section("[clustering]\nmin_cluster_size = 3\n")]
+#[case::unknown_key("[hierarchy]\nmin_size = 3\n")]
+#[case::unknown_metric("[source]\nkind = \"text\"\npath = \"a\"\nmetric = \"cosine\"\n")]
+fn malformed_config_is_rejected(#[case] contents: &str) -> TestResult {
+    let dir = temp_dir();
+    let config = write_config(&dir, contents)?;
+    let err = config_command(&config)
+        .resolve()
+        .expect_err("malformed config must fail");
+    assert!(matches!(err, CliError::ConfigParse { .. }), "{err:?}");
+    Ok(())
+}
+
+#[rstest]
+fn invalid_byte_size_is_reported_with_its_key() -> TestResult {
+    let dir = temp_dir();
+    let config = write_config(&dir, "[limits]\nmax_bytes = \"2Q\"\n")?;
+    let err = config_command(&config)
+        .resolve()
+        .expect_err("unknown suffix must fail");
+    assert!(matches!(
+        err,
+        CliError::InvalidConfig {
+            key: "limits.max_bytes",
+            ..
+        }
+    ));
+    Ok(())
+}
+
+#[rstest]
+fn run_cli_executes_source_from_config() -> TestResult {
+    let dir = temp_dir();
+    create_text_file(&dir, "lines.txt", "alpha\nbeta\ngamma\n")?;
+    let config = write_config(
+        &dir,
+        "[source]\nkind = \"text\"\npath = \"lines.txt\"\nmetric = \"levenshtein\"\n\
+         [hierarchy]\nmin_cluster_size = 2\n",
+    )?;
+    let summary = run_cli(Cli {
+        command: Command::Run(config_command(&config)),
+    })?;
+    assert_eq!(summary.data_source, "lines");
+    assert_eq!(summary.result.assignments().len(), 3);
+    Ok(())
+}
+
+#[rstest]
+fn run_without_any_source_fails() {
+    let err = run_cli(Cli {
+        command: Command::Run(RunCommand::default()),
+    })
+    .expect_err("a source is required");
+    assert!(matches!(err, CliError::MissingSource));
+}
+
+#[rstest]
+fn invalid_hnsw_flags_are_rejected() -> TestResult {
+    let dir = temp_dir();
+    let path = create_text_file(&dir, "lines.txt", "alpha\nbeta\n")?;
+    let path_arg = path.to_string_lossy().into_owned();
+    let run = parse_run(&[
+        "chutoro",
+        "run",
+        "--hnsw-max-connections",
+        "32",
+        "--hnsw-ef-construction",
+        "8",
+        "text",
+        &path_arg,
+        "--metric",
+        "levenshtein",
+    ]);
+    let err = run_cli(Cli {
+        command: Command::Run(run),
+    })
+    .expect_err("ef_construction below max_connections must fail");
+    assert!(matches!(err, CliError::Hnsw(_)));
+    Ok(())
+}
+
+#[rstest]
+fn config_init_writes_template_to_writer() -> TestResult {
+    let command = ConfigCommand {
+        action: ConfigAction::Init(ConfigInitArgs::default()),
+    };
+    let mut buffer = Vec::new();
+    run_config(&command, &mut buffer)?;
+    assert_eq!(String::from_utf8(buffer)?, CONFIG_TEMPLATE);
+    assert!(matches!(
+        run_cli(Cli {
+            command: Command::Config(command),
+        }),
+        Err(CliError::NotARun { command: "config" })
+    ));
+    Ok(())
+}
+
+#[rstest]
+fn config_init_refuses_to_overwrite_without_force() -> TestResult {
+    let dir = temp_dir();
+    let path = write_config(&dir, "# existing\n")?;
+    let mut args = ConfigInitArgs {
+        output: Some(path.clone()),
+        force: false,
+    };
+    let init = |args: &ConfigInitArgs| {
+        run_config(
+            &ConfigCommand {
+                action: ConfigAction::Init(args.clone()),
+            },
+            std::io::sink(),
+        )
+    };
+
+    let err = init(&args).expect_err("existing file must not be replaced");
+    assert!(matches!(err, CliError::Io { .. }));
+    assert_eq!(fs::read_to_string(&path)?, "# existing\n");
+
+    args.force = true;
+    init(&args)?;
+    assert_eq!(fs::read_to_string(&path)?, CONFIG_TEMPLATE);
+    Ok(())
+}
+
+#[rstest]
+fn clap_requires_output_for_force() {
+    assert!(Cli::try_parse_from(["chutoro", "config", "init", "--force"]).is_err());
+    assert!(Cli::try_parse_from(["chutoro", "config", "init", "--output", "c.toml"]).is_ok());
+}
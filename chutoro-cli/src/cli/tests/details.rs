@@ -0,0 +1,180 @@
+//! Tests for the `--detail clusters` table.
+
+use super::super::commands::run_command;
+use super::super::{
+    Cli, ClusterDetail, Command, ExecutionSummary, RunCommand, SummaryDetail, cluster_details,
+    render_summary, render_summary_json,
+};
+
+use chutoro_core::{ClusterId, ClusteringResult, DataSource, DataSourceError};
+use clap::Parser;
+use rstest::rstest;
+use serde_json::{Value, json};
+
+use super::helpers::{create_text_file, temp_dir, text_command};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+/// Points on a line compared by absolute difference.
+struct Line(Vec<f32>);
+
+impl DataSource for Line {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn name(&self) -> &str {
+        "line"
+    }
+
+    fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+        Ok((self.0[i] - self.0[j]).abs())
+    }
+}
+
+fn parse(args: &[&str]) -> RunCommand {
+    match Cli::try_parse_from(args) {
+        Ok(Cli {
+            command: Command::Run(run),
+        }) => run,
+        Ok(other) => panic!("expected a run command, got {other:?}"),
+        Err(err) => panic!("arguments must parse: {err}"),
+    }
+}
+
+fn detailed_summary() -> ExecutionSummary {
+    ExecutionSummary {
+        data_source: "demo".into(),
+        result: ClusteringResult::from_assignments(vec![ClusterId::new(0), ClusterId::new(1)]),
+        quality: None,
+        clusters: Some(vec![
+            ClusterDetail {
+                cluster: 0,
+                noise: false,
+                size: 1,
+                fraction: 0.5,
+                exemplar: Some(0),
+                mean_distance: None,
+            },
+            ClusterDetail {
+                cluster: 1,
+                noise: false,
+                size: 1,
+                fraction: 0.5,
+                exemplar: Some(1),
+                mean_distance: Some(0.25),
+            },
+        ]),
+    }
+}
+
+#[rstest]
+fn cluster_details_report_size_share_exemplar_and_spread() -> TestResult {
+    let source = Line(vec![0.0, 1.0, 2.0, 10.0, 12.0]);
+    let result = ClusteringResult::from_assignments([0, 0, 0, 1, 1].map(ClusterId::new).to_vec());
+
+    let details = cluster_details(&result, &source)?;
+
+    assert_eq!(
+        details,
+        vec![
+            ClusterDetail {
+                cluster: 0,
+                noise: false,
+                size: 3,
+                fraction: 0.6,
+                exemplar: Some(1),
+                mean_distance: Some(4.0 / 3.0),
+            },
+            ClusterDetail {
+                cluster: 1,
+                noise: false,
+                size: 2,
+                fraction: 0.4,
+                exemplar: Some(3),
+                mean_distance: Some(2.0),
+            },
+        ]
+    );
+    Ok(())
+}
+
+#[rstest]
+fn clap_parses_the_detail_flag() {
+    let run = parse(&[
+        "chutoro",
+        "run",
+        "--detail",
+        "clusters",
+        "text",
+        "data.txt",
+        "--metric",
+        "levenshtein",
+    ]);
+    assert_eq!(run.detail, Some(SummaryDetail::Clusters));
+}
+
+#[rstest]
+fn text_summary_prints_the_cluster_table_before_assignments() -> TestResult {
+    let mut buffer = Vec::new();
+    render_summary(&detailed_summary(), &mut buffer)?;
+    let text = String::from_utf8(buffer)?;
+    assert!(text.contains(concat!(
+        "cluster\tsize\tpercent\texemplar\tmean_distance\n",
+        "0\t1\t50.0%\t0\t-\n",
+        "1\t1\t50.0%\t1\t0.2500\n",
+        "0\t0\n",
+    )));
+    Ok(())
+}
+
+#[rstest]
+fn json_summary_lists_cluster_details() -> TestResult {
+    let mut buffer = Vec::new();
+    let command = parse(&[
+        "chutoro",
+        "run",
+        "text",
+        "data.txt",
+        "--metric",
+        "levenshtein",
+    ]);
+    render_summary_json(&detailed_summary(), &command, &mut buffer)?;
+    let document: Value = serde_json::from_slice(&buffer)?;
+    assert_eq!(
+        document["cluster_details"][1],
+        json!({
+            "cluster": 1,
+            "noise": false,
+            "size": 1,
+            "fraction": 0.5,
+            "exemplar": 1,
+            "mean_distance": 0.25,
+        })
+    );
+    Ok(())
+}
+
+#[rstest]
+#[case::without_detail(None)]
+#[case::with_detail(Some(SummaryDetail::Clusters))]
+fn runs_compute_details_only_when_asked(#[case] detail: Option<SummaryDetail>) -> TestResult {
+    let dir = temp_dir();
+    let path = create_text_file(&dir, "lines.txt", "alpha\nbeta\ngamma\n")?;
+    let command = RunCommand {
+        detail,
+        ..text_command(path, 2, None)
+    };
+
+    let summary = run_command(command)?;
+
+    match summary.clusters {
+        None => assert_eq!(detail, None),
+        Some(clusters) => {
+            assert_eq!(clusters.len(), summary.result.cluster_count());
+            let total: usize = clusters.iter().map(|cluster| cluster.size).sum();
+            assert_eq!(total, 3);
+        }
+    }
+    Ok(())
+}
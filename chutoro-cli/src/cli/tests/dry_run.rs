@@ -0,0 +1,142 @@
+//! Tests for `chutoro run --dry-run`.
+
+use super::super::{
+    Cli, CliError, Command, ExitStatus, ParquetArgs, RunCommand, RunSource, dry_run_command,
+    render_dry_run, render_dry_run_json,
+};
+
+use clap::Parser;
+use rstest::rstest;
+
+use super::fixtures::create_parquet_file;
+use super::helpers::{create_text_file, temp_dir, text_command};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const WORDS: &str = "alpha\nalphb\nalphc\nbeta\nbetb\nbetc\n";
+
+#[rstest]
+fn the_flag_parses() {
+    let cli = Cli::try_parse_from([
+        "chutoro",
+        "run",
+        "--dry-run",
+        "text",
+        "--metric",
+        "levenshtein",
+        "words.txt",
+    ])
+    .expect("arguments must parse");
+
+    let Command::Run(run) = cli.command else {
+        panic!("expected a run command");
+    };
+    assert!(run.dry_run);
+}
+
+#[rstest]
+fn text_sources_pass_and_render() -> TestResult {
+    let dir = temp_dir();
+    let path = create_text_file(&dir, "words.txt", WORDS)?;
+
+    let summary = dry_run_command(&text_command(path, 2, None))?;
+    let mut buffer = Vec::new();
+    render_dry_run(&summary, &mut buffer)?;
+
+    assert_eq!(summary.data_source, "words");
+    assert_eq!(summary.points, 6);
+    assert_eq!(summary.exit_status(), ExitStatus::Success);
+    let text = String::from_utf8(buffer)?;
+    assert!(text.starts_with("data source: words\npoints: 6\n"));
+    assert!(text.contains("probes: pairs=1024 non_finite=0 negative=0 identity=0 asymmetric=0\n"));
+    assert!(text.contains("estimate: peak="));
+    assert!(text.ends_with("dry run: passed\n"));
+    Ok(())
+}
+
+#[rstest]
+fn parquet_sources_pass() -> TestResult {
+    let dir = temp_dir();
+    let path = create_parquet_file(&dir, "vectors.parquet")?;
+    let command = RunCommand {
+        min_cluster_size: Some(1),
+        source: Some(RunSource::Parquet(ParquetArgs {
+            path,
+            columns: vec!["features".to_owned()],
+            name: None,
+            lossy_f64: false,
+            id_column: None,
+            label_column: None,
+            output: None,
+        })),
+        ..RunCommand::default()
+    };
+
+    let summary = dry_run_command(&command)?;
+
+    assert_eq!(summary.data_source, "vectors");
+    assert!(summary.report.passed());
+    Ok(())
+}
+
+#[rstest]
+fn memory_limits_fail_with_the_resource_status() -> TestResult {
+    let dir = temp_dir();
+    let path = create_text_file(&dir, "words.txt", WORDS)?;
+    let command = RunCommand {
+        max_bytes: Some(1),
+        ..text_command(path, 2, None)
+    };
+
+    let summary = dry_run_command(&command)?;
+    let mut buffer = Vec::new();
+    render_dry_run_json(&summary, &command, &mut buffer)?;
+
+    assert_eq!(summary.exit_status(), ExitStatus::ResourceLimit);
+    let document: serde_json::Value = serde_json::from_slice(&buffer)?;
+    assert_eq!(document["status"], "failed");
+    assert_eq!(document["dry_run"], true);
+    assert_eq!(document["points"], 6);
+    assert_eq!(document["probes"]["pairs"], 1024);
+    assert_eq!(document["estimate"]["max_bytes"], 1);
+    assert_eq!(document["estimate"]["fits"], false);
+    assert_eq!(document["parameters"]["source"], "text");
+    Ok(())
+}
+
+#[rstest]
+fn nothing_is_written() -> TestResult {
+    let dir = temp_dir();
+    let path = create_text_file(&dir, "words.txt", WORDS)?;
+    let manifest = dir.path().join("manifest.json");
+    let command = RunCommand {
+        manifest: Some(manifest.clone()),
+        dry_run: true,
+        ..text_command(path, 2, None)
+    };
+
+    dry_run_command(&command)?;
+
+    assert!(!manifest.exists());
+    Ok(())
+}
+
+#[rstest]
+fn sources_a_run_would_reject_are_errors() -> TestResult {
+    let dir = temp_dir();
+    let path = create_text_file(&dir, "words.txt", "alpha\n")?;
+
+    let err = dry_run_command(&text_command(path, 5, None))
+        .expect_err("a source smaller than the minimum cluster size must fail");
+
+    assert!(matches!(err, CliError::Core(_)));
+    assert_eq!(err.exit_status(), ExitStatus::Data);
+    Ok(())
+}
+
+#[rstest]
+fn a_source_is_required() {
+    let err = dry_run_command(&RunCommand::default()).expect_err("no source must fail");
+
+    assert!(matches!(err, CliError::MissingSource));
+}
@@ -0,0 +1,150 @@
+//! Tests for the exit statuses and remediation hints attached to CLI errors.
+
+use std::sync::Arc;
+
+use super::super::commands::run_command;
+use super::super::{CliError, ExitStatus, ParquetArgs, RunCommand, RunSource};
+
+use chutoro_core::{ChutoroError, DataSourceError, HnswError, HnswErrorCode};
+use chutoro_providers_dense::DenseMatrixProviderError;
+use chutoro_providers_text::TextProviderError;
+use rstest::rstest;
+
+use super::fixtures::create_parquet_file;
+use super::helpers::temp_dir;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+fn data_source_failure(error: DataSourceError) -> CliError {
+    CliError::Core(ChutoroError::DataSource {
+        data_source: Arc::from("demo"),
+        error,
+    })
+}
+
+fn hnsw_failure(code: HnswErrorCode) -> CliError {
+    CliError::Core(ChutoroError::CpuHnswFailure {
+        code: Arc::from(code.as_str()),
+        message: Arc::from("failed"),
+    })
+}
+
+#[rstest]
+#[case::missing_source(CliError::MissingSource, ExitStatus::Config)]
+#[case::io(
+    CliError::Io {
+        path: "missing.txt".into(),
+        source: std::io::Error::from(std::io::ErrorKind::NotFound),
+    },
+    ExitStatus::Io
+)]
+#[case::text_io(
+    CliError::Text(TextProviderError::Io(std::io::ErrorKind::Other.into())),
+    ExitStatus::Io
+)]
+#[case::empty_text(CliError::Text(TextProviderError::EmptyInput), ExitStatus::Data)]
+#[case::lossy(
+    CliError::Dense(DenseMatrixProviderError::LossyNarrowingDisabled),
+    ExitStatus::Data
+)]
+#[case::hnsw_params(
+    CliError::Hnsw(HnswError::InvalidParameters { reason: "bad".into() }),
+    ExitStatus::Config
+)]
+#[case::min_cluster_size(
+    CliError::Core(ChutoroError::InvalidMinClusterSize { got: 0 }),
+    ExitStatus::Config
+)]
+#[case::non_finite(hnsw_failure(HnswErrorCode::NonFiniteDistance), ExitStatus::Data)]
+#[case::graph_invariant(
+    hnsw_failure(HnswErrorCode::GraphInvariantViolation),
+    ExitStatus::Failure
+)]
+#[case::budget(
+    data_source_failure(DataSourceError::DistanceBudgetExhausted { budget: 10 }),
+    ExitStatus::ResourceLimit
+)]
+#[case::cancelled(data_source_failure(DataSourceError::Cancelled), ExitStatus::Cancelled)]
+#[case::dimension(data_source_failure(DataSourceError::ZeroDimension), ExitStatus::Data)]
+#[case::manifest(
+    CliError::UnsupportedManifest { path: "run.json".into(), version: 99 },
+    ExitStatus::Manifest
+)]
+fn errors_map_to_exit_statuses(#[case] error: CliError, #[case] expected: ExitStatus) {
+    assert_eq!(error.exit_status(), expected);
+}
+
+#[rstest]
+fn exit_codes_are_distinct() {
+    let statuses = [
+        ExitStatus::Success,
+        ExitStatus::Failure,
+        ExitStatus::Usage,
+        ExitStatus::Config,
+        ExitStatus::Io,
+        ExitStatus::Data,
+        ExitStatus::ResourceLimit,
+        ExitStatus::Manifest,
+        ExitStatus::Cancelled,
+    ];
+    let codes: Vec<u8> = statuses.iter().map(|status| status.code()).collect();
+    assert_eq!(codes, (0..=8).collect::<Vec<u8>>());
+}
+
+#[rstest]
+fn missing_feature_column_lists_the_available_columns() -> TestResult {
+    let dir = temp_dir();
+    let path = create_parquet_file(&dir, "vectors.parquet")?;
+    let command = RunCommand {
+        source: Some(RunSource::Parquet(ParquetArgs {
+            path,
+            columns: vec!["embedding".into()],
+            name: None,
+            lossy_f64: false,
+            id_column: None,
+            label_column: None,
+            output: None,
+        })),
+        ..RunCommand::default()
+    };
+
+    let err = run_command(command).expect_err("unknown column must fail");
+
+    assert_eq!(err.exit_status(), ExitStatus::Data);
+    assert_eq!(
+        err.hint().as_deref(),
+        Some("available columns are: features, id")
+    );
+    Ok(())
+}
+
+#[rstest]
+#[case::lossy(
+    CliError::Dense(DenseMatrixProviderError::LossyNarrowingDisabled),
+    "--lossy-f64"
+)]
+#[case::nulls(
+    CliError::Dense(DenseMatrixProviderError::NullRow { row: 3 }),
+    "chutoro inspect"
+)]
+#[case::config(
+    CliError::InvalidConfig { path: "c.toml".into(), key: "hnsw.seed", message: "bad".into() },
+    "chutoro config init"
+)]
+#[case::min_cluster_size(
+    CliError::Core(ChutoroError::InvalidMinClusterSize { got: 0 }),
+    "--min-cluster-size"
+)]
+fn hints_name_the_remedy(#[case] error: CliError, #[case] remedy: &str) {
+    let hint = error.hint().expect("a hint is known");
+
+    assert!(hint.contains(remedy), "{hint}");
+}
+
+#[rstest]
+fn write_failures_have_no_hint() {
+    let error = CliError::Write(std::io::ErrorKind::BrokenPipe.into());
+
+    assert_eq!(error.hint(), None);
+    assert_eq!(error.exit_status(), ExitStatus::Io);
+}
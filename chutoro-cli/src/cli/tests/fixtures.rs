@@ -8,7 +8,7 @@ use std::fs::File;
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use arrow_array::{ArrayRef, FixedSizeListArray, Float32Array, RecordBatch};
+use arrow_array::{ArrayRef, FixedSizeListArray, Float32Array, RecordBatch, StringArray};
 use arrow_schema::{DataType, Field, Schema};
 use parquet::arrow::arrow_writer::ArrowWriter;
 use tempfile::TempDir;
@@ -16,7 +16,8 @@ use tempfile::TempDir;
 /// Creates a small Parquet file containing a fixed `features` column.
 ///
 /// The file is written to `dir` using the provided `name`, and contains a
-/// single record batch with a `features: FixedSizeList<Float32, 2>` column.
+/// single record batch with a `features: FixedSizeList<Float32, 2>` column and
+/// a `Utf8` `id` column holding `"p0"` to `"p3"`.
 ///
 /// This is intended for CLI tests that exercise Parquet ingestion without
 /// relying on external fixtures.
@@ -41,7 +42,10 @@ pub fn create_parquet_file(
 fn build_schema() -> Arc<Schema> {
     let item_field = Arc::new(Field::new("item", DataType::Float32, false));
     let list_type = DataType::FixedSizeList(item_field.clone(), 2);
-    Arc::new(Schema::new(vec![Field::new("features", list_type, false)]))
+    Arc::new(Schema::new(vec![
+        Field::new("features", list_type, false),
+        Field::new("id", DataType::Utf8, false),
+    ]))
 }
 
 fn build_record_batch(schema: Arc<Schema>) -> RecordBatch {
@@ -49,7 +53,8 @@ fn build_record_batch(schema: Arc<Schema>) -> RecordBatch {
     let values = Float32Array::from(vec![0.0_f32, 0.0, 1.0, 1.0, 2.0, 2.0, 3.0, 3.0]);
     let item_field = Arc::new(Field::new("item", DataType::Float32, false));
     let list = FixedSizeListArray::new(item_field, 2, Arc::new(values) as ArrayRef, None);
-    match RecordBatch::try_new(schema, vec![Arc::new(list) as ArrayRef]) {
+    let ids = StringArray::from(vec!["p0", "p1", "p2", "p3"]);
+    match RecordBatch::try_new(schema, vec![Arc::new(list) as ArrayRef, Arc::new(ids)]) {
         Ok(batch) => batch,
         Err(err) => panic!("failed to construct record batch: {err}"),
     }
@@ -0,0 +1,72 @@
+//! Small helpers shared across CLI tests.
+//!
+//! The CLI unit tests build temporary input files and assert error handling
+//! behaviour. These helpers keep the test cases concise and consistent.
+
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use chutoro_core::{ChutoroBuilder, ClusteringResult, EdgeHarvest};
+use tempfile::TempDir;
+
+use super::super::commands::run_command;
+use super::super::{Cli, CliError, RunCommand, RunSource, TextArgs, TextMetric, run_cli};
+
+pub(super) fn temp_dir() -> TempDir {
+    match TempDir::new() {
+        Ok(dir) => dir,
+        Err(err) => panic!("failed to create temp dir: {err}"),
+    }
+}
+
+pub(super) fn create_text_file(dir: &TempDir, name: &str, contents: &str) -> io::Result<PathBuf> {
+    let path = dir.path().join(name);
+    let mut file = File::create(&path)?;
+    file.write_all(contents.as_bytes())?;
+    Ok(path)
+}
+
+/// Returns a two-point result carrying a disconnected-components warning,
+/// clustered from a k-NN graph without edges.
+pub(super) fn result_with_warning() -> ClusteringResult {
+    let chutoro = match ChutoroBuilder::new().with_min_cluster_size(1).build() {
+        Ok(chutoro) => chutoro,
+        Err(err) => panic!("builder must accept a minimum cluster size of one: {err}"),
+    };
+    match chutoro.cluster_from_knn_graph(2, EdgeHarvest::default()) {
+        Ok(result) => result,
+        Err(err) => panic!("an edgeless graph must cluster: {err}"),
+    }
+}
+
+/// Builds a Levenshtein text `run` command over `path` with default output.
+pub(super) fn text_command(
+    path: PathBuf,
+    min_cluster_size: usize,
+    name: Option<&str>,
+) -> RunCommand {
+    RunCommand {
+        min_cluster_size: Some(min_cluster_size),
+        source: Some(RunSource::Text(TextArgs {
+            path,
+            metric: TextMetric::Levenshtein,
+            name: name.map(ToOwned::to_owned),
+        })),
+        ..RunCommand::default()
+    }
+}
+
+pub(super) fn run_cli_expecting_error(cli: Cli, panic_msg: &str) -> CliError {
+    match run_cli(cli) {
+        Ok(_) => panic!("{panic_msg}"),
+        Err(err) => err,
+    }
+}
+
+pub(super) fn run_command_expecting_error(cmd: RunCommand, panic_msg: &str) -> CliError {
+    match run_command(cmd) {
+        Ok(_) => panic!("{panic_msg}"),
+        Err(err) => err,
+    }
+}
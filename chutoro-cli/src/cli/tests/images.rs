@@ -0,0 +1,168 @@
+//! Tests for clustering image folders with `chutoro run images`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::super::commands::run_command;
+use super::super::{
+    Cli, CliError, Command, ImageFeatureKind, ManifestSource, RunCommand, RunManifest, RunSource,
+};
+
+use chutoro_providers_image::ImageProviderError;
+use clap::Parser;
+use image::imageops::{self, FilterType};
+use image::{GrayImage, Luma};
+use rstest::rstest;
+use tempfile::TempDir;
+
+use super::helpers::{create_text_file, temp_dir};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+/// Seeded noise on an 8×8 grid, smoothly interpolated up to `side` pixels.
+fn pattern(seed: u32, side: u32) -> GrayImage {
+    let mut state = seed.wrapping_mul(0x9e37_79b9) | 1;
+    let coarse = GrayImage::from_fn(8, 8, |_, _| {
+        state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        Luma([(state >> 24) as u8])
+    });
+    imageops::resize(&coarse, side, side, FilterType::Triangle)
+}
+
+/// Writes three originals with two rescaled copies each into `dir/photos`.
+fn photos(dir: &TempDir) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let root = dir.path().join("photos");
+    for seed in 0..3 {
+        let set = root.join(format!("set{seed}"));
+        fs::create_dir_all(&set)?;
+        let original = pattern(seed + 1, 128);
+        original.save(set.join("original.png"))?;
+        for side in [96, 64] {
+            imageops::resize(&original, side, side, FilterType::Triangle)
+                .save(set.join(format!("copy{side}.png")))?;
+        }
+    }
+    Ok(root)
+}
+
+fn parse_run(args: &[&str]) -> RunCommand {
+    match Cli::try_parse_from(args) {
+        Ok(Cli {
+            command: Command::Run(run),
+        }) => run,
+        Ok(other) => panic!("expected a run command, got {other:?}"),
+        Err(err) => panic!("arguments must parse: {err}"),
+    }
+}
+
+fn arg(path: &Path) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+#[rstest]
+#[case::dhash("dhash")]
+#[case::phash("phash")]
+#[case::pixels("pixels")]
+fn clusters_copies_of_each_image(#[case] feature: &str) -> TestResult {
+    let dir = temp_dir();
+    let root = arg(&photos(&dir)?);
+    let command = parse_run(&[
+        "chutoro",
+        "run",
+        "--min-cluster-size",
+        "2",
+        "images",
+        &root,
+        "--feature",
+        feature,
+        "--pixel-side",
+        "8",
+    ]);
+
+    let summary = run_command(command)?;
+
+    let labels = summary.result.assignments();
+    assert_eq!(summary.data_source, "photos");
+    assert_eq!(labels.len(), 9);
+    for set in labels.chunks(3) {
+        assert!(set.iter().all(|label| *label == set[0]), "{labels:?}");
+    }
+    Ok(())
+}
+
+#[rstest]
+fn config_sources_default_to_difference_hashes() -> TestResult {
+    let dir = temp_dir();
+    let config = create_text_file(
+        &dir,
+        "chutoro.toml",
+        "[source]\nkind = \"images\"\npath = \"photos\"\n",
+    )?;
+    let run = RunCommand {
+        config: Some(config),
+        ..RunCommand::default()
+    }
+    .resolve()?;
+
+    let Some(RunSource::Images(args)) = run.source else {
+        panic!("expected an image source, got {:?}", run.source);
+    };
+    assert_eq!(args.path, dir.path().join("photos"));
+    assert_eq!(args.feature, ImageFeatureKind::Dhash);
+    assert_eq!(args.pixel_side, 16);
+    Ok(())
+}
+
+#[rstest]
+fn manifest_replay_detects_edited_images() -> TestResult {
+    let dir = temp_dir();
+    let root = photos(&dir)?;
+    let manifest_path = dir.path().join("run.json");
+    let mut command = parse_run(&[
+        "chutoro",
+        "run",
+        "--min-cluster-size",
+        "2",
+        "images",
+        &arg(&root),
+        "--feature",
+        "phash",
+    ]);
+    command.manifest = Some(manifest_path.clone());
+    let first = run_command(command)?;
+
+    let manifest = RunManifest::load(&manifest_path)?;
+    assert!(matches!(
+        manifest.source,
+        ManifestSource::Images {
+            feature: ImageFeatureKind::Phash,
+            ..
+        }
+    ));
+    assert_eq!(
+        manifest.replay()?.result.assignments(),
+        first.result.assignments()
+    );
+
+    pattern(9, 128).save(root.join("set0/original.png"))?;
+    let err = manifest
+        .replay()
+        .expect_err("edited images must be detected");
+    assert!(matches!(err, CliError::DatasetMismatch { .. }), "{err:?}");
+    Ok(())
+}
+
+#[rstest]
+fn folders_without_images_are_rejected() -> TestResult {
+    let dir = temp_dir();
+    create_text_file(&dir, "notes.txt", "no pictures here")?;
+    let command = parse_run(&["chutoro", "run", "images", &arg(dir.path())]);
+
+    let err = run_command(command).expect_err("empty folders must fail");
+
+    assert!(
+        matches!(err, CliError::Image(ImageProviderError::NoImages { .. })),
+        "{err:?}"
+    );
+    Ok(())
+}
@@ -0,0 +1,159 @@
+//! Tests for source naming, stdin selection, and transparent decompression of
+//! text inputs.
+
+use std::path::Path;
+
+use super::super::commands::run_command;
+use super::super::input::{
+    Compression, STDIN_PATH, derive_data_source_name, is_stdin, open_text_reader,
+};
+use super::super::{CliError, RunCommand, RunSource, TextArgs, TextMetric};
+
+use chutoro_test_support::tracing::RecordingLayer;
+use rstest::rstest;
+use tracing_subscriber::layer::SubscriberExt;
+
+use super::helpers::temp_dir;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+const LINES: &str = "alpha\nbeta\ngamma\n";
+
+#[rstest]
+#[case::plain("lines.txt", Compression::None)]
+#[case::gzip("lines.txt.gz", Compression::Gzip)]
+#[case::gzip_upper("LINES.GZ", Compression::Gzip)]
+#[case::zstd("lines.zst", Compression::Zstd)]
+#[case::zstd_long("lines.zstd", Compression::Zstd)]
+#[case::no_extension("lines", Compression::None)]
+fn detects_compression_from_extension(#[case] path: &str, #[case] expected: Compression) {
+    assert_eq!(Compression::from_path(Path::new(path)), expected);
+}
+
+#[rstest]
+fn dash_selects_stdin() {
+    assert!(is_stdin(Path::new(STDIN_PATH)));
+    assert!(!is_stdin(Path::new("./-")));
+}
+
+#[cfg(feature = "gzip")]
+#[rstest]
+fn reads_gzip_input() -> TestResult {
+    use std::io::Write;
+
+    let dir = temp_dir();
+    let path = dir.path().join("lines.txt.gz");
+    let mut encoder =
+        flate2::write::GzEncoder::new(std::fs::File::create(&path)?, flate2::Compression::fast());
+    encoder.write_all(LINES.as_bytes())?;
+    encoder.finish()?;
+
+    assert_decoded(&path)
+}
+
+#[cfg(feature = "zstd")]
+#[rstest]
+fn reads_zstd_input() -> TestResult {
+    let dir = temp_dir();
+    let path = dir.path().join("lines.txt.zst");
+    std::fs::write(&path, zstd::encode_all(LINES.as_bytes(), 0)?)?;
+
+    assert_decoded(&path)
+}
+
+#[cfg(not(feature = "gzip"))]
+#[rstest]
+fn rejects_gzip_without_feature() -> TestResult {
+    use super::super::CliError;
+
+    let dir = temp_dir();
+    let path = dir.path().join("lines.txt.gz");
+    std::fs::write(&path, b"not decoded")?;
+    let Err(err) = open_text_reader(&path) else {
+        panic!("gzip input must be rejected without the gzip feature");
+    };
+    assert!(matches!(
+        err,
+        CliError::UnsupportedCompression { format: "gzip", .. }
+    ));
+    Ok(())
+}
+
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+fn assert_decoded(path: &Path) -> TestResult {
+    use std::io::Read;
+
+    use super::super::commands::run_command;
+    use super::helpers::text_command;
+
+    let mut decoded = String::new();
+    open_text_reader(path)?.read_to_string(&mut decoded)?;
+    assert_eq!(decoded, LINES);
+
+    let summary = run_command(text_command(path.to_path_buf(), 2, None))?;
+    assert_eq!(summary.data_source, "lines");
+    assert_eq!(summary.result.assignments().len(), 3);
+    Ok(())
+}
+
+#[rstest]
+#[case::override_name("/tmp/source.parquet", Some("override"), "override")]
+#[case::stem_with_extension("/tmp/source.parquet", None, "source")]
+#[case::stem_without_extension("/tmp/source", None, "source")]
+#[case::missing_stem("", None, "data_source")]
+#[case::stdin("-", None, "stdin")]
+#[case::compressed("/tmp/logs.txt.gz", None, "logs")]
+fn derive_data_source_name_selects_expected_name(
+    #[case] raw_path: &str,
+    #[case] override_name: Option<&'static str>,
+    #[case] expected: &str,
+) {
+    let path = Path::new(raw_path);
+    let name = derive_data_source_name(path, override_name);
+    assert_eq!(name, expected);
+}
+
+#[rstest]
+fn open_text_reader_records_path_on_error() -> TestResult {
+    let dir = temp_dir();
+    let missing_path = dir.path().join("missing.txt");
+    let layer = RecordingLayer::default();
+    let subscriber = tracing_subscriber::registry().with(layer.clone());
+
+    let command = RunCommand {
+        min_cluster_size: Some(1),
+        source: Some(RunSource::Text(TextArgs {
+            path: missing_path.clone(),
+            metric: TextMetric::Levenshtein,
+            name: None,
+        })),
+        ..RunCommand::default()
+    };
+
+    let err = tracing::subscriber::with_default(subscriber, || run_command(command))
+        .expect_err("missing file must fail");
+    assert!(matches!(err, CliError::Io { .. }));
+
+    let spans = layer.spans();
+    let reader_span = spans
+        .iter()
+        .find(|span| span.name == "cli.open_text_reader")
+        .expect("reader span must exist");
+    assert!(
+        reader_span
+            .fields
+            .get("path")
+            .is_some_and(|value| value == "missing.txt")
+    );
+
+    let run_span = spans
+        .iter()
+        .find(|span| span.name == "cli.run_text")
+        .expect("run_text span must exist");
+    assert_eq!(
+        run_span.fields.get("override_name"),
+        Some(&"<derived>".to_owned())
+    );
+    Ok(())
+}
@@ -0,0 +1,173 @@
+//! Tests for `chutoro inspect`, the pre-flight summary of Parquet inputs.
+
+use std::fs::File;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use super::super::{
+    Cli, CliError, Command, HnswArgs, InspectCommand, OutputArgs, inspect_parquet, render_inspect,
+    run_cli, run_inspect,
+};
+
+use arrow_array::{ArrayRef, Float32Array, Float64Array, RecordBatch};
+use arrow_schema::{DataType, Field, Schema};
+use chutoro_providers_dense::DenseMatrixProviderError;
+use clap::Parser;
+use parquet::arrow::arrow_writer::ArrowWriter;
+use rstest::rstest;
+use tempfile::TempDir;
+
+use super::fixtures::create_parquet_file;
+use super::helpers::temp_dir;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+fn inspect_command(parquet: PathBuf, columns: &[&str]) -> InspectCommand {
+    InspectCommand {
+        parquet,
+        columns: columns.iter().map(|&column| column.to_owned()).collect(),
+        max_bytes: None,
+        hnsw: HnswArgs::default(),
+        output: OutputArgs::default(),
+    }
+}
+
+/// Writes nullable `price` (Float64) and `rating` (Float32) scalar columns
+/// with a null row, a NaN, and an infinity.
+fn create_nullable_file(dir: &TempDir) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let path = dir.path().join("nullable.parquet");
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("price", DataType::Float64, true),
+        Field::new("rating", DataType::Float32, true),
+    ]));
+    let price = Float64Array::from(vec![Some(-2.5), None, Some(f64::NAN), Some(10.0)]);
+    let rating = Float32Array::from(vec![Some(1.0), Some(f32::INFINITY), Some(4.0), Some(2.0)]);
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![Arc::new(price) as ArrayRef, Arc::new(rating)],
+    )?;
+    let mut writer = ArrowWriter::try_new(File::create(&path)?, schema, None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(path)
+}
+
+#[rstest]
+fn reports_shape_range_and_metric() -> TestResult {
+    let dir = temp_dir();
+    let path = create_parquet_file(&dir, "vectors.parquet")?;
+
+    let report = inspect_parquet(&inspect_command(path, &["features"]))?;
+
+    assert_eq!(report.file, "vectors.parquet");
+    assert_eq!((report.rows, report.dimension), (4, 2));
+    assert_eq!(report.metric, "euclidean");
+    let [column] = report.columns.as_slice() else {
+        panic!("one column was inspected");
+    };
+    assert_eq!(column.dimension, 2);
+    assert_eq!((column.null_rows, column.null_values), (0, 0));
+    assert_eq!((column.min, column.max), (Some(0.0), Some(3.0)));
+    assert_eq!(report.estimate.source_bytes, Some(32));
+    assert!(report.estimate.peak_bytes >= 32);
+    assert_eq!(report.estimate.fits, None);
+    Ok(())
+}
+
+#[rstest]
+fn counts_nulls_and_non_finite_values() -> TestResult {
+    let dir = temp_dir();
+    let path = create_nullable_file(&dir)?;
+
+    let report = inspect_parquet(&inspect_command(path, &["price", "rating"]))?;
+
+    assert_eq!((report.rows, report.dimension), (4, 2));
+    let [price, rating] = report.columns.as_slice() else {
+        panic!("two columns were inspected");
+    };
+    assert_eq!(price.data_type, "Float64");
+    assert_eq!((price.null_rows, price.non_finite_values), (1, 1));
+    assert_eq!((price.min, price.max), (Some(-2.5), Some(10.0)));
+    assert_eq!((rating.null_rows, rating.non_finite_values), (0, 1));
+    assert_eq!((rating.min, rating.max), (Some(1.0), Some(4.0)));
+    Ok(())
+}
+
+#[rstest]
+#[case::missing("absent")]
+#[case::not_float("id")]
+fn rejects_unusable_columns(#[case] column: &str) -> TestResult {
+    let dir = temp_dir();
+    let path = create_parquet_file(&dir, "vectors.parquet")?;
+
+    let err =
+        inspect_parquet(&inspect_command(path, &[column])).expect_err("column must be rejected");
+
+    assert!(matches!(
+        err,
+        CliError::Dense(
+            DenseMatrixProviderError::ColumnNotFound { .. }
+                | DenseMatrixProviderError::InvalidColumnType { .. }
+        )
+    ));
+    Ok(())
+}
+
+#[rstest]
+fn text_report_flags_an_exceeded_limit() -> TestResult {
+    let dir = temp_dir();
+    let path = create_parquet_file(&dir, "vectors.parquet")?;
+    let command = InspectCommand {
+        max_bytes: Some(1),
+        ..inspect_command(path, &["features"])
+    };
+
+    let report = inspect_parquet(&command)?;
+    let mut buffer = Vec::new();
+    render_inspect(&report, &mut buffer)?;
+
+    assert_eq!(report.estimate.fits, Some(false));
+    let text = String::from_utf8(buffer)?;
+    assert!(text.starts_with("file: vectors.parquet\nrows: 4\ndimension: 2\n"));
+    assert!(text.contains("column features: "));
+    assert!(text.contains("range=[0, 3]"));
+    assert!(text.ends_with("max_bytes: 1 B (exceeds)\n"), "{text}");
+    Ok(())
+}
+
+#[rstest]
+fn parses_and_renders_json() -> TestResult {
+    let dir = temp_dir();
+    let path = create_parquet_file(&dir, "vectors.parquet")?;
+    let cli = Cli::try_parse_from([
+        "chutoro",
+        "inspect",
+        "--parquet",
+        path.to_str().ok_or("temp path is UTF-8")?,
+        "--column",
+        "features",
+        "--json",
+    ])?;
+    let Command::Inspect(command) = cli.command else {
+        panic!("expected the inspect command");
+    };
+
+    let mut buffer = Vec::new();
+    run_inspect(&command, &mut buffer)?;
+
+    let document: serde_json::Value = serde_json::from_slice(&buffer)?;
+    assert_eq!(document["rows"], 4);
+    assert_eq!(document["columns"][0]["name"], "features");
+    assert!(document["estimate"]["peak_bytes"].as_u64().is_some());
+    Ok(())
+}
+
+#[rstest]
+fn inspect_is_not_a_run() -> TestResult {
+    let cli = Cli::try_parse_from(["chutoro", "inspect", "--parquet", "x", "--column", "f"])?;
+
+    let err = run_cli(cli).expect_err("inspect does not run the pipeline");
+
+    assert!(matches!(err, CliError::NotARun { command: "inspect" }));
+    Ok(())
+}
@@ -0,0 +1,208 @@
+//! Tests for the JSON run and failure summaries.
+
+use super::super::commands::run_command;
+use super::super::{
+    Cli, CliError, Command, ExecutionSummary, RunCommand, render_failure_json, render_summary_json,
+};
+
+use chutoro_core::{ChutoroError, ClusterId, ClusteringResult};
+use clap::Parser;
+use rstest::rstest;
+use serde_json::{Value, json};
+
+use super::helpers::{create_text_file, result_with_warning, temp_dir, text_command};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+fn parse(args: &[&str]) -> RunCommand {
+    match Cli::try_parse_from(args) {
+        Ok(Cli {
+            command: Command::Run(run),
+        }) => run,
+        Ok(other) => panic!("expected a run command, got {other:?}"),
+        Err(err) => panic!("arguments must parse: {err}"),
+    }
+}
+
+fn text_run() -> RunCommand {
+    parse(&[
+        "chutoro",
+        "run",
+        "--format",
+        "json",
+        "--min-cluster-size",
+        "2",
+        "text",
+        "data.txt",
+        "--metric",
+        "levenshtein",
+    ])
+}
+
+#[rstest]
+fn success_document_reports_summary_and_parameters() -> TestResult {
+    let summary = ExecutionSummary {
+        data_source: "demo".into(),
+        result: ClusteringResult::from_assignments(vec![ClusterId::new(0), ClusterId::new(1)]),
+        quality: None,
+        clusters: None,
+    };
+    let mut buffer = Vec::new();
+    render_summary_json(&summary, &text_run(), &mut buffer)?;
+    let document: Value = serde_json::from_slice(&buffer)?;
+    assert_eq!(
+        document,
+        json!({
+            "status": "ok",
+            "data_source": "demo",
+            "points": 2,
+            "clusters": 2,
+            "noise_fraction": 0.0,
+            "timings": null,
+            "warnings": [],
+            "quality": null,
+            "cluster_details": null,
+            "parameters": {
+                "command": "run",
+                "config": null,
+                "min_cluster_size": 2,
+                "max_bytes": null,
+                "hnsw": {"max_connections": 16, "ef_construction": 64},
+                "source": "text",
+                "path": "data.txt",
+                "columns": null,
+                "metric": "levenshtein",
+                "feature": null,
+                "pixel_side": null,
+                "name": null,
+                "lossy_f64": null,
+                "id_column": null,
+                "label_column": null,
+                "output": null,
+            },
+            "assignments": [0, 1],
+        })
+    );
+    Ok(())
+}
+
+#[rstest]
+fn success_document_lists_warnings() -> TestResult {
+    let summary = ExecutionSummary {
+        data_source: "demo".into(),
+        result: result_with_warning(),
+        quality: None,
+        clusters: None,
+    };
+    let mut buffer = Vec::new();
+    render_summary_json(&summary, &text_run(), &mut buffer)?;
+    let document: Value = serde_json::from_slice(&buffer)?;
+    assert_eq!(
+        document["warnings"],
+        json!([{
+            "code": "CHUTORO_WARN_DISCONNECTED_COMPONENTS",
+            "message": "the spanning forest has 2 components; clusters never span them",
+        }])
+    );
+    Ok(())
+}
+
+#[rstest]
+fn success_document_includes_pipeline_timings() -> TestResult {
+    let dir = temp_dir();
+    let path = create_text_file(&dir, "lines.txt", "alpha\nbeta\ngamma\n")?;
+    let command = text_command(path, 2, Some("lines"));
+    let summary = run_command(command.clone())?;
+    let mut buffer = Vec::new();
+    render_summary_json(&summary, &command, &mut buffer)?;
+    let document: Value = serde_json::from_slice(&buffer)?;
+
+    assert_eq!(document["data_source"], "lines");
+    assert_eq!(document["points"], 3);
+    assert_eq!(document["parameters"]["name"], "lines");
+    let noise = document["noise_fraction"].as_f64().expect("numeric noise");
+    assert!((0.0..=1.0).contains(&noise));
+    for key in [
+        "hnsw_build_ms",
+        "edge_harvest_ms",
+        "mst_ms",
+        "hierarchy_ms",
+        "total_ms",
+    ] {
+        let value = document["timings"][key].as_f64().expect("numeric timing");
+        assert!(value >= 0.0, "{key} must be non-negative");
+    }
+    Ok(())
+}
+
+#[rstest]
+fn failure_document_reports_core_error_codes() -> TestResult {
+    let command = parse(&[
+        "chutoro",
+        "run",
+        "--format",
+        "json",
+        "--max-bytes",
+        "1K",
+        "parquet",
+        "data.parquet",
+        "--columns",
+        "features,weight",
+    ]);
+    let error = CliError::Core(ChutoroError::InvalidMinClusterSize { got: 0 });
+    let mut buffer = Vec::new();
+    render_failure_json(&error, &command, &mut buffer)?;
+    let document: Value = serde_json::from_slice(&buffer)?;
+
+    assert_eq!(document["status"], "error");
+    assert_eq!(
+        document["error"]["code"],
+        "CHUTORO_INVALID_MIN_CLUSTER_SIZE"
+    );
+    assert_eq!(document["error"]["data_source_code"], Value::Null);
+    assert_eq!(document["error"]["message"], error.to_string());
+    assert_eq!(
+        document["error"]["hint"],
+        "--min-cluster-size must be at least 1"
+    );
+    assert_eq!(document["error"]["exit_code"], 3);
+    assert_eq!(document["parameters"]["source"], "parquet");
+    assert_eq!(
+        document["parameters"]["columns"],
+        json!(["features", "weight"])
+    );
+    assert_eq!(document["parameters"]["max_bytes"], 1024);
+    Ok(())
+}
+
+#[rstest]
+fn failure_document_omits_codes_for_io_errors() -> TestResult {
+    let error = CliError::Io {
+        path: "missing.txt".into(),
+        source: std::io::Error::from(std::io::ErrorKind::NotFound),
+    };
+    let mut buffer = Vec::new();
+    render_failure_json(&error, &text_run(), &mut buffer)?;
+    let document: Value = serde_json::from_slice(&buffer)?;
+    assert_eq!(document["error"]["code"], Value::Null);
+    assert_eq!(document["error"]["exit_code"], 4);
+    assert!(buffer.ends_with(b"\n"));
+    Ok(())
+}
+
+#[rstest]
+fn failure_document_reports_missing_source_as_null() -> TestResult {
+    let command = parse(&["chutoro", "run", "--json", "--hnsw-max-connections", "0"]);
+    let mut buffer = Vec::new();
+    render_failure_json(&CliError::MissingSource, &command, &mut buffer)?;
+    let document: Value = serde_json::from_slice(&buffer)?;
+    let parameters = &document["parameters"];
+    assert_eq!(parameters["source"], Value::Null);
+    assert_eq!(parameters["path"], Value::Null);
+    assert_eq!(parameters["min_cluster_size"], 5);
+    assert_eq!(
+        parameters["hnsw"],
+        json!({"max_connections": 0, "ef_construction": null})
+    );
+    Ok(())
+}
@@ -0,0 +1,147 @@
+//! Tests for scoring Parquet runs against a `--label-column`.
+
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::super::commands::run_command;
+use super::super::{
+    Cli, CliError, Command, ParquetArgs, RunCommand, RunSource, render_summary, render_summary_json,
+};
+
+use arrow_array::{ArrayRef, Float32Array, Int64Array, RecordBatch, StringArray};
+use arrow_schema::DataType;
+use clap::Parser;
+use parquet::arrow::arrow_writer::ArrowWriter;
+use rstest::rstest;
+use serde_json::Value;
+
+use super::helpers::temp_dir;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+/// Writes two well-separated groups of three points with a `truth` column
+/// naming each group, an integer `parity` column that cuts across them, and a
+/// float `score` column.
+fn labelled_parquet(dir: &Path) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let path = dir.join("labelled.parquet");
+    let x: ArrayRef = Arc::new(Float32Array::from(vec![0.0, 0.1, 0.2, 10.0, 10.1, 10.2]));
+    let truth: ArrayRef = Arc::new(StringArray::from(vec!["a", "a", "a", "b", "b", "b"]));
+    let parity: ArrayRef = Arc::new(Int64Array::from(vec![0, 1, 0, 1, 0, 1]));
+    let score: ArrayRef = Arc::new(Float32Array::from(vec![0.5; 6]));
+    let batch = RecordBatch::try_from_iter([
+        ("x", x),
+        ("truth", truth),
+        ("parity", parity),
+        ("score", score),
+    ])?;
+    let mut writer = ArrowWriter::try_new(File::create(&path)?, batch.schema(), None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(path)
+}
+
+fn labelled_command(path: PathBuf, label_column: &str) -> RunCommand {
+    RunCommand {
+        min_cluster_size: Some(2),
+        source: Some(RunSource::Parquet(ParquetArgs {
+            path,
+            columns: vec!["x".into()],
+            name: None,
+            lossy_f64: false,
+            id_column: None,
+            label_column: Some(label_column.into()),
+            output: None,
+        })),
+        ..RunCommand::default()
+    }
+}
+
+#[rstest]
+fn matching_labels_score_perfectly() -> TestResult {
+    let dir = temp_dir();
+    let path = labelled_parquet(dir.path())?;
+    let command = labelled_command(path, "truth");
+    let summary = run_command(command.clone())?;
+
+    let score = summary.quality.expect("a label column was given");
+    assert!((score.ari - 1.0).abs() < 1e-9);
+    assert!((score.nmi - 1.0).abs() < 1e-9);
+
+    let mut text = Vec::new();
+    render_summary(&summary, &mut text)?;
+    assert!(String::from_utf8(text)?.contains("quality: ari=1.0000 nmi=1.0000"));
+    let mut json = Vec::new();
+    render_summary_json(&summary, &command, &mut json)?;
+    let document: Value = serde_json::from_slice(&json)?;
+    assert_eq!(document["quality"]["ari"], 1.0);
+    assert_eq!(document["parameters"]["label_column"], "truth");
+    Ok(())
+}
+
+#[rstest]
+fn integer_labels_that_cut_across_clusters_score_poorly() -> TestResult {
+    let dir = temp_dir();
+    let path = labelled_parquet(dir.path())?;
+    let summary = run_command(labelled_command(path, "parity"))?;
+
+    let score = summary.quality.expect("a label column was given");
+    assert!(score.ari < 0.5);
+    assert!(score.nmi < 0.5);
+    Ok(())
+}
+
+#[rstest]
+fn missing_label_column_is_reported() -> TestResult {
+    let dir = temp_dir();
+    let path = labelled_parquet(dir.path())?;
+    let err = run_command(labelled_command(path, "ground_truth"))
+        .expect_err("unknown label column must fail");
+    assert!(
+        matches!(&err, CliError::LabelColumnNotFound { column, available }
+            if column == "ground_truth" && available == &["x", "truth", "parity", "score"])
+    );
+    assert_eq!(
+        err.hint().as_deref(),
+        Some("available columns are: x, truth, parity, score")
+    );
+    Ok(())
+}
+
+#[rstest]
+fn float_label_column_is_rejected() -> TestResult {
+    let dir = temp_dir();
+    let path = labelled_parquet(dir.path())?;
+    let err =
+        run_command(labelled_command(path, "score")).expect_err("float labels must be rejected");
+    assert!(matches!(
+        err,
+        CliError::UnsupportedLabelColumn { column, data_type: DataType::Float32 }
+            if column == "score"
+    ));
+    Ok(())
+}
+
+#[rstest]
+fn clap_accepts_label_column_without_output() {
+    let args = [
+        "chutoro",
+        "run",
+        "parquet",
+        "v.parquet",
+        "--column",
+        "features",
+        "--label-column",
+        "ground_truth",
+    ];
+    match Cli::try_parse_from(args) {
+        Ok(Cli {
+            command:
+                Command::Run(RunCommand {
+                    source: Some(RunSource::Parquet(parquet)),
+                    ..
+                }),
+        }) => assert_eq!(parquet.label_column.as_deref(), Some("ground_truth")),
+        other => panic!("expected a parquet run, got {other:?}"),
+    }
+}
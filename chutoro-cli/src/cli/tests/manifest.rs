@@ -0,0 +1,136 @@
+//! Tests for writing, loading, and replaying run manifests.
+
+use super::super::commands::run_command;
+use super::super::{
+    Cli, CliError, Command, MANIFEST_VERSION, ManifestSource, RunCommand, RunManifest, TextMetric,
+};
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::Parser;
+use rstest::rstest;
+use tempfile::TempDir;
+
+use super::helpers::{create_text_file, temp_dir, text_command};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const WORDS: &str = "alpha\nalpine\nalpaca\nbeta\nbetamax\nbetting\n";
+
+/// Runs a text command over [`WORDS`] that records a manifest, returning the
+/// command and the manifest path.
+fn recorded_run(dir: &TempDir) -> Result<(RunCommand, PathBuf), Box<dyn std::error::Error>> {
+    let data = create_text_file(dir, "words.txt", WORDS)?;
+    let manifest = dir.path().join("run.json");
+    let mut command = text_command(data, 2, None);
+    command.hnsw.seed = Some(11);
+    command.manifest = Some(manifest.clone());
+    Ok((command, manifest))
+}
+
+#[rstest]
+fn manifest_records_the_resolved_run() -> TestResult {
+    let dir = temp_dir();
+    let (command, path) = recorded_run(&dir)?;
+    let summary = run_command(command)?;
+
+    let manifest = RunManifest::load(&path)?;
+
+    assert_eq!(manifest.version, MANIFEST_VERSION);
+    assert_eq!(manifest.chutoro_version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(manifest.parameters.min_cluster_size, 2);
+    assert_eq!(manifest.parameters.hnsw.max_connections, 16);
+    assert_eq!(manifest.parameters.hnsw.seed, 11);
+    assert!(matches!(
+        manifest.source,
+        ManifestSource::Text {
+            metric: TextMetric::Levenshtein,
+            ..
+        }
+    ));
+    assert_eq!(manifest.dataset.name, "words");
+    assert!(
+        manifest
+            .dataset
+            .hash
+            .as_deref()
+            .is_some_and(|hash| hash.starts_with("fnv1a64:"))
+    );
+    assert!(manifest.timings.is_some());
+    assert_eq!(manifest.result.points, 6);
+    assert_eq!(manifest.result.clusters, summary.result.cluster_count());
+    Ok(())
+}
+
+#[rstest]
+fn replay_reproduces_the_recorded_assignments() -> TestResult {
+    let dir = temp_dir();
+    let (command, path) = recorded_run(&dir)?;
+    let original = run_command(command)?;
+
+    let replayed = RunManifest::load(&path)?.replay()?;
+
+    assert_eq!(replayed.data_source, original.data_source);
+    assert_eq!(replayed.result.assignments(), original.result.assignments());
+    Ok(())
+}
+
+#[rstest]
+fn replay_rejects_a_changed_dataset() -> TestResult {
+    let dir = temp_dir();
+    let (command, path) = recorded_run(&dir)?;
+    run_command(command)?;
+    let manifest = RunManifest::load(&path)?;
+    fs::write(manifest.source.path(), "gamma\ngammon\ndelta\n")?;
+
+    let err = manifest
+        .replay()
+        .expect_err("a changed dataset must be rejected");
+
+    assert!(matches!(err, CliError::DatasetMismatch { .. }));
+    Ok(())
+}
+
+#[rstest]
+#[case::unsupported_version(r#"{"version": 99}"#, "unsupported version 99")]
+#[case::malformed("{", "failed to parse manifest")]
+fn load_rejects_unreadable_manifests(#[case] contents: &str, #[case] message: &str) -> TestResult {
+    let dir = temp_dir();
+    let path = create_text_file(&dir, "run.json", contents)?;
+
+    let err = RunManifest::load(&path).expect_err("manifest must be rejected");
+
+    assert!(err.to_string().contains(message), "unexpected error: {err}");
+    Ok(())
+}
+
+#[rstest]
+fn manifest_and_seed_flags_parse() {
+    let cli = Cli::try_parse_from([
+        "chutoro",
+        "run",
+        "--manifest",
+        "run.json",
+        "--hnsw-seed",
+        "42",
+        "text",
+        "lines.txt",
+        "--metric",
+        "levenshtein",
+    ])
+    .expect("arguments must parse");
+
+    let Command::Run(run) = cli.command else {
+        panic!("expected a run command");
+    };
+    assert_eq!(run.manifest.as_deref(), Some(Path::new("run.json")));
+    assert_eq!(run.hnsw.seed, Some(42));
+    assert_eq!(
+        run.hnsw
+            .to_params()
+            .expect("parameters are valid")
+            .rng_seed(),
+        42
+    );
+}
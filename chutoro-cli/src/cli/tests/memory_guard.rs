@@ -1,13 +1,14 @@
 //! Tests for the `--max-bytes` memory guard and `parse_byte_size` parser.
 
-use super::super::commands::{parse_byte_size, run_command};
+use super::super::commands::run_command;
+use super::super::config::parse_byte_size;
 use super::super::{Cli, CliError, Command, RunCommand, RunSource, TextArgs, TextMetric};
 
 use chutoro_core::ChutoroError;
 use clap::Parser;
 use rstest::rstest;
 
-use super::test_helpers::{create_text_file, run_command_expecting_error, temp_dir};
+use super::helpers::{create_text_file, run_command_expecting_error, temp_dir};
 
 type TestResult = Result<(), Box<dyn std::error::Error>>;
 
@@ -69,13 +70,14 @@ fn run_command_rejects_when_max_bytes_exceeded() -> TestResult {
     // A limit of 100 bytes is far too small for any real pipeline run.
     let err = run_command_expecting_error(
         RunCommand {
-            min_cluster_size: 1,
+            min_cluster_size: Some(1),
             max_bytes: Some(100),
-            source: RunSource::Text(TextArgs {
+            source: Some(RunSource::Text(TextArgs {
                 path,
                 metric: TextMetric::Levenshtein,
                 name: None,
-            }),
+            })),
+            ..RunCommand::default()
         },
         "100-byte limit must be exceeded",
     );
@@ -96,13 +98,14 @@ fn run_command_succeeds_when_max_bytes_sufficient() -> TestResult {
 
     // 1 GiB should be more than enough for 3 items.
     let summary = run_command(RunCommand {
-        min_cluster_size: 1,
+        min_cluster_size: Some(1),
         max_bytes: Some(1_073_741_824),
-        source: RunSource::Text(TextArgs {
+        source: Some(RunSource::Text(TextArgs {
             path,
             metric: TextMetric::Levenshtein,
             name: None,
-        }),
+        })),
+        ..RunCommand::default()
     })?;
     assert_eq!(summary.result.assignments().len(), 3);
     Ok(())
@@ -114,13 +117,14 @@ fn run_command_zero_max_bytes_rejects_any_dataset() -> TestResult {
     let path = create_text_file(&dir, "lines.txt", "alpha\nbeta\n")?;
     let err = run_command_expecting_error(
         RunCommand {
-            min_cluster_size: 1,
+            min_cluster_size: Some(1),
             max_bytes: Some(0),
-            source: RunSource::Text(TextArgs {
+            source: Some(RunSource::Text(TextArgs {
                 path,
                 metric: TextMetric::Levenshtein,
                 name: None,
-            }),
+            })),
+            ..RunCommand::default()
         },
         "zero max_bytes must reject any dataset",
     );
@@ -148,6 +152,7 @@ fn clap_parses_max_bytes_flag() {
         Command::Run(cmd) => {
             assert_eq!(cmd.max_bytes, Some(2 * 1024 * 1024 * 1024));
         }
+        other => panic!("expected a run command, got {other:?}"),
     }
 }
 
@@ -166,5 +171,6 @@ fn clap_omits_max_bytes_when_absent() {
         Command::Run(cmd) => {
             assert_eq!(cmd.max_bytes, None);
         }
+        other => panic!("expected a run command, got {other:?}"),
     }
 }
@@ -0,0 +1,21 @@
+//! Unit tests for the CLI commands and data ingestion helpers, grouped by
+//! command.
+
+mod batch;
+mod config;
+mod details;
+mod dry_run;
+mod failure;
+mod fixtures;
+mod helpers;
+mod images;
+mod input;
+mod inspect;
+mod json;
+mod labels;
+mod manifest;
+mod memory_guard;
+mod parquet_output;
+mod render;
+mod run;
+mod stability;
@@ -0,0 +1,200 @@
+//! Tests for Parquet feature column selection and for writing cluster
+//! assignments to Parquet with `--output`.
+
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use super::super::commands::run_command;
+use super::super::{Cli, CliError, Command, ParquetArgs, RunCommand, RunSource};
+
+use arrow_array::{Array, Float32Array, RecordBatch, StringArray, UInt64Array};
+use arrow_schema::DataType;
+use clap::Parser;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use rstest::rstest;
+
+use super::fixtures::create_parquet_file;
+use super::helpers::temp_dir;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+fn parquet_command(path: PathBuf, id_column: Option<&str>, output: &Path) -> RunCommand {
+    RunCommand {
+        min_cluster_size: Some(2),
+        source: Some(RunSource::Parquet(ParquetArgs {
+            path,
+            columns: vec!["features".into()],
+            name: None,
+            lossy_f64: false,
+            id_column: id_column.map(ToOwned::to_owned),
+            label_column: None,
+            output: Some(output.to_path_buf()),
+        })),
+        ..RunCommand::default()
+    }
+}
+
+fn read_single_batch(path: &Path) -> Result<RecordBatch, Box<dyn std::error::Error>> {
+    let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(path)?)?.build()?;
+    let mut batches = reader.collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(batches.len(), 1, "small outputs fit in one batch");
+    Ok(batches.remove(0))
+}
+
+fn column<'a, T: 'static>(batch: &'a RecordBatch, name: &str) -> &'a T {
+    batch
+        .column_by_name(name)
+        .unwrap_or_else(|| panic!("missing column {name}"))
+        .as_any()
+        .downcast_ref::<T>()
+        .unwrap_or_else(|| panic!("unexpected type for {name}"))
+}
+
+#[rstest]
+fn output_joins_assignments_with_id_column() -> TestResult {
+    let dir = temp_dir();
+    let input = create_parquet_file(&dir, "vectors.parquet")?;
+    let output = dir.path().join("clusters.parquet");
+    let summary = run_command(parquet_command(input, Some("id"), &output))?;
+
+    let batch = read_single_batch(&output)?;
+    let names: Vec<_> = batch
+        .schema()
+        .fields()
+        .iter()
+        .map(|field| field.name().clone())
+        .collect();
+    assert_eq!(names, ["id", "cluster_id", "probability", "outlier_score"]);
+
+    let ids = column::<StringArray>(&batch, "id");
+    assert_eq!(
+        ids.iter().collect::<Vec<_>>(),
+        [Some("p0"), Some("p1"), Some("p2"), Some("p3")]
+    );
+
+    let cluster_ids = column::<UInt64Array>(&batch, "cluster_id");
+    let noise = summary.result.noise_label();
+    for (row, assignment) in summary.result.assignments().iter().enumerate() {
+        if Some(*assignment) == noise {
+            assert!(cluster_ids.is_null(row), "noise rows have no cluster");
+        } else {
+            assert_eq!(cluster_ids.value(row), assignment.get());
+        }
+    }
+    for name in ["probability", "outlier_score"] {
+        let scores = column::<Float32Array>(&batch, name);
+        assert_eq!(scores.null_count(), 0);
+        assert!(
+            scores
+                .values()
+                .iter()
+                .all(|value| (0.0..=1.0).contains(value))
+        );
+    }
+    Ok(())
+}
+
+#[rstest]
+fn output_defaults_to_row_index() -> TestResult {
+    let dir = temp_dir();
+    let input = create_parquet_file(&dir, "vectors.parquet")?;
+    let output = dir.path().join("clusters.parquet");
+    run_command(parquet_command(input, None, &output))?;
+
+    let batch = read_single_batch(&output)?;
+    let field = batch.schema().field(0).clone();
+    assert_eq!(field.name(), "row");
+    assert_eq!(field.data_type(), &DataType::UInt64);
+    let rows = column::<UInt64Array>(&batch, "row");
+    assert_eq!(rows.values().to_vec(), [0, 1, 2, 3]);
+    Ok(())
+}
+
+#[rstest]
+fn missing_id_column_is_reported() -> TestResult {
+    let dir = temp_dir();
+    let input = create_parquet_file(&dir, "vectors.parquet")?;
+    let output = dir.path().join("clusters.parquet");
+    let err = run_command(parquet_command(input, Some("uuid"), &output))
+        .expect_err("unknown id column must fail");
+    assert!(
+        matches!(err, CliError::IdColumnNotFound { column, available }
+            if column == "uuid" && available == ["features", "id"])
+    );
+    Ok(())
+}
+
+#[rstest]
+fn clap_requires_output_for_id_column() {
+    let base = [
+        "chutoro",
+        "run",
+        "parquet",
+        "v.parquet",
+        "--column",
+        "features",
+    ];
+    let mut args = base.to_vec();
+    args.extend(["--id-column", "id"]);
+    assert!(Cli::try_parse_from(&args).is_err());
+    args.extend(["--output", "clusters.parquet"]);
+    assert!(Cli::try_parse_from(&args).is_ok());
+}
+
+#[rstest]
+#[case::delimited(&["--columns", "embedding,price"])]
+#[case::repeated(&["--column", "embedding", "--columns", "price"])]
+fn clap_collects_feature_columns_in_order(#[case] flags: &[&str]) {
+    let mut args = vec!["chutoro", "run", "parquet", "v.parquet"];
+    args.extend(flags);
+    match Cli::try_parse_from(&args) {
+        Ok(Cli {
+            command:
+                Command::Run(RunCommand {
+                    source: Some(RunSource::Parquet(parquet)),
+                    ..
+                }),
+        }) => assert_eq!(parquet.columns, ["embedding", "price"]),
+        other => panic!("expected a parquet run, got {other:?}"),
+    }
+    assert!(Cli::try_parse_from(["chutoro", "run", "parquet", "v.parquet"]).is_err());
+}
+
+#[rstest]
+fn clap_and_config_enable_lossy_f64() -> TestResult {
+    let parquet = |args: &[&str]| match Cli::try_parse_from(args) {
+        Ok(Cli {
+            command:
+                Command::Run(RunCommand {
+                    source: Some(RunSource::Parquet(parquet)),
+                    ..
+                }),
+        }) => parquet,
+        other => panic!("expected a parquet run, got {other:?}"),
+    };
+    let base = ["chutoro", "run", "parquet", "v.parquet", "--column", "f"];
+    assert!(!parquet(&base).lossy_f64);
+    let mut args = base.to_vec();
+    args.push("--lossy-f64");
+    assert!(parquet(&args).lossy_f64);
+
+    let dir = temp_dir();
+    let config = dir.path().join("chutoro.toml");
+    std::fs::write(
+        &config,
+        "[source]\nkind = \"parquet\"\npath = \"v.parquet\"\ncolumn = \"f\"\nlossy_f64 = true\n",
+    )?;
+    let resolved = RunCommand {
+        config: Some(config),
+        ..RunCommand::default()
+    }
+    .resolve()?;
+    assert!(matches!(
+        resolved.source,
+        Some(RunSource::Parquet(ParquetArgs {
+            lossy_f64: true,
+            ..
+        }))
+    ));
+    Ok(())
+}
@@ -0,0 +1,112 @@
+//! Tests for the text summary renderer and output format selection.
+
+use super::super::commands::run_command;
+use super::super::{Cli, Command, ExecutionSummary, SummaryFormat, render_summary};
+
+use chutoro_core::{ClusterId, ClusteringResult};
+use clap::Parser;
+use rstest::rstest;
+
+use super::helpers::{create_text_file, result_with_warning, temp_dir, text_command};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+#[rstest]
+fn render_summary_outputs_assignments() -> TestResult {
+    let summary = ExecutionSummary {
+        data_source: "demo".into(),
+        result: ClusteringResult::from_assignments(vec![ClusterId::new(0), ClusterId::new(1)]),
+        quality: None,
+        clusters: None,
+    };
+    let mut buffer = Vec::new();
+    render_summary(&summary, &mut buffer)?;
+    let text = String::from_utf8(buffer)?;
+    assert!(text.contains("data source: demo"));
+    assert!(text.contains("clusters: 2"));
+    assert!(text.contains("0\t0"));
+    assert!(text.contains("1\t1"));
+    assert!(!text.contains("timings:"));
+    assert!(!text.contains("warning:"));
+    Ok(())
+}
+
+#[rstest]
+fn render_summary_lists_warnings_with_their_codes() -> TestResult {
+    let summary = ExecutionSummary {
+        data_source: "demo".into(),
+        result: result_with_warning(),
+        quality: None,
+        clusters: None,
+    };
+    let mut buffer = Vec::new();
+    render_summary(&summary, &mut buffer)?;
+    let text = String::from_utf8(buffer)?;
+    assert!(
+        text.contains(
+            "\nwarning: CHUTORO_WARN_DISCONNECTED_COMPONENTS the spanning forest has 2 \
+         components; clusters never span them\n0\t"
+        ),
+        "{text}"
+    );
+    Ok(())
+}
+
+#[rstest]
+fn render_summary_includes_pipeline_timings() -> TestResult {
+    let dir = temp_dir();
+    let path = create_text_file(&dir, "lines.txt", "alpha\nbeta\ngamma\n")?;
+    let summary = run_command(text_command(path, 2, None))?;
+    let mut buffer = Vec::new();
+    render_summary(&summary, &mut buffer)?;
+    let text = String::from_utf8(buffer)?;
+    let timings = text
+        .lines()
+        .find(|line| line.starts_with("timings: "))
+        .expect("pipeline runs must report timings");
+    for stage in [
+        "hnsw_build=",
+        "edge_harvest=",
+        "mst=",
+        "hierarchy=",
+        "total=",
+    ] {
+        assert!(timings.contains(stage), "missing {stage} in {timings}");
+    }
+    Ok(())
+}
+
+#[rstest]
+#[case::default(&[], SummaryFormat::Text)]
+#[case::format_text(&["--format", "text"], SummaryFormat::Text)]
+#[case::format_json(&["--format", "json"], SummaryFormat::Json)]
+#[case::json_shorthand(&["--json"], SummaryFormat::Json)]
+fn clap_selects_summary_format(
+    #[case] flags: &[&str],
+    #[case] expected: SummaryFormat,
+) -> TestResult {
+    let mut args = vec!["chutoro", "run"];
+    args.extend_from_slice(flags);
+    args.extend(["text", "data.txt", "--metric", "levenshtein"]);
+    let Command::Run(run) = Cli::try_parse_from(args)?.command else {
+        panic!("expected a run command");
+    };
+    assert_eq!(run.output.summary_format(), expected);
+    Ok(())
+}
+
+#[rstest]
+fn clap_rejects_conflicting_format_flags() {
+    let args = [
+        "chutoro",
+        "run",
+        "--json",
+        "--format",
+        "text",
+        "text",
+        "data.txt",
+        "--metric",
+        "levenshtein",
+    ];
+    assert!(Cli::try_parse_from(args).is_err());
+}
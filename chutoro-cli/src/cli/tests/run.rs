@@ -1,14 +1,8 @@
-//! Unit tests for the CLI commands and data ingestion helpers.
-
-use super::commands::{derive_data_source_name, run_command};
-use super::{
-    Cli, CliError, Command, ExecutionSummary, ParquetArgs, RunCommand, RunSource, TextArgs,
-    TextMetric, render_summary, run_cli,
-};
+//! Tests for `chutoro run` over text and Parquet inputs.
 
 use std::path::Path;
 
-use chutoro_core::{ChutoroError, ClusteringResult};
+use chutoro_core::ChutoroError;
 use clap::Parser;
 use rstest::rstest;
 use tracing::Level;
@@ -19,13 +13,13 @@ use chutoro_test_support::tracing::RecordingLayer;
 use chutoro_providers_dense::DenseMatrixProviderError;
 use chutoro_providers_text::TextProviderError;
 
-#[path = "test_fixtures.rs"]
-mod test_fixtures;
-use test_fixtures::create_parquet_file;
-
-#[path = "test_helpers.rs"]
-mod test_helpers;
-use test_helpers::{
+use super::super::commands::run_command;
+use super::super::{
+    Cli, CliError, Command, ExecutionSummary, ParquetArgs, RunCommand, RunSource, TextArgs,
+    TextMetric, run_cli,
+};
+use super::fixtures::create_parquet_file;
+use super::helpers::{
     create_text_file, run_cli_expecting_error, run_command_expecting_error, temp_dir,
 };
 
@@ -38,13 +32,13 @@ type TestResult = Result<(), Box<dyn std::error::Error>>;
 fn run_text_once(path: &Path, min_cluster_size: usize) -> Result<ExecutionSummary, CliError> {
     let cli = Cli {
         command: Command::Run(RunCommand {
-            min_cluster_size,
-            max_bytes: None,
-            source: RunSource::Text(TextArgs {
+            min_cluster_size: Some(min_cluster_size),
+            source: Some(RunSource::Text(TextArgs {
                 path: path.to_path_buf(),
                 metric: TextMetric::Levenshtein,
                 name: None,
-            }),
+            })),
+            ..RunCommand::default()
         }),
     };
     run_cli(cli)
@@ -68,21 +62,6 @@ fn assert_text_result_summary(
     Ok(clusters)
 }
 
-#[rstest]
-#[case::override_name("/tmp/source.parquet", Some("override"), "override")]
-#[case::stem_with_extension("/tmp/source.parquet", None, "source")]
-#[case::stem_without_extension("/tmp/source", None, "source")]
-#[case::missing_stem("", None, "data_source")]
-fn derive_data_source_name_selects_expected_name(
-    #[case] raw_path: &str,
-    #[case] override_name: Option<&'static str>,
-    #[case] expected: &str,
-) {
-    let path = Path::new(raw_path);
-    let name = derive_data_source_name(path, override_name);
-    assert_eq!(name, expected);
-}
-
 #[test]
 fn run_text_success() -> TestResult {
     let dir = temp_dir();
@@ -111,13 +90,13 @@ fn run_text_rejects_insufficient_items() -> TestResult {
     let path = create_text_file(&dir, "lines.txt", "alpha\nbeta\n")?;
     let cli = Cli {
         command: Command::Run(RunCommand {
-            min_cluster_size: 3,
-            max_bytes: None,
-            source: RunSource::Text(TextArgs {
+            min_cluster_size: Some(3),
+            source: Some(RunSource::Text(TextArgs {
                 path,
                 metric: TextMetric::Levenshtein,
                 name: None,
-            }),
+            })),
+            ..RunCommand::default()
         }),
     };
     let err = run_cli_expecting_error(cli, "run must fail for insufficient items");
@@ -134,13 +113,13 @@ fn run_text_rejects_empty_files() -> TestResult {
     let path = create_text_file(&dir, "empty.txt", "")?;
     let cli = Cli {
         command: Command::Run(RunCommand {
-            min_cluster_size: 1,
-            max_bytes: None,
-            source: RunSource::Text(TextArgs {
+            min_cluster_size: Some(1),
+            source: Some(RunSource::Text(TextArgs {
                 path,
                 metric: TextMetric::Levenshtein,
                 name: None,
-            }),
+            })),
+            ..RunCommand::default()
         }),
     };
     let err = run_cli_expecting_error(cli, "empty input must fail");
@@ -154,13 +133,17 @@ fn run_parquet_success() -> TestResult {
     let path = create_parquet_file(&dir, "vectors.parquet")?;
     let cli = Cli {
         command: Command::Run(RunCommand {
-            min_cluster_size: 2,
-            max_bytes: None,
-            source: RunSource::Parquet(ParquetArgs {
+            min_cluster_size: Some(2),
+            source: Some(RunSource::Parquet(ParquetArgs {
                 path,
-                column: "features".into(),
+                columns: vec!["features".into()],
                 name: Some("parquet".into()),
-            }),
+                lossy_f64: false,
+                id_column: None,
+                label_column: None,
+                output: None,
+            })),
+            ..RunCommand::default()
         }),
     };
     let summary = run_cli(cli)?;
@@ -178,13 +161,17 @@ fn run_parquet_rejects_missing_column() -> TestResult {
     let path = create_parquet_file(&dir, "vectors.parquet")?;
     let cli = Cli {
         command: Command::Run(RunCommand {
-            min_cluster_size: 1,
-            max_bytes: None,
-            source: RunSource::Parquet(ParquetArgs {
+            min_cluster_size: Some(1),
+            source: Some(RunSource::Parquet(ParquetArgs {
                 path,
-                column: "unknown".into(),
+                columns: vec!["unknown".into()],
                 name: None,
-            }),
+                lossy_f64: false,
+                id_column: None,
+                label_column: None,
+                output: None,
+            })),
+            ..RunCommand::default()
         }),
     };
     let err = run_cli_expecting_error(cli, "unknown column must fail");
@@ -201,13 +188,13 @@ fn run_command_rejects_zero_min_cluster_size() -> TestResult {
     let path = create_text_file(&dir, "lines.txt", "alpha\nbeta\ngamma\n")?;
     let err = run_command_expecting_error(
         RunCommand {
-            min_cluster_size: 0,
-            max_bytes: None,
-            source: RunSource::Text(TextArgs {
+            min_cluster_size: Some(0),
+            source: Some(RunSource::Text(TextArgs {
                 path,
                 metric: TextMetric::Levenshtein,
                 name: None,
-            }),
+            })),
+            ..RunCommand::default()
         },
         "zero min-cluster-size must fail",
     );
@@ -218,25 +205,6 @@ fn run_command_rejects_zero_min_cluster_size() -> TestResult {
     Ok(())
 }
 
-#[rstest]
-fn render_summary_outputs_assignments() -> TestResult {
-    let summary = ExecutionSummary {
-        data_source: "demo".into(),
-        result: ClusteringResult::from_assignments(vec![
-            chutoro_core::ClusterId::new(0),
-            chutoro_core::ClusterId::new(1),
-        ]),
-    };
-    let mut buffer = Vec::new();
-    render_summary(&summary, &mut buffer)?;
-    let text = String::from_utf8(buffer)?;
-    assert!(text.contains("data source: demo"));
-    assert!(text.contains("clusters: 2"));
-    assert!(text.contains("0\t0"));
-    assert!(text.contains("1\t1"));
-    Ok(())
-}
-
 #[rstest]
 fn clap_rejects_unknown_metric() {
     let args = [
@@ -259,13 +227,13 @@ fn run_command_emits_tracing_fields() -> TestResult {
     let subscriber = tracing_subscriber::registry().with(layer.clone());
 
     let command = RunCommand {
-        min_cluster_size: 2,
-        max_bytes: None,
-        source: RunSource::Text(TextArgs {
+        min_cluster_size: Some(2),
+        source: Some(RunSource::Text(TextArgs {
             path,
             metric: TextMetric::Levenshtein,
             name: None,
-        }),
+        })),
+        ..RunCommand::default()
     };
 
     let summary = tracing::subscriber::with_default(subscriber, || run_command(command))?;
@@ -325,50 +293,3 @@ fn run_command_emits_tracing_fields() -> TestResult {
     }));
     Ok(())
 }
-
-#[rstest]
-fn open_text_reader_records_path_on_error() -> TestResult {
-    let dir = temp_dir();
-    let missing_path = dir.path().join("missing.txt");
-    let layer = RecordingLayer::default();
-    let subscriber = tracing_subscriber::registry().with(layer.clone());
-
-    let command = RunCommand {
-        min_cluster_size: 1,
-        max_bytes: None,
-        source: RunSource::Text(TextArgs {
-            path: missing_path.clone(),
-            metric: TextMetric::Levenshtein,
-            name: None,
-        }),
-    };
-
-    let err = tracing::subscriber::with_default(subscriber, || run_command(command))
-        .expect_err("missing file must fail");
-    assert!(matches!(err, CliError::Io { .. }));
-
-    let spans = layer.spans();
-    let reader_span = spans
-        .iter()
-        .find(|span| span.name == "cli.open_text_reader")
-        .expect("reader span must exist");
-    assert!(
-        reader_span
-            .fields
-            .get("path")
-            .is_some_and(|value| value == "missing.txt")
-    );
-
-    let run_span = spans
-        .iter()
-        .find(|span| span.name == "cli.run_text")
-        .expect("run_text span must exist");
-    assert_eq!(
-        run_span.fields.get("override_name"),
-        Some(&"<derived>".to_owned())
-    );
-    Ok(())
-}
-
-#[path = "test_memory_guard.rs"]
-mod test_memory_guard;
@@ -0,0 +1,148 @@
+//! Tests for `chutoro stability`.
+
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+
+use super::super::{
+    Cli, CliError, Command, RunSource, StabilityCommand, render_stability, render_stability_json,
+    run_cli, stability_command,
+};
+
+use clap::Parser;
+use rstest::rstest;
+
+use super::helpers::{create_text_file, temp_dir, text_command};
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+const WORDS: &str = "alpha\nalphb\nalphc\nalphd\nbeta\nbetb\nbetc\nbetd\n";
+
+fn parse(args: &[&str]) -> StabilityCommand {
+    let cli = Cli::try_parse_from(["chutoro", "stability"].iter().chain(args))
+        .expect("arguments must parse");
+    let Command::Stability(command) = cli.command else {
+        panic!("expected a stability command");
+    };
+    command
+}
+
+fn stability(path: PathBuf, runs: usize) -> StabilityCommand {
+    let run = text_command(path, 2, None);
+    StabilityCommand {
+        runs: NonZeroUsize::new(runs).expect("runs must be non-zero"),
+        first_seed: 10,
+        config: None,
+        min_cluster_size: run.min_cluster_size,
+        max_bytes: None,
+        hnsw: run.hnsw,
+        output: run.output,
+        source: run.source,
+    }
+}
+
+#[rstest]
+fn defaults_compare_five_runs() {
+    let command = parse(&["text", "--metric", "levenshtein", "words.txt"]);
+
+    assert_eq!(command.seeds(), [0, 1, 2, 3, 4]);
+    assert!(matches!(command.source, Some(RunSource::Text(_))));
+}
+
+#[rstest]
+#[case::zero("0")]
+#[case::negative("-1")]
+fn run_counts_must_be_positive(#[case] runs: &str) {
+    assert!(Cli::try_parse_from(["chutoro", "stability", "--runs", runs]).is_err());
+}
+
+#[rstest]
+fn text_sources_compare_and_render() -> TestResult {
+    let dir = temp_dir();
+    let path = create_text_file(&dir, "words.txt", WORDS)?;
+
+    let summary = stability_command(&stability(path, 3))?;
+    let mut buffer = Vec::new();
+    render_stability(&summary, &mut buffer)?;
+
+    assert_eq!(summary.data_source, "words");
+    assert_eq!(summary.seeds, [10, 11, 12]);
+    assert_eq!(summary.results.len(), 3);
+    assert_eq!(summary.report.runs(), 3);
+    assert_eq!(summary.report.agreements().len(), 3);
+    let text = String::from_utf8(buffer)?;
+    assert!(text.starts_with("data source: words\nrun 0: seed=10 clusters="));
+    assert!(text.contains("\nrun 2: seed=12 "));
+    assert!(text.contains("\nagreement: mean_ari="));
+    assert!(text.contains("\npair 0-1: ari="));
+    assert!(text.contains("\npair 1-2: ari="));
+    Ok(())
+}
+
+#[rstest]
+fn json_reports_every_run_and_pair() -> TestResult {
+    let dir = temp_dir();
+    let path = create_text_file(&dir, "words.txt", WORDS)?;
+    let command = stability(path, 2);
+
+    let summary = stability_command(&command)?;
+    let mut buffer = Vec::new();
+    render_stability_json(&summary, &command.to_run(), &mut buffer)?;
+
+    let document: serde_json::Value = serde_json::from_slice(&buffer)?;
+    assert_eq!(document["status"], "ok");
+    assert_eq!(document["points"], 8);
+    assert_eq!(document["runs"][1]["seed"], 11);
+    assert_eq!(document["pairs"][0]["left"], 0);
+    assert_eq!(document["pairs"][0]["right"], 1);
+    assert!(document["mean_ari"].is_f64());
+    assert_eq!(
+        document["clusters"].as_array().map(Vec::len),
+        Some(summary.report.clusters().len())
+    );
+    assert_eq!(document["parameters"]["source"], "text");
+    Ok(())
+}
+
+#[rstest]
+fn a_single_run_has_no_agreement() -> TestResult {
+    let dir = temp_dir();
+    let path = create_text_file(&dir, "words.txt", WORDS)?;
+    let command = stability(path, 1);
+
+    let summary = stability_command(&command)?;
+    let mut text = Vec::new();
+    render_stability(&summary, &mut text)?;
+    let mut json = Vec::new();
+    render_stability_json(&summary, &command.to_run(), &mut json)?;
+
+    assert!(String::from_utf8(text)?.contains("agreement: mean_ari=n/a min_ari=n/a"));
+    let document: serde_json::Value = serde_json::from_slice(&json)?;
+    assert!(document["mean_ari"].is_null());
+    assert_eq!(document["pairs"], serde_json::json!([]));
+    Ok(())
+}
+
+#[rstest]
+fn a_source_is_required() {
+    let command = parse(&[]);
+
+    let err = stability_command(&command).expect_err("no source must fail");
+
+    assert!(matches!(err, CliError::MissingSource));
+}
+
+#[rstest]
+fn stability_is_not_a_run() {
+    let cli = Cli {
+        command: Command::Stability(parse(&[])),
+    };
+
+    let err = run_cli(cli).expect_err("stability must not run the pipeline");
+
+    assert!(matches!(
+        err,
+        CliError::NotARun {
+            command: "stability"
+        }
+    ));
+}
@@ -1,9 +1,9 @@
 //! CLI entry point for executing the chutoro CPU clustering pipeline.
 //!
 //! Parses command-line arguments with clap, executes the clustering pipeline,
-//! renders the summary to stdout, and maps errors to
-//! appropriate exit codes. Logging is initialized eagerly so subsequent
-//! operations can emit structured diagnostics via `tracing`.
+//! configuration, inspection, stability, or batch command, renders the output to
+//! stdout, and maps errors to appropriate exit codes. Logging is initialized eagerly so
+//! subsequent operations can emit structured diagnostics via `tracing`.
 
 use std::io::{self, BufWriter, Write};
 use std::process::ExitCode;
@@ -12,55 +12,158 @@ use anyhow::{Context, Result};
 use clap::Parser;
 
 use chutoro_cli::{
-    cli::{Cli, CliError, render_summary, run_cli},
+    cli::{
+        BatchCommand, Cli, CliError, Command, ExitStatus, RunCommand, StabilityCommand,
+        SummaryFormat, batch_command, dry_run_command, render_batch, render_batch_json,
+        render_dry_run, render_dry_run_json, render_failure_json, render_stability,
+        render_stability_json, render_summary, render_summary_json, run_command, run_config,
+        run_inspect, stability_command,
+    },
     logging::{self, LoggingError},
 };
 use tracing::error;
 
-/// Parse CLI arguments, execute the command, render the summary, and flush the
-/// output stream.
-fn try_main() -> Result<()> {
+/// Parse CLI arguments, execute the command, and flush the output stream.
+///
+/// Returns the exit status of a command that completed, which is only a
+/// failure for a dry run whose checks did not pass.
+fn try_main() -> Result<ExitStatus> {
     let cli = Cli::parse();
-    let summary = run_cli(cli).context("failed to execute command")?;
     let stdout = io::stdout();
     let mut writer = BufWriter::new(stdout.lock());
-    render_summary(&summary, &mut writer).context("failed to render summary")?;
-    writer.flush().context("failed to flush output")?;
-    Ok(())
+    match cli.command {
+        Command::Run(run) => execute_run(run, &mut writer),
+        Command::Config(config) => {
+            let outcome = run_config(&config, &mut writer);
+            let flushed = writer.flush();
+            outcome.context("failed to execute config command")?;
+            flushed.context("failed to flush output")?;
+            Ok(ExitStatus::Success)
+        }
+        Command::Inspect(inspect) => {
+            let outcome = run_inspect(&inspect, &mut writer);
+            let flushed = writer.flush();
+            outcome.context("failed to execute inspect command")?;
+            flushed.context("failed to flush output")?;
+            Ok(ExitStatus::Success)
+        }
+        Command::Stability(stability) => execute_stability(&stability, &mut writer),
+        Command::Batch(batch) => execute_batch(&batch, &mut writer),
+    }
+}
+
+/// Resolve the run configuration, execute the pipeline, and render the summary.
+///
+/// JSON output is written for failures as well as successes so callers always
+/// receive a document; the failure is still propagated for logging and the
+/// exit code. The output format honours `--config` once it has loaded.
+fn execute_run(run: RunCommand, writer: &mut impl Write) -> Result<ExitStatus> {
+    let (command, outcome) = match run.clone().resolve() {
+        Ok(resolved) if resolved.dry_run => return execute_dry_run(&resolved, writer),
+        Ok(resolved) => (resolved.clone(), run_command(resolved)),
+        Err(err) => (run, Err(err)),
+    };
+
+    let rendered = match (&outcome, command.output.summary_format()) {
+        (Ok(summary), SummaryFormat::Text) => render_summary(summary, &mut *writer),
+        (Ok(summary), SummaryFormat::Json) => render_summary_json(summary, &command, &mut *writer),
+        (Err(err), SummaryFormat::Json) => render_failure_json(err, &command, &mut *writer),
+        (Err(_), SummaryFormat::Text) => Ok(()),
+    };
+    let flushed = writer.flush();
+
+    outcome.context("failed to execute command")?;
+    rendered.context("failed to render summary")?;
+    flushed.context("failed to flush output")?;
+    Ok(ExitStatus::Success)
+}
+
+/// Validate a resolved run command without clustering and render the report.
+///
+/// A report whose checks failed is rendered like any other and reported
+/// through the returned exit status rather than as an error.
+fn execute_dry_run(command: &RunCommand, writer: &mut impl Write) -> Result<ExitStatus> {
+    let outcome = dry_run_command(command);
+    let rendered = match (&outcome, command.output.summary_format()) {
+        (Ok(summary), SummaryFormat::Text) => render_dry_run(summary, &mut *writer),
+        (Ok(summary), SummaryFormat::Json) => render_dry_run_json(summary, command, &mut *writer),
+        (Err(err), SummaryFormat::Json) => render_failure_json(err, command, &mut *writer),
+        (Err(_), SummaryFormat::Text) => Ok(()),
+    };
+    let flushed = writer.flush();
+
+    let summary = outcome.context("failed to execute dry run")?;
+    rendered.context("failed to render dry run")?;
+    flushed.context("failed to flush output")?;
+    Ok(summary.exit_status())
+}
+
+/// Cluster the input under each seed and render how much the runs agree.
+///
+/// The output format and reported parameters honour `--config` when it loads;
+/// a config that fails to load is reported through the command's own error.
+fn execute_stability(command: &StabilityCommand, writer: &mut impl Write) -> Result<ExitStatus> {
+    let outcome = stability_command(command);
+    let run = command.to_run();
+    let run = run.clone().resolve().unwrap_or(run);
+    let rendered = match (&outcome, run.output.summary_format()) {
+        (Ok(summary), SummaryFormat::Text) => render_stability(summary, &mut *writer),
+        (Ok(summary), SummaryFormat::Json) => render_stability_json(summary, &run, &mut *writer),
+        (Err(err), SummaryFormat::Json) => render_failure_json(err, &run, &mut *writer),
+        (Err(_), SummaryFormat::Text) => Ok(()),
+    };
+    let flushed = writer.flush();
+
+    outcome.context("failed to execute stability command")?;
+    rendered.context("failed to render stability report")?;
+    flushed.context("failed to flush output")?;
+    Ok(ExitStatus::Success)
+}
+
+/// Run every job of the batch and render the consolidated report.
+///
+/// Failed jobs are listed in the report and reported through the returned
+/// exit status; only a jobs file that cannot be loaded is an error.
+fn execute_batch(command: &BatchCommand, writer: &mut impl Write) -> Result<ExitStatus> {
+    let summary = batch_command(command).context("failed to execute batch command")?;
+    let rendered = match command.output.summary_format() {
+        SummaryFormat::Text => render_batch(&summary, &mut *writer),
+        SummaryFormat::Json => render_batch_json(&summary, &mut *writer),
+    };
+    let flushed = writer.flush();
+
+    rendered.context("failed to render batch report")?;
+    flushed.context("failed to flush output")?;
+    Ok(summary.exit_status())
 }
 
 fn main() -> ExitCode {
     if let Err(err) = logging::init_logging() {
         report_logging_init_error(&err);
-        return ExitCode::FAILURE;
+        return ExitStatus::Failure.into();
     }
 
-    if let Err(err) = try_main() {
-        let (code, data_source_code) = err
-            .chain()
-            .find_map(|cause| {
-                // Downcast each cause so context layers do not obscure `CliError`
-                // instances that carry structured codes.
-                let cause: &(dyn std::error::Error + 'static) = cause;
-                cause
-                    .downcast_ref::<CliError>()
-                    .and_then(|cli_error| match cli_error {
-                        CliError::Core(core) => Some((Some(core.code()), core.data_source_code())),
-                        _ => None,
-                    })
-            })
-            .unwrap_or((None, None));
-
-        error!(
-            error = %err,
-            code = ?code.map(|c| c.as_str()),
-            data_source_code = ?data_source_code.map(|c| c.as_str()),
-            "command execution failed"
-        );
-        return ExitCode::FAILURE;
-    }
+    let err = match try_main() {
+        Ok(status) => return status.into(),
+        Err(err) => err,
+    };
+    // Search each cause so context layers do not obscure the `CliError`
+    // that carries the structured codes, hint, and exit status.
+    let cli_error = err.chain().find_map(|cause| {
+        let cause: &(dyn std::error::Error + 'static) = cause;
+        cause.downcast_ref::<CliError>()
+    });
+    let status = cli_error.map_or(ExitStatus::Failure, CliError::exit_status);
 
-    ExitCode::SUCCESS
+    error!(
+        error = %err,
+        code = ?cli_error.and_then(CliError::code).map(|c| c.as_str()),
+        data_source_code = ?cli_error.and_then(CliError::data_source_code).map(|c| c.as_str()),
+        hint = ?cli_error.and_then(CliError::hint),
+        exit_code = status.code(),
+        "command execution failed"
+    );
+    status.into()
 }
 
 /// Emit a fallback diagnostic to stderr when tracing initialization fails.
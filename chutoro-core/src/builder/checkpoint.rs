@@ -0,0 +1,67 @@
+//! Builder option that checkpoints pipeline stages to disk.
+//!
+//! Checkpoints trade disk space for recovery: a run that fails after its
+//! index or spanning-forest stage can be resumed with
+//! [`crate::Chutoro::resume`] instead of starting over.
+
+use std::path::{Path, PathBuf};
+
+use crate::{
+    Result,
+    checkpoint::{CheckpointOptions, validate_checkpoints},
+};
+
+use super::ChutoroBuilder;
+
+impl ChutoroBuilder {
+    /// Writes the output of the index and spanning-forest stages to
+    /// `directory` as each completes.
+    ///
+    /// The directory receives `index.bin` with the HNSW graph, `harvest.bin`
+    /// with its candidate edges, and `mst.bin` with the spanning forest and
+    /// its reports. Each run replaces the files of the previous one, and a
+    /// run that fails leaves those of its completed stages in place, so
+    /// [`crate::Chutoro::resume`] can continue from the latest of them.
+    /// Files are never removed after a successful run.
+    ///
+    /// [`Self::build`] rejects a `directory` that is not an existing
+    /// directory, and checkpoints combined with
+    /// [`Self::with_sample`] or [`Self::with_edge_provenance`], which
+    /// checkpoints do not cover.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::ChutoroBuilder;
+    ///
+    /// let directory = std::env::temp_dir();
+    /// let builder = ChutoroBuilder::new().with_checkpoint_directory(&directory);
+    /// assert_eq!(builder.checkpoint_directory(), Some(directory.as_path()));
+    /// ```
+    #[must_use]
+    pub fn with_checkpoint_directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.pipeline.checkpoint = Some(CheckpointOptions {
+            directory: directory.into(),
+            resume: false,
+        });
+        self
+    }
+
+    /// Returns the directory stage checkpoints are written to, if
+    /// configured.
+    #[must_use]
+    pub fn checkpoint_directory(&self) -> Option<&Path> {
+        self.pipeline
+            .checkpoint
+            .as_ref()
+            .map(|checkpoint| checkpoint.directory.as_path())
+    }
+
+    /// Checks that the checkpoint directory exists and that the run's stages
+    /// can be checkpointed.
+    pub(super) fn validate_checkpoint(&self) -> Result<()> {
+        match &self.pipeline.checkpoint {
+            Some(checkpoint) => validate_checkpoints(&checkpoint.directory, &self.pipeline),
+            None => Ok(()),
+        }
+    }
+}
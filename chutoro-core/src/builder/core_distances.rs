@@ -0,0 +1,69 @@
+//! Builder option that supplies core distances computed outside the pipeline.
+//!
+//! Pipelines with an exact k-NN precomputation already know each point's
+//! core distance, and the HNSW estimate can only be less accurate.
+
+use std::sync::Arc;
+
+use crate::{Result, error::ChutoroError};
+
+use super::ChutoroBuilder;
+
+impl ChutoroBuilder {
+    /// Uses `core_distances` in the mutual-reachability transform instead of
+    /// deriving each point's core distance from its HNSW neighbourhood.
+    ///
+    /// Entry `i` is the core distance of point `i`, so runs fail with
+    /// [`ChutoroError::InvalidCoreDistances`] unless the vector has one entry
+    /// per point of the data source or k-NN graph. The values replace those
+    /// of the default harvest stage and of
+    /// [`crate::Chutoro::cluster_from_knn_graph`]; a custom
+    /// [`crate::HarvestStage`] still computes its own. [`Self::build`]
+    /// rejects negative or non-finite values, and sampling, which clusters a
+    /// subset the vector does not describe.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::ChutoroBuilder;
+    ///
+    /// let builder = ChutoroBuilder::new().with_core_distances(vec![0.5, 0.5, 1.0]);
+    /// assert_eq!(builder.core_distances(), Some(&[0.5, 0.5, 1.0][..]));
+    /// ```
+    #[must_use]
+    pub fn with_core_distances(mut self, core_distances: Vec<f32>) -> Self {
+        self.pipeline.core_distances = Some(Arc::from(core_distances));
+        self
+    }
+
+    /// Returns the supplied core distances, if any.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn core_distances(&self) -> Option<&[f32]> { self.pipeline.core_distances.as_deref() }
+
+    /// Checks that supplied core distances are usable.
+    pub(super) fn validate_core_distances(&self) -> Result<()> {
+        let Some(core_distances) = &self.pipeline.core_distances else {
+            return Ok(());
+        };
+        let invalid = |reason: String| {
+            Err(ChutoroError::InvalidCoreDistances {
+                reason: Arc::from(reason),
+            })
+        };
+        if let Some((point, value)) = core_distances
+            .iter()
+            .enumerate()
+            .find(|(_, value)| !value.is_finite() || value.is_sign_negative())
+        {
+            return invalid(format!(
+                "core distance {value} of point {point} is not finite and non-negative"
+            ));
+        }
+        if self.pipeline.sample.is_some() {
+            return invalid(String::from(
+                "sampling clusters a subset that the core distances do not describe",
+            ));
+        }
+        Ok(())
+    }
+}
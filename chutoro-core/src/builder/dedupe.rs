@@ -0,0 +1,74 @@
+//! Builder option that collapses exact duplicates before clustering.
+//!
+//! Duplicates are found through [`crate::DataSource::dedupe_key`] and
+//! confirmed at distance zero; the pipeline clusters one weighted
+//! representative per group and copies its label to the rest.
+
+use std::sync::Arc;
+
+use crate::{Result, error::ChutoroError};
+
+use super::ChutoroBuilder;
+
+impl ChutoroBuilder {
+    /// Collapses exact duplicates into weighted representatives before the
+    /// index is built.
+    ///
+    /// Points whose [`crate::DataSource::dedupe_key`] values match and whose
+    /// distance is zero form one group. Only the first point of each group is
+    /// indexed and clustered, counted as many times as the group has members:
+    /// its own duplicates sit at distance zero when core distances are
+    /// computed, and it contributes the group's size to cluster sizes and
+    /// stability. The other members then take its label and membership
+    /// scores, so labels match a run over every point while the index never
+    /// links duplicates to each other. Sources that return no keys are
+    /// clustered as usual.
+    ///
+    /// The grouping pass costs one distance evaluation per duplicate, counted
+    /// against any [`Self::with_max_distance_evaluations`] budget, and its
+    /// time is reported as part of the HNSW build stage. Deduplicated results
+    /// carry no [`crate::ClusterHierarchy`]. [`Self::build`] rejects
+    /// deduplication combined with sampling, a prebuilt index, supplied core
+    /// distances, or a custom harvest or hierarchy stage, all of which index
+    /// points by their position in the full data source.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::ChutoroBuilder;
+    ///
+    /// let builder = ChutoroBuilder::new().dedupe_exact(true);
+    /// assert!(builder.deduplicates_exact());
+    /// ```
+    #[must_use]
+    pub fn dedupe_exact(mut self, enabled: bool) -> Self {
+        self.pipeline.dedupe_exact = enabled;
+        self
+    }
+
+    /// Returns whether runs collapse exact duplicates.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn deduplicates_exact(&self) -> bool { self.pipeline.dedupe_exact }
+
+    /// Checks that no other option needs every point clustered directly.
+    pub(super) fn validate_dedupe(&self) -> Result<()> {
+        if !self.pipeline.dedupe_exact {
+            return Ok(());
+        }
+        let pipeline = &self.pipeline;
+        let reason = if pipeline.sample.is_some() {
+            "deduplication cannot be combined with sampling"
+        } else if pipeline.prebuilt.is_some() {
+            "a prebuilt index holds every point and cannot be deduplicated"
+        } else if pipeline.core_distances.is_some() {
+            "supplied core distances cover every point, not the representatives"
+        } else if pipeline.stages.harvest.is_some() || pipeline.stages.hierarchy.is_some() {
+            "custom harvest and hierarchy stages do not see duplicate weights"
+        } else {
+            return Ok(());
+        };
+        Err(ChutoroError::InvalidDeduplication {
+            reason: Arc::from(reason),
+        })
+    }
+}
@@ -0,0 +1,118 @@
+//! Builder options governing how runs evaluate and weight distances.
+
+use crate::{DistancePolicy, DistancePrecision, DistanceTransform};
+
+use super::ChutoroBuilder;
+
+impl ChutoroBuilder {
+    /// Chooses how runs treat NaN or infinite distances from the data source.
+    ///
+    /// The default [`DistancePolicy::Strict`] fails the run on the first
+    /// non-finite distance. [`DistancePolicy::ClampToMax`] keeps such pairs at
+    /// the maximum distance, and [`DistancePolicy::SkipEdge`] drops them from
+    /// the candidate edges, so dirty data degrades the clustering instead of
+    /// aborting it. Replacement counts are reported via
+    /// [`crate::ClusteringResult::distance_policy`]. A prebuilt index keeps
+    /// the policy it was built with; see [`crate::HnswParams::with_distance_policy`].
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ChutoroBuilder, DistancePolicy};
+    ///
+    /// let builder = ChutoroBuilder::new().with_distance_policy(DistancePolicy::SkipEdge);
+    /// assert_eq!(builder.distance_policy(), DistancePolicy::SkipEdge);
+    /// ```
+    #[must_use]
+    pub fn with_distance_policy(mut self, policy: DistancePolicy) -> Self {
+        self.pipeline.distance_policy = policy;
+        self
+    }
+
+    /// Returns how runs treat non-finite distances.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn distance_policy(&self) -> DistancePolicy { self.pipeline.distance_policy }
+
+    /// Sets the precision of the distances that weight the spanning tree.
+    ///
+    /// The index is always built and searched with `f32` distances. Under
+    /// [`DistancePrecision::Double`] the built-in harvest stage re-evaluates
+    /// each harvested edge and each core neighbourhood with
+    /// [`crate::DataSource::distance_f64`], so near-duplicate distances that
+    /// collapse in `f32` still order the merges. kNN-graph runs and custom
+    /// harvest stages keep their own weights.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ChutoroBuilder, DistancePrecision};
+    ///
+    /// let builder = ChutoroBuilder::new().with_distance_precision(DistancePrecision::Double);
+    /// assert_eq!(builder.distance_precision(), DistancePrecision::Double);
+    /// ```
+    #[must_use]
+    pub fn with_distance_precision(mut self, precision: DistancePrecision) -> Self {
+        self.pipeline.distance_precision = precision;
+        self
+    }
+
+    /// Returns the precision of the distances that weight the spanning tree.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn distance_precision(&self) -> DistancePrecision { self.pipeline.distance_precision }
+
+    /// Applies `transform` to every distance the data source returns.
+    ///
+    /// The transform runs straight after the provider, so
+    /// [`DistanceTransform::Square`] clusters a Euclidean source by squared
+    /// Euclidean distance without a wrapper source. The transformed
+    /// [`crate::MetricDescriptor`], which names the transform and downgrades
+    /// the metric class when the triangle inequality may no longer hold, is
+    /// what the triangle check and the HNSW index see. Prebuilt indices and
+    /// [`crate::Chutoro::predict`] use the source as given.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ChutoroBuilder, DistanceTransform};
+    ///
+    /// let builder = ChutoroBuilder::new().with_distance_transform(DistanceTransform::Log1p);
+    /// assert_eq!(builder.distance_transform(), DistanceTransform::Log1p);
+    /// ```
+    #[must_use]
+    pub fn with_distance_transform(mut self, transform: DistanceTransform) -> Self {
+        self.pipeline.distance_transform = transform;
+        self
+    }
+
+    /// Returns the transform applied to source distances.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn distance_transform(&self) -> DistanceTransform { self.pipeline.distance_transform }
+
+    /// Caps how many distances a run may ask the data source to evaluate.
+    ///
+    /// For expensive metrics, such as edit distance over long strings, the
+    /// number of distance calls dominates the cost of a run. Every call the
+    /// pipeline makes is counted, and a run that would exceed `evaluations`
+    /// stops with [`crate::ChutoroError::DistanceBudgetExceeded`] instead of
+    /// finishing late. Runs report their count via
+    /// [`crate::ClusteringResult::distance_evaluations`] whether or not a
+    /// budget is set.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::ChutoroBuilder;
+    ///
+    /// let builder = ChutoroBuilder::new().with_max_distance_evaluations(1_000_000);
+    /// assert_eq!(builder.max_distance_evaluations(), Some(1_000_000));
+    /// ```
+    #[must_use]
+    pub fn with_max_distance_evaluations(mut self, evaluations: u64) -> Self {
+        self.pipeline.max_distance_evaluations = Some(evaluations);
+        self
+    }
+
+    /// Returns the configured distance-evaluation budget, if any.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn max_distance_evaluations(&self) -> Option<u64> { self.pipeline.max_distance_evaluations }
+}
@@ -0,0 +1,125 @@
+//! Builder options that shape the candidate edges handed to MST
+//! construction.
+
+use std::num::NonZeroUsize;
+
+use crate::EdgeBudget;
+
+use super::ChutoroBuilder;
+
+impl ChutoroBuilder {
+    /// Caps the candidate edges passed to MST construction.
+    ///
+    /// Dense harvests are sparsified to each node's lightest incident edges
+    /// plus the globally lightest edges up to the budget, trading exactness
+    /// for a smaller MST stage. The number of dropped edges is reported via
+    /// [`crate::ClusteringResult::sparsification`].
+    ///
+    /// # Examples
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use chutoro_core::{ChutoroBuilder, EdgeBudget};
+    ///
+    /// let budget = EdgeBudget::new(NonZeroUsize::new(10_000).expect("literal is non-zero"));
+    /// let builder = ChutoroBuilder::new().with_edge_budget(budget);
+    /// assert_eq!(builder.edge_budget(), Some(budget));
+    /// ```
+    #[must_use]
+    pub fn with_edge_budget(mut self, budget: EdgeBudget) -> Self {
+        self.pipeline.edge_budget = Some(budget);
+        self
+    }
+
+    /// Returns the configured candidate-edge budget, if any.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn edge_budget(&self) -> Option<EdgeBudget> { self.pipeline.edge_budget }
+
+    /// Keeps only harvested edges between mutual `k`-nearest candidates.
+    ///
+    /// After harvesting, an edge survives only when each endpoint ranks the
+    /// other among the `k` nearest nodes it shares an edge with (see
+    /// [`crate::EdgeHarvest::mutualise`]). Dropping one-sided links sharpens
+    /// cluster boundaries on noisy data but can disconnect the graph, so it
+    /// pairs well with [`Self::with_connect_components`]. Core distances are
+    /// still computed from the full HNSW neighbourhoods.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use chutoro_core::ChutoroBuilder;
+    ///
+    /// let k = NonZeroUsize::new(10).expect("literal is non-zero");
+    /// let builder = ChutoroBuilder::new().with_mutual_neighbours(k);
+    /// assert_eq!(builder.mutual_neighbours(), Some(k));
+    /// ```
+    #[must_use]
+    pub fn with_mutual_neighbours(mut self, k: NonZeroUsize) -> Self {
+        self.pipeline.mutual_neighbours = Some(k);
+        self
+    }
+
+    /// Returns the mutual-neighbour filter width, if configured.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn mutual_neighbours(&self) -> Option<NonZeroUsize> { self.pipeline.mutual_neighbours }
+
+    /// Enables joining disconnected components before hierarchy extraction.
+    ///
+    /// When the harvested graph is disconnected, the minimum spanning forest
+    /// has several trees and clusters never span them. With repair enabled the
+    /// pipeline evaluates distances between one representative per component
+    /// and adds the cheapest bridge edges needed to join them. The outcome is
+    /// recorded in [`crate::ClusteringResult::connectivity`] either way.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::ChutoroBuilder;
+    ///
+    /// let builder = ChutoroBuilder::new().with_connect_components(true);
+    /// assert!(builder.connect_components());
+    /// ```
+    #[must_use]
+    pub fn with_connect_components(mut self, enabled: bool) -> Self {
+        self.pipeline.connect_components = enabled;
+        self
+    }
+
+    /// Returns whether disconnected components are joined before hierarchy
+    /// extraction.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn connect_components(&self) -> bool { self.pipeline.connect_components }
+
+    /// Records where the HNSW build found each spanning-tree edge.
+    ///
+    /// The built-in index stage then notes, for every harvested edge, the
+    /// insertion that discovered it, the layer it was searching, and that
+    /// layer's construction search width. The result keeps the spanning
+    /// forest with that provenance, so
+    /// [`crate::ClusteringResult::explain_pair`] can report the path joining
+    /// two points and the density at which they separate. Recording costs one
+    /// table entry per harvested edge during the run. Runs with a prebuilt
+    /// index or a custom index stage record no provenance, and sampled or
+    /// deduplicated runs keep no forest.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::ChutoroBuilder;
+    ///
+    /// let builder = ChutoroBuilder::new().with_edge_provenance(true);
+    /// assert!(builder.edge_provenance());
+    /// ```
+    #[must_use]
+    pub fn with_edge_provenance(mut self, enabled: bool) -> Self {
+        self.pipeline.edge_provenance = enabled;
+        self
+    }
+
+    /// Returns whether runs record where each spanning-tree edge was found.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn edge_provenance(&self) -> bool { self.pipeline.edge_provenance }
+}
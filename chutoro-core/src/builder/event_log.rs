@@ -0,0 +1,63 @@
+//! Builder option that records pipeline internals to a JSON Lines file.
+//!
+//! The log is meant for offline analysis and bug reports rather than live
+//! diagnosis, which `tracing` already covers.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::{Result, event_log::EventLog};
+
+use super::{ChutoroBuilder, PipelineOptions};
+
+impl ChutoroBuilder {
+    /// Appends a JSON Lines record of each run's internals to `path`.
+    ///
+    /// Every run, including [`crate::Chutoro::cluster_from_knn_graph`],
+    /// writes one JSON object per line: a `run_started` record with the
+    /// data source and resolved parameters, a `stage_completed` record with
+    /// the elapsed time of each stage, a `stage_statistics` record with the
+    /// size of each stage's output, one `warning` record per result warning,
+    /// and a closing `run_finished` or `run_failed` record. Each record
+    /// carries an `event` name and a `unix_ms` timestamp. Records are written
+    /// as they happen, so a failed run still leaves everything up to the
+    /// failure in the file.
+    ///
+    /// [`Self::build`] creates the file if needed and opens it for
+    /// appending, so several runs, or several [`crate::Chutoro`] instances,
+    /// can share one log. Clustering sessions do not write to it. A
+    /// successful run whose records cannot be written fails with
+    /// [`crate::ChutoroError::EventLog`].
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::ChutoroBuilder;
+    ///
+    /// let path = std::env::temp_dir().join("chutoro-events.jsonl");
+    /// let builder = ChutoroBuilder::new().with_event_log(&path);
+    /// assert_eq!(builder.event_log(), Some(path.as_path()));
+    /// ```
+    #[must_use]
+    pub fn with_event_log(mut self, path: impl Into<PathBuf>) -> Self {
+        self.pipeline.event_log = Some(path.into());
+        self
+    }
+
+    /// Returns the file runs append event records to, if configured.
+    #[must_use]
+    pub fn event_log(&self) -> Option<&Path> {
+        self.pipeline.event_log.as_deref()
+    }
+}
+
+impl PipelineOptions {
+    /// Opens the configured event log so runs can write to it.
+    pub(super) fn with_opened_event_log(mut self) -> Result<Self> {
+        if let Some(path) = &self.event_log {
+            self.stages.event_log = Some(Arc::new(EventLog::open(path)?));
+        }
+        Ok(self)
+    }
+}
@@ -0,0 +1,76 @@
+//! Builder option that selects how candidate edges are discovered.
+//!
+//! HNSW is the default because its index also serves later queries. For
+//! one-shot clustering an NN-descent k-NN graph can replace it entirely.
+
+use std::sync::Arc;
+
+use crate::{GraphBuilder, Result, error::ChutoroError};
+
+use super::ChutoroBuilder;
+
+impl ChutoroBuilder {
+    /// Chooses the structure that discovers candidate edges.
+    ///
+    /// [`GraphBuilder::NnDescent`] builds an approximate k-nearest-neighbour
+    /// graph instead of an HNSW index. Its edges become the harvest, each
+    /// point's core distance is read from its neighbours in the graph, and
+    /// the mutual-neighbour filter, edge budget, spilling, distance
+    /// precision, deduplication, and component repair apply as usual. No
+    /// index is built, so the run records no edge provenance and reports a
+    /// [`crate::StageArtefact::Graph`] artefact in place of
+    /// [`crate::StageArtefact::Index`]. Choose `k` at least the minimum
+    /// cluster size so every core distance is found in the graph.
+    ///
+    /// [`Self::build`] rejects a zero `k` or iteration count, a sample rate
+    /// outside `(0, 1]`, and NN-descent combined with sampling, a prebuilt
+    /// index, a custom index or harvest stage, or noise reassignment, all of
+    /// which search an HNSW index.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ChutoroBuilder, GraphBuilder};
+    ///
+    /// let graph = GraphBuilder::NnDescent {
+    ///     k: 10,
+    ///     iterations: 8,
+    ///     sample_rate: 1.0,
+    /// };
+    /// let builder = ChutoroBuilder::new().with_graph_builder(graph);
+    /// assert_eq!(builder.graph_builder(), graph);
+    /// ```
+    #[must_use]
+    pub fn with_graph_builder(mut self, graph_builder: GraphBuilder) -> Self {
+        self.pipeline.graph_builder = graph_builder;
+        self
+    }
+
+    /// Returns the structure that discovers candidate edges.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn graph_builder(&self) -> GraphBuilder { self.pipeline.graph_builder }
+
+    /// Checks the graph builder settings and that no other option needs an
+    /// HNSW index the graph builder will not build.
+    pub(super) fn validate_graph_builder(&self) -> Result<()> {
+        let pipeline = &self.pipeline;
+        let reason = if let Some(reason) = pipeline.graph_builder.validate() {
+            reason
+        } else if pipeline.graph_builder == GraphBuilder::Hnsw {
+            return Ok(());
+        } else if pipeline.sample.is_some() {
+            "sampled runs assign the remaining points through an HNSW index"
+        } else if pipeline.prebuilt.is_some() {
+            "a prebuilt index already supplies the candidate edges"
+        } else if pipeline.stages.index.is_some() || pipeline.stages.harvest.is_some() {
+            "custom index and harvest stages need an HNSW index"
+        } else if pipeline.reassign_noise.is_some() {
+            "noise reassignment searches an HNSW index"
+        } else {
+            return Ok(());
+        };
+        Err(ChutoroError::InvalidGraphBuilder {
+            reason: Arc::from(reason),
+        })
+    }
+}
@@ -0,0 +1,40 @@
+//! Builder option that limits the size of selected clusters.
+//!
+//! Density-based selection favours one large, long-lived cluster when dense
+//! groups sit inside a diffuse background. Capping the selectable size makes
+//! hierarchy extraction report the groups instead.
+
+use std::num::NonZeroUsize;
+
+use super::ChutoroBuilder;
+
+impl ChutoroBuilder {
+    /// Forbids selecting clusters with more than `max_cluster_size` points,
+    /// forcing oversized clusters to split further down the condensed tree.
+    ///
+    /// Applies to the default hierarchy stage; see
+    /// [`crate::HierarchyConfig::with_max_cluster_size`] for the selection
+    /// rule. Custom stages can read the limit from
+    /// [`crate::StageContext::hierarchy_config`].
+    ///
+    /// # Examples
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use chutoro_core::ChutoroBuilder;
+    ///
+    /// let limit = NonZeroUsize::new(1_000).expect("literal is non-zero");
+    /// let builder = ChutoroBuilder::new().with_max_cluster_size(limit);
+    /// assert_eq!(builder.max_cluster_size(), Some(limit));
+    /// ```
+    #[must_use]
+    pub fn with_max_cluster_size(mut self, max_cluster_size: NonZeroUsize) -> Self {
+        self.pipeline.max_cluster_size = Some(max_cluster_size);
+        self
+    }
+
+    /// Returns the largest selectable cluster size, if limited.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn max_cluster_size(&self) -> Option<NonZeroUsize> { self.pipeline.max_cluster_size }
+}
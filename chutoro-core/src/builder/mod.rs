@@ -2,25 +2,58 @@
 //!
 //! Exposes the execution strategy selection surface and builder validation used before constructing [`Chutoro`] instances.
 
-use std::num::NonZeroUsize;
-
 #[cfg(feature = "cpu")]
 use std::sync::Arc;
 
 #[cfg(feature = "cpu")]
 use crate::{ClusteringSession, DataSource, HnswParams, SessionConfig, SessionRefreshPolicy};
-use crate::{Result, chutoro::Chutoro, error::ChutoroError};
+use crate::{Result, chutoro::Chutoro};
+
+#[cfg(feature = "cpu")]
+mod checkpoint;
+mod core_distances;
+#[cfg(feature = "cpu")]
+mod dedupe;
+mod distance;
+mod edges;
+#[cfg(feature = "cpu")]
+mod event_log;
+#[cfg(feature = "cpu")]
+mod graph;
+#[cfg(feature = "cpu")]
+mod hierarchy;
+#[cfg(feature = "cpu")]
+mod online;
+mod pipeline;
+#[cfg(feature = "cpu")]
+mod prebuilt;
+mod reassign;
+mod sample;
+mod seed_labels;
+#[cfg(feature = "cpu")]
+mod spill;
+#[cfg(feature = "cpu")]
+mod stages;
+mod triangle;
+mod validate;
+
+pub(crate) use self::pipeline::PipelineOptions;
+#[cfg(feature = "cpu")]
+pub(crate) use self::prebuilt::PrebuiltIndex;
+use self::validate::GpuRejectionReason;
 #[cfg(feature = "cpu")]
 use tracing::debug;
-use tracing::warn;
 
 /// Indicates how [`Chutoro`] selects a compute backend when [`Chutoro::run`] is
 /// invoked.
 ///
-/// `Auto` resolves backends deterministically. Today it runs the CPU backend
-/// (enabled by the default `cpu` feature). Once a real GPU backend ships, it
-/// will select the GPU implementation when available and fall back to the CPU
-/// otherwise so behaviour stays stable across builds.
+/// `Auto` probes the available backends when each run starts and takes the
+/// first that can run: the GPU implementation when it is compiled in and a
+/// device is present, then the CPU pipeline on a multi-threaded Rayon pool,
+/// then the CPU pipeline on a single thread. No GPU implementation ships yet,
+/// so today `Auto` always runs on the CPU (enabled by the default `cpu`
+/// feature). [`crate::ClusteringResult::backend`] records which [`crate::Backend`]
+/// ran, so one binary can serve hosts with different hardware.
 ///
 /// # Examples
 /// ```
@@ -58,38 +91,18 @@ pub struct ChutoroBuilder {
     min_cluster_size: usize,
     execution_strategy: ExecutionStrategy,
     max_bytes: Option<u64>,
-    #[cfg(feature = "cpu")]
-    hnsw_params: HnswParams,
+    pipeline: PipelineOptions,
     #[cfg(feature = "cpu")]
     session_refresh_policy: SessionRefreshPolicy,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum GpuRejectionReason {
-    BackendNotCompiled,
-    #[cfg(feature = "cpu")]
-    SessionsCpuOnly,
-}
-
-impl GpuRejectionReason {
-    #[rustfmt::skip]
-    fn as_str(self) -> &'static str {
-        match self {
-            Self::BackendNotCompiled => "GPU backend unavailable",
-            #[cfg(feature = "cpu")]
-            Self::SessionsCpuOnly => "sessions are unconditionally CPU-only",
-        }
-    }
-}
-
 impl Default for ChutoroBuilder {
     fn default() -> Self {
         Self {
             min_cluster_size: 5,
             execution_strategy: ExecutionStrategy::Auto,
             max_bytes: None,
-            #[cfg(feature = "cpu")]
-            hnsw_params: HnswParams::default(),
+            pipeline: PipelineOptions::default(),
             #[cfg(feature = "cpu")]
             session_refresh_policy: SessionRefreshPolicy::manual(),
         }
@@ -170,7 +183,7 @@ impl ChutoroBuilder {
     /// Sets an upper bound on estimated peak memory (in bytes).
     ///
     /// When set, [`Chutoro::run`] will compute a pre-flight estimate and
-    /// return [`ChutoroError::MemoryLimitExceeded`] if the estimate exceeds
+    /// return [`crate::ChutoroError::MemoryLimitExceeded`] if the estimate exceeds
     /// this limit.  Omit this call to leave the guard disabled (the default).
     ///
     /// # Examples
@@ -200,7 +213,10 @@ impl ChutoroBuilder {
     #[must_use]
     pub fn max_bytes(&self) -> Option<u64> { self.max_bytes }
 
-    /// Sets the HNSW parameters used when constructing clustering sessions.
+    /// Sets the HNSW parameters used by batch runs and clustering sessions.
+    ///
+    /// A master seed from [`Self::with_seed`] replaces the parameters' RNG
+    /// seed when the configuration is built.
     ///
     /// # Examples
     /// ```
@@ -213,15 +229,15 @@ impl ChutoroBuilder {
     #[cfg(feature = "cpu")]
     #[must_use]
     pub fn with_hnsw_params(mut self, params: HnswParams) -> Self {
-        self.hnsw_params = params;
+        self.pipeline.hnsw_params = params;
         self
     }
 
-    /// Returns the HNSW parameters used for session construction.
+    /// Returns the HNSW parameters used by batch runs and sessions.
     #[cfg(feature = "cpu")]
     #[must_use]
     pub fn hnsw_params(&self) -> &HnswParams {
-        &self.hnsw_params
+        &self.pipeline.hnsw_params
     }
 
     /// Sets the refresh policy carried into clustering sessions.
@@ -265,12 +281,30 @@ impl ChutoroBuilder {
         let gpu_rejection_reason =
             (!cfg!(feature = "gpu")).then_some(GpuRejectionReason::BackendNotCompiled);
         self.validate_execution_strategy(gpu_rejection_reason)?;
+        self.validate_sample()?;
+        self.validate_reassign_policy()?;
+        self.validate_seed_labels()?;
+        self.validate_core_distances()?;
+        #[cfg(feature = "cpu")]
+        self.validate_prebuilt_index()?;
+        #[cfg(feature = "cpu")]
+        self.validate_spill()?;
+        #[cfg(feature = "cpu")]
+        self.validate_checkpoint()?;
+        #[cfg(feature = "cpu")]
+        self.validate_dedupe()?;
+        #[cfg(feature = "cpu")]
+        self.validate_graph_builder()?;
 
-        Ok(Chutoro::new(
-            min_cluster_size,
-            self.execution_strategy,
-            self.max_bytes,
-        ))
+        let pipeline = self.pipeline.seeded();
+        #[cfg(feature = "cpu")]
+        let pipeline = pipeline
+            .with_index_distance_policy()
+            .with_opened_event_log()?;
+        Ok(
+            Chutoro::new(min_cluster_size, self.execution_strategy, self.max_bytes)
+                .with_pipeline_options(pipeline),
+        )
     }
 
     /// Constructs an empty [`ClusteringSession`] from the current builder
@@ -288,15 +322,15 @@ impl ChutoroBuilder {
     ///
     /// # Errors
     ///
-    /// Returns [`ChutoroError::InvalidMinClusterSize`] when `min_cluster_size` is
+    /// Returns [`crate::ChutoroError::InvalidMinClusterSize`] when `min_cluster_size` is
     /// `0` (i.e. when [`ChutoroBuilder::with_min_cluster_size`] was called with
     /// `0`).
     ///
-    /// Returns [`ChutoroError::BackendUnavailable`] when the builder's execution
+    /// Returns [`crate::ChutoroError::BackendUnavailable`] when the builder's execution
     /// strategy is [`ExecutionStrategy::GpuPreferred`]; sessions are
     /// unconditionally CPU-only.
     ///
-    /// Returns [`ChutoroError::CpuHnswFailure`] when the underlying `CpuHnsw`
+    /// Returns [`crate::ChutoroError::CpuHnswFailure`] when the underlying `CpuHnsw`
     /// index cannot be allocated (e.g. the HNSW library reports an internal
     /// construction error).
     #[cfg(feature = "cpu")]
@@ -308,7 +342,7 @@ impl ChutoroBuilder {
         self.validate_execution_strategy(Some(GpuRejectionReason::SessionsCpuOnly))?;
         let config = SessionConfig::new(
             min_cluster_size,
-            self.hnsw_params,
+            self.pipeline.seeded().hnsw_params,
             self.session_refresh_policy,
         );
         debug!(
@@ -318,37 +352,4 @@ impl ChutoroBuilder {
 
         ClusteringSession::new(config, source)
     }
-
-    fn validate_min_cluster_size(&self) -> Result<NonZeroUsize> {
-        NonZeroUsize::new(self.min_cluster_size).ok_or_else(|| {
-            warn!(
-                got = self.min_cluster_size,
-                "build rejected: min_cluster_size must be non-zero"
-            );
-            ChutoroError::InvalidMinClusterSize {
-                got: self.min_cluster_size,
-            }
-        })
-    }
-
-    fn validate_execution_strategy(
-        &self,
-        gpu_rejection_reason: Option<GpuRejectionReason>,
-    ) -> Result<()> {
-        if matches!(self.execution_strategy, ExecutionStrategy::GpuPreferred)
-            && let Some(reason) = gpu_rejection_reason
-        {
-            warn!(
-                requested = ?ExecutionStrategy::GpuPreferred,
-                rejection_reason = %reason.as_str(),
-                "build rejected: GpuPreferred strategy requested but {}",
-                reason.as_str()
-            );
-            return Err(ChutoroError::BackendUnavailable {
-                requested: ExecutionStrategy::GpuPreferred,
-            });
-        }
-
-        Ok(())
-    }
 }
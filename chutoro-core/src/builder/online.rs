@@ -0,0 +1,53 @@
+//! Construction of [`crate::OnlineClusterer`] from the builder configuration.
+//!
+//! The clusterer reuses the whole pipeline configuration for its periodic
+//! refreshes, so the only new setting is the micro-cluster radius.
+
+use std::sync::Arc;
+
+use tracing::debug;
+
+use crate::{DataSource, OnlineClusterer, Result, error::ChutoroError};
+
+use super::{ChutoroBuilder, GpuRejectionReason};
+
+impl ChutoroBuilder {
+    /// Constructs an empty [`OnlineClusterer`] over `source` whose
+    /// micro-clusters absorb points within `radius` of their centre.
+    ///
+    /// Refreshes run the pipeline built from this configuration over the
+    /// micro-cluster centres. They happen every
+    /// [`crate::SessionRefreshPolicy::refresh_every_n`] insertions when the
+    /// [`Self::with_session_refresh_policy`] policy sets one, and otherwise
+    /// only when [`OnlineClusterer::refresh`] is called. A radius of `0.0`
+    /// only merges duplicate points.
+    ///
+    /// # Errors
+    /// Returns [`ChutoroError::InvalidOnlineConfig`] when `radius` is
+    /// negative or not finite, [`ChutoroError::BackendUnavailable`] for
+    /// [`crate::ExecutionStrategy::GpuPreferred`] because online clustering is
+    /// CPU-only, [`ChutoroError::CpuHnswFailure`] when the centre index cannot
+    /// be allocated, and any error [`Self::build`] reports.
+    pub fn build_online<D: DataSource + Send + Sync>(
+        self,
+        source: Arc<D>,
+        radius: f32,
+    ) -> Result<OnlineClusterer<D>> {
+        if !radius.is_finite() || radius < 0.0 {
+            return Err(ChutoroError::InvalidOnlineConfig {
+                reason: Arc::from(format!(
+                    "radius must be finite and non-negative (got {radius})"
+                )),
+            });
+        }
+        self.validate_execution_strategy(Some(GpuRejectionReason::OnlineCpuOnly))?;
+        let refresh_every = self.session_refresh_policy.refresh_every_n();
+        let chutoro = self.build()?;
+        debug!(
+            radius,
+            ?refresh_every,
+            "build_online: constructing OnlineClusterer"
+        );
+        OnlineClusterer::new(chutoro, source, radius, refresh_every)
+    }
+}
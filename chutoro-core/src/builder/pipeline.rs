@@ -0,0 +1,103 @@
+//! Pipeline tuning options carried from [`ChutoroBuilder`] into runs.
+//!
+//! These settings adjust which points the CPU pipeline clusters directly,
+//! where it obtains its HNSW index and candidate edges, and how those edges
+//! are post-processed before hierarchy extraction.
+
+use std::num::NonZeroUsize;
+#[cfg(feature = "cpu")]
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::{
+    DistancePolicy, DistancePrecision, DistanceTransform, EdgeBudget, ReassignPolicy, SeedStream,
+    sample::Sampling,
+};
+#[cfg(feature = "cpu")]
+use crate::{
+    GraphBuilder, HierarchyConfig, HnswParams, checkpoint::CheckpointOptions,
+    stages::PipelineStages,
+};
+
+#[cfg(feature = "cpu")]
+use super::PrebuiltIndex;
+
+/// Optional pipeline stages configured on the builder and applied by
+/// [`crate::Chutoro::run`].
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PipelineOptions {
+    pub(crate) edge_budget: Option<EdgeBudget>,
+    pub(crate) mutual_neighbours: Option<NonZeroUsize>,
+    pub(crate) connect_components: bool,
+    pub(crate) edge_provenance: bool,
+    pub(crate) reassign_noise: Option<ReassignPolicy>,
+    pub(crate) sample: Option<Sampling>,
+    pub(crate) distance_policy: DistancePolicy,
+    pub(crate) distance_precision: DistancePrecision,
+    pub(crate) distance_transform: DistanceTransform,
+    pub(crate) max_distance_evaluations: Option<u64>,
+    pub(crate) seed: Option<u64>,
+    pub(crate) triangle_check: Option<NonZeroUsize>,
+    pub(crate) core_distances: Option<Arc<[f32]>>,
+    pub(crate) seed_labels: Option<Arc<[(usize, crate::ClusterId)]>>,
+    #[cfg(feature = "cpu")]
+    pub(crate) max_cluster_size: Option<NonZeroUsize>,
+    #[cfg(feature = "cpu")]
+    pub(crate) hnsw_params: HnswParams,
+    #[cfg(feature = "cpu")]
+    pub(crate) prebuilt: Option<PrebuiltIndex>,
+    #[cfg(feature = "cpu")]
+    pub(crate) graph_builder: GraphBuilder,
+    #[cfg(feature = "cpu")]
+    pub(crate) stages: PipelineStages,
+    #[cfg(feature = "cpu")]
+    pub(crate) weighted_edge_spill_directory: Option<PathBuf>,
+    #[cfg(feature = "cpu")]
+    pub(crate) checkpoint: Option<CheckpointOptions>,
+    #[cfg(feature = "cpu")]
+    pub(crate) event_log: Option<PathBuf>,
+    #[cfg(feature = "cpu")]
+    pub(crate) dedupe_exact: bool,
+    /// How many points each clustered point stands for; set internally for
+    /// runs over deduplicated representatives.
+    #[cfg(feature = "cpu")]
+    pub(crate) point_weights: Option<Arc<[usize]>>,
+}
+
+impl PipelineOptions {
+    /// Replaces the component seeds with those derived from the master seed,
+    /// if one is set. A prebuilt index keeps the seed it was built with.
+    pub(crate) fn seeded(mut self) -> Self {
+        let Some(master) = self.seed else {
+            return self;
+        };
+        if let Some(sampling) = &mut self.sample {
+            sampling.seed = SeedStream::Sample.derive(master);
+        }
+        #[cfg(feature = "cpu")]
+        if self.prebuilt.is_none() {
+            self.hnsw_params = self
+                .hnsw_params
+                .with_rng_seed(SeedStream::HnswLevels.derive(master));
+        }
+        self
+    }
+
+    /// Hands the run's distance policy to the index it builds. A prebuilt
+    /// index keeps the policy it was built with.
+    #[cfg(feature = "cpu")]
+    pub(crate) fn with_index_distance_policy(mut self) -> Self {
+        self.hnsw_params = self.hnsw_params.with_distance_policy(self.distance_policy);
+        self
+    }
+
+    /// Returns the hierarchy configuration for runs with `min_cluster_size`.
+    #[cfg(feature = "cpu")]
+    pub(crate) fn hierarchy_config(&self, min_cluster_size: NonZeroUsize) -> HierarchyConfig {
+        let config = HierarchyConfig::new(min_cluster_size);
+        match self.max_cluster_size {
+            Some(max_cluster_size) => config.with_max_cluster_size(max_cluster_size),
+            None => config,
+        }
+    }
+}
@@ -0,0 +1,100 @@
+//! Builder option that clusters over an application-owned HNSW index.
+
+use std::sync::Arc;
+
+use crate::{CpuHnsw, EdgeHarvest, Result, error::ChutoroError};
+
+use super::ChutoroBuilder;
+
+/// An application-owned HNSW index and the edges harvested while building it.
+#[derive(Debug, Clone)]
+pub(crate) struct PrebuiltIndex {
+    pub(crate) index: Arc<CpuHnsw>,
+    pub(crate) harvest: Arc<EdgeHarvest>,
+}
+
+impl ChutoroBuilder {
+    /// Reuses an existing HNSW index instead of building one per run.
+    ///
+    /// Applications that already maintain a [`CpuHnsw`] for search can cluster
+    /// over it without rebuilding or duplicating the graph. `harvest` must be
+    /// the [`EdgeHarvest`] returned alongside the index by
+    /// [`CpuHnsw::build_with_edges`]. The builder adopts the index's
+    /// parameters; [`Self::build`] rejects the configuration if different
+    /// parameters are set afterwards or the harvest references points the
+    /// index does not hold. Runs fail when the data source length differs
+    /// from the index length.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use chutoro_core::{ChutoroBuilder, CpuHnsw, DataSource, DataSourceError, HnswParams};
+    ///
+    /// struct Line(Vec<f32>);
+    ///
+    /// impl DataSource for Line {
+    ///     fn len(&self) -> usize { self.0.len() }
+    ///     fn name(&self) -> &str { "line" }
+    ///     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+    ///         Ok((self.0[i] - self.0[j]).abs())
+    ///     }
+    /// }
+    ///
+    /// let source = Line(vec![0.0, 1.0, 2.0, 10.0, 11.0, 12.0]);
+    /// let params = HnswParams::new(4, 8).expect("params are valid");
+    /// let (index, harvest) = CpuHnsw::build_with_edges(&source, params).expect("index builds");
+    /// let chutoro = ChutoroBuilder::new()
+    ///     .with_min_cluster_size(2)
+    ///     .with_prebuilt_index(Arc::new(index), harvest)
+    ///     .build()
+    ///     .expect("configuration is valid");
+    /// let result = chutoro.run(&source).expect("run succeeds");
+    /// assert_eq!(result.assignments().len(), source.len());
+    /// ```
+    #[must_use]
+    pub fn with_prebuilt_index(mut self, index: Arc<CpuHnsw>, harvest: EdgeHarvest) -> Self {
+        self.pipeline.hnsw_params = index.params().clone();
+        self.pipeline.prebuilt = Some(PrebuiltIndex {
+            index,
+            harvest: Arc::new(harvest),
+        });
+        self
+    }
+
+    /// Returns the prebuilt index reused by runs, if configured.
+    #[must_use]
+    pub fn prebuilt_index(&self) -> Option<&Arc<CpuHnsw>> {
+        self.pipeline
+            .prebuilt
+            .as_ref()
+            .map(|prebuilt| &prebuilt.index)
+    }
+
+    /// Checks that a prebuilt index agrees with the builder configuration.
+    pub(super) fn validate_prebuilt_index(&self) -> Result<()> {
+        let Some(prebuilt) = &self.pipeline.prebuilt else {
+            return Ok(());
+        };
+        if prebuilt.index.params() != &self.pipeline.hnsw_params {
+            return Err(ChutoroError::PrebuiltIndexMismatch {
+                reason: Arc::from("index parameters differ from the configured HNSW parameters"),
+            });
+        }
+        let points = prebuilt.index.len();
+        if let Some(edge) = prebuilt
+            .harvest
+            .iter()
+            .find(|edge| edge.source().max(edge.target()) >= points)
+        {
+            return Err(ChutoroError::PrebuiltIndexMismatch {
+                reason: Arc::from(format!(
+                    "harvest edge ({}, {}) references a point outside the {points}-point index",
+                    edge.source(),
+                    edge.target()
+                )),
+            });
+        }
+        Ok(())
+    }
+}
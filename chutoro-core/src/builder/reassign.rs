@@ -0,0 +1,63 @@
+//! Builder option that moves noise points into nearby clusters.
+//!
+//! Reassignment runs after hierarchy extraction, so it never changes which
+//! clusters exist; it only labels points the hierarchy left as noise.
+
+use std::sync::Arc;
+
+use crate::{ReassignPolicy, Result, error::ChutoroError};
+
+use super::ChutoroBuilder;
+
+impl ChutoroBuilder {
+    /// Relabels noise points according to `policy` after clustering.
+    ///
+    /// With [`ReassignPolicy::NearestCluster`], each noise point's HNSW
+    /// neighbourhood is searched with the `ef_construction` width, and the
+    /// point joins the cluster of its nearest clustered neighbour when that
+    /// neighbour lies within `max_distance`. Points without one stay noise,
+    /// and the noise label is retired once every noise point is reassigned.
+    /// Reassigned points keep their zero membership probability, so callers
+    /// can still tell them apart. Sampled runs reassign the sample before its
+    /// labels are extended to the remaining points.
+    ///
+    /// The outcome is reported via
+    /// [`crate::ClusteringResult::noise_reassignment`]. [`Self::build`]
+    /// rejects a negative or NaN `max_distance`.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ChutoroBuilder, ReassignPolicy};
+    ///
+    /// let policy = ReassignPolicy::NearestCluster { max_distance: 2.5 };
+    /// let chutoro = ChutoroBuilder::new()
+    ///     .reassign_noise(policy)
+    ///     .build()
+    ///     .expect("configuration is valid");
+    /// assert_eq!(chutoro.reassign_policy(), Some(policy));
+    /// ```
+    #[must_use]
+    pub fn reassign_noise(mut self, policy: ReassignPolicy) -> Self {
+        self.pipeline.reassign_noise = Some(policy);
+        self
+    }
+
+    /// Returns the noise reassignment policy, if configured.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn reassign_policy(&self) -> Option<ReassignPolicy> { self.pipeline.reassign_noise }
+
+    /// Checks that the noise reassignment policy is usable.
+    pub(super) fn validate_reassign_policy(&self) -> Result<()> {
+        let Some(reason) = self
+            .pipeline
+            .reassign_noise
+            .and_then(ReassignPolicy::validate)
+        else {
+            return Ok(());
+        };
+        Err(ChutoroError::InvalidReassignPolicy {
+            reason: Arc::from(reason),
+        })
+    }
+}
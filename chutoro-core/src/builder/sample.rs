@@ -0,0 +1,98 @@
+//! Builder options for subsampled runs and master seeding.
+
+use std::sync::Arc;
+
+use crate::{Result, SampleSpec, error::ChutoroError, sample::Sampling};
+
+use super::ChutoroBuilder;
+
+impl ChutoroBuilder {
+    /// Clusters a deterministic subsample and labels the remaining points
+    /// from it.
+    ///
+    /// `seed` selects which points are sampled, so repeated runs over the same
+    /// source produce the same result. The sample is clustered as usual; each
+    /// remaining point then joins the cluster of its nearest clustered
+    /// neighbour in the sample's HNSW index, inheriting that neighbour's
+    /// membership scores. Every point is still labelled while the index, MST,
+    /// and hierarchy stages only process the sample. The split is recorded in
+    /// [`crate::ClusteringResult::sampling`]. [`Self::build`] rejects
+    /// fractions outside `(0, 1]`, a zero count, and sampling combined with
+    /// [`Self::with_prebuilt_index`].
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ChutoroBuilder, SampleSpec};
+    ///
+    /// let builder = ChutoroBuilder::new().with_sample(SampleSpec::Fraction(0.1), 42);
+    /// assert_eq!(builder.sample(), Some((SampleSpec::Fraction(0.1), 42)));
+    /// ```
+    #[must_use]
+    pub fn with_sample(mut self, spec: SampleSpec, seed: u64) -> Self {
+        self.pipeline.sample = Some(Sampling { spec, seed });
+        self
+    }
+
+    /// Returns the configured sample specification and seed, if any.
+    #[must_use]
+    pub fn sample(&self) -> Option<(SampleSpec, u64)> {
+        self.pipeline
+            .sample
+            .map(|sampling| (sampling.spec, sampling.seed))
+    }
+
+    /// Sets one master seed from which every randomized component is seeded.
+    ///
+    /// [`Self::build`] derives the HNSW level-sampling seed and the sample
+    /// selection seed from `master` as described by [`crate::SeedStream::derive`],
+    /// replacing any seed given to [`crate::HnswParams::with_rng_seed`] or
+    /// [`Self::with_sample`]. HNSW worker RNGs are derived from the
+    /// level-sampling seed, and a prebuilt index keeps its own seed. The
+    /// seeds each run used are recorded in
+    /// [`crate::ClusteringResult::seeds`].
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ChutoroBuilder, SampleSpec, SeedStream};
+    ///
+    /// let chutoro = ChutoroBuilder::new()
+    ///     .with_seed(42)
+    ///     .with_sample(SampleSpec::Fraction(0.5), 7)
+    ///     .build()
+    ///     .expect("configuration is valid");
+    /// assert_eq!(chutoro.seed(), Some(42));
+    /// assert_eq!(
+    ///     chutoro.sample().map(|(_, seed)| seed),
+    ///     Some(SeedStream::Sample.derive(42))
+    /// );
+    /// ```
+    #[must_use]
+    pub fn with_seed(mut self, master: u64) -> Self {
+        self.pipeline.seed = Some(master);
+        self
+    }
+
+    /// Returns the configured master seed, if any.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn seed(&self) -> Option<u64> { self.pipeline.seed }
+
+    /// Checks that the sample specification is usable.
+    pub(super) fn validate_sample(&self) -> Result<()> {
+        let Some(sampling) = self.pipeline.sample else {
+            return Ok(());
+        };
+        if let Some(reason) = sampling.spec.validate() {
+            return Err(ChutoroError::InvalidSample {
+                reason: Arc::from(reason),
+            });
+        }
+        #[cfg(feature = "cpu")]
+        if self.pipeline.prebuilt.is_some() {
+            return Err(ChutoroError::InvalidSample {
+                reason: Arc::from("sampling builds its own index and cannot reuse a prebuilt one"),
+            });
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+//! Builder option that anchors clusters to known labels.
+//!
+//! Anchoring runs after the backend returns, so it never changes which
+//! points cluster together; it only renumbers the clusters that contain
+//! labelled points.
+
+use std::sync::Arc;
+
+use crate::{ClusterId, Result, error::ChutoroError, seed_labels};
+
+use super::ChutoroBuilder;
+
+impl ChutoroBuilder {
+    /// Labels a subset of points with known clusters before the run.
+    ///
+    /// Each entry pairs a point index with its label. After clustering, each
+    /// cluster containing seeds adopts the label most of them carry, and
+    /// unseeded clusters are numbered after the largest seed label, followed
+    /// by the noise label. Seeds always keep their own label, even when the
+    /// pipeline left them as noise. Clusters whose seeds disagree are listed
+    /// in [`crate::ClusteringResult::seed_labels`] rather than failing the
+    /// run.
+    ///
+    /// Unlike [`Self::with_seed`], which fixes the random streams, this sets
+    /// class labels. [`Self::build`] rejects a point seeded twice and labels
+    /// that are not contiguous from zero, and runs fail with
+    /// [`ChutoroError::InvalidSeedLabels`] when a seeded point lies beyond
+    /// the data source.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ChutoroBuilder, ClusterId};
+    ///
+    /// let seeds = [(0, ClusterId::new(0)), (42, ClusterId::new(1))];
+    /// let builder = ChutoroBuilder::new().with_seed_labels(&seeds);
+    /// assert_eq!(builder.seed_labels(), Some(&seeds[..]));
+    /// ```
+    #[must_use]
+    pub fn with_seed_labels(mut self, seeds: &[(usize, ClusterId)]) -> Self {
+        self.pipeline.seed_labels = Some(Arc::from(seeds));
+        self
+    }
+
+    /// Returns the known labels, if any were supplied.
+    #[must_use]
+    pub fn seed_labels(&self) -> Option<&[(usize, ClusterId)]> {
+        self.pipeline.seed_labels.as_deref()
+    }
+
+    /// Checks that the seed labels are usable.
+    pub(super) fn validate_seed_labels(&self) -> Result<()> {
+        let Some(reason) = self
+            .pipeline
+            .seed_labels
+            .as_deref()
+            .and_then(seed_labels::validate)
+        else {
+            return Ok(());
+        };
+        Err(ChutoroError::InvalidSeedLabels {
+            reason: Arc::from(reason),
+        })
+    }
+}
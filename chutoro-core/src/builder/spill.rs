@@ -0,0 +1,75 @@
+//! Builder option that spills the weighted MST input to disk.
+//!
+//! Spilling trades I/O for memory: the mutual-reachability edges are written
+//! to sorted temporary files and merged back into Kruskal instead of being
+//! held in RAM. The raw harvest from the index stage is not spilled. Results
+//! are identical to an in-memory run.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::{Result, error::ChutoroError};
+
+use super::ChutoroBuilder;
+
+impl ChutoroBuilder {
+    /// Spills the weighted candidate edges to temporary files under
+    /// `directory` instead of holding them in memory.
+    ///
+    /// Each run creates a fresh subdirectory of `directory`, writes the
+    /// mutual-reachability edges to it in sorted chunks, merges them into
+    /// Kruskal, and removes the subdirectory when the run ends. This roughly
+    /// halves the peak memory of the harvest and MST stages at the cost of
+    /// writing and reading every edge once; the raw HNSW harvest still stays
+    /// in memory. Clustering results are identical to an in-memory run.
+    ///
+    /// With an edge budget or a custom harvest stage the weighted harvest is
+    /// built in memory first and spilled before Kruskal runs. Otherwise no
+    /// [`crate::StageArtefact::Harvest`] artefact is reported, because the
+    /// weighted edges never exist in memory.
+    /// [`Self::build`] rejects a `directory` that is not an existing
+    /// directory, and spilling combined with
+    /// [`Self::with_mst_stage`], which needs the harvest in memory.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::ChutoroBuilder;
+    ///
+    /// let directory = std::env::temp_dir();
+    /// let builder = ChutoroBuilder::new().with_weighted_edge_spill_directory(&directory);
+    /// assert_eq!(builder.weighted_edge_spill_directory(), Some(directory.as_path()));
+    /// ```
+    #[must_use]
+    pub fn with_weighted_edge_spill_directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.pipeline.weighted_edge_spill_directory = Some(directory.into());
+        self
+    }
+
+    /// Returns the directory weighted edges are spilled under, if
+    /// configured.
+    #[must_use]
+    pub fn weighted_edge_spill_directory(&self) -> Option<&Path> {
+        self.pipeline.weighted_edge_spill_directory.as_deref()
+    }
+
+    /// Checks that the weighted-edge spill directory exists and that the MST stage reads
+    /// from the spill.
+    pub(super) fn validate_spill(&self) -> Result<()> {
+        let Some(directory) = &self.pipeline.weighted_edge_spill_directory else {
+            return Ok(());
+        };
+        let reason = if !directory.is_dir() {
+            "spill directory does not exist or is not a directory"
+        } else if self.pipeline.stages.mst.is_some() {
+            "a custom MST stage needs the harvest in memory and cannot read a spill"
+        } else {
+            return Ok(());
+        };
+        Err(ChutoroError::Spill {
+            path: Arc::from(directory.as_path()),
+            reason: Arc::from(reason),
+        })
+    }
+}
@@ -0,0 +1,173 @@
+//! Builder hooks that substitute or observe individual CPU pipeline stages.
+//!
+//! Each stage hook replaces one stage of [`crate::Chutoro::run`] while the
+//! others keep their built-in behaviour. Stage output is checked against the
+//! data source before the next stage consumes it. An artefact hook observes
+//! every stage's output without changing it.
+
+use std::sync::Arc;
+
+use crate::{
+    HarvestStage, HierarchyStage, IndexStage, MstStage, StageArtefactHook, stages::ArtefactHook,
+};
+
+use super::ChutoroBuilder;
+
+impl ChutoroBuilder {
+    /// Replaces the stage that builds the HNSW index and harvests candidate
+    /// edges.
+    ///
+    /// Sampled runs use the stage to index the sample. A prebuilt index from
+    /// [`Self::with_prebuilt_index`] takes precedence.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ChutoroBuilder, DefaultIndexStage};
+    ///
+    /// let builder = ChutoroBuilder::new().with_index_stage(DefaultIndexStage);
+    /// assert!(builder.index_stage().is_some());
+    /// ```
+    #[must_use]
+    pub fn with_index_stage(mut self, stage: impl IndexStage + 'static) -> Self {
+        self.pipeline.stages.index = Some(Arc::new(stage));
+        self
+    }
+
+    /// Returns the custom index stage, if configured.
+    #[must_use]
+    pub fn index_stage(&self) -> Option<&dyn IndexStage> {
+        self.pipeline.stages.index.as_deref()
+    }
+
+    /// Replaces the stage that weights harvested edges for MST construction.
+    ///
+    /// The stage must report one core distance per point. Edges skipped by
+    /// the distance policy are removed before it runs, and the edge budget is
+    /// applied to its output.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ChutoroBuilder, DefaultHarvestStage};
+    ///
+    /// let builder = ChutoroBuilder::new().with_harvest_stage(DefaultHarvestStage);
+    /// assert!(builder.harvest_stage().is_some());
+    /// ```
+    #[must_use]
+    pub fn with_harvest_stage(mut self, stage: impl HarvestStage + 'static) -> Self {
+        self.pipeline.stages.harvest = Some(Arc::new(stage));
+        self
+    }
+
+    /// Returns the custom harvest stage, if configured.
+    #[must_use]
+    pub fn harvest_stage(&self) -> Option<&dyn HarvestStage> {
+        self.pipeline.stages.harvest.as_deref()
+    }
+
+    /// Replaces the stage that computes the spanning forest, for example to
+    /// supply a precomputed MST.
+    ///
+    /// Forest edges must reference points of the source. Component repair
+    /// runs on the returned forest when enabled.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ChutoroBuilder, DefaultMstStage};
+    ///
+    /// let builder = ChutoroBuilder::new().with_mst_stage(DefaultMstStage);
+    /// assert!(builder.mst_stage().is_some());
+    /// ```
+    #[must_use]
+    pub fn with_mst_stage(mut self, stage: impl MstStage + 'static) -> Self {
+        self.pipeline.stages.mst = Some(Arc::new(stage));
+        self
+    }
+
+    /// Returns the custom MST stage, if configured.
+    #[must_use]
+    pub fn mst_stage(&self) -> Option<&dyn MstStage> {
+        self.pipeline.stages.mst.as_deref()
+    }
+
+    /// Replaces the stage that extracts the flat clustering from the forest.
+    ///
+    /// The stage must label every point.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ChutoroBuilder, DefaultHierarchyStage};
+    ///
+    /// let builder = ChutoroBuilder::new().with_hierarchy_stage(DefaultHierarchyStage);
+    /// assert!(builder.hierarchy_stage().is_some());
+    /// ```
+    #[must_use]
+    pub fn with_hierarchy_stage(mut self, stage: impl HierarchyStage + 'static) -> Self {
+        self.pipeline.stages.hierarchy = Some(Arc::new(stage));
+        self
+    }
+
+    /// Returns the custom hierarchy stage, if configured.
+    #[must_use]
+    pub fn hierarchy_stage(&self) -> Option<&dyn HierarchyStage> {
+        self.pipeline.stages.hierarchy.as_deref()
+    }
+
+    /// Registers a hook that receives each stage's output as it completes.
+    ///
+    /// The hook sees read-only borrows of the index and raw harvest, the
+    /// weighted edges passed to MST construction, the forest, and the
+    /// condensed tree, so tools can persist or visualise intermediates
+    /// without the pipeline knowing their export formats. Registering a
+    /// second hook replaces the first.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// use chutoro_core::{ChutoroBuilder, DataSource, DataSourceError, StageArtefact};
+    ///
+    /// struct Line(Vec<f32>);
+    ///
+    /// impl DataSource for Line {
+    ///     fn len(&self) -> usize { self.0.len() }
+    ///     fn name(&self) -> &str { "line" }
+    ///     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+    ///         Ok((self.0[i] - self.0[j]).abs())
+    ///     }
+    /// }
+    ///
+    /// let forest_sizes = Arc::new(Mutex::new(Vec::new()));
+    /// let sink = Arc::clone(&forest_sizes);
+    /// let chutoro = ChutoroBuilder::new()
+    ///     .with_min_cluster_size(2)
+    ///     .on_stage_complete(move |artefact: StageArtefact<'_>| {
+    ///         if let StageArtefact::Mst(edges) = artefact {
+    ///             sink.lock().expect("lock is healthy").push(edges.len());
+    ///         }
+    ///     })
+    ///     .build()
+    ///     .expect("configuration is valid");
+    /// chutoro
+    ///     .run(&Line(vec![0.0, 1.0, 2.0, 10.0, 11.0]))
+    ///     .expect("run succeeds");
+    /// assert_eq!(*forest_sizes.lock().expect("lock is healthy"), [4]);
+    /// ```
+    #[must_use]
+    pub fn on_stage_complete(mut self, hook: impl StageArtefactHook + 'static) -> Self {
+        self.pipeline.stages.hook = Some(ArtefactHook(Arc::new(hook)));
+        self
+    }
+
+    /// Returns the registered stage hook, if any.
+    ///
+    /// The hook is shared, so wrappers can register a hook of their own that
+    /// forwards to it.
+    #[must_use]
+    pub fn stage_hook(&self) -> Option<&Arc<dyn StageArtefactHook>> {
+        self.pipeline
+            .stages
+            .hook
+            .as_ref()
+            .map(|ArtefactHook(hook)| hook)
+    }
+}
@@ -0,0 +1,44 @@
+//! Builder option that spot-checks the triangle inequality before a run.
+//!
+//! The check costs three distance evaluations per sampled triple and only
+//! applies to sources whose [`crate::MetricDescriptor`] declares a
+//! [`crate::MetricClass`] that promises the inequality.
+
+use std::num::NonZeroUsize;
+
+use super::ChutoroBuilder;
+
+impl ChutoroBuilder {
+    /// Samples `triples` triples of points before each run and warns when a
+    /// source that declares a metric breaks the triangle inequality.
+    ///
+    /// The check runs only when the source's
+    /// [`crate::MetricDescriptor::class`] satisfies
+    /// [`crate::MetricClass::satisfies_triangle_inequality`]; undeclared and
+    /// non-metric sources are trusted as they are. Violations are recorded as
+    /// [`crate::Warning::TriangleInequalityViolated`] rather than failing the
+    /// run, since HNSW still returns neighbours, only less accurately. The
+    /// triples follow the master seed when one is set, and their evaluations
+    /// do not count towards [`Self::with_max_distance_evaluations`].
+    ///
+    /// # Examples
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use chutoro_core::ChutoroBuilder;
+    ///
+    /// let triples = NonZeroUsize::new(256).expect("literal is non-zero");
+    /// let builder = ChutoroBuilder::new().with_triangle_check(triples);
+    /// assert_eq!(builder.triangle_check(), Some(triples));
+    /// ```
+    #[must_use]
+    pub fn with_triangle_check(mut self, triples: NonZeroUsize) -> Self {
+        self.pipeline.triangle_check = Some(triples);
+        self
+    }
+
+    /// Returns the number of triples checked before each run, if enabled.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn triangle_check(&self) -> Option<NonZeroUsize> { self.pipeline.triangle_check }
+}
@@ -0,0 +1,70 @@
+//! Validation shared by the builder's terminal constructors.
+//!
+//! [`ChutoroBuilder::build`], [`ChutoroBuilder::build_session`] and the online
+//! entry point all check the minimum cluster size and execution strategy;
+//! only the reason for rejecting a GPU request differs between them.
+
+use std::num::NonZeroUsize;
+
+use tracing::warn;
+
+use crate::{Result, error::ChutoroError};
+
+use super::{ChutoroBuilder, ExecutionStrategy};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum GpuRejectionReason {
+    BackendNotCompiled,
+    #[cfg(feature = "cpu")]
+    SessionsCpuOnly,
+    #[cfg(feature = "cpu")]
+    OnlineCpuOnly,
+}
+
+impl GpuRejectionReason {
+    #[rustfmt::skip]
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::BackendNotCompiled => "GPU backend unavailable",
+            #[cfg(feature = "cpu")]
+            Self::SessionsCpuOnly => "sessions are unconditionally CPU-only",
+            #[cfg(feature = "cpu")]
+            Self::OnlineCpuOnly => "online clustering is unconditionally CPU-only",
+        }
+    }
+}
+
+impl ChutoroBuilder {
+    pub(super) fn validate_min_cluster_size(&self) -> Result<NonZeroUsize> {
+        NonZeroUsize::new(self.min_cluster_size).ok_or_else(|| {
+            warn!(
+                got = self.min_cluster_size,
+                "build rejected: min_cluster_size must be non-zero"
+            );
+            ChutoroError::InvalidMinClusterSize {
+                got: self.min_cluster_size,
+            }
+        })
+    }
+
+    pub(super) fn validate_execution_strategy(
+        &self,
+        gpu_rejection_reason: Option<GpuRejectionReason>,
+    ) -> Result<()> {
+        if matches!(self.execution_strategy, ExecutionStrategy::GpuPreferred)
+            && let Some(reason) = gpu_rejection_reason
+        {
+            warn!(
+                requested = ?ExecutionStrategy::GpuPreferred,
+                rejection_reason = %reason.as_str(),
+                "build rejected: GpuPreferred strategy requested but {}",
+                reason.as_str()
+            );
+            return Err(ChutoroError::BackendUnavailable {
+                requested: ExecutionStrategy::GpuPreferred,
+            });
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+//! Fingerprints tying checkpoints to the source and options that wrote them.
+//!
+//! A checkpoint is only reusable by a run over the same data with the same
+//! stage configuration. The point count alone cannot tell two sources of
+//! equal length apart, so each checkpoint also records a hash of the
+//! source's identity and a sample of its distances, together with the
+//! options that shape the index, harvest, and forest stages.
+
+use std::hash::Hasher;
+
+use crate::{DataSource, builder::PipelineOptions};
+
+/// How many distances the fingerprint samples from the source.
+const DISTANCE_SAMPLES: usize = 16;
+
+/// The run a checkpoint belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct RunIdentity {
+    pub(super) items: usize,
+    pub(super) fingerprint: u64,
+}
+
+impl RunIdentity {
+    /// Identifies a run over the first `items` points of `source` with
+    /// `options`.
+    ///
+    /// Evaluates up to [`DISTANCE_SAMPLES`] distances between points spread
+    /// across the source, so sources of equal length and name but different
+    /// data fingerprint differently. A failed evaluation hashes as a marker
+    /// rather than failing the run; the stages report it when they meet it.
+    pub(super) fn new<D: DataSource>(source: &D, items: usize, options: &PipelineOptions) -> Self {
+        let mut hasher = Fnv1a::default();
+        hash_source(&mut hasher, source, items);
+        hash_options(&mut hasher, options);
+        Self {
+            items,
+            fingerprint: hasher.finish(),
+        }
+    }
+}
+
+fn hash_source<D: DataSource>(hasher: &mut Fnv1a, source: &D, items: usize) {
+    hasher.write_text(source.name());
+    hasher.write_text(source.metric_descriptor().as_str());
+    hasher.write_u64(source.dimension_hint().map_or(u64::MAX, |dim| dim as u64));
+    if let Some(ids) = source.row_ids() {
+        ids.ids().iter().for_each(|id| hasher.write_text(id));
+    }
+    if items < 2 {
+        return;
+    }
+    let stride = items.div_ceil(DISTANCE_SAMPLES);
+    for left in (0..items).step_by(stride) {
+        let right = (left + items / 2) % items;
+        let distance = source.distance_f64(left, right);
+        hasher.write_u64(distance.map_or(u64::MAX, f64::to_bits));
+    }
+}
+
+/// Hashes the `Debug` rendering of the options that change what the
+/// checkpointed stages produce. Hierarchy options are left out: they act
+/// after the forest, so a resumed run may change them freely.
+fn hash_options(hasher: &mut Fnv1a, options: &PipelineOptions) {
+    let shaping = format!(
+        "{:?}|{:?}|{}|{:?}|{:?}|{:?}|{:?}|{:?}|{}|{}",
+        options.edge_budget,
+        options.mutual_neighbours,
+        options.connect_components,
+        options.distance_policy,
+        options.distance_precision,
+        options.distance_transform,
+        options.hnsw_params,
+        options.graph_builder,
+        options.dedupe_exact,
+        options.prebuilt.is_some(),
+    );
+    hasher.write_text(&shaping);
+    if let Some(core_distances) = &options.core_distances {
+        core_distances
+            .iter()
+            .for_each(|distance| hasher.write_u32(distance.to_bits()));
+    }
+}
+
+/// 64-bit FNV-1a, which unlike the standard library's hasher is stable
+/// across Rust releases and so across the builds that write and resume a
+/// checkpoint.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv1a {
+    /// Hashes `text` with a terminator, so adjacent strings cannot run
+    /// together.
+    fn write_text(&mut self, text: &str) {
+        self.write(text.as_bytes());
+        self.write_u8(0xff);
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u32(&mut self, word: u32) {
+        self.write(&word.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, word: u64) {
+        self.write(&word.to_le_bytes());
+    }
+}
@@ -0,0 +1,255 @@
+//! Byte layout of checkpoint files.
+//!
+//! Every file opens with an eight-byte tag naming its contents and format
+//! version, followed by the number of points the run covers and the run's
+//! fingerprint. The rest is
+//! little-endian `u64` words. Edges take four words each, laid out as in
+//! spill chunks: source, target, the `f64` weight's bit pattern, and the
+//! sequence.
+
+use std::io::{self, Read, Write};
+
+use crate::{
+    CandidateEdge, ConnectivityReport, CpuHnsw, EdgeHarvest, HnswParams, MstEdge,
+    SparsificationReport,
+};
+
+use super::{ForestOutput, fingerprint::RunIdentity};
+
+const INDEX_TAG: &[u8; 8] = b"CHTIDX02";
+const HARVEST_TAG: &[u8; 8] = b"CHTHRV02";
+const FOREST_TAG: &[u8; 8] = b"CHTMST02";
+
+/// Writes the index stage's HNSW graph with the parameters it was built with.
+pub(super) fn write_index(
+    writer: &mut impl Write,
+    run: RunIdentity,
+    params: &HnswParams,
+    index: &CpuHnsw,
+) -> io::Result<()> {
+    write_header(writer, INDEX_TAG, run)?;
+    write_word(writer, params.max_connections() as u64)?;
+    write_word(writer, params.ef_construction() as u64)?;
+    index.write_snapshot(writer)
+}
+
+/// Restores an HNSW graph, rejecting one built with other parameters.
+pub(super) fn read_index(
+    reader: &mut impl Read,
+    run: RunIdentity,
+    params: &HnswParams,
+) -> io::Result<CpuHnsw> {
+    read_header(reader, INDEX_TAG, run)?;
+    let stored = (read_usize(reader)?, read_usize(reader)?);
+    let expected = (params.max_connections(), params.ef_construction());
+    if stored != expected {
+        return Err(invalid_data(format!(
+            "index was built with (M, ef_construction) = {stored:?}, but the run uses {expected:?}"
+        )));
+    }
+    CpuHnsw::read_snapshot(params.clone(), reader)
+}
+
+/// Writes the index stage's harvest, noting whether an index snapshot
+/// accompanies it.
+pub(super) fn write_harvest(
+    writer: &mut impl Write,
+    run: RunIdentity,
+    has_index: bool,
+    harvest: &EdgeHarvest,
+) -> io::Result<()> {
+    write_header(writer, HARVEST_TAG, run)?;
+    write_word(writer, u64::from(has_index))?;
+    write_word(writer, harvest.len() as u64)?;
+    harvest.iter().try_for_each(|edge| {
+        let bits = edge.distance_f64().to_bits();
+        write_edge(
+            writer,
+            (edge.source(), edge.target(), bits, edge.sequence()),
+        )
+    })
+}
+
+/// Reads a harvest written by [`write_harvest`] and whether an index
+/// snapshot accompanies it.
+pub(super) fn read_harvest(
+    reader: &mut impl Read,
+    run: RunIdentity,
+) -> io::Result<(bool, EdgeHarvest)> {
+    read_header(reader, HARVEST_TAG, run)?;
+    let items = run.items;
+    let has_index = read_word(reader)? != 0;
+    let count = read_usize(reader)?;
+    let edges = (0..count)
+        .map(|_| {
+            let (source, target, bits, sequence) = read_edge(reader, items)?;
+            Ok(CandidateEdge::new_f64(
+                source,
+                target,
+                f64::from_bits(bits),
+                sequence,
+            ))
+        })
+        .collect::<io::Result<_>>()?;
+    Ok((has_index, EdgeHarvest::new(edges)))
+}
+
+/// Writes the spanning forest and the reports produced while building it.
+pub(super) fn write_forest(
+    writer: &mut impl Write,
+    (run, min_cluster_size): (RunIdentity, usize),
+    forest: &ForestOutput,
+) -> io::Result<()> {
+    write_header(writer, FOREST_TAG, run)?;
+    write_word(writer, min_cluster_size as u64)?;
+    let sizes = forest.connectivity.component_sizes();
+    write_word(writer, sizes.len() as u64)?;
+    for &size in sizes {
+        write_word(writer, size as u64)?;
+    }
+    write_word(writer, forest.connectivity.bridge_edges_added() as u64)?;
+    let sparsification = forest.sparsification.as_ref().map(|report| {
+        let counts = [
+            report.input_edges(),
+            report.retained_edges(),
+            report.per_node_edges(),
+        ];
+        counts.map(|count| count as u64)
+    });
+    write_word(writer, u64::from(sparsification.is_some()))?;
+    for word in sparsification.into_iter().flatten() {
+        write_word(writer, word)?;
+    }
+    write_word(writer, forest.edges.len() as u64)?;
+    forest.edges.iter().try_for_each(|edge| {
+        let bits = edge.weight_f64().to_bits();
+        write_edge(
+            writer,
+            (edge.source(), edge.target(), bits, edge.sequence()),
+        )
+    })
+}
+
+/// Reads a forest written by [`write_forest`], rejecting one built with a
+/// different minimum cluster size.
+pub(super) fn read_forest(
+    reader: &mut impl Read,
+    (run, min_cluster_size): (RunIdentity, usize),
+) -> io::Result<ForestOutput> {
+    read_header(reader, FOREST_TAG, run)?;
+    let items = run.items;
+    let stored = read_usize(reader)?;
+    if stored != min_cluster_size {
+        return Err(invalid_data(format!(
+            "forest was built with min_cluster_size {stored}, but the run uses {min_cluster_size}"
+        )));
+    }
+    let components = read_count(reader, items)?;
+    let sizes = (0..components)
+        .map(|_| read_usize(reader))
+        .collect::<io::Result<_>>()?;
+    let connectivity = ConnectivityReport::new(sizes, read_usize(reader)?);
+    let sparsification = match read_word(reader)? {
+        0 => None,
+        _ => {
+            let (input, retained) = (read_usize(reader)?, read_usize(reader)?);
+            let per_node = read_usize(reader)?;
+            Some(SparsificationReport::new(input, retained, per_node))
+        }
+    };
+    let count = read_count(reader, items)?;
+    let edges = (0..count)
+        .map(|_| {
+            let (source, target, bits, sequence) = read_edge(reader, items)?;
+            Ok(MstEdge::new_f64(
+                source,
+                target,
+                f64::from_bits(bits),
+                sequence,
+            ))
+        })
+        .collect::<io::Result<_>>()?;
+    Ok(ForestOutput {
+        edges,
+        connectivity,
+        sparsification,
+    })
+}
+
+fn write_header(writer: &mut impl Write, tag: &[u8; 8], run: RunIdentity) -> io::Result<()> {
+    writer.write_all(tag)?;
+    write_word(writer, run.items as u64)?;
+    write_word(writer, run.fingerprint)
+}
+
+/// Checks the tag and that the file belongs to `run`.
+fn read_header(reader: &mut impl Read, tag: &[u8; 8], run: RunIdentity) -> io::Result<()> {
+    let mut found = [0; 8];
+    reader.read_exact(&mut found)?;
+    if &found != tag {
+        return Err(invalid_data(format!(
+            "expected a `{}` file, found tag {found:?}",
+            String::from_utf8_lossy(tag)
+        )));
+    }
+    let stored = read_usize(reader)?;
+    if stored != run.items {
+        return Err(invalid_data(format!(
+            "checkpoint covers {stored} points, but the source has {}",
+            run.items
+        )));
+    }
+    if read_word(reader)? != run.fingerprint {
+        return Err(invalid_data(
+            "checkpoint was written for a different source or stage configuration",
+        ));
+    }
+    Ok(())
+}
+
+fn write_edge(writer: &mut impl Write, edge: (usize, usize, u64, u64)) -> io::Result<()> {
+    let (source, target, bits, sequence) = edge;
+    for word in [source as u64, target as u64, bits, sequence] {
+        write_word(writer, word)?;
+    }
+    Ok(())
+}
+
+fn read_edge(reader: &mut impl Read, items: usize) -> io::Result<(usize, usize, u64, u64)> {
+    let (source, target) = (read_usize(reader)?, read_usize(reader)?);
+    if source >= items || target >= items {
+        return Err(invalid_data(format!(
+            "edge ({source}, {target}) lies outside the {items} points"
+        )));
+    }
+    Ok((source, target, read_word(reader)?, read_word(reader)?))
+}
+
+/// Reads a count of at most `limit` entries.
+fn read_count(reader: &mut impl Read, limit: usize) -> io::Result<usize> {
+    let count = read_usize(reader)?;
+    if count > limit {
+        return Err(invalid_data(format!(
+            "{count} entries exceed the {limit} points"
+        )));
+    }
+    Ok(count)
+}
+
+fn write_word(writer: &mut impl Write, word: u64) -> io::Result<()> {
+    writer.write_all(&word.to_le_bytes())
+}
+
+fn read_word(reader: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn read_usize(reader: &mut impl Read) -> io::Result<usize> {
+    usize::try_from(read_word(reader)?).map_err(invalid_data)
+}
+
+fn invalid_data(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
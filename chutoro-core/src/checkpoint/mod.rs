@@ -0,0 +1,257 @@
+//! Stage checkpoints that let a failed run resume where it stopped.
+//!
+//! With [`crate::ChutoroBuilder::with_checkpoint_directory`] set, a run
+//! writes the output of its two expensive stages to the directory as they
+//! complete: `index.bin` holds the HNSW graph and `harvest.bin` its raw
+//! candidate edges, and `mst.bin` holds the spanning forest with its
+//! connectivity and sparsification reports. [`crate::Chutoro::resume`]
+//! reloads the latest of them and runs only the remaining stages.
+//!
+//! Every checkpoint records a fingerprint of the source and of the options
+//! that shape these stages, and a resumed run rejects checkpoints whose
+//! fingerprint differs from its own rather than reuse another run's output.
+//!
+//! Each file is written to a `.partial` sibling and renamed into place, so a
+//! crash mid-write never leaves a truncated checkpoint behind. `harvest.bin`
+//! is written after `index.bin` and so marks the index stage complete. A
+//! fresh run removes the files of any earlier run before starting.
+
+mod fingerprint;
+mod format;
+
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::{
+    ConnectivityReport, DataSource, MstEdge, Result, SparsificationReport,
+    builder::PipelineOptions, distance_policy::DistanceGuard, error::ChutoroError,
+    graph_builder::BuiltGraph, stages::StageContext,
+};
+
+use self::{
+    fingerprint::RunIdentity,
+    format::{read_forest, read_harvest, read_index, write_forest, write_harvest, write_index},
+};
+
+const INDEX_FILE: &str = "index.bin";
+const HARVEST_FILE: &str = "harvest.bin";
+const FOREST_FILE: &str = "mst.bin";
+
+/// Where a run writes its checkpoints, and whether it resumes from them.
+#[derive(Debug, Clone)]
+pub(crate) struct CheckpointOptions {
+    pub(crate) directory: PathBuf,
+    pub(crate) resume: bool,
+}
+
+/// Checks that runs configured with `options` can checkpoint to
+/// `directory`.
+///
+/// # Errors
+/// Returns [`ChutoroError::Checkpoint`] when `directory` is not an existing
+/// directory, or when sampling or edge provenance is configured: sampled
+/// runs build their index outside the checkpointed stages, and provenance is
+/// not recorded in checkpoints.
+pub(crate) fn validate_checkpoints(directory: &Path, options: &PipelineOptions) -> Result<()> {
+    let reason = if !directory.is_dir() {
+        "checkpoint directory does not exist or is not a directory"
+    } else if options.sample.is_some() {
+        "sampled runs build their index outside the checkpointed stages"
+    } else if options.edge_provenance {
+        "edge provenance is not recorded in checkpoints"
+    } else {
+        return Ok(());
+    };
+    Err(checkpoint_error(directory, reason))
+}
+
+/// The spanning forest handed to hierarchy extraction, with the reports
+/// produced while building it.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ForestOutput {
+    pub(crate) edges: Vec<MstEdge>,
+    pub(crate) connectivity: ConnectivityReport,
+    pub(crate) sparsification: Option<SparsificationReport>,
+}
+
+/// The checkpoint files of one run.
+#[derive(Debug)]
+pub(crate) struct Checkpoints<'a> {
+    directory: &'a Path,
+    run: RunIdentity,
+    min_cluster_size: usize,
+    resume: bool,
+}
+
+impl<'a> Checkpoints<'a> {
+    /// Opens the checkpoints configured in `options`, if any, for a run over
+    /// the first `items` points of `source` with the given minimum cluster
+    /// size.
+    ///
+    /// # Errors
+    /// Returns [`ChutoroError::Checkpoint`] when a fresh run cannot remove
+    /// the files of an earlier one.
+    pub(crate) fn open<D: DataSource>(
+        source: &D,
+        options: &'a PipelineOptions,
+        (items, min_cluster_size): (usize, usize),
+    ) -> Result<Option<Self>> {
+        let Some(config) = &options.checkpoint else {
+            return Ok(None);
+        };
+        let checkpoints = Self {
+            directory: &config.directory,
+            run: RunIdentity::new(source, items, options),
+            min_cluster_size,
+            resume: config.resume,
+        };
+        if !config.resume {
+            for name in [FOREST_FILE, HARVEST_FILE, INDEX_FILE] {
+                checkpoints.remove(name)?;
+            }
+        }
+        Ok(Some(checkpoints))
+    }
+
+    /// Restores the index stage's output when resuming from a checkpoint of
+    /// it, and otherwise builds it and checkpoints the result.
+    ///
+    /// # Errors
+    /// Returns [`ChutoroError::Checkpoint`] when a checkpoint cannot be read
+    /// or written, and the errors of [`BuiltGraph::build`].
+    pub(crate) fn graph<D: DataSource + Sync>(
+        &self,
+        source: &D,
+        context: &StageContext<'_>,
+        options: &PipelineOptions,
+    ) -> Result<BuiltGraph> {
+        if let Some(graph) = self.load_graph(options)? {
+            return Ok(graph);
+        }
+        let graph = BuiltGraph::build(source, context, options)?;
+        let (index, harvest) = match &graph {
+            BuiltGraph::Hnsw(index, harvest, _) => (Some(index), harvest),
+            BuiltGraph::NnDescent(harvest, _) => (None, harvest),
+        };
+        if let Some(index) = index {
+            let params = &options.hnsw_params;
+            self.write(INDEX_FILE, |writer| {
+                write_index(writer, self.run, params, index)
+            })?;
+        }
+        self.write(HARVEST_FILE, |writer| {
+            write_harvest(writer, self.run, index.is_some(), harvest)
+        })?;
+        Ok(graph)
+    }
+
+    /// Loads the forest stage's output when resuming from a checkpoint of
+    /// it.
+    ///
+    /// # Errors
+    /// Returns [`ChutoroError::Checkpoint`] when the checkpoint cannot be
+    /// read or belongs to a different run.
+    pub(crate) fn load_forest(&self) -> Result<Option<ForestOutput>> {
+        let run = (self.run, self.min_cluster_size);
+        self.read(FOREST_FILE, |reader| read_forest(reader, run))
+    }
+
+    /// Checkpoints the forest stage's output.
+    ///
+    /// # Errors
+    /// Returns [`ChutoroError::Checkpoint`] when the checkpoint cannot be
+    /// written.
+    pub(crate) fn save_forest(&self, forest: &ForestOutput) -> Result<()> {
+        let run = (self.run, self.min_cluster_size);
+        self.write(FOREST_FILE, |writer| write_forest(writer, run, forest))
+    }
+
+    fn load_graph(&self, options: &PipelineOptions) -> Result<Option<BuiltGraph>> {
+        let run = self.run;
+        let Some((has_index, harvest)) =
+            self.read(HARVEST_FILE, |reader| read_harvest(reader, run))?
+        else {
+            return Ok(None);
+        };
+        if !has_index {
+            let guard = DistanceGuard::new(options.distance_policy);
+            return Ok(Some(BuiltGraph::NnDescent(harvest, guard)));
+        }
+        let params = &options.hnsw_params;
+        let Some(index) = self.read(INDEX_FILE, |reader| read_index(reader, run, params))? else {
+            return Err(checkpoint_error(
+                &self.path(INDEX_FILE),
+                "the harvest checkpoint has no index beside it",
+            ));
+        };
+        Ok(Some(BuiltGraph::Hnsw(Box::new(index), harvest, None)))
+    }
+
+    /// Reads `name` with `body` when resuming and the file exists.
+    fn read<T>(
+        &self,
+        name: &str,
+        body: impl FnOnce(&mut BufReader<File>) -> io::Result<T>,
+    ) -> Result<Option<T>> {
+        if !self.resume {
+            return Ok(None);
+        }
+        let path = self.path(name);
+        let read = match File::open(&path) {
+            Ok(file) => body(&mut BufReader::new(file)).map(Some),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        };
+        read.map_err(|error| checkpoint_error(&path, &error.to_string()))
+    }
+
+    /// Writes `name` with `body` through a `.partial` file renamed into place.
+    fn write(
+        &self,
+        name: &str,
+        body: impl FnOnce(&mut BufWriter<File>) -> io::Result<()>,
+    ) -> Result<()> {
+        let path = self.path(name);
+        let partial = path.with_extension("partial");
+        let written = File::create(&partial)
+            .and_then(|file| {
+                let mut writer = BufWriter::new(file);
+                body(&mut writer)?;
+                writer.flush()?;
+                writer.get_ref().sync_all()
+            })
+            .and_then(|()| fs::rename(&partial, &path));
+        written.map_err(|error| {
+            let _ = fs::remove_file(&partial);
+            checkpoint_error(&path, &error.to_string())
+        })
+    }
+
+    fn remove(&self, name: &str) -> Result<()> {
+        let path = self.path(name);
+        match fs::remove_file(&path) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => {
+                Err(checkpoint_error(&path, &error.to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.directory.join(name)
+    }
+}
+
+fn checkpoint_error(path: &Path, reason: &str) -> ChutoroError {
+    ChutoroError::Checkpoint {
+        path: Arc::from(path),
+        reason: Arc::from(reason),
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,187 @@
+//! Unit tests for checkpoint encoding.
+
+use std::{
+    io::Cursor,
+    num::NonZeroUsize,
+    sync::{Arc, atomic::AtomicUsize},
+};
+
+use rand::{Rng, SeedableRng, rngs::SmallRng};
+use rstest::rstest;
+
+use super::{fingerprint::RunIdentity, format::*, *};
+use crate::{CandidateEdge, CpuHnsw, EdgeHarvest, HnswParams, test_utils::CountingSource};
+
+fn source(items: usize) -> CountingSource {
+    let mut rng = SmallRng::seed_from_u64(5);
+    let data = (0..items).map(|_| rng.gen_range(0.0..100.0)).collect();
+    CountingSource::new(data, Arc::new(AtomicUsize::new(0)))
+}
+
+/// A run over `items` points with a fixed fingerprint.
+fn run(items: usize) -> RunIdentity {
+    RunIdentity {
+        items,
+        fingerprint: 0x5eed,
+    }
+}
+
+fn params() -> HnswParams {
+    HnswParams::new(4, 16).expect("params").with_rng_seed(9)
+}
+
+/// Every `(node, level, neighbour)` link and the `(node, level)` entry point.
+type Adjacency = (Vec<(usize, usize, usize)>, Option<(usize, usize)>);
+
+fn adjacency(index: &CpuHnsw) -> Adjacency {
+    let view = index.read_view().expect("graph lock is healthy");
+    let entry = view.entry().map(|entry| (entry.node, entry.level));
+    (view.edges().collect(), entry)
+}
+
+fn forest() -> ForestOutput {
+    ForestOutput {
+        edges: vec![MstEdge::new(0, 1, 0.5, 3), MstEdge::new_f64(2, 3, 1.25, 7)],
+        connectivity: ConnectivityReport::new(vec![2, 2], 0),
+        sparsification: Some(SparsificationReport::new(10, 6, 2)),
+    }
+}
+
+#[rstest]
+fn index_round_trip_restores_the_graph() {
+    let data = source(60);
+    let (index, _) = CpuHnsw::build_with_edges(&data, params()).expect("build");
+    let mut bytes = Vec::new();
+    write_index(&mut bytes, run(60), &params(), &index).expect("write");
+
+    let restored = read_index(&mut Cursor::new(bytes), run(60), &params()).expect("read");
+
+    assert_eq!(restored.len(), index.len());
+    assert_eq!(adjacency(&restored), adjacency(&index));
+    let ef = NonZeroUsize::new(8).expect("non-zero");
+    for query in [0, 17, 59] {
+        assert_eq!(
+            restored.search(&data, query, ef).expect("search"),
+            index.search(&data, query, ef).expect("search")
+        );
+    }
+}
+
+#[rstest]
+#[case::no_index(false)]
+#[case::with_index(true)]
+fn harvest_round_trip_preserves_edges(#[case] has_index: bool) {
+    let harvest = EdgeHarvest::new(vec![
+        CandidateEdge::new(3, 1, 2.0, 1),
+        CandidateEdge::new_f64(0, 2, 0.1, 0),
+    ]);
+    let mut bytes = Vec::new();
+    write_harvest(&mut bytes, run(4), has_index, &harvest).expect("write");
+
+    let restored = read_harvest(&mut Cursor::new(bytes), run(4)).expect("read");
+
+    assert_eq!(restored, (has_index, harvest));
+}
+
+#[rstest]
+#[case::sparsified(forest())]
+#[case::unsparsified(ForestOutput { sparsification: None, ..forest() })]
+fn forest_round_trip_preserves_edges_and_reports(#[case] forest: ForestOutput) {
+    let mut bytes = Vec::new();
+    write_forest(&mut bytes, (run(4), 2), &forest).expect("write");
+
+    let restored = read_forest(&mut Cursor::new(bytes), (run(4), 2)).expect("read");
+
+    assert_eq!(restored, forest);
+}
+
+#[rstest]
+#[case::other_point_count((run(5), 2), "covers 4 points")]
+#[case::other_fingerprint((RunIdentity { fingerprint: 1, ..run(4) }, 2), "different source")]
+#[case::other_min_cluster_size((run(4), 3), "min_cluster_size 2")]
+fn forest_from_another_run_is_rejected(
+    #[case] other: (RunIdentity, usize),
+    #[case] expected: &str,
+) {
+    let mut bytes = Vec::new();
+    write_forest(&mut bytes, (run(4), 2), &forest()).expect("write");
+
+    let error = read_forest(&mut Cursor::new(bytes), other).expect_err("run differs");
+
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert!(error.to_string().contains(expected), "{error}");
+}
+
+#[rstest]
+fn index_with_other_parameters_is_rejected() {
+    let (index, _) = CpuHnsw::build_with_edges(&source(20), params()).expect("build");
+    let mut bytes = Vec::new();
+    write_index(&mut bytes, run(20), &params(), &index).expect("write");
+    let other = HnswParams::new(8, 16).expect("params");
+
+    let error = read_index(&mut Cursor::new(bytes), run(20), &other).err();
+
+    assert_eq!(
+        error.map(|error| error.kind()),
+        Some(io::ErrorKind::InvalidData)
+    );
+}
+
+#[rstest]
+fn files_of_another_kind_are_rejected() {
+    let mut bytes = Vec::new();
+    write_forest(&mut bytes, (run(4), 2), &forest()).expect("write");
+
+    let error = read_harvest(&mut Cursor::new(bytes), run(4)).expect_err("wrong tag");
+
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+}
+
+#[rstest]
+fn truncated_files_are_rejected() {
+    let mut bytes = Vec::new();
+    write_forest(&mut bytes, (run(4), 2), &forest()).expect("write");
+    bytes.truncate(bytes.len() - 5);
+
+    let error = read_forest(&mut Cursor::new(bytes), (run(4), 2)).expect_err("truncated");
+
+    assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[rstest]
+fn edges_outside_the_run_are_rejected() {
+    let harvest = EdgeHarvest::new(vec![CandidateEdge::new(0, 9, 1.0, 0)]);
+    let mut bytes = Vec::new();
+    write_harvest(&mut bytes, run(4), false, &harvest).expect("write");
+
+    let error = read_harvest(&mut Cursor::new(bytes), run(4)).expect_err("edge out of range");
+
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+}
+
+#[rstest]
+fn fingerprints_tell_sources_and_stage_options_apart() {
+    let options = PipelineOptions::default();
+    let identity = RunIdentity::new(&source(40), 40, &options);
+    let shifted = CountingSource::new(
+        (0..40).map(|point| point as f32).collect(),
+        Arc::new(AtomicUsize::new(0)),
+    );
+    let connected = PipelineOptions {
+        connect_components: !options.connect_components,
+        ..PipelineOptions::default()
+    };
+    let capped = PipelineOptions {
+        max_cluster_size: NonZeroUsize::new(10),
+        ..PipelineOptions::default()
+    };
+
+    assert_eq!(RunIdentity::new(&source(40), 40, &options), identity);
+    assert_ne!(RunIdentity::new(&shifted, 40, &options), identity);
+    assert_ne!(RunIdentity::new(&source(40), 40, &connected), identity);
+    assert_eq!(
+        RunIdentity::new(&source(40), 40, &capped),
+        identity,
+        "hierarchy options act after the checkpointed stages"
+    );
+}
@@ -0,0 +1,124 @@
+//! Backend probing for [`ExecutionStrategy`] resolution.
+//!
+//! [`ExecutionStrategy::Auto`] walks a fallback chain when a run starts: the
+//! GPU backend when it is compiled in and a device is present, then the CPU
+//! pipeline on a multi-threaded pool, then the CPU pipeline on a single
+//! thread when the host has one core or the run starts on a one-thread pool.
+//! The backend that ran is recorded in the [`crate::ClusteringResult`].
+
+use crate::{Result, builder::ExecutionStrategy, datasource::DataSource, result::ClusteringResult};
+
+use super::Chutoro;
+
+const CPU_PATH_AVAILABLE: bool = cfg!(feature = "cpu");
+// The `gpu` feature currently exposes the orchestration surface only;
+// no accelerated implementation ships yet.
+const GPU_PATH_AVAILABLE: bool = false;
+
+/// Backend that executed a clustering run.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::Backend;
+///
+/// assert_eq!(Backend::CpuParallel.as_str(), "cpu-parallel");
+/// assert!(Backend::CpuSequential.is_cpu());
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum Backend {
+    /// The GPU implementation.
+    Gpu,
+    /// The CPU pipeline on a multi-threaded Rayon pool.
+    CpuParallel,
+    /// The CPU pipeline on a dedicated one-thread Rayon pool, because the
+    /// host has a single core or the pool the run started on had one thread.
+    CpuSequential,
+}
+
+impl Backend {
+    /// Returns a stable, lowercase name for logs and summaries.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Gpu => "gpu",
+            Self::CpuParallel => "cpu-parallel",
+            Self::CpuSequential => "cpu-sequential",
+        }
+    }
+
+    /// Returns whether the backend is one of the CPU pipelines.
+    #[must_use]
+    pub const fn is_cpu(self) -> bool {
+        matches!(self, Self::CpuParallel | Self::CpuSequential)
+    }
+
+    /// Returns the CPU backend the host and the current Rayon pool support.
+    #[cfg(feature = "cpu")]
+    fn cpu() -> Self {
+        let cores = std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get);
+        if cores > 1 && rayon::current_num_threads() > 1 {
+            Self::CpuParallel
+        } else {
+            Self::CpuSequential
+        }
+    }
+
+    #[cfg(not(feature = "cpu"))]
+    const fn cpu() -> Self {
+        Self::CpuSequential
+    }
+}
+
+/// Returns whether a GPU backend is compiled in and has a device to run on.
+///
+/// No accelerated implementation ships yet, so there is no device to probe.
+const fn gpu_ready() -> bool {
+    GPU_PATH_AVAILABLE
+}
+
+impl Chutoro {
+    /// Resolves the configured strategy to the backend this run will use.
+    pub(super) fn choose_backend(&self) -> Backend {
+        match self.execution_strategy {
+            ExecutionStrategy::Auto if gpu_ready() || !CPU_PATH_AVAILABLE => Backend::Gpu,
+            ExecutionStrategy::Auto | ExecutionStrategy::CpuOnly => Backend::cpu(),
+            ExecutionStrategy::GpuPreferred => Backend::Gpu,
+        }
+    }
+
+    /// Runs the CPU pipeline on a one-thread Rayon pool, so every stage
+    /// executes in order on a single worker whatever pool the caller is on.
+    ///
+    /// # Errors
+    /// Returns [`ChutoroError::BackendUnavailable`](crate::ChutoroError::BackendUnavailable)
+    /// when the pool cannot be started, along with any error the pipeline
+    /// reports.
+    pub(super) fn run_cpu_sequential<D: DataSource + Sync>(
+        &self,
+        source: &D,
+        items: usize,
+    ) -> Result<ClusteringResult> {
+        #[cfg(feature = "cpu")]
+        if rayon::current_num_threads() > 1 {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(1)
+                .build()
+                .map_err(|_| crate::ChutoroError::BackendUnavailable {
+                    requested: self.execution_strategy,
+                })?;
+            return pool.install(|| self.run_cpu(source, items));
+        }
+        self.run_cpu(source, items)
+    }
+
+    /// Returns whether no backend the strategy accepts is available.
+    pub(super) fn is_backend_unavailable(&self) -> bool {
+        match self.execution_strategy {
+            ExecutionStrategy::Auto => !(CPU_PATH_AVAILABLE || gpu_ready()),
+            ExecutionStrategy::CpuOnly => !CPU_PATH_AVAILABLE,
+            ExecutionStrategy::GpuPreferred => !gpu_ready(),
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Resuming runs from stage checkpoints.
+
+use std::path::Path;
+
+use super::Chutoro;
+use crate::{
+    DataSource, Result,
+    checkpoint::{CheckpointOptions, validate_checkpoints},
+    result::ClusteringResult,
+};
+
+impl Chutoro {
+    /// Returns the directory runs write stage checkpoints to, if configured.
+    #[must_use]
+    pub fn checkpoint_directory(&self) -> Option<&Path> {
+        self.pipeline
+            .checkpoint
+            .as_ref()
+            .map(|checkpoint| checkpoint.directory.as_path())
+    }
+
+    /// Resumes a run over `source` from the checkpoints in `directory`.
+    ///
+    /// Loads the latest stage checkpointed there, the spanning forest or
+    /// else the index and its harvest, and runs only the stages after it,
+    /// writing checkpoints for those it completes. When the directory holds
+    /// no checkpoints the run starts from scratch. `directory` need not be
+    /// the one configured with
+    /// [`crate::ChutoroBuilder::with_checkpoint_directory`], but `source`
+    /// and the configuration must match those of the interrupted run:
+    /// checkpoints record the point count, the minimum cluster size, and a
+    /// fingerprint of the source and of the options that shape the index,
+    /// harvest, and forest stages, and are rejected when any of these
+    /// differ. Fingerprinting evaluates up to 16 distances.
+    ///
+    /// Results carry timings for the executed stages only, no
+    /// distance-cache warnings for a restored index, and the connectivity
+    /// and sparsification reports of the run that wrote the forest.
+    ///
+    /// # Errors
+    /// Returns [`crate::ChutoroError::Checkpoint`] when `directory` is not
+    /// an existing directory, when sampling or edge provenance is
+    /// configured, or when a checkpoint cannot be read or belongs to a
+    /// different run, and the errors of [`Self::run`] otherwise.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// # use chutoro_core::{ChutoroBuilder, DataSource, DataSourceError};
+    /// # struct Dummy(Vec<f32>);
+    /// # impl DataSource for Dummy {
+    /// #     fn len(&self) -> usize { self.0.len() }
+    /// #     fn name(&self) -> &str { "dummy" }
+    /// #     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+    /// #         Ok((self.0[i] - self.0[j]).abs())
+    /// #     }
+    /// # }
+    /// let source = Dummy(vec![1.0, 2.0, 4.0, 8.0]);
+    /// let chutoro = ChutoroBuilder::new()
+    ///     .with_min_cluster_size(2)
+    ///     .with_checkpoint_directory("checkpoints")
+    ///     .build()?;
+    /// let result = match chutoro.run(&source) {
+    ///     Ok(result) => result,
+    ///     Err(_) => chutoro.resume(&source, "checkpoints")?,
+    /// };
+    /// # Ok::<(), chutoro_core::ChutoroError>(())
+    /// ```
+    pub fn resume<D: DataSource + Sync>(
+        &self,
+        source: &D,
+        directory: impl AsRef<Path>,
+    ) -> Result<ClusteringResult> {
+        let directory = directory.as_ref();
+        validate_checkpoints(directory, &self.pipeline)?;
+        let mut pipeline = self.pipeline.clone();
+        pipeline.checkpoint = Some(CheckpointOptions {
+            directory: directory.to_path_buf(),
+            resume: true,
+        });
+        self.clone().with_pipeline_options(pipeline).run(source)
+    }
+}
@@ -0,0 +1,51 @@
+//! Checks that a source and the configured seed labels suit a run before
+//! any stage starts.
+
+use std::sync::Arc;
+
+use tracing::warn;
+
+use super::Chutoro;
+use crate::{ClusterId, Result, datasource::DataSource, error::ChutoroError};
+
+impl Chutoro {
+    /// Rejects sources the configured pipeline cannot cluster and backends
+    /// missing from this build.
+    pub(super) fn check_source<D: DataSource>(&self, source: &D, items: usize) -> Result<()> {
+        if items == 0 {
+            warn!(
+                data_source = source.name(),
+                "data source is empty, returning error"
+            );
+            return Err(ChutoroError::EmptySource {
+                data_source: Arc::from(source.name()),
+            });
+        }
+        if items < self.min_cluster_size.get() {
+            return Err(ChutoroError::InsufficientItems {
+                data_source: Arc::from(source.name()),
+                items,
+                min_cluster_size: self.min_cluster_size,
+            });
+        }
+        match self.backend_unavailable_error() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the configured seed labels once every seeded point is known
+    /// to lie within the data source.
+    pub(super) fn checked_seed_labels(
+        &self,
+        items: usize,
+    ) -> Result<Option<&[(usize, ClusterId)]>> {
+        let seeds = self.pipeline.seed_labels.as_deref();
+        match seeds.and_then(|seeds| crate::seed_labels::out_of_range(seeds, items)) {
+            Some(reason) => Err(ChutoroError::InvalidSeedLabels {
+                reason: Arc::from(reason),
+            }),
+            None => Ok(seeds),
+        }
+    }
+}
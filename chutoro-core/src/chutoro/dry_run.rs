@@ -0,0 +1,51 @@
+//! Dry runs that validate a configuration against a source without
+//! clustering it.
+
+use std::sync::Arc;
+
+use super::Chutoro;
+use crate::{
+    Result, SeedStream,
+    datasource::{DataSource, validate_source},
+    distance_transform::TransformSource,
+    dry_run::{DRY_RUN_PROBES, DryRunReport},
+    error::ChutoroError,
+};
+
+impl Chutoro {
+    /// Checks that [`Chutoro::run`] could cluster `source`, without building
+    /// the index.
+    ///
+    /// The dry run applies the same source and backend checks as a run,
+    /// estimates memory as [`Chutoro::estimate_resources`] does, and probes
+    /// 1,024 seeded pairs of points with [`validate_source`], after any
+    /// configured [`crate::DistanceTransform`]. Probes are drawn from the
+    /// master seed when one is set, so repeated dry runs evaluate the same
+    /// pairs. An estimate above `max_bytes` is reported rather than returned
+    /// as an error, so one dry run surfaces every problem; see
+    /// [`DryRunReport::passed`].
+    ///
+    /// # Errors
+    /// Returns [`ChutoroError::EmptySource`], [`ChutoroError::InsufficientItems`],
+    /// or [`ChutoroError::BackendUnavailable`] exactly as [`Chutoro::run`]
+    /// would, and [`ChutoroError::DataSource`] when a probed distance cannot
+    /// be evaluated.
+    pub fn dry_run<D: DataSource>(&self, source: &D) -> Result<DryRunReport> {
+        let items = source.len();
+        self.check_source(source, items)?;
+        let source = &TransformSource::new(source, self.pipeline.distance_transform);
+        let seed = SeedStream::DryRunProbe.derive(self.pipeline.seed.unwrap_or_default());
+        let validation = validate_source(source, DRY_RUN_PROBES, seed).map_err(|error| {
+            ChutoroError::DataSource {
+                data_source: Arc::from(source.name()),
+                error,
+            }
+        })?;
+        Ok(DryRunReport::new(
+            self.estimate_resources(source),
+            self.max_bytes,
+            self.pipeline.distance_policy,
+            validation,
+        ))
+    }
+}
@@ -0,0 +1,195 @@
+//! Clustering over a caller-supplied k-nearest-neighbour graph.
+//!
+//! Applications that already hold a k-NN graph, for example from FAISS or
+//! ScaNN, can skip the HNSW build entirely. Core distances are read from each
+//! node's incident edges, unless the builder supplied them, after which the supplied edges are re-weighted with
+//! mutual-reachability distances and fed through the usual MST and hierarchy
+//! stages, making [`Chutoro`] an HDBSCAN-on-a-graph tool.
+
+use std::sync::Arc;
+
+use tracing::instrument;
+
+use super::Chutoro;
+use crate::{
+    CondensedTree, DistancePrecision, EdgeHarvest, MinimumSpanningForest, Result,
+    connectivity::ForestComponents,
+    cpu_pipeline::{
+        apply_edge_budget, extract_clustering, map_cpu_mst_error, supplied_core_distances,
+    },
+    error::ChutoroError,
+    graph_builder::{CoreSelection, graph_core_distances, graph_core_distances_f64},
+    parallel_kruskal_owned,
+    precision::CoreDistances,
+    result::ClusteringResult,
+    stages::StageArtefact,
+    timings::Stage,
+};
+
+/// Name reported in errors about a supplied graph, which has no data source.
+const GRAPH_NAME: &str = "knn_graph";
+
+impl Chutoro {
+    /// Clusters the `node_count` nodes of a precomputed k-NN graph.
+    ///
+    /// `edges` holds the graph's weighted edges in either or both
+    /// directions; each node's core distance is the distance to its
+    /// `min_cluster_size`-th nearest neighbour among its incident edges, or
+    /// to its farthest neighbour when it has fewer. Graphs with at least
+    /// `min_cluster_size` neighbours per node therefore reproduce the core
+    /// distances of a full run.
+    ///
+    /// The configured minimum cluster size, edge budget, mutual-neighbour
+    /// filter, supplied core distances, distance precision, and artefact
+    /// hook apply, so [`crate::DistancePrecision::Double`] weights the tree
+    /// with each edge's [`crate::CandidateEdge::distance_f64`]; core
+    /// distances are computed before the filter. The HNSW parameters, prebuilt index, sampling, distance policy,
+    /// distance budget, noise reassignment, and custom stages do not, because
+    /// no distances are evaluated. Disconnected graphs are clustered per component and
+    /// reported by [`ClusteringResult::connectivity`]; they cannot be bridged
+    /// without a data source, so `connect_components` is ignored.
+    ///
+    /// # Errors
+    /// Returns [`ChutoroError::EmptySource`] when `node_count` is zero,
+    /// [`ChutoroError::InsufficientItems`] when it is below
+    /// `min_cluster_size`, and [`ChutoroError::InvalidKnnGraph`] when an edge
+    /// references a node outside the graph or carries a negative or
+    /// non-finite distance. Returns [`ChutoroError::InvalidCoreDistances`]
+    /// when supplied core distances do not cover exactly `node_count` nodes.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{CandidateEdge, ChutoroBuilder, EdgeHarvest};
+    ///
+    /// // Points at 0, 1, 2, 3 and 20, 21, 22, 23 on a line, each linked to
+    /// // its nearest neighbours.
+    /// let positions = [0.0_f32, 1.0, 2.0, 3.0, 20.0, 21.0, 22.0, 23.0];
+    /// let mut edges = Vec::new();
+    /// for (i, a) in positions.iter().enumerate() {
+    ///     for (j, b) in positions.iter().enumerate().skip(i + 1).take(2) {
+    ///         edges.push(CandidateEdge::new(i, j, (a - b).abs(), edges.len() as u64));
+    ///     }
+    /// }
+    /// let chutoro = ChutoroBuilder::new().with_min_cluster_size(2).build()?;
+    /// let result = chutoro.cluster_from_knn_graph(8, EdgeHarvest::new(edges))?;
+    /// assert_eq!(result.cluster_count(), 2);
+    /// assert_eq!(result.assignments()[0], result.assignments()[3]);
+    /// assert_ne!(result.assignments()[0], result.assignments()[4]);
+    /// # Ok::<(), chutoro_core::ChutoroError>(())
+    /// ```
+    #[instrument(
+        name = "core.cluster_from_knn_graph",
+        err,
+        skip(self, edges),
+        fields(nodes = node_count, edges = edges.len(), min_cluster_size = %self.min_cluster_size()),
+    )]
+    pub fn cluster_from_knn_graph(
+        &self,
+        node_count: usize,
+        edges: EdgeHarvest,
+    ) -> Result<ClusteringResult> {
+        self.logged(GRAPH_NAME, node_count, || {
+            self.cluster_graph(node_count, edges)
+        })
+    }
+
+    fn cluster_graph(&self, node_count: usize, edges: EdgeHarvest) -> Result<ClusteringResult> {
+        let min_cluster_size = self.min_cluster_size();
+        if node_count == 0 {
+            return Err(ChutoroError::EmptySource {
+                data_source: Arc::from(GRAPH_NAME),
+            });
+        }
+        if node_count < min_cluster_size.get() {
+            return Err(ChutoroError::InsufficientItems {
+                data_source: Arc::from(GRAPH_NAME),
+                items: node_count,
+                min_cluster_size,
+            });
+        }
+        validate_edges(node_count, &edges)?;
+
+        let stages = &self.pipeline.stages;
+        let mut clock = stages.clock();
+        clock.lap(Stage::HnswBuild);
+        let core_distances = self.graph_core_distances(node_count, &edges)?;
+        let edges = match self.mutual_neighbours() {
+            Some(k) => edges.mutualise(k),
+            None => edges,
+        };
+        let mutual_harvest = core_distances.weight_graph(&edges);
+        let (mutual_harvest, sparsification) =
+            apply_edge_budget(mutual_harvest, node_count, self.edge_budget());
+        clock.lap(Stage::EdgeHarvest);
+        stages.notify(StageArtefact::Harvest(&mutual_harvest));
+
+        let forest = parallel_kruskal_owned(node_count, mutual_harvest)
+            .map(MinimumSpanningForest::into_edges)
+            .map_err(map_cpu_mst_error)?;
+        let connectivity = ForestComponents::from_edges(node_count, &forest).into_report(0);
+        clock.lap(Stage::Mst);
+        stages.notify(StageArtefact::Mst(&forest));
+
+        let (clustering, condensed) = extract_clustering(
+            node_count,
+            &forest,
+            self.pipeline.hierarchy_config(min_cluster_size),
+            None,
+        )?;
+        stages.notify(StageArtefact::CondensedTree(CondensedTree::new(&condensed)));
+        clock.lap(Stage::Hierarchy);
+
+        Ok(clustering
+            .with_sparsification(sparsification)
+            .with_connectivity(Some(connectivity))
+            .with_timings(Some(clock.finish()))
+            .with_report_warnings())
+    }
+}
+
+impl Chutoro {
+    /// Returns the supplied core distances, or reads them from the graph at
+    /// the configured precision.
+    fn graph_core_distances(
+        &self,
+        node_count: usize,
+        edges: &EdgeHarvest,
+    ) -> Result<CoreDistances> {
+        let precision = self.pipeline.distance_precision;
+        if let Some(supplied) = &self.pipeline.core_distances {
+            return supplied_core_distances(supplied, node_count)
+                .map(|core| CoreDistances::new(core, precision));
+        }
+        let core = CoreSelection {
+            items: node_count,
+            min_cluster_size: self.min_cluster_size(),
+            weights: None,
+        };
+        Ok(match precision {
+            DistancePrecision::Single => CoreDistances::Single(graph_core_distances(edges, core)),
+            DistancePrecision::Double => {
+                CoreDistances::Double(graph_core_distances_f64(edges, core))
+            }
+        })
+    }
+}
+
+/// Rejects edges that would index past the graph or poison the MST weights.
+fn validate_edges(node_count: usize, edges: &EdgeHarvest) -> Result<()> {
+    for edge in edges.iter() {
+        let (source, target, distance) = (edge.source(), edge.target(), edge.distance());
+        let reason = if source.max(target) >= node_count {
+            format!(
+                "edge ({source}, {target}) references a node outside the {node_count}-node graph"
+            )
+        } else if !distance.is_finite() || distance < 0.0 {
+            format!("edge ({source}, {target}) has invalid distance {distance}")
+        } else {
+            continue;
+        };
+        return Err(ChutoroError::InvalidKnnGraph {
+            reason: Arc::from(reason),
+        });
+    }
+    Ok(())
+}
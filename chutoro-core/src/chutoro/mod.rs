@@ -3,24 +3,26 @@
 //! Provides the [`Chutoro`] runtime entry point and helpers for selecting
 //! execution backends and wrapping data-source failures.
 
-use std::{num::NonZeroUsize, sync::Arc};
+use std::num::NonZeroUsize;
+#[cfg(feature = "cpu")]
+use std::sync::Arc;
 
 use crate::{
-    Result, builder::ExecutionStrategy, datasource::DataSource, error::ChutoroError,
+    EdgeBudget, ReassignPolicy, Result,
+    builder::{ExecutionStrategy, PipelineOptions},
+    datasource::DataSource,
+    error::ChutoroError,
     result::ClusteringResult,
 };
-use tracing::{instrument, warn};
+#[cfg(feature = "cpu")]
+use crate::{distance_budget::BudgetSource, distance_transform::TransformSource};
+use tracing::instrument;
 
-const CPU_PATH_AVAILABLE: bool = cfg!(feature = "cpu");
-// The `gpu` feature currently exposes the orchestration surface only;
-// no accelerated implementation ships yet.
-const GPU_PATH_AVAILABLE: bool = false;
-
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-enum BackendChoice {
-    Cpu,
-    Gpu,
-}
+pub use self::backend::Backend;
+#[cfg(feature = "cpu")]
+pub use self::predict::Prediction;
+#[cfg(feature = "cpu")]
+pub use self::search::SearchParams;
 
 /// Entry point for running the clustering pipeline.
 ///
@@ -56,6 +58,7 @@ pub struct Chutoro {
     min_cluster_size: NonZeroUsize,
     execution_strategy: ExecutionStrategy,
     max_bytes: Option<u64>,
+    pipeline: PipelineOptions,
 }
 
 impl Chutoro {
@@ -68,9 +71,26 @@ impl Chutoro {
             min_cluster_size,
             execution_strategy,
             max_bytes,
+            pipeline: PipelineOptions::default(),
         }
     }
 
+    pub(crate) fn with_pipeline_options(mut self, pipeline: PipelineOptions) -> Self {
+        self.pipeline = pipeline;
+        self
+    }
+
+    #[cfg(feature = "cpu")]
+    pub(crate) fn pipeline(&self) -> &PipelineOptions {
+        &self.pipeline
+    }
+
+    /// Returns the HNSW parameters runs will use, seeds included.
+    #[cfg(feature = "cpu")]
+    pub(crate) fn hnsw_params(&self) -> &crate::HnswParams {
+        &self.pipeline.hnsw_params
+    }
+
     /// Returns the minimum cluster size configured for this instance.
     ///
     /// # Examples
@@ -121,6 +141,75 @@ impl Chutoro {
     #[must_use]
     pub fn max_bytes(&self) -> Option<u64> { self.max_bytes }
 
+    /// Returns the candidate-edge budget applied before MST construction, if
+    /// configured.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn edge_budget(&self) -> Option<EdgeBudget> { self.pipeline.edge_budget }
+
+    /// Returns the mutual-neighbour filter applied to harvested edges, if
+    /// configured.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn mutual_neighbours(&self) -> Option<NonZeroUsize> { self.pipeline.mutual_neighbours }
+
+    /// Returns whether disconnected components are joined before hierarchy
+    /// extraction.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn connect_components(&self) -> bool { self.pipeline.connect_components }
+
+    /// Returns the policy used to relabel noise points after clustering, if
+    /// configured.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn reassign_policy(&self) -> Option<ReassignPolicy> { self.pipeline.reassign_noise }
+
+    /// Returns the master seed the component seeds were derived from, if
+    /// configured.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn seed(&self) -> Option<u64> { self.pipeline.seed }
+
+    /// Returns the distance-evaluation budget applied to runs, if configured.
+    #[must_use]
+    pub fn max_distance_evaluations(&self) -> Option<u64> {
+        self.pipeline.max_distance_evaluations
+    }
+
+    /// Returns the sample specification and seed used by runs, if configured.
+    #[must_use]
+    pub fn sample(&self) -> Option<(crate::SampleSpec, u64)> {
+        self.pipeline
+            .sample
+            .map(|sampling| (sampling.spec, sampling.seed))
+    }
+
+    /// Returns the prebuilt HNSW index reused by runs, if configured.
+    #[cfg(feature = "cpu")]
+    #[must_use]
+    pub fn prebuilt_index(&self) -> Option<&Arc<crate::CpuHnsw>> {
+        self.pipeline
+            .prebuilt
+            .as_ref()
+            .map(|prebuilt| &prebuilt.index)
+    }
+
+    /// Returns the directory weighted edges are spilled under, if
+    /// configured.
+    #[cfg(feature = "cpu")]
+    #[must_use]
+    pub fn weighted_edge_spill_directory(&self) -> Option<&std::path::Path> {
+        self.pipeline.weighted_edge_spill_directory.as_deref()
+    }
+
+    /// Returns the file runs append event records to, if configured.
+    #[cfg(feature = "cpu")]
+    #[must_use]
+    pub fn event_log(&self) -> Option<&std::path::Path> {
+        self.pipeline.event_log.as_deref()
+    }
+
     /// Executes the clustering pipeline against the provided [`DataSource`].
     ///
     /// # Errors
@@ -178,73 +267,45 @@ impl Chutoro {
         source: &D,
         items: usize,
     ) -> Result<ClusteringResult> {
-        if items == 0 {
-            warn!(
-                data_source = source.name(),
-                "data source is empty, returning error"
-            );
-            return Err(ChutoroError::EmptySource {
-                data_source: Arc::from(source.name()),
-            });
-        }
-        if items < self.min_cluster_size.get() {
-            return Err(ChutoroError::InsufficientItems {
-                data_source: Arc::from(source.name()),
-                items,
-                min_cluster_size: self.min_cluster_size,
-            });
-        }
-        if let Some(err) = self.backend_unavailable_error() {
-            return Err(err);
-        }
-
-        self.check_memory_limit(source, items)?;
-
-        match self.choose_backend() {
-            BackendChoice::Cpu => self.run_cpu(source, items),
-            BackendChoice::Gpu => self.run_gpu(source, items),
-        }
+        self.logged(source.name(), items, || self.run_checked(source, items))
     }
 
-    /// Returns an error if the estimated peak memory exceeds `max_bytes`.
-    fn check_memory_limit<D: DataSource>(&self, source: &D, items: usize) -> Result<()> {
-        let limit = match self.max_bytes {
-            Some(limit) => limit,
-            None => return Ok(()),
-        };
-
-        // Use the default HNSW max_connections for estimation.  The pipeline
-        // always constructs params via `HnswParams::default()`, so this is
-        // consistent with actual usage.  Validated by the
-        // `default_max_connections_matches_hnsw_params` test.
-        const DEFAULT_MAX_CONNECTIONS: usize = 16;
-        let estimated = crate::memory::estimate_peak_bytes(items, DEFAULT_MAX_CONNECTIONS);
-
-        if estimated > limit {
-            return Err(ChutoroError::MemoryLimitExceeded {
-                data_source: Arc::from(source.name()),
-                point_count: items,
-                estimated_bytes: estimated,
-                max_bytes: limit,
-                estimated_display: Arc::from(crate::memory::format_bytes(estimated)),
-                limit_display: Arc::from(crate::memory::format_bytes(limit)),
-            });
+    /// Runs `run`, recording it in the event log when one is configured.
+    fn logged(
+        &self,
+        data_source: &str,
+        items: usize,
+        run: impl FnOnce() -> Result<ClusteringResult>,
+    ) -> Result<ClusteringResult> {
+        #[cfg(feature = "cpu")]
+        if let Some(log) = &self.pipeline.stages.event_log {
+            log.run_started(self, data_source, items);
+            return log.run_ended(run());
         }
-        Ok(())
+        let _ = (data_source, items);
+        run()
     }
 
-    fn choose_backend(&self) -> BackendChoice {
-        match self.execution_strategy {
-            ExecutionStrategy::Auto => {
-                if CPU_PATH_AVAILABLE {
-                    BackendChoice::Cpu
-                } else {
-                    BackendChoice::Gpu
-                }
-            }
-            ExecutionStrategy::CpuOnly => BackendChoice::Cpu,
-            ExecutionStrategy::GpuPreferred => BackendChoice::Gpu,
-        }
+    fn run_checked<D: DataSource + Sync>(
+        &self,
+        source: &D,
+        items: usize,
+    ) -> Result<ClusteringResult> {
+        self.check_source(source, items)?;
+        self.check_memory_limit(source, items)?;
+        let seeds = self.checked_seed_labels(items)?;
+
+        let backend = self.choose_backend();
+        let result = match backend {
+            Backend::CpuParallel => self.run_cpu(source, items),
+            Backend::CpuSequential => self.run_cpu_sequential(source, items),
+            Backend::Gpu => self.run_gpu(source, items),
+        }?;
+        let result = match seeds {
+            Some(seeds) => crate::seed_labels::anchor(result, seeds),
+            None => result,
+        };
+        Ok(result.with_backend(Some(backend)))
     }
 
     /// Execute the CPU FISHDBC pipeline; available with the `cpu` feature.
@@ -257,7 +318,17 @@ impl Chutoro {
     fn run_cpu<D: DataSource + Sync>(&self, source: &D, items: usize) -> Result<ClusteringResult> {
         #[cfg(feature = "cpu")]
         {
-            crate::cpu_pipeline::run_cpu_pipeline_with_len(source, items, self.min_cluster_size)
+            let transformed = TransformSource::new(source, self.pipeline.distance_transform);
+            let triangles = self.check_triangles(&transformed)?;
+            let budgeted = BudgetSource::new(&transformed, self.pipeline.max_distance_evaluations);
+            let result = crate::dedupe::run_deduplicated_pipeline(
+                &budgeted,
+                items,
+                self.min_cluster_size,
+                &self.pipeline,
+            )
+            .map_err(|error| budgeted.explain(error))?;
+            Ok(self.with_run_reports(result, budgeted.evaluations(), triangles))
         }
         #[cfg(not(feature = "cpu"))]
         {
@@ -285,90 +356,23 @@ impl Chutoro {
             requested: self.execution_strategy,
         })
     }
-
-    fn is_backend_unavailable(&self) -> bool {
-        match self.execution_strategy {
-            ExecutionStrategy::Auto => !(CPU_PATH_AVAILABLE || GPU_PATH_AVAILABLE),
-            ExecutionStrategy::CpuOnly => !CPU_PATH_AVAILABLE,
-            ExecutionStrategy::GpuPreferred => !GPU_PATH_AVAILABLE,
-        }
-    }
 }
 
+mod backend;
+#[cfg(feature = "cpu")]
+mod checkpoint;
+mod checks;
+mod dry_run;
+#[cfg(feature = "cpu")]
+mod knn_graph;
+#[cfg(feature = "cpu")]
+mod predict;
+mod resources;
+#[cfg(feature = "cpu")]
+mod run_reports;
+#[cfg(feature = "cpu")]
+mod search;
 #[cfg(test)]
-mod tests {
-    //! Unit tests for the Chutoro builder facade.
-
-    use super::*;
-    use crate::ChutoroBuilder;
-
-    #[test]
-    fn gpu_preferred_requires_gpu_feature() {
-        let chutoro = Chutoro::new(
-            NonZeroUsize::new(1).expect("literal 1 is non-zero"),
-            ExecutionStrategy::GpuPreferred,
-            None,
-        );
-        let err = chutoro.backend_unavailable_error();
-        assert!(matches!(
-            err,
-            Some(ChutoroError::BackendUnavailable {
-                requested: ExecutionStrategy::GpuPreferred
-            })
-        ));
-    }
-
-    #[test]
-    fn backend_available_when_features_enabled() {
-        if cfg!(feature = "cpu") {
-            for strategy in [ExecutionStrategy::Auto, ExecutionStrategy::CpuOnly] {
-                let chutoro = Chutoro::new(
-                    NonZeroUsize::new(1).expect("literal 1 is non-zero"),
-                    strategy,
-                    None,
-                );
-                assert!(chutoro.backend_unavailable_error().is_none());
-            }
-        }
-
-        let chutoro = Chutoro::new(
-            NonZeroUsize::new(1).expect("literal 1 is non-zero"),
-            ExecutionStrategy::GpuPreferred,
-            None,
-        );
-        assert!(matches!(
-            chutoro.backend_unavailable_error(),
-            Some(ChutoroError::BackendUnavailable {
-                requested: ExecutionStrategy::GpuPreferred
-            })
-        ));
-    }
-
-    #[test]
-    fn max_bytes_none_imposes_no_limit() {
-        let chutoro = ChutoroBuilder::new().build().expect("build must succeed");
-        assert_eq!(chutoro.max_bytes(), None);
-    }
-
-    #[test]
-    fn max_bytes_propagates_through_builder() {
-        let chutoro = ChutoroBuilder::new()
-            .with_max_bytes(1_000_000)
-            .build()
-            .expect("build must succeed");
-        assert_eq!(chutoro.max_bytes(), Some(1_000_000));
-    }
-
-    /// Guards against silent drift if `HnswParams::default().max_connections`
-    /// ever changes.  The constant in `check_memory_limit` must stay in sync.
-    #[cfg(feature = "cpu")]
-    #[test]
-    fn default_max_connections_matches_hnsw_params() {
-        let params = crate::HnswParams::default();
-        assert_eq!(
-            params.max_connections(),
-            16,
-            "DEFAULT_MAX_CONNECTIONS in check_memory_limit must be updated to match"
-        );
-    }
-}
+mod tests;
+#[cfg(feature = "cpu")]
+mod triangle;
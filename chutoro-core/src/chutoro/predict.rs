@@ -0,0 +1,133 @@
+//! Approximate labels for points that were not part of a clustering run.
+//!
+//! Scoring services want to place new vectors into an existing clustering
+//! without re-running the pipeline. The retained HNSW index already answers
+//! nearest-neighbour queries over the training rows, so each new point takes
+//! the label of its nearest training neighbour, with a strength that reflects
+//! how firmly that neighbour and the rest of the point's neighbourhood belong
+//! to the cluster.
+
+use std::{num::NonZeroUsize, ops::Range, sync::Arc};
+
+use rayon::prelude::*;
+
+use super::Chutoro;
+use crate::{
+    ClusterId, CpuHnsw, DataSource, Neighbour, Result, cpu_pipeline::map_cpu_hnsw_error,
+    error::ChutoroError, result::ClusteringResult,
+};
+
+/// The cluster a new point most likely belongs to.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::Prediction;
+///
+/// let prediction = Prediction::default();
+/// assert_eq!(prediction.label(), None);
+/// assert_eq!(prediction.strength(), 0.0);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Prediction {
+    label: Option<ClusterId>,
+    strength: f32,
+}
+
+impl Prediction {
+    /// Returns the predicted cluster, or `None` when the point falls in noise.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn label(&self) -> Option<ClusterId> { self.label }
+
+    /// Returns a confidence in `[0, 1]` for [`Self::label`]; `0.0` for noise.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn strength(&self) -> f32 { self.strength }
+}
+
+impl Chutoro {
+    /// Predicts labels for the rows `queries` of `source` against a finished
+    /// `clustering`.
+    ///
+    /// The prebuilt index must hold the training rows, which occupy
+    /// `0..clustering.assignments().len()` of `source`; query rows usually
+    /// follow them. Each query adopts the label of its nearest training
+    /// neighbour and is noise when that neighbour is. Its strength is the
+    /// neighbour's membership probability scaled by the share of the query's
+    /// `min_cluster_size` nearest training neighbours that carry the same
+    /// label, so points on a boundary or in a sparse region score low.
+    ///
+    /// # Errors
+    /// Returns [`ChutoroError::PrebuiltIndexMismatch`] when no prebuilt index
+    /// is configured or it does not hold exactly the clustered rows, and
+    /// [`ChutoroError::DataSource`] when a query lies outside `source`.
+    pub fn predict<D: DataSource + Sync>(
+        &self,
+        source: &D,
+        clustering: &ClusteringResult,
+        queries: Range<usize>,
+    ) -> Result<Vec<Prediction>> {
+        let index = self.trained_index(clustering)?;
+        let trained = clustering.assignments().len();
+        let ef = index
+            .params()
+            .ef_construction()
+            .max(self.min_cluster_size.get() + 1);
+        let Some(ef) = NonZeroUsize::new(ef.min(trained)) else {
+            return Ok(vec![Prediction::default(); queries.len()]);
+        };
+        queries
+            .into_par_iter()
+            .map(|query| {
+                let neighbours = index
+                    .search_uncached(source, query, ef)
+                    .map_err(|error| map_cpu_hnsw_error(source, error))?;
+                let training = neighbours
+                    .into_iter()
+                    .filter(|neighbour| neighbour.id < trained && neighbour.id != query)
+                    .take(self.min_cluster_size.get());
+                Ok(predict_from(clustering, training.collect()))
+            })
+            .collect()
+    }
+
+    /// Returns the prebuilt index, checking it covers `clustering`.
+    fn trained_index(&self, clustering: &ClusteringResult) -> Result<&CpuHnsw> {
+        let index = self
+            .prebuilt_index()
+            .ok_or_else(|| ChutoroError::PrebuiltIndexMismatch {
+                reason: Arc::from("prediction requires a prebuilt index"),
+            })?;
+        let (points, rows) = (index.len(), clustering.assignments().len());
+        if points != rows {
+            return Err(ChutoroError::PrebuiltIndexMismatch {
+                reason: Arc::from(format!(
+                    "index holds {points} points but the clustering labels {rows}"
+                )),
+            });
+        }
+        Ok(index)
+    }
+}
+
+/// Scores a query from its nearest training neighbours, closest first.
+fn predict_from(clustering: &ClusteringResult, neighbours: Vec<Neighbour>) -> Prediction {
+    let Some(nearest) = neighbours.first() else {
+        return Prediction::default();
+    };
+    let label = clustering.assignments()[nearest.id];
+    if Some(label) == clustering.noise_label() {
+        return Prediction::default();
+    }
+    let probability = clustering
+        .membership()
+        .map_or(1.0, |scores| scores.probabilities()[nearest.id]);
+    let agreeing = neighbours
+        .iter()
+        .filter(|neighbour| clustering.assignments()[neighbour.id] == label)
+        .count();
+    Prediction {
+        label: Some(label),
+        strength: probability * agreeing as f32 / neighbours.len() as f32,
+    }
+}
@@ -0,0 +1,89 @@
+//! Pre-flight resource estimation and the `max_bytes` guard.
+
+use std::sync::Arc;
+
+use super::Chutoro;
+use crate::{Result, datasource::DataSource, error::ChutoroError, memory::ResourceEstimate};
+
+/// Fan-out of `HnswParams::default()`, used for memory estimates when the CPU
+/// backend (and therefore `HnswParams`) is not compiled in.
+#[cfg(not(feature = "cpu"))]
+const DEFAULT_MAX_CONNECTIONS: usize = 16;
+
+impl Chutoro {
+    /// Predicts the peak memory of running this configuration over `source`,
+    /// without touching any distances.
+    ///
+    /// The estimate follows the configured HNSW fan-out and distance-cache
+    /// capacity, counts only the sample when sampling is configured, and
+    /// reports the source's own footprint when it exposes
+    /// [`DataSource::dimension_hint`]. Its [`ResourceEstimate::peak_bytes`]
+    /// is the figure compared against `max_bytes` by [`Chutoro::run`], so a
+    /// job that would be rejected can be turned away before it is queued.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ChutoroBuilder, DataSource, DataSourceError};
+    ///
+    /// struct Rows { len: usize }
+    ///
+    /// impl DataSource for Rows {
+    ///     fn len(&self) -> usize { self.len }
+    ///     fn name(&self) -> &str { "rows" }
+    ///     fn dimension_hint(&self) -> Option<usize> { Some(64) }
+    ///     fn distance(&self, _: usize, _: usize) -> Result<f32, DataSourceError> {
+    ///         Ok(0.0)
+    ///     }
+    /// }
+    ///
+    /// let chutoro = ChutoroBuilder::new().build()?;
+    /// let estimate = chutoro.estimate_resources(&Rows { len: 10_000_000 });
+    /// assert_eq!(estimate.source_bytes(), Some(2_560_000_000));
+    /// assert!(!estimate.fits_within(16 << 30));
+    /// # Ok::<(), chutoro_core::ChutoroError>(())
+    /// ```
+    #[must_use]
+    pub fn estimate_resources<D: DataSource>(&self, source: &D) -> ResourceEstimate {
+        self.resource_estimate(source.len())
+            .with_dimension(source.dimension_hint())
+    }
+
+    /// Returns an error if the estimated peak memory exceeds `max_bytes`.
+    pub(super) fn check_memory_limit<D: DataSource>(&self, source: &D, items: usize) -> Result<()> {
+        let Some(limit) = self.max_bytes else {
+            return Ok(());
+        };
+
+        let estimated = self.resource_estimate(items).peak_bytes();
+        if estimated > limit {
+            return Err(ChutoroError::MemoryLimitExceeded {
+                data_source: Arc::from(source.name()),
+                point_count: items,
+                estimated_bytes: estimated,
+                max_bytes: limit,
+                estimated_display: Arc::from(crate::memory::format_bytes(estimated)),
+                limit_display: Arc::from(crate::memory::format_bytes(limit)),
+            });
+        }
+        Ok(())
+    }
+
+    /// Estimates the pipeline for `items` source items under this
+    /// configuration.
+    fn resource_estimate(&self, items: usize) -> ResourceEstimate {
+        // Sampled runs only index and cluster the sample.
+        let indexed = self.pipeline.sample.map_or(items, |sampling| {
+            sampling.size(items, self.min_cluster_size.get())
+        });
+        #[cfg(feature = "cpu")]
+        {
+            let params = &self.pipeline.hnsw_params;
+            ResourceEstimate::new(indexed, params.max_connections())
+                .with_cache_entries(params.distance_cache_config().max_entries().get())
+        }
+        #[cfg(not(feature = "cpu"))]
+        {
+            ResourceEstimate::new(indexed, DEFAULT_MAX_CONNECTIONS)
+        }
+    }
+}
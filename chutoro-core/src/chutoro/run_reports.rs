@@ -0,0 +1,34 @@
+//! Reports attached to a CPU run's result once the pipeline returns.
+
+use super::Chutoro;
+use crate::{ClusteringResult, ParameterReport, SeedReport, Warning};
+
+impl Chutoro {
+    /// Records the seeds and parameters the run used and its distance
+    /// evaluation count on `result`, then the warnings implied by its
+    /// reports and by the pre-run triangle check.
+    pub(super) fn with_run_reports(
+        &self,
+        result: ClusteringResult,
+        evaluations: u64,
+        triangles: Option<Warning>,
+    ) -> ClusteringResult {
+        let params = &self.pipeline.hnsw_params;
+        let seeds = SeedReport::new(
+            self.pipeline.seed,
+            params.rng_seed(),
+            self.pipeline.sample.map(|sampling| sampling.seed),
+        );
+        let parameters = ParameterReport::new(
+            self.min_cluster_size,
+            params.max_connections(),
+            params.ef_construction(),
+        );
+        result
+            .with_seeds(Some(seeds))
+            .with_parameters(Some(parameters))
+            .with_distance_evaluations(Some(evaluations))
+            .with_report_warnings()
+            .with_warnings(triangles)
+    }
+}
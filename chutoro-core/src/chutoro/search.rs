@@ -0,0 +1,110 @@
+//! Nearest-neighbour queries against the retained HNSW index.
+//!
+//! The index's construction `ef` suits clustering, not every later query: a
+//! service may want a narrower beam for latency, a wider one for recall, or
+//! only the rows one tenant owns. [`SearchParams`] carries those choices per
+//! call, and the row filter is applied inside the traversal rather than to
+//! its results.
+
+use std::{fmt, num::NonZeroUsize, sync::Arc};
+
+use super::Chutoro;
+use crate::{
+    CpuHnsw, DataSource, HnswError, Neighbour, Result, cpu_pipeline::map_cpu_hnsw_error,
+    error::ChutoroError, hnsw::GraphQuery,
+};
+
+/// Per-call settings for [`Chutoro::search_with`].
+///
+/// # Examples
+/// ```
+/// use std::num::NonZeroUsize;
+///
+/// use chutoro_core::SearchParams;
+///
+/// let even = |row: usize| row.is_multiple_of(2);
+/// let params = SearchParams {
+///     ef: NonZeroUsize::new(32),
+///     max_distance: Some(1.5),
+///     filter: Some(&even),
+/// };
+/// assert!(params.filter.is_some_and(|filter| filter(4)));
+/// assert!(SearchParams::default().ef.is_none());
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct SearchParams<'a> {
+    /// Beam width and maximum number of neighbours; defaults to the index's
+    /// construction `ef`.
+    pub ef: Option<NonZeroUsize>,
+    /// Drops neighbours farther than this from the query.
+    pub max_distance: Option<f32>,
+    /// Keeps only the rows for which the predicate returns `true`.
+    pub filter: Option<&'a dyn Fn(usize) -> bool>,
+}
+
+impl fmt::Debug for SearchParams<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SearchParams")
+            .field("ef", &self.ef)
+            .field("max_distance", &self.max_distance)
+            .field("filter", &self.filter.map(|_| "<predicate>"))
+            .finish()
+    }
+}
+
+impl Chutoro {
+    /// Returns the nearest indexed rows to row `query` of `source`, closest
+    /// first, under the per-call `params`.
+    ///
+    /// The prebuilt index answers the query, so `source` must hold its rows
+    /// at `0..index.len()`; `query` may be one of them or a row that follows.
+    /// Distances to `query` are not cached, so query rows may change between
+    /// calls. The filter is applied during the traversal as in
+    /// [`CpuHnsw::search_filtered`], so up to `ef` accepted rows are found
+    /// however selective it is.
+    ///
+    /// # Errors
+    /// Returns [`ChutoroError::PrebuiltIndexMismatch`] when no prebuilt index
+    /// is configured, [`ChutoroError::CpuHnswFailure`] when `max_distance` is
+    /// negative or NaN or the search fails, and [`ChutoroError::DataSource`]
+    /// when `query` lies outside `source`.
+    pub fn search_with<D: DataSource + Sync>(
+        &self,
+        source: &D,
+        query: usize,
+        params: SearchParams<'_>,
+    ) -> Result<Vec<Neighbour>> {
+        let index = self.search_index()?;
+        if let Some(limit) = params.max_distance
+            && (limit.is_nan() || limit < 0.0)
+        {
+            return Err(map_cpu_hnsw_error(
+                source,
+                HnswError::InvalidParameters {
+                    reason: format!("search max_distance must be non-negative, got {limit}"),
+                },
+            ));
+        }
+        let ef = params.ef.unwrap_or_else(|| {
+            NonZeroUsize::new(index.params().ef_construction()).unwrap_or(NonZeroUsize::MIN)
+        });
+        let query = GraphQuery { query, ef };
+        let accept_all = |_: usize| true;
+        let mut neighbours = index
+            .search_uncached_filtered(source, query, params.filter.unwrap_or(&accept_all))
+            .map_err(|error| map_cpu_hnsw_error(source, error))?;
+        if let Some(limit) = params.max_distance {
+            neighbours.retain(|neighbour| neighbour.distance <= limit);
+        }
+        Ok(neighbours)
+    }
+
+    /// Returns the prebuilt index queries are answered from.
+    fn search_index(&self) -> Result<&CpuHnsw> {
+        self.prebuilt_index()
+            .map(Arc::as_ref)
+            .ok_or_else(|| ChutoroError::PrebuiltIndexMismatch {
+                reason: Arc::from("search requires a prebuilt index"),
+            })
+    }
+}
@@ -0,0 +1,252 @@
+//! Unit tests for the Chutoro builder facade.
+
+use super::*;
+use crate::ChutoroBuilder;
+
+#[test]
+fn gpu_preferred_requires_gpu_feature() {
+    let chutoro = Chutoro::new(
+        NonZeroUsize::new(1).expect("literal 1 is non-zero"),
+        ExecutionStrategy::GpuPreferred,
+        None,
+    );
+    let err = chutoro.backend_unavailable_error();
+    assert!(matches!(
+        err,
+        Some(ChutoroError::BackendUnavailable {
+            requested: ExecutionStrategy::GpuPreferred
+        })
+    ));
+}
+
+#[test]
+fn backend_available_when_features_enabled() {
+    if cfg!(feature = "cpu") {
+        for strategy in [ExecutionStrategy::Auto, ExecutionStrategy::CpuOnly] {
+            let chutoro = Chutoro::new(
+                NonZeroUsize::new(1).expect("literal 1 is non-zero"),
+                strategy,
+                None,
+            );
+            assert!(chutoro.backend_unavailable_error().is_none());
+        }
+    }
+
+    let chutoro = Chutoro::new(
+        NonZeroUsize::new(1).expect("literal 1 is non-zero"),
+        ExecutionStrategy::GpuPreferred,
+        None,
+    );
+    assert!(matches!(
+        chutoro.backend_unavailable_error(),
+        Some(ChutoroError::BackendUnavailable {
+            requested: ExecutionStrategy::GpuPreferred
+        })
+    ));
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn auto_falls_back_to_the_sequential_cpu_on_one_thread() {
+    let chutoro = Chutoro::new(
+        NonZeroUsize::new(1).expect("literal 1 is non-zero"),
+        ExecutionStrategy::Auto,
+        None,
+    );
+    let backend_with = |threads| {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .expect("thread pool must build")
+            .install(|| chutoro.choose_backend())
+    };
+    let multi_core = std::thread::available_parallelism().map_or(1, NonZeroUsize::get) > 1;
+    let wide = if multi_core {
+        Backend::CpuParallel
+    } else {
+        Backend::CpuSequential
+    };
+    assert_eq!(backend_with(1), Backend::CpuSequential);
+    assert_eq!(backend_with(2), wide);
+}
+
+/// Records the widest Rayon pool any distance evaluation ran on.
+#[cfg(feature = "cpu")]
+struct PoolWidthSource {
+    data: Vec<f32>,
+    widest: std::sync::atomic::AtomicUsize,
+}
+
+#[cfg(feature = "cpu")]
+impl DataSource for PoolWidthSource {
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn name(&self) -> &str {
+        "pool-width"
+    }
+
+    fn distance(
+        &self,
+        left: usize,
+        right: usize,
+    ) -> core::result::Result<f32, crate::DataSourceError> {
+        self.widest.fetch_max(
+            rayon::current_num_threads(),
+            std::sync::atomic::Ordering::Relaxed,
+        );
+        let a = self
+            .data
+            .get(left)
+            .ok_or(crate::DataSourceError::OutOfBounds { index: left })?;
+        let b = self
+            .data
+            .get(right)
+            .ok_or(crate::DataSourceError::OutOfBounds { index: right })?;
+        Ok((a - b).abs())
+    }
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn one_thread_pool_records_the_sequential_backend() {
+    let source = PoolWidthSource {
+        data: vec![0.0, 0.1, 0.2, 10.0, 10.1, 10.2],
+        widest: std::sync::atomic::AtomicUsize::new(0),
+    };
+    let chutoro = ChutoroBuilder::new()
+        .with_min_cluster_size(2)
+        .build()
+        .expect("configuration must be valid");
+    let result = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .expect("thread pool must build")
+        .install(|| chutoro.run(&source))
+        .expect("run must succeed");
+    assert_eq!(result.backend(), Some(Backend::CpuSequential));
+    assert_eq!(result.assignments().len(), 6);
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn sequential_backend_runs_every_stage_on_one_thread() {
+    let source = PoolWidthSource {
+        data: vec![0.0, 0.1, 0.2, 10.0, 10.1, 10.2],
+        widest: std::sync::atomic::AtomicUsize::new(0),
+    };
+    let chutoro = ChutoroBuilder::new()
+        .with_min_cluster_size(2)
+        .build()
+        .expect("configuration must be valid");
+    let result = rayon::ThreadPoolBuilder::new()
+        .num_threads(4)
+        .build()
+        .expect("thread pool must build")
+        .install(|| chutoro.run_cpu_sequential(&source, source.len()))
+        .expect("run must succeed");
+    assert_eq!(result.assignments().len(), 6);
+    assert_eq!(
+        source.widest.load(std::sync::atomic::Ordering::Relaxed),
+        1,
+        "every distance must be evaluated on the one-thread pool"
+    );
+}
+
+#[test]
+fn max_bytes_none_imposes_no_limit() {
+    let chutoro = ChutoroBuilder::new().build().expect("build must succeed");
+    assert_eq!(chutoro.max_bytes(), None);
+}
+
+#[test]
+fn max_bytes_propagates_through_builder() {
+    let chutoro = ChutoroBuilder::new()
+        .with_max_bytes(1_000_000)
+        .build()
+        .expect("build must succeed");
+    assert_eq!(chutoro.max_bytes(), Some(1_000_000));
+}
+
+/// Guards against silent drift if `HnswParams::default().max_connections`
+/// ever changes.  `DEFAULT_MAX_CONNECTIONS`, used for memory estimates in
+/// builds without the CPU backend, must stay in sync.
+#[cfg(feature = "cpu")]
+#[test]
+fn default_max_connections_matches_hnsw_params() {
+    let params = crate::HnswParams::default();
+    assert_eq!(
+        params.max_connections(),
+        16,
+        "DEFAULT_MAX_CONNECTIONS must be updated to match"
+    );
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn memory_estimate_uses_configured_hnsw_fan_out() {
+    let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let source = crate::test_utils::CountingSource::new(vec![0.0; 32], calls);
+    let limit = crate::memory::estimate_peak_bytes(32, 16);
+    let build = |params: crate::HnswParams| {
+        ChutoroBuilder::new()
+            .with_max_bytes(limit)
+            .with_hnsw_params(params)
+            .build()
+            .expect("build must succeed")
+    };
+
+    assert!(
+        build(crate::HnswParams::default())
+            .check_memory_limit(&source, 32)
+            .is_ok()
+    );
+    let wide = crate::HnswParams::new(32, 64).expect("params must be valid");
+    assert!(matches!(
+        build(wide).check_memory_limit(&source, 32),
+        Err(ChutoroError::MemoryLimitExceeded { .. })
+    ));
+}
+
+#[cfg(feature = "cpu")]
+#[test]
+fn memory_estimate_uses_configured_cache_capacity() {
+    let source = crate::test_utils::CountingSource::new(
+        vec![0.0; 32],
+        Arc::new(std::sync::atomic::AtomicUsize::new(0)),
+    );
+    let small_cache = crate::HnswParams::default().with_distance_cache_max_entries(
+        NonZeroUsize::new(1024).expect("literal 1024 is non-zero"),
+    );
+    let chutoro = ChutoroBuilder::new()
+        .with_hnsw_params(small_cache)
+        .build()
+        .expect("build must succeed");
+
+    let estimate = chutoro.estimate_resources(&source);
+
+    assert_eq!(
+        estimate,
+        crate::ResourceEstimate::new(32, 16).with_cache_entries(1024)
+    );
+    assert!(estimate.peak_bytes() < crate::memory::estimate_peak_bytes(32, 16));
+}
+
+#[test]
+fn resource_estimate_counts_only_the_sample() {
+    let chutoro = ChutoroBuilder::new()
+        .with_min_cluster_size(2)
+        .with_sample(crate::SampleSpec::Count(10), 7)
+        .build()
+        .expect("build must succeed");
+    let source = crate::test_utils::CountingSource::new(
+        vec![0.0; 40],
+        Arc::new(std::sync::atomic::AtomicUsize::new(0)),
+    );
+
+    let estimate = chutoro.estimate_resources(&source);
+
+    assert_eq!(estimate.point_count(), 10);
+    assert_eq!(estimate.source_bytes(), None);
+}
@@ -0,0 +1,53 @@
+//! The pre-run triangle-inequality check enabled by
+//! [`crate::ChutoroBuilder::with_triangle_check`].
+
+use std::sync::Arc;
+
+use tracing::warn;
+
+use super::Chutoro;
+use crate::{
+    Result, SeedStream, Warning, datasource::DataSource, datasource::check_triangle_inequality,
+    error::ChutoroError,
+};
+
+impl Chutoro {
+    /// Samples triples from `source` when the check is enabled and the source
+    /// declares a class that satisfies the triangle inequality, returning a
+    /// warning if any triple breaks it.
+    pub(super) fn check_triangles<D: DataSource>(&self, source: &D) -> Result<Option<Warning>> {
+        let Some(triples) = self.pipeline.triangle_check else {
+            return Ok(None);
+        };
+        let descriptor = source.metric_descriptor();
+        let Some(class) = descriptor
+            .class()
+            .filter(|class| class.satisfies_triangle_inequality())
+        else {
+            return Ok(None);
+        };
+        let seed = SeedStream::TriangleCheck.derive(self.pipeline.seed.unwrap_or_default());
+        let report = check_triangle_inequality(source, triples.get(), seed).map_err(|error| {
+            ChutoroError::DataSource {
+                data_source: Arc::from(source.name()),
+                error,
+            }
+        })?;
+        let Some(example) = report.violations().first() else {
+            return Ok(None);
+        };
+        warn!(
+            data_source = source.name(),
+            metric = descriptor.as_str(),
+            class = ?class,
+            violations = report.violations().len(),
+            sampled = report.sampled_triples(),
+            example = %example,
+            "declared metric breaks the triangle inequality"
+        );
+        Ok(Some(Warning::TriangleInequalityViolated {
+            violations: report.violations().len(),
+            sampled: report.sampled_triples(),
+        }))
+    }
+}
@@ -0,0 +1,305 @@
+//! Connectivity reporting and repair for mutual-reachability forests.
+//!
+//! HNSW harvests are not guaranteed to connect every point. When the minimum
+//! spanning forest has several trees, hierarchy extraction treats each tree
+//! independently and clusters never span them. This module records that
+//! outcome in a [`ConnectivityReport`] and, when requested, joins the trees
+//! with bridge edges computed from one representative point per component.
+
+#[cfg(feature = "cpu")]
+use std::{borrow::Cow, sync::Arc};
+
+#[cfg(feature = "cpu")]
+use tracing::info;
+
+#[cfg(feature = "cpu")]
+use crate::{
+    DataSource, MstEdge, MstError, Result, distance_policy::DistanceGuard, error::ChutoroError,
+};
+
+/// Describes how connected the mutual-reachability forest was.
+///
+/// Component sizes are listed in order of each component's lowest point
+/// index and describe the forest before any repair.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::ConnectivityReport;
+///
+/// let report = ConnectivityReport::new(vec![3, 2], 1);
+/// assert_eq!(report.component_count(), 2);
+/// assert_eq!(report.bridge_edges_added(), 1);
+/// assert!(!report.is_connected());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConnectivityReport {
+    component_sizes: Vec<usize>,
+    bridge_edges_added: usize,
+}
+
+impl ConnectivityReport {
+    /// Creates a report from component sizes and the number of bridges added.
+    #[must_use]
+    pub fn new(component_sizes: Vec<usize>, bridge_edges_added: usize) -> Self {
+        Self {
+            component_sizes,
+            bridge_edges_added,
+        }
+    }
+
+    /// Returns the number of connected components in the harvested graph.
+    #[must_use]
+    pub fn component_count(&self) -> usize {
+        self.component_sizes.len()
+    }
+
+    /// Returns the number of points in each component.
+    #[must_use]
+    pub fn component_sizes(&self) -> &[usize] {
+        &self.component_sizes
+    }
+
+    /// Returns the number of bridge edges added to join components.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn bridge_edges_added(&self) -> usize { self.bridge_edges_added }
+
+    /// Returns whether the harvested graph formed a single component.
+    #[must_use]
+    pub fn is_connected(&self) -> bool {
+        self.component_sizes.len() <= 1
+    }
+}
+
+/// Connected components of a spanning forest.
+#[cfg(feature = "cpu")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ForestComponents {
+    representatives: Vec<usize>,
+    sizes: Vec<usize>,
+}
+
+#[cfg(feature = "cpu")]
+impl ForestComponents {
+    /// Labels the components spanned by `edges` over `node_count` points.
+    ///
+    /// Each component is represented by its lowest point index.
+    pub(crate) fn from_edges(node_count: usize, edges: &[MstEdge]) -> Self {
+        let mut parents: Vec<usize> = (0..node_count).collect();
+        for edge in edges {
+            let left = find_root(&mut parents, edge.source());
+            let right = find_root(&mut parents, edge.target());
+            // Rooting at the lower index keeps each root at its component's
+            // lowest point.
+            match left.cmp(&right) {
+                std::cmp::Ordering::Less => parents[right] = left,
+                std::cmp::Ordering::Greater => parents[left] = right,
+                std::cmp::Ordering::Equal => {}
+            }
+        }
+
+        let mut slots = vec![usize::MAX; node_count];
+        let mut representatives = Vec::new();
+        let mut sizes = Vec::new();
+        for point in 0..node_count {
+            let root = find_root(&mut parents, point);
+            if slots[root] == usize::MAX {
+                slots[root] = representatives.len();
+                representatives.push(root);
+                sizes.push(0);
+            }
+            sizes[slots[root]] += 1;
+        }
+        Self {
+            representatives,
+            sizes,
+        }
+    }
+
+    /// Consumes the components into a report noting `bridge_edges_added`.
+    pub(crate) fn into_report(self, bridge_edges_added: usize) -> ConnectivityReport {
+        ConnectivityReport::new(self.sizes, bridge_edges_added)
+    }
+}
+
+#[cfg(feature = "cpu")]
+fn find_root(parents: &mut [usize], mut node: usize) -> usize {
+    while parents[node] != node {
+        parents[node] = parents[parents[node]];
+        node = parents[node];
+    }
+    node
+}
+
+/// Reports forest connectivity and, when `repair` is set, appends the bridge
+/// edges that join its components.
+#[cfg(feature = "cpu")]
+pub(crate) fn connect_forest<'a, D: DataSource + Sync>(
+    source: (&D, &DistanceGuard),
+    forest_edges: &'a [MstEdge],
+    core_distances: &[f32],
+    repair: bool,
+) -> Result<(Cow<'a, [MstEdge]>, ConnectivityReport)> {
+    let components = ForestComponents::from_edges(core_distances.len(), forest_edges);
+    if !repair {
+        return Ok((Cow::Borrowed(forest_edges), components.into_report(0)));
+    }
+
+    let first_sequence = forest_edges
+        .iter()
+        .map(|edge| edge.sequence())
+        .max()
+        .map_or(0, |sequence| sequence.saturating_add(1));
+    let bridges = bridge_components(source, &components, core_distances, first_sequence)?;
+    if !bridges.is_empty() {
+        info!(
+            components = bridges.len() + 1,
+            "joined disconnected components with bridge edges"
+        );
+    }
+    let report = components.into_report(bridges.len());
+    let mut edges = forest_edges.to_vec();
+    edges.extend(bridges);
+    Ok((Cow::Owned(edges), report))
+}
+
+/// Computes the bridge edges joining all components of a forest.
+///
+/// Runs Prim's algorithm over the component representatives using
+/// mutual-reachability weights, so `k` components cost at most
+/// `k * (k - 1) / 2` distance evaluations and yield `k - 1` bridges. Bridge
+/// sequences start at `first_sequence` to keep tie-breaking deterministic.
+///
+/// # Errors
+/// Returns [`ChutoroError::DataSource`] when a distance evaluation fails and
+/// [`ChutoroError::CpuMstFailure`] when the distance policy rejects a
+/// non-finite bridge distance.
+#[cfg(feature = "cpu")]
+pub(crate) fn bridge_components<D: DataSource + Sync>(
+    source: (&D, &DistanceGuard),
+    components: &ForestComponents,
+    core_distances: &[f32],
+    first_sequence: u64,
+) -> Result<Vec<MstEdge>> {
+    let representatives = &components.representatives;
+    let Some((&first, rest)) = representatives.split_first() else {
+        return Ok(Vec::new());
+    };
+
+    // (representative, cheapest weight into the tree, tree endpoint)
+    let mut frontier: Vec<(usize, f32, usize)> = rest
+        .iter()
+        .map(|&rep| (rep, f32::INFINITY, first))
+        .collect();
+    let mut bridges = Vec::with_capacity(rest.len());
+    let mut joined = first;
+    let mut sequence = first_sequence;
+
+    while !frontier.is_empty() {
+        relax_frontier(source, core_distances, joined, &mut frontier)?;
+        let Some(best) = frontier
+            .iter()
+            .enumerate()
+            .min_by(|(_, left), (_, right)| left.1.total_cmp(&right.1))
+            .map(|(position, _)| position)
+        else {
+            break;
+        };
+        let (rep, weight, anchor) = frontier.swap_remove(best);
+        bridges.push(MstEdge::new(anchor, rep, weight, sequence));
+        sequence = sequence.saturating_add(1);
+        joined = rep;
+    }
+
+    Ok(bridges)
+}
+
+#[cfg(feature = "cpu")]
+fn relax_frontier<D: DataSource + Sync>(
+    (source, guard): (&D, &DistanceGuard),
+    core_distances: &[f32],
+    joined: usize,
+    frontier: &mut [(usize, f32, usize)],
+) -> Result<()> {
+    let candidates: Vec<usize> = frontier.iter().map(|(rep, _, _)| *rep).collect();
+    let distances = source
+        .batch_distances(joined, &candidates)
+        .map_err(|error| ChutoroError::DataSource {
+            data_source: Arc::from(source.name()),
+            error,
+        })?;
+
+    for ((rep, best, anchor), distance) in frontier.iter_mut().zip(distances) {
+        let Some(distance) = guard.admit(joined, *rep, distance) else {
+            let error = MstError::NonFiniteWeight {
+                left: joined,
+                right: *rep,
+            };
+            return Err(ChutoroError::CpuMstFailure {
+                code: Arc::from(error.code().as_str()),
+                message: Arc::from(error.to_string()),
+            });
+        };
+        let weight = distance
+            .max(core_distances[joined])
+            .max(core_distances[*rep]);
+        if weight < *best {
+            *best = weight;
+            *anchor = joined;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "cpu"))]
+mod tests {
+    //! Unit tests for forest connectivity labelling and repair.
+
+    use rstest::rstest;
+
+    use super::*;
+    use crate::test_utils::CountingSource;
+
+    fn forest(pairs: &[(usize, usize)]) -> Vec<MstEdge> {
+        pairs
+            .iter()
+            .enumerate()
+            .map(|(sequence, &(left, right))| MstEdge::new(left, right, 1.0, sequence as u64))
+            .collect()
+    }
+
+    #[rstest]
+    #[case::connected(3, &[(0, 1), (1, 2)], &[3])]
+    #[case::isolated_points(3, &[], &[1, 1, 1])]
+    #[case::two_trees(5, &[(3, 4), (0, 2)], &[2, 1, 2])]
+    fn labels_components_by_lowest_point(
+        #[case] node_count: usize,
+        #[case] pairs: &[(usize, usize)],
+        #[case] expected_sizes: &[usize],
+    ) {
+        let components = ForestComponents::from_edges(node_count, &forest(pairs));
+        let report = components.into_report(0);
+        assert_eq!(report.component_sizes(), expected_sizes);
+        assert_eq!(report.is_connected(), expected_sizes.len() == 1);
+    }
+
+    #[test]
+    fn bridges_join_components_with_nearest_representatives() {
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let source = CountingSource::new(vec![0.0, 1.0, 10.0, 11.0, 4.0], calls);
+        let edges = forest(&[(0, 1), (2, 3)]);
+        let components = ForestComponents::from_edges(5, &edges);
+        let core = vec![0.0; 5];
+
+        let guard = DistanceGuard::default();
+        let bridges =
+            bridge_components((&source, &guard), &components, &core, 10).expect("bridging");
+        let pairs: Vec<(usize, usize, f32)> = bridges
+            .iter()
+            .map(|edge| (edge.source(), edge.target(), edge.weight()))
+            .collect();
+        assert_eq!(pairs, vec![(0, 4, 4.0), (2, 4, 6.0)]);
+        assert_eq!(bridges[0].sequence(), 10);
+    }
+}
@@ -0,0 +1,199 @@
+//! Core distances for mutual-reachability weighting, supplied by the caller
+//! or read from each point's HNSW or k-NN graph neighbourhood.
+
+use std::{num::NonZeroUsize, sync::Arc};
+
+use super::{HarvestInputs, map_cpu_hnsw_error};
+use crate::{
+    CpuHnsw, DataSource, DistancePrecision, HnswParams, Result,
+    builder::PipelineOptions,
+    error::ChutoroError,
+    graph_builder::{
+        CoreSelection, Neighbourhoods, graph_core_distances, precise_graph_core_distances,
+    },
+    precision::{CoreDistances, precise_core_distances},
+};
+
+/// Returns the core distances supplied to the builder, or computes them from
+/// the run's neighbourhoods when none were, at the run's
+/// [`crate::DistancePrecision`].
+#[cfg(feature = "cpu")]
+pub(crate) fn pipeline_core_distances<D: DataSource + Sync>(
+    inputs: &HarvestInputs<'_, D>,
+    options: &PipelineOptions,
+) -> Result<CoreDistances> {
+    let HarvestInputs {
+        source,
+        context,
+        neighbourhoods,
+        guard,
+        ..
+    } = *inputs;
+    let weights = options.point_weights.as_deref();
+    let precision = options.distance_precision;
+    if let Some(supplied) = &options.core_distances {
+        return supplied_core_distances(supplied, context.len())
+            .map(|core| CoreDistances::new(core, precision));
+    }
+    let double = precision == DistancePrecision::Double;
+    match neighbourhoods {
+        Neighbourhoods::Index(index) if double => {
+            precise_core_distances(source, index, context.min_cluster_size(), weights)
+                .map(CoreDistances::Double)
+        }
+        Neighbourhoods::Index(index) => {
+            compute_core_distances(source, index, context.min_cluster_size(), weights)
+                .map(CoreDistances::Single)
+        }
+        Neighbourhoods::Graph(graph) => {
+            let core = CoreSelection {
+                items: context.len(),
+                min_cluster_size: context.min_cluster_size(),
+                weights,
+            };
+            if double {
+                precise_graph_core_distances(source, guard, graph, core).map(CoreDistances::Double)
+            } else {
+                Ok(CoreDistances::Single(graph_core_distances(graph, core)))
+            }
+        }
+    }
+}
+
+/// Checks that supplied core distances cover exactly `items` points.
+#[cfg(feature = "cpu")]
+pub(crate) fn supplied_core_distances(supplied: &[f32], items: usize) -> Result<Vec<f32>> {
+    if supplied.len() != items {
+        return Err(ChutoroError::InvalidCoreDistances {
+            reason: Arc::from(format!(
+                "{} core distances were supplied for {items} points",
+                supplied.len()
+            )),
+        });
+    }
+    Ok(supplied.to_vec())
+}
+
+/// Computes each indexed point's core distance with the pipeline's search
+/// width.
+#[cfg(feature = "cpu")]
+pub(crate) fn core_distances<D: DataSource + Sync>(
+    source: &D,
+    index: &CpuHnsw,
+    min_cluster_size: NonZeroUsize,
+) -> Result<Vec<f32>> {
+    compute_core_distances(source, index, min_cluster_size, None)
+}
+
+/// Chooses the search width used to find each point's core neighbourhood.
+#[cfg(feature = "cpu")]
+fn core_search_ef(
+    params: &HnswParams,
+    items: usize,
+    min_cluster_size: NonZeroUsize,
+) -> NonZeroUsize {
+    let desired = min_cluster_size
+        .get()
+        .saturating_add(1)
+        .max(params.ef_construction())
+        .min(items);
+    let Some(ef) = NonZeroUsize::new(desired) else {
+        unreachable!("ef_construction is non-zero so the computed ef is non-zero");
+    };
+    ef
+}
+
+/// Computes each indexed point's core distance from its HNSW neighbourhood.
+#[cfg(feature = "cpu")]
+fn compute_core_distances<D: DataSource + Sync>(
+    source: &D,
+    index: &CpuHnsw,
+    min_cluster_size: NonZeroUsize,
+    weights: Option<&[usize]>,
+) -> Result<Vec<f32>> {
+    let search = CoreSearch::new(index, min_cluster_size, weights);
+    (0..index.len())
+        .map(|point| {
+            let others = search.neighbours(source, point)?;
+            Ok(search.select(point, &others))
+        })
+        .collect()
+}
+
+/// Finds each point's core neighbourhood and picks its core distance.
+#[cfg(feature = "cpu")]
+pub(crate) struct CoreSearch<'a> {
+    index: &'a CpuHnsw,
+    ef: NonZeroUsize,
+    min_cluster_size: NonZeroUsize,
+    weights: Option<&'a [usize]>,
+}
+
+#[cfg(feature = "cpu")]
+impl<'a> CoreSearch<'a> {
+    pub(crate) fn new(
+        index: &'a CpuHnsw,
+        min_cluster_size: NonZeroUsize,
+        weights: Option<&'a [usize]>,
+    ) -> Self {
+        Self {
+            index,
+            ef: core_search_ef(index.params(), index.len(), min_cluster_size),
+            min_cluster_size,
+            weights,
+        }
+    }
+
+    /// Returns the neighbours of `point`, nearest first, without the point
+    /// itself.
+    pub(crate) fn neighbours<D: DataSource + Sync>(
+        &self,
+        source: &D,
+        point: usize,
+    ) -> Result<Vec<(usize, f32)>> {
+        let neighbours = self
+            .index
+            .search(source, point, self.ef)
+            .map_err(|error| map_cpu_hnsw_error(source, error))?;
+        Ok(neighbours
+            .into_iter()
+            .filter(|neighbour| neighbour.id != point)
+            .map(|neighbour| (neighbour.id, neighbour.distance))
+            .collect())
+    }
+
+    /// Picks the core distance of `point` from `others`, nearest first, as
+    /// [`select_core_distance`] does.
+    pub(crate) fn select<T: Copy + Default>(&self, point: usize, others: &[(usize, T)]) -> T {
+        select_core_distance(point, others, self.min_cluster_size, self.weights)
+    }
+}
+
+/// Picks the core distance of `point` from `others`, nearest first: the
+/// distance to its `min_cluster_size`-th neighbour, to its farthest when it
+/// has fewer, or the default for an isolated point.
+///
+/// With weights, a point stands for `weights[point]` exact duplicates: its
+/// own duplicates sit at distance zero and each neighbour counts as many
+/// times as it is weighted.
+#[cfg(feature = "cpu")]
+pub(crate) fn select_core_distance<T: Copy + Default>(
+    point: usize,
+    others: &[(usize, T)],
+    min_cluster_size: NonZeroUsize,
+    weights: Option<&[usize]>,
+) -> T {
+    let weight = |point: usize| weights.map_or(1, |weights| weights[point]);
+    let mut counted = weight(point) - 1;
+    if counted >= min_cluster_size.get() {
+        return T::default();
+    }
+    others
+        .iter()
+        .find(|(neighbour, _)| {
+            counted += weight(*neighbour);
+            counted >= min_cluster_size.get()
+        })
+        .or(others.last())
+        .map_or_else(T::default, |&(_, distance)| distance)
+}
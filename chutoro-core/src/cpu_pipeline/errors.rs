@@ -0,0 +1,35 @@
+//! Conversions from the CPU components' errors into [`ChutoroError`].
+
+use std::sync::Arc;
+
+use crate::{DataSource, HnswError, MstError, error::ChutoroError};
+
+#[cfg(feature = "cpu")]
+pub(crate) fn map_cpu_hnsw_error<D: DataSource>(source: &D, error: HnswError) -> ChutoroError {
+    match error {
+        HnswError::DataSource(error) => ChutoroError::DataSource {
+            data_source: Arc::from(source.name()),
+            error,
+        },
+        other => ChutoroError::CpuHnswFailure {
+            code: Arc::from(other.code().as_str()),
+            message: Arc::from(other.to_string()),
+        },
+    }
+}
+
+#[cfg(feature = "cpu")]
+pub(crate) fn map_cpu_mst_error(error: MstError) -> ChutoroError {
+    ChutoroError::CpuMstFailure {
+        code: Arc::from(error.code().as_str()),
+        message: Arc::from(error.to_string()),
+    }
+}
+
+#[cfg(feature = "cpu")]
+pub(crate) fn map_cpu_hierarchy_error(error: crate::HierarchyError) -> ChutoroError {
+    ChutoroError::CpuHierarchyFailure {
+        code: Arc::from(error.code().as_str()),
+        message: Arc::from(error.to_string()),
+    }
+}
@@ -0,0 +1,74 @@
+//! Filtering, sparsification, and mutual-reachability weighting of the
+//! harvested candidate edges.
+
+use std::borrow::Cow;
+
+use tracing::info;
+
+use crate::{
+    CandidateEdge, EdgeBudget, EdgeHarvest, SparsificationReport, builder::PipelineOptions,
+    sparsify_harvest,
+};
+
+/// Applies any mutual-neighbour filter to the harvest.
+///
+/// Edges of pairs skipped under [`crate::DistancePolicy::SkipEdge`] never
+/// reach the harvest: the graph stage leaves them out as it finishes.
+#[cfg(feature = "cpu")]
+pub(super) fn filter_harvest<'a>(
+    edges: &'a EdgeHarvest,
+    options: &PipelineOptions,
+) -> Cow<'a, EdgeHarvest> {
+    match options.mutual_neighbours {
+        Some(k) => Cow::Owned(edges.mutualise(k)),
+        None => Cow::Borrowed(edges),
+    }
+}
+
+/// Sparsifies `harvest` when an edge budget is configured.
+#[cfg(feature = "cpu")]
+pub(crate) fn apply_edge_budget(
+    harvest: EdgeHarvest,
+    items: usize,
+    budget: Option<EdgeBudget>,
+) -> (EdgeHarvest, Option<SparsificationReport>) {
+    let Some(budget) = budget else {
+        return (harvest, None);
+    };
+    let (sparse, report) = sparsify_harvest(&harvest, items, budget);
+    info!(
+        input_edges = report.input_edges(),
+        retained_edges = report.retained_edges(),
+        dropped_edges = report.dropped_edges(),
+        "sparsified candidate edges to budget"
+    );
+    (sparse, Some(report))
+}
+
+/// Re-weights harvested edges with mutual-reachability distances.
+#[cfg(feature = "cpu")]
+pub(crate) fn mutual_reachability_harvest(
+    harvested: &EdgeHarvest,
+    core_distances: &[f32],
+) -> EdgeHarvest {
+    let mutual_edges: Vec<CandidateEdge> = harvested
+        .iter()
+        .map(|edge| mutual_reachability_edge(edge, core_distances))
+        .collect();
+    EdgeHarvest::new(mutual_edges)
+}
+
+/// Re-weights one edge with its mutual-reachability distance.
+#[cfg(feature = "cpu")]
+pub(crate) fn mutual_reachability_edge(
+    edge: &CandidateEdge,
+    core_distances: &[f32],
+) -> CandidateEdge {
+    let left = edge.source();
+    let right = edge.target();
+    let weight = edge
+        .distance()
+        .max(core_distances[left])
+        .max(core_distances[right]);
+    CandidateEdge::new(left, right, weight, edge.sequence())
+}
@@ -0,0 +1,309 @@
+//! CPU FISHDBC pipeline orchestration helpers.
+//!
+//! This module exists to centralize the core CPU pipeline steps so they can be
+//! reused across `Chutoro` orchestration and tests:
+//!
+//! - Build an HNSW index while harvesting candidate edges, reuse a prebuilt
+//!   index and its harvest, or build a k-NN graph by NN-descent.
+//! - Optionally keep only harvested edges between mutual neighbours.
+//! - Convert harvested edges to mutual-reachability weights using core
+//!   distances computed from HNSW or k-NN graph neighbourhoods, or supplied
+//!   by the caller.
+//! - Optionally sparsify the weighted harvest to an [`crate::EdgeBudget`].
+//! - Build the mutual-reachability minimum spanning forest (Kruskal).
+//! - Report forest connectivity and optionally bridge its components.
+//! - Extract a flat clustering and optionally reassign its noise points.
+//!
+//! Each step can be replaced by a [`crate::IndexStage`],
+//! [`crate::HarvestStage`], [`crate::MstStage`], or [`crate::HierarchyStage`]
+//! configured on the builder; the helpers here are the built-in stages.
+
+mod core_distance;
+mod errors;
+mod harvest;
+
+use std::{borrow::Cow, num::NonZeroUsize, sync::Arc};
+
+use self::harvest::filter_harvest;
+pub(crate) use self::{
+    core_distance::{
+        CoreSearch, core_distances, pipeline_core_distances, select_core_distance,
+        supplied_core_distances,
+    },
+    errors::{map_cpu_hierarchy_error, map_cpu_hnsw_error, map_cpu_mst_error},
+    harvest::{apply_edge_budget, mutual_reachability_edge, mutual_reachability_harvest},
+};
+use crate::{
+    ClusterHierarchy, ClusterId, CondensedTree, CpuHnsw, DataSource, EdgeHarvest, HierarchyConfig,
+    MstEdge, Result, SparsificationReport, WeightedHarvest,
+    builder::{PipelineOptions, PrebuiltIndex},
+    checkpoint::{Checkpoints, ForestOutput},
+    connectivity::connect_forest,
+    distance_policy::DistanceGuard,
+    error::ChutoroError,
+    graph_builder::{BuiltGraph, Neighbourhoods, RunGraph},
+    hierarchy::{CondensedForest, extract_weighted_clustering},
+    reassign::reassign_noise,
+    result::ClusteringResult,
+    spill::spilled_forest,
+    stages::{StageArtefact, StageContext, ensure_stage_output, spanning_forest},
+    timings::{Stage, StageClock},
+    warning::cache_pressure,
+};
+
+/// Runs the CPU pipeline end-to-end for the provided [`DataSource`].
+///
+/// # Errors
+/// Returns the same errors as [`crate::Chutoro::run`], including empty or
+/// undersized sources, data source failures, and CPU pipeline failures.
+#[cfg(feature = "cpu")]
+pub fn run_cpu_pipeline<D: DataSource + Sync>(
+    source: &D,
+    min_cluster_size: NonZeroUsize,
+) -> Result<ClusteringResult> {
+    let items = source.len();
+    if items == 0 {
+        return Err(ChutoroError::EmptySource {
+            data_source: Arc::from(source.name()),
+        });
+    }
+    if items < min_cluster_size.get() {
+        return Err(ChutoroError::InsufficientItems {
+            data_source: Arc::from(source.name()),
+            items,
+            min_cluster_size,
+        });
+    }
+
+    run_cpu_pipeline_with_len(source, items, min_cluster_size, &PipelineOptions::default())
+}
+
+#[cfg(feature = "cpu")]
+pub(crate) fn run_cpu_pipeline_with_len<D: DataSource + Sync>(
+    source: &D,
+    items: usize,
+    min_cluster_size: NonZeroUsize,
+    options: &PipelineOptions,
+) -> Result<ClusteringResult> {
+    let hierarchy = options.hierarchy_config(min_cluster_size);
+    let context = StageContext::new(source, hierarchy, &options.hnsw_params);
+    let mut clock = options.stages.clock();
+    let checkpoints = Checkpoints::open(source, options, (items, min_cluster_size.get()))?;
+    let built;
+    // A prebuilt index's guard may already hold counts from earlier runs.
+    let mut replaced_before = None;
+    let graph = match (&options.prebuilt, &checkpoints) {
+        (Some(prebuilt), _) => {
+            ensure_prebuilt_covers_source(prebuilt, items)?;
+            replaced_before = prebuilt.index.distance_guard().report();
+            RunGraph::prebuilt(prebuilt)
+        }
+        (None, Some(checkpoints)) => {
+            built = checkpoints.graph(source, &context, options)?;
+            built.view()
+        }
+        (None, None) => {
+            built = BuiltGraph::build(source, &context, options)?;
+            built.view()
+        }
+    };
+    clock.lap(Stage::HnswBuild);
+    options.stages.notify(graph.artefact());
+
+    let index = graph.neighbourhoods.index();
+    let stored = match &checkpoints {
+        Some(checkpoints) => checkpoints.load_forest()?,
+        None => None,
+    };
+    let forest = match stored {
+        Some(forest) => {
+            clock.lap(Stage::EdgeHarvest);
+            forest
+        }
+        None => {
+            let forest = forest_stage(source, (&context, &graph), options, &mut clock)?;
+            if let Some(checkpoints) = &checkpoints {
+                checkpoints.save_forest(&forest)?;
+            }
+            forest
+        }
+    };
+    clock.lap(Stage::Mst);
+    options.stages.notify(StageArtefact::Mst(&forest.edges));
+    let explained = graph
+        .provenance
+        .map(|provenance| provenance.explain(items, &forest.edges));
+
+    let clustering = match &options.stages.hierarchy {
+        Some(stage) => stage.extract(&context, &forest.edges)?,
+        None => {
+            let weights = options.point_weights.as_deref();
+            let (clustering, condensed) =
+                extract_clustering(items, &forest.edges, hierarchy, weights)?;
+            options
+                .stages
+                .notify(StageArtefact::CondensedTree(CondensedTree::new(&condensed)));
+            clustering
+        }
+    };
+    ensure_stage_output("hierarchy", clustering.assignments().len(), items, "labels")?;
+    let clustering = match index {
+        Some(index) => reassign_noise(source, index, clustering, options.reassign_noise)?,
+        None => clustering,
+    };
+    clock.lap(Stage::Hierarchy);
+
+    Ok(clustering
+        .with_sparsification(forest.sparsification)
+        .with_connectivity(Some(forest.connectivity))
+        .with_timings(Some(clock.finish()))
+        .with_warnings(index.and_then(cache_pressure))
+        .with_explained_forest(explained)
+        .with_distance_policy(
+            graph
+                .guard
+                .report()
+                .map(|report| report.since(replaced_before)),
+        ))
+}
+
+/// Weights the graph's harvest, builds the spanning forest from it, and
+/// reports or bridges the forest's components.
+#[cfg(feature = "cpu")]
+fn forest_stage<D: DataSource + Sync>(
+    source: &D,
+    (context, graph): (&StageContext<'_>, &RunGraph<'_>),
+    options: &PipelineOptions,
+    clock: &mut StageClock,
+) -> Result<ForestOutput> {
+    let harvested = filter_harvest(graph.harvest, options);
+    let inputs = HarvestInputs {
+        source,
+        context,
+        neighbourhoods: graph.neighbourhoods,
+        harvested: &harvested,
+        guard: graph.guard,
+    };
+    let (forest, core_distances, sparsification) = match &options.weighted_edge_spill_directory {
+        Some(directory) => spilled_forest(&inputs, directory, options, clock)?,
+        None => {
+            let (mutual_harvest, core_distances, sparsification) =
+                weighted_edges(&inputs, options)?;
+            clock.lap(Stage::EdgeHarvest);
+            options
+                .stages
+                .notify(StageArtefact::Harvest(&mutual_harvest));
+            let forest = spanning_forest(context, mutual_harvest, &options.stages)?;
+            (forest, core_distances, sparsification)
+        }
+    };
+    let (edges, connectivity) = connect_forest(
+        (source, graph.guard),
+        &forest,
+        &core_distances,
+        options.connect_components,
+    )?;
+    let edges = match edges {
+        Cow::Owned(edges) => edges,
+        Cow::Borrowed(_) => forest,
+    };
+    Ok(ForestOutput {
+        edges,
+        connectivity,
+        sparsification,
+    })
+}
+
+/// The index stage's output, ready to be weighted for MST construction.
+#[cfg(feature = "cpu")]
+pub(crate) struct HarvestInputs<'a, D> {
+    pub(crate) source: &'a D,
+    pub(crate) context: &'a StageContext<'a>,
+    pub(crate) neighbourhoods: Neighbourhoods<'a>,
+    pub(crate) harvested: &'a EdgeHarvest,
+    pub(crate) guard: &'a DistanceGuard,
+}
+
+/// Weights the harvest with the configured harvest stage and applies any
+/// edge budget, returning the MST input, core distances, and budget report.
+#[cfg(feature = "cpu")]
+pub(crate) fn weighted_edges<D: DataSource + Sync>(
+    inputs: &HarvestInputs<'_, D>,
+    options: &PipelineOptions,
+) -> Result<(EdgeHarvest, Vec<f32>, Option<SparsificationReport>)> {
+    let HarvestInputs {
+        context,
+        neighbourhoods,
+        harvested,
+        ..
+    } = *inputs;
+    let items = context.len();
+    // The builder rejects custom harvest stages for runs without an index.
+    let (mutual_harvest, core_distances) = match (&options.stages.harvest, neighbourhoods) {
+        (Some(stage), Neighbourhoods::Index(index)) => {
+            stage.weight(context, index, harvested)?.into_parts()
+        }
+        _ => {
+            let core_distances = pipeline_core_distances(inputs, options)?;
+            let mutual_harvest =
+                core_distances.weight_harvest(inputs.source, inputs.guard, harvested)?;
+            (mutual_harvest, core_distances.into_reported())
+        }
+    };
+    ensure_stage_output("harvest", core_distances.len(), items, "core distances")?;
+    let (mutual_harvest, sparsification) =
+        apply_edge_budget(mutual_harvest, items, options.edge_budget);
+    Ok((mutual_harvest, core_distances, sparsification))
+}
+
+/// Computes core distances and re-weights `harvested` with
+/// mutual-reachability distances.
+#[cfg(feature = "cpu")]
+pub(crate) fn weight_harvest<D: DataSource + Sync>(
+    source: &D,
+    index: &CpuHnsw,
+    harvested: &EdgeHarvest,
+    min_cluster_size: NonZeroUsize,
+) -> Result<WeightedHarvest> {
+    let core_distances = core_distances(source, index, min_cluster_size)?;
+    let mutual_harvest = mutual_reachability_harvest(harvested, &core_distances);
+    Ok(WeightedHarvest::new(mutual_harvest, core_distances))
+}
+
+/// Extracts flat labels, the noise label, and membership scores from the
+/// forest, returning the condensed tree they were selected from.
+#[cfg(feature = "cpu")]
+pub(crate) fn extract_clustering(
+    items: usize,
+    edges: &[MstEdge],
+    config: HierarchyConfig,
+    weights: Option<&[usize]>,
+) -> Result<(ClusteringResult, CondensedForest)> {
+    let flat = extract_weighted_clustering(items, edges, config, weights)
+        .map_err(map_cpu_hierarchy_error)?;
+    let assignments = flat
+        .labels
+        .into_iter()
+        .map(|label| ClusterId::new(label as u64))
+        .collect();
+    let hierarchy = ClusterHierarchy::from_condensed(CondensedTree::new(&flat.condensed), items);
+    let clustering = ClusteringResult::from_assignments(assignments)
+        .with_noise_label(flat.noise_label.map(|label| ClusterId::new(label as u64)))
+        .with_membership(Some(flat.scores))
+        .with_hierarchy(hierarchy);
+    Ok((clustering, flat.condensed))
+}
+
+/// Rejects prebuilt indices that do not hold exactly the source's points.
+#[cfg(feature = "cpu")]
+fn ensure_prebuilt_covers_source(prebuilt: &PrebuiltIndex, items: usize) -> Result<()> {
+    let points = prebuilt.index.len();
+    if points == items {
+        return Ok(());
+    }
+    Err(ChutoroError::PrebuiltIndexMismatch {
+        reason: Arc::from(format!(
+            "index holds {points} points but the data source has {items}"
+        )),
+    })
+}
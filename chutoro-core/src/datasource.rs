@@ -1,38 +1,75 @@
 //! Data source abstractions for the Chutoro core runtime.
+//!
+//! Alongside the [`DataSource`] trait, [`validate_source`] samples a source's
+//! distances to check that it behaves as a metric, and
+//! [`check_triangle_inequality`] samples triples to check a declared
+//! [`MetricClass`].
 
-use crate::error::DataSourceError;
+use crate::{IdMap, error::DataSourceError};
 use std::{fmt, sync::Arc};
 
+mod batch;
+mod triangle;
+mod validate;
+
+pub use triangle::{TriangleReport, TriangleViolation, check_triangle_inequality};
+pub use validate::{ValidationReport, Violation, ViolationKind, validate_source};
+
 /// Describes the distance metric exposed by a [`DataSource`].
 ///
 /// The identifier must include all configuration that affects distance
 /// semantics. For example, cosine distance with pre-computed norms should
 /// expose a different descriptor to the raw cosine metric so that caches can
-/// distinguish them.
+/// distinguish them. Sources may also declare a [`MetricClass`], which
+/// [`crate::ChutoroBuilder::with_triangle_check`] uses to decide whether the
+/// triangle inequality should hold.
 ///
 /// # Examples
 /// ```
-/// use chutoro_core::MetricDescriptor;
+/// use chutoro_core::{MetricClass, MetricDescriptor};
 ///
 /// let descriptor = MetricDescriptor::new("cosine:prenorm=true");
 /// assert_eq!(descriptor.as_str(), "cosine:prenorm=true");
+/// assert_eq!(descriptor.class(), None);
+///
+/// let euclidean = MetricDescriptor::new("euclidean").with_class(MetricClass::Metric);
+/// assert_eq!(euclidean.class(), Some(MetricClass::Metric));
 /// ```
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
-pub struct MetricDescriptor(Arc<str>);
+pub struct MetricDescriptor {
+    identifier: Arc<str>,
+    class: Option<MetricClass>,
+}
 
 impl MetricDescriptor {
-    /// Creates a descriptor from a string identifier.
+    /// Creates a descriptor from a string identifier, with no declared
+    /// class.
     #[must_use]
     pub fn new(identifier: impl Into<Arc<str>>) -> Self {
-        Self(identifier.into())
+        Self {
+            identifier: identifier.into(),
+            class: None,
+        }
+    }
+
+    /// Declares the properties the metric guarantees.
+    #[must_use]
+    pub fn with_class(mut self, class: MetricClass) -> Self {
+        self.class = Some(class);
+        self
     }
 
     /// Returns the metric identifier as a `&str`.
     #[must_use]
     pub fn as_str(&self) -> &str {
-        &self.0
+        &self.identifier
     }
 
+    /// Returns the declared class, or `None` when the source makes no claim.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn class(&self) -> Option<MetricClass> { self.class }
+
     /// Builds the default "unknown" descriptor.
     #[must_use]
     pub fn unknown() -> Self {
@@ -40,6 +77,39 @@ impl MetricDescriptor {
     }
 }
 
+/// Properties a distance function declares through [`MetricDescriptor`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum MetricClass {
+    /// Non-negative, symmetric, zero only between identical items, and
+    /// satisfies the triangle inequality, such as Euclidean or Levenshtein
+    /// distance.
+    Metric,
+    /// A metric except that distinct items may be at distance zero, such as
+    /// the Hamming distance between perceptual hashes.
+    PseudoMetric,
+    /// Makes no triangle-inequality guarantee, such as cosine or squared
+    /// Euclidean distance. HNSW still works, but recall may degrade.
+    NonMetric,
+}
+
+impl MetricClass {
+    /// Returns whether distances of this class satisfy the triangle
+    /// inequality.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::MetricClass;
+    ///
+    /// assert!(MetricClass::PseudoMetric.satisfies_triangle_inequality());
+    /// assert!(!MetricClass::NonMetric.satisfies_triangle_inequality());
+    /// ```
+    #[must_use]
+    pub const fn satisfies_triangle_inequality(self) -> bool {
+        matches!(self, Self::Metric | Self::PseudoMetric)
+    }
+}
+
 impl Default for MetricDescriptor {
     fn default() -> Self {
         Self::unknown()
@@ -82,6 +152,10 @@ impl fmt::Display for MetricDescriptor {
 ///
 /// let batched = src.batch_distances(0, &[1, 2])?;
 /// assert_eq!(batched, [1.0, 3.0]);
+///
+/// let mut scored = vec![0.0; 2];
+/// src.batch_distances_into(2, &[0, 1], &mut scored)?;
+/// assert_eq!(scored, [3.0, 2.0]);
 /// # Ok::<(), DataSourceError>(())
 /// ```
 pub trait DataSource {
@@ -142,9 +216,105 @@ pub trait DataSource {
         MetricDescriptor::unknown()
     }
 
+    /// Returns the number of `f32` components per item when the source holds
+    /// fixed-width vectors.
+    ///
+    /// Only used to report the source's own footprint in
+    /// [`crate::ResourceEstimate`]; the default returns `None`.
+    #[must_use]
+    fn dimension_hint(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the caller-supplied identifier of each row, when the source
+    /// carries them.
+    ///
+    /// Pass the map to [`crate::ClusteringResult::labels_by_id`] to report
+    /// labels against the original keys. The default returns `None`.
+    #[must_use]
+    fn row_ids(&self) -> Option<&IdMap> {
+        None
+    }
+
+    /// Returns a hash that equal items share, when the source can compute
+    /// one cheaply, such as a hash of a row's bytes.
+    ///
+    /// Runs with [`crate::ChutoroBuilder::dedupe_exact`] treat items with
+    /// equal keys as duplicate candidates and collapse those at distance
+    /// zero, so a key only needs to be equal for exact duplicates; collisions
+    /// cost one distance evaluation each. The default returns `None`, and
+    /// items without a key are never collapsed.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::hash::{BuildHasher, RandomState};
+    ///
+    /// use chutoro_core::{DataSource, DataSourceError};
+    ///
+    /// struct Words(Vec<&'static str>, RandomState);
+    ///
+    /// impl DataSource for Words {
+    ///     fn len(&self) -> usize { self.0.len() }
+    ///     fn name(&self) -> &str { "words" }
+    ///     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+    ///         Ok(if self.0[i] == self.0[j] { 0.0 } else { 1.0 })
+    ///     }
+    ///     fn dedupe_key(&self, index: usize) -> Option<u64> {
+    ///         self.0.get(index).map(|word| self.1.hash_one(word))
+    ///     }
+    /// }
+    ///
+    /// let words = Words(vec!["a", "b", "a"], RandomState::new());
+    /// assert_eq!(words.dedupe_key(0), words.dedupe_key(2));
+    /// ```
+    #[must_use]
+    fn dedupe_key(&self, index: usize) -> Option<u64> {
+        let _ = index;
+        None
+    }
+
     /// Computes the distance between two items.
     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError>;
 
+    /// Computes the distance between two items at `f64` precision.
+    ///
+    /// Runs with [`crate::DistancePrecision::Double`] weight the minimum
+    /// spanning tree with these distances while the HNSW index keeps using
+    /// [`Self::distance`]. Sources whose distances span a huge dynamic range
+    /// should override this method to compute in `f64`. The default widens
+    /// [`Self::distance`], which is lossless but gains no precision.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`Self::distance`].
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{DataSource, DataSourceError};
+    ///
+    /// struct Exponents(Vec<f64>);
+    ///
+    /// impl DataSource for Exponents {
+    ///     fn len(&self) -> usize { self.0.len() }
+    ///     fn name(&self) -> &str { "exponents" }
+    ///     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+    ///         self.distance_f64(i, j).map(|distance| distance as f32)
+    ///     }
+    ///     fn distance_f64(&self, i: usize, j: usize) -> Result<f64, DataSourceError> {
+    ///         let a = self.0.get(i).ok_or(DataSourceError::OutOfBounds { index: i })?;
+    ///         let b = self.0.get(j).ok_or(DataSourceError::OutOfBounds { index: j })?;
+    ///         Ok((a - b).abs())
+    ///     }
+    /// }
+    ///
+    /// // As `f32` values the two items would be identical.
+    /// let src = Exponents(vec![1.0e9, 1.0e9 + 1.0e-3]);
+    /// assert!((src.distance_f64(0, 1)? - 1.0e-3).abs() < 1.0e-6);
+    /// # Ok::<(), DataSourceError>(())
+    /// ```
+    fn distance_f64(&self, i: usize, j: usize) -> Result<f64, DataSourceError> {
+        self.distance(i, j).map(f64::from)
+    }
+
     /// Computes the distances from `query` to every entry in `candidates`.
     ///
     /// Implementations can override this method to provide query-centric
@@ -163,17 +333,32 @@ pub trait DataSource {
         query: usize,
         candidates: &[usize],
     ) -> Result<Vec<f32>, DataSourceError> {
-        if query >= self.len() {
-            return Err(DataSourceError::OutOfBounds { index: query });
-        }
-        let pairs: Vec<(usize, usize)> = candidates
-            .iter()
-            .copied()
-            .map(|candidate| (query, candidate))
-            .collect();
-        let mut out = vec![0.0_f32; pairs.len()];
-        self.distance_batch(&pairs, &mut out)?;
-        Ok(out)
+        batch::query_distances(self, query, candidates)
+    }
+
+    /// Writes the distances from `query` to every entry in `targets` into
+    /// `out`.
+    ///
+    /// HNSW search scores the neighbours it expands through this method,
+    /// reusing one buffer across a layer search, so providers with
+    /// vectorised kernels can override it to score a query against many
+    /// targets without allocating per call. The default delegates to
+    /// [`Self::batch_distances`], so sources that override neither method
+    /// are scored through [`Self::distance`].
+    ///
+    /// # Errors
+    /// Returns [`DataSourceError::OutputLengthMismatch`] if
+    /// `targets.len() != out.len()`, or any [`DataSourceError`] surfaced by
+    /// [`Self::batch_distances`].
+    ///
+    /// If the call fails, `out` is left unmodified.
+    fn batch_distances_into(
+        &self,
+        query: usize,
+        targets: &[usize],
+        out: &mut [f32],
+    ) -> Result<(), DataSourceError> {
+        batch::query_distances_into(self, query, targets, out)
     }
 
     /// Computes several distances at once, storing results in `out`.
@@ -189,19 +374,7 @@ pub trait DataSource {
         pairs: &[(usize, usize)],
         out: &mut [f32],
     ) -> Result<(), DataSourceError> {
-        if pairs.len() != out.len() {
-            return Err(DataSourceError::OutputLengthMismatch {
-                out: out.len(),
-                expected: pairs.len(),
-            });
-        }
-        // Compute into a temp buffer to keep `out` unchanged on error.
-        let mut tmp = vec![0.0_f32; pairs.len()];
-        for (idx, (i, j)) in pairs.iter().enumerate() {
-            tmp[idx] = self.distance(*i, *j)?;
-        }
-        out.copy_from_slice(&tmp);
-        Ok(())
+        batch::pair_distances(self, pairs, out)
     }
 }
 
@@ -0,0 +1,74 @@
+//! Default batched distance kernels for [`DataSource`].
+//!
+//! The provided batch methods of [`DataSource`] delegate here: query-centric
+//! batches become pair batches, and pair batches fall back to one
+//! [`DataSource::distance`] call per pair. Each kernel leaves its output
+//! untouched when it fails.
+
+use super::DataSource;
+use crate::error::DataSourceError;
+
+/// Scores `candidates` against `query` through [`DataSource::distance_batch`].
+pub(super) fn query_distances<S: DataSource + ?Sized>(
+    source: &S,
+    query: usize,
+    candidates: &[usize],
+) -> Result<Vec<f32>, DataSourceError> {
+    if query >= source.len() {
+        return Err(DataSourceError::OutOfBounds { index: query });
+    }
+    let pairs: Vec<(usize, usize)> = candidates
+        .iter()
+        .copied()
+        .map(|candidate| (query, candidate))
+        .collect();
+    let mut out = vec![0.0_f32; pairs.len()];
+    source.distance_batch(&pairs, &mut out)?;
+    Ok(out)
+}
+
+/// Scores `targets` against `query` through [`DataSource::batch_distances`]
+/// and copies the result into `out`.
+pub(super) fn query_distances_into<S: DataSource + ?Sized>(
+    source: &S,
+    query: usize,
+    targets: &[usize],
+    out: &mut [f32],
+) -> Result<(), DataSourceError> {
+    if targets.len() != out.len() {
+        return Err(DataSourceError::OutputLengthMismatch {
+            out: out.len(),
+            expected: targets.len(),
+        });
+    }
+    let distances = source.batch_distances(query, targets)?;
+    if distances.len() != out.len() {
+        return Err(DataSourceError::OutputLengthMismatch {
+            out: out.len(),
+            expected: distances.len(),
+        });
+    }
+    out.copy_from_slice(&distances);
+    Ok(())
+}
+
+/// Scores each pair with [`DataSource::distance`].
+pub(super) fn pair_distances<S: DataSource + ?Sized>(
+    source: &S,
+    pairs: &[(usize, usize)],
+    out: &mut [f32],
+) -> Result<(), DataSourceError> {
+    if pairs.len() != out.len() {
+        return Err(DataSourceError::OutputLengthMismatch {
+            out: out.len(),
+            expected: pairs.len(),
+        });
+    }
+    // Compute into a temp buffer to keep `out` unchanged on error.
+    let mut tmp = vec![0.0_f32; pairs.len()];
+    for (idx, (i, j)) in pairs.iter().enumerate() {
+        tmp[idx] = source.distance(*i, *j)?;
+    }
+    out.copy_from_slice(&tmp);
+    Ok(())
+}
@@ -8,6 +8,7 @@ use std::sync::{
 };
 
 mod batch_first_source;
+mod batch_into;
 
 #[test]
 fn batch_distances_invokes_scalar_distance() {
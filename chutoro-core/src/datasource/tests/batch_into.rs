@@ -0,0 +1,60 @@
+//! Tests for the default `DataSource::batch_distances_into`.
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{DataSource, DataSourceError, test_utils::CountingSource};
+
+#[test]
+fn batch_distances_into_writes_scalar_distances_by_default() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let source = CountingSource::new(vec![0.0, 1.0, 3.0], Arc::clone(&calls));
+    let mut out = [0.0; 2];
+
+    source
+        .batch_distances_into(2, &[0, 1], &mut out)
+        .expect("batch distances should succeed");
+
+    assert_eq!(out, [3.0, 2.0]);
+    assert_eq!(calls.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn batch_distances_into_rejects_mismatched_output() {
+    let source = CountingSource::new(vec![0.0, 1.0], Arc::new(AtomicUsize::new(0)));
+    let mut out = [7.0; 3];
+
+    let err = source
+        .batch_distances_into(0, &[1], &mut out)
+        .expect_err("mismatched output length must fail");
+
+    assert!(
+        matches!(
+            err,
+            DataSourceError::OutputLengthMismatch {
+                out: 3,
+                expected: 1
+            }
+        ),
+        "expected OutputLengthMismatch, got {err:?}",
+    );
+    assert_eq!(out, [7.0; 3]);
+}
+
+#[test]
+fn batch_distances_into_leaves_output_unmodified_on_error() {
+    let source = CountingSource::new(vec![0.0, 1.0], Arc::new(AtomicUsize::new(0)));
+    let mut out = [7.0; 2];
+
+    let err = source
+        .batch_distances_into(0, &[1, 5], &mut out)
+        .expect_err("invalid target must fail");
+
+    assert!(
+        matches!(err, DataSourceError::OutOfBounds { index: 5 }),
+        "expected OutOfBounds with index 5, got {err:?}",
+    );
+    assert_eq!(out, [7.0; 2]);
+}
@@ -0,0 +1,154 @@
+//! Sampled triangle-inequality checks for sources that declare a metric.
+//!
+//! HNSW navigates greedily, trusting that a point close to a close neighbour
+//! is itself close. When a source declares a [`super::MetricClass`] that
+//! promises the triangle inequality but breaks it, recall degrades without
+//! any error. [`check_triangle_inequality`] samples triples so the mismatch
+//! can be reported.
+
+use std::fmt;
+
+use crate::{DataSource, DataSourceError};
+
+use super::validate::{TOLERANCE, draw, next_pair};
+
+/// A sampled triple where `d(first, third)` exceeds the detour through
+/// `middle`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TriangleViolation {
+    /// Start of the direct path.
+    pub first: usize,
+    /// Point the detour passes through.
+    pub middle: usize,
+    /// End of the direct path.
+    pub third: usize,
+    /// `d(first, third)`.
+    pub direct: f32,
+    /// `d(first, middle) + d(middle, third)`.
+    pub detour: f32,
+}
+
+impl fmt::Display for TriangleViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            first,
+            middle,
+            third,
+            direct,
+            detour,
+        } = self;
+        write!(
+            f,
+            "d({first}, {third}) = {direct} exceeds d({first}, {middle}) + d({middle}, {third}) = {detour}"
+        )
+    }
+}
+
+/// Findings of [`check_triangle_inequality`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TriangleReport {
+    sampled_triples: usize,
+    violations: Vec<TriangleViolation>,
+}
+
+impl TriangleReport {
+    /// Returns the number of triples that were sampled.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn sampled_triples(&self) -> usize { self.sampled_triples }
+
+    /// Returns every violating triple, in sampling order.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn violations(&self) -> &[TriangleViolation] { &self.violations }
+
+    /// Returns whether every sampled triple satisfied the inequality.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    fn record<D: DataSource + ?Sized>(
+        &mut self,
+        source: &D,
+        (first, middle, third): (usize, usize, usize),
+    ) -> Result<(), DataSourceError> {
+        let direct = source.distance(first, third)?;
+        let detour = source.distance(first, middle)? + source.distance(middle, third)?;
+        self.sampled_triples += 1;
+        // Non-finite distances are `validate_source`'s concern, and compare
+        // false here.
+        if direct - detour > TOLERANCE * direct.abs().max(1.0) {
+            self.violations.push(TriangleViolation {
+                first,
+                middle,
+                third,
+                direct,
+                detour,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Checks `d(i, k) <= d(i, j) + d(j, k)` on `sample_size` seeded triples of
+/// distinct points.
+///
+/// Each triple costs three evaluations. Rounding errors up to `1e-5`
+/// relative to the direct distance are tolerated, and NaN or infinite
+/// distances are left to [`super::validate_source`]. The same `seed` always
+/// samples the same triples; sources with fewer than three points have none.
+///
+/// # Errors
+/// Returns the first [`DataSourceError`] reported by the source.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::{DataSource, DataSourceError, datasource::check_triangle_inequality};
+///
+/// /// Squared distance on a line, which is not a metric.
+/// struct Squared(Vec<f32>);
+///
+/// impl DataSource for Squared {
+///     fn len(&self) -> usize { self.0.len() }
+///     fn name(&self) -> &str { "squared" }
+///     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+///         Ok((self.0[i] - self.0[j]).powi(2))
+///     }
+/// }
+///
+/// let report = check_triangle_inequality(&Squared(vec![0.0, 1.0, 2.0]), 64, 7)?;
+/// assert!(!report.is_valid());
+/// let violation = report.violations()[0];
+/// assert_eq!((violation.direct, violation.detour), (4.0, 2.0));
+/// # Ok::<(), DataSourceError>(())
+/// ```
+pub fn check_triangle_inequality<D: DataSource + ?Sized>(
+    source: &D,
+    sample_size: usize,
+    seed: u64,
+) -> Result<TriangleReport, DataSourceError> {
+    let mut report = TriangleReport::default();
+    let items = source.len();
+    if items < 3 {
+        return Ok(report);
+    }
+    let mut state = seed;
+    for _ in 0..sample_size {
+        report.record(source, next_triple(&mut state, items))?;
+    }
+    Ok(report)
+}
+
+/// Draws three distinct indices below `items`, which must be at least three.
+fn next_triple(state: &mut u64, items: usize) -> (usize, usize, usize) {
+    let (first, third) = next_pair(state, items);
+    // Skip over `first` and `third` so `middle` is uniform among the rest.
+    let mut middle = draw(state, items - 2);
+    for taken in [first.min(third), first.max(third)] {
+        if middle >= taken {
+            middle += 1;
+        }
+    }
+    (first, middle, third)
+}
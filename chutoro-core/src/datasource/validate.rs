@@ -0,0 +1,275 @@
+//! Sampled checks of the metric properties a [`DataSource`] must satisfy.
+//!
+//! The pipeline trusts every distance it is given: a metric that returns NaN,
+//! a negative value, a non-zero self-distance, or a value that depends on
+//! argument order corrupts the index and the hierarchy without failing the
+//! run. [`validate_source`] evaluates a seeded sample of pairs so a broken
+//! implementation can be caught before it is clustered.
+
+use std::fmt;
+
+use crate::{DataSource, DataSourceError, seed::splitmix64};
+
+/// Absolute tolerance for self-distances and negative distances, and the
+/// relative tolerance for asymmetry, scaled by the larger distance (or `1.0`
+/// for smaller ones). Absorbs rounding in metrics such as cosine distance.
+pub(super) const TOLERANCE: f32 = 1e-5;
+
+/// Metric properties checked by [`validate_source`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ViolationKind {
+    /// A distance was NaN or infinite.
+    NonFinite,
+    /// A distance was negative.
+    Negative,
+    /// `d(i, i)` was not zero.
+    Identity,
+    /// `d(i, j)` differed from `d(j, i)`.
+    Asymmetric,
+}
+
+/// A sampled distance that breaks a metric property.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum Violation {
+    /// `d(left, right)` was NaN or infinite.
+    NonFinite {
+        /// First point passed to [`DataSource::distance`].
+        left: usize,
+        /// Second point passed to [`DataSource::distance`].
+        right: usize,
+        /// Returned distance.
+        distance: f32,
+    },
+    /// `d(left, right)` was negative.
+    Negative {
+        /// First point passed to [`DataSource::distance`].
+        left: usize,
+        /// Second point passed to [`DataSource::distance`].
+        right: usize,
+        /// Returned distance.
+        distance: f32,
+    },
+    /// The finite distance from a point to itself was not zero.
+    Identity {
+        /// Point compared with itself.
+        index: usize,
+        /// Returned distance.
+        distance: f32,
+    },
+    /// Both directions of a pair were finite but differed.
+    Asymmetric {
+        /// First point of the pair.
+        left: usize,
+        /// Second point of the pair.
+        right: usize,
+        /// `d(left, right)`.
+        forward: f32,
+        /// `d(right, left)`.
+        backward: f32,
+    },
+}
+
+impl Violation {
+    /// Returns the property this violation breaks.
+    #[must_use]
+    pub const fn kind(&self) -> ViolationKind {
+        match self {
+            Self::NonFinite { .. } => ViolationKind::NonFinite,
+            Self::Negative { .. } => ViolationKind::Negative,
+            Self::Identity { .. } => ViolationKind::Identity,
+            Self::Asymmetric { .. } => ViolationKind::Asymmetric,
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonFinite {
+                left,
+                right,
+                distance,
+            } => write!(f, "d({left}, {right}) = {distance} is not finite"),
+            Self::Negative {
+                left,
+                right,
+                distance,
+            } => write!(f, "d({left}, {right}) = {distance} is negative"),
+            Self::Identity { index, distance } => {
+                write!(f, "d({index}, {index}) = {distance} is not zero")
+            }
+            Self::Asymmetric {
+                left,
+                right,
+                forward,
+                backward,
+            } => write!(
+                f,
+                "d({left}, {right}) = {forward} differs from d({right}, {left}) = {backward}"
+            ),
+        }
+    }
+}
+
+/// Findings of [`validate_source`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ValidationReport {
+    sampled_pairs: usize,
+    violations: Vec<Violation>,
+}
+
+impl ValidationReport {
+    /// Returns the number of point pairs that were sampled.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn sampled_pairs(&self) -> usize { self.sampled_pairs }
+
+    /// Returns every violation found, in sampling order.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn violations(&self) -> &[Violation] { &self.violations }
+
+    /// Returns the number of sampled pairs that broke `kind`.
+    ///
+    /// Each pair records at most one violation of each kind.
+    #[must_use]
+    pub fn count(&self, kind: ViolationKind) -> usize {
+        self.violations
+            .iter()
+            .filter(|violation| violation.kind() == kind)
+            .count()
+    }
+
+    /// Returns whether every sampled distance satisfied the metric
+    /// properties.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Evaluates both directions of `(left, right)` and `d(left, left)`,
+    /// recording at most one violation of each kind.
+    fn record<D: DataSource + ?Sized>(
+        &mut self,
+        source: &D,
+        (left, right): (usize, usize),
+    ) -> Result<(), DataSourceError> {
+        let forward = source.distance(left, right)?;
+        let backward = source.distance(right, left)?;
+        let identity = source.distance(left, left)?;
+        self.sampled_pairs += 1;
+        let evaluations = [
+            (left, right, forward),
+            (right, left, backward),
+            (left, left, identity),
+        ];
+        if let Some(&(left, right, distance)) = evaluations
+            .iter()
+            .find(|(.., distance)| !distance.is_finite())
+        {
+            self.violations.push(Violation::NonFinite {
+                left,
+                right,
+                distance,
+            });
+        }
+        if let Some(&(left, right, distance)) = evaluations[..2]
+            .iter()
+            .find(|(.., distance)| *distance < -TOLERANCE)
+        {
+            self.violations.push(Violation::Negative {
+                left,
+                right,
+                distance,
+            });
+        }
+        if identity.is_finite() && identity.abs() > TOLERANCE {
+            self.violations.push(Violation::Identity {
+                index: left,
+                distance: identity,
+            });
+        }
+        let scale = forward.abs().max(backward.abs()).max(1.0);
+        if forward.is_finite()
+            && backward.is_finite()
+            && (forward - backward).abs() > TOLERANCE * scale
+        {
+            self.violations.push(Violation::Asymmetric {
+                left,
+                right,
+                forward,
+                backward,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Checks `source` against the properties of a metric on `sample_size`
+/// seeded pairs of distinct points.
+///
+/// Each pair `(i, j)` costs three evaluations: `d(i, j)` and `d(j, i)` must
+/// be finite, non-negative, and equal, and `d(i, i)` must be zero. Small
+/// rounding errors are tolerated. The same `seed` always samples the same
+/// pairs; sources with fewer than two points have no pairs to sample.
+///
+/// # Errors
+/// Returns the first [`DataSourceError`] reported by the source.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::{
+///     DataSource, DataSourceError,
+///     datasource::{ViolationKind, validate_source},
+/// };
+///
+/// /// Forgets the absolute value, so `d(j, i) = -d(i, j)`.
+/// struct Signed(Vec<f32>);
+///
+/// impl DataSource for Signed {
+///     fn len(&self) -> usize { self.0.len() }
+///     fn name(&self) -> &str { "signed" }
+///     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+///         Ok(self.0[i] - self.0[j])
+///     }
+/// }
+///
+/// let report = validate_source(&Signed(vec![0.0, 1.0, 5.0]), 16, 7)?;
+/// assert!(!report.is_valid());
+/// assert_eq!(report.count(ViolationKind::Negative), 16);
+/// assert_eq!(report.count(ViolationKind::Identity), 0);
+/// # Ok::<(), DataSourceError>(())
+/// ```
+pub fn validate_source<D: DataSource + ?Sized>(
+    source: &D,
+    sample_size: usize,
+    seed: u64,
+) -> Result<ValidationReport, DataSourceError> {
+    let mut report = ValidationReport::default();
+    let items = source.len();
+    if items < 2 {
+        return Ok(report);
+    }
+    let mut state = seed;
+    for _ in 0..sample_size {
+        report.record(source, next_pair(&mut state, items))?;
+    }
+    Ok(report)
+}
+
+/// Draws two distinct indices below `items`, which must be at least two.
+pub(super) fn next_pair(state: &mut u64, items: usize) -> (usize, usize) {
+    let left = draw(state, items);
+    let offset = 1 + draw(state, items - 1);
+    (left, (left + offset) % items)
+}
+
+/// Advances `state` and returns an index below `bound`.
+pub(super) fn draw(state: &mut u64, bound: usize) -> usize {
+    *state = splitmix64(*state);
+    // `usize` is at most 64 bits on every supported target, so the modulus
+    // fits back into `usize`.
+    (*state % bound as u64) as usize
+}
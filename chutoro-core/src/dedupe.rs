@@ -0,0 +1,167 @@
+//! Collapsing exact duplicates before the CPU pipeline runs.
+//!
+//! Identical points are each other's nearest neighbours, so a dataset with
+//! many of them fills HNSW neighbour lists with zero-distance links and
+//! spends distance evaluations confirming what is already known. With
+//! [`crate::ChutoroBuilder::dedupe_exact`], points sharing a
+//! [`DataSource::dedupe_key`] are confirmed with a zero-distance probe and
+//! collapsed into one representative. The pipeline clusters the
+//! representatives, each weighted by the size of its group so core distances,
+//! cluster sizes, and stability match a run over every point, and the
+//! duplicates then take their representative's label.
+
+use std::{collections::HashMap, num::NonZeroUsize, sync::Arc, time::Instant};
+
+use rayon::prelude::*;
+use tracing::info;
+
+use crate::{
+    ClusteringResult, DataSource, Result,
+    builder::PipelineOptions,
+    cpu_pipeline::run_cpu_pipeline_with_len,
+    error::ChutoroError,
+    sample::{SampleView, extrapolate, run_sampled_pipeline},
+    timings::Stage,
+};
+
+/// Points grouped with their exact duplicates.
+#[derive(Debug)]
+struct Duplicates {
+    /// Every point index with the representatives first, each half sorted.
+    order: Vec<usize>,
+    /// Number of representatives at the front of `order`.
+    representatives: usize,
+    /// For each duplicate in `order`, the position of its representative.
+    origin: Vec<usize>,
+    /// Size of each representative's group, by position.
+    weights: Arc<[usize]>,
+}
+
+/// Runs the pipeline over one representative per group of exact duplicates
+/// when deduplication is enabled, and over every point otherwise.
+pub(crate) fn run_deduplicated_pipeline<D: DataSource + Sync>(
+    source: &D,
+    items: usize,
+    min_cluster_size: NonZeroUsize,
+    options: &PipelineOptions,
+) -> Result<ClusteringResult> {
+    if !options.dedupe_exact {
+        return run_sampled_pipeline(source, items, min_cluster_size, options);
+    }
+    let started = Instant::now();
+    let groups = find_duplicates(source, items)?;
+    let grouping_time = started.elapsed();
+    if groups.origin.is_empty() {
+        return run_sampled_pipeline(source, items, min_cluster_size, options);
+    }
+    let count = groups.representatives;
+    let representatives = SampleView::new(source, &groups.order, count);
+    let weighted = PipelineOptions {
+        point_weights: Some(Arc::clone(&groups.weights)),
+        ..options.clone()
+    };
+    let clustered =
+        run_cpu_pipeline_with_len(&representatives, count, min_cluster_size, &weighted)?;
+    info!(
+        representatives = count,
+        duplicates = items - count,
+        "collapsed exact duplicates before clustering"
+    );
+    let result = extrapolate(&clustered, &groups.order, &groups.origin);
+    let timings = result
+        .timings()
+        .map(|timings| timings.with_stage_added(Stage::HnswBuild, grouping_time));
+    Ok(result.with_timings(timings))
+}
+
+/// Groups points whose keys match and whose distance is zero.
+///
+/// A point joins the first earlier representative with the same key at
+/// distance zero, so hash collisions never merge distinct points. Points
+/// without a key stay on their own.
+fn find_duplicates<D: DataSource + Sync>(source: &D, items: usize) -> Result<Duplicates> {
+    let keys: Vec<Option<u64>> = (0..items)
+        .into_par_iter()
+        .map(|point| source.dedupe_key(point))
+        .collect();
+    let mut buckets: HashMap<u64, Vec<usize>> = HashMap::new();
+    let mut representative: Vec<usize> = (0..items).collect();
+    for (point, key) in keys.into_iter().enumerate() {
+        let Some(key) = key else {
+            continue;
+        };
+        let bucket = buckets.entry(key).or_default();
+        match matching_representative(source, bucket, point)? {
+            Some(existing) => representative[point] = existing,
+            None => bucket.push(point),
+        }
+    }
+    Ok(Duplicates::from_representatives(&representative))
+}
+
+/// Returns the member of `bucket` at distance zero from `point`, if any.
+fn matching_representative<D: DataSource>(
+    source: &D,
+    bucket: &[usize],
+    point: usize,
+) -> Result<Option<usize>> {
+    for &candidate in bucket {
+        let distance =
+            source
+                .distance(candidate, point)
+                .map_err(|error| ChutoroError::DataSource {
+                    data_source: Arc::from(source.name()),
+                    error,
+                })?;
+        if distance == 0.0 {
+            return Ok(Some(candidate));
+        }
+    }
+    Ok(None)
+}
+
+impl Duplicates {
+    /// Builds the grouping from each point's representative point.
+    fn from_representatives(representative: &[usize]) -> Self {
+        let (mut order, rest): (Vec<usize>, Vec<usize>) =
+            (0..representative.len()).partition(|&point| representative[point] == point);
+        let representatives = order.len();
+        let mut position = vec![0; representative.len()];
+        for (slot, &point) in order.iter().enumerate() {
+            position[point] = slot;
+        }
+        let mut weights = vec![1; representatives];
+        let origin: Vec<usize> = rest
+            .iter()
+            .map(|&point| {
+                let slot = position[representative[point]];
+                weights[slot] += 1;
+                slot
+            })
+            .collect();
+        order.extend(rest);
+        Self {
+            order,
+            representatives,
+            origin,
+            weights: weights.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for duplicate grouping.
+
+    use super::*;
+
+    #[test]
+    fn groups_points_behind_their_first_occurrence() {
+        let groups = Duplicates::from_representatives(&[0, 1, 0, 3, 1, 0]);
+
+        assert_eq!(groups.order, [0, 1, 3, 2, 4, 5]);
+        assert_eq!(groups.representatives, 3);
+        assert_eq!(groups.origin, [0, 1, 0]);
+        assert_eq!(&*groups.weights, [3, 2, 1]);
+    }
+}
@@ -0,0 +1,208 @@
+//! Counting and capping the distance evaluations a run performs.
+//!
+//! For expensive metrics, such as Levenshtein distance over long strings,
+//! the number of distance calls rather than the point count drives the cost
+//! of a run. The CPU pipeline wraps the source in a [`BudgetSource`] that
+//! counts every pair the source evaluates and, when a budget is configured
+//! via [`crate::ChutoroBuilder::with_max_distance_evaluations`], refuses to
+//! evaluate past it. Distance-cache hits never reach the source and are not
+//! counted.
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, AtomicU64, Ordering},
+};
+
+use crate::{DataSource, DataSourceError, IdMap, MetricDescriptor, error::ChutoroError};
+
+/// Counts the distances evaluated by the wrapped source and enforces an
+/// optional budget.
+pub(crate) struct BudgetSource<'a, D> {
+    source: &'a D,
+    budget: Option<u64>,
+    evaluations: AtomicU64,
+    exhausted: AtomicBool,
+}
+
+impl<'a, D: DataSource> BudgetSource<'a, D> {
+    pub(crate) fn new(source: &'a D, budget: Option<u64>) -> Self {
+        Self {
+            source,
+            budget,
+            evaluations: AtomicU64::new(0),
+            exhausted: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns how many distances the wrapped source has evaluated.
+    pub(crate) fn evaluations(&self) -> u64 {
+        self.evaluations.load(Ordering::Relaxed)
+    }
+
+    /// Replaces `error` with [`ChutoroError::DistanceBudgetExceeded`] when the
+    /// run failed because the budget ran out.
+    ///
+    /// Once the budget is exhausted every stage sees the refusal as a data
+    /// source failure, possibly wrapped in a stage-specific error, so the
+    /// original error carries no useful detail.
+    pub(crate) fn explain(&self, error: ChutoroError) -> ChutoroError {
+        match self.budget {
+            Some(budget) if self.exhausted.load(Ordering::Relaxed) => {
+                ChutoroError::DistanceBudgetExceeded {
+                    data_source: Arc::from(self.source.name()),
+                    budget,
+                    evaluations: self.evaluations(),
+                }
+            }
+            _ => error,
+        }
+    }
+
+    /// Reserves `count` evaluations, failing without reserving any when the
+    /// budget cannot cover them all.
+    fn charge(&self, count: usize) -> Result<(), DataSourceError> {
+        // `usize` is at most 64 bits on every supported target.
+        let count = count as u64;
+        let Some(budget) = self.budget else {
+            self.evaluations.fetch_add(count, Ordering::Relaxed);
+            return Ok(());
+        };
+        self.evaluations
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used| {
+                used.checked_add(count).filter(|&total| total <= budget)
+            })
+            .map(|_| ())
+            .map_err(|_| {
+                self.exhausted.store(true, Ordering::Relaxed);
+                DataSourceError::DistanceBudgetExhausted { budget }
+            })
+    }
+}
+
+impl<D: DataSource> DataSource for BudgetSource<'_, D> {
+    #[rustfmt::skip]
+    fn len(&self) -> usize { self.source.len() }
+
+    #[rustfmt::skip]
+    fn name(&self) -> &str { self.source.name() }
+
+    fn metric_descriptor(&self) -> MetricDescriptor {
+        self.source.metric_descriptor()
+    }
+
+    fn dimension_hint(&self) -> Option<usize> {
+        self.source.dimension_hint()
+    }
+
+    fn row_ids(&self) -> Option<&IdMap> {
+        self.source.row_ids()
+    }
+
+    fn dedupe_key(&self, index: usize) -> Option<u64> {
+        self.source.dedupe_key(index)
+    }
+
+    fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+        self.charge(1)?;
+        self.source.distance(i, j)
+    }
+
+    fn distance_f64(&self, i: usize, j: usize) -> Result<f64, DataSourceError> {
+        self.charge(1)?;
+        self.source.distance_f64(i, j)
+    }
+
+    fn batch_distances(
+        &self,
+        query: usize,
+        candidates: &[usize],
+    ) -> Result<Vec<f32>, DataSourceError> {
+        self.charge(candidates.len())?;
+        self.source.batch_distances(query, candidates)
+    }
+
+    fn batch_distances_into(
+        &self,
+        query: usize,
+        targets: &[usize],
+        out: &mut [f32],
+    ) -> Result<(), DataSourceError> {
+        self.charge(targets.len())?;
+        self.source.batch_distances_into(query, targets, out)
+    }
+
+    fn distance_batch(
+        &self,
+        pairs: &[(usize, usize)],
+        out: &mut [f32],
+    ) -> Result<(), DataSourceError> {
+        self.charge(pairs.len())?;
+        self.source.distance_batch(pairs, out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for distance counting and budget enforcement.
+
+    use rstest::rstest;
+
+    use super::*;
+
+    struct Unit;
+
+    impl DataSource for Unit {
+        #[rustfmt::skip]
+        fn len(&self) -> usize { 4 }
+
+        #[rustfmt::skip]
+        fn name(&self) -> &str { "unit" }
+
+        fn distance(&self, _: usize, _: usize) -> Result<f32, DataSourceError> {
+            Ok(1.0)
+        }
+    }
+
+    #[rstest]
+    fn counts_every_evaluated_pair() {
+        let source = BudgetSource::new(&Unit, None);
+        let mut out = [0.0; 2];
+
+        source.distance(0, 1).expect("distance succeeds");
+        source
+            .batch_distances(0, &[1, 2, 3])
+            .expect("batch succeeds");
+        source
+            .distance_batch(&[(1, 2), (2, 3)], &mut out)
+            .expect("pairs succeed");
+
+        assert_eq!(source.evaluations(), 6);
+    }
+
+    #[rstest]
+    fn refuses_batches_the_budget_cannot_cover() {
+        let source = BudgetSource::new(&Unit, Some(3));
+
+        source.batch_distances(0, &[1, 2]).expect("within budget");
+        let error = source
+            .batch_distances(0, &[1, 2])
+            .expect_err("batch exceeds budget");
+        source.distance(0, 3).expect("last evaluation fits");
+
+        assert_eq!(
+            error,
+            DataSourceError::DistanceBudgetExhausted { budget: 3 }
+        );
+        assert_eq!(source.evaluations(), 3);
+        assert!(matches!(
+            source.explain(ChutoroError::InvalidSample {
+                reason: Arc::from("unrelated"),
+            }),
+            ChutoroError::DistanceBudgetExceeded {
+                budget: 3,
+                evaluations: 3,
+                ..
+            }
+        ));
+    }
+}
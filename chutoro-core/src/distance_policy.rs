@@ -0,0 +1,260 @@
+//! Handling of non-finite distances returned by a [`crate::DataSource`].
+//!
+//! Providers backed by dirty data can yield NaN or infinite distances. By
+//! default the first such value aborts the run, but a [`DistancePolicy`] lets
+//! the CPU pipeline clamp or skip them instead. The policy is applied where
+//! each distance is validated: HNSW distance validation, NN-descent, and
+//! component repair consult the run's [`DistanceGuard`], which replaces the
+//! value, remembers skipped pairs, and counts what it did for the
+//! [`DistancePolicyReport`].
+
+#[cfg(feature = "cpu")]
+use std::{
+    collections::HashSet,
+    sync::{
+        Mutex, PoisonError,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+#[cfg(feature = "cpu")]
+use crate::{CandidateEdge, EdgeHarvest};
+
+/// Distance that replaces a non-finite distance under a lenient policy.
+#[cfg(feature = "cpu")]
+const REPLACED_DISTANCE: f32 = f32::MAX;
+
+/// Selects how the CPU pipeline treats NaN or infinite distances.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::{ChutoroBuilder, DistancePolicy};
+///
+/// let builder = ChutoroBuilder::new().with_distance_policy(DistancePolicy::ClampToMax);
+/// assert_eq!(builder.distance_policy(), DistancePolicy::ClampToMax);
+/// assert_eq!(DistancePolicy::default(), DistancePolicy::Strict);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DistancePolicy {
+    /// Fail the run on the first non-finite distance.
+    #[default]
+    Strict,
+    /// Replace non-finite distances with `f32::MAX`, so the pair is treated
+    /// as maximally distant but still connected.
+    ClampToMax,
+    /// Treat the pair as unrelated: it never ranks as a near neighbour and
+    /// its candidate edge is dropped before MST construction.
+    SkipEdge,
+}
+
+/// Counts the non-finite distances a [`DistancePolicy`] replaced during a run.
+///
+/// Counts are per distance evaluation: cached replacements are reused, but a
+/// pair evaluated again after leaving the distance cache is counted again.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::{DistancePolicy, DistancePolicyReport};
+///
+/// let report = DistancePolicyReport::new(DistancePolicy::SkipEdge, 0, 3);
+/// assert_eq!(report.skipped(), 3);
+/// assert_eq!(report.total(), 3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DistancePolicyReport {
+    policy: DistancePolicy,
+    clamped: usize,
+    skipped: usize,
+}
+
+impl DistancePolicyReport {
+    /// Creates a report for `policy` with the given replacement counts.
+    #[must_use]
+    pub fn new(policy: DistancePolicy, clamped: usize, skipped: usize) -> Self {
+        Self {
+            policy,
+            clamped,
+            skipped,
+        }
+    }
+
+    /// Returns the policy applied during the run.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn policy(&self) -> DistancePolicy { self.policy }
+
+    /// Returns how many non-finite distances were clamped to `f32::MAX`.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn clamped(&self) -> usize { self.clamped }
+
+    /// Returns how many non-finite distances were skipped.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn skipped(&self) -> usize { self.skipped }
+
+    /// Returns how many non-finite distances were replaced in total.
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.clamped + self.skipped
+    }
+
+    /// Returns the replacements made since `earlier` was taken from the
+    /// same guard.
+    #[cfg(feature = "cpu")]
+    pub(crate) fn since(self, earlier: Option<Self>) -> Self {
+        let Some(earlier) = earlier else {
+            return self;
+        };
+        Self::new(
+            self.policy,
+            self.clamped.saturating_sub(earlier.clamped),
+            self.skipped.saturating_sub(earlier.skipped),
+        )
+    }
+}
+
+/// Applies a [`DistancePolicy`] to the non-finite distances a run validates.
+///
+/// Skipped pairs are recorded by their endpoints, so dropping their
+/// candidate edges does not depend on the replacement value.
+#[cfg(feature = "cpu")]
+#[derive(Debug, Default)]
+pub(crate) struct DistanceGuard {
+    policy: DistancePolicy,
+    clamped: AtomicUsize,
+    skipped: AtomicUsize,
+    skipped_pairs: Mutex<HashSet<(usize, usize)>>,
+}
+
+#[cfg(feature = "cpu")]
+impl DistanceGuard {
+    pub(crate) fn new(policy: DistancePolicy) -> Self {
+        Self {
+            policy,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the distance to use for the pair, or `None` when a strict
+    /// policy rejects a non-finite `distance`.
+    pub(crate) fn admit(&self, left: usize, right: usize, distance: f32) -> Option<f32> {
+        if distance.is_finite() {
+            return Some(distance);
+        }
+        self.replace(left, right).then_some(REPLACED_DISTANCE)
+    }
+
+    /// Applies the policy to a double-precision re-evaluation of a pair the
+    /// run already validated in single precision. Strict runs keep the value.
+    pub(crate) fn admit_f64(&self, left: usize, right: usize, distance: f64) -> f64 {
+        if distance.is_finite() || !self.replace(left, right) {
+            return distance;
+        }
+        f64::from(REPLACED_DISTANCE)
+    }
+
+    /// Returns the replacement counts, or `None` under the strict policy.
+    pub(crate) fn report(&self) -> Option<DistancePolicyReport> {
+        let clamped = self.clamped.load(Ordering::Relaxed);
+        let skipped = self.skipped.load(Ordering::Relaxed);
+        match self.policy {
+            DistancePolicy::Strict => None,
+            _ => Some(DistancePolicyReport::new(self.policy, clamped, skipped)),
+        }
+    }
+
+    /// Returns `edges` without the edges of skipped pairs, or `None` when no
+    /// pair was skipped.
+    pub(crate) fn without_skipped(&self, edges: &EdgeHarvest) -> Option<EdgeHarvest> {
+        let skipped = self
+            .skipped_pairs
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if skipped.is_empty() {
+            return None;
+        }
+        let kept =
+            |edge: &&CandidateEdge| !skipped.contains(&ordered(edge.source(), edge.target()));
+        Some(EdgeHarvest::new(
+            edges.iter().filter(kept).copied().collect(),
+        ))
+    }
+
+    /// Counts a replacement for the pair, returning `false` under the strict
+    /// policy.
+    fn replace(&self, left: usize, right: usize) -> bool {
+        match self.policy {
+            DistancePolicy::Strict => return false,
+            DistancePolicy::ClampToMax => self.clamped.fetch_add(1, Ordering::Relaxed),
+            DistancePolicy::SkipEdge => {
+                self.skipped_pairs
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .insert(ordered(left, right));
+                self.skipped.fetch_add(1, Ordering::Relaxed)
+            }
+        };
+        true
+    }
+}
+
+#[cfg(feature = "cpu")]
+fn ordered(left: usize, right: usize) -> (usize, usize) {
+    (left.min(right), left.max(right))
+}
+
+#[cfg(all(test, feature = "cpu"))]
+mod tests {
+    //! Unit tests for non-finite distance replacement.
+
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::strict(DistancePolicy::Strict, None)]
+    #[case::clamp(DistancePolicy::ClampToMax, Some((2, 0)))]
+    #[case::skip(DistancePolicy::SkipEdge, Some((0, 2)))]
+    fn replaces_non_finite_distances(
+        #[case] policy: DistancePolicy,
+        #[case] expected: Option<(usize, usize)>,
+    ) {
+        let guard = DistanceGuard::new(policy);
+
+        assert_eq!(guard.admit(0, 1, 1.0), Some(1.0));
+        let replaced = guard.admit(0, 2, f32::NAN);
+        let single = guard.admit(2, 1, f32::INFINITY);
+
+        if policy == DistancePolicy::Strict {
+            assert_eq!((replaced, single), (None, None));
+        } else {
+            assert_eq!((replaced, single), (Some(f32::MAX), Some(f32::MAX)));
+        }
+        let counts = guard
+            .report()
+            .map(|report| (report.clamped(), report.skipped()));
+        assert_eq!(counts, expected);
+    }
+
+    #[test]
+    fn drops_only_the_edges_of_skipped_pairs() {
+        let guard = DistanceGuard::new(DistancePolicy::SkipEdge);
+        let edges = EdgeHarvest::new(vec![
+            CandidateEdge::new(0, 1, f32::MAX, 0),
+            CandidateEdge::new(1, 2, f32::MAX, 1),
+        ]);
+        assert!(guard.without_skipped(&edges).is_none());
+
+        assert_eq!(guard.admit(2, 1, f32::NAN), Some(f32::MAX));
+        let kept = guard.without_skipped(&edges).expect("a pair was skipped");
+
+        let pairs: Vec<_> = kept
+            .iter()
+            .map(|edge| (edge.source(), edge.target()))
+            .collect();
+        assert_eq!(pairs, vec![(0, 1)]);
+    }
+}
@@ -0,0 +1,299 @@
+//! Monotone transforms applied to every distance a [`crate::DataSource`]
+//! returns.
+//!
+//! Switching between Euclidean and squared Euclidean distance, or damping a
+//! heavy-tailed metric with a logarithm, should not require writing a wrapper
+//! source. A [`DistanceTransform`] configured on the builder is applied by
+//! runs and dry runs straight after the provider computes each distance,
+//! before the distance policy, the HNSW index, or core distances see it, and
+//! the transform is recorded in the wrapped source's [`MetricDescriptor`].
+
+use std::fmt;
+
+use crate::{DataSource, DataSourceError, IdMap, MetricClass, MetricDescriptor};
+
+/// A function applied to each distance the data source returns.
+///
+/// Transforms should be non-decreasing and map `0.0` to `0.0`, so that
+/// nearest neighbours keep their order and identical items stay at distance
+/// zero. [`DistanceTransform::Sqrt`] and [`DistanceTransform::Log1p`] keep the
+/// triangle inequality of a metric, while [`DistanceTransform::Square`] does
+/// not, so the transformed descriptor downgrades the declared
+/// [`MetricClass`] accordingly.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::{ChutoroBuilder, DistanceTransform};
+///
+/// let builder = ChutoroBuilder::new().with_distance_transform(DistanceTransform::Square);
+/// assert_eq!(builder.distance_transform(), DistanceTransform::Square);
+/// assert_eq!(DistanceTransform::Log1p.apply(0.0), 0.0);
+/// assert_eq!(DistanceTransform::Custom(|d| 2.0 * d).apply(1.5), 3.0);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub enum DistanceTransform {
+    /// Leave distances unchanged.
+    #[default]
+    Identity,
+    /// Square each distance, turning Euclidean into squared Euclidean.
+    Square,
+    /// Take the square root of each distance, turning squared Euclidean back
+    /// into Euclidean.
+    Sqrt,
+    /// Replace each distance `d` with `ln(1 + d)`, compressing long tails.
+    Log1p,
+    /// Apply a caller-supplied function to each distance.
+    Custom(fn(f32) -> f32),
+}
+
+impl DistanceTransform {
+    /// Applies the transform to one distance.
+    #[must_use]
+    pub fn apply(self, distance: f32) -> f32 {
+        match self {
+            Self::Identity => distance,
+            Self::Square => distance * distance,
+            Self::Sqrt => distance.sqrt(),
+            Self::Log1p => distance.ln_1p(),
+            Self::Custom(transform) => transform(distance),
+        }
+    }
+
+    /// Applies the transform at `f64` precision; custom transforms, which
+    /// take an `f32`, are applied to the narrowed distance.
+    pub(crate) fn apply_f64(self, distance: f64) -> f64 {
+        match self {
+            Self::Identity => distance,
+            Self::Square => distance * distance,
+            Self::Sqrt => distance.sqrt(),
+            Self::Log1p => distance.ln_1p(),
+            Self::Custom(transform) => f64::from(transform(distance as f32)),
+        }
+    }
+
+    /// Returns whether the transform leaves distances unchanged.
+    #[must_use]
+    pub fn is_identity(self) -> bool {
+        matches!(self, Self::Identity)
+    }
+
+    /// Returns the descriptor of `metric` after the transform.
+    ///
+    /// The transform is appended to the identifier, so caches keyed by the
+    /// descriptor tell transformed and raw distances apart. A custom
+    /// transform makes no guarantee, so its descriptor declares no class.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{DistanceTransform, MetricClass, MetricDescriptor};
+    ///
+    /// let euclidean = MetricDescriptor::new("euclidean").with_class(MetricClass::Metric);
+    /// let squared = DistanceTransform::Square.describe(&euclidean);
+    /// assert_eq!(squared.as_str(), "euclidean|square");
+    /// assert_eq!(squared.class(), Some(MetricClass::NonMetric));
+    /// ```
+    #[must_use]
+    pub fn describe(self, metric: &MetricDescriptor) -> MetricDescriptor {
+        if self.is_identity() {
+            return metric.clone();
+        }
+        let described = MetricDescriptor::new(format!("{metric}|{self}"));
+        let class = match self {
+            Self::Identity | Self::Sqrt | Self::Log1p => metric.class(),
+            Self::Square => metric.class().map(|_| MetricClass::NonMetric),
+            Self::Custom(_) => None,
+        };
+        match class {
+            Some(class) => described.with_class(class),
+            None => described,
+        }
+    }
+}
+
+impl PartialEq for DistanceTransform {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Custom(left), Self::Custom(right)) => std::ptr::fn_addr_eq(*left, *right),
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+impl fmt::Display for DistanceTransform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Identity => "identity",
+            Self::Square => "square",
+            Self::Sqrt => "sqrt",
+            Self::Log1p => "log1p",
+            Self::Custom(_) => "custom",
+        })
+    }
+}
+
+/// Applies a [`DistanceTransform`] to every distance the wrapped source
+/// returns.
+pub(crate) struct TransformSource<'a, D> {
+    source: &'a D,
+    transform: DistanceTransform,
+}
+
+impl<'a, D: DataSource> TransformSource<'a, D> {
+    pub(crate) fn new(source: &'a D, transform: DistanceTransform) -> Self {
+        Self { source, transform }
+    }
+
+    fn apply_all(&self, distances: &mut [f32]) {
+        if self.transform.is_identity() {
+            return;
+        }
+        for distance in distances {
+            *distance = self.transform.apply(*distance);
+        }
+    }
+}
+
+impl<D: DataSource> DataSource for TransformSource<'_, D> {
+    #[rustfmt::skip]
+    fn len(&self) -> usize { self.source.len() }
+
+    #[rustfmt::skip]
+    fn name(&self) -> &str { self.source.name() }
+
+    fn metric_descriptor(&self) -> MetricDescriptor {
+        self.transform.describe(&self.source.metric_descriptor())
+    }
+
+    fn dimension_hint(&self) -> Option<usize> {
+        self.source.dimension_hint()
+    }
+
+    fn row_ids(&self) -> Option<&IdMap> {
+        self.source.row_ids()
+    }
+
+    fn dedupe_key(&self, index: usize) -> Option<u64> {
+        self.source.dedupe_key(index)
+    }
+
+    fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+        self.source
+            .distance(i, j)
+            .map(|distance| self.transform.apply(distance))
+    }
+
+    fn distance_f64(&self, i: usize, j: usize) -> Result<f64, DataSourceError> {
+        self.source
+            .distance_f64(i, j)
+            .map(|distance| self.transform.apply_f64(distance))
+    }
+
+    fn batch_distances(
+        &self,
+        query: usize,
+        candidates: &[usize],
+    ) -> Result<Vec<f32>, DataSourceError> {
+        let mut distances = self.source.batch_distances(query, candidates)?;
+        self.apply_all(&mut distances);
+        Ok(distances)
+    }
+
+    fn batch_distances_into(
+        &self,
+        query: usize,
+        targets: &[usize],
+        out: &mut [f32],
+    ) -> Result<(), DataSourceError> {
+        self.source.batch_distances_into(query, targets, out)?;
+        self.apply_all(out);
+        Ok(())
+    }
+
+    fn distance_batch(
+        &self,
+        pairs: &[(usize, usize)],
+        out: &mut [f32],
+    ) -> Result<(), DataSourceError> {
+        self.source.distance_batch(pairs, out)?;
+        self.apply_all(out);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for transforming source distances.
+
+    use rstest::rstest;
+
+    use super::*;
+
+    struct Line;
+
+    impl DataSource for Line {
+        #[rustfmt::skip]
+        fn len(&self) -> usize { 3 }
+
+        #[rustfmt::skip]
+        fn name(&self) -> &str { "line" }
+
+        fn metric_descriptor(&self) -> MetricDescriptor {
+            MetricDescriptor::new("line").with_class(MetricClass::Metric)
+        }
+
+        fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+            Ok(i.abs_diff(j) as f32 * 3.0)
+        }
+    }
+
+    #[rstest]
+    #[case::identity(DistanceTransform::Identity, 6.0, "line", Some(MetricClass::Metric))]
+    #[case::square(
+        DistanceTransform::Square,
+        36.0,
+        "line|square",
+        Some(MetricClass::NonMetric)
+    )]
+    #[case::sqrt(DistanceTransform::Sqrt, 6.0_f32.sqrt(), "line|sqrt", Some(MetricClass::Metric))]
+    #[case::log1p(DistanceTransform::Log1p, 6.0_f32.ln_1p(), "line|log1p", Some(MetricClass::Metric))]
+    #[case::custom(DistanceTransform::Custom(|d| d + 1.0), 7.0, "line|custom", None)]
+    fn transforms_every_distance(
+        #[case] transform: DistanceTransform,
+        #[case] expected: f32,
+        #[case] identifier: &str,
+        #[case] class: Option<MetricClass>,
+    ) {
+        let source = TransformSource::new(&Line, transform);
+        let batch = source.batch_distances(0, &[2]).expect("batch succeeds");
+        let mut pairs = [0.0];
+        source
+            .distance_batch(&[(2, 0)], &mut pairs)
+            .expect("pairs succeed");
+
+        assert_eq!(source.distance(0, 2).expect("distance succeeds"), expected);
+        assert_eq!((batch[0], pairs[0]), (expected, expected));
+        let descriptor = source.metric_descriptor();
+        assert_eq!(descriptor.as_str(), identifier);
+        assert_eq!(descriptor.class(), class);
+    }
+
+    #[rstest]
+    fn custom_transforms_compare_by_function() {
+        fn double(distance: f32) -> f32 {
+            distance * 2.0
+        }
+        fn halve(distance: f32) -> f32 {
+            distance / 2.0
+        }
+
+        assert_eq!(
+            DistanceTransform::Custom(double),
+            DistanceTransform::Custom(double)
+        );
+        assert_ne!(
+            DistanceTransform::Custom(double),
+            DistanceTransform::Custom(halve)
+        );
+        assert_ne!(DistanceTransform::Square, DistanceTransform::Sqrt);
+    }
+}
@@ -0,0 +1,323 @@
+//! Drift between a baseline clustering and a later one.
+//!
+//! Monitoring pipelines re-cluster as data accumulates and need to know when
+//! the structure has moved: a cluster growing or shrinking, clusters
+//! appearing or vanishing, or a cluster's centre wandering. A [`DriftReport`]
+//! matches the clusters of two runs over the points they share and flags the
+//! changes that exceed a set of [`DriftThresholds`].
+//!
+//! The later run must label the baseline's points first, in the same order,
+//! as a `ClusteringSession` does after appending rows or a batch job
+//! does when it re-clusters a growing table.
+
+mod types;
+
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use crate::{ClusterId, ClusteringResult};
+
+pub use self::types::{ClusterDrift, DriftThresholds};
+
+/// Errors raised when two clusterings cannot be compared.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum DriftError {
+    /// The current run labels fewer points than the baseline, so it cannot
+    /// extend it.
+    #[error("current run labels {current} points but the baseline labels {baseline}")]
+    BaselineNotPrefix {
+        /// Points labelled by the baseline.
+        baseline: usize,
+        /// Points labelled by the current run.
+        current: usize,
+    },
+    /// The dense rows do not cover the current run's points.
+    #[error("{rows} rows were supplied for {points} points")]
+    RowCountMismatch {
+        /// Number of rows supplied.
+        rows: usize,
+        /// Points labelled by the current run.
+        points: usize,
+    },
+    /// A dense row has a different width from the first row.
+    #[error("row {row} has {actual} features but row 0 has {expected}")]
+    RaggedRows {
+        /// Index of the offending row.
+        row: usize,
+        /// Width of the first row.
+        expected: usize,
+        /// Width of the offending row.
+        actual: usize,
+    },
+}
+
+/// Changes between a baseline clustering and a later one.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::{ClusterId, ClusteringResult, DriftReport, DriftThresholds};
+///
+/// let ids = |labels: &[u64]| {
+///     ClusteringResult::from_assignments(labels.iter().copied().map(ClusterId::new).collect())
+/// };
+/// let baseline = ids(&[0, 0, 1, 1]);
+/// let current = ids(&[1, 1, 0, 0, 1, 1, 1, 2]);
+///
+/// let report = DriftReport::compare(&baseline, &current, DriftThresholds::default())?;
+/// assert_eq!(report.clusters()[0].current, ClusterId::new(1));
+/// assert!(report.clusters()[0].flagged);
+/// assert_eq!(report.appeared(), &[ClusterId::new(2)]);
+/// assert!(report.disappeared().is_empty());
+/// # Ok::<(), chutoro_core::DriftError>(())
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct DriftReport {
+    clusters: Vec<ClusterDrift>,
+    appeared: Vec<ClusterId>,
+    disappeared: Vec<ClusterId>,
+}
+
+impl DriftReport {
+    /// Compares `current` with `baseline` by population alone.
+    ///
+    /// Each non-noise baseline cluster is matched with the current cluster
+    /// of highest Jaccard similarity over the baseline's points, provided it
+    /// reaches [`DriftThresholds::match_jaccard`]. Unmatched baseline clusters
+    /// have disappeared; current clusters matched by none have appeared.
+    ///
+    /// # Errors
+    /// Returns [`DriftError::BaselineNotPrefix`] when `current` labels fewer
+    /// points than `baseline`.
+    pub fn compare(
+        baseline: &ClusteringResult,
+        current: &ClusteringResult,
+        thresholds: DriftThresholds,
+    ) -> Result<Self, DriftError> {
+        Self::build(baseline, current, thresholds, None)
+    }
+
+    /// Compares like [`Self::compare`] and also measures how far each
+    /// matched cluster's centroid moved.
+    ///
+    /// `rows` holds the feature vector of every point of `current`; the
+    /// baseline's centroids are taken over its prefix.
+    ///
+    /// # Errors
+    /// Returns the errors of [`Self::compare`], plus
+    /// [`DriftError::RowCountMismatch`] when `rows` does not cover `current`
+    /// and [`DriftError::RaggedRows`] when the rows differ in width.
+    pub fn compare_dense(
+        baseline: &ClusteringResult,
+        current: &ClusteringResult,
+        rows: &[Vec<f32>],
+        thresholds: DriftThresholds,
+    ) -> Result<Self, DriftError> {
+        let points = current.assignments().len();
+        if rows.len() != points {
+            return Err(DriftError::RowCountMismatch {
+                rows: rows.len(),
+                points,
+            });
+        }
+        let expected = rows.first().map_or(0, Vec::len);
+        if let Some((row, actual)) = rows
+            .iter()
+            .map(Vec::len)
+            .enumerate()
+            .find(|&(_, width)| width != expected)
+        {
+            return Err(DriftError::RaggedRows {
+                row,
+                expected,
+                actual,
+            });
+        }
+        Self::build(baseline, current, thresholds, Some(rows))
+    }
+
+    /// Returns the matched clusters, in baseline identifier order.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn clusters(&self) -> &[ClusterDrift] { &self.clusters }
+
+    /// Returns the current clusters that continue no baseline cluster.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn appeared(&self) -> &[ClusterId] { &self.appeared }
+
+    /// Returns the baseline clusters with no continuation.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn disappeared(&self) -> &[ClusterId] { &self.disappeared }
+
+    /// Returns whether any cluster appeared, disappeared, or was flagged.
+    #[must_use]
+    pub fn has_drift(&self) -> bool {
+        !self.appeared.is_empty()
+            || !self.disappeared.is_empty()
+            || self.clusters.iter().any(|cluster| cluster.flagged)
+    }
+
+    fn build(
+        baseline: &ClusteringResult,
+        current: &ClusteringResult,
+        thresholds: DriftThresholds,
+        rows: Option<&[Vec<f32>]>,
+    ) -> Result<Self, DriftError> {
+        let shared = baseline.assignments().len();
+        if current.assignments().len() < shared {
+            return Err(DriftError::BaselineNotPrefix {
+                baseline: shared,
+                current: current.assignments().len(),
+            });
+        }
+        let before = Partition::new(baseline, shared);
+        let after = Partition::new(current, shared);
+        let centroids = rows.map(|rows| (before.centroids(rows), after.centroids(rows)));
+        let mut continued = vec![false; after.sizes.len()];
+        let mut report = Self {
+            clusters: Vec::new(),
+            appeared: Vec::new(),
+            disappeared: Vec::new(),
+        };
+        for (cluster, jaccard) in best_matches(&before, &after) {
+            let Some((next, jaccard)) = jaccard.filter(|&(_, j)| j >= thresholds.match_jaccard())
+            else {
+                report.disappeared.push(ClusterId::new(cluster as u64));
+                continue;
+            };
+            continued[next] = true;
+            let shift = centroids
+                .as_ref()
+                .map(|(old, new)| distance(&old[cluster], &new[next]));
+            report.clusters.push(ClusterDrift {
+                baseline: ClusterId::new(cluster as u64),
+                current: ClusterId::new(next as u64),
+                jaccard,
+                baseline_share: before.share(cluster),
+                current_share: after.share(next),
+                centroid_shift: shift,
+                flagged: false,
+            });
+        }
+        report.flag(thresholds);
+        report.appeared = after
+            .clusters()
+            .filter(|&cluster| !continued[cluster])
+            .map(|cluster| ClusterId::new(cluster as u64))
+            .collect();
+        Ok(report)
+    }
+
+    fn flag(&mut self, thresholds: DriftThresholds) {
+        for cluster in &mut self.clusters {
+            let moved = thresholds
+                .centroid_shift()
+                .zip(cluster.centroid_shift)
+                .is_some_and(|(limit, shift)| shift > limit);
+            cluster.flagged =
+                cluster.population_shift().abs() > thresholds.population_shift() || moved;
+        }
+    }
+}
+
+/// The non-noise clusters of one run, with sizes over all points and over
+/// the shared prefix.
+struct Partition<'a> {
+    labels: Vec<Option<usize>>,
+    sizes: Vec<usize>,
+    shared_sizes: Vec<usize>,
+    result: &'a ClusteringResult,
+}
+
+impl<'a> Partition<'a> {
+    fn new(result: &'a ClusteringResult, shared: usize) -> Self {
+        let labels: Vec<Option<usize>> = result
+            .assignments()
+            .iter()
+            .map(|&id| (Some(id) != result.noise_label()).then_some(id.get() as usize))
+            .collect();
+        let mut sizes = vec![0; result.cluster_count()];
+        let mut shared_sizes = vec![0; result.cluster_count()];
+        for (point, cluster) in labels.iter().enumerate() {
+            if let Some(cluster) = *cluster {
+                sizes[cluster] += 1;
+                shared_sizes[cluster] += usize::from(point < shared);
+            }
+        }
+        Self {
+            labels,
+            sizes,
+            shared_sizes,
+            result,
+        }
+    }
+
+    /// Yields the non-noise clusters with at least one point.
+    fn clusters(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.sizes.len()).filter(|&cluster| self.sizes[cluster] > 0)
+    }
+
+    fn share(&self, cluster: usize) -> f64 {
+        self.sizes[cluster] as f64 / self.result.assignments().len() as f64
+    }
+
+    /// Averages the rows of each cluster; empty clusters get an empty vector.
+    fn centroids(&self, rows: &[Vec<f32>]) -> Vec<Vec<f32>> {
+        let width = rows.first().map_or(0, Vec::len);
+        let mut sums = vec![vec![0.0_f64; width]; self.sizes.len()];
+        for (row, cluster) in rows.iter().zip(&self.labels) {
+            let Some(cluster) = *cluster else { continue };
+            for (sum, &value) in sums[cluster].iter_mut().zip(row) {
+                *sum += f64::from(value);
+            }
+        }
+        sums.into_iter()
+            .zip(&self.sizes)
+            .map(|(sum, &size)| {
+                sum.into_iter()
+                    .map(|total| (total / size.max(1) as f64) as f32)
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Pairs each baseline cluster with the current cluster of highest Jaccard
+/// similarity over the shared points, if any overlaps it.
+fn best_matches(
+    before: &Partition<'_>,
+    after: &Partition<'_>,
+) -> Vec<(usize, Option<(usize, f64)>)> {
+    let mut overlaps = HashMap::<(usize, usize), usize>::new();
+    for pair in before.labels.iter().zip(&after.labels) {
+        if let (&Some(old), &Some(new)) = pair {
+            *overlaps.entry((old, new)).or_default() += 1;
+        }
+    }
+    let mut best: Vec<Option<(usize, f64)>> = vec![None; before.sizes.len()];
+    for ((old, new), shared) in overlaps {
+        let union = before.shared_sizes[old] + after.shared_sizes[new] - shared;
+        let jaccard = shared as f64 / union as f64;
+        let slot = &mut best[old];
+        if slot
+            .is_none_or(|(previous, score)| jaccard > score || (jaccard == score && new < previous))
+        {
+            *slot = Some((new, jaccard));
+        }
+    }
+    before
+        .clusters()
+        .map(|cluster| (cluster, best[cluster]))
+        .collect()
+}
+
+fn distance(left: &[f32], right: &[f32]) -> f32 {
+    left.iter()
+        .zip(right)
+        .map(|(a, b)| (a - b) * (a - b))
+        .sum::<f32>()
+        .sqrt()
+}
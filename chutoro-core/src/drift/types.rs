@@ -0,0 +1,105 @@
+//! Thresholds a drift comparison applies and the per-cluster changes it
+//! reports.
+
+use crate::{ClusterId, PERSISTENCE_THRESHOLD};
+
+/// Limits beyond which a [`DriftReport`](super::DriftReport) flags a change.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::DriftThresholds;
+///
+/// let thresholds = DriftThresholds::default()
+///     .with_population_shift(0.1)
+///     .with_centroid_shift(2.5);
+/// assert_eq!(thresholds.population_shift(), 0.1);
+/// assert_eq!(thresholds.centroid_shift(), Some(2.5));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DriftThresholds {
+    match_jaccard: f64,
+    population_shift: f64,
+    centroid_shift: Option<f32>,
+}
+
+impl Default for DriftThresholds {
+    fn default() -> Self {
+        Self {
+            match_jaccard: PERSISTENCE_THRESHOLD,
+            population_shift: 0.05,
+            centroid_shift: None,
+        }
+    }
+}
+
+impl DriftThresholds {
+    /// Sets the Jaccard similarity over shared points at which a current
+    /// cluster continues a baseline cluster. Defaults to
+    /// [`PERSISTENCE_THRESHOLD`].
+    #[must_use]
+    pub fn with_match_jaccard(mut self, jaccard: f64) -> Self {
+        self.match_jaccard = jaccard;
+        self
+    }
+
+    /// Sets the change in a cluster's share of all points that is flagged.
+    /// Defaults to `0.05`, five percentage points.
+    #[must_use]
+    pub fn with_population_shift(mut self, shift: f64) -> Self {
+        self.population_shift = shift;
+        self
+    }
+
+    /// Sets the centroid movement that is flagged, in the units of the
+    /// dense rows. Unset by default, since a useful distance depends on the
+    /// data's scale.
+    #[must_use]
+    pub fn with_centroid_shift(mut self, shift: f32) -> Self {
+        self.centroid_shift = Some(shift);
+        self
+    }
+
+    /// Returns the Jaccard similarity needed to match clusters.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn match_jaccard(&self) -> f64 { self.match_jaccard }
+
+    /// Returns the flagged change in population share.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn population_shift(&self) -> f64 { self.population_shift }
+
+    /// Returns the flagged centroid movement, if set.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn centroid_shift(&self) -> Option<f32> { self.centroid_shift }
+}
+
+/// How one baseline cluster changed in the current run.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClusterDrift {
+    /// Cluster of the baseline run.
+    pub baseline: ClusterId,
+    /// Cluster of the current run that continues it.
+    pub current: ClusterId,
+    /// Jaccard similarity of the two clusters over the shared points.
+    pub jaccard: f64,
+    /// Fraction of the baseline's points in the baseline cluster.
+    pub baseline_share: f64,
+    /// Fraction of the current run's points in the current cluster.
+    pub current_share: f64,
+    /// Distance between the two clusters' centroids, when dense rows were
+    /// supplied.
+    pub centroid_shift: Option<f32>,
+    /// Whether the population or centroid change exceeds its threshold.
+    pub flagged: bool,
+}
+
+impl ClusterDrift {
+    /// Returns the change in population share, positive when the cluster
+    /// grew relative to the rest of the data.
+    #[must_use]
+    pub fn population_shift(&self) -> f64 {
+        self.current_share - self.baseline_share
+    }
+}
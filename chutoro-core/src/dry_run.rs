@@ -0,0 +1,131 @@
+//! Pre-flight validation of a configuration against a data source.
+//!
+//! [`crate::Chutoro::dry_run`] checks everything a run would reject up front,
+//! estimates memory, and probes a seeded sample of point pairs with
+//! [`crate::datasource::validate_source`] for distances a metric should never
+//! return. No index is built, so a dry run costs a few thousand distance
+//! evaluations regardless of the dataset's size.
+
+use crate::{
+    DistancePolicy, ResourceEstimate,
+    datasource::{ValidationReport, ViolationKind},
+};
+
+/// Number of point pairs a dry run probes; each costs three evaluations.
+pub(crate) const DRY_RUN_PROBES: usize = 1024;
+
+/// Findings of [`crate::Chutoro::dry_run`].
+///
+/// # Examples
+/// ```
+/// use chutoro_core::{ChutoroBuilder, DataSource, DataSourceError};
+///
+/// struct Line(Vec<f32>);
+///
+/// impl DataSource for Line {
+///     fn len(&self) -> usize { self.0.len() }
+///     fn name(&self) -> &str { "line" }
+///     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+///         Ok((self.0[i] - self.0[j]).abs())
+///     }
+/// }
+///
+/// let chutoro = ChutoroBuilder::new().with_min_cluster_size(2).build()?;
+/// let report = chutoro.dry_run(&Line(vec![0.0, 1.0, 5.0]))?;
+/// assert!(report.passed());
+/// assert_eq!(report.non_finite_pairs(), 0);
+/// # Ok::<(), chutoro_core::ChutoroError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DryRunReport {
+    estimate: ResourceEstimate,
+    max_bytes: Option<u64>,
+    distance_policy: DistancePolicy,
+    validation: ValidationReport,
+}
+
+impl DryRunReport {
+    pub(crate) fn new(
+        estimate: ResourceEstimate,
+        max_bytes: Option<u64>,
+        distance_policy: DistancePolicy,
+        validation: ValidationReport,
+    ) -> Self {
+        Self {
+            estimate,
+            max_bytes,
+            distance_policy,
+            validation,
+        }
+    }
+
+    /// Returns the estimated memory of the run.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn estimate(&self) -> ResourceEstimate { self.estimate }
+
+    /// Returns the configured memory limit, if any.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn max_bytes(&self) -> Option<u64> { self.max_bytes }
+
+    /// Returns whether the estimate fits `max_bytes`, or `None` without a
+    /// limit.
+    #[must_use]
+    pub fn fits_memory(&self) -> Option<bool> {
+        self.max_bytes.map(|limit| self.estimate.fits_within(limit))
+    }
+
+    /// Returns the number of point pairs whose distances were probed.
+    #[must_use]
+    pub fn probed_pairs(&self) -> usize {
+        self.validation.sampled_pairs()
+    }
+
+    /// Returns the probed pairs with a NaN or infinite distance.
+    #[must_use]
+    pub fn non_finite_pairs(&self) -> usize {
+        self.validation.count(ViolationKind::NonFinite)
+    }
+
+    /// Returns the probed pairs with a negative distance in either direction.
+    #[must_use]
+    pub fn negative_pairs(&self) -> usize {
+        self.validation.count(ViolationKind::Negative)
+    }
+
+    /// Returns the probed pairs whose first point had a non-zero distance to
+    /// itself.
+    #[must_use]
+    pub fn identity_pairs(&self) -> usize {
+        self.validation.count(ViolationKind::Identity)
+    }
+
+    /// Returns the probed pairs whose distance differs by argument order.
+    #[must_use]
+    pub fn asymmetric_pairs(&self) -> usize {
+        self.validation.count(ViolationKind::Asymmetric)
+    }
+
+    /// Returns the probe's findings, including the offending points.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn validation(&self) -> &ValidationReport { &self.validation }
+
+    /// Returns whether a run is expected to succeed and cluster well.
+    ///
+    /// A dry run fails when the estimate exceeds `max_bytes`, when any probed
+    /// distance is negative, asymmetric, or a non-zero self-distance, or when
+    /// a non-finite distance was found under [`DistancePolicy::Strict`], which
+    /// would abort the run.
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        let non_finite_fatal =
+            self.distance_policy == DistancePolicy::Strict && self.non_finite_pairs() > 0;
+        self.fits_memory() != Some(false)
+            && !non_finite_fatal
+            && self.negative_pairs() == 0
+            && self.identity_pairs() == 0
+            && self.asymmetric_pairs() == 0
+    }
+}
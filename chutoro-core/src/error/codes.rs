@@ -0,0 +1,77 @@
+//! Declares the stable, machine-readable code enum that accompanies each
+//! public error type.
+
+macro_rules! define_error_codes {
+    (
+        $(#[$enum_meta:meta])*
+        enum $CodeTy:ident for $ErrTy:ident {
+            $(
+                $(#[$variant_meta:meta])*
+                $CodeVariant:ident => $ErrVariant:ident $( { $($pattern:tt)* } )? => $code:expr
+            ),+ $(,)?
+        }
+    ) => {
+        $(#[$enum_meta])*
+        #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+        #[non_exhaustive]
+        pub enum $CodeTy {
+            $(
+                $(#[$variant_meta])*
+                $CodeVariant,
+            )+
+        }
+
+        impl $CodeTy {
+            /// Return the stable machine-readable representation of this error code.
+            pub const fn as_str(self) -> &'static str {
+                match self {
+                    $(Self::$CodeVariant => $code,)+
+                }
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl serde::Serialize for $CodeTy {
+            fn serialize<S: serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> core::result::Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for $CodeTy {
+            fn deserialize<D: serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> core::result::Result<Self, D::Error> {
+                let code = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+                match code.as_ref() {
+                    $($code => Ok(Self::$CodeVariant),)+
+                    other => Err(serde::de::Error::unknown_variant(other, &[$($code),+])),
+                }
+            }
+        }
+
+        impl std::fmt::Display for $CodeTy {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl $ErrTy {
+            #[doc = concat!(
+                "Retrieve the stable [`",
+                stringify!($CodeTy),
+                "`] for this error."
+            )]
+            pub const fn code(&self) -> $CodeTy {
+                match self {
+                    $(Self::$ErrVariant $( { $($pattern)* } )? => $CodeTy::$CodeVariant,)+
+                }
+            }
+        }
+    };
+}
+
+pub(super) use define_error_codes;
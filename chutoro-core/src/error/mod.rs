@@ -2,62 +2,15 @@
 //!
 //! Defines error enums exposed by the public API and a convenient result alias.
 
-use std::{fmt, num::NonZeroUsize, sync::Arc};
+mod codes;
+
+use std::{num::NonZeroUsize, path::Path, sync::Arc};
 
 use thiserror::Error;
 
+use self::codes::define_error_codes;
 use crate::builder::ExecutionStrategy;
 
-macro_rules! define_error_codes {
-    (
-        $(#[$enum_meta:meta])*
-        enum $CodeTy:ident for $ErrTy:ident {
-            $(
-                $(#[$variant_meta:meta])*
-                $CodeVariant:ident => $ErrVariant:ident $( { $($pattern:tt)* } )? => $code:expr
-            ),+ $(,)?
-        }
-    ) => {
-        $(#[$enum_meta])*
-        #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-        #[non_exhaustive]
-        pub enum $CodeTy {
-            $(
-                $(#[$variant_meta])*
-                $CodeVariant,
-            )+
-        }
-
-        impl $CodeTy {
-            /// Return the stable machine-readable representation of this error code.
-            pub const fn as_str(self) -> &'static str {
-                match self {
-                    $(Self::$CodeVariant => $code,)+
-                }
-            }
-        }
-
-        impl fmt::Display for $CodeTy {
-            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                f.write_str(self.as_str())
-            }
-        }
-
-        impl $ErrTy {
-            #[doc = concat!(
-                "Retrieve the stable [`",
-                stringify!($CodeTy),
-                "`] for this error."
-            )]
-            pub const fn code(&self) -> $CodeTy {
-                match self {
-                    $(Self::$ErrVariant $( { $($pattern)* } )? => $CodeTy::$CodeVariant,)+
-                }
-            }
-        }
-    };
-}
-
 /// An error produced by [`crate::DataSource`] operations.
 #[non_exhaustive]
 #[derive(Clone, Debug, Eq, Error, PartialEq)]
@@ -90,6 +43,16 @@ pub enum DataSourceError {
     /// Data source rows must have positive dimension.
     #[error("data source vectors must have positive dimension")]
     ZeroDimension,
+    /// The run's distance-evaluation budget cannot cover the request.
+    #[error("distance evaluation budget of {budget} is exhausted")]
+    DistanceBudgetExhausted {
+        /// Configured maximum number of distance evaluations.
+        budget: u64,
+    },
+    /// The caller cancelled the run and the source stopped evaluating
+    /// distances.
+    #[error("distance evaluation was cancelled")]
+    Cancelled,
 }
 
 define_error_codes! {
@@ -105,6 +68,10 @@ define_error_codes! {
         EmptyData => EmptyData => "DATA_SOURCE_EMPTY",
         /// Data source rows must have positive dimension.
         ZeroDimension => ZeroDimension => "DATA_SOURCE_ZERO_DIMENSION",
+        /// The run's distance-evaluation budget cannot cover the request.
+        DistanceBudgetExhausted => DistanceBudgetExhausted { .. } => "DATA_SOURCE_DISTANCE_BUDGET_EXHAUSTED",
+        /// The caller cancelled the run and the source stopped evaluating distances.
+        Cancelled => Cancelled => "DATA_SOURCE_CANCELLED",
     }
 }
 
@@ -196,6 +163,111 @@ pub enum ChutoroError {
         /// Human-readable limit (e.g., "1.0 GiB").
         limit_display: Arc<str>,
     },
+    /// A prebuilt HNSW index cannot be used with the requested configuration
+    /// or data source.
+    #[error("prebuilt index is incompatible: {reason}")]
+    PrebuiltIndexMismatch {
+        /// Description of the incompatibility.
+        reason: Arc<str>,
+    },
+    /// The configured sample specification cannot be used.
+    #[error("invalid sample: {reason}")]
+    InvalidSample {
+        /// Description of the problem.
+        reason: Arc<str>,
+    },
+    /// The configured noise reassignment policy cannot be used.
+    #[error("invalid noise reassignment policy: {reason}")]
+    InvalidReassignPolicy {
+        /// Description of the problem.
+        reason: Arc<str>,
+    },
+    /// The configuration of an [`crate::OnlineClusterer`] cannot be used.
+    #[error("invalid online clusterer configuration: {reason}")]
+    InvalidOnlineConfig {
+        /// Description of the problem.
+        reason: Arc<str>,
+    },
+    /// Supplied seed labels cannot be used.
+    #[error("invalid seed labels: {reason}")]
+    InvalidSeedLabels {
+        /// Description of the problem.
+        reason: Arc<str>,
+    },
+    /// Supplied core distances cannot be used.
+    #[error("invalid core distances: {reason}")]
+    InvalidCoreDistances {
+        /// Description of the problem.
+        reason: Arc<str>,
+    },
+    /// A custom pipeline stage returned output inconsistent with the data
+    /// source.
+    #[error("{stage} stage returned invalid output: {reason}")]
+    InvalidStageOutput {
+        /// Name of the stage (`index`, `harvest`, `mst`, or `hierarchy`).
+        stage: Arc<str>,
+        /// Description of the inconsistency.
+        reason: Arc<str>,
+    },
+    /// The run needed more distance evaluations than the configured budget.
+    #[error(
+        "clustering `{data_source}` needs more than the budget of {budget} distance \
+         evaluations ({evaluations} performed)"
+    )]
+    DistanceBudgetExceeded {
+        /// Identifier for the data source.
+        data_source: Arc<str>,
+        /// Configured maximum number of distance evaluations.
+        budget: u64,
+        /// Distance evaluations performed before the run stopped.
+        evaluations: u64,
+    },
+    /// A precomputed k-NN graph passed to
+    /// [`crate::Chutoro::cluster_from_knn_graph`] is malformed.
+    #[error("invalid k-NN graph: {reason}")]
+    InvalidKnnGraph {
+        /// Description of the offending edge.
+        reason: Arc<str>,
+    },
+    /// Spilling candidate edges to disk failed, or the spill directory
+    /// cannot be used with the requested configuration.
+    #[error("edge spill at `{}` failed: {reason}", path.display())]
+    Spill {
+        /// The directory or chunk file involved.
+        path: Arc<Path>,
+        /// Description of the failure.
+        reason: Arc<str>,
+    },
+    /// Exact-duplicate collapsing cannot be combined with the requested
+    /// configuration.
+    #[error("invalid deduplication configuration: {reason}")]
+    InvalidDeduplication {
+        /// Description of the conflicting option.
+        reason: Arc<str>,
+    },
+    /// The configured [`crate::GraphBuilder`] cannot be used.
+    #[error("invalid graph builder: {reason}")]
+    InvalidGraphBuilder {
+        /// Description of the problem.
+        reason: Arc<str>,
+    },
+    /// The event log could not be opened or written.
+    #[error("event log `{}` failed: {reason}", path.display())]
+    EventLog {
+        /// The log file.
+        path: Arc<Path>,
+        /// Description of the failure.
+        reason: Arc<str>,
+    },
+    /// Writing or reading a pipeline checkpoint failed, or the checkpoint
+    /// directory cannot be used with the requested configuration.
+    #[error("checkpoint at `{}` failed: {reason}", path.display())]
+    Checkpoint {
+        /// The checkpoint directory or file involved.
+        path: Arc<Path>,
+        /// Description of the failure.
+        reason: Arc<str>,
+    },
 }
 
 define_error_codes! {
@@ -220,6 +292,34 @@ define_error_codes! {
         CpuHierarchyFailure => CpuHierarchyFailure { .. } => "CHUTORO_CPU_HIERARCHY_FAILURE",
         /// Estimated memory exceeds the configured limit.
         MemoryLimitExceeded => MemoryLimitExceeded { .. } => "CHUTORO_MEMORY_LIMIT_EXCEEDED",
+        /// A prebuilt HNSW index is incompatible with the configuration or data source.
+        PrebuiltIndexMismatch => PrebuiltIndexMismatch { .. } => "CHUTORO_PREBUILT_INDEX_MISMATCH",
+        /// The configured sample specification cannot be used.
+        InvalidSample => InvalidSample { .. } => "CHUTORO_INVALID_SAMPLE",
+        /// The configured noise reassignment policy cannot be used.
+        InvalidReassignPolicy => InvalidReassignPolicy { .. } => "CHUTORO_INVALID_REASSIGN_POLICY",
+        /// The configuration of an online clusterer cannot be used.
+        InvalidOnlineConfig => InvalidOnlineConfig { .. } => "CHUTORO_INVALID_ONLINE_CONFIG",
+        /// Supplied seed labels cannot be used.
+        InvalidSeedLabels => InvalidSeedLabels { .. } => "CHUTORO_INVALID_SEED_LABELS",
+        /// Supplied core distances cannot be used.
+        InvalidCoreDistances => InvalidCoreDistances { .. } => "CHUTORO_INVALID_CORE_DISTANCES",
+        /// A custom pipeline stage returned output inconsistent with the data source.
+        InvalidStageOutput => InvalidStageOutput { .. } => "CHUTORO_INVALID_STAGE_OUTPUT",
+        /// The run needed more distance evaluations than the configured budget.
+        DistanceBudgetExceeded => DistanceBudgetExceeded { .. } => "CHUTORO_DISTANCE_BUDGET_EXCEEDED",
+        /// A precomputed k-NN graph is malformed.
+        InvalidKnnGraph => InvalidKnnGraph { .. } => "CHUTORO_INVALID_KNN_GRAPH",
+        /// Spilling candidate edges to disk failed.
+        SpillFailure => Spill { .. } => "CHUTORO_SPILL_FAILURE",
+        /// Exact-duplicate collapsing conflicts with another option.
+        InvalidDeduplication => InvalidDeduplication { .. } => "CHUTORO_INVALID_DEDUPLICATION",
+        /// The configured graph builder cannot be used.
+        InvalidGraphBuilder => InvalidGraphBuilder { .. } => "CHUTORO_INVALID_GRAPH_BUILDER",
+        /// The event log could not be opened or written.
+        EventLogFailure => EventLog { .. } => "CHUTORO_EVENT_LOG_FAILURE",
+        /// A pipeline checkpoint could not be written, read, or resumed.
+        CheckpointFailure => Checkpoint { .. } => "CHUTORO_CHECKPOINT_FAILURE",
     }
 }
 
@@ -0,0 +1,273 @@
+//! Opt-in JSON Lines log of pipeline internals.
+//!
+//! Tracing output is tuned for live diagnosis and depends on the subscriber
+//! an application installs. The event log is a fixed, self-describing record
+//! of what a run did — the parameters it resolved, the stages it completed
+//! with their sizes and durations, and the warnings or error it ended with —
+//! written to a file that users can attach to a bug report or analyse
+//! offline. Records are flushed as they are written, so a run that aborts
+//! still leaves everything up to the failure on disk.
+
+use std::{
+    fmt::Write as _,
+    fs::{File, OpenOptions},
+    io::{self, Write as _},
+    path::Path,
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    Chutoro, ClusteringResult, Result, StageArtefact, Warning, error::ChutoroError, timings::Stage,
+};
+
+/// Appends one JSON object per line to the configured file.
+#[derive(Debug)]
+pub(crate) struct EventLog {
+    path: Arc<Path>,
+    state: Mutex<LogState>,
+}
+
+#[derive(Debug)]
+struct LogState {
+    file: File,
+    /// The first write failure; later records are dropped once one occurs.
+    error: Option<io::Error>,
+}
+
+impl EventLog {
+    /// Opens `path` for appending, creating it if needed.
+    pub(crate) fn open(path: &Path) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|error| failure(path, &error))?;
+        Ok(Self {
+            path: Arc::from(path),
+            state: Mutex::new(LogState { file, error: None }),
+        })
+    }
+
+    /// Records the outcome of a run and passes it through.
+    ///
+    /// The run's own error takes precedence; a successful run whose log
+    /// could not be written fails with [`ChutoroError::EventLog`].
+    pub(crate) fn run_ended(&self, outcome: Result<ClusteringResult>) -> Result<ClusteringResult> {
+        match &outcome {
+            Ok(result) => self.run_finished(result),
+            Err(error) => self.write(
+                Record::new("run_failed")
+                    .str("code", error.code().as_str())
+                    .str("message", &error.to_string()),
+            ),
+        }
+        let result = outcome?;
+        self.take_error()?;
+        Ok(result)
+    }
+
+    /// Records that `chutoro` is about to cluster `items` points from
+    /// `data_source`, with the parameters it resolved.
+    pub(crate) fn run_started(&self, chutoro: &Chutoro, data_source: &str, items: usize) {
+        let pipeline = chutoro.pipeline();
+        let params = &pipeline.hnsw_params;
+        self.write(
+            Record::new("run_started")
+                .str("data_source", data_source)
+                .uint("items", items as u64)
+                .str("strategy", &format!("{:?}", chutoro.execution_strategy()))
+                .uint("min_cluster_size", chutoro.min_cluster_size().get() as u64)
+                .uint("max_connections", params.max_connections() as u64)
+                .uint("ef_construction", params.ef_construction() as u64)
+                .uint("hnsw_seed", params.rng_seed())
+                .str("hnsw_rng", &format!("{:?}", params.rng_kind()))
+                .opt_uint("seed", chutoro.seed())
+                .str(
+                    "distance_transform",
+                    &format!("{:?}", pipeline.distance_transform),
+                )
+                .str(
+                    "distance_policy",
+                    &format!("{:?}", pipeline.distance_policy),
+                )
+                .opt_uint(
+                    "max_distance_evaluations",
+                    chutoro.max_distance_evaluations(),
+                )
+                .opt_uint("max_bytes", chutoro.max_bytes()),
+        );
+    }
+
+    fn run_finished(&self, result: &ClusteringResult) {
+        for warning in result.warnings() {
+            self.warning(warning);
+        }
+        self.write(
+            Record::new("run_finished")
+                .str(
+                    "backend",
+                    result.backend().map_or("", |backend| backend.as_str()),
+                )
+                .uint("clusters", result.cluster_count() as u64)
+                .float("noise_fraction", result.noise_fraction())
+                .opt_uint("distance_evaluations", result.distance_evaluations())
+                .opt_millis("total_ms", result.timings().map(|timings| timings.total())),
+        );
+    }
+
+    fn warning(&self, warning: &Warning) {
+        self.write(
+            Record::new("warning")
+                .str("code", &warning.code().to_string())
+                .str("message", &warning.to_string()),
+        );
+    }
+
+    /// Records that `stage` finished after `elapsed`.
+    pub(crate) fn stage_completed(&self, stage: Stage, elapsed: Duration) {
+        self.write(
+            Record::new("stage_completed")
+                .str("stage", stage.as_str())
+                .opt_millis("elapsed_ms", Some(elapsed)),
+        );
+    }
+
+    /// Records the size of what a stage produced.
+    pub(crate) fn artefact(&self, artefact: &StageArtefact<'_>) {
+        let record = Record::new("stage_statistics");
+        let record = match artefact {
+            StageArtefact::Index { index, harvest } => record
+                .str("stage", "index")
+                .uint("nodes", index.len() as u64)
+                .uint("harvested_edges", harvest.len() as u64),
+            StageArtefact::Graph(graph) => record
+                .str("stage", "graph")
+                .uint("harvested_edges", graph.len() as u64),
+            StageArtefact::Harvest(harvest) => record
+                .str("stage", "harvest")
+                .uint("edges", harvest.len() as u64),
+            StageArtefact::Mst(edges) => {
+                record.str("stage", "mst").uint("edges", edges.len() as u64)
+            }
+            StageArtefact::CondensedTree(tree) => record
+                .str("stage", "hierarchy")
+                .uint("condensed_clusters", tree.cluster_count() as u64),
+        };
+        self.write(record);
+    }
+
+    fn write(&self, record: Record) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if state.error.is_some() {
+            return;
+        }
+        let mut line = record.line;
+        line.push_str("}\n");
+        if let Err(error) = state.file.write_all(line.as_bytes()) {
+            state.error = Some(error);
+        }
+    }
+
+    /// Returns and clears the first write failure since the last call.
+    fn take_error(&self) -> Result<()> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        match state.error.take() {
+            Some(error) => Err(failure(&self.path, &error)),
+            None => Ok(()),
+        }
+    }
+}
+
+fn failure(path: &Path, error: &io::Error) -> ChutoroError {
+    ChutoroError::EventLog {
+        path: Arc::from(path),
+        reason: Arc::from(error.to_string()),
+    }
+}
+
+/// A JSON object under construction; the closing brace is added on write.
+struct Record {
+    line: String,
+}
+
+impl Record {
+    fn new(event: &str) -> Self {
+        let unix_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis());
+        let mut line = String::from("{");
+        push_string(&mut line, "event");
+        line.push(':');
+        push_string(&mut line, event);
+        let _ = write!(line, ",\"unix_ms\":{unix_ms}");
+        Self { line }
+    }
+
+    fn key(mut self, key: &str) -> Self {
+        self.line.push(',');
+        push_string(&mut self.line, key);
+        self.line.push(':');
+        self
+    }
+
+    fn str(self, key: &str, value: &str) -> Self {
+        let mut record = self.key(key);
+        push_string(&mut record.line, value);
+        record
+    }
+
+    fn uint(self, key: &str, value: u64) -> Self {
+        let mut record = self.key(key);
+        let _ = write!(record.line, "{value}");
+        record
+    }
+
+    fn opt_uint(self, key: &str, value: Option<u64>) -> Self {
+        match value {
+            Some(value) => self.uint(key, value),
+            None => self.null(key),
+        }
+    }
+
+    fn float(self, key: &str, value: f64) -> Self {
+        if !value.is_finite() {
+            return self.null(key);
+        }
+        let mut record = self.key(key);
+        let _ = write!(record.line, "{value}");
+        record
+    }
+
+    fn opt_millis(self, key: &str, value: Option<Duration>) -> Self {
+        match value {
+            Some(elapsed) => self.float(key, elapsed.as_secs_f64() * 1_000.0),
+            None => self.null(key),
+        }
+    }
+
+    fn null(self, key: &str) -> Self {
+        let mut record = self.key(key);
+        record.line.push_str("null");
+        record
+    }
+}
+
+/// Appends `value` as a quoted JSON string.
+fn push_string(line: &mut String, value: &str) {
+    line.push('"');
+    for character in value.chars() {
+        match character {
+            '"' => line.push_str("\\\""),
+            '\\' => line.push_str("\\\\"),
+            '\n' => line.push_str("\\n"),
+            '\r' => line.push_str("\\r"),
+            '\t' => line.push_str("\\t"),
+            control if control.is_control() => {
+                let _ = write!(line, "\\u{:04x}", u32::from(control));
+            }
+            other => line.push(other),
+        }
+    }
+    line.push('"');
+}
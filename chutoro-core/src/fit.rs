@@ -0,0 +1,235 @@
+//! One-call clustering of in-memory dense vectors.
+//!
+//! [`cluster_dense`] mirrors the `fit` entry point of Python's `hdbscan`: it
+//! wraps the rows in a Euclidean data source, picks HNSW parameters from the
+//! dataset size, and runs the pipeline, so new users can cluster a matrix
+//! before learning the [`crate::ChutoroBuilder`] surface.
+
+use std::sync::Arc;
+
+use crate::{
+    ChutoroBuilder, ChutoroError, DataSource, DataSourceError, HnswParams, MetricDescriptor,
+    Result, result::ClusteringResult,
+};
+
+/// Name reported for the rows passed to [`cluster_dense`].
+const SOURCE_NAME: &str = "dense";
+
+/// Options for [`cluster_dense`].
+///
+/// # Examples
+/// ```
+/// use chutoro_core::ClusterOptions;
+///
+/// let options = ClusterOptions::default().with_min_cluster_size(10).with_seed(7);
+/// assert_eq!(options.min_cluster_size(), 10);
+/// assert_eq!(options.seed(), Some(7));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClusterOptions {
+    min_cluster_size: usize,
+    seed: Option<u64>,
+    hnsw_params: Option<HnswParams>,
+    max_bytes: Option<u64>,
+}
+
+impl Default for ClusterOptions {
+    fn default() -> Self {
+        Self {
+            min_cluster_size: 5,
+            seed: None,
+            hnsw_params: None,
+            max_bytes: None,
+        }
+    }
+}
+
+impl ClusterOptions {
+    /// Sets the minimum number of points per cluster (default `5`).
+    #[must_use]
+    pub fn with_min_cluster_size(mut self, min_cluster_size: usize) -> Self {
+        self.min_cluster_size = min_cluster_size;
+        self
+    }
+
+    /// Sets the master seed, making runs reproducible.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Overrides the HNSW parameters derived from the dataset size.
+    #[must_use]
+    pub fn with_hnsw_params(mut self, params: HnswParams) -> Self {
+        self.hnsw_params = Some(params);
+        self
+    }
+
+    /// Sets the estimated memory limit in bytes.
+    #[must_use]
+    pub fn with_max_bytes(mut self, bytes: u64) -> Self {
+        self.max_bytes = Some(bytes);
+        self
+    }
+
+    /// Returns the minimum cluster size.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn min_cluster_size(&self) -> usize { self.min_cluster_size }
+
+    /// Returns the master seed, if set.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn seed(&self) -> Option<u64> { self.seed }
+
+    /// Returns the HNSW parameter override, if set.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn hnsw_params(&self) -> Option<&HnswParams> { self.hnsw_params.as_ref() }
+
+    /// Returns the memory limit, if set.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn max_bytes(&self) -> Option<u64> { self.max_bytes }
+}
+
+/// Clusters `data`, one point per row, by Euclidean distance.
+///
+/// Unless [`ClusterOptions::with_hnsw_params`] overrides them, the HNSW
+/// parameters grow with the dataset: `max_connections` is `log2` of the row
+/// count clamped to `8..=32`, and `ef_construction` is four times that but at
+/// least `64`, matching [`HnswParams::default`] at 65,536 rows. The rows are
+/// borrowed, not copied.
+///
+/// # Errors
+/// Returns [`ChutoroError::DataSource`] with
+/// [`DataSourceError::ZeroDimension`] when the rows are empty vectors or
+/// [`DataSourceError::DimensionMismatch`] when their lengths differ, and
+/// otherwise the errors of [`ChutoroBuilder::build`] and
+/// [`crate::Chutoro::run`].
+///
+/// # Examples
+/// ```
+/// use chutoro_core::{ClusterOptions, cluster_dense};
+///
+/// let data = vec![
+///     vec![0.0, 0.0],
+///     vec![0.1, 0.0],
+///     vec![0.0, 0.1],
+///     vec![10.0, 10.0],
+///     vec![10.1, 10.0],
+///     vec![10.0, 10.1],
+/// ];
+/// let result = cluster_dense(&data, ClusterOptions::default().with_min_cluster_size(2))?;
+/// assert_eq!(result.cluster_count(), 2);
+/// assert_ne!(result.assignments()[0], result.assignments()[3]);
+/// # Ok::<(), chutoro_core::ChutoroError>(())
+/// ```
+pub fn cluster_dense(data: &[Vec<f32>], opts: ClusterOptions) -> Result<ClusteringResult> {
+    let source = DenseRows::try_new(data)?;
+    let hnsw_params = opts
+        .hnsw_params
+        .unwrap_or_else(|| default_hnsw_params(data.len()));
+    let mut builder = ChutoroBuilder::new()
+        .with_min_cluster_size(opts.min_cluster_size)
+        .with_hnsw_params(hnsw_params);
+    if let Some(seed) = opts.seed {
+        builder = builder.with_seed(seed);
+    }
+    if let Some(bytes) = opts.max_bytes {
+        builder = builder.with_max_bytes(bytes);
+    }
+    builder.build()?.run(&source)
+}
+
+/// Scales the HNSW fan-out with `log2(items)`, so small inputs build quickly
+/// and large ones keep their recall.
+fn default_hnsw_params(items: usize) -> HnswParams {
+    let max_connections = (items.max(2).ilog2() as usize).clamp(8, 32);
+    let ef_construction = (max_connections * 4).max(64);
+    HnswParams::new(max_connections, ef_construction).unwrap_or_default()
+}
+
+/// Borrowed rows of equal length compared by Euclidean distance.
+struct DenseRows<'a> {
+    rows: &'a [Vec<f32>],
+}
+
+impl<'a> DenseRows<'a> {
+    fn try_new(rows: &'a [Vec<f32>]) -> Result<Self> {
+        let invalid = |error| ChutoroError::DataSource {
+            data_source: Arc::from(SOURCE_NAME),
+            error,
+        };
+        let Some(first) = rows.first() else {
+            return Ok(Self { rows });
+        };
+        if first.is_empty() {
+            return Err(invalid(DataSourceError::ZeroDimension));
+        }
+        if let Some(row) = rows.iter().find(|row| row.len() != first.len()) {
+            return Err(invalid(DataSourceError::DimensionMismatch {
+                left: first.len(),
+                right: row.len(),
+            }));
+        }
+        Ok(Self { rows })
+    }
+
+    fn row(&self, index: usize) -> core::result::Result<&[f32], DataSourceError> {
+        self.rows
+            .get(index)
+            .map(Vec::as_slice)
+            .ok_or(DataSourceError::OutOfBounds { index })
+    }
+}
+
+impl DataSource for DenseRows<'_> {
+    fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn name(&self) -> &str {
+        SOURCE_NAME
+    }
+
+    fn metric_descriptor(&self) -> MetricDescriptor {
+        MetricDescriptor::new("euclidean")
+    }
+
+    fn dimension_hint(&self) -> Option<usize> {
+        self.rows.first().map(Vec::len)
+    }
+
+    fn distance(&self, i: usize, j: usize) -> core::result::Result<f32, DataSourceError> {
+        let (left, right) = (self.row(i)?, self.row(j)?);
+        let squared: f32 = left.iter().zip(right).map(|(a, b)| (a - b) * (a - b)).sum();
+        Ok(squared.sqrt())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for the size-derived HNSW defaults.
+
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::tiny(3, 8, 64)]
+    #[case::default_size(65_536, 16, 64)]
+    #[case::large(1 << 20, 20, 80)]
+    #[case::huge(usize::MAX, 32, 128)]
+    fn hnsw_defaults_scale_with_size(
+        #[case] items: usize,
+        #[case] max_connections: usize,
+        #[case] ef_construction: usize,
+    ) {
+        let params = default_hnsw_params(items);
+
+        assert_eq!(params.max_connections(), max_connections);
+        assert_eq!(params.ef_construction(), ef_construction);
+    }
+}
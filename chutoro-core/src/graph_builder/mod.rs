@@ -0,0 +1,280 @@
+//! Selection of the structure that discovers candidate edges.
+//!
+//! The CPU pipeline builds an HNSW index by default, harvesting candidate
+//! edges as points are inserted and searching the index again for core
+//! distances. A run that clusters once and never queries the index can
+//! instead build an approximate k-nearest-neighbour graph with NN-descent:
+//! its edges become the [`EdgeHarvest`] and each point's core distance is
+//! read from the graph, so no index is built at all.
+
+mod nn_descent;
+
+use std::{num::NonZeroUsize, sync::Arc};
+
+use rayon::prelude::*;
+
+use crate::{
+    CpuHnsw, DataSource, EdgeHarvest, Result, StageArtefact,
+    builder::{PipelineOptions, PrebuiltIndex},
+    cpu_pipeline::select_core_distance,
+    distance_policy::DistanceGuard,
+    error::ChutoroError,
+    hnsw::HarvestProvenance,
+    seed::SeedStream,
+    stages::{StageContext, build_traced_index},
+};
+
+use self::nn_descent::NnDescent;
+
+/// Chooses how the CPU pipeline discovers candidate edges.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::{ChutoroBuilder, GraphBuilder};
+///
+/// let graph = GraphBuilder::NnDescent {
+///     k: 16,
+///     iterations: 10,
+///     sample_rate: 0.5,
+/// };
+/// let builder = ChutoroBuilder::new().with_graph_builder(graph);
+/// assert_eq!(builder.graph_builder(), graph);
+/// assert_eq!(GraphBuilder::default(), GraphBuilder::Hnsw);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GraphBuilder {
+    /// Build an HNSW index and harvest the edges its insertions discover.
+    #[default]
+    Hnsw,
+    /// Build an approximate k-nearest-neighbour graph with NN-descent.
+    NnDescent {
+        /// Neighbours kept per point; at least the minimum cluster size
+        /// keeps core distances exact.
+        k: usize,
+        /// Maximum number of refinement rounds; the descent stops earlier
+        /// once a round improves no neighbour list.
+        iterations: usize,
+        /// Fraction of each point's new neighbours, and of its reverse
+        /// neighbours, joined per round; must lie in `(0, 1]`.
+        sample_rate: f32,
+    },
+}
+
+impl GraphBuilder {
+    /// Describes why the configuration is invalid, if it is.
+    pub(crate) fn validate(self) -> Option<&'static str> {
+        match self {
+            Self::Hnsw => None,
+            Self::NnDescent { k: 0, .. } => {
+                Some("NN-descent needs at least one neighbour per point")
+            }
+            Self::NnDescent { iterations: 0, .. } => {
+                Some("NN-descent needs at least one iteration")
+            }
+            Self::NnDescent { sample_rate, .. } if !(sample_rate > 0.0 && sample_rate <= 1.0) => {
+                Some("NN-descent sample rate must lie in (0, 1]")
+            }
+            Self::NnDescent { .. } => None,
+        }
+    }
+}
+
+/// The candidate-edge structure a run built.
+#[derive(Debug)]
+pub(crate) enum BuiltGraph {
+    Hnsw(Box<CpuHnsw>, EdgeHarvest, Option<HarvestProvenance>),
+    NnDescent(EdgeHarvest, DistanceGuard),
+}
+
+impl BuiltGraph {
+    /// Builds the structure `options` selects over the context's source.
+    ///
+    /// # Errors
+    /// Returns [`ChutoroError::DataSource`] when a distance fails,
+    /// [`ChutoroError::InvalidKnnGraph`] when NN-descent meets a non-finite
+    /// distance, and the index stage's errors for HNSW builds.
+    pub(crate) fn build<D: DataSource + Sync>(
+        source: &D,
+        context: &StageContext<'_>,
+        options: &PipelineOptions,
+    ) -> Result<Self> {
+        match options.graph_builder {
+            GraphBuilder::Hnsw => {
+                let (index, harvest, provenance) =
+                    build_traced_index(source, context, &options.stages, options.edge_provenance)?;
+                Ok(Self::Hnsw(Box::new(index), harvest, provenance))
+            }
+            GraphBuilder::NnDescent {
+                k,
+                iterations,
+                sample_rate,
+            } => {
+                let seed = SeedStream::NnDescent.derive(options.seed.unwrap_or_default());
+                let guard = DistanceGuard::new(options.distance_policy);
+                let graph =
+                    NnDescent::new(k, iterations, sample_rate, seed).build(source, &guard)?;
+                Ok(Self::NnDescent(graph, guard))
+            }
+        }
+    }
+
+    /// Borrows the built structure for the rest of the run.
+    pub(crate) fn view(&self) -> RunGraph<'_> {
+        match self {
+            Self::Hnsw(index, harvest, provenance) => RunGraph {
+                neighbourhoods: Neighbourhoods::Index(index),
+                harvest,
+                provenance: provenance.as_ref(),
+                guard: index.distance_guard(),
+            },
+            Self::NnDescent(graph, guard) => RunGraph {
+                neighbourhoods: Neighbourhoods::Graph(graph),
+                harvest: graph,
+                provenance: None,
+                guard,
+            },
+        }
+    }
+}
+
+/// The candidate edges of a run, where its core distances come from, and
+/// the guard applying its distance policy.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct RunGraph<'a> {
+    pub(crate) neighbourhoods: Neighbourhoods<'a>,
+    pub(crate) harvest: &'a EdgeHarvest,
+    pub(crate) provenance: Option<&'a HarvestProvenance>,
+    pub(crate) guard: &'a DistanceGuard,
+}
+
+impl<'a> RunGraph<'a> {
+    /// Adopts a prebuilt index and its harvest.
+    pub(crate) fn prebuilt(prebuilt: &'a PrebuiltIndex) -> Self {
+        Self {
+            neighbourhoods: Neighbourhoods::Index(&prebuilt.index),
+            harvest: &prebuilt.harvest,
+            provenance: None,
+            guard: prebuilt.index.distance_guard(),
+        }
+    }
+
+    /// Returns the artefact reporting the finished graph stage.
+    pub(crate) fn artefact(&self) -> StageArtefact<'a> {
+        match self.neighbourhoods {
+            Neighbourhoods::Index(index) => StageArtefact::Index {
+                index,
+                harvest: self.harvest,
+            },
+            Neighbourhoods::Graph(graph) => StageArtefact::Graph(graph),
+        }
+    }
+}
+
+/// Where a run reads each point's neighbourhood for its core distance.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Neighbourhoods<'a> {
+    /// Searched from an HNSW index.
+    Index(&'a CpuHnsw),
+    /// Read from the incident edges of an unfiltered k-NN graph.
+    Graph(&'a EdgeHarvest),
+}
+
+impl<'a> Neighbourhoods<'a> {
+    /// Returns the HNSW index, when the run built or adopted one.
+    pub(crate) fn index(self) -> Option<&'a CpuHnsw> {
+        match self {
+            Self::Index(index) => Some(index),
+            Self::Graph(_) => None,
+        }
+    }
+}
+
+/// Computes each point's core distance from the incident edges of `graph`,
+/// counting each neighbour once at its shortest distance and ignoring
+/// self-loops.
+///
+/// Points with fewer than `min_cluster_size` neighbours fall back to their
+/// farthest neighbour, and isolated points to zero.
+pub(crate) fn graph_core_distances(graph: &EdgeHarvest, core: CoreSelection<'_>) -> Vec<f32> {
+    graph_core_distances_f64(graph, core)
+        .into_iter()
+        .map(|distance| distance as f32)
+        .collect()
+}
+
+/// Computes core distances like [`graph_core_distances`] from the edges'
+/// `f64` distances, for runs that weight the tree in double precision.
+pub(crate) fn graph_core_distances_f64(graph: &EdgeHarvest, core: CoreSelection<'_>) -> Vec<f64> {
+    incident_neighbours(core.items, graph)
+        .iter()
+        .enumerate()
+        .map(|(point, neighbours)| core.select(point, neighbours))
+        .collect()
+}
+
+/// Computes core distances like [`graph_core_distances`], re-evaluating every
+/// neighbour with [`DataSource::distance_f64`] and re-ranking them before the
+/// core neighbour is picked.
+///
+/// # Errors
+/// Returns [`ChutoroError::DataSource`] when a distance fails.
+pub(crate) fn precise_graph_core_distances<D: DataSource + Sync>(
+    source: &D,
+    guard: &DistanceGuard,
+    graph: &EdgeHarvest,
+    core: CoreSelection<'_>,
+) -> Result<Vec<f64>> {
+    incident_neighbours(core.items, graph)
+        .par_iter()
+        .enumerate()
+        .map(|(point, neighbours)| {
+            let mut precise = neighbours
+                .iter()
+                .map(|&(neighbour, _)| {
+                    source
+                        .distance_f64(point, neighbour)
+                        .map(|distance| (neighbour, guard.admit_f64(point, neighbour, distance)))
+                        .map_err(|error| ChutoroError::DataSource {
+                            data_source: Arc::from(source.name()),
+                            error,
+                        })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            precise.sort_by(|left, right| left.1.total_cmp(&right.1).then(left.0.cmp(&right.0)));
+            Ok(core.select(point, &precise))
+        })
+        .collect()
+}
+
+/// The points whose core distances are read and how they are counted.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct CoreSelection<'a> {
+    pub(crate) items: usize,
+    pub(crate) min_cluster_size: NonZeroUsize,
+    pub(crate) weights: Option<&'a [usize]>,
+}
+
+impl CoreSelection<'_> {
+    fn select<T: Copy + Default>(&self, point: usize, others: &[(usize, T)]) -> T {
+        select_core_distance(point, others, self.min_cluster_size, self.weights)
+    }
+}
+
+/// Lists each point's distinct neighbours in `graph`, nearest first.
+fn incident_neighbours(items: usize, graph: &EdgeHarvest) -> Vec<Vec<(usize, f64)>> {
+    let mut incident: Vec<Vec<(usize, f64)>> = vec![Vec::new(); items];
+    for edge in graph.iter().filter(|edge| edge.source() != edge.target()) {
+        incident[edge.source()].push((edge.target(), edge.distance_f64()));
+        incident[edge.target()].push((edge.source(), edge.distance_f64()));
+    }
+    for neighbours in &mut incident {
+        neighbours.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)));
+        neighbours.dedup_by_key(|neighbour| neighbour.0);
+        neighbours.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+    }
+    incident
+}
+
+#[cfg(test)]
+mod tests;
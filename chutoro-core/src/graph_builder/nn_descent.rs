@@ -0,0 +1,323 @@
+//! Approximate k-nearest-neighbour graphs by NN-descent.
+//!
+//! NN-descent (Dong, Charikar and Li, 2011) starts every point with random
+//! neighbours and repeatedly applies "a neighbour of a neighbour is likely a
+//! neighbour": each round joins every pair among a point's sampled
+//! neighbours and reverse neighbours, and keeps any pair that improves
+//! either endpoint's list. Only neighbours that joined a list since the
+//! previous round are compared with each other or with older ones, so late
+//! rounds evaluate few distances.
+//!
+//! The distances of each round's joins are evaluated in parallel against a
+//! snapshot of the lists and applied in point order afterwards, so a seed
+//! reproduces the graph whatever the thread count.
+
+use std::sync::Arc;
+
+use rand::{Rng, SeedableRng, rngs::SmallRng, seq::index};
+use rayon::prelude::*;
+use tracing::debug;
+
+use crate::{
+    CandidateEdge, DataSource, DataSourceError, EdgeHarvest, Result,
+    distance_policy::DistanceGuard, error::ChutoroError, seed::splitmix64,
+};
+
+/// One entry of a point's neighbour list.
+#[derive(Clone, Copy, Debug)]
+struct Entry {
+    id: usize,
+    distance: f32,
+    /// Whether the entry joined the list since it was last sampled.
+    fresh: bool,
+}
+
+/// A point's nearest known neighbours, nearest first.
+#[derive(Clone, Debug, Default)]
+struct NeighbourList(Vec<Entry>);
+
+impl NeighbourList {
+    /// Returns the distance a candidate must beat to join a list holding
+    /// `k` entries.
+    fn bound(&self, k: usize) -> f32 {
+        match self.0.len() {
+            len if len < k => f32::INFINITY,
+            _ => self.0.last().map_or(f32::INFINITY, |entry| entry.distance),
+        }
+    }
+
+    fn contains(&self, id: usize) -> bool {
+        self.0.iter().any(|entry| entry.id == id)
+    }
+
+    /// Inserts `id` at `distance` when it improves the list, returning
+    /// whether it did.
+    fn insert(&mut self, id: usize, distance: f32, k: usize) -> bool {
+        if distance >= self.bound(k) || self.contains(id) {
+            return false;
+        }
+        let position = self
+            .0
+            .partition_point(|entry| (entry.distance, entry.id) < (distance, id));
+        self.0.insert(
+            position,
+            Entry {
+                id,
+                distance,
+                fresh: true,
+            },
+        );
+        self.0.truncate(k);
+        true
+    }
+}
+
+/// The neighbours a point joins in one round.
+#[derive(Clone, Debug, Default)]
+struct JoinSet {
+    fresh: Vec<usize>,
+    old: Vec<usize>,
+}
+
+impl JoinSet {
+    fn push(&mut self, id: usize, fresh: bool) {
+        if fresh {
+            self.fresh.push(id);
+        } else {
+            self.old.push(id);
+        }
+    }
+}
+
+/// NN-descent settings for one run.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct NnDescent {
+    k: usize,
+    iterations: usize,
+    sample_rate: f32,
+    seed: u64,
+}
+
+impl NnDescent {
+    pub(super) fn new(k: usize, iterations: usize, sample_rate: f32, seed: u64) -> Self {
+        Self {
+            k,
+            iterations,
+            sample_rate,
+            seed,
+        }
+    }
+
+    /// Builds the k-NN graph of `source`, returning each unordered neighbour
+    /// pair once as a candidate edge.
+    ///
+    /// Non-finite distances pass through `guard`, and the edges of pairs it
+    /// skipped are left out of the graph.
+    ///
+    /// # Errors
+    /// Returns [`ChutoroError::DataSource`] when a distance fails and
+    /// [`ChutoroError::InvalidKnnGraph`] when the policy rejects a non-finite
+    /// one.
+    pub(super) fn build<D: DataSource + Sync>(
+        &self,
+        source: &D,
+        guard: &DistanceGuard,
+    ) -> Result<EdgeHarvest> {
+        let items = source.len();
+        let k = self.k.min(items.saturating_sub(1));
+        if k == 0 {
+            return Ok(EdgeHarvest::new(Vec::new()));
+        }
+        let mut lists = self.initial_lists(source, guard, k)?;
+        for round in 0..self.iterations {
+            let joins = self.sample_joins(&mut lists, round);
+            let candidates = local_joins((source, guard), &lists, &joins, k)?;
+            let updates = candidates
+                .into_iter()
+                .flatten()
+                .map(|(left, right, distance)| {
+                    usize::from(lists[left].insert(right, distance, k))
+                        + usize::from(lists[right].insert(left, distance, k))
+                })
+                .sum::<usize>();
+            debug!(round, updates, "NN-descent round finished");
+            if updates == 0 {
+                break;
+            }
+        }
+        let graph = harvest(&lists);
+        Ok(guard.without_skipped(&graph).unwrap_or(graph))
+    }
+
+    /// Seeds every point with `k` distinct random neighbours.
+    fn initial_lists<D: DataSource + Sync>(
+        &self,
+        source: &D,
+        guard: &DistanceGuard,
+        k: usize,
+    ) -> Result<Vec<NeighbourList>> {
+        let items = source.len();
+        (0..items)
+            .into_par_iter()
+            .map(|point| {
+                let mut rng = SmallRng::seed_from_u64(splitmix64(self.seed ^ point as u64));
+                let others: Vec<usize> = index::sample(&mut rng, items - 1, k)
+                    .into_iter()
+                    .map(|other| if other >= point { other + 1 } else { other })
+                    .collect();
+                let distances = source
+                    .batch_distances(point, &others)
+                    .map_err(|error| source_error(source, error))?;
+                let mut list = NeighbourList::default();
+                for (&other, &distance) in others.iter().zip(&distances) {
+                    let distance = admit(guard, point, other, distance)?;
+                    list.insert(other, distance, k);
+                }
+                Ok(list)
+            })
+            .collect()
+    }
+
+    /// Samples each point's fresh and old neighbours, adds sampled reverse
+    /// neighbours, and marks the sampled fresh entries as old.
+    fn sample_joins(&self, lists: &mut [NeighbourList], round: usize) -> Vec<JoinSet> {
+        let mut rng = SmallRng::seed_from_u64(splitmix64(self.seed ^ !(round as u64)));
+        let per_round = self.sample_size();
+        let mut forward = vec![JoinSet::default(); lists.len()];
+        let mut reverse = vec![JoinSet::default(); lists.len()];
+        for (point, list) in lists.iter_mut().enumerate() {
+            let fresh: Vec<usize> = (0..list.0.len()).filter(|&i| list.0[i].fresh).collect();
+            let sampled = sample(&mut rng, fresh, per_round);
+            for &position in &sampled {
+                list.0[position].fresh = false;
+            }
+            let joined = list.0.iter().enumerate().filter_map(|(position, entry)| {
+                match (sampled.contains(&position), entry.fresh) {
+                    (true, _) => Some((entry.id, true)),
+                    (false, true) => None,
+                    (false, false) => Some((entry.id, false)),
+                }
+            });
+            for (neighbour, fresh) in joined {
+                forward[point].push(neighbour, fresh);
+                reverse[neighbour].push(point, fresh);
+            }
+        }
+        for (joins, reversed) in forward.iter_mut().zip(reverse) {
+            merge(
+                &mut joins.fresh,
+                sample(&mut rng, reversed.fresh, per_round),
+            );
+            merge(&mut joins.old, sample(&mut rng, reversed.old, per_round));
+        }
+        forward
+    }
+
+    /// Returns how many fresh and reverse neighbours are joined per round.
+    fn sample_size(&self) -> usize {
+        ((self.k as f32 * self.sample_rate).ceil() as usize).max(1)
+    }
+}
+
+/// Evaluates every pair joined through each point and keeps those that
+/// improve either endpoint's list as it stood before the round.
+fn local_joins<D: DataSource + Sync>(
+    (source, guard): (&D, &DistanceGuard),
+    lists: &[NeighbourList],
+    joins: &[JoinSet],
+    k: usize,
+) -> Result<Vec<Vec<(usize, usize, f32)>>> {
+    joins
+        .par_iter()
+        .map(|join| {
+            let pairs: Vec<(usize, usize)> = join
+                .fresh
+                .iter()
+                .enumerate()
+                .flat_map(|(i, &left)| {
+                    let later = join.fresh[i + 1..].iter();
+                    later.chain(&join.old).map(move |&right| (left, right))
+                })
+                .filter(|&(left, right)| {
+                    left != right && !(lists[left].contains(right) && lists[right].contains(left))
+                })
+                .collect();
+            let mut distances = vec![0.0_f32; pairs.len()];
+            source
+                .distance_batch(&pairs, &mut distances)
+                .map_err(|error| source_error(source, error))?;
+            pairs
+                .into_iter()
+                .zip(distances)
+                .filter_map(
+                    |((left, right), distance)| match admit(guard, left, right, distance) {
+                        Ok(distance) => {
+                            let improves =
+                                distance < lists[left].bound(k) || distance < lists[right].bound(k);
+                            improves.then_some(Ok((left, right, distance)))
+                        }
+                        Err(error) => Some(Err(error)),
+                    },
+                )
+                .collect()
+        })
+        .collect()
+}
+
+/// Emits each neighbour pair of the finished lists once.
+fn harvest(lists: &[NeighbourList]) -> EdgeHarvest {
+    let mut sequence = 0_u64;
+    let mut edges = Vec::new();
+    for (point, list) in lists.iter().enumerate() {
+        for entry in &list.0 {
+            if point < entry.id || !lists[entry.id].contains(point) {
+                edges.push(CandidateEdge::new(
+                    point,
+                    entry.id,
+                    entry.distance,
+                    sequence,
+                ));
+                sequence += 1;
+            }
+        }
+    }
+    EdgeHarvest::new(edges)
+}
+
+/// Keeps `amount` randomly chosen values of `values`, or all of them when
+/// there are no more.
+fn sample<R: Rng>(rng: &mut R, mut values: Vec<usize>, amount: usize) -> Vec<usize> {
+    if values.len() > amount {
+        let (chosen, _) = rand::seq::SliceRandom::partial_shuffle(&mut values[..], rng, amount);
+        return chosen.to_vec();
+    }
+    values
+}
+
+/// Appends the values of `extra` not already in `into`.
+fn merge(into: &mut Vec<usize>, extra: Vec<usize>) {
+    for value in extra {
+        if !into.contains(&value) {
+            into.push(value);
+        }
+    }
+}
+
+/// Applies the run's distance policy to an evaluated distance.
+fn admit(guard: &DistanceGuard, left: usize, right: usize, distance: f32) -> Result<f32> {
+    if let Some(distance) = guard.admit(left, right, distance) {
+        return Ok(distance);
+    }
+    Err(ChutoroError::InvalidKnnGraph {
+        reason: Arc::from(format!(
+            "NN-descent evaluated non-finite distance {distance} between points {left} and {right}"
+        )),
+    })
+}
+
+fn source_error<D: DataSource>(source: &D, error: DataSourceError) -> ChutoroError {
+    ChutoroError::DataSource {
+        data_source: Arc::from(source.name()),
+        error,
+    }
+}
@@ -0,0 +1,159 @@
+//! Unit tests for NN-descent graph construction.
+
+use std::collections::HashSet;
+
+use rstest::rstest;
+
+use super::nn_descent::NnDescent;
+use crate::{
+    DataSource, DataSourceError, DistancePolicy, EdgeHarvest, distance_policy::DistanceGuard,
+    error::ChutoroError, oracles::ExactKnnGraph,
+};
+
+/// Points scattered over the plane by a fixed linear congruential sequence.
+struct Plane(Vec<(f32, f32)>);
+
+impl Plane {
+    fn scattered(count: usize) -> Self {
+        let mut state = 17_u32;
+        let mut next = || {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            (state >> 8) as f32 / (1 << 24) as f32
+        };
+        Self(
+            (0..count)
+                .map(|_| (next() * 100.0, next() * 100.0))
+                .collect(),
+        )
+    }
+}
+
+impl DataSource for Plane {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn name(&self) -> &str {
+        "plane"
+    }
+
+    fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+        let (a, b) = (self.0[i], self.0[j]);
+        Ok((a.0 - b.0).hypot(a.1 - b.1))
+    }
+}
+
+/// A source whose distances to point 0 are not finite.
+struct Poisoned;
+
+impl DataSource for Poisoned {
+    fn len(&self) -> usize {
+        6
+    }
+
+    fn name(&self) -> &str {
+        "poisoned"
+    }
+
+    fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+        Ok(if i == 0 || j == 0 {
+            f32::NAN
+        } else {
+            i.abs_diff(j) as f32
+        })
+    }
+}
+
+fn pairs(graph: &EdgeHarvest) -> HashSet<(usize, usize)> {
+    graph
+        .iter()
+        .map(|edge| {
+            let (a, b) = (edge.source(), edge.target());
+            (a.min(b), a.max(b))
+        })
+        .collect()
+}
+
+#[rstest]
+fn graph_recalls_most_exact_neighbours() {
+    let source = Plane::scattered(400);
+    let graph = NnDescent::new(8, 12, 1.0, 3)
+        .build(&source, &DistanceGuard::default())
+        .expect("distances are finite");
+    let exact = ExactKnnGraph::build(&source, 8).expect("distances are valid");
+
+    let found = pairs(&graph);
+    let hits = (0..source.len())
+        .flat_map(|point| {
+            let neighbours = exact.neighbours(point).expect("point exists");
+            neighbours
+                .iter()
+                .map(move |neighbour| (point.min(neighbour.id), point.max(neighbour.id)))
+        })
+        .filter(|pair| found.contains(pair))
+        .count();
+
+    assert!(
+        hits * 100 >= source.len() * 8 * 95,
+        "recalled {hits} of 3200"
+    );
+}
+
+#[rstest]
+fn each_pair_is_harvested_once() {
+    let source = Plane::scattered(200);
+    let graph = NnDescent::new(6, 8, 0.5, 1)
+        .build(&source, &DistanceGuard::default())
+        .expect("distances are finite");
+
+    assert_eq!(pairs(&graph).len(), graph.len());
+    assert!(graph.iter().all(|edge| edge.source() != edge.target()));
+}
+
+#[rstest]
+fn a_seed_reproduces_the_graph() {
+    let source = Plane::scattered(150);
+    let build = |seed| {
+        NnDescent::new(5, 6, 0.5, seed)
+            .build(&source, &DistanceGuard::default())
+            .expect("distances are finite")
+            .into_inner()
+    };
+
+    assert_eq!(build(9), build(9));
+}
+
+#[rstest]
+fn small_sources_become_complete_graphs() {
+    let source = Plane::scattered(5);
+    let graph = NnDescent::new(10, 4, 1.0, 0)
+        .build(&source, &DistanceGuard::default())
+        .expect("distances are finite");
+
+    assert_eq!(pairs(&graph).len(), 10);
+}
+
+#[rstest]
+fn non_finite_distances_are_rejected() {
+    let err = NnDescent::new(3, 4, 1.0, 0)
+        .build(&Poisoned, &DistanceGuard::default())
+        .expect_err("point 0 has no finite distance");
+
+    assert!(matches!(err, ChutoroError::InvalidKnnGraph { .. }));
+}
+
+#[rstest]
+fn skipped_pairs_are_left_out_of_the_graph() {
+    let guard = DistanceGuard::new(DistancePolicy::SkipEdge);
+    let graph = NnDescent::new(3, 4, 1.0, 0)
+        .build(&Poisoned, &guard)
+        .expect("non-finite distances are skipped");
+
+    assert!(!graph.is_empty());
+    assert!(
+        graph
+            .iter()
+            .all(|edge| edge.source() != 0 && edge.target() != 0)
+    );
+    assert!(guard.report().is_some_and(|report| report.skipped() > 0));
+}
@@ -0,0 +1,78 @@
+//! Label extraction straight from a harvest of raw neighbour distances.
+//!
+//! Custom pipelines built on [`crate::CpuHnsw::build_with_edges`] hold edges
+//! weighted by plain distances. [`extract_labels_from_harvest`] applies the
+//! mutual-reachability transform itself before running Kruskal and hierarchy
+//! extraction, so callers cannot feed raw distances to the hierarchy by
+//! mistake.
+
+use crate::{
+    EdgeHarvest, Result,
+    cpu_pipeline::{map_cpu_hierarchy_error, map_cpu_mst_error, mutual_reachability_harvest},
+    graph_builder::{CoreSelection, graph_core_distances},
+    mst::validate_harvest,
+    parallel_kruskal_owned,
+};
+
+use super::{HierarchyConfig, extract_labels_from_mst};
+
+/// Extracts flat cluster labels from a harvest of raw neighbour distances.
+///
+/// Each point's core distance is the distance to its `min_cluster_size`-th
+/// nearest neighbour among its incident harvest edges, or to its farthest
+/// neighbour when it has fewer, exactly as
+/// [`crate::Chutoro::cluster_from_knn_graph`] computes it. The edges are then
+/// re-weighted with mutual-reachability distances, reduced to a minimum
+/// spanning forest with [`crate::parallel_kruskal_owned`], and labelled with
+/// [`extract_labels_from_mst`]. Labels follow the same contiguous scheme,
+/// with noise, if any, under the last label.
+///
+/// A harvest from [`crate::CpuHnsw::build_with_edges`] only lists the
+/// neighbours each point was linked to during insertion, so its core
+/// distances approximate those of a full pipeline run.
+///
+/// # Errors
+/// Returns [`crate::ChutoroError::CpuMstFailure`] when `node_count` is zero
+/// or an edge references a point outside `node_count` or carries a
+/// non-finite distance, and [`crate::ChutoroError::CpuHierarchyFailure`]
+/// when `min_cluster_size` exceeds `node_count` or a distance is negative.
+///
+/// # Examples
+/// ```
+/// use std::num::NonZeroUsize;
+///
+/// use chutoro_core::{CandidateEdge, EdgeHarvest, HierarchyConfig, extract_labels_from_harvest};
+///
+/// // Two groups of points on a line, at 0..3 and 20..23, each point linked
+/// // to its two nearest neighbours on the right.
+/// let positions = [0.0_f32, 1.0, 2.0, 3.0, 20.0, 21.0, 22.0, 23.0];
+/// let mut edges = Vec::new();
+/// for (i, a) in positions.iter().enumerate() {
+///     for (j, b) in positions.iter().enumerate().skip(i + 1).take(2) {
+///         edges.push(CandidateEdge::new(i, j, (a - b).abs(), edges.len() as u64));
+///     }
+/// }
+/// let config = HierarchyConfig::new(NonZeroUsize::new(2).expect("non-zero"));
+/// let labels = extract_labels_from_harvest(8, &EdgeHarvest::new(edges), config)?;
+/// assert_eq!(labels[0], labels[3]);
+/// assert_ne!(labels[0], labels[4]);
+/// # Ok::<(), chutoro_core::ChutoroError>(())
+/// ```
+pub fn extract_labels_from_harvest(
+    node_count: usize,
+    harvest: &EdgeHarvest,
+    config: HierarchyConfig,
+) -> Result<Vec<usize>> {
+    validate_harvest(node_count, harvest).map_err(map_cpu_mst_error)?;
+    let core_distances = graph_core_distances(
+        harvest,
+        CoreSelection {
+            items: node_count,
+            min_cluster_size: config.min_cluster_size(),
+            weights: None,
+        },
+    );
+    let mutual_harvest = mutual_reachability_harvest(harvest, &core_distances);
+    let forest = parallel_kruskal_owned(node_count, mutual_harvest).map_err(map_cpu_mst_error)?;
+    extract_labels_from_mst(node_count, forest.edges(), config).map_err(map_cpu_hierarchy_error)
+}
@@ -13,32 +13,73 @@
 //! - Labelling points by the selected cluster they are contained within,
 //!   falling back to a dedicated noise label when no selected cluster applies.
 //!
+//! It also derives OPTICS-style reachability plots from the same MST so the
+//! density structure can be inspected before choosing `min_cluster_size`.
+//!
 //! The implementation is intentionally sequential to keep the logic simple and
 //! deterministic. This stage is typically not the dominant runtime cost
 //! relative to HNSW construction and MST computation.
 
+mod harvest;
+mod reachability;
 mod single_linkage;
 mod union_find;
 
 use std::num::NonZeroUsize;
 
-use crate::mst::MstEdge;
+use crate::{MembershipScores, mst::MstEdge};
+
+pub use self::harvest::extract_labels_from_harvest;
+pub use self::reachability::{ReachabilityPlot, ReachabilityPoint, reachability_plot};
+pub use self::single_linkage::{
+    CondensedChild, CondensedRow, CondensedTree, HierarchyError, HierarchyErrorCode,
+};
 
-pub use self::single_linkage::{HierarchyError, HierarchyErrorCode};
+pub(crate) use self::single_linkage::CondensedForest;
 
-use self::single_linkage::{CondensedForest, extract_flat_labels};
+use self::single_linkage::{extract_flat_labels, membership_scores};
 
 /// Configuration for hierarchy extraction.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HierarchyConfig {
     min_cluster_size: NonZeroUsize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    max_cluster_size: Option<NonZeroUsize>,
 }
 
 impl HierarchyConfig {
     /// Creates a configuration using the provided `min_cluster_size`.
     #[must_use]
     pub fn new(min_cluster_size: NonZeroUsize) -> Self {
-        Self { min_cluster_size }
+        Self {
+            min_cluster_size,
+            max_cluster_size: None,
+        }
+    }
+
+    /// Forbids selecting clusters with more than `max_cluster_size` points.
+    ///
+    /// Cluster selection then descends past any oversized cluster to its
+    /// children, as `hdbscan`'s `max_cluster_size` does, so a dominant
+    /// background cluster is reported as the subclusters it splits into.
+    /// Clusters that never split are kept whatever their size, since there
+    /// is nothing further down the condensed tree to choose instead.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use chutoro_core::HierarchyConfig;
+    ///
+    /// let config = HierarchyConfig::new(NonZeroUsize::new(5).expect("non-zero"))
+    ///     .with_max_cluster_size(NonZeroUsize::new(500).expect("non-zero"));
+    /// assert_eq!(config.max_cluster_size().map(NonZeroUsize::get), Some(500));
+    /// ```
+    #[must_use]
+    pub fn with_max_cluster_size(mut self, max_cluster_size: NonZeroUsize) -> Self {
+        self.max_cluster_size = Some(max_cluster_size);
+        self
     }
 
     /// Returns the minimum cluster size.
@@ -46,6 +87,12 @@ impl HierarchyConfig {
     pub fn min_cluster_size(&self) -> NonZeroUsize {
         self.min_cluster_size
     }
+
+    /// Returns the largest selectable cluster size, if limited.
+    #[must_use]
+    pub fn max_cluster_size(&self) -> Option<NonZeroUsize> {
+        self.max_cluster_size
+    }
 }
 
 /// Extracts flat cluster labels from a mutual-reachability MST/forest.
@@ -68,8 +115,51 @@ pub fn extract_labels_from_mst(
     edges: &[MstEdge],
     config: HierarchyConfig,
 ) -> Result<Vec<usize>, HierarchyError> {
-    let condensed = CondensedForest::from_mst(node_count, edges, config.min_cluster_size())?;
-    extract_flat_labels(node_count, &condensed)
+    extract_flat_clustering(node_count, edges, config).map(|flat| flat.labels)
+}
+
+/// Flat labels and per-point scores extracted from one condensed hierarchy.
+#[derive(Debug)]
+pub(crate) struct FlatClustering {
+    pub(crate) labels: Vec<usize>,
+    /// The noise label, when any point is classified as noise.
+    pub(crate) noise_label: Option<usize>,
+    pub(crate) scores: MembershipScores,
+    /// The condensed tree the labels were selected from.
+    pub(crate) condensed: CondensedForest,
+}
+
+/// Extracts flat labels together with the noise label and membership scores.
+pub(crate) fn extract_flat_clustering(
+    node_count: usize,
+    edges: &[MstEdge],
+    config: HierarchyConfig,
+) -> Result<FlatClustering, HierarchyError> {
+    extract_weighted_clustering(node_count, edges, config, None)
+}
+
+/// Like [`extract_flat_clustering`], but each point counts `weights[point]`
+/// times towards cluster sizes and stability, as when it stands for exact
+/// duplicates that were collapsed before the run.
+pub(crate) fn extract_weighted_clustering(
+    node_count: usize,
+    edges: &[MstEdge],
+    config: HierarchyConfig,
+    weights: Option<&[usize]>,
+) -> Result<FlatClustering, HierarchyError> {
+    let condensed =
+        CondensedForest::from_mst(node_count, edges, config.min_cluster_size(), weights)?;
+    let (labels, selected) =
+        extract_flat_labels(node_count, &condensed, config.max_cluster_size())?;
+    let noise_label = selected.len();
+    let has_noise = labels.contains(&noise_label);
+    let scores = membership_scores(&condensed, &labels, &selected);
+    Ok(FlatClustering {
+        labels,
+        noise_label: has_noise.then_some(noise_label),
+        scores,
+        condensed,
+    })
 }
 
 #[cfg(test)]
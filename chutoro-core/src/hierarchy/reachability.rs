@@ -0,0 +1,209 @@
+//! OPTICS-style reachability plots derived from the mutual-reachability MST.
+//!
+//! Walking the minimum spanning tree in Prim order, always taking the lightest
+//! edge out of the visited set, reproduces the ordering OPTICS would produce
+//! for the same mutual-reachability distances. Plotting each point's
+//! reachability in that order shows clusters as valleys whose width is the
+//! cluster size, which is the quickest way to choose a sensible
+//! `min_cluster_size` before running the full hierarchy extraction.
+
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+    io::{self, BufWriter, Write},
+};
+
+use crate::mst::MstEdge;
+
+use super::single_linkage::{CondensedForest, HierarchyError};
+
+/// One entry of a [`ReachabilityPlot`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ReachabilityPoint {
+    point: usize,
+    reachability: f32,
+}
+
+impl ReachabilityPoint {
+    /// Returns the point's index in the data source.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn point(&self) -> usize { self.point }
+
+    /// Returns the weight of the MST edge through which the walk reached the
+    /// point, or [`f32::INFINITY`] for the first point of each component.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn reachability(&self) -> f32 { self.reachability }
+}
+
+/// Points in reachability order with the distance at which each was reached.
+///
+/// Produced by [`reachability_plot`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReachabilityPlot {
+    points: Vec<ReachabilityPoint>,
+}
+
+impl ReachabilityPlot {
+    /// Returns every point in reachability order.
+    #[must_use]
+    pub fn points(&self) -> &[ReachabilityPoint] {
+        &self.points
+    }
+
+    /// Returns the number of points in the plot.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns `true` when the plot holds no points.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Writes the plot as CSV rows of `position,point,reachability` under a
+    /// header. Component starts are written as `inf`.
+    ///
+    /// # Errors
+    /// Returns any error raised by `writer`.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{MstEdge, reachability_plot};
+    ///
+    /// let edges = [MstEdge::new(0, 1, 0.5, 0), MstEdge::new(1, 2, 2.0, 1)];
+    /// let plot = reachability_plot(3, &edges)?;
+    /// let mut csv = Vec::new();
+    /// plot.write_csv(&mut csv)?;
+    ///
+    /// assert_eq!(
+    ///     String::from_utf8(csv)?,
+    ///     "position,point,reachability\n0,0,inf\n1,1,0.5\n2,2,2\n",
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_csv(&self, writer: impl Write) -> io::Result<()> {
+        let mut writer = BufWriter::new(writer);
+        writeln!(writer, "position,point,reachability")?;
+        for (position, entry) in self.points.iter().enumerate() {
+            writeln!(writer, "{position},{},{}", entry.point, entry.reachability)?;
+        }
+        writer.flush()
+    }
+}
+
+/// Orders the points of a mutual-reachability MST/forest as OPTICS would.
+///
+/// Each component is walked from its lowest point index, always expanding
+/// the lightest edge leaving the visited set and breaking ties by point
+/// index, so the ordering is deterministic. A point's reachability is the
+/// weight of the edge that reached it; the first point of every component has
+/// [`f32::INFINITY`], so disconnected components are separated by a peak.
+///
+/// # Errors
+/// Returns [`HierarchyError::EmptyDataset`] when `node_count == 0` and
+/// [`HierarchyError::InvalidEdgeWeight`] when an edge weight is negative or
+/// non-finite.
+///
+/// # Panics
+/// Panics when an edge endpoint is not below `node_count`.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::{MstEdge, reachability_plot};
+///
+/// // Two tight pairs joined by a long edge.
+/// let edges = [
+///     MstEdge::new(0, 1, 0.1, 0),
+///     MstEdge::new(2, 3, 0.2, 1),
+///     MstEdge::new(1, 2, 5.0, 2),
+/// ];
+/// let plot = reachability_plot(4, &edges)?;
+/// let distances: Vec<f32> = plot.points().iter().map(|p| p.reachability()).collect();
+///
+/// assert_eq!(distances, [f32::INFINITY, 0.1, 5.0, 0.2]);
+/// # Ok::<(), chutoro_core::HierarchyError>(())
+/// ```
+pub fn reachability_plot(
+    node_count: usize,
+    edges: &[MstEdge],
+) -> Result<ReachabilityPlot, HierarchyError> {
+    if node_count == 0 {
+        return Err(HierarchyError::EmptyDataset);
+    }
+    CondensedForest::validate_edges(edges)?;
+
+    let mut adjacency = vec![Vec::new(); node_count];
+    for edge in edges {
+        adjacency[edge.source()].push((edge.target(), edge.weight()));
+        adjacency[edge.target()].push((edge.source(), edge.weight()));
+    }
+
+    let mut visited = vec![false; node_count];
+    let mut points = Vec::with_capacity(node_count);
+    for start in 0..node_count {
+        if !visited[start] {
+            walk_component(start, &adjacency, &mut visited, &mut points);
+        }
+    }
+    Ok(ReachabilityPlot { points })
+}
+
+/// Appends the component containing `start` to `points` in Prim order.
+fn walk_component(
+    start: usize,
+    adjacency: &[Vec<(usize, f32)>],
+    visited: &mut [bool],
+    points: &mut Vec<ReachabilityPoint>,
+) {
+    let mut frontier = BinaryHeap::from([Reverse(Step {
+        reachability: f32::INFINITY,
+        point: start,
+    })]);
+    while let Some(Reverse(step)) = frontier.pop() {
+        if std::mem::replace(&mut visited[step.point], true) {
+            continue;
+        }
+        points.push(ReachabilityPoint {
+            point: step.point,
+            reachability: step.reachability,
+        });
+        frontier.extend(
+            adjacency[step.point]
+                .iter()
+                .filter(|&&(next, _)| !visited[next])
+                .map(|&(point, reachability)| {
+                    Reverse(Step {
+                        reachability,
+                        point,
+                    })
+                }),
+        );
+    }
+}
+
+/// A frontier entry ordered by reachability, then point index.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Step {
+    reachability: f32,
+    point: usize,
+}
+
+impl Eq for Step {}
+
+impl Ord for Step {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.reachability
+            .total_cmp(&other.reachability)
+            .then(self.point.cmp(&other.point))
+    }
+}
+
+impl PartialOrd for Step {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
@@ -48,7 +48,7 @@ impl<'a> CondenseBuilder<'a> {
         let node = &self.forest.nodes[node_id];
         let Some((left, right)) = node.left.zip(node.right) else {
             if let Some(point) = node.point {
-                record_point_event(self.clusters, cluster_id, point, f32::INFINITY);
+                record_point_event(self.clusters, cluster_id, (point, node.size), f64::INFINITY);
             }
             return;
         };
@@ -81,7 +81,7 @@ impl<'a> CondenseBuilder<'a> {
         }
     }
 
-    fn create_child_cluster(&mut self, parent: usize, lambda: f32, size: usize) -> usize {
+    fn create_child_cluster(&mut self, parent: usize, lambda: f64, size: usize) -> usize {
         let child_id = self.clusters.len();
         self.clusters
             .push(CondensedCluster::new(Some(parent), lambda));
@@ -93,16 +93,16 @@ impl<'a> CondenseBuilder<'a> {
                 lambda,
                 size,
             });
-        record_stability_increment(&mut self.clusters[parent], lambda, size as f32);
+        record_stability_increment(&mut self.clusters[parent], lambda, size as f64);
         child_id
     }
 
-    fn emit_pruned_points(&mut self, node_id: usize, cluster_id: usize, lambda: f32) {
+    fn emit_pruned_points(&mut self, node_id: usize, cluster_id: usize, lambda: f64) {
         let mut stack = vec![node_id];
         while let Some(current) = stack.pop() {
             let node = &self.forest.nodes[current];
             if let Some(point) = node.point {
-                record_point_event(self.clusters, cluster_id, point, lambda);
+                record_point_event(self.clusters, cluster_id, (point, node.size), lambda);
                 continue;
             }
             if let Some(left) = node.left {
@@ -115,28 +115,30 @@ impl<'a> CondenseBuilder<'a> {
     }
 }
 
+/// Records that `point`, standing for `weight` points, left the cluster.
 fn record_point_event(
     clusters: &mut [CondensedCluster],
     cluster_id: usize,
-    point: usize,
-    lambda: f32,
+    (point, weight): (usize, usize),
+    lambda: f64,
 ) {
     let cluster = &mut clusters[cluster_id];
     cluster.events.push(CondensedEvent::Point {
         index: point,
         lambda,
+        weight,
     });
-    record_stability_increment(cluster, lambda, 1.0);
+    record_stability_increment(cluster, lambda, weight as f64);
 }
 
-fn record_stability_increment(cluster: &mut CondensedCluster, lambda: f32, size: f32) {
+fn record_stability_increment(cluster: &mut CondensedCluster, lambda: f64, size: f64) {
     let increment = (lambda - cluster.birth_lambda) * size;
     cluster.stability += increment;
 }
 
-fn weight_to_lambda(weight: f32) -> f32 {
+fn weight_to_lambda(weight: f64) -> f64 {
     if weight == 0.0 {
-        f32::INFINITY
+        f64::INFINITY
     } else {
         1.0 / weight
     }
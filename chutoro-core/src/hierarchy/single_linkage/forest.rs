@@ -29,7 +29,7 @@ impl SingleLinkageForest {
             nodes.push(LinkageNode {
                 left: Some(left_node),
                 right: Some(right_node),
-                weight: edge.weight(),
+                weight: edge.weight_f64(),
                 size,
                 point: None,
             });
@@ -51,14 +51,20 @@ impl SingleLinkageForest {
         roots
     }
 
-    pub(super) fn from_mst(node_count: usize, edges: &[MstEdge]) -> Self {
+    /// Builds the dendrogram; a point counts `weights[point]` times towards
+    /// cluster sizes, or once without weights.
+    pub(super) fn from_mst(
+        node_count: usize,
+        edges: &[MstEdge],
+        weights: Option<&[usize]>,
+    ) -> Self {
         let mut nodes = Vec::with_capacity(node_count.saturating_mul(2).saturating_sub(1));
         for point in 0..node_count {
             nodes.push(LinkageNode {
                 left: None,
                 right: None,
                 weight: 0.0,
-                size: 1,
+                size: weights.map_or(1, |weights| weights[point]),
                 point: Some(point),
             });
         }
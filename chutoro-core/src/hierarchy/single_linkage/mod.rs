@@ -21,6 +21,8 @@
 
 mod condense;
 mod forest;
+mod scores;
+mod tree;
 
 use std::num::NonZeroUsize;
 
@@ -28,6 +30,9 @@ use crate::mst::MstEdge;
 
 use self::condense::CondenseBuilder;
 
+pub(crate) use self::scores::membership_scores;
+pub use self::tree::{CondensedChild, CondensedRow, CondensedTree};
+
 /// Errors returned by hierarchy extraction.
 #[derive(Clone, Debug, thiserror::Error, PartialEq)]
 #[non_exhaustive]
@@ -69,6 +74,11 @@ impl HierarchyError {
 
 /// Machine-readable error codes for [`HierarchyError`].
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "SCREAMING_SNAKE_CASE")
+)]
 pub enum HierarchyErrorCode {
     /// The caller requested hierarchy extraction for an empty dataset.
     EmptyDataset,
@@ -94,11 +104,14 @@ impl HierarchyErrorCode {
 enum CondensedEvent {
     Point {
         index: usize,
-        lambda: f32,
+        lambda: f64,
+        /// Number of points the event stands for; above one only for
+        /// deduplicated representatives.
+        weight: usize,
     },
     ChildCluster {
         cluster: usize,
-        lambda: f32,
+        lambda: f64,
         size: usize,
     },
 }
@@ -106,14 +119,26 @@ enum CondensedEvent {
 #[derive(Clone, Debug, PartialEq)]
 pub(crate) struct CondensedCluster {
     parent: Option<usize>,
-    birth_lambda: f32,
-    stability: f32,
+    birth_lambda: f64,
+    stability: f64,
     events: Vec<CondensedEvent>,
     children: Vec<usize>,
 }
 
 impl CondensedCluster {
-    fn new(parent: Option<usize>, birth_lambda: f32) -> Self {
+    /// Returns the number of points in the cluster, including those of its
+    /// child clusters.
+    fn size(&self) -> usize {
+        self.events
+            .iter()
+            .map(|event| match event {
+                CondensedEvent::Point { weight, .. } => *weight,
+                CondensedEvent::ChildCluster { size, .. } => *size,
+            })
+            .sum()
+    }
+
+    fn new(parent: Option<usize>, birth_lambda: f64) -> Self {
         Self {
             parent,
             birth_lambda,
@@ -131,16 +156,16 @@ pub(crate) struct CondensedForest {
 }
 
 impl CondensedForest {
-    fn validate_edges(edges: &[MstEdge]) -> Result<(), HierarchyError> {
+    pub(in crate::hierarchy) fn validate_edges(edges: &[MstEdge]) -> Result<(), HierarchyError> {
         for edge in edges {
-            let weight = edge.weight();
+            let weight = edge.weight_f64();
             if !weight.is_finite() || weight < 0.0 {
                 let left = edge.source().min(edge.target());
                 let right = edge.source().max(edge.target());
                 return Err(HierarchyError::InvalidEdgeWeight {
                     left,
                     right,
-                    weight,
+                    weight: weight as f32,
                 });
             }
         }
@@ -167,25 +192,29 @@ impl CondensedForest {
         builder.condense_cluster(root, cluster_id);
     }
 
+    /// Condenses the hierarchy of `edges`; with `weights`, each point counts
+    /// as `weights[point]` points towards cluster sizes and stability.
     pub(crate) fn from_mst(
         node_count: usize,
         edges: &[MstEdge],
         min_cluster_size: NonZeroUsize,
+        weights: Option<&[usize]>,
     ) -> Result<Self, HierarchyError> {
         let min_cluster_size = min_cluster_size.get();
         if node_count == 0 {
             return Err(HierarchyError::EmptyDataset);
         }
-        if min_cluster_size > node_count {
+        let total = weights.map_or(node_count, |weights| weights.iter().sum());
+        if min_cluster_size > total {
             return Err(HierarchyError::MinClusterSizeTooLarge {
-                node_count,
+                node_count: total,
                 min_cluster_size,
             });
         }
 
         Self::validate_edges(edges)?;
 
-        let forest = SingleLinkageForest::from_mst(node_count, edges);
+        let forest = SingleLinkageForest::from_mst(node_count, edges, weights);
         let mut condensed = Self {
             clusters: Vec::new(),
             roots: Vec::new(),
@@ -206,19 +235,26 @@ impl CondensedForest {
 ///
 /// When no clusters are selected (for example when all components are smaller
 /// than `min_cluster_size` during condensation), the noise label is `0`.
+///
+/// Returns the labels alongside the condensed cluster selected for each label.
+/// The number of selected clusters is also the noise label. Clusters with
+/// more than `max_cluster_size` points are selected only when they have no
+/// children.
 pub(crate) fn extract_flat_labels(
     node_count: usize,
     condensed: &CondensedForest,
-) -> Result<Vec<usize>, HierarchyError> {
+    max_cluster_size: Option<NonZeroUsize>,
+) -> Result<(Vec<usize>, Vec<usize>), HierarchyError> {
     if node_count == 0 {
         return Err(HierarchyError::EmptyDataset);
     }
     if condensed.clusters.is_empty() {
         // No condensed clusters implies every point is noise.
-        return Ok(vec![0; node_count]);
+        return Ok((vec![0; node_count], Vec::new()));
     }
 
-    let selected = select_stable_clusters(condensed);
+    let max_size = max_cluster_size.map_or(usize::MAX, NonZeroUsize::get);
+    let selected = select_stable_clusters(condensed, max_size);
     let mut selected_ids: Vec<usize> = selected.into_iter().collect();
     selected_ids.sort_unstable();
 
@@ -236,10 +272,11 @@ pub(crate) fn extract_flat_labels(
     let cluster_count = selected_ids.len();
     // When `cluster_count == 0`, the returned labels are all `0`, representing
     // the dedicated noise label (there are no clusters to offset it from).
-    Ok(labels
+    let labels = labels
         .into_iter()
         .map(|label| label.unwrap_or(cluster_count))
-        .collect())
+        .collect();
+    Ok((labels, selected_ids))
 }
 
 struct Labeller<'a> {
@@ -278,10 +315,10 @@ impl<'a> Labeller<'a> {
     }
 }
 
-fn select_stable_clusters(condensed: &CondensedForest) -> Vec<usize> {
+fn select_stable_clusters(condensed: &CondensedForest, max_size: usize) -> Vec<usize> {
     let mut selected = Vec::new();
     for root in condensed.roots.iter().copied() {
-        select_stable_clusters_inner(condensed, root, &mut selected);
+        select_stable_clusters_inner(condensed, root, max_size, &mut selected);
     }
     if selected.is_empty() {
         // Fallback: select all roots to avoid returning only noise for
@@ -294,24 +331,26 @@ fn select_stable_clusters(condensed: &CondensedForest) -> Vec<usize> {
 fn select_stable_clusters_inner(
     condensed: &CondensedForest,
     cluster_id: usize,
+    max_size: usize,
     selected: &mut Vec<usize>,
-) -> f32 {
+) -> f64 {
     let cluster = &condensed.clusters[cluster_id];
     if cluster.children.is_empty() {
         selected.push(cluster_id);
         return cluster.stability;
     }
 
-    let mut child_score = 0.0_f32;
+    let mut child_score = 0.0_f64;
     let mut child_selected = Vec::with_capacity(cluster.children.len());
     for child in &cluster.children {
         let before = selected.len();
-        let score = select_stable_clusters_inner(condensed, *child, selected);
+        let score = select_stable_clusters_inner(condensed, *child, max_size, selected);
         child_score += score;
         child_selected.push((before, selected.len()));
     }
 
-    if child_score > cluster.stability {
+    // An oversized cluster defers to its children whatever their stability.
+    if child_score > cluster.stability || cluster.size() > max_size {
         return child_score;
     }
 
@@ -328,7 +367,7 @@ fn select_stable_clusters_inner(
 struct LinkageNode {
     left: Option<usize>,
     right: Option<usize>,
-    weight: f32,
+    weight: f64,
     size: usize,
     point: Option<usize>,
 }
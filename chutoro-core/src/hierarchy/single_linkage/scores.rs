@@ -0,0 +1,99 @@
+//! Membership probabilities and GLOSH outlier scores from a condensed forest.
+//!
+//! Both scores compare the lambda (`1 / distance`) at which a point leaves the
+//! condensed tree with the largest finite lambda reached anywhere beneath the
+//! cluster it is measured against. Infinite lambdas, produced by duplicate
+//! points, are clamped to that maximum so duplicates score as core members.
+
+use crate::MembershipScores;
+
+use super::{CondensedEvent, CondensedForest};
+
+/// Where and when a point left the condensed tree.
+#[derive(Clone, Copy)]
+struct PointExit {
+    cluster: usize,
+    lambda: f64,
+}
+
+/// Scores every point given the flat labels and the selected cluster ids.
+///
+/// `selected[label]` must be the condensed cluster behind `label`; any label
+/// beyond `selected` is treated as noise.
+pub(crate) fn membership_scores(
+    condensed: &CondensedForest,
+    labels: &[usize],
+    selected: &[usize],
+) -> MembershipScores {
+    let exits = point_exits(condensed, labels.len());
+    let deaths = cluster_deaths(condensed);
+
+    let probabilities = labels
+        .iter()
+        .zip(&exits)
+        .map(|(&label, exit)| match (selected.get(label), exit) {
+            (Some(&cluster), Some(exit)) => relative_lambda(exit.lambda, deaths[cluster]) as f32,
+            _ => 0.0,
+        })
+        .collect();
+    let outlier_scores = exits
+        .iter()
+        .map(|exit| {
+            // Points outside the condensed forest belong to components too
+            // small to form a cluster, so they are maximally outlying.
+            exit.map_or(1.0, |exit| {
+                1.0 - relative_lambda(exit.lambda, deaths[exit.cluster]) as f32
+            })
+        })
+        .collect();
+    MembershipScores::new(probabilities, outlier_scores)
+}
+
+fn point_exits(condensed: &CondensedForest, node_count: usize) -> Vec<Option<PointExit>> {
+    let mut exits = vec![None; node_count];
+    for (cluster, entry) in condensed.clusters.iter().enumerate() {
+        for event in &entry.events {
+            if let CondensedEvent::Point { index, lambda, .. } = *event {
+                exits[index] = Some(PointExit { cluster, lambda });
+            }
+        }
+    }
+    exits
+}
+
+/// Returns the largest finite lambda observed in each cluster's subtree.
+fn cluster_deaths(condensed: &CondensedForest) -> Vec<f64> {
+    let mut deaths: Vec<f64> = condensed
+        .clusters
+        .iter()
+        .map(|cluster| {
+            cluster
+                .events
+                .iter()
+                .map(|event| match *event {
+                    CondensedEvent::Point { lambda, .. }
+                    | CondensedEvent::ChildCluster { lambda, .. } => lambda,
+                })
+                .filter(|lambda| lambda.is_finite())
+                .fold(0.0_f64, f64::max)
+        })
+        .collect();
+    // Children are always created after their parent, so a reverse sweep
+    // folds every descendant into its ancestors.
+    for cluster in (0..deaths.len()).rev() {
+        if let Some(parent) = condensed.clusters[cluster].parent {
+            deaths[parent] = deaths[parent].max(deaths[cluster]);
+        }
+    }
+    deaths
+}
+
+/// Returns `lambda / death`, clamped to `[0, 1]`.
+fn relative_lambda(lambda: f64, death: f64) -> f64 {
+    if death <= 0.0 {
+        // No finite structure beneath the cluster: every point is a core
+        // member at the same (infinite) density.
+        return 1.0;
+    }
+    (lambda.min(death) / death).clamp(0.0, 1.0)
+}
@@ -0,0 +1,157 @@
+//! Read-only view of a condensed hierarchy for export and inspection.
+
+use super::{CondensedEvent, CondensedForest};
+use crate::{
+    HierarchyComparison,
+    hierarchy_compare::{self, TreeShape},
+};
+
+/// What a [`CondensedRow`] attaches to its parent cluster.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CondensedChild {
+    /// A point that left the parent cluster.
+    Point(usize),
+    /// A child cluster split off from the parent.
+    Cluster(usize),
+}
+
+/// One parent-child relation of the condensed tree, in the row format used by
+/// HDBSCAN implementations.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CondensedRow {
+    parent: usize,
+    child: CondensedChild,
+    lambda: f64,
+    size: usize,
+}
+
+impl CondensedRow {
+    /// Returns the parent cluster id.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn parent(&self) -> usize { self.parent }
+
+    /// Returns the point or cluster attached to the parent.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn child(&self) -> CondensedChild { self.child }
+
+    /// Returns the density (`1 / distance`) at which the child left the
+    /// parent; points that never leave report `f64::INFINITY`.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn lambda(&self) -> f64 { self.lambda }
+
+    /// Returns the number of points in the child (`1` for a point, or the
+    /// number of exact duplicates a deduplicated point stands for).
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn size(&self) -> usize { self.size }
+}
+
+/// Borrowed view of the condensed cluster tree built during hierarchy
+/// extraction.
+///
+/// Cluster ids are dense indices starting at `0`; components smaller than
+/// the minimum cluster size have no cluster at all.
+#[derive(Clone, Copy, Debug)]
+pub struct CondensedTree<'a> {
+    forest: &'a CondensedForest,
+}
+
+impl<'a> CondensedTree<'a> {
+    pub(crate) fn new(forest: &'a CondensedForest) -> Self {
+        Self { forest }
+    }
+
+    /// Returns the number of condensed clusters.
+    #[must_use]
+    pub fn cluster_count(&self) -> usize {
+        self.forest.clusters.len()
+    }
+
+    /// Returns the clusters without a parent, one per sufficiently large
+    /// connected component.
+    #[must_use]
+    pub fn roots(&self) -> &'a [usize] {
+        &self.forest.roots
+    }
+
+    /// Returns the parent of `cluster`, or `None` for roots and unknown ids.
+    #[must_use]
+    pub fn parent(&self, cluster: usize) -> Option<usize> {
+        self.forest.clusters.get(cluster)?.parent
+    }
+
+    /// Returns the stability ("excess of mass") of `cluster`.
+    #[must_use]
+    pub fn stability(&self, cluster: usize) -> Option<f32> {
+        self.forest
+            .clusters
+            .get(cluster)
+            .map(|entry| entry.stability as f32)
+    }
+
+    /// Returns every parent-child relation, grouped by parent cluster.
+    pub fn rows(&self) -> impl Iterator<Item = CondensedRow> + 'a {
+        self.forest
+            .clusters
+            .iter()
+            .enumerate()
+            .flat_map(|(parent, cluster)| {
+                cluster.events.iter().map(move |event| match *event {
+                    CondensedEvent::Point {
+                        index,
+                        lambda,
+                        weight,
+                    } => CondensedRow {
+                        parent,
+                        child: CondensedChild::Point(index),
+                        lambda,
+                        size: weight,
+                    },
+                    CondensedEvent::ChildCluster {
+                        cluster,
+                        lambda,
+                        size,
+                    } => CondensedRow {
+                        parent,
+                        child: CondensedChild::Cluster(cluster),
+                        lambda,
+                        size,
+                    },
+                })
+            })
+    }
+
+    /// Compares this tree with `other`, such as the tree of a run with
+    /// different parameters over the same points.
+    ///
+    /// The result scores the hierarchies by a tree edit distance in which
+    /// mapping one cluster onto another costs one minus the Jaccard
+    /// similarity of their points, and lists which clusters were mapped.
+    /// The view ends with the stage hook, so compare trees that outlive a
+    /// run through [`crate::ClusterHierarchy::compare`].
+    #[must_use]
+    pub fn compare(&self, other: &CondensedTree<'_>) -> HierarchyComparison {
+        hierarchy_compare::compare(&self.shape(), &other.shape())
+    }
+
+    fn shape(&self) -> TreeShape {
+        TreeShape {
+            parents: self
+                .forest
+                .clusters
+                .iter()
+                .map(|cluster| cluster.parent)
+                .collect(),
+            exits: self
+                .rows()
+                .filter_map(|row| match row.child() {
+                    CondensedChild::Point(point) => Some((point, row.parent())),
+                    CondensedChild::Cluster(_) => None,
+                })
+                .collect(),
+        }
+    }
+}
@@ -4,9 +4,11 @@ use std::num::NonZeroUsize;
 
 use rstest::rstest;
 
+use super::extract_flat_clustering;
 use crate::{
-    CandidateEdge, EdgeHarvest, HierarchyConfig, HierarchyError, extract_labels_from_mst,
-    parallel_kruskal,
+    CandidateEdge, ChutoroBuilder, ChutoroError, ClusterId, CondensedChild, CondensedTree,
+    EdgeHarvest, HierarchyConfig, HierarchyError, extract_labels_from_harvest,
+    extract_labels_from_mst, parallel_kruskal,
 };
 
 fn core_distances_1d(points: &[f32], min_cluster_size: usize) -> Vec<f32> {
@@ -43,6 +45,16 @@ fn mutual_reachability_edges_1d(points: &[f32], min_cluster_size: usize) -> Edge
     EdgeHarvest::new(edges)
 }
 
+fn raw_edges_1d(points: &[f32], neighbours: usize) -> EdgeHarvest {
+    let mut edges = Vec::new();
+    for (i, a) in points.iter().enumerate() {
+        for (j, b) in points.iter().enumerate().skip(i + 1).take(neighbours) {
+            edges.push(CandidateEdge::new(i, j, (a - b).abs(), edges.len() as u64));
+        }
+    }
+    EdgeHarvest::new(edges)
+}
+
 fn unique_label_count(labels: &[usize]) -> usize {
     use std::collections::HashSet;
 
@@ -100,6 +112,59 @@ fn assigns_outlier_to_noise_when_min_cluster_size_excludes_it() {
     );
 }
 
+#[rstest]
+#[case::clean(vec![0.0, 0.1, 0.2, 10.0, 10.1, 10.2], None)]
+#[case::outlier(vec![0.0, 0.1, 0.2, 10.0, 10.1, 10.2, 100.0], Some(2))]
+fn reports_noise_label_only_when_used(
+    #[case] points: Vec<f32>,
+    #[case] expected_noise: Option<usize>,
+) {
+    let min_cluster_size = 2;
+    let harvest = mutual_reachability_edges_1d(&points, min_cluster_size);
+    let forest = parallel_kruskal(points.len(), &harvest).expect("MST should succeed");
+
+    let flat = extract_flat_clustering(
+        points.len(),
+        forest.edges(),
+        HierarchyConfig::new(NonZeroUsize::new(min_cluster_size).expect("non-zero")),
+    )
+    .expect("hierarchy extraction should succeed");
+
+    assert_eq!(flat.noise_label, expected_noise);
+    if let Some(noise) = flat.noise_label {
+        assert_eq!(flat.labels[points.len() - 1], noise);
+    }
+}
+
+#[test]
+fn scores_outliers_above_cluster_members() {
+    let points = vec![0.0, 0.1, 0.2, 10.0, 10.1, 10.2, 100.0];
+    let min_cluster_size = 2;
+    let harvest = mutual_reachability_edges_1d(&points, min_cluster_size);
+    let forest = parallel_kruskal(points.len(), &harvest).expect("MST should succeed");
+
+    let flat = extract_flat_clustering(
+        points.len(),
+        forest.edges(),
+        HierarchyConfig::new(NonZeroUsize::new(min_cluster_size).expect("non-zero")),
+    )
+    .expect("hierarchy extraction should succeed");
+
+    let probabilities = flat.scores.probabilities();
+    let outliers = flat.scores.outlier_scores();
+    assert_eq!(probabilities.len(), points.len());
+    assert_eq!(outliers.len(), points.len());
+    for (&probability, &outlier) in probabilities.iter().zip(outliers) {
+        assert!((0.0..=1.0).contains(&probability));
+        assert!((0.0..=1.0).contains(&outlier));
+    }
+    let last = points.len() - 1;
+    assert_eq!(probabilities[last], 0.0, "noise has no membership");
+    assert!(probabilities[..last].iter().all(|&p| p > 0.0));
+    let max_member_outlier = outliers[..last].iter().copied().fold(0.0_f32, f32::max);
+    assert!(outliers[last] > max_member_outlier);
+}
+
 #[test]
 fn assigns_all_points_to_noise_when_every_component_is_too_small() {
     let node_count = 4;
@@ -113,6 +178,15 @@ fn assigns_all_points_to_noise_when_every_component_is_too_small() {
     .expect("hierarchy extraction should succeed for empty forests");
 
     assert_eq!(labels, vec![0; node_count]);
+
+    let flat = extract_flat_clustering(
+        node_count,
+        &[],
+        HierarchyConfig::new(NonZeroUsize::new(min_cluster_size).expect("non-zero")),
+    )
+    .expect("hierarchy extraction should succeed for empty forests");
+    assert_eq!(flat.scores.probabilities(), &[0.0; 4]);
+    assert_eq!(flat.scores.outlier_scores(), &[1.0; 4]);
 }
 
 #[test]
@@ -151,3 +225,140 @@ fn rejects_negative_edge_weights() {
 
     assert!(matches!(err, HierarchyError::InvalidEdgeWeight { .. }));
 }
+
+#[rstest]
+fn condensed_tree_view_describes_the_split() {
+    let points = [0.0, 0.1, 0.2, 10.0, 10.1, 10.2];
+    let harvest = mutual_reachability_edges_1d(&points, 2);
+    let forest = parallel_kruskal(points.len(), &harvest).expect("MST should succeed");
+    let config = HierarchyConfig::new(NonZeroUsize::new(2).expect("literal is non-zero"));
+    let flat = extract_flat_clustering(points.len(), forest.edges(), config)
+        .expect("hierarchy extraction should succeed");
+
+    let tree = CondensedTree::new(&flat.condensed);
+
+    assert_eq!(tree.roots(), &[0]);
+    assert_eq!(tree.cluster_count(), 3);
+    assert_eq!((tree.parent(1), tree.parent(2)), (Some(0), Some(0)));
+    assert_eq!(tree.parent(0), None);
+    assert!(tree.stability(1).is_some_and(|stability| stability > 0.0));
+    assert_eq!(tree.stability(3), None);
+    let split: Vec<_> = tree.rows().filter(|row| row.parent() == 0).collect();
+    assert_eq!(split.len(), 2);
+    assert!(
+        split
+            .iter()
+            .all(|row| matches!(row.child(), CondensedChild::Cluster(_)))
+    );
+    assert_eq!(split.iter().map(|row| row.size()).sum::<usize>(), 6);
+    let points_seen = tree
+        .rows()
+        .filter(|row| matches!(row.child(), CondensedChild::Point(_)))
+        .count();
+    assert_eq!(points_seen, points.len());
+}
+
+#[rstest]
+#[case::uncapped(None, 2)]
+#[case::capped(NonZeroUsize::new(3), 3)]
+fn max_cluster_size_splits_oversized_clusters(
+    #[case] max_cluster_size: Option<NonZeroUsize>,
+    #[case] expected_clusters: usize,
+) {
+    let points = vec![0.0, 0.1, 0.2, 0.5, 0.6, 0.7, 100.0, 100.1, 100.2];
+    let min_cluster_size = 2;
+    let harvest = mutual_reachability_edges_1d(&points, min_cluster_size);
+    let forest = parallel_kruskal(points.len(), &harvest).expect("MST should succeed");
+    let mut config = HierarchyConfig::new(NonZeroUsize::new(min_cluster_size).expect("non-zero"));
+    if let Some(max) = max_cluster_size {
+        config = config.with_max_cluster_size(max);
+    }
+
+    let flat = extract_flat_clustering(points.len(), forest.edges(), config)
+        .expect("hierarchy extraction should succeed");
+
+    assert_eq!(flat.noise_label, None);
+    assert_eq!(unique_label_count(&flat.labels), expected_clusters);
+    assert_eq!(flat.labels[6], flat.labels[8]);
+    assert_eq!(
+        flat.labels[0] == flat.labels[5],
+        max_cluster_size.is_none(),
+        "only the cap should split the six points near the origin"
+    );
+}
+
+#[rstest]
+#[case::two_clusters(vec![0.0, 0.1, 0.2, 10.0, 10.1, 10.2], 2)]
+#[case::outlier(vec![0.0, 0.1, 0.2, 0.8, 0.9, 1.0, 100.0], 2)]
+#[case::wide_core(vec![0.0, 0.4, 0.5, 1.5, 9.0, 9.2, 9.9, 10.0], 3)]
+fn harvest_labels_apply_mutual_reachability(
+    #[case] points: Vec<f32>,
+    #[case] min_cluster_size: usize,
+) {
+    let config = HierarchyConfig::new(NonZeroUsize::new(min_cluster_size).expect("non-zero"));
+    let mutual = mutual_reachability_edges_1d(&points, min_cluster_size);
+    let forest = parallel_kruskal(points.len(), &mutual).expect("MST should succeed");
+    let expected = extract_labels_from_mst(points.len(), forest.edges(), config)
+        .expect("hierarchy extraction should succeed");
+
+    let raw = raw_edges_1d(&points, points.len());
+    let labels = extract_labels_from_harvest(points.len(), &raw, config)
+        .expect("harvest extraction should succeed");
+
+    assert_eq!(labels, expected);
+}
+
+#[rstest]
+fn harvest_labels_match_knn_graph_clustering() {
+    let points = [0.0, 1.0, 2.0, 3.0, 20.0, 21.0, 22.0, 23.0, 60.0];
+    let harvest = raw_edges_1d(&points, 2);
+    let config = HierarchyConfig::new(NonZeroUsize::new(2).expect("non-zero"));
+
+    let labels = extract_labels_from_harvest(points.len(), &harvest, config)
+        .expect("harvest extraction should succeed");
+    let result = ChutoroBuilder::new()
+        .with_min_cluster_size(2)
+        .build()
+        .expect("builder should accept the configuration")
+        .cluster_from_knn_graph(points.len(), harvest)
+        .expect("graph clustering should succeed");
+
+    assert_ne!(labels[0], labels[4]);
+    let expected: Vec<_> = labels
+        .iter()
+        .map(|&label| ClusterId::new(label as u64))
+        .collect();
+    assert_eq!(result.assignments(), expected.as_slice());
+}
+
+#[rstest]
+#[case::empty(0, vec![], "EMPTY_GRAPH")]
+#[case::invalid_node(2, vec![CandidateEdge::new(0, 2, 1.0, 0)], "INVALID_NODE_ID")]
+#[case::non_finite(2, vec![CandidateEdge::new(0, 1, f32::NAN, 0)], "NON_FINITE_WEIGHT")]
+fn harvest_extraction_rejects_invalid_edges(
+    #[case] node_count: usize,
+    #[case] edges: Vec<CandidateEdge>,
+    #[case] expected_code: &str,
+) {
+    let config = HierarchyConfig::new(NonZeroUsize::new(2).expect("non-zero"));
+    let err = extract_labels_from_harvest(node_count, &EdgeHarvest::new(edges), config)
+        .expect_err("invalid harvests must be rejected");
+
+    assert!(
+        matches!(&err, ChutoroError::CpuMstFailure { code, .. } if &**code == expected_code),
+        "unexpected error: {err:?}"
+    );
+}
+
+#[rstest]
+fn harvest_extraction_reports_hierarchy_errors() {
+    let harvest = EdgeHarvest::new(vec![CandidateEdge::new(0, 1, 1.0, 0)]);
+    let config = HierarchyConfig::new(NonZeroUsize::new(3).expect("non-zero"));
+    let err = extract_labels_from_harvest(2, &harvest, config)
+        .expect_err("min_cluster_size > node_count is invalid");
+
+    assert!(
+        matches!(&err, ChutoroError::CpuHierarchyFailure { code, .. } if &**code == "MIN_CLUSTER_SIZE_TOO_LARGE"),
+        "unexpected error: {err:?}"
+    );
+}
@@ -0,0 +1,104 @@
+//! Minimum-cost assignment for the child matchings of the tree alignment.
+//!
+//! This is the Hungarian algorithm with row and column potentials, adding
+//! one row per phase along a shortest augmenting path, in `O(n³)` time.
+//! Cluster trees rarely split a cluster more than two ways, so the matrices
+//! are tiny except at the forest roots.
+
+/// Solves the `n × n` assignment problem over the row-major `costs`,
+/// returning the column assigned to each row.
+pub(super) fn assign(costs: &[f64], n: usize) -> Vec<usize> {
+    debug_assert_eq!(costs.len(), n * n, "cost matrix must be square");
+    let mut solver = Hungarian::new(costs, n);
+    for row in 1..=n {
+        solver.add_row(row);
+    }
+    let mut assigned = vec![0; n];
+    for column in 1..=n {
+        assigned[solver.owner[column] - 1] = column - 1;
+    }
+    assigned
+}
+
+/// Solver state; rows and columns are numbered from `1`, and column `0` is
+/// a sentinel holding the row being added.
+struct Hungarian<'a> {
+    costs: &'a [f64],
+    n: usize,
+    row_potential: Vec<f64>,
+    column_potential: Vec<f64>,
+    /// Row assigned to each column, or `0` while the column is free.
+    owner: Vec<usize>,
+    /// Previous column on the augmenting path to each column.
+    way: Vec<usize>,
+}
+
+impl<'a> Hungarian<'a> {
+    fn new(costs: &'a [f64], n: usize) -> Self {
+        Self {
+            costs,
+            n,
+            row_potential: vec![0.0; n + 1],
+            column_potential: vec![0.0; n + 1],
+            owner: vec![0; n + 1],
+            way: vec![0; n + 1],
+        }
+    }
+
+    fn reduced_cost(&self, row: usize, column: usize) -> f64 {
+        self.costs[(row - 1) * self.n + column - 1]
+            - self.row_potential[row]
+            - self.column_potential[column]
+    }
+
+    /// Assigns `row`, shifting earlier rows along the cheapest augmenting
+    /// path to a free column.
+    fn add_row(&mut self, row: usize) {
+        self.owner[0] = row;
+        let mut slack = vec![f64::INFINITY; self.n + 1];
+        let mut used = vec![false; self.n + 1];
+        let mut column = 0;
+        while self.owner[column] != 0 {
+            used[column] = true;
+            let (next, delta) = self.relax(column, &used, &mut slack);
+            self.shift(&used, &mut slack, delta);
+            column = next;
+        }
+        while column != 0 {
+            let previous = self.way[column];
+            self.owner[column] = self.owner[previous];
+            column = previous;
+        }
+    }
+
+    /// Moves the potentials of the visited rows and columns by `delta`,
+    /// keeping every reduced cost non-negative.
+    fn shift(&mut self, used: &[bool], slack: &mut [f64], delta: f64) {
+        for (column, &visited) in used.iter().enumerate() {
+            if visited {
+                self.row_potential[self.owner[column]] += delta;
+                self.column_potential[column] -= delta;
+            } else {
+                slack[column] -= delta;
+            }
+        }
+    }
+
+    /// Updates the slack of every unvisited column through the row owning
+    /// `from`, and returns the column with the least slack and that slack.
+    fn relax(&mut self, from: usize, used: &[bool], slack: &mut [f64]) -> (usize, f64) {
+        let row = self.owner[from];
+        let mut best = (0, f64::INFINITY);
+        for column in (1..=self.n).filter(|&column| !used[column]) {
+            let reduced = self.reduced_cost(row, column);
+            if reduced < slack[column] {
+                slack[column] = reduced;
+                self.way[column] = from;
+            }
+            if slack[column] < best.1 {
+                best = (column, slack[column]);
+            }
+        }
+        best
+    }
+}
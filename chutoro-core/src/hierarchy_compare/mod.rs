@@ -0,0 +1,379 @@
+//! Structural comparison of two cluster hierarchies over the same points.
+//!
+//! Flat scores such as ARI say whether two runs label the points alike, but
+//! not whether a parameter change split, merged, or re-parented clusters
+//! higher up the tree. `CondensedTree::compare` and
+//! [`crate::ClusterHierarchy::compare`] answer that with a constrained tree
+//! edit distance (Zhang, 1996) between the two cluster forests. Deleting or
+//! inserting a cluster costs `1`, and mapping one cluster onto another costs
+//! one minus the Jaccard similarity of the points below them. Deleting a
+//! cluster promotes its children, so a run that splits one cluster further
+//! pays one edit per extra cluster rather than for a whole subtree.
+//!
+//! The constraint maps disjoint subtrees to disjoint subtrees, which keeps
+//! the unordered problem polynomial: each pair of clusters solves one small
+//! assignment problem between their children, for `O(n₁ n₂)` pairs over
+//! trees of `n₁` and `n₂` clusters. The price is that regrouping siblings,
+//! such as nesting two of three children under a new cluster, costs more
+//! than the single insertion an unconstrained distance would charge.
+
+mod assignment;
+#[cfg(test)]
+mod tests;
+
+use std::collections::HashMap;
+
+use self::assignment::assign;
+
+/// A pair of clusters that a [`HierarchyComparison`] maps onto each other.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClusterMatch {
+    /// Cluster of the tree `compare` was called on.
+    pub left: usize,
+    /// Cluster of the tree passed to `compare`.
+    pub right: usize,
+    /// Jaccard similarity of the points below the two clusters.
+    pub jaccard: f64,
+}
+
+/// How far apart two cluster hierarchies are, returned by
+/// `CondensedTree::compare` and [`crate::ClusterHierarchy::compare`].
+///
+/// # Examples
+/// ```rust,ignore
+/// let before = baseline.hierarchy().expect("CPU runs keep the hierarchy");
+/// let after = retuned.hierarchy().expect("CPU runs keep the hierarchy");
+/// let comparison = before.compare(after);
+/// println!("similarity {:.2}", comparison.similarity());
+/// for matched in comparison.matches() {
+///     println!("{} -> {} ({:.2})", matched.left, matched.right, matched.jaccard);
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct HierarchyComparison {
+    distance: f64,
+    similarity: f64,
+    matches: Vec<ClusterMatch>,
+}
+
+impl HierarchyComparison {
+    /// Returns the edit distance: one per inserted or deleted cluster, plus
+    /// one minus the Jaccard similarity of each mapped pair.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn distance(&self) -> f64 { self.distance }
+
+    /// Returns `1 - distance / (n₁ + n₂)` for trees of `n₁` and `n₂`
+    /// clusters: `1.0` for identical hierarchies and `0.0` when no cluster
+    /// could be mapped. Two empty trees score `1.0`.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn similarity(&self) -> f64 { self.similarity }
+
+    /// Returns the mapped clusters, ordered by `left`. Clusters of either
+    /// tree that appear in no pair were deleted or inserted.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn matches(&self) -> &[ClusterMatch] { &self.matches }
+}
+
+/// A cluster forest reduced to what the comparison needs.
+pub(crate) struct TreeShape {
+    /// Parent of each cluster; every child is numbered after its parent.
+    pub(crate) parents: Vec<Option<usize>>,
+    /// Each point paired with the cluster it leaves.
+    pub(crate) exits: Vec<(usize, usize)>,
+}
+
+/// Aligns `left` with `right`.
+pub(crate) fn compare(left: &TreeShape, right: &TreeShape) -> HierarchyComparison {
+    let clusters = left.parents.len() + right.parents.len();
+    let mut aligner = Aligner::new(Tree::new(left), Tree::new(right), overlap(left, right));
+    aligner.fill();
+    let distance = aligner.forest_cost[0];
+    let similarity = if clusters == 0 {
+        1.0
+    } else {
+        (1.0 - distance / clusters as f64).clamp(0.0, 1.0)
+    };
+    HierarchyComparison {
+        distance,
+        similarity,
+        matches: aligner.trace(),
+    }
+}
+
+/// A cluster forest under a virtual root at node `0`, with cluster `c` at
+/// node `c + 1` so that every child still follows its parent.
+struct Tree {
+    parents: Vec<usize>,
+    children: Vec<Vec<usize>>,
+    /// Nodes in each subtree, including its root.
+    sizes: Vec<usize>,
+    /// Points leaving each subtree.
+    points: Vec<usize>,
+}
+
+impl Tree {
+    fn new(shape: &TreeShape) -> Self {
+        let nodes = shape.parents.len() + 1;
+        let mut parents = vec![0; nodes];
+        let mut children = vec![Vec::new(); nodes];
+        for (cluster, parent) in shape.parents.iter().enumerate() {
+            let parent = parent.map_or(0, |parent| parent + 1);
+            debug_assert!(parent <= cluster, "children must follow their parents");
+            parents[cluster + 1] = parent;
+            children[parent].push(cluster + 1);
+        }
+        let mut sizes = vec![1; nodes];
+        let mut points = vec![0; nodes];
+        for &(_, cluster) in &shape.exits {
+            points[cluster + 1] += 1;
+        }
+        for node in (1..nodes).rev() {
+            sizes[parents[node]] += sizes[node];
+            points[parents[node]] += points[node];
+        }
+        Self {
+            parents,
+            children,
+            sizes,
+            points,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// Cost of deleting or inserting the subtree rooted at `node`.
+    fn tree_cost(&self, node: usize) -> f64 {
+        self.sizes[node] as f64
+    }
+
+    /// Cost of deleting or inserting everything below `node`.
+    fn forest_cost(&self, node: usize) -> f64 {
+        (self.sizes[node] - 1) as f64
+    }
+}
+
+/// Counts the points shared by every pair of subtrees, row-major by left
+/// node.
+fn overlap(left: &TreeShape, right: &TreeShape) -> Vec<usize> {
+    let (rows, columns) = (left.parents.len() + 1, right.parents.len() + 1);
+    let homes: HashMap<usize, usize> = right
+        .exits
+        .iter()
+        .map(|&(point, cluster)| (point, cluster + 1))
+        .collect();
+    let mut counts = vec![0; rows * columns];
+    for &(point, cluster) in &left.exits {
+        if let Some(&column) = homes.get(&point) {
+            counts[(cluster + 1) * columns + column] += 1;
+        }
+    }
+    for (row, parent) in left.parents.iter().enumerate().rev() {
+        let parent = parent.map_or(0, |parent| parent + 1);
+        for column in 0..columns {
+            counts[parent * columns + column] += counts[(row + 1) * columns + column];
+        }
+    }
+    for (column, parent) in right.parents.iter().enumerate().rev() {
+        let parent = parent.map_or(0, |parent| parent + 1);
+        for row in 0..rows {
+            counts[row * columns + parent] += counts[row * columns + column + 1];
+        }
+    }
+    counts
+}
+
+/// The alternative that achieved a distance.
+#[derive(Clone, Copy, Debug)]
+enum Step {
+    /// The two roots map onto each other.
+    Map,
+    /// The left node is deleted and this child of it aligns with the right
+    /// side.
+    Left(usize),
+    /// The right node is inserted and this child of it aligns with the left
+    /// side.
+    Right(usize),
+    /// The children are matched one-to-one, the rest deleted or inserted.
+    Children,
+}
+
+/// Distances between every pair of subtrees (`tree_*`) and of the forests
+/// below them (`forest_*`), indexed row-major by left node.
+struct Aligner {
+    left: Tree,
+    right: Tree,
+    overlap: Vec<usize>,
+    tree_cost: Vec<f64>,
+    tree_step: Vec<Step>,
+    forest_cost: Vec<f64>,
+    forest_step: Vec<Step>,
+}
+
+impl Aligner {
+    fn new(left: Tree, right: Tree, overlap: Vec<usize>) -> Self {
+        let pairs = left.len() * right.len();
+        Self {
+            left,
+            right,
+            overlap,
+            tree_cost: vec![0.0; pairs],
+            tree_step: vec![Step::Map; pairs],
+            forest_cost: vec![0.0; pairs],
+            forest_step: vec![Step::Children; pairs],
+        }
+    }
+
+    fn index(&self, left: usize, right: usize) -> usize {
+        left * self.right.len() + right
+    }
+
+    fn jaccard(&self, left: usize, right: usize) -> f64 {
+        let shared = self.overlap[self.index(left, right)];
+        let union = self.left.points[left] + self.right.points[right] - shared;
+        if union == 0 {
+            1.0
+        } else {
+            shared as f64 / union as f64
+        }
+    }
+
+    /// Fills both tables, descendants first.
+    fn fill(&mut self) {
+        for left in (0..self.left.len()).rev() {
+            for right in (0..self.right.len()).rev() {
+                let index = self.index(left, right);
+                let (forest, step) = self.best_forest(left, right);
+                self.forest_cost[index] = forest;
+                self.forest_step[index] = step;
+                let (tree, step) = self.best_tree(left, right, forest);
+                self.tree_cost[index] = tree;
+                self.tree_step[index] = step;
+            }
+        }
+    }
+
+    fn best_tree(&self, left: usize, right: usize, forest: f64) -> (f64, Step) {
+        let mut best = (forest + 1.0 - self.jaccard(left, right), Step::Map);
+        for &child in &self.right.children[right] {
+            let cost = self.right.tree_cost(right) + self.tree_cost[self.index(left, child)]
+                - self.right.tree_cost(child);
+            if cost < best.0 {
+                best = (cost, Step::Right(child));
+            }
+        }
+        for &child in &self.left.children[left] {
+            let cost = self.left.tree_cost(left) + self.tree_cost[self.index(child, right)]
+                - self.left.tree_cost(child);
+            if cost < best.0 {
+                best = (cost, Step::Left(child));
+            }
+        }
+        best
+    }
+
+    fn best_forest(&self, left: usize, right: usize) -> (f64, Step) {
+        let mut best = (self.match_children(left, right).0, Step::Children);
+        for &child in &self.right.children[right] {
+            let cost = self.right.forest_cost(right) + self.forest_cost[self.index(left, child)]
+                - self.right.forest_cost(child);
+            if cost < best.0 {
+                best = (cost, Step::Right(child));
+            }
+        }
+        for &child in &self.left.children[left] {
+            let cost = self.left.forest_cost(left) + self.forest_cost[self.index(child, right)]
+                - self.left.forest_cost(child);
+            if cost < best.0 {
+                best = (cost, Step::Left(child));
+            }
+        }
+        best
+    }
+
+    /// Matches the children of `left` with those of `right`, returning the
+    /// cost and the matched pairs. Unmatched children are deleted or
+    /// inserted with their subtrees.
+    fn match_children(&self, left: usize, right: usize) -> (f64, Vec<(usize, usize)>) {
+        let (lefts, rights) = (&self.left.children[left], &self.right.children[right]);
+        let dropped = |tree: &Tree, nodes: &[usize]| -> f64 {
+            nodes.iter().map(|&node| tree.tree_cost(node)).sum()
+        };
+        if lefts.is_empty() || rights.is_empty() {
+            let cost = dropped(&self.left, lefts) + dropped(&self.right, rights);
+            return (cost, Vec::new());
+        }
+        // Rows are the left children then one insertion slot per right
+        // child; columns are the right children then one deletion slot per
+        // left child. Forbidden cells cost more than dropping everything.
+        let (rows, columns) = (lefts.len(), rights.len());
+        let n = rows + columns;
+        let forbidden = self.left.tree_cost(0) + self.right.tree_cost(0) + 1.0;
+        let mut costs = vec![0.0; n * n];
+        for (row, &child) in lefts.iter().enumerate() {
+            for (column, &other) in rights.iter().enumerate() {
+                costs[row * n + column] = self.tree_cost[self.index(child, other)];
+            }
+            costs[row * n + columns..(row + 1) * n].fill(forbidden);
+            costs[row * n + columns + row] = self.left.tree_cost(child);
+        }
+        for (slot, &other) in rights.iter().enumerate() {
+            let row = rows + slot;
+            costs[row * n..row * n + columns].fill(forbidden);
+            costs[row * n + slot] = self.right.tree_cost(other);
+        }
+        let assigned = assign(&costs, n);
+        let cost = assigned
+            .iter()
+            .enumerate()
+            .map(|(row, &column)| costs[row * n + column])
+            .sum();
+        let pairs = assigned
+            .iter()
+            .take(rows)
+            .enumerate()
+            .filter(|&(_, &column)| column < columns)
+            .map(|(row, &column)| (lefts[row], rights[column]))
+            .collect();
+        (cost, pairs)
+    }
+
+    /// Follows the recorded steps from the two virtual roots, collecting
+    /// the mapped clusters.
+    fn trace(&self) -> Vec<ClusterMatch> {
+        let mut matches = Vec::new();
+        let mut pending = vec![(false, 0, 0)];
+        while let Some((tree, left, right)) = pending.pop() {
+            let index = self.index(left, right);
+            let step = if tree {
+                self.tree_step[index]
+            } else {
+                self.forest_step[index]
+            };
+            match step {
+                Step::Map => {
+                    matches.push(ClusterMatch {
+                        left: left - 1,
+                        right: right - 1,
+                        jaccard: self.jaccard(left, right),
+                    });
+                    pending.push((false, left, right));
+                }
+                Step::Left(child) => pending.push((tree, child, right)),
+                Step::Right(child) => pending.push((tree, left, child)),
+                Step::Children => pending.extend(
+                    self.match_children(left, right)
+                        .1
+                        .into_iter()
+                        .map(|(left, right)| (true, left, right)),
+                ),
+            }
+        }
+        matches.sort_unstable_by_key(|matched| (matched.left, matched.right));
+        matches
+    }
+}
@@ -0,0 +1,115 @@
+//! Unit tests for the cluster tree alignment.
+
+use rstest::rstest;
+
+use super::{TreeShape, assignment::assign, compare};
+
+/// Builds a shape whose cluster `c` holds the points in `ranges[c]`.
+fn shape(parents: &[Option<usize>], ranges: &[std::ops::Range<usize>]) -> TreeShape {
+    TreeShape {
+        parents: parents.to_vec(),
+        exits: ranges
+            .iter()
+            .enumerate()
+            .flat_map(|(cluster, points)| points.clone().map(move |point| (point, cluster)))
+            .collect(),
+    }
+}
+
+/// A root splitting into three leaves of three points each.
+fn flat() -> TreeShape {
+    shape(
+        &[None, Some(0), Some(0), Some(0)],
+        &[0..0, 0..3, 3..6, 6..9],
+    )
+}
+
+fn pairs(left: &TreeShape, right: &TreeShape) -> Vec<(usize, usize)> {
+    compare(left, right)
+        .matches()
+        .iter()
+        .map(|matched| (matched.left, matched.right))
+        .collect()
+}
+
+#[test]
+fn identical_trees_match_every_cluster() {
+    let comparison = compare(&flat(), &flat());
+
+    assert_eq!(comparison.distance(), 0.0);
+    assert_eq!(comparison.similarity(), 1.0);
+    assert_eq!(pairs(&flat(), &flat()), [(0, 0), (1, 1), (2, 2), (3, 3)]);
+    assert!(comparison.matches().iter().all(|m| m.jaccard == 1.0));
+}
+
+#[test]
+fn child_order_does_not_matter() {
+    let reordered = shape(
+        &[None, Some(0), Some(0), Some(0)],
+        &[0..0, 6..9, 0..3, 3..6],
+    );
+
+    assert_eq!(compare(&flat(), &reordered).distance(), 0.0);
+    assert_eq!(pairs(&flat(), &reordered), [(0, 0), (1, 2), (2, 3), (3, 1)]);
+}
+
+#[test]
+fn finer_splits_cost_one_deletion_per_extra_cluster() {
+    let finer = shape(
+        &[None, Some(0), Some(0), Some(1), Some(1)],
+        &[0..0, 0..0, 6..9, 0..3, 3..6],
+    );
+    let coarser = shape(&[None, Some(0), Some(0)], &[0..0, 0..6, 6..9]);
+
+    let comparison = compare(&finer, &coarser);
+
+    assert_eq!(comparison.distance(), 2.0);
+    assert!((comparison.similarity() - (1.0 - 2.0 / 8.0)).abs() < 1e-12);
+    assert_eq!(pairs(&finer, &coarser), [(0, 0), (1, 1), (2, 2)]);
+}
+
+#[test]
+fn moved_points_lower_the_jaccard_of_mapped_clusters() {
+    let shifted = shape(
+        &[None, Some(0), Some(0), Some(0)],
+        &[0..0, 0..2, 2..6, 6..9],
+    );
+
+    let comparison = compare(&flat(), &shifted);
+
+    let jaccards: Vec<f64> = comparison.matches().iter().map(|m| m.jaccard).collect();
+    assert_eq!(jaccards, [1.0, 2.0 / 3.0, 3.0 / 4.0, 1.0]);
+    assert!((comparison.distance() - (1.0 / 3.0 + 1.0 / 4.0)).abs() < 1e-12);
+}
+
+#[test]
+fn separate_roots_align_under_one_root() {
+    let forest = shape(&[None, None, None], &[0..3, 3..6, 6..9]);
+
+    let comparison = compare(&forest, &flat());
+
+    assert_eq!(comparison.distance(), 1.0);
+    assert_eq!(pairs(&forest, &flat()), [(0, 1), (1, 2), (2, 3)]);
+}
+
+#[rstest]
+#[case::empty_left(shape(&[], &[]), flat(), 0.0)]
+#[case::empty_right(flat(), shape(&[], &[]), 0.0)]
+#[case::both_empty(shape(&[], &[]), shape(&[], &[]), 1.0)]
+fn empty_trees_have_nothing_to_match(
+    #[case] left: TreeShape,
+    #[case] right: TreeShape,
+    #[case] similarity: f64,
+) {
+    let comparison = compare(&left, &right);
+
+    assert_eq!(comparison.similarity(), similarity);
+    assert!(comparison.matches().is_empty());
+}
+
+#[test]
+fn assignment_finds_the_cheapest_permutation() {
+    let costs = [4.0, 1.0, 3.0, 2.0, 0.0, 5.0, 3.0, 2.0, 2.0];
+
+    assert_eq!(assign(&costs, 3), [1, 0, 2]);
+}
@@ -0,0 +1,284 @@
+//! Distance-cache configuration and its automatic sizing heuristic.
+//!
+//! [`DistanceCacheConfig::auto`] sizes the cache from the dataset length, a
+//! hint about how expensive the metric is, and the memory currently available
+//! on the host. The heuristic itself is a pure function of those three inputs,
+//! exposed as [`DistanceCacheConfig::auto_with_available_memory`], so a run can
+//! be reproduced on another machine by pinning the memory figure.
+
+use std::{num::NonZeroUsize, time::Duration};
+
+use sysinfo::System;
+
+/// Configuration parameters for the distance cache used by [`crate::CpuHnsw`].
+///
+/// # Examples
+/// ```
+/// use chutoro_core::DistanceCacheConfig;
+/// use std::num::NonZeroUsize;
+///
+/// let config = DistanceCacheConfig::new(NonZeroUsize::new(1024).unwrap())
+///     .with_ttl(None);
+/// assert_eq!(config.max_entries().get(), 1024);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DistanceCacheConfig {
+    max_entries: NonZeroUsize,
+    ttl: Option<Duration>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    shards: Option<NonZeroUsize>,
+}
+
+impl DistanceCacheConfig {
+    /// Default maximum number of cached distances retained before eviction.
+    pub const DEFAULT_MAX_ENTRIES: usize = 1_048_576;
+
+    /// Builds a configuration with the provided maximum capacity.
+    pub fn new(max_entries: NonZeroUsize) -> Self {
+        Self {
+            max_entries,
+            ttl: None,
+            shards: None,
+        }
+    }
+
+    /// Sets an optional time-to-live applied to cached entries.
+    #[must_use]
+    pub fn with_ttl(mut self, ttl: Option<Duration>) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Updates the maximum number of cached entries retained before eviction.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use chutoro_core::DistanceCacheConfig;
+    /// use std::num::NonZeroUsize;
+    ///
+    /// let config = DistanceCacheConfig::default()
+    ///     .with_max_entries(NonZeroUsize::new(2).unwrap());
+    /// assert_eq!(config.max_entries().get(), 2);
+    /// ```
+    #[must_use]
+    pub fn with_max_entries(mut self, max: NonZeroUsize) -> Self {
+        self.max_entries = max;
+        self
+    }
+
+    /// Fixes the number of LRU shards instead of deriving it from the
+    /// capacity; `None` restores the derived count.
+    #[must_use]
+    pub fn with_shards(mut self, shards: Option<NonZeroUsize>) -> Self {
+        self.shards = shards;
+        self
+    }
+
+    /// Returns the maximum number of cached distances retained before eviction.
+    pub fn max_entries(&self) -> NonZeroUsize {
+        self.max_entries
+    }
+
+    /// Returns the configured time-to-live, if any.
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl
+    }
+
+    /// Returns the fixed LRU shard count, if one was set.
+    pub fn shards(&self) -> Option<NonZeroUsize> {
+        self.shards
+    }
+}
+
+impl Default for DistanceCacheConfig {
+    fn default() -> Self {
+        let Some(max_entries) = NonZeroUsize::new(Self::DEFAULT_MAX_ENTRIES) else {
+            unreachable!("default cache size must be non-zero");
+        };
+        Self::new(max_entries)
+    }
+}
+
+/// How expensive a single distance evaluation is relative to a cache lookup.
+///
+/// Guides [`DistanceCacheConfig::auto`]: the dearer the metric, the more
+/// entries are worth keeping per point and the larger the share of available
+/// memory the cache may claim.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum MetricCostHint {
+    /// Comparable to a hash lookup, such as low-dimensional Euclidean or
+    /// Hamming distance over packed hashes.
+    Cheap,
+    /// Tens to hundreds of floating-point operations, such as cosine distance
+    /// over typical embedding widths.
+    #[default]
+    Moderate,
+    /// Dominates insertion time, such as edit distance over long strings or
+    /// metrics that call out to another service.
+    Expensive,
+}
+
+impl MetricCostHint {
+    /// Cached distances worth keeping per point before reuse tails off.
+    const fn entries_per_point(self) -> usize {
+        match self {
+            Self::Cheap => 8,
+            Self::Moderate => 32,
+            Self::Expensive => 128,
+        }
+    }
+
+    /// The cache may claim `1 / divisor` of the available memory.
+    const fn memory_divisor(self) -> u64 {
+        match self {
+            Self::Cheap => 64,
+            Self::Moderate => 32,
+            Self::Expensive => 8,
+        }
+    }
+}
+
+/// Estimated resident bytes per cached distance: the key, the value and its
+/// insertion time, the map slot, and the LRU node.
+const ESTIMATED_ENTRY_BYTES: u64 = 128;
+/// Smallest capacity chosen automatically, so tiny datasets still get one
+/// full shard.
+const MIN_AUTO_ENTRIES: usize = 4096;
+/// Entries per LRU shard the automatic shard count aims for.
+const AUTO_ENTRIES_PER_SHARD: usize = 4096;
+/// Upper bound on the automatic shard count.
+const MAX_AUTO_SHARDS: usize = 256;
+
+impl DistanceCacheConfig {
+    /// Sizes the cache for `dataset_len` points using the memory currently
+    /// available on the host.
+    ///
+    /// Falls back to [`Self::DEFAULT_MAX_ENTRIES`] as the memory ceiling when
+    /// the platform does not report available memory. See
+    /// [`Self::auto_with_available_memory`] for the heuristic.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{DistanceCacheConfig, HnswParams, MetricCostHint};
+    ///
+    /// let cache = DistanceCacheConfig::auto(10_000, MetricCostHint::Expensive);
+    /// assert!(cache.max_entries().get() >= 4096);
+    /// let params = HnswParams::default().with_distance_cache_config(cache);
+    /// # let _ = params;
+    /// ```
+    #[must_use]
+    pub fn auto(dataset_len: usize, metric_cost_hint: MetricCostHint) -> Self {
+        let mut system = System::new();
+        system.refresh_memory();
+        Self::auto_with_available_memory(dataset_len, metric_cost_hint, system.available_memory())
+    }
+
+    /// Sizes the cache deterministically from explicit inputs.
+    ///
+    /// The capacity is `dataset_len` times a per-point allowance that grows
+    /// with the metric cost (8, 32, or 128 entries), at least 4096 entries,
+    /// and at most the number of 128-byte entries that fit in a cost-dependent
+    /// share of `available_bytes` (1/64, 1/32, or 1/8). A zero
+    /// `available_bytes` means "unknown" and caps the capacity at
+    /// [`Self::DEFAULT_MAX_ENTRIES`] instead. The shard count is the power of
+    /// two nearest above one shard per 4096 entries, capped at 256.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{DistanceCacheConfig, MetricCostHint};
+    ///
+    /// let gib = 1 << 30;
+    /// let config =
+    ///     DistanceCacheConfig::auto_with_available_memory(100_000, MetricCostHint::Moderate, gib);
+    /// assert_eq!(config.max_entries().get(), 262_144);
+    /// assert_eq!(config.shards().map(|shards| shards.get()), Some(64));
+    /// ```
+    #[must_use]
+    pub fn auto_with_available_memory(
+        dataset_len: usize,
+        metric_cost_hint: MetricCostHint,
+        available_bytes: u64,
+    ) -> Self {
+        let wanted = dataset_len.saturating_mul(metric_cost_hint.entries_per_point());
+        let ceiling = if available_bytes == 0 {
+            Self::DEFAULT_MAX_ENTRIES
+        } else {
+            let budget =
+                available_bytes / metric_cost_hint.memory_divisor() / ESTIMATED_ENTRY_BYTES;
+            usize::try_from(budget).unwrap_or(usize::MAX)
+        };
+        let entries = wanted.min(ceiling).max(MIN_AUTO_ENTRIES);
+        let shards = entries
+            .div_ceil(AUTO_ENTRIES_PER_SHARD)
+            .next_power_of_two()
+            .min(MAX_AUTO_SHARDS);
+        let (Some(max_entries), Some(shards)) =
+            (NonZeroUsize::new(entries), NonZeroUsize::new(shards))
+        else {
+            unreachable!("automatic cache sizes are at least one");
+        };
+        Self::new(max_entries).with_shards(Some(shards))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for the automatic cache sizing heuristic.
+
+    use rstest::rstest;
+
+    use super::*;
+
+    const GIB: u64 = 1 << 30;
+
+    #[rstest]
+    #[case::tiny_dataset(10, MetricCostHint::Expensive, GIB, (4096, 1))]
+    #[case::cheap(100_000, MetricCostHint::Cheap, GIB, (131_072, 32))]
+    #[case::moderate(100_000, MetricCostHint::Moderate, GIB, (262_144, 64))]
+    #[case::expensive(100_000, MetricCostHint::Expensive, GIB, (1_048_576, 256))]
+    #[case::dataset_bound(1_000, MetricCostHint::Moderate, GIB, (32_000, 8))]
+    #[case::unknown_memory(10_000_000, MetricCostHint::Moderate, 0, (1_048_576, 256))]
+    #[case::starved(100_000, MetricCostHint::Cheap, 1 << 20, (4096, 1))]
+    fn sizes_follow_the_heuristic(
+        #[case] dataset_len: usize,
+        #[case] hint: MetricCostHint,
+        #[case] available_bytes: u64,
+        #[case] (entries, shards): (usize, usize),
+    ) {
+        let config =
+            DistanceCacheConfig::auto_with_available_memory(dataset_len, hint, available_bytes);
+
+        assert_eq!(config.max_entries().get(), entries);
+        assert_eq!(config.shards().map(NonZeroUsize::get), Some(shards));
+        assert_eq!(config.ttl(), None);
+    }
+
+    #[rstest]
+    fn dearer_metrics_never_get_smaller_caches() {
+        let sizes: Vec<_> = [
+            MetricCostHint::Cheap,
+            MetricCostHint::Moderate,
+            MetricCostHint::Expensive,
+        ]
+        .into_iter()
+        .map(|hint| {
+            DistanceCacheConfig::auto_with_available_memory(50_000, hint, 8 * GIB)
+                .max_entries()
+                .get()
+        })
+        .collect();
+
+        assert!(sizes.is_sorted(), "{sizes:?}");
+    }
+
+    #[rstest]
+    fn live_memory_sizing_respects_the_floor() {
+        let config = DistanceCacheConfig::auto(0, MetricCostHint::Cheap);
+
+        assert_eq!(config.max_entries().get(), MIN_AUTO_ENTRIES);
+        assert_eq!(config.shards().map(NonZeroUsize::get), Some(1));
+    }
+}
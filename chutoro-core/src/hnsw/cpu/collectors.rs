@@ -2,9 +2,9 @@
 
 use rayon::prelude::*;
 
-use super::{CandidateEdge, CpuHnsw, EdgeHarvest};
+use super::{CandidateEdge, CpuHnsw, EdgeHarvest, HnswParams, NodeContext};
 use crate::datasource::DataSource;
-use crate::hnsw::error::HnswError;
+use crate::hnsw::{error::HnswError, provenance::HarvestProvenance, types::InsertionPlan};
 
 /// Trait for collecting candidate edges during insertion.
 ///
@@ -13,6 +13,10 @@ use crate::hnsw::error::HnswError;
 pub(super) trait EdgeCollector {
     /// Collects edges discovered during a single node insertion.
     fn collect(&mut self, edges: Vec<CandidateEdge>);
+
+    /// Observes the plan the edges were extracted from; only collectors
+    /// that record provenance look at it.
+    fn trace(&mut self, _ctx: NodeContext, _plan: &InsertionPlan, _params: &HnswParams) {}
 }
 
 /// No-op collector that discards edges without allocation.
@@ -41,6 +45,35 @@ impl EdgeCollector for VecCollector {
     }
 }
 
+/// Collector that accumulates edges alongside where each was found.
+pub(super) struct TracedCollector {
+    edges: Vec<CandidateEdge>,
+    provenance: HarvestProvenance,
+}
+
+impl TracedCollector {
+    pub(super) fn new() -> Self {
+        Self {
+            edges: Vec::new(),
+            provenance: HarvestProvenance::default(),
+        }
+    }
+
+    pub(super) fn into_parts(self) -> (Vec<CandidateEdge>, HarvestProvenance) {
+        (self.edges, self.provenance)
+    }
+}
+
+impl EdgeCollector for TracedCollector {
+    fn collect(&mut self, mut edges: Vec<CandidateEdge>) {
+        self.edges.append(&mut edges);
+    }
+
+    fn trace(&mut self, ctx: NodeContext, plan: &InsertionPlan, params: &HnswParams) {
+        self.provenance.record(ctx, plan, params);
+    }
+}
+
 impl EdgeHarvest {
     /// Collects edges from Rayon-dispatched insertions using `map` → `try_reduce`.
     ///
@@ -63,6 +96,39 @@ impl EdgeHarvest {
                 Ok(acc)
             })?;
 
-        Ok(Self::from_unsorted(edges))
+        Ok(Self::from_index_edges(index, edges))
+    }
+
+    /// Sorts the edges `index` harvested, dropping those of pairs its
+    /// [`crate::DistancePolicy::SkipEdge`] policy skipped.
+    fn from_index_edges(index: &CpuHnsw, edges: Vec<CandidateEdge>) -> Self {
+        let harvest = Self::from_unsorted(edges);
+        index
+            .distance_guard()
+            .without_skipped(&harvest)
+            .unwrap_or(harvest)
+    }
+}
+
+impl HarvestProvenance {
+    /// Collects edges and their provenance from Rayon-dispatched insertions,
+    /// as [`EdgeHarvest::from_parallel_inserts`] does for edges alone.
+    pub(super) fn from_parallel_inserts<D: DataSource + Sync>(
+        index: &CpuHnsw,
+        source: &D,
+        items: usize,
+    ) -> Result<(EdgeHarvest, Self), HnswError> {
+        let (edges, provenance) = (1..items)
+            .into_par_iter()
+            .map(|node| index.insert_traced(node, source))
+            .try_reduce(
+                || (Vec::new(), Self::default()),
+                |(mut edges, mut provenance), (node_edges, node_provenance)| {
+                    edges.extend(node_edges);
+                    provenance.merge(node_provenance);
+                    Ok((edges, provenance))
+                },
+            )?;
+        Ok((EdgeHarvest::from_index_edges(index, edges), provenance))
     }
 }
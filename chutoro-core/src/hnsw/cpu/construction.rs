@@ -99,6 +99,22 @@ impl CpuHnsw {
         Ok((index, edges))
     }
 
+    /// Builds the index like [`Self::build_with_edges`], also recording where
+    /// each harvested edge was found.
+    pub(crate) fn build_traced<D: DataSource + Sync>(
+        source: &D,
+        params: HnswParams,
+    ) -> Result<(Self, EdgeHarvest, HarvestProvenance), HnswError> {
+        let index = Self::build_initial(source, params)?;
+        let items = source.len();
+        let (edges, provenance) = if items > 1 {
+            HarvestProvenance::from_parallel_inserts(&index, source, items)?
+        } else {
+            (EdgeHarvest::default(), HarvestProvenance::default())
+        };
+        Ok((index, edges, provenance))
+    }
+
     /// Shared initial setup for both `build` and `build_with_edges`.
     ///
     /// Creates the index, inserts the entry point (node 0), and returns
@@ -112,22 +128,85 @@ impl CpuHnsw {
             return Err(HnswError::EmptyBuild);
         }
         let index = Self::with_capacity(params, items)?;
-        let level = index.sample_level()?;
-        let sequence = index.allocate_sequence();
+        index.insert_entry(0, source)?;
+        Ok(index)
+    }
+
+    /// Inserts `node` into an empty index as its entry point.
+    fn insert_entry<D: DataSource + Sync>(&self, node: usize, source: &D) -> Result<(), HnswError> {
+        let level = self.sample_level()?;
+        let sequence = self.allocate_sequence();
         let node_ctx = NodeContext {
-            node: 0,
+            node,
             level,
             sequence,
         };
-        validate_distance(
-            Some(&index.distance_cache),
-            source,
-            node_ctx.node,
-            node_ctx.node,
-        )?;
-        index.write_graph(|graph| index.insert_initial(graph, node_ctx))?;
-        index.len.store(1, Ordering::Relaxed);
+        validate_distance(Some(&self.distance_cache), source, node, node)?;
+        self.write_graph(|graph| self.insert_initial(graph, node_ctx))?;
+        self.len.store(1, Ordering::Relaxed);
+        Ok(())
+    }
 
+    /// Rebuilds the index with `params`, keeping every node identifier.
+    ///
+    /// The nodes present when the call starts are reinserted in identifier
+    /// order into a fresh graph with the same capacity, so searches against
+    /// the returned index yield the same ids as before. Distances already held
+    /// in this index's cache seed the new cache, up to its configured
+    /// capacity, so the rebuild only recomputes pairs it has not seen. This
+    /// index is left untouched and remains searchable until the caller swaps
+    /// in the replacement; nodes inserted during the rebuild are not copied.
+    ///
+    /// # Errors
+    /// Returns [`HnswError::DataSource`] when `source` cannot supply a
+    /// distance for a retained node, and the usual insertion errors
+    /// otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use chutoro_core::{CpuHnsw, DataSource, DataSourceError, HnswParams};
+    ///
+    /// struct Dummy(Vec<f32>);
+    /// impl DataSource for Dummy {
+    ///     fn len(&self) -> usize { self.0.len() }
+    ///     fn name(&self) -> &str { "dummy" }
+    ///     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+    ///         Ok((self.0[i] - self.0[j]).abs())
+    ///     }
+    /// }
+    ///
+    /// let data = Dummy(vec![0.0, 1.0, 2.5, 6.0]);
+    /// let index = CpuHnsw::build(&data, HnswParams::new(2, 4).expect("params"))
+    ///     .expect("build must succeed");
+    /// let retuned = index
+    ///     .rebuild(&data, HnswParams::new(3, 12).expect("params"))
+    ///     .expect("rebuild must succeed");
+    /// assert_eq!(retuned.len(), 4);
+    /// assert_eq!(retuned.params().max_connections(), 3);
+    /// let neighbours = retuned
+    ///     .search(&data, 2, NonZeroUsize::new(2).expect("non-zero"))
+    ///     .expect("search must succeed");
+    /// assert_eq!(neighbours[0].id, 2);
+    /// ```
+    pub fn rebuild<D: DataSource + Sync>(
+        &self,
+        source: &D,
+        params: HnswParams,
+    ) -> Result<Self, HnswError> {
+        let (capacity, nodes) = self.read_graph(|graph| {
+            let nodes: Vec<usize> = graph.nodes_iter().map(|(id, _)| id).collect();
+            Ok((graph.capacity(), nodes))
+        })?;
+        let index = Self::with_capacity(params, capacity)?;
+        index.distance_cache.seed_from(&self.distance_cache);
+        let Some((&entry, rest)) = nodes.split_first() else {
+            return Ok(index);
+        };
+        index.insert_entry(entry, source)?;
+        rest.par_iter()
+            .try_for_each(|&node| index.insert(node, source))?;
         Ok(index)
     }
 
@@ -147,13 +226,14 @@ impl CpuHnsw {
             });
         }
         let base_seed = params.rng_seed();
-        let worker_rngs = build_worker_rngs(base_seed);
+        let worker_rngs = build_worker_rngs(params.rng_kind(), base_seed);
 
-        let cache = DistanceCache::new(*params.distance_cache_config());
+        let cache = DistanceCache::new(*params.distance_cache_config())
+            .with_distance_policy(params.distance_policy());
         let graph = Graph::with_capacity(params.clone(), capacity);
 
         Ok(Self {
-            rng: Mutex::new(SmallRng::seed_from_u64(base_seed)),
+            rng: Mutex::new(LevelRng::new(params.rng_kind(), base_seed)),
             worker_rngs,
             graph: Arc::new(RwLock::new(graph)),
             distance_cache: cache,
@@ -0,0 +1,125 @@
+//! Read-only snapshot of a built index for search-heavy workloads.
+
+use std::num::NonZeroUsize;
+
+use crate::DataSource;
+use crate::hnsw::{
+    distance_cache::DistanceCache,
+    error::HnswError,
+    graph::FrozenGraph,
+    helpers::{EnsureQueryArgs, ensure_query_present},
+    params::HnswParams,
+    types::Neighbour,
+};
+
+use super::{CpuHnsw, GraphQuery, search::search_bottom};
+
+/// Immutable snapshot of a [`CpuHnsw`] graph in a compressed sparse row
+/// layout.
+///
+/// Every neighbour list is packed into one contiguous array, so searches
+/// follow offsets instead of chasing one heap allocation per node and level,
+/// and take no lock. The snapshot answers queries exactly as the index did
+/// when it was frozen; insertions into the index afterwards do not reach it.
+#[derive(Debug)]
+pub struct FrozenHnsw {
+    params: HnswParams,
+    graph: FrozenGraph,
+    distance_cache: DistanceCache,
+    len: usize,
+}
+
+impl CpuHnsw {
+    /// Copies the graph into a [`FrozenHnsw`] for searching once
+    /// construction has finished.
+    ///
+    /// The snapshot starts with a copy of the index's distance cache.
+    /// Insertion keeps using the mutable layout, so freeze again after
+    /// inserting to search the new nodes.
+    ///
+    /// # Errors
+    /// Returns [`HnswError::LockPoisoned`] when the graph lock is poisoned.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use chutoro_core::{CpuHnsw, DataSource, DataSourceError, HnswParams};
+    /// # struct Dummy(Vec<f32>);
+    /// # impl DataSource for Dummy {
+    /// #     fn len(&self) -> usize { self.0.len() }
+    /// #     fn name(&self) -> &str { "dummy" }
+    /// #     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+    /// #         Ok((self.0[i] - self.0[j]).abs())
+    /// #     }
+    /// # }
+    /// let params = HnswParams::new(2, 4).expect("params");
+    /// let data = Dummy(vec![0.0, 1.0, 3.5, 4.0]);
+    /// let index = CpuHnsw::build(&data, params).expect("build must succeed");
+    /// let frozen = index.freeze().expect("graph lock is healthy");
+    ///
+    /// let ef = NonZeroUsize::new(2).expect("non-zero");
+    /// assert_eq!(frozen.len(), 4);
+    /// assert_eq!(
+    ///     frozen.search(&data, 2, ef).expect("search"),
+    ///     index.search(&data, 2, ef).expect("search"),
+    /// );
+    /// ```
+    pub fn freeze(&self) -> Result<FrozenHnsw, HnswError> {
+        let (graph, len) = self.read_graph(|graph| Ok((graph.freeze(), self.len())))?;
+        let distance_cache = DistanceCache::new(*self.params.distance_cache_config())
+            .with_guard_of(&self.distance_cache);
+        distance_cache.seed_from(&self.distance_cache);
+        Ok(FrozenHnsw {
+            params: self.params.clone(),
+            graph,
+            distance_cache,
+            len,
+        })
+    }
+}
+
+impl FrozenHnsw {
+    /// Searches the snapshot for the `ef` nearest neighbours of `query`,
+    /// exactly as [`CpuHnsw::search`] does on the index it was frozen from.
+    ///
+    /// # Errors
+    /// Returns [`HnswError::GraphEmpty`] when the snapshot holds no nodes,
+    /// and the distance and invariant errors of [`CpuHnsw::search`].
+    pub fn search<D: DataSource + Sync>(
+        &self,
+        source: &D,
+        query: usize,
+        ef: NonZeroUsize,
+    ) -> Result<Vec<Neighbour>, HnswError> {
+        let cache = &self.distance_cache;
+        let searcher = self.graph.searcher();
+        let mut neighbours =
+            search_bottom(&searcher, Some(cache), source, GraphQuery { query, ef })?;
+        ensure_query_present(
+            cache,
+            EnsureQueryArgs {
+                source,
+                query,
+                ef,
+                neighbours: &mut neighbours,
+            },
+        )?;
+        Ok(neighbours)
+    }
+
+    /// Returns the number of nodes in the snapshot.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn len(&self) -> usize { self.len }
+
+    /// Returns whether the snapshot holds no nodes.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn is_empty(&self) -> bool { self.len == 0 }
+
+    /// Returns the parameters of the index the snapshot was frozen from.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn params(&self) -> &HnswParams { &self.params }
+}
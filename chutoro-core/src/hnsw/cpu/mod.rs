@@ -5,14 +5,19 @@
 
 mod collectors;
 mod construction;
+mod frozen;
 pub(super) mod internal;
 pub(super) mod rng;
+mod search;
+mod snapshot;
 pub(super) mod trim;
+mod view;
 
 #[cfg(test)]
 pub(super) mod test_helpers;
 
 use std::{
+    io::Write,
     num::NonZeroUsize,
     sync::{
         Arc, Mutex, RwLock,
@@ -20,33 +25,39 @@ use std::{
     },
 };
 
-use rand::{SeedableRng, rngs::SmallRng};
 use rayon::prelude::*;
 
-use crate::DataSource;
+use crate::{DataSource, distance_policy::DistanceGuard};
 
 use super::{
+    candidate::{CandidateEdge, EdgeHarvest},
     distance_cache::DistanceCache,
     error::HnswError,
-    graph::{ApplyContext, Graph, NodeContext, SearchContext},
-    helpers::{EnsureQueryArgs, ensure_query_present, normalize_neighbour_order},
+    export::{GraphExportError, GraphFormat, write_graph},
+    graph::{ApplyContext, Graph, NodeContext},
+    helpers::{EnsureQueryArgs, ensure_query_present},
     insert::{PlanningInputs, extract_candidate_edges},
     invariants::HnswInvariantChecker,
     params::HnswParams,
-    types::{CandidateEdge, EdgeHarvest, Neighbour},
+    provenance::HarvestProvenance,
+    statistics::HnswStatistics,
+    types::Neighbour,
     validate::validate_distance,
 };
 
-use self::collectors::{EdgeCollector, NoopCollector, VecCollector};
-use self::rng::build_worker_rngs;
+use self::collectors::{EdgeCollector, NoopCollector, TracedCollector, VecCollector};
+pub use self::frozen::FrozenHnsw;
+use self::rng::{LevelRng, build_worker_rngs};
+pub(crate) use self::search::GraphQuery;
+pub use self::view::{HnswNodeView, HnswNodes, HnswReadView};
 
 /// Parallel CPU HNSW index coordinating insertions through two-phase locking.
 #[derive(Debug)]
 pub struct CpuHnsw {
     pub(super) params: HnswParams,
     pub(super) graph: Arc<RwLock<Graph>>,
-    rng: Mutex<SmallRng>,
-    worker_rngs: Vec<Mutex<SmallRng>>,
+    rng: Mutex<LevelRng>,
+    worker_rngs: Vec<Mutex<LevelRng>>,
     distance_cache: DistanceCache,
     insert_mutex: Mutex<()>,
     next_sequence: AtomicU64,
@@ -92,6 +103,18 @@ impl CpuHnsw {
         Ok(collector.into_inner())
     }
 
+    /// Inserts a node and returns its candidate edges with where each was
+    /// found.
+    fn insert_traced<D: DataSource + Sync>(
+        &self,
+        node: usize,
+        source: &D,
+    ) -> Result<(Vec<CandidateEdge>, HarvestProvenance), HnswError> {
+        let mut collector = TracedCollector::new();
+        self.insert_with_collector(node, source, &mut collector)?;
+        Ok(collector.into_parts())
+    }
+
     /// Inserts a node into the graph and returns harvested candidate edges.
     ///
     /// This method performs the same insertion as [`Self::insert`], but returns
@@ -186,6 +209,7 @@ impl CpuHnsw {
         // Extract candidate edges before consuming the plan
         let edges = extract_candidate_edges(node, sequence, &plan);
         collector.collect(edges);
+        collector.trace(node_ctx, &plan, &self.params);
 
         let (prepared, trim_jobs) = self.write_graph(|graph| {
             let mut executor = graph.insertion_executor();
@@ -235,32 +259,8 @@ impl CpuHnsw {
         query: usize,
         ef: NonZeroUsize,
     ) -> Result<Vec<Neighbour>, HnswError> {
-        let graph = self.read_graph_guard()?;
-        let entry = graph.entry().ok_or(HnswError::GraphEmpty)?;
-        let searcher = graph.searcher();
-        let mut current = entry.node;
-        for level in (1..=entry.level).rev() {
-            current = searcher.greedy_search_layer(
-                Some(&self.distance_cache),
-                source,
-                SearchContext {
-                    query,
-                    entry: current,
-                    level,
-                },
-            )?;
-        }
-        let mut neighbours = searcher.search_layer(
-            Some(&self.distance_cache),
-            source,
-            SearchContext {
-                query,
-                entry: current,
-                level: 0,
-            }
-            .with_ef(ef.get()),
-        )?;
-        normalize_neighbour_order(&mut neighbours);
+        let mut neighbours =
+            self.search_graph(Some(&self.distance_cache), source, GraphQuery { query, ef })?;
         ensure_query_present(
             &self.distance_cache,
             EnsureQueryArgs {
@@ -283,6 +283,85 @@ impl CpuHnsw {
     #[rustfmt::skip]
     pub fn is_empty(&self) -> bool { self.len() == 0 }
 
+    /// Returns the parameters the index was constructed with.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn params(&self) -> &HnswParams { &self.params }
+
+    /// Summarizes the level distribution, per-level connectivity, and
+    /// distance cache occupancy of the index.
+    ///
+    /// # Errors
+    /// Returns [`HnswError::LockPoisoned`] when the graph lock is poisoned.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{CpuHnsw, HnswParams};
+    ///
+    /// let params = HnswParams::new(2, 4).expect("params");
+    /// let index = CpuHnsw::with_capacity(params, 4).expect("index");
+    /// let stats = index.statistics().expect("graph lock is healthy");
+    /// assert_eq!(stats.node_count(), 0);
+    /// assert_eq!(stats.entry_level(), None);
+    /// ```
+    pub fn statistics(&self) -> Result<HnswStatistics, HnswError> {
+        let graph = self.read_graph_guard()?;
+        Ok(HnswStatistics::collect(&graph, &self.distance_cache))
+    }
+
+    /// Writes the graph's nodes and per-layer edges to `writer` in `format`
+    /// for visualisation tools.
+    ///
+    /// Nodes carry their top level and whether they are the entry point;
+    /// edges are the stored, directed adjacency entries tagged with their
+    /// layer. Output is buffered internally and flushed before returning.
+    /// Insertions wait on the graph lock while the export is written.
+    ///
+    /// # Errors
+    /// Returns [`GraphExportError::Hnsw`] when the graph lock is poisoned
+    /// and [`GraphExportError::Io`] when writing fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{CpuHnsw, DataSource, DataSourceError, GraphFormat, HnswParams};
+    /// # struct Dummy(Vec<f32>);
+    /// # impl DataSource for Dummy {
+    /// #     fn len(&self) -> usize { self.0.len() }
+    /// #     fn name(&self) -> &str { "dummy" }
+    /// #     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+    /// #         Ok((self.0[i] - self.0[j]).abs())
+    /// #     }
+    /// # }
+    /// let params = HnswParams::new(2, 4).expect("params");
+    /// let index = CpuHnsw::build(&Dummy(vec![0.0, 1.0, 3.5]), params).expect("build");
+    ///
+    /// let mut dot = Vec::new();
+    /// index.export_graph(&mut dot, GraphFormat::Dot).expect("export");
+    /// let dot = String::from_utf8(dot).expect("DOT is UTF-8");
+    /// assert!(dot.starts_with("digraph hnsw {\n"));
+    /// assert!(dot.contains(" -> "));
+    /// ```
+    pub fn export_graph(
+        &self,
+        writer: impl Write,
+        format: GraphFormat,
+    ) -> Result<(), GraphExportError> {
+        let graph = self.read_graph_guard()?;
+        Ok(write_graph(&graph, writer, format)?)
+    }
+
+    /// Returns the distance cache's eviction count and capacity, without
+    /// taking the graph lock [`Self::statistics`] needs.
+    pub(crate) fn distance_cache_pressure(&self) -> (u64, usize) {
+        let cache = &self.distance_cache;
+        (cache.evictions(), cache.capacity())
+    }
+
+    /// Returns the guard applying the index's distance policy.
+    pub(crate) fn distance_guard(&self) -> &DistanceGuard {
+        self.distance_cache.guard()
+    }
+
     /// Returns a handle for checking structural invariants.
     #[must_use]
     pub fn invariants(&self) -> HnswInvariantChecker<'_> {
@@ -1,38 +1,76 @@
 //! RNG setup and level sampling utilities for the CPU HNSW implementation.
 
+mod small_v1;
+
 use std::sync::Mutex;
 
-use rand::{Rng, SeedableRng, distributions::Standard, rngs::SmallRng};
+use rand_chacha::{
+    ChaCha8Rng,
+    rand_core::{RngCore, SeedableRng},
+};
 use rayon::{current_num_threads, current_thread_index};
 
-use crate::hnsw::error::HnswError;
+use crate::{
+    hnsw::{error::HnswError, params::RngKind},
+    seed::{SPLITMIX_INCREMENT, splitmix64},
+};
 
+use self::small_v1::SmallRngV1;
 use super::CpuHnsw;
 
-/// SplitMix64 increment (the 64-bit golden ratio) used for per-worker seed
-/// derivation.
-const WORKER_SEED_SPACING: u64 = 0x9E37_79B9_7F4A_7C15;
-const SPLITMIX_MULT_A: u64 = 0xBF58_476D_1CE4_E5B9;
-const SPLITMIX_MULT_B: u64 = 0x94D0_49BB_1331_11EB;
+/// Spacing between per-worker seeds: the SplitMix64 increment.
+const WORKER_SEED_SPACING: u64 = SPLITMIX_INCREMENT;
 
-#[inline]
-pub(super) fn mix_worker_seed(base_seed: u64, worker_index: usize) -> u64 {
-    splitmix64(base_seed ^ ((worker_index as u64 + 1).wrapping_mul(WORKER_SEED_SPACING)))
+/// Scale turning the top 53 bits of a draw into a float in `[0, 1)`.
+const UNIT_SCALE: f64 = 1.0 / (1_u64 << 53) as f64;
+
+/// Level-sampling generator, producing the stream its [`RngKind`] names.
+#[derive(Clone, Debug)]
+pub(crate) enum LevelRng {
+    SmallV1(SmallRngV1),
+    ChaCha8(Box<ChaCha8Rng>),
+}
+
+impl LevelRng {
+    pub(crate) fn new(kind: RngKind, seed: u64) -> Self {
+        match kind {
+            RngKind::SmallRngV1 => Self::SmallV1(SmallRngV1::seed_from_u64(seed)),
+            RngKind::ChaCha8 => Self::ChaCha8(Box::new(ChaCha8Rng::from_seed(chacha_key(seed)))),
+        }
+    }
+
+    /// Draws a float in `[0, 1)` from the top 53 bits of the next output, as
+    /// `rand` 0.8 samples `f64` from its `Standard` distribution.
+    pub(crate) fn next_unit(&mut self) -> f64 {
+        let bits = match self {
+            Self::SmallV1(rng) => rng.next_u64(),
+            Self::ChaCha8(rng) => rng.next_u64(),
+        };
+        (bits >> 11) as f64 * UNIT_SCALE
+    }
+}
+
+/// Expands `seed` into a ChaCha key of four consecutive SplitMix64 outputs.
+fn chacha_key(seed: u64) -> [u8; 32] {
+    let mut key = [0_u8; 32];
+    let mut counter = seed;
+    for chunk in key.chunks_exact_mut(8) {
+        chunk.copy_from_slice(&splitmix64(counter).to_le_bytes());
+        counter = counter.wrapping_add(SPLITMIX_INCREMENT);
+    }
+    key
 }
 
 #[inline]
-fn splitmix64(mut state: u64) -> u64 {
-    state = state.wrapping_add(WORKER_SEED_SPACING);
-    state = (state ^ (state >> 30)).wrapping_mul(SPLITMIX_MULT_A);
-    state = (state ^ (state >> 27)).wrapping_mul(SPLITMIX_MULT_B);
-    state ^ (state >> 31)
+pub(super) fn mix_worker_seed(base_seed: u64, worker_index: usize) -> u64 {
+    splitmix64(base_seed ^ ((worker_index as u64 + 1).wrapping_mul(WORKER_SEED_SPACING)))
 }
 
-pub(super) fn build_worker_rngs(base_seed: u64) -> Vec<Mutex<SmallRng>> {
+pub(super) fn build_worker_rngs(kind: RngKind, base_seed: u64) -> Vec<Mutex<LevelRng>> {
     (0..current_num_threads())
         .map(|idx| {
             let seed = mix_worker_seed(base_seed, idx);
-            Mutex::new(SmallRng::seed_from_u64(seed))
+            Mutex::new(LevelRng::new(kind, seed))
         })
         .collect()
 }
@@ -54,10 +92,14 @@ impl CpuHnsw {
         Ok(self.sample_level_from_rng(&mut rng))
     }
 
-    pub(super) fn sample_level_from_rng(&self, rng: &mut SmallRng) -> usize {
+    pub(super) fn sample_level_from_rng(&self, rng: &mut LevelRng) -> usize {
+        if self.params.level_distribution().is_some() {
+            let draw = rng.next_unit();
+            return self.params.distributed_level(draw).unwrap_or_default();
+        }
         let mut level = 0_usize;
         while level < self.params.max_level() {
-            let draw: f64 = rng.sample(Standard);
+            let draw = rng.next_unit();
             if self.params.should_stop(draw) {
                 break;
             }
@@ -66,3 +108,6 @@ impl CpuHnsw {
         level
     }
 }
+
+#[cfg(test)]
+mod tests;
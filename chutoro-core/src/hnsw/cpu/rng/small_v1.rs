@@ -0,0 +1,67 @@
+//! Frozen copy of the generator behind [`crate::RngKind::SmallRngV1`].
+//!
+//! `rand` 0.8 implements `SmallRng` on 64-bit targets as xoshiro256++ and
+//! fills its state from a `u64` seed with PCG32. Both are reproduced here so
+//! that the stream no longer depends on the `rand` version in use.
+
+/// PCG32 multiplier used to expand the seed.
+const PCG_MULTIPLIER: u64 = 6_364_136_223_846_793_005;
+/// PCG32 increment used to expand the seed.
+const PCG_INCREMENT: u64 = 11_634_580_027_462_260_723;
+
+/// xoshiro256++ generator seeded as `rand` 0.8 seeds `SmallRng`.
+#[derive(Clone, Debug)]
+pub(crate) struct SmallRngV1 {
+    state: [u64; 4],
+}
+
+impl SmallRngV1 {
+    pub(crate) fn seed_from_u64(seed: u64) -> Self {
+        let mut pcg = seed;
+        let mut state = [0_u64; 4];
+        for word in &mut state {
+            let low = u64::from(pcg32(&mut pcg));
+            let high = u64::from(pcg32(&mut pcg));
+            *word = low | (high << 32);
+        }
+        if state == [0; 4] {
+            return Self::seed_from_splitmix(0);
+        }
+        Self { state }
+    }
+
+    /// Seeds from SplitMix64 outputs, as xoshiro256++ does when handed an
+    /// all-zero state, which it could never leave.
+    pub(super) fn seed_from_splitmix(seed: u64) -> Self {
+        let mut counter = seed;
+        let mut state = [0_u64; 4];
+        for word in &mut state {
+            *word = crate::seed::splitmix64(counter);
+            counter = counter.wrapping_add(crate::seed::SPLITMIX_INCREMENT);
+        }
+        Self { state }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        let [s0, s1, s2, s3] = &mut self.state;
+        let result = s0.wrapping_add(*s3).rotate_left(23).wrapping_add(*s0);
+        let shifted = *s1 << 17;
+        *s2 ^= *s0;
+        *s3 ^= *s1;
+        *s1 ^= *s2;
+        *s0 ^= *s3;
+        *s2 ^= shifted;
+        *s3 = s3.rotate_left(45);
+        result
+    }
+}
+
+/// Advances the PCG32 state and returns its next output.
+fn pcg32(state: &mut u64) -> u32 {
+    *state = state
+        .wrapping_mul(PCG_MULTIPLIER)
+        .wrapping_add(PCG_INCREMENT);
+    let xorshifted = (((*state >> 18) ^ *state) >> 27) as u32;
+    let rotation = (*state >> 59) as u32;
+    xorshifted.rotate_right(rotation)
+}
@@ -0,0 +1,53 @@
+//! Tests pinning the streams of each level-sampling generator.
+
+use rand::{Rng, SeedableRng, distributions::Standard, rngs::SmallRng};
+use rstest::rstest;
+
+use super::*;
+
+fn draws(kind: RngKind, seed: u64) -> Vec<f64> {
+    let mut rng = LevelRng::new(kind, seed);
+    (0..3).map(|_| rng.next_unit()).collect()
+}
+
+#[rstest]
+#[case(0)]
+#[case(42)]
+#[case(0x5EED_CAFE)]
+#[case(u64::MAX)]
+fn small_rng_v1_reproduces_rand_small_rng(#[case] seed: u64) {
+    let mut expected = SmallRng::seed_from_u64(seed);
+    let mut frozen = LevelRng::new(RngKind::SmallRngV1, seed);
+    for _ in 0..1_000 {
+        let draw: f64 = expected.sample(Standard);
+        assert_eq!(frozen.next_unit().to_bits(), draw.to_bits());
+    }
+}
+
+#[rstest]
+#[case::small_rng_v1(
+    RngKind::SmallRngV1,
+    [0.15935863614561085, 0.30424576388446034, 0.295564972432393]
+)]
+#[case::chacha8(
+    RngKind::ChaCha8,
+    [0.1917361602025135, 0.09114982297259133, 0.968028053549324]
+)]
+fn streams_are_frozen(#[case] kind: RngKind, #[case] expected: [f64; 3]) {
+    assert_eq!(draws(kind, 42), expected);
+}
+
+#[rstest]
+fn kinds_and_seeds_give_distinct_streams() {
+    assert_ne!(draws(RngKind::SmallRngV1, 7), draws(RngKind::ChaCha8, 7));
+    assert_ne!(draws(RngKind::ChaCha8, 7), draws(RngKind::ChaCha8, 8));
+}
+
+#[rstest]
+fn splitmix_fallback_matches_xoshiro_seeding() {
+    let mut expected = rand::rngs::SmallRng::from_seed([0; 32]);
+    let mut frozen = SmallRngV1::seed_from_splitmix(0);
+    for _ in 0..16 {
+        assert_eq!(frozen.next_u64(), expected.r#gen::<u64>());
+    }
+}
@@ -0,0 +1,329 @@
+//! Layered nearest-neighbour search shared by the public and crate-internal
+//! search entry points.
+
+mod descent;
+
+use std::num::NonZeroUsize;
+
+use crate::DataSource;
+use crate::hnsw::{
+    distance_cache::DistanceCache,
+    error::HnswError,
+    graph::SearchContext,
+    helpers::{EnsureQueryArgs, ensure_query_present, normalize_neighbour_order},
+    search::{FilteredContext, RangeContext},
+    types::{Neighbour, NeighbourDetail},
+};
+
+pub(super) use self::descent::search_bottom;
+use self::descent::{descend_to_bottom, trace_descent};
+use super::CpuHnsw;
+
+/// A row to search for and the number of neighbours to keep.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct GraphQuery {
+    pub(crate) query: usize,
+    pub(crate) ef: NonZeroUsize,
+}
+
+/// A graph query whose results must pass `filter`.
+#[derive(Clone, Copy)]
+struct FilteredQuery<'f> {
+    query: GraphQuery,
+    filter: &'f dyn Fn(usize) -> bool,
+}
+
+impl CpuHnsw {
+    /// Searches like [`Self::search`] and reports, for each neighbour, the
+    /// highest layer on which the search evaluated it and its insertion
+    /// sequence.
+    ///
+    /// A neighbour found on an upper layer was a neighbour of the greedy
+    /// descent path, so a recall failure whose true neighbours all report
+    /// level `0` points at the bottom-layer search rather than the descent.
+    /// Unlike [`Self::search`], the results are the traversal as it ran: an
+    /// indexed `query` the search did not reach is not added.
+    ///
+    /// # Errors
+    /// Returns the errors of [`Self::search`], and
+    /// [`HnswError::GraphInvariantViolation`] when a result has no recorded
+    /// insertion sequence.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use chutoro_core::{CpuHnsw, DataSource, DataSourceError, HnswParams};
+    /// # struct Dummy(Vec<f32>);
+    /// # impl DataSource for Dummy {
+    /// #     fn len(&self) -> usize { self.0.len() }
+    /// #     fn name(&self) -> &str { "dummy" }
+    /// #     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+    /// #         Ok((self.0[i] - self.0[j]).abs())
+    /// #     }
+    /// # }
+    /// let params = HnswParams::new(2, 4).expect("params");
+    /// let data = Dummy(vec![0.0, 1.0, 3.5]);
+    /// let index = CpuHnsw::build(&data, params).expect("build must succeed");
+    ///
+    /// let ef = NonZeroUsize::new(3).expect("non-zero");
+    /// let details = index.search_detailed(&data, 0, ef).expect("search");
+    /// let plain = index.search(&data, 0, ef).expect("search");
+    /// let neighbours: Vec<_> = details.iter().map(|detail| detail.neighbour()).collect();
+    /// assert_eq!(neighbours, plain);
+    /// ```
+    pub fn search_detailed<D: DataSource + Sync>(
+        &self,
+        source: &D,
+        query: usize,
+        ef: NonZeroUsize,
+    ) -> Result<Vec<NeighbourDetail>, HnswError> {
+        let cache = Some(&self.distance_cache);
+        let graph = self.read_graph_guard()?;
+        let (entry, levels) = trace_descent(&graph, cache, source, query)?;
+        let mut neighbours = graph.searcher().search_layer(
+            cache,
+            source,
+            SearchContext {
+                query,
+                entry,
+                level: 0,
+            }
+            .with_ef(ef.get()),
+        )?;
+        normalize_neighbour_order(&mut neighbours);
+        neighbours
+            .into_iter()
+            .map(|Neighbour { id, distance }| {
+                let sequence =
+                    graph
+                        .node_sequence(id)
+                        .ok_or_else(|| HnswError::GraphInvariantViolation {
+                            message: format!("sequence missing for node {id} during search"),
+                        })?;
+                Ok(NeighbourDetail {
+                    id,
+                    distance,
+                    level: levels.get(&id).copied().unwrap_or(0),
+                    sequence,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns up to `limit` indexed points within `radius` of `query`,
+    /// closest first.
+    ///
+    /// The search descends the upper layers greedily, then expands the bottom
+    /// layer only through points inside the radius, so the cost grows with
+    /// the number of matches rather than with a fixed beam width. Like
+    /// [`Self::search`], an indexed `query` is its own match at distance
+    /// zero. The result is approximate: points inside the radius that are
+    /// only linked to the rest of the ball through points outside it can be
+    /// missed.
+    ///
+    /// # Errors
+    /// Returns [`HnswError::InvalidParameters`] when `radius` is negative or
+    /// NaN, [`HnswError::GraphEmpty`] when nothing has been inserted, and the
+    /// search errors of [`Self::search`] otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use chutoro_core::{CpuHnsw, DataSource, DataSourceError, HnswParams};
+    /// # struct Dummy(Vec<f32>);
+    /// # impl DataSource for Dummy {
+    /// #     fn len(&self) -> usize { self.0.len() }
+    /// #     fn name(&self) -> &str { "dummy" }
+    /// #     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+    /// #         Ok((self.0[i] - self.0[j]).abs())
+    /// #     }
+    /// # }
+    /// let params = HnswParams::new(2, 4).expect("params");
+    /// let data = Dummy(vec![0.0, 0.5, 1.0, 9.0]);
+    /// let index = CpuHnsw::build(&data, params).expect("build must succeed");
+    ///
+    /// let limit = NonZeroUsize::new(10).expect("non-zero");
+    /// let within = index.range_search(&data, 0, 0.75, limit).expect("search");
+    /// let ids: Vec<_> = within.iter().map(|neighbour| neighbour.id).collect();
+    /// assert_eq!(ids, [0, 1]);
+    /// ```
+    #[expect(
+        clippy::too_many_arguments,
+        reason = "mirrors `search`, adding the radius alongside the result limit"
+    )]
+    pub fn range_search<D: DataSource + Sync>(
+        &self,
+        source: &D,
+        query: usize,
+        radius: f32,
+        limit: NonZeroUsize,
+    ) -> Result<Vec<Neighbour>, HnswError> {
+        if radius.is_nan() || radius < 0.0 {
+            return Err(HnswError::InvalidParameters {
+                reason: format!("range search radius must be non-negative, got {radius}"),
+            });
+        }
+        let cache = Some(&self.distance_cache);
+        let graph = self.read_graph_guard()?;
+        let searcher = graph.searcher();
+        let entry = descend_to_bottom(&searcher, cache, source, query)?;
+        let mut neighbours = searcher.range_search_layer(
+            cache,
+            source,
+            RangeContext {
+                base: SearchContext {
+                    query,
+                    entry,
+                    level: 0,
+                },
+                radius,
+                limit: limit.get(),
+            },
+        )?;
+        normalize_neighbour_order(&mut neighbours);
+        Ok(neighbours)
+    }
+
+    /// Searches like [`Self::search`] but returns only points for which
+    /// `filter` returns `true`, so tenants sharing one index see only their
+    /// own rows.
+    ///
+    /// The filter is applied during the bottom-layer traversal. Rejected
+    /// points are still expanded, because they may be the only links between
+    /// accepted ones, but each one widens the beam by a slot, so up to `ef`
+    /// accepted points are returned whenever the layer connects that many to
+    /// the entry point. The cost grows as the filter becomes more selective:
+    /// a filter that accepts nothing visits every point reachable on the
+    /// bottom layer. Like [`Self::search`], an indexed `query` the filter
+    /// accepts is its own match at distance zero.
+    ///
+    /// # Errors
+    /// Returns the errors of [`Self::search`].
+    ///
+    /// # Examples
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use chutoro_core::{CpuHnsw, DataSource, DataSourceError, HnswParams};
+    /// # struct Dummy(Vec<f32>);
+    /// # impl DataSource for Dummy {
+    /// #     fn len(&self) -> usize { self.0.len() }
+    /// #     fn name(&self) -> &str { "dummy" }
+    /// #     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+    /// #         Ok((self.0[i] - self.0[j]).abs())
+    /// #     }
+    /// # }
+    /// let params = HnswParams::new(2, 4).expect("params");
+    /// let data = Dummy(vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+    /// let index = CpuHnsw::build(&data, params).expect("build must succeed");
+    ///
+    /// let ef = NonZeroUsize::new(2).expect("non-zero");
+    /// let odd = index
+    ///     .search_filtered(&data, 2, ef, |row| row % 2 == 1)
+    ///     .expect("search");
+    /// let mut ids: Vec<_> = odd.iter().map(|neighbour| neighbour.id).collect();
+    /// ids.sort_unstable();
+    /// assert_eq!(ids, [1, 3]);
+    /// ```
+    #[expect(
+        clippy::too_many_arguments,
+        reason = "mirrors `search`, adding the filter alongside the beam width"
+    )]
+    pub fn search_filtered<D: DataSource + Sync>(
+        &self,
+        source: &D,
+        query: usize,
+        ef: NonZeroUsize,
+        filter: impl Fn(usize) -> bool,
+    ) -> Result<Vec<Neighbour>, HnswError> {
+        let cache = Some(&self.distance_cache);
+        let mut neighbours = self.search_graph_filtered(
+            cache,
+            source,
+            FilteredQuery {
+                query: GraphQuery { query, ef },
+                filter: &filter,
+            },
+        )?;
+        if filter(query) {
+            ensure_query_present(
+                &self.distance_cache,
+                EnsureQueryArgs {
+                    source,
+                    query,
+                    ef,
+                    neighbours: &mut neighbours,
+                },
+            )?;
+        }
+        Ok(neighbours)
+    }
+
+    /// Searches like [`Self::search`] but bypasses the distance cache and
+    /// does not insert `query` into the results.
+    ///
+    /// Rows outside the index may change between calls, as when prediction
+    /// queries are appended to the training rows, so their distances must not
+    /// be cached against the row index.
+    pub(crate) fn search_uncached<D: DataSource + Sync>(
+        &self,
+        source: &D,
+        query: usize,
+        ef: NonZeroUsize,
+    ) -> Result<Vec<Neighbour>, HnswError> {
+        self.search_graph(None, source, GraphQuery { query, ef })
+    }
+
+    /// Searches like [`Self::search_uncached`] but returns only neighbours
+    /// for which `filter` returns `true`, as [`Self::search_filtered`] does.
+    pub(crate) fn search_uncached_filtered<D: DataSource + Sync>(
+        &self,
+        source: &D,
+        query: GraphQuery,
+        filter: &dyn Fn(usize) -> bool,
+    ) -> Result<Vec<Neighbour>, HnswError> {
+        self.search_graph_filtered(None, source, FilteredQuery { query, filter })
+    }
+
+    /// Descends the layers from the entry point and returns up to `ef`
+    /// accepted neighbours of the query, closest first.
+    fn search_graph_filtered<D: DataSource + Sync>(
+        &self,
+        cache: Option<&DistanceCache>,
+        source: &D,
+        FilteredQuery { query, filter }: FilteredQuery<'_>,
+    ) -> Result<Vec<Neighbour>, HnswError> {
+        let graph = self.read_graph_guard()?;
+        let searcher = graph.searcher();
+        let entry = descend_to_bottom(&searcher, cache, source, query.query)?;
+        let mut neighbours = searcher.filtered_search_layer(
+            cache,
+            source,
+            FilteredContext {
+                base: SearchContext {
+                    query: query.query,
+                    entry,
+                    level: 0,
+                }
+                .with_ef(query.ef.get()),
+                filter,
+            },
+        )?;
+        normalize_neighbour_order(&mut neighbours);
+        Ok(neighbours)
+    }
+
+    /// Descends the layers from the entry point and returns up to `ef`
+    /// neighbours of the query, closest first.
+    pub(super) fn search_graph<D: DataSource + Sync>(
+        &self,
+        cache: Option<&DistanceCache>,
+        source: &D,
+        query: GraphQuery,
+    ) -> Result<Vec<Neighbour>, HnswError> {
+        let graph = self.read_graph_guard()?;
+        search_bottom(&graph.searcher(), cache, source, query)
+    }
+}
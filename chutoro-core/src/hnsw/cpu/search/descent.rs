@@ -0,0 +1,100 @@
+//! Greedy descent through the upper layers, shared by the live and frozen
+//! search entry points.
+
+use std::collections::HashMap;
+
+use crate::DataSource;
+use crate::hnsw::{
+    distance_cache::DistanceCache,
+    error::HnswError,
+    graph::{Graph, SearchContext},
+    helpers::normalize_neighbour_order,
+    search::LayerSearcher,
+    types::Neighbour,
+};
+
+use super::GraphQuery;
+
+/// Descends through the graph `searcher` walks and returns up to `ef`
+/// neighbours of the query from the bottom layer, closest first.
+pub(in crate::hnsw::cpu) fn search_bottom<D: DataSource + Sync>(
+    searcher: &LayerSearcher<'_>,
+    cache: Option<&DistanceCache>,
+    source: &D,
+    GraphQuery { query, ef }: GraphQuery,
+) -> Result<Vec<Neighbour>, HnswError> {
+    let entry = descend_to_bottom(searcher, cache, source, query)?;
+    let mut neighbours = searcher.search_layer(
+        cache,
+        source,
+        SearchContext {
+            query,
+            entry,
+            level: 0,
+        }
+        .with_ef(ef.get()),
+    )?;
+    normalize_neighbour_order(&mut neighbours);
+    Ok(neighbours)
+}
+
+/// Descends greedily from the entry point through the upper layers and
+/// returns the bottom-layer node closest to `query` on the way down.
+pub(super) fn descend_to_bottom<D: DataSource + Sync>(
+    searcher: &LayerSearcher<'_>,
+    cache: Option<&DistanceCache>,
+    source: &D,
+    query: usize,
+) -> Result<usize, HnswError> {
+    let entry = searcher.entry().ok_or(HnswError::GraphEmpty)?;
+    let mut current = entry.node;
+    for level in (1..=entry.level).rev() {
+        current = searcher.greedy_search_layer(
+            cache,
+            source,
+            SearchContext {
+                query,
+                entry: current,
+                level,
+            },
+        )?;
+    }
+    Ok(current)
+}
+
+/// Descends like [`descend_to_bottom`] and also returns the highest layer on
+/// which each node's distance was evaluated: the nodes on the greedy path and
+/// the neighbours they compared against.
+pub(super) fn trace_descent<D: DataSource + Sync>(
+    graph: &Graph,
+    cache: Option<&DistanceCache>,
+    source: &D,
+    query: usize,
+) -> Result<(usize, HashMap<usize, usize>), HnswError> {
+    let entry = graph.entry().ok_or(HnswError::GraphEmpty)?;
+    let searcher = graph.searcher();
+    let mut levels = HashMap::from([(entry.node, entry.level)]);
+    let mut current = entry.node;
+    for level in (1..=entry.level).rev() {
+        let path = searcher.greedy_search_path(
+            cache,
+            source,
+            SearchContext {
+                query,
+                entry: current,
+                level,
+            },
+        )?;
+        let evaluated = path.iter().flat_map(|&node| {
+            let neighbours = graph
+                .node(node)
+                .map_or(&[][..], |node| node.neighbours(level));
+            std::iter::once(node).chain(neighbours.iter().copied())
+        });
+        for node in evaluated {
+            levels.entry(node).or_insert(level);
+        }
+        current = path.last().copied().unwrap_or(current);
+    }
+    Ok((current, levels))
+}
@@ -0,0 +1,160 @@
+//! Binary snapshots of a [`CpuHnsw`] graph for pipeline checkpoints.
+//!
+//! A snapshot stores the slot capacity, every node's insertion sequence and
+//! per-level neighbour lists, and the entry point, all as little-endian `u64`
+//! words. The entry node is written first so a restore can seed the graph
+//! with it before attaching the rest. Parameters are not stored: callers
+//! restore with the parameters the index was built with. The distance cache
+//! and level generators of a restored index start afresh.
+
+use std::io::{self, Read, Write};
+
+use super::*;
+use crate::hnsw::node::NodeRef;
+
+impl CpuHnsw {
+    /// Writes the graph to `writer` as a snapshot.
+    ///
+    /// # Errors
+    /// Returns an error when the graph lock is poisoned or writing fails.
+    pub(crate) fn write_snapshot(&self, writer: &mut impl Write) -> io::Result<()> {
+        let graph = self.read_graph_guard().map_err(io::Error::other)?;
+        let entry = graph.entry().map(|entry| entry.node);
+        write_word(writer, graph.capacity() as u64)?;
+        write_word(writer, graph.nodes_iter().count() as u64)?;
+        write_word(writer, u64::from(entry.is_some()))?;
+        if let Some(node) = entry.and_then(|id| graph.node(id).map(|node| (id, node))) {
+            write_node(writer, node)?;
+        }
+        for node in graph.nodes_iter().filter(|(id, _)| Some(*id) != entry) {
+            write_node(writer, node)?;
+        }
+        Ok(())
+    }
+
+    /// Restores an index from a snapshot written by [`Self::write_snapshot`].
+    ///
+    /// # Errors
+    /// Returns [`io::ErrorKind::InvalidData`] when the snapshot is malformed
+    /// or does not fit `params`, and any error raised while reading.
+    pub(crate) fn read_snapshot(params: HnswParams, reader: &mut impl Read) -> io::Result<Self> {
+        let capacity = read_usize(reader)?;
+        let count = read_usize(reader)?;
+        let has_entry = read_word(reader)? != 0;
+        let max_level = params.max_level();
+        let index = Self::with_capacity(params, capacity).map_err(invalid_data)?;
+        let mut next_sequence = 0;
+        {
+            let mut graph = index.write_graph_guard().map_err(invalid_data)?;
+            for position in 0..count {
+                let (ctx, lists) = read_node(reader, (capacity, max_level))?;
+                restore_node(&mut graph, (ctx, lists), position == 0 && has_entry)?;
+                next_sequence = ctx.sequence.saturating_add(1).max(next_sequence);
+            }
+        }
+        index.len.store(count, Ordering::Relaxed);
+        index.next_sequence.store(next_sequence, Ordering::Relaxed);
+        Ok(index)
+    }
+}
+
+/// Attaches a node read from a snapshot, as the entry point when `is_entry`
+/// holds, and fills its neighbour lists.
+fn restore_node(
+    graph: &mut Graph,
+    (ctx, lists): (NodeContext, Vec<Vec<usize>>),
+    is_entry: bool,
+) -> io::Result<()> {
+    if is_entry {
+        graph.insert_first(ctx)
+    } else {
+        graph.attach_node(ctx)
+    }
+    .map_err(invalid_data)?;
+    let Some(mut node) = graph.node_mut(ctx.node) else {
+        unreachable!("node {} was attached above", ctx.node);
+    };
+    for (level, neighbours) in lists.into_iter().enumerate() {
+        node.neighbours_mut(level).extend(neighbours);
+    }
+    Ok(())
+}
+
+fn write_node(writer: &mut impl Write, (id, node): (usize, NodeRef<'_>)) -> io::Result<()> {
+    write_word(writer, id as u64)?;
+    write_word(writer, node.level_count() as u64)?;
+    write_word(writer, node.sequence())?;
+    for level in 0..node.level_count() {
+        let neighbours = node.neighbours(level);
+        write_word(writer, neighbours.len() as u64)?;
+        for &neighbour in neighbours {
+            write_word(writer, neighbour as u64)?;
+        }
+    }
+    Ok(())
+}
+
+/// Reads one node record, checking every identifier against `capacity` and
+/// the node's top level against `max_level` before allocating its lists.
+fn read_node(
+    reader: &mut impl Read,
+    (capacity, max_level): (usize, usize),
+) -> io::Result<(NodeContext, Vec<Vec<usize>>)> {
+    let node = read_id(reader, capacity)?;
+    let level_count = read_usize(reader)?;
+    let sequence = read_word(reader)?;
+    let level = match level_count.checked_sub(1) {
+        Some(level) if level <= max_level => level,
+        _ => {
+            return Err(invalid_data(format!(
+                "node {node} spans {level_count} levels, outside 1..={}",
+                max_level + 1
+            )));
+        }
+    };
+    let lists = (0..level_count)
+        .map(|_| {
+            let len = read_usize(reader)?;
+            if len > capacity {
+                return Err(invalid_data(format!(
+                    "node {node} lists {len} neighbours among {capacity} slots"
+                )));
+            }
+            (0..len).map(|_| read_id(reader, capacity)).collect()
+        })
+        .collect::<io::Result<_>>()?;
+    let ctx = NodeContext {
+        node,
+        level,
+        sequence,
+    };
+    Ok((ctx, lists))
+}
+
+fn write_word(writer: &mut impl Write, word: u64) -> io::Result<()> {
+    writer.write_all(&word.to_le_bytes())
+}
+
+fn read_word(reader: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn read_usize(reader: &mut impl Read) -> io::Result<usize> {
+    usize::try_from(read_word(reader)?).map_err(invalid_data)
+}
+
+fn read_id(reader: &mut impl Read, capacity: usize) -> io::Result<usize> {
+    let id = read_usize(reader)?;
+    if id >= capacity {
+        return Err(invalid_data(format!(
+            "node {id} lies outside the snapshot's {capacity} slots"
+        )));
+    }
+    Ok(id)
+}
+
+fn invalid_data(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
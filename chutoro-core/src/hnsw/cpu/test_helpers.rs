@@ -2,13 +2,14 @@
 
 use std::sync::Mutex;
 
-use rand::{SeedableRng, rngs::SmallRng};
-
 use crate::hnsw::{
     distance_cache::DistanceCache, error::HnswError, graph::Graph, params::HnswParams,
 };
 
-use super::{CpuHnsw, internal, rng::build_worker_rngs};
+use super::{
+    CpuHnsw, internal,
+    rng::{LevelRng, build_worker_rngs},
+};
 
 impl CpuHnsw {
     /// Test-only healing hook that re-enforces reachability and bidirectionality.
@@ -18,8 +19,8 @@ impl CpuHnsw {
     pub fn heal_for_test(&self) {
         let healed = self.write_graph(|graph| {
             let mut executor = graph.insertion_executor();
-            executor.heal_reachability(self.params.max_connections());
-            executor.enforce_bidirectional_all(self.params.max_connections());
+            executor.heal_reachability(self.params.connection_limits());
+            executor.enforce_bidirectional_all(self.params.connection_limits());
             Ok(())
         });
         if let Err(err) = healed {
@@ -53,9 +54,10 @@ impl CpuHnsw {
 
     pub(crate) fn reconfigure_for_test(&mut self, params: HnswParams) {
         let base_seed = params.rng_seed();
-        self.rng = Mutex::new(SmallRng::seed_from_u64(base_seed));
-        self.worker_rngs = build_worker_rngs(base_seed);
-        self.distance_cache = DistanceCache::new(*params.distance_cache_config());
+        self.rng = Mutex::new(LevelRng::new(params.rng_kind(), base_seed));
+        self.worker_rngs = build_worker_rngs(params.rng_kind(), base_seed);
+        self.distance_cache = DistanceCache::new(*params.distance_cache_config())
+            .with_distance_policy(params.distance_policy());
         self.params = params;
         let reconfigured = self.write_graph(|graph| {
             graph.set_params(&self.params);
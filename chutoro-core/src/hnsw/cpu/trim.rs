@@ -12,6 +12,7 @@ use crate::{
         insert::{TrimJob, TrimResult},
         params::connection_limit_for_level,
         types::RankedNeighbour,
+        validate::validate_batch_distances,
     },
 };
 
@@ -20,13 +21,17 @@ use super::CpuHnsw;
 impl CpuHnsw {
     /// Scores trim jobs in parallel, validating candidate, sequence, and
     /// distance lengths before emitting ranked neighbour lists capped at each
-    /// edge context's `max_connections`.
+    /// edge context's connection limit for the level.
     ///
     /// The caller supplies trimmed candidates gathered while the graph lock is
     /// held. This method then validates the batched distances without the lock
     /// and deterministically orders neighbours by distance and insertion
-    /// sequence so ties remain stable while a bounded binary heap retains only
-    /// the best `max_connections` entries.
+    /// sequence so ties remain stable. Under [`TrimPolicy::Nearest`] a bounded
+    /// binary heap retains only the best entries up to that limit; the
+    /// pruning policies instead walk the ordered candidates and skip those a
+    /// kept neighbour already covers.
+    ///
+    /// [`TrimPolicy::Nearest`]: crate::hnsw::TrimPolicy::Nearest
     ///
     /// # Examples
     /// ```rust,ignore
@@ -36,6 +41,7 @@ impl CpuHnsw {
     ///     DataSourceError,
     ///     HnswParams,
     ///     hnsw::graph::EdgeContext,
+    ///     hnsw::params::ConnectionLimits,
     ///     hnsw::insert::executor::TrimJob,
     /// };
     ///
@@ -53,7 +59,7 @@ impl CpuHnsw {
     /// let hnsw = CpuHnsw::with_capacity(params, 2).expect("capacity should accept two nodes");
     /// let trim_jobs = vec![TrimJob {
     ///     node: 0,
-    ///     ctx: EdgeContext { level: 0, max_connections: 1 },
+    ///     ctx: EdgeContext { level: 0, limits: ConnectionLimits::uniform(1) },
     ///     candidates: vec![0],
     ///     sequences: vec![0],
     /// }];
@@ -89,7 +95,7 @@ impl CpuHnsw {
             sequences,
         } = job;
 
-        let connection_limit = connection_limit_for_level(ctx.level, ctx.max_connections);
+        let connection_limit = connection_limit_for_level(ctx.level, ctx.limits);
 
         if candidates.len() != sequences.len() {
             return Err(HnswError::InvalidParameters {
@@ -112,21 +118,23 @@ impl CpuHnsw {
             });
         }
 
-        let mut heap = BinaryHeap::with_capacity(connection_limit);
-        for (index, id) in candidates.into_iter().enumerate() {
-            let sequence = sequences[index];
-            let distance = distances[index];
-            heap.push(RankedNeighbour::new(id, distance, sequence));
-            if heap.len() > connection_limit {
-                heap.pop();
-            }
-        }
-
-        let neighbours = heap
-            .into_sorted_vec()
+        let ranked = candidates
             .into_iter()
-            .map(|neighbour| neighbour.into_neighbour().id)
-            .collect();
+            .zip(distances)
+            .zip(sequences)
+            .map(|((id, distance), sequence)| RankedNeighbour::new(id, distance, sequence));
+        let neighbours = match self.params.trim_policy().relaxation() {
+            None => nearest(ranked, connection_limit),
+            Some(alpha) => {
+                let mut ranked: Vec<_> = ranked.collect();
+                ranked.sort_unstable();
+                let pruning = Pruning {
+                    limit: connection_limit,
+                    alpha,
+                };
+                self.prune(source, &ranked, pruning)?
+            }
+        };
 
         Ok(TrimResult {
             node,
@@ -134,4 +142,53 @@ impl CpuHnsw {
             neighbours,
         })
     }
+
+    /// Walks `ranked`, nearest first, keeping each candidate whose distance
+    /// to the trimmed node is below `alpha` times its distance to every
+    /// neighbour already kept, until `limit` are kept.
+    fn prune<D: DataSource + Sync>(
+        &self,
+        source: &D,
+        ranked: &[RankedNeighbour],
+        Pruning { limit, alpha }: Pruning,
+    ) -> Result<Vec<usize>, HnswError> {
+        let mut kept = Vec::with_capacity(limit);
+        for candidate in ranked.iter().map(|ranked| ranked.into_neighbour()) {
+            if kept.len() == limit {
+                break;
+            }
+            let separations =
+                validate_batch_distances(Some(&self.distance_cache), source, candidate.id, &kept)?;
+            if separations
+                .into_iter()
+                .all(|separation| candidate.distance < alpha * separation)
+            {
+                kept.push(candidate.id);
+            }
+        }
+        Ok(kept)
+    }
+}
+
+/// Bounds on a pruning trim: the list's connection limit and the relaxation
+/// factor of the policy.
+#[derive(Clone, Copy, Debug)]
+struct Pruning {
+    limit: usize,
+    alpha: f32,
+}
+
+/// Keeps the `limit` nearest of `ranked`, nearest first.
+fn nearest(ranked: impl Iterator<Item = RankedNeighbour>, limit: usize) -> Vec<usize> {
+    let mut heap = BinaryHeap::with_capacity(limit);
+    for neighbour in ranked {
+        heap.push(neighbour);
+        if heap.len() > limit {
+            heap.pop();
+        }
+    }
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|neighbour| neighbour.into_neighbour().id)
+        .collect()
 }
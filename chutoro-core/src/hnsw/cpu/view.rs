@@ -0,0 +1,176 @@
+//! Read-only access to the live graph for analysis and export tools.
+
+use std::sync::RwLockReadGuard;
+
+use crate::hnsw::{error::HnswError, graph::Graph, node::NodeRef, types::EntryPoint};
+
+use super::CpuHnsw;
+
+/// Read-only view of a [`CpuHnsw`] graph, returned by
+/// [`CpuHnsw::read_view`].
+///
+/// The view holds the graph's read lock, so the adjacency it reports cannot
+/// change while it is alive and insertions wait until it is dropped.
+/// Searches take the same shared lock and proceed alongside it. Neighbour
+/// lists are borrowed from the index rather than copied.
+///
+/// Iterating a reference to the view yields every inserted node in
+/// identifier order.
+#[derive(Debug)]
+pub struct HnswReadView<'index> {
+    graph: RwLockReadGuard<'index, Graph>,
+}
+
+/// One inserted node of a [`HnswReadView`].
+#[derive(Clone, Copy, Debug)]
+pub struct HnswNodeView<'view> {
+    id: usize,
+    node: NodeRef<'view>,
+}
+
+/// Iterator over the inserted nodes of a [`HnswReadView`], in identifier
+/// order.
+#[derive(Debug)]
+pub struct HnswNodes<'view> {
+    graph: &'view Graph,
+    next: usize,
+}
+
+impl CpuHnsw {
+    /// Locks the graph for reading and returns a view of its adjacency.
+    ///
+    /// # Errors
+    /// Returns [`HnswError::LockPoisoned`] when the graph lock is poisoned.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{CpuHnsw, DataSource, DataSourceError, HnswParams};
+    /// # struct Dummy(Vec<f32>);
+    /// # impl DataSource for Dummy {
+    /// #     fn len(&self) -> usize { self.0.len() }
+    /// #     fn name(&self) -> &str { "dummy" }
+    /// #     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+    /// #         Ok((self.0[i] - self.0[j]).abs())
+    /// #     }
+    /// # }
+    /// let params = HnswParams::new(2, 4).expect("params");
+    /// let index = CpuHnsw::build(&Dummy(vec![0.0, 1.0, 3.5]), params).expect("build");
+    ///
+    /// let view = index.read_view().expect("graph lock is healthy");
+    /// let entry = view.entry().expect("populated graphs have an entry point");
+    /// assert_eq!(view.levels(entry.node), Some(entry.level + 1));
+    /// for node in &view {
+    ///     for &neighbour in node.neighbours(0) {
+    ///         assert!(view.neighbours(neighbour, 0).is_some());
+    ///     }
+    /// }
+    /// assert_eq!(view.iter().count(), 3);
+    /// ```
+    pub fn read_view(&self) -> Result<HnswReadView<'_>, HnswError> {
+        Ok(HnswReadView {
+            graph: self.read_graph_guard()?,
+        })
+    }
+}
+
+impl HnswReadView<'_> {
+    /// Returns the node searches start from, or `None` when the graph is
+    /// empty.
+    #[must_use]
+    pub fn entry(&self) -> Option<EntryPoint> {
+        self.graph.entry()
+    }
+
+    /// Returns a view of `node`, or `None` when it has not been inserted.
+    #[must_use]
+    pub fn node(&self, node: usize) -> Option<HnswNodeView<'_>> {
+        self.graph.node(node).map(|view| HnswNodeView {
+            id: node,
+            node: view,
+        })
+    }
+
+    /// Returns the number of levels `node` occupies, counting the base layer,
+    /// or `None` when it has not been inserted.
+    #[must_use]
+    pub fn levels(&self, node: usize) -> Option<usize> {
+        self.node(node).map(HnswNodeView::levels)
+    }
+
+    /// Returns the neighbours of `node` on `level`, or `None` when the node
+    /// has not been inserted or does not reach `level`.
+    #[must_use]
+    pub fn neighbours(&self, node: usize, level: usize) -> Option<&[usize]> {
+        let node = self.node(node)?;
+        (level < node.levels()).then(|| node.neighbours(level))
+    }
+
+    /// Iterates over the inserted nodes in identifier order.
+    #[must_use]
+    pub fn iter(&self) -> HnswNodes<'_> {
+        HnswNodes {
+            graph: &self.graph,
+            next: 0,
+        }
+    }
+
+    /// Iterates over every stored adjacency entry as
+    /// `(source, target, level)`. A link kept by both endpoints appears
+    /// once from each side.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize, usize)> + '_ {
+        self.iter().flat_map(|node| {
+            node.node
+                .iter_neighbours()
+                .map(move |(level, target)| (node.id, target, level))
+        })
+    }
+}
+
+impl<'view> IntoIterator for &'view HnswReadView<'_> {
+    type Item = HnswNodeView<'view>;
+    type IntoIter = HnswNodes<'view>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'view> HnswNodeView<'view> {
+    /// Returns the node's identifier.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn id(self) -> usize { self.id }
+
+    /// Returns the number of levels the node occupies, counting the base
+    /// layer.
+    #[must_use]
+    pub fn levels(self) -> usize {
+        self.node.level_count()
+    }
+
+    /// Returns the node's neighbours on `level`, which is empty when the
+    /// node does not reach `level`.
+    #[must_use]
+    pub fn neighbours(self, level: usize) -> &'view [usize] {
+        if level < self.levels() {
+            self.node.neighbours(level)
+        } else {
+            &[]
+        }
+    }
+}
+
+impl<'view> Iterator for HnswNodes<'view> {
+    type Item = HnswNodeView<'view>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next < self.graph.capacity() {
+            let id = self.next;
+            self.next += 1;
+            if let Some(node) = self.graph.node(id) {
+                return Some(HnswNodeView { id, node });
+            }
+        }
+        None
+    }
+}
@@ -8,7 +8,10 @@ use std::{
     collections::hash_map::DefaultHasher,
     hash::{Hash, Hasher},
     num::NonZeroUsize,
-    sync::Mutex,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
     time::{Duration, Instant},
 };
 
@@ -16,80 +19,12 @@ use dashmap::DashMap;
 use lru::LruCache;
 use tracing::instrument;
 
-use crate::{datasource::MetricDescriptor, hnsw::error::HnswError};
-
-/// Configuration parameters for the distance cache used by [`crate::CpuHnsw`].
-///
-/// # Examples
-/// ```
-/// use chutoro_core::DistanceCacheConfig;
-/// use std::num::NonZeroUsize;
-///
-/// let config = DistanceCacheConfig::new(NonZeroUsize::new(1024).unwrap())
-///     .with_ttl(None);
-/// assert_eq!(config.max_entries().get(), 1024);
-/// ```
-#[derive(Clone, Copy, Debug, PartialEq)]
-pub struct DistanceCacheConfig {
-    max_entries: NonZeroUsize,
-    ttl: Option<Duration>,
-}
-
-impl DistanceCacheConfig {
-    /// Default maximum number of cached distances retained before eviction.
-    pub const DEFAULT_MAX_ENTRIES: usize = 1_048_576;
-
-    /// Builds a configuration with the provided maximum capacity.
-    pub fn new(max_entries: NonZeroUsize) -> Self {
-        Self {
-            max_entries,
-            ttl: None,
-        }
-    }
-
-    /// Sets an optional time-to-live applied to cached entries.
-    #[must_use]
-    pub fn with_ttl(mut self, ttl: Option<Duration>) -> Self {
-        self.ttl = ttl;
-        self
-    }
-
-    /// Updates the maximum number of cached entries retained before eviction.
-    ///
-    /// # Examples
-    /// ```rust
-    /// use chutoro_core::DistanceCacheConfig;
-    /// use std::num::NonZeroUsize;
-    ///
-    /// let config = DistanceCacheConfig::default()
-    ///     .with_max_entries(NonZeroUsize::new(2).unwrap());
-    /// assert_eq!(config.max_entries().get(), 2);
-    /// ```
-    #[must_use]
-    pub fn with_max_entries(mut self, max: NonZeroUsize) -> Self {
-        self.max_entries = max;
-        self
-    }
-
-    /// Returns the maximum number of cached distances retained before eviction.
-    pub fn max_entries(&self) -> NonZeroUsize {
-        self.max_entries
-    }
-
-    /// Returns the configured time-to-live, if any.
-    pub fn ttl(&self) -> Option<Duration> {
-        self.ttl
-    }
-}
-
-impl Default for DistanceCacheConfig {
-    fn default() -> Self {
-        let Some(max_entries) = NonZeroUsize::new(Self::DEFAULT_MAX_ENTRIES) else {
-            unreachable!("default cache size must be non-zero");
-        };
-        Self::new(max_entries)
-    }
-}
+use crate::{
+    DistancePolicy,
+    datasource::MetricDescriptor,
+    distance_policy::DistanceGuard,
+    hnsw::{cache_config::DistanceCacheConfig, error::HnswError},
+};
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 struct DistanceKey {
@@ -150,6 +85,8 @@ pub(crate) struct DistanceCache {
     entries: DashMap<DistanceKey, CacheEntry>,
     shards: Vec<LruShard>,
     config: DistanceCacheConfig,
+    evictions: AtomicU64,
+    guard: Arc<DistanceGuard>,
 }
 
 impl DistanceCache {
@@ -173,12 +110,62 @@ impl DistanceCache {
     pub(crate) fn new(config: DistanceCacheConfig) -> Self {
         let capacity = config.max_entries();
         let cap_usize = capacity.get();
-        let shard_capacities = lru_shard_capacities(cap_usize);
+        let shard_capacities = lru_shard_capacities(cap_usize, config.shards());
         let shards = shard_capacities.into_iter().map(LruShard::new).collect();
         Self {
             entries: DashMap::with_capacity(cap_usize),
             shards,
             config,
+            evictions: AtomicU64::new(0),
+            guard: Arc::default(),
+        }
+    }
+
+    /// Applies `policy` to the non-finite distances validated through this
+    /// cache.
+    pub(crate) fn with_distance_policy(mut self, policy: DistancePolicy) -> Self {
+        self.guard = Arc::new(DistanceGuard::new(policy));
+        self
+    }
+
+    /// Shares the policy guard of `other`, so both caches report into the
+    /// same counts.
+    pub(crate) fn with_guard_of(mut self, other: &Self) -> Self {
+        self.guard = Arc::clone(&other.guard);
+        self
+    }
+
+    /// Returns the guard applying the index's [`DistancePolicy`].
+    pub(crate) fn guard(&self) -> &DistanceGuard {
+        &self.guard
+    }
+
+    /// Returns the number of cached distances.
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the configured maximum number of cached distances.
+    pub(crate) fn capacity(&self) -> usize {
+        self.config.max_entries().get()
+    }
+
+    /// Returns how many entries were evicted for capacity or expiry since
+    /// the cache was built.
+    pub(crate) fn evictions(&self) -> u64 {
+        self.evictions.load(Ordering::Relaxed)
+    }
+
+    /// Copies the unexpired entries of `other` into this cache, evicting as
+    /// usual once this cache's capacity is reached.
+    pub(crate) fn seed_from(&self, other: &Self) {
+        for entry in &other.entries {
+            if other.is_expired(entry.value()) {
+                continue;
+            }
+            self.entries
+                .insert(entry.key().clone(), entry.value().clone());
+            self.touch(entry.key());
         }
     }
 
@@ -262,7 +249,11 @@ impl DistanceCache {
             .usage
             .lock()
             .unwrap_or_else(std::sync::PoisonError::into_inner);
-        if let Some((evicted, _)) = usage.push(key.clone(), ()) {
+        // `push` also returns the previous pair when the key was already
+        // tracked; only a different key means something was evicted.
+        if let Some((evicted, _)) = usage.push(key.clone(), ())
+            && evicted != *key
+        {
             self.entries.remove(&evicted);
             self.record_eviction();
         }
@@ -333,14 +324,12 @@ impl DistanceCache {
     #[cfg(not(feature = "metrics"))]
     fn record_miss(&self) {}
 
-    #[cfg(feature = "metrics")]
     fn record_eviction(&self) {
+        self.evictions.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
         metrics::counter!("distance_cache_evictions").increment(1);
     }
 
-    #[cfg(not(feature = "metrics"))]
-    fn record_eviction(&self) {}
-
     #[cfg(feature = "metrics")]
     fn record_lookup_latency(&self, elapsed: Duration) {
         metrics::histogram!("distance_cache_lookup_latency_histogram")
@@ -353,11 +342,20 @@ impl DistanceCache {
 
 // no inherent methods on PendingMiss
 
-fn lru_shard_capacities(total_capacity: usize) -> Vec<NonZeroUsize> {
+fn lru_shard_capacities(
+    total_capacity: usize,
+    requested: Option<NonZeroUsize>,
+) -> Vec<NonZeroUsize> {
     debug_assert!(total_capacity > 0, "total capacity must be non-zero");
-    let desired_shards = total_capacity.div_ceil(TARGET_LRU_ENTRIES_PER_SHARD);
-    let shard_count = desired_shards
-        .clamp(1, DEFAULT_LRU_SHARDS)
+    let shard_count = requested
+        .map_or_else(
+            || {
+                total_capacity
+                    .div_ceil(TARGET_LRU_ENTRIES_PER_SHARD)
+                    .clamp(1, DEFAULT_LRU_SHARDS)
+            },
+            NonZeroUsize::get,
+        )
         .min(total_capacity);
     let base = total_capacity / shard_count;
     let remainder = total_capacity % shard_count;
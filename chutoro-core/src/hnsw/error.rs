@@ -70,6 +70,11 @@ impl HnswError {
 
 /// Machine-readable error codes for [`HnswError`].
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "SCREAMING_SNAKE_CASE")
+)]
 pub enum HnswErrorCode {
     /// Construction was attempted on an empty data source.
     EmptyBuild,
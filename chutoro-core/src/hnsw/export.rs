@@ -0,0 +1,125 @@
+//! Text exports of a [`crate::CpuHnsw`] graph for visualisation tools.
+//!
+//! Recall problems are easier to diagnose when the layers can be seen:
+//! GraphML loads into Gephi or yEd, DOT into Graphviz, and the CSV edge list
+//! into anything that reads tables. Every format lists the stored adjacency
+//! entries as directed edges tagged with their layer, so a link kept by both
+//! endpoints appears twice, matching [`crate::HnswStatistics`].
+
+use std::io::{self, BufWriter, Write};
+
+use thiserror::Error;
+
+use super::{error::HnswError, graph::Graph};
+
+/// File formats accepted by [`crate::CpuHnsw::export_graph`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GraphFormat {
+    /// GraphML with `level` and `entry` node attributes and a `layer` edge
+    /// attribute, for Gephi, yEd, or NetworkX.
+    GraphMl,
+    /// A Graphviz `digraph` carrying the same attributes; the entry point is
+    /// drawn as a double circle.
+    Dot,
+    /// CSV rows of `source,target,layer` under a header. Node levels are not
+    /// included.
+    EdgeListCsv,
+}
+
+/// Errors raised by [`crate::CpuHnsw::export_graph`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum GraphExportError {
+    /// The graph could not be read.
+    #[error(transparent)]
+    Hnsw(#[from] HnswError),
+    /// Writing the export failed.
+    #[error("failed to write HNSW graph: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// Writes `graph` to `writer` in `format`, nodes in identifier order.
+pub(crate) fn write_graph(
+    graph: &Graph,
+    writer: impl Write,
+    format: GraphFormat,
+) -> io::Result<()> {
+    let mut writer = BufWriter::new(writer);
+    match format {
+        GraphFormat::GraphMl => write_graphml(graph, &mut writer)?,
+        GraphFormat::Dot => write_dot(graph, &mut writer)?,
+        GraphFormat::EdgeListCsv => write_edge_list(graph, &mut writer)?,
+    }
+    writer.flush()
+}
+
+/// Yields `(source, target, layer)` for every stored adjacency entry.
+fn edges(graph: &Graph) -> impl Iterator<Item = (usize, usize, usize)> + '_ {
+    graph.nodes_iter().flat_map(|(source, node)| {
+        node.iter_neighbours()
+            .map(move |(layer, target)| (source, target, layer))
+    })
+}
+
+/// Yields `(node, level, is_entry)` for every inserted node.
+fn nodes(graph: &Graph) -> impl Iterator<Item = (usize, usize, bool)> + '_ {
+    let entry = graph.entry().map(|entry| entry.node);
+    graph
+        .nodes_iter()
+        .map(move |(id, node)| (id, node.level_count() - 1, Some(id) == entry))
+}
+
+fn write_graphml(graph: &Graph, writer: &mut impl Write) -> io::Result<()> {
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#
+    )?;
+    writeln!(
+        writer,
+        r#"  <key id="level" for="node" attr.name="level" attr.type="int"/>"#
+    )?;
+    writeln!(
+        writer,
+        r#"  <key id="entry" for="node" attr.name="entry" attr.type="boolean"/>"#
+    )?;
+    writeln!(
+        writer,
+        r#"  <key id="layer" for="edge" attr.name="layer" attr.type="int"/>"#
+    )?;
+    writeln!(writer, r#"  <graph id="hnsw" edgedefault="directed">"#)?;
+    for (id, level, entry) in nodes(graph) {
+        writeln!(
+            writer,
+            r#"    <node id="n{id}"><data key="level">{level}</data><data key="entry">{entry}</data></node>"#
+        )?;
+    }
+    for (source, target, layer) in edges(graph) {
+        writeln!(
+            writer,
+            r#"    <edge source="n{source}" target="n{target}"><data key="layer">{layer}</data></edge>"#
+        )?;
+    }
+    writeln!(writer, "  </graph>")?;
+    writeln!(writer, "</graphml>")
+}
+
+fn write_dot(graph: &Graph, writer: &mut impl Write) -> io::Result<()> {
+    writeln!(writer, "digraph hnsw {{")?;
+    for (id, level, entry) in nodes(graph) {
+        let shape = if entry { ", shape=doublecircle" } else { "" };
+        writeln!(writer, "  {id} [level={level}, entry={entry}{shape}];")?;
+    }
+    for (source, target, layer) in edges(graph) {
+        writeln!(writer, "  {source} -> {target} [layer={layer}];")?;
+    }
+    writeln!(writer, "}}")
+}
+
+fn write_edge_list(graph: &Graph, writer: &mut impl Write) -> io::Result<()> {
+    writeln!(writer, "source,target,layer")?;
+    for (source, target, layer) in edges(graph) {
+        writeln!(writer, "{source},{target},{layer}")?;
+    }
+    Ok(())
+}
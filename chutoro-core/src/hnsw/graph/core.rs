@@ -3,8 +3,8 @@
 use crate::hnsw::{
     error::HnswError,
     insert::{InsertionExecutor, InsertionPlanner},
-    node::Node,
-    params::HnswParams,
+    node::{NodeMut, NodeRef, NodeStore},
+    params::{ConnectionLimits, HnswParams},
     search::LayerSearcher,
     types::{EntryPoint, InsertionPlan},
 };
@@ -31,8 +31,8 @@ pub(crate) struct NodeContext {
 pub(crate) struct EdgeContext {
     /// Layer level for the edge operation.
     pub(crate) level: usize,
-    /// Maximum number of connections permitted at this level.
-    pub(crate) max_connections: usize,
+    /// Per-layer connection limits; the bound for `level` applies.
+    pub(crate) limits: ConnectionLimits,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -56,23 +56,29 @@ impl DescentContext {
 }
 
 #[derive(Clone, Copy, Debug)]
-pub(crate) struct LayerPlanContext {
+pub(crate) struct LayerPlanContext<'a> {
     pub(crate) query: usize,
     pub(crate) target_level: usize,
     pub(crate) current: usize,
-    pub(crate) ef: usize,
+    /// Supplies the construction search width for each planned layer.
+    pub(crate) params: &'a HnswParams,
 }
 
-impl LayerPlanContext {
+impl<'a> LayerPlanContext<'a> {
     /// Construct a layer-planning context.
     #[must_use]
     #[inline]
-    pub(crate) fn new(query: usize, current: usize, target_level: usize, ef: usize) -> Self {
+    pub(crate) fn new(
+        query: usize,
+        current: usize,
+        target_level: usize,
+        params: &'a HnswParams,
+    ) -> Self {
         Self {
             query,
             target_level,
             current,
-            ef,
+            params,
         }
     }
 }
@@ -166,7 +172,7 @@ impl NeighbourSearchContext {
 #[derive(Clone, Debug)]
 pub(crate) struct Graph {
     pub(super) params: HnswParams,
-    pub(super) nodes: Vec<Option<Node>>,
+    pub(super) nodes: NodeStore,
     pub(super) entry: Option<EntryPoint>,
 }
 
@@ -179,9 +185,10 @@ impl Graph {
     #[inline]
     pub(crate) fn with_capacity(params: HnswParams, capacity: usize) -> Self {
         debug_assert!(capacity > 0, "capacity must be greater than zero");
+        let nodes = NodeStore::new(&params, capacity);
         Self {
             params,
-            nodes: vec![None; capacity],
+            nodes,
             entry: None,
         }
     }
@@ -201,7 +208,7 @@ impl Graph {
     /// ```
     #[must_use]
     pub(crate) fn capacity(&self) -> usize {
-        self.nodes.len()
+        self.nodes.capacity()
     }
 
     /// Iterates over all inserted nodes along with their identifiers.
@@ -216,11 +223,8 @@ impl Graph {
     /// let ids: Vec<_> = graph.nodes_iter().map(|(id, _)| id).collect();
     /// assert_eq!(ids, vec![0, 1]);
     /// ```
-    pub(crate) fn nodes_iter(&self) -> impl Iterator<Item = (usize, &Node)> {
-        self.nodes
-            .iter()
-            .enumerate()
-            .filter_map(|(id, node)| node.as_ref().map(|node_ref| (id, node_ref)))
+    pub(crate) fn nodes_iter(&self) -> impl Iterator<Item = (usize, NodeRef<'_>)> {
+        (0..self.nodes.capacity()).filter_map(|id| self.nodes.get(id).map(|node| (id, node)))
     }
 
     pub(crate) fn insert_first(&mut self, ctx: NodeContext) -> Result<(), HnswError> {
@@ -243,16 +247,15 @@ impl Graph {
                 ),
             });
         }
-        let slot = self
-            .nodes
-            .get_mut(ctx.node)
-            .ok_or_else(|| HnswError::InvalidParameters {
+        if !self.has_slot(ctx.node) {
+            return Err(HnswError::InvalidParameters {
                 reason: format!("node {} is outside pre-allocated capacity", ctx.node),
-            })?;
-        if slot.is_some() {
+            });
+        }
+        if self.nodes.get(ctx.node).is_some() {
             return Err(HnswError::DuplicateNode { node: ctx.node });
         }
-        *slot = Some(Node::new(ctx.level, ctx.sequence));
+        self.nodes.insert(ctx.node, ctx.level, ctx.sequence);
         Ok(())
     }
 
@@ -267,12 +270,12 @@ impl Graph {
         }
     }
 
-    pub(crate) fn node(&self, id: usize) -> Option<&Node> {
-        self.nodes.get(id).and_then(Option::as_ref)
+    pub(crate) fn node(&self, id: usize) -> Option<NodeRef<'_>> {
+        self.nodes.get(id)
     }
 
-    pub(crate) fn node_mut(&mut self, id: usize) -> Option<&mut Node> {
-        self.nodes.get_mut(id).and_then(Option::as_mut)
+    pub(crate) fn node_mut(&mut self, id: usize) -> Option<NodeMut<'_>> {
+        self.nodes.get_mut(id)
     }
 
     /// Retrieves the insertion sequence assigned to a node for deterministic
@@ -301,11 +304,11 @@ impl Graph {
     /// assert_eq!(graph.node_sequence(1), None);
     /// ```
     pub(crate) fn node_sequence(&self, id: usize) -> Option<u64> {
-        self.node(id).map(Node::sequence)
+        self.node(id).map(NodeRef::sequence)
     }
 
     pub(crate) fn has_slot(&self, node: usize) -> bool {
-        self.nodes.get(node).is_some()
+        node < self.nodes.capacity()
     }
 
     #[inline]
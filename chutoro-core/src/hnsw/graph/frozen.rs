@@ -0,0 +1,65 @@
+//! Read-only snapshot of the graph in a compact layout for search.
+
+use crate::hnsw::{
+    node::{FrozenNodes, NodeRef},
+    search::LayerSearcher,
+    types::EntryPoint,
+};
+
+use super::Graph;
+
+/// Immutable copy of a [`Graph`] whose neighbour lists are packed into
+/// compressed sparse row arrays.
+///
+/// Searches walk the same lists in the same order as on the live graph, so
+/// they return the same neighbours, but a node's lists are contiguous with
+/// its neighbours' rather than scattered across the heap.
+#[derive(Clone, Debug)]
+pub(crate) struct FrozenGraph {
+    nodes: FrozenNodes,
+    entry: Option<EntryPoint>,
+}
+
+impl Graph {
+    /// Copies the graph into a [`FrozenGraph`]; later edits to the graph do
+    /// not reach the copy.
+    ///
+    /// # Examples
+    /// ```rust,ignore
+    /// use crate::hnsw::{graph::{Graph, NodeContext}, params::HnswParams};
+    ///
+    /// let params = HnswParams::new(2, 4).expect("params must be valid");
+    /// let mut graph = Graph::with_capacity(params, 2);
+    /// graph
+    ///     .insert_first(NodeContext { node: 0, level: 1, sequence: 3 })
+    ///     .expect("insert first node");
+    /// let frozen = graph.freeze();
+    /// assert_eq!(frozen.node_sequence(0), Some(3));
+    /// assert_eq!(frozen.node(0).expect("node 0").level_count(), 2);
+    /// ```
+    pub(crate) fn freeze(&self) -> FrozenGraph {
+        FrozenGraph {
+            nodes: self.nodes.freeze(),
+            entry: self.entry,
+        }
+    }
+}
+
+impl FrozenGraph {
+    pub(crate) fn entry(&self) -> Option<EntryPoint> {
+        self.entry
+    }
+
+    pub(crate) fn node(&self, id: usize) -> Option<NodeRef<'_>> {
+        self.nodes.get(id)
+    }
+
+    pub(crate) fn node_sequence(&self, id: usize) -> Option<u64> {
+        self.nodes.sequence(id)
+    }
+
+    #[inline]
+    pub(crate) fn searcher(&self) -> LayerSearcher<'_> {
+        LayerSearcher::frozen(self)
+    }
+}
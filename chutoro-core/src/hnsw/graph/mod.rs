@@ -1,7 +1,9 @@
 //! Internal graph representation for the CPU HNSW implementation.
 
 mod core;
+mod frozen;
 #[cfg(test)]
 mod test_helpers;
 
 pub(crate) use core::*;
+pub(crate) use frozen::FrozenGraph;
@@ -10,7 +10,7 @@ use std::collections::{HashSet, VecDeque};
 use super::core::Graph;
 use crate::hnsw::{
     error::HnswError,
-    node::Node,
+    node::NodeRef,
     params::{self, HnswParams},
     types::EntryPoint,
 };
@@ -25,17 +25,17 @@ impl Graph {
 
     pub(crate) fn delete_node(&mut self, node: usize) -> Result<bool, HnswError> {
         self.validate_delete_target(node)?;
-        let Some(existing) = self.nodes.get(node).and_then(Option::as_ref) else {
+        let Some(existing) = self.node(node) else {
             return Ok(false);
         };
 
+        let removed_neighbours = collect_neighbour_layers(existing);
         let snapshot_nodes = self.nodes.clone();
         let snapshot_entry = self.entry;
-        let removed_neighbours = collect_neighbour_layers(existing);
 
-        let Some(_taken) = self.nodes.get_mut(node).and_then(Option::take) else {
+        if !self.nodes.remove(node) {
             unreachable!("node presence checked above");
-        };
+        }
 
         self.strip_references_to(node);
         self.reconnect_layers(removed_neighbours);
@@ -96,7 +96,7 @@ impl Graph {
             return false;
         }
 
-        self.nodes.get(neighbour).and_then(Option::as_ref).is_some()
+        self.node(neighbour).is_some()
     }
 
     /// Connects consecutive pairs of neighbours bidirectionally at the given level.
@@ -126,15 +126,15 @@ impl Graph {
     }
 
     pub(super) fn try_add_edge(&mut self, origin: usize, target: usize, level: usize) -> bool {
-        let limit = params::connection_limit_for_level(level, self.params.max_connections());
-        let Some(node) = self.nodes.get_mut(origin).and_then(Option::as_mut) else {
+        let limit = params::connection_limit_for_level(level, self.params.connection_limits());
+        let Some(mut node) = self.node_mut(origin) else {
             return false;
         };
         if level >= node.level_count() {
             return false;
         }
 
-        let neighbours = node.neighbours_mut(level);
+        let mut neighbours = node.neighbours_mut(level);
         if neighbours.contains(&target) {
             return true;
         }
@@ -148,34 +148,37 @@ impl Graph {
     }
 
     pub(super) fn remove_edge(&mut self, origin: usize, target: usize, level: usize) {
-        let Some(node) = self.nodes.get_mut(origin).and_then(Option::as_mut) else {
+        let Some(mut node) = self.node_mut(origin) else {
             return;
         };
         if level >= node.level_count() {
             return;
         }
 
-        let neighbours = node.neighbours_mut(level);
+        let mut neighbours = node.neighbours_mut(level);
         if let Some(pos) = neighbours.iter().position(|&candidate| candidate == target) {
             neighbours.remove(pos);
         }
     }
 
     fn validate_delete_target(&self, node: usize) -> Result<(), HnswError> {
-        if node >= self.nodes.len() {
+        if !self.has_slot(node) {
             return Err(HnswError::InvalidParameters {
-                reason: format!("node {node} exceeds graph capacity {}", self.nodes.len()),
+                reason: format!("node {node} exceeds graph capacity {}", self.capacity()),
             });
         }
         Ok(())
     }
 
     fn strip_references_to(&mut self, node: usize) {
-        for maybe_node in self.nodes.iter_mut().flatten() {
-            let levels = maybe_node.level_count();
-            for level in 0..levels {
-                let neighbours = maybe_node.neighbours_mut(level);
-                neighbours.retain(|&target| target != node);
+        for id in 0..self.capacity() {
+            let Some(mut existing) = self.node_mut(id) else {
+                continue;
+            };
+            for level in 0..existing.level_count() {
+                existing
+                    .neighbours_mut(level)
+                    .retain(|&target| target != node);
             }
         }
     }
@@ -197,13 +200,11 @@ impl Graph {
                 message: "entry point missing after delete".into(),
             })?;
 
-        let mut state = ReachabilityState::new(self.nodes.len(), entry.node);
+        let mut state = ReachabilityState::new(self.capacity(), entry.node);
 
         while let Some(node_id) = state.queue.pop_front() {
             let node = self
-                .nodes
-                .get(node_id)
-                .and_then(Option::as_ref)
+                .node(node_id)
                 .ok_or_else(|| HnswError::GraphInvariantViolation {
                     message: format!("node {node_id} missing during reachability walk"),
                 })?;
@@ -235,12 +236,12 @@ impl Graph {
         target: usize,
         state: &mut ReachabilityState,
     ) -> Result<(), HnswError> {
-        if target >= self.nodes.len() {
+        if !self.has_slot(target) {
             return Err(HnswError::GraphInvariantViolation {
                 message: format!("node {origin} references out-of-bounds neighbour {target}"),
             });
         }
-        if self.nodes.get(target).and_then(Option::as_ref).is_none() {
+        if self.node(target).is_none() {
             return Err(HnswError::GraphInvariantViolation {
                 message: format!("node {origin} references missing neighbour {target}"),
             });
@@ -277,7 +278,7 @@ impl ReachabilityState {
     }
 }
 
-fn collect_neighbour_layers(removed: &Node) -> Vec<Vec<usize>> {
+fn collect_neighbour_layers(removed: NodeRef<'_>) -> Vec<Vec<usize>> {
     (0..removed.level_count())
         .map(|level| removed.neighbours(level).to_vec())
         .collect()
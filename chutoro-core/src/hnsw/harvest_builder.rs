@@ -0,0 +1,189 @@
+//! Concurrent construction of [`EdgeHarvest`] values from external indexes.
+//!
+//! Callers that discover candidate edges with their own structures, such as
+//! IVF lists or a precomputed k-NN graph, can feed them to
+//! [`crate::Chutoro::cluster_from_knn_graph`] without assigning sequence
+//! numbers by hand. Each producer thread takes an [`EdgeHarvestProducer`]
+//! that buffers its edges locally and hands them to one of a fixed set of
+//! sharded buffers a batch at a time, so producers touch shared state once
+//! per batch rather than once per edge.
+
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        Mutex, PoisonError,
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+    },
+    thread,
+};
+
+use super::candidate::{CandidateEdge, EdgeHarvest};
+
+/// Number of edges a producer buffers before handing them to its shard.
+const PRODUCER_BATCH: usize = 1024;
+
+/// Collects candidate edges from any number of threads into an
+/// [`EdgeHarvest`].
+///
+/// Give each producing thread its own handle from [`Self::producer`].
+/// Producers are assigned shards in turn, and each flush reserves a
+/// contiguous block of sequence numbers, so the finished harvest has unique
+/// sequences and every producer's edges keep the order they were added in.
+/// How the blocks of different producers interleave depends on scheduling,
+/// so adding the same edges again may number them differently; the MST
+/// stage breaks distance ties by endpoints before sequence, so clusterings
+/// built from the same set of edges agree.
+///
+/// # Examples
+/// ```
+/// use std::thread;
+///
+/// use chutoro_core::EdgeHarvestBuilder;
+///
+/// let builder = EdgeHarvestBuilder::new();
+/// thread::scope(|scope| {
+///     for offset in [0, 10] {
+///         let builder = &builder;
+///         scope.spawn(move || {
+///             let mut producer = builder.producer();
+///             for point in offset..offset + 9 {
+///                 producer.add_edge(point, point + 1, 1.0);
+///             }
+///         });
+///     }
+/// });
+///
+/// let harvest = builder.finish();
+/// assert_eq!(harvest.len(), 18);
+/// ```
+#[derive(Debug)]
+pub struct EdgeHarvestBuilder {
+    shards: Box<[Mutex<Vec<CandidateEdge>>]>,
+    next_sequence: AtomicU64,
+    next_shard: AtomicUsize,
+}
+
+impl EdgeHarvestBuilder {
+    /// Creates a builder with one shard per available core.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_shards(thread::available_parallelism().unwrap_or(NonZeroUsize::MIN))
+    }
+
+    /// Creates a builder that spreads edges across `shards` buffers.
+    #[must_use]
+    pub fn with_shards(shards: NonZeroUsize) -> Self {
+        Self {
+            shards: (0..shards.get()).map(|_| Mutex::new(Vec::new())).collect(),
+            next_sequence: AtomicU64::new(0),
+            next_shard: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns a handle that buffers one producer's edges and flushes them
+    /// to the next shard in turn.
+    #[must_use]
+    pub fn producer(&self) -> EdgeHarvestProducer<'_> {
+        let shard = self.next_shard.fetch_add(1, Ordering::Relaxed) % self.shards.len();
+        EdgeHarvestProducer {
+            builder: self,
+            shard,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Returns the number of edges producers have flushed so far.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.next_sequence.load(Ordering::Relaxed) as usize
+    }
+
+    /// Returns whether no edges have been flushed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Merges the shards into a harvest ordered by sequence.
+    ///
+    /// Producers borrow the builder, so every producer has been dropped, and
+    /// has flushed its edges, by the time this is called.
+    #[must_use]
+    pub fn finish(self) -> EdgeHarvest {
+        let edges = self
+            .shards
+            .into_vec()
+            .into_iter()
+            .flat_map(|shard| shard.into_inner().unwrap_or_else(PoisonError::into_inner))
+            .collect();
+        EdgeHarvest::from_unsorted(edges)
+    }
+
+    /// Numbers `pending` with the next block of sequences and moves it into
+    /// `shard`.
+    fn push_batch(&self, shard: usize, pending: &mut Vec<(usize, usize, f64)>) {
+        let first = self
+            .next_sequence
+            .fetch_add(pending.len() as u64, Ordering::Relaxed);
+        let edges = pending
+            .drain(..)
+            .zip(first..)
+            .map(|((source, target, distance), sequence)| {
+                CandidateEdge::new_f64(source, target, distance, sequence)
+            });
+        // A producer that panicked mid-push leaves a complete vector behind.
+        self.shards[shard]
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .extend(edges);
+    }
+}
+
+impl Default for EdgeHarvestBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One producer's handle on an [`EdgeHarvestBuilder`].
+///
+/// Edges are buffered until 1024 accumulate, [`Self::flush`] is called, or
+/// the producer is dropped, and are numbered when they are flushed.
+#[derive(Debug)]
+pub struct EdgeHarvestProducer<'a> {
+    builder: &'a EdgeHarvestBuilder,
+    shard: usize,
+    pending: Vec<(usize, usize, f64)>,
+}
+
+impl EdgeHarvestProducer<'_> {
+    /// Records an edge from `source` to `target`.
+    ///
+    /// Edges are validated when the harvest is clustered, so non-finite or
+    /// negative distances and out-of-range endpoints are reported there.
+    pub fn add_edge(&mut self, source: usize, target: usize, distance: f32) {
+        self.add_edge_f64(source, target, f64::from(distance));
+    }
+
+    /// Records an edge with a double-precision distance, which the spanning
+    /// tree keeps when the run opts into [`crate::DistancePrecision::Double`].
+    pub fn add_edge_f64(&mut self, source: usize, target: usize, distance: f64) {
+        self.pending.push((source, target, distance));
+        if self.pending.len() >= PRODUCER_BATCH {
+            self.flush();
+        }
+    }
+
+    /// Hands the buffered edges to the builder.
+    pub fn flush(&mut self) {
+        if !self.pending.is_empty() {
+            self.builder.push_batch(self.shard, &mut self.pending);
+        }
+    }
+}
+
+impl Drop for EdgeHarvestProducer<'_> {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
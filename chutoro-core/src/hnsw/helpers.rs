@@ -10,7 +10,7 @@ use super::{
     distance_cache::{DistanceCache, LookupOutcome},
     error::HnswError,
     types::Neighbour,
-    validate::{validate_batch_distances, validate_distance},
+    validate::{admit_distance, validate_distance},
 };
 
 /// Bundles the context required to ensure a search result includes the query
@@ -148,7 +148,7 @@ pub(crate) fn batch_distances_for_trim<D: DataSource + Sync>(
         return Ok(distances);
     }
 
-    let miss_distances = validate_batch_distances(None, source, node, &miss_candidates)?;
+    let miss_distances = source.batch_distances(node, &miss_candidates)?;
     if miss_distances.len() != miss_candidates.len() {
         return Err(HnswError::InvalidParameters {
             reason: format!(
@@ -159,9 +159,10 @@ pub(crate) fn batch_distances_for_trim<D: DataSource + Sync>(
         });
     }
 
-    for ((index, miss), distance) in miss_meta.into_iter().zip(miss_distances.into_iter()) {
-        cache.complete_miss(miss, distance)?;
-        distances[index] = distance;
+    let misses = miss_meta.into_iter().zip(miss_candidates);
+    for (((index, miss), candidate), distance) in misses.zip(miss_distances) {
+        let distance = admit_distance(Some(cache), node, candidate, distance)?;
+        distances[index] = cache.complete_miss(miss, distance)?;
     }
 
     Ok(distances)
@@ -5,7 +5,7 @@
 //! the insertion. Reconciliation of forward and reverse edges is delegated to
 //! [`EdgeReconciler`] to keep responsibilities focused.
 
-use crate::hnsw::{error::HnswError, graph::Graph};
+use crate::hnsw::{error::HnswError, graph::Graph, node::NodeRef, params::ConnectionLimits};
 
 use super::{
     reconciliation::EdgeReconciler,
@@ -36,7 +36,7 @@ impl<'graph> CommitApplicator<'graph> {
         node_level: usize,
         existing_nodes_with_new_node: Vec<Vec<usize>>,
     ) -> Result<(), HnswError> {
-        let Some(node_ref) = self.graph.node_mut(node_id) else {
+        let Some(mut node_ref) = self.graph.node_mut(node_id) else {
             return Err(HnswError::GraphInvariantViolation {
                 message: format!("node {node_id} missing after attach during commit"),
             });
@@ -46,7 +46,7 @@ impl<'graph> CommitApplicator<'graph> {
             .enumerate()
             .take(node_level + 1)
         {
-            let list = node_ref.neighbours_mut(level);
+            let mut list = node_ref.neighbours_mut(level);
             list.clear();
             list.extend(neighbours);
         }
@@ -58,7 +58,7 @@ impl<'graph> CommitApplicator<'graph> {
     pub(super) fn apply_neighbour_updates(
         &mut self,
         final_updates: Vec<FinalisedUpdate>,
-        max_connections: usize,
+        limits: ConnectionLimits,
         new_node: NewNodeContext,
     ) -> Result<ApplyUpdatesOutcome, HnswError> {
         let mut touched: Vec<(usize, usize)> = Vec::with_capacity(final_updates.len());
@@ -78,19 +78,19 @@ impl<'graph> CommitApplicator<'graph> {
             let ctx = UpdateContext {
                 origin: update.node,
                 level,
-                max_connections,
+                limits,
             };
 
             reconciler.reconcile_removed_edges(&ctx, &previous, &next);
             reconciler.reconcile_added_edges(&ctx, &mut next);
 
-            let node_ref = reconciler
+            let mut node_ref = reconciler
                 .graph_mut()
                 .node_mut(update.node)
                 .ok_or_else(|| HnswError::GraphInvariantViolation {
                     message: format!("node {} missing during insertion commit", update.node),
                 })?;
-            let list = node_ref.neighbours_mut(level);
+            let mut list = node_ref.neighbours_mut(level);
             list.clear();
             list.extend(next);
 
@@ -100,7 +100,7 @@ impl<'graph> CommitApplicator<'graph> {
         // Apply deferred scrubs now that all updates have written their edges.
         // This filters out scrubs that would remove edges just added by other
         // updates in the same batch.
-        reconciler.apply_deferred_scrubs(max_connections);
+        reconciler.apply_deferred_scrubs(limits);
 
         // Compute which existing nodes have the new node in their final
         // neighbour lists. This is done AFTER scrubs are applied to ensure
@@ -133,7 +133,7 @@ impl<'graph> CommitApplicator<'graph> {
     )]
     fn collect_edges_to_new_node(
         node_id: usize,
-        node: &crate::hnsw::node::Node,
+        node: NodeRef<'_>,
         new_node: &NewNodeContext,
         reciprocated: &mut [Vec<usize>],
     ) {
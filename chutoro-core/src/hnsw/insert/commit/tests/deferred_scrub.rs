@@ -24,7 +24,7 @@ use super::*;
 fn benign_deferred_scrub_is_noop_when_edge_already_removed(
     params_one_connection: HnswParams,
 ) -> Result<(), HnswError> {
-    let max_connections = params_one_connection.max_connections();
+    let limits = params_one_connection.connection_limits();
     let mut graph = Graph::with_capacity(params_one_connection, 5);
 
     // Insert 5 nodes at level 1
@@ -40,17 +40,17 @@ fn benign_deferred_scrub_is_noop_when_edge_already_removed(
 
     // Update 1: node 0 adds node 1 as neighbour.
     // This evicts node 2 from node 1's list and queues a deferred scrub for 2→1.
-    let update1 = build_update(0, 1, vec![1], max_connections);
+    let update1 = build_update(0, 1, vec![1], limits);
 
     // Update 2: node 2 replaces node 1 with node 3 in its neighbour list.
     // This removes edge 2→1 before the deferred scrub runs.
-    let update2 = build_update(2, 1, vec![3], max_connections);
+    let update2 = build_update(2, 1, vec![3], limits);
 
     let new_node = NewNodeContext { id: 4, level: 1 };
 
     let mut applicator = CommitApplicator::new(&mut graph);
     let (reciprocated, _) =
-        applicator.apply_neighbour_updates(vec![update1, update2], max_connections, new_node)?;
+        applicator.apply_neighbour_updates(vec![update1, update2], limits, new_node)?;
     applicator.apply_new_node_neighbours(new_node.id, new_node.level, reciprocated)?;
 
     // The deferred scrub for 2→1 should be a no-op since update2 already
@@ -85,8 +85,8 @@ fn eviction_skips_scrub_if_reciprocity_restored(
 
     // First update: node 0 adds node 1 (evicts node 2 from node 1)
     // Second update: node 2 re-adds node 1 (restores the reciprocal edge)
-    let update1 = build_update(0, 1, vec![1], ctx.max_connections);
-    let update2 = build_update(2, 1, vec![1], ctx.max_connections);
+    let update1 = build_update(0, 1, vec![1], ctx.limits);
+    let update2 = build_update(2, 1, vec![1], ctx.limits);
     let graph = ctx.apply_updates(vec![update1, update2])?;
 
     // Node 2 should still have its forward edge to node 1 (scrub was skipped)
@@ -102,7 +102,7 @@ fn eviction_skips_scrub_if_reciprocity_restored(
 /// eviction. Both orphaned forward edges should be scrubbed.
 #[rstest]
 fn multiple_evictions_in_batch_update(params_one_connection: HnswParams) -> Result<(), HnswError> {
-    let max_connections = params_one_connection.max_connections();
+    let limits = params_one_connection.connection_limits();
     let mut graph = Graph::with_capacity(params_one_connection, 7);
 
     insert_node(&mut graph, 0, 1, 0)?;
@@ -124,13 +124,13 @@ fn multiple_evictions_in_batch_update(params_one_connection: HnswParams) -> Resu
     // First update: node 0 adds node 1 (evicts node 2 from node 1)
     // Second update: node 5 adds node 3 (evicts node 4 from node 3)
     // Using different origin nodes so each can succeed independently
-    let update1 = build_update(0, 1, vec![1], max_connections);
-    let update2 = build_update(5, 1, vec![3], max_connections);
+    let update1 = build_update(0, 1, vec![1], limits);
+    let update2 = build_update(5, 1, vec![3], limits);
     let new_node = NewNodeContext { id: 6, level: 1 };
 
     let mut applicator = CommitApplicator::new(&mut graph);
     let (reciprocated, _) =
-        applicator.apply_neighbour_updates(vec![update1, update2], max_connections, new_node)?;
+        applicator.apply_neighbour_updates(vec![update1, update2], limits, new_node)?;
     applicator.apply_new_node_neighbours(new_node.id, new_node.level, reciprocated)?;
 
     // Both evicted nodes' forward edges should be scrubbed
@@ -153,7 +153,7 @@ fn multiple_evictions_in_batch_update(params_one_connection: HnswParams) -> Resu
 fn eviction_respects_furthest_first_ordering() -> Result<(), HnswError> {
     // Use max_connections = 2 so level-1 capacity is 2
     let params = HnswParams::new(2, 4)?;
-    let max_connections = params.max_connections();
+    let limits = params.connection_limits();
     let mut graph = Graph::with_capacity(params, 5);
 
     insert_node(&mut graph, 0, 1, 0)?;
@@ -164,7 +164,7 @@ fn eviction_respects_furthest_first_ordering() -> Result<(), HnswError> {
 
     // Seed node 1 at capacity with nodes 2 (furthest, front) and 3 (closer, back)
     // Order matters: push node 2 first (furthest), then node 3 (closer)
-    let node1 = graph.node_mut(1).expect("node 1 should exist");
+    let mut node1 = graph.node_mut(1).expect("node 1 should exist");
     node1.neighbours_mut(1).push(2); // furthest (front)
     node1.neighbours_mut(1).push(3); // closer (back)
 
@@ -173,12 +173,11 @@ fn eviction_respects_furthest_first_ordering() -> Result<(), HnswError> {
     add_edge_if_missing(&mut graph, 3, 1, 1);
 
     // Node 0 adds node 1, triggering eviction
-    let update = build_update(0, 1, vec![1], max_connections);
+    let update = build_update(0, 1, vec![1], limits);
     let new_node = NewNodeContext { id: 4, level: 1 };
 
     let mut applicator = CommitApplicator::new(&mut graph);
-    let (reciprocated, _) =
-        applicator.apply_neighbour_updates(vec![update], max_connections, new_node)?;
+    let (reciprocated, _) = applicator.apply_neighbour_updates(vec![update], limits, new_node)?;
     applicator.apply_new_node_neighbours(new_node.id, new_node.level, reciprocated)?;
 
     // Node 2 (furthest, front) should be evicted
@@ -197,7 +196,7 @@ fn eviction_respects_furthest_first_ordering() -> Result<(), HnswError> {
 /// Context for base layer healing tests with a 4-node graph at level 0.
 struct HealingTestContext {
     graph: Graph,
-    max_connections: usize,
+    limits: ConnectionLimits,
 }
 
 impl HealingTestContext {
@@ -205,7 +204,7 @@ impl HealingTestContext {
     /// with bidirectional edges to nodes 0 and 2, and node 2 is only connected
     /// to node 1 (will become isolated on eviction).
     fn new(params: HnswParams) -> Result<Self, HnswError> {
-        let max_connections = params.max_connections();
+        let limits = params.connection_limits();
         let mut graph = Graph::with_capacity(params, 4);
 
         // All nodes at level 0 (base layer)
@@ -223,10 +222,7 @@ impl HealingTestContext {
         // Node 2 only connected to node 1
         // After eviction, node 2 becomes isolated
 
-        Ok(Self {
-            graph,
-            max_connections,
-        })
+        Ok(Self { graph, limits })
     }
 
     /// Applies the given updates and returns the graph for assertions.
@@ -237,7 +233,7 @@ impl HealingTestContext {
     ) -> Result<Graph, HnswError> {
         let mut applicator = CommitApplicator::new(&mut self.graph);
         let (reciprocated, _) =
-            applicator.apply_neighbour_updates(updates, self.max_connections, new_node)?;
+            applicator.apply_neighbour_updates(updates, self.limits, new_node)?;
         applicator.apply_new_node_neighbours(new_node.id, new_node.level, reciprocated)?;
         Ok(self.graph)
     }
@@ -255,7 +251,7 @@ fn eviction_at_base_layer_triggers_healing() -> Result<(), HnswError> {
     let ctx = HealingTestContext::new(params)?;
 
     // Node 3 adds node 1, triggering eviction of node 2 from node 1
-    let update = build_update(3, 0, vec![1], ctx.max_connections);
+    let update = build_update(3, 0, vec![1], ctx.limits);
     let new_node = NewNodeContext { id: 3, level: 0 };
 
     let graph = ctx.apply_updates(vec![update], new_node)?;
@@ -7,7 +7,7 @@ use crate::hnsw::{
     error::HnswError,
     graph::{EdgeContext, Graph, NodeContext},
     insert::types::{NewNodeContext, StagedUpdate},
-    params::HnswParams,
+    params::{ConnectionLimits, HnswParams},
 };
 use rstest::{fixture, rstest};
 
@@ -62,12 +62,9 @@ fn build_update(
     node: usize,
     level: usize,
     neighbours: Vec<usize>,
-    max_connections: usize,
+    limits: ConnectionLimits,
 ) -> (StagedUpdate, Vec<usize>) {
-    let ctx = EdgeContext {
-        level,
-        max_connections,
-    };
+    let ctx = EdgeContext { level, limits };
     let staged = StagedUpdate {
         node,
         ctx,
@@ -83,7 +80,7 @@ fn commit_updates_write_reciprocal_edges(
     #[case] level: usize,
     params_two_connections: HnswParams,
 ) -> Result<(), HnswError> {
-    let max_connections = params_two_connections.max_connections();
+    let limits = params_two_connections.connection_limits();
     let mut graph = Graph::with_capacity(params_two_connections.clone(), 3);
 
     insert_node(&mut graph, 0, level, 0)?;
@@ -93,12 +90,11 @@ fn commit_updates_write_reciprocal_edges(
     add_edge_if_missing(&mut graph, 0, 1, level);
     add_edge_if_missing(&mut graph, 1, 0, level);
 
-    let update = build_update(0, level, vec![1, 2], max_connections);
+    let update = build_update(0, level, vec![1, 2], limits);
     let new_node = NewNodeContext { id: 2, level };
 
     let mut applicator = CommitApplicator::new(&mut graph);
-    let (reciprocated, _) =
-        applicator.apply_neighbour_updates(vec![update], max_connections, new_node)?;
+    let (reciprocated, _) = applicator.apply_neighbour_updates(vec![update], limits, new_node)?;
     applicator.apply_new_node_neighbours(new_node.id, new_node.level, reciprocated)?;
 
     assert_bidirectional_edge(&graph, 0, 2, level);
@@ -110,7 +106,7 @@ fn commit_updates_write_reciprocal_edges(
 #[rstest]
 fn commit_updates_scrub_evicted_forward_edge() -> Result<(), HnswError> {
     let params = HnswParams::new(1, 4)?;
-    let max_connections = params.max_connections();
+    let limits = params.connection_limits();
     let mut graph = Graph::with_capacity(params, 4);
 
     insert_node(&mut graph, 0, 1, 0)?;
@@ -121,15 +117,14 @@ fn commit_updates_scrub_evicted_forward_edge() -> Result<(), HnswError> {
     add_edge_if_missing(&mut graph, 1, 2, 1);
     add_edge_if_missing(&mut graph, 2, 1, 1);
 
-    let update = build_update(0, 1, vec![1], max_connections);
+    let update = build_update(0, 1, vec![1], limits);
     let new_node = NewNodeContext { id: 3, level: 1 };
 
     let mut applicator = CommitApplicator::new(&mut graph);
-    let (reciprocated, _) =
-        applicator.apply_neighbour_updates(vec![update], max_connections, new_node)?;
+    let (reciprocated, _) = applicator.apply_neighbour_updates(vec![update], limits, new_node)?;
     applicator.apply_new_node_neighbours(new_node.id, new_node.level, reciprocated)?;
 
-    let limit = limits::compute_connection_limit(1, max_connections);
+    let limit = limits::compute_connection_limit(1, limits);
     for node_id in [0, 1, 2, 3] {
         let node_msg = format!("node {node_id} should exist");
         let node = graph.node(node_id).expect(&node_msg);
@@ -148,7 +143,7 @@ fn commit_updates_scrub_evicted_forward_edge() -> Result<(), HnswError> {
 
 #[rstest]
 fn commit_updates_report_missing_origin(params_two_connections: HnswParams) {
-    let max_connections = params_two_connections.max_connections();
+    let limits = params_two_connections.connection_limits();
     let mut graph = Graph::with_capacity(params_two_connections, 2);
 
     graph
@@ -166,12 +161,12 @@ fn commit_updates_report_missing_origin(params_two_connections: HnswParams) {
         })
         .expect("attach node 1");
 
-    let update = build_update(99, 0, vec![0], max_connections);
+    let update = build_update(99, 0, vec![0], limits);
     let new_node = NewNodeContext { id: 1, level: 0 };
 
     let mut applicator = CommitApplicator::new(&mut graph);
     let err = applicator
-        .apply_neighbour_updates(vec![update], max_connections, new_node)
+        .apply_neighbour_updates(vec![update], limits, new_node)
         .expect_err("missing origin should error");
 
     assert!(matches!(err, HnswError::GraphInvariantViolation { .. }));
@@ -206,7 +201,7 @@ fn assert_has_edge(graph: &Graph, origin: usize, target: usize, level: usize) {
 /// Context for eviction tests with a 4-node graph where node 1 is at capacity.
 struct EvictionTestContext {
     graph: Graph,
-    max_connections: usize,
+    limits: ConnectionLimits,
     new_node: NewNodeContext,
 }
 
@@ -214,7 +209,7 @@ impl EvictionTestContext {
     /// Creates a test graph with 4 nodes at level 1, where node 1 is seeded
     /// at capacity with a bidirectional edge to node 2.
     fn new(params: HnswParams) -> Result<Self, HnswError> {
-        let max_connections = params.max_connections();
+        let limits = params.connection_limits();
         let mut graph = Graph::with_capacity(params, 4);
 
         insert_node(&mut graph, 0, 1, 0)?;
@@ -229,7 +224,7 @@ impl EvictionTestContext {
         let new_node = NewNodeContext { id: 3, level: 1 };
         Ok(Self {
             graph,
-            max_connections,
+            limits,
             new_node,
         })
     }
@@ -241,7 +236,7 @@ impl EvictionTestContext {
     ) -> Result<Graph, HnswError> {
         let mut applicator = CommitApplicator::new(&mut self.graph);
         let (reciprocated, _) =
-            applicator.apply_neighbour_updates(updates, self.max_connections, self.new_node)?;
+            applicator.apply_neighbour_updates(updates, self.limits, self.new_node)?;
         applicator.apply_new_node_neighbours(
             self.new_node.id,
             self.new_node.level,
@@ -261,7 +256,7 @@ fn eviction_scrubs_orphaned_forward_edge(
     params_one_connection: HnswParams,
 ) -> Result<(), HnswError> {
     let ctx = EvictionTestContext::new(params_one_connection)?;
-    let update = build_update(0, 1, vec![1], ctx.max_connections);
+    let update = build_update(0, 1, vec![1], ctx.limits);
     let graph = ctx.apply_updates(vec![update])?;
 
     // Node 0 and node 1 should be linked
@@ -11,7 +11,7 @@ use std::collections::HashSet;
 
 use super::limits::compute_connection_limit;
 use super::types::{LinkContext, UpdateContext};
-use crate::hnsw::graph::Graph;
+use crate::hnsw::{graph::Graph, node::NeighboursMut, params::ConnectionLimits};
 
 #[derive(Debug)]
 pub(super) struct ConnectivityHealer<'graph> {
@@ -27,7 +27,7 @@ impl<'graph> ConnectivityHealer<'graph> {
     ///
     /// Uses an iterative work queue to process any nodes that become isolated
     /// due to evictions, avoiding deep recursion that could cause stack overflow.
-    pub(super) fn ensure_base_connectivity(&mut self, node: usize, max_connections: usize) {
+    pub(super) fn ensure_base_connectivity(&mut self, node: usize, limits: ConnectionLimits) {
         let mut work_queue: Vec<usize> = vec![node];
         let mut visited: HashSet<usize> = HashSet::new();
 
@@ -47,7 +47,7 @@ impl<'graph> ConnectivityHealer<'graph> {
             let ctx = UpdateContext {
                 origin: entry.node,
                 level: 0,
-                max_connections,
+                limits,
             };
 
             if let Some(evicted) = self.link_new_node_inner(&ctx, current) {
@@ -68,7 +68,7 @@ impl<'graph> ConnectivityHealer<'graph> {
     fn link_new_node_base_layer(&mut self, ctx: &UpdateContext, new_node: usize) -> bool {
         let result = self.link_new_node_inner(ctx, new_node);
         if let Some(evicted) = result {
-            self.process_eviction_queue(evicted, ctx.max_connections);
+            self.process_eviction_queue(evicted, ctx.limits);
         }
 
         result.is_some() || self.node_has_link(new_node, ctx.origin, 0)
@@ -81,12 +81,12 @@ impl<'graph> ConnectivityHealer<'graph> {
     }
 
     /// Processes evicted nodes iteratively to restore their connectivity.
-    fn process_eviction_queue(&mut self, initial: usize, max_connections: usize) {
+    fn process_eviction_queue(&mut self, initial: usize, limits: ConnectionLimits) {
         let mut work_queue: Vec<usize> = vec![initial];
         let mut visited: HashSet<usize> = HashSet::new();
 
         while let Some(current) = work_queue.pop() {
-            if let Some(evicted) = self.try_heal_node(&mut visited, current, max_connections) {
+            if let Some(evicted) = self.try_heal_node(&mut visited, current, limits) {
                 work_queue.push(evicted);
             }
         }
@@ -97,7 +97,7 @@ impl<'graph> ConnectivityHealer<'graph> {
         &mut self,
         visited: &mut HashSet<usize>,
         current: usize,
-        max_connections: usize,
+        limits: ConnectionLimits,
     ) -> Option<usize> {
         if !visited.insert(current) {
             return None;
@@ -111,7 +111,7 @@ impl<'graph> ConnectivityHealer<'graph> {
         let heal_ctx = UpdateContext {
             origin: entry.node,
             level: 0,
-            max_connections,
+            limits,
         };
 
         self.link_new_node_inner(&heal_ctx, current)
@@ -127,14 +127,14 @@ impl<'graph> ConnectivityHealer<'graph> {
     /// Inner implementation of link_new_node that returns the evicted node (if any)
     /// instead of recursively handling it.
     fn link_new_node_inner(&mut self, ctx: &UpdateContext, new_node: usize) -> Option<usize> {
-        let limit = compute_connection_limit(ctx.level, ctx.max_connections);
+        let limit = compute_connection_limit(ctx.level, ctx.limits);
         if !self.can_link_at_level(ctx.origin, ctx.level) {
             return None;
         }
 
-        let candidate_node = self.graph.node_mut(ctx.origin)?;
-        let neighbours = candidate_node.neighbours_mut(ctx.level);
-        let evicted = Self::add_to_neighbour_list(neighbours, new_node, limit);
+        let mut candidate_node = self.graph.node_mut(ctx.origin)?;
+        let mut neighbours = candidate_node.neighbours_mut(ctx.level);
+        let evicted = Self::add_to_neighbour_list(&mut neighbours, new_node, limit);
         if !neighbours.contains(&new_node) {
             return None;
         }
@@ -143,9 +143,9 @@ impl<'graph> ConnectivityHealer<'graph> {
             return None;
         }
 
-        let new_node_ref = self.graph.node_mut(new_node)?;
-        let neighbours = new_node_ref.neighbours_mut(ctx.level);
-        Self::add_to_neighbour_list(neighbours, ctx.origin, limit);
+        let mut new_node_ref = self.graph.node_mut(new_node)?;
+        let mut neighbours = new_node_ref.neighbours_mut(ctx.level);
+        Self::add_to_neighbour_list(&mut neighbours, ctx.origin, limit);
         if !neighbours.contains(&ctx.origin) {
             return None;
         }
@@ -166,14 +166,14 @@ impl<'graph> ConnectivityHealer<'graph> {
         evicted: usize,
         ctx: &UpdateContext,
     ) -> Option<usize> {
-        let Some(evicted_node) = self.graph.node_mut(evicted) else {
+        let Some(mut evicted_node) = self.graph.node_mut(evicted) else {
             return Some(ctx.origin); // Link succeeded to origin's perspective
         };
         if ctx.level >= evicted_node.level_count() {
             return Some(ctx.origin);
         }
 
-        let evicted_neighbours = evicted_node.neighbours_mut(ctx.level);
+        let mut evicted_neighbours = evicted_node.neighbours_mut(ctx.level);
         if let Some(pos) = evicted_neighbours.iter().position(|&id| id == ctx.origin) {
             evicted_neighbours.remove(pos);
         }
@@ -188,14 +188,14 @@ impl<'graph> ConnectivityHealer<'graph> {
     pub(super) fn attach_entry_fallback(
         &mut self,
         level: usize,
-        max_connections: usize,
+        limits: ConnectionLimits,
         new_node: usize,
     ) -> Option<usize> {
         self.graph.entry().and_then(|entry| {
             let ctx = UpdateContext {
                 origin: entry.node,
                 level,
-                max_connections,
+                limits,
             };
             self.link_new_node(&ctx, new_node).then_some(entry.node)
         })
@@ -213,12 +213,12 @@ impl<'graph> ConnectivityHealer<'graph> {
                 let link = UpdateContext {
                     origin: candidate,
                     level: ctx.level,
-                    max_connections: ctx.max_connections,
+                    limits: ctx.limits,
                 };
                 self.link_new_node(&link, ctx.new_node)
             });
 
-        linked.or_else(|| self.attach_entry_fallback(ctx.level, ctx.max_connections, ctx.new_node))
+        linked.or_else(|| self.attach_entry_fallback(ctx.level, ctx.limits, ctx.new_node))
     }
 
     fn can_link_at_level(&self, node_id: usize, level: usize) -> bool {
@@ -228,7 +228,7 @@ impl<'graph> ConnectivityHealer<'graph> {
     }
 
     fn add_to_neighbour_list(
-        neighbours: &mut Vec<usize>,
+        neighbours: &mut NeighboursMut<'_>,
         new_id: usize,
         limit: usize,
     ) -> Option<usize> {
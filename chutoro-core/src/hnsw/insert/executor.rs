@@ -3,6 +3,8 @@
 
 use std::collections::HashMap;
 
+#[cfg(test)]
+use crate::hnsw::params::ConnectionLimits;
 use crate::hnsw::{
     error::HnswError,
     graph::{ApplyContext, Graph, NodeContext},
@@ -55,7 +57,7 @@ impl<'graph> InsertionExecutor<'graph> {
         stager.ensure_slot_available(node)?;
 
         let promote_entry = level > self.graph.entry().map(|entry| entry.level).unwrap_or(0);
-        let max_connections = params.max_connections();
+        let limits = params.connection_limits();
         let LayerProcessingOutcome {
             mut new_node_neighbours,
             staged,
@@ -68,7 +70,7 @@ impl<'graph> InsertionExecutor<'graph> {
                 sequence,
             },
             plan,
-            max_connections,
+            limits,
         )?;
         InsertionStager::dedupe_new_node_lists(&mut new_node_neighbours);
         let (updates, trim_jobs) = stager.generate_updates_and_trim_jobs(
@@ -80,7 +82,7 @@ impl<'graph> InsertionExecutor<'graph> {
             TrimWork {
                 staged,
                 needs_trim,
-                max_connections,
+                limits,
             },
         )?;
 
@@ -94,7 +96,7 @@ impl<'graph> InsertionExecutor<'graph> {
                 promote_entry,
                 new_node_neighbours,
                 updates,
-                max_connections,
+                limits,
             },
             trim_jobs,
         ))
@@ -111,7 +113,7 @@ impl<'graph> InsertionExecutor<'graph> {
             promote_entry,
             new_node_neighbours,
             updates,
-            max_connections,
+            limits,
         } = prepared;
 
         let new_node = NewNodeContext {
@@ -127,7 +129,7 @@ impl<'graph> InsertionExecutor<'graph> {
             original: &new_node_neighbours,
             final_updates: &mut final_updates,
             new_node: new_node.id,
-            max_connections,
+            limits,
         }
         .apply();
 
@@ -135,7 +137,7 @@ impl<'graph> InsertionExecutor<'graph> {
 
         let (mut reciprocated, mut touched) = {
             let mut applicator = CommitApplicator::new(self.graph);
-            applicator.apply_neighbour_updates(final_updates, max_connections, new_node)?
+            applicator.apply_neighbour_updates(final_updates, limits, new_node)?
         };
 
         self.heal_connectivity_gaps(
@@ -143,7 +145,7 @@ impl<'graph> InsertionExecutor<'graph> {
             HealingContext {
                 filtered_new_node_neighbours: &filtered_new_node_neighbours,
                 new_node_id: new_node.id,
-                max_connections,
+                limits,
             },
         );
 
@@ -157,7 +159,7 @@ impl<'graph> InsertionExecutor<'graph> {
         #[cfg(any(test, debug_assertions))]
         {
             let auditor = ReciprocityAuditor::new(self.graph);
-            auditor.assert_reciprocity_for_touched(&touched, max_connections);
+            auditor.assert_reciprocity_for_touched(&touched, limits);
         }
         #[cfg(not(any(test, debug_assertions)))]
         let _ = &touched;
@@ -198,7 +200,7 @@ impl<'graph> InsertionExecutor<'graph> {
         for (level, neighbours) in reciprocated.iter_mut().enumerate() {
             neighbours.sort_unstable();
             neighbours.dedup();
-            let limit = compute_connection_limit(level, healing_ctx.max_connections);
+            let limit = compute_connection_limit(level, healing_ctx.limits);
             if neighbours.len() > limit {
                 neighbours.truncate(limit);
             }
@@ -208,7 +210,7 @@ impl<'graph> InsertionExecutor<'graph> {
 
             let link_ctx = LinkContext {
                 level,
-                max_connections: healing_ctx.max_connections,
+                limits: healing_ctx.limits,
                 new_node: healing_ctx.new_node_id,
             };
 
@@ -229,14 +231,13 @@ impl<'graph> InsertionExecutor<'graph> {
         expect(dead_code, reason = "test helper unused in release builds")
     )]
     #[cfg(test)]
-    pub(crate) fn heal_reachability(&mut self, max_connections: usize) {
-        super::test_helpers::TestHelpers::new(self.graph).heal_reachability(max_connections);
+    pub(crate) fn heal_reachability(&mut self, limits: ConnectionLimits) {
+        super::test_helpers::TestHelpers::new(self.graph).heal_reachability(limits);
     }
 
     #[cfg(test)]
-    pub(crate) fn enforce_bidirectional_all(&mut self, max_connections: usize) {
-        super::test_helpers::TestHelpers::new(self.graph)
-            .enforce_bidirectional_all(max_connections);
+    pub(crate) fn enforce_bidirectional_all(&mut self, limits: ConnectionLimits) {
+        super::test_helpers::TestHelpers::new(self.graph).enforce_bidirectional_all(limits);
     }
 }
 
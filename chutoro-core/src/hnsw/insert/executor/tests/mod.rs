@@ -14,7 +14,7 @@ use crate::hnsw::insert::{
 use crate::hnsw::{
     error::HnswError,
     graph::{ApplyContext, Graph, NodeContext},
-    params::HnswParams,
+    params::{ConnectionLimits, HnswParams},
     types::{InsertionPlan, LayerPlan, Neighbour},
 };
 use rstest::rstest;
@@ -107,7 +107,7 @@ fn ensure_reverse_edge_evicts_and_scrubs_forward_link() {
         &types::UpdateContext {
             origin: 0,
             level: 1,
-            max_connections: 1,
+            limits: ConnectionLimits::uniform(1),
         },
         1,
     );
@@ -115,7 +115,7 @@ fn ensure_reverse_edge_evicts_and_scrubs_forward_link() {
     assert!(ensured, "reverse edge should be ensured even when evicting");
 
     // Apply deferred scrubs to remove the evicted node's forward edge.
-    reconciler.apply_deferred_scrubs(1);
+    reconciler.apply_deferred_scrubs(ConnectionLimits::uniform(1));
 
     let target = reconciler.graph.node(1).unwrap();
     assert_eq!(target.neighbours(1), &[0]);
@@ -211,7 +211,7 @@ fn enforce_bidirectional_all_adds_upper_layer_backlink() {
 
     add_edge_if_missing(&mut graph, 0, 1, 1);
 
-    TestHelpers::new(&mut graph).enforce_bidirectional_all(2);
+    TestHelpers::new(&mut graph).enforce_bidirectional_all(ConnectionLimits::uniform(2));
 
     assert_bidirectional_edge(&graph, 0, 1, 1);
 }
@@ -225,7 +225,7 @@ fn enforce_bidirectional_all_removes_invalid_upper_edge() {
     // One-way edge exists at level 1, but target only has level 0.
     add_edge_if_missing(&mut graph, 0, 1, 1);
 
-    TestHelpers::new(&mut graph).enforce_bidirectional_all(2);
+    TestHelpers::new(&mut graph).enforce_bidirectional_all(ConnectionLimits::uniform(2));
 
     assert_no_edge(&graph, 0, 1, 1);
     assert_no_edge(&graph, 1, 0, 1);
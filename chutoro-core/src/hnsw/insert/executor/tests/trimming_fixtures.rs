@@ -61,7 +61,7 @@ pub(super) fn verify_post_trim_reciprocity(
     new_node_id: usize,
     evicted: usize,
 ) {
-    let connection_limit = connection_limit_for_level(0, params.max_connections());
+    let connection_limit = connection_limit_for_level(0, params.connection_limits());
     let entry = graph.node(0).expect("entry node available");
     let entry_neighbours = entry.neighbours(0);
     assert!(
@@ -154,16 +154,17 @@ pub(super) fn setup_reciprocal_edges_with_reserve(
 }
 
 fn set_entry_neighbours(graph: &mut Graph, neighbours: &[usize]) {
-    let entry_neighbours = graph.node_mut(0).expect("entry present").neighbours_mut(0);
+    let mut entry = graph.node_mut(0).expect("entry present");
+    let mut entry_neighbours = entry.neighbours_mut(0);
     entry_neighbours.clear();
     entry_neighbours.extend(neighbours.iter().copied());
 }
 
 fn link_if_absent(graph: &mut Graph, origin: usize, target: usize) {
-    let Some(node) = graph.node_mut(origin) else {
+    let Some(mut node) = graph.node_mut(origin) else {
         panic!("node {origin} should be present");
     };
-    let list = node.neighbours_mut(0);
+    let mut list = node.neighbours_mut(0);
     if !list.contains(&target) {
         list.push(target);
     }
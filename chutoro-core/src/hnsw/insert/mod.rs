@@ -23,7 +23,9 @@ pub(super) use planner::{InsertionPlanner, PlanningInputs};
 #[cfg(kani)]
 pub(crate) use types::{FinalisedUpdate, NewNodeContext, StagedUpdate};
 
-use crate::hnsw::types::{CandidateEdge, InsertionPlan};
+#[cfg(kani)]
+use crate::hnsw::params::ConnectionLimits;
+use crate::hnsw::{candidate::CandidateEdge, types::InsertionPlan};
 
 /// Extracts candidate edges from an insertion plan.
 ///
@@ -91,16 +93,16 @@ pub(super) fn extract_candidate_edges(
 pub(crate) struct KaniUpdateContext {
     pub(crate) origin: usize,
     pub(crate) level: usize,
-    pub(crate) max_connections: usize,
+    pub(crate) limits: ConnectionLimits,
 }
 
 #[cfg(kani)]
 impl KaniUpdateContext {
-    pub(crate) fn new(origin: usize, level: usize, max_connections: usize) -> Self {
+    pub(crate) fn new(origin: usize, level: usize, limits: ConnectionLimits) -> Self {
         Self {
             origin,
             level,
-            max_connections,
+            limits,
         }
     }
 }
@@ -153,7 +155,7 @@ fn validate_new_node_for_kani(graph: &crate::hnsw::graph::Graph, new_node: &type
 fn validate_update_for_kani(
     graph: &crate::hnsw::graph::Graph,
     update: &types::FinalisedUpdate,
-    max_connections: usize,
+    limits: ConnectionLimits,
 ) {
     let (staged, neighbours) = update;
     assume_node_has_level(graph, staged.node, staged.ctx.level);
@@ -172,7 +174,7 @@ fn validate_update_for_kani(
     );
     kani::assume(no_self_loops);
 
-    let limit = limits::compute_connection_limit(staged.ctx.level, max_connections);
+    let limit = limits::compute_connection_limit(staged.ctx.level, limits);
     let within_limit = neighbours.len() <= limit;
     debug_assert!(
         within_limit,
@@ -213,19 +215,18 @@ fn validate_update_for_kani(
 #[cfg(kani)]
 pub(crate) fn apply_commit_updates_for_kani(
     graph: &mut crate::hnsw::graph::Graph,
-    max_connections: usize,
+    limits: ConnectionLimits,
     new_node: types::NewNodeContext,
     updates: Vec<types::FinalisedUpdate>,
 ) -> Result<(), crate::hnsw::error::HnswError> {
     validate_new_node_for_kani(graph, &new_node);
 
     for update in &updates {
-        validate_update_for_kani(graph, update, max_connections);
+        validate_update_for_kani(graph, update, limits);
     }
 
     let mut applicator = commit::CommitApplicator::new(graph);
-    let (reciprocated, _touched) =
-        applicator.apply_neighbour_updates(updates, max_connections, new_node)?;
+    let (reciprocated, _touched) = applicator.apply_neighbour_updates(updates, limits, new_node)?;
     applicator.apply_new_node_neighbours(new_node.id, new_node.level, reciprocated)?;
 
     Ok(())
@@ -283,19 +284,19 @@ pub(crate) fn apply_reconciled_update_for_kani(
     let update_ctx = types::UpdateContext {
         origin: ctx.origin,
         level: ctx.level,
-        max_connections: ctx.max_connections,
+        limits: ctx.limits,
     };
     let mut reconciler = reconciliation::EdgeReconciler::new(graph);
     reconciler.reconcile_removed_edges(&update_ctx, &previous, next.as_slice());
     reconciler.reconcile_added_edges(&update_ctx, next);
 
-    if let Some(node_ref) = reconciler.graph_mut().node_mut(ctx.origin) {
-        let list = node_ref.neighbours_mut(ctx.level);
+    if let Some(mut node_ref) = reconciler.graph_mut().node_mut(ctx.origin) {
+        let mut list = node_ref.neighbours_mut(ctx.level);
         list.clear();
         list.extend(next.iter().copied());
     }
 
-    reconciler.apply_deferred_scrubs(ctx.max_connections);
+    reconciler.apply_deferred_scrubs(ctx.limits);
 }
 
 /// Ensures a reverse edge using the production reconciler for Kani harnesses.
@@ -336,7 +337,7 @@ pub(crate) fn ensure_reverse_edge_for_kani(
     let update_ctx = types::UpdateContext {
         origin: ctx.origin,
         level: ctx.level,
-        max_connections: ctx.max_connections,
+        limits: ctx.limits,
     };
     let mut reconciler = reconciliation::EdgeReconciler::new(graph);
     reconciler.ensure_reverse_edge(&update_ctx, target)
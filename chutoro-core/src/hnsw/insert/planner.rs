@@ -89,8 +89,7 @@ impl<'graph> InsertionPlanner<'graph> {
         let target_level = ctx.level.min(entry.level);
         let descent_ctx = DescentContext::new(ctx.node, entry, target_level);
         let current = self.greedy_descend_to_target_level(source, descent_ctx, cache)?;
-        let layer_ctx =
-            LayerPlanContext::new(ctx.node, current, target_level, params.ef_construction());
+        let layer_ctx = LayerPlanContext::new(ctx.node, current, target_level, params);
         let layers = self.build_layer_plans_from_target(source, layer_ctx, cache)?;
         Ok(InsertionPlan { layers })
     }
@@ -122,7 +121,7 @@ impl<'graph> InsertionPlanner<'graph> {
     fn build_layer_plans_from_target<D: DataSource + Sync>(
         &self,
         source: &D,
-        ctx: LayerPlanContext,
+        ctx: LayerPlanContext<'_>,
         cache: Option<&DistanceCache>,
     ) -> Result<Vec<LayerPlan>, HnswError> {
         let mut layers = Vec::with_capacity(ctx.target_level + 1);
@@ -137,7 +136,7 @@ impl<'graph> InsertionPlanner<'graph> {
                     entry: current,
                     level,
                 }
-                .with_ef(ctx.ef),
+                .with_ef(ctx.params.ef_construction_for_layer(level)),
             )?;
             if let Some(best) = candidates.first() {
                 current = best.id;
@@ -9,6 +9,7 @@ use std::collections::HashSet;
 
 #[cfg(any(test, debug_assertions))]
 use crate::hnsw::graph::Graph;
+use crate::hnsw::params::ConnectionLimits;
 
 use super::{limits::compute_connection_limit, types::FinalisedUpdate};
 
@@ -22,7 +23,7 @@ pub(super) struct ReciprocityAuditor<'graph> {
 #[derive(Debug, Clone, Copy)]
 struct AuditContext {
     level: usize,
-    max_connections: usize,
+    limits: ConnectionLimits,
 }
 
 #[cfg(any(test, debug_assertions))]
@@ -34,7 +35,7 @@ impl<'graph> ReciprocityAuditor<'graph> {
     pub(super) fn assert_reciprocity_for_touched(
         &self,
         touched: &[(usize, usize)],
-        max_connections: usize,
+        limits: ConnectionLimits,
     ) {
         let mut seen = HashSet::new();
         for &(origin, level) in touched {
@@ -42,10 +43,7 @@ impl<'graph> ReciprocityAuditor<'graph> {
                 continue;
             }
 
-            let ctx = AuditContext {
-                level,
-                max_connections,
-            };
+            let ctx = AuditContext { level, limits };
             self.assert_origin_state(origin, ctx);
         }
     }
@@ -60,7 +58,7 @@ impl<'graph> ReciprocityAuditor<'graph> {
         }
 
         let origin_neighbours = origin_node.neighbours(level);
-        let origin_limit = compute_connection_limit(level, ctx.max_connections);
+        let origin_limit = compute_connection_limit(level, ctx.limits);
         assert!(
             origin_neighbours.len() <= origin_limit,
             "reciprocity audit: node {origin} exceeds degree limit {origin_limit} at level \
@@ -88,7 +86,7 @@ impl<'graph> ReciprocityAuditor<'graph> {
         );
 
         let neighbours = target_node.neighbours(ctx.level);
-        let limit = compute_connection_limit(ctx.level, ctx.max_connections);
+        let limit = compute_connection_limit(ctx.level, ctx.limits);
         assert!(
             neighbours.contains(&origin),
             "reciprocity audit: missing reverse edge {target}->{origin} at level {level}; \
@@ -108,7 +106,7 @@ pub(super) struct ReciprocityWorkspace<'a> {
     pub(super) original: &'a [Vec<usize>],
     pub(super) final_updates: &'a mut [FinalisedUpdate],
     pub(super) new_node: usize,
-    pub(super) max_connections: usize,
+    pub(super) limits: ConnectionLimits,
 }
 
 impl<'a> ReciprocityWorkspace<'a> {
@@ -118,14 +116,14 @@ impl<'a> ReciprocityWorkspace<'a> {
             original,
             final_updates,
             new_node,
-            max_connections,
+            limits,
         } = self;
 
         let mut selector = FallbackSelector {
             original,
             final_updates,
             new_node,
-            max_connections,
+            limits,
         };
 
         for (level, neighbours) in filtered.iter_mut().enumerate() {
@@ -147,7 +145,7 @@ struct FallbackSelector<'a> {
     original: &'a [Vec<usize>],
     final_updates: &'a mut [FinalisedUpdate],
     new_node: usize,
-    max_connections: usize,
+    limits: ConnectionLimits,
 }
 
 impl<'a> FallbackSelector<'a> {
@@ -163,7 +161,7 @@ impl<'a> FallbackSelector<'a> {
 
     fn select(&mut self, level: usize) -> Option<usize> {
         let fallback_candidates = self.original.get(level).map(Vec::as_slice).unwrap_or(&[]);
-        let limit = compute_connection_limit(level, self.max_connections);
+        let limit = compute_connection_limit(level, self.limits);
 
         for &candidate in fallback_candidates {
             let Some((_, neighbour_list)) = self
@@ -9,7 +9,7 @@
 //! deferred scrubs are filtered against the final edge set to ensure we don't
 //! remove edges that were added by later updates.
 
-use crate::hnsw::graph::Graph;
+use crate::hnsw::{graph::Graph, params::ConnectionLimits};
 
 use super::{
     connectivity::ConnectivityHealer,
@@ -50,14 +50,14 @@ impl<'graph> EdgeReconciler<'graph> {
             if next.contains(&target) {
                 continue;
             }
-            let Some(target_node) = self.graph.node_mut(target) else {
+            let Some(mut target_node) = self.graph.node_mut(target) else {
                 continue;
             };
             if ctx.level >= target_node.level_count() {
                 continue;
             }
 
-            let neighbours = target_node.neighbours_mut(ctx.level);
+            let mut neighbours = target_node.neighbours_mut(ctx.level);
             let Some(pos) = neighbours.iter().position(|&id| id == ctx.origin) else {
                 continue;
             };
@@ -74,7 +74,7 @@ impl<'graph> EdgeReconciler<'graph> {
 
         let mut healer = ConnectivityHealer::new(self.graph);
         for node in isolated {
-            healer.ensure_base_connectivity(node, ctx.max_connections);
+            healer.ensure_base_connectivity(node, ctx.limits);
         }
     }
 
@@ -83,15 +83,15 @@ impl<'graph> EdgeReconciler<'graph> {
     }
 
     pub(super) fn ensure_reverse_edge(&mut self, ctx: &UpdateContext, target: usize) -> bool {
-        let Some(target_node) = self.graph.node_mut(target) else {
+        let Some(mut target_node) = self.graph.node_mut(target) else {
             return false;
         };
         if ctx.level >= target_node.level_count() {
             return false;
         }
 
-        let limit = compute_connection_limit(ctx.level, ctx.max_connections);
-        let neighbours = target_node.neighbours_mut(ctx.level);
+        let limit = compute_connection_limit(ctx.level, ctx.limits);
+        let mut neighbours = target_node.neighbours_mut(ctx.level);
         if neighbours.contains(&ctx.origin) {
             return true;
         }
@@ -137,7 +137,7 @@ impl<'graph> EdgeReconciler<'graph> {
     /// 1. If target now links back to origin, reciprocity is intact - skip
     /// 2. If origin no longer links to target, the edge is already gone - skip
     /// 3. Otherwise, the forward edge is orphaned - remove it
-    pub(super) fn apply_deferred_scrubs(&mut self, max_connections: usize) {
+    pub(super) fn apply_deferred_scrubs(&mut self, limits: ConnectionLimits) {
         let scrubs = std::mem::take(&mut self.deferred_scrubs);
         for scrub in scrubs {
             // Check if target now has a forward link back to origin (i.e.,
@@ -158,7 +158,7 @@ impl<'graph> EdgeReconciler<'graph> {
             let ctx = UpdateContext {
                 origin: scrub.origin,
                 level: scrub.level,
-                max_connections,
+                limits,
             };
             self.remove_forward_edge_from(&ctx, scrub.target);
         }
@@ -176,20 +176,20 @@ impl<'graph> EdgeReconciler<'graph> {
     }
 
     pub(super) fn remove_forward_edge_from(&mut self, ctx: &UpdateContext, target: usize) {
-        let Some(origin_node) = self.graph.node_mut(ctx.origin) else {
+        let Some(mut origin_node) = self.graph.node_mut(ctx.origin) else {
             return;
         };
         if ctx.level >= origin_node.level_count() {
             return;
         }
 
-        let neighbours = origin_node.neighbours_mut(ctx.level);
+        let mut neighbours = origin_node.neighbours_mut(ctx.level);
         let initial_len = neighbours.len();
         if let Some(pos) = neighbours.iter().position(|&id| id == target) {
             neighbours.remove(pos);
-            if Self::should_heal_connectivity(initial_len, neighbours, ctx.level) {
+            if Self::should_heal_connectivity(initial_len, &neighbours, ctx.level) {
                 let mut healer = ConnectivityHealer::new(self.graph);
-                healer.ensure_base_connectivity(ctx.origin, ctx.max_connections);
+                healer.ensure_base_connectivity(ctx.origin, ctx.limits);
             }
         }
     }
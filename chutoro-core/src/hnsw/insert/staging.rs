@@ -10,6 +10,7 @@ use std::collections::{HashMap, HashSet};
 use crate::hnsw::{
     error::HnswError,
     graph::{EdgeContext, Graph, NodeContext},
+    params::ConnectionLimits,
     types::InsertionPlan,
 };
 
@@ -47,7 +48,7 @@ impl<'graph> InsertionStager<'graph> {
         &self,
         ctx: NodeContext,
         plan: InsertionPlan,
-        max_connections: usize,
+        limits: ConnectionLimits,
     ) -> Result<LayerProcessingOutcome, HnswError> {
         let mut new_node_neighbours = vec![Vec::new(); ctx.level + 1];
         let mut staged: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
@@ -60,7 +61,7 @@ impl<'graph> InsertionStager<'graph> {
             .filter(|layer| layer.level <= ctx.level)
         {
             let level_index = layer.level;
-            let level_capacity = compute_connection_limit(level_index, max_connections);
+            let level_capacity = compute_connection_limit(level_index, limits);
 
             for neighbour in layer.neighbours.into_iter().take(level_capacity) {
                 self.stage_neighbour(
@@ -98,17 +99,14 @@ impl<'graph> InsertionStager<'graph> {
         let TrimWork {
             mut staged,
             needs_trim,
-            max_connections,
+            limits,
         } = work;
         let mut updates = Vec::with_capacity(staged.len());
         let mut trim_jobs = Vec::with_capacity(needs_trim.len());
 
         for ((other, lvl), mut candidates) in staged.drain() {
             Self::dedupe_candidates(&mut candidates);
-            let ctx = EdgeContext {
-                level: lvl,
-                max_connections,
-            };
+            let ctx = EdgeContext { level: lvl, limits };
             prioritise_new_node(new_node.node, &mut candidates);
             if needs_trim.contains(&(other, lvl)) {
                 let mut sequences = Vec::with_capacity(candidates.len());
@@ -4,16 +4,16 @@ use super::{
     connectivity::ConnectivityHealer, limits::compute_connection_limit,
     reconciliation::EdgeReconciler, types::UpdateContext,
 };
-use crate::hnsw::graph::Graph;
+use crate::hnsw::{graph::Graph, params::ConnectionLimits};
 
 pub(crate) fn add_edge_if_missing(graph: &mut Graph, origin: usize, target: usize, level: usize) {
     #[cfg(kani)]
     {
-        let Some(node) = graph.node_mut(origin) else {
+        let Some(mut node) = graph.node_mut(origin) else {
             kani::assert(false, "Kani origin node must exist");
             return;
         };
-        let neighbours = node.neighbours_mut(level);
+        let mut neighbours = node.neighbours_mut(level);
         if !neighbours.contains(&target) {
             neighbours.push(target);
         }
@@ -21,11 +21,11 @@ pub(crate) fn add_edge_if_missing(graph: &mut Graph, origin: usize, target: usiz
 
     #[cfg(not(kani))]
     {
-        let Some(node) = graph.node_mut(origin) else {
+        let Some(mut node) = graph.node_mut(origin) else {
             debug_assert!(false, "missing origin node {origin}");
             return;
         };
-        let neighbours = node.neighbours_mut(level);
+        let mut neighbours = node.neighbours_mut(level);
         if !neighbours.contains(&target) {
             neighbours.push(target);
         }
@@ -57,7 +57,7 @@ impl<'graph> TestHelpers<'graph> {
         not(debug_assertions),
         expect(dead_code, reason = "test helper unused in release builds")
     )]
-    pub(super) fn heal_reachability(&mut self, max_connections: usize) {
+    pub(super) fn heal_reachability(&mut self, limits: ConnectionLimits) {
         let Some(entry) = self.graph.entry() else {
             return;
         };
@@ -77,7 +77,7 @@ impl<'graph> TestHelpers<'graph> {
 
             let mut progress = false;
             for node_id in unreachable {
-                progress |= self.try_connect_unreachable_node(node_id, &visited, max_connections);
+                progress |= self.try_connect_unreachable_node(node_id, &visited, limits);
             }
 
             if !progress {
@@ -90,14 +90,14 @@ impl<'graph> TestHelpers<'graph> {
         &mut self,
         node_id: usize,
         visited: &[bool],
-        max_connections: usize,
+        limits: ConnectionLimits,
     ) -> bool {
-        let base_limit = compute_connection_limit(0, max_connections);
+        let base_limit = compute_connection_limit(0, limits);
         if let Some(origin) = self.first_reachable_with_capacity(visited, base_limit) {
             let ctx = UpdateContext {
                 origin,
                 level: 0,
-                max_connections,
+                limits,
             };
             let mut healer = ConnectivityHealer::new(self.graph);
             if healer.link_new_node(&ctx, node_id) {
@@ -109,7 +109,7 @@ impl<'graph> TestHelpers<'graph> {
             let ctx = UpdateContext {
                 origin,
                 level: 0,
-                max_connections,
+                limits,
             };
             let mut healer = ConnectivityHealer::new(self.graph);
             if healer.link_new_node(&ctx, node_id) {
@@ -176,17 +176,17 @@ impl<'graph> TestHelpers<'graph> {
         not(debug_assertions),
         expect(dead_code, reason = "test helper unused in release builds")
     )]
-    pub(super) fn enforce_bidirectional_all(&mut self, max_connections: usize) {
+    pub(super) fn enforce_bidirectional_all(&mut self, limits: ConnectionLimits) {
         for (origin, level, target) in self.collect_edges() {
             let ctx = UpdateContext {
                 origin,
                 level,
-                max_connections,
+                limits,
             };
             self.heal_or_remove_edge(&ctx, target);
         }
 
-        self.validate_all_edges_reciprocal(max_connections);
+        self.validate_all_edges_reciprocal(limits);
     }
 
     pub(super) fn collect_edges(&self) -> Vec<(usize, usize, usize)> {
@@ -200,11 +200,11 @@ impl<'graph> TestHelpers<'graph> {
     }
 
     pub(super) fn heal_or_remove_edge(&mut self, ctx: &UpdateContext, target: usize) {
-        if let Some(target_node) = self.graph.node_mut(target)
+        if let Some(mut target_node) = self.graph.node_mut(target)
             && ctx.level < target_node.level_count()
         {
-            let limit = compute_connection_limit(ctx.level, ctx.max_connections);
-            let neighbours = target_node.neighbours_mut(ctx.level);
+            let limit = compute_connection_limit(ctx.level, ctx.limits);
+            let mut neighbours = target_node.neighbours_mut(ctx.level);
             if neighbours.contains(&ctx.origin) {
                 return;
             }
@@ -223,7 +223,7 @@ impl<'graph> TestHelpers<'graph> {
         clippy::excessive_nesting,
         reason = "test-only reciprocal validation keeps explicit panic messages"
     )]
-    pub(super) fn validate_all_edges_reciprocal(&self, max_connections: usize) {
+    pub(super) fn validate_all_edges_reciprocal(&self, limits: ConnectionLimits) {
         for (origin, node) in self.graph.nodes_iter() {
             for (level, target) in node.iter_neighbours() {
                 let target_node = match self.graph.node(target) {
@@ -242,7 +242,7 @@ impl<'graph> TestHelpers<'graph> {
                 );
 
                 let neighbours = target_node.neighbours(level);
-                let limit = compute_connection_limit(level, max_connections);
+                let limit = compute_connection_limit(level, limits);
                 assert!(
                     neighbours.contains(&origin),
                     "enforce_bidirectional_all left one-way edge {origin}->{target} at level {level}; target degree {} (limit {limit})",
@@ -7,7 +7,10 @@
 
 use std::collections::{HashMap, HashSet};
 
-use crate::hnsw::graph::{EdgeContext, NodeContext};
+use crate::hnsw::{
+    graph::{EdgeContext, NodeContext},
+    params::ConnectionLimits,
+};
 
 /// Captures the neighbour candidates for a node that may require trimming.
 ///
@@ -21,7 +24,7 @@ use crate::hnsw::graph::{EdgeContext, NodeContext};
 ///     insert::TrimJob,
 /// };
 ///
-/// let ctx = EdgeContext { level: 0, max_connections: 2 };
+/// let ctx = EdgeContext { level: 0, limits: 2 };
 /// let job = TrimJob {
 ///     node: 1,
 ///     ctx,
@@ -44,7 +47,7 @@ pub(crate) struct PreparedInsertion {
     pub(crate) promote_entry: bool,
     pub(crate) new_node_neighbours: Vec<Vec<usize>>,
     pub(crate) updates: Vec<StagedUpdate>,
-    pub(crate) max_connections: usize,
+    pub(crate) limits: ConnectionLimits,
 }
 
 /// Captures the staged neighbour set for a node at a given level.
@@ -77,7 +80,7 @@ pub(super) struct LayerProcessingOutcome {
 pub(super) struct TrimWork {
     pub(super) staged: HashMap<(usize, usize), Vec<usize>>,
     pub(super) needs_trim: HashSet<(usize, usize)>,
-    pub(super) max_connections: usize,
+    pub(super) limits: ConnectionLimits,
 }
 
 #[derive(Clone, Copy)]
@@ -90,13 +93,13 @@ pub(crate) struct NewNodeContext {
 pub(super) struct UpdateContext {
     pub(super) origin: usize,
     pub(super) level: usize,
-    pub(super) max_connections: usize,
+    pub(super) limits: ConnectionLimits,
 }
 
 #[derive(Clone, Copy)]
 pub(super) struct LinkContext {
     pub(super) level: usize,
-    pub(super) max_connections: usize,
+    pub(super) limits: ConnectionLimits,
     pub(super) new_node: usize,
 }
 
@@ -104,7 +107,7 @@ pub(super) struct LinkContext {
 pub(super) struct HealingContext<'a> {
     pub(super) filtered_new_node_neighbours: &'a [Vec<usize>],
     pub(super) new_node_id: usize,
-    pub(super) max_connections: usize,
+    pub(super) limits: ConnectionLimits,
 }
 
 /// A deferred scrub request collected during reconciliation.
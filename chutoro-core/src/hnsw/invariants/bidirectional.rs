@@ -23,28 +23,37 @@ pub(super) fn check_bidirectional(
         trace!(edges = edge_count, "checking bidirectional links");
     }
     for_each_edge(ctx.graph, |source, target, level| {
-        trace!(source, target, level, "checking edge for backlink");
-        match validator.ensure(source, target, level) {
-            Ok(neighbour) => {
-                let neighbours = neighbour.neighbours(level);
-                if neighbours.contains(&source) {
-                    Ok(())
-                } else {
-                    debug!(
-                        source,
-                        target,
-                        level,
-                        neighbours = ?neighbours,
-                        "missing backlink"
-                    );
-                    mode.record(HnswInvariantViolation::MissingBacklink {
-                        origin: source,
-                        target,
-                        layer: level,
-                    })
-                }
+        check_backlink(&validator, (source, target, level), mode)
+    })
+}
+
+/// Checks that `target` exists at `level` and links back to `source`.
+pub(super) fn check_backlink(
+    validator: &LayerValidator<'_>,
+    (source, target, level): (usize, usize, usize),
+    mode: &mut EvaluationMode<'_>,
+) -> Result<(), HnswInvariantViolation> {
+    trace!(source, target, level, "checking edge for backlink");
+    match validator.ensure(source, target, level) {
+        Ok(neighbour) => {
+            let neighbours = neighbour.neighbours(level);
+            if neighbours.contains(&source) {
+                Ok(())
+            } else {
+                debug!(
+                    source,
+                    target,
+                    level,
+                    neighbours = ?neighbours,
+                    "missing backlink"
+                );
+                mode.record(HnswInvariantViolation::MissingBacklink {
+                    origin: source,
+                    target,
+                    layer: level,
+                })
             }
-            Err(err) => mode.record(err),
         }
-    })
+        Err(err) => mode.record(err),
+    }
 }
@@ -1,38 +1,42 @@
 //! Node degree-bound checks for the HNSW invariants suite.
 //!
 //! This module exports `check_degree_bounds`, which validates each node's
-//! neighbour count against the base-layer and upper-layer limits from
+//! neighbour count against the per-layer connection limits from
 //! `HnswParams`. It complements `layer_consistency.rs`, which validates
 //! whether those neighbours refer to valid layers, and `tests.rs`, which
 //! exercises both invariant checks through shared graph fixtures.
 
+use crate::hnsw::node::NodeRef;
+
 use super::{EvaluationMode, GraphContext, HnswInvariantViolation};
 
 pub(super) fn check_degree_bounds(
     ctx: GraphContext<'_>,
     mode: &mut EvaluationMode<'_>,
 ) -> Result<(), HnswInvariantViolation> {
-    let upper = ctx.params.max_connections();
-    let base_limit = upper
-        .checked_mul(2)
-        .ok_or_else(|| HnswInvariantViolation::ConfigError {
-            message: format!(
-                "max_connections ({upper}) overflowed when doubling for base-layer bounds"
-            ),
-        })?;
-
     for (node_id, node) in ctx.graph.nodes_iter() {
-        for level in 0..node.level_count() {
-            let limit = if level == 0 { base_limit } else { upper };
-            let degree = node.neighbours(level).len();
-            if degree > limit {
-                mode.record(HnswInvariantViolation::DegreeBounds {
-                    node: node_id,
-                    layer: level,
-                    degree,
-                    limit,
-                })?;
-            }
+        check_node_degrees(ctx, (node_id, node), mode)?;
+    }
+    Ok(())
+}
+
+/// Checks every layer of a single node against its connection limit.
+pub(super) fn check_node_degrees(
+    ctx: GraphContext<'_>,
+    (node_id, node): (usize, NodeRef<'_>),
+    mode: &mut EvaluationMode<'_>,
+) -> Result<(), HnswInvariantViolation> {
+    let limits = ctx.params.connection_limits();
+    for level in 0..node.level_count() {
+        let limit = limits.for_level(level);
+        let degree = node.neighbours(level).len();
+        if degree > limit {
+            mode.record(HnswInvariantViolation::DegreeBounds {
+                node: node_id,
+                layer: level,
+                degree,
+                limit,
+            })?;
         }
     }
     Ok(())
@@ -5,7 +5,7 @@
 //! the `kani` configuration, additional predicates are exposed for use in
 //! formal verification harnesses.
 
-use crate::hnsw::{graph::Graph, node::Node};
+use crate::hnsw::{graph::Graph, node::NodeRef};
 
 use super::{HnswInvariantViolation, LayerConsistencyDetail};
 
@@ -40,7 +40,7 @@ impl<'a> LayerValidator<'a> {
         origin: usize,
         target: usize,
         layer: usize,
-    ) -> Result<&'a Node, HnswInvariantViolation> {
+    ) -> Result<NodeRef<'a>, HnswInvariantViolation> {
         if target >= self.capacity {
             return Err(HnswInvariantViolation::LayerConsistency {
                 origin,
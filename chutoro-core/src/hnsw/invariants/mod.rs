@@ -9,6 +9,7 @@ mod degree_bounds;
 mod helpers;
 mod layer_consistency;
 mod reachability;
+mod touched;
 
 use std::fmt;
 
@@ -20,6 +21,7 @@ use crate::hnsw::{CpuHnsw, graph::Graph, params::HnswParams};
 use self::{
     bidirectional::check_bidirectional, degree_bounds::check_degree_bounds,
     layer_consistency::check_layer_consistency, reachability::check_reachability,
+    touched::check_touched,
 };
 
 #[cfg(kani)]
@@ -188,6 +190,54 @@ impl<'index> HnswInvariantChecker<'index> {
         self.check(HnswInvariant::BidirectionalLinks)
     }
 
+    /// Checks only the nodes in `touched` and the nodes they link to, on
+    /// every layer, returning the first violation encountered.
+    ///
+    /// Pass the nodes inserted since the last check: insertion rewrites only
+    /// their lists and those of their neighbours, so this validates layer
+    /// consistency, degree bounds, and bidirectional links for everything an
+    /// insertion can change, at a cost proportional to the neighbourhoods
+    /// rather than the graph. Reachability needs a full traversal and is not
+    /// checked; run [`Self::reachability`] for that.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{CpuHnsw, DataSource, DataSourceError, HnswParams};
+    /// # struct Dummy(Vec<f32>);
+    /// # impl DataSource for Dummy {
+    /// #     fn len(&self) -> usize { self.0.len() }
+    /// #     fn name(&self) -> &str { "dummy" }
+    /// #     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+    /// #         Ok((self.0[i] - self.0[j]).abs())
+    /// #     }
+    /// # }
+    /// let data = Dummy(vec![0.0, 1.0, 3.5, 4.0]);
+    /// let index = CpuHnsw::with_capacity(HnswParams::new(2, 4).expect("params"), 4)
+    ///     .expect("capacity");
+    /// for node in 0..4 {
+    ///     index.insert(node, &data).expect("insert");
+    ///     index.invariants().check_touched(&[node]).expect("neighbourhood is sound");
+    /// }
+    /// ```
+    pub fn check_touched(&self, touched: &[usize]) -> Result<(), HnswInvariantViolation> {
+        self.with_context(|ctx| check_touched(ctx, touched, &mut EvaluationMode::FailFast))
+    }
+
+    /// Runs the checks of [`Self::check_touched`] and returns every violation
+    /// discovered.
+    #[must_use]
+    pub fn collect_touched(&self, touched: &[usize]) -> Vec<HnswInvariantViolation> {
+        let mut violations = Vec::new();
+        let mut mode = EvaluationMode::Collect {
+            sink: &mut violations,
+            log: false,
+        };
+        if let Err(err) = self.with_context(|ctx| check_touched(ctx, touched, &mut mode)) {
+            violations.push(err);
+        }
+        violations
+    }
+
     /// Executes every invariant and returns the full set of violations.
     #[must_use]
     pub fn collect_all(&self) -> Vec<HnswInvariantViolation> {
@@ -206,11 +206,11 @@ mod tests {
             level: 0,
             sequence: 1,
         })?;
-        let Some(node_zero) = graph.node_mut(0) else {
+        let Some(mut node_zero) = graph.node_mut(0) else {
             panic!("node 0 should exist");
         };
         node_zero.neighbours_mut(0).push(1);
-        let Some(node_one) = graph.node_mut(1) else {
+        let Some(mut node_one) = graph.node_mut(1) else {
             panic!("node 1 should exist");
         };
         node_one.neighbours_mut(0).push(0);
@@ -41,8 +41,8 @@ fn collect_many_with_logging_reports_degree_violation() {
             .graph
             .write()
             .unwrap_or_else(std::sync::PoisonError::into_inner);
-        if let Some(node) = graph.node_mut(0) {
-            let neighbours = node.neighbours_mut(0);
+        if let Some(mut node) = graph.node_mut(0) {
+            let mut neighbours = node.neighbours_mut(0);
             neighbours.clear();
             neighbours.extend(std::iter::repeat_n(1, 10));
         }
@@ -60,7 +60,7 @@ fn collect_many_with_logging_reports_degree_violation() {
 }
 
 fn clear_node(graph: &mut Graph, node_id: usize) {
-    if let Some(node) = graph.node_mut(node_id) {
+    if let Some(mut node) = graph.node_mut(node_id) {
         let levels = node.level_count();
         for level in 0..levels {
             node.neighbours_mut(level).clear();
@@ -234,7 +234,8 @@ fn duplicate_neighbour_is_detectable() {
         .expect("attach node");
 
     // Manually inject a duplicate (this is invalid)
-    let neighbours = graph.node_mut(0).expect("node").neighbours_mut(0);
+    let mut node = graph.node_mut(0).expect("node");
+    let mut neighbours = node.neighbours_mut(0);
     neighbours.push(1);
     neighbours.push(1); // Duplicate
 
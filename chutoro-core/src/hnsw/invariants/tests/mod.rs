@@ -120,7 +120,8 @@ fn degree_bounds_detects_overflow(#[case] level: usize, #[case] degree: usize) {
             .push(0);
     }
 
-    let node = graph.node_mut(0).expect("entry").neighbours_mut(level);
+    let mut entry = graph.node_mut(0).expect("entry");
+    let mut node = entry.neighbours_mut(level);
     node.clear();
     node.extend(1..=degree);
 
@@ -204,4 +205,5 @@ fn collect_all_reports_multiple_violations() {
 }
 
 mod collection;
+mod touched;
 use collection::assert_collects_unreachable_nodes;
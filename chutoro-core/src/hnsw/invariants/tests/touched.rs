@@ -0,0 +1,138 @@
+//! Invariant tests for checks scoped to touched nodes.
+
+use super::*;
+use crate::hnsw::invariants::LayerConsistencyDetail;
+
+fn soak_source() -> Dummy {
+    Dummy(
+        (0..120)
+            .map(|i| (i * 37 % 101) as f32 + i as f32 * 1.0e-3)
+            .collect(),
+    )
+}
+
+fn build_soak_index() -> (CpuHnsw, Dummy) {
+    let data = soak_source();
+    let params = HnswParams::new(4, 16).expect("params").with_rng_seed(3);
+    let index = CpuHnsw::build(&data, params).expect("build hnsw");
+    (index, data)
+}
+
+/// Removes `origin` from `target`'s base-layer list, leaving `origin -> target`
+/// without its backlink.
+fn drop_backlink(index: &CpuHnsw, origin: usize, target: usize) {
+    let mut graph = index
+        .graph
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    graph
+        .node_mut(target)
+        .expect("target exists")
+        .neighbours_mut(0)
+        .retain(|&id| id != origin);
+}
+
+fn base_neighbours(index: &CpuHnsw, node: usize) -> Vec<usize> {
+    let graph = index
+        .graph
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    graph
+        .node(node)
+        .expect("node exists")
+        .neighbours(0)
+        .to_vec()
+}
+
+fn links_to(index: &CpuHnsw, node: usize, target: usize) -> bool {
+    let graph = index
+        .graph
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    graph
+        .node(node)
+        .expect("node exists")
+        .iter_neighbours()
+        .any(|(_, neighbour)| neighbour == target)
+}
+
+#[test]
+fn check_touched_passes_after_every_insertion() {
+    let data = soak_source();
+    let params = HnswParams::new(4, 16).expect("params").with_rng_seed(3);
+    let index = CpuHnsw::with_capacity(params, data.0.len()).expect("capacity");
+    for node in 0..data.0.len() {
+        index.insert(node, &data).expect("insert");
+        index
+            .invariants()
+            .check_touched(&[node])
+            .expect("touched neighbourhood must be sound");
+    }
+    index.invariants().check_all().expect("graph valid");
+}
+
+#[test]
+fn check_touched_reports_broken_edges_of_touched_nodes() {
+    let (index, _data) = build_soak_index();
+    let target = base_neighbours(&index, 0)[0];
+    drop_backlink(&index, 0, target);
+
+    let err = index
+        .invariants()
+        .check_touched(&[0])
+        .expect_err("missing backlink must be reported");
+    assert_eq!(
+        err,
+        HnswInvariantViolation::MissingBacklink {
+            origin: 0,
+            target,
+            layer: 0,
+        }
+    );
+}
+
+#[test]
+fn check_touched_ignores_nodes_outside_the_neighbourhood() {
+    let (index, data) = build_soak_index();
+    let target = base_neighbours(&index, 0)[0];
+    drop_backlink(&index, 0, target);
+    let distant = (1..data.0.len())
+        .find(|&node| !links_to(&index, node, 0))
+        .expect("some node does not link to node 0");
+
+    assert!(index.invariants().collect_touched(&[distant]).is_empty());
+    assert!(!index.invariants().collect_all().is_empty());
+}
+
+#[test]
+fn collect_touched_reports_missing_and_overfull_nodes() {
+    let (index, _data) = build_index();
+    {
+        let mut graph = index
+            .graph
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let mut node = graph.node_mut(1).expect("node 1");
+        let mut neighbours = node.neighbours_mut(0);
+        neighbours.clear();
+        neighbours.extend(std::iter::repeat_n(2, 10));
+    }
+
+    let violations = index.invariants().collect_touched(&[1, 9]);
+    assert!(violations.iter().any(|violation| matches!(
+        violation,
+        HnswInvariantViolation::LayerConsistency {
+            origin: 9,
+            detail: LayerConsistencyDetail::MissingNode,
+            ..
+        }
+    )));
+    assert!(violations.iter().any(|violation| matches!(
+        violation,
+        HnswInvariantViolation::DegreeBounds {
+            node: 1,
+            degree: 10,
+            ..
+        }
+    )));
+}
@@ -0,0 +1,78 @@
+//! Incremental invariant checks scoped to recently modified nodes.
+//!
+//! Insertion only rewrites the lists of the inserted node and of the
+//! neighbours it links to, so re-validating that neighbourhood catches the
+//! corruption a single insertion can introduce without walking the whole
+//! graph. Reachability is a property of the whole graph and stays with the
+//! full checks.
+
+use crate::hnsw::graph::Graph;
+
+use super::{
+    EvaluationMode, GraphContext, HnswInvariantViolation, LayerConsistencyDetail,
+    bidirectional::check_backlink, degree_bounds::check_node_degrees, helpers::LayerValidator,
+};
+
+/// Checks degree bounds, layer consistency, and backlinks for every node in
+/// `touched` and every node they link to, on any layer.
+///
+/// A touched node missing from the graph is recorded as a
+/// [`HnswInvariantViolation::LayerConsistency`] violation.
+pub(super) fn check_touched(
+    ctx: GraphContext<'_>,
+    touched: &[usize],
+    mode: &mut EvaluationMode<'_>,
+) -> Result<(), HnswInvariantViolation> {
+    let mut scope = Vec::with_capacity(touched.len());
+    for &node_id in touched {
+        if ctx.graph.node(node_id).is_none() {
+            mode.record(HnswInvariantViolation::LayerConsistency {
+                origin: node_id,
+                target: node_id,
+                layer: 0,
+                detail: LayerConsistencyDetail::MissingNode,
+            })?;
+            continue;
+        }
+        extend_scope(ctx.graph, node_id, &mut scope);
+    }
+    scope.sort_unstable();
+    scope.dedup();
+
+    let validator = LayerValidator::new(ctx.graph);
+    for node_id in scope {
+        check_node(ctx, &validator, node_id, mode)?;
+    }
+    Ok(())
+}
+
+fn extend_scope(graph: &Graph, node_id: usize, scope: &mut Vec<usize>) {
+    scope.push(node_id);
+    if let Some(node) = graph.node(node_id) {
+        scope.extend(node.iter_neighbours().map(|(_, target)| target));
+    }
+}
+
+/// Checks one node's degrees and outgoing edges, and its level when it is
+/// the entry point. Neighbours that do not exist are skipped here because
+/// the edge pointing at them is reported from the node that holds it.
+fn check_node(
+    ctx: GraphContext<'_>,
+    validator: &LayerValidator<'_>,
+    node_id: usize,
+    mode: &mut EvaluationMode<'_>,
+) -> Result<(), HnswInvariantViolation> {
+    let Some(node) = ctx.graph.node(node_id) else {
+        return Ok(());
+    };
+    check_node_degrees(ctx, (node_id, node), mode)?;
+    for (level, target) in node.iter_neighbours() {
+        check_backlink(validator, (node_id, target, level), mode)?;
+    }
+    if let Some(entry) = ctx.graph.entry().filter(|entry| entry.node == node_id)
+        && let Err(err) = validator.ensure(entry.node, entry.node, entry.level)
+    {
+        mode.record(err)?;
+    }
+    Ok(())
+}
@@ -10,7 +10,7 @@ use crate::hnsw::{
         ensure_reverse_edge_for_kani, test_helpers::add_edge_if_missing,
     },
     invariants::is_bidirectional,
-    params::HnswParams,
+    params::{ConnectionLimits, HnswParams},
 };
 
 /// Smoke-checks that a tiny symmetric graph satisfies the invariant.
@@ -57,10 +57,10 @@ fn verify_bidirectional_links_smoke_2_nodes_1_layer() {
 /// between nodes 0 and 2 so that node 0's level-1 neighbour list is at
 /// capacity before the commit path runs.
 ///
-/// Returns `(graph, max_connections)` on success.
-fn setup_commit_path_graph() -> Result<(Graph, usize), HnswError> {
+/// Returns `(graph, limits)` on success.
+fn setup_commit_path_graph() -> Result<(Graph, ConnectionLimits), HnswError> {
     let params = HnswParams::new(1, 2)?;
-    let max_connections = params.max_connections();
+    let limits = params.connection_limits();
     let mut graph = Graph::with_capacity(params, 3);
     graph.insert_first(NodeContext {
         node: 0,
@@ -79,7 +79,7 @@ fn setup_commit_path_graph() -> Result<(Graph, usize), HnswError> {
     })?;
     add_edge_if_missing(&mut graph, 0, 2, 1);
     add_edge_if_missing(&mut graph, 2, 0, 1);
-    Ok((graph, max_connections))
+    Ok((graph, limits))
 }
 
 /// Verifies that HNSW graph edges are bidirectional (symmetric).
@@ -108,7 +108,7 @@ fn setup_commit_path_graph() -> Result<(Graph, usize), HnswError> {
 #[kani::proof]
 #[kani::unwind(10)]
 fn verify_bidirectional_links_commit_path_3_nodes() {
-    let Ok((mut graph, max_connections)) = setup_commit_path_graph() else {
+    let Ok((mut graph, limits)) = setup_commit_path_graph() else {
         kani::assert(false, "commit-path graph setup must succeed");
         return;
     };
@@ -130,10 +130,7 @@ fn verify_bidirectional_links_commit_path_3_nodes() {
         "node 2 must contain seeded level-1 edge to node 0",
     );
 
-    let update_ctx = EdgeContext {
-        level: 1,
-        max_connections,
-    };
+    let update_ctx = EdgeContext { level: 1, limits };
     let staged = StagedUpdate {
         node: 1,
         ctx: update_ctx,
@@ -141,7 +138,7 @@ fn verify_bidirectional_links_commit_path_3_nodes() {
     };
     let updates: Vec<FinalisedUpdate> = vec![(staged, vec![0])];
     let new_node = NewNodeContext { id: 1, level: 1 };
-    apply_commit_updates_for_kani(&mut graph, max_connections, new_node, updates)
+    apply_commit_updates_for_kani(&mut graph, limits, new_node, updates)
         .expect("commit-path updates must succeed");
 
     kani::assert(
@@ -179,7 +176,7 @@ fn verify_bidirectional_links_reconciliation_2_nodes_1_layer() {
         kani::assert(false, "Kani params must be valid");
         return;
     };
-    let max_connections = params.max_connections();
+    let limits = params.connection_limits();
     let mut graph = Graph::with_capacity(params, 2);
 
     let inserted = graph
@@ -201,7 +198,7 @@ fn verify_bidirectional_links_reconciliation_2_nodes_1_layer() {
     let should_link = kani::any::<bool>();
     if should_link {
         add_edge_if_missing(&mut graph, 0, 1, 0);
-        let ctx = KaniUpdateContext::new(0, 0, max_connections);
+        let ctx = KaniUpdateContext::new(0, 0, limits);
         let added = ensure_reverse_edge_for_kani(&mut graph, ctx, 1);
         kani::assert(added, "expected reverse edge to be inserted");
     }
@@ -220,7 +217,7 @@ fn verify_bidirectional_links_reconciliation_2_nodes_1_layer() {
 #[kani::unwind(10)]
 fn verify_bidirectional_links_reconciliation_3_nodes_1_layer() {
     let params = HnswParams::new(2, 2).expect("params must be valid");
-    let max_connections = params.max_connections();
+    let limits = params.connection_limits();
     let mut graph = Graph::with_capacity(params, 3);
 
     graph
@@ -265,7 +262,7 @@ fn verify_bidirectional_links_reconciliation_3_nodes_1_layer() {
         push_if_absent(&mut next, 2);
     }
 
-    let ctx = KaniUpdateContext::new(0, 0, max_connections);
+    let ctx = KaniUpdateContext::new(0, 0, limits);
     apply_reconciled_update_for_kani(&mut graph, ctx, &mut next);
 
     kani::assert(
@@ -78,7 +78,7 @@ fn verify_eviction_deferred_scrub_reciprocity() {
         kani::assert(false, "failed to construct eviction HNSW params");
         return;
     };
-    let max_connections = params.max_connections();
+    let limits = params.connection_limits();
     let setup_result = setup_eviction_test_graph(params);
     kani::assert(
         setup_result.is_ok(),
@@ -96,10 +96,7 @@ fn verify_eviction_deferred_scrub_reciprocity() {
     // Update: node 0 adds node 1 as neighbour at level 1.
     // When ensure_reverse_edge(origin=0, target=1) runs, node 1 is at
     // capacity, so node 2 is evicted and a deferred scrub is created.
-    let update_ctx = EdgeContext {
-        level: 1,
-        max_connections,
-    };
+    let update_ctx = EdgeContext { level: 1, limits };
     let staged = StagedUpdate {
         node: 0,
         ctx: update_ctx,
@@ -108,8 +105,7 @@ fn verify_eviction_deferred_scrub_reciprocity() {
     let updates: Vec<FinalisedUpdate> = vec![(staged, vec![1])];
     let new_node = NewNodeContext { id: 3, level: 1 };
 
-    let commit_result =
-        apply_commit_updates_for_kani(&mut graph, max_connections, new_node, updates);
+    let commit_result = apply_commit_updates_for_kani(&mut graph, limits, new_node, updates);
     kani::assert(commit_result.is_ok(), "commit-path updates must succeed");
     if commit_result.is_err() {
         return;
@@ -131,7 +131,7 @@ fn verify_no_self_loops_4_nodes() {
         kani::assert(false, "failed to construct bounded HNSW params");
         return;
     };
-    let max_connections = params.max_connections();
+    let limits = params.connection_limits();
     let Some(mut graph) = setup_four_node_graph(params) else {
         return;
     };
@@ -139,7 +139,7 @@ fn verify_no_self_loops_4_nodes() {
     let level = symbolic_update_level();
     let origin = update_origin_for_level(level);
     let target = bounded_node_id_for_kani();
-    let ctx = KaniUpdateContext::new(origin, level, max_connections);
+    let ctx = KaniUpdateContext::new(origin, level, limits);
     let mut next = deduped_targets(target, upper_layer_peer(origin));
     apply_reconciled_update_for_kani(&mut graph, ctx, &mut next);
 
@@ -147,7 +147,7 @@ fn verify_no_self_loops_4_nodes() {
         let second_level = symbolic_update_level();
         let second_origin = update_origin_for_level(second_level);
         let second_target = bounded_node_id_for_kani();
-        let second_ctx = KaniUpdateContext::new(second_origin, second_level, max_connections);
+        let second_ctx = KaniUpdateContext::new(second_origin, second_level, limits);
         let mut second_next = deduped_targets(second_target, upper_layer_peer(second_origin));
         apply_reconciled_update_for_kani(&mut graph, second_ctx, &mut second_next);
     }
@@ -175,7 +175,7 @@ fn verify_neighbour_uniqueness_4_nodes() {
         kani::assert(false, "failed to construct bounded HNSW params");
         return;
     };
-    let max_connections = params.max_connections();
+    let limits = params.connection_limits();
     let Some(mut graph) = setup_four_node_graph(params) else {
         return;
     };
@@ -184,7 +184,7 @@ fn verify_neighbour_uniqueness_4_nodes() {
     let origin = update_origin_for_level(level);
     let first_target = bounded_node_id_for_kani();
     let second_target = bounded_node_id_for_kani();
-    let ctx = KaniUpdateContext::new(origin, level, max_connections);
+    let ctx = KaniUpdateContext::new(origin, level, limits);
     let mut next = deduped_targets(first_target, upper_layer_peer(origin));
     apply_reconciled_update_for_kani(&mut graph, ctx, &mut next);
 
@@ -0,0 +1,127 @@
+//! Loom model checks for the CPU HNSW locking protocol.
+//!
+//! `CpuHnsw` inserts hold the insert mutex across three graph-lock sections
+//! (read to plan, write to apply, write to commit trims) while searches and
+//! invariant checks take the read lock and consult the distance cache.
+//! These models replay that choreography on a miniature index built from
+//! `loom` primitives, so every interleaving of concurrent inserts, searches,
+//! and checks is explored within the preemption bound. Loom fails a model on
+//! deadlock, and a panic inside any critical section (what would poison a
+//! real lock) fails it too.
+//!
+//! # Running the models
+//!
+//! ```bash
+//! make loom
+//! ```
+//!
+//! or directly:
+//!
+//! ```bash
+//! cargo test -p chutoro-core --features loom loom_models
+//! ```
+//!
+//! Changes to the lock order in `hnsw/cpu` or `distance_cache.rs` should be
+//! reflected in [`protocol`] so the models keep describing the real index.
+
+mod protocol;
+
+use loom::{sync::Arc, thread};
+
+use self::protocol::{MiniIndex, POSITIONS};
+
+/// Bounds the preemptions loom explores per execution; three is enough to
+/// interleave every lock section of two inserts with a reader.
+const PREEMPTION_BOUND: usize = 3;
+
+fn check(model: impl Fn() + Sync + Send + 'static) {
+    let mut builder = loom::model::Builder::new();
+    builder.preemption_bound = Some(PREEMPTION_BOUND);
+    builder.check(model);
+}
+
+/// Returns an index holding the first two points, with a degree bound small
+/// enough that later inserts trigger trimming and a cache small enough to
+/// evict.
+fn seeded_index() -> Arc<MiniIndex> {
+    let index = MiniIndex::new(2, 2);
+    index.insert(0).expect("seed insert must succeed");
+    index.insert(1).expect("seed insert must succeed");
+    Arc::new(index)
+}
+
+#[test]
+fn concurrent_inserts_and_search_settle_into_a_valid_graph() {
+    check(|| {
+        let index = seeded_index();
+        let inserters: Vec<_> = [2, 3]
+            .into_iter()
+            .map(|node| {
+                let shared = Arc::clone(&index);
+                thread::spawn(move || shared.insert(node))
+            })
+            .collect();
+
+        let found = index.search(3).expect("search must not poison");
+
+        for inserter in inserters {
+            inserter
+                .join()
+                .expect("inserter must not panic")
+                .expect("insert must not poison");
+        }
+        assert!(found.is_some(), "search over a seeded graph finds a node");
+        assert_eq!(
+            index.nodes().expect("graph lock"),
+            (0..POSITIONS.len()).collect::<Vec<_>>()
+        );
+        assert_eq!(index.missing_backlink().expect("graph lock"), None);
+        assert!(index.max_observed_degree().expect("graph lock") <= 2);
+    });
+}
+
+#[test]
+fn checkers_never_observe_a_one_way_edge() {
+    check(|| {
+        let index = seeded_index();
+        let shared = Arc::clone(&index);
+        let inserter = thread::spawn(move || {
+            shared.insert(2)?;
+            shared.insert(3)
+        });
+
+        // Apply and commit each leave links symmetric, so a reader between
+        // them may see an over-full list but never a missing backlink.
+        let observed = index.missing_backlink().expect("graph lock");
+
+        inserter
+            .join()
+            .expect("inserter must not panic")
+            .expect("insert must not poison");
+        assert_eq!(observed, None);
+    });
+}
+
+#[test]
+fn concurrent_searches_share_the_cache_without_deadlock() {
+    check(|| {
+        let index = seeded_index();
+        let searchers: Vec<_> = [0, 1]
+            .into_iter()
+            .map(|query| {
+                let shared = Arc::clone(&index);
+                thread::spawn(move || shared.search(query))
+            })
+            .collect();
+
+        index.insert(2).expect("insert must not poison");
+
+        for (query, searcher) in [0, 1].into_iter().zip(searchers) {
+            let found = searcher
+                .join()
+                .expect("searcher must not panic")
+                .expect("search must not poison");
+            assert_eq!(found, Some(query));
+        }
+    });
+}
@@ -0,0 +1,275 @@
+//! A miniature index that replays the CPU HNSW locking protocol on loom
+//! primitives.
+//!
+//! Only the lock choreography is faithful; the graph is a single layer and
+//! "distance" is the gap between fixed positions on a line. The lock order
+//! mirrors [`crate::hnsw::CpuHnsw`]:
+//!
+//! - inserts take the insert mutex, then the level RNG, then the graph lock in
+//!   three separate sections (read to plan, write to apply, write to commit
+//!   trims), scoring trims through the cache with no graph lock held;
+//! - searches take only the graph read lock and consult the cache under it;
+//! - the cache reads its entry map, releases it, then updates the LRU usage
+//!   list and removes evicted entries while holding that list, as the sharded
+//!   `DistanceCache` does.
+
+use loom::sync::{Mutex, RwLock};
+
+/// Positions of the model's points; node ids index this array.
+pub(super) const POSITIONS: [u32; 4] = [0, 1, 3, 6];
+
+/// Failure reported by the model, mirroring `HnswError::LockPoisoned`.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct Poisoned(pub(super) &'static str);
+
+#[derive(Debug, Default)]
+struct MiniGraph {
+    adjacency: [Option<Vec<usize>>; POSITIONS.len()],
+    entry: Option<usize>,
+}
+
+impl MiniGraph {
+    fn unlink(&mut self, origin: usize, target: usize) {
+        if let Some(Some(list)) = self.adjacency.get_mut(origin) {
+            list.retain(|node| *node != target);
+        }
+    }
+}
+
+/// Distance cache with a bounded usage list, modelled on `DistanceCache`.
+#[derive(Debug)]
+struct MiniCache {
+    entries: Mutex<Vec<((usize, usize), u32)>>,
+    usage: Mutex<Vec<(usize, usize)>>,
+    capacity: usize,
+}
+
+impl MiniCache {
+    fn distance(&self, left: usize, right: usize) -> Result<u32, Poisoned> {
+        let key = (left.min(right), left.max(right));
+        let cached = self
+            .entries
+            .lock()
+            .map_err(|_| Poisoned("cache entries"))?
+            .iter()
+            .find_map(|(stored, value)| (*stored == key).then_some(*value));
+        let value = match cached {
+            Some(value) => value,
+            None => {
+                let value = position(left).abs_diff(position(right));
+                self.entries
+                    .lock()
+                    .map_err(|_| Poisoned("cache entries"))?
+                    .push((key, value));
+                value
+            }
+        };
+        self.touch(key)?;
+        Ok(value)
+    }
+
+    fn touch(&self, key: (usize, usize)) -> Result<(), Poisoned> {
+        let mut usage = self.usage.lock().map_err(|_| Poisoned("cache usage"))?;
+        usage.retain(|used| *used != key);
+        usage.push(key);
+        if usage.len() > self.capacity {
+            let evicted = usage.remove(0);
+            self.entries
+                .lock()
+                .map_err(|_| Poisoned("cache entries"))?
+                .retain(|(stored, _)| *stored != evicted);
+        }
+        Ok(())
+    }
+}
+
+fn position(node: usize) -> u32 {
+    POSITIONS.get(node).copied().unwrap_or(u32::MAX)
+}
+
+/// The miniature index shared between model threads.
+#[derive(Debug)]
+pub(super) struct MiniIndex {
+    graph: RwLock<MiniGraph>,
+    insert_mutex: Mutex<()>,
+    rng: Mutex<u64>,
+    cache: MiniCache,
+    max_degree: usize,
+}
+
+impl MiniIndex {
+    pub(super) fn new(max_degree: usize, cache_capacity: usize) -> Self {
+        Self {
+            graph: RwLock::new(MiniGraph::default()),
+            insert_mutex: Mutex::new(()),
+            rng: Mutex::new(0),
+            cache: MiniCache {
+                entries: Mutex::new(Vec::new()),
+                usage: Mutex::new(Vec::new()),
+                capacity: cache_capacity,
+            },
+            max_degree,
+        }
+    }
+
+    /// Inserts `node` following the CPU index's three-section protocol.
+    pub(super) fn insert(&self, node: usize) -> Result<(), Poisoned> {
+        let _insertion = self
+            .insert_mutex
+            .lock()
+            .map_err(|_| Poisoned("insert mutex"))?;
+        *self.rng.lock().map_err(|_| Poisoned("rng"))? += 1;
+        if self.try_insert_initial(node)? {
+            return Ok(());
+        }
+        let plan = self.plan(node)?;
+        let trims = self.apply(node, plan)?;
+        let scored = trims
+            .into_iter()
+            .map(|(owner, neighbours)| Ok((owner, self.nearest(owner, neighbours)?)))
+            .collect::<Result<Vec<_>, Poisoned>>()?;
+        self.commit(scored)
+    }
+
+    fn try_insert_initial(&self, node: usize) -> Result<bool, Poisoned> {
+        let mut graph = self.graph.write().map_err(|_| Poisoned("graph"))?;
+        if graph.entry.is_some() {
+            return Ok(false);
+        }
+        if let Some(slot) = graph.adjacency.get_mut(node) {
+            *slot = Some(Vec::new());
+        }
+        graph.entry = Some(node);
+        Ok(true)
+    }
+
+    fn plan(&self, node: usize) -> Result<Vec<usize>, Poisoned> {
+        let graph = self.graph.read().map_err(|_| Poisoned("graph"))?;
+        let present = present_nodes(&graph);
+        self.nearest(node, present)
+    }
+
+    /// Links `node` both ways and returns the adjacency lists now over bound.
+    fn apply(&self, node: usize, plan: Vec<usize>) -> Result<Vec<(usize, Vec<usize>)>, Poisoned> {
+        let mut graph = self.graph.write().map_err(|_| Poisoned("graph"))?;
+        let mut trims = Vec::new();
+        for &neighbour in &plan {
+            let Some(Some(list)) = graph.adjacency.get_mut(neighbour) else {
+                continue;
+            };
+            list.push(node);
+            if list.len() > self.max_degree {
+                trims.push((neighbour, list.clone()));
+            }
+        }
+        if let Some(slot) = graph.adjacency.get_mut(node) {
+            *slot = Some(plan);
+        }
+        Ok(trims)
+    }
+
+    /// Installs trimmed lists and drops the reverse links of removed edges.
+    fn commit(&self, scored: Vec<(usize, Vec<usize>)>) -> Result<(), Poisoned> {
+        let mut graph = self.graph.write().map_err(|_| Poisoned("graph"))?;
+        for (owner, kept) in scored {
+            let previous = graph
+                .adjacency
+                .get_mut(owner)
+                .and_then(|slot| slot.replace(kept.clone()))
+                .unwrap_or_default();
+            for dropped in previous.into_iter().filter(|node| !kept.contains(node)) {
+                graph.unlink(dropped, owner);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the `max_degree` candidates closest to `query`, scoring them
+    /// through the cache.
+    fn nearest(&self, query: usize, candidates: Vec<usize>) -> Result<Vec<usize>, Poisoned> {
+        let mut scored = candidates
+            .into_iter()
+            .filter(|candidate| *candidate != query)
+            .map(|candidate| Ok((self.cache.distance(query, candidate)?, candidate)))
+            .collect::<Result<Vec<_>, Poisoned>>()?;
+        scored.sort_unstable();
+        scored.truncate(self.max_degree);
+        Ok(scored.into_iter().map(|(_, candidate)| candidate).collect())
+    }
+
+    /// Greedily walks from the entry point towards `query` under the read
+    /// lock, returning the closest node found.
+    pub(super) fn search(&self, query: usize) -> Result<Option<usize>, Poisoned> {
+        let graph = self.graph.read().map_err(|_| Poisoned("graph"))?;
+        let Some(mut current) = graph.entry else {
+            return Ok(None);
+        };
+        let mut best = self.cache.distance(query, current)?;
+        loop {
+            let neighbours = graph
+                .adjacency
+                .get(current)
+                .and_then(Option::as_ref)
+                .cloned()
+                .unwrap_or_default();
+            let scored = neighbours
+                .into_iter()
+                .map(|neighbour| Ok((self.cache.distance(query, neighbour)?, neighbour)))
+                .collect::<Result<Vec<_>, Poisoned>>()?;
+            match scored.into_iter().min() {
+                Some((distance, neighbour)) if distance < best => {
+                    (best, current) = (distance, neighbour);
+                }
+                _ => return Ok(Some(current)),
+            }
+        }
+    }
+
+    /// Returns the inserted nodes.
+    pub(super) fn nodes(&self) -> Result<Vec<usize>, Poisoned> {
+        let graph = self.graph.read().map_err(|_| Poisoned("graph"))?;
+        Ok(present_nodes(&graph))
+    }
+
+    /// Returns the first edge lacking its reverse link, if any.
+    pub(super) fn missing_backlink(&self) -> Result<Option<(usize, usize)>, Poisoned> {
+        let graph = self.graph.read().map_err(|_| Poisoned("graph"))?;
+        Ok(edges(&graph).find(|&(origin, target)| {
+            graph
+                .adjacency
+                .get(target)
+                .and_then(Option::as_ref)
+                .is_none_or(|list| !list.contains(&origin))
+        }))
+    }
+
+    /// Returns the largest adjacency list length.
+    pub(super) fn max_observed_degree(&self) -> Result<usize, Poisoned> {
+        let graph = self.graph.read().map_err(|_| Poisoned("graph"))?;
+        Ok(graph
+            .adjacency
+            .iter()
+            .flatten()
+            .map(Vec::len)
+            .max()
+            .unwrap_or(0))
+    }
+}
+
+fn present_nodes(graph: &MiniGraph) -> Vec<usize> {
+    graph
+        .adjacency
+        .iter()
+        .enumerate()
+        .filter_map(|(node, slot)| slot.as_ref().map(|_| node))
+        .collect()
+}
+
+fn edges(graph: &MiniGraph) -> impl Iterator<Item = (usize, usize)> + '_ {
+    graph
+        .adjacency
+        .iter()
+        .enumerate()
+        .filter_map(|(node, slot)| slot.as_ref().map(|list| (node, list)))
+        .flat_map(|(node, list)| list.iter().map(move |target| (node, *target)))
+}
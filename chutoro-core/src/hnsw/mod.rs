@@ -5,30 +5,46 @@
 //! lock on the graph, and write access is limited to the mutation window when
 //! inserting a node.
 
+mod cache_config;
+mod candidate;
 mod cpu;
 mod distance_cache;
 mod error;
+mod export;
 mod graph;
+mod harvest_builder;
 mod helpers;
 mod insert;
 mod invariants;
+mod mutual;
 mod node;
 mod params;
+mod provenance;
 mod search;
+mod statistics;
 mod types;
 mod validate;
 
 pub use self::{
-    cpu::CpuHnsw,
-    distance_cache::DistanceCacheConfig,
+    cache_config::{DistanceCacheConfig, MetricCostHint},
+    candidate::{CandidateEdge, EdgeHarvest},
+    cpu::{CpuHnsw, FrozenHnsw, HnswNodeView, HnswNodes, HnswReadView},
     error::{HnswError, HnswErrorCode},
+    export::{GraphExportError, GraphFormat},
+    harvest_builder::{EdgeHarvestBuilder, EdgeHarvestProducer},
     invariants::{HnswInvariant, HnswInvariantChecker, HnswInvariantViolation},
-    params::HnswParams,
-    types::{CandidateEdge, EdgeHarvest, Neighbour},
+    params::{AdjacencyStorage, HnswParams, MAX_LAYER_OVERRIDE, RngKind, TrimPolicy},
+    statistics::HnswStatistics,
+    types::{EntryPoint, Neighbour, NeighbourDetail},
 };
 
+pub(crate) use self::{cpu::GraphQuery, provenance::HarvestProvenance};
+
 #[cfg(test)]
 mod tests;
 
 #[cfg(kani)]
 mod kani_proofs;
+
+#[cfg(all(test, feature = "loom"))]
+mod loom_models;
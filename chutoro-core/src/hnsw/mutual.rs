@@ -0,0 +1,85 @@
+//! Mutual-neighbour filtering of harvested candidate edges.
+//!
+//! Noise points sit close to many points without being close to any of them
+//! in return. Keeping only the edges whose endpoints both rank each other
+//! among their nearest candidates removes those one-sided links, which
+//! sharpens HDBSCAN-style cluster boundaries on noisy data.
+
+use std::num::NonZeroUsize;
+
+use rayon::prelude::*;
+
+use super::candidate::{CandidateEdge, EdgeHarvest};
+
+impl EdgeHarvest {
+    /// Keeps only the edges between mutual neighbours.
+    ///
+    /// A node's candidate list holds the `k` nearest distinct nodes it shares
+    /// a harvested edge with, in either direction, with ties broken by node
+    /// id. An edge survives when each endpoint appears in the other's
+    /// candidate list. Self-loops are dropped, and survivors keep the harvest
+    /// ordering. Filtering can split the graph into several components.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use chutoro_core::{CandidateEdge, EdgeHarvest};
+    ///
+    /// // Points 0 and 1 are each other's nearest; 2 is nearest to 1, but 1
+    /// // prefers 0.
+    /// let harvest = EdgeHarvest::new(vec![
+    ///     CandidateEdge::new(1, 0, 1.0, 1),
+    ///     CandidateEdge::new(2, 1, 2.0, 2),
+    /// ]);
+    /// let mutual = harvest.mutualise(NonZeroUsize::MIN);
+    /// assert_eq!(mutual.len(), 1);
+    /// ```
+    #[must_use]
+    pub fn mutualise(&self, k: NonZeroUsize) -> Self {
+        let lists = candidate_lists(&self.0, k.get());
+        let edges = self
+            .0
+            .par_iter()
+            .filter(|edge| is_mutual(&lists, edge))
+            .copied()
+            .collect();
+        Self(edges)
+    }
+}
+
+/// Returns each node's `k` nearest distinct neighbours, sorted by node id.
+fn candidate_lists(edges: &[CandidateEdge], k: usize) -> Vec<Vec<usize>> {
+    let nodes = edges
+        .iter()
+        .map(|edge| edge.source().max(edge.target()) + 1)
+        .max()
+        .unwrap_or(0);
+    let mut incident: Vec<Vec<(f32, usize)>> = vec![Vec::new(); nodes];
+    for edge in edges.iter().filter(|edge| edge.source() != edge.target()) {
+        incident[edge.source()].push((edge.distance(), edge.target()));
+        incident[edge.target()].push((edge.distance(), edge.source()));
+    }
+    incident
+        .into_par_iter()
+        .map(|candidates| nearest_distinct(candidates, k))
+        .collect()
+}
+
+fn nearest_distinct(mut candidates: Vec<(f32, usize)>, k: usize) -> Vec<usize> {
+    // Keep the shortest edge to each neighbour, then the `k` nearest of those.
+    candidates.sort_unstable_by(|a, b| a.1.cmp(&b.1).then(a.0.total_cmp(&b.0)));
+    candidates.dedup_by_key(|&mut (_, node)| node);
+    candidates.sort_unstable_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+    candidates.truncate(k);
+    let mut nearest: Vec<usize> = candidates.into_iter().map(|(_, node)| node).collect();
+    nearest.sort_unstable();
+    nearest
+}
+
+fn is_mutual(lists: &[Vec<usize>], edge: &CandidateEdge) -> bool {
+    let (source, target) = (edge.source(), edge.target());
+    source != target
+        && lists[source].binary_search(&target).is_ok()
+        && lists[target].binary_search(&source).is_ok()
+}
@@ -0,0 +1,73 @@
+//! Memory behind the base-layer block of an [`super::AdjacencyArena`].
+
+/// Memory holding the base-layer slices.
+#[derive(Debug)]
+pub(super) enum Backing {
+    Heap(Vec<usize>),
+    #[cfg(feature = "hugepages")]
+    Mapped(memmap2::MmapMut),
+}
+
+impl Backing {
+    pub(super) fn heap(words: usize) -> Self {
+        Self::Heap(vec![0; words])
+    }
+
+    /// Maps `words` zeroed words anonymously and advises the kernel to back
+    /// them with transparent huge pages, falling back to the heap when the
+    /// mapping fails.
+    #[cfg(feature = "hugepages")]
+    pub(super) fn huge_pages(words: usize) -> Self {
+        match memmap2::MmapMut::map_anon(words * size_of::<usize>()) {
+            Ok(map) => {
+                advise_huge_pages(&map);
+                Self::Mapped(map)
+            }
+            Err(err) => {
+                tracing::warn!(%err, words, "huge-page mapping failed; using the heap");
+                Self::heap(words)
+            }
+        }
+    }
+
+    pub(super) fn as_slice(&self) -> &[usize] {
+        match self {
+            Self::Heap(words) => words,
+            #[cfg(feature = "hugepages")]
+            Self::Mapped(map) => bytemuck::cast_slice(map),
+        }
+    }
+
+    pub(super) fn as_mut_slice(&mut self) -> &mut [usize] {
+        match self {
+            Self::Heap(words) => words,
+            #[cfg(feature = "hugepages")]
+            Self::Mapped(map) => bytemuck::cast_slice_mut(map),
+        }
+    }
+}
+
+impl Clone for Backing {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Heap(words) => Self::Heap(words.clone()),
+            #[cfg(feature = "hugepages")]
+            Self::Mapped(_) => {
+                let words = self.as_slice();
+                let mut copy = Self::huge_pages(words.len());
+                copy.as_mut_slice().copy_from_slice(words);
+                copy
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "hugepages", target_os = "linux"))]
+fn advise_huge_pages(map: &memmap2::MmapMut) {
+    if let Err(err) = map.advise(memmap2::Advice::HugePage) {
+        tracing::debug!(%err, "transparent huge pages unavailable");
+    }
+}
+
+#[cfg(all(feature = "hugepages", not(target_os = "linux")))]
+fn advise_huge_pages(_map: &memmap2::MmapMut) {}
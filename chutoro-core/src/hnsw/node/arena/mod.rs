@@ -0,0 +1,340 @@
+//! Fixed-capacity neighbour slices backing [`AdjacencyStorage::Arena`].
+//!
+//! Base-layer lists sit at a fixed stride per node identifier in a block
+//! allocated once, so the densest layer never reallocates. Upper-layer lists
+//! are bump-allocated, one run of slices per node, in a block that grows as
+//! nodes above the base layer arrive. Each slice starts with a length word;
+//! a list that outgrows its slice is marked [`SPILLED`] and moves to a side
+//! table, so a list may still grow past its layer's limit.
+//!
+//! [`AdjacencyStorage::Arena`]: crate::AdjacencyStorage::Arena
+
+mod backing;
+
+use std::collections::HashMap;
+
+use self::backing::Backing;
+use crate::hnsw::params::HnswParams;
+
+/// Length word of a list that lives in [`AdjacencyArena::spilled`].
+const SPILLED: usize = usize::MAX;
+
+/// Per-node metadata; the node's base-layer slice is implied by its
+/// identifier.
+#[derive(Clone, Copy, Debug)]
+struct Slot {
+    sequence: u64,
+    levels: usize,
+    /// Offset of the node's first upper-layer slice in `upper`.
+    upper: usize,
+}
+
+/// Location of one list's slice: its length word and capacity.
+#[derive(Clone, Copy, Debug)]
+struct Place {
+    upper: bool,
+    header: usize,
+    capacity: usize,
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct AdjacencyArena {
+    base: Backing,
+    base_stride: usize,
+    upper: Vec<usize>,
+    upper_stride: usize,
+    slots: Vec<Option<Slot>>,
+    /// Lists that outgrew their slice, keyed by `(node, level)`.
+    spilled: HashMap<(usize, usize), Vec<usize>>,
+}
+
+impl AdjacencyArena {
+    pub(super) fn new(params: &HnswParams, capacity: usize) -> Self {
+        Self::with_backing(params, capacity, Backing::heap)
+    }
+
+    #[cfg(feature = "hugepages")]
+    pub(super) fn with_huge_pages(params: &HnswParams, capacity: usize) -> Self {
+        Self::with_backing(params, capacity, Backing::huge_pages)
+    }
+
+    fn with_backing(params: &HnswParams, capacity: usize, backing: fn(usize) -> Backing) -> Self {
+        let limits = params.connection_limits();
+        let base_stride = limits.for_level(0) + 1;
+        let upper_stride = (1..=params.max_level())
+            .map(|level| limits.for_level(level))
+            .max()
+            .unwrap_or(0)
+            + 1;
+        // About one node in `M` rises above the base layer, and most of those
+        // stop one layer up.
+        let expected_upper = capacity / params.max_connections().max(2);
+        Self {
+            base: backing(capacity * base_stride),
+            base_stride,
+            upper: Vec::with_capacity(expected_upper * upper_stride),
+            upper_stride,
+            slots: vec![None; capacity],
+            spilled: HashMap::new(),
+        }
+    }
+
+    pub(super) fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub(super) fn node(&self, id: usize) -> Option<ArenaNode<'_>> {
+        let slot = (*self.slots.get(id)?)?;
+        Some(ArenaNode {
+            arena: self,
+            id,
+            slot,
+        })
+    }
+
+    pub(super) fn node_mut(&mut self, id: usize) -> Option<ArenaNodeMut<'_>> {
+        let slot = (*self.slots.get(id)?)?;
+        Some(ArenaNodeMut {
+            arena: self,
+            id,
+            slot,
+        })
+    }
+
+    pub(super) fn insert(&mut self, id: usize, level: usize, sequence: u64) {
+        let upper = self.upper.len();
+        self.upper.resize(upper + level * self.upper_stride, 0);
+        if let Some(slot) = self.slots.get_mut(id) {
+            *slot = Some(Slot {
+                sequence,
+                levels: level + 1,
+                upper,
+            });
+        }
+    }
+
+    /// Empties slot `id`; the node's upper-layer slices are not reclaimed.
+    #[cfg(test)]
+    pub(super) fn remove(&mut self, id: usize) -> bool {
+        let Some(slot) = self.slots.get_mut(id).and_then(Option::take) else {
+            return false;
+        };
+        for level in 0..slot.levels {
+            let place = self.place(id, slot, level);
+            self.words_mut(place.upper)[place.header] = 0;
+            self.spilled.remove(&(id, level));
+        }
+        true
+    }
+
+    fn place(&self, id: usize, slot: Slot, level: usize) -> Place {
+        if level >= slot.levels {
+            unreachable!("levels are initialized during construction");
+        }
+        if level == 0 {
+            Place {
+                upper: false,
+                header: id * self.base_stride,
+                capacity: self.base_stride - 1,
+            }
+        } else {
+            Place {
+                upper: true,
+                header: slot.upper + (level - 1) * self.upper_stride,
+                capacity: self.upper_stride - 1,
+            }
+        }
+    }
+
+    fn words(&self, upper: bool) -> &[usize] {
+        if upper {
+            &self.upper
+        } else {
+            self.base.as_slice()
+        }
+    }
+
+    fn words_mut(&mut self, upper: bool) -> &mut [usize] {
+        if upper {
+            &mut self.upper
+        } else {
+            self.base.as_mut_slice()
+        }
+    }
+
+    fn read(&self, key: (usize, usize), place: Place) -> &[usize] {
+        let words = self.words(place.upper);
+        match words[place.header] {
+            SPILLED => self.spilled.get(&key).map_or(&[], Vec::as_slice),
+            len => &words[place.header + 1..=place.header + len],
+        }
+    }
+}
+
+/// Shared view of a node stored in an [`AdjacencyArena`].
+#[derive(Clone, Copy, Debug)]
+pub(super) struct ArenaNode<'a> {
+    arena: &'a AdjacencyArena,
+    id: usize,
+    slot: Slot,
+}
+
+impl<'a> ArenaNode<'a> {
+    pub(super) fn neighbours(self, level: usize) -> &'a [usize] {
+        let place = self.arena.place(self.id, self.slot, level);
+        self.arena.read((self.id, level), place)
+    }
+
+    pub(super) fn sequence(self) -> u64 {
+        self.slot.sequence
+    }
+
+    pub(super) fn level_count(self) -> usize {
+        self.slot.levels
+    }
+}
+
+/// Exclusive view of a node stored in an [`AdjacencyArena`].
+#[derive(Debug)]
+pub(super) struct ArenaNodeMut<'a> {
+    arena: &'a mut AdjacencyArena,
+    id: usize,
+    slot: Slot,
+}
+
+impl ArenaNodeMut<'_> {
+    pub(super) fn level_count(&self) -> usize {
+        self.slot.levels
+    }
+
+    pub(super) fn list(&mut self, level: usize) -> ArenaList<'_> {
+        let place = self.arena.place(self.id, self.slot, level);
+        ArenaList {
+            arena: &mut *self.arena,
+            key: (self.id, level),
+            place,
+        }
+    }
+}
+
+/// Editable list of one node on one level of an [`AdjacencyArena`].
+#[derive(Debug)]
+pub(super) struct ArenaList<'a> {
+    arena: &'a mut AdjacencyArena,
+    key: (usize, usize),
+    place: Place,
+}
+
+/// Where an [`ArenaList`] currently keeps its neighbours.
+enum Storage<'s> {
+    /// The list's slice: the length word followed by `capacity` entries.
+    Inline(&'s mut [usize]),
+    Spilled(&'s mut Vec<usize>),
+}
+
+impl ArenaList<'_> {
+    pub(super) fn as_slice(&self) -> &[usize] {
+        self.arena.read(self.key, self.place)
+    }
+
+    fn storage(&mut self) -> Storage<'_> {
+        let Place {
+            upper,
+            header,
+            capacity,
+        } = self.place;
+        let AdjacencyArena {
+            base,
+            upper: upper_words,
+            spilled,
+            ..
+        } = &mut *self.arena;
+        let words = if upper {
+            upper_words.as_mut_slice()
+        } else {
+            base.as_mut_slice()
+        };
+        let region = &mut words[header..=header + capacity];
+        if region[0] == SPILLED {
+            Storage::Spilled(spilled.entry(self.key).or_default())
+        } else {
+            Storage::Inline(region)
+        }
+    }
+
+    pub(super) fn push(&mut self, id: usize) {
+        let overflow = match self.storage() {
+            Storage::Spilled(list) => {
+                list.push(id);
+                return;
+            }
+            Storage::Inline(region) => {
+                let len = region[0];
+                if let Some(free) = region.get_mut(len + 1) {
+                    *free = id;
+                    region[0] = len + 1;
+                    return;
+                }
+                region[0] = SPILLED;
+                let mut moved = region[1..].to_vec();
+                moved.push(id);
+                moved
+            }
+        };
+        self.arena.spilled.insert(self.key, overflow);
+    }
+
+    pub(super) fn pop(&mut self) -> Option<usize> {
+        match self.storage() {
+            Storage::Spilled(list) => list.pop(),
+            Storage::Inline(region) => {
+                let len = region[0];
+                let last = (len > 0).then(|| region[len])?;
+                region[0] = len - 1;
+                Some(last)
+            }
+        }
+    }
+
+    pub(super) fn remove(&mut self, index: usize) -> usize {
+        match self.storage() {
+            Storage::Spilled(list) => list.remove(index),
+            Storage::Inline(region) => {
+                let len = region[0];
+                let items = &mut region[1..=len];
+                let removed = items[index];
+                items.copy_within(index + 1.., index);
+                region[0] = len - 1;
+                removed
+            }
+        }
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.arena.spilled.remove(&self.key);
+        self.arena.words_mut(self.place.upper)[self.place.header] = 0;
+    }
+
+    #[cfg(test)]
+    pub(super) fn retain(&mut self, keep: impl FnMut(&usize) -> bool) {
+        match self.storage() {
+            Storage::Spilled(list) => list.retain(keep),
+            Storage::Inline(region) => retain_inline(region, keep),
+        }
+    }
+}
+
+/// Compacts the kept entries of an inline slice to its front.
+#[cfg(test)]
+fn retain_inline(region: &mut [usize], mut keep: impl FnMut(&usize) -> bool) {
+    let len = region[0];
+    let mut kept = 0;
+    for read in 1..=len {
+        let id = region[read];
+        if keep(&id) {
+            kept += 1;
+            region[kept] = id;
+        }
+    }
+    region[0] = kept;
+}
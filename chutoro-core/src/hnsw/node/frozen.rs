@@ -0,0 +1,104 @@
+//! Compressed sparse row (CSR) snapshot of a [`NodeStore`] for read-only
+//! traversal.
+//!
+//! Every neighbour list sits in one `targets` array, ordered by node and then
+//! by level, so walking a node's base-layer list touches a single contiguous
+//! run instead of a separately allocated vector. Sequences live in their own
+//! dense array because search looks one up for every neighbour it enqueues.
+//!
+//! [`NodeStore`]: super::NodeStore
+
+use super::{NodeRef, NodeStore};
+
+/// Frozen copy of every node's neighbour lists and insertion sequence.
+#[derive(Clone, Debug)]
+pub(crate) struct FrozenNodes {
+    /// `lists[id]..lists[id + 1]` indexes node `id`'s lists in `offsets`,
+    /// base layer first; vacant slots own no lists.
+    lists: Vec<usize>,
+    /// `offsets[list]..offsets[list + 1]` is the list's range in `targets`.
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    /// Insertion sequence per slot; zero for vacant slots.
+    sequences: Vec<u64>,
+}
+
+impl FrozenNodes {
+    pub(super) fn from_store(store: &NodeStore) -> Self {
+        let capacity = store.capacity();
+        let mut frozen = Self {
+            lists: Vec::with_capacity(capacity + 1),
+            offsets: vec![0],
+            targets: Vec::new(),
+            sequences: vec![0; capacity],
+        };
+        frozen.lists.push(0);
+        for id in 0..capacity {
+            if let Some(node) = store.get(id) {
+                frozen.sequences[id] = node.sequence();
+                frozen.push_lists(node);
+            }
+            frozen.lists.push(frozen.offsets.len() - 1);
+        }
+        frozen
+    }
+
+    /// Appends every list of `node`, base layer first.
+    fn push_lists(&mut self, node: NodeRef<'_>) {
+        for level in 0..node.level_count() {
+            self.targets.extend_from_slice(node.neighbours(level));
+            self.offsets.push(self.targets.len());
+        }
+    }
+
+    pub(crate) fn get(&self, id: usize) -> Option<NodeRef<'_>> {
+        let (first, levels) = self.span(id)?;
+        Some(NodeRef::frozen(FrozenNode {
+            nodes: self,
+            id,
+            first,
+            levels,
+        }))
+    }
+
+    /// Returns the insertion sequence of node `id` without building a view.
+    pub(crate) fn sequence(&self, id: usize) -> Option<u64> {
+        self.span(id).map(|_| self.sequences[id])
+    }
+
+    /// Returns the index of node `id`'s first list and its level count, or
+    /// `None` for a vacant or out-of-range slot.
+    fn span(&self, id: usize) -> Option<(usize, usize)> {
+        let first = *self.lists.get(id)?;
+        let end = *self.lists.get(id + 1)?;
+        (end > first).then_some((first, end - first))
+    }
+}
+
+/// Shared view of a node stored in [`FrozenNodes`].
+#[derive(Clone, Copy, Debug)]
+pub(super) struct FrozenNode<'a> {
+    nodes: &'a FrozenNodes,
+    id: usize,
+    first: usize,
+    levels: usize,
+}
+
+impl<'a> FrozenNode<'a> {
+    pub(super) fn neighbours(self, level: usize) -> &'a [usize] {
+        if level >= self.levels {
+            unreachable!("levels are initialized during construction");
+        }
+        let list = self.first + level;
+        let offsets = &self.nodes.offsets;
+        &self.nodes.targets[offsets[list]..offsets[list + 1]]
+    }
+
+    pub(super) fn sequence(self) -> u64 {
+        self.nodes.sequences[self.id]
+    }
+
+    pub(super) fn level_count(self) -> usize {
+        self.levels
+    }
+}
@@ -0,0 +1,138 @@
+//! Node storage for the CPU HNSW graph.
+//!
+//! Maintains per-level neighbour lists and provides accessors used during
+//! search, insertion, and trimming. A [`NodeStore`] keeps the lists either in
+//! one vector per node and level or in a fixed-capacity arena, as selected by
+//! [`AdjacencyStorage`], and hands out [`NodeRef`] and [`NodeMut`] views that
+//! hide the difference from the rest of the graph. [`NodeStore::freeze`]
+//! copies the lists into a read-only [`FrozenNodes`] snapshot whose views are
+//! interchangeable with the live ones.
+
+mod arena;
+mod frozen;
+mod view;
+
+#[cfg(test)]
+mod tests;
+
+use self::arena::AdjacencyArena;
+pub(crate) use self::frozen::FrozenNodes;
+pub(crate) use self::view::{NeighboursMut, NodeMut, NodeRef};
+use crate::hnsw::params::{AdjacencyStorage, HnswParams};
+
+/// A node whose neighbour lists are separate vectors, one per level.
+#[derive(Clone, Debug)]
+struct NodeLists {
+    neighbours: Vec<Vec<usize>>,
+    sequence: u64,
+}
+
+impl NodeLists {
+    fn new(level: usize, sequence: u64) -> Self {
+        let mut neighbours = Vec::with_capacity(level + 1);
+        neighbours.resize_with(level + 1, Vec::new);
+        Self {
+            neighbours,
+            sequence,
+        }
+    }
+
+    fn neighbours(&self, level: usize) -> &[usize] {
+        debug_assert!(
+            level < self.neighbours.len(),
+            "levels are initialized during construction"
+        );
+        let Some(neighbours) = self.neighbours.get(level) else {
+            unreachable!("levels are initialized during construction");
+        };
+        neighbours.as_slice()
+    }
+
+    fn neighbours_mut(&mut self, level: usize) -> &mut Vec<usize> {
+        let Some(neighbours) = self.neighbours.get_mut(level) else {
+            unreachable!("levels are initialized during construction");
+        };
+        neighbours
+    }
+}
+
+/// Fixed-capacity table of node slots, indexed by node identifier.
+#[derive(Clone, Debug)]
+pub(crate) struct NodeStore {
+    slots: Slots,
+}
+
+#[derive(Clone, Debug)]
+enum Slots {
+    Lists(Vec<Option<NodeLists>>),
+    Arena(AdjacencyArena),
+}
+
+impl NodeStore {
+    /// Allocates `capacity` empty slots in the layout selected by `params`.
+    pub(crate) fn new(params: &HnswParams, capacity: usize) -> Self {
+        let slots = match params.adjacency_storage() {
+            AdjacencyStorage::PerNode => Slots::Lists(vec![None; capacity]),
+            AdjacencyStorage::Arena => Slots::Arena(AdjacencyArena::new(params, capacity)),
+            #[cfg(feature = "hugepages")]
+            AdjacencyStorage::HugePageArena => {
+                Slots::Arena(AdjacencyArena::with_huge_pages(params, capacity))
+            }
+        };
+        Self { slots }
+    }
+
+    /// Returns the number of slots, occupied or not.
+    pub(crate) fn capacity(&self) -> usize {
+        match &self.slots {
+            Slots::Lists(nodes) => nodes.len(),
+            Slots::Arena(arena) => arena.capacity(),
+        }
+    }
+
+    pub(crate) fn get(&self, id: usize) -> Option<NodeRef<'_>> {
+        match &self.slots {
+            Slots::Lists(nodes) => nodes.get(id)?.as_ref().map(NodeRef::lists),
+            Slots::Arena(arena) => arena.node(id).map(NodeRef::arena),
+        }
+    }
+
+    pub(crate) fn get_mut(&mut self, id: usize) -> Option<NodeMut<'_>> {
+        match &mut self.slots {
+            Slots::Lists(nodes) => nodes.get_mut(id)?.as_mut().map(NodeMut::lists),
+            Slots::Arena(arena) => arena.node_mut(id).map(NodeMut::arena),
+        }
+    }
+
+    /// Fills the vacant slot `id` with a node spanning levels `0..=level`.
+    ///
+    /// The caller checks that `id` is within capacity and vacant.
+    pub(crate) fn insert(&mut self, id: usize, level: usize, sequence: u64) {
+        debug_assert!(
+            id < self.capacity() && self.get(id).is_none(),
+            "slot {id} must be vacant and within capacity"
+        );
+        match &mut self.slots {
+            Slots::Lists(nodes) => {
+                if let Some(slot) = nodes.get_mut(id) {
+                    *slot = Some(NodeLists::new(level, sequence));
+                }
+            }
+            Slots::Arena(arena) => arena.insert(id, level, sequence),
+        }
+    }
+
+    /// Copies every node into a compressed sparse row snapshot.
+    pub(crate) fn freeze(&self) -> FrozenNodes {
+        FrozenNodes::from_store(self)
+    }
+
+    /// Empties slot `id`, returning whether it held a node.
+    #[cfg(test)]
+    pub(crate) fn remove(&mut self, id: usize) -> bool {
+        match &mut self.slots {
+            Slots::Lists(nodes) => nodes.get_mut(id).and_then(Option::take).is_some(),
+            Slots::Arena(arena) => arena.remove(id),
+        }
+    }
+}
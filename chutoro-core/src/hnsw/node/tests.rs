@@ -0,0 +1,178 @@
+//! Tests for the neighbour-list operations of both node layouts.
+
+use rstest::{fixture, rstest};
+
+use super::{NodeRef, NodeStore};
+use crate::hnsw::params::{AdjacencyStorage, HnswParams};
+
+/// Base-layer slices hold four neighbours and upper-layer slices two.
+#[fixture]
+fn params() -> HnswParams {
+    HnswParams::new(2, 4)
+        .expect("params must be valid")
+        .with_max_level(2)
+}
+
+fn store(params: &HnswParams, storage: AdjacencyStorage) -> NodeStore {
+    let mut store = NodeStore::new(&params.clone().with_adjacency_storage(storage), 3);
+    store.insert(0, 2, 10);
+    store.insert(2, 1, 12);
+    store
+}
+
+fn list(store: &NodeStore, id: usize, level: usize) -> Vec<usize> {
+    store
+        .get(id)
+        .expect("node must exist")
+        .neighbours(level)
+        .to_vec()
+}
+
+/// Level count, sequence, and every `(level, id)` neighbour of a node.
+fn summary(node: NodeRef<'_>) -> (usize, u64, Vec<(usize, usize)>) {
+    (
+        node.level_count(),
+        node.sequence(),
+        node.iter_neighbours().collect(),
+    )
+}
+
+fn edit(store: &mut NodeStore, id: usize, level: usize, ids: &[usize]) {
+    let mut node = store.get_mut(id).expect("node must exist");
+    node.neighbours_mut(level).extend(ids.iter().copied());
+}
+
+#[rstest]
+#[case::per_node(AdjacencyStorage::PerNode)]
+#[case::arena(AdjacencyStorage::Arena)]
+fn slots_report_their_nodes(params: HnswParams, #[case] storage: AdjacencyStorage) {
+    let store = store(&params, storage);
+
+    assert_eq!(store.capacity(), 3);
+    assert!(store.get(1).is_none());
+    assert!(store.get(3).is_none());
+    let node = store.get(0).expect("node must exist");
+    assert_eq!((node.level_count(), node.sequence()), (3, 10));
+    assert!(node.iter_neighbours().next().is_none());
+}
+
+#[rstest]
+#[case::per_node(AdjacencyStorage::PerNode)]
+#[case::arena(AdjacencyStorage::Arena)]
+fn lists_on_each_level_are_independent(params: HnswParams, #[case] storage: AdjacencyStorage) {
+    let mut store = store(&params, storage);
+
+    edit(&mut store, 0, 0, &[1, 2]);
+    edit(&mut store, 0, 2, &[2]);
+    edit(&mut store, 2, 1, &[0]);
+
+    let node = store.get(0).expect("node must exist");
+    assert_eq!(
+        node.iter_neighbours().collect::<Vec<_>>(),
+        [(0, 1), (0, 2), (2, 2)]
+    );
+    assert_eq!(list(&store, 2, 1), [0]);
+    assert!(list(&store, 2, 0).is_empty());
+}
+
+#[rstest]
+#[case::per_node(AdjacencyStorage::PerNode)]
+#[case::arena(AdjacencyStorage::Arena)]
+fn lists_support_the_vec_operations(params: HnswParams, #[case] storage: AdjacencyStorage) {
+    let mut store = store(&params, storage);
+    let mut node = store.get_mut(0).expect("node must exist");
+    let mut neighbours = node.neighbours_mut(0);
+
+    neighbours.extend([5, 6, 7]);
+    assert_eq!(neighbours.pop(), Some(7));
+    assert_eq!(neighbours.remove(0), 5);
+    neighbours.push(8);
+    neighbours.retain(|&id| id != 6);
+    assert_eq!(*neighbours, [8]);
+    neighbours.clear();
+    assert!(neighbours.is_empty());
+    assert_eq!(neighbours.pop(), None);
+}
+
+#[rstest]
+#[case::base_layer(0, 4)]
+#[case::upper_layer(1, 2)]
+fn arena_lists_spill_past_their_capacity(
+    params: HnswParams,
+    #[case] level: usize,
+    #[case] capacity: usize,
+) {
+    let mut store = store(&params, AdjacencyStorage::Arena);
+    let ids: Vec<usize> = (10..11 + capacity).collect();
+
+    edit(&mut store, 0, level, &ids);
+    edit(&mut store, 2, level, &[1]);
+    assert_eq!(list(&store, 0, level), ids);
+    assert_eq!(list(&store, 2, level), [1]);
+
+    let mut node = store.get_mut(0).expect("node must exist");
+    let mut neighbours = node.neighbours_mut(level);
+    assert_eq!(neighbours.remove(0), 10);
+    neighbours.retain(|&id| id % 2 == 0);
+    let kept: Vec<usize> = ids[1..].iter().copied().filter(|id| id % 2 == 0).collect();
+    assert_eq!(*neighbours, kept);
+
+    neighbours.clear();
+    neighbours.extend([3, 4]);
+    assert_eq!(list(&store, 0, level), [3, 4]);
+}
+
+#[rstest]
+fn cloned_arenas_are_independent(params: HnswParams) {
+    let mut store = store(&params, AdjacencyStorage::Arena);
+    edit(&mut store, 0, 0, &[1, 2, 3, 4, 5]);
+
+    let snapshot = store.clone();
+    edit(&mut store, 0, 0, &[6]);
+    assert!(store.remove(2));
+
+    assert_eq!(list(&snapshot, 0, 0), [1, 2, 3, 4, 5]);
+    assert!(snapshot.get(2).is_some());
+    assert_eq!(list(&store, 0, 0), [1, 2, 3, 4, 5, 6]);
+}
+
+#[rstest]
+#[case::per_node(AdjacencyStorage::PerNode)]
+#[case::arena(AdjacencyStorage::Arena)]
+fn removed_slots_can_be_refilled(params: HnswParams, #[case] storage: AdjacencyStorage) {
+    let mut store = store(&params, storage);
+    edit(&mut store, 2, 0, &[0, 1, 3, 4, 5]);
+    edit(&mut store, 2, 1, &[0]);
+
+    assert!(store.remove(2));
+    assert!(!store.remove(2));
+    store.insert(2, 1, 20);
+
+    let node = store.get(2).expect("node must exist");
+    assert_eq!(node.sequence(), 20);
+    assert!(node.iter_neighbours().next().is_none());
+}
+
+#[rstest]
+#[case::per_node(AdjacencyStorage::PerNode)]
+#[case::arena(AdjacencyStorage::Arena)]
+fn frozen_stores_keep_every_list(params: HnswParams, #[case] storage: AdjacencyStorage) {
+    let mut store = store(&params, storage);
+    edit(&mut store, 0, 0, &[1, 2, 3, 4, 5]);
+    edit(&mut store, 0, 2, &[2]);
+    edit(&mut store, 2, 1, &[0]);
+
+    let frozen = store.freeze();
+    edit(&mut store, 2, 0, &[0]);
+
+    assert_eq!(
+        frozen.get(0).map(summary),
+        Some((3, 10, vec![(0, 1), (0, 2), (0, 3), (0, 4), (0, 5), (2, 2)]))
+    );
+    assert_eq!(frozen.get(2).map(summary), Some((2, 12, vec![(1, 0)])));
+    assert_eq!(frozen.sequence(2), Some(12));
+    for vacant in [1, 3] {
+        assert!(frozen.get(vacant).is_none());
+        assert_eq!(frozen.sequence(vacant), None);
+    }
+}
@@ -0,0 +1,230 @@
+//! Borrowed views of a node, whichever layout holds its neighbour lists.
+//! Frozen snapshots hand out only shared views.
+
+use std::ops::Deref;
+
+use super::{
+    NodeLists,
+    arena::{ArenaList, ArenaNode, ArenaNodeMut},
+    frozen::FrozenNode,
+};
+
+/// Shared view of an inserted node.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct NodeRef<'a> {
+    node: Shared<'a>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Shared<'a> {
+    Lists(&'a NodeLists),
+    Arena(ArenaNode<'a>),
+    Frozen(FrozenNode<'a>),
+}
+
+impl<'a> NodeRef<'a> {
+    pub(super) fn lists(node: &'a NodeLists) -> Self {
+        Self {
+            node: Shared::Lists(node),
+        }
+    }
+
+    pub(super) fn arena(node: ArenaNode<'a>) -> Self {
+        Self {
+            node: Shared::Arena(node),
+        }
+    }
+
+    pub(super) fn frozen(node: FrozenNode<'a>) -> Self {
+        Self {
+            node: Shared::Frozen(node),
+        }
+    }
+
+    pub(crate) fn neighbours(self, level: usize) -> &'a [usize] {
+        match self.node {
+            Shared::Lists(node) => node.neighbours(level),
+            Shared::Arena(node) => node.neighbours(level),
+            Shared::Frozen(node) => node.neighbours(level),
+        }
+    }
+
+    pub(crate) fn sequence(self) -> u64 {
+        match self.node {
+            Shared::Lists(node) => node.sequence,
+            Shared::Arena(node) => node.sequence(),
+            Shared::Frozen(node) => node.sequence(),
+        }
+    }
+
+    /// Returns the number of levels initialized for this node.
+    ///
+    /// # Examples
+    /// ```rust,ignore
+    /// use crate::hnsw::{graph::{Graph, NodeContext}, params::HnswParams};
+    ///
+    /// let params = HnswParams::new(4, 8).expect("params must be valid");
+    /// let mut graph = Graph::with_capacity(params, 1);
+    /// graph.insert_first(NodeContext { node: 0, level: 1, sequence: 42 }).expect("insert node");
+    /// let node = graph.node(0).expect("node 0 must exist");
+    /// assert_eq!(node.level_count(), 2);
+    /// ```
+    #[must_use]
+    pub(crate) fn level_count(self) -> usize {
+        match self.node {
+            Shared::Lists(node) => node.neighbours.len(),
+            Shared::Arena(node) => node.level_count(),
+            Shared::Frozen(node) => node.level_count(),
+        }
+    }
+
+    /// Iterates over every neighbour across all layers, yielding `(level, id)`.
+    ///
+    /// # Examples
+    /// ```rust,ignore
+    /// use crate::hnsw::{graph::{Graph, NodeContext}, params::HnswParams};
+    ///
+    /// let params = HnswParams::new(4, 8).expect("params");
+    /// let mut graph = Graph::with_capacity(params, 2);
+    /// graph
+    ///     .insert_first(NodeContext { node: 0, level: 0, sequence: 0 })
+    ///     .expect("insert first node");
+    /// graph
+    ///     .attach_node(NodeContext { node: 1, level: 0, sequence: 1 })
+    ///     .expect("attach second node");
+    /// graph.node_mut(0).expect("node 0").neighbours_mut(0).push(1);
+    /// let neighbours: Vec<_> = graph
+    ///     .node(0)
+    ///     .expect("node 0")
+    ///     .iter_neighbours()
+    ///     .collect();
+    /// assert_eq!(neighbours, vec![(0, 1)]);
+    /// ```
+    pub(crate) fn iter_neighbours(self) -> impl Iterator<Item = (usize, usize)> + 'a {
+        (0..self.level_count()).flat_map(move |level| {
+            self.neighbours(level)
+                .iter()
+                .map(move |&target| (level, target))
+        })
+    }
+}
+
+/// Exclusive view of an inserted node, handing out its neighbour lists for
+/// editing.
+#[derive(Debug)]
+pub(crate) struct NodeMut<'a> {
+    node: Exclusive<'a>,
+}
+
+#[derive(Debug)]
+enum Exclusive<'a> {
+    Lists(&'a mut NodeLists),
+    Arena(ArenaNodeMut<'a>),
+}
+
+impl<'a> NodeMut<'a> {
+    pub(super) fn lists(node: &'a mut NodeLists) -> Self {
+        Self {
+            node: Exclusive::Lists(node),
+        }
+    }
+
+    pub(super) fn arena(node: ArenaNodeMut<'a>) -> Self {
+        Self {
+            node: Exclusive::Arena(node),
+        }
+    }
+
+    /// Returns the number of levels initialized for this node.
+    #[must_use]
+    pub(crate) fn level_count(&self) -> usize {
+        match &self.node {
+            Exclusive::Lists(node) => node.neighbours.len(),
+            Exclusive::Arena(node) => node.level_count(),
+        }
+    }
+
+    pub(crate) fn neighbours_mut(&mut self, level: usize) -> NeighboursMut<'_> {
+        let list = match &mut self.node {
+            Exclusive::Lists(node) => List::Vec(node.neighbours_mut(level)),
+            Exclusive::Arena(node) => List::Arena(node.list(level)),
+        };
+        NeighboursMut { list }
+    }
+}
+
+/// Editable neighbour list of one node on one level.
+///
+/// Offers the subset of the `Vec` API that insertion and trimming use, and
+/// dereferences to the current neighbours.
+#[derive(Debug)]
+pub(crate) struct NeighboursMut<'a> {
+    list: List<'a>,
+}
+
+#[derive(Debug)]
+enum List<'a> {
+    Vec(&'a mut Vec<usize>),
+    Arena(ArenaList<'a>),
+}
+
+impl NeighboursMut<'_> {
+    pub(crate) fn push(&mut self, id: usize) {
+        match &mut self.list {
+            List::Vec(list) => list.push(id),
+            List::Arena(list) => list.push(id),
+        }
+    }
+
+    pub(crate) fn pop(&mut self) -> Option<usize> {
+        match &mut self.list {
+            List::Vec(list) => list.pop(),
+            List::Arena(list) => list.pop(),
+        }
+    }
+
+    /// Removes and returns the neighbour at `index`, shifting later ones
+    /// down.
+    ///
+    /// # Panics
+    /// Panics when `index` is out of bounds.
+    pub(crate) fn remove(&mut self, index: usize) -> usize {
+        match &mut self.list {
+            List::Vec(list) => list.remove(index),
+            List::Arena(list) => list.remove(index),
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        match &mut self.list {
+            List::Vec(list) => list.clear(),
+            List::Arena(list) => list.clear(),
+        }
+    }
+
+    pub(crate) fn extend(&mut self, ids: impl IntoIterator<Item = usize>) {
+        match &mut self.list {
+            List::Vec(list) => list.extend(ids),
+            List::Arena(list) => ids.into_iter().for_each(|id| list.push(id)),
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn retain(&mut self, keep: impl FnMut(&usize) -> bool) {
+        match &mut self.list {
+            List::Vec(list) => list.retain(keep),
+            List::Arena(list) => list.retain(keep),
+        }
+    }
+}
+
+impl Deref for NeighboursMut<'_> {
+    type Target = [usize];
+
+    fn deref(&self) -> &[usize] {
+        match &self.list {
+            List::Vec(list) => list.as_slice(),
+            List::Arena(list) => list.as_slice(),
+        }
+    }
+}
@@ -2,17 +2,52 @@
 
 use std::{num::NonZeroUsize, time::Duration};
 
-use crate::hnsw::{distance_cache::DistanceCacheConfig, error::HnswError};
+use crate::{
+    DistancePolicy,
+    hnsw::{cache_config::DistanceCacheConfig, error::HnswError},
+};
+
+mod layers;
+mod levels;
+mod limits;
+#[cfg(feature = "serde")]
+mod raw;
+mod rng;
+mod storage;
+mod trim;
+
+pub(crate) use self::limits::{ConnectionLimits, connection_limit_for_level};
+#[cfg(feature = "serde")]
+use self::raw::RawHnswParams;
+use self::{
+    levels::{level_from_weights, validate_level_weights},
+    trim::validate_trim_policy,
+};
+pub use self::{
+    limits::MAX_LAYER_OVERRIDE, rng::RngKind, storage::AdjacencyStorage, trim::TrimPolicy,
+};
 
 /// Configuration parameters for the CPU HNSW index.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "RawHnswParams")
+)]
 pub struct HnswParams {
     max_connections: usize,
     ef_construction: usize,
     level_multiplier: f64,
     max_level: usize,
     rng_seed: u64,
+    rng_kind: RngKind,
     distance_cache: DistanceCacheConfig,
+    base_connections: Option<usize>,
+    layer_overrides: Vec<(usize, usize, usize)>,
+    level_weights: Option<Vec<f64>>,
+    adjacency_storage: AdjacencyStorage,
+    trim_policy: TrimPolicy,
+    distance_policy: DistancePolicy,
 }
 
 impl HnswParams {
@@ -47,18 +82,69 @@ impl HnswParams {
             level_multiplier: (max_connections as f64).ln().recip(),
             max_level: 12,
             rng_seed: 0x5EED_CAFE,
+            rng_kind: RngKind::default(),
             distance_cache: DistanceCacheConfig::default(),
+            base_connections: None,
+            layer_overrides: Vec::new(),
+            level_weights: None,
+            adjacency_storage: AdjacencyStorage::default(),
+            trim_policy: TrimPolicy::default(),
+            distance_policy: DistancePolicy::default(),
         })
     }
 
-    /// Overrides the random level multiplier used when sampling layers.
+    /// Overrides the level multiplier, `mL`, used when sampling layers.
+    ///
+    /// A node rises above each layer with probability `exp(-1/mL)`. The
+    /// default of `1/ln(M)` makes each layer hold about `1/M` of the nodes of
+    /// the one below; smaller multipliers flatten the hierarchy, which can
+    /// suit skewed datasets. Non-positive and NaN multipliers are raised to
+    /// the smallest positive value, leaving every node on the base layer.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::HnswParams;
+    ///
+    /// let params = HnswParams::new(16, 64)?.with_level_multiplier(0.2);
+    /// assert_eq!(params.level_multiplier(), 0.2);
+    /// # Ok::<(), chutoro_core::HnswError>(())
+    /// ```
     #[must_use]
     pub fn with_level_multiplier(mut self, multiplier: f64) -> Self {
         self.level_multiplier = multiplier.max(f64::MIN_POSITIVE);
         self
     }
 
+    /// Samples node levels from a custom discrete distribution instead of the
+    /// geometric one set by [`Self::with_level_multiplier`].
+    ///
+    /// `weights[level]` is the relative weight of a node landing on `level`;
+    /// weights need not sum to one.
+    ///
+    /// # Errors
+    /// Returns [`HnswError::InvalidParameters`] when `weights` is empty,
+    /// covers levels above [`Self::with_max_level`]'s cap, contains a
+    /// negative or non-finite weight, or sums to zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::HnswParams;
+    ///
+    /// // A flat two-layer hierarchy: a tenth of the nodes on layer 1.
+    /// let params = HnswParams::new(16, 64)?.with_level_distribution(&[0.9, 0.1])?;
+    /// assert_eq!(params.level_distribution(), Some(&[0.9, 0.1][..]));
+    /// # Ok::<(), chutoro_core::HnswError>(())
+    /// ```
+    pub fn with_level_distribution(mut self, weights: &[f64]) -> Result<Self, HnswError> {
+        validate_level_weights(weights, self.max_level)?;
+        self.level_weights = Some(weights.to_vec());
+        Ok(self)
+    }
+
     /// Caps the maximum layer that will be sampled for new nodes.
+    ///
+    /// Levels drawn from a custom distribution above the cap are clamped to
+    /// it.
     #[must_use]
     pub fn with_max_level(mut self, max_level: usize) -> Self {
         self.max_level = max_level;
@@ -72,6 +158,13 @@ impl HnswParams {
         self
     }
 
+    /// Selects the generator that samples node levels; see [`RngKind`].
+    #[must_use]
+    pub fn with_rng_kind(mut self, kind: RngKind) -> Self {
+        self.rng_kind = kind;
+        self
+    }
+
     /// Applies a custom distance-cache configuration.
     #[must_use]
     pub fn with_distance_cache_config(mut self, config: DistanceCacheConfig) -> Self {
@@ -79,6 +172,47 @@ impl HnswParams {
         self
     }
 
+    /// Selects how the graph lays out its neighbour lists in memory; see
+    /// [`AdjacencyStorage`].
+    #[must_use]
+    pub fn with_adjacency_storage(mut self, storage: AdjacencyStorage) -> Self {
+        self.adjacency_storage = storage;
+        self
+    }
+
+    /// Selects which neighbours an over-full list keeps when it is trimmed;
+    /// see [`TrimPolicy`].
+    ///
+    /// # Errors
+    /// Returns [`HnswError::InvalidParameters`] when a
+    /// [`TrimPolicy::RngRelaxed`] factor is below one or not finite.
+    pub fn with_trim_policy(mut self, policy: TrimPolicy) -> Result<Self, HnswError> {
+        validate_trim_policy(policy)?;
+        self.trim_policy = policy;
+        Ok(self)
+    }
+
+    /// Selects how the index treats NaN or infinite distances; see
+    /// [`DistancePolicy`].
+    ///
+    /// Lenient policies replace the value with `f32::MAX` when the index
+    /// validates it, and [`DistancePolicy::SkipEdge`] also records the pair
+    /// so its candidate edge is dropped before MST construction.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{DistancePolicy, HnswParams};
+    ///
+    /// let params = HnswParams::new(16, 64)?.with_distance_policy(DistancePolicy::SkipEdge);
+    /// assert_eq!(params.distance_policy(), DistancePolicy::SkipEdge);
+    /// # Ok::<(), chutoro_core::HnswError>(())
+    /// ```
+    #[must_use]
+    pub fn with_distance_policy(mut self, policy: DistancePolicy) -> Self {
+        self.distance_policy = policy;
+        self
+    }
+
     /// Overrides the maximum number of cached distances while preserving the
     /// existing cache time-to-live.
     #[must_use]
@@ -106,14 +240,53 @@ impl HnswParams {
         self.ef_construction
     }
 
+    /// Returns the level multiplier, `mL`, of the geometric level sampler.
+    #[must_use]
+    pub fn level_multiplier(&self) -> f64 {
+        self.level_multiplier
+    }
+
+    /// Returns the custom level weights, if levels are not sampled
+    /// geometrically.
+    #[must_use]
+    pub fn level_distribution(&self) -> Option<&[f64]> {
+        self.level_weights.as_deref()
+    }
+
     pub(crate) fn max_level(&self) -> usize {
         self.max_level
     }
 
-    pub(crate) fn rng_seed(&self) -> u64 {
+    /// Returns the seed used for level sampling during insertion.
+    #[must_use]
+    pub fn rng_seed(&self) -> u64 {
         self.rng_seed
     }
 
+    /// Returns the generator used for level sampling.
+    #[must_use]
+    pub fn rng_kind(&self) -> RngKind {
+        self.rng_kind
+    }
+
+    /// Returns the memory layout of the graph's neighbour lists.
+    #[must_use]
+    pub fn adjacency_storage(&self) -> AdjacencyStorage {
+        self.adjacency_storage
+    }
+
+    /// Returns the policy applied when trimming over-full neighbour lists.
+    #[must_use]
+    pub fn trim_policy(&self) -> TrimPolicy {
+        self.trim_policy
+    }
+
+    /// Returns the policy applied to non-finite distances.
+    #[must_use]
+    pub fn distance_policy(&self) -> DistancePolicy {
+        self.distance_policy
+    }
+
     pub(crate) fn distance_cache_config(&self) -> &DistanceCacheConfig {
         &self.distance_cache
     }
@@ -126,6 +299,15 @@ impl HnswParams {
         let clamped = draw.clamp(1.0e-12, 1.0 - f64::EPSILON);
         (-clamped.ln()) * self.level_multiplier < 1.0
     }
+
+    /// Maps a uniform draw to a level of the custom distribution, capped at
+    /// `max_level`.
+    ///
+    /// Returns `None` when levels are sampled geometrically.
+    pub(crate) fn distributed_level(&self, draw: f64) -> Option<usize> {
+        let weights = self.level_weights.as_deref()?;
+        Some(level_from_weights(weights, draw).min(self.max_level))
+    }
 }
 
 impl Default for HnswParams {
@@ -136,15 +318,3 @@ impl Default for HnswParams {
         }
     }
 }
-
-/// Returns the connection limit for a given level.
-///
-/// Level 0 (base layer) permits twice as many connections as higher levels,
-/// following standard HNSW design to improve recall at the densest layer.
-pub(crate) fn connection_limit_for_level(level: usize, max_connections: usize) -> usize {
-    if level == 0 {
-        max_connections.saturating_mul(2)
-    } else {
-        max_connections
-    }
-}
@@ -0,0 +1,118 @@
+//! Per-layer neighbour limits and construction search widths.
+
+use super::{ConnectionLimits, HnswParams, limits::validate_layer_override};
+use crate::hnsw::error::HnswError;
+
+impl HnswParams {
+    /// Sets the base layer's neighbour limit, `M0`, which defaults to twice
+    /// [`Self::max_connections`].
+    ///
+    /// The base layer holds every node and carries most of the recall, so a
+    /// larger `M0` buys recall at the cost of adjacency memory.
+    ///
+    /// # Errors
+    /// Returns [`HnswError::InvalidParameters`] when `m0` is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::HnswParams;
+    ///
+    /// let params = HnswParams::new(16, 64)?.with_base_layer_connections(48)?;
+    /// assert_eq!(params.connection_limit(0), 48);
+    /// assert_eq!(params.connection_limit(1), 16);
+    /// # Ok::<(), chutoro_core::HnswError>(())
+    /// ```
+    pub fn with_base_layer_connections(mut self, m0: usize) -> Result<Self, HnswError> {
+        if m0 == 0 {
+            return Err(HnswError::InvalidParameters {
+                reason: "base-layer connections must be greater than zero".into(),
+            });
+        }
+        self.base_connections = Some(m0);
+        Ok(self)
+    }
+
+    /// Overrides the neighbour limit and construction search width of
+    /// individual layers, given as `(layer, m, ef_construction)` triples.
+    ///
+    /// Layers without an override use [`Self::max_connections`] (or `M0` on
+    /// the base layer) and [`Self::ef_construction`]. An override's `m`
+    /// replaces the layer's limit outright, including the base layer's `M0`.
+    /// Later triples for the same layer replace earlier ones, and each call
+    /// replaces the previous set of overrides.
+    ///
+    /// # Errors
+    /// Returns [`HnswError::InvalidParameters`] when a layer exceeds
+    /// [`crate::MAX_LAYER_OVERRIDE`], `m` is zero, or `ef_construction` is smaller
+    /// than `m`.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::HnswParams;
+    ///
+    /// // Sparser, cheaper upper layers; a wider search on the base layer.
+    /// let params = HnswParams::new(16, 64)?.with_layer_overrides(&[(0, 32, 200), (2, 8, 32)])?;
+    /// assert_eq!(params.connection_limit(0), 32);
+    /// assert_eq!(params.ef_construction_for_layer(0), 200);
+    /// assert_eq!(params.connection_limit(1), 16);
+    /// assert_eq!(params.connection_limit(2), 8);
+    /// assert_eq!(params.ef_construction_for_layer(2), 32);
+    /// # Ok::<(), chutoro_core::HnswError>(())
+    /// ```
+    pub fn with_layer_overrides(
+        mut self,
+        overrides: &[(usize, usize, usize)],
+    ) -> Result<Self, HnswError> {
+        let mut accepted: Vec<(usize, usize, usize)> = Vec::with_capacity(overrides.len());
+        for &(layer, m, ef) in overrides {
+            validate_layer_override(layer, m, ef)?;
+            accepted.retain(|&(existing, _, _)| existing != layer);
+            accepted.push((layer, m, ef));
+        }
+        accepted.sort_unstable_by_key(|&(layer, _, _)| layer);
+        self.layer_overrides = accepted;
+        Ok(self)
+    }
+
+    /// Returns the per-layer `(layer, m, ef_construction)` overrides, sorted
+    /// by layer.
+    #[must_use]
+    pub fn layer_overrides(&self) -> &[(usize, usize, usize)] {
+        &self.layer_overrides
+    }
+
+    /// Returns the neighbour limit enforced on `layer`, after `M0` and any
+    /// layer override.
+    #[must_use]
+    pub fn connection_limit(&self, layer: usize) -> usize {
+        self.connection_limits().for_level(layer)
+    }
+
+    /// Returns the construction search width used on `layer`, after any
+    /// layer override.
+    #[must_use]
+    pub fn ef_construction_for_layer(&self, layer: usize) -> usize {
+        self.layer_override(layer)
+            .map_or(self.ef_construction, |(_, _, ef)| ef)
+    }
+
+    /// Returns the neighbour limit of every layer, for threading through
+    /// insertion without borrowing the parameters.
+    pub(crate) fn connection_limits(&self) -> ConnectionLimits {
+        let mut limits = ConnectionLimits::uniform(self.max_connections);
+        if let Some(m0) = self.base_connections {
+            limits.set(0, m0);
+        }
+        for &(layer, m, _) in &self.layer_overrides {
+            limits.set(layer, m);
+        }
+        limits
+    }
+
+    fn layer_override(&self, layer: usize) -> Option<(usize, usize, usize)> {
+        self.layer_overrides
+            .iter()
+            .copied()
+            .find(|&(overridden, _, _)| overridden == layer)
+    }
+}
@@ -0,0 +1,52 @@
+//! Custom discrete distributions for sampling node levels.
+//!
+//! A distribution is a list of non-negative weights, one per level starting at
+//! the base layer. Weights need not sum to one; each level is drawn with
+//! probability proportional to its weight.
+
+use crate::hnsw::error::HnswError;
+
+/// Checks that `weights` describe a usable distribution whose highest level
+/// does not exceed `max_level`.
+pub(super) fn validate_level_weights(weights: &[f64], max_level: usize) -> Result<(), HnswError> {
+    let reason = if weights.is_empty() {
+        "level distribution must contain at least one weight".to_owned()
+    } else if weights.len() - 1 > max_level {
+        format!(
+            "level distribution covers levels 0..={} but max_level is {max_level}",
+            weights.len() - 1
+        )
+    } else if let Some((level, weight)) = weights
+        .iter()
+        .enumerate()
+        .find(|(_, weight)| !weight.is_finite() || **weight < 0.0)
+    {
+        format!("level {level} weight ({weight}) must be finite and non-negative")
+    } else if weights.iter().sum::<f64>() <= 0.0 {
+        "level distribution must have a positive total weight".to_owned()
+    } else {
+        return Ok(());
+    };
+    Err(HnswError::InvalidParameters { reason })
+}
+
+/// Maps a uniform `draw` in `[0, 1)` to a level drawn from `weights`.
+///
+/// Falls back to the highest weighted level when rounding leaves the scaled
+/// draw beyond the cumulative total.
+pub(super) fn level_from_weights(weights: &[f64], draw: f64) -> usize {
+    let mut remaining = draw.clamp(0.0, 1.0) * weights.iter().sum::<f64>();
+    let mut last_weighted = 0;
+    for (level, &weight) in weights
+        .iter()
+        .enumerate()
+        .filter(|(_, weight)| **weight > 0.0)
+    {
+        if remaining < weight {
+            return level;
+        }
+        remaining -= weight;
+        last_weighted = level;
+    }
+    last_weighted
+}
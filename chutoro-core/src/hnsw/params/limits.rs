@@ -0,0 +1,61 @@
+//! Per-layer neighbour limits resolved from [`super::HnswParams`].
+
+use crate::hnsw::error::HnswError;
+
+/// Highest layer whose neighbour limit and search width can be overridden.
+///
+/// Each layer holds about `1/M` of the nodes of the one below, so layers above
+/// this are practically never populated.
+pub const MAX_LAYER_OVERRIDE: usize = 15;
+
+pub(super) fn validate_layer_override(layer: usize, m: usize, ef: usize) -> Result<(), HnswError> {
+    let reason = if layer > MAX_LAYER_OVERRIDE {
+        format!("layer {layer} exceeds the highest overridable layer ({MAX_LAYER_OVERRIDE})")
+    } else if m == 0 {
+        format!("layer {layer} max_connections must be greater than zero")
+    } else if ef < m {
+        format!("layer {layer} ef_construction ({ef}) must be >= max_connections ({m})")
+    } else {
+        return Ok(());
+    };
+    Err(HnswError::InvalidParameters { reason })
+}
+
+/// Neighbour limits for every layer, copied into insertion contexts.
+///
+/// Layers above [`MAX_LAYER_OVERRIDE`] always use the upper-layer limit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct ConnectionLimits {
+    layers: [usize; MAX_LAYER_OVERRIDE + 1],
+    upper: usize,
+}
+
+impl ConnectionLimits {
+    /// Returns the standard limits: `2 * max_connections` on the base layer
+    /// (following the reference algorithm, to improve recall at the densest
+    /// layer) and `max_connections` above it.
+    pub(crate) fn uniform(max_connections: usize) -> Self {
+        let mut layers = [max_connections; MAX_LAYER_OVERRIDE + 1];
+        layers[0] = max_connections.saturating_mul(2);
+        Self {
+            layers,
+            upper: max_connections,
+        }
+    }
+
+    /// Returns the neighbour limit on `level`.
+    pub(crate) fn for_level(self, level: usize) -> usize {
+        self.layers.get(level).copied().unwrap_or(self.upper)
+    }
+
+    pub(super) fn set(&mut self, level: usize, limit: usize) {
+        if let Some(slot) = self.layers.get_mut(level) {
+            *slot = limit;
+        }
+    }
+}
+
+/// Returns the connection limit for a given level.
+pub(crate) fn connection_limit_for_level(level: usize, limits: ConnectionLimits) -> usize {
+    limits.for_level(level)
+}
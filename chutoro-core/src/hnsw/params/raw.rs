@@ -0,0 +1,56 @@
+//! Validated deserialization of [`HnswParams`].
+
+use super::{AdjacencyStorage, HnswParams, RngKind, TrimPolicy};
+use crate::{
+    DistancePolicy,
+    hnsw::{cache_config::DistanceCacheConfig, error::HnswError},
+};
+
+/// Serialized form of [`HnswParams`], validated on the way back in.
+#[derive(serde::Deserialize)]
+pub(super) struct RawHnswParams {
+    max_connections: usize,
+    ef_construction: usize,
+    level_multiplier: f64,
+    max_level: usize,
+    rng_seed: u64,
+    #[serde(default)]
+    rng_kind: RngKind,
+    distance_cache: DistanceCacheConfig,
+    #[serde(default)]
+    base_connections: Option<usize>,
+    #[serde(default)]
+    layer_overrides: Vec<(usize, usize, usize)>,
+    #[serde(default)]
+    level_weights: Option<Vec<f64>>,
+    #[serde(default)]
+    adjacency_storage: AdjacencyStorage,
+    #[serde(default)]
+    trim_policy: TrimPolicy,
+    #[serde(default)]
+    distance_policy: DistancePolicy,
+}
+
+impl TryFrom<RawHnswParams> for HnswParams {
+    type Error = HnswError;
+
+    fn try_from(raw: RawHnswParams) -> Result<Self, Self::Error> {
+        let mut params = Self::new(raw.max_connections, raw.ef_construction)?
+            .with_level_multiplier(raw.level_multiplier)
+            .with_max_level(raw.max_level)
+            .with_rng_seed(raw.rng_seed)
+            .with_rng_kind(raw.rng_kind)
+            .with_distance_cache_config(raw.distance_cache)
+            .with_distance_policy(raw.distance_policy)
+            .with_adjacency_storage(raw.adjacency_storage)
+            .with_layer_overrides(&raw.layer_overrides)?
+            .with_trim_policy(raw.trim_policy)?;
+        if let Some(m0) = raw.base_connections {
+            params = params.with_base_layer_connections(m0)?;
+        }
+        if let Some(weights) = raw.level_weights {
+            params = params.with_level_distribution(&weights)?;
+        }
+        Ok(params)
+    }
+}
@@ -0,0 +1,41 @@
+//! Memory layouts for the neighbour lists of the CPU HNSW graph.
+
+/// Selects how the CPU index lays out its neighbour lists in memory.
+///
+/// Every node keeps one neighbour list per layer it occupies. The default,
+/// [`Self::PerNode`], gives each list its own growable vector. On builds of
+/// millions of nodes those small allocations carry measurable allocator
+/// overhead and fragmentation; [`Self::Arena`] instead carves fixed-capacity
+/// slices, sized from the layer limits (`M0` on the base layer, the largest
+/// upper-layer `M` above it), out of one base-layer block allocated up front
+/// and one growing block for the upper layers, so attaching a node costs
+/// amortised `O(1)` allocations. A list that outgrows its slice moves to a
+/// side table, so the layout never changes the graph that is built.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::{AdjacencyStorage, HnswParams};
+///
+/// let params = HnswParams::new(16, 64)?.with_adjacency_storage(AdjacencyStorage::Arena);
+/// assert_eq!(params.adjacency_storage(), AdjacencyStorage::Arena);
+/// # Ok::<(), chutoro_core::HnswError>(())
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum AdjacencyStorage {
+    /// One growable vector per node and layer.
+    #[default]
+    PerNode,
+    /// Fixed-capacity slices carved from blocks sized at construction.
+    Arena,
+    /// Like [`Self::Arena`], with the base-layer block mapped anonymously and
+    /// advised for transparent huge pages, cutting TLB misses during search.
+    ///
+    /// The advice is a hint: kernels without transparent huge pages, and
+    /// platforms other than Linux, back the block with ordinary pages, and a
+    /// failed mapping falls back to the heap. Requires the `hugepages`
+    /// feature.
+    #[cfg(feature = "hugepages")]
+    HugePageArena,
+}
@@ -0,0 +1,74 @@
+//! Policies for trimming neighbour lists that exceed their layer's limit.
+
+use crate::hnsw::error::HnswError;
+
+/// Selects which neighbours a node keeps when an insertion pushes its list
+/// past the layer's connection limit.
+///
+/// [`Self::Nearest`], the default, keeps the closest candidates. On clustered
+/// data those tend to sit in the node's own cluster, so the few edges that
+/// bridge clusters are the first to go and the insertion executor has to heal
+/// the graph afterwards. The two pruning policies walk the candidates nearest
+/// first and skip any that a neighbour already kept covers, which spends the
+/// limited degree on distinct directions instead:
+///
+/// - [`Self::DiversityPreserving`] is the heuristic of the HNSW paper: a
+///   candidate is kept only when it is closer to the node than to every
+///   neighbour already kept.
+/// - [`Self::RngRelaxed`] relaxes that relative-neighbourhood-graph test by
+///   `alpha`: a candidate is kept unless some kept neighbour is closer to it
+///   than its distance to the node divided by `alpha`. An `alpha` of `1.0`
+///   matches [`Self::DiversityPreserving`]; larger values prune less and keep
+///   more long edges.
+///
+/// Both pruning policies may leave a list below the limit, and both compute
+/// distances between candidates, so trimming costs more than with
+/// [`Self::Nearest`].
+///
+/// # Examples
+/// ```
+/// use chutoro_core::{HnswParams, TrimPolicy};
+///
+/// let params = HnswParams::new(16, 64)?.with_trim_policy(TrimPolicy::RngRelaxed { alpha: 1.2 })?;
+/// assert_eq!(params.trim_policy(), TrimPolicy::RngRelaxed { alpha: 1.2 });
+/// # Ok::<(), chutoro_core::HnswError>(())
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum TrimPolicy {
+    /// Keep the nearest candidates.
+    #[default]
+    Nearest,
+    /// Keep candidates closer to the node than to any neighbour already kept.
+    DiversityPreserving,
+    /// Keep candidates unless a kept neighbour is closer to them than their
+    /// distance to the node divided by `alpha`.
+    RngRelaxed {
+        /// Relaxation factor; finite and at least `1.0`.
+        alpha: f32,
+    },
+}
+
+impl TrimPolicy {
+    /// Returns the relaxation factor of a pruning policy, or `None` for
+    /// [`Self::Nearest`].
+    pub(crate) fn relaxation(self) -> Option<f32> {
+        match self {
+            Self::Nearest => None,
+            Self::DiversityPreserving => Some(1.0),
+            Self::RngRelaxed { alpha } => Some(alpha),
+        }
+    }
+}
+
+pub(super) fn validate_trim_policy(policy: TrimPolicy) -> Result<(), HnswError> {
+    match policy {
+        TrimPolicy::RngRelaxed { alpha } if !(alpha.is_finite() && alpha >= 1.0) => {
+            Err(HnswError::InvalidParameters {
+                reason: format!("trim relaxation alpha ({alpha}) must be finite and >= 1"),
+            })
+        }
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,74 @@
+//! Per-edge provenance recorded while harvesting candidate edges.
+//!
+//! Every harvested edge carries the insertion sequence of the point whose
+//! insertion found it, so the pair of that sequence and the edge's other
+//! endpoint identifies the search that produced it. The table keeps, for each
+//! such pair, the highest layer whose search returned the neighbour and the
+//! construction search width used there.
+
+use std::collections::HashMap;
+
+use super::{graph::NodeContext, params::HnswParams, types::InsertionPlan};
+use crate::{
+    EdgeProvenance, MstEdge,
+    result::{ExplainedEdge, ExplainedForest},
+};
+
+/// Provenance of the edges harvested while building an index, keyed by
+/// insertion sequence and discovered neighbour.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct HarvestProvenance(HashMap<(u64, usize), EdgeProvenance>);
+
+impl HarvestProvenance {
+    /// Records every neighbour the insertion planned in `plan`.
+    pub(super) fn record(&mut self, ctx: NodeContext, plan: &InsertionPlan, params: &HnswParams) {
+        for layer in &plan.layers {
+            let ef = params.ef_construction_for_layer(layer.level);
+            let found = EdgeProvenance::new(ctx.node, layer.level, ef, ctx.sequence);
+            for neighbour in layer.neighbours.iter().filter(|n| n.id != ctx.node) {
+                self.keep_highest((ctx.sequence, neighbour.id), found);
+            }
+        }
+    }
+
+    fn keep_highest(&mut self, key: (u64, usize), found: EdgeProvenance) {
+        let kept = self.0.entry(key).or_insert(found);
+        if found.layer() > kept.layer() {
+            *kept = found;
+        }
+    }
+
+    /// Absorbs the provenance of other insertions.
+    pub(super) fn merge(&mut self, other: Self) {
+        self.0.extend(other.0);
+    }
+
+    /// Returns where the build found `edge`, or `None` when it did not
+    /// produce the edge.
+    fn lookup(&self, edge: &MstEdge) -> Option<EdgeProvenance> {
+        let (source, target) = (edge.source(), edge.target());
+        let found_by = |neighbour: usize, inserted: usize| {
+            self.0
+                .get(&(edge.sequence(), neighbour))
+                .filter(|provenance| provenance.inserted() == inserted)
+                .copied()
+        };
+        found_by(target, source).or_else(|| found_by(source, target))
+    }
+
+    /// Attaches provenance to each edge of the spanning forest over
+    /// `points` points.
+    pub(crate) fn explain(&self, points: usize, edges: &[MstEdge]) -> ExplainedForest {
+        let explained = edges
+            .iter()
+            .map(|edge| {
+                let pair = (
+                    edge.source().min(edge.target()),
+                    edge.source().max(edge.target()),
+                );
+                ExplainedEdge::new(pair, edge.weight_f64(), self.lookup(edge))
+            })
+            .collect();
+        ExplainedForest::new(points, explained)
+    }
+}
@@ -11,13 +11,20 @@ use crate::DataSource;
 use super::{
     distance_cache::DistanceCache,
     error::HnswError,
-    graph::{ExtendedSearchContext, NeighbourSearchContext, SearchContext},
-    node::Node,
+    graph::ExtendedSearchContext,
     types::Neighbour,
-    validate::{validate_batch_distances, validate_distance},
+    validate::{validate_batch_distances_into, validate_distance},
 };
 
-use super::graph::Graph;
+use self::layout::Layout;
+
+mod filtered;
+mod greedy;
+mod layout;
+mod range;
+
+pub(crate) use filtered::FilteredContext;
+pub(crate) use range::RangeContext;
 
 #[derive(Debug)]
 struct SearchState {
@@ -223,80 +230,23 @@ impl<'a, D: DataSource + Sync> SearchInputs<'a, D> {
         validate_distance(self.cache, self.source, left, right)
     }
 
-    /// Validates and returns the distances from the query node to candidates.
-    fn validate_batch(&self, query: usize, candidates: &[usize]) -> Result<Vec<f32>, HnswError> {
-        validate_batch_distances(self.cache, self.source, query, candidates)
+    /// Validates the distances from the query node to candidates into `out`.
+    fn validate_batch(
+        &self,
+        query: usize,
+        candidates: &[usize],
+        out: &mut Vec<f32>,
+    ) -> Result<(), HnswError> {
+        validate_batch_distances_into((self.cache, self.source), query, candidates, out)
     }
 }
 
 #[derive(Debug)]
 pub(crate) struct LayerSearcher<'graph> {
-    graph: &'graph Graph,
+    graph: Layout<'graph>,
 }
 
-impl<'graph> LayerSearcher<'graph> {
-    pub(super) fn new(graph: &'graph Graph) -> Self {
-        Self { graph }
-    }
-
-    pub(super) fn greedy_search_layer<D: DataSource + Sync>(
-        &self,
-        cache: Option<&DistanceCache>,
-        source: &D,
-        ctx: SearchContext,
-    ) -> Result<usize, HnswError> {
-        let inputs = SearchInputs::new(cache, source);
-        let mut current = ctx.entry();
-        let mut current_dist = inputs.validate_distance(ctx.query(), current)?;
-        let mut improved = true;
-        while improved {
-            improved = false;
-            let Some(node) = self.graph.node(current) else {
-                return Err(HnswError::GraphInvariantViolation {
-                    message: format!(
-                        "node {current} missing during greedy search at level {}",
-                        ctx.level()
-                    ),
-                });
-            };
-
-            let search_ctx = ctx.with_distance(current_dist);
-            let next = self.find_better_neighbour(&inputs, search_ctx, node)?;
-
-            if let Some(neighbour) = next {
-                current = neighbour.id;
-                current_dist = neighbour.distance;
-                improved = true;
-            }
-        }
-        Ok(current)
-    }
-
-    fn find_better_neighbour<D: DataSource + Sync>(
-        &self,
-        inputs: &SearchInputs<'_, D>,
-        ctx: NeighbourSearchContext,
-        node: &Node,
-    ) -> Result<Option<SearchNeighbour>, HnswError> {
-        let neighbours = node.neighbours(ctx.level());
-        if neighbours.is_empty() {
-            return Ok(None);
-        }
-
-        let distances = inputs.validate_batch(ctx.query(), neighbours)?;
-        if let Some((best_id, best_dist)) = neighbours
-            .iter()
-            .copied()
-            .zip(distances)
-            .min_by(|a, b| a.1.total_cmp(&b.1))
-            && best_dist < ctx.current_dist
-        {
-            let sequence = self.sequence_for_node(best_id, "greedy search")?;
-            return Ok(Some(SearchNeighbour::new(best_id, best_dist, sequence)));
-        }
-        Ok(None)
-    }
-
+impl LayerSearcher<'_> {
     fn sequence_or_invariant(&self, node: usize, message: String) -> Result<u64, HnswError> {
         self.graph
             .node_sequence(node)
@@ -328,6 +278,7 @@ impl<'graph> LayerSearcher<'graph> {
         } else {
             SearchState::with_capacity(entry_neighbour, ctx.ef)
         };
+        let mut distances = Vec::new();
 
         while let Some(candidate) = state.pop_candidate() {
             if state.should_terminate(ctx.ef, candidate.distance) {
@@ -358,8 +309,8 @@ impl<'graph> LayerSearcher<'graph> {
                 continue;
             }
 
-            let distances = inputs.validate_batch(ctx.query(), &fresh)?;
-            for (candidate, distance) in fresh.into_iter().zip(distances.into_iter()) {
+            inputs.validate_batch(ctx.query(), &fresh, &mut distances)?;
+            for (candidate, &distance) in fresh.into_iter().zip(&distances) {
                 let sequence = self.sequence_for_node(candidate, "layer expansion")?;
                 state.try_enqueue(SearchNeighbour::new(candidate, distance, sequence), ctx.ef);
             }
@@ -0,0 +1,142 @@
+//! Bottom-layer search restricted to the points a predicate accepts.
+//!
+//! Rejected points still guide the traversal, because they may be the only
+//! links between accepted ones, but they never occupy a result slot. Each
+//! rejected point the search meets widens the beam by one, so the beam keeps
+//! `ef` accepted points however many rejected ones lie between them and the
+//! query. The widening is local to the neighbourhood being expanded: rejected
+//! points farther than the `ef`-th accepted point are never queued.
+
+use std::collections::{BinaryHeap, HashSet};
+
+use crate::DataSource;
+use crate::hnsw::{
+    distance_cache::DistanceCache, error::HnswError, graph::ExtendedSearchContext, types::Neighbour,
+};
+
+use super::{BestNeighbour, CandidateNeighbour, LayerSearcher, SearchInputs, SearchNeighbour};
+
+/// A layer search that keeps only points for which `filter` returns `true`.
+#[derive(Clone, Copy)]
+pub(crate) struct FilteredContext<'f> {
+    pub(crate) base: ExtendedSearchContext,
+    pub(crate) filter: &'f dyn Fn(usize) -> bool,
+}
+
+struct FilteredState<'f> {
+    ef: usize,
+    filter: &'f dyn Fn(usize) -> bool,
+    discovered: HashSet<usize>,
+    candidates: BinaryHeap<CandidateNeighbour>,
+    accepted: BinaryHeap<BestNeighbour>,
+}
+
+impl<'f> FilteredState<'f> {
+    fn new(entry: SearchNeighbour, ctx: FilteredContext<'f>) -> Self {
+        let ef = ctx.base.ef.max(1);
+        let mut state = Self {
+            ef,
+            filter: ctx.filter,
+            discovered: HashSet::from([entry.id]),
+            candidates: BinaryHeap::with_capacity(ef),
+            accepted: BinaryHeap::with_capacity(ef + 1),
+        };
+        state.admit(entry);
+        state
+    }
+
+    fn pop_candidate(&mut self) -> Option<SearchNeighbour> {
+        self.candidates
+            .pop()
+            .map(|CandidateNeighbour(neighbour)| neighbour)
+    }
+
+    fn discover(&mut self, candidate: usize) -> bool {
+        self.discovered.insert(candidate)
+    }
+
+    /// Returns whether `distance` is no closer than the furthest of `ef`
+    /// accepted points, so neither it nor anything beyond it can improve
+    /// the results.
+    fn beyond_full_results(&self, distance: f32) -> bool {
+        self.accepted.len() >= self.ef
+            && self
+                .accepted
+                .peek()
+                .is_some_and(|BestNeighbour(furthest)| distance >= furthest.distance)
+    }
+
+    /// Queues `candidate` for expansion and, when the filter accepts it,
+    /// keeps it as a result.
+    fn admit(&mut self, candidate: SearchNeighbour) {
+        if self.beyond_full_results(candidate.distance) {
+            return;
+        }
+        self.candidates.push(CandidateNeighbour(candidate));
+        if (self.filter)(candidate.id) {
+            self.accepted.push(BestNeighbour(candidate));
+            if self.accepted.len() > self.ef {
+                self.accepted.pop();
+            }
+        }
+    }
+
+    fn finalise(self) -> Vec<Neighbour> {
+        self.accepted
+            .into_sorted_vec()
+            .into_iter()
+            .map(|BestNeighbour(neighbour)| neighbour.into_public())
+            .collect()
+    }
+}
+
+impl LayerSearcher<'_> {
+    /// Returns up to `ctx.base.ef` accepted points closest to the query,
+    /// closest first, searching the layer from `ctx.base.entry()`.
+    ///
+    /// A filter that accepts no reachable point expands every point the
+    /// layer connects to the entry.
+    pub(crate) fn filtered_search_layer<D: DataSource + Sync>(
+        &self,
+        cache: Option<&DistanceCache>,
+        source: &D,
+        ctx: FilteredContext<'_>,
+    ) -> Result<Vec<Neighbour>, HnswError> {
+        let inputs = SearchInputs::new(cache, source);
+        let (query, entry, level) = (ctx.base.query(), ctx.base.entry(), ctx.base.level());
+        let entry_dist = inputs.validate_distance(query, entry)?;
+        let entry_sequence = self.sequence_for_node(entry, "filtered search")?;
+        let mut state =
+            FilteredState::new(SearchNeighbour::new(entry, entry_dist, entry_sequence), ctx);
+
+        let mut distances = Vec::new();
+        while let Some(candidate) = state.pop_candidate() {
+            if state.beyond_full_results(candidate.distance) {
+                break;
+            }
+            let Some(node) = self.graph.node(candidate.id) else {
+                return Err(HnswError::GraphInvariantViolation {
+                    message: format!(
+                        "node {} missing during filtered search at level {level}",
+                        candidate.id
+                    ),
+                });
+            };
+            let fresh: Vec<_> = node
+                .neighbours(level)
+                .iter()
+                .copied()
+                .filter(|neighbour| state.discover(*neighbour))
+                .collect();
+            if fresh.is_empty() {
+                continue;
+            }
+            inputs.validate_batch(query, &fresh, &mut distances)?;
+            for (neighbour, &distance) in fresh.into_iter().zip(&distances) {
+                let sequence = self.sequence_for_node(neighbour, "filtered expansion")?;
+                state.admit(SearchNeighbour::new(neighbour, distance, sequence));
+            }
+        }
+        Ok(state.finalise())
+    }
+}
@@ -0,0 +1,99 @@
+//! Greedy descent through the upper layers of the graph.
+//!
+//! Each step moves to the neighbour closest to the query and stops at the
+//! first node none of whose neighbours is closer.
+
+use crate::DataSource;
+use crate::hnsw::{
+    distance_cache::DistanceCache,
+    error::HnswError,
+    graph::{NeighbourSearchContext, SearchContext},
+    node::NodeRef,
+};
+
+use super::{LayerSearcher, SearchInputs, SearchNeighbour};
+
+impl LayerSearcher<'_> {
+    pub(in crate::hnsw) fn greedy_search_layer<D: DataSource + Sync>(
+        &self,
+        cache: Option<&DistanceCache>,
+        source: &D,
+        ctx: SearchContext,
+    ) -> Result<usize, HnswError> {
+        self.greedy_walk(&SearchInputs::new(cache, source), ctx, |_| {})
+    }
+
+    /// Descends greedily like [`Self::greedy_search_layer`] and returns every
+    /// node the walk stood on, from the entry to the local minimum.
+    pub(crate) fn greedy_search_path<D: DataSource + Sync>(
+        &self,
+        cache: Option<&DistanceCache>,
+        source: &D,
+        ctx: SearchContext,
+    ) -> Result<Vec<usize>, HnswError> {
+        let mut path = Vec::new();
+        self.greedy_walk(&SearchInputs::new(cache, source), ctx, |node| {
+            path.push(node);
+        })?;
+        Ok(path)
+    }
+
+    fn greedy_walk<D: DataSource + Sync>(
+        &self,
+        inputs: &SearchInputs<'_, D>,
+        ctx: SearchContext,
+        mut visit: impl FnMut(usize),
+    ) -> Result<usize, HnswError> {
+        let mut current = ctx.entry();
+        let mut current_dist = inputs.validate_distance(ctx.query(), current)?;
+        let mut improved = true;
+        while improved {
+            improved = false;
+            visit(current);
+            let Some(node) = self.graph.node(current) else {
+                return Err(HnswError::GraphInvariantViolation {
+                    message: format!(
+                        "node {current} missing during greedy search at level {}",
+                        ctx.level()
+                    ),
+                });
+            };
+
+            let search_ctx = ctx.with_distance(current_dist);
+            let next = self.find_better_neighbour(inputs, search_ctx, node)?;
+
+            if let Some(neighbour) = next {
+                current = neighbour.id;
+                current_dist = neighbour.distance;
+                improved = true;
+            }
+        }
+        Ok(current)
+    }
+
+    fn find_better_neighbour<D: DataSource + Sync>(
+        &self,
+        inputs: &SearchInputs<'_, D>,
+        ctx: NeighbourSearchContext,
+        node: NodeRef<'_>,
+    ) -> Result<Option<SearchNeighbour>, HnswError> {
+        let neighbours = node.neighbours(ctx.level());
+        if neighbours.is_empty() {
+            return Ok(None);
+        }
+
+        let mut distances = Vec::new();
+        inputs.validate_batch(ctx.query(), neighbours, &mut distances)?;
+        if let Some((best_id, best_dist)) = neighbours
+            .iter()
+            .copied()
+            .zip(distances)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            && best_dist < ctx.current_dist
+        {
+            let sequence = self.sequence_for_node(best_id, "greedy search")?;
+            return Ok(Some(SearchNeighbour::new(best_id, best_dist, sequence)));
+        }
+        Ok(None)
+    }
+}
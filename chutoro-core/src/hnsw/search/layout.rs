@@ -0,0 +1,58 @@
+//! Graph layouts a [`LayerSearcher`] can walk.
+
+use crate::hnsw::{
+    graph::{FrozenGraph, Graph},
+    node::NodeRef,
+    types::EntryPoint,
+};
+
+use super::LayerSearcher;
+
+/// The live graph being built, or a frozen snapshot of a finished one.
+#[derive(Clone, Copy, Debug)]
+pub(super) enum Layout<'graph> {
+    Live(&'graph Graph),
+    Frozen(&'graph FrozenGraph),
+}
+
+impl<'graph> Layout<'graph> {
+    pub(super) fn node(self, id: usize) -> Option<NodeRef<'graph>> {
+        match self {
+            Self::Live(graph) => graph.node(id),
+            Self::Frozen(graph) => graph.node(id),
+        }
+    }
+
+    pub(super) fn node_sequence(self, id: usize) -> Option<u64> {
+        match self {
+            Self::Live(graph) => graph.node_sequence(id),
+            Self::Frozen(graph) => graph.node_sequence(id),
+        }
+    }
+
+    fn entry(self) -> Option<EntryPoint> {
+        match self {
+            Self::Live(graph) => graph.entry(),
+            Self::Frozen(graph) => graph.entry(),
+        }
+    }
+}
+
+impl<'graph> LayerSearcher<'graph> {
+    pub(crate) fn new(graph: &'graph Graph) -> Self {
+        Self {
+            graph: Layout::Live(graph),
+        }
+    }
+
+    pub(crate) fn frozen(graph: &'graph FrozenGraph) -> Self {
+        Self {
+            graph: Layout::Frozen(graph),
+        }
+    }
+
+    /// Returns the entry point of the graph being searched.
+    pub(crate) fn entry(&self) -> Option<EntryPoint> {
+        self.graph.entry()
+    }
+}
@@ -0,0 +1,156 @@
+//! Radius-bounded search over the bottom layer.
+//!
+//! Range search reuses the best-first expansion of the k-NN layer search but
+//! only expands points inside the radius, so the traversal stays within the
+//! ball once it has reached it. Until the first point inside the radius is
+//! found, the search moves greedily towards the query.
+
+use std::collections::{BinaryHeap, HashSet};
+
+use crate::DataSource;
+use crate::hnsw::{
+    distance_cache::DistanceCache, error::HnswError, graph::SearchContext, types::Neighbour,
+};
+
+use super::{BestNeighbour, CandidateNeighbour, LayerSearcher, SearchInputs, SearchNeighbour};
+
+/// A bottom-layer search for points within `radius` of the query, keeping
+/// at most `limit` of them.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct RangeContext {
+    pub(crate) base: SearchContext,
+    pub(crate) radius: f32,
+    pub(crate) limit: usize,
+}
+
+#[derive(Debug)]
+struct RangeState {
+    radius: f32,
+    limit: usize,
+    discovered: HashSet<usize>,
+    candidates: BinaryHeap<CandidateNeighbour>,
+    found: BinaryHeap<BestNeighbour>,
+}
+
+impl RangeState {
+    fn new(entry: SearchNeighbour, ctx: RangeContext) -> Self {
+        let mut state = Self {
+            radius: ctx.radius,
+            limit: ctx.limit,
+            discovered: HashSet::from([entry.id]),
+            candidates: BinaryHeap::from([CandidateNeighbour(entry)]),
+            found: BinaryHeap::new(),
+        };
+        if entry.distance <= state.radius {
+            state.found.push(BestNeighbour(entry));
+        }
+        state
+    }
+
+    fn pop_candidate(&mut self) -> Option<SearchNeighbour> {
+        self.candidates
+            .pop()
+            .map(|CandidateNeighbour(neighbour)| neighbour)
+    }
+
+    fn discover(&mut self, candidate: usize) -> bool {
+        self.discovered.insert(candidate)
+    }
+
+    /// Returns whether `distance` is no closer than the furthest point kept
+    /// once `limit` points have been found.
+    fn beyond_full_results(&self, distance: f32) -> bool {
+        self.found.len() >= self.limit
+            && self
+                .found
+                .peek()
+                .is_some_and(|BestNeighbour(furthest)| distance >= furthest.distance)
+    }
+
+    /// Returns whether no remaining candidate can improve the results.
+    ///
+    /// Candidates come off the queue closest first, so once a point inside
+    /// the radius has been found, the first candidate outside it means the
+    /// ball has been exhausted.
+    fn should_terminate(&self, candidate_distance: f32) -> bool {
+        (candidate_distance > self.radius && !self.found.is_empty())
+            || self.beyond_full_results(candidate_distance)
+    }
+
+    /// Records `candidate`, reached from a point at distance `from`.
+    ///
+    /// Points inside the radius are kept and expanded. Points outside it are
+    /// only expanded while approaching the ball, and only when they move
+    /// closer to the query.
+    fn admit(&mut self, candidate: SearchNeighbour, from: f32) {
+        if self.beyond_full_results(candidate.distance) {
+            return;
+        }
+        if candidate.distance <= self.radius {
+            self.found.push(BestNeighbour(candidate));
+            if self.found.len() > self.limit {
+                self.found.pop();
+            }
+        } else if !self.found.is_empty() || candidate.distance >= from {
+            return;
+        }
+        self.candidates.push(CandidateNeighbour(candidate));
+    }
+
+    fn finalise(self) -> Vec<Neighbour> {
+        let mut neighbours = self.found.into_vec();
+        neighbours.sort_unstable();
+        neighbours
+            .into_iter()
+            .map(|BestNeighbour(neighbour)| neighbour.into_public())
+            .collect()
+    }
+}
+
+impl LayerSearcher<'_> {
+    /// Returns up to `ctx.limit` points within `ctx.radius` of the query,
+    /// closest first, searching the bottom layer from `ctx.base.entry`.
+    pub(crate) fn range_search_layer<D: DataSource + Sync>(
+        &self,
+        cache: Option<&DistanceCache>,
+        source: &D,
+        ctx: RangeContext,
+    ) -> Result<Vec<Neighbour>, HnswError> {
+        let inputs = SearchInputs::new(cache, source);
+        let SearchContext { query, entry, .. } = ctx.base;
+        let entry_dist = inputs.validate_distance(query, entry)?;
+        let entry_sequence = self.sequence_for_node(entry, "range search")?;
+        let mut state =
+            RangeState::new(SearchNeighbour::new(entry, entry_dist, entry_sequence), ctx);
+
+        let mut distances = Vec::new();
+        while let Some(candidate) = state.pop_candidate() {
+            if state.should_terminate(candidate.distance) {
+                break;
+            }
+            let Some(node) = self.graph.node(candidate.id) else {
+                return Err(HnswError::GraphInvariantViolation {
+                    message: format!("node {} missing during range search", candidate.id),
+                });
+            };
+            let fresh: Vec<_> = node
+                .neighbours(0)
+                .iter()
+                .copied()
+                .filter(|neighbour| state.discover(*neighbour))
+                .collect();
+            if fresh.is_empty() {
+                continue;
+            }
+            inputs.validate_batch(query, &fresh, &mut distances)?;
+            for (neighbour, &distance) in fresh.into_iter().zip(&distances) {
+                let sequence = self.sequence_for_node(neighbour, "range expansion")?;
+                state.admit(
+                    SearchNeighbour::new(neighbour, distance, sequence),
+                    candidate.distance,
+                );
+            }
+        }
+        Ok(state.finalise())
+    }
+}
@@ -0,0 +1,130 @@
+//! Structural statistics for a [`crate::CpuHnsw`] index.
+//!
+//! Summarizes how nodes are distributed across levels, how densely each level
+//! is connected, and how full the distance cache is. The figures support
+//! capacity planning and let tests assert that level sampling follows the
+//! expected geometric tail.
+
+use super::{distance_cache::DistanceCache, graph::Graph};
+
+/// Snapshot of an index's level distribution, connectivity, and cache use.
+///
+/// Edge counts are directed adjacency entries: an undirected link stored on
+/// both endpoints counts twice.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::{CpuHnsw, DataSource, DataSourceError, HnswParams};
+/// # struct Dummy(Vec<f32>);
+/// # impl DataSource for Dummy {
+/// #     fn len(&self) -> usize { self.0.len() }
+/// #     fn name(&self) -> &str { "dummy" }
+/// #     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+/// #         Ok((self.0[i] - self.0[j]).abs())
+/// #     }
+/// # }
+/// let params = HnswParams::new(2, 4).expect("params");
+/// let data = Dummy(vec![0.0, 1.0, 3.5, 7.0]);
+/// let index = CpuHnsw::build(&data, params).expect("build must succeed");
+/// let stats = index.statistics().expect("graph lock is healthy");
+/// assert_eq!(stats.nodes_per_level()[0], 4);
+/// assert!(stats.total_edges() > 0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct HnswStatistics {
+    nodes_per_level: Vec<usize>,
+    edges_per_level: Vec<usize>,
+    entry_level: Option<usize>,
+    cache_entries: usize,
+    cache_capacity: usize,
+    cache_evictions: u64,
+}
+
+impl HnswStatistics {
+    pub(crate) fn collect(graph: &Graph, cache: &DistanceCache) -> Self {
+        let mut nodes_per_level = Vec::new();
+        let mut edges_per_level = Vec::new();
+        for (_, node) in graph.nodes_iter() {
+            let levels = node.level_count();
+            if nodes_per_level.len() < levels {
+                nodes_per_level.resize(levels, 0);
+                edges_per_level.resize(levels, 0);
+            }
+            for level in 0..levels {
+                nodes_per_level[level] += 1;
+                edges_per_level[level] += node.neighbours(level).len();
+            }
+        }
+        Self {
+            nodes_per_level,
+            edges_per_level,
+            entry_level: graph.entry().map(|entry| entry.level),
+            cache_entries: cache.len(),
+            cache_capacity: cache.capacity(),
+            cache_evictions: cache.evictions(),
+        }
+    }
+
+    /// Returns the number of nodes present on each level, starting at the
+    /// base layer. Every node appears on level `0` and on each level up to
+    /// its sampled level.
+    #[must_use]
+    pub fn nodes_per_level(&self) -> &[usize] {
+        &self.nodes_per_level
+    }
+
+    /// Returns the number of inserted nodes.
+    #[must_use]
+    pub fn node_count(&self) -> usize {
+        self.nodes_per_level.first().copied().unwrap_or(0)
+    }
+
+    /// Returns the stored adjacency entries on each level.
+    #[must_use]
+    pub fn edges_per_level(&self) -> &[usize] {
+        &self.edges_per_level
+    }
+
+    /// Returns the mean out-degree of the nodes on each level.
+    #[must_use]
+    pub fn average_degree_per_level(&self) -> Vec<f64> {
+        self.nodes_per_level
+            .iter()
+            .zip(&self.edges_per_level)
+            .map(|(&nodes, &edges)| edges as f64 / nodes as f64)
+            .collect()
+    }
+
+    /// Returns the stored adjacency entries across all levels.
+    #[must_use]
+    pub fn total_edges(&self) -> usize {
+        self.edges_per_level.iter().sum()
+    }
+
+    /// Returns the level of the entry point, or `None` for an empty index.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn entry_level(&self) -> Option<usize> { self.entry_level }
+
+    /// Returns the number of distances currently held in the cache.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn cache_entries(&self) -> usize { self.cache_entries }
+
+    /// Returns the maximum number of distances the cache retains.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn cache_capacity(&self) -> usize { self.cache_capacity }
+
+    /// Returns how many cached distances were evicted, for capacity or
+    /// expiry, since the index was built.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn cache_evictions(&self) -> u64 { self.cache_evictions }
+
+    /// Returns the fraction of the cache capacity in use.
+    #[must_use]
+    pub fn cache_occupancy(&self) -> f64 {
+        self.cache_entries as f64 / self.cache_capacity as f64
+    }
+}
@@ -0,0 +1,235 @@
+//! Tests that HNSW scoring goes through the batched distance methods.
+
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use rstest::rstest;
+
+use crate::{
+    DataSource, DataSourceError,
+    hnsw::{CpuHnsw, HnswParams},
+    test_utils::CountingSource,
+};
+
+#[derive(Clone)]
+struct DistanceBatchInstrumentedSource {
+    base: CountingSource,
+    batch_calls: Arc<AtomicUsize>,
+}
+
+impl DistanceBatchInstrumentedSource {
+    fn new(data: Vec<f32>, batch_calls: Arc<AtomicUsize>) -> Self {
+        let base = CountingSource::with_name(
+            "distance-batch-instrumented",
+            data,
+            Arc::new(AtomicUsize::new(0)),
+        );
+        Self { base, batch_calls }
+    }
+}
+
+impl DataSource for DistanceBatchInstrumentedSource {
+    fn len(&self) -> usize {
+        self.base.len()
+    }
+
+    fn name(&self) -> &str {
+        self.base.name()
+    }
+
+    fn distance(&self, left: usize, right: usize) -> Result<f32, DataSourceError> {
+        self.base.distance(left, right)
+    }
+
+    fn distance_batch(
+        &self,
+        pairs: &[(usize, usize)],
+        out: &mut [f32],
+    ) -> Result<(), DataSourceError> {
+        self.batch_calls.fetch_add(1, Ordering::Relaxed);
+        if pairs.len() != out.len() {
+            return Err(DataSourceError::OutputLengthMismatch {
+                out: out.len(),
+                expected: pairs.len(),
+            });
+        }
+
+        for ((left, right), slot) in pairs.iter().copied().zip(out.iter_mut()) {
+            let a = self
+                .base
+                .data()
+                .get(left)
+                .ok_or(DataSourceError::OutOfBounds { index: left })?;
+            let b = self
+                .base
+                .data()
+                .get(right)
+                .ok_or(DataSourceError::OutOfBounds { index: right })?;
+            *slot = (a - b).abs();
+        }
+        Ok(())
+    }
+}
+
+#[rstest]
+fn uses_batch_distances_during_scoring() {
+    #[derive(Clone)]
+    struct InstrumentedSource {
+        base: CountingSource,
+        batch_calls: Arc<AtomicUsize>,
+    }
+
+    impl InstrumentedSource {
+        fn new(data: Vec<f32>, batch_calls: Arc<AtomicUsize>) -> Self {
+            let base =
+                CountingSource::with_name("instrumented", data, Arc::new(AtomicUsize::new(0)));
+            Self { base, batch_calls }
+        }
+    }
+
+    impl DataSource for InstrumentedSource {
+        fn len(&self) -> usize {
+            self.base.len()
+        }
+
+        fn name(&self) -> &str {
+            self.base.name()
+        }
+
+        fn distance(&self, left: usize, right: usize) -> Result<f32, DataSourceError> {
+            self.base.distance(left, right)
+        }
+
+        fn batch_distances(
+            &self,
+            query: usize,
+            candidates: &[usize],
+        ) -> Result<Vec<f32>, DataSourceError> {
+            self.batch_calls.fetch_add(1, Ordering::Relaxed);
+            candidates
+                .iter()
+                .map(|&candidate| {
+                    let a = self
+                        .base
+                        .data()
+                        .get(query)
+                        .ok_or(DataSourceError::OutOfBounds { index: query })?;
+                    let b = self
+                        .base
+                        .data()
+                        .get(candidate)
+                        .ok_or(DataSourceError::OutOfBounds { index: candidate })?;
+                    Ok((a - b).abs())
+                })
+                .collect()
+        }
+    }
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let source = InstrumentedSource::new(vec![0.0, 1.0, 2.0, 5.0], Arc::clone(&calls));
+    let params = HnswParams::new(2, 4)
+        .expect("params must be valid")
+        .with_rng_seed(11);
+    let index = CpuHnsw::build(&source, params).expect("build must succeed");
+
+    index
+        .search(
+            &source,
+            1,
+            NonZeroUsize::new(4).expect("ef must be non-zero"),
+        )
+        .expect("search must succeed");
+
+    assert!(
+        calls.load(Ordering::Relaxed) > 0,
+        "batch distances should be exercised",
+    );
+}
+
+#[rstest]
+fn uses_distance_batch_via_default_batch_distances_during_scoring() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let source = DistanceBatchInstrumentedSource::new(vec![0.0, 1.0, 2.0, 5.0], Arc::clone(&calls));
+    let params = HnswParams::new(2, 4)
+        .expect("params must be valid")
+        .with_rng_seed(13);
+    let index = CpuHnsw::build(&source, params).expect("build must succeed");
+
+    index
+        .search(
+            &source,
+            1,
+            NonZeroUsize::new(4).expect("ef must be non-zero"),
+        )
+        .expect("search must succeed");
+
+    assert!(
+        calls.load(Ordering::Relaxed) > 0,
+        "distance_batch should be exercised via default batch_distances",
+    );
+}
+
+#[rstest]
+fn search_scores_neighbours_through_batch_distances_into() {
+    #[derive(Clone)]
+    struct QueryBatchSource {
+        base: CountingSource,
+        into_calls: Arc<AtomicUsize>,
+    }
+
+    impl DataSource for QueryBatchSource {
+        fn len(&self) -> usize {
+            self.base.len()
+        }
+
+        fn name(&self) -> &str {
+            self.base.name()
+        }
+
+        fn distance(&self, left: usize, right: usize) -> Result<f32, DataSourceError> {
+            self.base.distance(left, right)
+        }
+
+        fn batch_distances_into(
+            &self,
+            query: usize,
+            targets: &[usize],
+            out: &mut [f32],
+        ) -> Result<(), DataSourceError> {
+            self.into_calls.fetch_add(1, Ordering::Relaxed);
+            for (&target, slot) in targets.iter().zip(out.iter_mut()) {
+                *slot = self.base.distance(query, target)?;
+            }
+            Ok(())
+        }
+    }
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let source = QueryBatchSource {
+        base: CountingSource::new(vec![0.0, 1.0, 2.0, 5.0, 9.0], Arc::new(AtomicUsize::new(0))),
+        into_calls: Arc::clone(&calls),
+    };
+    let params = HnswParams::new(2, 4)
+        .expect("params must be valid")
+        .with_rng_seed(17);
+    let index = CpuHnsw::build(&source, params).expect("build must succeed");
+
+    let neighbours = index
+        .search(
+            &source,
+            1,
+            NonZeroUsize::new(4).expect("ef must be non-zero"),
+        )
+        .expect("search must succeed");
+
+    assert_eq!(neighbours.first().map(|neighbour| neighbour.id), Some(1));
+    assert!(
+        calls.load(Ordering::Relaxed) > 0,
+        "search should score neighbours through batch_distances_into",
+    );
+}
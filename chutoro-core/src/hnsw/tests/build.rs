@@ -1,83 +1,19 @@
 //! Build and end-to-end search tests for the CPU HNSW index.
 
-use std::{
-    num::NonZeroUsize,
-    sync::{
-        Arc,
-        atomic::{AtomicUsize, Ordering},
-    },
-};
+use std::num::NonZeroUsize;
 
 use rstest::rstest;
 
 use crate::{
-    DataSource, DataSourceError,
-    hnsw::{CpuHnsw, HnswError, HnswParams, graph::EdgeContext, insert::TrimJob},
-    test_utils::CountingSource,
+    DataSource,
+    hnsw::{
+        CpuHnsw, HnswError, HnswParams, graph::EdgeContext, insert::TrimJob,
+        params::ConnectionLimits,
+    },
 };
 
 use super::fixtures::{DummySource, assert_sorted_by_distance};
 
-#[derive(Clone)]
-struct DistanceBatchInstrumentedSource {
-    base: CountingSource,
-    batch_calls: Arc<AtomicUsize>,
-}
-
-impl DistanceBatchInstrumentedSource {
-    fn new(data: Vec<f32>, batch_calls: Arc<AtomicUsize>) -> Self {
-        let base = CountingSource::with_name(
-            "distance-batch-instrumented",
-            data,
-            Arc::new(AtomicUsize::new(0)),
-        );
-        Self { base, batch_calls }
-    }
-}
-
-impl DataSource for DistanceBatchInstrumentedSource {
-    fn len(&self) -> usize {
-        self.base.len()
-    }
-
-    fn name(&self) -> &str {
-        self.base.name()
-    }
-
-    fn distance(&self, left: usize, right: usize) -> Result<f32, DataSourceError> {
-        self.base.distance(left, right)
-    }
-
-    fn distance_batch(
-        &self,
-        pairs: &[(usize, usize)],
-        out: &mut [f32],
-    ) -> Result<(), DataSourceError> {
-        self.batch_calls.fetch_add(1, Ordering::Relaxed);
-        if pairs.len() != out.len() {
-            return Err(DataSourceError::OutputLengthMismatch {
-                out: out.len(),
-                expected: pairs.len(),
-            });
-        }
-
-        for ((left, right), slot) in pairs.iter().copied().zip(out.iter_mut()) {
-            let a = self
-                .base
-                .data()
-                .get(left)
-                .ok_or(DataSourceError::OutOfBounds { index: left })?;
-            let b = self
-                .base
-                .data()
-                .get(right)
-                .ok_or(DataSourceError::OutOfBounds { index: right })?;
-            *slot = (a - b).abs();
-        }
-        Ok(())
-    }
-}
-
 #[rstest]
 #[case(2, 8)]
 #[case(4, 16)]
@@ -163,104 +99,6 @@ fn builds_and_searches(#[case] m: usize, #[case] ef: usize) {
     assert_sorted_by_distance(&neighbours);
 }
 
-#[rstest]
-fn uses_batch_distances_during_scoring() {
-    #[derive(Clone)]
-    struct InstrumentedSource {
-        base: CountingSource,
-        batch_calls: Arc<AtomicUsize>,
-    }
-
-    impl InstrumentedSource {
-        fn new(data: Vec<f32>, batch_calls: Arc<AtomicUsize>) -> Self {
-            let base =
-                CountingSource::with_name("instrumented", data, Arc::new(AtomicUsize::new(0)));
-            Self { base, batch_calls }
-        }
-    }
-
-    impl DataSource for InstrumentedSource {
-        fn len(&self) -> usize {
-            self.base.len()
-        }
-
-        fn name(&self) -> &str {
-            self.base.name()
-        }
-
-        fn distance(&self, left: usize, right: usize) -> Result<f32, DataSourceError> {
-            self.base.distance(left, right)
-        }
-
-        fn batch_distances(
-            &self,
-            query: usize,
-            candidates: &[usize],
-        ) -> Result<Vec<f32>, DataSourceError> {
-            self.batch_calls.fetch_add(1, Ordering::Relaxed);
-            candidates
-                .iter()
-                .map(|&candidate| {
-                    let a = self
-                        .base
-                        .data()
-                        .get(query)
-                        .ok_or(DataSourceError::OutOfBounds { index: query })?;
-                    let b = self
-                        .base
-                        .data()
-                        .get(candidate)
-                        .ok_or(DataSourceError::OutOfBounds { index: candidate })?;
-                    Ok((a - b).abs())
-                })
-                .collect()
-        }
-    }
-
-    let calls = Arc::new(AtomicUsize::new(0));
-    let source = InstrumentedSource::new(vec![0.0, 1.0, 2.0, 5.0], Arc::clone(&calls));
-    let params = HnswParams::new(2, 4)
-        .expect("params must be valid")
-        .with_rng_seed(11);
-    let index = CpuHnsw::build(&source, params).expect("build must succeed");
-
-    index
-        .search(
-            &source,
-            1,
-            NonZeroUsize::new(4).expect("ef must be non-zero"),
-        )
-        .expect("search must succeed");
-
-    assert!(
-        calls.load(Ordering::Relaxed) > 0,
-        "batch distances should be exercised",
-    );
-}
-
-#[rstest]
-fn uses_distance_batch_via_default_batch_distances_during_scoring() {
-    let calls = Arc::new(AtomicUsize::new(0));
-    let source = DistanceBatchInstrumentedSource::new(vec![0.0, 1.0, 2.0, 5.0], Arc::clone(&calls));
-    let params = HnswParams::new(2, 4)
-        .expect("params must be valid")
-        .with_rng_seed(13);
-    let index = CpuHnsw::build(&source, params).expect("build must succeed");
-
-    index
-        .search(
-            &source,
-            1,
-            NonZeroUsize::new(4).expect("ef must be non-zero"),
-        )
-        .expect("search must succeed");
-
-    assert!(
-        calls.load(Ordering::Relaxed) > 0,
-        "distance_batch should be exercised via default batch_distances",
-    );
-}
-
 #[rstest]
 fn duplicate_insert_is_rejected() {
     let source = DummySource::new(vec![0.0, 1.0, 2.0]);
@@ -294,7 +132,7 @@ fn trimming_prefers_lower_id_on_distance_ties() -> Result<(), HnswError> {
     let index = CpuHnsw::with_capacity(params.clone(), 3)?;
     let ctx = EdgeContext {
         level: 0,
-        max_connections: params.max_connections(),
+        limits: params.connection_limits(),
     };
     let job = TrimJob {
         node: 0,
@@ -328,7 +166,7 @@ fn score_trim_jobs_limits_results_to_max_connections() -> Result<(), HnswError>
     let index = CpuHnsw::with_capacity(params.clone(), 6)?;
     let ctx = EdgeContext {
         level: 0,
-        max_connections: 2,
+        limits: ConnectionLimits::uniform(2),
     };
     let job = TrimJob {
         node: 0,
@@ -355,7 +193,7 @@ fn score_trim_jobs_rejects_length_mismatch() {
     let index = CpuHnsw::with_capacity(params.clone(), 3).expect("index");
     let ctx = EdgeContext {
         level: 0,
-        max_connections: 1,
+        limits: ConnectionLimits::uniform(1),
     };
     let job = TrimJob {
         node: 0,
@@ -6,7 +6,10 @@ use rstest::rstest;
 
 use crate::{
     MetricDescriptor,
-    hnsw::distance_cache::{DistanceCache, DistanceCacheConfig, LookupOutcome},
+    hnsw::{
+        DistanceCacheConfig,
+        distance_cache::{DistanceCache, LookupOutcome},
+    },
 };
 
 fn cache_with_capacity(capacity: usize) -> DistanceCache {
@@ -34,6 +37,26 @@ fn caches_and_reuses_distances() {
     }
 }
 
+#[rstest]
+fn repeated_hits_keep_the_entry_cached() {
+    let cache = cache_with_capacity(4);
+    let metric = MetricDescriptor::new("repeat");
+    let LookupOutcome::Miss(miss) = cache.begin_lookup(&metric, 2, 3) else {
+        panic!("cache should be empty on first lookup");
+    };
+    cache
+        .complete_miss(miss, 1.5)
+        .expect("completing miss must succeed");
+
+    for _ in 0..3 {
+        match cache.begin_lookup(&metric, 2, 3) {
+            LookupOutcome::Hit(value) => assert_eq!(value, 1.5),
+            LookupOutcome::Miss(_) => panic!("a hit must not evict its own entry"),
+        }
+    }
+    assert_eq!(cache.len(), 1);
+}
+
 #[rstest]
 fn lru_eviction_discards_oldest_entry() {
     let cache = cache_with_capacity(2);
@@ -77,6 +100,28 @@ fn lru_eviction_discards_oldest_entry() {
     }
 }
 
+#[rstest]
+#[case::fewer_than_entries(2)]
+#[case::more_than_entries(64)]
+fn fixed_shard_counts_keep_capacity_bounded(#[case] shards: usize) {
+    let config = DistanceCacheConfig::new(NonZeroUsize::new(8).expect("non-zero"))
+        .with_shards(NonZeroUsize::new(shards));
+    let cache = DistanceCache::new(config);
+    let metric = MetricDescriptor::new("sharded");
+
+    for right in 1..=32 {
+        let LookupOutcome::Miss(miss) = cache.begin_lookup(&metric, 0, right) else {
+            panic!("each pair is new");
+        };
+        cache
+            .complete_miss(miss, right as f32)
+            .expect("completing miss must succeed");
+    }
+
+    assert_eq!(cache.capacity(), 8);
+    assert!(cache.len() <= 8, "cache holds {} entries", cache.len());
+}
+
 #[rstest]
 fn ttl_expiry_forces_refresh() {
     let config = DistanceCacheConfig::new(NonZeroUsize::new(2).expect("capacity"))
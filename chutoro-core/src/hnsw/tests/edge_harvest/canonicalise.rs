@@ -0,0 +1,95 @@
+//! Tests for collapsing an edge harvest into a simple undirected graph.
+
+use super::*;
+
+fn endpoints(harvest: &EdgeHarvest) -> Vec<(usize, usize, f32)> {
+    let mut edges: Vec<_> = harvest
+        .iter()
+        .map(|edge| (edge.source(), edge.target(), edge.distance()))
+        .collect();
+    edges.sort_by_key(|&(source, target, _)| (source, target));
+    edges
+}
+
+#[rstest]
+fn canonicalise_keeps_minimum_distance_per_pair() {
+    let harvest = EdgeHarvest::new(vec![
+        CandidateEdge::new(2, 0, 0.9, 1),
+        CandidateEdge::new(0, 2, 0.4, 2),
+        CandidateEdge::new(1, 2, 0.3, 3),
+        CandidateEdge::new(2, 1, 0.7, 4),
+        CandidateEdge::new(0, 1, 0.5, 5),
+    ]);
+
+    let simple = harvest.canonicalise();
+
+    assert_eq!(
+        endpoints(&simple),
+        vec![(0, 1, 0.5), (0, 2, 0.4), (1, 2, 0.3)]
+    );
+}
+
+#[rstest]
+fn canonicalise_drops_self_loops() {
+    let harvest = EdgeHarvest::new(vec![
+        CandidateEdge::new(3, 3, 0.0, 1),
+        CandidateEdge::new(3, 4, 1.0, 2),
+    ]);
+
+    assert_eq!(endpoints(&harvest.canonicalise()), vec![(3, 4, 1.0)]);
+}
+
+#[rstest]
+fn canonicalise_prefers_earliest_sequence_on_ties() {
+    let harvest = EdgeHarvest::new(vec![
+        CandidateEdge::new(1, 0, 0.5, 7),
+        CandidateEdge::new(0, 1, 0.5, 3),
+    ]);
+
+    let simple = harvest.canonicalise();
+
+    let edge = simple.iter().next().expect("one edge survives");
+    assert_eq!((edge.sequence(), simple.len()), (3, 1));
+}
+
+#[rstest]
+fn canonicalise_preserves_harvest_ordering() {
+    let harvest = EdgeHarvest::new(vec![
+        CandidateEdge::new(4, 1, 0.2, 9),
+        CandidateEdge::new(0, 3, 0.8, 2),
+        CandidateEdge::new(2, 1, 0.1, 5),
+    ]);
+
+    let simple = harvest.canonicalise();
+
+    assert!(
+        simple
+            .windows(2)
+            .all(|pair| pair[0].sequence() <= pair[1].sequence())
+    );
+    assert!(simple.iter().all(|edge| edge.source() <= edge.target()));
+}
+
+#[rstest]
+fn canonicalised_build_harvest_is_a_simple_graph() {
+    let source = DummySource::new((0..32).map(|i| i as f32).collect());
+    let params = HnswParams::new(4, 16).expect("params").with_rng_seed(7);
+    let (_, edges) = CpuHnsw::build_with_edges(&source, params).expect("build");
+
+    let simple = edges.clone().canonicalise();
+
+    let pairs: HashSet<_> = simple
+        .iter()
+        .map(|edge| (edge.source(), edge.target()))
+        .collect();
+    assert_eq!(pairs.len(), simple.len());
+    let original: HashSet<_> = edges
+        .iter()
+        .filter(|edge| edge.source() != edge.target())
+        .map(|edge| {
+            let edge = edge.canonicalise();
+            (edge.source(), edge.target())
+        })
+        .collect();
+    assert_eq!(pairs, original);
+}
@@ -283,4 +283,6 @@ fn build_with_edges_has_consistent_count(
     );
 }
 
+mod canonicalise;
 mod coverage;
+mod mutualise;
@@ -0,0 +1,69 @@
+//! Tests for keeping only the harvested edges between mutual neighbours.
+
+use super::*;
+
+fn k(value: usize) -> NonZeroUsize {
+    NonZeroUsize::new(value).expect("test widths are non-zero")
+}
+
+fn pairs(harvest: &EdgeHarvest) -> Vec<(usize, usize)> {
+    harvest
+        .iter()
+        .map(|edge| (edge.source(), edge.target()))
+        .collect()
+}
+
+/// Points on a line at 0, 1, 3, and 10: point 3 is one-sided to everyone.
+fn line_harvest() -> EdgeHarvest {
+    EdgeHarvest::new(vec![
+        CandidateEdge::new(1, 0, 1.0, 1),
+        CandidateEdge::new(2, 1, 2.0, 2),
+        CandidateEdge::new(2, 0, 3.0, 3),
+        CandidateEdge::new(3, 2, 7.0, 4),
+    ])
+}
+
+#[rstest]
+#[case::nearest_only(1, vec![(1, 0)])]
+#[case::two_nearest(2, vec![(1, 0), (2, 1), (2, 0)])]
+#[case::every_candidate(4, vec![(1, 0), (2, 1), (2, 0), (3, 2)])]
+fn mutualise_keeps_reciprocal_edges(#[case] width: usize, #[case] expected: Vec<(usize, usize)>) {
+    assert_eq!(pairs(&line_harvest().mutualise(k(width))), expected);
+}
+
+#[rstest]
+fn mutualise_counts_edges_in_either_direction_once() {
+    let harvest = EdgeHarvest::new(vec![
+        CandidateEdge::new(1, 0, 1.0, 1),
+        CandidateEdge::new(0, 1, 1.0, 2),
+        CandidateEdge::new(2, 0, 1.5, 3),
+    ]);
+
+    // Node 0's nearest distinct neighbour is 1, despite the duplicate edge.
+    assert_eq!(
+        pairs(&harvest.mutualise(k(1))),
+        vec![(1, 0), (0, 1)],
+        "both copies of the mutual pair survive"
+    );
+}
+
+#[rstest]
+fn mutualise_breaks_distance_ties_by_node_id() {
+    let harvest = EdgeHarvest::new(vec![
+        CandidateEdge::new(1, 0, 1.0, 1),
+        CandidateEdge::new(2, 0, 1.0, 2),
+    ]);
+
+    assert_eq!(pairs(&harvest.mutualise(k(1))), vec![(1, 0)]);
+}
+
+#[rstest]
+fn mutualise_drops_self_loops() {
+    let harvest = EdgeHarvest::new(vec![
+        CandidateEdge::new(0, 0, 0.0, 1),
+        CandidateEdge::new(1, 0, 1.0, 2),
+    ]);
+
+    assert_eq!(pairs(&harvest.mutualise(k(2))), vec![(1, 0)]);
+    assert!(EdgeHarvest::default().mutualise(k(1)).is_empty());
+}
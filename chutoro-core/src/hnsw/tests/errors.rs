@@ -3,10 +3,10 @@
 use rstest::{fixture, rstest};
 
 use crate::{
-    DataSource, DataSourceError,
+    DataSource, DataSourceError, DistancePolicy,
     hnsw::{
-        CpuHnsw, HnswError, HnswParams,
-        distance_cache::{DistanceCache, DistanceCacheConfig},
+        CpuHnsw, DistanceCacheConfig, HnswError, HnswParams,
+        distance_cache::DistanceCache,
         graph::{Graph, NodeContext, SearchContext},
         validate::validate_batch_distances,
     },
@@ -46,6 +46,61 @@ fn non_finite_distance_is_reported() {
     }
 }
 
+#[rstest]
+fn skip_edge_drops_skipped_pairs_but_keeps_maximal_distances() {
+    /// Point 3 has no finite distance, and points 0 and 1 are exactly
+    /// `f32::MAX` apart.
+    struct Distant;
+
+    impl DataSource for Distant {
+        fn len(&self) -> usize {
+            4
+        }
+
+        fn name(&self) -> &str {
+            "distant"
+        }
+
+        fn distance(&self, left: usize, right: usize) -> Result<f32, DataSourceError> {
+            Ok(match (left.min(right), left.max(right)) {
+                (pair_left, pair_right) if pair_left == pair_right => 0.0,
+                (_, 3) => f32::NAN,
+                (0, 1) => f32::MAX,
+                _ => 1.0,
+            })
+        }
+    }
+
+    let params = HnswParams::new(4, 8)
+        .expect("params must be valid")
+        .with_distance_policy(DistancePolicy::SkipEdge);
+    let (index, harvest) =
+        CpuHnsw::build_with_edges(&Distant, params).expect("skipped distances must not fail");
+
+    let pairs: Vec<(usize, usize)> = harvest
+        .iter()
+        .map(|edge| {
+            (
+                edge.source().min(edge.target()),
+                edge.source().max(edge.target()),
+            )
+        })
+        .collect();
+    assert!(
+        pairs.contains(&(0, 1)),
+        "maximal distances are kept: {pairs:?}"
+    );
+    assert!(
+        pairs.iter().all(|&(_, right)| right != 3),
+        "skipped pairs are dropped: {pairs:?}"
+    );
+    let report = index
+        .distance_guard()
+        .report()
+        .expect("lenient policies report");
+    assert!(report.skipped() > 0);
+}
+
 #[rstest]
 fn reports_invariant_violation_when_search_node_missing() {
     let source = DummySource::new(vec![0.0, 1.0, 2.0]);
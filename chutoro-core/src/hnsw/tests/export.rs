@@ -0,0 +1,157 @@
+//! Tests for the graph exports written by `CpuHnsw::export_graph`.
+
+use std::io::{self, Write};
+
+use rstest::rstest;
+
+use crate::hnsw::{CpuHnsw, GraphExportError, GraphFormat, HnswParams};
+
+use super::fixtures::DummySource;
+
+fn build(points: usize) -> CpuHnsw {
+    let params = HnswParams::new(4, 16)
+        .expect("params must be valid")
+        .with_rng_seed(11);
+    let data = (0..points).map(|i| i as f32 * 0.5).collect();
+    CpuHnsw::build(&DummySource::new(data), params).expect("build must succeed")
+}
+
+fn export(index: &CpuHnsw, format: GraphFormat) -> String {
+    let mut buffer = Vec::new();
+    index
+        .export_graph(&mut buffer, format)
+        .expect("export must succeed");
+    String::from_utf8(buffer).expect("exports are UTF-8")
+}
+
+/// Returns the numbers in `line`, in order.
+fn numbers(line: &str) -> Vec<usize> {
+    line.split(|c: char| !c.is_ascii_digit())
+        .filter(|run| !run.is_empty())
+        .map(|run| run.parse().expect("runs of digits parse"))
+        .collect()
+}
+
+/// Parses `(source, target, layer)` from the edge lines of an export.
+fn edges(text: &str, format: GraphFormat) -> Vec<(usize, usize, usize)> {
+    text.lines()
+        .filter(|line| match format {
+            GraphFormat::GraphMl => line.starts_with("    <edge "),
+            GraphFormat::Dot => line.contains(" -> "),
+            GraphFormat::EdgeListCsv => !line.starts_with("source"),
+        })
+        .map(|line| match numbers(line)[..] {
+            [source, target, layer] => (source, target, layer),
+            ref other => panic!("expected three numbers in {line:?}, got {other:?}"),
+        })
+        .collect()
+}
+
+#[rstest]
+#[case::graphml(GraphFormat::GraphMl)]
+#[case::dot(GraphFormat::Dot)]
+#[case::csv(GraphFormat::EdgeListCsv)]
+fn every_format_lists_each_adjacency_entry(#[case] format: GraphFormat) {
+    let index = build(60);
+    let stats = index.statistics().expect("statistics must be available");
+
+    let exported = edges(&export(&index, format), format);
+
+    let mut per_layer = vec![0; stats.edges_per_level().len()];
+    for &(_, _, layer) in &exported {
+        per_layer[layer] += 1;
+    }
+    assert_eq!(per_layer, stats.edges_per_level());
+    assert_eq!(
+        exported,
+        edges(
+            &export(&index, GraphFormat::EdgeListCsv),
+            GraphFormat::EdgeListCsv
+        ),
+        "formats list the same edges in the same order",
+    );
+}
+
+#[rstest]
+#[case::graphml(GraphFormat::GraphMl, "    <node ", r#"<data key="entry">true"#)]
+#[case::dot(GraphFormat::Dot, "  ", "entry=true")]
+fn nodes_carry_levels_and_the_entry_point(
+    #[case] format: GraphFormat,
+    #[case] node_prefix: &str,
+    #[case] entry_marker: &str,
+) {
+    let index = build(60);
+    let stats = index.statistics().expect("statistics must be available");
+    let text = export(&index, format);
+    let nodes: Vec<_> = text
+        .lines()
+        .filter(|line| line.starts_with(node_prefix) && line.contains("level"))
+        .filter(|line| !line.contains("->"))
+        .collect();
+
+    assert_eq!(nodes.len(), stats.node_count());
+    let entries: Vec<_> = nodes
+        .iter()
+        .filter(|line| line.contains(entry_marker))
+        .collect();
+    let [entry] = entries[..] else {
+        panic!("expected one entry point, got {entries:?}");
+    };
+    let top = stats.entry_level().expect("the index is not empty");
+    assert!(
+        entry.contains(&format!(r#"<data key="level">{top}<"#))
+            || entry.contains(&format!("level={top},"))
+    );
+}
+
+#[rstest]
+fn documents_are_well_formed() {
+    let index = build(3);
+
+    let graphml = export(&index, GraphFormat::GraphMl);
+    let dot = export(&index, GraphFormat::Dot);
+    let csv = export(&index, GraphFormat::EdgeListCsv);
+
+    assert!(graphml.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+    assert!(graphml.contains(r#"<graph id="hnsw" edgedefault="directed">"#));
+    assert!(graphml.ends_with("  </graph>\n</graphml>\n"));
+    assert!(dot.starts_with("digraph hnsw {\n"));
+    assert!(dot.ends_with("}\n"));
+    assert!(dot.contains("shape=doublecircle"));
+    assert!(csv.starts_with("source,target,layer\n"));
+}
+
+#[rstest]
+fn empty_indexes_export_no_nodes() {
+    let params = HnswParams::new(2, 4).expect("params must be valid");
+    let index = CpuHnsw::with_capacity(params, 4).expect("index must allocate");
+
+    assert_eq!(
+        export(&index, GraphFormat::EdgeListCsv),
+        "source,target,layer\n"
+    );
+    assert_eq!(export(&index, GraphFormat::Dot), "digraph hnsw {\n}\n");
+    assert!(!export(&index, GraphFormat::GraphMl).contains("<node "));
+}
+
+/// A writer whose every write fails.
+struct Broken;
+
+impl Write for Broken {
+    fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+        Err(io::Error::other("disk full"))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[rstest]
+fn write_failures_are_reported() {
+    let err = build(10)
+        .export_graph(Broken, GraphFormat::Dot)
+        .expect_err("writes must fail");
+
+    assert!(matches!(err, GraphExportError::Io(_)));
+}
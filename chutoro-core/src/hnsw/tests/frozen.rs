@@ -0,0 +1,88 @@
+//! Tests for searching frozen snapshots of the graph.
+
+use std::num::NonZeroUsize;
+
+use rstest::rstest;
+
+use super::fixtures::DummySource;
+use crate::hnsw::{AdjacencyStorage, CpuHnsw, HnswError, HnswParams};
+
+fn source() -> DummySource {
+    DummySource::new(
+        (0..200)
+            .map(|i| (i * 53 % 97) as f32 + i as f32 * 1.0e-3)
+            .collect(),
+    )
+}
+
+fn params(storage: AdjacencyStorage) -> HnswParams {
+    HnswParams::new(4, 16)
+        .expect("params must be valid")
+        .with_rng_seed(11)
+        .with_adjacency_storage(storage)
+}
+
+#[rstest]
+#[case::per_node(AdjacencyStorage::PerNode)]
+#[case::arena(AdjacencyStorage::Arena)]
+fn frozen_searches_match_the_live_graph(#[case] storage: AdjacencyStorage) {
+    let source = source();
+    let index = CpuHnsw::build(&source, params(storage)).expect("build must succeed");
+    let frozen = index.freeze().expect("freeze must succeed");
+
+    assert_eq!(frozen.len(), index.len());
+    assert_eq!(frozen.params(), index.params());
+    for ef in [1, 8, 32] {
+        let ef = NonZeroUsize::new(ef).expect("ef must be non-zero");
+        for query in 0..200 {
+            assert_eq!(
+                frozen
+                    .search(&source, query, ef)
+                    .expect("search must succeed"),
+                index
+                    .search(&source, query, ef)
+                    .expect("search must succeed"),
+                "query {query} with ef {ef}",
+            );
+        }
+    }
+}
+
+#[rstest]
+fn snapshots_ignore_later_insertions() {
+    let source = source();
+    let index = CpuHnsw::with_capacity(params(AdjacencyStorage::PerNode), 200)
+        .expect("capacity must be valid");
+    for node in 0..100 {
+        index.insert(node, &source).expect("insert must succeed");
+    }
+    let frozen = index.freeze().expect("freeze must succeed");
+    for node in 100..200 {
+        index.insert(node, &source).expect("insert must succeed");
+    }
+
+    let ef = NonZeroUsize::new(200).expect("ef must be non-zero");
+    let found = frozen
+        .search(&source, 150, ef)
+        .expect("search must succeed");
+    assert_eq!(frozen.len(), 100);
+    assert!(
+        found
+            .iter()
+            .all(|neighbour| neighbour.id < 100 || neighbour.id == 150)
+    );
+}
+
+#[rstest]
+fn empty_snapshots_reject_searches() {
+    let index = CpuHnsw::with_capacity(params(AdjacencyStorage::PerNode), 4)
+        .expect("capacity must be valid");
+    let frozen = index.freeze().expect("freeze must succeed");
+
+    assert!(frozen.is_empty());
+    let ef = NonZeroUsize::new(1).expect("ef must be non-zero");
+    assert!(matches!(
+        frozen.search(&source(), 0, ef),
+        Err(HnswError::GraphEmpty)
+    ));
+}
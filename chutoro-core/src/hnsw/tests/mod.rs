@@ -1,14 +1,22 @@
 //! Integration tests for the CPU HNSW graph.
 
+mod batch_scoring;
 mod build;
 mod cache;
 mod edge_harvest;
 mod errors;
+mod export;
 mod fixtures;
+mod frozen;
 mod metadata;
 mod params;
 mod property;
+mod rebuild;
 mod sampling;
 mod search;
+mod statistics;
+mod storage;
 pub(super) mod support;
+mod trim;
+mod view;
 mod write_lock;
@@ -2,7 +2,9 @@
 
 use std::{num::NonZeroUsize, time::Duration};
 
-use crate::hnsw::HnswParams;
+use rstest::rstest;
+
+use crate::hnsw::{HnswError, HnswParams, MAX_LAYER_OVERRIDE};
 
 #[test]
 fn accepts_equal_search_and_connection_width() {
@@ -25,3 +27,109 @@ fn preserves_distance_cache_ttl_when_overriding_capacity() {
     assert_eq!(config.ttl(), ttl, "TTL must survive capacity overrides");
     assert_eq!(config.max_entries().get(), 32);
 }
+
+#[test]
+fn base_layer_connections_replace_the_doubled_default() {
+    let params = HnswParams::new(8, 16).expect("parameters must be valid");
+    assert_eq!(params.connection_limit(0), 16);
+
+    let params = params
+        .with_base_layer_connections(12)
+        .expect("non-zero M0 must be valid");
+    assert_eq!(params.connection_limit(0), 12);
+    assert_eq!(params.connection_limit(1), 8);
+    assert_eq!(params.connection_limit(MAX_LAYER_OVERRIDE + 4), 8);
+}
+
+#[test]
+fn layer_overrides_apply_per_layer_and_last_entry_wins() {
+    let params = HnswParams::new(8, 16)
+        .expect("parameters must be valid")
+        .with_base_layer_connections(24)
+        .expect("non-zero M0 must be valid")
+        .with_layer_overrides(&[(3, 2, 4), (1, 4, 32), (3, 6, 6)])
+        .expect("overrides must be valid");
+
+    assert_eq!(params.layer_overrides(), &[(1, 4, 32), (3, 6, 6)]);
+    assert_eq!(params.connection_limit(0), 24);
+    assert_eq!(params.ef_construction_for_layer(0), 16);
+    assert_eq!(params.connection_limit(1), 4);
+    assert_eq!(params.ef_construction_for_layer(1), 32);
+    assert_eq!(params.connection_limit(2), 8);
+    assert_eq!(params.connection_limit(3), 6);
+    assert_eq!(params.ef_construction_for_layer(3), 6);
+}
+
+#[rstest]
+#[case::layer_too_high((MAX_LAYER_OVERRIDE + 1, 4, 8), "highest overridable layer")]
+#[case::zero_connections((1, 0, 8), "greater than zero")]
+#[case::ef_below_m((1, 8, 4), "ef_construction (4)")]
+fn rejects_invalid_layer_overrides(
+    #[case] layer_override: (usize, usize, usize),
+    #[case] expected: &str,
+) {
+    let err = HnswParams::new(8, 16)
+        .expect("parameters must be valid")
+        .with_layer_overrides(&[layer_override])
+        .expect_err("override must be rejected");
+
+    assert!(
+        matches!(&err, HnswError::InvalidParameters { reason } if reason.contains(expected)),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn rejects_zero_base_layer_connections() {
+    let err = HnswParams::new(8, 16)
+        .expect("parameters must be valid")
+        .with_base_layer_connections(0)
+        .expect_err("zero M0 must be rejected");
+
+    assert!(matches!(err, HnswError::InvalidParameters { .. }));
+}
+
+#[rstest]
+#[case::empty(&[], "at least one weight")]
+#[case::above_max_level(&[1.0, 1.0, 1.0, 1.0], "max_level is 2")]
+#[case::negative(&[1.0, -0.5], "level 1 weight")]
+#[case::not_finite(&[f64::NAN], "level 0 weight")]
+#[case::zero_total(&[0.0, 0.0], "positive total weight")]
+fn rejects_invalid_level_distributions(#[case] weights: &[f64], #[case] expected: &str) {
+    let err = HnswParams::new(8, 16)
+        .expect("parameters must be valid")
+        .with_max_level(2)
+        .with_level_distribution(weights)
+        .expect_err("distribution must be rejected");
+
+    assert!(
+        matches!(&err, HnswError::InvalidParameters { reason } if reason.contains(expected)),
+        "{err:?}"
+    );
+}
+
+#[rstest]
+#[case::first(0.0, 0)]
+#[case::skips_empty_levels(0.3, 2)]
+#[case::last(0.9, 3)]
+#[case::rounding_overflow(1.0, 3)]
+fn level_distribution_maps_draws_to_weighted_levels(#[case] draw: f64, #[case] level: usize) {
+    let params = HnswParams::new(8, 16)
+        .expect("parameters must be valid")
+        .with_level_distribution(&[0.25, 0.0, 0.5, 0.25, 0.0])
+        .expect("distribution must be valid");
+
+    assert_eq!(params.distributed_level(draw), Some(level));
+}
+
+#[test]
+fn lowering_max_level_clamps_distributed_levels() {
+    let params = HnswParams::new(8, 16)
+        .expect("parameters must be valid")
+        .with_level_distribution(&[0.0, 0.0, 1.0])
+        .expect("distribution must be valid")
+        .with_max_level(1);
+
+    assert_eq!(params.distributed_level(0.5), Some(1));
+    assert_eq!(HnswParams::default().distributed_level(0.5), None);
+}
@@ -161,7 +161,7 @@ fn snapshot_graph(index: &CpuHnsw) -> GraphSnapshot {
 }
 
 /// Captures a snapshot of a single node's structural state.
-fn snapshot_node(node: &crate::hnsw::node::Node) -> NodeSnapshot {
+fn snapshot_node(node: crate::hnsw::node::NodeRef<'_>) -> NodeSnapshot {
     let neighbours = (0..node.level_count())
         .map(|level| {
             let mut nbrs: Vec<_> = node.neighbours(level).to_vec();
@@ -1,11 +1,10 @@
 //! Search correctness helpers for the CPU HNSW property suite.
 //!
-//! Hosts the brute-force oracle, recall measurement utilities, configurable
-//! recall thresholds, and targeted unit tests that exercise the helper logic
-//! directly via `rstest`.
+//! Compares searches with the exact-neighbour oracle in [`crate::oracles`] and
+//! hosts configurable recall thresholds and targeted unit tests that exercise
+//! the helper logic directly via `rstest`.
 
 use std::{
-    collections::{BinaryHeap, HashSet},
     num::NonZeroUsize,
     time::{Duration, Instant},
 };
@@ -15,6 +14,7 @@ use super::types::HnswFixture;
 #[cfg(test)]
 use super::types::{DistributionMetadata, HnswParamsSeed, VectorDistribution};
 use crate::error::DataSourceError;
+use crate::oracles::{exact_neighbours, recall_at_k};
 use crate::{CpuHnsw, DataSource, Neighbour};
 use proptest::{
     prop_assume,
@@ -62,7 +62,7 @@ pub(super) fn run_search_correctness_property(
     hnsw_neighbours.truncate(k);
 
     let oracle_started = Instant::now();
-    let oracle = brute_force_top_k(&source, query, k)
+    let oracle = exact_neighbours(&source, query, k)
         .map_err(|err| TestCaseError::fail(format!("oracle failed: {err}")))?;
     let oracle_elapsed = oracle_started.elapsed();
 
@@ -88,47 +88,6 @@ pub(super) fn run_search_correctness_property(
     ensure_recall_meets_threshold(recall, &recall_ctx)
 }
 
-fn brute_force_top_k<D: DataSource + Sync>(
-    source: &D,
-    query: usize,
-    k: usize,
-) -> Result<Vec<Neighbour>, DataSourceError> {
-    if k == 0 {
-        return Ok(Vec::new());
-    }
-
-    let mut heap: BinaryHeap<Neighbour> = BinaryHeap::with_capacity(k);
-    for candidate in 0..source.len() {
-        let distance = source.distance(query, candidate)?;
-        heap.push(Neighbour {
-            id: candidate,
-            distance,
-        });
-        if heap.len() > k {
-            heap.pop();
-        }
-    }
-
-    let mut neighbours = heap.into_vec();
-    neighbours.sort_unstable();
-    Ok(neighbours)
-}
-
-fn recall_at_k(oracle: &[Neighbour], observed: &[Neighbour], k: usize) -> f32 {
-    assert!(k > 0, "k must be positive");
-    if oracle.is_empty() {
-        return 0.0;
-    }
-    let target = k.min(oracle.len());
-    let oracle_ids: HashSet<usize> = oracle.iter().take(target).map(|n| n.id).collect();
-    let hits = observed
-        .iter()
-        .take(target)
-        .filter(|neighbour| oracle_ids.contains(&neighbour.id))
-        .count();
-    hits as f32 / target as f32
-}
-
 #[derive(Clone, Copy, Debug)]
 struct SearchTimings {
     hnsw: Duration,
@@ -302,17 +261,17 @@ impl DataSource for MatrixSource {
 
 #[cfg(test)]
 #[test]
-fn brute_force_top_k_returns_empty_when_k_is_zero() {
+fn exact_neighbours_returns_empty_when_k_is_zero() {
     let source = MatrixSource::new(vec![vec![0.0, 0.5], vec![0.5, 0.0]]);
-    let neighbours = brute_force_top_k(&source, 0, 0).expect("k=0 should succeed");
+    let neighbours = exact_neighbours(&source, 0, 0).expect("k=0 should succeed");
     assert!(neighbours.is_empty());
 }
 
 #[cfg(test)]
 #[test]
-fn brute_force_top_k_returns_all_when_k_exceeds_len() {
+fn exact_neighbours_returns_all_when_k_exceeds_len() {
     let source = MatrixSource::new(vec![vec![0.0, 0.4], vec![0.4, 0.0]]);
-    let neighbours = brute_force_top_k(&source, 0, 10).expect("k>len should return all nodes");
+    let neighbours = exact_neighbours(&source, 0, 10).expect("k>len should return all nodes");
     assert_eq!(neighbours.len(), 2);
     assert_eq!(
         neighbours.iter().map(|n| n.id).collect::<Vec<_>>(),
@@ -322,9 +281,9 @@ fn brute_force_top_k_returns_all_when_k_exceeds_len() {
 
 #[cfg(test)]
 #[test]
-fn brute_force_top_k_handles_empty_source() {
+fn exact_neighbours_handles_empty_source() {
     let source = MatrixSource::new(Vec::new());
-    let neighbours = brute_force_top_k(&source, 0, 1).expect("empty source should be ok");
+    let neighbours = exact_neighbours(&source, 0, 1).expect("empty source should be ok");
     assert!(neighbours.is_empty());
 }
 
@@ -0,0 +1,127 @@
+//! Tests for rebuilding an index with new parameters via `CpuHnsw::rebuild`.
+
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use rstest::rstest;
+
+use crate::{
+    hnsw::{CpuHnsw, HnswParams},
+    test_utils::CountingSource,
+};
+
+use super::fixtures::DummySource;
+
+fn params(max_connections: usize, ef_construction: usize) -> HnswParams {
+    HnswParams::new(max_connections, ef_construction)
+        .expect("params must be valid")
+        .with_rng_seed(11)
+}
+
+fn line(points: usize) -> DummySource {
+    DummySource::new((0..points).map(|i| i as f32).collect())
+}
+
+#[rstest]
+fn rebuild_adopts_new_parameters_and_keeps_every_node() {
+    let source = line(64);
+    let index = CpuHnsw::build(&source, params(4, 8)).expect("build must succeed");
+
+    let rebuilt = index
+        .rebuild(&source, params(8, 32))
+        .expect("rebuild must succeed");
+
+    assert_eq!(rebuilt.len(), 64);
+    assert_eq!(rebuilt.params().max_connections(), 8);
+    assert_eq!(rebuilt.params().ef_construction(), 32);
+    rebuilt
+        .invariants()
+        .check_all()
+        .expect("graph must be valid");
+    let ef = NonZeroUsize::new(3).expect("non-zero");
+    let neighbours = rebuilt.search(&source, 40, ef).expect("search");
+    let mut ids: Vec<_> = neighbours.iter().map(|n| n.id).collect();
+    ids.sort_unstable();
+    assert_eq!(ids, vec![39, 40, 41]);
+}
+
+#[rstest]
+fn rebuild_preserves_sparse_identifiers() {
+    let source = line(10);
+    let index = CpuHnsw::with_capacity(params(2, 4), 10).expect("index");
+    for node in [7, 2, 5] {
+        index.insert(node, &source).expect("insert must succeed");
+    }
+
+    let rebuilt = index
+        .rebuild(&source, params(3, 6))
+        .expect("rebuild must succeed");
+
+    assert_eq!(rebuilt.len(), 3);
+    let ef = NonZeroUsize::new(10).expect("non-zero");
+    let mut ids: Vec<_> = rebuilt
+        .search(&source, 5, ef)
+        .expect("search")
+        .into_iter()
+        .map(|n| n.id)
+        .collect();
+    ids.sort_unstable();
+    assert_eq!(ids, vec![2, 5, 7]);
+    rebuilt
+        .insert(0, &source)
+        .expect("unused slots stay insertable");
+}
+
+#[rstest]
+fn rebuild_reuses_cached_distances() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let source = CountingSource::new((0..48).map(|i| i as f32).collect(), Arc::clone(&calls));
+    let index = CpuHnsw::build(&source, params(4, 16)).expect("build must succeed");
+    let build_calls = calls.swap(0, Ordering::Relaxed);
+
+    let rebuilt = index
+        .rebuild(&source, params(4, 16))
+        .expect("rebuild must succeed");
+
+    let rebuild_calls = calls.load(Ordering::Relaxed);
+    assert!(
+        rebuild_calls < build_calls,
+        "seeded cache should avoid recomputation ({rebuild_calls} >= {build_calls})",
+    );
+    let stats = rebuilt.statistics().expect("statistics");
+    assert!(stats.cache_entries() > 0);
+}
+
+#[rstest]
+fn rebuilding_an_empty_index_yields_an_empty_index() {
+    let source = line(4);
+    let index = CpuHnsw::with_capacity(params(2, 4), 4).expect("index");
+
+    let rebuilt = index
+        .rebuild(&source, params(3, 6))
+        .expect("rebuild must succeed");
+
+    assert!(rebuilt.is_empty());
+    assert_eq!(rebuilt.params().max_connections(), 3);
+}
+
+#[rstest]
+fn original_index_remains_usable_after_rebuild() {
+    let source = line(16);
+    let index = CpuHnsw::build(&source, params(2, 4)).expect("build must succeed");
+
+    let _rebuilt = index
+        .rebuild(&source, params(6, 12))
+        .expect("rebuild must succeed");
+
+    assert_eq!(index.params().max_connections(), 2);
+    index
+        .invariants()
+        .check_all()
+        .expect("original graph untouched");
+}
@@ -1,21 +1,24 @@
 //! Sampling tests ensuring level distribution matches the geometric tail.
 
-use rand::{Rng, SeedableRng, distributions::Standard, rngs::SmallRng};
+use rstest::rstest;
 
-use crate::hnsw::HnswParams;
+use crate::hnsw::{HnswParams, RngKind, cpu::rng::LevelRng};
 
-#[test]
-fn level_sampling_matches_geometric_tail() {
+#[rstest]
+#[case::small_rng_v1(RngKind::SmallRngV1)]
+#[case::chacha8(RngKind::ChaCha8)]
+fn level_sampling_matches_geometric_tail(#[case] kind: RngKind) {
     let params = HnswParams::new(16, 64)
         .expect("params must be valid")
-        .with_rng_seed(1337);
-    let mut rng = SmallRng::seed_from_u64(params.rng_seed());
+        .with_rng_seed(1337)
+        .with_rng_kind(kind);
+    let mut rng = LevelRng::new(params.rng_kind(), params.rng_seed());
     let mut counts = vec![0_usize; params.max_level() + 1];
     let samples = 10_000;
     for _ in 0..samples {
         let mut level = 0_usize;
         while level < params.max_level() {
-            let draw: f64 = rng.sample(Standard);
+            let draw = rng.next_unit();
             if params.should_stop(draw) {
                 break;
             }
@@ -12,7 +12,7 @@ use crate::{
     },
 };
 
-use super::fixtures::DummySource;
+use super::fixtures::{DummySource, assert_sorted_by_distance};
 
 #[rstest]
 fn greedy_descent_selects_closest_neighbour() {
@@ -217,3 +217,182 @@ fn search_respects_minimum_ef() {
         "with ef=1 the search should keep a candidate no worse than the entry point",
     );
 }
+
+fn range_ids(index: &CpuHnsw, source: &DummySource, query: usize, radius: f32) -> Vec<usize> {
+    let limit = NonZeroUsize::new(source.len()).expect("source must not be empty");
+    let neighbours = index
+        .range_search(source, query, radius, limit)
+        .expect("range search must succeed");
+    assert_sorted_by_distance(&neighbours);
+    let mut ids: Vec<_> = neighbours
+        .into_iter()
+        .map(|neighbour| neighbour.id)
+        .collect();
+    ids.sort_unstable();
+    ids
+}
+
+#[rstest]
+#[case::duplicates_only(0.0)]
+#[case::narrow(0.75)]
+#[case::wide(4.5)]
+fn range_search_matches_brute_force(#[case] radius: f32) {
+    let points: Vec<f32> = (0..120).map(|i| (i / 2) as f32 * 0.5).collect();
+    let source = DummySource::new(points);
+    let params = HnswParams::new(4, 16)
+        .expect("params must be valid")
+        .with_rng_seed(41);
+    let index = CpuHnsw::build(&source, params).expect("build must succeed");
+
+    for query in [0, 37, 119] {
+        let expected: Vec<_> = (0..source.len())
+            .filter(|&other| source.distance(query, other).expect("distance") <= radius)
+            .collect();
+        assert_eq!(range_ids(&index, &source, query, radius), expected);
+    }
+}
+
+#[rstest]
+fn range_search_keeps_the_closest_matches_up_to_the_limit() {
+    let source = DummySource::new(vec![0.0, 0.1, 0.2, 0.3, 0.4, 5.0]);
+    let params = HnswParams::new(2, 8)
+        .expect("params must be valid")
+        .with_rng_seed(7);
+    let index = CpuHnsw::build(&source, params).expect("build must succeed");
+
+    let neighbours = index
+        .range_search(&source, 0, 1.0, NonZeroUsize::new(3).expect("non-zero"))
+        .expect("range search must succeed");
+    let ids: Vec<_> = neighbours.iter().map(|neighbour| neighbour.id).collect();
+    assert_eq!(ids, [0, 1, 2]);
+}
+
+#[rstest]
+#[case::negative(-1.0)]
+#[case::nan(f32::NAN)]
+fn range_search_rejects_invalid_radii(#[case] radius: f32) {
+    let source = DummySource::new(vec![0.0, 1.0]);
+    let params = HnswParams::new(2, 4).expect("params must be valid");
+    let index = CpuHnsw::build(&source, params).expect("build must succeed");
+
+    let err = index
+        .range_search(&source, 0, radius, NonZeroUsize::MIN)
+        .expect_err("invalid radius must be rejected");
+    assert!(matches!(err, HnswError::InvalidParameters { .. }));
+}
+
+#[rstest]
+fn range_search_on_an_empty_index_fails() {
+    let source = DummySource::new(vec![0.0]);
+    let params = HnswParams::new(2, 4).expect("params must be valid");
+    let index = CpuHnsw::with_capacity(params, 1).expect("index");
+
+    let err = index
+        .range_search(&source, 0, 1.0, NonZeroUsize::MIN)
+        .expect_err("an empty index has no entry point");
+    assert_eq!(err, HnswError::GraphEmpty);
+}
+
+#[rstest]
+fn detailed_search_matches_search_and_attributes_levels() {
+    let source = DummySource::new((0..64).map(|i| i as f32 * 0.25).collect());
+    let params = HnswParams::new(4, 16)
+        .expect("params must be valid")
+        .with_rng_seed(17);
+    let index = CpuHnsw::build(&source, params).expect("build must succeed");
+    let (entry, sequences) = index.inspect_graph(|graph| {
+        let entry = graph.entry().expect("entry exists");
+        let sequences: Vec<_> = (0..source.len())
+            .map(|node| graph.node_sequence(node).expect("node is indexed"))
+            .collect();
+        (entry, sequences)
+    });
+    assert!(entry.level > 0, "the seed must give the graph upper layers");
+    let ef = NonZeroUsize::new(source.len()).expect("non-zero");
+
+    let details = index
+        .search_detailed(&source, 5, ef)
+        .expect("detailed search must succeed");
+    let plain = index.search(&source, 5, ef).expect("search must succeed");
+
+    let neighbours: Vec<_> = details.iter().map(|detail| detail.neighbour()).collect();
+    assert_eq!(neighbours, plain);
+    for detail in &details {
+        assert_eq!(detail.sequence, sequences[detail.id]);
+        assert!(detail.level <= entry.level);
+    }
+    let entry_detail = details
+        .iter()
+        .find(|detail| detail.id == entry.node)
+        .expect("a full-width search reaches the entry point");
+    assert_eq!(entry_detail.level, entry.level);
+    assert!(details.iter().any(|detail| detail.level == 0));
+}
+
+#[rstest]
+#[case::one_in_two(2)]
+#[case::one_in_ten(10)]
+#[case::one_in_thirty(30)]
+fn filtered_search_keeps_ef_accepted_neighbours(#[case] stride: usize) {
+    let source = DummySource::new((0..120).map(|i| i as f32 * 0.5).collect());
+    let params = HnswParams::new(4, 16)
+        .expect("params must be valid")
+        .with_rng_seed(23);
+    let index = CpuHnsw::build(&source, params).expect("build must succeed");
+    let accepted = |row: usize| row.is_multiple_of(stride);
+    let ef = NonZeroUsize::new(4).expect("non-zero");
+
+    for query in [1, 58, 119] {
+        let neighbours = index
+            .search_filtered(&source, query, ef, accepted)
+            .expect("filtered search must succeed");
+        assert_sorted_by_distance(&neighbours);
+
+        let mut expected: Vec<_> = (0..source.len()).filter(|&row| accepted(row)).collect();
+        expected.sort_by(|&a, &b| {
+            let (da, db) = (
+                source.distance(query, a).expect("distance"),
+                source.distance(query, b).expect("distance"),
+            );
+            da.total_cmp(&db).then(a.cmp(&b))
+        });
+        expected.truncate(ef.get());
+        let mut ids: Vec<_> = neighbours.iter().map(|neighbour| neighbour.id).collect();
+        ids.sort_unstable();
+        expected.sort_unstable();
+        assert_eq!(ids, expected, "query {query} with stride {stride}");
+    }
+}
+
+#[rstest]
+fn filtered_search_omits_a_rejected_query() {
+    let source = DummySource::new((0..32).map(|i| i as f32).collect());
+    let params = HnswParams::new(4, 8)
+        .expect("params must be valid")
+        .with_rng_seed(3);
+    let index = CpuHnsw::build(&source, params).expect("build must succeed");
+    let ef = NonZeroUsize::new(3).expect("non-zero");
+
+    let others = index
+        .search_filtered(&source, 10, ef, |row| row != 10)
+        .expect("filtered search must succeed");
+    let everyone = index
+        .search_filtered(&source, 10, ef, |_| true)
+        .expect("filtered search must succeed");
+
+    let ids: Vec<_> = others.iter().map(|neighbour| neighbour.id).collect();
+    assert_eq!(ids, [9, 11, 8]);
+    assert_eq!(everyone, index.search(&source, 10, ef).expect("search"));
+}
+
+#[rstest]
+fn filtered_search_accepting_nothing_returns_nothing() {
+    let source = DummySource::new((0..40).map(|i| i as f32).collect());
+    let params = HnswParams::new(4, 8).expect("params must be valid");
+    let index = CpuHnsw::build(&source, params).expect("build must succeed");
+
+    let neighbours = index
+        .search_filtered(&source, 5, NonZeroUsize::MIN, |_| false)
+        .expect("filtered search must succeed");
+    assert!(neighbours.is_empty());
+}
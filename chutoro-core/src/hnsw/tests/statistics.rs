@@ -0,0 +1,170 @@
+//! Tests for the structural statistics reported by `CpuHnsw::statistics`.
+
+use std::num::NonZeroUsize;
+
+use rstest::rstest;
+
+use crate::hnsw::{CpuHnsw, DistanceCacheConfig, HnswParams};
+
+use super::fixtures::DummySource;
+
+fn build(points: usize, max_connections: usize) -> CpuHnsw {
+    let params = HnswParams::new(max_connections, 32)
+        .expect("params must be valid")
+        .with_rng_seed(7);
+    let data = (0..points).map(|i| i as f32 * 0.5).collect();
+    CpuHnsw::build(&DummySource::new(data), params).expect("build must succeed")
+}
+
+#[rstest]
+fn statistics_count_every_node_on_the_base_level() {
+    let index = build(200, 8);
+    let stats = index.statistics().expect("statistics must be available");
+
+    assert_eq!(stats.node_count(), 200);
+    assert_eq!(stats.nodes_per_level().len(), stats.edges_per_level().len());
+    assert_eq!(
+        stats.entry_level(),
+        Some(stats.nodes_per_level().len() - 1),
+        "the entry point sits on the highest populated level",
+    );
+    assert!(
+        stats
+            .nodes_per_level()
+            .windows(2)
+            .all(|pair| pair[0] >= pair[1]),
+        "upper levels never hold more nodes than lower ones",
+    );
+    assert_eq!(
+        stats.total_edges(),
+        stats.edges_per_level().iter().sum::<usize>()
+    );
+}
+
+#[rstest]
+fn average_degree_respects_per_level_connection_limits() {
+    let max_connections = 6;
+    let index = build(300, max_connections);
+    let stats = index.statistics().expect("statistics must be available");
+    let degrees = stats.average_degree_per_level();
+
+    assert_eq!(degrees.len(), stats.nodes_per_level().len());
+    assert!(degrees[0] > 1.0, "base level must be connected");
+    assert!(degrees[0] <= (max_connections * 2) as f64);
+    for &degree in &degrees[1..] {
+        assert!(degree <= max_connections as f64);
+    }
+}
+
+#[rstest]
+fn construction_respects_layer_overrides() {
+    let params = HnswParams::new(6, 32)
+        .expect("params must be valid")
+        .with_rng_seed(7)
+        .with_base_layer_connections(4)
+        .expect("M0 must be valid")
+        .with_layer_overrides(&[(1, 2, 16)])
+        .expect("overrides must be valid");
+    let data = (0..300).map(|i| i as f32 * 0.5).collect();
+    let index = CpuHnsw::build(&DummySource::new(data), params).expect("build must succeed");
+
+    index
+        .invariants()
+        .check_all()
+        .expect("degree bounds must follow the overrides");
+    let max_degrees = index.inspect_graph(|graph| {
+        let mut max_degrees = Vec::new();
+        for (_, node) in graph.nodes_iter() {
+            max_degrees.resize(max_degrees.len().max(node.level_count()), 0);
+            for (level, max_degree) in max_degrees.iter_mut().enumerate().take(node.level_count()) {
+                *max_degree = (*max_degree).max(node.neighbours(level).len());
+            }
+        }
+        max_degrees
+    });
+    assert!(max_degrees[0] <= 4, "base layer degrees {max_degrees:?}");
+    assert!(max_degrees[1] <= 2, "layer 1 degrees {max_degrees:?}");
+    assert!(max_degrees[2..].iter().all(|&degree| degree <= 6));
+}
+
+#[rstest]
+fn level_distribution_follows_the_geometric_tail() {
+    let max_connections = 4;
+    let index = build(4_000, max_connections);
+    let stats = index.statistics().expect("statistics must be available");
+    let levels = stats.nodes_per_level();
+
+    let ratio = levels[1] as f64 / levels[0] as f64;
+    let expected = 1.0 / max_connections as f64;
+    assert!(
+        (ratio - expected).abs() < 0.05,
+        "level 1 should hold about 1/M of the nodes (observed {ratio}, expected {expected})",
+    );
+}
+
+#[rstest]
+fn custom_level_distribution_shapes_the_hierarchy() {
+    let params = HnswParams::new(4, 32)
+        .expect("params must be valid")
+        .with_rng_seed(7)
+        .with_level_distribution(&[0.5, 0.5])
+        .expect("distribution must be valid");
+    let data = (0..2_000).map(|i| i as f32 * 0.5).collect();
+    let index = CpuHnsw::build(&DummySource::new(data), params).expect("build must succeed");
+    let stats = index.statistics().expect("statistics must be available");
+    let levels = stats.nodes_per_level();
+
+    assert_eq!(levels.len(), 2, "no node may rise above level 1");
+    let ratio = levels[1] as f64 / levels[0] as f64;
+    assert!(
+        (ratio - 0.5).abs() < 0.05,
+        "level 1 should hold about half the nodes (observed {ratio})",
+    );
+}
+
+#[rstest]
+fn statistics_report_cache_occupancy() {
+    let index = build(50, 4);
+    let stats = index.statistics().expect("statistics must be available");
+
+    assert!(stats.cache_entries() > 0);
+    assert!(stats.cache_entries() <= stats.cache_capacity());
+    let occupancy = stats.cache_occupancy();
+    assert!(occupancy > 0.0 && occupancy <= 1.0);
+}
+
+#[rstest]
+fn statistics_count_cache_evictions() {
+    let cache = DistanceCacheConfig::new(NonZeroUsize::new(8).expect("literal is non-zero"));
+    let params = HnswParams::new(4, 32)
+        .expect("params must be valid")
+        .with_rng_seed(7)
+        .with_distance_cache_config(cache);
+    let data = (0..50).map(|i| i as f32 * 0.5).collect();
+    let index = CpuHnsw::build(&DummySource::new(data), params).expect("build must succeed");
+    let stats = index.statistics().expect("statistics must be available");
+
+    assert!(stats.cache_evictions() > 0);
+    assert!(stats.cache_entries() <= stats.cache_capacity());
+    let roomy = build(50, 4)
+        .statistics()
+        .expect("statistics must be available");
+    assert_eq!(
+        roomy.cache_evictions(),
+        0,
+        "the default cache holds every pair"
+    );
+}
+
+#[rstest]
+fn empty_index_reports_no_levels() {
+    let params = HnswParams::new(4, 8).expect("params must be valid");
+    let index = CpuHnsw::with_capacity(params, 8).expect("index must allocate");
+    let stats = index.statistics().expect("statistics must be available");
+
+    assert_eq!(stats.node_count(), 0);
+    assert!(stats.nodes_per_level().is_empty());
+    assert_eq!(stats.total_edges(), 0);
+    assert_eq!(stats.entry_level(), None);
+    assert_eq!(stats.cache_entries(), 0);
+}
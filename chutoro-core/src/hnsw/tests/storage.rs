@@ -0,0 +1,110 @@
+//! Tests for the arena layouts of the graph's neighbour lists.
+
+use std::num::NonZeroUsize;
+
+use rstest::rstest;
+
+use super::fixtures::DummySource;
+use crate::{
+    DataSource,
+    hnsw::{AdjacencyStorage, CpuHnsw, HnswParams},
+};
+
+/// Neighbour lists of every node, per level.
+type Adjacency = Vec<(usize, Vec<Vec<usize>>)>;
+
+fn source() -> DummySource {
+    DummySource::new(
+        (0..240)
+            .map(|i| (i * 37 % 101) as f32 + i as f32 * 1.0e-3)
+            .collect(),
+    )
+}
+
+/// Inserts every point in order, so the graph is deterministic.
+fn build(source: &DummySource, storage: AdjacencyStorage) -> CpuHnsw {
+    let params = HnswParams::new(4, 16)
+        .expect("params must be valid")
+        .with_rng_seed(7)
+        .with_adjacency_storage(storage);
+    let index = CpuHnsw::with_capacity(params, 240).expect("capacity must be valid");
+    for node in 0..240 {
+        index.insert(node, source).expect("insert must succeed");
+    }
+    index
+}
+
+fn adjacency(index: &CpuHnsw) -> Adjacency {
+    index.inspect_graph(|graph| {
+        graph
+            .nodes_iter()
+            .map(|(id, node)| {
+                let levels = (0..node.level_count())
+                    .map(|level| node.neighbours(level).to_vec())
+                    .collect();
+                (id, levels)
+            })
+            .collect()
+    })
+}
+
+/// Checks the build holds every invariant and that searches find each
+/// query's exact top ten neighbours.
+fn assert_builds_a_sound_graph(storage: AdjacencyStorage) {
+    let source = source();
+    let index = build(&source, storage);
+
+    assert!(adjacency(&index).iter().any(|(_, levels)| levels.len() > 1));
+    index
+        .invariants()
+        .check_all()
+        .expect("invariants must hold");
+
+    let ef = NonZeroUsize::new(32).expect("ef must be non-zero");
+    for query in [0, 57, 239] {
+        let found: Vec<usize> = index
+            .search(&source, query, ef)
+            .expect("search must succeed")
+            .iter()
+            .take(10)
+            .map(|neighbour| neighbour.id)
+            .collect();
+        let mut exact: Vec<usize> = (0..240).collect();
+        exact.sort_by(|&left, &right| {
+            let distance = |id| source.distance(query, id).expect("ids are in range");
+            distance(left).total_cmp(&distance(right))
+        });
+        assert_eq!(found, exact[..10]);
+    }
+}
+
+#[rstest]
+#[case::per_node(AdjacencyStorage::PerNode)]
+#[case::arena(AdjacencyStorage::Arena)]
+fn layouts_build_sound_graphs(#[case] storage: AdjacencyStorage) {
+    assert_builds_a_sound_graph(storage);
+}
+
+#[cfg(feature = "hugepages")]
+#[rstest]
+fn huge_page_arena_builds_a_sound_graph() {
+    assert_builds_a_sound_graph(AdjacencyStorage::HugePageArena);
+}
+
+#[rstest]
+fn arena_graphs_survive_deletion() {
+    let source = source();
+    let mut index = build(&source, AdjacencyStorage::Arena);
+
+    assert!(index.delete_node_for_test(57).expect("delete must succeed"));
+
+    assert!(
+        adjacency(&index).iter().all(|(id, levels)| {
+            *id != 57 && levels.iter().flatten().all(|&target| target != 57)
+        })
+    );
+    index
+        .invariants()
+        .check_all()
+        .expect("invariants must hold");
+}
@@ -0,0 +1,131 @@
+//! Tests for the policies that trim over-full neighbour lists.
+
+use std::num::NonZeroUsize;
+
+use rstest::rstest;
+
+use super::fixtures::DummySource;
+use crate::{
+    DataSource,
+    hnsw::{
+        CpuHnsw, HnswError, HnswInvariant, HnswParams, TrimPolicy, graph::EdgeContext,
+        insert::TrimJob, params::ConnectionLimits,
+    },
+};
+
+/// Node 0 at the origin with two tight groups of candidates on either side
+/// and one outlier: `1..=3` near `1.0`, `4..=5` near `-1.0`, and `6` at `3.0`.
+fn flanked_source() -> DummySource {
+    DummySource::new(vec![0.0, 1.0, 1.1, 1.2, -1.0, -1.05, 3.0])
+}
+
+fn trim_with(policy: TrimPolicy) -> Result<Vec<usize>, HnswError> {
+    let params = HnswParams::new(3, 6)?.with_trim_policy(policy)?;
+    let index = CpuHnsw::with_capacity(params, 7)?;
+    let job = TrimJob {
+        node: 0,
+        ctx: EdgeContext {
+            level: 1,
+            limits: ConnectionLimits::uniform(3),
+        },
+        candidates: vec![6, 5, 4, 3, 2, 1],
+        sequences: vec![6, 5, 4, 3, 2, 1],
+    };
+    let mut results = index.score_trim_jobs(vec![job], &flanked_source())?;
+    Ok(results.pop().expect("trim job yields a result").neighbours)
+}
+
+#[rstest]
+#[case::nearest(TrimPolicy::Nearest, vec![1, 4, 5])]
+#[case::diversity(TrimPolicy::DiversityPreserving, vec![1, 4])]
+#[case::unrelaxed(TrimPolicy::RngRelaxed { alpha: 1.0 }, vec![1, 4])]
+#[case::relaxed(TrimPolicy::RngRelaxed { alpha: 2.0 }, vec![1, 4, 6])]
+#[case::loose(TrimPolicy::RngRelaxed { alpha: 25.0 }, vec![1, 4, 5])]
+fn policies_choose_which_candidates_survive(
+    #[case] policy: TrimPolicy,
+    #[case] expected: Vec<usize>,
+) -> Result<(), HnswError> {
+    assert_eq!(trim_with(policy)?, expected);
+    Ok(())
+}
+
+#[rstest]
+#[case::below_one(0.5)]
+#[case::nan(f32::NAN)]
+#[case::infinite(f32::INFINITY)]
+fn relaxation_factors_are_validated(#[case] alpha: f32) {
+    let params = HnswParams::new(4, 8).expect("params must be valid");
+    let err = params
+        .with_trim_policy(TrimPolicy::RngRelaxed { alpha })
+        .expect_err("invalid alpha must be rejected");
+    assert!(matches!(err, HnswError::InvalidParameters { .. }));
+}
+
+/// Eight tight clusters of thirty points, far apart on a line.
+fn clustered_source() -> DummySource {
+    DummySource::new(
+        (0..240)
+            .map(|i| (i % 8) as f32 * 100.0 + (i / 8) as f32 * 0.01)
+            .collect(),
+    )
+}
+
+fn build_clustered(policy: TrimPolicy, seed: u64) -> Result<CpuHnsw, HnswError> {
+    let params = HnswParams::new(4, 16)?
+        .with_rng_seed(seed)
+        .with_trim_policy(policy)?;
+    CpuHnsw::build(&clustered_source(), params)
+}
+
+fn unreachable_nodes(policy: TrimPolicy) -> Result<usize, HnswError> {
+    let mut unreachable = 0;
+    for seed in 0..6 {
+        let index = build_clustered(policy, seed)?;
+        unreachable += index
+            .invariants()
+            .collect_many([HnswInvariant::Reachability])
+            .len();
+    }
+    Ok(unreachable)
+}
+
+#[rstest]
+#[case::diversity(TrimPolicy::DiversityPreserving)]
+#[case::relaxed(TrimPolicy::RngRelaxed { alpha: 1.2 })]
+fn pruning_keeps_clustered_graphs_better_connected(
+    #[case] policy: TrimPolicy,
+) -> Result<(), HnswError> {
+    // Nearest trimming drops the edges that bridge clusters, so some nodes
+    // become unreachable from the entry point; pruning keeps them.
+    let baseline = unreachable_nodes(TrimPolicy::Nearest)?;
+    let pruned = unreachable_nodes(policy)?;
+    assert!(
+        pruned < baseline,
+        "{policy:?} left {pruned} unreachable nodes, nearest left {baseline}",
+    );
+    Ok(())
+}
+
+#[rstest]
+#[case::diversity(TrimPolicy::DiversityPreserving)]
+#[case::relaxed(TrimPolicy::RngRelaxed { alpha: 1.2 })]
+fn pruned_graphs_stay_sound_and_searchable(#[case] policy: TrimPolicy) -> Result<(), HnswError> {
+    let source = clustered_source();
+    let index = build_clustered(policy, 5)?;
+
+    index
+        .invariants()
+        .check_many([
+            HnswInvariant::LayerConsistency,
+            HnswInvariant::DegreeBounds,
+            HnswInvariant::BidirectionalLinks,
+        ])
+        .expect("structural invariants must hold");
+    let ef = NonZeroUsize::new(8).expect("ef must be non-zero");
+    for query in [0, 9, 131, 239] {
+        let nearest = index.search(&source, query, ef)?;
+        let own_cluster = source.distance(query, nearest[0].id)?;
+        assert!(own_cluster < 1.0, "query {query} must find its own cluster");
+    }
+    Ok(())
+}
@@ -0,0 +1,78 @@
+//! Tests for the read-only adjacency view returned by `CpuHnsw::read_view`.
+
+use rstest::rstest;
+
+use crate::hnsw::{AdjacencyStorage, CpuHnsw, HnswParams};
+
+use super::fixtures::DummySource;
+
+fn params(storage: AdjacencyStorage) -> HnswParams {
+    HnswParams::new(6, 32)
+        .expect("params must be valid")
+        .with_rng_seed(13)
+        .with_adjacency_storage(storage)
+}
+
+fn source(points: usize) -> DummySource {
+    DummySource::new((0..points).map(|i| (i * 29 % 83) as f32).collect())
+}
+
+#[rstest]
+#[case::per_node(AdjacencyStorage::PerNode)]
+#[case::arena(AdjacencyStorage::Arena)]
+fn views_agree_with_statistics(#[case] storage: AdjacencyStorage) {
+    let index = CpuHnsw::build(&source(200), params(storage)).expect("build must succeed");
+    let stats = index.statistics().expect("statistics must be available");
+    let view = index.read_view().expect("view must be available");
+
+    let mut edges_per_level = vec![0; stats.edges_per_level().len()];
+    for node in &view {
+        assert_eq!(view.levels(node.id()), Some(node.levels()));
+        for (level, edges) in edges_per_level.iter_mut().enumerate() {
+            *edges += node.neighbours(level).len();
+        }
+    }
+    assert_eq!(view.iter().count(), index.len());
+    assert_eq!(edges_per_level, stats.edges_per_level());
+    assert_eq!(view.entry().map(|entry| entry.level), stats.entry_level());
+    assert_eq!(view.edges().count(), stats.total_edges());
+    for (source, target, level) in view.edges() {
+        let neighbours = view
+            .neighbours(source, level)
+            .expect("source reaches level");
+        assert!(neighbours.contains(&target));
+    }
+}
+
+#[rstest]
+fn lookups_outside_the_graph_return_none() {
+    let data = source(8);
+    let index = CpuHnsw::with_capacity(params(AdjacencyStorage::PerNode), 8)
+        .expect("capacity must be valid");
+    for node in [0, 2, 3, 5] {
+        index.insert(node, &data).expect("insert must succeed");
+    }
+    let view = index.read_view().expect("view must be available");
+
+    let ids: Vec<_> = view.iter().map(|node| node.id()).collect();
+    assert_eq!(ids, [0, 2, 3, 5]);
+    assert!(view.node(1).is_none());
+    assert_eq!(view.levels(40), None);
+    assert_eq!(view.neighbours(4, 0), None);
+
+    let node = view.node(3).expect("node 3 was inserted");
+    assert_eq!(view.neighbours(3, node.levels()), None);
+    assert!(node.neighbours(node.levels()).is_empty());
+    assert_eq!(view.neighbours(3, 0), Some(node.neighbours(0)));
+}
+
+#[rstest]
+fn empty_graphs_have_no_entry_point() {
+    let index = CpuHnsw::with_capacity(params(AdjacencyStorage::PerNode), 4)
+        .expect("capacity must be valid");
+    let view = index.read_view().expect("view must be available");
+
+    assert!(view.entry().is_none());
+    assert_eq!(view.iter().next().map(|node| node.id()), None);
+    assert_eq!(view.edges().count(), 0);
+}
@@ -1,11 +1,14 @@
 //! Distance validation helpers for HNSW operations.
 //!
-//! This module exports `validate_distance`, `validate_batch_distances`, and
-//! `validate_batch_without_cache` for checked single and batched distance
-//! lookups. Their shared `lookup_or_compute` helper consults an optional
-//! `DistanceCache` before falling back to `DataSource::distance` using the
-//! source's `metric_descriptor`, bridging `distance_cache.rs` cache state with
-//! `error.rs` failure reporting through `HnswError`.
+//! This module exports `validate_distance`, `validate_batch_distances`,
+//! `validate_batch_distances_into`, and `validate_batch_without_cache` for
+//! checked single and batched distance lookups. Their shared
+//! `lookup_or_compute` helper consults an optional `DistanceCache` before
+//! falling back to `DataSource::distance` using the source's
+//! `metric_descriptor`, bridging `distance_cache.rs` cache state with
+//! `error.rs` failure reporting through `HnswError`. Freshly computed
+//! distances pass through `admit_distance`, which applies the cache's
+//! [`crate::DistancePolicy`] before the value is cached or reported.
 
 use super::{
     distance_cache::{DistanceCache, LookupOutcome, PendingMiss},
@@ -24,7 +27,8 @@ fn lookup_or_compute<D: DataSource + Sync>(
         match cache.begin_lookup(&metric, left, right) {
             LookupOutcome::Hit(value) => Ok(value),
             LookupOutcome::Miss(pending) => {
-                let value = source.distance(left, right)?;
+                let value =
+                    admit_distance(Some(cache), left, right, source.distance(left, right)?)?;
                 cache.complete_miss(pending, value)
             }
         }
@@ -43,7 +47,7 @@ fn batch_lookup_or_compute<D: DataSource + Sync>(
     let mut results: Vec<Option<f32>> = vec![None; candidates.len()];
     let mut pending = Vec::new();
 
-    context.populate(&mut results, &mut pending);
+    context.populate(&mut pending, |index, value| results[index] = Some(value));
 
     if !pending.is_empty() {
         context.resolve(pending, &mut results)?;
@@ -52,6 +56,48 @@ fn batch_lookup_or_compute<D: DataSource + Sync>(
     ensure_all_resolved(query, candidates, results)
 }
 
+/// Scores cache misses through [`DataSource::batch_distances_into`], writing
+/// hits and computed distances straight into `out`.
+fn batch_lookup_or_compute_into<D: DataSource + Sync>(
+    context: &CacheBatch<'_, D>,
+    out: &mut [f32],
+) -> Result<(), HnswError> {
+    let mut pending = Vec::new();
+    context.populate(&mut pending, |index, value| out[index] = value);
+    if pending.is_empty() {
+        return Ok(());
+    }
+
+    let missing = context.missing(&pending);
+    let mut computed = vec![0.0_f32; missing.len()];
+    context
+        .source
+        .batch_distances_into(context.query, &missing, &mut computed)?;
+    for ((index, miss), value) in pending.into_iter().zip(computed) {
+        out[index] = context.complete(index, miss, value)?;
+    }
+    Ok(())
+}
+
+/// Applies the cache's distance policy to a computed distance.
+///
+/// Finite values pass through. A non-finite value is replaced when the
+/// policy allows it and rejected with [`HnswError::NonFiniteDistance`] when
+/// the policy is strict or there is no cache to carry one.
+pub(crate) fn admit_distance(
+    cache: Option<&DistanceCache>,
+    left: usize,
+    right: usize,
+    value: f32,
+) -> Result<f32, HnswError> {
+    if value.is_finite() {
+        return Ok(value);
+    }
+    cache
+        .and_then(|cache| cache.guard().admit(left, right, value))
+        .ok_or(HnswError::NonFiniteDistance { left, right })
+}
+
 pub(crate) fn validate_distance<D: DataSource + Sync>(
     cache: Option<&DistanceCache>,
     source: &D,
@@ -79,23 +125,48 @@ pub(crate) fn validate_batch_distances<D: DataSource + Sync>(
     }
 }
 
+/// Validates the distances from `query` to `candidates` into `out`, which is
+/// resized to match so callers can reuse one buffer across batches.
+pub(crate) fn validate_batch_distances_into<D: DataSource + Sync>(
+    (cache, source): (Option<&DistanceCache>, &D),
+    query: usize,
+    candidates: &[usize],
+    out: &mut Vec<f32>,
+) -> Result<(), HnswError> {
+    out.clear();
+    out.resize(candidates.len(), 0.0);
+    if let Some(cache) = cache {
+        let context = CacheBatch::new(cache, source, query, candidates);
+        return batch_lookup_or_compute_into(&context, out);
+    }
+    source.batch_distances_into(query, candidates, out)?;
+    ensure_finite(query, candidates, out)
+}
+
 fn validate_batch_without_cache<D: DataSource + Sync>(
     source: &D,
     query: usize,
     candidates: &[usize],
 ) -> Result<Vec<f32>, HnswError> {
     let distances = source.batch_distances(query, candidates)?;
-    for (&candidate, &distance) in candidates.iter().zip(distances.iter()) {
-        if !distance.is_finite() {
-            return Err(HnswError::NonFiniteDistance {
-                left: query,
-                right: candidate,
-            });
-        }
-    }
+    ensure_finite(query, candidates, &distances)?;
     Ok(distances)
 }
 
+fn ensure_finite(query: usize, candidates: &[usize], distances: &[f32]) -> Result<(), HnswError> {
+    match candidates
+        .iter()
+        .zip(distances)
+        .find(|(_, distance)| !distance.is_finite())
+    {
+        Some((&candidate, _)) => Err(HnswError::NonFiniteDistance {
+            left: query,
+            right: candidate,
+        }),
+        None => Ok(()),
+    }
+}
+
 struct CacheBatch<'a, D: DataSource + Sync> {
     cache: &'a DistanceCache,
     source: &'a D,
@@ -115,24 +186,29 @@ impl<'a, D: DataSource + Sync> CacheBatch<'a, D> {
         }
     }
 
-    fn populate(&self, results: &mut [Option<f32>], pending: &mut Vec<(usize, PendingMiss)>) {
+    /// Reports each cache hit to `hit` and queues each miss in `pending`.
+    fn populate(&self, pending: &mut Vec<(usize, PendingMiss)>, mut hit: impl FnMut(usize, f32)) {
         for (index, &candidate) in self.candidates.iter().enumerate() {
             match self.cache.begin_lookup(&self.metric, self.query, candidate) {
-                LookupOutcome::Hit(value) => results[index] = Some(value),
+                LookupOutcome::Hit(value) => hit(index, value),
                 LookupOutcome::Miss(miss) => pending.push((index, miss)),
             }
         }
     }
 
+    fn missing(&self, pending: &[(usize, PendingMiss)]) -> Vec<usize> {
+        pending
+            .iter()
+            .map(|(index, _)| self.candidates[*index])
+            .collect()
+    }
+
     fn resolve(
         &self,
         pending: Vec<(usize, PendingMiss)>,
         results: &mut [Option<f32>],
     ) -> Result<(), HnswError> {
-        let missing: Vec<usize> = pending
-            .iter()
-            .map(|(index, _)| self.candidates[*index])
-            .collect();
+        let missing = self.missing(&pending);
         let computed = self.source.batch_distances(self.query, &missing)?;
 
         if computed.len() != pending.len() {
@@ -146,12 +222,19 @@ impl<'a, D: DataSource + Sync> CacheBatch<'a, D> {
         }
 
         for ((index, miss), value) in pending.into_iter().zip(computed.into_iter()) {
-            let value = self.cache.complete_miss(miss, value)?;
-            results[index] = Some(value);
+            results[index] = Some(self.complete(index, miss, value)?);
         }
 
         Ok(())
     }
+
+    /// Admits the distance computed for the `index`-th candidate and caches
+    /// it.
+    fn complete(&self, index: usize, miss: PendingMiss, value: f32) -> Result<f32, HnswError> {
+        let candidate = self.candidates[index];
+        let value = admit_distance(Some(self.cache), self.query, candidate, value)?;
+        self.cache.complete_miss(miss, value)
+    }
 }
 
 fn ensure_all_resolved(
@@ -0,0 +1,114 @@
+//! Mapping between dense row indices and caller-supplied row identifiers.
+//!
+//! [`DataSource`](crate::DataSource) addresses items by contiguous `usize`
+//! indices, while real datasets are keyed by strings such as UUIDs or file
+//! names. An [`IdMap`] records one identifier per row so results can be
+//! reported against the original keys.
+
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+/// Errors raised when row identifiers cannot be attached to a dataset.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum RowIdError {
+    /// Two rows share an identifier, so lookups by identifier are ambiguous.
+    #[error("row identifier `{id}` is used by rows {first} and {second}")]
+    Duplicate {
+        /// The repeated identifier.
+        id: String,
+        /// Index of the first row that uses it.
+        first: usize,
+        /// Index of the later row that repeats it.
+        second: usize,
+    },
+    /// The number of identifiers does not match the number of rows.
+    #[error("{ids} row identifiers were supplied for {rows} rows")]
+    LengthMismatch {
+        /// Number of identifiers supplied.
+        ids: usize,
+        /// Number of rows they must describe.
+        rows: usize,
+    },
+}
+
+/// One unique identifier per row, in row order.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::IdMap;
+///
+/// let ids = IdMap::new(vec!["a".into(), "b".into()])?;
+/// assert_eq!(ids.id(1), Some("b"));
+/// assert_eq!(ids.index_of("a"), Some(0));
+/// # Ok::<(), chutoro_core::RowIdError>(())
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IdMap {
+    ids: Vec<String>,
+    indices: HashMap<String, usize>,
+}
+
+impl IdMap {
+    /// Builds a map assigning `ids[i]` to row `i`.
+    ///
+    /// # Errors
+    /// Returns [`RowIdError::Duplicate`] when an identifier appears twice.
+    pub fn new(ids: Vec<String>) -> Result<Self, RowIdError> {
+        let mut indices = HashMap::with_capacity(ids.len());
+        for (index, id) in ids.iter().enumerate() {
+            if let Some(first) = indices.insert(id.clone(), index) {
+                return Err(RowIdError::Duplicate {
+                    id: id.clone(),
+                    first,
+                    second: index,
+                });
+            }
+        }
+        Ok(Self { ids, indices })
+    }
+
+    /// Builds a map for a dataset of `rows` items.
+    ///
+    /// # Errors
+    /// Returns [`RowIdError::LengthMismatch`] when `ids` does not hold exactly
+    /// `rows` entries and [`RowIdError::Duplicate`] when an identifier
+    /// repeats.
+    pub fn for_rows(ids: Vec<String>, rows: usize) -> Result<Self, RowIdError> {
+        if ids.len() != rows {
+            return Err(RowIdError::LengthMismatch {
+                ids: ids.len(),
+                rows,
+            });
+        }
+        Self::new(ids)
+    }
+
+    /// Returns the number of rows described.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn len(&self) -> usize { self.ids.len() }
+
+    /// Returns `true` when the map describes no rows.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn is_empty(&self) -> bool { self.ids.is_empty() }
+
+    /// Returns the identifier of row `index`.
+    #[must_use]
+    pub fn id(&self, index: usize) -> Option<&str> {
+        self.ids.get(index).map(String::as_str)
+    }
+
+    /// Returns the row carrying `id`.
+    #[must_use]
+    pub fn index_of(&self, id: &str) -> Option<usize> {
+        self.indices.get(id).copied()
+    }
+
+    /// Returns every identifier in row order.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn ids(&self) -> &[String] { &self.ids }
+}
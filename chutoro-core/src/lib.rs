@@ -1,61 +1,171 @@
 //! Chutoro core library.
 
 mod builder;
+#[cfg(feature = "cpu")]
+mod checkpoint;
 mod chutoro;
 mod clustering_quality;
+mod connectivity;
 #[cfg(feature = "cpu")]
 mod cpu_pipeline;
-mod datasource;
+pub mod datasource;
+#[cfg(feature = "cpu")]
+mod dedupe;
 mod distance;
+#[cfg(feature = "cpu")]
+mod distance_budget;
+mod distance_policy;
+mod distance_transform;
+mod drift;
+mod dry_run;
 mod error;
 #[cfg(feature = "cpu")]
+mod event_log;
+#[cfg(feature = "cpu")]
+mod fit;
+#[cfg(feature = "cpu")]
+mod graph_builder;
+#[cfg(feature = "cpu")]
 mod hierarchy;
+mod hierarchy_compare;
 #[cfg(feature = "cpu")]
 mod hnsw;
+mod ids;
+mod membership;
 mod memory;
 #[cfg(feature = "cpu")]
 mod mst;
+#[cfg(feature = "cpu")]
+mod online;
+#[cfg(all(feature = "cpu", any(test, feature = "test-oracles")))]
+pub mod oracles;
+#[cfg(feature = "parquet")]
+mod parquet_io;
+mod precision;
+#[cfg(feature = "cpu")]
+mod preset;
+mod reassign;
 mod result;
+mod sample;
+mod seed;
+mod seed_labels;
 #[cfg(feature = "cpu")]
 mod session;
+mod sparsify;
+#[cfg(feature = "cpu")]
+mod spill;
+#[cfg(feature = "cpu")]
+mod stages;
+mod timings;
+mod warning;
 
 pub use crate::{
     builder::{ChutoroBuilder, ExecutionStrategy},
-    chutoro::Chutoro,
+    chutoro::{Backend, Chutoro},
     clustering_quality::{
         ClusteringQualityError, ClusteringQualityScore, adjusted_rand_index,
         clustering_quality_score, normalized_mutual_information,
     },
-    datasource::{DataSource, MetricDescriptor},
+    connectivity::ConnectivityReport,
+    datasource::{DataSource, MetricClass, MetricDescriptor},
     distance::{
         CosineNorms, Distance, DistanceError, Norm, Result as DistanceResult, VectorKind,
         cosine_distance, euclidean_distance,
     },
+    distance_policy::{DistancePolicy, DistancePolicyReport},
+    distance_transform::DistanceTransform,
+    drift::{ClusterDrift, DriftError, DriftReport, DriftThresholds},
+    dry_run::DryRunReport,
     error::{ChutoroError, ChutoroErrorCode, DataSourceError, DataSourceErrorCode, Result},
-    memory::{estimate_peak_bytes, format_bytes},
-    result::{ClusterId, ClusteringResult, NonContiguousClusterIds},
+    hierarchy_compare::{ClusterMatch, HierarchyComparison},
+    ids::{IdMap, RowIdError},
+    membership::MembershipScores,
+    memory::{ResourceEstimate, estimate_peak_bytes, format_bytes},
+    precision::DistancePrecision,
+    reassign::{NoiseReassignmentReport, ReassignPolicy},
+    result::{
+        ClusterExemplars, ClusterHierarchy, ClusterId, ClusterPersistence, ClusteringResult,
+        EdgeProvenance, ExemplarError, ExplainedEdge, HierarchyCut, HierarchyCutError,
+        NonContiguousClusterIds, PERSISTENCE_THRESHOLD, PairExplanation, ParameterReport,
+        ResultDecodeError, RunAgreement, StabilityReport,
+    },
+    sample::{SampleSpec, SamplingReport},
+    seed::{SeedReport, SeedStream},
+    seed_labels::{SeedLabelConflict, SeedLabelReport},
+    sparsify::{EdgeBudget, SparsificationReport},
+    timings::StageTimings,
+    warning::{Warning, WarningCode},
 };
 
 #[cfg(feature = "cpu")]
 pub use crate::cpu_pipeline::run_cpu_pipeline;
 
+#[cfg(feature = "cpu")]
+/// One-call clustering of dense vectors; requires the `cpu` feature.
+pub use crate::fit::{ClusterOptions, cluster_dense};
+
+#[cfg(feature = "cpu")]
+/// Candidate-graph construction backends; requires the `cpu` feature.
+pub use crate::graph_builder::GraphBuilder;
+
+#[cfg(feature = "cpu")]
+/// Candidate-edge sparsification helpers; requires the `cpu` feature.
+pub use crate::sparsify::sparsify_harvest;
+
 #[cfg(feature = "cpu")]
 /// CPU-accelerated HNSW index components; requires the `cpu` feature.
 pub use crate::hnsw::{
-    CandidateEdge, CpuHnsw, DistanceCacheConfig, EdgeHarvest, HnswError, HnswErrorCode,
-    HnswInvariant, HnswInvariantChecker, HnswInvariantViolation, HnswParams, Neighbour,
+    AdjacencyStorage, CandidateEdge, CpuHnsw, DistanceCacheConfig, EdgeHarvest, EdgeHarvestBuilder,
+    EdgeHarvestProducer, EntryPoint, FrozenHnsw, GraphExportError, GraphFormat, HnswError,
+    HnswErrorCode, HnswInvariant, HnswInvariantChecker, HnswInvariantViolation, HnswNodeView,
+    HnswNodes, HnswParams, HnswReadView, HnswStatistics, MAX_LAYER_OVERRIDE, MetricCostHint,
+    Neighbour, NeighbourDetail, RngKind, TrimPolicy,
 };
 
 #[cfg(feature = "cpu")]
 /// CPU minimum spanning tree (MST) utilities; requires the `cpu` feature.
-pub use crate::mst::{MinimumSpanningForest, MstEdge, MstError, MstErrorCode, parallel_kruskal};
+pub use crate::mst::{
+    MinimumSpanningForest, MstCandidate, MstDiagnostics, MstEdge, MstError, MstErrorCode,
+    parallel_kruskal, parallel_kruskal_from_iter, parallel_kruskal_owned,
+};
 
 #[cfg(feature = "cpu")]
 /// Hierarchy extraction utilities for the CPU pipeline; requires the `cpu` feature.
 pub use crate::hierarchy::{
-    HierarchyConfig, HierarchyError, HierarchyErrorCode, extract_labels_from_mst,
+    CondensedChild, CondensedRow, CondensedTree, HierarchyConfig, HierarchyError,
+    HierarchyErrorCode, ReachabilityPlot, ReachabilityPoint, extract_labels_from_harvest,
+    extract_labels_from_mst, reachability_plot,
+};
+
+#[cfg(feature = "cpu")]
+/// Substitutable CPU pipeline stages; requires the `cpu` feature.
+pub use crate::stages::{
+    DefaultHarvestStage, DefaultHierarchyStage, DefaultIndexStage, DefaultMstStage, HarvestStage,
+    HierarchyStage, IndexStage, MstStage, StageArtefact, StageArtefactHook, StageContext,
+    WeightedHarvest,
 };
 
+#[cfg(feature = "cpu")]
+/// Approximate labels for unseen points; requires the `cpu` feature.
+pub use crate::chutoro::Prediction;
+
+#[cfg(feature = "cpu")]
+/// Per-call settings for queries against the retained index; requires the
+/// `cpu` feature.
+pub use crate::chutoro::SearchParams;
+
+#[cfg(feature = "parquet")]
+/// Versioned Parquet files for edge artefacts; requires the `parquet` feature.
+pub use crate::parquet_io::{PARQUET_SCHEMA_VERSION, ParquetArtefactError};
+
+#[cfg(feature = "cpu")]
+/// Named parameter sets for common workloads; requires the `cpu` feature.
+pub use crate::preset::Preset;
+
+#[cfg(feature = "cpu")]
+/// Micro-cluster summaries for streaming data; requires the `cpu` feature.
+pub use crate::online::{MicroCluster, OnlineClusterer};
+
 #[cfg(feature = "cpu")]
 /// CPU incremental clustering session types; requires the `cpu` feature.
 pub use crate::session::{ClusteringSession, SessionConfig, SessionRefreshPolicy};
@@ -0,0 +1,85 @@
+//! Per-point membership strength and outlier scores.
+//!
+//! Hierarchy extraction measures how long each point stays attached to its
+//! cluster in the condensed tree. Those persistence values become a membership
+//! probability for clustered points and a GLOSH outlier score for every point,
+//! both attached to [`crate::ClusteringResult`].
+
+/// Membership probabilities and GLOSH outlier scores, indexed by point.
+///
+/// `probabilities[i]` is `1.0` for a point that persists until its selected
+/// cluster dissolves, falls towards `0.0` for points that leave the cluster
+/// early, and is `0.0` for noise. `outlier_scores[i]` follows the GLOSH
+/// definition: `0.0` for points in the densest part of their cluster and
+/// approaching `1.0` for points that are outliers relative to it.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::MembershipScores;
+///
+/// let scores = MembershipScores::default();
+/// assert!(scores.probabilities().is_empty());
+/// assert!(scores.outlier_scores().is_empty());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "RawMembershipScores")
+)]
+pub struct MembershipScores {
+    probabilities: Vec<f32>,
+    outlier_scores: Vec<f32>,
+}
+
+// Scores are always finite values in `[0, 1]`, so equality is reflexive.
+impl Eq for MembershipScores {}
+
+impl MembershipScores {
+    pub(crate) fn new(probabilities: Vec<f32>, outlier_scores: Vec<f32>) -> Self {
+        debug_assert_eq!(probabilities.len(), outlier_scores.len());
+        Self {
+            probabilities,
+            outlier_scores,
+        }
+    }
+
+    /// Returns the membership probability of each point in its cluster.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn probabilities(&self) -> &[f32] { &self.probabilities }
+
+    /// Returns the GLOSH outlier score of each point.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn outlier_scores(&self) -> &[f32] { &self.outlier_scores }
+}
+
+/// Serialized form of [`MembershipScores`], validated on the way back in.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawMembershipScores {
+    probabilities: Vec<f32>,
+    outlier_scores: Vec<f32>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawMembershipScores> for MembershipScores {
+    type Error = &'static str;
+
+    fn try_from(raw: RawMembershipScores) -> Result<Self, Self::Error> {
+        if raw.probabilities.len() != raw.outlier_scores.len() {
+            return Err("probabilities and outlier_scores must have equal lengths");
+        }
+        let in_range = |score: &f32| (0.0..=1.0).contains(score);
+        if !raw
+            .probabilities
+            .iter()
+            .chain(&raw.outlier_scores)
+            .all(in_range)
+        {
+            return Err("membership scores must lie in [0, 1]");
+        }
+        Ok(Self::new(raw.probabilities, raw.outlier_scores))
+    }
+}
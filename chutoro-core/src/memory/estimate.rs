@@ -0,0 +1,266 @@
+//! Per-stage peak-memory estimates for the CPU clustering pipeline.
+//!
+//! [`ResourceEstimate`] breaks the prediction down by pipeline stage so an
+//! operator can see which stage dominates; [`estimate_peak_bytes`] is the
+//! single figure the `max_bytes` guard compares against.
+
+use std::fmt;
+
+use super::format_bytes;
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+/// Safety multiplier applied to the raw estimate to cover heap fragmentation,
+/// Rayon thread-local buffers, and transient allocations.  1.5× is chosen as
+/// a balance between avoiding false positives and catching genuine OOM risks.
+const SAFETY_MULTIPLIER_NUMERATOR: u64 = 3;
+const SAFETY_MULTIPLIER_DENOMINATOR: u64 = 2;
+
+/// Default maximum distance cache entries.  Mirrors the value in
+/// `DistanceCacheConfig::DEFAULT_MAX_ENTRIES` but is duplicated here so the
+/// estimation module compiles without the `cpu` feature gate.
+const DEFAULT_CACHE_MAX_ENTRIES: u64 = 1_048_576;
+
+/// Estimated overhead per node in the HNSW graph: `Option<Node>`, `Vec`
+/// headers for the per-level neighbour lists, sequence counter, and alignment
+/// padding.  Derived from the layout of `hnsw::graph::Node` on 64-bit Linux.
+const NODE_OVERHEAD_BYTES: u64 = 80;
+
+/// Size of a single `CandidateEdge` (`source: usize`, `target: usize`,
+/// `distance: f32`, `sequence: u64`) including padding on 64-bit platforms.
+const CANDIDATE_EDGE_BYTES: u64 = 32;
+
+/// Size of a single `MstEdge` (identical layout to `CandidateEdge`).
+const MST_EDGE_BYTES: u64 = 32;
+
+/// Estimated per-entry overhead for the distance cache, accounting for the
+/// `DashMap` slot, the `LruCache` bookkeeping, and the stored key/value.
+const CACHE_ENTRY_BYTES: u64 = 80;
+
+/// Size of a single-linkage forest node (three `Option<usize>` links, an
+/// `f32` weight, and a `usize` size) including padding on 64-bit platforms.
+const LINKAGE_NODE_BYTES: u64 = 64;
+
+/// Size of a condensed-tree event recording a point leaving a cluster.
+const CONDENSED_EVENT_BYTES: u64 = 32;
+
+/// Per-point outputs: the cluster label and the membership score, padded.
+const OUTPUT_BYTES_PER_POINT: u64 = 16;
+
+/// Size of an `f32` — used for the core-distances vector and dense rows.
+const F32_BYTES: u64 = 4;
+
+/// Size of a `usize` — derived at compile time so the estimate adapts to the
+/// target platform (8 bytes on 64-bit, 4 bytes on 32-bit).
+const USIZE_BYTES: u64 = std::mem::size_of::<usize>() as u64;
+
+// ---------------------------------------------------------------------------
+// Estimation
+// ---------------------------------------------------------------------------
+
+/// Predicted memory of each CPU pipeline stage for a dataset of a given size.
+///
+/// Stage figures are raw byte counts; [`Self::peak_bytes`] sums them and
+/// applies a 1.5× safety multiplier for heap fragmentation, Rayon
+/// thread-local buffers, and transient allocations. The source's own storage
+/// is reported separately by [`Self::source_bytes`] because it is already
+/// resident before the pipeline starts.
+///
+/// # Examples
+///
+/// ```
+/// use chutoro_core::ResourceEstimate;
+///
+/// let estimate = ResourceEstimate::new(1_000_000, 16).with_dimension(Some(128));
+/// assert!(estimate.edge_harvest_bytes() > estimate.mst_bytes());
+/// assert_eq!(estimate.source_bytes(), Some(512_000_000));
+/// assert!(!estimate.fits_within(1 << 30));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResourceEstimate {
+    point_count: usize,
+    max_connections: usize,
+    cache_entries: usize,
+    dimension: Option<usize>,
+}
+
+impl ResourceEstimate {
+    /// Estimates a run over `point_count` items with HNSW fan-out
+    /// `max_connections` (`M`) and the default distance-cache capacity.
+    #[must_use]
+    pub fn new(point_count: usize, max_connections: usize) -> Self {
+        Self {
+            point_count,
+            max_connections,
+            cache_entries: DEFAULT_CACHE_MAX_ENTRIES as usize,
+            dimension: None,
+        }
+    }
+
+    /// Replaces the distance-cache capacity assumed for the index stage.
+    #[must_use]
+    pub fn with_cache_entries(mut self, entries: usize) -> Self {
+        self.cache_entries = entries;
+        self
+    }
+
+    /// Records the number of `f32` components per item, enabling
+    /// [`Self::source_bytes`].
+    #[must_use]
+    pub fn with_dimension(mut self, dimension: Option<usize>) -> Self {
+        self.dimension = dimension;
+        self
+    }
+
+    /// Returns the number of items the pipeline indexes and clusters.
+    #[must_use]
+    pub fn point_count(&self) -> usize {
+        self.point_count
+    }
+
+    /// Returns the HNSW fan-out (`M`) the estimate assumes.
+    #[must_use]
+    pub fn max_connections(&self) -> usize {
+        self.max_connections
+    }
+
+    /// Returns the vector dimension, if known.
+    #[must_use]
+    pub fn dimension(&self) -> Option<usize> {
+        self.dimension
+    }
+
+    /// Bytes held by the HNSW index: level-0 adjacency (`2 × M` neighbours
+    /// per node), per-node overhead, and the distance cache at full capacity.
+    ///
+    /// The cache is counted in full regardless of point count, because
+    /// pairwise lookups during construction can fill it even for small `n`.
+    #[must_use]
+    pub fn index_bytes(&self) -> u64 {
+        if self.point_count == 0 {
+            return 0;
+        }
+        let n = self.point_count as u64;
+        let m = self.max_connections as u64;
+        let adjacency = n.saturating_mul(2_u64.saturating_mul(m).saturating_mul(USIZE_BYTES));
+        let nodes = n.saturating_mul(NODE_OVERHEAD_BYTES);
+        let cache = (self.cache_entries as u64).saturating_mul(CACHE_ENTRY_BYTES);
+        adjacency.saturating_add(nodes).saturating_add(cache)
+    }
+
+    /// Bytes held by the edge harvest: the `≈ n × M` candidate edges, their
+    /// mutual-reachability rewrite, and one core distance per point.
+    #[must_use]
+    pub fn edge_harvest_bytes(&self) -> u64 {
+        let n = self.point_count as u64;
+        let candidates = n
+            .saturating_mul(self.max_connections as u64)
+            .saturating_mul(CANDIDATE_EDGE_BYTES);
+        let core_distances = n.saturating_mul(F32_BYTES);
+        candidates.saturating_mul(2).saturating_add(core_distances)
+    }
+
+    /// Bytes held by the minimum spanning forest: up to `n` edges.
+    #[must_use]
+    pub fn mst_bytes(&self) -> u64 {
+        (self.point_count as u64).saturating_mul(MST_EDGE_BYTES)
+    }
+
+    /// Bytes held by hierarchy extraction: the `2n − 1` single-linkage nodes,
+    /// one condensed-tree event per point, and the per-point labels and
+    /// membership scores.
+    #[must_use]
+    pub fn hierarchy_bytes(&self) -> u64 {
+        let n = self.point_count as u64;
+        let linkage = n.saturating_mul(2).saturating_mul(LINKAGE_NODE_BYTES);
+        let per_point = CONDENSED_EVENT_BYTES + OUTPUT_BYTES_PER_POINT;
+        linkage.saturating_add(n.saturating_mul(per_point))
+    }
+
+    /// Bytes of a dense `f32` matrix holding every item, when the dimension
+    /// is known. Not included in [`Self::peak_bytes`].
+    #[must_use]
+    pub fn source_bytes(&self) -> Option<u64> {
+        self.dimension.map(|dimension| {
+            (self.point_count as u64)
+                .saturating_mul(dimension as u64)
+                .saturating_mul(F32_BYTES)
+        })
+    }
+
+    /// Conservative peak bytes for the whole pipeline: every stage summed,
+    /// then scaled by the 1.5× safety multiplier.
+    #[must_use]
+    pub fn peak_bytes(&self) -> u64 {
+        self.index_bytes()
+            .saturating_add(self.edge_harvest_bytes())
+            .saturating_add(self.mst_bytes())
+            .saturating_add(self.hierarchy_bytes())
+            .saturating_mul(SAFETY_MULTIPLIER_NUMERATOR)
+            .saturating_div(SAFETY_MULTIPLIER_DENOMINATOR)
+    }
+
+    /// Returns whether [`Self::peak_bytes`] stays within `max_bytes`, the
+    /// same test `ChutoroBuilder::with_max_bytes` applies before a run.
+    #[must_use]
+    pub fn fits_within(&self, max_bytes: u64) -> bool {
+        self.peak_bytes() <= max_bytes
+    }
+}
+
+impl fmt::Display for ResourceEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} points: index {}, edge harvest {}, MST {}, hierarchy {}, peak {}",
+            self.point_count,
+            format_bytes(self.index_bytes()),
+            format_bytes(self.edge_harvest_bytes()),
+            format_bytes(self.mst_bytes()),
+            format_bytes(self.hierarchy_bytes()),
+            format_bytes(self.peak_bytes()),
+        )?;
+        if let Some(source) = self.source_bytes() {
+            write!(f, " (plus {} of source data)", format_bytes(source))?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns a conservative estimate of peak memory (in bytes) that the CPU
+/// pipeline will require for `point_count` items with the given HNSW
+/// `max_connections` parameter (`M`).
+///
+/// The estimate covers:
+///
+/// - HNSW level-0 adjacency lists (`2 × M` neighbours per node).
+/// - Per-node struct overhead (Vec headers, sequence counter, alignment).
+/// - Distance cache (full configured capacity of 1,048,576 entries).
+/// - Candidate edges harvested during HNSW build (`≈ n × M`).
+/// - Core-distance vector (`n × sizeof(f32)`).
+/// - Mutual-reachability edge rewrite (same count as candidate edges).
+/// - MST forest edges (`n` edges, rounding up from `n − 1`).
+/// - Single-linkage forest, condensed tree, labels, and membership scores.
+///
+/// A 1.5× safety multiplier is applied to the raw total to account for heap
+/// fragmentation, Rayon thread-local buffers, and transient allocations.
+/// [`ResourceEstimate`] reports the same figure broken down by stage.
+///
+/// # Examples
+///
+/// ```
+/// use chutoro_core::estimate_peak_bytes;
+///
+/// let bytes = estimate_peak_bytes(1_000, 16);
+/// assert!(bytes > 0, "estimate must be positive for non-empty datasets");
+///
+/// let zero = estimate_peak_bytes(0, 16);
+/// assert_eq!(zero, 0, "empty dataset requires no memory");
+/// ```
+#[must_use]
+pub fn estimate_peak_bytes(point_count: usize, max_connections: usize) -> u64 {
+    ResourceEstimate::new(point_count, max_connections).peak_bytes()
+}
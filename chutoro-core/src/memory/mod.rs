@@ -0,0 +1,60 @@
+//! Pre-flight memory estimation for the CPU clustering pipeline.
+//!
+//! Provides a conservative estimate of peak memory consumption so callers can
+//! reject oversized datasets before any allocation occurs.  The estimate is
+//! intentionally pessimistic — it uses a safety multiplier to account for heap
+//! fragmentation, Rayon thread-local buffers, and temporary allocations that
+//! are difficult to predict statically.
+
+mod estimate;
+
+pub use self::estimate::{ResourceEstimate, estimate_peak_bytes};
+
+// ---------------------------------------------------------------------------
+// Formatting
+// ---------------------------------------------------------------------------
+
+const KIB: u64 = 1024;
+const MIB: u64 = 1024 * KIB;
+const GIB: u64 = 1024 * MIB;
+const TIB: u64 = 1024 * GIB;
+
+/// Selects the appropriate binary unit and divisor for a byte count.
+fn binary_unit(bytes: u64) -> (&'static str, u64) {
+    if bytes >= TIB {
+        ("TiB", TIB)
+    } else if bytes >= GIB {
+        ("GiB", GIB)
+    } else if bytes >= MIB {
+        ("MiB", MIB)
+    } else {
+        ("KiB", KIB)
+    }
+}
+
+/// Formats a byte count as a human-readable string using binary units.
+///
+/// Returns values like `"0 B"`, `"1.0 KiB"`, `"2.4 GiB"`.  The result uses
+/// one decimal place for values ≥ 1 KiB.
+///
+/// # Examples
+///
+/// ```
+/// use chutoro_core::format_bytes;
+///
+/// assert_eq!(format_bytes(0), "0 B");
+/// assert_eq!(format_bytes(1023), "1023 B");
+/// assert_eq!(format_bytes(1024), "1.0 KiB");
+/// assert_eq!(format_bytes(1_073_741_824), "1.0 GiB");
+/// ```
+#[must_use]
+pub fn format_bytes(bytes: u64) -> String {
+    if bytes < KIB {
+        return format!("{bytes} B");
+    }
+    let (label, divisor) = binary_unit(bytes);
+    format!("{:.1} {label}", bytes as f64 / divisor as f64)
+}
+
+#[cfg(test)]
+mod tests;
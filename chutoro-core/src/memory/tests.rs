@@ -0,0 +1,167 @@
+//! Unit tests for memory accounting.
+
+use rstest::rstest;
+
+use super::*;
+
+// -- estimate_peak_bytes: happy paths -----------------------------------
+
+#[rstest]
+#[case::small_m16(100, 16)]
+#[case::medium_m8(1_000, 8)]
+#[case::large_m16(1_000_000, 16)]
+#[case::large_m24(1_000_000, 24)]
+fn estimate_returns_positive_for_non_empty(
+    #[case] point_count: usize,
+    #[case] max_connections: usize,
+) {
+    let bytes = estimate_peak_bytes(point_count, max_connections);
+    assert!(
+        bytes > 0,
+        "expected positive estimate for n={point_count}, M={max_connections}, got {bytes}"
+    );
+}
+
+#[rstest]
+#[case::m8_vs_m16(1_000, 8, 16)]
+#[case::m16_vs_m24(1_000, 16, 24)]
+fn estimate_grows_with_max_connections(
+    #[case] point_count: usize,
+    #[case] m_small: usize,
+    #[case] m_large: usize,
+) {
+    let small = estimate_peak_bytes(point_count, m_small);
+    let large = estimate_peak_bytes(point_count, m_large);
+    assert!(
+        large > small,
+        "expected M={m_large} estimate ({large}) > M={m_small} estimate ({small})"
+    );
+}
+
+#[rstest]
+#[case::hundred_vs_thousand(100, 1_000, 16)]
+#[case::thousand_vs_million(1_000, 1_000_000, 16)]
+fn estimate_grows_with_point_count(
+    #[case] n_small: usize,
+    #[case] n_large: usize,
+    #[case] max_connections: usize,
+) {
+    let small = estimate_peak_bytes(n_small, max_connections);
+    let large = estimate_peak_bytes(n_large, max_connections);
+    assert!(
+        large > small,
+        "expected n={n_large} estimate ({large}) > n={n_small} estimate ({small})"
+    );
+}
+
+// -- estimate_peak_bytes: edge cases ------------------------------------
+
+#[rstest]
+fn estimate_zero_points_returns_zero() {
+    assert_eq!(estimate_peak_bytes(0, 16), 0);
+}
+
+#[rstest]
+fn estimate_one_point_returns_positive_with_cache_base() {
+    let bytes = estimate_peak_bytes(1, 16);
+    assert!(bytes > 0, "single point should still have overhead");
+    // The estimate includes the full distance cache base cost (~120 MiB),
+    // so even a single point produces a sizeable estimate.
+    assert!(
+        bytes > 100_000_000,
+        "expected cache base cost to dominate for n=1"
+    );
+}
+
+#[rstest]
+fn estimate_m_one_returns_valid() {
+    let bytes = estimate_peak_bytes(1_000, 1);
+    assert!(bytes > 0, "M=1 should still produce a positive estimate");
+}
+
+// -- estimate_peak_bytes: overflow protection ---------------------------
+
+#[rstest]
+fn estimate_huge_point_count_does_not_panic() {
+    // Must not panic; saturating arithmetic should cap at u64::MAX.
+    let bytes = estimate_peak_bytes(usize::MAX, 24);
+    assert!(bytes > 0);
+}
+
+// -- ResourceEstimate ---------------------------------------------------
+
+#[rstest]
+fn peak_is_the_scaled_sum_of_stages() {
+    let estimate = ResourceEstimate::new(10_000, 16);
+    let stages = estimate.index_bytes()
+        + estimate.edge_harvest_bytes()
+        + estimate.mst_bytes()
+        + estimate.hierarchy_bytes();
+
+    assert_eq!(estimate.peak_bytes(), stages * 3 / 2);
+    assert_eq!(estimate_peak_bytes(10_000, 16), estimate.peak_bytes());
+}
+
+#[rstest]
+fn cache_capacity_only_changes_the_index_stage() {
+    let default = ResourceEstimate::new(10_000, 16);
+    let small = default.with_cache_entries(1024);
+
+    assert_eq!(
+        default.index_bytes() - small.index_bytes(),
+        (1_048_576 - 1024) * 80
+    );
+    assert_eq!(default.edge_harvest_bytes(), small.edge_harvest_bytes());
+    assert_eq!(default.hierarchy_bytes(), small.hierarchy_bytes());
+}
+
+#[rstest]
+#[case::unknown(None, None)]
+#[case::known(Some(32), Some(1_000 * 32 * 4))]
+fn source_bytes_follow_the_dimension(
+    #[case] dimension: Option<usize>,
+    #[case] expected: Option<u64>,
+) {
+    let estimate = ResourceEstimate::new(1_000, 16).with_dimension(dimension);
+
+    assert_eq!(estimate.source_bytes(), expected);
+    assert_eq!(
+        estimate.peak_bytes(),
+        estimate_peak_bytes(1_000, 16),
+        "source data is reported separately from the pipeline peak"
+    );
+}
+
+#[rstest]
+fn display_lists_every_stage() {
+    let text = ResourceEstimate::new(1_000, 16)
+        .with_dimension(Some(8))
+        .to_string();
+
+    for stage in [
+        "index",
+        "edge harvest",
+        "MST",
+        "hierarchy",
+        "peak",
+        "source",
+    ] {
+        assert!(text.contains(stage), "{text}");
+    }
+}
+
+// -- format_bytes -------------------------------------------------------
+
+#[rstest]
+#[case::zero(0, "0 B")]
+#[case::small(512, "512 B")]
+#[case::just_below_kib(1023, "1023 B")]
+#[case::one_kib(1024, "1.0 KiB")]
+#[case::one_and_half_kib(1536, "1.5 KiB")]
+#[case::one_mib(1_048_576, "1.0 MiB")]
+#[case::one_gib(1_073_741_824, "1.0 GiB")]
+#[case::one_tib(1_099_511_627_776, "1.0 TiB")]
+#[case::two_point_four_gib(2_576_980_378, "2.4 GiB")]
+fn format_bytes_produces_expected_output(#[case] input: u64, #[case] expected: &str) {
+    assert_eq!(format_bytes(input), expected);
+}
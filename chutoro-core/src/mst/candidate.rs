@@ -0,0 +1,116 @@
+//! Standalone MST input for graphs built outside chutoro.
+//!
+//! [`MstCandidate`] carries the endpoints, weight, and tie-break sequence of
+//! one undirected edge, so callers with their own graph builders can feed
+//! [`parallel_kruskal_from_iter`] without assembling an
+//! [`crate::EdgeHarvest`].
+
+use crate::CandidateEdge;
+
+use super::{MinimumSpanningForest, MstError, edge_list::prepare_candidate_list, kruskal_sorted};
+
+/// An undirected, weighted edge offered to [`parallel_kruskal_from_iter`].
+///
+/// Endpoints may be given in either order. The sequence breaks ties between
+/// edges of equal weight and endpoints: of several such duplicates, the one
+/// with the lowest sequence is kept.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::MstCandidate;
+///
+/// let candidate = MstCandidate::new(4, 2, 0.5, 9);
+/// assert_eq!((candidate.left(), candidate.right()), (4, 2));
+/// assert_eq!(candidate.weight(), 0.5);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MstCandidate {
+    left: usize,
+    right: usize,
+    weight: f64,
+    sequence: u64,
+}
+
+impl MstCandidate {
+    /// Creates a candidate edge.
+    #[must_use]
+    pub fn new(left: usize, right: usize, weight: f32, sequence: u64) -> Self {
+        Self::new_f64(left, right, f64::from(weight), sequence)
+    }
+
+    /// Creates a candidate edge with a double-precision weight.
+    #[must_use]
+    pub fn new_f64(left: usize, right: usize, weight: f64, sequence: u64) -> Self {
+        Self {
+            left,
+            right,
+            weight,
+            sequence,
+        }
+    }
+
+    /// Returns the first endpoint, as given.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn left(&self) -> usize { self.left }
+
+    /// Returns the second endpoint, as given.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn right(&self) -> usize { self.right }
+
+    /// Returns the edge weight at the precision it was recorded with.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn weight(&self) -> f64 { self.weight }
+
+    /// Returns the deterministic tie-break sequence.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn sequence(&self) -> u64 { self.sequence }
+}
+
+impl From<CandidateEdge> for MstCandidate {
+    fn from(edge: CandidateEdge) -> Self {
+        Self::new_f64(
+            edge.source(),
+            edge.target(),
+            edge.distance_f64(),
+            edge.sequence(),
+        )
+    }
+}
+
+/// Computes a minimum spanning forest over candidates from any iterator.
+///
+/// Behaves exactly like [`super::parallel_kruskal`], including its
+/// diagnostics, for callers whose edges do not come from an HNSW harvest.
+/// The candidates are read once into an owned list, which is then sorted in
+/// parallel.
+///
+/// # Errors
+///
+/// Returns an error when:
+/// - `node_count == 0`
+/// - a candidate references a node id `>= node_count`
+/// - a candidate weight is non-finite
+///
+/// # Examples
+/// ```
+/// use chutoro_core::{MstCandidate, parallel_kruskal_from_iter};
+///
+/// let ring = (0..4).map(|node| MstCandidate::new(node, (node + 1) % 4, 1.0 + node as f32, 0));
+/// let forest = parallel_kruskal_from_iter(4, ring).expect("valid graph");
+/// assert!(forest.is_tree());
+/// assert_eq!(forest.edges().len(), 3);
+/// ```
+pub fn parallel_kruskal_from_iter(
+    node_count: usize,
+    candidates: impl IntoIterator<Item = MstCandidate>,
+) -> Result<MinimumSpanningForest, MstError> {
+    if node_count == 0 {
+        return Err(MstError::EmptyGraph);
+    }
+    let (edge_list, dropped) = prepare_candidate_list(candidates, node_count)?;
+    kruskal_sorted(node_count, &edge_list, dropped)
+}
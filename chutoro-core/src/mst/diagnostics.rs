@@ -0,0 +1,137 @@
+//! What happened to the input edges while a forest was built.
+//!
+//! Self-loops and repeated edges are dropped silently by the edge-list
+//! preparation, and disconnected inputs yield forests rather than trees. A
+//! clustering that looks wrong is often explained by one of these, so the
+//! counts are kept alongside the forest instead of being discarded.
+
+use super::{MstEdge, union_find::ConcurrentUnionFind};
+
+/// Input edges removed before Kruskal's scan.
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct DroppedEdges {
+    pub(super) self_edges: usize,
+    pub(super) duplicates: usize,
+}
+
+/// Diagnostics gathered while computing a [`super::MinimumSpanningForest`].
+///
+/// # Examples
+/// ```
+/// use chutoro_core::{CandidateEdge, EdgeHarvest, parallel_kruskal};
+///
+/// let harvest = EdgeHarvest::new(vec![
+///     CandidateEdge::new(0, 1, 1.0, 0),
+///     CandidateEdge::new(1, 0, 1.0, 1),
+///     CandidateEdge::new(2, 2, 0.0, 2),
+///     CandidateEdge::new(2, 3, 4.0, 3),
+/// ]);
+/// let forest = parallel_kruskal(4, &harvest).expect("valid graph");
+/// let diagnostics = forest.diagnostics();
+///
+/// assert_eq!(diagnostics.self_edges_dropped(), 1);
+/// assert_eq!(diagnostics.duplicate_edges_merged(), 1);
+/// assert_eq!(diagnostics.weight_range(), Some((1.0, 4.0)));
+/// assert_eq!(diagnostics.component_edge_counts(), &[1, 1]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MstDiagnostics {
+    self_edges_dropped: usize,
+    duplicate_edges_merged: usize,
+    weight_range: Option<(f32, f32)>,
+    component_edge_counts: Vec<usize>,
+}
+
+/// Running counts of the candidate edges Kruskal scans.
+pub(super) struct EdgeTally {
+    dropped: DroppedEdges,
+    /// Candidate edges counted against their source node.
+    per_node: Vec<usize>,
+    weight_range: Option<(f32, f32)>,
+}
+
+impl EdgeTally {
+    pub(super) fn new(node_count: usize, dropped: DroppedEdges) -> Self {
+        Self {
+            dropped,
+            per_node: vec![0; node_count],
+            weight_range: None,
+        }
+    }
+
+    /// Counts `edge`, which must not be lighter than any edge counted before.
+    pub(super) fn record(&mut self, edge: &MstEdge) {
+        self.per_node[edge.source] += 1;
+        let weight = edge.weight();
+        let low = self.weight_range.map_or(weight, |(low, _)| low);
+        self.weight_range = Some((low, weight));
+    }
+
+    pub(super) fn record_duplicate(&mut self) {
+        self.dropped.duplicates += 1;
+    }
+
+    /// Totals the counts for each component of the forest in `union_find`.
+    pub(super) fn finish(self, union_find: &ConcurrentUnionFind) -> MstDiagnostics {
+        let mut slots = vec![None; self.per_node.len()];
+        let mut component_edge_counts = Vec::new();
+        for (node, &count) in self.per_node.iter().enumerate() {
+            let slot = slots[union_find.find(node)].get_or_insert_with(|| {
+                component_edge_counts.push(0);
+                component_edge_counts.len() - 1
+            });
+            component_edge_counts[*slot] += count;
+        }
+        MstDiagnostics {
+            self_edges_dropped: self.dropped.self_edges,
+            duplicate_edges_merged: self.dropped.duplicates,
+            weight_range: self.weight_range,
+            component_edge_counts,
+        }
+    }
+}
+
+impl MstDiagnostics {
+    /// Reassembles diagnostics recorded alongside a stored forest.
+    #[cfg(feature = "parquet")]
+    pub(crate) fn from_parts(
+        self_edges_dropped: usize,
+        duplicate_edges_merged: usize,
+        weight_range: Option<(f32, f32)>,
+        component_edge_counts: Vec<usize>,
+    ) -> Self {
+        Self {
+            self_edges_dropped,
+            duplicate_edges_merged,
+            weight_range,
+            component_edge_counts,
+        }
+    }
+
+    /// Returns the number of self-loops dropped from the input.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn self_edges_dropped(&self) -> usize { self.self_edges_dropped }
+
+    /// Returns the number of input edges dropped because an edge with the
+    /// same endpoints and weight had already been seen, in either direction.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn duplicate_edges_merged(&self) -> usize { self.duplicate_edges_merged }
+
+    /// Returns the smallest and largest weight among the candidate edges
+    /// that survived deduplication, or `None` when there were none.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn weight_range(&self) -> Option<(f32, f32)> { self.weight_range }
+
+    /// Returns how many candidate edges fell inside each connected component
+    /// of the forest, ordered by the smallest node of each component.
+    ///
+    /// Isolated nodes form components with no edges, so the slice always has
+    /// one entry per component.
+    #[must_use]
+    pub fn component_edge_counts(&self) -> &[usize] {
+        &self.component_edge_counts
+    }
+}
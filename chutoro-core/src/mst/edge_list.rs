@@ -0,0 +1,140 @@
+//! Conversion of candidate edges into the sorted MST edge list.
+//!
+//! Edges are validated, canonicalized to `source <= target`, stripped of
+//! self-loops, sorted by the `MstEdge` ordering, and deduplicated so Kruskal
+//! can scan them once.
+
+use rayon::prelude::*;
+
+use crate::{CandidateEdge, EdgeHarvest};
+
+use super::{MstCandidate, MstEdge, MstError, diagnostics::DroppedEdges};
+
+pub(super) fn validate_and_canonicalize_edge(
+    edge: &CandidateEdge,
+    node_count: usize,
+) -> Result<Option<MstEdge>, MstError> {
+    validate_candidate(MstCandidate::from(*edge), node_count)
+}
+
+fn validate_candidate(
+    candidate: MstCandidate,
+    node_count: usize,
+) -> Result<Option<MstEdge>, MstError> {
+    let source = candidate.left();
+    let target = candidate.right();
+
+    if source >= node_count {
+        return Err(MstError::InvalidNodeId {
+            node: source,
+            node_count,
+        });
+    }
+    if target >= node_count {
+        return Err(MstError::InvalidNodeId {
+            node: target,
+            node_count,
+        });
+    }
+
+    let weight = candidate.weight();
+    if !weight.is_finite() {
+        return Err(MstError::NonFiniteWeight {
+            left: source,
+            right: target,
+        });
+    }
+
+    if source == target {
+        return Ok(None);
+    }
+
+    Ok(Some(MstEdge::new_f64(
+        source,
+        target,
+        weight,
+        candidate.sequence(),
+    )))
+}
+
+/// Checks that every harvested edge is acceptable MST input without building
+/// the edge list, so callers can index per-node data by its endpoints first.
+pub(crate) fn validate_harvest(node_count: usize, harvest: &EdgeHarvest) -> Result<(), MstError> {
+    if node_count == 0 {
+        return Err(MstError::EmptyGraph);
+    }
+    harvest
+        .iter()
+        .try_for_each(|edge| validate_and_canonicalize_edge(edge, node_count).map(drop))
+}
+
+pub(super) fn prepare_edge_list<'a>(
+    edges: impl IntoIterator<Item = &'a CandidateEdge>,
+    node_count: usize,
+) -> Result<(Vec<MstEdge>, DroppedEdges), MstError> {
+    let edges: Vec<&CandidateEdge> = edges.into_iter().collect();
+    let input = edges.len();
+    let mut edge_list = edges
+        .par_iter()
+        .try_fold(Vec::new, |mut acc, edge| {
+            if let Some(mst_edge) = validate_and_canonicalize_edge(edge, node_count)? {
+                acc.push(mst_edge);
+            }
+            Ok(acc)
+        })
+        .try_reduce(Vec::new, |mut left, right| {
+            left.extend(right);
+            Ok(left)
+        })?;
+
+    let dropped = sort_and_dedup(&mut edge_list, input);
+    Ok((edge_list, dropped))
+}
+
+/// Converts owned edges into MST edges, reusing the harvest's allocation.
+///
+/// `CandidateEdge` and `MstEdge` share a layout, so the standard library's
+/// in-place collection rewrites the buffer instead of allocating a second one.
+pub(super) fn prepare_owned_edge_list(
+    edges: Vec<CandidateEdge>,
+    node_count: usize,
+) -> Result<(Vec<MstEdge>, DroppedEdges), MstError> {
+    let input = edges.len();
+    let mut edge_list = edges
+        .into_iter()
+        .filter_map(|edge| validate_and_canonicalize_edge(&edge, node_count).transpose())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let dropped = sort_and_dedup(&mut edge_list, input);
+    Ok((edge_list, dropped))
+}
+
+/// Converts candidates from an arbitrary iterator into MST edges.
+pub(super) fn prepare_candidate_list(
+    candidates: impl IntoIterator<Item = MstCandidate>,
+    node_count: usize,
+) -> Result<(Vec<MstEdge>, DroppedEdges), MstError> {
+    let mut input = 0_usize;
+    let mut edge_list = candidates
+        .into_iter()
+        .inspect(|_| input += 1)
+        .filter_map(|candidate| validate_candidate(candidate, node_count).transpose())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let dropped = sort_and_dedup(&mut edge_list, input);
+    Ok((edge_list, dropped))
+}
+
+/// Sorts and deduplicates the surviving edges of `input` candidates,
+/// reporting how many were dropped at each step.
+fn sort_and_dedup(edge_list: &mut Vec<MstEdge>, input: usize) -> DroppedEdges {
+    let canonical = edge_list.len();
+    edge_list.par_sort_unstable();
+    edge_list.dedup_by(|left, right| {
+        left.weight == right.weight && left.source == right.source && left.target == right.target
+    });
+    DroppedEdges {
+        self_edges: input - canonical,
+        duplicates: canonical - edge_list.len(),
+    }
+}
@@ -0,0 +1,56 @@
+//! The spanning forest returned by MST construction.
+
+use super::{MstDiagnostics, MstEdge};
+
+/// The output of a minimum spanning forest computation.
+///
+/// When the input graph is connected, the forest is a minimum spanning tree.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MinimumSpanningForest {
+    edges: Vec<MstEdge>,
+    component_count: usize,
+    diagnostics: MstDiagnostics,
+}
+
+impl MinimumSpanningForest {
+    /// Returns the MST/forest edges.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn edges(&self) -> &[MstEdge] { &self.edges }
+
+    /// Returns the number of connected components in the resulting forest.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn component_count(&self) -> usize { self.component_count }
+
+    /// Returns `true` when the forest spans a single connected component.
+    #[must_use]
+    pub fn is_tree(&self) -> bool {
+        self.component_count == 1
+    }
+
+    /// Returns the self-loops, duplicates, weight range, and per-component
+    /// edge counts observed while building the forest.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn diagnostics(&self) -> &MstDiagnostics { &self.diagnostics }
+
+    /// Assembles a forest from its sorted edges and metadata, as built by
+    /// Kruskal or read back from storage.
+    pub(crate) fn from_parts(
+        edges: Vec<MstEdge>,
+        component_count: usize,
+        diagnostics: MstDiagnostics,
+    ) -> Self {
+        Self {
+            edges,
+            component_count,
+            diagnostics,
+        }
+    }
+
+    /// Consumes the forest and returns its edges.
+    pub(crate) fn into_edges(self) -> Vec<MstEdge> {
+        self.edges
+    }
+}
@@ -2,17 +2,38 @@
 //!
 //! This module provides a parallel Kruskal implementation intended for CPU
 //! backends. The algorithm parallelizes the global edge sort via Rayon and
-//! performs concurrent cycle checks using a striped-lock union-find.
-
+//! performs concurrent cycle checks using a striped-lock union-find. Large
+//! groups of equal-weight edges are resolved in parallel without changing
+//! which edges the sequential scan would accept.
+
+mod candidate;
+mod diagnostics;
+mod edge_list;
+mod forest;
+mod stream;
 mod union_find;
+mod weight_group;
 
 use std::cmp::Ordering;
 
-use rayon::prelude::*;
-
 use crate::{CandidateEdge, EdgeHarvest};
 
-use self::union_find::ConcurrentUnionFind;
+pub use self::{
+    candidate::{MstCandidate, parallel_kruskal_from_iter},
+    diagnostics::MstDiagnostics,
+    forest::MinimumSpanningForest,
+};
+pub(crate) use self::{
+    edge_list::validate_harvest,
+    stream::{canonical_mst_edge, kruskal_sorted_stream},
+};
+
+use self::{
+    diagnostics::{DroppedEdges, EdgeTally},
+    edge_list::{prepare_edge_list, prepare_owned_edge_list},
+    union_find::ConcurrentUnionFind,
+    weight_group::process_weight_group,
+};
 
 /// Errors returned while computing a minimum spanning tree/forest.
 #[derive(Clone, Debug, thiserror::Error, PartialEq)]
@@ -71,6 +92,11 @@ impl MstError {
 
 /// Machine-readable error codes for [`MstError`].
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "SCREAMING_SNAKE_CASE")
+)]
 pub enum MstErrorCode {
     /// The caller requested an MST for an empty graph.
     EmptyGraph,
@@ -99,15 +125,56 @@ impl MstErrorCode {
 }
 
 /// A single MST edge in canonical undirected form (`source <= target`).
+///
+/// Weights are held at `f64` precision so edges built from
+/// [`crate::CandidateEdge::distance_f64`] are ordered without rounding.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "RawMstEdge")
+)]
 pub struct MstEdge {
     source: usize,
     target: usize,
-    weight: f32,
+    weight: f64,
     sequence: u64,
 }
 
 impl MstEdge {
+    /// Creates an edge, canonicalizing the endpoints so `source <= target`.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::MstEdge;
+    ///
+    /// let edge = MstEdge::new(3, 1, 0.5, 7);
+    /// assert_eq!((edge.source(), edge.target()), (1, 3));
+    /// ```
+    #[must_use]
+    pub fn new(left: usize, right: usize, weight: f32, sequence: u64) -> Self {
+        Self::new_f64(left, right, f64::from(weight), sequence)
+    }
+
+    /// Creates an edge with a double-precision weight.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::MstEdge;
+    ///
+    /// let edge = MstEdge::new_f64(0, 1, 1.0 + 1e-12, 0);
+    /// assert!(edge.weight_f64() > 1.0);
+    /// ```
+    #[must_use]
+    pub fn new_f64(left: usize, right: usize, weight: f64, sequence: u64) -> Self {
+        Self {
+            source: left.min(right),
+            target: left.max(right),
+            weight,
+            sequence,
+        }
+    }
+
     /// Returns the smaller endpoint id.
     #[must_use]
     #[rustfmt::skip]
@@ -121,7 +188,12 @@ impl MstEdge {
     /// Returns the edge weight.
     #[must_use]
     #[rustfmt::skip]
-    pub fn weight(&self) -> f32 { self.weight }
+    pub fn weight(&self) -> f32 { self.weight as f32 }
+
+    /// Returns the edge weight at the `f64` precision it was recorded with.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn weight_f64(&self) -> f64 { self.weight }
 
     /// Returns the deterministic tie-break sequence associated with the edge.
     #[must_use]
@@ -129,6 +201,23 @@ impl MstEdge {
     pub fn sequence(&self) -> u64 { self.sequence }
 }
 
+/// Serialized form of [`MstEdge`], canonicalized on the way back in.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawMstEdge {
+    source: usize,
+    target: usize,
+    weight: f64,
+    sequence: u64,
+}
+
+#[cfg(feature = "serde")]
+impl From<RawMstEdge> for MstEdge {
+    fn from(raw: RawMstEdge) -> Self {
+        Self::new_f64(raw.source, raw.target, raw.weight, raw.sequence)
+    }
+}
+
 impl Eq for MstEdge {}
 
 impl Ord for MstEdge {
@@ -147,33 +236,6 @@ impl PartialOrd for MstEdge {
     }
 }
 
-/// The output of a minimum spanning forest computation.
-///
-/// When the input graph is connected, the forest is a minimum spanning tree.
-#[derive(Clone, Debug, PartialEq)]
-pub struct MinimumSpanningForest {
-    edges: Vec<MstEdge>,
-    component_count: usize,
-}
-
-impl MinimumSpanningForest {
-    /// Returns the MST/forest edges.
-    #[must_use]
-    #[rustfmt::skip]
-    pub fn edges(&self) -> &[MstEdge] { &self.edges }
-
-    /// Returns the number of connected components in the resulting forest.
-    #[must_use]
-    #[rustfmt::skip]
-    pub fn component_count(&self) -> usize { self.component_count }
-
-    /// Returns `true` when the forest spans a single connected component.
-    #[must_use]
-    pub fn is_tree(&self) -> bool {
-        self.component_count == 1
-    }
-}
-
 /// Computes a minimum spanning forest using parallel Kruskal's algorithm.
 ///
 /// The input edges are interpreted as undirected and are canonicalized to
@@ -192,66 +254,39 @@ pub fn parallel_kruskal(
     parallel_kruskal_from_edges(node_count, edges.iter())
 }
 
-fn validate_and_canonicalize_edge(
-    edge: &CandidateEdge,
+/// Computes a minimum spanning forest, consuming the harvest.
+///
+/// Behaves exactly like [`parallel_kruskal`], but converts and sorts the
+/// edges inside the harvest's own buffer. Prefer it when the harvest is no
+/// longer needed: it avoids holding a second copy of the largest allocation
+/// in the pipeline.
+///
+/// # Errors
+///
+/// Returns the same errors as [`parallel_kruskal`].
+///
+/// # Examples
+/// ```
+/// use chutoro_core::{CandidateEdge, EdgeHarvest, parallel_kruskal_owned};
+///
+/// let harvest = EdgeHarvest::new(vec![
+///     CandidateEdge::new(0, 1, 1.0, 0),
+///     CandidateEdge::new(1, 2, 2.0, 1),
+///     CandidateEdge::new(0, 2, 3.0, 2),
+/// ]);
+/// let forest = parallel_kruskal_owned(3, harvest).expect("valid graph");
+/// assert!(forest.is_tree());
+/// assert_eq!(forest.edges().len(), 2);
+/// ```
+pub fn parallel_kruskal_owned(
     node_count: usize,
-) -> Result<Option<MstEdge>, MstError> {
-    let source = edge.source();
-    let target = edge.target();
-
-    if source >= node_count {
-        return Err(MstError::InvalidNodeId {
-            node: source,
-            node_count,
-        });
-    }
-    if target >= node_count {
-        return Err(MstError::InvalidNodeId {
-            node: target,
-            node_count,
-        });
-    }
-
-    let weight = edge.distance();
-    if !weight.is_finite() {
-        return Err(MstError::NonFiniteWeight {
-            left: source,
-            right: target,
-        });
-    }
-
-    if source == target {
-        return Ok(None);
-    }
-
-    let (source, target) = if source <= target {
-        (source, target)
-    } else {
-        (target, source)
-    };
-
-    Ok(Some(MstEdge {
-        source,
-        target,
-        weight,
-        sequence: edge.sequence(),
-    }))
-}
-
-fn process_weight_group(
-    group: &[MstEdge],
-    union_find: &ConcurrentUnionFind,
-) -> Result<Vec<MstEdge>, MstError> {
-    // Process edges sequentially to ensure deterministic MST selection.
-    // Since edges are already sorted by (weight, source, target, sequence),
-    // sequential iteration produces reproducible results.
-    let mut accepted = Vec::new();
-    for edge in group {
-        if union_find.try_union(edge.source, edge.target)? {
-            accepted.push(*edge);
-        }
+    edges: EdgeHarvest,
+) -> Result<MinimumSpanningForest, MstError> {
+    if node_count == 0 {
+        return Err(MstError::EmptyGraph);
     }
-    Ok(accepted)
+    let (edge_list, dropped) = prepare_owned_edge_list(edges.into_inner(), node_count)?;
+    kruskal_sorted(node_count, &edge_list, dropped)
 }
 
 fn is_mst_complete(
@@ -262,31 +297,6 @@ fn is_mst_complete(
     union_find.components() == 1 && forest_edges.len() == node_count.saturating_sub(1)
 }
 
-fn prepare_edge_list<'a>(
-    edges: impl IntoIterator<Item = &'a CandidateEdge>,
-    node_count: usize,
-) -> Result<Vec<MstEdge>, MstError> {
-    let edges: Vec<&CandidateEdge> = edges.into_iter().collect();
-    let mut edge_list = edges
-        .par_iter()
-        .try_fold(Vec::new, |mut acc, edge| {
-            if let Some(mst_edge) = validate_and_canonicalize_edge(edge, node_count)? {
-                acc.push(mst_edge);
-            }
-            Ok(acc)
-        })
-        .try_reduce(Vec::new, |mut left, right| {
-            left.extend(right);
-            Ok(left)
-        })?;
-
-    edge_list.par_sort_unstable();
-    edge_list.dedup_by(|left, right| {
-        left.weight == right.weight && left.source == right.source && left.target == right.target
-    });
-    Ok(edge_list)
-}
-
 pub(crate) fn parallel_kruskal_from_edges<'a>(
     node_count: usize,
     edges: impl IntoIterator<Item = &'a CandidateEdge>,
@@ -294,16 +304,25 @@ pub(crate) fn parallel_kruskal_from_edges<'a>(
     if node_count == 0 {
         return Err(MstError::EmptyGraph);
     }
+    let (edge_list, dropped) = prepare_edge_list(edges, node_count)?;
+    kruskal_sorted(node_count, &edge_list, dropped)
+}
 
-    let edge_list = prepare_edge_list(edges, node_count)?;
-
-    if edge_list.is_empty() {
-        return Ok(MinimumSpanningForest {
-            edges: Vec::new(),
-            component_count: node_count,
-        });
+/// Counts every candidate edge, including those after the forest completed.
+fn tally_edges(node_count: usize, dropped: DroppedEdges, edge_list: &[MstEdge]) -> EdgeTally {
+    let mut tally = EdgeTally::new(node_count, dropped);
+    for edge in edge_list {
+        tally.record(edge);
     }
+    tally
+}
 
+/// Runs Kruskal over edges already validated, sorted, and deduplicated.
+fn kruskal_sorted(
+    node_count: usize,
+    edge_list: &[MstEdge],
+    dropped: DroppedEdges,
+) -> Result<MinimumSpanningForest, MstError> {
     let union_find = ConcurrentUnionFind::new(node_count);
     let mut forest_edges = Vec::with_capacity(node_count.saturating_sub(1));
 
@@ -328,10 +347,11 @@ pub(crate) fn parallel_kruskal_from_edges<'a>(
     }
 
     forest_edges.sort_unstable();
-    Ok(MinimumSpanningForest {
-        edges: forest_edges,
-        component_count: union_find.components(),
-    })
+    Ok(MinimumSpanningForest::from_parts(
+        forest_edges,
+        union_find.components(),
+        tally_edges(node_count, dropped, edge_list).finish(&union_find),
+    ))
 }
 
 #[cfg(kani)]
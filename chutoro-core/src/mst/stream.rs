@@ -0,0 +1,81 @@
+//! Kruskal over an edge stream that is already sorted.
+//!
+//! External sorting hands Kruskal its edges one at a time instead of as a
+//! slice. Only the current equal-weight group is buffered, and each group is
+//! resolved by [`process_weight_group`], so the forest matches the one the
+//! in-memory path builds from the same edges.
+
+use crate::CandidateEdge;
+
+use super::{
+    MinimumSpanningForest, MstEdge, MstError,
+    diagnostics::{DroppedEdges, EdgeTally},
+    edge_list::validate_and_canonicalize_edge,
+    is_mst_complete,
+    union_find::ConcurrentUnionFind,
+    weight_group::process_weight_group,
+};
+
+/// Validates `edge` against `node_count` and converts it to an [`MstEdge`],
+/// returning `None` for self-loops.
+pub(crate) fn canonical_mst_edge(
+    edge: &CandidateEdge,
+    node_count: usize,
+) -> Result<Option<MstEdge>, MstError> {
+    validate_and_canonicalize_edge(edge, node_count)
+}
+
+/// Builds the minimum spanning forest from edges yielded in [`MstEdge`]
+/// order, as produced by [`canonical_mst_edge`].
+///
+/// Duplicate edges are skipped as the in-memory path does: of several edges
+/// with the same endpoints and weight, the one with the lowest sequence is
+/// kept. Errors from the stream are returned as they are; union-find failures
+/// are converted with `map_error`.
+///
+/// Self-loops never reach the stream, and edges after the forest completes
+/// are not read, so the diagnostics only describe the edges consumed.
+pub(crate) fn kruskal_sorted_stream<E>(
+    node_count: usize,
+    edges: impl IntoIterator<Item = Result<MstEdge, E>>,
+    map_error: impl Fn(MstError) -> E,
+) -> Result<MinimumSpanningForest, E> {
+    if node_count == 0 {
+        return Err(map_error(MstError::EmptyGraph));
+    }
+    let union_find = ConcurrentUnionFind::new(node_count);
+    let mut forest_edges = Vec::with_capacity(node_count.saturating_sub(1));
+    let mut group: Vec<MstEdge> = Vec::new();
+    let mut tally = EdgeTally::new(node_count, DroppedEdges::default());
+
+    for edge in edges {
+        let edge = edge?;
+        match group
+            .last()
+            .map(|last| (last.weight, last.source, last.target))
+        {
+            Some((weight, _, _)) if weight != edge.weight => {
+                forest_edges.extend(process_weight_group(&group, &union_find).map_err(&map_error)?);
+                group.clear();
+                if is_mst_complete(node_count, &union_find, &forest_edges) {
+                    break;
+                }
+            }
+            Some((_, source, target)) if (source, target) == (edge.source, edge.target) => {
+                tally.record_duplicate();
+                continue;
+            }
+            _ => {}
+        }
+        tally.record(&edge);
+        group.push(edge);
+    }
+    forest_edges.extend(process_weight_group(&group, &union_find).map_err(&map_error)?);
+
+    forest_edges.sort_unstable();
+    Ok(MinimumSpanningForest::from_parts(
+        forest_edges,
+        union_find.components(),
+        tally.finish(&union_find),
+    ))
+}
@@ -0,0 +1,67 @@
+//! Tests for MST computation over user-supplied candidate iterators.
+
+use super::*;
+use crate::mst::{MstCandidate, parallel_kruskal_from_iter};
+
+fn candidates(edges: &[(usize, usize, f32, u64)]) -> impl Iterator<Item = MstCandidate> + '_ {
+    edges
+        .iter()
+        .map(|&(left, right, weight, sequence)| MstCandidate::new(left, right, weight, sequence))
+}
+
+#[rstest]
+#[case::connected(4, &[(0, 1, 1.0, 0), (1, 2, 2.0, 1), (2, 3, 3.0, 2), (0, 2, 6.0, 3)])]
+#[case::duplicates(4, &[(1, 0, 1.0, 2), (0, 1, 1.0, 1), (2, 0, 1.0, 9), (0, 2, 1.0, 3), (3, 3, 0.5, 4)])]
+#[case::disconnected(5, &[(0, 1, 1.0, 0), (3, 4, 2.0, 1)])]
+fn matches_harvest_based_kruskal(
+    #[case] node_count: usize,
+    #[case] edges: &[(usize, usize, f32, u64)],
+) {
+    let expected = parallel_kruskal(node_count, &harvest(edges)).expect("MST must succeed");
+    let forest =
+        parallel_kruskal_from_iter(node_count, candidates(edges)).expect("MST must succeed");
+
+    assert_eq!(forest, expected);
+}
+
+#[rstest]
+fn duplicates_keep_the_lowest_sequence() {
+    let forest = parallel_kruskal_from_iter(2, candidates(&[(1, 0, 1.0, 7), (0, 1, 1.0, 3)]))
+        .expect("MST must succeed");
+
+    assert_eq!(forest.edges(), &[MstEdge::new(0, 1, 1.0, 3)]);
+    assert_eq!(forest.diagnostics().duplicate_edges_merged(), 1);
+}
+
+#[rstest]
+fn converted_harvest_edges_round_trip() {
+    let edge = CandidateEdge::new(3, 1, 0.25, 5);
+    let candidate = MstCandidate::from(edge);
+
+    assert_eq!(
+        (candidate.left(), candidate.right(), candidate.sequence()),
+        (3, 1, 5)
+    );
+    assert_eq!(candidate.weight(), edge.distance_f64());
+}
+
+#[rstest]
+#[case::empty(0, vec![], MstError::EmptyGraph)]
+#[case::invalid_node(
+    2,
+    vec![MstCandidate::new(0, 2, 1.0, 0)],
+    MstError::InvalidNodeId { node: 2, node_count: 2 },
+)]
+#[case::non_finite(
+    2,
+    vec![MstCandidate::new_f64(1, 0, f64::NAN, 0)],
+    MstError::NonFiniteWeight { left: 1, right: 0 },
+)]
+fn rejects_invalid_input(
+    #[case] node_count: usize,
+    #[case] input: Vec<MstCandidate>,
+    #[case] expected: MstError,
+) {
+    let err = parallel_kruskal_from_iter(node_count, input).expect_err("input must be rejected");
+    assert_eq!(err, expected);
+}
@@ -0,0 +1,80 @@
+//! Tests for the diagnostics recorded alongside each forest.
+
+use std::convert::Infallible;
+
+use super::*;
+use crate::mst::kruskal_sorted_stream;
+
+#[rstest]
+fn counts_self_loops_and_duplicates() {
+    let edges = harvest(&[
+        (0, 1, 1.0, 0),
+        (1, 0, 1.0, 1),
+        (0, 1, 1.0, 2),
+        (1, 1, 0.0, 3),
+        (2, 2, 0.5, 4),
+        (1, 2, 2.0, 5),
+    ]);
+
+    let forest = parallel_kruskal(3, &edges).expect("MST must succeed");
+    let diagnostics = forest.diagnostics();
+
+    assert_eq!(diagnostics.self_edges_dropped(), 2);
+    assert_eq!(diagnostics.duplicate_edges_merged(), 2);
+    assert_eq!(diagnostics.weight_range(), Some((1.0, 2.0)));
+    assert_eq!(diagnostics.component_edge_counts(), &[2]);
+}
+
+#[rstest]
+fn parallel_edges_with_distinct_weights_are_kept() {
+    let edges = harvest(&[(0, 1, 1.0, 0), (1, 0, 3.0, 1)]);
+
+    let forest = parallel_kruskal_owned(2, edges).expect("MST must succeed");
+
+    assert_eq!(forest.diagnostics().duplicate_edges_merged(), 0);
+    assert_eq!(forest.diagnostics().weight_range(), Some((1.0, 3.0)));
+    assert_eq!(forest.diagnostics().component_edge_counts(), &[2]);
+}
+
+#[rstest]
+fn components_are_ordered_by_smallest_node() {
+    let edges = harvest(&[
+        (3, 4, 1.0, 0),
+        (1, 3, 2.0, 1),
+        (0, 2, 5.0, 2),
+        (1, 4, 6.0, 3),
+    ]);
+
+    let forest = parallel_kruskal(6, &edges).expect("MST must succeed");
+
+    assert_eq!(forest.component_count(), 3);
+    assert_eq!(forest.diagnostics().component_edge_counts(), &[1, 3, 0]);
+}
+
+#[rstest]
+fn edgeless_graphs_report_isolated_nodes() {
+    let forest = parallel_kruskal(3, &EdgeHarvest::default()).expect("MST must succeed");
+
+    let diagnostics = forest.diagnostics();
+    assert_eq!(diagnostics.weight_range(), None);
+    assert_eq!(diagnostics.component_edge_counts(), &[0, 0, 0]);
+}
+
+#[rstest]
+fn streamed_forests_count_duplicates() {
+    let edges = [
+        MstEdge::new(0, 1, 1.0, 0),
+        MstEdge::new(0, 1, 1.0, 1),
+        MstEdge::new(2, 3, 2.0, 2),
+    ];
+
+    let forest = kruskal_sorted_stream(4, edges.map(Ok::<_, Infallible>), |error| {
+        panic!("stream must not fail: {error}")
+    })
+    .expect("MST must succeed");
+
+    let diagnostics = forest.diagnostics();
+    assert_eq!(diagnostics.duplicate_edges_merged(), 1);
+    assert_eq!(diagnostics.weight_range(), Some((1.0, 2.0)));
+    assert_eq!(diagnostics.component_edge_counts(), &[1, 1]);
+}
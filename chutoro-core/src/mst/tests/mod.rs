@@ -4,7 +4,7 @@ use rstest::rstest;
 
 use crate::{CandidateEdge, EdgeHarvest};
 
-use super::{MstEdge, MstError, parallel_kruskal};
+use super::{MstEdge, MstError, parallel_kruskal, parallel_kruskal_owned};
 
 fn harvest(edges: &[(usize, usize, f32, u64)]) -> EdgeHarvest {
     EdgeHarvest::new(
@@ -92,6 +92,46 @@ fn rejects_invalid_inputs(
         result.expect_err("input should be rejected"),
         expected_error
     );
+    let owned = parallel_kruskal_owned(node_count, edge_harvest);
+    assert_eq!(
+        owned.expect_err("owned input should be rejected"),
+        expected_error
+    );
+}
+
+#[rstest]
+fn owned_kruskal_matches_borrowed_kruskal() {
+    let node_count = 40;
+    // Sparse pseudo-random graph with many tied weights and mirrored pairs.
+    let pairs = (0..node_count).flat_map(|left| (0..node_count).map(move |right| (left, right)));
+    let edges: Vec<(usize, usize, f32, u64)> = pairs
+        .filter(|&(left, right)| left != right && (left * 7 + right) % 5 == 0)
+        .zip(0_u64..)
+        .map(|((left, right), sequence)| {
+            let weight = ((left * 31 + right * 17) % 11) as f32;
+            (left, right, weight, sequence)
+        })
+        .collect();
+    let edge_harvest = harvest(&edges);
+
+    let borrowed = parallel_kruskal(node_count, &edge_harvest).expect("borrowed MST");
+    let owned = parallel_kruskal_owned(node_count, edge_harvest).expect("owned MST");
+
+    assert_eq!(owned, borrowed);
+}
+
+#[test]
+fn mst_edges_share_the_candidate_edge_layout() {
+    // The owned path relies on in-place collection, which the standard
+    // library only performs when both element types share size and alignment.
+    assert_eq!(
+        std::mem::size_of::<CandidateEdge>(),
+        std::mem::size_of::<MstEdge>()
+    );
+    assert_eq!(
+        std::mem::align_of::<CandidateEdge>(),
+        std::mem::align_of::<MstEdge>()
+    );
 }
 
 #[test]
@@ -150,4 +190,6 @@ fn undirected_edges_are_canonicalized_and_deduplicated() {
     assert_eq!(edge.sequence(), 10);
 }
 
+mod candidates;
+mod diagnostics;
 mod forests;
@@ -113,7 +113,7 @@ impl ConcurrentUnionFind {
         self.parents[node].load(Ordering::Acquire) == node
     }
 
-    fn find(&self, node: usize) -> usize {
+    pub(super) fn find(&self, node: usize) -> usize {
         let mut current = node;
         loop {
             let parent = self.parents[current].load(Ordering::Acquire);
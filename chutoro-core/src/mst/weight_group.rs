@@ -0,0 +1,280 @@
+//! Deterministic acceptance of equal-weight edge groups during Kruskal.
+//!
+//! Kruskal's algorithm walks edges in `(weight, source, target, sequence)`
+//! order. Within a group of equal weights the sequential scan is the reference
+//! behaviour: an edge is accepted when its endpoints are not yet connected by
+//! an earlier accepted edge. Quantised or integer-valued distances can produce
+//! very large groups, so big groups are resolved with a Borůvka-style
+//! contraction that accepts exactly the same edges as the sequential scan.
+//!
+//! Each round maps the outstanding edges onto their current union-find roots,
+//! discards edges whose endpoints already share a root, and accepts every edge
+//! that is the lowest-indexed outstanding edge incident to at least one of its
+//! root components. Treating the group index as a strict weight, such an edge
+//! is the lightest edge crossing the cut around that component, so it belongs
+//! to the unique minimum spanning forest the sequential scan produces.
+//! Accepted edges can never form a cycle: the highest-indexed edge on any
+//! cycle has a lower-indexed cycle edge at both endpoints. Accepted edges are
+//! merged, and the next round contracts over the updated roots until no
+//! outstanding edges remain.
+
+use std::collections::HashMap;
+
+use rayon::prelude::*;
+
+use super::{MstEdge, MstError, union_find::ConcurrentUnionFind};
+
+/// Groups at or below this size are resolved with the sequential scan, where
+/// the per-round bookkeeping of the parallel path would dominate.
+pub(super) const PARALLEL_GROUP_THRESHOLD: usize = 4096;
+
+/// Accepts the spanning-forest edges from a group of equal-weight edges.
+///
+/// The returned edges are ordered by their position in `group`, regardless of
+/// whether the sequential or the parallel path resolved the group.
+pub(super) fn process_weight_group(
+    group: &[MstEdge],
+    union_find: &ConcurrentUnionFind,
+) -> Result<Vec<MstEdge>, MstError> {
+    if group.len() <= PARALLEL_GROUP_THRESHOLD {
+        process_weight_group_sequential(group, union_find)
+    } else {
+        process_weight_group_parallel(group, union_find)
+    }
+}
+
+/// Processes edges one at a time in group order.
+pub(super) fn process_weight_group_sequential(
+    group: &[MstEdge],
+    union_find: &ConcurrentUnionFind,
+) -> Result<Vec<MstEdge>, MstError> {
+    let mut accepted = Vec::new();
+    for edge in group {
+        if union_find.try_union(edge.source, edge.target)? {
+            accepted.push(*edge);
+        }
+    }
+    Ok(accepted)
+}
+
+/// Resolves the group through parallel contraction rounds.
+///
+/// Produces the same accepted set as [`process_weight_group_sequential`].
+pub(super) fn process_weight_group_parallel(
+    group: &[MstEdge],
+    union_find: &ConcurrentUnionFind,
+) -> Result<Vec<MstEdge>, MstError> {
+    let mut outstanding: Vec<usize> = (0..group.len()).collect();
+    let mut accepted_indices = Vec::new();
+
+    loop {
+        let rooted = root_outstanding_edges(group, &outstanding, union_find);
+        if rooted.is_empty() {
+            break;
+        }
+        let lightest = lightest_edge_per_root(&rooted);
+        let winners: Vec<RootedEdge> = rooted
+            .par_iter()
+            .copied()
+            .filter(|edge| edge.is_lightest_at_either_root(&lightest))
+            .collect();
+
+        winners
+            .par_iter()
+            .try_for_each(|edge| union_winner(group, *edge, union_find))?;
+
+        accepted_indices.extend(winners.iter().map(|edge| edge.index));
+        outstanding = rooted
+            .into_iter()
+            .filter(|edge| !edge.is_lightest_at_either_root(&lightest))
+            .map(|edge| edge.index)
+            .collect();
+    }
+
+    accepted_indices.par_sort_unstable();
+    Ok(accepted_indices
+        .into_iter()
+        .filter_map(|index| group.get(index).copied())
+        .collect())
+}
+
+/// An outstanding group edge expressed in terms of its endpoint roots.
+#[derive(Clone, Copy, Debug)]
+struct RootedEdge {
+    index: usize,
+    left_root: usize,
+    right_root: usize,
+}
+
+impl RootedEdge {
+    fn is_lightest_at_either_root(&self, lightest: &HashMap<usize, usize>) -> bool {
+        lightest.get(&self.left_root) == Some(&self.index)
+            || lightest.get(&self.right_root) == Some(&self.index)
+    }
+}
+
+/// Maps outstanding edges to their roots, dropping edges that would close a
+/// cycle. Order follows `outstanding`, which stays sorted by group index.
+fn root_outstanding_edges(
+    group: &[MstEdge],
+    outstanding: &[usize],
+    union_find: &ConcurrentUnionFind,
+) -> Vec<RootedEdge> {
+    outstanding
+        .par_iter()
+        .filter_map(|&index| {
+            let edge = group.get(index)?;
+            let left_root = union_find.find(edge.source);
+            let right_root = union_find.find(edge.target);
+            (left_root != right_root).then_some(RootedEdge {
+                index,
+                left_root,
+                right_root,
+            })
+        })
+        .collect()
+}
+
+/// Records the lowest group index incident to each root component.
+fn lightest_edge_per_root(rooted: &[RootedEdge]) -> HashMap<usize, usize> {
+    rooted
+        .par_iter()
+        .fold(HashMap::new, |mut acc, edge| {
+            record_lightest(&mut acc, edge.left_root, edge.index);
+            record_lightest(&mut acc, edge.right_root, edge.index);
+            acc
+        })
+        .reduce(HashMap::new, |mut left, right| {
+            for (root, index) in right {
+                record_lightest(&mut left, root, index);
+            }
+            left
+        })
+}
+
+fn record_lightest(lightest: &mut HashMap<usize, usize>, root: usize, index: usize) {
+    lightest
+        .entry(root)
+        .and_modify(|current| *current = (*current).min(index))
+        .or_insert(index);
+}
+
+fn union_winner(
+    group: &[MstEdge],
+    edge: RootedEdge,
+    union_find: &ConcurrentUnionFind,
+) -> Result<(), MstError> {
+    let Some(mst_edge) = group.get(edge.index) else {
+        return Err(MstError::InvariantViolation {
+            invariant: "accepted edge index must lie within its weight group",
+            index: edge.index,
+            lock_count: group.len(),
+        });
+    };
+    if union_find.try_union(mst_edge.source, mst_edge.target)? {
+        Ok(())
+    } else {
+        Err(MstError::InvariantViolation {
+            invariant: "edges accepted in one contraction round must be acyclic",
+            index: edge.index,
+            lock_count: group.len(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Equivalence tests for parallel weight-group acceptance.
+
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    use super::*;
+
+    fn group_from_pairs(pairs: &[(usize, usize)]) -> Vec<MstEdge> {
+        let mut edges: Vec<MstEdge> = pairs
+            .iter()
+            .enumerate()
+            .filter(|(_, (left, right))| left != right)
+            .map(|(sequence, &(left, right))| MstEdge {
+                source: left.min(right),
+                target: left.max(right),
+                weight: 1.0,
+                sequence: sequence as u64,
+            })
+            .collect();
+        edges.sort_unstable();
+        edges.dedup_by(|left, right| left.source == right.source && left.target == right.target);
+        edges
+    }
+
+    fn accept_both_ways(
+        node_count: usize,
+        pre_merged: &[(usize, usize)],
+        group: &[MstEdge],
+    ) -> (Vec<MstEdge>, Vec<MstEdge>) {
+        let sequential_uf = ConcurrentUnionFind::new(node_count);
+        let parallel_uf = ConcurrentUnionFind::new(node_count);
+        for &(left, right) in pre_merged {
+            sequential_uf
+                .try_union(left, right)
+                .expect("union must succeed");
+            parallel_uf
+                .try_union(left, right)
+                .expect("union must succeed");
+        }
+        let sequential =
+            process_weight_group_sequential(group, &sequential_uf).expect("sequential scan");
+        let parallel = process_weight_group_parallel(group, &parallel_uf).expect("parallel scan");
+        assert_eq!(sequential_uf.components(), parallel_uf.components());
+        (sequential, parallel)
+    }
+
+    #[rstest]
+    #[case::triangle(3, &[], &[(0, 1), (1, 2), (0, 2)])]
+    #[case::star(5, &[], &[(0, 1), (0, 2), (0, 3), (0, 4), (1, 2)])]
+    #[case::pre_merged_component(4, &[(0, 1)], &[(0, 1), (1, 2), (0, 2), (2, 3)])]
+    #[case::all_redundant(3, &[(0, 1), (1, 2)], &[(0, 2), (0, 1)])]
+    fn parallel_matches_sequential_on_fixed_groups(
+        #[case] node_count: usize,
+        #[case] pre_merged: &[(usize, usize)],
+        #[case] pairs: &[(usize, usize)],
+    ) {
+        let group = group_from_pairs(pairs);
+        let (sequential, parallel) = accept_both_ways(node_count, pre_merged, &group);
+        assert_eq!(sequential, parallel);
+    }
+
+    #[test]
+    fn large_groups_take_the_parallel_path() {
+        // A dense ring of equal-weight chords exceeds the threshold and must
+        // still accept exactly `node_count - 1` edges.
+        let node_count = 128;
+        let pairs: Vec<(usize, usize)> = (0..node_count)
+            .flat_map(|left| (1..=40).map(move |step| (left, (left + step) % node_count)))
+            .collect();
+        let group = group_from_pairs(&pairs);
+        assert!(group.len() > PARALLEL_GROUP_THRESHOLD);
+
+        let union_find = ConcurrentUnionFind::new(node_count);
+        let accepted = process_weight_group(&group, &union_find).expect("group must resolve");
+        assert_eq!(accepted.len(), node_count - 1);
+        assert_eq!(union_find.components(), 1);
+    }
+
+    proptest! {
+        #[test]
+        fn parallel_matches_sequential_on_random_groups(
+            node_count in 2_usize..48,
+            raw_pairs in prop::collection::vec((0_usize..48, 0_usize..48), 0..256),
+            raw_pre_merged in prop::collection::vec((0_usize..48, 0_usize..48), 0..8),
+        ) {
+            let clamp = |(left, right): (usize, usize)| (left % node_count, right % node_count);
+            let pairs: Vec<_> = raw_pairs.into_iter().map(clamp).collect();
+            let pre_merged: Vec<_> = raw_pre_merged.into_iter().map(clamp).collect();
+            let group = group_from_pairs(&pairs);
+            let (sequential, parallel) = accept_both_ways(node_count, &pre_merged, &group);
+            prop_assert_eq!(sequential, parallel);
+        }
+    }
+}
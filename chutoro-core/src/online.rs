@@ -0,0 +1,338 @@
+//! Micro-cluster summaries for clustering unbounded streams.
+//!
+//! FISHDBC was designed for data that arrives one point at a time. An
+//! [`OnlineClusterer`] keeps memory proportional to the number of distinct
+//! regions seen rather than the number of points: each arriving point either
+//! joins the nearest micro-cluster, when it lies within the configured radius
+//! of that micro-cluster's centre, or starts a new one. Periodically the full
+//! pipeline runs over the micro-cluster centres, and every point inherits the
+//! label of its micro-cluster.
+//!
+//! A [`DataSource`] only measures distances, so a micro-cluster's centre is
+//! the point that founded it rather than an averaged centroid.
+
+use std::{num::NonZeroUsize, sync::Arc};
+
+use tracing::debug;
+
+use crate::{
+    Chutoro, ChutoroError, ClusterId, CpuHnsw, DataSource, DataSourceError, HnswError,
+    MetricDescriptor, Result, cpu_pipeline::map_cpu_hnsw_error,
+};
+
+/// Summary of the points absorbed by one micro-cluster.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MicroCluster {
+    centre: usize,
+    weight: usize,
+    radius: f32,
+}
+
+impl MicroCluster {
+    /// Returns the index of the point that founded the micro-cluster, which
+    /// stands in for its centroid.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn centre(&self) -> usize { self.centre }
+
+    /// Returns the number of points absorbed, including the centre.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn weight(&self) -> usize { self.weight }
+
+    /// Returns the largest distance from the centre to an absorbed point.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn radius(&self) -> f32 { self.radius }
+}
+
+/// Clusters a stream of points through micro-cluster summaries.
+///
+/// Built with [`crate::ChutoroBuilder::build_online`]. Points are indices
+/// into the shared [`DataSource`], which may hold items that have not been
+/// inserted yet. Centres are indexed in an HNSW graph so each insertion finds
+/// its nearest micro-cluster without scanning them all.
+///
+/// The builder's pipeline configuration is applied to the centres on each
+/// refresh, so `min_cluster_size` counts micro-clusters rather than points.
+///
+/// # Examples
+/// ```
+/// use std::sync::Arc;
+///
+/// use chutoro_core::{ChutoroBuilder, ChutoroError, DataSource, DataSourceError};
+///
+/// struct Line(Vec<f32>);
+///
+/// impl DataSource for Line {
+///     fn len(&self) -> usize { self.0.len() }
+///     fn name(&self) -> &str { "line" }
+///     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+///         Ok((self.0[i] - self.0[j]).abs())
+///     }
+/// }
+///
+/// let points = [0.0, 0.1, 1.0, 1.1, 2.0, 50.0, 50.1, 51.0, 51.1, 52.0];
+/// let source = Arc::new(Line(points.to_vec()));
+/// let mut online = ChutoroBuilder::new()
+///     .with_min_cluster_size(2)
+///     .build_online(source, 0.5)?;
+///
+/// for point in 0..10 {
+///     online.insert(point)?;
+/// }
+/// online.refresh()?;
+///
+/// assert_eq!(online.micro_clusters().len(), 6);
+/// let labels = online.snapshot_labels();
+/// assert_eq!(labels[0], labels[4]);
+/// assert_ne!(labels[0], labels[5]);
+/// # Ok::<(), ChutoroError>(())
+/// ```
+#[derive(Debug)]
+pub struct OnlineClusterer<D: DataSource + Send + Sync> {
+    chutoro: Chutoro,
+    source: Arc<D>,
+    radius: f32,
+    refresh_every: Option<NonZeroUsize>,
+    index: CpuHnsw,
+    micro_clusters: Vec<MicroCluster>,
+    /// Micro-cluster of each source point, once inserted.
+    owners: Vec<Option<usize>>,
+    inserted: usize,
+    since_refresh: usize,
+    labels: Arc<Vec<Option<ClusterId>>>,
+    snapshot_version: u64,
+}
+
+impl<D: DataSource + Send + Sync> OnlineClusterer<D> {
+    pub(crate) fn new(
+        chutoro: Chutoro,
+        source: Arc<D>,
+        radius: f32,
+        refresh_every: Option<NonZeroUsize>,
+    ) -> Result<Self> {
+        let len = source.len();
+        let index =
+            CpuHnsw::with_capacity(chutoro.hnsw_params().clone(), len.max(1)).map_err(|error| {
+                ChutoroError::CpuHnswFailure {
+                    code: Arc::from(error.code().as_str()),
+                    message: Arc::from(error.to_string()),
+                }
+            })?;
+        Ok(Self {
+            chutoro,
+            source,
+            radius,
+            refresh_every,
+            index,
+            micro_clusters: Vec::new(),
+            owners: vec![None; len],
+            inserted: 0,
+            since_refresh: 0,
+            labels: Arc::new(vec![None; len]),
+            snapshot_version: 0,
+        })
+    }
+
+    /// Returns the radius within which a point joins an existing
+    /// micro-cluster.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn radius(&self) -> f32 { self.radius }
+
+    /// Returns the number of points inserted so far.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn point_count(&self) -> usize { self.inserted }
+
+    /// Returns the micro-cluster summaries in creation order.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn micro_clusters(&self) -> &[MicroCluster] { &self.micro_clusters }
+
+    /// Returns the number of refreshes that have published labels.
+    #[must_use]
+    #[rustfmt::skip]
+    pub fn snapshot_version(&self) -> u64 { self.snapshot_version }
+
+    /// Returns the labels published by the most recent refresh, indexed by
+    /// point.
+    ///
+    /// A point's entry is `None` when it is noise or was inserted after the
+    /// refresh. The snapshot is shared, so holding it does not block
+    /// insertion.
+    #[must_use]
+    pub fn snapshot_labels(&self) -> Arc<Vec<Option<ClusterId>>> {
+        Arc::clone(&self.labels)
+    }
+
+    /// Adds `point` to its nearest micro-cluster, or founds a new one when
+    /// every centre lies further away than the radius, and refreshes the
+    /// labels when the refresh interval is reached.
+    ///
+    /// # Errors
+    /// Returns [`ChutoroError::DataSource`] with
+    /// [`DataSourceError::OutOfBounds`] when `point` is not in the source,
+    /// [`ChutoroError::CpuHnswFailure`] when it was already inserted or the
+    /// centre index fails, and any error raised by [`Self::refresh`].
+    pub fn insert(&mut self, point: usize) -> Result<()> {
+        let Some(owner) = self.owners.get(point).copied() else {
+            return Err(ChutoroError::DataSource {
+                data_source: Arc::from(self.source.name()),
+                error: DataSourceError::OutOfBounds { index: point },
+            });
+        };
+        if owner.is_some() {
+            return Err(self.hnsw_error(HnswError::DuplicateNode { node: point }));
+        }
+        let owner = match self.nearest_within_radius(point)? {
+            Some((owner, distance)) => {
+                let micro = &mut self.micro_clusters[owner];
+                micro.weight += 1;
+                micro.radius = micro.radius.max(distance);
+                owner
+            }
+            None => self.found(point)?,
+        };
+        self.owners[point] = Some(owner);
+        self.inserted += 1;
+        self.since_refresh += 1;
+        if self
+            .refresh_every
+            .is_some_and(|every| self.since_refresh >= every.get())
+        {
+            self.refresh()?;
+        }
+        Ok(())
+    }
+
+    /// Clusters the micro-cluster centres with the builder's pipeline and
+    /// publishes a new label snapshot.
+    ///
+    /// While there are fewer micro-clusters than `min_cluster_size`, no
+    /// cluster can form and every point is published as noise.
+    ///
+    /// # Errors
+    /// Returns any error raised by [`Chutoro::run`] over the centres.
+    pub fn refresh(&mut self) -> Result<()> {
+        let micro_labels = if self.micro_clusters.len() < self.chutoro.min_cluster_size().get() {
+            vec![None; self.micro_clusters.len()]
+        } else {
+            let centres = Centres {
+                source: self.source.as_ref(),
+                micro_clusters: &self.micro_clusters,
+            };
+            let result = self.chutoro.run(&centres)?;
+            let noise = result.noise_label();
+            result
+                .assignments()
+                .iter()
+                .map(|&id| (Some(id) != noise).then_some(id))
+                .collect()
+        };
+        let labels = self
+            .owners
+            .iter()
+            .map(|owner| owner.and_then(|owner| micro_labels[owner]))
+            .collect();
+        self.labels = Arc::new(labels);
+        self.snapshot_version += 1;
+        self.since_refresh = 0;
+        debug!(
+            micro_clusters = self.micro_clusters.len(),
+            points = self.inserted,
+            version = self.snapshot_version,
+            "online clusterer refreshed labels"
+        );
+        Ok(())
+    }
+
+    /// Returns the nearest micro-cluster and its distance when its centre
+    /// lies within the radius.
+    fn nearest_within_radius(&self, point: usize) -> Result<Option<(usize, f32)>> {
+        if self.micro_clusters.is_empty() {
+            return Ok(None);
+        }
+        let ef =
+            NonZeroUsize::new(self.index.params().ef_construction()).unwrap_or(NonZeroUsize::MIN);
+        let nearest = self
+            .index
+            .search(self.source.as_ref(), point, ef)
+            .map_err(|error| self.hnsw_error(error))?
+            .into_iter()
+            // Searches always report the query itself, which is not a centre.
+            .find(|neighbour| neighbour.id != point)
+            .filter(|neighbour| neighbour.distance <= self.radius);
+        Ok(nearest.map(|neighbour| {
+            let owner = self.owners[neighbour.id].expect("centres are always inserted points");
+            (owner, neighbour.distance)
+        }))
+    }
+
+    /// Starts a micro-cluster centred on `point` and returns its position.
+    fn found(&mut self, point: usize) -> Result<usize> {
+        self.index
+            .insert(point, self.source.as_ref())
+            .map_err(|error| self.hnsw_error(error))?;
+        self.micro_clusters.push(MicroCluster {
+            centre: point,
+            weight: 1,
+            radius: 0.0,
+        });
+        Ok(self.micro_clusters.len() - 1)
+    }
+
+    fn hnsw_error(&self, error: HnswError) -> ChutoroError {
+        map_cpu_hnsw_error(self.source.as_ref(), error)
+    }
+}
+
+/// The micro-cluster centres presented as a data source of their own.
+struct Centres<'a, D> {
+    source: &'a D,
+    micro_clusters: &'a [MicroCluster],
+}
+
+impl<D: DataSource> Centres<'_, D> {
+    fn centre(&self, index: usize) -> core::result::Result<usize, DataSourceError> {
+        self.micro_clusters
+            .get(index)
+            .map(MicroCluster::centre)
+            .ok_or(DataSourceError::OutOfBounds { index })
+    }
+}
+
+impl<D: DataSource> DataSource for Centres<'_, D> {
+    fn len(&self) -> usize {
+        self.micro_clusters.len()
+    }
+
+    fn name(&self) -> &str {
+        self.source.name()
+    }
+
+    fn distance(&self, i: usize, j: usize) -> core::result::Result<f32, DataSourceError> {
+        self.source.distance(self.centre(i)?, self.centre(j)?)
+    }
+
+    fn distance_f64(&self, i: usize, j: usize) -> core::result::Result<f64, DataSourceError> {
+        self.source.distance_f64(self.centre(i)?, self.centre(j)?)
+    }
+
+    fn distance_batch(
+        &self,
+        pairs: &[(usize, usize)],
+        out: &mut [f32],
+    ) -> core::result::Result<(), DataSourceError> {
+        let pairs = pairs
+            .iter()
+            .map(|&(i, j)| Ok((self.centre(i)?, self.centre(j)?)))
+            .collect::<core::result::Result<Vec<_>, DataSourceError>>()?;
+        self.source.distance_batch(&pairs, out)
+    }
+
+    fn metric_descriptor(&self) -> MetricDescriptor {
+        self.source.metric_descriptor()
+    }
+}
@@ -0,0 +1,159 @@
+//! Exact reference neighbour sets for property tests.
+//!
+//! Available with the `test-oracles` feature. The functions here are
+//! deliberately naive: they evaluate every pair sequentially through
+//! [`DataSource::distance`] and sort by [`Neighbour`]'s ordering (distance,
+//! then id), so their output is fully determined by the source. Downstream
+//! crates can use them to check that a [`DataSource`] implementation yields
+//! the neighbour sets its geometry predicts, and to measure how closely
+//! [`crate::CpuHnsw::search`] recovers them, in the same way the internal MST
+//! property suite checks the parallel Kruskal against a sequential one.
+
+use crate::{DataSource, DataSourceError, Neighbour};
+
+/// Returns the `k` points closest to `query`, including `query` itself,
+/// sorted by distance and then id.
+///
+/// The query is included because [`crate::CpuHnsw::search`] also returns it,
+/// so the two can be compared directly.
+///
+/// # Errors
+/// Returns the first [`DataSourceError`] reported by the source.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::{DataSource, DataSourceError, oracles::exact_neighbours};
+///
+/// struct Line(Vec<f32>);
+///
+/// impl DataSource for Line {
+///     fn len(&self) -> usize { self.0.len() }
+///     fn name(&self) -> &str { "line" }
+///     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+///         Ok((self.0[i] - self.0[j]).abs())
+///     }
+/// }
+///
+/// let source = Line(vec![0.0, 1.0, 3.0, 10.0]);
+/// let nearest = exact_neighbours(&source, 1, 2).expect("distances are valid");
+/// let ids: Vec<usize> = nearest.iter().map(|neighbour| neighbour.id).collect();
+/// assert_eq!(ids, [1, 0]);
+/// ```
+pub fn exact_neighbours<D: DataSource + ?Sized>(
+    source: &D,
+    query: usize,
+    k: usize,
+) -> Result<Vec<Neighbour>, DataSourceError> {
+    let mut neighbours = (0..source.len())
+        .map(|id| {
+            source
+                .distance(query, id)
+                .map(|distance| Neighbour { id, distance })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    neighbours.sort_unstable();
+    neighbours.truncate(k);
+    Ok(neighbours)
+}
+
+/// Returns the fraction of the first `k` `expected` neighbours that appear
+/// among the first `k` `observed` ones.
+///
+/// Only ids are compared, so ties broken differently still count as hits
+/// when both sides chose the same points. Recall is `1.0` when there is
+/// nothing to recall.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::{Neighbour, oracles::recall_at_k};
+///
+/// let expected = [Neighbour { id: 0, distance: 0.0 }, Neighbour { id: 1, distance: 1.0 }];
+/// let observed = [Neighbour { id: 0, distance: 0.0 }, Neighbour { id: 2, distance: 2.0 }];
+/// assert_eq!(recall_at_k(&expected, &observed, 2), 0.5);
+/// ```
+#[must_use]
+pub fn recall_at_k(expected: &[Neighbour], observed: &[Neighbour], k: usize) -> f32 {
+    let target = k.min(expected.len());
+    if target == 0 {
+        return 1.0;
+    }
+    let hits = observed
+        .iter()
+        .take(target)
+        .filter(|seen| expected[..target].iter().any(|want| want.id == seen.id))
+        .count();
+    hits as f32 / target as f32
+}
+
+/// The exact `k`-nearest-neighbour graph of a source, excluding self-loops.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::{DataSource, DataSourceError, oracles::ExactKnnGraph};
+///
+/// struct Line(Vec<f32>);
+///
+/// impl DataSource for Line {
+///     fn len(&self) -> usize { self.0.len() }
+///     fn name(&self) -> &str { "line" }
+///     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+///         Ok((self.0[i] - self.0[j]).abs())
+///     }
+/// }
+///
+/// let graph = ExactKnnGraph::build(&Line(vec![0.0, 1.0, 3.0, 10.0]), 1)
+///     .expect("distances are valid");
+/// let nearest: Vec<usize> = (0..graph.len())
+///     .map(|point| graph.neighbours(point).expect("point exists")[0].id)
+///     .collect();
+/// assert_eq!(nearest, [1, 0, 1, 2]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExactKnnGraph {
+    k: usize,
+    neighbours: Vec<Vec<Neighbour>>,
+}
+
+impl ExactKnnGraph {
+    /// Computes the `k` nearest other points of every point in `source`.
+    ///
+    /// Points with fewer than `k` others list all of them.
+    ///
+    /// # Errors
+    /// Returns the first [`DataSourceError`] reported by the source.
+    pub fn build<D: DataSource + ?Sized>(source: &D, k: usize) -> Result<Self, DataSourceError> {
+        let neighbours = (0..source.len())
+            .map(|point| {
+                let mut nearest = exact_neighbours(source, point, source.len())?;
+                nearest.retain(|neighbour| neighbour.id != point);
+                nearest.truncate(k);
+                Ok(nearest)
+            })
+            .collect::<Result<_, DataSourceError>>()?;
+        Ok(Self { k, neighbours })
+    }
+
+    /// Returns the requested neighbour count.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn k(&self) -> usize { self.k }
+
+    /// Returns the number of points in the graph.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.neighbours.len()
+    }
+
+    /// Returns whether the graph has no points.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.neighbours.is_empty()
+    }
+
+    /// Returns the nearest other points of `point`, closest first, or `None`
+    /// when `point` is out of range.
+    #[must_use]
+    pub fn neighbours(&self, point: usize) -> Option<&[Neighbour]> {
+        self.neighbours.get(point).map(Vec::as_slice)
+    }
+}
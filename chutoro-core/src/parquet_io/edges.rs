@@ -0,0 +1,213 @@
+//! The edge table layout shared by harvest and forest files.
+
+use std::{collections::HashMap, fs::File, path::Path, sync::Arc};
+
+use arrow_array::{Array, ArrayRef, Float64Array, RecordBatch, UInt64Array};
+use arrow_schema::{DataType, Field, Fields, Schema, SchemaRef};
+use parquet::{
+    arrow::{ArrowWriter, arrow_reader::ParquetRecordBatchReaderBuilder},
+    errors::ParquetError,
+};
+
+use super::{PARQUET_SCHEMA_VERSION, ParquetArtefactError};
+
+const ARTEFACT_KEY: &str = "chutoro.artefact";
+const VERSION_KEY: &str = "chutoro.schema_version";
+
+/// Rows per record batch when writing and reading edge tables.
+const BATCH_ROWS: usize = 65_536;
+
+/// One row of an edge table.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct EdgeRow {
+    pub(super) source: usize,
+    pub(super) target: usize,
+    pub(super) distance: f64,
+    pub(super) sequence: u64,
+}
+
+/// The artefact a file holds and the metadata recorded beside its edges.
+#[derive(Debug)]
+pub(super) struct Header {
+    pub(super) artefact: &'static str,
+    pub(super) metadata: HashMap<String, String>,
+}
+
+fn edge_fields() -> Fields {
+    Fields::from(vec![
+        Field::new("source", DataType::UInt64, false),
+        Field::new("target", DataType::UInt64, false),
+        Field::new("distance", DataType::Float64, false),
+        Field::new("sequence", DataType::UInt64, false),
+    ])
+}
+
+/// Writes `rows` to `path` as an edge table labelled by `header`.
+pub(super) fn write_edges(
+    path: &Path,
+    header: Header,
+    mut rows: impl Iterator<Item = EdgeRow>,
+) -> Result<(), ParquetArtefactError> {
+    let mut metadata = header.metadata;
+    metadata.insert(ARTEFACT_KEY.to_owned(), header.artefact.to_owned());
+    metadata.insert(VERSION_KEY.to_owned(), PARQUET_SCHEMA_VERSION.to_string());
+    let schema: SchemaRef = Arc::new(Schema::new_with_metadata(edge_fields(), metadata));
+    let mut writer = ArrowWriter::try_new(File::create(path)?, Arc::clone(&schema), None)?;
+    loop {
+        let chunk: Vec<EdgeRow> = rows.by_ref().take(BATCH_ROWS).collect();
+        if chunk.is_empty() {
+            break;
+        }
+        let batch = RecordBatch::try_new(Arc::clone(&schema), columns(&chunk))
+            .map_err(ParquetError::from)?;
+        writer.write(&batch)?;
+    }
+    writer.close()?;
+    Ok(())
+}
+
+fn columns(chunk: &[EdgeRow]) -> Vec<ArrayRef> {
+    let ids = |id: fn(&EdgeRow) -> u64| -> ArrayRef {
+        Arc::new(UInt64Array::from_iter_values(chunk.iter().map(id)))
+    };
+    vec![
+        ids(|row| row.source as u64),
+        ids(|row| row.target as u64),
+        Arc::new(Float64Array::from_iter_values(
+            chunk.iter().map(|row| row.distance),
+        )),
+        ids(|row| row.sequence),
+    ]
+}
+
+/// An edge table opened for reading, its header already checked.
+pub(super) struct EdgeTable {
+    artefact: &'static str,
+    metadata: HashMap<String, String>,
+    builder: ParquetRecordBatchReaderBuilder<File>,
+}
+
+impl EdgeTable {
+    /// Opens `path`, checking that it holds `artefact` in a schema version
+    /// this build reads.
+    pub(super) fn open(path: &Path, artefact: &'static str) -> Result<Self, ParquetArtefactError> {
+        let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(path)?)?;
+        let schema = Arc::clone(builder.schema());
+        check_header(&schema, artefact)?;
+        if schema.fields() != &edge_fields() {
+            return Err(malformed(
+                artefact,
+                "columns must be non-null `source`, `target`, `distance`, and `sequence` \
+                 of types UInt64, UInt64, Float64, and UInt64",
+            ));
+        }
+        Ok(Self {
+            artefact,
+            metadata: schema.metadata().clone(),
+            builder,
+        })
+    }
+
+    /// Parses the metadata value stored under `key`.
+    pub(super) fn metadata<T: std::str::FromStr>(
+        &self,
+        key: &str,
+    ) -> Result<T, ParquetArtefactError> {
+        self.metadata
+            .get(key)
+            .and_then(|value| value.parse().ok())
+            .ok_or_else(|| malformed(self.artefact, format!("missing or invalid `{key}`")))
+    }
+
+    /// Returns the raw metadata value stored under `key`, if any.
+    pub(super) fn metadata_str(&self, key: &str) -> Option<&str> {
+        self.metadata.get(key).map(String::as_str)
+    }
+
+    /// Reads every row, converting each with `edge`.
+    pub(super) fn read_edges<T>(
+        self,
+        edge: impl Fn(EdgeRow) -> T,
+    ) -> Result<Vec<T>, ParquetArtefactError> {
+        let artefact = self.artefact;
+        let reader = self.builder.with_batch_size(BATCH_ROWS).build()?;
+        let mut edges = Vec::new();
+        for batch in reader {
+            let batch = batch.map_err(ParquetError::from)?;
+            let rows = batch_rows(&batch, artefact)?;
+            edges.reserve(rows.len());
+            edges.extend(rows.into_iter().map(&edge));
+        }
+        Ok(edges)
+    }
+}
+
+/// Decodes one record batch, rejecting identifiers that do not fit `usize`
+/// and non-finite distances.
+fn batch_rows(
+    batch: &RecordBatch,
+    artefact: &'static str,
+) -> Result<Vec<EdgeRow>, ParquetArtefactError> {
+    let ids = |index: usize| {
+        batch
+            .column(index)
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .ok_or_else(|| malformed(artefact, "identifier columns must be UInt64"))
+    };
+    let (sources, targets, sequences) = (ids(0)?, ids(1)?, ids(3)?);
+    let distances = batch
+        .column(2)
+        .as_any()
+        .downcast_ref::<Float64Array>()
+        .ok_or_else(|| malformed(artefact, "`distance` must be Float64"))?;
+    let index = |id: u64| {
+        usize::try_from(id)
+            .map_err(|_| malformed(artefact, format!("node {id} does not fit this platform")))
+    };
+    (0..batch.num_rows())
+        .map(|row| {
+            let distance = distances.value(row);
+            if !distance.is_finite() {
+                return Err(malformed(
+                    artefact,
+                    format!("row {row} has distance {distance}"),
+                ));
+            }
+            Ok(EdgeRow {
+                source: index(sources.value(row))?,
+                target: index(targets.value(row))?,
+                distance,
+                sequence: sequences.value(row),
+            })
+        })
+        .collect()
+}
+
+fn check_header(schema: &Schema, artefact: &'static str) -> Result<(), ParquetArtefactError> {
+    let metadata = schema.metadata();
+    let found = metadata
+        .get(ARTEFACT_KEY)
+        .map_or("an unlabelled file", String::as_str);
+    if found != artefact {
+        return Err(ParquetArtefactError::WrongArtefact {
+            expected: artefact,
+            found: found.to_owned(),
+        });
+    }
+    let version = metadata.get(VERSION_KEY).map_or("none", String::as_str);
+    match version.parse::<u32>() {
+        Ok(parsed) if (1..=PARQUET_SCHEMA_VERSION).contains(&parsed) => Ok(()),
+        _ => Err(ParquetArtefactError::UnsupportedVersion {
+            artefact,
+            found: version.to_owned(),
+        }),
+    }
+}
+
+fn malformed(artefact: &'static str, reason: impl Into<String>) -> ParquetArtefactError {
+    ParquetArtefactError::Malformed {
+        artefact,
+        reason: reason.into(),
+    }
+}
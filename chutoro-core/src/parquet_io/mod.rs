@@ -0,0 +1,256 @@
+//! Parquet files for the candidate edges and spanning forest of a run.
+//!
+//! Checkpointing and inspection tools exchange these artefacts as files, so
+//! both use one stable layout: one row per edge with non-null `source` and
+//! `target` (`UInt64`), `distance` (`Float64`), and `sequence` (`UInt64`)
+//! columns. Distances are stored in double precision so forests weighted
+//! with [`crate::DataSource::distance_f64`] keep their weights. The Arrow
+//! schema metadata names the artefact under `chutoro.artefact` and the
+//! layout under `chutoro.schema_version`; readers refuse other artefacts and
+//! newer versions rather than guess at their columns.
+//!
+//! A forest file also records the forest's component count and diagnostics
+//! in metadata, so reading it back reproduces the forest exactly.
+
+mod edges;
+
+use std::{collections::HashMap, io, path::Path};
+
+use parquet::errors::ParquetError;
+use thiserror::Error;
+
+use crate::{CandidateEdge, EdgeHarvest, MinimumSpanningForest, MstDiagnostics, MstEdge};
+
+use self::edges::{EdgeRow, EdgeTable, Header, write_edges};
+
+/// The newest edge table layout this build writes and reads.
+pub const PARQUET_SCHEMA_VERSION: u32 = 1;
+
+const HARVEST: &str = "edge_harvest";
+const FOREST: &str = "minimum_spanning_forest";
+
+const COMPONENT_COUNT: &str = "chutoro.component_count";
+const SELF_EDGES_DROPPED: &str = "chutoro.self_edges_dropped";
+const DUPLICATE_EDGES_MERGED: &str = "chutoro.duplicate_edges_merged";
+const WEIGHT_RANGE: &str = "chutoro.weight_range";
+const COMPONENT_EDGE_COUNTS: &str = "chutoro.component_edge_counts";
+
+/// Errors raised when writing or reading edge artefacts as Parquet.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ParquetArtefactError {
+    /// The file could not be created or opened.
+    #[error("failed to access Parquet artefact: {0}")]
+    Io(#[from] io::Error),
+    /// Encoding or decoding the Parquet data failed.
+    #[error("failed to encode or decode Parquet artefact: {0}")]
+    Parquet(#[from] ParquetError),
+    /// The file holds a different artefact.
+    #[error("expected a {expected} file but found {found}")]
+    WrongArtefact {
+        /// The artefact the reader was asked for.
+        expected: &'static str,
+        /// The artefact recorded in the file.
+        found: String,
+    },
+    /// The file's schema version is missing or newer than this build reads.
+    #[error(
+        "unsupported {artefact} schema version {found}; this build reads up to \
+         {PARQUET_SCHEMA_VERSION}"
+    )]
+    UnsupportedVersion {
+        /// The artefact being read.
+        artefact: &'static str,
+        /// The version recorded in the file.
+        found: String,
+    },
+    /// The file claims the layout but its columns or metadata disagree.
+    #[error("malformed {artefact} file: {reason}")]
+    Malformed {
+        /// The artefact being read.
+        artefact: &'static str,
+        /// What was wrong.
+        reason: String,
+    },
+}
+
+impl EdgeHarvest {
+    /// Writes the harvest to `path` as a Parquet edge table, one row per
+    /// edge in harvest order.
+    ///
+    /// # Errors
+    /// Returns [`ParquetArtefactError::Io`] when the file cannot be created
+    /// and [`ParquetArtefactError::Parquet`] when encoding fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{CandidateEdge, EdgeHarvest};
+    ///
+    /// let dir = std::env::temp_dir().join("chutoro-harvest-doctest.parquet");
+    /// let harvest = EdgeHarvest::new(vec![
+    ///     CandidateEdge::new(0, 1, 0.5, 0),
+    ///     CandidateEdge::new(1, 2, 1.5, 1),
+    /// ]);
+    /// harvest.write_parquet(&dir).expect("write");
+    /// assert_eq!(EdgeHarvest::read_parquet(&dir).expect("read"), harvest);
+    /// # std::fs::remove_file(dir).expect("cleanup");
+    /// ```
+    pub fn write_parquet(&self, path: impl AsRef<Path>) -> Result<(), ParquetArtefactError> {
+        let header = Header {
+            artefact: HARVEST,
+            metadata: HashMap::new(),
+        };
+        write_edges(
+            path.as_ref(),
+            header,
+            self.iter().map(|edge| EdgeRow {
+                source: edge.source(),
+                target: edge.target(),
+                distance: edge.distance_f64(),
+                sequence: edge.sequence(),
+            }),
+        )
+    }
+
+    /// Reads a harvest written by [`Self::write_parquet`].
+    ///
+    /// # Errors
+    /// Returns [`ParquetArtefactError::WrongArtefact`] for a file holding
+    /// another artefact, [`ParquetArtefactError::UnsupportedVersion`] for a
+    /// newer layout, [`ParquetArtefactError::Malformed`] for unexpected
+    /// columns or non-finite distances, and the I/O and decoding errors of
+    /// [`Self::write_parquet`].
+    pub fn read_parquet(path: impl AsRef<Path>) -> Result<Self, ParquetArtefactError> {
+        let edges = EdgeTable::open(path.as_ref(), HARVEST)?.read_edges(|row| {
+            CandidateEdge::new_f64(row.source, row.target, row.distance, row.sequence)
+        })?;
+        Ok(Self::new(edges))
+    }
+}
+
+impl MinimumSpanningForest {
+    /// Writes the forest to `path` as a Parquet edge table, recording its
+    /// component count and diagnostics in the file metadata.
+    ///
+    /// # Errors
+    /// Returns [`ParquetArtefactError::Io`] when the file cannot be created
+    /// and [`ParquetArtefactError::Parquet`] when encoding fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{CandidateEdge, EdgeHarvest, MinimumSpanningForest, parallel_kruskal};
+    ///
+    /// let harvest = EdgeHarvest::new(vec![
+    ///     CandidateEdge::new(0, 1, 1.0, 0),
+    ///     CandidateEdge::new(1, 2, 2.0, 1),
+    ///     CandidateEdge::new(0, 2, 3.0, 2),
+    /// ]);
+    /// let forest = parallel_kruskal(3, &harvest).expect("valid graph");
+    ///
+    /// let path = std::env::temp_dir().join("chutoro-forest-doctest.parquet");
+    /// forest.write_parquet(&path).expect("write");
+    /// assert_eq!(MinimumSpanningForest::read_parquet(&path).expect("read"), forest);
+    /// # std::fs::remove_file(path).expect("cleanup");
+    /// ```
+    pub fn write_parquet(&self, path: impl AsRef<Path>) -> Result<(), ParquetArtefactError> {
+        let header = Header {
+            artefact: FOREST,
+            metadata: forest_metadata(self),
+        };
+        write_edges(
+            path.as_ref(),
+            header,
+            self.edges().iter().map(|edge| EdgeRow {
+                source: edge.source(),
+                target: edge.target(),
+                distance: edge.weight_f64(),
+                sequence: edge.sequence(),
+            }),
+        )
+    }
+
+    /// Reads a forest written by [`Self::write_parquet`].
+    ///
+    /// # Errors
+    /// Returns the errors of [`EdgeHarvest::read_parquet`], and
+    /// [`ParquetArtefactError::Malformed`] when the component count or
+    /// diagnostics metadata is missing or invalid.
+    pub fn read_parquet(path: impl AsRef<Path>) -> Result<Self, ParquetArtefactError> {
+        let table = EdgeTable::open(path.as_ref(), FOREST)?;
+        let component_count = table.metadata(COMPONENT_COUNT)?;
+        let diagnostics = MstDiagnostics::from_parts(
+            table.metadata(SELF_EDGES_DROPPED)?,
+            table.metadata(DUPLICATE_EDGES_MERGED)?,
+            parse_weight_range(&table)?,
+            parse_counts(&table)?,
+        );
+        let mut edges = table.read_edges(|row| {
+            MstEdge::new_f64(row.source, row.target, row.distance, row.sequence)
+        })?;
+        edges.sort_unstable();
+        Ok(Self::from_parts(edges, component_count, diagnostics))
+    }
+}
+
+fn forest_metadata(forest: &MinimumSpanningForest) -> HashMap<String, String> {
+    let diagnostics = forest.diagnostics();
+    let weight_range = diagnostics
+        .weight_range()
+        .map_or_else(String::new, |(low, high)| format!("{low},{high}"));
+    let counts: Vec<String> = diagnostics
+        .component_edge_counts()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    HashMap::from([
+        (
+            COMPONENT_COUNT.to_owned(),
+            forest.component_count().to_string(),
+        ),
+        (
+            SELF_EDGES_DROPPED.to_owned(),
+            diagnostics.self_edges_dropped().to_string(),
+        ),
+        (
+            DUPLICATE_EDGES_MERGED.to_owned(),
+            diagnostics.duplicate_edges_merged().to_string(),
+        ),
+        (WEIGHT_RANGE.to_owned(), weight_range),
+        (COMPONENT_EDGE_COUNTS.to_owned(), counts.join(",")),
+    ])
+}
+
+/// Parses the `low,high` weight range, which is empty when no candidate
+/// edge was scanned.
+fn parse_weight_range(table: &EdgeTable) -> Result<Option<(f32, f32)>, ParquetArtefactError> {
+    let invalid = || ParquetArtefactError::Malformed {
+        artefact: FOREST,
+        reason: format!("missing or invalid `{WEIGHT_RANGE}`"),
+    };
+    let value = table.metadata_str(WEIGHT_RANGE).ok_or_else(invalid)?;
+    if value.is_empty() {
+        return Ok(None);
+    }
+    let (low, high) = value.split_once(',').ok_or_else(invalid)?;
+    match (low.parse(), high.parse()) {
+        (Ok(low), Ok(high)) => Ok(Some((low, high))),
+        _ => Err(invalid()),
+    }
+}
+
+fn parse_counts(table: &EdgeTable) -> Result<Vec<usize>, ParquetArtefactError> {
+    let invalid = || ParquetArtefactError::Malformed {
+        artefact: FOREST,
+        reason: format!("missing or invalid `{COMPONENT_EDGE_COUNTS}`"),
+    };
+    let value = table
+        .metadata_str(COMPONENT_EDGE_COUNTS)
+        .ok_or_else(invalid)?;
+    if value.is_empty() {
+        return Ok(Vec::new());
+    }
+    value
+        .split(',')
+        .map(|count| count.parse().map_err(|_| invalid()))
+        .collect()
+}
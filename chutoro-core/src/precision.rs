@@ -0,0 +1,193 @@
+//! Double-precision weighting of the mutual-reachability graph.
+//!
+//! Distances are `f32` throughout the HNSW index, which is the fast path and
+//! the default. Under [`DistancePrecision::Double`] the CPU pipeline still
+//! builds and searches the index with [`crate::DataSource::distance`], but
+//! re-evaluates the distances that become MST weights, namely each harvested
+//! edge and each point's core neighbourhood, with
+//! [`crate::DataSource::distance_f64`]. Edge weights, the spanning forest,
+//! and the hierarchy's densities and stabilities are then carried in `f64`,
+//! so distances that differ only beyond `f32` precision still order the
+//! merges and contribute to cluster stability.
+
+#[cfg(feature = "cpu")]
+use std::{num::NonZeroUsize, sync::Arc};
+
+#[cfg(feature = "cpu")]
+use rayon::prelude::*;
+
+#[cfg(feature = "cpu")]
+use crate::{
+    CandidateEdge, CpuHnsw, DataSource, EdgeHarvest, Result,
+    cpu_pipeline::{CoreSearch, mutual_reachability_edge, mutual_reachability_harvest},
+    distance_policy::DistanceGuard,
+    error::ChutoroError,
+};
+
+/// Selects the precision of the distances that weight the spanning tree.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::{ChutoroBuilder, DistancePrecision};
+///
+/// let builder = ChutoroBuilder::new().with_distance_precision(DistancePrecision::Double);
+/// assert_eq!(builder.distance_precision(), DistancePrecision::Double);
+/// assert_eq!(DistancePrecision::default(), DistancePrecision::Single);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DistancePrecision {
+    /// Weight edges with the `f32` distances the index was built with.
+    #[default]
+    Single,
+    /// Re-evaluate edge weights and core distances with
+    /// [`crate::DataSource::distance_f64`], at the cost of one extra
+    /// distance per harvested edge and per core neighbour.
+    Double,
+}
+
+/// Core distances at the precision the run weights its edges with.
+#[cfg(feature = "cpu")]
+#[derive(Debug)]
+pub(crate) enum CoreDistances {
+    Single(Vec<f32>),
+    Double(Vec<f64>),
+}
+
+#[cfg(feature = "cpu")]
+impl CoreDistances {
+    /// Wraps `core` for a run at `precision`.
+    pub(crate) fn new(core: Vec<f32>, precision: DistancePrecision) -> Self {
+        match precision {
+            DistancePrecision::Single => Self::Single(core),
+            DistancePrecision::Double => Self::Double(core.into_iter().map(f64::from).collect()),
+        }
+    }
+
+    /// Re-weights one edge with its mutual-reachability distance, applying
+    /// `guard` to a non-finite re-evaluated distance.
+    ///
+    /// # Errors
+    /// Returns [`ChutoroError::DataSource`] when re-evaluating the edge's
+    /// distance fails.
+    pub(crate) fn weight_edge<D: DataSource>(
+        &self,
+        source: &D,
+        guard: &DistanceGuard,
+        edge: &CandidateEdge,
+    ) -> Result<CandidateEdge> {
+        match self {
+            Self::Single(core) => Ok(mutual_reachability_edge(edge, core)),
+            Self::Double(core) => precise_edge((source, guard), edge, core),
+        }
+    }
+
+    /// Re-weights every harvested edge with its mutual-reachability
+    /// distance, as [`Self::weight_edge`] does.
+    ///
+    /// # Errors
+    /// Returns [`ChutoroError::DataSource`] when re-evaluating a distance
+    /// fails.
+    pub(crate) fn weight_harvest<D: DataSource + Sync>(
+        &self,
+        source: &D,
+        guard: &DistanceGuard,
+        harvested: &EdgeHarvest,
+    ) -> Result<EdgeHarvest> {
+        match self {
+            Self::Single(core) => Ok(mutual_reachability_harvest(harvested, core)),
+            Self::Double(core) => {
+                let edges: Vec<&CandidateEdge> = harvested.iter().collect();
+                let weighted = edges
+                    .par_iter()
+                    .map(|edge| precise_edge((source, guard), edge, core))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(EdgeHarvest::new(weighted))
+            }
+        }
+    }
+
+    /// Re-weights the edges of a supplied graph with their mutual-reachability
+    /// distances, reading each edge's own distance at the run's precision.
+    pub(crate) fn weight_graph(&self, graph: &EdgeHarvest) -> EdgeHarvest {
+        match self {
+            Self::Single(core) => mutual_reachability_harvest(graph, core),
+            Self::Double(core) => EdgeHarvest::new(
+                graph
+                    .iter()
+                    .map(|edge| {
+                        let (left, right) = (edge.source(), edge.target());
+                        let weight = edge.distance_f64().max(core[left]).max(core[right]);
+                        CandidateEdge::new_f64(left, right, weight, edge.sequence())
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Returns the core distances as reported to callers, narrowed to `f32`.
+    pub(crate) fn into_reported(self) -> Vec<f32> {
+        match self {
+            Self::Single(core) => core,
+            Self::Double(core) => core.into_iter().map(|distance| distance as f32).collect(),
+        }
+    }
+}
+
+/// Computes each indexed point's core distance by re-evaluating its HNSW
+/// neighbourhood with [`DataSource::distance_f64`].
+///
+/// Neighbours are re-ranked by their `f64` distances before the core
+/// neighbour is picked, so the choice matches the `f64` metric.
+///
+/// # Errors
+/// Returns [`ChutoroError::DataSource`] when a search or distance fails.
+#[cfg(feature = "cpu")]
+pub(crate) fn precise_core_distances<D: DataSource + Sync>(
+    source: &D,
+    index: &CpuHnsw,
+    min_cluster_size: NonZeroUsize,
+    weights: Option<&[usize]>,
+) -> Result<Vec<f64>> {
+    let search = CoreSearch::new(index, min_cluster_size, weights);
+    let guard = index.distance_guard();
+    (0..index.len())
+        .map(|point| {
+            let mut others = search
+                .neighbours(source, point)?
+                .into_iter()
+                .map(|(neighbour, _)| {
+                    source
+                        .distance_f64(point, neighbour)
+                        .map(|distance| (neighbour, guard.admit_f64(point, neighbour, distance)))
+                        .map_err(|error| source_error(source, error))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            others.sort_by(|left, right| left.1.total_cmp(&right.1).then(left.0.cmp(&right.0)));
+            Ok(search.select(point, &others))
+        })
+        .collect()
+}
+
+#[cfg(feature = "cpu")]
+fn precise_edge<D: DataSource>(
+    (source, guard): (&D, &DistanceGuard),
+    edge: &CandidateEdge,
+    core: &[f64],
+) -> Result<CandidateEdge> {
+    let (left, right) = (edge.source(), edge.target());
+    let distance = source
+        .distance_f64(left, right)
+        .map_err(|error| source_error(source, error))?;
+    let distance = guard.admit_f64(left, right, distance);
+    let weight = distance.max(core[left]).max(core[right]);
+    Ok(CandidateEdge::new_f64(left, right, weight, edge.sequence()))
+}
+
+#[cfg(feature = "cpu")]
+fn source_error<D: DataSource>(source: &D, error: crate::DataSourceError) -> ChutoroError {
+    ChutoroError::DataSource {
+        data_source: Arc::from(source.name()),
+        error,
+    }
+}
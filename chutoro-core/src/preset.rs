@@ -0,0 +1,92 @@
+//! Named parameter sets for common workloads.
+//!
+//! The values come from the `ef_construction` sweep and regression baselines
+//! in `chutoro-benches`, so new users can start from a measured trade-off
+//! instead of copying numbers out of benchmark code.
+
+use std::num::NonZeroUsize;
+
+use crate::{HierarchyConfig, HnswParams};
+
+/// A recall and runtime trade-off shared by [`HnswParams::preset`] and
+/// [`HierarchyConfig::preset`].
+///
+/// Presets are ordered from cheapest to most thorough. The figures quoted
+/// are from the 1,000-point, eight-blob regression case in `chutoro-benches`:
+/// recall@10 of the index with `ef_search` equal to `ef_construction`, the
+/// ARI of the full pipeline against the generating blobs, and pipeline run
+/// time relative to [`Preset::Balanced`], measured from release builds on
+/// one thread. Recall falls on larger or higher-dimensional data, and the
+/// gaps between presets widen with it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum Preset {
+    /// `M = 8`, `ef_construction = 16`, `min_cluster_size = 5`: the cheapest
+    /// index the `ef_construction` sweep measures. Runs in about 0.4 times
+    /// the time of [`Preset::Balanced`] with recall around 0.96 and ARI
+    /// around 0.88. Suits exploratory runs and well-separated clusters.
+    FastApproximate,
+    /// `M = 16`, `ef_construction = 100`, `min_cluster_size = 10`: the
+    /// `hnswlib` default and the knee of the sweep, with recall and ARI
+    /// above 0.98.
+    #[default]
+    Balanced,
+    /// `M = 24`, `ef_construction = 200`, `min_cluster_size = 15`: about
+    /// 1.7 times the run time and one and a half times the graph memory of
+    /// [`Preset::Balanced`], for data where its recall falls short. Larger
+    /// settings rarely pay off.
+    HighRecall,
+}
+
+impl HnswParams {
+    /// Returns the index parameters for `preset`, with the default seed and
+    /// distance cache.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{HnswParams, Preset};
+    ///
+    /// let params = HnswParams::preset(Preset::HighRecall);
+    /// assert_eq!((params.max_connections(), params.ef_construction()), (24, 200));
+    /// ```
+    #[must_use]
+    pub fn preset(preset: Preset) -> Self {
+        let (max_connections, ef_construction) = match preset {
+            Preset::FastApproximate => (8, 16),
+            Preset::Balanced => (16, 100),
+            Preset::HighRecall => (24, 200),
+        };
+        match Self::new(max_connections, ef_construction) {
+            Ok(params) => params,
+            Err(err) => unreachable!("preset parameters must be valid: {err}"),
+        }
+    }
+}
+
+impl HierarchyConfig {
+    /// Returns the hierarchy configuration for `preset`.
+    ///
+    /// Larger minimum cluster sizes estimate density from more neighbours,
+    /// so a missed neighbour shifts a core distance less, at the cost of
+    /// absorbing clusters smaller than the minimum into noise. Each size
+    /// stays below the matching [`HnswParams::preset`] fan-out so core
+    /// neighbourhoods are reachable through a point's direct links.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{HierarchyConfig, Preset};
+    ///
+    /// let config = HierarchyConfig::preset(Preset::Balanced);
+    /// assert_eq!(config.min_cluster_size().get(), 10);
+    /// ```
+    #[must_use]
+    pub fn preset(preset: Preset) -> Self {
+        let min_cluster_size = match preset {
+            Preset::FastApproximate => NonZeroUsize::new(5),
+            Preset::Balanced => NonZeroUsize::new(10),
+            Preset::HighRecall => NonZeroUsize::new(15),
+        };
+        Self::new(min_cluster_size.unwrap_or(NonZeroUsize::MIN))
+    }
+}
@@ -0,0 +1,182 @@
+//! Post-processing that moves noise points into nearby clusters.
+//!
+//! Density-based clustering leaves points in sparse regions unlabelled, but
+//! many applications need every row to belong to a cluster. A
+//! [`ReassignPolicy`] lets the CPU pipeline search the HNSW index around each
+//! noise point after hierarchy extraction and adopt the label of its nearest
+//! clustered neighbour, recording the outcome in a
+//! [`NoiseReassignmentReport`].
+
+#[cfg(feature = "cpu")]
+use std::num::NonZeroUsize;
+
+#[cfg(feature = "cpu")]
+use rayon::prelude::*;
+#[cfg(feature = "cpu")]
+use tracing::info;
+
+#[cfg(feature = "cpu")]
+use crate::{
+    ClusterId, ClusteringResult, CpuHnsw, DataSource, Result, cpu_pipeline::map_cpu_hnsw_error,
+};
+
+/// Selects how [`crate::ChutoroBuilder::reassign_noise`] relabels noise
+/// points.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::{ChutoroBuilder, ReassignPolicy};
+///
+/// let policy = ReassignPolicy::NearestCluster { max_distance: 0.5 };
+/// let builder = ChutoroBuilder::new().reassign_noise(policy);
+/// assert_eq!(builder.reassign_policy(), Some(policy));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReassignPolicy {
+    /// Adopt the label of the nearest clustered neighbour returned by an HNSW
+    /// search, provided it lies within `max_distance`. Points with no such
+    /// neighbour stay noise.
+    NearestCluster {
+        /// Largest distance at which a noise point joins a cluster; must be
+        /// non-negative.
+        max_distance: f32,
+    },
+}
+
+impl ReassignPolicy {
+    /// Returns why the policy cannot be used, if it is invalid.
+    pub(crate) fn validate(self) -> Option<&'static str> {
+        let Self::NearestCluster { max_distance } = self;
+        (max_distance.is_nan() || max_distance < 0.0)
+            .then_some("max_distance must be a non-negative number")
+    }
+}
+
+/// Counts the noise points a [`ReassignPolicy`] moved into clusters.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::NoiseReassignmentReport;
+///
+/// let report = NoiseReassignmentReport::new(10, 7);
+/// assert_eq!(report.reassigned(), 7);
+/// assert_eq!(report.remaining(), 3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NoiseReassignmentReport {
+    noise_points: usize,
+    reassigned: usize,
+}
+
+impl NoiseReassignmentReport {
+    /// Creates a report for a run that found `noise_points` noise points and
+    /// moved `reassigned` of them into clusters.
+    #[must_use]
+    pub fn new(noise_points: usize, reassigned: usize) -> Self {
+        Self {
+            noise_points,
+            reassigned,
+        }
+    }
+
+    /// Returns how many points were noise before reassignment.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn noise_points(&self) -> usize { self.noise_points }
+
+    /// Returns how many noise points joined a cluster.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn reassigned(&self) -> usize { self.reassigned }
+
+    /// Returns how many points are still noise.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.noise_points.saturating_sub(self.reassigned)
+    }
+}
+
+/// Applies `policy` to `clustering`, searching `index` for each noise point's
+/// nearest clustered neighbour. Returns `clustering` unchanged when no policy
+/// is configured.
+#[cfg(feature = "cpu")]
+pub(crate) fn reassign_noise<D: DataSource + Sync>(
+    source: &D,
+    index: &CpuHnsw,
+    clustering: ClusteringResult,
+    policy: Option<ReassignPolicy>,
+) -> Result<ClusteringResult> {
+    let Some(ReassignPolicy::NearestCluster { max_distance }) = policy else {
+        return Ok(clustering);
+    };
+    let Some(noise) = clustering.noise_label() else {
+        let report = NoiseReassignmentReport::new(0, 0);
+        return Ok(clustering.with_noise_reassignment(Some(report)));
+    };
+    let Some(ef) = NonZeroUsize::new(index.params().ef_construction().min(index.len())) else {
+        return Ok(clustering);
+    };
+    let search = NearestCluster {
+        labels: clustering.assignments(),
+        noise,
+        ef,
+        max_distance,
+    };
+    let noise_points: Vec<usize> = (0..search.labels.len())
+        .filter(|&point| search.labels[point] == noise)
+        .collect();
+    let nearest = noise_points
+        .par_iter()
+        .map(|&point| search.label_for(source, index, point))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut assignments = search.labels.to_vec();
+    let moves: Vec<_> = noise_points
+        .iter()
+        .zip(nearest)
+        .filter_map(|(&point, label)| Some((point, label?)))
+        .collect();
+    for &(point, label) in &moves {
+        assignments[point] = label;
+    }
+    info!(
+        noise_points = noise_points.len(),
+        reassigned = moves.len(),
+        "reassigned noise points to nearby clusters"
+    );
+    let report = NoiseReassignmentReport::new(noise_points.len(), moves.len());
+    Ok(clustering
+        .with_relabelled_noise(assignments)
+        .with_noise_reassignment(Some(report)))
+}
+
+/// Search settings shared by every noise point.
+#[cfg(feature = "cpu")]
+struct NearestCluster<'a> {
+    labels: &'a [ClusterId],
+    noise: ClusterId,
+    ef: NonZeroUsize,
+    max_distance: f32,
+}
+
+#[cfg(feature = "cpu")]
+impl NearestCluster<'_> {
+    /// Returns the label of the nearest clustered neighbour of `point` within
+    /// the distance threshold, if the search finds one.
+    fn label_for<D: DataSource + Sync>(
+        &self,
+        source: &D,
+        index: &CpuHnsw,
+        point: usize,
+    ) -> Result<Option<ClusterId>> {
+        let neighbours = index
+            .search(source, point, self.ef)
+            .map_err(|error| map_cpu_hnsw_error(source, error))?;
+        Ok(neighbours
+            .iter()
+            .take_while(|neighbour| neighbour.distance <= self.max_distance)
+            .map(|neighbour| self.labels[neighbour.id])
+            .find(|&label| label != self.noise))
+    }
+}
@@ -0,0 +1,155 @@
+//! Little-endian field codec behind [`ClusteringResult::to_bytes`].
+//!
+//! [`Encoder`] and [`Decoder`] write and read the primitive fields of the
+//! versioned result format: integers, durations, membership scores,
+//! presence-tagged options, and tagged warnings. Decoding reports the field
+//! being read, so a corrupt buffer names where it went wrong.
+//!
+//! [`ClusteringResult::to_bytes`]: super::ClusteringResult::to_bytes
+
+use std::time::Duration;
+
+use super::persist::ResultDecodeError;
+use crate::Warning;
+
+pub(super) struct Encoder(pub(super) Vec<u8>);
+
+impl Encoder {
+    pub(super) fn u64(&mut self, value: u64) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub(super) fn len(&mut self, value: usize) {
+        // `usize` is at most 64 bits on every supported target.
+        self.u64(value as u64);
+    }
+
+    pub(super) fn warning(&mut self, warning: Warning) {
+        let (tag, first, second) = match warning {
+            Warning::NonFiniteDistancesClamped { count } => (0, count as u64, 0),
+            Warning::NonFiniteEdgesSkipped { count } => (1, count as u64, 0),
+            Warning::DisconnectedComponents { components } => (2, components as u64, 0),
+            Warning::ComponentsBridged {
+                components,
+                bridge_edges,
+            } => (3, components as u64, bridge_edges as u64),
+            Warning::DistanceCachePressure {
+                evictions,
+                capacity,
+            } => (4, evictions, capacity as u64),
+            Warning::TriangleInequalityViolated {
+                violations,
+                sampled,
+            } => (5, violations as u64, sampled as u64),
+        };
+        self.0.push(tag);
+        self.u64(first);
+        self.u64(second);
+    }
+
+    pub(super) fn option<T>(&mut self, value: Option<T>, write: impl FnOnce(&mut Self, T)) {
+        match value {
+            None => self.0.push(0),
+            Some(inner) => {
+                self.0.push(1);
+                write(self, inner);
+            }
+        }
+    }
+}
+
+pub(super) struct Decoder<'a>(pub(super) &'a [u8]);
+
+impl Decoder<'_> {
+    pub(super) fn take<const N: usize>(
+        &mut self,
+        field: &'static str,
+    ) -> Result<[u8; N], ResultDecodeError> {
+        let (head, rest) = self
+            .0
+            .split_first_chunk::<N>()
+            .ok_or(ResultDecodeError::Truncated { field })?;
+        self.0 = rest;
+        Ok(*head)
+    }
+
+    pub(super) fn u64(&mut self, field: &'static str) -> Result<u64, ResultDecodeError> {
+        self.take(field).map(u64::from_le_bytes)
+    }
+
+    pub(super) fn len(&mut self, field: &'static str) -> Result<usize, ResultDecodeError> {
+        let value = self.u64(field)?;
+        usize::try_from(value).map_err(|_| ResultDecodeError::InvalidField { field })
+    }
+
+    /// Reads a warning tag and its two fields; unused fields must be zero.
+    pub(super) fn warning(&mut self) -> Result<Warning, ResultDecodeError> {
+        let field = "warnings";
+        let [tag] = self.take(field)?;
+        let first = self.u64(field)?;
+        let second = self.u64(field)?;
+        let len = |value: u64| {
+            usize::try_from(value).map_err(|_| ResultDecodeError::InvalidField { field })
+        };
+        match (tag, second) {
+            (0, 0) => Ok(Warning::NonFiniteDistancesClamped { count: len(first)? }),
+            (1, 0) => Ok(Warning::NonFiniteEdgesSkipped { count: len(first)? }),
+            (2, 0) => Ok(Warning::DisconnectedComponents {
+                components: len(first)?,
+            }),
+            (3, _) => Ok(Warning::ComponentsBridged {
+                components: len(first)?,
+                bridge_edges: len(second)?,
+            }),
+            (4, _) => Ok(Warning::DistanceCachePressure {
+                evictions: first,
+                capacity: len(second)?,
+            }),
+            (5, _) => Ok(Warning::TriangleInequalityViolated {
+                violations: len(first)?,
+                sampled: len(second)?,
+            }),
+            _ => Err(ResultDecodeError::InvalidField { field }),
+        }
+    }
+
+    pub(super) fn duration(&mut self, field: &'static str) -> Result<Duration, ResultDecodeError> {
+        let secs = self.u64(field)?;
+        let nanos = u32::from_le_bytes(self.take(field)?);
+        if nanos >= 1_000_000_000 {
+            return Err(ResultDecodeError::InvalidField { field });
+        }
+        Ok(Duration::new(secs, nanos))
+    }
+
+    /// Reads `count` scores, each of which must be a finite value in
+    /// `[0, 1]`.
+    pub(super) fn scores(&mut self, count: usize) -> Result<Vec<f32>, ResultDecodeError> {
+        let field = "membership";
+        if count > self.0.len() / 4 {
+            return Err(ResultDecodeError::Truncated { field });
+        }
+        (0..count)
+            .map(|_| {
+                let score = f32::from_bits(u32::from_le_bytes(self.take(field)?));
+                if (0.0..=1.0).contains(&score) {
+                    Ok(score)
+                } else {
+                    Err(ResultDecodeError::InvalidField { field })
+                }
+            })
+            .collect()
+    }
+
+    pub(super) fn option<T>(
+        &mut self,
+        field: &'static str,
+        read: impl FnOnce(&mut Self) -> Result<T, ResultDecodeError>,
+    ) -> Result<Option<T>, ResultDecodeError> {
+        match self.take::<1>(field)? {
+            [0] => Ok(None),
+            [1] => read(self).map(Some),
+            _ => Err(ResultDecodeError::InvalidField { field }),
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Representative points of each cluster for human review.
+//!
+//! A point's membership probability measures how long it stays in its
+//! cluster as the condensed tree is cut at rising density, so the points that
+//! leave last are the cluster's core. Many core points share the top
+//! probability, so ties are broken by how close each point lies to the rest
+//! of the core, which favours points from the middle of the cluster over
+//! points from a dense fringe.
+
+use thiserror::Error;
+
+use super::{ClusterId, ClusteringResult, slot};
+use crate::{DataSource, DataSourceError};
+
+/// Number of highest-probability members each candidate is compared with
+/// when breaking ties between equally persistent points.
+const CORE_REFERENCES: usize = 32;
+
+/// Representative points of one cluster, produced by
+/// [`ClusteringResult::exemplars`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClusterExemplars {
+    /// The cluster the points belong to.
+    pub cluster: ClusterId,
+    /// Point indices, most representative first.
+    pub points: Vec<usize>,
+}
+
+/// Errors raised by [`ClusteringResult::exemplars`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ExemplarError {
+    /// The data source does not hold the points the result assigns.
+    #[error("result assigns {assignments} points but the data source holds {source_len}")]
+    LengthMismatch {
+        /// Number of points in the result.
+        assignments: usize,
+        /// Number of items in the data source.
+        source_len: usize,
+    },
+    /// Measuring a distance failed.
+    #[error(transparent)]
+    DataSource(#[from] DataSourceError),
+}
+
+impl ClusteringResult {
+    /// Returns up to `per_cluster` representative points of each non-noise
+    /// cluster, in cluster order.
+    ///
+    /// Points are ranked by membership probability, so those that persist
+    /// longest in the condensed tree come first. Ties, including every point
+    /// of a result without membership scores, go to the point with the
+    /// smallest total distance to the cluster's most persistent members, and
+    /// then to the lower point index. `source` must be the data that was
+    /// clustered.
+    ///
+    /// # Errors
+    /// Returns [`ExemplarError::LengthMismatch`] when `source` does not hold
+    /// one item per assignment and [`ExemplarError::DataSource`] when a
+    /// distance cannot be computed.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ClusterId, ClusteringResult, DataSource, DataSourceError};
+    ///
+    /// struct Line(Vec<f32>);
+    ///
+    /// impl DataSource for Line {
+    ///     fn len(&self) -> usize { self.0.len() }
+    ///     fn name(&self) -> &str { "line" }
+    ///     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+    ///         Ok((self.0[i] - self.0[j]).abs())
+    ///     }
+    /// }
+    ///
+    /// let source = Line(vec![0.0, 1.0, 2.0, 10.0, 11.0]);
+    /// let result = ClusteringResult::from_assignments(
+    ///     [0, 0, 0, 1, 1].map(ClusterId::new).to_vec(),
+    /// );
+    ///
+    /// let exemplars = result.exemplars(&source, 1)?;
+    /// assert_eq!(exemplars[0].points, [1]);
+    /// assert_eq!(exemplars[1].points, [3]);
+    /// # Ok::<(), chutoro_core::ExemplarError>(())
+    /// ```
+    pub fn exemplars<D: DataSource + ?Sized>(
+        &self,
+        source: &D,
+        per_cluster: usize,
+    ) -> Result<Vec<ClusterExemplars>, ExemplarError> {
+        if source.len() != self.assignments.len() {
+            return Err(ExemplarError::LengthMismatch {
+                assignments: self.assignments.len(),
+                source_len: source.len(),
+            });
+        }
+        let mut members = vec![Vec::new(); self.cluster_count];
+        for (point, id) in self.assignments.iter().enumerate() {
+            if Some(*id) != self.noise_label {
+                members[slot(*id)].push(point);
+            }
+        }
+        members
+            .into_iter()
+            .enumerate()
+            .filter(|(_, points)| !points.is_empty())
+            .map(|(index, points)| {
+                Ok(ClusterExemplars {
+                    cluster: ClusterId::new(index as u64),
+                    points: self.rank_members(source, points, per_cluster)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Orders `points` by descending probability and keeps the first
+    /// `per_cluster`, breaking ties at the cut by centrality.
+    fn rank_members<D: DataSource + ?Sized>(
+        &self,
+        source: &D,
+        mut points: Vec<usize>,
+        per_cluster: usize,
+    ) -> Result<Vec<usize>, DataSourceError> {
+        let probability = |point: usize| {
+            self.membership
+                .as_ref()
+                .and_then(|scores| scores.probabilities().get(point).copied())
+                .unwrap_or(1.0)
+        };
+        points.sort_by(|&a, &b| probability(b).total_cmp(&probability(a)).then(a.cmp(&b)));
+        let kept = per_cluster.min(points.len());
+        let Some(&last) = kept.checked_sub(1).and_then(|index| points.get(index)) else {
+            return Ok(Vec::new());
+        };
+
+        // Only points tied with the last one kept can change the selection.
+        let cut = probability(last);
+        let candidates = points.partition_point(|&point| probability(point) > cut);
+        let tied = points.partition_point(|&point| probability(point) >= cut);
+        let references = &points[..CORE_REFERENCES.min(points.len())];
+        let pairs: Vec<(usize, usize)> = points[candidates..tied]
+            .iter()
+            .flat_map(|&point| references.iter().map(move |&other| (point, other)))
+            .collect();
+        let mut distances = vec![0.0; pairs.len()];
+        source.distance_batch(&pairs, &mut distances)?;
+
+        let mut centrality: Vec<(f32, usize)> = points[candidates..tied]
+            .iter()
+            .zip(distances.chunks(references.len()))
+            .map(|(&point, row)| (row.iter().sum(), point))
+            .collect();
+        centrality.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+        points.truncate(candidates);
+        points.extend(centrality.into_iter().map(|(_, point)| point));
+        points.truncate(per_cluster);
+        Ok(points)
+    }
+}
@@ -0,0 +1,361 @@
+//! Spanning-tree provenance retained on a [`ClusteringResult`].
+//!
+//! Answering "why are these two rows in the same cluster?" needs more than
+//! the condensed tree: it needs the chain of spanning-tree edges that joins
+//! the rows and where the HNSW build found each of them. With
+//! [`crate::ChutoroBuilder::with_edge_provenance`] the CPU pipeline records
+//! the insertion, layer, and search width behind every harvested edge and
+//! keeps the final spanning forest, so [`ClusteringResult::explain_pair`] can
+//! trace the path between any two points on demand.
+
+use std::collections::VecDeque;
+
+use super::{
+    ClusteringResult, ResultDecodeError,
+    codec::{Decoder, Encoder},
+};
+
+/// Where the HNSW build discovered a spanning-tree edge.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::EdgeProvenance;
+///
+/// let provenance = EdgeProvenance::new(4, 1, 32, 7);
+/// assert_eq!(provenance.inserted(), 4);
+/// assert_eq!((provenance.layer(), provenance.ef()), (1, 32));
+/// assert_eq!(provenance.sequence(), 7);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EdgeProvenance {
+    inserted: usize,
+    layer: usize,
+    ef: usize,
+    sequence: u64,
+}
+
+impl EdgeProvenance {
+    /// Records that inserting `inserted`, the `sequence`-th insertion, found
+    /// the edge while searching `layer` with search width `ef`.
+    #[must_use]
+    pub fn new(inserted: usize, layer: usize, ef: usize, sequence: u64) -> Self {
+        Self {
+            inserted,
+            layer,
+            ef,
+            sequence,
+        }
+    }
+
+    /// Returns the point whose insertion discovered the edge.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn inserted(&self) -> usize { self.inserted }
+
+    /// Returns the highest layer whose search found the edge.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn layer(&self) -> usize { self.layer }
+
+    /// Returns the construction search width used on that layer.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn ef(&self) -> usize { self.ef }
+
+    /// Returns the insertion sequence of [`Self::inserted`].
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn sequence(&self) -> u64 { self.sequence }
+}
+
+/// A spanning-tree edge with the mutual-reachability weight it joined its
+/// endpoints at.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExplainedEdge {
+    source: usize,
+    target: usize,
+    weight: f64,
+    provenance: Option<EdgeProvenance>,
+}
+
+impl ExplainedEdge {
+    #[cfg(feature = "cpu")]
+    pub(crate) fn new(
+        (source, target): (usize, usize),
+        weight: f64,
+        provenance: Option<EdgeProvenance>,
+    ) -> Self {
+        Self {
+            source,
+            target,
+            weight,
+            provenance,
+        }
+    }
+
+    /// Returns the lower endpoint.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn source(&self) -> usize { self.source }
+
+    /// Returns the higher endpoint.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn target(&self) -> usize { self.target }
+
+    /// Returns the edge's mutual-reachability weight.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn weight(&self) -> f64 { self.weight }
+
+    /// Returns where the HNSW build found the edge, or `None` for edges it
+    /// did not produce, such as bridges added by component repair.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn provenance(&self) -> Option<EdgeProvenance> { self.provenance }
+}
+
+/// The spanning-tree path joining two points, from
+/// [`ClusteringResult::explain_pair`].
+///
+/// # Examples
+/// ```rust,ignore
+/// let explanation = result.explain_pair(3, 17).expect("provenance was recorded");
+/// for edge in explanation.path() {
+///     println!("{} -- {} at {}: {:?}", edge.source(), edge.target(), edge.weight(), edge.provenance());
+/// }
+/// println!("separate above lambda {}", explanation.lambda());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PairExplanation {
+    path: Vec<ExplainedEdge>,
+    lambda: f64,
+}
+
+impl PairExplanation {
+    /// Returns the edges from the first point to the second, in order. The
+    /// path is empty when the points coincide or share no tree.
+    #[must_use]
+    pub fn path(&self) -> &[ExplainedEdge] {
+        &self.path
+    }
+
+    /// Returns the density at which the points stop sharing a cluster: the
+    /// reciprocal of the heaviest weight on the path.
+    ///
+    /// The value is infinite for a point explained against itself and `0.0`
+    /// for points in different trees, which never share a cluster.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn lambda(&self) -> f64 { self.lambda }
+
+    /// Returns the heaviest edge on the path, whose removal separates the
+    /// points.
+    #[must_use]
+    pub fn bottleneck(&self) -> Option<&ExplainedEdge> {
+        self.path
+            .iter()
+            .max_by(|left, right| left.weight.total_cmp(&right.weight))
+    }
+
+    /// Returns whether the points share a tree of the spanning forest.
+    #[must_use]
+    pub fn connected(&self) -> bool {
+        self.lambda > 0.0
+    }
+}
+
+/// The spanning forest of a run, with each edge's provenance.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct ExplainedForest {
+    points: usize,
+    edges: Vec<ExplainedEdge>,
+}
+
+impl ExplainedForest {
+    /// Wraps the forest `edges` over `points` points.
+    #[cfg(feature = "cpu")]
+    pub(crate) fn new(points: usize, edges: Vec<ExplainedEdge>) -> Self {
+        Self { points, edges }
+    }
+
+    /// Returns the number of points the forest spans.
+    #[cfg(feature = "serde")]
+    #[rustfmt::skip]
+    pub(super) fn point_count(&self) -> usize { self.points }
+
+    /// Returns whether every edge joins two distinct points of the forest
+    /// with a usable weight.
+    pub(super) fn is_valid(&self) -> bool {
+        self.edges.len() < self.points.max(1)
+            && self.edges.iter().all(|edge| {
+                edge.source < edge.target
+                    && edge.target < self.points
+                    && !edge.weight.is_nan()
+                    && edge.weight >= 0.0
+            })
+    }
+
+    fn explain(&self, from: usize, to: usize) -> Option<PairExplanation> {
+        if from >= self.points || to >= self.points {
+            return None;
+        }
+        if from == to {
+            return Some(PairExplanation {
+                path: Vec::new(),
+                lambda: f64::INFINITY,
+            });
+        }
+        let Some(path) = self.path(from, to) else {
+            return Some(PairExplanation {
+                path: Vec::new(),
+                lambda: 0.0,
+            });
+        };
+        let heaviest = path.iter().map(|edge| edge.weight).fold(0.0_f64, f64::max);
+        let lambda = if heaviest > 0.0 {
+            1.0 / heaviest
+        } else {
+            f64::INFINITY
+        };
+        Some(PairExplanation { path, lambda })
+    }
+
+    /// Breadth-first search from `from`, returning the edges leading to `to`.
+    fn path(&self, from: usize, to: usize) -> Option<Vec<ExplainedEdge>> {
+        let mut adjacent = vec![Vec::new(); self.points];
+        for (position, edge) in self.edges.iter().enumerate() {
+            adjacent[edge.source].push((edge.target, position));
+            adjacent[edge.target].push((edge.source, position));
+        }
+        let mut reached_by: Vec<Option<(usize, usize)>> = vec![None; self.points];
+        let mut queue = VecDeque::from([from]);
+        while let Some(point) = queue.pop_front() {
+            if point == to {
+                break;
+            }
+            let unreached: Vec<_> = adjacent[point]
+                .iter()
+                .filter(|&&(next, _)| next != from && reached_by[next].is_none())
+                .copied()
+                .collect();
+            for (next, position) in unreached {
+                reached_by[next] = Some((point, position));
+                queue.push_back(next);
+            }
+        }
+        let mut path = Vec::new();
+        let mut point = to;
+        while point != from {
+            let (previous, position) = reached_by[point]?;
+            path.push(self.edges[position]);
+            point = previous;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Writes the forest; the point count is the result's.
+    pub(super) fn encode(&self, out: &mut Encoder) {
+        out.len(self.edges.len());
+        for edge in &self.edges {
+            out.len(edge.source);
+            out.len(edge.target);
+            out.0
+                .extend_from_slice(&edge.weight.to_bits().to_le_bytes());
+            out.option(edge.provenance, |out, provenance| {
+                out.len(provenance.inserted);
+                out.len(provenance.layer);
+                out.len(provenance.ef);
+                out.u64(provenance.sequence);
+            });
+        }
+    }
+
+    /// Reads a forest over `points` points written by [`Self::encode`].
+    pub(super) fn decode(
+        input: &mut Decoder<'_>,
+        points: usize,
+    ) -> Result<Self, ResultDecodeError> {
+        let field = "explained_forest";
+        let count = input.len(field)?;
+        // A forest has fewer edges than points, which bounds the allocation.
+        if count >= points.max(1) {
+            return Err(ResultDecodeError::InvalidField { field });
+        }
+        let edges = (0..count)
+            .map(|_| {
+                let source = input.len(field)?;
+                let target = input.len(field)?;
+                let weight = f64::from_bits(u64::from_le_bytes(input.take(field)?));
+                let provenance = input.option(field, |input| {
+                    Ok(EdgeProvenance {
+                        inserted: input.len(field)?,
+                        layer: input.len(field)?,
+                        ef: input.len(field)?,
+                        sequence: input.u64(field)?,
+                    })
+                })?;
+                Ok(ExplainedEdge {
+                    source,
+                    target,
+                    weight,
+                    provenance,
+                })
+            })
+            .collect::<Result<_, ResultDecodeError>>()?;
+        let forest = Self { points, edges };
+        if forest.is_valid() {
+            Ok(forest)
+        } else {
+            Err(ResultDecodeError::InvalidField { field })
+        }
+    }
+}
+
+// Weights are checked to be non-NaN wherever a forest is built or decoded,
+// so equality is reflexive.
+impl Eq for ExplainedForest {}
+
+impl ClusteringResult {
+    /// Explains why two points do or do not share a cluster, when the run
+    /// recorded edge provenance.
+    ///
+    /// Returns the path of spanning-tree edges joining `from` to `to`, each
+    /// with where the HNSW build found it, and the density at which the
+    /// single-linkage hierarchy separates the points. Returns `None` when
+    /// either point is out of range or the result holds no provenance: runs
+    /// without [`crate::ChutoroBuilder::with_edge_provenance`], and sampled
+    /// or deduplicated runs, whose spanning forest does not cover every
+    /// point.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ClusterId, ClusteringResult};
+    ///
+    /// let result = ClusteringResult::from_assignments(vec![ClusterId::new(0); 2]);
+    /// assert!(result.explain_pair(0, 1).is_none());
+    /// ```
+    #[must_use]
+    pub fn explain_pair(&self, from: usize, to: usize) -> Option<PairExplanation> {
+        self.explained_forest.as_ref()?.explain(from, to)
+    }
+
+    /// Returns the spanning forest with each edge's provenance, when the run
+    /// recorded it.
+    #[must_use]
+    pub fn explained_edges(&self) -> Option<&[ExplainedEdge]> {
+        self.explained_forest
+            .as_ref()
+            .map(|forest| forest.edges.as_slice())
+    }
+
+    #[cfg(feature = "cpu")]
+    pub(crate) fn with_explained_forest(mut self, forest: Option<ExplainedForest>) -> Self {
+        self.explained_forest = forest;
+        self
+    }
+}
@@ -0,0 +1,313 @@
+//! Navigable cluster hierarchy retained on a [`ClusteringResult`].
+//!
+//! Hierarchy extraction picks one flat clustering out of the condensed tree,
+//! but exploring other granularities should not mean rerunning the pipeline.
+//! The CPU pipeline keeps the tree's structure, the density (`lambda`) at
+//! which each cluster was born, and the density at which each point left it,
+//! so [`ClusterHierarchy::cut_at_lambda`] and [`ClusterHierarchy::descend`]
+//! can label the points at any depth on demand.
+
+mod rows;
+
+use thiserror::Error;
+
+use super::{ClusterId, ClusteringResult};
+use crate::{
+    HierarchyComparison,
+    hierarchy_compare::{self, TreeShape},
+};
+
+/// What a row of the hierarchy attaches to its parent cluster.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(in crate::result) enum Member {
+    Point(usize),
+    Cluster(usize),
+}
+
+/// One cluster of a [`ClusterHierarchy`].
+#[derive(Clone, Debug, PartialEq)]
+struct Node {
+    parent: Option<usize>,
+    birth_lambda: f64,
+    children: Vec<usize>,
+    /// Points that left this cluster, with the density at which they left.
+    exits: Vec<(usize, f64)>,
+}
+
+/// Condensed cluster tree kept from hierarchy extraction.
+///
+/// Cluster ids are those of [`crate::CondensedTree`]: dense indices from `0`,
+/// with every child numbered after its parent.
+///
+/// # Examples
+/// ```rust,ignore
+/// let hierarchy = result.hierarchy().expect("CPU runs keep the hierarchy");
+/// let coarse = hierarchy.cut_at_lambda(0.5)?;
+/// let finer = hierarchy.descend(coarse.clusters()[0])?;
+/// println!("{} subclusters", finer.clusters().len());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        into = "super::serde_repr::RawHierarchy",
+        try_from = "super::serde_repr::RawHierarchy"
+    )
+)]
+pub struct ClusterHierarchy {
+    points: usize,
+    nodes: Vec<Node>,
+    roots: Vec<usize>,
+}
+
+// Lambdas are reciprocals of finite, non-negative distances, so they are
+// never NaN and equality is reflexive.
+impl Eq for ClusterHierarchy {}
+
+/// A flat labelling produced by navigating a [`ClusterHierarchy`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HierarchyCut {
+    result: ClusteringResult,
+    clusters: Vec<usize>,
+}
+
+impl HierarchyCut {
+    /// Returns the labelling. Points outside every cluster of the cut share
+    /// the result's noise label.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn result(&self) -> &ClusteringResult { &self.result }
+
+    /// Returns the hierarchy cluster behind each label, indexed by label.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn clusters(&self) -> &[usize] { &self.clusters }
+
+    /// Consumes the cut, returning its labelling.
+    #[must_use]
+    pub fn into_result(self) -> ClusteringResult {
+        self.result
+    }
+}
+
+/// Errors raised while navigating a [`ClusterHierarchy`].
+#[derive(Clone, Copy, Debug, Error, PartialEq)]
+#[non_exhaustive]
+pub enum HierarchyCutError {
+    /// The cluster id is not part of the hierarchy.
+    #[error("cluster {cluster} is not in a hierarchy of {cluster_count} clusters")]
+    UnknownCluster {
+        /// The requested cluster.
+        cluster: usize,
+        /// Number of clusters in the hierarchy.
+        cluster_count: usize,
+    },
+    /// The density is negative or NaN.
+    #[error("lambda {lambda} must be a non-negative number")]
+    InvalidLambda {
+        /// The requested density.
+        lambda: f64,
+    },
+}
+
+impl ClusteringResult {
+    /// Returns the cluster hierarchy the labels were selected from, when the
+    /// result came from the CPU pipeline's hierarchy extraction.
+    ///
+    /// Sampled runs and custom [`crate::HierarchyStage`] implementations
+    /// return `None`, since the tree would not cover every point.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ClusterId, ClusteringResult};
+    ///
+    /// let result = ClusteringResult::from_assignments(vec![ClusterId::new(0)]);
+    /// assert!(result.hierarchy().is_none());
+    /// ```
+    #[must_use]
+    pub fn hierarchy(&self) -> Option<&ClusterHierarchy> {
+        self.hierarchy.as_ref()
+    }
+
+    #[cfg(feature = "cpu")]
+    pub(crate) fn with_hierarchy(mut self, hierarchy: Option<ClusterHierarchy>) -> Self {
+        self.hierarchy = hierarchy;
+        self
+    }
+}
+
+impl ClusterHierarchy {
+    /// Returns the number of points the hierarchy labels.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn point_count(&self) -> usize { self.points }
+
+    /// Returns the number of clusters in the hierarchy.
+    #[must_use]
+    pub fn cluster_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the clusters without a parent, one per connected component
+    /// large enough to form a cluster.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn roots(&self) -> &[usize] { &self.roots }
+
+    /// Returns the parent of `cluster`, or `None` for roots and unknown ids.
+    #[must_use]
+    pub fn parent(&self, cluster: usize) -> Option<usize> {
+        self.nodes.get(cluster)?.parent
+    }
+
+    /// Returns the clusters `cluster` splits into, or an empty slice for
+    /// leaves and unknown ids.
+    #[must_use]
+    pub fn children(&self, cluster: usize) -> &[usize] {
+        self.nodes
+            .get(cluster)
+            .map_or(&[], |node| node.children.as_slice())
+    }
+
+    /// Returns the density (`1 / distance`) at which `cluster` split from its
+    /// parent; roots report `0.0`.
+    #[must_use]
+    pub fn birth_lambda(&self, cluster: usize) -> Option<f64> {
+        self.nodes.get(cluster).map(|node| node.birth_lambda)
+    }
+
+    /// Labels the points by the clusters alive at density `lambda`.
+    ///
+    /// A cluster is alive from its birth until it splits, and a point belongs
+    /// to it while the point's own exit density is at least `lambda`, so
+    /// raising `lambda` yields finer clusters and more noise. `0.0` returns
+    /// the roots, and clusters whose points have all left are omitted.
+    ///
+    /// # Errors
+    /// Returns [`HierarchyCutError::InvalidLambda`] when `lambda` is negative
+    /// or NaN.
+    pub fn cut_at_lambda(&self, lambda: f64) -> Result<HierarchyCut, HierarchyCutError> {
+        if lambda.is_nan() || lambda < 0.0 {
+            return Err(HierarchyCutError::InvalidLambda { lambda });
+        }
+        let mut alive = Vec::new();
+        let mut stack: Vec<usize> = self.roots.iter().rev().copied().collect();
+        while let Some(cluster) = stack.pop() {
+            let children = &self.nodes[cluster].children;
+            let split = !children.is_empty()
+                && children
+                    .iter()
+                    .all(|&child| self.nodes[child].birth_lambda <= lambda);
+            if split {
+                stack.extend(children.iter().rev());
+            } else {
+                alive.push(cluster);
+            }
+        }
+        Ok(self.cut(&alive, lambda))
+    }
+
+    /// Labels the points by the clusters `cluster` splits into.
+    ///
+    /// Every point that reaches a child cluster is labelled with it, however
+    /// dense, while the points that left `cluster` before it split, and
+    /// those outside it, are noise. A leaf has nothing below it, so it is
+    /// returned as a single cluster.
+    ///
+    /// # Errors
+    /// Returns [`HierarchyCutError::UnknownCluster`] when `cluster` is not in
+    /// the hierarchy.
+    pub fn descend(&self, cluster: usize) -> Result<HierarchyCut, HierarchyCutError> {
+        let node = self
+            .nodes
+            .get(cluster)
+            .ok_or(HierarchyCutError::UnknownCluster {
+                cluster,
+                cluster_count: self.nodes.len(),
+            })?;
+        Ok(if node.children.is_empty() {
+            self.cut(&[cluster], 0.0)
+        } else {
+            self.cut(&node.children, 0.0)
+        })
+    }
+
+    /// Compares this hierarchy with `other`, such as the hierarchy of a run
+    /// with different parameters over the same points.
+    ///
+    /// See [`HierarchyComparison`] for the score; the matches pair cluster
+    /// ids of `self` (`left`) with those of `other` (`right`).
+    ///
+    /// # Examples
+    /// ```rust,ignore
+    /// let comparison = baseline_hierarchy.compare(retuned_hierarchy);
+    /// assert!((0.0..=1.0).contains(&comparison.similarity()));
+    /// ```
+    #[must_use]
+    pub fn compare(&self, other: &ClusterHierarchy) -> HierarchyComparison {
+        hierarchy_compare::compare(&self.shape(), &other.shape())
+    }
+
+    fn shape(&self) -> TreeShape {
+        TreeShape {
+            parents: self.nodes.iter().map(|node| node.parent).collect(),
+            exits: self
+                .nodes
+                .iter()
+                .enumerate()
+                .flat_map(|(cluster, node)| {
+                    node.exits.iter().map(move |&(point, _)| (point, cluster))
+                })
+                .collect(),
+        }
+    }
+
+    /// Labels the points that leave each of `clusters`, or any cluster below
+    /// it, at a density of at least `lambda`. Empty clusters get no label.
+    fn cut(&self, clusters: &[usize], lambda: f64) -> HierarchyCut {
+        let mut labels: Vec<Option<u64>> = vec![None; self.points];
+        let mut kept = Vec::with_capacity(clusters.len());
+        for &top in clusters {
+            let members = self.members(top, lambda);
+            if members.is_empty() {
+                continue;
+            }
+            let label = kept.len() as u64;
+            for point in members {
+                labels[point] = Some(label);
+            }
+            kept.push(top);
+        }
+        let noise = ClusterId::new(kept.len() as u64);
+        let has_noise = labels.contains(&None);
+        let assignments = labels
+            .into_iter()
+            .map(|label| label.map_or(noise, ClusterId::new))
+            .collect();
+        let mut result = ClusteringResult::from_assignments(assignments);
+        result.noise_label = has_noise.then_some(noise);
+        HierarchyCut {
+            result,
+            clusters: kept,
+        }
+    }
+
+    /// Returns the points that leave `top`, or any cluster below it, at a
+    /// density of at least `lambda`.
+    fn members(&self, top: usize, lambda: f64) -> Vec<usize> {
+        let mut members = Vec::new();
+        let mut stack = vec![top];
+        while let Some(cluster) = stack.pop() {
+            let node = &self.nodes[cluster];
+            stack.extend(&node.children);
+            members.extend(
+                node.exits
+                    .iter()
+                    .filter(|(_, exit)| *exit >= lambda)
+                    .map(|&(point, _)| point),
+            );
+        }
+        members
+    }
+}
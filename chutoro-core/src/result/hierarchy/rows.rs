@@ -0,0 +1,147 @@
+//! Row view of a [`ClusterHierarchy`], through which it is built from a
+//! condensed tree and exported to and imported from persisted results.
+
+use super::{ClusterHierarchy, Member, Node};
+use crate::result::{
+    ResultDecodeError,
+    codec::{Decoder, Encoder},
+};
+
+impl ClusterHierarchy {
+    /// Copies the navigable parts of a condensed tree over `points` points.
+    #[cfg(feature = "cpu")]
+    pub(crate) fn from_condensed(tree: crate::CondensedTree<'_>, points: usize) -> Option<Self> {
+        let rows = tree.rows().map(|row| {
+            let member = match row.child() {
+                crate::CondensedChild::Point(point) => Member::Point(point),
+                crate::CondensedChild::Cluster(child) => Member::Cluster(child),
+            };
+            (row.parent(), member, row.lambda())
+        });
+        Self::from_rows(points, tree.cluster_count(), rows)
+    }
+
+    /// Rebuilds a hierarchy over `points` points from `cluster_count`
+    /// clusters and the rows attaching points and child clusters to their
+    /// parents. Returns `None` unless the rows describe a forest in which
+    /// children follow their parents and each point leaves at most once.
+    pub(in crate::result) fn from_rows(
+        points: usize,
+        cluster_count: usize,
+        rows: impl IntoIterator<Item = (usize, Member, f64)>,
+    ) -> Option<Self> {
+        let mut nodes = vec![
+            Node {
+                parent: None,
+                birth_lambda: 0.0,
+                children: Vec::new(),
+                exits: Vec::new(),
+            };
+            cluster_count
+        ];
+        let mut seen = vec![false; points];
+        for (parent, member, lambda) in rows {
+            if parent >= cluster_count || lambda.is_nan() || lambda < 0.0 {
+                return None;
+            }
+            attach(&mut nodes, &mut seen, (parent, member, lambda))?;
+        }
+        let roots = (0..cluster_count)
+            .filter(|&cluster| nodes[cluster].parent.is_none())
+            .collect();
+        Some(Self {
+            points,
+            nodes,
+            roots,
+        })
+    }
+
+    /// Returns every row, children before points within each cluster.
+    pub(in crate::result) fn rows(&self) -> impl Iterator<Item = (usize, Member, f64)> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .flat_map(move |(parent, node)| {
+                let children = node.children.iter().map(move |&child| {
+                    let lambda = self.nodes[child].birth_lambda;
+                    (parent, Member::Cluster(child), lambda)
+                });
+                let exits = node
+                    .exits
+                    .iter()
+                    .map(move |&(point, lambda)| (parent, Member::Point(point), lambda));
+                children.chain(exits)
+            })
+    }
+
+    /// Writes the hierarchy; the point count is the result's.
+    pub(in crate::result) fn encode(&self, out: &mut Encoder) {
+        out.len(self.nodes.len());
+        out.len(self.rows().count());
+        for (parent, member, lambda) in self.rows() {
+            out.len(parent);
+            let (tag, index) = match member {
+                Member::Point(point) => (0, point),
+                Member::Cluster(child) => (1, child),
+            };
+            out.0.push(tag);
+            out.len(index);
+            out.0.extend_from_slice(&lambda.to_bits().to_le_bytes());
+        }
+    }
+
+    /// Reads a hierarchy over `points` points written by [`Self::encode`].
+    pub(in crate::result) fn decode(
+        input: &mut Decoder<'_>,
+        points: usize,
+    ) -> Result<Self, ResultDecodeError> {
+        let field = "hierarchy";
+        let cluster_count = input.len(field)?;
+        let rows: Vec<_> = (0..input.len(field)?)
+            .map(|_| {
+                let parent = input.len(field)?;
+                let [tag] = input.take(field)?;
+                let index = input.len(field)?;
+                let lambda = f64::from_bits(u64::from_le_bytes(input.take(field)?));
+                let member = match tag {
+                    0 => Member::Point(index),
+                    1 => Member::Cluster(index),
+                    _ => return Err(ResultDecodeError::InvalidField { field }),
+                };
+                Ok((parent, member, lambda))
+            })
+            .collect::<Result<_, _>>()?;
+        // Every cluster holds at least one row, which bounds the allocation.
+        if cluster_count > rows.len() {
+            return Err(ResultDecodeError::InvalidField { field });
+        }
+        Self::from_rows(points, cluster_count, rows)
+            .ok_or(ResultDecodeError::InvalidField { field })
+    }
+}
+
+/// Attaches a row's member to its parent, returning `None` when a point leaves twice
+/// or a cluster is out of order or already attached.
+fn attach(
+    nodes: &mut [Node],
+    seen: &mut [bool],
+    (parent, member, lambda): (usize, Member, f64),
+) -> Option<()> {
+    match member {
+        Member::Point(point) => {
+            if std::mem::replace(seen.get_mut(point)?, true) {
+                return None;
+            }
+            nodes[parent].exits.push((point, lambda));
+        }
+        Member::Cluster(child) => {
+            let node = nodes.get_mut(child).filter(|_| child > parent)?;
+            if node.parent.replace(parent).is_some() {
+                return None;
+            }
+            node.birth_lambda = lambda;
+            nodes[parent].children.push(child);
+        }
+    }
+    Some(())
+}
@@ -0,0 +1,47 @@
+//! Cluster labels keyed by caller-supplied row identifiers.
+
+use std::collections::BTreeMap;
+
+use super::{ClusterId, ClusteringResult};
+use crate::{IdMap, RowIdError};
+
+impl ClusteringResult {
+    /// Returns each point's label keyed by its row identifier.
+    ///
+    /// Noise points keep their [`Self::noise_label`], as in
+    /// [`Self::assignments`]. `ids` must describe the rows that were
+    /// clustered, typically via [`crate::DataSource::row_ids`].
+    ///
+    /// # Errors
+    /// Returns [`RowIdError::LengthMismatch`] when `ids` does not hold one
+    /// identifier per assignment.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ClusterId, ClusteringResult, IdMap};
+    ///
+    /// let ids = IdMap::new(vec!["b7".into(), "a3".into()])?;
+    /// let result = ClusteringResult::from_assignments(vec![ClusterId::new(0), ClusterId::new(1)]);
+    ///
+    /// let labels = result.labels_by_id(&ids)?;
+    /// assert_eq!(labels["a3"], ClusterId::new(1));
+    /// # Ok::<(), chutoro_core::RowIdError>(())
+    /// ```
+    pub fn labels_by_id<'a>(
+        &self,
+        ids: &'a IdMap,
+    ) -> Result<BTreeMap<&'a str, ClusterId>, RowIdError> {
+        if ids.len() != self.assignments.len() {
+            return Err(RowIdError::LengthMismatch {
+                ids: ids.len(),
+                rows: self.assignments.len(),
+            });
+        }
+        Ok(ids
+            .ids()
+            .iter()
+            .map(String::as_str)
+            .zip(self.assignments.iter().copied())
+            .collect())
+    }
+}
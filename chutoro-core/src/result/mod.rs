@@ -6,6 +6,34 @@
 use std::collections::HashSet;
 use thiserror::Error;
 
+use crate::{
+    Backend, connectivity::ConnectivityReport, distance_policy::DistancePolicyReport,
+    membership::MembershipScores, reassign::NoiseReassignmentReport, sample::SamplingReport,
+    seed::SeedReport, seed_labels::SeedLabelReport, sparsify::SparsificationReport,
+    timings::StageTimings, warning::Warning,
+};
+
+mod codec;
+mod exemplars;
+mod explain;
+mod hierarchy;
+mod ids;
+mod noise;
+mod parameters;
+mod persist;
+mod reports;
+#[cfg(feature = "serde")]
+mod serde_repr;
+mod stability;
+
+pub use exemplars::{ClusterExemplars, ExemplarError};
+pub(crate) use explain::ExplainedForest;
+pub use explain::{EdgeProvenance, ExplainedEdge, PairExplanation};
+pub use hierarchy::{ClusterHierarchy, HierarchyCut, HierarchyCutError};
+pub use parameters::ParameterReport;
+pub use persist::ResultDecodeError;
+pub use stability::{ClusterPersistence, PERSISTENCE_THRESHOLD, RunAgreement, StabilityReport};
+
 const USIZE_MAX_U64: u64 = usize::MAX as u64;
 
 #[inline]
@@ -13,6 +41,12 @@ fn exceeds_pointer_width(value: u64) -> bool {
     value == USIZE_MAX_U64 || usize::try_from(value).is_err()
 }
 
+/// Converts an identifier to an index; construction and decoding reject
+/// identifiers that do not fit `usize`.
+fn slot(id: ClusterId) -> usize {
+    id.get() as usize
+}
+
 /// Represents the output of a [`crate::Chutoro::run`] invocation.
 ///
 /// # Examples
@@ -24,9 +58,30 @@ fn exceeds_pointer_width(value: u64) -> bool {
 /// assert_eq!(result.cluster_count(), 2);
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "serde_repr::RawClusteringResult")
+)]
 pub struct ClusteringResult {
     assignments: Vec<ClusterId>,
     cluster_count: usize,
+    sparsification: Option<SparsificationReport>,
+    connectivity: Option<ConnectivityReport>,
+    timings: Option<StageTimings>,
+    noise_label: Option<ClusterId>,
+    membership: Option<MembershipScores>,
+    sampling: Option<SamplingReport>,
+    distance_policy: Option<DistancePolicyReport>,
+    seeds: Option<SeedReport>,
+    parameters: Option<ParameterReport>,
+    distance_evaluations: Option<u64>,
+    noise_reassignment: Option<NoiseReassignmentReport>,
+    warnings: Vec<Warning>,
+    backend: Option<Backend>,
+    seed_labels: Option<SeedLabelReport>,
+    hierarchy: Option<ClusterHierarchy>,
+    explained_forest: Option<ExplainedForest>,
 }
 
 /// Error returned when cluster identifiers are not contiguous starting at zero.
@@ -97,6 +152,22 @@ impl ClusteringResult {
             return Ok(Self {
                 assignments,
                 cluster_count: 0,
+                sparsification: None,
+                connectivity: None,
+                timings: None,
+                noise_label: None,
+                membership: None,
+                sampling: None,
+                distance_policy: None,
+                seeds: None,
+                parameters: None,
+                distance_evaluations: None,
+                noise_reassignment: None,
+                warnings: Vec::new(),
+                backend: None,
+                seed_labels: None,
+                hierarchy: None,
+                explained_forest: None,
             });
         }
 
@@ -137,6 +208,22 @@ impl ClusteringResult {
         Ok(Self {
             assignments,
             cluster_count: seen.len(),
+            sparsification: None,
+            connectivity: None,
+            timings: None,
+            noise_label: None,
+            membership: None,
+            sampling: None,
+            distance_policy: None,
+            seeds: None,
+            parameters: None,
+            distance_evaluations: None,
+            noise_reassignment: None,
+            warnings: Vec::new(),
+            backend: None,
+            seed_labels: None,
+            hierarchy: None,
+            explained_forest: None,
         })
     }
 
@@ -154,6 +241,25 @@ impl ClusteringResult {
         &self.assignments
     }
 
+    /// Returns the cluster label of every point as an `ndarray` vector, in
+    /// insertion order.
+    ///
+    /// Noise points keep their [`Self::noise_label`], so the array can be
+    /// compared element-wise with [`Self::assignments`].
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ClusteringResult, ClusterId};
+    ///
+    /// let result = ClusteringResult::from_assignments(vec![ClusterId::new(1), ClusterId::new(0)]);
+    /// assert_eq!(result.labels_as_array1(), ndarray::array![1, 0]);
+    /// ```
+    #[cfg(feature = "ndarray")]
+    #[must_use]
+    pub fn labels_as_array1(&self) -> ndarray::Array1<u64> {
+        self.assignments.iter().map(|id| id.get()).collect()
+    }
+
     /// Counts how many distinct clusters exist within the assignments.
     ///
     /// # Examples
@@ -179,6 +285,11 @@ impl ClusteringResult {
 /// assert_eq!(id.get(), 4);
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct ClusterId(u64);
 
 impl ClusterId {
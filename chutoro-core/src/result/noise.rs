@@ -0,0 +1,97 @@
+//! Noise labelling and membership scores on a [`ClusteringResult`].
+//!
+//! Hierarchy extraction may set aside a noise label for points that belong
+//! to no cluster and attach per-point membership probabilities and outlier
+//! scores. Results built directly from assignments carry neither.
+
+use crate::membership::MembershipScores;
+
+use super::{ClusterId, ClusteringResult};
+
+impl ClusteringResult {
+    /// Returns the label assigned to noise points, when the run classified
+    /// any point as noise.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ClusteringResult, ClusterId};
+    ///
+    /// let result = ClusteringResult::from_assignments(vec![ClusterId::new(0)]);
+    /// assert!(result.noise_label().is_none());
+    /// ```
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn noise_label(&self) -> Option<ClusterId> { self.noise_label }
+
+    /// Counts the points assigned to the noise label.
+    #[must_use]
+    pub fn noise_count(&self) -> usize {
+        self.noise_label.map_or(0, |noise| {
+            self.assignments.iter().filter(|id| **id == noise).count()
+        })
+    }
+
+    /// Returns the fraction of points classified as noise, or `0.0` for an
+    /// empty result.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ClusteringResult, ClusterId};
+    ///
+    /// let result = ClusteringResult::from_assignments(vec![ClusterId::new(0)]);
+    /// assert_eq!(result.noise_fraction(), 0.0);
+    /// ```
+    #[must_use]
+    pub fn noise_fraction(&self) -> f64 {
+        if self.assignments.is_empty() {
+            return 0.0;
+        }
+        self.noise_count() as f64 / self.assignments.len() as f64
+    }
+
+    #[cfg(feature = "cpu")]
+    pub(crate) fn with_noise_label(mut self, noise_label: Option<ClusterId>) -> Self {
+        self.noise_label = noise_label;
+        self
+    }
+
+    /// Replaces the assignments after noise points were relabelled, retiring
+    /// the noise label once no point carries it so identifiers stay
+    /// contiguous.
+    #[cfg(feature = "cpu")]
+    pub(crate) fn with_relabelled_noise(mut self, mut assignments: Vec<ClusterId>) -> Self {
+        if let Some(noise) = self
+            .noise_label
+            .filter(|noise| !assignments.contains(noise))
+        {
+            for id in assignments.iter_mut().filter(|id| **id > noise) {
+                *id = ClusterId::new(id.get() - 1);
+            }
+            self.noise_label = None;
+            self.cluster_count -= 1;
+        }
+        self.assignments = assignments;
+        self
+    }
+
+    /// Returns per-point membership probabilities and outlier scores, when
+    /// the result came from hierarchy extraction.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ClusteringResult, ClusterId};
+    ///
+    /// let result = ClusteringResult::from_assignments(vec![ClusterId::new(0)]);
+    /// assert!(result.membership().is_none());
+    /// ```
+    #[must_use]
+    pub fn membership(&self) -> Option<&MembershipScores> {
+        self.membership.as_ref()
+    }
+
+    #[cfg(feature = "cpu")]
+    pub(crate) fn with_membership(mut self, membership: Option<MembershipScores>) -> Self {
+        self.membership = membership;
+        self
+    }
+}
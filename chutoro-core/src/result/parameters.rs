@@ -0,0 +1,83 @@
+//! The clustering parameters a run was configured with.
+//!
+//! Recorded alongside the seeds so a persisted [`ClusteringResult`] says how
+//! it was produced, not only what it contains.
+
+use std::num::NonZeroUsize;
+
+use super::ClusteringResult;
+
+/// The parameters that shaped a clustering run.
+///
+/// # Examples
+/// ```
+/// use std::num::NonZeroUsize;
+///
+/// use chutoro_core::ParameterReport;
+///
+/// let min_cluster_size = NonZeroUsize::new(5).expect("non-zero");
+/// let report = ParameterReport::new(min_cluster_size, 16, 64);
+/// assert_eq!(report.min_cluster_size().get(), 5);
+/// assert_eq!(report.max_connections(), 16);
+/// assert_eq!(report.ef_construction(), 64);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParameterReport {
+    min_cluster_size: NonZeroUsize,
+    max_connections: usize,
+    ef_construction: usize,
+}
+
+impl ParameterReport {
+    /// Creates a report from the minimum cluster size and HNSW parameters.
+    #[must_use]
+    pub fn new(
+        min_cluster_size: NonZeroUsize,
+        max_connections: usize,
+        ef_construction: usize,
+    ) -> Self {
+        Self {
+            min_cluster_size,
+            max_connections,
+            ef_construction,
+        }
+    }
+
+    /// Returns the minimum cluster size.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn min_cluster_size(&self) -> NonZeroUsize { self.min_cluster_size }
+
+    /// Returns the HNSW fan-out (`M`).
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn max_connections(&self) -> usize { self.max_connections }
+
+    /// Returns the HNSW construction beam width.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn ef_construction(&self) -> usize { self.ef_construction }
+}
+
+impl ClusteringResult {
+    /// Returns the parameters the run was configured with, when the result
+    /// was produced by the CPU pipeline.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ClusteringResult, ClusterId};
+    ///
+    /// let result = ClusteringResult::from_assignments(vec![ClusterId::new(0)]);
+    /// assert!(result.parameters().is_none());
+    /// ```
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn parameters(&self) -> Option<ParameterReport> { self.parameters }
+
+    #[cfg(feature = "cpu")]
+    pub(crate) fn with_parameters(mut self, report: Option<ParameterReport>) -> Self {
+        self.parameters = report;
+        self
+    }
+}
@@ -0,0 +1,371 @@
+//! Versioned binary encoding for [`ClusteringResult`].
+//!
+//! Services cache clustering output and hydrate it later without rerunning
+//! the pipeline. The format is hand-rolled so the core crate stays free of
+//! serialization dependencies:
+//!
+//! - an 8-byte magic, `CHUTORES`, followed by a little-endian `u16` version;
+//! - the assignments as a `u64` count followed by one `u64` per point;
+//! - the noise label, membership scores, sparsification, connectivity,
+//!   timings, sampling, distance-policy, seed, parameter, distance-evaluation,
+//!   and noise-reassignment reports, each as a presence byte (`0` absent, `1`
+//!   present) followed by its fields;
+//! - the warnings as a `u64` count followed by a tag byte and the warning's
+//!   fields;
+//! - the backend that ran, as a presence byte followed by a tag byte;
+//! - the seed-label report, as a presence byte followed by its counts and
+//!   each conflict's adopted label and `u64`-counted labels;
+//! - the cluster hierarchy, as a presence byte followed by the cluster and
+//!   row counts and each row's parent, tag byte, point or child index, and
+//!   `f64` density;
+//! - the explained spanning forest, as a presence byte followed by the edge
+//!   count and each edge's endpoints, `f64` weight, and provenance, itself a
+//!   presence byte followed by the inserted point, layer, search width, and
+//!   sequence.
+//!
+//! A change to this layout bumps the version, and decoders reject versions
+//! they do not know.
+//!
+//! Integers are little-endian `u64`, scores are `f32` bit patterns, weights
+//! and densities are `f64` bit patterns, and durations are whole seconds plus
+//! a `u32` of nanoseconds. Decoding checks every invariant the constructors
+//! enforce, so a hydrated result is as trustworthy as a fresh one.
+
+use std::{num::NonZeroUsize, time::Duration};
+
+use thiserror::Error;
+
+use super::{
+    ClusterHierarchy, ClusterId, ClusteringResult, ExplainedForest, NonContiguousClusterIds,
+    ParameterReport,
+    codec::{Decoder, Encoder},
+};
+use crate::{
+    Backend, ConnectivityReport, DistancePolicy, DistancePolicyReport, MembershipScores,
+    NoiseReassignmentReport, SamplingReport, SeedLabelConflict, SeedLabelReport, SeedReport,
+    SparsificationReport, StageTimings,
+};
+
+const MAGIC: &[u8; 8] = b"CHUTORES";
+/// The encoding version written by [`ClusteringResult::to_bytes`].
+const FORMAT_VERSION: u16 = 1;
+
+/// Error returned when bytes cannot be decoded into a [`ClusteringResult`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum ResultDecodeError {
+    /// The bytes do not start with the result magic.
+    #[error("bytes are not an encoded clustering result")]
+    BadMagic,
+    /// The bytes were written by an unsupported format version.
+    #[error("unsupported clustering result format version {version}")]
+    UnsupportedVersion {
+        /// Version found in the header.
+        version: u16,
+    },
+    /// The bytes ended before `field` was complete.
+    #[error("encoded clustering result is truncated in {field}")]
+    Truncated {
+        /// The field being decoded.
+        field: &'static str,
+    },
+    /// `field` held a value no encoder writes.
+    #[error("encoded clustering result has an invalid {field}")]
+    InvalidField {
+        /// The field being decoded.
+        field: &'static str,
+    },
+    /// Bytes remained after the last field.
+    #[error("encoded clustering result has {count} trailing bytes")]
+    TrailingBytes {
+        /// Number of unread bytes.
+        count: usize,
+    },
+    /// The decoded assignments break the cluster identifier invariant.
+    #[error("encoded assignments are invalid: {0}")]
+    Assignments(#[from] NonContiguousClusterIds),
+}
+
+impl ClusteringResult {
+    /// Encodes the result, including every attached report, as versioned
+    /// bytes that [`Self::from_bytes`] restores exactly.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ClusteringResult, ClusterId};
+    ///
+    /// let result = ClusteringResult::from_assignments(vec![ClusterId::new(0)]);
+    /// let restored = ClusteringResult::from_bytes(&result.to_bytes())
+    ///     .expect("encoded results decode");
+    /// assert_eq!(restored, result);
+    /// ```
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Encoder(Vec::with_capacity(16 + 8 * self.assignments.len()));
+        out.0.extend_from_slice(MAGIC);
+        out.0.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        out.len(self.assignments.len());
+        for id in &self.assignments {
+            out.u64(id.get());
+        }
+        out.option(self.noise_label, |out, id| out.u64(id.get()));
+        out.option(self.membership.as_ref(), |out, scores| {
+            out.len(scores.probabilities().len());
+            for &score in scores.probabilities().iter().chain(scores.outlier_scores()) {
+                out.0.extend_from_slice(&score.to_bits().to_le_bytes());
+            }
+        });
+        out.option(self.sparsification, |out, report| {
+            out.len(report.input_edges());
+            out.len(report.retained_edges());
+            out.len(report.per_node_edges());
+        });
+        out.option(self.connectivity.as_ref(), |out, report| {
+            out.len(report.component_sizes().len());
+            for &size in report.component_sizes() {
+                out.len(size);
+            }
+            out.len(report.bridge_edges_added());
+        });
+        out.option(self.timings, |out, timings| {
+            for duration in timings.durations() {
+                out.u64(duration.as_secs());
+                out.0
+                    .extend_from_slice(&duration.subsec_nanos().to_le_bytes());
+            }
+        });
+        out.option(self.sampling, |out, report| {
+            out.len(report.sampled_points());
+            out.len(report.assigned_points());
+            out.u64(report.seed());
+        });
+        out.option(self.distance_policy, |out, report| {
+            out.0.push(policy_tag(report.policy()));
+            out.len(report.clamped());
+            out.len(report.skipped());
+        });
+        out.option(self.seeds, |out, seeds| {
+            out.option(seeds.master(), Encoder::u64);
+            out.u64(seeds.hnsw());
+            out.option(seeds.sample(), Encoder::u64);
+        });
+        out.option(self.parameters, |out, report| {
+            out.len(report.min_cluster_size().get());
+            out.len(report.max_connections());
+            out.len(report.ef_construction());
+        });
+        out.option(self.distance_evaluations, Encoder::u64);
+        out.option(self.noise_reassignment, |out, report| {
+            out.len(report.noise_points());
+            out.len(report.reassigned());
+        });
+        out.len(self.warnings.len());
+        for warning in &self.warnings {
+            out.warning(*warning);
+        }
+        out.option(self.backend, |out, backend| {
+            out.0.push(backend_tag(backend))
+        });
+        out.option(self.seed_labels.as_ref(), encode_seed_labels);
+        out.option(self.hierarchy.as_ref(), |out, hierarchy| {
+            hierarchy.encode(out)
+        });
+        out.option(self.explained_forest.as_ref(), |out, forest| {
+            forest.encode(out)
+        });
+        out.0
+    }
+
+    /// Decodes a result written by [`Self::to_bytes`].
+    ///
+    /// # Errors
+    /// Returns [`ResultDecodeError`] when the bytes are not an encoded result,
+    /// come from an unsupported format version, are truncated or padded, or
+    /// hold values that break the result's invariants.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ResultDecodeError> {
+        let mut input = Decoder(bytes);
+        if input.take::<8>("magic")? != *MAGIC {
+            return Err(ResultDecodeError::BadMagic);
+        }
+        let version = u16::from_le_bytes(input.take("version")?);
+        if version != FORMAT_VERSION {
+            return Err(ResultDecodeError::UnsupportedVersion { version });
+        }
+
+        let count = input.len("assignments")?;
+        let assignments = (0..count)
+            .map(|_| input.u64("assignments").map(ClusterId::new))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut result = Self::try_from_assignments(assignments)?;
+        result.noise_label = input.option("noise_label", |input| {
+            input.u64("noise_label").map(ClusterId::new)
+        })?;
+        result.membership = input.option("membership", |input| {
+            let points = input.len("membership")?;
+            if points != result.assignments.len() {
+                return Err(ResultDecodeError::InvalidField {
+                    field: "membership",
+                });
+            }
+            let probabilities = input.scores(points)?;
+            let outlier_scores = input.scores(points)?;
+            Ok(MembershipScores::new(probabilities, outlier_scores))
+        })?;
+        result.sparsification = input.option("sparsification", |input| {
+            let field = "sparsification";
+            Ok(SparsificationReport::new(
+                input.len(field)?,
+                input.len(field)?,
+                input.len(field)?,
+            ))
+        })?;
+        result.connectivity = input.option("connectivity", |input| {
+            let field = "connectivity";
+            let components = input.len(field)?;
+            let sizes = (0..components)
+                .map(|_| input.len(field))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(ConnectivityReport::new(sizes, input.len(field)?))
+        })?;
+        result.timings = input.option("timings", |input| {
+            let mut durations = [Duration::ZERO; 5];
+            for duration in &mut durations {
+                *duration = input.duration("timings")?;
+            }
+            Ok(StageTimings::from_durations(durations))
+        })?;
+        result.sampling = input.option("sampling", |input| {
+            let field = "sampling";
+            Ok(SamplingReport::new(
+                input.len(field)?,
+                input.len(field)?,
+                input.u64(field)?,
+            ))
+        })?;
+        result.distance_policy = input.option("distance_policy", |input| {
+            let field = "distance_policy";
+            let [tag] = input.take(field)?;
+            let policy = policy_from_tag(tag).ok_or(ResultDecodeError::InvalidField { field })?;
+            Ok(DistancePolicyReport::new(
+                policy,
+                input.len(field)?,
+                input.len(field)?,
+            ))
+        })?;
+        result.seeds = input.option("seeds", |input| {
+            let master = input.option("seeds", |input| input.u64("seeds"))?;
+            let hnsw = input.u64("seeds")?;
+            let sample = input.option("seeds", |input| input.u64("seeds"))?;
+            Ok(SeedReport::new(master, hnsw, sample))
+        })?;
+        result.parameters = input.option("parameters", |input| {
+            let field = "parameters";
+            let min_cluster_size = NonZeroUsize::new(input.len(field)?)
+                .ok_or(ResultDecodeError::InvalidField { field })?;
+            Ok(ParameterReport::new(
+                min_cluster_size,
+                input.len(field)?,
+                input.len(field)?,
+            ))
+        })?;
+        result.distance_evaluations = input.option("distance_evaluations", |input| {
+            input.u64("distance_evaluations")
+        })?;
+        result.noise_reassignment = input.option("noise_reassignment", |input| {
+            let field = "noise_reassignment";
+            Ok(NoiseReassignmentReport::new(
+                input.len(field)?,
+                input.len(field)?,
+            ))
+        })?;
+        let count = input.len("warnings")?;
+        result.warnings = (0..count)
+            .map(|_| input.warning())
+            .collect::<Result<Vec<_>, _>>()?;
+        result.backend = input.option("backend", |input| {
+            let field = "backend";
+            let [tag] = input.take(field)?;
+            backend_from_tag(tag).ok_or(ResultDecodeError::InvalidField { field })
+        })?;
+        result.seed_labels = input.option("seed_labels", decode_seed_labels)?;
+        let points = result.assignments.len();
+        result.hierarchy =
+            input.option("hierarchy", |input| ClusterHierarchy::decode(input, points))?;
+        result.explained_forest = input.option("explained_forest", |input| {
+            ExplainedForest::decode(input, points)
+        })?;
+
+        match input.0.len() {
+            0 => Ok(result),
+            count => Err(ResultDecodeError::TrailingBytes { count }),
+        }
+    }
+}
+
+fn policy_tag(policy: DistancePolicy) -> u8 {
+    match policy {
+        DistancePolicy::Strict => 0,
+        DistancePolicy::ClampToMax => 1,
+        DistancePolicy::SkipEdge => 2,
+    }
+}
+
+fn policy_from_tag(tag: u8) -> Option<DistancePolicy> {
+    match tag {
+        0 => Some(DistancePolicy::Strict),
+        1 => Some(DistancePolicy::ClampToMax),
+        2 => Some(DistancePolicy::SkipEdge),
+        _ => None,
+    }
+}
+
+fn encode_seed_labels(out: &mut Encoder, report: &SeedLabelReport) {
+    out.len(report.seeds());
+    out.len(report.anchored_clusters());
+    out.len(report.noise_seeds());
+    out.len(report.conflicts().len());
+    for conflict in report.conflicts() {
+        out.u64(conflict.adopted().get());
+        out.len(conflict.labels().len());
+        for label in conflict.labels() {
+            out.u64(label.get());
+        }
+    }
+}
+
+fn decode_seed_labels(input: &mut Decoder<'_>) -> Result<SeedLabelReport, ResultDecodeError> {
+    let field = "seed_labels";
+    let (seeds, anchored, noise_seeds) = (input.len(field)?, input.len(field)?, input.len(field)?);
+    let conflicts = (0..input.len(field)?)
+        .map(|_| {
+            let adopted = ClusterId::new(input.u64(field)?);
+            let labels = (0..input.len(field)?)
+                .map(|_| input.u64(field).map(ClusterId::new))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(SeedLabelConflict::new(adopted, labels))
+        })
+        .collect::<Result<Vec<_>, ResultDecodeError>>()?;
+    Ok(SeedLabelReport::new(
+        seeds,
+        anchored,
+        noise_seeds,
+        conflicts,
+    ))
+}
+
+fn backend_tag(backend: Backend) -> u8 {
+    match backend {
+        Backend::Gpu => 0,
+        Backend::CpuParallel => 1,
+        Backend::CpuSequential => 2,
+    }
+}
+
+fn backend_from_tag(tag: u8) -> Option<Backend> {
+    match tag {
+        0 => Some(Backend::Gpu),
+        1 => Some(Backend::CpuParallel),
+        2 => Some(Backend::CpuSequential),
+        _ => None,
+    }
+}
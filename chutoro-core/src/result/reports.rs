@@ -0,0 +1,276 @@
+//! Optional pipeline reports attached to a [`ClusteringResult`].
+//!
+//! The CPU pipeline records how each optional stage behaved: edge
+//! sparsification, forest connectivity, stage timings, sampling, the
+//! handling of non-finite distances, the seeds used, how many distances
+//! were evaluated, how many noise points were reassigned, any non-fatal
+//! warnings, the backend that ran, and how seed labels were propagated.
+//! Results built directly from assignments carry none of them.
+
+use crate::{
+    Backend, connectivity::ConnectivityReport, distance_policy::DistancePolicyReport,
+    reassign::NoiseReassignmentReport, sample::SamplingReport, seed::SeedReport,
+    seed_labels::SeedLabelReport, sparsify::SparsificationReport, timings::StageTimings,
+    warning::Warning,
+};
+
+use super::{ClusterId, ClusteringResult};
+
+impl ClusteringResult {
+    /// Returns how the candidate-edge harvest was sparsified, when the run was
+    /// configured with an [`crate::EdgeBudget`].
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ClusteringResult, ClusterId};
+    ///
+    /// let result = ClusteringResult::from_assignments(vec![ClusterId::new(0)]);
+    /// assert!(result.sparsification().is_none());
+    /// ```
+    #[must_use]
+    pub fn sparsification(&self) -> Option<&SparsificationReport> {
+        self.sparsification.as_ref()
+    }
+
+    #[cfg(feature = "cpu")]
+    pub(crate) fn with_sparsification(mut self, report: Option<SparsificationReport>) -> Self {
+        self.sparsification = report;
+        self
+    }
+
+    /// Returns the connectivity of the mutual-reachability forest, when the
+    /// result was produced by the CPU pipeline.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ClusteringResult, ClusterId};
+    ///
+    /// let result = ClusteringResult::from_assignments(vec![ClusterId::new(0)]);
+    /// assert!(result.connectivity().is_none());
+    /// ```
+    #[must_use]
+    pub fn connectivity(&self) -> Option<&ConnectivityReport> {
+        self.connectivity.as_ref()
+    }
+
+    #[cfg(feature = "cpu")]
+    pub(crate) fn with_connectivity(mut self, report: Option<ConnectivityReport>) -> Self {
+        self.connectivity = report;
+        self
+    }
+
+    /// Returns per-stage wall-clock timings, when the result was produced by
+    /// the CPU pipeline.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ClusteringResult, ClusterId};
+    ///
+    /// let result = ClusteringResult::from_assignments(vec![ClusterId::new(0)]);
+    /// assert!(result.timings().is_none());
+    /// ```
+    #[must_use]
+    pub fn timings(&self) -> Option<&StageTimings> {
+        self.timings.as_ref()
+    }
+
+    #[cfg(feature = "cpu")]
+    pub(crate) fn with_timings(mut self, timings: Option<StageTimings>) -> Self {
+        self.timings = timings;
+        self
+    }
+
+    /// Returns how the dataset was split between clustering and
+    /// nearest-cluster assignment, when the run was configured with
+    /// [`crate::ChutoroBuilder::with_sample`].
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ClusteringResult, ClusterId};
+    ///
+    /// let result = ClusteringResult::from_assignments(vec![ClusterId::new(0)]);
+    /// assert!(result.sampling().is_none());
+    /// ```
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn sampling(&self) -> Option<SamplingReport> { self.sampling }
+
+    #[cfg(feature = "cpu")]
+    pub(crate) fn with_sampling(mut self, report: Option<SamplingReport>) -> Self {
+        self.sampling = report;
+        self
+    }
+
+    /// Returns how many non-finite distances were clamped or skipped, when
+    /// the run was configured with a non-strict
+    /// [`crate::ChutoroBuilder::with_distance_policy`].
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ClusteringResult, ClusterId};
+    ///
+    /// let result = ClusteringResult::from_assignments(vec![ClusterId::new(0)]);
+    /// assert!(result.distance_policy().is_none());
+    /// ```
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn distance_policy(&self) -> Option<DistancePolicyReport> { self.distance_policy }
+
+    #[cfg(feature = "cpu")]
+    pub(crate) fn with_distance_policy(mut self, report: Option<DistancePolicyReport>) -> Self {
+        self.distance_policy = report;
+        self
+    }
+
+    /// Returns the seeds the run used, when the result was produced by the
+    /// CPU pipeline.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ClusteringResult, ClusterId};
+    ///
+    /// let result = ClusteringResult::from_assignments(vec![ClusterId::new(0)]);
+    /// assert!(result.seeds().is_none());
+    /// ```
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn seeds(&self) -> Option<SeedReport> { self.seeds }
+
+    #[cfg(feature = "cpu")]
+    pub(crate) fn with_seeds(mut self, report: Option<SeedReport>) -> Self {
+        self.seeds = report;
+        self
+    }
+
+    /// Returns how many distances the data source evaluated during the run,
+    /// when the result was produced by the CPU pipeline.
+    ///
+    /// Lookups answered by the HNSW distance cache are not counted. Cap the
+    /// count with [`crate::ChutoroBuilder::with_max_distance_evaluations`].
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ClusteringResult, ClusterId};
+    ///
+    /// let result = ClusteringResult::from_assignments(vec![ClusterId::new(0)]);
+    /// assert!(result.distance_evaluations().is_none());
+    /// ```
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn distance_evaluations(&self) -> Option<u64> { self.distance_evaluations }
+
+    #[cfg(feature = "cpu")]
+    pub(crate) fn with_distance_evaluations(mut self, evaluations: Option<u64>) -> Self {
+        self.distance_evaluations = evaluations;
+        self
+    }
+
+    /// Returns how many noise points were moved into clusters, when the run
+    /// was configured with [`crate::ChutoroBuilder::reassign_noise`].
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ClusteringResult, ClusterId};
+    ///
+    /// let result = ClusteringResult::from_assignments(vec![ClusterId::new(0)]);
+    /// assert!(result.noise_reassignment().is_none());
+    /// ```
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn noise_reassignment(&self) -> Option<NoiseReassignmentReport> { self.noise_reassignment }
+
+    #[cfg(feature = "cpu")]
+    pub(crate) fn with_noise_reassignment(
+        mut self,
+        report: Option<NoiseReassignmentReport>,
+    ) -> Self {
+        self.noise_reassignment = report;
+        self
+    }
+
+    /// Returns the backend that executed the run, when the result was
+    /// produced by [`crate::Chutoro::run`].
+    ///
+    /// With [`crate::ExecutionStrategy::Auto`] this records which step of the
+    /// fallback chain was taken.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ClusteringResult, ClusterId};
+    ///
+    /// let result = ClusteringResult::from_assignments(vec![ClusterId::new(0)]);
+    /// assert!(result.backend().is_none());
+    /// ```
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn backend(&self) -> Option<Backend> { self.backend }
+
+    pub(crate) fn with_backend(mut self, backend: Option<Backend>) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Returns how known labels were propagated to the clusters, when the run
+    /// was configured with [`crate::ChutoroBuilder::with_seed_labels`].
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ClusteringResult, ClusterId};
+    ///
+    /// let result = ClusteringResult::from_assignments(vec![ClusterId::new(0)]);
+    /// assert!(result.seed_labels().is_none());
+    /// ```
+    #[must_use]
+    pub fn seed_labels(&self) -> Option<&SeedLabelReport> {
+        self.seed_labels.as_ref()
+    }
+
+    /// Replaces the assignments after clusters adopted seed labels. The
+    /// caller keeps identifiers contiguous from zero.
+    pub(crate) fn with_anchored_assignments(
+        mut self,
+        assignments: Vec<ClusterId>,
+        noise_label: Option<ClusterId>,
+        report: Option<SeedLabelReport>,
+    ) -> Self {
+        self.cluster_count = assignments
+            .iter()
+            .max()
+            .map_or(0, |max| super::slot(*max) + 1);
+        self.assignments = assignments;
+        self.noise_label = noise_label;
+        self.seed_labels = report;
+        self
+    }
+
+    /// Returns the non-fatal events raised during the run, in the order they
+    /// were recorded.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ClusteringResult, ClusterId};
+    ///
+    /// let result = ClusteringResult::from_assignments(vec![ClusterId::new(0)]);
+    /// assert!(result.warnings().is_empty());
+    /// ```
+    #[must_use]
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    /// Appends `warnings` to those already recorded.
+    #[cfg(feature = "cpu")]
+    pub(crate) fn with_warnings(mut self, warnings: impl IntoIterator<Item = Warning>) -> Self {
+        self.warnings.extend(warnings);
+        self
+    }
+
+    /// Appends the warnings implied by the attached distance-policy and
+    /// connectivity reports.
+    #[cfg(feature = "cpu")]
+    pub(crate) fn with_report_warnings(self) -> Self {
+        let warnings =
+            crate::warning::report_warnings(self.distance_policy, self.connectivity.as_ref());
+        self.with_warnings(warnings)
+    }
+}
@@ -0,0 +1,166 @@
+//! Serde deserialization for [`ClusteringResult`].
+//!
+//! Deserialization goes through [`RawClusteringResult`] so the assignments
+//! are re-validated and the cluster count re-derived, exactly as
+//! [`ClusteringResult::try_from_assignments`] does for fresh results. A
+//! [`ClusterHierarchy`] round-trips through [`RawHierarchy`] rows, which are
+//! re-validated the same way.
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    ClusterHierarchy, ClusterId, ClusteringResult, ExplainedForest, ParameterReport,
+    ResultDecodeError, hierarchy::Member,
+};
+use crate::{
+    Backend, ConnectivityReport, DistancePolicyReport, MembershipScores, NoiseReassignmentReport,
+    SamplingReport, SeedLabelReport, SeedReport, SparsificationReport, StageTimings, Warning,
+};
+
+/// Serialized form of [`ClusteringResult`]. The serialized `cluster_count` is
+/// ignored and recomputed from the assignments.
+#[derive(Deserialize)]
+pub(super) struct RawClusteringResult {
+    assignments: Vec<ClusterId>,
+    #[serde(default)]
+    sparsification: Option<SparsificationReport>,
+    #[serde(default)]
+    connectivity: Option<ConnectivityReport>,
+    #[serde(default)]
+    timings: Option<StageTimings>,
+    #[serde(default)]
+    noise_label: Option<ClusterId>,
+    #[serde(default)]
+    membership: Option<MembershipScores>,
+    #[serde(default)]
+    sampling: Option<SamplingReport>,
+    #[serde(default)]
+    distance_policy: Option<DistancePolicyReport>,
+    #[serde(default)]
+    seeds: Option<SeedReport>,
+    #[serde(default)]
+    parameters: Option<ParameterReport>,
+    #[serde(default)]
+    distance_evaluations: Option<u64>,
+    #[serde(default)]
+    noise_reassignment: Option<NoiseReassignmentReport>,
+    #[serde(default)]
+    warnings: Vec<Warning>,
+    #[serde(default)]
+    backend: Option<Backend>,
+    #[serde(default)]
+    seed_labels: Option<SeedLabelReport>,
+    #[serde(default)]
+    hierarchy: Option<ClusterHierarchy>,
+    #[serde(default)]
+    explained_forest: Option<ExplainedForest>,
+}
+
+impl TryFrom<RawClusteringResult> for ClusteringResult {
+    type Error = ResultDecodeError;
+
+    fn try_from(raw: RawClusteringResult) -> Result<Self, Self::Error> {
+        let mut result = Self::try_from_assignments(raw.assignments)?;
+        if raw
+            .membership
+            .as_ref()
+            .is_some_and(|scores| scores.probabilities().len() != result.assignments.len())
+        {
+            return Err(ResultDecodeError::InvalidField {
+                field: "membership",
+            });
+        }
+        if raw
+            .hierarchy
+            .as_ref()
+            .is_some_and(|hierarchy| hierarchy.point_count() != result.assignments.len())
+        {
+            return Err(ResultDecodeError::InvalidField { field: "hierarchy" });
+        }
+        if raw.explained_forest.as_ref().is_some_and(|forest| {
+            forest.point_count() != result.assignments.len() || !forest.is_valid()
+        }) {
+            return Err(ResultDecodeError::InvalidField {
+                field: "explained_forest",
+            });
+        }
+        result.sparsification = raw.sparsification;
+        result.connectivity = raw.connectivity;
+        result.timings = raw.timings;
+        result.noise_label = raw.noise_label;
+        result.membership = raw.membership;
+        result.sampling = raw.sampling;
+        result.distance_policy = raw.distance_policy;
+        result.seeds = raw.seeds;
+        result.parameters = raw.parameters;
+        result.distance_evaluations = raw.distance_evaluations;
+        result.noise_reassignment = raw.noise_reassignment;
+        result.warnings = raw.warnings;
+        result.backend = raw.backend;
+        result.seed_labels = raw.seed_labels;
+        result.hierarchy = raw.hierarchy;
+        result.explained_forest = raw.explained_forest;
+        Ok(result)
+    }
+}
+
+/// Serialized form of [`ClusterHierarchy`], as the rows attaching points and
+/// child clusters to their parents.
+#[derive(Serialize, Deserialize)]
+pub(super) struct RawHierarchy {
+    points: usize,
+    clusters: usize,
+    rows: Vec<RawRow>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RawRow {
+    parent: usize,
+    member: RawMember,
+    /// The row's density, or `None` for points that never left; JSON has no
+    /// infinity.
+    lambda: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize)]
+enum RawMember {
+    Point(usize),
+    Cluster(usize),
+}
+
+impl From<ClusterHierarchy> for RawHierarchy {
+    fn from(hierarchy: ClusterHierarchy) -> Self {
+        let rows = hierarchy
+            .rows()
+            .map(|(parent, member, lambda)| RawRow {
+                parent,
+                member: match member {
+                    Member::Point(point) => RawMember::Point(point),
+                    Member::Cluster(child) => RawMember::Cluster(child),
+                },
+                lambda: lambda.is_finite().then_some(lambda),
+            })
+            .collect();
+        Self {
+            points: hierarchy.point_count(),
+            clusters: hierarchy.cluster_count(),
+            rows,
+        }
+    }
+}
+
+impl TryFrom<RawHierarchy> for ClusterHierarchy {
+    type Error = ResultDecodeError;
+
+    fn try_from(raw: RawHierarchy) -> Result<Self, Self::Error> {
+        let rows = raw.rows.into_iter().map(|row| {
+            let member = match row.member {
+                RawMember::Point(point) => Member::Point(point),
+                RawMember::Cluster(child) => Member::Cluster(child),
+            };
+            (row.parent, member, row.lambda.unwrap_or(f64::INFINITY))
+        });
+        Self::from_rows(raw.points, raw.clusters, rows)
+            .ok_or(ResultDecodeError::InvalidField { field: "hierarchy" })
+    }
+}
@@ -0,0 +1,227 @@
+//! Agreement between clusterings of the same points, such as runs of one
+//! configuration under different seeds.
+//!
+//! HNSW construction is randomized, so two runs that differ only in their
+//! seed can disagree about borderline points or whole clusters.
+//! [`ClusteringResult::stability_report`] quantifies that: pairwise ARI and
+//! NMI say how much the partitions agree overall, and per-cluster persistence
+//! says which clusters of a reference run can be trusted.
+
+use std::collections::HashMap;
+
+use super::{ClusterId, ClusteringResult, slot};
+use crate::{ClusteringQualityError, ClusteringQualityScore, clustering_quality_score};
+
+/// Jaccard similarity at or above which a cluster counts as recovered by
+/// another run.
+pub const PERSISTENCE_THRESHOLD: f64 = 0.5;
+
+/// Agreement between two runs of a [`StabilityReport`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RunAgreement {
+    /// Index of the first run, where `0` is the reference run.
+    pub left: usize,
+    /// Index of the second run, always greater than `left`.
+    pub right: usize,
+    /// ARI and NMI between the two runs' assignments.
+    pub score: ClusteringQualityScore,
+}
+
+/// How consistently one cluster of the reference run reappears in the
+/// others.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClusterPersistence {
+    /// Cluster of the reference run.
+    pub cluster: ClusterId,
+    /// Number of points in the cluster.
+    pub size: usize,
+    /// Fraction of the other runs with a cluster whose Jaccard similarity to
+    /// this one reaches [`PERSISTENCE_THRESHOLD`].
+    pub frequency: f64,
+    /// Mean over the other runs of the best Jaccard similarity to any of
+    /// their clusters.
+    pub mean_jaccard: f64,
+}
+
+/// Stability of a clustering across repeated runs, returned by
+/// [`ClusteringResult::stability_report`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct StabilityReport {
+    runs: usize,
+    agreements: Vec<RunAgreement>,
+    clusters: Vec<ClusterPersistence>,
+}
+
+impl StabilityReport {
+    /// Returns the number of runs compared, including the reference run.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn runs(&self) -> usize { self.runs }
+
+    /// Returns the agreement of every pair of runs, ordered by `left` then
+    /// `right`.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn agreements(&self) -> &[RunAgreement] { &self.agreements }
+
+    /// Returns the persistence of each non-noise cluster of the reference
+    /// run, in identifier order.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn clusters(&self) -> &[ClusterPersistence] { &self.clusters }
+
+    /// Returns the mean pairwise ARI, or `None` for a single run.
+    #[must_use]
+    pub fn mean_ari(&self) -> Option<f64> {
+        self.mean(|score| score.ari)
+    }
+
+    /// Returns the mean pairwise NMI, or `None` for a single run.
+    #[must_use]
+    pub fn mean_nmi(&self) -> Option<f64> {
+        self.mean(|score| score.nmi)
+    }
+
+    /// Returns the lowest pairwise ARI, or `None` for a single run.
+    #[must_use]
+    pub fn min_ari(&self) -> Option<f64> {
+        self.agreements
+            .iter()
+            .map(|agreement| agreement.score.ari)
+            .reduce(f64::min)
+    }
+
+    fn mean(&self, metric: impl Fn(&ClusteringQualityScore) -> f64) -> Option<f64> {
+        if self.agreements.is_empty() {
+            return None;
+        }
+        let total: f64 = self
+            .agreements
+            .iter()
+            .map(|agreement| metric(&agreement.score))
+            .sum();
+        Some(total / self.agreements.len() as f64)
+    }
+}
+
+impl ClusteringResult {
+    /// Compares this result with `others`, clusterings of the same points,
+    /// typically from runs under different seeds.
+    ///
+    /// This result is run `0` and `others` follow in order. Every pair of
+    /// runs is scored with ARI and NMI, treating noise as one more label.
+    /// Each non-noise cluster of this result is then matched against every
+    /// other run's clusters by Jaccard similarity; its
+    /// [`ClusterPersistence::frequency`] is the fraction of runs where the
+    /// best match reaches [`PERSISTENCE_THRESHOLD`]. With no `others`, there
+    /// are no pairs and every cluster persists vacuously.
+    ///
+    /// # Errors
+    /// Returns [`ClusteringQualityError::LabelLengthMismatch`] when a result
+    /// covers a different number of points.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{ClusterId, ClusteringResult};
+    ///
+    /// let ids = |labels: &[u64]| {
+    ///     ClusteringResult::from_assignments(labels.iter().copied().map(ClusterId::new).collect())
+    /// };
+    /// let reference = ids(&[0, 0, 0, 1, 1, 1]);
+    /// let relabelled = ids(&[1, 1, 1, 0, 0, 0]);
+    /// let split = ids(&[0, 1, 2, 3, 4, 5]);
+    ///
+    /// let report = reference.stability_report(&[relabelled, split])?;
+    /// assert_eq!(report.runs(), 3);
+    /// assert_eq!(report.agreements()[0].score.ari, 1.0);
+    /// assert_eq!(report.clusters()[0].frequency, 0.5);
+    /// # Ok::<(), chutoro_core::ClusteringQualityError>(())
+    /// ```
+    pub fn stability_report(
+        &self,
+        others: &[ClusteringResult],
+    ) -> Result<StabilityReport, ClusteringQualityError> {
+        let labels: Vec<Vec<usize>> = std::iter::once(self)
+            .chain(others)
+            .map(|run| run.assignments.iter().map(|&id| slot(id)).collect())
+            .collect();
+        let mut agreements = Vec::new();
+        for (left, left_labels) in labels.iter().enumerate() {
+            for (right, right_labels) in labels.iter().enumerate().skip(left + 1) {
+                let score = clustering_quality_score(left_labels, right_labels)?;
+                agreements.push(RunAgreement { left, right, score });
+            }
+        }
+        Ok(StabilityReport {
+            runs: labels.len(),
+            agreements,
+            clusters: self.cluster_persistence(others),
+        })
+    }
+
+    /// Matches each non-noise cluster against `others`, whose lengths were
+    /// already checked.
+    fn cluster_persistence(&self, others: &[ClusteringResult]) -> Vec<ClusterPersistence> {
+        let sizes = self.cluster_sizes();
+        let mut recovered = vec![0_usize; sizes.len()];
+        let mut jaccard_sums = vec![0.0_f64; sizes.len()];
+        for other in others {
+            for (cluster, jaccard) in self.best_jaccard(&sizes, other).into_iter().enumerate() {
+                jaccard_sums[cluster] += jaccard;
+                recovered[cluster] += usize::from(jaccard >= PERSISTENCE_THRESHOLD);
+            }
+        }
+        let share = |value: f64| {
+            if others.is_empty() {
+                1.0
+            } else {
+                value / others.len() as f64
+            }
+        };
+        sizes
+            .iter()
+            .enumerate()
+            .map(|(cluster, &size)| ClusterPersistence {
+                cluster: ClusterId::new(cluster as u64),
+                size,
+                frequency: share(recovered[cluster] as f64),
+                mean_jaccard: share(jaccard_sums[cluster]),
+            })
+            .filter(|persistence| Some(persistence.cluster) != self.noise_label)
+            .collect()
+    }
+
+    /// Returns, for each cluster of `self`, its highest Jaccard similarity to
+    /// a non-noise cluster of `other`.
+    fn best_jaccard(&self, sizes: &[usize], other: &ClusteringResult) -> Vec<f64> {
+        let other_sizes = other.cluster_sizes();
+        let mut overlaps = HashMap::<(usize, usize), usize>::new();
+        for pair in self.clusters_of_points().zip(other.clusters_of_points()) {
+            if let (Some(left), Some(right)) = pair {
+                *overlaps.entry((left, right)).or_default() += 1;
+            }
+        }
+        let mut best = vec![0.0_f64; sizes.len()];
+        for ((left, right), shared) in overlaps {
+            let union = sizes[left] + other_sizes[right] - shared;
+            best[left] = best[left].max(shared as f64 / union as f64);
+        }
+        best
+    }
+
+    /// Counts the points of each cluster; the noise label's count stays zero.
+    fn cluster_sizes(&self) -> Vec<usize> {
+        let mut sizes = vec![0; self.cluster_count];
+        for cluster in self.clusters_of_points().flatten() {
+            sizes[cluster] += 1;
+        }
+        sizes
+    }
+
+    /// Yields each point's cluster slot, or `None` for noise.
+    fn clusters_of_points(&self) -> impl Iterator<Item = Option<usize>> + '_ {
+        self.assignments
+            .iter()
+            .map(|&id| (Some(id) != self.noise_label).then(|| slot(id)))
+    }
+}
@@ -0,0 +1,385 @@
+//! Deterministic subsampling for exploratory runs over large datasets.
+//!
+//! A [`SampleSpec`] selects a seeded subset of the points. The CPU pipeline
+//! clusters that subset as usual, then labels every remaining point with the
+//! cluster of its nearest clustered neighbour in the sample's HNSW index. The
+//! expensive stages therefore scale with the sample rather than the dataset,
+//! while every point still receives an assignment. A [`SamplingReport`]
+//! records how the run was split.
+
+#[cfg(feature = "cpu")]
+use std::{num::NonZeroUsize, sync::Arc, time::Instant};
+
+#[cfg(feature = "cpu")]
+use rand::{SeedableRng, rngs::SmallRng, seq::index};
+#[cfg(feature = "cpu")]
+use rayon::prelude::*;
+#[cfg(feature = "cpu")]
+use tracing::info;
+
+#[cfg(feature = "cpu")]
+use crate::{
+    ClusterId, ClusteringResult, CpuHnsw, DataSource, DataSourceError, MembershipScores,
+    MetricDescriptor, Result,
+    builder::{PipelineOptions, PrebuiltIndex},
+    cpu_pipeline::{map_cpu_hnsw_error, run_cpu_pipeline_with_len},
+    stages::{StageContext, build_index},
+    timings::Stage,
+};
+
+/// Selects how many points a sampled run clusters directly.
+///
+/// Sample sizes are rounded up and clamped to the dataset, and never fall
+/// below the configured minimum cluster size so the sample can always form a
+/// cluster.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::SampleSpec;
+///
+/// assert_eq!(SampleSpec::Fraction(0.1).sample_size(1_000), 100);
+/// assert_eq!(SampleSpec::Count(250).sample_size(100), 100);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SampleSpec {
+    /// Cluster this fraction of the points; must lie in `(0, 1]`.
+    Fraction(f64),
+    /// Cluster this many points; must be non-zero.
+    Count(usize),
+}
+
+impl SampleSpec {
+    /// Returns the number of points sampled from a dataset of `items` points,
+    /// before the minimum cluster size is applied.
+    #[must_use]
+    pub fn sample_size(self, items: usize) -> usize {
+        let size = match self {
+            Self::Fraction(fraction) => (items as f64 * fraction).ceil() as usize,
+            Self::Count(count) => count,
+        };
+        size.min(items)
+    }
+
+    /// Describes why the specification is invalid, if it is.
+    pub(crate) fn validate(self) -> Option<&'static str> {
+        match self {
+            Self::Fraction(fraction) if !(fraction > 0.0 && fraction <= 1.0) => {
+                Some("sample fraction must lie in (0, 1]")
+            }
+            Self::Count(0) => Some("sample count must be non-zero"),
+            _ => None,
+        }
+    }
+}
+
+/// A sample specification paired with the seed that selects its points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Sampling {
+    pub(crate) spec: SampleSpec,
+    pub(crate) seed: u64,
+}
+
+impl Sampling {
+    /// Returns the number of points clustered directly, raised to the
+    /// minimum cluster size where the dataset allows.
+    pub(crate) fn size(self, items: usize, min_cluster_size: usize) -> usize {
+        self.spec
+            .sample_size(items)
+            .max(min_cluster_size)
+            .min(items)
+    }
+}
+
+/// Describes how a sampled run divided the dataset.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::SamplingReport;
+///
+/// let report = SamplingReport::new(100, 900, 7);
+/// assert_eq!(report.total_points(), 1_000);
+/// assert_eq!(report.seed(), 7);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SamplingReport {
+    sampled_points: usize,
+    assigned_points: usize,
+    seed: u64,
+}
+
+impl SamplingReport {
+    /// Creates a report from the sampled and nearest-cluster assigned counts.
+    #[must_use]
+    pub fn new(sampled_points: usize, assigned_points: usize, seed: u64) -> Self {
+        Self {
+            sampled_points,
+            assigned_points,
+            seed,
+        }
+    }
+
+    /// Returns the number of points clustered directly.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn sampled_points(&self) -> usize { self.sampled_points }
+
+    /// Returns the number of points labelled from their nearest neighbours.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn assigned_points(&self) -> usize { self.assigned_points }
+
+    /// Returns the seed used to select the sample.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn seed(&self) -> u64 { self.seed }
+
+    /// Returns the number of points in the dataset.
+    #[must_use]
+    pub fn total_points(&self) -> usize {
+        self.sampled_points + self.assigned_points
+    }
+}
+
+/// Clusters a seeded sample of `source` and labels the remaining points from
+/// the sample's HNSW index. Runs without a sample use the full pipeline.
+#[cfg(feature = "cpu")]
+pub(crate) fn run_sampled_pipeline<D: DataSource + Sync>(
+    source: &D,
+    items: usize,
+    min_cluster_size: NonZeroUsize,
+    options: &PipelineOptions,
+) -> Result<ClusteringResult> {
+    let Some(sampling) = options.sample else {
+        return run_cpu_pipeline_with_len(source, items, min_cluster_size, options);
+    };
+    let size = sampling.size(items, min_cluster_size.get());
+    let order = sample_order(items, size, sampling.seed);
+    let sample = SampleView::new(source, &order, size);
+
+    let started = Instant::now();
+    let context = StageContext::new(
+        &sample,
+        options.hierarchy_config(min_cluster_size),
+        &options.hnsw_params,
+    );
+    let (index, harvest) = build_index(&sample, &context, &options.stages)?;
+    let build_time = started.elapsed();
+    let index = Arc::new(index);
+    let sample_options = PipelineOptions {
+        prebuilt: Some(PrebuiltIndex {
+            index: Arc::clone(&index),
+            harvest: Arc::new(harvest),
+        }),
+        sample: None,
+        ..options.clone()
+    };
+    let sampled = run_cpu_pipeline_with_len(&sample, size, min_cluster_size, &sample_options)?;
+
+    let started = Instant::now();
+    let everything = SampleView::new(source, &order, items);
+    let nearest = nearest_sampled(&everything, &index, &sampled, size)?;
+    let assign_time = started.elapsed();
+    info!(
+        sampled_points = size,
+        assigned_points = items - size,
+        "assigned unsampled points to their nearest clusters"
+    );
+
+    let report = SamplingReport::new(size, items - size, sampling.seed);
+    Ok(extrapolate(&sampled, &order, &nearest)
+        .with_timings(sampled.timings().map(|timings| {
+            timings
+                .with_stage_added(Stage::HnswBuild, build_time)
+                .with_stage_added(Stage::Hierarchy, assign_time)
+        }))
+        .with_sampling(Some(report))
+        .with_distance_policy(index.distance_guard().report()))
+}
+
+/// Returns every point index with the `size` sampled points first.
+///
+/// Both halves are sorted so results do not depend on the sampler's
+/// iteration order.
+#[cfg(feature = "cpu")]
+fn sample_order(items: usize, size: usize, seed: u64) -> Vec<usize> {
+    let mut rng = SmallRng::seed_from_u64(seed);
+    let mut chosen = vec![false; items];
+    for point in index::sample(&mut rng, items, size) {
+        chosen[point] = true;
+    }
+    let (mut order, rest): (Vec<usize>, Vec<usize>) = (0..items).partition(|&point| chosen[point]);
+    order.extend(rest);
+    order
+}
+
+/// For each unsampled position `size..`, finds the sampled position whose
+/// label it inherits: the nearest clustered neighbour, or the nearest
+/// neighbour when every candidate is noise.
+#[cfg(feature = "cpu")]
+fn nearest_sampled<D: DataSource + Sync>(
+    everything: &SampleView<'_, D>,
+    index: &CpuHnsw,
+    sampled: &ClusteringResult,
+    size: usize,
+) -> Result<Vec<usize>> {
+    let Some(ef) = NonZeroUsize::new(index.params().ef_construction().min(size)) else {
+        unreachable!("samples hold at least one point");
+    };
+    let labels = sampled.assignments();
+    let noise = sampled.noise_label();
+    (size..everything.len())
+        .into_par_iter()
+        .map(|query| {
+            let neighbours = index
+                .search(everything, query, ef)
+                .map_err(|error| map_cpu_hnsw_error(everything.source, error))?;
+            let mut candidates = neighbours
+                .iter()
+                .map(|neighbour| neighbour.id)
+                .filter(|&id| id != query);
+            let nearest = candidates.clone().next().unwrap_or(0);
+            Ok(candidates
+                .find(|&id| Some(labels[id]) != noise)
+                .unwrap_or(nearest))
+        })
+        .collect()
+}
+
+/// Expands sample labels, scores, and reports to every point in `order`;
+/// position `size + i` copies from sample position `nearest[i]`.
+#[cfg(feature = "cpu")]
+pub(crate) fn extrapolate(
+    sampled: &ClusteringResult,
+    order: &[usize],
+    nearest: &[usize],
+) -> ClusteringResult {
+    let size = sampled.assignments().len();
+    // Position `p` in `order` copies from sample position `origin[p]`.
+    let origin = (0..size).chain(nearest.iter().copied());
+    let mut assignments = vec![ClusterId::new(0); order.len()];
+    let mut probabilities = vec![0.0; order.len()];
+    let mut outlier_scores = vec![1.0; order.len()];
+    for (&point, source) in order.iter().zip(origin) {
+        assignments[point] = sampled.assignments()[source];
+        if let Some(scores) = sampled.membership() {
+            probabilities[point] = scores.probabilities()[source];
+            outlier_scores[point] = scores.outlier_scores()[source];
+        }
+    }
+    ClusteringResult::from_assignments(assignments)
+        .with_membership(
+            sampled
+                .membership()
+                .map(|_| MembershipScores::new(probabilities, outlier_scores)),
+        )
+        .with_noise_label(sampled.noise_label())
+        .with_noise_reassignment(sampled.noise_reassignment())
+        .with_sparsification(sampled.sparsification().copied())
+        .with_connectivity(sampled.connectivity().cloned())
+        .with_warnings(sampled.warnings().iter().copied())
+        .with_timings(sampled.timings().copied())
+        .with_distance_policy(sampled.distance_policy())
+}
+
+/// Presents the first `len` points of `order` as a [`DataSource`].
+#[cfg(feature = "cpu")]
+pub(crate) struct SampleView<'a, D> {
+    source: &'a D,
+    order: &'a [usize],
+    len: usize,
+}
+
+#[cfg(feature = "cpu")]
+impl<'a, D: DataSource> SampleView<'a, D> {
+    pub(crate) fn new(source: &'a D, order: &'a [usize], len: usize) -> Self {
+        Self { source, order, len }
+    }
+
+    fn point(&self, index: usize) -> std::result::Result<usize, DataSourceError> {
+        self.order
+            .get(..self.len)
+            .and_then(|visible| visible.get(index))
+            .copied()
+            .ok_or(DataSourceError::OutOfBounds { index })
+    }
+}
+
+#[cfg(feature = "cpu")]
+impl<D: DataSource> DataSource for SampleView<'_, D> {
+    #[rustfmt::skip]
+    fn len(&self) -> usize { self.len }
+
+    #[rustfmt::skip]
+    fn name(&self) -> &str { self.source.name() }
+
+    fn metric_descriptor(&self) -> MetricDescriptor {
+        self.source.metric_descriptor()
+    }
+
+    fn dimension_hint(&self) -> Option<usize> {
+        self.source.dimension_hint()
+    }
+
+    fn distance(&self, i: usize, j: usize) -> std::result::Result<f32, DataSourceError> {
+        self.source.distance(self.point(i)?, self.point(j)?)
+    }
+
+    fn distance_f64(&self, i: usize, j: usize) -> std::result::Result<f64, DataSourceError> {
+        self.source.distance_f64(self.point(i)?, self.point(j)?)
+    }
+
+    fn distance_batch(
+        &self,
+        pairs: &[(usize, usize)],
+        out: &mut [f32],
+    ) -> std::result::Result<(), DataSourceError> {
+        let mapped = pairs
+            .iter()
+            .map(|&(i, j)| Ok((self.point(i)?, self.point(j)?)))
+            .collect::<std::result::Result<Vec<_>, DataSourceError>>()?;
+        self.source.distance_batch(&mapped, out)
+    }
+}
+
+#[cfg(all(test, feature = "cpu"))]
+mod tests {
+    //! Unit tests for sample selection and sizing.
+
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::fraction_rounds_up(SampleSpec::Fraction(0.25), 10, 3)]
+    #[case::full_fraction(SampleSpec::Fraction(1.0), 10, 10)]
+    #[case::count(SampleSpec::Count(4), 10, 4)]
+    #[case::count_clamped(SampleSpec::Count(40), 10, 10)]
+    fn sample_size_rounds_up_and_clamps(
+        #[case] spec: SampleSpec,
+        #[case] items: usize,
+        #[case] expected: usize,
+    ) {
+        assert_eq!(spec.sample_size(items), expected);
+    }
+
+    #[rstest]
+    #[case::zero_fraction(SampleSpec::Fraction(0.0))]
+    #[case::large_fraction(SampleSpec::Fraction(1.5))]
+    #[case::nan_fraction(SampleSpec::Fraction(f64::NAN))]
+    #[case::zero_count(SampleSpec::Count(0))]
+    fn invalid_specs_are_described(#[case] spec: SampleSpec) {
+        assert!(spec.validate().is_some());
+    }
+
+    #[rstest]
+    fn sample_order_is_a_seeded_permutation() {
+        let order = sample_order(50, 10, 7);
+        assert_eq!(order, sample_order(50, 10, 7));
+        assert_ne!(order, sample_order(50, 10, 8));
+        let mut sorted = order.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..50).collect::<Vec<_>>());
+        assert!(order[..10].is_sorted() && order[10..].is_sorted());
+    }
+}
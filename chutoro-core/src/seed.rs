@@ -0,0 +1,142 @@
+//! Master seed derivation and reporting.
+//!
+//! A master seed set with [`crate::ChutoroBuilder::with_seed`] is expanded
+//! into one independent seed per randomized component, so a single number
+//! reproduces a whole run. Each component seed is
+//! `splitmix64(master ^ stream)`, where `stream` is the component's fixed
+//! [`SeedStream`] constant. HNSW worker RNGs are in turn derived from the
+//! level-sampling seed by mixing in the worker index. The seeds a run
+//! actually used are recorded in [`crate::ClusteringResult::seeds`].
+
+/// SplitMix64 increment (the 64-bit golden ratio).
+pub(crate) const SPLITMIX_INCREMENT: u64 = 0x9E37_79B9_7F4A_7C15;
+const SPLITMIX_MULT_A: u64 = 0xBF58_476D_1CE4_E5B9;
+const SPLITMIX_MULT_B: u64 = 0x94D0_49BB_1331_11EB;
+
+/// Applies one SplitMix64 step, which turns related inputs into
+/// statistically independent outputs.
+#[inline]
+pub(crate) fn splitmix64(mut state: u64) -> u64 {
+    state = state.wrapping_add(SPLITMIX_INCREMENT);
+    state = (state ^ (state >> 30)).wrapping_mul(SPLITMIX_MULT_A);
+    state = (state ^ (state >> 27)).wrapping_mul(SPLITMIX_MULT_B);
+    state ^ (state >> 31)
+}
+
+/// A randomized component seeded from the master seed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SeedStream {
+    /// HNSW level sampling, from which per-worker RNGs are derived.
+    HnswLevels,
+    /// Selection of the points clustered by a sampled run.
+    Sample,
+    /// Selection of the point pairs probed by [`crate::Chutoro::dry_run`].
+    DryRunProbe,
+    /// Selection of the triples sampled by
+    /// [`crate::ChutoroBuilder::with_triangle_check`].
+    TriangleCheck,
+    /// Initial neighbours and per-round samples of
+    /// [`crate::GraphBuilder::NnDescent`].
+    NnDescent,
+}
+
+impl SeedStream {
+    /// Returns the seed this component uses under `master`.
+    ///
+    /// The derivation is part of the public contract and will not change
+    /// without a major version bump.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::SeedStream;
+    ///
+    /// let levels = SeedStream::HnswLevels.derive(42);
+    /// assert_eq!(levels, SeedStream::HnswLevels.derive(42));
+    /// assert_ne!(levels, SeedStream::Sample.derive(42));
+    /// ```
+    #[must_use]
+    pub fn derive(self, master: u64) -> u64 {
+        splitmix64(master ^ self.constant())
+    }
+
+    #[rustfmt::skip]
+    fn constant(self) -> u64 {
+        match self {
+            Self::HnswLevels => 0x484E_5357_4C56_4C53, // "HNSWLVLS"
+            Self::Sample => 0x5341_4D50_4C49_4E47,     // "SAMPLING"
+            Self::DryRunProbe => 0x4452_5950_524F_4245, // "DRYPROBE"
+            Self::TriangleCheck => 0x5452_4941_4E47_4C45, // "TRIANGLE"
+            Self::NnDescent => 0x4E4E_4445_5343_4E54,     // "NNDESCNT"
+        }
+    }
+}
+
+/// The seeds a run used, recorded so any result can be reproduced.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::SeedReport;
+///
+/// let report = SeedReport::new(Some(42), 7, None);
+/// assert_eq!(report.master(), Some(42));
+/// assert_eq!(report.hnsw(), 7);
+/// assert_eq!(report.sample(), None);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeedReport {
+    master: Option<u64>,
+    hnsw: u64,
+    sample: Option<u64>,
+}
+
+impl SeedReport {
+    /// Creates a report from the master seed and the component seeds.
+    #[must_use]
+    pub fn new(master: Option<u64>, hnsw: u64, sample: Option<u64>) -> Self {
+        Self {
+            master,
+            hnsw,
+            sample,
+        }
+    }
+
+    /// Returns the master seed, when the run was configured with one.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn master(&self) -> Option<u64> { self.master }
+
+    /// Returns the HNSW level-sampling seed.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn hnsw(&self) -> u64 { self.hnsw }
+
+    /// Returns the sample-selection seed, when the run was sampled.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn sample(&self) -> Option<u64> { self.sample }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::hnsw(SeedStream::HnswLevels)]
+    #[case::sample(SeedStream::Sample)]
+    #[case::dry_run_probe(SeedStream::DryRunProbe)]
+    #[case::triangle_check(SeedStream::TriangleCheck)]
+    #[case::nn_descent(SeedStream::NnDescent)]
+    fn derivation_is_stable(#[case] stream: SeedStream) {
+        assert_eq!(stream.derive(0), splitmix64(stream.constant()));
+        assert_ne!(stream.derive(0), stream.derive(1));
+    }
+
+    #[rstest]
+    fn splitmix_matches_the_reference_sequence() {
+        // First output of the reference SplitMix64 generator seeded with 0.
+        assert_eq!(splitmix64(0), 0xE220_A839_7B1D_CDAF);
+    }
+}
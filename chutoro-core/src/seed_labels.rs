@@ -0,0 +1,231 @@
+//! Semi-supervised anchoring of clusters to known labels.
+//!
+//! Taxonomy-anchored workflows know the class of a few points before
+//! clustering. [`crate::ChutoroBuilder::with_seed_labels`] records those
+//! points, and once the pipeline has run, every cluster containing seeds
+//! adopts their label while clusters without seeds are numbered after the
+//! last seed label. Clusters whose seeds disagree are reported in a
+//! [`SeedLabelReport`] instead of failing the run.
+
+use std::collections::BTreeMap;
+
+use crate::{ClusterId, ClusteringResult};
+
+/// A found cluster whose seeds carried more than one label.
+///
+/// The cluster adopts the label most of its seeds carry, preferring the
+/// smallest label on a tie; the seeds themselves keep their own labels.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::{ClusterId, SeedLabelConflict};
+///
+/// let conflict = SeedLabelConflict::new(ClusterId::new(1), vec![ClusterId::new(0), ClusterId::new(1)]);
+/// assert_eq!(conflict.adopted(), ClusterId::new(1));
+/// assert_eq!(conflict.labels().len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeedLabelConflict {
+    adopted: ClusterId,
+    labels: Vec<ClusterId>,
+}
+
+impl SeedLabelConflict {
+    /// Records a cluster that adopted `adopted` although its seeds carried
+    /// every label in `labels`.
+    #[must_use]
+    pub fn new(adopted: ClusterId, labels: Vec<ClusterId>) -> Self {
+        Self { adopted, labels }
+    }
+
+    /// Returns the label the cluster adopted.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn adopted(&self) -> ClusterId { self.adopted }
+
+    /// Returns the distinct seed labels found in the cluster, in ascending
+    /// order.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn labels(&self) -> &[ClusterId] { &self.labels }
+}
+
+/// Describes how seed labels were propagated to the clusters of a run.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::SeedLabelReport;
+///
+/// let report = SeedLabelReport::new(4, 2, 1, Vec::new());
+/// assert_eq!(report.anchored_clusters(), 2);
+/// assert!(report.conflicts().is_empty());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeedLabelReport {
+    seeds: usize,
+    anchored_clusters: usize,
+    noise_seeds: usize,
+    conflicts: Vec<SeedLabelConflict>,
+}
+
+impl SeedLabelReport {
+    /// Creates a report for `seeds` labelled points, which anchored
+    /// `anchored_clusters` found clusters and of which `noise_seeds` fell in
+    /// noise.
+    #[must_use]
+    pub fn new(
+        seeds: usize,
+        anchored_clusters: usize,
+        noise_seeds: usize,
+        conflicts: Vec<SeedLabelConflict>,
+    ) -> Self {
+        Self {
+            seeds,
+            anchored_clusters,
+            noise_seeds,
+            conflicts,
+        }
+    }
+
+    /// Returns how many points carried a known label.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn seeds(&self) -> usize { self.seeds }
+
+    /// Returns how many clusters found by the pipeline contained seeds and
+    /// adopted a seed label.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn anchored_clusters(&self) -> usize { self.anchored_clusters }
+
+    /// Returns how many seeds the pipeline left as noise. They keep their
+    /// known label without anchoring a cluster.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn noise_seeds(&self) -> usize { self.noise_seeds }
+
+    /// Returns the clusters whose seeds disagreed.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn conflicts(&self) -> &[SeedLabelConflict] { &self.conflicts }
+}
+
+/// Returns why `seeds` cannot be used, if they are invalid. Labels must be
+/// contiguous from zero, so the anchored result keeps contiguous
+/// identifiers, and each point may be seeded once.
+pub(crate) fn validate(seeds: &[(usize, ClusterId)]) -> Option<String> {
+    let mut points = BTreeMap::new();
+    for &(point, label) in seeds {
+        if let Some(previous) = points.insert(point, label) {
+            return Some(format!(
+                "point {point} is seeded with both {} and {}",
+                previous.get(),
+                label.get()
+            ));
+        }
+    }
+    let mut labels: Vec<u64> = seeds.iter().map(|(_, label)| label.get()).collect();
+    labels.sort_unstable();
+    labels.dedup();
+    labels
+        .iter()
+        .zip(0_u64..)
+        .find(|(label, expected)| *label != expected)
+        .map(|(_, missing)| {
+            format!("seed labels must be contiguous from zero; {missing} is unused")
+        })
+}
+
+/// Returns why `seeds` cannot label a run over `items` points, if a seed
+/// lies beyond the data source.
+pub(crate) fn out_of_range(seeds: &[(usize, ClusterId)], items: usize) -> Option<String> {
+    seeds
+        .iter()
+        .find(|(point, _)| *point >= items)
+        .map(|(point, _)| format!("seeded point {point} is out of range for {items} points"))
+}
+
+/// Relabels `clustering` so clusters containing seeds adopt their label.
+///
+/// Seed labels keep their values, unseeded clusters follow them in their
+/// original order, and the noise label comes last; it is retired when every
+/// noise point was a seed.
+pub(crate) fn anchor(
+    clustering: ClusteringResult,
+    seeds: &[(usize, ClusterId)],
+) -> ClusteringResult {
+    let noise = clustering.noise_label();
+    let (votes, noise_seeds) = tally(clustering.assignments(), noise, seeds);
+    let mut mapping = BTreeMap::new();
+    let mut conflicts = Vec::new();
+    for (&found, labels) in &votes {
+        let adopted = majority(labels);
+        if labels.len() > 1 {
+            conflicts.push(SeedLabelConflict::new(
+                adopted,
+                labels.keys().copied().collect(),
+            ));
+        }
+        mapping.insert(found, adopted);
+    }
+    let mut next = seeds
+        .iter()
+        .map(|(_, label)| label.get() + 1)
+        .max()
+        .unwrap_or(0);
+    for found in (0..clustering.cluster_count() as u64).map(ClusterId::new) {
+        if Some(found) != noise && !mapping.contains_key(&found) {
+            mapping.insert(found, ClusterId::new(next));
+            next += 1;
+        }
+    }
+
+    let relabelled_noise = ClusterId::new(next);
+    let mut assignments: Vec<ClusterId> = clustering
+        .assignments()
+        .iter()
+        .map(|found| mapping.get(found).copied().unwrap_or(relabelled_noise))
+        .collect();
+    for &(point, label) in seeds {
+        assignments[point] = label;
+    }
+    let noise_label = noise.and(
+        assignments
+            .contains(&relabelled_noise)
+            .then_some(relabelled_noise),
+    );
+    let report = SeedLabelReport::new(seeds.len(), votes.len(), noise_seeds, conflicts);
+    clustering.with_anchored_assignments(assignments, noise_label, Some(report))
+}
+
+/// Votes per seed label, keyed by the found cluster each seed fell in.
+type Votes = BTreeMap<ClusterId, BTreeMap<ClusterId, usize>>;
+
+/// Counts the seed labels in each found cluster, and the seeds left as noise.
+fn tally(
+    labels: &[ClusterId],
+    noise: Option<ClusterId>,
+    seeds: &[(usize, ClusterId)],
+) -> (Votes, usize) {
+    let mut votes = Votes::new();
+    let mut noise_seeds = 0;
+    for &(point, label) in seeds {
+        match labels[point] {
+            found if Some(found) == noise => noise_seeds += 1,
+            found => *votes.entry(found).or_default().entry(label).or_default() += 1,
+        }
+    }
+    (votes, noise_seeds)
+}
+
+/// Returns the label most seeds voted for, preferring the smallest on a tie.
+fn majority(labels: &BTreeMap<ClusterId, usize>) -> ClusterId {
+    labels
+        .iter()
+        .max_by(|(left, left_votes), (right, right_votes)| {
+            left_votes.cmp(right_votes).then(right.cmp(left))
+        })
+        .map_or(ClusterId::new(0), |(&label, _)| label)
+}
@@ -0,0 +1,295 @@
+//! Candidate-edge sparsification ahead of MST construction.
+//!
+//! Dense harvests dominate the time and memory spent in the MST stage. An
+//! [`EdgeBudget`] trades exactness for a smaller graph: every node keeps its
+//! lightest incident edges, and the remaining budget is filled with the
+//! globally lightest edges. The resulting [`SparsificationReport`] records how
+//! much of the harvest was discarded.
+
+use std::num::NonZeroUsize;
+
+#[cfg(feature = "cpu")]
+use rayon::prelude::*;
+
+#[cfg(feature = "cpu")]
+use crate::{CandidateEdge, EdgeHarvest};
+
+/// Number of lightest incident edges every node retains by default.
+const DEFAULT_PER_NODE: usize = 2;
+
+/// Caps the number of candidate edges passed to MST construction.
+///
+/// Edges are ranked by weight with deterministic `(source, target, sequence)`
+/// tie-breaks. Each node first keeps its `per_node` lightest incident edges so
+/// sparsification never isolates a point that had neighbours; any budget left
+/// after that is spent on the lightest remaining edges. When the per-node floor
+/// alone exceeds `max_edges`, the floor wins and the budget is overshot.
+///
+/// # Examples
+/// ```
+/// use std::num::NonZeroUsize;
+///
+/// use chutoro_core::EdgeBudget;
+///
+/// let max_edges = NonZeroUsize::new(1_000).expect("literal is non-zero");
+/// let budget = EdgeBudget::new(max_edges).with_per_node(4);
+/// assert_eq!(budget.max_edges().get(), 1_000);
+/// assert_eq!(budget.per_node(), 4);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EdgeBudget {
+    max_edges: NonZeroUsize,
+    per_node: usize,
+}
+
+impl EdgeBudget {
+    /// Creates a budget of `max_edges` candidate edges with the default
+    /// per-node floor of two edges.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use chutoro_core::EdgeBudget;
+    ///
+    /// let budget = EdgeBudget::new(NonZeroUsize::new(64).expect("literal is non-zero"));
+    /// assert_eq!(budget.per_node(), 2);
+    /// ```
+    #[must_use]
+    pub fn new(max_edges: NonZeroUsize) -> Self {
+        Self {
+            max_edges,
+            per_node: DEFAULT_PER_NODE,
+        }
+    }
+
+    /// Overrides how many lightest incident edges each node retains.
+    ///
+    /// A value of `0` disables the floor so only the global budget applies.
+    #[must_use]
+    pub fn with_per_node(mut self, per_node: usize) -> Self {
+        self.per_node = per_node;
+        self
+    }
+
+    /// Returns the global edge budget.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn max_edges(&self) -> NonZeroUsize { self.max_edges }
+
+    /// Returns the number of edges each node retains regardless of budget.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn per_node(&self) -> usize { self.per_node }
+}
+
+/// Summarizes the effect of applying an [`EdgeBudget`] to a harvest.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::SparsificationReport;
+///
+/// let report = SparsificationReport::new(10, 6, 4);
+/// assert_eq!(report.dropped_edges(), 4);
+/// assert!(report.is_lossy());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SparsificationReport {
+    input_edges: usize,
+    retained_edges: usize,
+    per_node_edges: usize,
+}
+
+impl SparsificationReport {
+    /// Creates a report from edge counts.
+    ///
+    /// `per_node_edges` counts the retained edges selected by the per-node
+    /// floor rather than the global budget.
+    #[must_use]
+    pub fn new(input_edges: usize, retained_edges: usize, per_node_edges: usize) -> Self {
+        Self {
+            input_edges,
+            retained_edges,
+            per_node_edges,
+        }
+    }
+
+    /// Returns the number of edges in the harvest before sparsification.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn input_edges(&self) -> usize { self.input_edges }
+
+    /// Returns the number of edges passed on to MST construction.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn retained_edges(&self) -> usize { self.retained_edges }
+
+    /// Returns the number of retained edges selected by the per-node floor.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn per_node_edges(&self) -> usize { self.per_node_edges }
+
+    /// Returns the number of harvested edges that were discarded.
+    #[must_use]
+    pub fn dropped_edges(&self) -> usize {
+        self.input_edges.saturating_sub(self.retained_edges)
+    }
+
+    /// Returns whether any edges were discarded.
+    #[must_use]
+    pub fn is_lossy(&self) -> bool {
+        self.dropped_edges() > 0
+    }
+}
+
+/// Applies `budget` to `harvest`, returning the retained edges and a report.
+///
+/// Harvests already within budget are returned unchanged. Edge endpoints
+/// outside `node_count` are kept so MST validation can report them.
+///
+/// # Examples
+/// ```
+/// use std::num::NonZeroUsize;
+///
+/// use chutoro_core::{CandidateEdge, EdgeBudget, EdgeHarvest, sparsify_harvest};
+///
+/// let harvest = EdgeHarvest::new(vec![
+///     CandidateEdge::new(0, 1, 0.1, 0),
+///     CandidateEdge::new(1, 2, 0.2, 1),
+///     CandidateEdge::new(0, 2, 0.9, 2),
+/// ]);
+/// let budget = EdgeBudget::new(NonZeroUsize::new(2).expect("literal is non-zero"))
+///     .with_per_node(0);
+/// let (sparse, report) = sparsify_harvest(&harvest, 3, budget);
+/// assert_eq!(sparse.len(), 2);
+/// assert_eq!(report.dropped_edges(), 1);
+/// ```
+#[cfg(feature = "cpu")]
+#[must_use]
+pub fn sparsify_harvest(
+    harvest: &EdgeHarvest,
+    node_count: usize,
+    budget: EdgeBudget,
+) -> (EdgeHarvest, SparsificationReport) {
+    let input_edges = harvest.len();
+    if input_edges <= budget.max_edges.get() {
+        return (
+            harvest.clone(),
+            SparsificationReport::new(input_edges, input_edges, 0),
+        );
+    }
+
+    let mut ranked: Vec<&CandidateEdge> = harvest.iter().collect();
+    ranked.par_sort_unstable();
+
+    let keep = select_per_node_floor(&ranked, node_count, budget.per_node);
+    let per_node_edges = keep.iter().filter(|kept| **kept).count();
+    let mut remaining = budget.max_edges.get().saturating_sub(per_node_edges);
+
+    let mut retained = Vec::with_capacity(budget.max_edges.get().max(per_node_edges));
+    for (edge, floor) in ranked.into_iter().zip(keep) {
+        if floor {
+            retained.push(*edge);
+        } else if remaining > 0 {
+            remaining -= 1;
+            retained.push(*edge);
+        }
+    }
+
+    let report = SparsificationReport::new(input_edges, retained.len(), per_node_edges);
+    (EdgeHarvest::new(retained), report)
+}
+
+/// Marks each ranked edge that is among the `per_node` lightest edges incident
+/// to either endpoint.
+#[cfg(feature = "cpu")]
+fn select_per_node_floor(
+    ranked: &[&CandidateEdge],
+    node_count: usize,
+    per_node: usize,
+) -> Vec<bool> {
+    let mut seen = vec![0_usize; node_count];
+    ranked
+        .iter()
+        .map(|edge| {
+            let mut claim = |node: usize| match seen.get_mut(node) {
+                Some(count) => {
+                    *count += 1;
+                    *count <= per_node
+                }
+                None => true,
+            };
+            let left = claim(edge.source());
+            let right = claim(edge.target());
+            left || right
+        })
+        .collect()
+}
+
+#[cfg(all(test, feature = "cpu"))]
+mod tests {
+    //! Unit tests for harvest sparsification.
+
+    use rstest::rstest;
+
+    use super::*;
+
+    fn budget(max_edges: usize, per_node: usize) -> EdgeBudget {
+        EdgeBudget::new(NonZeroUsize::new(max_edges).expect("test budgets are non-zero"))
+            .with_per_node(per_node)
+    }
+
+    /// A complete graph on four nodes with distinct weights.
+    fn complete_harvest() -> EdgeHarvest {
+        EdgeHarvest::new(vec![
+            CandidateEdge::new(0, 1, 0.1, 0),
+            CandidateEdge::new(2, 3, 0.2, 1),
+            CandidateEdge::new(1, 2, 0.3, 2),
+            CandidateEdge::new(0, 2, 0.4, 3),
+            CandidateEdge::new(1, 3, 0.5, 4),
+            CandidateEdge::new(0, 3, 0.6, 5),
+        ])
+    }
+
+    #[test]
+    fn harvests_within_budget_are_untouched() {
+        let harvest = complete_harvest();
+        let (sparse, report) = sparsify_harvest(&harvest, 4, budget(6, 0));
+        assert_eq!(sparse, harvest);
+        assert!(!report.is_lossy());
+        assert_eq!(report.per_node_edges(), 0);
+    }
+
+    #[rstest]
+    #[case::global_only(3, 0, &[0.1, 0.2, 0.3])]
+    #[case::floor_within_budget(3, 1, &[0.1, 0.2, 0.3])]
+    #[case::floor_overshoots_budget(1, 1, &[0.1, 0.2])]
+    fn retains_lightest_edges(
+        #[case] max_edges: usize,
+        #[case] per_node: usize,
+        #[case] expected: &[f32],
+    ) {
+        let (sparse, report) =
+            sparsify_harvest(&complete_harvest(), 4, budget(max_edges, per_node));
+        let mut weights: Vec<f32> = sparse.iter().map(CandidateEdge::distance).collect();
+        weights.sort_by(f32::total_cmp);
+        assert_eq!(weights, expected);
+        assert_eq!(report.input_edges(), 6);
+        assert_eq!(report.dropped_edges(), 6 - expected.len());
+    }
+
+    #[test]
+    fn per_node_floor_keeps_heavy_edges_to_outliers() {
+        let harvest = EdgeHarvest::new(vec![
+            CandidateEdge::new(0, 1, 0.1, 0),
+            CandidateEdge::new(1, 2, 0.1, 1),
+            CandidateEdge::new(0, 2, 0.2, 2),
+            CandidateEdge::new(2, 3, 9.0, 3),
+        ]);
+        let (sparse, report) = sparsify_harvest(&harvest, 4, budget(2, 1));
+        assert!(sparse.iter().any(|edge| edge.target() == 3));
+        assert_eq!(report.per_node_edges(), 3);
+        assert_eq!(report.retained_edges(), 3);
+    }
+}
@@ -0,0 +1,130 @@
+//! Chunk encoding and the k-way merge that reads chunks back in order.
+//!
+//! Each edge is stored as 32 little-endian bytes: the source and target as
+//! `u64`, the `f64` weight's bit pattern as `u64`, and the sequence as `u64`.
+
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    fs::File,
+    io::{self, BufReader, Read},
+    path::PathBuf,
+};
+
+use crate::MstEdge;
+
+const EDGE_BYTES: usize = 32;
+
+/// A sorted chunk written by [`super::EdgeSpill`].
+#[derive(Debug)]
+pub(super) struct ChunkFile {
+    path: PathBuf,
+    edges: usize,
+}
+
+impl ChunkFile {
+    pub(super) fn new(path: PathBuf, edges: usize) -> Self {
+        Self { path, edges }
+    }
+}
+
+pub(super) fn encode(edge: &MstEdge) -> [u8; EDGE_BYTES] {
+    let mut bytes = [0; EDGE_BYTES];
+    bytes[..8].copy_from_slice(&(edge.source() as u64).to_le_bytes());
+    bytes[8..16].copy_from_slice(&(edge.target() as u64).to_le_bytes());
+    bytes[16..24].copy_from_slice(&edge.weight_f64().to_bits().to_le_bytes());
+    bytes[24..].copy_from_slice(&edge.sequence().to_le_bytes());
+    bytes
+}
+
+fn decode(bytes: &[u8; EDGE_BYTES]) -> io::Result<MstEdge> {
+    let word = |range: std::ops::Range<usize>| {
+        let mut buffer = [0; 8];
+        buffer.copy_from_slice(&bytes[range]);
+        u64::from_le_bytes(buffer)
+    };
+    let endpoint = |value: u64| {
+        usize::try_from(value)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "endpoint overflows usize"))
+    };
+    Ok(MstEdge::new_f64(
+        endpoint(word(0..8))?,
+        endpoint(word(8..16))?,
+        f64::from_bits(word(16..24)),
+        word(24..32),
+    ))
+}
+
+/// Reads one chunk front to back.
+struct ChunkReader {
+    reader: BufReader<File>,
+    remaining: usize,
+}
+
+impl ChunkReader {
+    fn next_edge(&mut self) -> io::Result<Option<MstEdge>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        let mut bytes = [0; EDGE_BYTES];
+        self.reader.read_exact(&mut bytes)?;
+        self.remaining -= 1;
+        decode(&bytes).map(Some)
+    }
+}
+
+/// Edges from every chunk in global [`MstEdge`] order.
+///
+/// Holds the smallest unread edge of each chunk in a heap, so the merge needs
+/// one buffered reader and one edge per chunk.
+pub(super) struct MergedEdges {
+    readers: Vec<ChunkReader>,
+    front: BinaryHeap<Reverse<(MstEdge, usize)>>,
+    failed: bool,
+}
+
+impl MergedEdges {
+    pub(super) fn open(chunks: &[ChunkFile]) -> io::Result<Self> {
+        let mut readers = Vec::with_capacity(chunks.len());
+        let mut front = BinaryHeap::with_capacity(chunks.len());
+        for (position, chunk) in chunks.iter().enumerate() {
+            let mut reader = ChunkReader {
+                reader: BufReader::new(File::open(&chunk.path)?),
+                remaining: chunk.edges,
+            };
+            if let Some(edge) = reader.next_edge()? {
+                front.push(Reverse((edge, position)));
+            }
+            readers.push(reader);
+        }
+        Ok(Self {
+            readers,
+            front,
+            failed: false,
+        })
+    }
+}
+
+impl Iterator for MergedEdges {
+    type Item = io::Result<MstEdge>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let Reverse((edge, position)) = self.front.pop()?;
+        let refill = self
+            .readers
+            .get_mut(position)
+            .map_or(Ok(None), ChunkReader::next_edge);
+        match refill {
+            Ok(Some(next)) => self.front.push(Reverse((next, position))),
+            Ok(None) => {}
+            Err(error) => {
+                self.failed = true;
+                return Some(Err(error));
+            }
+        }
+        Some(Ok(edge))
+    }
+}
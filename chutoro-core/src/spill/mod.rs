@@ -0,0 +1,201 @@
+//! Disk-backed MST input for runs that cannot hold the weighted harvest.
+//!
+//! With [`crate::ChutoroBuilder::with_weighted_edge_spill_directory`] set, the
+//! mutual-reachability edges are not collected into an in-memory
+//! [`EdgeHarvest`]. They are validated and buffered in chunks; each full chunk
+//! is sorted and written to a temporary file, and the files are then merged
+//! back in [`MstEdge`] order straight into Kruskal. Only one chunk, the merge
+//! front, and the current equal-weight group are resident at a time. The
+//! files live in a fresh subdirectory that is removed when the run ends,
+//! whether it succeeds or fails.
+
+mod merge;
+
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use crate::{
+    CandidateEdge, DataSource, MstEdge, Result, SparsificationReport,
+    builder::PipelineOptions,
+    cpu_pipeline::{HarvestInputs, map_cpu_mst_error, pipeline_core_distances, weighted_edges},
+    error::ChutoroError,
+    mst::{canonical_mst_edge, kruskal_sorted_stream},
+    stages::StageArtefact,
+    timings::{Stage, StageClock},
+};
+
+use self::merge::{ChunkFile, MergedEdges};
+
+/// Fewest edges buffered per chunk, about 32 MiB of [`MstEdge`]s.
+const MIN_CHUNK_EDGES: usize = 1 << 20;
+/// Most chunk files a run creates, bounding the open files during the merge.
+const MAX_CHUNKS: usize = 256;
+
+/// Distinguishes spill directories created by one process.
+static SPILL_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Weights `harvested`, spills the result to `directory`, and builds the
+/// spanning forest from the merged chunks.
+///
+/// The built-in harvest stage streams each re-weighted edge to disk. A custom
+/// harvest stage or an edge budget needs the weighted harvest in memory, so
+/// it is weighted as usual, reported to the artefact hook, and spilled before
+/// Kruskal runs.
+pub(crate) fn spilled_forest<D: DataSource + Sync>(
+    inputs: &HarvestInputs<'_, D>,
+    directory: &Path,
+    options: &PipelineOptions,
+    clock: &mut StageClock,
+) -> Result<(Vec<MstEdge>, Vec<f32>, Option<SparsificationReport>)> {
+    let HarvestInputs {
+        context, harvested, ..
+    } = *inputs;
+    let items = context.len();
+    let chunk_edges = harvested.len().div_ceil(MAX_CHUNKS).max(MIN_CHUNK_EDGES);
+    let mut spill = EdgeSpill::create(directory, items, chunk_edges)?;
+    let (core_distances, sparsification) =
+        if options.stages.harvest.is_none() && options.edge_budget.is_none() {
+            let core = pipeline_core_distances(inputs, options)?;
+            for edge in harvested.iter() {
+                spill.push(&core.weight_edge(inputs.source, inputs.guard, edge)?)?;
+            }
+            (core.into_reported(), None)
+        } else {
+            let (mutual_harvest, core, report) = weighted_edges(inputs, options)?;
+            options
+                .stages
+                .notify(StageArtefact::Harvest(&mutual_harvest));
+            for edge in mutual_harvest.iter() {
+                spill.push(edge)?;
+            }
+            (core, report)
+        };
+    clock.lap(Stage::EdgeHarvest);
+    Ok((spill.spanning_forest()?, core_distances, sparsification))
+}
+
+/// Sorted chunk files of validated MST edges under a private directory.
+#[derive(Debug)]
+pub(crate) struct EdgeSpill {
+    directory: PathBuf,
+    node_count: usize,
+    chunk_edges: usize,
+    buffer: Vec<MstEdge>,
+    chunks: Vec<ChunkFile>,
+}
+
+impl EdgeSpill {
+    /// Creates a fresh subdirectory of `parent` to hold the chunks.
+    ///
+    /// # Errors
+    /// Returns [`ChutoroError::Spill`] when the subdirectory cannot be
+    /// created.
+    pub(crate) fn create(parent: &Path, node_count: usize, chunk_edges: usize) -> Result<Self> {
+        let directory = loop {
+            let candidate = parent.join(format!(
+                "chutoro-spill-{}-{}",
+                std::process::id(),
+                SPILL_COUNTER.fetch_add(1, Ordering::Relaxed)
+            ));
+            match fs::create_dir(&candidate) {
+                Ok(()) => break candidate,
+                Err(error) if error.kind() == io::ErrorKind::AlreadyExists => {}
+                Err(error) => return Err(spill_error(parent, &error)),
+            }
+        };
+        Ok(Self {
+            directory,
+            node_count,
+            chunk_edges: chunk_edges.max(1),
+            buffer: Vec::new(),
+            chunks: Vec::new(),
+        })
+    }
+
+    /// Returns the subdirectory holding this spill's chunk files.
+    #[cfg(test)]
+    pub(crate) fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Validates `edge` and buffers it, writing a chunk once the buffer is
+    /// full. Self-loops are dropped.
+    ///
+    /// # Errors
+    /// Returns [`ChutoroError::CpuMstFailure`] for an invalid edge and
+    /// [`ChutoroError::Spill`] when a chunk cannot be written.
+    pub(crate) fn push(&mut self, edge: &CandidateEdge) -> Result<()> {
+        let Some(edge) = canonical_mst_edge(edge, self.node_count).map_err(map_cpu_mst_error)?
+        else {
+            return Ok(());
+        };
+        self.buffer.push(edge);
+        if self.buffer.len() >= self.chunk_edges {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Writes the remaining buffer and runs Kruskal over the merged chunks.
+    ///
+    /// # Errors
+    /// Returns [`ChutoroError::Spill`] when a chunk cannot be written or read
+    /// back, and [`ChutoroError::CpuMstFailure`] when Kruskal fails.
+    pub(crate) fn spanning_forest(mut self) -> Result<Vec<MstEdge>> {
+        self.flush()?;
+        self.buffer = Vec::new();
+        let directory = self.directory.clone();
+        let merged = MergedEdges::open(&self.chunks)
+            .map_err(|error| spill_error(&directory, &error))?
+            .map(|edge| edge.map_err(|error| spill_error(&directory, &error)));
+        kruskal_sorted_stream(self.node_count, merged, map_cpu_mst_error)
+            .map(|forest| forest.into_edges())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        self.buffer.sort_unstable();
+        let path = self
+            .directory
+            .join(format!("chunk-{:05}.bin", self.chunks.len()));
+        write_chunk(&path, &self.buffer).map_err(|error| spill_error(&path, &error))?;
+        self.chunks.push(ChunkFile::new(path, self.buffer.len()));
+        self.buffer.clear();
+        Ok(())
+    }
+}
+
+impl Drop for EdgeSpill {
+    fn drop(&mut self) {
+        // Best effort: a leftover directory wastes disk but cannot affect
+        // later runs, which always create a fresh one.
+        let _ = fs::remove_dir_all(&self.directory);
+    }
+}
+
+fn write_chunk(path: &Path, edges: &[MstEdge]) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    for edge in edges {
+        writer.write_all(&merge::encode(edge))?;
+    }
+    writer.flush()
+}
+
+fn spill_error(path: &Path, error: &io::Error) -> ChutoroError {
+    ChutoroError::Spill {
+        path: Arc::from(path),
+        reason: Arc::from(error.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,103 @@
+//! Unit tests for chunked edge spilling and the sorted merge.
+
+use rand::{Rng, SeedableRng, rngs::SmallRng};
+use rstest::rstest;
+
+use super::*;
+use crate::{EdgeHarvest, parallel_kruskal};
+
+/// Edges with few distinct weights, repeated pairs, and self-loops, so the
+/// merge has ties and duplicates to resolve across chunks.
+fn noisy_harvest(nodes: usize, edges: usize, seed: u64) -> EdgeHarvest {
+    let mut rng = SmallRng::seed_from_u64(seed);
+    EdgeHarvest::new(
+        (0..edges)
+            .map(|sequence| {
+                let weight = f32::from(rng.gen_range(0_u8..6));
+                CandidateEdge::new(
+                    rng.gen_range(0..nodes),
+                    rng.gen_range(0..nodes),
+                    weight,
+                    sequence as u64,
+                )
+            })
+            .collect(),
+    )
+}
+
+#[rstest]
+#[case::single_chunk(1_000)]
+#[case::many_chunks(7)]
+#[case::one_edge_per_chunk(1)]
+fn spilled_forest_matches_in_memory_kruskal(#[case] chunk_edges: usize) {
+    let parent = tempfile::tempdir().expect("tempdir");
+    let harvest = noisy_harvest(40, 300, 11);
+    let mut spill = EdgeSpill::create(parent.path(), 40, chunk_edges).expect("spill");
+    for edge in harvest.iter() {
+        spill.push(edge).expect("valid edge");
+    }
+
+    let spilled = spill.spanning_forest().expect("merge succeeds");
+    let expected = parallel_kruskal(40, &harvest).expect("valid graph");
+
+    assert_eq!(spilled, expected.edges());
+}
+
+#[rstest]
+fn spilled_edges_keep_double_precision_weights() {
+    let parent = tempfile::tempdir().expect("tempdir");
+    let mut spill = EdgeSpill::create(parent.path(), 3, 1).expect("spill");
+    // One edge per chunk, so the merge orders chunks whose weights differ
+    // only beyond f32 precision.
+    spill
+        .push(&CandidateEdge::new_f64(0, 2, 1.0 + 1e-12, 0))
+        .expect("valid edge");
+    spill
+        .push(&CandidateEdge::new_f64(1, 2, 1.0, 1))
+        .expect("valid edge");
+    spill
+        .push(&CandidateEdge::new_f64(0, 1, 1.0 + 2e-12, 2))
+        .expect("valid edge");
+
+    let spilled = spill.spanning_forest().expect("merge succeeds");
+
+    let weights: Vec<f64> = spilled.iter().map(MstEdge::weight_f64).collect();
+    assert_eq!(weights, vec![1.0, 1.0 + 1e-12]);
+}
+
+#[rstest]
+fn spill_directory_is_removed_when_dropped() {
+    let parent = tempfile::tempdir().expect("tempdir");
+    let mut spill = EdgeSpill::create(parent.path(), 4, 1).expect("spill");
+    spill
+        .push(&CandidateEdge::new(0, 1, 1.0, 0))
+        .expect("valid edge");
+    let directory = spill.directory().to_path_buf();
+    assert!(directory.join("chunk-00000.bin").is_file());
+
+    drop(spill);
+
+    assert!(!directory.exists());
+}
+
+#[rstest]
+fn out_of_range_edges_are_rejected_before_spilling() {
+    let parent = tempfile::tempdir().expect("tempdir");
+    let mut spill = EdgeSpill::create(parent.path(), 2, 8).expect("spill");
+
+    let err = spill
+        .push(&CandidateEdge::new(0, 5, 1.0, 0))
+        .expect_err("node 5 is outside the graph");
+
+    assert!(matches!(err, ChutoroError::CpuMstFailure { .. }));
+}
+
+#[rstest]
+fn missing_parent_directory_is_a_spill_error() {
+    let parent = tempfile::tempdir().expect("tempdir");
+    let missing = parent.path().join("missing");
+
+    let err = EdgeSpill::create(&missing, 2, 8).expect_err("parent is missing");
+
+    assert!(matches!(err, ChutoroError::Spill { ref path, .. } if **path == *missing));
+}
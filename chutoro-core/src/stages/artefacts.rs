@@ -0,0 +1,80 @@
+//! Read-only access to intermediate pipeline artefacts.
+//!
+//! A [`StageArtefactHook`] registered with
+//! [`crate::ChutoroBuilder::on_stage_complete`] is called once per stage with
+//! borrows of what the stage produced, so tools can persist or visualise
+//! intermediates in whatever format they need. The borrows end when the hook
+//! returns; nothing is cloned unless the hook clones it.
+
+use std::{fmt, sync::Arc};
+
+use crate::{CondensedTree, CpuHnsw, EdgeHarvest, MstEdge};
+
+/// An intermediate result delivered to a [`StageArtefactHook`].
+///
+/// Point ids refer to the clustered source; for sampled runs that is the
+/// sample, in sampled order.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub enum StageArtefact<'a> {
+    /// The index stage finished (or a prebuilt index was adopted).
+    Index {
+        /// The HNSW index used for core distances.
+        index: &'a CpuHnsw,
+        /// The raw candidate edges harvested while building it.
+        harvest: &'a EdgeHarvest,
+    },
+    /// The NN-descent graph builder finished, in place of the index stage:
+    /// the k-nearest-neighbour graph's edges, which are also the raw
+    /// harvest.
+    Graph(&'a EdgeHarvest),
+    /// The harvest stage finished: the weighted edges passed to MST
+    /// construction, after any edge budget. Not reported when edges spill
+    /// straight to disk.
+    Harvest(&'a EdgeHarvest),
+    /// The MST stage finished: the forest edges, including any bridge edges
+    /// added by component repair.
+    Mst(&'a [MstEdge]),
+    /// The built-in hierarchy stage finished. Custom hierarchy stages do not
+    /// produce a condensed tree.
+    CondensedTree(CondensedTree<'a>),
+}
+
+/// Receives [`StageArtefact`]s as a run progresses.
+///
+/// Closures taking a [`StageArtefact`] implement this trait.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::{ChutoroBuilder, StageArtefact};
+///
+/// let builder = ChutoroBuilder::new().on_stage_complete(|artefact: StageArtefact<'_>| {
+///     if let StageArtefact::Mst(edges) = artefact {
+///         println!("forest has {} edges", edges.len());
+///     }
+/// });
+/// assert!(builder.stage_hook().is_some());
+/// ```
+pub trait StageArtefactHook: Send + Sync {
+    /// Inspects `artefact`; called on the thread running the pipeline.
+    fn on_stage_complete(&self, artefact: StageArtefact<'_>);
+}
+
+impl<F> StageArtefactHook for F
+where
+    F: Fn(StageArtefact<'_>) + Send + Sync,
+{
+    fn on_stage_complete(&self, artefact: StageArtefact<'_>) {
+        self(artefact);
+    }
+}
+
+/// Shared hook handle; closures are not `Debug`, so this prints a marker.
+#[derive(Clone)]
+pub(crate) struct ArtefactHook(pub(crate) Arc<dyn StageArtefactHook>);
+
+impl fmt::Debug for ArtefactHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ArtefactHook(..)")
+    }
+}
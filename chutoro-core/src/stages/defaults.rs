@@ -0,0 +1,128 @@
+//! Built-in implementations of the pipeline stage traits.
+//!
+//! The orchestration calls the same helpers directly with the concrete data
+//! source when a stage is not overridden; these types route a
+//! [`StageContext`]'s type-erased source through them so custom stages can
+//! delegate to or wrap the defaults.
+
+use crate::{
+    ClusteringResult, CpuHnsw, DataSource, DataSourceError, EdgeHarvest, IdMap, MetricDescriptor,
+    MstEdge, Result,
+    cpu_pipeline::{extract_clustering, map_cpu_hnsw_error, map_cpu_mst_error, weight_harvest},
+    parallel_kruskal_owned,
+};
+
+use super::{HarvestStage, HierarchyStage, IndexStage, MstStage, StageContext, WeightedHarvest};
+
+/// Builds the index with [`CpuHnsw::build_with_edges`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultIndexStage;
+
+impl IndexStage for DefaultIndexStage {
+    fn build(&self, context: &StageContext<'_>) -> Result<(CpuHnsw, EdgeHarvest)> {
+        let source = DynSource(context.source());
+        CpuHnsw::build_with_edges(&source, context.hnsw_params().clone())
+            .map_err(|error| map_cpu_hnsw_error(&source, error))
+    }
+}
+
+/// Weights edges with mutual-reachability distances derived from each
+/// point's HNSW core neighbourhood.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultHarvestStage;
+
+impl HarvestStage for DefaultHarvestStage {
+    fn weight(
+        &self,
+        context: &StageContext<'_>,
+        index: &CpuHnsw,
+        harvest: &EdgeHarvest,
+    ) -> Result<WeightedHarvest> {
+        let source = DynSource(context.source());
+        weight_harvest(&source, index, harvest, context.min_cluster_size())
+    }
+}
+
+/// Computes the forest with [`parallel_kruskal_owned`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultMstStage;
+
+impl MstStage for DefaultMstStage {
+    fn spanning_forest(
+        &self,
+        context: &StageContext<'_>,
+        edges: EdgeHarvest,
+    ) -> Result<Vec<MstEdge>> {
+        parallel_kruskal_owned(context.len(), edges)
+            .map(|forest| forest.into_edges())
+            .map_err(map_cpu_mst_error)
+    }
+}
+
+/// Condenses the single-linkage hierarchy and selects the most stable
+/// clusters, attaching the noise label and membership scores.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultHierarchyStage;
+
+impl HierarchyStage for DefaultHierarchyStage {
+    fn extract(&self, context: &StageContext<'_>, edges: &[MstEdge]) -> Result<ClusteringResult> {
+        extract_clustering(context.len(), edges, context.hierarchy_config(), None)
+            .map(|(clustering, _)| clustering)
+    }
+}
+
+/// Sized view of a type-erased source, so generic pipeline helpers accept it.
+struct DynSource<'a>(&'a (dyn DataSource + Sync));
+
+impl DataSource for DynSource<'_> {
+    #[rustfmt::skip]
+    fn len(&self) -> usize { self.0.len() }
+
+    #[rustfmt::skip]
+    fn name(&self) -> &str { self.0.name() }
+
+    fn metric_descriptor(&self) -> MetricDescriptor {
+        self.0.metric_descriptor()
+    }
+
+    fn dimension_hint(&self) -> Option<usize> {
+        self.0.dimension_hint()
+    }
+
+    fn row_ids(&self) -> Option<&IdMap> {
+        self.0.row_ids()
+    }
+
+    fn distance(&self, i: usize, j: usize) -> core::result::Result<f32, DataSourceError> {
+        self.0.distance(i, j)
+    }
+
+    fn distance_f64(&self, i: usize, j: usize) -> core::result::Result<f64, DataSourceError> {
+        self.0.distance_f64(i, j)
+    }
+
+    fn batch_distances(
+        &self,
+        query: usize,
+        candidates: &[usize],
+    ) -> core::result::Result<Vec<f32>, DataSourceError> {
+        self.0.batch_distances(query, candidates)
+    }
+
+    fn batch_distances_into(
+        &self,
+        query: usize,
+        targets: &[usize],
+        out: &mut [f32],
+    ) -> core::result::Result<(), DataSourceError> {
+        self.0.batch_distances_into(query, targets, out)
+    }
+
+    fn distance_batch(
+        &self,
+        pairs: &[(usize, usize)],
+        out: &mut [f32],
+    ) -> core::result::Result<(), DataSourceError> {
+        self.0.distance_batch(pairs, out)
+    }
+}
@@ -0,0 +1,134 @@
+//! Selection between configured stage overrides and the built-in stages.
+//!
+//! Custom stage output is checked against the source before later stages
+//! index into it, so a faulty extension fails the run with
+//! [`ChutoroError::InvalidStageOutput`] instead of panicking.
+
+use std::sync::Arc;
+
+use tracing::debug;
+
+use crate::{
+    CpuHnsw, DataSource, EdgeHarvest, MinimumSpanningForest, MstEdge, Result,
+    cpu_pipeline::{map_cpu_hnsw_error, map_cpu_mst_error},
+    error::ChutoroError,
+    hnsw::HarvestProvenance,
+    parallel_kruskal_owned,
+};
+
+use super::{PipelineStages, StageContext};
+
+/// Builds the index with the configured index stage, or the built-in HNSW
+/// build when none is set.
+pub(crate) fn build_index<D: DataSource + Sync>(
+    source: &D,
+    context: &StageContext<'_>,
+    stages: &PipelineStages,
+) -> Result<(CpuHnsw, EdgeHarvest)> {
+    let Some(stage) = &stages.index else {
+        return CpuHnsw::build_with_edges(source, context.hnsw_params().clone())
+            .map_err(|error| map_cpu_hnsw_error(source, error));
+    };
+    let (index, harvest) = stage.build(context)?;
+    ensure_stage_output("index", index.len(), source.len(), "indexed points")?;
+    if let Some(edge) = harvest
+        .iter()
+        .find(|edge| edge.source().max(edge.target()) >= source.len())
+    {
+        return Err(invalid_stage_output(
+            "index",
+            format!(
+                "harvest edge ({}, {}) references a point outside the source",
+                edge.source(),
+                edge.target()
+            ),
+        ));
+    }
+    Ok((index, harvest))
+}
+
+/// Builds the index like [`build_index`], recording where each harvested
+/// edge was found when `trace` is set and the built-in build runs.
+pub(crate) fn build_traced_index<D: DataSource + Sync>(
+    source: &D,
+    context: &StageContext<'_>,
+    stages: &PipelineStages,
+    trace: bool,
+) -> Result<(CpuHnsw, EdgeHarvest, Option<HarvestProvenance>)> {
+    if !trace || stages.index.is_some() {
+        return build_index(source, context, stages).map(|(index, harvest)| (index, harvest, None));
+    }
+    CpuHnsw::build_traced(source, context.hnsw_params().clone())
+        .map(|(index, harvest, provenance)| (index, harvest, Some(provenance)))
+        .map_err(|error| map_cpu_hnsw_error(source, error))
+}
+
+/// Computes the spanning forest with the configured MST stage, or parallel
+/// Kruskal when none is set.
+pub(crate) fn spanning_forest(
+    context: &StageContext<'_>,
+    edges: EdgeHarvest,
+    stages: &PipelineStages,
+) -> Result<Vec<MstEdge>> {
+    let Some(stage) = &stages.mst else {
+        return parallel_kruskal_owned(context.len(), edges)
+            .map(|forest| {
+                log_diagnostics(&forest);
+                forest.into_edges()
+            })
+            .map_err(map_cpu_mst_error);
+    };
+    let forest = stage.spanning_forest(context, edges)?;
+    if let Some(edge) = forest.iter().find(|edge| edge.target() >= context.len()) {
+        return Err(invalid_stage_output(
+            "mst",
+            format!(
+                "forest edge ({}, {}) references a point outside the source",
+                edge.source(),
+                edge.target()
+            ),
+        ));
+    }
+    Ok(forest)
+}
+
+/// Logs what the forest computation dropped and how the edges split across
+/// components, which explains many surprising clusterings.
+fn log_diagnostics(forest: &MinimumSpanningForest) {
+    let diagnostics = forest.diagnostics();
+    debug!(
+        self_edges_dropped = diagnostics.self_edges_dropped(),
+        duplicate_edges_merged = diagnostics.duplicate_edges_merged(),
+        weight_range = ?diagnostics.weight_range(),
+        components = diagnostics.component_edge_counts().len(),
+        isolated_points = diagnostics
+            .component_edge_counts()
+            .iter()
+            .filter(|&&count| count == 0)
+            .count(),
+        "spanning forest built"
+    );
+}
+
+/// Rejects custom stage output whose size does not match the source.
+pub(crate) fn ensure_stage_output(
+    stage: &'static str,
+    actual: usize,
+    items: usize,
+    what: &str,
+) -> Result<()> {
+    if actual == items {
+        return Ok(());
+    }
+    Err(invalid_stage_output(
+        stage,
+        format!("produced {actual} {what} for a {items}-point source"),
+    ))
+}
+
+fn invalid_stage_output(stage: &'static str, reason: String) -> ChutoroError {
+    ChutoroError::InvalidStageOutput {
+        stage: Arc::from(stage),
+        reason: Arc::from(reason),
+    }
+}
@@ -0,0 +1,236 @@
+//! Substitutable stages of the CPU clustering pipeline.
+//!
+//! [`crate::Chutoro::run`] executes four stages in order: build an HNSW index
+//! and harvest candidate edges, weight the harvest for MST construction,
+//! compute a minimum spanning forest, and extract a flat clustering from it.
+//! Each stage is described by a trait so research extensions can replace a
+//! single step through the builder (for example a custom edge weighting or a
+//! precomputed MST) while the orchestration, policies, and reports stay in
+//! the core. Stages that are not overridden run the built-in implementation,
+//! which the `Default*Stage` types also expose for composition.
+
+mod artefacts;
+mod defaults;
+mod dispatch;
+
+use std::{fmt, num::NonZeroUsize, sync::Arc};
+
+use crate::{
+    ClusteringResult, CpuHnsw, DataSource, EdgeHarvest, HierarchyConfig, HnswParams, MstEdge,
+    Result, event_log::EventLog, timings::StageClock,
+};
+
+pub(crate) use self::artefacts::ArtefactHook;
+pub use self::artefacts::{StageArtefact, StageArtefactHook};
+pub use self::defaults::{
+    DefaultHarvestStage, DefaultHierarchyStage, DefaultIndexStage, DefaultMstStage,
+};
+pub(crate) use self::dispatch::{
+    build_index, build_traced_index, ensure_stage_output, spanning_forest,
+};
+
+/// Inputs shared by every stage of a single run.
+///
+/// For sampled runs the source is the sample, so stage code never needs to
+/// know whether sampling is active.
+pub struct StageContext<'a> {
+    source: &'a (dyn DataSource + Sync),
+    hierarchy: HierarchyConfig,
+    hnsw_params: &'a HnswParams,
+}
+
+impl<'a> StageContext<'a> {
+    pub(crate) fn new(
+        source: &'a (dyn DataSource + Sync),
+        hierarchy: HierarchyConfig,
+        hnsw_params: &'a HnswParams,
+    ) -> Self {
+        Self {
+            source,
+            hierarchy,
+            hnsw_params,
+        }
+    }
+
+    /// Returns the data source being clustered.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn source(&self) -> &'a (dyn DataSource + Sync) { self.source }
+
+    /// Returns the number of points being clustered.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.source.len()
+    }
+
+    /// Returns whether the run has no points.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.source.is_empty()
+    }
+
+    /// Returns the configured minimum cluster size.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn min_cluster_size(&self) -> NonZeroUsize { self.hierarchy.min_cluster_size() }
+
+    /// Returns the configured hierarchy extraction settings.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn hierarchy_config(&self) -> HierarchyConfig { self.hierarchy }
+
+    /// Returns the configured HNSW parameters.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn hnsw_params(&self) -> &'a HnswParams { self.hnsw_params }
+}
+
+impl fmt::Debug for StageContext<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StageContext")
+            .field("source", &self.source.name())
+            .field("hierarchy", &self.hierarchy)
+            .field("hnsw_params", &self.hnsw_params)
+            .finish()
+    }
+}
+
+/// Candidate edges weighted for MST construction, with the core distance of
+/// every point.
+///
+/// Core distances are used when bridging disconnected components, so there
+/// must be exactly one per point.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WeightedHarvest {
+    edges: EdgeHarvest,
+    core_distances: Vec<f32>,
+}
+
+impl WeightedHarvest {
+    /// Pairs weighted edges with per-point core distances.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::{CandidateEdge, EdgeHarvest, WeightedHarvest};
+    ///
+    /// let edges = EdgeHarvest::new(vec![CandidateEdge::new(0, 1, 1.5, 0)]);
+    /// let weighted = WeightedHarvest::new(edges, vec![1.0, 1.5]);
+    /// assert_eq!(weighted.edges().len(), 1);
+    /// assert_eq!(weighted.core_distances(), &[1.0, 1.5]);
+    /// ```
+    #[must_use]
+    pub fn new(edges: EdgeHarvest, core_distances: Vec<f32>) -> Self {
+        Self {
+            edges,
+            core_distances,
+        }
+    }
+
+    /// Returns the weighted candidate edges.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn edges(&self) -> &EdgeHarvest { &self.edges }
+
+    /// Returns the core distance of every point.
+    #[must_use]
+    pub fn core_distances(&self) -> &[f32] {
+        &self.core_distances
+    }
+
+    /// Splits the harvest into its edges and core distances.
+    #[must_use]
+    pub fn into_parts(self) -> (EdgeHarvest, Vec<f32>) {
+        (self.edges, self.core_distances)
+    }
+}
+
+/// Builds the HNSW index and harvests candidate edges.
+///
+/// The index must hold every point of [`StageContext::source`], because core
+/// distances and sampled-run assignment search it. Prebuilt indices
+/// configured via [`crate::ChutoroBuilder::with_prebuilt_index`] take
+/// precedence over this stage.
+pub trait IndexStage: fmt::Debug + Send + Sync {
+    /// Returns the index and the candidate edges discovered while building it.
+    ///
+    /// # Errors
+    /// Returns a [`crate::ChutoroError`] when the index cannot be built.
+    fn build(&self, context: &StageContext<'_>) -> Result<(CpuHnsw, EdgeHarvest)>;
+}
+
+/// Converts harvested edges into the weighted edges passed to MST
+/// construction.
+///
+/// The pipeline has already dropped edges skipped by the
+/// [`crate::DistancePolicy`]; the configured [`crate::EdgeBudget`] is applied
+/// to the output.
+pub trait HarvestStage: fmt::Debug + Send + Sync {
+    /// Weights `harvest` and reports each point's core distance.
+    ///
+    /// # Errors
+    /// Returns a [`crate::ChutoroError`] when weighting fails.
+    fn weight(
+        &self,
+        context: &StageContext<'_>,
+        index: &CpuHnsw,
+        harvest: &EdgeHarvest,
+    ) -> Result<WeightedHarvest>;
+}
+
+/// Computes the spanning forest over the weighted edges.
+///
+/// The forest may be disconnected; component repair runs on its output when
+/// enabled.
+pub trait MstStage: fmt::Debug + Send + Sync {
+    /// Returns the forest edges chosen from `edges`.
+    ///
+    /// # Errors
+    /// Returns a [`crate::ChutoroError`] when the forest cannot be computed.
+    fn spanning_forest(
+        &self,
+        context: &StageContext<'_>,
+        edges: EdgeHarvest,
+    ) -> Result<Vec<MstEdge>>;
+}
+
+/// Extracts the flat clustering from the (possibly repaired) forest.
+///
+/// The returned result must label every point; the pipeline attaches its own
+/// reports (sparsification, connectivity, timings) afterwards.
+pub trait HierarchyStage: fmt::Debug + Send + Sync {
+    /// Labels every point from the forest `edges`.
+    ///
+    /// # Errors
+    /// Returns a [`crate::ChutoroError`] when extraction fails.
+    fn extract(&self, context: &StageContext<'_>, edges: &[MstEdge]) -> Result<ClusteringResult>;
+}
+
+/// Stage overrides and observers configured on the builder; `None` runs the
+/// built-in stage.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct PipelineStages {
+    pub(crate) index: Option<Arc<dyn IndexStage>>,
+    pub(crate) harvest: Option<Arc<dyn HarvestStage>>,
+    pub(crate) mst: Option<Arc<dyn MstStage>>,
+    pub(crate) hierarchy: Option<Arc<dyn HierarchyStage>>,
+    pub(crate) hook: Option<ArtefactHook>,
+    pub(crate) event_log: Option<Arc<EventLog>>,
+}
+
+impl PipelineStages {
+    /// Starts a stage clock that reports laps to the event log, if any.
+    pub(crate) fn clock(&self) -> StageClock {
+        StageClock::start().with_event_log(self.event_log.clone())
+    }
+
+    /// Records `artefact` in the event log and passes it to the configured
+    /// hook, if any.
+    pub(crate) fn notify(&self, artefact: StageArtefact<'_>) {
+        if let Some(log) = &self.event_log {
+            log.artefact(&artefact);
+        }
+        if let Some(ArtefactHook(hook)) = &self.hook {
+            hook.on_stage_complete(artefact);
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Wall-clock timings for the stages of a clustering run.
+//!
+//! The CPU pipeline records how long each stage takes so callers can see where
+//! time goes without attaching a profiler. Timings are attached to
+//! [`crate::ClusteringResult`] and logged at debug level as stages finish.
+
+use std::time::Duration;
+#[cfg(feature = "cpu")]
+use std::{sync::Arc, time::Instant};
+
+#[cfg(feature = "cpu")]
+use tracing::debug;
+
+#[cfg(feature = "cpu")]
+use crate::event_log::EventLog;
+
+/// Per-stage wall-clock durations for a single clustering run.
+///
+/// `edge_harvest` covers turning the HNSW harvest into mutual-reachability
+/// edges: core-distance searches, re-weighting, and any edge-budget
+/// sparsification. `mst` includes connectivity reporting and repair. `total`
+/// spans the whole pipeline, so it is at least the sum of the stages.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::StageTimings;
+///
+/// let timings = StageTimings::default();
+/// assert!(timings.total().is_zero());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StageTimings {
+    hnsw_build: Duration,
+    edge_harvest: Duration,
+    mst: Duration,
+    hierarchy: Duration,
+    total: Duration,
+}
+
+impl StageTimings {
+    /// Returns the time spent building (or adopting) the HNSW index.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn hnsw_build(&self) -> Duration { self.hnsw_build }
+
+    /// Returns the time spent deriving mutual-reachability edges.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn edge_harvest(&self) -> Duration { self.edge_harvest }
+
+    /// Returns the time spent constructing the minimum spanning forest.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn mst(&self) -> Duration { self.mst }
+
+    /// Returns the time spent extracting the cluster hierarchy and labels.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn hierarchy(&self) -> Duration { self.hierarchy }
+
+    /// Returns the wall-clock duration of the whole pipeline.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn total(&self) -> Duration { self.total }
+
+    /// Rebuilds timings from stored durations, as when decoding a persisted
+    /// result.
+    pub(crate) fn from_durations(durations: [Duration; 5]) -> Self {
+        let [hnsw_build, edge_harvest, mst, hierarchy, total] = durations;
+        Self {
+            hnsw_build,
+            edge_harvest,
+            mst,
+            hierarchy,
+            total,
+        }
+    }
+
+    /// Returns the stage durations in the order accepted by
+    /// [`Self::from_durations`].
+    pub(crate) fn durations(&self) -> [Duration; 5] {
+        [
+            self.hnsw_build,
+            self.edge_harvest,
+            self.mst,
+            self.hierarchy,
+            self.total,
+        ]
+    }
+
+    /// Adds time spent outside a [`StageClock`] to `stage` and the total.
+    #[cfg(feature = "cpu")]
+    pub(crate) fn with_stage_added(mut self, stage: Stage, elapsed: Duration) -> Self {
+        *self.slot(stage) += elapsed;
+        self.total += elapsed;
+        self
+    }
+
+    #[cfg(feature = "cpu")]
+    fn slot(&mut self, stage: Stage) -> &mut Duration {
+        match stage {
+            Stage::HnswBuild => &mut self.hnsw_build,
+            Stage::EdgeHarvest => &mut self.edge_harvest,
+            Stage::Mst => &mut self.mst,
+            Stage::Hierarchy => &mut self.hierarchy,
+        }
+    }
+}
+
+/// Pipeline stages tracked by [`StageTimings`].
+#[cfg(feature = "cpu")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Stage {
+    HnswBuild,
+    EdgeHarvest,
+    Mst,
+    Hierarchy,
+}
+
+#[cfg(feature = "cpu")]
+impl Stage {
+    #[rustfmt::skip]
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::HnswBuild => "hnsw_build",
+            Self::EdgeHarvest => "edge_harvest",
+            Self::Mst => "mst",
+            Self::Hierarchy => "hierarchy",
+        }
+    }
+}
+
+/// Measures consecutive pipeline stages.
+#[cfg(feature = "cpu")]
+#[derive(Debug)]
+pub(crate) struct StageClock {
+    started: Instant,
+    lap_started: Instant,
+    timings: StageTimings,
+    event_log: Option<Arc<EventLog>>,
+}
+
+#[cfg(feature = "cpu")]
+impl StageClock {
+    /// Starts timing the pipeline and its first stage.
+    pub(crate) fn start() -> Self {
+        let now = Instant::now();
+        Self {
+            started: now,
+            lap_started: now,
+            timings: StageTimings::default(),
+            event_log: None,
+        }
+    }
+
+    /// Also records each lap in `event_log`, when one is configured.
+    pub(crate) fn with_event_log(mut self, event_log: Option<Arc<EventLog>>) -> Self {
+        self.event_log = event_log;
+        self
+    }
+
+    /// Attributes the time since the previous lap to `stage`.
+    pub(crate) fn lap(&mut self, stage: Stage) {
+        let now = Instant::now();
+        let elapsed = now.saturating_duration_since(self.lap_started);
+        self.lap_started = now;
+        *self.timings.slot(stage) += elapsed;
+        debug!(stage = stage.as_str(), elapsed = ?elapsed, "pipeline stage completed");
+        if let Some(log) = &self.event_log {
+            log.stage_completed(stage, elapsed);
+        }
+    }
+
+    /// Stops the clock and returns the recorded timings.
+    pub(crate) fn finish(mut self) -> StageTimings {
+        self.timings.total = self.started.elapsed();
+        self.timings
+    }
+}
+
+#[cfg(all(test, feature = "cpu"))]
+mod tests {
+    //! Unit tests for stage timing bookkeeping.
+
+    use super::*;
+
+    #[test]
+    fn total_covers_every_lap() {
+        let mut clock = StageClock::start();
+        for stage in [
+            Stage::HnswBuild,
+            Stage::EdgeHarvest,
+            Stage::Mst,
+            Stage::Hierarchy,
+        ] {
+            std::thread::sleep(Duration::from_millis(1));
+            clock.lap(stage);
+        }
+        let timings = clock.finish();
+
+        let stages = [
+            timings.hnsw_build(),
+            timings.edge_harvest(),
+            timings.mst(),
+            timings.hierarchy(),
+        ];
+        assert!(stages.iter().all(|elapsed| !elapsed.is_zero()));
+        assert!(timings.total() >= stages.iter().sum());
+    }
+
+    #[test]
+    fn repeated_laps_accumulate() {
+        let mut clock = StageClock::start();
+        std::thread::sleep(Duration::from_millis(1));
+        clock.lap(Stage::Mst);
+        let first = clock.timings.mst();
+        std::thread::sleep(Duration::from_millis(1));
+        clock.lap(Stage::Mst);
+        assert!(clock.finish().mst() > first);
+    }
+}
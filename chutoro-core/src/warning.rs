@@ -0,0 +1,208 @@
+//! Non-fatal events recorded on a [`crate::ClusteringResult`].
+//!
+//! Some events degrade a run without failing it: non-finite distances
+//! replaced under a lenient [`crate::DistancePolicy`], a spanning forest left
+//! in several components or joined with bridge edges, a distance cache too
+//! small for the working set, and a declared metric that breaks the triangle
+//! inequality. The pipeline records each as a [`Warning`]
+//! with a stable [`WarningCode`], so callers can act on them rather than
+//! discovering them in the logs.
+
+use std::fmt;
+
+#[cfg(feature = "cpu")]
+use crate::{ConnectivityReport, CpuHnsw, DistancePolicyReport};
+
+/// Stable codes describing [`Warning`] variants.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum WarningCode {
+    /// Non-finite distances were clamped to `f32::MAX`.
+    NonFiniteDistancesClamped,
+    /// Non-finite distances were skipped and their edges dropped.
+    NonFiniteEdgesSkipped,
+    /// The spanning forest was left in several components.
+    DisconnectedComponents,
+    /// Bridge edges joined the components of the spanning forest.
+    ComponentsBridged,
+    /// The distance cache evicted at least its capacity in entries.
+    DistanceCachePressure,
+    /// A source declared a metric but broke the triangle inequality.
+    TriangleInequalityViolated,
+}
+
+impl WarningCode {
+    /// Return the stable machine-readable representation of this warning code.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::NonFiniteDistancesClamped => "CHUTORO_WARN_NON_FINITE_CLAMPED",
+            Self::NonFiniteEdgesSkipped => "CHUTORO_WARN_NON_FINITE_SKIPPED",
+            Self::DisconnectedComponents => "CHUTORO_WARN_DISCONNECTED_COMPONENTS",
+            Self::ComponentsBridged => "CHUTORO_WARN_COMPONENTS_BRIDGED",
+            Self::DistanceCachePressure => "CHUTORO_WARN_DISTANCE_CACHE_PRESSURE",
+            Self::TriangleInequalityViolated => "CHUTORO_WARN_TRIANGLE_INEQUALITY",
+        }
+    }
+}
+
+impl fmt::Display for WarningCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A non-fatal event raised during a run.
+///
+/// # Examples
+/// ```
+/// use chutoro_core::{Warning, WarningCode};
+///
+/// let warning = Warning::ComponentsBridged { components: 3, bridge_edges: 2 };
+/// assert_eq!(warning.code(), WarningCode::ComponentsBridged);
+/// assert_eq!(warning.code().as_str(), "CHUTORO_WARN_COMPONENTS_BRIDGED");
+/// assert_eq!(warning.to_string(), "joined 3 forest components with 2 bridge edges");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum Warning {
+    /// [`crate::DistancePolicy::ClampToMax`] replaced non-finite distances.
+    NonFiniteDistancesClamped {
+        /// Number of distance evaluations that were clamped.
+        count: usize,
+    },
+    /// [`crate::DistancePolicy::SkipEdge`] replaced non-finite distances and
+    /// dropped their candidate edges.
+    NonFiniteEdgesSkipped {
+        /// Number of distance evaluations that were skipped.
+        count: usize,
+    },
+    /// The spanning forest had several components and no bridges were
+    /// added, so clusters never span them.
+    DisconnectedComponents {
+        /// Number of components in the forest.
+        components: usize,
+    },
+    /// Bridge edges joined the components of the spanning forest.
+    ComponentsBridged {
+        /// Number of components before repair.
+        components: usize,
+        /// Number of bridge edges added.
+        bridge_edges: usize,
+    },
+    /// The HNSW distance cache evicted at least as many entries as it holds,
+    /// so distances were likely recomputed.
+    DistanceCachePressure {
+        /// Number of entries evicted for capacity or expiry.
+        evictions: u64,
+        /// Configured maximum number of cached distances.
+        capacity: usize,
+    },
+    /// Sampled triples broke the triangle inequality although the source's
+    /// [`crate::MetricClass`] promises it, so HNSW recall may suffer.
+    TriangleInequalityViolated {
+        /// Number of violating triples.
+        violations: usize,
+        /// Number of triples sampled.
+        sampled: usize,
+    },
+}
+
+impl Warning {
+    /// Retrieve the stable [`WarningCode`] for this warning.
+    #[must_use]
+    pub const fn code(&self) -> WarningCode {
+        match self {
+            Self::NonFiniteDistancesClamped { .. } => WarningCode::NonFiniteDistancesClamped,
+            Self::NonFiniteEdgesSkipped { .. } => WarningCode::NonFiniteEdgesSkipped,
+            Self::DisconnectedComponents { .. } => WarningCode::DisconnectedComponents,
+            Self::ComponentsBridged { .. } => WarningCode::ComponentsBridged,
+            Self::DistanceCachePressure { .. } => WarningCode::DistanceCachePressure,
+            Self::TriangleInequalityViolated { .. } => WarningCode::TriangleInequalityViolated,
+        }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonFiniteDistancesClamped { count } => {
+                write!(f, "clamped {count} non-finite distances to f32::MAX")
+            }
+            Self::NonFiniteEdgesSkipped { count } => {
+                write!(f, "skipped {count} non-finite distances and their edges")
+            }
+            Self::DisconnectedComponents { components } => write!(
+                f,
+                "the spanning forest has {components} components; clusters never span them"
+            ),
+            Self::ComponentsBridged {
+                components,
+                bridge_edges,
+            } => write!(
+                f,
+                "joined {components} forest components with {bridge_edges} bridge edges"
+            ),
+            Self::DistanceCachePressure {
+                evictions,
+                capacity,
+            } => write!(
+                f,
+                "the distance cache evicted {evictions} entries with capacity {capacity}"
+            ),
+            Self::TriangleInequalityViolated {
+                violations,
+                sampled,
+            } => write!(
+                f,
+                "{violations} of {sampled} sampled triples break the triangle inequality \
+                 of a declared metric"
+            ),
+        }
+    }
+}
+
+/// Derives the warnings implied by a run's distance-policy and connectivity
+/// reports.
+#[cfg(feature = "cpu")]
+pub(crate) fn report_warnings(
+    policy: Option<DistancePolicyReport>,
+    connectivity: Option<&ConnectivityReport>,
+) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+    if let Some(report) = policy {
+        if report.clamped() > 0 {
+            warnings.push(Warning::NonFiniteDistancesClamped {
+                count: report.clamped(),
+            });
+        }
+        if report.skipped() > 0 {
+            warnings.push(Warning::NonFiniteEdgesSkipped {
+                count: report.skipped(),
+            });
+        }
+    }
+    if let Some(report) = connectivity.filter(|report| !report.is_connected()) {
+        let components = report.component_count();
+        warnings.push(match report.bridge_edges_added() {
+            0 => Warning::DisconnectedComponents { components },
+            bridge_edges => Warning::ComponentsBridged {
+                components,
+                bridge_edges,
+            },
+        });
+    }
+    warnings
+}
+
+/// Warns when the index's distance cache evicted at least its capacity.
+#[cfg(feature = "cpu")]
+pub(crate) fn cache_pressure(index: &CpuHnsw) -> Option<Warning> {
+    let (evictions, capacity) = index.distance_cache_pressure();
+    // `usize` is at most 64 bits on every supported target.
+    (evictions >= capacity as u64).then_some(Warning::DistanceCachePressure {
+        evictions,
+        capacity,
+    })
+}
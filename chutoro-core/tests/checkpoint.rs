@@ -0,0 +1,321 @@
+//! Tests for stage checkpoints and resuming runs from them.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use std::{
+    fs,
+    path::Path,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+};
+
+use chutoro_core::{
+    ChutoroBuilder, ChutoroError, ClusteringResult, CpuHnsw, DataSource, DataSourceError,
+    DefaultHierarchyStage, DefaultIndexStage, EdgeHarvest, GraphBuilder, HierarchyStage,
+    IndexStage, MstEdge, Result, SampleSpec, StageContext,
+};
+use common::Dummy;
+use rstest::{fixture, rstest};
+use tempfile::TempDir;
+
+const CHECKPOINT_FILES: [&str; 3] = ["index.bin", "harvest.bin", "mst.bin"];
+/// The most distances a run evaluates to fingerprint its checkpoints.
+const FINGERPRINT_SAMPLES: usize = 16;
+
+/// Three well-separated groups of 20 points each.
+#[fixture]
+fn source() -> Dummy {
+    Dummy::new(
+        (0..60)
+            .map(|point| (point % 3) as f32 * 100.0 + (point / 3) as f32 * 0.5)
+            .collect(),
+    )
+}
+
+#[fixture]
+fn checkpoint_dir() -> TempDir {
+    tempfile::tempdir().expect("tempdir")
+}
+
+/// Counts index builds before delegating to the built-in stage.
+#[derive(Debug, Default)]
+struct CountingIndex(Arc<AtomicUsize>);
+
+impl IndexStage for CountingIndex {
+    fn build(&self, context: &StageContext<'_>) -> Result<(CpuHnsw, EdgeHarvest)> {
+        self.0.fetch_add(1, Ordering::Relaxed);
+        DefaultIndexStage.build(context)
+    }
+}
+
+/// Fails its first extraction, as a crash late in a run would.
+#[derive(Debug, Default)]
+struct FailsOnce(AtomicBool);
+
+impl HierarchyStage for FailsOnce {
+    fn extract(&self, context: &StageContext<'_>, edges: &[MstEdge]) -> Result<ClusteringResult> {
+        if !self.0.swap(true, Ordering::Relaxed) {
+            return Err(ChutoroError::CpuHierarchyFailure {
+                code: Arc::from("SIMULATED"),
+                message: Arc::from("simulated failure"),
+            });
+        }
+        DefaultHierarchyStage.extract(context, edges)
+    }
+}
+
+/// Counts distance evaluations made against the wrapped source.
+struct Counted {
+    inner: Dummy,
+    calls: AtomicUsize,
+}
+
+impl DataSource for Counted {
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn distance(&self, i: usize, j: usize) -> std::result::Result<f32, DataSourceError> {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        self.inner.distance(i, j)
+    }
+}
+
+fn builder(directory: &Path) -> ChutoroBuilder {
+    ChutoroBuilder::new()
+        .with_min_cluster_size(5)
+        .with_checkpoint_directory(directory)
+}
+
+fn present(directory: &Path) -> Vec<&'static str> {
+    CHECKPOINT_FILES
+        .into_iter()
+        .filter(|name| directory.join(name).exists())
+        .collect()
+}
+
+#[rstest]
+fn runs_write_every_stage_checkpoint(source: Dummy, checkpoint_dir: TempDir) {
+    let result = builder(checkpoint_dir.path())
+        .build()
+        .expect("configuration must be valid")
+        .run(&source)
+        .expect("run must succeed");
+
+    assert_eq!(result.cluster_count(), 3);
+    assert_eq!(present(checkpoint_dir.path()), CHECKPOINT_FILES);
+    let entries = fs::read_dir(checkpoint_dir.path())
+        .expect("read dir")
+        .count();
+    assert_eq!(entries, CHECKPOINT_FILES.len(), "no partial files remain");
+}
+
+#[rstest]
+#[case::from_forest(None)]
+#[case::from_index(Some("mst.bin"))]
+fn resumed_runs_reuse_checkpoints(
+    source: Dummy,
+    checkpoint_dir: TempDir,
+    #[case] removed: Option<&str>,
+) {
+    let builds = Arc::new(AtomicUsize::new(0));
+    let chutoro = builder(checkpoint_dir.path())
+        .with_index_stage(CountingIndex(Arc::clone(&builds)))
+        .build()
+        .expect("configuration must be valid");
+    let original = chutoro.run(&source).expect("run must succeed");
+    if let Some(name) = removed {
+        fs::remove_file(checkpoint_dir.path().join(name)).expect("remove checkpoint");
+    }
+
+    let resumed = chutoro
+        .resume(&source, checkpoint_dir.path())
+        .expect("resume must succeed");
+
+    assert_eq!(
+        builds.load(Ordering::Relaxed),
+        1,
+        "the index is not rebuilt"
+    );
+    assert_eq!(resumed.assignments(), original.assignments());
+    assert_eq!(resumed.connectivity(), original.connectivity());
+    assert_eq!(present(checkpoint_dir.path()), CHECKPOINT_FILES);
+}
+
+#[rstest]
+fn nn_descent_runs_resume_from_their_graph(source: Dummy, checkpoint_dir: TempDir) {
+    let chutoro = builder(checkpoint_dir.path())
+        .with_graph_builder(GraphBuilder::NnDescent {
+            k: 8,
+            iterations: 10,
+            sample_rate: 1.0,
+        })
+        .build()
+        .expect("configuration must be valid");
+    let original = chutoro.run(&source).expect("run must succeed");
+    fs::remove_file(checkpoint_dir.path().join("mst.bin")).expect("remove checkpoint");
+
+    let resumed = chutoro
+        .resume(&source, checkpoint_dir.path())
+        .expect("resume must succeed");
+
+    assert_eq!(present(checkpoint_dir.path()), ["harvest.bin", "mst.bin"]);
+    assert_eq!(resumed.assignments(), original.assignments());
+}
+
+#[rstest]
+fn failed_runs_resume_without_recomputing_distances(source: Dummy, checkpoint_dir: TempDir) {
+    let chutoro = builder(checkpoint_dir.path())
+        .with_hierarchy_stage(FailsOnce::default())
+        .build()
+        .expect("configuration must be valid");
+    let source = Counted {
+        inner: source,
+        calls: AtomicUsize::new(0),
+    };
+    chutoro
+        .run(&source)
+        .expect_err("the hierarchy stage fails first time");
+    source.calls.store(0, Ordering::Relaxed);
+
+    let resumed = chutoro
+        .resume(&source, checkpoint_dir.path())
+        .expect("resume must succeed");
+
+    assert_eq!(resumed.cluster_count(), 3);
+    assert!(
+        source.calls.load(Ordering::Relaxed) <= FINGERPRINT_SAMPLES,
+        "only the checkpoint fingerprint evaluates distances"
+    );
+}
+
+#[rstest]
+fn resuming_without_checkpoints_runs_from_scratch(source: Dummy, checkpoint_dir: TempDir) {
+    let chutoro = ChutoroBuilder::new()
+        .with_min_cluster_size(5)
+        .build()
+        .expect("configuration must be valid");
+
+    let resumed = chutoro
+        .resume(&source, checkpoint_dir.path())
+        .expect("resume must succeed");
+
+    assert_eq!(resumed.cluster_count(), 3);
+    assert_eq!(present(checkpoint_dir.path()), CHECKPOINT_FILES);
+}
+
+#[rstest]
+fn fresh_runs_replace_stale_checkpoints(source: Dummy, checkpoint_dir: TempDir) {
+    for name in CHECKPOINT_FILES {
+        fs::write(checkpoint_dir.path().join(name), b"stale").expect("write stale file");
+    }
+
+    let result = builder(checkpoint_dir.path())
+        .build()
+        .expect("configuration must be valid")
+        .run(&source)
+        .expect("run must ignore stale checkpoints");
+
+    assert_eq!(result.cluster_count(), 3);
+}
+
+#[rstest]
+fn checkpoints_from_another_source_are_rejected(source: Dummy, checkpoint_dir: TempDir) {
+    let chutoro = builder(checkpoint_dir.path())
+        .build()
+        .expect("configuration must be valid");
+    chutoro.run(&source).expect("run must succeed");
+    let shorter = Dummy::new((0..30).map(|point| point as f32).collect());
+
+    let err = chutoro
+        .resume(&shorter, checkpoint_dir.path())
+        .expect_err("checkpoints cover 60 points");
+
+    assert_eq!(err.code().as_str(), "CHUTORO_CHECKPOINT_FAILURE");
+    assert!(err.to_string().contains("covers 60 points"), "{err}");
+}
+
+#[rstest]
+#[case::from_forest(None)]
+#[case::from_index(Some("mst.bin"))]
+fn checkpoints_from_a_same_length_source_are_rejected(
+    source: Dummy,
+    checkpoint_dir: TempDir,
+    #[case] removed: Option<&str>,
+) {
+    let chutoro = builder(checkpoint_dir.path())
+        .build()
+        .expect("configuration must be valid");
+    chutoro.run(&source).expect("run must succeed");
+    if let Some(name) = removed {
+        fs::remove_file(checkpoint_dir.path().join(name)).expect("remove checkpoint");
+    }
+    let other = Dummy::new((0..60).map(|point| point as f32).collect());
+
+    let err = chutoro
+        .resume(&other, checkpoint_dir.path())
+        .expect_err("checkpoints belong to another source");
+
+    assert!(matches!(err, ChutoroError::Checkpoint { .. }), "{err:?}");
+    assert!(err.to_string().contains("different source"), "{err}");
+}
+
+#[rstest]
+fn checkpoints_from_other_stage_options_are_rejected(source: Dummy, checkpoint_dir: TempDir) {
+    builder(checkpoint_dir.path())
+        .build()
+        .expect("configuration must be valid")
+        .run(&source)
+        .expect("run must succeed");
+
+    let err = builder(checkpoint_dir.path())
+        .with_connect_components(true)
+        .build()
+        .expect("configuration must be valid")
+        .resume(&source, checkpoint_dir.path())
+        .expect_err("checkpoints were written without bridging");
+
+    assert!(matches!(err, ChutoroError::Checkpoint { .. }), "{err:?}");
+}
+
+#[rstest]
+#[case::sampled(|builder: ChutoroBuilder| builder.with_sample(SampleSpec::Fraction(0.5), 7))]
+#[case::provenance(|builder: ChutoroBuilder| builder.with_edge_provenance(true))]
+fn rejects_stages_checkpoints_do_not_cover(
+    checkpoint_dir: TempDir,
+    #[case] configure: fn(ChutoroBuilder) -> ChutoroBuilder,
+) {
+    let err = configure(builder(checkpoint_dir.path()))
+        .build()
+        .expect_err("configuration must be rejected");
+
+    assert!(matches!(err, ChutoroError::Checkpoint { .. }), "{err:?}");
+}
+
+#[rstest]
+fn rejects_missing_checkpoint_directory(source: Dummy, checkpoint_dir: TempDir) {
+    let missing = checkpoint_dir.path().join("absent");
+
+    let err = builder(&missing)
+        .build()
+        .expect_err("a missing directory must be rejected");
+    let resumed = ChutoroBuilder::new()
+        .build()
+        .expect("configuration must be valid")
+        .resume(&source, &missing)
+        .expect_err("a missing directory must be rejected");
+
+    for err in [err, resumed] {
+        assert!(
+            matches!(&err, ChutoroError::Checkpoint { path, .. } if **path == *missing),
+            "{err:?}"
+        );
+    }
+}
@@ -67,6 +67,112 @@ fn run_records_core_tracing(dummy: Dummy) {
     assert!(spans.iter().any(|span| span.name == "core.run_cpu"));
 }
 
+#[cfg(feature = "cpu")]
+#[rstest]
+fn run_reports_edge_budget_sparsification() {
+    let values: Vec<f32> = (0..32)
+        .map(|i| (i % 8) as f32 + (i / 8) as f32 * 100.0)
+        .collect();
+    let source = Dummy::new(values);
+    let budget =
+        chutoro_core::EdgeBudget::new(std::num::NonZeroUsize::new(8).expect("literal is non-zero"));
+    let chutoro = ChutoroBuilder::new()
+        .with_min_cluster_size(2)
+        .with_edge_budget(budget)
+        .build()
+        .expect("configuration must be valid");
+    assert_eq!(chutoro.edge_budget(), Some(budget));
+
+    let result = chutoro.run(&source).expect("run must succeed");
+    assert_eq!(result.assignments().len(), source.len());
+    let report = result
+        .sparsification()
+        .expect("budgeted runs must report sparsification");
+    assert!(report.is_lossy());
+    assert_eq!(
+        report.retained_edges() + report.dropped_edges(),
+        report.input_edges()
+    );
+}
+
+#[cfg(feature = "cpu")]
+#[rstest]
+#[case::report_only(false)]
+#[case::repair(true)]
+fn run_reports_connectivity(#[case] repair: bool) {
+    let values: Vec<f32> = (0..24)
+        .map(|i| (i % 6) as f32 + (i / 6) as f32 * 50.0)
+        .collect();
+    let source = Dummy::new(values);
+    let chutoro = ChutoroBuilder::new()
+        .with_min_cluster_size(2)
+        .with_connect_components(repair)
+        .build()
+        .expect("configuration must be valid");
+    assert_eq!(chutoro.connect_components(), repair);
+
+    let result = chutoro.run(&source).expect("run must succeed");
+    let report = result
+        .connectivity()
+        .expect("CPU runs must report connectivity");
+    assert_eq!(report.component_sizes().iter().sum::<usize>(), source.len());
+    let expected_bridges = if repair {
+        report.component_count() - 1
+    } else {
+        0
+    };
+    assert_eq!(report.bridge_edges_added(), expected_bridges);
+}
+
+#[cfg(feature = "cpu")]
+#[rstest]
+fn run_reports_stage_timings(dummy: Dummy) {
+    let chutoro = ChutoroBuilder::new()
+        .with_min_cluster_size(2)
+        .build()
+        .expect("configuration must be valid");
+    let result = chutoro.run(&dummy).expect("run must succeed");
+    let timings = result.timings().expect("CPU runs must record timings");
+    let stages =
+        timings.hnsw_build() + timings.edge_harvest() + timings.mst() + timings.hierarchy();
+    assert!(timings.total() >= stages);
+}
+
+#[cfg(feature = "cpu")]
+#[rstest]
+#[case::auto(ExecutionStrategy::Auto)]
+#[case::cpu_only(ExecutionStrategy::CpuOnly)]
+fn run_records_the_cpu_backend(dummy: Dummy, #[case] strategy: ExecutionStrategy) {
+    let chutoro = ChutoroBuilder::new()
+        .with_min_cluster_size(2)
+        .with_execution_strategy(strategy)
+        .build()
+        .expect("configuration must be valid");
+    let result = chutoro.run(&dummy).expect("run must succeed");
+    let backend = result.backend().expect("runs must record their backend");
+    assert!(backend.is_cpu(), "unexpected backend {}", backend.as_str());
+}
+
+#[cfg(feature = "cpu")]
+#[rstest]
+fn run_reports_noise_fraction() {
+    let source = Dummy::new(vec![0.0, 0.1, 0.2, 10.0, 10.1, 10.2, 100.0]);
+    let chutoro = ChutoroBuilder::new()
+        .with_min_cluster_size(2)
+        .build()
+        .expect("configuration must be valid");
+    let result = chutoro.run(&source).expect("run must succeed");
+    let noise = result.noise_label().expect("the outlier must be noise");
+    assert_eq!(result.assignments()[6], noise);
+    assert_eq!(result.noise_count(), 1);
+    assert!((result.noise_fraction() - 1.0 / 7.0).abs() < f64::EPSILON);
+
+    let membership = result.membership().expect("pipeline runs score points");
+    assert_eq!(membership.probabilities().len(), 7);
+    assert_eq!(membership.probabilities()[6], 0.0);
+    assert_eq!(membership.outlier_scores().len(), 7);
+}
+
 #[rstest]
 fn run_logs_empty_source_warning() {
     let chutoro = ChutoroBuilder::new()
@@ -0,0 +1,69 @@
+//! Tests for the one-call `cluster_dense` entry point.
+#![cfg(feature = "cpu")]
+
+use chutoro_core::{ChutoroError, ClusterOptions, DataSourceError, HnswParams, cluster_dense};
+use rstest::{fixture, rstest};
+
+/// Two tight groups of four points in the plane.
+#[fixture]
+fn blobs() -> Vec<Vec<f32>> {
+    let mut rows = Vec::new();
+    for centre in [0.0_f32, 50.0] {
+        for offset in [0.0, 0.5, 1.0, 1.5] {
+            rows.push(vec![centre + offset, centre - offset]);
+        }
+    }
+    rows
+}
+
+#[rstest]
+fn separates_well_spaced_groups(blobs: Vec<Vec<f32>>) {
+    let result = cluster_dense(&blobs, ClusterOptions::default().with_min_cluster_size(2))
+        .expect("clustering must succeed");
+
+    let labels = result.assignments();
+    assert_eq!(result.cluster_count(), 2);
+    assert!(labels[..4].iter().all(|label| *label == labels[0]));
+    assert!(labels[4..].iter().all(|label| *label == labels[4]));
+    assert_ne!(labels[0], labels[4]);
+}
+
+#[rstest]
+fn options_reach_the_pipeline(blobs: Vec<Vec<f32>>) {
+    let params = HnswParams::new(4, 12).expect("params must be valid");
+    let options = ClusterOptions::default()
+        .with_min_cluster_size(3)
+        .with_seed(42)
+        .with_hnsw_params(params);
+
+    let result = cluster_dense(&blobs, options).expect("clustering must succeed");
+
+    let parameters = result.parameters().expect("parameters are reported");
+    assert_eq!(parameters.min_cluster_size().get(), 3);
+    assert_eq!(parameters.max_connections(), 4);
+    assert_eq!(parameters.ef_construction(), 12);
+    assert_eq!(result.seeds().and_then(|seeds| seeds.master()), Some(42));
+}
+
+#[rstest]
+#[case::zero_dimension(vec![vec![], vec![]], DataSourceError::ZeroDimension)]
+#[case::ragged(
+    vec![vec![0.0, 1.0], vec![2.0]],
+    DataSourceError::DimensionMismatch { left: 2, right: 1 },
+)]
+fn rejects_malformed_rows(#[case] data: Vec<Vec<f32>>, #[case] expected: DataSourceError) {
+    let err = cluster_dense(&data, ClusterOptions::default().with_min_cluster_size(1))
+        .expect_err("malformed rows must be rejected");
+
+    assert!(
+        matches!(&err, ChutoroError::DataSource { error, .. } if *error == expected),
+        "{err:?}"
+    );
+}
+
+#[rstest]
+fn empty_input_reports_an_empty_source() {
+    let err = cluster_dense(&[], ClusterOptions::default()).expect_err("empty input must fail");
+
+    assert!(matches!(err, ChutoroError::EmptySource { .. }));
+}
@@ -2,9 +2,12 @@
 //!
 //! This module exports the `Dummy` `DataSource` fixture used by integration
 //! tests to supply small in-memory scalar datasets with deterministic absolute
-//! distance semantics. It keeps common test scaffolding beside the integration
+//! distance semantics, and the `Truncated` fixture whose last point cannot be
+//! scored. It keeps common test scaffolding beside the integration
 //! test crates that exercise the public `chutoro-core` API.
 
+pub mod truncated;
+
 use chutoro_core::{DataSource, DataSourceError};
 
 #[derive(Clone)]
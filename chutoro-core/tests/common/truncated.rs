@@ -0,0 +1,30 @@
+//! A `DataSource` fixture whose last point cannot be scored.
+//!
+//! Only some test crates use it, so dead-code warnings are silenced for the
+//! crates that include `common` without it.
+#![allow(
+    dead_code,
+    reason = "each integration test compiles `common` separately"
+)]
+
+use chutoro_core::{DataSource, DataSourceError};
+
+/// A source that cannot evaluate distances involving its last point.
+pub struct Truncated;
+
+impl DataSource for Truncated {
+    fn len(&self) -> usize {
+        4
+    }
+
+    fn name(&self) -> &str {
+        "truncated"
+    }
+
+    fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+        match i.max(j) {
+            3 => Err(DataSourceError::OutOfBounds { index: 3 }),
+            _ => Ok(i.abs_diff(j) as f32),
+        }
+    }
+}
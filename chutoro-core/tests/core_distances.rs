@@ -0,0 +1,105 @@
+//! Tests for core distances supplied to the builder.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use chutoro_core::{
+    CandidateEdge, ChutoroBuilder, ChutoroError, EdgeHarvest, SampleSpec, StageArtefact,
+};
+use common::Dummy;
+use rstest::rstest;
+
+const POINTS: [f32; 8] = [0.0, 1.0, 2.0, 3.0, 20.0, 21.0, 22.0, 23.0];
+
+/// Returns the lightest mutual-reachability weight passed to the MST.
+fn lightest_weight(builder: ChutoroBuilder) -> f32 {
+    let lightest = Arc::new(Mutex::new(f32::INFINITY));
+    let sink = Arc::clone(&lightest);
+    builder
+        .with_min_cluster_size(2)
+        .on_stage_complete(move |artefact: StageArtefact<'_>| {
+            if let StageArtefact::Harvest(harvest) = artefact {
+                let min = harvest
+                    .iter()
+                    .map(CandidateEdge::distance)
+                    .fold(f32::INFINITY, f32::min);
+                *sink.lock().expect("lock is not poisoned") = min;
+            }
+        })
+        .build()
+        .expect("configuration must be valid")
+        .run(&Dummy::new(POINTS.to_vec()))
+        .expect("run must succeed");
+    *lightest.lock().expect("lock is not poisoned")
+}
+
+#[rstest]
+fn supplied_core_distances_replace_the_derived_ones() {
+    let derived = lightest_weight(ChutoroBuilder::new());
+    let supplied = lightest_weight(ChutoroBuilder::new().with_core_distances(vec![5.0; 8]));
+
+    assert_eq!(derived, 1.0);
+    assert_eq!(supplied, 5.0);
+}
+
+#[rstest]
+fn supplied_core_distances_cluster_the_groups() {
+    let result = ChutoroBuilder::new()
+        .with_min_cluster_size(2)
+        .with_core_distances(vec![2.0; 8])
+        .build()
+        .expect("configuration must be valid")
+        .run(&Dummy::new(POINTS.to_vec()))
+        .expect("run must succeed");
+
+    let labels = result.assignments();
+    assert_eq!(result.cluster_count(), 2);
+    assert_eq!(labels[0], labels[3]);
+    assert_ne!(labels[0], labels[4]);
+}
+
+#[rstest]
+#[case::nan(f32::NAN)]
+#[case::infinite(f32::INFINITY)]
+#[case::negative(-1.0)]
+fn build_rejects_unusable_core_distances(#[case] value: f32) {
+    let err = ChutoroBuilder::new()
+        .with_core_distances(vec![1.0, value])
+        .build()
+        .expect_err("the value is unusable");
+
+    assert!(matches!(err, ChutoroError::InvalidCoreDistances { .. }));
+    assert_eq!(err.code().as_str(), "CHUTORO_INVALID_CORE_DISTANCES");
+}
+
+#[rstest]
+fn build_rejects_core_distances_with_sampling() {
+    let err = ChutoroBuilder::new()
+        .with_core_distances(vec![1.0; 8])
+        .with_sample(SampleSpec::Fraction(0.5), 7)
+        .build()
+        .expect_err("sampling cannot use supplied core distances");
+
+    assert!(matches!(err, ChutoroError::InvalidCoreDistances { .. }));
+}
+
+#[rstest]
+fn runs_reject_core_distances_of_the_wrong_length() {
+    let chutoro = ChutoroBuilder::new()
+        .with_min_cluster_size(2)
+        .with_core_distances(vec![1.0; 7])
+        .build()
+        .expect("configuration must be valid");
+
+    let run = chutoro
+        .run(&Dummy::new(POINTS.to_vec()))
+        .expect_err("seven distances cannot cover eight points");
+    let graph = chutoro
+        .cluster_from_knn_graph(8, EdgeHarvest::new(vec![CandidateEdge::new(0, 1, 1.0, 0)]))
+        .expect_err("seven distances cannot cover eight nodes");
+
+    assert!(matches!(run, ChutoroError::InvalidCoreDistances { .. }));
+    assert!(matches!(graph, ChutoroError::InvalidCoreDistances { .. }));
+}
@@ -0,0 +1,133 @@
+//! Tests for collapsing exact duplicates into weighted representatives.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use chutoro_core::{
+    ChutoroBuilder, ChutoroError, ClusteringResult, DataSource, DataSourceError, SampleSpec,
+};
+use common::Dummy;
+use rstest::{fixture, rstest};
+
+/// Scalar points that expose their bit pattern as a deduplication key and
+/// count distance evaluations.
+struct Keyed {
+    data: Vec<f32>,
+    calls: AtomicU64,
+}
+
+impl DataSource for Keyed {
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn name(&self) -> &str {
+        "keyed"
+    }
+
+    fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        let a = self
+            .data
+            .get(i)
+            .ok_or(DataSourceError::OutOfBounds { index: i })?;
+        let b = self
+            .data
+            .get(j)
+            .ok_or(DataSourceError::OutOfBounds { index: j })?;
+        Ok((a - b).abs())
+    }
+
+    fn dedupe_key(&self, index: usize) -> Option<u64> {
+        self.data.get(index).map(|value| u64::from(value.to_bits()))
+    }
+}
+
+/// Two groups of ten distinct values, each value repeated four times.
+#[fixture]
+fn source() -> Keyed {
+    let values = (0..10)
+        .map(|i| i as f32 * 0.1)
+        .chain((0..10).map(|i| 100.0 + i as f32 * 0.1));
+    Keyed {
+        data: values.flat_map(|value| [value; 4]).collect(),
+        calls: AtomicU64::new(0),
+    }
+}
+
+fn run(source: &Keyed, dedupe: bool) -> ClusteringResult {
+    source.calls.store(0, Ordering::Relaxed);
+    ChutoroBuilder::new()
+        .with_min_cluster_size(5)
+        .dedupe_exact(dedupe)
+        .build()
+        .expect("configuration must be valid")
+        .run(source)
+        .expect("run must succeed")
+}
+
+/// Asserts that two labellings partition the points identically.
+fn assert_same_partition(left: &ClusteringResult, right: &ClusteringResult) {
+    let mut mapping = HashMap::new();
+    for (a, b) in left.assignments().iter().zip(right.assignments()) {
+        assert_eq!(*mapping.entry(a.get()).or_insert(b.get()), b.get());
+    }
+    assert_eq!(left.cluster_count(), right.cluster_count());
+}
+
+#[rstest]
+fn duplicates_share_their_representatives_label(source: Keyed) {
+    let result = run(&source, true);
+
+    assert_eq!(result.assignments().len(), 80);
+    for group in result.assignments().chunks(4) {
+        assert!(group.iter().all(|&label| label == group[0]));
+    }
+    assert_eq!(result.cluster_count(), 2);
+    let (near, far) = result.assignments().split_at(40);
+    assert_ne!(near[0], far[0]);
+}
+
+#[rstest]
+fn deduplicated_runs_match_full_runs(source: Keyed) {
+    let full = run(&source, false);
+    let full_calls = source.calls.load(Ordering::Relaxed);
+    let deduplicated = run(&source, true);
+    let deduplicated_calls = source.calls.load(Ordering::Relaxed);
+
+    assert_same_partition(&full, &deduplicated);
+    assert!(deduplicated_calls < full_calls);
+    assert_eq!(
+        deduplicated.distance_evaluations(),
+        Some(deduplicated_calls)
+    );
+}
+
+#[rstest]
+fn sources_without_keys_are_left_alone() {
+    let source = Dummy::new([1.0_f32, 1.0, 1.0, 2.0, 2.0, 2.0, 9.0, 9.0, 9.0].into());
+    let result = ChutoroBuilder::new()
+        .with_min_cluster_size(3)
+        .dedupe_exact(true)
+        .build()
+        .expect("configuration must be valid")
+        .run(&source)
+        .expect("run must succeed");
+    assert_eq!(result.assignments().len(), 9);
+}
+
+#[rstest]
+fn build_rejects_deduplication_with_sampling() {
+    let err = ChutoroBuilder::new()
+        .dedupe_exact(true)
+        .with_sample(SampleSpec::Fraction(0.5), 3)
+        .build()
+        .expect_err("deduplication and sampling must be rejected together");
+    assert!(matches!(err, ChutoroError::InvalidDeduplication { .. }));
+    assert_eq!(err.code().as_str(), "CHUTORO_INVALID_DEDUPLICATION");
+}
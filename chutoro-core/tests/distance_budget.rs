@@ -0,0 +1,113 @@
+//! Tests for counting distance evaluations and enforcing a budget on them.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use chutoro_core::{
+    ChutoroBuilder, ChutoroError, ChutoroErrorCode, ClusteringResult, DataSource, DataSourceError,
+    SampleSpec,
+};
+use common::Dummy;
+use rstest::{fixture, rstest};
+
+/// Wraps [`Dummy`] and counts every distance it is asked for.
+struct Counted {
+    inner: Dummy,
+    calls: AtomicU64,
+}
+
+impl DataSource for Counted {
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn name(&self) -> &str {
+        "counted"
+    }
+
+    fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        self.inner.distance(i, j)
+    }
+}
+
+/// Two well-separated groups of 30 points each.
+#[fixture]
+fn groups() -> Counted {
+    let near = (0..30).map(|i| i as f32 * 0.1);
+    let far = (0..30).map(|i| 100.0 + i as f32 * 0.1);
+    Counted {
+        inner: Dummy::new(near.chain(far).collect()),
+        calls: AtomicU64::new(0),
+    }
+}
+
+fn run(builder: ChutoroBuilder, source: &Counted) -> Result<ClusteringResult, ChutoroError> {
+    builder
+        .with_min_cluster_size(3)
+        .build()
+        .expect("configuration must be valid")
+        .run(source)
+}
+
+#[rstest]
+#[case::full(ChutoroBuilder::new())]
+#[case::sampled(ChutoroBuilder::new().with_sample(SampleSpec::Fraction(0.5), 5))]
+fn runs_report_every_evaluation(groups: Counted, #[case] builder: ChutoroBuilder) {
+    let result = run(builder, &groups).expect("run must succeed");
+
+    let evaluations = result
+        .distance_evaluations()
+        .expect("CPU runs count distance evaluations");
+    assert!(evaluations > 0);
+    assert_eq!(evaluations, groups.calls.load(Ordering::Relaxed));
+}
+
+#[rstest]
+fn generous_budgets_do_not_change_the_run(groups: Counted) {
+    let result = run(
+        ChutoroBuilder::new()
+            .with_seed(3)
+            .with_max_distance_evaluations(u64::MAX),
+        &groups,
+    )
+    .expect("run must succeed");
+
+    assert_eq!(result.cluster_count(), 2);
+}
+
+#[rstest]
+fn exhausted_budgets_stop_the_run(groups: Counted) {
+    let err = run(
+        ChutoroBuilder::new().with_max_distance_evaluations(100),
+        &groups,
+    )
+    .expect_err("the run needs more than 100 evaluations");
+
+    assert_eq!(err.code(), ChutoroErrorCode::DistanceBudgetExceeded);
+    match err {
+        ChutoroError::DistanceBudgetExceeded {
+            data_source,
+            budget,
+            evaluations,
+        } => {
+            assert_eq!(data_source.as_ref(), "counted");
+            assert_eq!(budget, 100);
+            assert!(evaluations <= budget);
+            assert_eq!(evaluations, groups.calls.load(Ordering::Relaxed));
+        }
+        other => panic!("expected an exhausted budget, got {other:?}"),
+    }
+}
+
+#[rstest]
+fn budgets_are_exposed_on_the_runtime() {
+    let chutoro = ChutoroBuilder::new()
+        .with_max_distance_evaluations(42)
+        .build()
+        .expect("configuration must be valid");
+
+    assert_eq!(chutoro.max_distance_evaluations(), Some(42));
+}
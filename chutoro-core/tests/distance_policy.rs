@@ -0,0 +1,105 @@
+//! Tests for clamping or skipping non-finite distances during a run.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use chutoro_core::{ChutoroBuilder, ChutoroError, ClusteringResult, DistancePolicy, GraphBuilder};
+use common::Dummy;
+use rstest::{fixture, rstest};
+
+/// Two well-separated groups of 20 points each, followed by a NaN point whose
+/// every distance is NaN.
+#[fixture]
+fn dirty() -> Dummy {
+    let near = (0..20).map(|i| i as f32 * 0.1);
+    let far = (0..20).map(|i| 100.0 + i as f32 * 0.1);
+    Dummy::new(near.chain(far).chain([f32::NAN]).collect())
+}
+
+fn run(source: &Dummy, policy: DistancePolicy) -> Result<ClusteringResult, ChutoroError> {
+    ChutoroBuilder::new()
+        .with_min_cluster_size(3)
+        .with_distance_policy(policy)
+        .build()
+        .expect("configuration must be valid")
+        .run(source)
+}
+
+#[rstest]
+fn strict_policy_rejects_non_finite_distances(dirty: Dummy) {
+    let err = run(&dirty, DistancePolicy::Strict).expect_err("NaN distances must fail");
+
+    match err {
+        ChutoroError::CpuHnswFailure { code, .. } => {
+            assert_eq!(code.as_ref(), "NON_FINITE_DISTANCE");
+        }
+        other => panic!("expected a CPU HNSW failure, got {other:?}"),
+    }
+}
+
+#[rstest]
+#[case::clamp(DistancePolicy::ClampToMax)]
+#[case::skip(DistancePolicy::SkipEdge)]
+fn lenient_policies_cluster_dirty_data(dirty: Dummy, #[case] policy: DistancePolicy) {
+    let result = run(&dirty, policy).expect("lenient policies must not abort");
+
+    assert_eq!(result.assignments().len(), 41);
+    let (near, rest) = result.assignments().split_at(20);
+    let (far, nan) = rest.split_at(20);
+    assert!(near.iter().all(|&label| label == near[0]));
+    assert!(far.iter().all(|&label| label == far[0]));
+    assert_ne!(near[0], far[0]);
+    assert_eq!(Some(nan[0]), result.noise_label());
+
+    let report = result
+        .distance_policy()
+        .expect("lenient policies report replacements");
+    assert_eq!(report.policy(), policy);
+    assert!(report.total() > 0);
+    match policy {
+        DistancePolicy::ClampToMax => assert_eq!(report.skipped(), 0),
+        _ => assert_eq!(report.clamped(), 0),
+    }
+}
+
+#[rstest]
+fn nn_descent_graphs_skip_non_finite_pairs(dirty: Dummy) {
+    let graph = GraphBuilder::NnDescent {
+        k: 8,
+        iterations: 6,
+        sample_rate: 1.0,
+    };
+    let result = ChutoroBuilder::new()
+        .with_min_cluster_size(3)
+        .with_graph_builder(graph)
+        .with_distance_policy(DistancePolicy::SkipEdge)
+        .build()
+        .expect("configuration must be valid")
+        .run(&dirty)
+        .expect("skipped distances must not abort");
+
+    assert_eq!(result.assignments().last().copied(), result.noise_label());
+    let report = result
+        .distance_policy()
+        .expect("lenient policies report replacements");
+    assert!(report.skipped() > 0);
+}
+
+#[rstest]
+fn clean_runs_report_nothing_under_strict_policy() {
+    let source = Dummy::new((0..12).map(|i| i as f32).collect());
+    let result = run(&source, DistancePolicy::Strict).expect("clean data must cluster");
+
+    assert!(result.distance_policy().is_none());
+}
+
+#[rstest]
+fn clean_runs_report_zero_replacements_under_lenient_policy() {
+    let source = Dummy::new((0..12).map(|i| i as f32).collect());
+    let result = run(&source, DistancePolicy::SkipEdge).expect("clean data must cluster");
+
+    let report = result
+        .distance_policy()
+        .expect("lenient policies always report");
+    assert_eq!(report.total(), 0);
+}
@@ -0,0 +1,186 @@
+//! Tests for weighting the spanning tree with double-precision distances.
+#![cfg(feature = "cpu")]
+
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use chutoro_core::{
+    ChutoroBuilder, ClusteringResult, DataSource, DataSourceError, DistancePrecision, StageArtefact,
+};
+use rstest::{fixture, rstest};
+
+/// Offset large enough that every distance collapses to the same `f32`.
+const OFFSET: f64 = 1.0e8;
+
+/// Two groups of points on a line whose distances sit on top of a large
+/// offset, so only [`DataSource::distance_f64`] can tell them apart.
+struct Offset {
+    points: Vec<f64>,
+    precise_calls: AtomicUsize,
+}
+
+impl Offset {
+    fn exact(&self, i: usize, j: usize) -> f64 {
+        if i == j {
+            0.0
+        } else {
+            OFFSET + (self.points[i] - self.points[j]).abs()
+        }
+    }
+}
+
+impl DataSource for Offset {
+    fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    fn name(&self) -> &str {
+        "offset"
+    }
+
+    fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+        Ok(self.exact(i, j) as f32)
+    }
+
+    fn distance_f64(&self, i: usize, j: usize) -> Result<f64, DataSourceError> {
+        self.precise_calls.fetch_add(1, Ordering::Relaxed);
+        Ok(self.exact(i, j))
+    }
+}
+
+#[fixture]
+fn offset() -> Offset {
+    let near = (0..8).map(|i| f64::from(i) * 0.001);
+    let far = (0..8).map(|i| 1.0 + f64::from(i) * 0.001);
+    Offset {
+        points: near.chain(far).collect(),
+        precise_calls: AtomicUsize::new(0),
+    }
+}
+
+fn run(source: &Offset, precision: DistancePrecision) -> ClusteringResult {
+    ChutoroBuilder::new()
+        .with_min_cluster_size(3)
+        .with_seed(11)
+        .with_distance_precision(precision)
+        .build()
+        .expect("configuration must be valid")
+        .run(source)
+        .expect("run must succeed")
+}
+
+/// Runs `source` and returns the spanning tree's edge weights.
+fn mst_weights(source: &Offset, precision: DistancePrecision) -> Vec<f64> {
+    let weights = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&weights);
+    ChutoroBuilder::new()
+        .with_min_cluster_size(3)
+        .with_seed(11)
+        .with_distance_precision(precision)
+        .on_stage_complete(move |artefact: StageArtefact<'_>| {
+            if let StageArtefact::Mst(edges) = artefact {
+                sink.lock()
+                    .expect("hook lock is healthy")
+                    .extend(edges.iter().map(|edge| edge.weight_f64()));
+            }
+        })
+        .build()
+        .expect("configuration must be valid")
+        .run(source)
+        .expect("run must succeed");
+    weights.lock().expect("hook lock is healthy").clone()
+}
+
+#[rstest]
+fn single_precision_never_asks_for_f64_distances(offset: Offset) {
+    run(&offset, DistancePrecision::Single);
+
+    assert_eq!(offset.precise_calls.load(Ordering::Relaxed), 0);
+}
+
+#[rstest]
+fn single_precision_collapses_the_spanning_tree_weights(offset: Offset) {
+    let weights = mst_weights(&offset, DistancePrecision::Single);
+
+    assert_eq!(weights.len(), 15);
+    let collapsed = f64::from(OFFSET as f32);
+    assert!(weights.iter().all(|&weight| weight == collapsed));
+}
+
+#[rstest]
+fn double_precision_keeps_the_spanning_tree_weights_apart(offset: Offset) {
+    let weights = mst_weights(&offset, DistancePrecision::Double);
+
+    assert!(offset.precise_calls.load(Ordering::Relaxed) > 0);
+    assert_eq!(weights.len(), 15);
+    assert!(weights.iter().all(|&weight| weight > OFFSET));
+    // Fourteen short hops within the groups and one bridge between them.
+    let bridges = weights
+        .iter()
+        .filter(|&&weight| weight > OFFSET + 0.5)
+        .count();
+    assert_eq!(bridges, 1);
+}
+
+#[rstest]
+fn double_precision_keeps_the_labels_of_separated_groups() {
+    let near = (0..8).map(|i| f64::from(i) * 0.1);
+    let far = (0..8).map(|i| 10.0 + f64::from(i) * 0.1);
+    let source = Offset {
+        points: near.chain(far).collect(),
+        precise_calls: AtomicUsize::new(0),
+    };
+    let single = run(&source, DistancePrecision::Single);
+    let double = run(&source, DistancePrecision::Double);
+
+    assert_eq!(single.assignments(), double.assignments());
+}
+
+#[rstest]
+fn double_precision_cuts_between_lambdas_below_f32_resolution(offset: Offset) {
+    let result = run(&offset, DistancePrecision::Double);
+    let hierarchy = result.hierarchy().expect("CPU runs keep the hierarchy");
+    let [root] = hierarchy.roots() else {
+        panic!("the groups share one component");
+    };
+    let groups = hierarchy.children(*root);
+    assert_eq!(groups.len(), 2);
+    let split = hierarchy.birth_lambda(groups[0]).expect("known cluster");
+    // Either side of the bridge, and sparser than every hop within a group.
+    let sparse = 1.0 / (OFFSET + 1.5);
+    let dense = 1.0 / (OFFSET + 0.5);
+    assert!(sparse < split && split < dense);
+    assert!(dense - sparse < f64::from(f32::EPSILON) * split);
+
+    let whole = hierarchy.cut_at_lambda(sparse).expect("valid lambda");
+    let apart = hierarchy.cut_at_lambda(dense).expect("valid lambda");
+
+    assert_eq!(whole.clusters(), [*root]);
+    assert_eq!(apart.clusters(), groups);
+    assert_eq!(apart.result().noise_label(), None);
+}
+
+#[rstest]
+fn double_precision_explanations_keep_f64_weights(offset: Offset) {
+    let result = ChutoroBuilder::new()
+        .with_min_cluster_size(3)
+        .with_seed(11)
+        .with_distance_precision(DistancePrecision::Double)
+        .with_edge_provenance(true)
+        .build()
+        .expect("configuration must be valid")
+        .run(&offset)
+        .expect("run must succeed");
+
+    let across = result.explain_pair(0, 15).expect("points are in range");
+    let bridge = across.bottleneck().expect("the path is non-empty");
+    assert!(bridge.weight() > OFFSET + 0.5);
+    assert_eq!(across.lambda(), 1.0 / bridge.weight());
+    let within = result.explain_pair(0, 7).expect("points are in range");
+    assert!(within.lambda() > across.lambda());
+
+    let hydrated = ClusteringResult::from_bytes(&result.to_bytes()).expect("bytes must decode");
+    assert_eq!(hydrated.explained_edges(), result.explained_edges());
+}
@@ -0,0 +1,121 @@
+//! Tests for transforming source distances before the pipeline sees them.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use std::num::NonZeroUsize;
+
+use chutoro_core::{
+    ChutoroBuilder, ClusteringResult, DataSource, DataSourceError, DistanceTransform, MetricClass,
+    MetricDescriptor, WarningCode,
+};
+use common::Dummy;
+use rstest::rstest;
+
+/// Two groups of points on a line that declare themselves a metric, compared
+/// by plain or squared distance.
+struct Line {
+    points: Vec<f32>,
+    squared: bool,
+}
+
+impl Line {
+    fn new(squared: bool) -> Self {
+        Self {
+            points: groups(),
+            squared,
+        }
+    }
+}
+
+impl DataSource for Line {
+    fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    fn name(&self) -> &str {
+        "line"
+    }
+
+    fn metric_descriptor(&self) -> MetricDescriptor {
+        MetricDescriptor::new("line").with_class(MetricClass::Metric)
+    }
+
+    fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+        let distance = (self.points[i] - self.points[j]).abs();
+        Ok(if self.squared {
+            distance * distance
+        } else {
+            distance
+        })
+    }
+}
+
+fn groups() -> Vec<f32> {
+    let near = (0..20).map(|i| i as f32 * 0.5);
+    let far = (0..20).map(|i| 100.0 + i as f32 * 0.5);
+    near.chain(far).collect()
+}
+
+fn builder(transform: DistanceTransform) -> ChutoroBuilder {
+    ChutoroBuilder::new()
+        .with_min_cluster_size(3)
+        .with_seed(5)
+        .with_distance_transform(transform)
+}
+
+fn run<D: DataSource + Sync>(builder: ChutoroBuilder, source: &D) -> ClusteringResult {
+    builder
+        .build()
+        .expect("configuration must be valid")
+        .run(source)
+        .expect("run must succeed")
+}
+
+fn triangle_warnings(transform: DistanceTransform, source: &Line) -> usize {
+    let triples = NonZeroUsize::new(256).expect("non-zero");
+    let result = run(builder(transform).with_triangle_check(triples), source);
+    result
+        .warnings()
+        .iter()
+        .filter(|warning| warning.code() == WarningCode::TriangleInequalityViolated)
+        .count()
+}
+
+#[rstest]
+fn squaring_matches_a_squared_source() {
+    let transformed = run(builder(DistanceTransform::Square), &Dummy::new(groups()));
+    let squared = run(builder(DistanceTransform::Identity), &Line::new(true));
+
+    assert_eq!(transformed.assignments(), squared.assignments());
+    assert_eq!(transformed.cluster_count(), 2);
+}
+
+#[rstest]
+fn sqrt_restores_the_triangle_inequality() {
+    let source = Line::new(true);
+
+    assert_eq!(triangle_warnings(DistanceTransform::Identity, &source), 1);
+    assert_eq!(triangle_warnings(DistanceTransform::Sqrt, &source), 0);
+}
+
+#[rstest]
+#[case::square(DistanceTransform::Square)]
+#[case::custom(DistanceTransform::Custom(|distance| distance.powi(3)))]
+fn transforms_that_may_break_the_inequality_skip_the_check(#[case] transform: DistanceTransform) {
+    assert_eq!(triangle_warnings(transform, &Line::new(false)), 0);
+}
+
+#[rstest]
+fn dry_runs_probe_transformed_distances() {
+    let chutoro = builder(DistanceTransform::Custom(|distance| distance - 1.0))
+        .build()
+        .expect("configuration must be valid");
+
+    let report = chutoro
+        .dry_run(&Line::new(false))
+        .expect("probes must evaluate");
+
+    assert!(report.negative_pairs() > 0);
+    assert!(!report.passed());
+}
@@ -0,0 +1,144 @@
+//! Tests for drift reports between a baseline clustering and a later one.
+
+mod common;
+
+#[cfg(feature = "cpu")]
+use chutoro_core::ChutoroBuilder;
+use chutoro_core::{ClusterId, ClusteringResult, DriftError, DriftReport, DriftThresholds};
+#[cfg(feature = "cpu")]
+use common::Dummy;
+use rstest::rstest;
+
+fn ids(labels: &[u64]) -> ClusteringResult {
+    ClusteringResult::from_assignments(labels.iter().copied().map(ClusterId::new).collect())
+}
+
+#[rstest]
+fn relabelled_clusters_of_unchanged_size_do_not_drift() {
+    let baseline = ids(&[0, 0, 0, 1, 1, 1]);
+    let current = ids(&[1, 1, 1, 0, 0, 0, 1, 0]);
+
+    let report = DriftReport::compare(&baseline, &current, DriftThresholds::default())
+        .expect("current extends the baseline");
+
+    let matched: Vec<_> = report.clusters().iter().map(|c| c.current.get()).collect();
+    assert_eq!(matched, [1, 0]);
+    assert!(report.clusters().iter().all(|c| c.jaccard == 1.0));
+    assert!(!report.has_drift());
+}
+
+#[rstest]
+fn split_clusters_disappear_and_their_parts_appear() {
+    let baseline = ids(&[0, 0, 0, 0, 0, 0, 1, 1]);
+    let current = ids(&[0, 0, 1, 1, 2, 2, 3, 3]);
+
+    let report =
+        DriftReport::compare(&baseline, &current, DriftThresholds::default()).expect("same points");
+
+    assert_eq!(report.disappeared(), &[ClusterId::new(0)]);
+    assert_eq!(report.clusters()[0].current, ClusterId::new(3));
+    assert_eq!(report.appeared(), [0, 1, 2].map(ClusterId::new));
+    assert!(report.has_drift());
+}
+
+#[rstest]
+fn growth_beyond_the_population_threshold_is_flagged() {
+    let baseline = ids(&[0, 0, 1, 1]);
+    let current = ids(&[0, 0, 1, 1, 0, 0]);
+    let loose = DriftThresholds::default().with_population_shift(0.2);
+
+    let strict = DriftReport::compare(&baseline, &current, DriftThresholds::default())
+        .expect("current extends the baseline");
+    let relaxed = DriftReport::compare(&baseline, &current, loose).expect("same inputs");
+
+    let grown = strict.clusters()[0];
+    assert!((grown.population_shift() - (4.0 / 6.0 - 0.5)).abs() < 1e-12);
+    assert!(grown.flagged);
+    assert!(!relaxed.clusters()[0].flagged);
+}
+
+#[cfg(feature = "cpu")]
+#[rstest]
+fn noise_is_neither_matched_nor_reported_as_appearing() {
+    let chutoro = ChutoroBuilder::new()
+        .with_min_cluster_size(2)
+        .build()
+        .expect("configuration must be valid");
+    let points = [0.0, 0.1, 0.2, 10.0, 10.1, 10.2, 100.0];
+    let baseline = chutoro
+        .run(&Dummy::new(points.to_vec()))
+        .expect("baseline run must succeed");
+    let current = chutoro
+        .run(&Dummy::new(
+            points.iter().copied().chain([-100.0]).collect(),
+        ))
+        .expect("current run must succeed");
+    assert!(baseline.noise_label().is_some() && current.noise_label().is_some());
+
+    let report = DriftReport::compare(&baseline, &current, DriftThresholds::default())
+        .expect("current extends the baseline");
+
+    assert_eq!(report.clusters().len(), 2);
+    assert!(report.appeared().is_empty());
+    assert!(report.disappeared().is_empty());
+}
+
+#[rstest]
+fn centroid_movement_is_measured_from_dense_rows() {
+    let baseline = ids(&[0, 0, 1, 1]);
+    let current = ids(&[0, 0, 1, 1, 0, 0]);
+    let rows = vec![
+        vec![0.0, 0.0],
+        vec![2.0, 0.0],
+        vec![10.0, 10.0],
+        vec![10.0, 12.0],
+        vec![4.0, 0.0],
+        vec![6.0, 0.0],
+    ];
+    let thresholds = DriftThresholds::default()
+        .with_population_shift(1.0)
+        .with_centroid_shift(1.0);
+
+    let report = DriftReport::compare_dense(&baseline, &current, &rows, thresholds)
+        .expect("rows cover the current run");
+
+    let [moved, still] = report.clusters() else {
+        panic!("both clusters must match");
+    };
+    assert_eq!(moved.centroid_shift, Some(2.0));
+    assert!(moved.flagged);
+    assert_eq!(still.centroid_shift, Some(0.0));
+    assert!(!still.flagged);
+}
+
+#[rstest]
+fn current_run_must_extend_the_baseline() {
+    let err = DriftReport::compare(&ids(&[0, 0, 1]), &ids(&[0, 0]), DriftThresholds::default())
+        .expect_err("a shorter run cannot extend the baseline");
+
+    assert_eq!(
+        err,
+        DriftError::BaselineNotPrefix {
+            baseline: 3,
+            current: 2
+        }
+    );
+}
+
+#[rstest]
+#[case::missing_row(vec![vec![0.0], vec![1.0]], DriftError::RowCountMismatch { rows: 2, points: 3 })]
+#[case::ragged(
+    vec![vec![0.0], vec![1.0, 2.0], vec![3.0]],
+    DriftError::RaggedRows { row: 1, expected: 1, actual: 2 }
+)]
+fn dense_rows_must_match_the_current_run(
+    #[case] rows: Vec<Vec<f32>>,
+    #[case] expected: DriftError,
+) {
+    let run = ids(&[0, 0, 1]);
+
+    let err = DriftReport::compare_dense(&run, &run, &rows, DriftThresholds::default())
+        .expect_err("rows are invalid");
+
+    assert_eq!(err, expected);
+}
@@ -0,0 +1,169 @@
+//! Tests for validating a configuration with `Chutoro::dry_run`.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use chutoro_core::{
+    ChutoroBuilder, ChutoroError, DataSource, DataSourceError, DistancePolicy, DryRunReport,
+};
+use common::{Dummy, truncated::Truncated};
+use rstest::{fixture, rstest};
+
+/// Forty evenly spaced points on a line.
+#[fixture]
+fn line() -> Dummy {
+    Dummy::new((0..40).map(|i| i as f32).collect())
+}
+
+/// How [`Skewed`] breaks the metric properties.
+#[derive(Clone, Copy, Debug)]
+enum Skew {
+    Asymmetric,
+    Negative,
+    SelfDistance,
+}
+
+/// A source whose distances are skewed by argument order, sign, or a
+/// non-zero distance from each point to itself.
+struct Skewed {
+    len: usize,
+    skew: Skew,
+}
+
+impl DataSource for Skewed {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn name(&self) -> &str {
+        "skewed"
+    }
+
+    fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+        let gap = i.abs_diff(j) as f32;
+        Ok(match (self.skew, i < j) {
+            (Skew::Negative, _) => -gap,
+            (Skew::SelfDistance, _) => gap + 1.0,
+            (Skew::Asymmetric, true) => gap,
+            (Skew::Asymmetric, false) => gap * 2.0,
+        })
+    }
+}
+
+fn dry_run<D: DataSource>(builder: ChutoroBuilder, source: &D) -> DryRunReport {
+    builder
+        .with_min_cluster_size(3)
+        .build()
+        .expect("configuration must be valid")
+        .dry_run(source)
+        .expect("dry run must succeed")
+}
+
+#[rstest]
+fn clean_sources_pass(line: Dummy) {
+    let chutoro = ChutoroBuilder::new()
+        .with_min_cluster_size(3)
+        .build()
+        .expect("configuration must be valid");
+
+    let report = chutoro.dry_run(&line).expect("dry run must succeed");
+
+    assert!(report.passed());
+    assert_eq!(report.probed_pairs(), 1024);
+    assert_eq!(
+        (
+            report.non_finite_pairs(),
+            report.negative_pairs(),
+            report.asymmetric_pairs()
+        ),
+        (0, 0, 0)
+    );
+    assert_eq!(report.estimate(), chutoro.estimate_resources(&line));
+    assert_eq!(report.fits_memory(), None);
+}
+
+#[rstest]
+#[case::strict(DistancePolicy::Strict, false)]
+#[case::clamp(DistancePolicy::ClampToMax, true)]
+#[case::skip(DistancePolicy::SkipEdge, true)]
+fn non_finite_distances_fail_only_strict_runs(
+    #[case] policy: DistancePolicy,
+    #[case] passes: bool,
+) {
+    let source = Dummy::new(vec![0.0, 1.0, 2.0, f32::NAN]);
+
+    let report = dry_run(ChutoroBuilder::new().with_distance_policy(policy), &source);
+
+    assert!(report.non_finite_pairs() > 0);
+    assert_eq!(report.passed(), passes);
+}
+
+#[rstest]
+#[case::asymmetric(Skew::Asymmetric, (0, 0, 1024))]
+#[case::negative(Skew::Negative, (1024, 0, 0))]
+#[case::self_distance(Skew::SelfDistance, (0, 1024, 0))]
+fn invalid_metrics_fail(#[case] skew: Skew, #[case] expected: (usize, usize, usize)) {
+    let report = dry_run(ChutoroBuilder::new(), &Skewed { len: 10, skew });
+
+    assert!(!report.passed());
+    assert_eq!(
+        (
+            report.negative_pairs(),
+            report.identity_pairs(),
+            report.asymmetric_pairs()
+        ),
+        expected
+    );
+    assert_eq!(report.validation().violations().len(), 1024);
+}
+
+#[rstest]
+fn memory_limits_are_reported_rather_than_raised(line: Dummy) {
+    let report = dry_run(ChutoroBuilder::new().with_max_bytes(1), &line);
+
+    assert_eq!(report.max_bytes(), Some(1));
+    assert_eq!(report.fits_memory(), Some(false));
+    assert!(!report.passed());
+}
+
+#[rstest]
+fn probes_follow_the_master_seed(line: Dummy) {
+    let seeded = || ChutoroBuilder::new().with_seed(9);
+
+    assert_eq!(dry_run(seeded(), &line), dry_run(seeded(), &line));
+}
+
+#[rstest]
+#[case::empty(Dummy::new(Vec::new()))]
+#[case::undersized(Dummy::new(vec![0.0, 1.0]))]
+fn rejects_sources_a_run_would_reject(#[case] source: Dummy) {
+    let chutoro = ChutoroBuilder::new()
+        .with_min_cluster_size(3)
+        .build()
+        .expect("configuration must be valid");
+
+    let err = chutoro.dry_run(&source).expect_err("dry run must fail");
+
+    assert!(matches!(
+        err,
+        ChutoroError::EmptySource { .. } | ChutoroError::InsufficientItems { .. }
+    ));
+}
+
+#[rstest]
+fn data_source_failures_are_raised() {
+    let chutoro = ChutoroBuilder::new()
+        .with_min_cluster_size(2)
+        .build()
+        .expect("configuration must be valid");
+
+    let err = chutoro.dry_run(&Truncated).expect_err("dry run must fail");
+
+    assert!(matches!(
+        err,
+        ChutoroError::DataSource {
+            error: DataSourceError::OutOfBounds { index: 3 },
+            ..
+        }
+    ));
+}
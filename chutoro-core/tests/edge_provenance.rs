@@ -0,0 +1,101 @@
+//! Tests for explaining point pairs through the recorded spanning forest.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use chutoro_core::{ChutoroBuilder, ClusteringResult, ExplainedEdge, HnswParams, SampleSpec};
+use common::Dummy;
+use rstest::{fixture, rstest};
+
+/// Two well-separated groups of 20 points each.
+#[fixture]
+fn groups() -> Dummy {
+    let near = (0..20).map(|i| i as f32 * 0.1);
+    let far = (0..20).map(|i| 100.0 + i as f32 * 0.1);
+    Dummy::new(near.chain(far).collect())
+}
+
+fn cluster(builder: ChutoroBuilder, source: &Dummy) -> ClusteringResult {
+    builder
+        .with_min_cluster_size(3)
+        .with_seed(3)
+        .build()
+        .expect("configuration must be valid")
+        .run(source)
+        .expect("run must succeed")
+}
+
+fn explained(source: &Dummy) -> ClusteringResult {
+    cluster(ChutoroBuilder::new().with_edge_provenance(true), source)
+}
+
+/// Asserts that `path` walks edge by edge from `from` to `to`.
+fn assert_walks(path: &[ExplainedEdge], from: usize, to: usize) {
+    let end = path.iter().fold(from, |point, edge| {
+        assert!(
+            edge.source() == point || edge.target() == point,
+            "edge {edge:?} does not continue from {point}"
+        );
+        edge.source() + edge.target() - point
+    });
+    assert_eq!(end, to);
+}
+
+#[rstest]
+fn runs_without_provenance_explain_nothing(groups: Dummy) {
+    let result = cluster(ChutoroBuilder::new(), &groups);
+
+    assert!(result.explain_pair(0, 1).is_none());
+    assert!(result.explained_edges().is_none());
+}
+
+#[rstest]
+fn every_harvested_forest_edge_records_its_insertion(groups: Dummy) {
+    let result = explained(&groups);
+    let edges = result.explained_edges().expect("provenance was recorded");
+    let ef = HnswParams::default().ef_construction();
+
+    assert_eq!(edges.len(), 39);
+    for edge in edges {
+        let provenance = edge.provenance().expect("the build produced every edge");
+        assert!([edge.source(), edge.target()].contains(&provenance.inserted()));
+        assert_eq!(provenance.ef(), ef);
+    }
+}
+
+#[rstest]
+fn pairs_within_a_group_join_below_the_bridge(groups: Dummy) {
+    let result = explained(&groups);
+
+    let within = result.explain_pair(0, 19).expect("points are in range");
+    let across = result.explain_pair(0, 39).expect("points are in range");
+
+    assert_walks(within.path(), 0, 19);
+    assert_walks(across.path(), 0, 39);
+    assert!(within.connected() && across.connected());
+    assert!(within.lambda() > across.lambda());
+    let bridge = across.bottleneck().expect("the path is non-empty");
+    assert!(bridge.weight() > 90.0);
+    assert_eq!(across.lambda(), 1.0 / bridge.weight());
+}
+
+#[rstest]
+fn degenerate_pairs_are_explained_without_a_path(groups: Dummy) {
+    let result = explained(&groups);
+
+    let itself = result.explain_pair(5, 5).expect("point is in range");
+    assert!(itself.path().is_empty());
+    assert_eq!(itself.lambda(), f64::INFINITY);
+    assert!(result.explain_pair(0, 40).is_none());
+}
+
+#[rstest]
+fn sampled_runs_keep_no_forest(groups: Dummy) {
+    let builder = ChutoroBuilder::new()
+        .with_edge_provenance(true)
+        .with_sample(SampleSpec::Fraction(0.5), 7);
+
+    let result = cluster(builder, &groups);
+
+    assert!(result.explain_pair(0, 1).is_none());
+}
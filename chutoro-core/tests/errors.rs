@@ -19,6 +19,11 @@ use rstest::rstest;
 )]
 #[case(DataSourceError::EmptyData, DataSourceErrorCode::EmptyData)]
 #[case(DataSourceError::ZeroDimension, DataSourceErrorCode::ZeroDimension)]
+#[case(
+    DataSourceError::DistanceBudgetExhausted { budget: 10 },
+    DataSourceErrorCode::DistanceBudgetExhausted,
+)]
+#[case(DataSourceError::Cancelled, DataSourceErrorCode::Cancelled)]
 fn returns_expected_data_source_code(
     #[case] error: DataSourceError,
     #[case] expected: DataSourceErrorCode,
@@ -62,6 +67,15 @@ fn returns_expected_data_source_code(
     ChutoroErrorCode::DataSourceFailure,
     Some(DataSourceErrorCode::OutOfBounds),
 )]
+#[case(
+    ChutoroError::DistanceBudgetExceeded {
+        data_source: Arc::from("source"),
+        budget: 10,
+        evaluations: 9,
+    },
+    ChutoroErrorCode::DistanceBudgetExceeded,
+    None,
+)]
 fn returns_expected_chutoro_code(
     #[case] error: ChutoroError,
     #[case] expected: ChutoroErrorCode,
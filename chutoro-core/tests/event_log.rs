@@ -0,0 +1,131 @@
+//! Tests for the JSON Lines event log of pipeline internals.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use std::{fs, path::Path};
+
+use chutoro_core::{ChutoroBuilder, ChutoroError};
+use common::Dummy;
+use rstest::{fixture, rstest};
+use serde_json::Value;
+use tempfile::TempDir;
+
+#[fixture]
+fn source() -> Dummy {
+    Dummy::new(
+        (0..30)
+            .map(|point| (point % 3) as f32 * 100.0 + (point / 3) as f32 * 0.5)
+            .collect(),
+    )
+}
+
+#[fixture]
+fn log_dir() -> TempDir {
+    tempfile::tempdir().expect("tempdir")
+}
+
+fn records(path: &Path) -> Vec<Value> {
+    fs::read_to_string(path)
+        .expect("event log must exist")
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("each line must be a JSON object"))
+        .collect()
+}
+
+fn events(records: &[Value]) -> Vec<&str> {
+    records
+        .iter()
+        .map(|record| record["event"].as_str().expect("event name"))
+        .collect()
+}
+
+#[rstest]
+fn runs_record_parameters_stages_and_outcome(source: Dummy, log_dir: TempDir) {
+    let path = log_dir.path().join("events.jsonl");
+    let chutoro = ChutoroBuilder::new()
+        .with_min_cluster_size(5)
+        .with_seed(7)
+        .with_event_log(&path)
+        .build()
+        .expect("configuration must be valid");
+    assert_eq!(chutoro.event_log(), Some(path.as_path()));
+    let result = chutoro.run(&source).expect("run must succeed");
+
+    let records = records(&path);
+    let events = events(&records);
+    assert_eq!(events.first(), Some(&"run_started"));
+    assert_eq!(events.last(), Some(&"run_finished"));
+    assert_eq!(
+        events
+            .iter()
+            .filter(|event| **event == "stage_completed")
+            .count(),
+        4
+    );
+
+    let started = &records[0];
+    assert_eq!(started["data_source"], "dummy");
+    assert_eq!(started["items"], 30);
+    assert_eq!(started["min_cluster_size"], 5);
+    assert_eq!(started["seed"], 7);
+    assert!(started["unix_ms"].is_u64());
+
+    let mst = records
+        .iter()
+        .find(|record| record["event"] == "stage_statistics" && record["stage"] == "mst")
+        .expect("MST statistics must be recorded");
+    assert_eq!(mst["edges"], 29);
+
+    let finished = records.last().expect("records");
+    assert_eq!(finished["clusters"], result.cluster_count());
+    let backend = result.backend().expect("runs record their backend");
+    assert_eq!(finished["backend"], backend.as_str());
+}
+
+#[rstest]
+fn failed_runs_record_the_error_code(log_dir: TempDir) {
+    let path = log_dir.path().join("events.jsonl");
+    let chutoro = ChutoroBuilder::new()
+        .with_min_cluster_size(5)
+        .with_event_log(&path)
+        .build()
+        .expect("configuration must be valid");
+    let err = chutoro
+        .run(&Dummy::new(vec![1.0, 2.0]))
+        .expect_err("too few items must fail");
+
+    let records = records(&path);
+    assert_eq!(events(&records), ["run_started", "run_failed"]);
+    assert_eq!(records[1]["code"], err.code().as_str());
+}
+
+#[rstest]
+fn runs_append_to_an_existing_log(source: Dummy, log_dir: TempDir) {
+    let path = log_dir.path().join("events.jsonl");
+    let chutoro = ChutoroBuilder::new()
+        .with_min_cluster_size(5)
+        .with_event_log(&path)
+        .build()
+        .expect("configuration must be valid");
+    chutoro.run(&source).expect("first run must succeed");
+    chutoro.run(&source).expect("second run must succeed");
+
+    let records = records(&path);
+    let started = events(&records)
+        .into_iter()
+        .filter(|event| *event == "run_started")
+        .count();
+    assert_eq!(started, 2);
+}
+
+#[rstest]
+fn build_rejects_an_unwritable_log(log_dir: TempDir) {
+    let path = log_dir.path().join("missing").join("events.jsonl");
+    let err = ChutoroBuilder::new()
+        .with_event_log(&path)
+        .build()
+        .expect_err("a log in a missing directory must be rejected");
+    assert!(matches!(err, ChutoroError::EventLog { .. }));
+    assert_eq!(err.code().as_str(), "CHUTORO_EVENT_LOG_FAILURE");
+}
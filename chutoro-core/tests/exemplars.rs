@@ -0,0 +1,94 @@
+//! Tests for `ClusteringResult::exemplars`.
+
+mod common;
+
+use chutoro_core::{ClusterId, ClusteringResult, ExemplarError};
+use common::Dummy;
+use rstest::rstest;
+
+fn labelled(labels: &[u64]) -> ClusteringResult {
+    ClusteringResult::from_assignments(labels.iter().copied().map(ClusterId::new).collect())
+}
+
+#[rstest]
+fn without_scores_the_most_central_points_win() {
+    let source = Dummy::new(vec![0.0, 1.0, 2.0, 3.0, 4.0, 20.0, 21.0]);
+    let result = labelled(&[0, 0, 0, 0, 0, 1, 1]);
+
+    let exemplars = result.exemplars(&source, 3).expect("lengths must match");
+
+    assert_eq!(exemplars.len(), 2);
+    assert_eq!(exemplars[0].cluster, ClusterId::new(0));
+    assert_eq!(exemplars[0].points, [2, 1, 3]);
+    assert_eq!(exemplars[1].cluster, ClusterId::new(1));
+    assert_eq!(
+        exemplars[1].points,
+        [5, 6],
+        "small clusters return every point"
+    );
+}
+
+#[rstest]
+fn zero_per_cluster_selects_nothing() {
+    let source = Dummy::new(vec![0.0, 1.0]);
+
+    let exemplars = labelled(&[0, 0])
+        .exemplars(&source, 0)
+        .expect("lengths must match");
+
+    assert_eq!(exemplars.len(), 1);
+    assert!(exemplars[0].points.is_empty());
+}
+
+#[rstest]
+fn mismatched_sources_are_rejected() {
+    let source = Dummy::new(vec![0.0, 1.0, 2.0]);
+
+    let err = labelled(&[0, 0])
+        .exemplars(&source, 1)
+        .expect_err("lengths differ");
+
+    assert!(matches!(
+        err,
+        ExemplarError::LengthMismatch {
+            assignments: 2,
+            source_len: 3
+        }
+    ));
+}
+
+#[cfg(feature = "cpu")]
+#[rstest]
+fn pipeline_exemplars_are_the_most_persistent_members() {
+    use chutoro_core::ChutoroBuilder;
+
+    let near = (0..12).map(|i| i as f32 * 0.1);
+    let far = (0..12).map(|i| 40.0 + i as f32 * 0.1);
+    let source = Dummy::new(near.chain(far).chain([-30.0, 500.0]).collect());
+    let result = ChutoroBuilder::new()
+        .with_min_cluster_size(5)
+        .build()
+        .expect("configuration must be valid")
+        .run(&source)
+        .expect("run must succeed");
+    let probabilities = result
+        .membership()
+        .expect("the pipeline scores membership")
+        .probabilities();
+
+    let exemplars = result.exemplars(&source, 4).expect("lengths must match");
+
+    assert_eq!(exemplars.len(), result.cluster_count() - 1);
+    for cluster in &exemplars {
+        assert_eq!(cluster.points.len(), 4);
+        assert_ne!(Some(cluster.cluster), result.noise_label());
+        let best = (0..result.assignments().len())
+            .filter(|&point| result.assignments()[point] == cluster.cluster)
+            .map(|point| probabilities[point])
+            .fold(0.0_f32, f32::max);
+        for &point in &cluster.points {
+            assert_eq!(result.assignments()[point], cluster.cluster);
+            assert_eq!(probabilities[point], best);
+        }
+    }
+}
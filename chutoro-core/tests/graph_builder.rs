@@ -0,0 +1,110 @@
+//! Tests for clustering over an NN-descent k-NN graph instead of HNSW.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use chutoro_core::{
+    ChutoroBuilder, ChutoroError, ClusteringResult, DefaultHarvestStage, GraphBuilder,
+    ReassignPolicy, SampleSpec, StageArtefact,
+};
+use common::Dummy;
+use rstest::{fixture, rstest};
+
+const NN_DESCENT: GraphBuilder = GraphBuilder::NnDescent {
+    k: 8,
+    iterations: 10,
+    sample_rate: 1.0,
+};
+
+/// Two well-separated groups of 30 points each.
+#[fixture]
+fn groups() -> Dummy {
+    let near = (0..30).map(|i| i as f32 * 0.1);
+    let far = (0..30).map(|i| 100.0 + i as f32 * 0.1);
+    Dummy::new(near.chain(far).collect())
+}
+
+fn cluster(builder: ChutoroBuilder, source: &Dummy) -> ClusteringResult {
+    builder
+        .with_min_cluster_size(5)
+        .with_seed(11)
+        .build()
+        .expect("configuration must be valid")
+        .run(source)
+        .expect("run must succeed")
+}
+
+#[rstest]
+fn nn_descent_separates_the_groups_like_hnsw(groups: Dummy) {
+    let hnsw = cluster(ChutoroBuilder::new(), &groups);
+    let graph = cluster(
+        ChutoroBuilder::new().with_graph_builder(NN_DESCENT),
+        &groups,
+    );
+
+    assert_eq!(graph.cluster_count(), 2);
+    assert_eq!(graph.assignments(), hnsw.assignments());
+    assert!(graph.connectivity().is_some());
+}
+
+#[rstest]
+fn runs_report_the_graph_in_place_of_an_index(groups: Dummy) {
+    let stages = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&stages);
+    let builder = ChutoroBuilder::new()
+        .with_graph_builder(NN_DESCENT)
+        .with_edge_provenance(true)
+        .on_stage_complete(move |artefact: StageArtefact<'_>| {
+            let stage = match artefact {
+                StageArtefact::Graph(graph) => {
+                    // Each of the 60 points keeps 8 neighbours, and every
+                    // pair is harvested once.
+                    assert!(graph.len() >= 60 * 8 / 2);
+                    "graph"
+                }
+                StageArtefact::Index { .. } => "index",
+                _ => "other",
+            };
+            sink.lock().expect("hook lock is healthy").push(stage);
+        });
+
+    let result = cluster(builder, &groups);
+
+    let stages = stages.lock().expect("hook lock is healthy");
+    assert_eq!(stages.first(), Some(&"graph"));
+    assert!(!stages.contains(&"index"));
+    assert!(result.explain_pair(0, 1).is_none());
+}
+
+#[rstest]
+#[case::zero_k(GraphBuilder::NnDescent { k: 0, iterations: 4, sample_rate: 1.0 })]
+#[case::zero_iterations(GraphBuilder::NnDescent { k: 4, iterations: 0, sample_rate: 1.0 })]
+#[case::zero_rate(GraphBuilder::NnDescent { k: 4, iterations: 4, sample_rate: 0.0 })]
+#[case::large_rate(GraphBuilder::NnDescent { k: 4, iterations: 4, sample_rate: 1.5 })]
+#[case::nan_rate(GraphBuilder::NnDescent { k: 4, iterations: 4, sample_rate: f32::NAN })]
+fn invalid_settings_are_rejected(#[case] graph: GraphBuilder) {
+    let err = ChutoroBuilder::new()
+        .with_graph_builder(graph)
+        .build()
+        .expect_err("settings are invalid");
+
+    assert!(matches!(err, ChutoroError::InvalidGraphBuilder { .. }));
+    assert_eq!(err.code().as_str(), "CHUTORO_INVALID_GRAPH_BUILDER");
+}
+
+#[rstest]
+#[case::sample(ChutoroBuilder::new().with_sample(SampleSpec::Fraction(0.5), 1))]
+#[case::harvest_stage(ChutoroBuilder::new().with_harvest_stage(DefaultHarvestStage))]
+#[case::reassign(
+    ChutoroBuilder::new().reassign_noise(ReassignPolicy::NearestCluster { max_distance: 1.0 })
+)]
+fn options_needing_an_index_are_rejected(#[case] builder: ChutoroBuilder) {
+    let err = builder
+        .with_graph_builder(NN_DESCENT)
+        .build()
+        .expect_err("the option needs an HNSW index");
+
+    assert!(matches!(err, ChutoroError::InvalidGraphBuilder { .. }));
+}
@@ -0,0 +1,121 @@
+//! Tests for comparing the cluster hierarchies of two runs.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use chutoro_core::{ChutoroBuilder, ClusterHierarchy, ClusteringResult, StageArtefact};
+use common::Dummy;
+use rstest::{fixture, rstest};
+
+/// Two distant groups, each made of two nearby subgroups of ten points.
+#[fixture]
+fn nested() -> Dummy {
+    let subgroup = |start: f32| (0..10).map(move |i| start + i as f32 * 0.01);
+    let points = subgroup(0.0)
+        .chain(subgroup(1.0))
+        .chain(subgroup(100.0))
+        .chain(subgroup(101.0));
+    Dummy::new(points.collect())
+}
+
+fn run(min_cluster_size: usize, source: &Dummy) -> ClusteringResult {
+    ChutoroBuilder::new()
+        .with_min_cluster_size(min_cluster_size)
+        .build()
+        .expect("configuration must be valid")
+        .run(source)
+        .expect("run must succeed")
+}
+
+fn hierarchy(result: &ClusteringResult) -> &ClusterHierarchy {
+    result.hierarchy().expect("CPU runs keep the hierarchy")
+}
+
+#[rstest]
+fn a_hierarchy_matches_itself_exactly(nested: Dummy) {
+    let result = run(5, &nested);
+    let hierarchy = hierarchy(&result);
+
+    let comparison = hierarchy.compare(hierarchy);
+
+    assert_eq!(comparison.distance(), 0.0);
+    assert_eq!(comparison.similarity(), 1.0);
+    assert_eq!(comparison.matches().len(), hierarchy.cluster_count());
+    assert!(
+        comparison
+            .matches()
+            .iter()
+            .all(|matched| matched.left == matched.right && matched.jaccard == 1.0)
+    );
+}
+
+#[rstest]
+fn coarser_runs_cost_one_edit_per_missing_subcluster(nested: Dummy) {
+    let finer_result = run(5, &nested);
+    let coarser_result = run(15, &nested);
+    let (finer, coarser) = (hierarchy(&finer_result), hierarchy(&coarser_result));
+    assert!(coarser.cluster_count() < finer.cluster_count());
+
+    let comparison = finer.compare(coarser);
+
+    let missing = finer.cluster_count() - coarser.cluster_count();
+    assert_eq!(comparison.distance(), missing as f64);
+    assert_eq!(comparison.matches().len(), coarser.cluster_count());
+    assert!(comparison.matches().iter().all(|m| m.jaccard == 1.0));
+    let total = (finer.cluster_count() + coarser.cluster_count()) as f64;
+    assert!((comparison.similarity() - (1.0 - missing as f64 / total)).abs() < 1e-12);
+}
+
+#[rstest]
+fn comparison_is_symmetric_in_its_score(nested: Dummy) {
+    let finer_result = run(5, &nested);
+    let coarser_result = run(15, &nested);
+    let (finer, coarser) = (hierarchy(&finer_result), hierarchy(&coarser_result));
+
+    let forward = finer.compare(coarser);
+    let backward = coarser.compare(finer);
+
+    assert_eq!(forward.distance(), backward.distance());
+    let mut swapped: Vec<(usize, usize)> = backward
+        .matches()
+        .iter()
+        .map(|matched| (matched.right, matched.left))
+        .collect();
+    swapped.sort_unstable();
+    let pairs: Vec<(usize, usize)> = forward
+        .matches()
+        .iter()
+        .map(|matched| (matched.left, matched.right))
+        .collect();
+    assert_eq!(swapped, pairs);
+}
+
+#[rstest]
+fn condensed_trees_compare_inside_the_stage_hook(nested: Dummy) {
+    let seen = Arc::new(Mutex::new(None));
+    let sink = Arc::clone(&seen);
+    let result = ChutoroBuilder::new()
+        .with_min_cluster_size(5)
+        .on_stage_complete(move |artefact: StageArtefact<'_>| {
+            if let StageArtefact::CondensedTree(tree) = artefact {
+                let comparison = tree.compare(&tree);
+                *sink.lock().expect("lock must not be poisoned") =
+                    Some((comparison, tree.cluster_count()));
+            }
+        })
+        .build()
+        .expect("configuration must be valid")
+        .run(&nested)
+        .expect("run must succeed");
+
+    let (comparison, clusters) = seen
+        .lock()
+        .expect("lock must not be poisoned")
+        .take()
+        .expect("the hook must see the condensed tree");
+    assert_eq!(comparison.similarity(), 1.0);
+    assert_eq!(comparison.matches().len(), clusters);
+    assert_eq!(clusters, hierarchy(&result).cluster_count());
+}
@@ -0,0 +1,159 @@
+//! Tests for navigating the cluster hierarchy retained on results.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use chutoro_core::{
+    ChutoroBuilder, ClusterHierarchy, ClusterId, ClusteringResult, HierarchyCutError, SampleSpec,
+};
+use common::Dummy;
+use rstest::{fixture, rstest};
+
+/// Points in each of the four subgroups.
+const SUBGROUP: usize = 10;
+
+/// Two distant groups, each made of two nearby subgroups of ten points.
+#[fixture]
+fn nested() -> Dummy {
+    let subgroup = |start: f32| (0..SUBGROUP).map(move |i| start + i as f32 * 0.01);
+    let points = subgroup(0.0)
+        .chain(subgroup(1.0))
+        .chain(subgroup(100.0))
+        .chain(subgroup(101.0));
+    Dummy::new(points.collect())
+}
+
+fn cluster(builder: ChutoroBuilder, source: &Dummy) -> ClusteringResult {
+    builder
+        .with_min_cluster_size(5)
+        .build()
+        .expect("configuration must be valid")
+        .run(source)
+        .expect("run must succeed")
+}
+
+fn hierarchy(result: &ClusteringResult) -> &ClusterHierarchy {
+    result.hierarchy().expect("CPU runs keep the hierarchy")
+}
+
+/// Asserts that each run of `size` points shares a label no other run uses.
+fn assert_groups_of(result: &ClusteringResult, size: usize) {
+    let labels = result.assignments();
+    let firsts: Vec<ClusterId> = labels.chunks(size).map(|chunk| chunk[0]).collect();
+    for (chunk, &first) in labels.chunks(size).zip(&firsts) {
+        assert!(chunk.iter().all(|&id| id == first), "labels {labels:?}");
+    }
+    let mut distinct = firsts.clone();
+    distinct.sort_unstable();
+    distinct.dedup();
+    assert_eq!(distinct.len(), firsts.len(), "labels {labels:?}");
+}
+
+#[rstest]
+fn descending_from_the_root_reveals_each_level(nested: Dummy) {
+    let result = cluster(ChutoroBuilder::new(), &nested);
+    let hierarchy = hierarchy(&result);
+    assert_eq!(hierarchy.point_count(), 4 * SUBGROUP);
+    let [root] = hierarchy.roots() else {
+        panic!(
+            "one connected dataset has one root: {:?}",
+            hierarchy.roots()
+        );
+    };
+
+    let groups = hierarchy
+        .descend(*root)
+        .expect("the root is in the hierarchy");
+    assert_eq!(groups.clusters(), hierarchy.children(*root));
+    assert_eq!(groups.result().cluster_count(), 2);
+    assert_groups_of(groups.result(), 2 * SUBGROUP);
+
+    for &group in groups.clusters() {
+        assert_eq!(hierarchy.parent(group), Some(*root));
+        let subgroups = hierarchy.descend(group).expect("children are known");
+        assert_eq!(subgroups.clusters().len(), 2);
+        assert!(subgroups.result().noise_label().is_some());
+    }
+}
+
+#[rstest]
+fn raising_lambda_yields_finer_cuts(nested: Dummy) {
+    let result = cluster(ChutoroBuilder::new(), &nested);
+    let hierarchy = hierarchy(&result);
+
+    let coarse = hierarchy
+        .cut_at_lambda(0.0)
+        .expect("zero is a valid lambda");
+    assert_eq!(coarse.clusters(), hierarchy.roots());
+    assert!(coarse.result().noise_label().is_none());
+
+    let [group, _] = hierarchy.children(hierarchy.roots()[0]) else {
+        panic!("the root splits in two");
+    };
+    let [subgroup, _] = hierarchy.children(*group) else {
+        panic!("each group splits in two");
+    };
+    let split = hierarchy.birth_lambda(*group).expect("known cluster");
+    let finer = hierarchy.birth_lambda(*subgroup).expect("known cluster");
+    assert!(split < finer);
+
+    let groups = hierarchy.cut_at_lambda(split).expect("valid lambda");
+    assert_groups_of(groups.result(), 2 * SUBGROUP);
+    let subgroups = hierarchy.cut_at_lambda(finer).expect("valid lambda");
+    assert_eq!(subgroups.result().cluster_count(), 4);
+    assert_groups_of(subgroups.result(), SUBGROUP);
+}
+
+#[rstest]
+fn leaves_descend_to_themselves(nested: Dummy) {
+    let result = cluster(ChutoroBuilder::new(), &nested);
+    let hierarchy = hierarchy(&result);
+    let leaf = (0..hierarchy.cluster_count())
+        .find(|&cluster| hierarchy.children(cluster).is_empty())
+        .expect("a finite tree has leaves");
+
+    let cut = hierarchy.descend(leaf).expect("the leaf is known");
+
+    assert_eq!(cut.clusters(), [leaf]);
+    let result = cut.into_result();
+    // The other subgroups are outside the leaf, so they share the noise label.
+    assert_eq!(result.noise_label(), Some(ClusterId::new(1)));
+    assert!(result.assignments().contains(&ClusterId::new(0)));
+}
+
+#[rstest]
+#[case::nan(f64::NAN)]
+#[case::negative(-1.0)]
+fn invalid_lambdas_are_rejected(nested: Dummy, #[case] lambda: f64) {
+    let result = cluster(ChutoroBuilder::new(), &nested);
+
+    let error = hierarchy(&result)
+        .cut_at_lambda(lambda)
+        .expect_err("lambda must be rejected");
+
+    assert!(matches!(error, HierarchyCutError::InvalidLambda { .. }));
+}
+
+#[rstest]
+fn unknown_clusters_are_rejected(nested: Dummy) {
+    let result = cluster(ChutoroBuilder::new(), &nested);
+    let hierarchy = hierarchy(&result);
+    let cluster_count = hierarchy.cluster_count();
+
+    assert_eq!(
+        hierarchy.descend(cluster_count),
+        Err(HierarchyCutError::UnknownCluster {
+            cluster: cluster_count,
+            cluster_count,
+        })
+    );
+}
+
+#[rstest]
+fn sampled_runs_keep_no_hierarchy(nested: Dummy) {
+    let builder = ChutoroBuilder::new().with_sample(SampleSpec::Fraction(0.5), 3);
+
+    let result = cluster(builder, &nested);
+
+    assert!(result.hierarchy().is_none());
+}
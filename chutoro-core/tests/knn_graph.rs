@@ -0,0 +1,227 @@
+//! Tests for clustering a precomputed k-nearest-neighbour graph.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use std::{num::NonZeroUsize, thread};
+
+use chutoro_core::{
+    CandidateEdge, Chutoro, ChutoroBuilder, ChutoroError, DataSource, DistancePrecision,
+    EdgeHarvest, EdgeHarvestBuilder,
+};
+use common::Dummy;
+use rstest::{fixture, rstest};
+
+#[fixture]
+fn source() -> Dummy {
+    Dummy::new(vec![0.0, 1.0, 2.0, 3.0, 20.0, 21.0, 22.0, 23.0])
+}
+
+fn chutoro(min_cluster_size: usize) -> Chutoro {
+    ChutoroBuilder::new()
+        .with_min_cluster_size(min_cluster_size)
+        .build()
+        .expect("configuration must be valid")
+}
+
+/// Builds the exact `k`-NN graph of `source`, one directed edge per
+/// neighbour.
+fn knn_edges(source: &Dummy, k: usize) -> Vec<CandidateEdge> {
+    let mut edges = Vec::new();
+    for point in 0..source.len() {
+        let mut others: Vec<(usize, f32)> = (0..source.len())
+            .filter(|&other| other != point)
+            .map(|other| (other, source.distance(point, other).expect("in range")))
+            .collect();
+        others.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+        for (other, distance) in others.into_iter().take(k) {
+            edges.push(CandidateEdge::new(
+                point,
+                other,
+                distance,
+                edges.len() as u64,
+            ));
+        }
+    }
+    edges
+}
+
+#[rstest]
+fn exact_graph_matches_a_full_run(source: Dummy) {
+    let chutoro = chutoro(2);
+    let graph = EdgeHarvest::new(knn_edges(&source, 4));
+
+    let from_graph = chutoro
+        .cluster_from_knn_graph(source.len(), graph)
+        .expect("graph must cluster");
+    let full = chutoro.run(&source).expect("run must succeed");
+
+    assert_eq!(from_graph.assignments(), full.assignments());
+    assert_eq!(from_graph.cluster_count(), 2);
+    assert!(from_graph.timings().is_some());
+}
+
+#[rstest]
+fn edge_direction_and_order_do_not_matter(source: Dummy) {
+    let edges = knn_edges(&source, 3);
+    let reversed: Vec<CandidateEdge> = edges
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(sequence, edge)| {
+            CandidateEdge::new(
+                edge.target(),
+                edge.source(),
+                edge.distance(),
+                sequence as u64,
+            )
+        })
+        .collect();
+    let chutoro = chutoro(2);
+
+    let forward = chutoro
+        .cluster_from_knn_graph(source.len(), EdgeHarvest::new(edges))
+        .expect("graph must cluster");
+    let backward = chutoro
+        .cluster_from_knn_graph(source.len(), EdgeHarvest::new(reversed))
+        .expect("graph must cluster");
+
+    assert_eq!(forward.assignments(), backward.assignments());
+}
+
+#[rstest]
+fn parallel_builders_match_a_sequential_graph(source: Dummy) {
+    let edges = knn_edges(&source, 4);
+    let builder = EdgeHarvestBuilder::with_shards(NonZeroUsize::new(3).expect("non-zero"));
+    thread::scope(|scope| {
+        for chunk in edges.chunks(5) {
+            let builder = &builder;
+            scope.spawn(move || {
+                let mut producer = builder.producer();
+                for edge in chunk {
+                    producer.add_edge(edge.source(), edge.target(), edge.distance());
+                }
+            });
+        }
+    });
+    assert_eq!(builder.len(), edges.len());
+    let harvest = builder.finish();
+    let mut sequences: Vec<u64> = harvest.iter().map(CandidateEdge::sequence).collect();
+    sequences.dedup();
+    assert_eq!(sequences.len(), edges.len(), "sequences are unique");
+    let chutoro = chutoro(2);
+
+    let built = chutoro
+        .cluster_from_knn_graph(source.len(), harvest)
+        .expect("graph must cluster");
+    let manual = chutoro
+        .cluster_from_knn_graph(source.len(), EdgeHarvest::new(edges))
+        .expect("graph must cluster");
+
+    assert_eq!(built.assignments(), manual.assignments());
+}
+
+#[rstest]
+fn producers_flush_their_edges_in_blocks() {
+    let builder = EdgeHarvestBuilder::with_shards(NonZeroUsize::new(2).expect("non-zero"));
+    let mut first = builder.producer();
+    let mut second = builder.producer();
+    for point in 0..3 {
+        first.add_edge(point, point + 1, 1.0);
+        second.add_edge(point + 10, point + 11, 2.0);
+    }
+    assert!(builder.is_empty(), "edges stay buffered until flushed");
+
+    second.flush();
+    drop(first);
+    drop(second);
+    let harvest = builder.finish();
+
+    let sources: Vec<usize> = harvest.iter().map(CandidateEdge::source).collect();
+    assert_eq!(sources, [10, 11, 12, 0, 1, 2]);
+    let sequences: Vec<u64> = harvest.iter().map(CandidateEdge::sequence).collect();
+    assert_eq!(sequences, [0, 1, 2, 3, 4, 5]);
+}
+
+#[rstest]
+fn double_precision_weights_the_tree_with_f64_edges() {
+    // Two groups of four whose distances differ only beyond `f32` precision.
+    let offset = 1.0e8;
+    let builder = EdgeHarvestBuilder::new();
+    let mut producer = builder.producer();
+    for group in [0, 4] {
+        for i in group..group + 4 {
+            for j in i + 1..group + 4 {
+                producer.add_edge_f64(i, j, offset + 0.001 * (j - i) as f64);
+            }
+        }
+    }
+    producer.add_edge_f64(3, 4, offset + 1.0);
+    drop(producer);
+    let harvest = builder.finish();
+    assert!(harvest.iter().all(|edge| edge.distance() == offset as f32));
+
+    let result = ChutoroBuilder::new()
+        .with_min_cluster_size(2)
+        .with_distance_precision(DistancePrecision::Double)
+        .build()
+        .expect("configuration must be valid")
+        .cluster_from_knn_graph(8, harvest)
+        .expect("graph must cluster");
+
+    // The bridge is the only weight that splits the tree into two groups.
+    let hierarchy = result.hierarchy().expect("graph runs keep the hierarchy");
+    let [root] = hierarchy.roots() else {
+        panic!("the bridge joins one component");
+    };
+    let groups = hierarchy.descend(*root).expect("known cluster");
+    assert_eq!(groups.clusters().len(), 2);
+    let labels = groups.result().assignments();
+    assert!(labels[..4].iter().all(|&label| label == labels[0]));
+    assert!(labels[4..].iter().all(|&label| label == labels[4]));
+    assert_ne!(labels[0], labels[4]);
+}
+
+#[rstest]
+fn disconnected_graphs_report_their_components(source: Dummy) {
+    // A 2-NN graph never links the two groups of four.
+    let graph = EdgeHarvest::new(knn_edges(&source, 2));
+
+    let result = chutoro(2)
+        .cluster_from_knn_graph(source.len(), graph)
+        .expect("graph must cluster");
+
+    let connectivity = result.connectivity().expect("connectivity is reported");
+    assert_eq!(connectivity.component_sizes(), &[4, 4]);
+    assert_eq!(result.assignments().len(), 8);
+}
+
+#[rstest]
+#[case::outside_graph(CandidateEdge::new(0, 8, 1.0, 0))]
+#[case::nan(CandidateEdge::new(0, 1, f32::NAN, 0))]
+#[case::negative(CandidateEdge::new(0, 1, -1.0, 0))]
+fn rejects_malformed_edges(#[case] edge: CandidateEdge) {
+    let err = chutoro(2)
+        .cluster_from_knn_graph(8, EdgeHarvest::new(vec![edge]))
+        .expect_err("malformed edge must be rejected");
+
+    assert!(
+        matches!(err, ChutoroError::InvalidKnnGraph { .. }),
+        "{err:?}"
+    );
+    assert_eq!(err.code().as_str(), "CHUTORO_INVALID_KNN_GRAPH");
+}
+
+#[rstest]
+#[case::empty(0)]
+#[case::undersized(2)]
+fn rejects_graphs_smaller_than_a_cluster(#[case] node_count: usize) {
+    let err = chutoro(3)
+        .cluster_from_knn_graph(node_count, EdgeHarvest::new(Vec::new()))
+        .expect_err("small graphs must be rejected");
+
+    assert!(matches!(
+        err,
+        ChutoroError::EmptySource { .. } | ChutoroError::InsufficientItems { .. }
+    ));
+}
@@ -0,0 +1,47 @@
+//! Tests for capping the size of clusters selected from the condensed tree.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use std::{collections::HashMap, num::NonZeroUsize};
+
+use chutoro_core::ChutoroBuilder;
+use common::Dummy;
+use rstest::rstest;
+
+/// Two nearby groups of ten points and a distant third group.
+fn groups() -> Dummy {
+    let near = (0..10).map(|point| point as f32 * 0.1);
+    let beside = (0..10).map(|point| 1.5 + point as f32 * 0.1);
+    let far = (0..10).map(|point| 100.0 + point as f32 * 0.1);
+    Dummy::new(near.chain(beside).chain(far).collect())
+}
+
+/// Returns the size of every non-noise cluster in a run.
+fn cluster_sizes(builder: ChutoroBuilder) -> Vec<usize> {
+    let result = builder
+        .with_min_cluster_size(5)
+        .build()
+        .expect("configuration must be valid")
+        .run(&groups())
+        .expect("run must succeed");
+    let mut sizes = HashMap::new();
+    for &label in result.assignments() {
+        if Some(label) != result.noise_label() {
+            *sizes.entry(label).or_insert(0) += 1;
+        }
+    }
+    let mut sizes: Vec<_> = sizes.into_values().collect();
+    sizes.sort_unstable();
+    sizes
+}
+
+#[rstest]
+fn oversized_clusters_split_into_their_children() {
+    let cap = NonZeroUsize::new(10).expect("literal is non-zero");
+    let builder = ChutoroBuilder::new().with_max_cluster_size(cap);
+    assert_eq!(builder.max_cluster_size(), Some(cap));
+
+    assert_eq!(cluster_sizes(ChutoroBuilder::new()), [10, 20]);
+    assert_eq!(cluster_sizes(builder), [10, 10, 10]);
+}
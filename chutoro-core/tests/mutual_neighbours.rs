@@ -0,0 +1,57 @@
+//! Tests for mutual-neighbour filtering of harvested candidate edges.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use chutoro_core::{ChutoroBuilder, StageArtefact};
+use common::Dummy;
+use rstest::rstest;
+
+/// Three well-separated groups of twenty points.
+fn groups() -> Dummy {
+    Dummy::new(
+        (0..60)
+            .map(|point| (point % 3) as f32 * 100.0 + (point / 3) as f32 * 0.5)
+            .collect(),
+    )
+}
+
+/// Returns the weighted harvest size and cluster count of a run.
+fn harvest_len(builder: ChutoroBuilder) -> (usize, usize) {
+    let harvested = Arc::new(AtomicUsize::new(0));
+    let sink = Arc::clone(&harvested);
+    let result = builder
+        .with_min_cluster_size(5)
+        .with_connect_components(true)
+        .on_stage_complete(move |artefact: StageArtefact<'_>| {
+            if let StageArtefact::Harvest(harvest) = artefact {
+                sink.store(harvest.len(), Ordering::Relaxed);
+            }
+        })
+        .build()
+        .expect("configuration must be valid")
+        .run(&groups())
+        .expect("run must succeed");
+    (harvested.load(Ordering::Relaxed), result.cluster_count())
+}
+
+#[rstest]
+fn mutual_filter_thins_the_harvest_and_keeps_the_groups() {
+    let k = NonZeroUsize::new(5).expect("literal is non-zero");
+    let builder = ChutoroBuilder::new().with_mutual_neighbours(k);
+    assert_eq!(builder.mutual_neighbours(), Some(k));
+
+    let (unfiltered, unfiltered_clusters) = harvest_len(ChutoroBuilder::new());
+    let (filtered, filtered_clusters) = harvest_len(builder);
+
+    assert!(filtered < unfiltered, "{filtered} >= {unfiltered}");
+    assert_eq!((unfiltered_clusters, filtered_clusters), (3, 3));
+}
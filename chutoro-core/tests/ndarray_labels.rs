@@ -0,0 +1,37 @@
+//! Tests for exporting cluster labels as `ndarray` vectors.
+#![cfg(all(feature = "ndarray", feature = "cpu"))]
+
+mod common;
+
+use chutoro_core::{ChutoroBuilder, ClusterId, ClusteringResult};
+use common::Dummy;
+use rstest::rstest;
+
+#[rstest]
+fn labels_follow_insertion_order() {
+    let result =
+        ClusteringResult::from_assignments([2, 0, 1, 0].into_iter().map(ClusterId::new).collect());
+
+    assert_eq!(result.labels_as_array1(), ndarray::array![2, 0, 1, 0]);
+}
+
+#[rstest]
+fn labels_match_the_assignments_of_a_run() {
+    let chutoro = ChutoroBuilder::new()
+        .with_min_cluster_size(2)
+        .build()
+        .expect("configuration must be valid");
+    let result = chutoro
+        .run(&Dummy::new(vec![0.0, 0.1, 0.2, 9.0, 9.1, 9.2]))
+        .expect("run must succeed");
+
+    let labels = result.labels_as_array1();
+
+    assert_eq!(labels.len(), result.assignments().len());
+    assert!(
+        labels
+            .iter()
+            .zip(result.assignments())
+            .all(|(&label, id)| label == id.get())
+    );
+}
@@ -0,0 +1,97 @@
+//! Tests for reassigning noise points to nearby clusters.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use chutoro_core::{
+    ChutoroBuilder, ChutoroError, ClusteringResult, NoiseReassignmentReport, ReassignPolicy,
+};
+use common::Dummy;
+use rstest::{fixture, rstest};
+
+/// Index of the outlier 30 units before the first group.
+const NEAR_OUTLIER: usize = 30;
+/// Index of the outlier far from both groups.
+const FAR_OUTLIER: usize = 31;
+
+/// Two tight groups of fifteen points, an outlier 30 units before the first
+/// group, and an outlier far from everything. Both outliers leave the
+/// hierarchy before the groups split, so neither joins a group.
+#[fixture]
+fn groups() -> Dummy {
+    let near = (0..15).map(|i| i as f32 * 0.1);
+    let far = (0..15).map(|i| 10.0 + i as f32 * 0.1);
+    let outliers = [-30.0, 500.0];
+    Dummy::new(near.chain(far).chain(outliers).collect())
+}
+
+fn cluster(builder: ChutoroBuilder, source: &Dummy) -> ClusteringResult {
+    builder
+        .with_min_cluster_size(5)
+        .build()
+        .expect("configuration must be valid")
+        .run(source)
+        .expect("run must succeed")
+}
+
+fn nearest_cluster(max_distance: f32) -> ChutoroBuilder {
+    ChutoroBuilder::new().reassign_noise(ReassignPolicy::NearestCluster { max_distance })
+}
+
+#[rstest]
+fn outliers_are_noise_without_reassignment(groups: Dummy) {
+    let result = cluster(ChutoroBuilder::new(), &groups);
+
+    let noise = result.noise_label().expect("outliers are noise");
+    assert_eq!(result.assignments()[NEAR_OUTLIER], noise);
+    assert_eq!(result.assignments()[FAR_OUTLIER], noise);
+    assert!(result.noise_reassignment().is_none());
+}
+
+#[rstest]
+fn only_noise_within_the_threshold_is_reassigned(groups: Dummy) {
+    let baseline = cluster(ChutoroBuilder::new(), &groups);
+
+    let result = cluster(nearest_cluster(50.0), &groups);
+
+    let labels = result.assignments();
+    assert_eq!(labels[NEAR_OUTLIER], labels[0]);
+    assert_eq!(result.noise_label(), baseline.noise_label());
+    assert_eq!(labels[FAR_OUTLIER], baseline.assignments()[FAR_OUTLIER]);
+    let report = result
+        .noise_reassignment()
+        .expect("reassignment is reported");
+    assert_eq!(report.noise_points(), baseline.noise_count());
+    assert_eq!(report.remaining(), result.noise_count());
+    assert!(report.reassigned() >= 1);
+}
+
+#[rstest]
+fn reassigning_every_noise_point_retires_the_noise_label(groups: Dummy) {
+    let baseline = cluster(ChutoroBuilder::new(), &groups);
+
+    let result = cluster(nearest_cluster(f32::INFINITY), &groups);
+
+    assert_eq!(result.noise_label(), None);
+    assert_eq!(result.noise_count(), 0);
+    assert_eq!(result.cluster_count(), baseline.cluster_count() - 1);
+    assert_eq!(
+        result.noise_reassignment(),
+        Some(NoiseReassignmentReport::new(
+            baseline.noise_count(),
+            baseline.noise_count()
+        ))
+    );
+}
+
+#[rstest]
+#[case::negative(-1.0)]
+#[case::nan(f32::NAN)]
+fn invalid_thresholds_are_rejected(#[case] max_distance: f32) {
+    let err = nearest_cluster(max_distance)
+        .build()
+        .expect_err("threshold must be rejected");
+
+    assert!(matches!(err, ChutoroError::InvalidReassignPolicy { .. }));
+    assert_eq!(err.code().as_str(), "CHUTORO_INVALID_REASSIGN_POLICY");
+}
@@ -0,0 +1,169 @@
+//! Tests for `OnlineClusterer`, the micro-cluster stream summariser.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use std::{num::NonZeroUsize, sync::Arc};
+
+use chutoro_core::{
+    ChutoroBuilder, ChutoroError, ExecutionStrategy, OnlineClusterer, SessionRefreshPolicy,
+};
+use common::Dummy;
+use rstest::rstest;
+
+/// Two groups of 30 evenly spaced points, 100 apart.
+fn two_groups() -> Arc<Dummy> {
+    let near = (0..30).map(|i| i as f32 * 0.4);
+    let far = (0..30).map(|i| 100.0 + i as f32 * 0.4);
+    Arc::new(Dummy::new(near.chain(far).collect()))
+}
+
+fn online(source: Arc<Dummy>, radius: f32, every: Option<usize>) -> OnlineClusterer<Dummy> {
+    let policy =
+        SessionRefreshPolicy::manual().with_refresh_every_n(every.and_then(NonZeroUsize::new));
+    ChutoroBuilder::new()
+        .with_min_cluster_size(3)
+        .with_session_refresh_policy(policy)
+        .build_online(source, radius)
+        .expect("configuration must be valid")
+}
+
+#[rstest]
+fn nearby_points_join_the_nearest_micro_cluster() {
+    let source = Arc::new(Dummy::new(vec![0.0, 0.25, 5.0, -0.5, 5.5]));
+    let mut clusterer = online(source, 0.5, None);
+
+    for point in 0..5 {
+        clusterer.insert(point).expect("insert must succeed");
+    }
+
+    let summaries: Vec<_> = clusterer
+        .micro_clusters()
+        .iter()
+        .map(|micro| (micro.centre(), micro.weight(), micro.radius()))
+        .collect();
+    assert_eq!(summaries, [(0, 3, 0.5), (2, 2, 0.5)]);
+    assert_eq!(clusterer.point_count(), 5);
+    assert_eq!(clusterer.snapshot_version(), 0, "no refresh was requested");
+}
+
+#[rstest]
+fn refreshes_separate_the_groups() {
+    let mut clusterer = online(two_groups(), 0.3, None);
+    for point in 0..60 {
+        clusterer.insert(point).expect("insert must succeed");
+    }
+
+    clusterer.refresh().expect("refresh must succeed");
+
+    assert_eq!(clusterer.micro_clusters().len(), 60);
+    let labels = clusterer.snapshot_labels();
+    assert!(labels[..30].iter().all(|label| *label == labels[0]));
+    assert!(labels[30..].iter().all(|label| *label == labels[30]));
+    assert!(labels[0].is_some());
+    assert_ne!(labels[0], labels[30]);
+}
+
+#[rstest]
+fn absorbed_points_inherit_their_micro_cluster_label() {
+    let mut clusterer = online(two_groups(), 1.0, None);
+    for point in 0..60 {
+        clusterer.insert(point).expect("insert must succeed");
+    }
+
+    clusterer.refresh().expect("refresh must succeed");
+
+    assert!(clusterer.micro_clusters().len() < 60);
+    assert!(
+        clusterer
+            .micro_clusters()
+            .iter()
+            .all(|micro| micro.radius() <= 1.0)
+    );
+    let labels = clusterer.snapshot_labels();
+    assert!(labels[..30].iter().all(|label| *label == labels[0]));
+    assert_ne!(labels[0], labels[30]);
+}
+
+#[rstest]
+fn refreshes_follow_the_session_policy() {
+    let mut clusterer = online(two_groups(), 0.5, Some(20));
+
+    for point in 0..45 {
+        clusterer.insert(point).expect("insert must succeed");
+    }
+
+    assert_eq!(clusterer.snapshot_version(), 2);
+    let labels = clusterer.snapshot_labels();
+    assert!(labels[..40].iter().any(Option::is_some));
+    assert!(
+        labels[40..].iter().all(Option::is_none),
+        "later points wait for the next refresh"
+    );
+}
+
+#[rstest]
+fn too_few_micro_clusters_publish_noise() {
+    let mut clusterer = online(two_groups(), 50.0, None);
+    for point in 0..60 {
+        clusterer.insert(point).expect("insert must succeed");
+    }
+
+    clusterer.refresh().expect("refresh must succeed");
+
+    assert_eq!(clusterer.micro_clusters().len(), 2);
+    assert_eq!(clusterer.snapshot_version(), 1);
+    assert!(clusterer.snapshot_labels().iter().all(Option::is_none));
+}
+
+#[rstest]
+fn snapshots_are_unaffected_by_later_refreshes() {
+    let mut clusterer = online(two_groups(), 0.5, None);
+    for point in 0..60 {
+        clusterer.insert(point).expect("insert must succeed");
+    }
+    let before = clusterer.snapshot_labels();
+
+    clusterer.refresh().expect("refresh must succeed");
+
+    assert!(before.iter().all(Option::is_none));
+    assert!(clusterer.snapshot_labels().iter().any(Option::is_some));
+}
+
+#[rstest]
+#[case::negative(-1.0)]
+#[case::nan(f32::NAN)]
+#[case::infinite(f32::INFINITY)]
+fn invalid_radii_are_rejected(#[case] radius: f32) {
+    let Err(err) = ChutoroBuilder::new().build_online(two_groups(), radius) else {
+        panic!("radius {radius} must be rejected");
+    };
+
+    assert!(matches!(err, ChutoroError::InvalidOnlineConfig { .. }));
+    assert_eq!(err.code().as_str(), "CHUTORO_INVALID_ONLINE_CONFIG");
+}
+
+#[rstest]
+fn gpu_strategies_are_rejected() {
+    let Err(err) = ChutoroBuilder::new()
+        .with_execution_strategy(ExecutionStrategy::GpuPreferred)
+        .build_online(two_groups(), 0.5)
+    else {
+        panic!("online clustering is CPU-only");
+    };
+
+    assert!(matches!(err, ChutoroError::BackendUnavailable { .. }));
+}
+
+#[rstest]
+fn out_of_range_and_repeated_points_are_rejected() {
+    let mut clusterer = online(two_groups(), 0.5, None);
+    clusterer.insert(0).expect("insert must succeed");
+
+    let out_of_range = clusterer.insert(60).expect_err("point 60 does not exist");
+    let repeated = clusterer.insert(0).expect_err("point 0 was inserted");
+
+    assert!(matches!(out_of_range, ChutoroError::DataSource { .. }));
+    assert!(matches!(repeated, ChutoroError::CpuHnswFailure { .. }));
+    assert_eq!(clusterer.point_count(), 1);
+}
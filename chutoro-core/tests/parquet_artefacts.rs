@@ -0,0 +1,185 @@
+//! Tests for the versioned Parquet files of harvests and spanning forests.
+#![cfg(feature = "parquet")]
+
+use std::{collections::HashMap, fs::File, path::Path, sync::Arc};
+
+use arrow_array::{ArrayRef, Float64Array, RecordBatch, UInt64Array};
+use arrow_schema::{DataType, Field, Schema};
+use chutoro_core::{
+    CandidateEdge, EdgeHarvest, MinimumSpanningForest, PARQUET_SCHEMA_VERSION,
+    ParquetArtefactError, parallel_kruskal,
+};
+use parquet::arrow::ArrowWriter;
+use rstest::{fixture, rstest};
+use tempfile::TempDir;
+
+#[fixture]
+fn dir() -> TempDir {
+    tempfile::tempdir().expect("temporary directory must be created")
+}
+
+/// Two components, a self-loop, and a duplicate edge.
+fn harvest() -> EdgeHarvest {
+    EdgeHarvest::new(vec![
+        CandidateEdge::new_f64(0, 1, 0.1 + 0.2, 0),
+        CandidateEdge::new(1, 2, 2.0, 1),
+        CandidateEdge::new(2, 0, 3.0, 2),
+        CandidateEdge::new(3, 3, 0.0, 3),
+        CandidateEdge::new(4, 5, 1.5, 4),
+        CandidateEdge::new(5, 4, 1.5, 5),
+    ])
+}
+
+/// Writes a raw edge table with the given metadata and columns.
+fn write_raw(path: &Path, metadata: &[(&str, &str)], fields: Vec<Field>, columns: Vec<ArrayRef>) {
+    let metadata: HashMap<String, String> = metadata
+        .iter()
+        .map(|&(key, value)| (key.to_owned(), value.to_owned()))
+        .collect();
+    let schema = Arc::new(Schema::new_with_metadata(fields, metadata));
+    let batch = RecordBatch::try_new(Arc::clone(&schema), columns).expect("batch must be valid");
+    let file = File::create(path).expect("file must be created");
+    let mut writer = ArrowWriter::try_new(file, schema, None).expect("writer must open");
+    writer.write(&batch).expect("batch must be written");
+    writer.close().expect("writer must close");
+}
+
+fn id_field(name: &str) -> Field {
+    Field::new(name, DataType::UInt64, false)
+}
+
+fn ids(values: &[u64]) -> ArrayRef {
+    Arc::new(UInt64Array::from(values.to_vec()))
+}
+
+#[rstest]
+fn harvests_round_trip_with_full_precision(dir: TempDir) {
+    let path = dir.path().join("harvest.parquet");
+    let harvest = harvest();
+
+    harvest
+        .write_parquet(&path)
+        .expect("harvest must be written");
+    let read = EdgeHarvest::read_parquet(&path).expect("harvest must be read");
+
+    assert_eq!(read, harvest);
+    assert_eq!(
+        read.iter().next().map(CandidateEdge::distance_f64),
+        Some(0.1 + 0.2)
+    );
+}
+
+#[rstest]
+fn empty_harvests_round_trip(dir: TempDir) {
+    let path = dir.path().join("empty.parquet");
+
+    EdgeHarvest::default()
+        .write_parquet(&path)
+        .expect("harvest must be written");
+
+    assert!(
+        EdgeHarvest::read_parquet(&path)
+            .expect("harvest must be read")
+            .is_empty()
+    );
+}
+
+#[rstest]
+fn forests_round_trip_with_their_diagnostics(dir: TempDir) {
+    let path = dir.path().join("forest.parquet");
+    let forest = parallel_kruskal(6, &harvest()).expect("forest must build");
+    assert_eq!(forest.component_count(), 3);
+
+    forest.write_parquet(&path).expect("forest must be written");
+    let read = MinimumSpanningForest::read_parquet(&path).expect("forest must be read");
+
+    assert_eq!(read, forest);
+    assert_eq!(read.diagnostics().self_edges_dropped(), 1);
+    assert_eq!(read.diagnostics().duplicate_edges_merged(), 1);
+}
+
+#[rstest]
+fn readers_reject_other_artefacts(dir: TempDir) {
+    let path = dir.path().join("forest.parquet");
+    parallel_kruskal(6, &harvest())
+        .expect("forest must build")
+        .write_parquet(&path)
+        .expect("forest must be written");
+
+    let err = EdgeHarvest::read_parquet(&path).expect_err("a forest is not a harvest");
+
+    assert!(matches!(
+        err,
+        ParquetArtefactError::WrongArtefact { expected: "edge_harvest", ref found }
+            if found == "minimum_spanning_forest"
+    ));
+}
+
+#[rstest]
+fn readers_reject_newer_schema_versions(dir: TempDir) {
+    let path = dir.path().join("future.parquet");
+    let newer = (PARQUET_SCHEMA_VERSION + 1).to_string();
+    write_raw(
+        &path,
+        &[
+            ("chutoro.artefact", "edge_harvest"),
+            ("chutoro.schema_version", &newer),
+        ],
+        vec![
+            id_field("source"),
+            id_field("target"),
+            Field::new("distance", DataType::Float64, false),
+            id_field("sequence"),
+        ],
+        vec![
+            ids(&[0]),
+            ids(&[1]),
+            Arc::new(Float64Array::from(vec![1.0])),
+            ids(&[0]),
+        ],
+    );
+
+    let err = EdgeHarvest::read_parquet(&path).expect_err("the version is newer");
+
+    assert!(
+        matches!(err, ParquetArtefactError::UnsupportedVersion { ref found, .. } if *found == newer)
+    );
+}
+
+#[rstest]
+fn readers_reject_unexpected_columns(dir: TempDir) {
+    let path = dir.path().join("columns.parquet");
+    write_raw(
+        &path,
+        &[
+            ("chutoro.artefact", "edge_harvest"),
+            ("chutoro.schema_version", "1"),
+        ],
+        vec![id_field("source"), id_field("target")],
+        vec![ids(&[0]), ids(&[1])],
+    );
+
+    let err = EdgeHarvest::read_parquet(&path).expect_err("columns are missing");
+
+    assert!(matches!(err, ParquetArtefactError::Malformed { .. }));
+}
+
+#[rstest]
+fn readers_reject_non_finite_distances(dir: TempDir) {
+    let path = dir.path().join("nan.parquet");
+    EdgeHarvest::new(vec![CandidateEdge::new(0, 1, f32::NAN, 0)])
+        .write_parquet(&path)
+        .expect("harvest must be written");
+
+    let err = EdgeHarvest::read_parquet(&path).expect_err("the distance is NaN");
+
+    assert!(matches!(err, ParquetArtefactError::Malformed { .. }));
+}
+
+#[rstest]
+fn missing_files_report_io_errors(dir: TempDir) {
+    let err = MinimumSpanningForest::read_parquet(dir.path().join("missing.parquet"))
+        .expect_err("the file does not exist");
+
+    assert!(matches!(err, ParquetArtefactError::Io(_)));
+}
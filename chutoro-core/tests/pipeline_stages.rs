@@ -0,0 +1,152 @@
+//! Tests for substituting individual CPU pipeline stages via the builder.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use chutoro_core::{
+    ChutoroBuilder, ChutoroError, ClusterId, ClusteringResult, CpuHnsw, DefaultHarvestStage,
+    DefaultIndexStage, EdgeHarvest, HarvestStage, HierarchyStage, IndexStage, MstEdge, MstStage,
+    Result, SampleSpec, StageContext, WeightedHarvest,
+};
+use common::Dummy;
+use rstest::{fixture, rstest};
+
+/// Two well-separated groups of 12 points each.
+#[fixture]
+fn groups() -> Dummy {
+    let near = (0..12).map(|i| i as f32 * 0.1);
+    let far = (0..12).map(|i| 50.0 + i as f32 * 0.1);
+    Dummy::new(near.chain(far).collect())
+}
+
+/// Counts calls before delegating to the built-in stage.
+#[derive(Debug, Default)]
+struct Counting(Arc<AtomicUsize>);
+
+impl IndexStage for Counting {
+    fn build(&self, context: &StageContext<'_>) -> Result<(CpuHnsw, EdgeHarvest)> {
+        self.0.fetch_add(1, Ordering::Relaxed);
+        DefaultIndexStage.build(context)
+    }
+}
+
+impl HarvestStage for Counting {
+    fn weight(
+        &self,
+        context: &StageContext<'_>,
+        index: &CpuHnsw,
+        harvest: &EdgeHarvest,
+    ) -> Result<WeightedHarvest> {
+        self.0.fetch_add(1, Ordering::Relaxed);
+        DefaultHarvestStage.weight(context, index, harvest)
+    }
+}
+
+/// Ignores the harvest and returns a fixed forest.
+#[derive(Debug)]
+struct Precomputed(Vec<MstEdge>);
+
+impl MstStage for Precomputed {
+    fn spanning_forest(
+        &self,
+        _context: &StageContext<'_>,
+        _edges: EdgeHarvest,
+    ) -> Result<Vec<MstEdge>> {
+        Ok(self.0.clone())
+    }
+}
+
+/// Labels only the first point, which the pipeline must reject.
+#[derive(Debug)]
+struct Truncated;
+
+impl HierarchyStage for Truncated {
+    fn extract(&self, _context: &StageContext<'_>, _edges: &[MstEdge]) -> Result<ClusteringResult> {
+        Ok(ClusteringResult::from_assignments(vec![ClusterId::new(0)]))
+    }
+}
+
+fn cluster(builder: ChutoroBuilder, source: &Dummy) -> Result<ClusteringResult> {
+    builder
+        .with_min_cluster_size(3)
+        .build()
+        .expect("configuration must be valid")
+        .run(source)
+}
+
+#[rstest]
+fn wrapped_default_stages_match_the_built_in_pipeline(groups: Dummy) {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let builder = ChutoroBuilder::new()
+        .with_index_stage(Counting(Arc::clone(&calls)))
+        .with_harvest_stage(Counting(Arc::clone(&calls)));
+
+    let custom = cluster(builder, &groups).expect("custom run must succeed");
+    let built_in = cluster(ChutoroBuilder::new(), &groups).expect("default run must succeed");
+
+    assert_eq!(calls.load(Ordering::Relaxed), 2);
+    assert_eq!(custom.assignments(), built_in.assignments());
+}
+
+#[rstest]
+fn precomputed_forest_replaces_kruskal(groups: Dummy) {
+    // A chain within each group, weighted so the groups split cleanly.
+    let forest = (0..11)
+        .chain(12..23)
+        .map(|left| MstEdge::new(left, left + 1, 0.1, left as u64))
+        .chain([MstEdge::new(11, 12, 50.0, 23)])
+        .collect();
+
+    let result = cluster(
+        ChutoroBuilder::new().with_mst_stage(Precomputed(forest)),
+        &groups,
+    )
+    .expect("precomputed forest must cluster");
+
+    let (near, far) = result.assignments().split_at(12);
+    assert!(near.iter().all(|&label| label == near[0]));
+    assert!(far.iter().all(|&label| label == far[0]));
+    assert_ne!(near[0], far[0]);
+}
+
+#[rstest]
+fn forests_referencing_unknown_points_are_rejected(groups: Dummy) {
+    let builder =
+        ChutoroBuilder::new().with_mst_stage(Precomputed(vec![MstEdge::new(0, 99, 1.0, 0)]));
+
+    let err = cluster(builder, &groups).expect_err("out-of-range forest must fail");
+
+    assert!(matches!(err, ChutoroError::InvalidStageOutput { ref stage, .. } if &**stage == "mst"));
+    assert_eq!(err.code().as_str(), "CHUTORO_INVALID_STAGE_OUTPUT");
+}
+
+#[rstest]
+fn hierarchy_output_must_label_every_point(groups: Dummy) {
+    let err = cluster(
+        ChutoroBuilder::new().with_hierarchy_stage(Truncated),
+        &groups,
+    )
+    .expect_err("partial labelling must fail");
+
+    assert!(
+        matches!(err, ChutoroError::InvalidStageOutput { ref stage, .. } if &**stage == "hierarchy")
+    );
+}
+
+#[rstest]
+fn sampled_runs_index_the_sample_with_the_custom_stage(groups: Dummy) {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let builder = ChutoroBuilder::new()
+        .with_index_stage(Counting(Arc::clone(&calls)))
+        .with_sample(SampleSpec::Fraction(0.5), 7);
+
+    let result = cluster(builder, &groups).expect("sampled run must succeed");
+
+    assert_eq!(calls.load(Ordering::Relaxed), 1);
+    assert_eq!(result.assignments().len(), 24);
+}
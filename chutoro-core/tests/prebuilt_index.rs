@@ -0,0 +1,79 @@
+//! Tests for clustering over an application-owned prebuilt HNSW index.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use std::sync::Arc;
+
+use chutoro_core::{CandidateEdge, ChutoroBuilder, ChutoroError, CpuHnsw, EdgeHarvest, HnswParams};
+use common::Dummy;
+use rstest::{fixture, rstest};
+
+#[fixture]
+fn source() -> Dummy {
+    Dummy::new(vec![0.0, 1.0, 2.0, 3.0, 20.0, 21.0, 22.0, 23.0])
+}
+
+fn prebuilt(source: &Dummy) -> (Arc<CpuHnsw>, EdgeHarvest) {
+    let (index, harvest) =
+        CpuHnsw::build_with_edges(source, HnswParams::default()).expect("index must build");
+    (Arc::new(index), harvest)
+}
+
+#[rstest]
+fn prebuilt_index_matches_fresh_run(source: Dummy) {
+    let (index, harvest) = prebuilt(&source);
+    let reused = ChutoroBuilder::new()
+        .with_min_cluster_size(2)
+        .with_prebuilt_index(Arc::clone(&index), harvest)
+        .build()
+        .expect("prebuilt configuration must be valid");
+    assert!(reused.prebuilt_index().is_some());
+
+    let fresh = ChutoroBuilder::new()
+        .with_min_cluster_size(2)
+        .build()
+        .expect("configuration must be valid");
+
+    let reused_result = reused.run(&source).expect("prebuilt run must succeed");
+    let fresh_result = fresh.run(&source).expect("fresh run must succeed");
+    assert_eq!(reused_result.assignments(), fresh_result.assignments());
+    assert_eq!(Arc::strong_count(&index), 2);
+}
+
+#[rstest]
+fn build_rejects_params_changed_after_adopting_index(source: Dummy) {
+    let (index, harvest) = prebuilt(&source);
+    let err = ChutoroBuilder::new()
+        .with_prebuilt_index(index, harvest)
+        .with_hnsw_params(HnswParams::new(4, 8).expect("params must be valid"))
+        .build()
+        .expect_err("mismatched params must be rejected");
+    assert!(matches!(err, ChutoroError::PrebuiltIndexMismatch { .. }));
+    assert_eq!(err.code().as_str(), "CHUTORO_PREBUILT_INDEX_MISMATCH");
+}
+
+#[rstest]
+fn build_rejects_harvest_outside_index(source: Dummy) {
+    let (index, _) = prebuilt(&source);
+    let harvest = EdgeHarvest::new(vec![CandidateEdge::new(0, 99, 1.0, 0)]);
+    let err = ChutoroBuilder::new()
+        .with_prebuilt_index(index, harvest)
+        .build()
+        .expect_err("out-of-range harvest must be rejected");
+    assert!(matches!(err, ChutoroError::PrebuiltIndexMismatch { .. }));
+}
+
+#[rstest]
+fn run_rejects_source_length_mismatch(source: Dummy) {
+    let (index, harvest) = prebuilt(&source);
+    let chutoro = ChutoroBuilder::new()
+        .with_min_cluster_size(2)
+        .with_prebuilt_index(index, harvest)
+        .build()
+        .expect("prebuilt configuration must be valid");
+    let err = chutoro
+        .run(&Dummy::new(vec![0.0, 1.0, 2.0]))
+        .expect_err("length mismatch must be rejected");
+    assert!(matches!(err, ChutoroError::PrebuiltIndexMismatch { .. }));
+}
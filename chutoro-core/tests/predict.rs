@@ -0,0 +1,76 @@
+//! Tests for predicting labels of new points against a finished clustering.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use std::sync::Arc;
+
+use chutoro_core::{Chutoro, ChutoroBuilder, ChutoroError, ClusteringResult, CpuHnsw, HnswParams};
+use common::Dummy;
+use rstest::rstest;
+
+const TRAINING: [f32; 8] = [0.0, 1.0, 2.0, 3.0, 20.0, 21.0, 22.0, 23.0];
+
+/// Appends `queries` behind the training rows.
+fn with_queries(queries: &[f32]) -> Dummy {
+    Dummy::new(TRAINING.iter().chain(queries).copied().collect())
+}
+
+fn fit() -> (Chutoro, ClusteringResult) {
+    let training = Dummy::new(TRAINING.to_vec());
+    let (index, harvest) =
+        CpuHnsw::build_with_edges(&training, HnswParams::default()).expect("index must build");
+    let chutoro = ChutoroBuilder::new()
+        .with_min_cluster_size(2)
+        .with_prebuilt_index(Arc::new(index), harvest)
+        .build()
+        .expect("configuration must be valid");
+    let clustering = chutoro.run(&training).expect("training run must succeed");
+    (chutoro, clustering)
+}
+
+#[rstest]
+fn queries_adopt_the_label_of_their_nearest_training_point() {
+    let (chutoro, clustering) = fit();
+    let source = with_queries(&[1.5, 21.5]);
+
+    let predictions = chutoro
+        .predict(&source, &clustering, 8..10)
+        .expect("prediction must succeed");
+
+    let labels = clustering.assignments();
+    assert_eq!(predictions[0].label(), Some(labels[1]));
+    assert_eq!(predictions[1].label(), Some(labels[5]));
+    assert!(predictions.iter().all(|p| p.strength() > 0.0));
+}
+
+#[rstest]
+fn later_batches_reuse_query_rows_without_stale_distances() {
+    let (chutoro, clustering) = fit();
+
+    let first = chutoro
+        .predict(&with_queries(&[0.5]), &clustering, 8..9)
+        .expect("first batch must succeed");
+    let second = chutoro
+        .predict(&with_queries(&[22.5]), &clustering, 8..9)
+        .expect("second batch must succeed");
+
+    assert_eq!(first[0].label(), Some(clustering.assignments()[0]));
+    assert_eq!(second[0].label(), Some(clustering.assignments()[7]));
+}
+
+#[rstest]
+fn prediction_requires_the_index_that_clustered_the_rows() {
+    let training = Dummy::new(TRAINING.to_vec());
+    let chutoro = ChutoroBuilder::new()
+        .with_min_cluster_size(2)
+        .build()
+        .expect("configuration must be valid");
+    let clustering = chutoro.run(&training).expect("training run must succeed");
+
+    let err = chutoro
+        .predict(&with_queries(&[1.5]), &clustering, 8..9)
+        .expect_err("prediction without an index must fail");
+
+    assert!(matches!(err, ChutoroError::PrebuiltIndexMismatch { .. }));
+}
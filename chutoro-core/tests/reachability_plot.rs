@@ -0,0 +1,128 @@
+//! Tests for OPTICS-style reachability plots built from MST edges.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use chutoro_core::{
+    ChutoroBuilder, HierarchyError, MstEdge, ReachabilityPlot, StageArtefact, reachability_plot,
+};
+use common::Dummy;
+use rstest::rstest;
+
+fn reachabilities(plot: &ReachabilityPlot) -> Vec<f32> {
+    plot.points().iter().map(|p| p.reachability()).collect()
+}
+
+fn order(plot: &ReachabilityPlot) -> Vec<usize> {
+    plot.points().iter().map(|p| p.point()).collect()
+}
+
+#[rstest]
+fn walks_the_lightest_edge_first() {
+    // A star centred on 0: the walk takes the cheap spokes before the dear.
+    let edges = [
+        MstEdge::new(0, 3, 3.0, 0),
+        MstEdge::new(0, 1, 1.0, 1),
+        MstEdge::new(0, 2, 2.0, 2),
+        MstEdge::new(3, 4, 0.5, 3),
+    ];
+
+    let plot = reachability_plot(5, &edges).expect("edges are valid");
+
+    assert_eq!(order(&plot), [0, 1, 2, 3, 4]);
+    assert_eq!(reachabilities(&plot), [f32::INFINITY, 1.0, 2.0, 3.0, 0.5]);
+}
+
+#[rstest]
+fn ties_break_by_point_index() {
+    let edges = [MstEdge::new(0, 2, 1.0, 0), MstEdge::new(0, 1, 1.0, 1)];
+
+    let plot = reachability_plot(3, &edges).expect("edges are valid");
+
+    assert_eq!(order(&plot), [0, 1, 2]);
+}
+
+#[rstest]
+fn components_start_with_infinite_reachability() {
+    let edges = [MstEdge::new(0, 2, 0.5, 0), MstEdge::new(1, 3, 0.25, 1)];
+
+    let plot = reachability_plot(5, &edges).expect("edges are valid");
+
+    assert_eq!(order(&plot), [0, 2, 1, 3, 4]);
+    assert_eq!(
+        reachabilities(&plot),
+        [f32::INFINITY, 0.5, f32::INFINITY, 0.25, f32::INFINITY]
+    );
+}
+
+#[rstest]
+#[case::empty(0, vec![], HierarchyError::EmptyDataset)]
+#[case::negative(
+    2,
+    vec![MstEdge::new(0, 1, -1.0, 0)],
+    HierarchyError::InvalidEdgeWeight { left: 0, right: 1, weight: -1.0 },
+)]
+fn invalid_input_is_rejected(
+    #[case] node_count: usize,
+    #[case] edges: Vec<MstEdge>,
+    #[case] expected: HierarchyError,
+) {
+    let err = reachability_plot(node_count, &edges).expect_err("input must be rejected");
+
+    assert_eq!(err.code(), expected.code());
+    assert_eq!(err.to_string(), expected.to_string());
+}
+
+#[rstest]
+fn csv_lists_points_in_order() {
+    let edges = [MstEdge::new(1, 0, 0.75, 0)];
+    let plot = reachability_plot(2, &edges).expect("edges are valid");
+
+    let mut csv = Vec::new();
+    plot.write_csv(&mut csv)
+        .expect("writing to a buffer succeeds");
+
+    assert_eq!(
+        String::from_utf8(csv).expect("CSV is UTF-8"),
+        "position,point,reachability\n0,0,inf\n1,1,0.75\n"
+    );
+}
+
+#[rstest]
+fn pipeline_clusters_form_valleys() {
+    let near = (0..10).map(|i| i as f32 * 0.1);
+    let far = (0..10).map(|i| 40.0 + i as f32 * 0.1);
+    let source = Dummy::new(near.chain(far).collect());
+    let mst = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&mst);
+    ChutoroBuilder::new()
+        .with_min_cluster_size(3)
+        .on_stage_complete(move |artefact: StageArtefact<'_>| {
+            if let StageArtefact::Mst(edges) = artefact {
+                sink.lock().expect("hook lock is healthy").extend(edges);
+            }
+        })
+        .build()
+        .expect("configuration must be valid")
+        .run(&source)
+        .expect("run must succeed");
+    let edges = mst.lock().expect("hook lock is healthy").clone();
+
+    let plot = reachability_plot(20, &edges).expect("MST edges are valid");
+
+    // Each group is contiguous, and only the jump between them is large.
+    let points = order(&plot);
+    let (first, second) = points.split_at(10);
+    assert!(first.iter().all(|&point| point < 10));
+    assert!(second.iter().all(|&point| point >= 10));
+    let distances = reachabilities(&plot);
+    assert!(distances[10] > 30.0);
+    assert!(
+        distances[1..10]
+            .iter()
+            .chain(&distances[11..])
+            .all(|&d| d < 1.0)
+    );
+}
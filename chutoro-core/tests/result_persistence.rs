@@ -0,0 +1,150 @@
+//! Tests for encoding and decoding clustering results.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use std::num::NonZeroUsize;
+
+use chutoro_core::{
+    ChutoroBuilder, ClusterId, ClusteringResult, DistancePolicy, EdgeBudget, HnswParams,
+    NonContiguousClusterIds, ParameterReport, ReassignPolicy, ResultDecodeError, SampleSpec,
+};
+use common::Dummy;
+use rstest::{fixture, rstest};
+
+/// Two well-separated groups of 12 points each.
+#[fixture]
+fn groups() -> Dummy {
+    let near = (0..12).map(|i| i as f32 * 0.1);
+    let far = (0..12).map(|i| 50.0 + i as f32 * 0.1);
+    Dummy::new(near.chain(far).collect())
+}
+
+fn cluster(builder: ChutoroBuilder, source: &Dummy) -> ClusteringResult {
+    builder
+        .with_min_cluster_size(3)
+        .build()
+        .expect("configuration must be valid")
+        .run(source)
+        .expect("run must succeed")
+}
+
+#[rstest]
+#[case::plain(ChutoroBuilder::new().with_seed(42))]
+#[case::sampled(ChutoroBuilder::new().with_sample(SampleSpec::Fraction(0.5), 7))]
+#[case::budgeted(
+    ChutoroBuilder::new()
+        .with_edge_budget(EdgeBudget::new(NonZeroUsize::new(40).expect("non-zero")))
+        .with_connect_components(true)
+        .with_distance_policy(DistancePolicy::ClampToMax)
+)]
+#[case::reassigned(
+    ChutoroBuilder::new().reassign_noise(ReassignPolicy::NearestCluster { max_distance: 1.0 })
+)]
+#[case::seed_labelled(
+    ChutoroBuilder::new().with_seed_labels(&[(0, ClusterId::new(0)), (1, ClusterId::new(1))])
+)]
+#[case::explained(ChutoroBuilder::new().with_edge_provenance(true))]
+fn pipeline_results_round_trip(groups: Dummy, #[case] builder: ChutoroBuilder) {
+    let result = cluster(builder, &groups);
+
+    let restored =
+        ClusteringResult::from_bytes(&result.to_bytes()).expect("encoded results decode");
+
+    assert_eq!(restored, result);
+    assert_eq!(restored.timings(), result.timings());
+    assert!(restored.membership().is_some());
+    assert!(restored.distance_evaluations().is_some());
+    assert_eq!(restored.backend(), result.backend());
+    assert_eq!(restored.hierarchy(), result.hierarchy());
+    assert_eq!(restored.explained_edges(), result.explained_edges());
+}
+
+#[rstest]
+fn runs_record_their_parameters(groups: Dummy) {
+    let params = HnswParams::new(8, 24).expect("valid parameters");
+
+    let result = cluster(ChutoroBuilder::new().with_hnsw_params(params), &groups);
+
+    let min_cluster_size = NonZeroUsize::new(3).expect("non-zero");
+    assert_eq!(
+        result.parameters(),
+        Some(ParameterReport::new(min_cluster_size, 8, 24))
+    );
+}
+
+#[rstest]
+fn empty_results_round_trip() {
+    let result = ClusteringResult::from_assignments(Vec::new());
+
+    let restored = ClusteringResult::from_bytes(&result.to_bytes()).expect("empty result decodes");
+
+    assert_eq!(restored, result);
+}
+
+#[rstest]
+fn every_truncation_is_rejected(groups: Dummy) {
+    let bytes = cluster(ChutoroBuilder::new(), &groups).to_bytes();
+
+    for end in 0..bytes.len() {
+        let prefix = bytes.get(..end).expect("prefix is in bounds");
+        let error = ClusteringResult::from_bytes(prefix).expect_err("truncated bytes must fail");
+        assert!(
+            matches!(error, ResultDecodeError::Truncated { .. }),
+            "prefix of {end} bytes gave {error:?}"
+        );
+    }
+}
+
+#[rstest]
+fn decoding_rejects_foreign_bytes() {
+    let mut bytes = ClusteringResult::from_assignments(vec![ClusterId::new(0)]).to_bytes();
+
+    let mut wrong_magic = bytes.clone();
+    wrong_magic[0] = b'X';
+    assert_eq!(
+        ClusteringResult::from_bytes(&wrong_magic),
+        Err(ResultDecodeError::BadMagic)
+    );
+
+    let mut future = bytes.clone();
+    future[8] = 2;
+    assert_eq!(
+        ClusteringResult::from_bytes(&future),
+        Err(ResultDecodeError::UnsupportedVersion { version: 2 })
+    );
+
+    bytes.push(0);
+    assert_eq!(
+        ClusteringResult::from_bytes(&bytes),
+        Err(ResultDecodeError::TrailingBytes { count: 1 })
+    );
+}
+
+#[rstest]
+fn decoding_enforces_assignment_invariants() {
+    let mut bytes = ClusteringResult::from_assignments(vec![ClusterId::new(0)]).to_bytes();
+    // The single assignment follows the 10-byte header and 8-byte count.
+    bytes[18] = 3;
+
+    assert_eq!(
+        ClusteringResult::from_bytes(&bytes),
+        Err(ResultDecodeError::Assignments(
+            NonContiguousClusterIds::MissingZero
+        ))
+    );
+}
+
+#[rstest]
+fn decoding_rejects_unknown_presence_flags() {
+    let mut bytes = ClusteringResult::from_assignments(vec![ClusterId::new(0)]).to_bytes();
+    // The noise-label flag follows the single assignment.
+    bytes[26] = 7;
+
+    assert_eq!(
+        ClusteringResult::from_bytes(&bytes),
+        Err(ResultDecodeError::InvalidField {
+            field: "noise_label"
+        })
+    );
+}
@@ -0,0 +1,63 @@
+//! Tests for `IdMap` and labels keyed by row identifier.
+
+use chutoro_core::{ClusterId, ClusteringResult, IdMap, RowIdError};
+use rstest::rstest;
+
+fn ids(keys: &[&str]) -> Vec<String> {
+    keys.iter().map(|&key| key.to_owned()).collect()
+}
+
+#[rstest]
+fn identifiers_map_both_ways() {
+    let map = IdMap::new(ids(&["uuid-b", "uuid-a", "uuid-c"])).expect("identifiers are unique");
+
+    assert_eq!(map.len(), 3);
+    assert_eq!(map.id(1), Some("uuid-a"));
+    assert_eq!(map.id(3), None);
+    assert_eq!(map.index_of("uuid-c"), Some(2));
+    assert_eq!(map.index_of("uuid-z"), None);
+}
+
+#[rstest]
+fn duplicate_identifiers_are_rejected() {
+    let err = IdMap::new(ids(&["a", "b", "a"])).expect_err("`a` repeats");
+
+    assert_eq!(
+        err,
+        RowIdError::Duplicate {
+            id: "a".into(),
+            first: 0,
+            second: 2
+        }
+    );
+}
+
+#[rstest]
+fn identifiers_must_cover_every_row() {
+    let err = IdMap::for_rows(ids(&["a", "b"]), 3).expect_err("one row is unnamed");
+
+    assert_eq!(err, RowIdError::LengthMismatch { ids: 2, rows: 3 });
+}
+
+#[rstest]
+fn labels_are_keyed_by_identifier() {
+    let map = IdMap::new(ids(&["x", "y", "z"])).expect("identifiers are unique");
+    let result = ClusteringResult::from_assignments([0, 1, 0].map(ClusterId::new).to_vec());
+
+    let labels = result.labels_by_id(&map).expect("lengths match");
+
+    assert_eq!(labels.len(), 3);
+    assert_eq!(labels["x"], ClusterId::new(0));
+    assert_eq!(labels["y"], ClusterId::new(1));
+    assert_eq!(labels["z"], ClusterId::new(0));
+}
+
+#[rstest]
+fn labels_by_id_rejects_a_foreign_map() {
+    let map = IdMap::new(ids(&["x"])).expect("identifiers are unique");
+    let result = ClusteringResult::from_assignments([0, 0].map(ClusterId::new).to_vec());
+
+    let err = result.labels_by_id(&map).expect_err("lengths differ");
+
+    assert_eq!(err, RowIdError::LengthMismatch { ids: 1, rows: 2 });
+}
@@ -0,0 +1,139 @@
+//! Tests for clustering a deterministic subsample and assigning the rest.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use std::sync::Arc;
+
+use chutoro_core::{
+    ChutoroBuilder, ChutoroError, CpuHnsw, HnswParams, SampleSpec, estimate_peak_bytes,
+};
+use common::Dummy;
+use rstest::{fixture, rstest};
+
+/// Two well-separated groups of 20 points each.
+#[fixture]
+fn source() -> Dummy {
+    let near = (0..20).map(|i| i as f32 * 0.1);
+    let far = (0..20).map(|i| 100.0 + i as f32 * 0.1);
+    Dummy::new(near.chain(far).collect())
+}
+
+#[rstest]
+#[case::fraction(SampleSpec::Fraction(0.25))]
+#[case::count(SampleSpec::Count(10))]
+fn sampled_run_labels_every_point(source: Dummy, #[case] spec: SampleSpec) {
+    let chutoro = ChutoroBuilder::new()
+        .with_min_cluster_size(3)
+        .with_sample(spec, 11)
+        .build()
+        .expect("sampled configuration must be valid");
+    let result = chutoro.run(&source).expect("sampled run must succeed");
+
+    assert_eq!(result.assignments().len(), 40);
+    let report = result.sampling().expect("sampled runs report the split");
+    assert_eq!(report.sampled_points(), 10);
+    assert_eq!(report.assigned_points(), 30);
+    assert_eq!(report.seed(), 11);
+    assert_eq!(result.cluster_count(), 2);
+    let (near, far) = result.assignments().split_at(20);
+    assert!(near.iter().all(|&label| label == near[0]));
+    assert!(far.iter().all(|&label| label == far[0]));
+    assert_ne!(near[0], far[0]);
+    let membership = result
+        .membership()
+        .expect("sampled runs still score points");
+    assert_eq!(membership.probabilities().len(), 40);
+    assert!(result.timings().is_some());
+}
+
+#[rstest]
+fn sampled_runs_are_deterministic_per_seed(source: Dummy) {
+    let run = |seed| {
+        ChutoroBuilder::new()
+            .with_min_cluster_size(3)
+            .with_sample(SampleSpec::Fraction(0.3), seed)
+            .build()
+            .expect("sampled configuration must be valid")
+            .run(&source)
+            .expect("sampled run must succeed")
+    };
+    let first = run(5);
+    let again = run(5);
+    assert_eq!(first.assignments(), again.assignments());
+    assert_eq!(first.membership(), again.membership());
+}
+
+#[rstest]
+fn sample_is_raised_to_min_cluster_size(source: Dummy) {
+    let result = ChutoroBuilder::new()
+        .with_min_cluster_size(8)
+        .with_sample(SampleSpec::Count(2), 0)
+        .build()
+        .expect("sampled configuration must be valid")
+        .run(&source)
+        .expect("sampled run must succeed");
+    let report = result.sampling().expect("sampled runs report the split");
+    assert_eq!(report.sampled_points(), 8);
+    assert_eq!(report.total_points(), 40);
+}
+
+#[rstest]
+fn unsampled_runs_have_no_sampling_report(source: Dummy) {
+    let result = ChutoroBuilder::new()
+        .with_min_cluster_size(3)
+        .build()
+        .expect("configuration must be valid")
+        .run(&source)
+        .expect("run must succeed");
+    assert!(result.sampling().is_none());
+}
+
+#[rstest]
+fn memory_guard_estimates_the_sample(source: Dummy) {
+    let sample_estimate = estimate_peak_bytes(10, HnswParams::default().max_connections());
+    let full_estimate = estimate_peak_bytes(40, HnswParams::default().max_connections());
+    let limit = sample_estimate + (full_estimate - sample_estimate) / 2;
+    let builder = ChutoroBuilder::new()
+        .with_min_cluster_size(3)
+        .with_max_bytes(limit);
+
+    let err = builder
+        .clone()
+        .build()
+        .expect("configuration must be valid")
+        .run(&source)
+        .expect_err("the full dataset exceeds the limit");
+    assert!(matches!(err, ChutoroError::MemoryLimitExceeded { .. }));
+    builder
+        .with_sample(SampleSpec::Count(10), 1)
+        .build()
+        .expect("sampled configuration must be valid")
+        .run(&source)
+        .expect("the sample fits within the limit");
+}
+
+#[rstest]
+#[case::zero_fraction(SampleSpec::Fraction(0.0))]
+#[case::fraction_above_one(SampleSpec::Fraction(1.5))]
+#[case::zero_count(SampleSpec::Count(0))]
+fn build_rejects_invalid_samples(#[case] spec: SampleSpec) {
+    let err = ChutoroBuilder::new()
+        .with_sample(spec, 0)
+        .build()
+        .expect_err("invalid sample must be rejected");
+    assert!(matches!(err, ChutoroError::InvalidSample { .. }));
+    assert_eq!(err.code().as_str(), "CHUTORO_INVALID_SAMPLE");
+}
+
+#[rstest]
+fn build_rejects_sampling_with_prebuilt_index(source: Dummy) {
+    let (index, harvest) =
+        CpuHnsw::build_with_edges(&source, HnswParams::default()).expect("index must build");
+    let err = ChutoroBuilder::new()
+        .with_prebuilt_index(Arc::new(index), harvest)
+        .with_sample(SampleSpec::Fraction(0.5), 0)
+        .build()
+        .expect_err("sampling cannot reuse a prebuilt index");
+    assert!(matches!(err, ChutoroError::InvalidSample { .. }));
+}
@@ -0,0 +1,126 @@
+//! Tests for per-call nearest-neighbour queries against the retained index.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use std::{num::NonZeroUsize, sync::Arc};
+
+use chutoro_core::{Chutoro, ChutoroBuilder, ChutoroError, CpuHnsw, HnswParams, SearchParams};
+use common::Dummy;
+use rstest::{fixture, rstest};
+
+/// Forty evenly spaced rows followed by one query row at `10.2`.
+#[fixture]
+fn source() -> Dummy {
+    let rows = (0..40).map(|i| i as f32 * 0.5);
+    Dummy::new(rows.chain([10.2]).collect())
+}
+
+/// Indexes the forty rows that precede the query.
+fn indexed() -> Chutoro {
+    let training = Dummy::new((0..40).map(|i| i as f32 * 0.5).collect());
+    let params = HnswParams::new(4, 16).expect("params are valid");
+    let (index, harvest) = CpuHnsw::build_with_edges(&training, params).expect("index must build");
+    ChutoroBuilder::new()
+        .with_min_cluster_size(2)
+        .with_prebuilt_index(Arc::new(index), harvest)
+        .build()
+        .expect("configuration must be valid")
+}
+
+fn ids(neighbours: &[chutoro_core::Neighbour]) -> Vec<usize> {
+    neighbours.iter().map(|neighbour| neighbour.id).collect()
+}
+
+#[rstest]
+fn ef_overrides_the_construction_beam_per_call(source: Dummy) {
+    let chutoro = indexed();
+
+    let default = chutoro
+        .search_with(&source, 40, SearchParams::default())
+        .expect("search must succeed");
+    let narrow = chutoro
+        .search_with(
+            &source,
+            40,
+            SearchParams {
+                ef: NonZeroUsize::new(3),
+                ..SearchParams::default()
+            },
+        )
+        .expect("search must succeed");
+
+    assert_eq!(default.len(), 16);
+    assert_eq!(ids(&narrow), [20, 21, 19]);
+}
+
+#[rstest]
+fn filters_keep_only_accepted_rows(source: Dummy) {
+    let chutoro = indexed();
+    let tenant = |row: usize| row.is_multiple_of(4);
+
+    let neighbours = chutoro
+        .search_with(
+            &source,
+            40,
+            SearchParams {
+                ef: NonZeroUsize::new(4),
+                filter: Some(&tenant),
+                ..SearchParams::default()
+            },
+        )
+        .expect("search must succeed");
+
+    assert_eq!(ids(&neighbours), [20, 24, 16, 28]);
+}
+
+#[rstest]
+fn max_distance_drops_far_rows(source: Dummy) {
+    let chutoro = indexed();
+
+    let neighbours = chutoro
+        .search_with(
+            &source,
+            40,
+            SearchParams {
+                max_distance: Some(0.75),
+                ..SearchParams::default()
+            },
+        )
+        .expect("search must succeed");
+
+    assert_eq!(ids(&neighbours), [20, 21, 19]);
+}
+
+#[rstest]
+#[case::negative(-1.0)]
+#[case::nan(f32::NAN)]
+fn invalid_max_distances_are_rejected(source: Dummy, #[case] max_distance: f32) {
+    let chutoro = indexed();
+
+    let err = chutoro
+        .search_with(
+            &source,
+            40,
+            SearchParams {
+                max_distance: Some(max_distance),
+                ..SearchParams::default()
+            },
+        )
+        .expect_err("max_distance is invalid");
+
+    assert!(matches!(err, ChutoroError::CpuHnswFailure { .. }));
+}
+
+#[rstest]
+fn search_requires_a_prebuilt_index(source: Dummy) {
+    let chutoro = ChutoroBuilder::new()
+        .build()
+        .expect("configuration must be valid");
+
+    let err = chutoro
+        .search_with(&source, 40, SearchParams::default())
+        .expect_err("search without an index must fail");
+
+    assert!(matches!(err, ChutoroError::PrebuiltIndexMismatch { .. }));
+}
@@ -0,0 +1,145 @@
+//! Tests for propagating known labels to the clusters that contain them.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use chutoro_core::{
+    ChutoroBuilder, ChutoroError, ClusterId, ClusteringResult, SeedLabelConflict, SeedLabelReport,
+};
+use common::Dummy;
+use rstest::{fixture, rstest};
+
+/// Index of the first point of the second group.
+const SECOND_GROUP: usize = 15;
+/// Index of the outlier far from both groups.
+const OUTLIER: usize = 30;
+
+/// Two tight groups of fifteen points and an outlier far from both.
+#[fixture]
+fn groups() -> Dummy {
+    let near = (0..15).map(|i| i as f32 * 0.1);
+    let far = (0..15).map(|i| 10.0 + i as f32 * 0.1);
+    Dummy::new(near.chain(far).chain([500.0]).collect())
+}
+
+fn cluster(seeds: &[(usize, u64)], source: &Dummy) -> ClusteringResult {
+    ChutoroBuilder::new()
+        .with_min_cluster_size(5)
+        .with_seed_labels(&labelled(seeds))
+        .build()
+        .expect("configuration must be valid")
+        .run(source)
+        .expect("run must succeed")
+}
+
+fn labelled(seeds: &[(usize, u64)]) -> Vec<(usize, ClusterId)> {
+    seeds
+        .iter()
+        .map(|&(point, label)| (point, ClusterId::new(label)))
+        .collect()
+}
+
+fn assert_contiguous(result: &ClusteringResult) {
+    let rebuilt = ClusteringResult::try_from_assignments(result.assignments().to_vec())
+        .expect("anchored identifiers stay contiguous");
+    assert_eq!(rebuilt.cluster_count(), result.cluster_count());
+}
+
+#[rstest]
+fn seeded_clusters_adopt_the_seed_label(groups: Dummy) {
+    let result = cluster(&[(0, 1), (SECOND_GROUP, 0)], &groups);
+
+    let labels = result.assignments();
+    assert!(
+        labels[..SECOND_GROUP]
+            .iter()
+            .all(|&id| id == ClusterId::new(1))
+    );
+    assert!(
+        labels[SECOND_GROUP..OUTLIER]
+            .iter()
+            .all(|&id| id == ClusterId::new(0))
+    );
+    assert_eq!(result.noise_label(), Some(ClusterId::new(2)));
+    assert_eq!(labels[OUTLIER], ClusterId::new(2));
+    assert_eq!(
+        result.seed_labels(),
+        Some(&SeedLabelReport::new(2, 2, 0, Vec::new()))
+    );
+    assert_contiguous(&result);
+}
+
+#[rstest]
+fn unseeded_clusters_follow_the_seed_labels(groups: Dummy) {
+    let result = cluster(&[(SECOND_GROUP, 0)], &groups);
+
+    let labels = result.assignments();
+    assert_eq!(labels[SECOND_GROUP], ClusterId::new(0));
+    assert_eq!(labels[0], ClusterId::new(1));
+    assert_eq!(result.noise_label(), Some(ClusterId::new(2)));
+    assert_contiguous(&result);
+}
+
+#[rstest]
+fn disagreeing_seeds_are_reported_as_a_conflict(groups: Dummy) {
+    let result = cluster(&[(0, 0), (1, 1), (2, 1)], &groups);
+
+    let labels = result.assignments();
+    assert_eq!(labels[0], ClusterId::new(0));
+    assert!(
+        labels[1..SECOND_GROUP]
+            .iter()
+            .all(|&id| id == ClusterId::new(1))
+    );
+    assert_eq!(labels[SECOND_GROUP], ClusterId::new(2));
+    let report = result.seed_labels().expect("seed labels are reported");
+    assert_eq!(
+        report.conflicts(),
+        [SeedLabelConflict::new(
+            ClusterId::new(1),
+            vec![ClusterId::new(0), ClusterId::new(1)]
+        )]
+    );
+    assert_contiguous(&result);
+}
+
+#[rstest]
+fn seeds_left_as_noise_keep_their_label(groups: Dummy) {
+    let result = cluster(&[(0, 0), (OUTLIER, 1)], &groups);
+
+    let labels = result.assignments();
+    assert_eq!(labels[OUTLIER], ClusterId::new(1));
+    assert_eq!(labels[SECOND_GROUP], ClusterId::new(2));
+    assert_eq!(result.noise_label(), None);
+    let report = result.seed_labels().expect("seed labels are reported");
+    assert_eq!(report.noise_seeds(), 1);
+    assert_eq!(report.anchored_clusters(), 1);
+    assert_contiguous(&result);
+}
+
+#[rstest]
+#[case::duplicate_point(&[(0, 0), (0, 1)])]
+#[case::gap(&[(0, 0), (1, 2)])]
+#[case::missing_zero(&[(0, 1)])]
+fn build_rejects_unusable_seed_labels(#[case] seeds: &[(usize, u64)]) {
+    let err = ChutoroBuilder::new()
+        .with_seed_labels(&labelled(seeds))
+        .build()
+        .expect_err("seed labels must be rejected");
+    assert!(matches!(err, ChutoroError::InvalidSeedLabels { .. }));
+}
+
+#[rstest]
+fn run_rejects_seeds_beyond_the_source(groups: Dummy) {
+    let err = ChutoroBuilder::new()
+        .with_min_cluster_size(5)
+        .with_seed_labels(&labelled(&[(0, 0), (99, 1)]))
+        .build()
+        .expect("configuration must be valid")
+        .run(&groups)
+        .expect_err("out-of-range seeds must fail");
+    assert_eq!(
+        err.to_string(),
+        "invalid seed labels: seeded point 99 is out of range for 31 points"
+    );
+}
@@ -0,0 +1,77 @@
+//! Tests for master seed derivation and seed reporting.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use std::sync::Arc;
+
+use chutoro_core::{
+    ChutoroBuilder, ClusteringResult, CpuHnsw, HnswParams, SampleSpec, SeedReport, SeedStream,
+};
+use common::Dummy;
+use rstest::{fixture, rstest};
+
+/// Two well-separated groups of 12 points each.
+#[fixture]
+fn groups() -> Dummy {
+    let near = (0..12).map(|i| i as f32 * 0.1);
+    let far = (0..12).map(|i| 50.0 + i as f32 * 0.1);
+    Dummy::new(near.chain(far).collect())
+}
+
+fn cluster(builder: ChutoroBuilder, source: &Dummy) -> ClusteringResult {
+    builder
+        .with_min_cluster_size(3)
+        .build()
+        .expect("configuration must be valid")
+        .run(source)
+        .expect("run must succeed")
+}
+
+#[rstest]
+fn master_seed_derives_and_reports_component_seeds(groups: Dummy) {
+    let first = cluster(ChutoroBuilder::new().with_seed(42), &groups);
+    let second = cluster(ChutoroBuilder::new().with_seed(42), &groups);
+
+    let expected = SeedReport::new(Some(42), SeedStream::HnswLevels.derive(42), None);
+    assert_eq!(first.seeds(), Some(expected));
+    assert_eq!(first.assignments(), second.assignments());
+}
+
+#[rstest]
+fn unseeded_runs_report_the_hnsw_seed(groups: Dummy) {
+    let params = HnswParams::default().with_rng_seed(9);
+
+    let result = cluster(ChutoroBuilder::new().with_hnsw_params(params), &groups);
+
+    assert_eq!(result.seeds(), Some(SeedReport::new(None, 9, None)));
+}
+
+#[rstest]
+fn master_seed_replaces_the_sample_seed(groups: Dummy) {
+    let builder = ChutoroBuilder::new()
+        .with_seed(5)
+        .with_sample(SampleSpec::Fraction(0.5), 7);
+
+    let result = cluster(builder, &groups);
+
+    let derived = SeedStream::Sample.derive(5);
+    let seeds = result.seeds().expect("CPU runs report seeds");
+    assert_eq!(seeds.sample(), Some(derived));
+    assert_eq!(result.sampling().map(|report| report.seed()), Some(derived));
+}
+
+#[rstest]
+fn prebuilt_indices_keep_their_seed(groups: Dummy) {
+    let params = HnswParams::default().with_rng_seed(3);
+    let (index, harvest) = CpuHnsw::build_with_edges(&groups, params).expect("index must build");
+
+    let result = cluster(
+        ChutoroBuilder::new()
+            .with_prebuilt_index(Arc::new(index), harvest)
+            .with_seed(42),
+        &groups,
+    );
+
+    assert_eq!(result.seeds(), Some(SeedReport::new(Some(42), 3, None)));
+}
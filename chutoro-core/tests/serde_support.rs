@@ -0,0 +1,126 @@
+//! Tests for the optional serde support on public configuration and
+//! artefact types.
+#![cfg(all(feature = "serde", feature = "cpu"))]
+
+mod common;
+
+use std::{num::NonZeroUsize, time::Duration};
+
+use chutoro_core::{
+    AdjacencyStorage, CandidateEdge, ChutoroBuilder, ChutoroErrorCode, ClusterId, ClusteringResult,
+    DataSourceErrorCode, HierarchyConfig, HierarchyErrorCode, HnswErrorCode, HnswParams, MstEdge,
+    MstErrorCode,
+};
+use common::Dummy;
+use rstest::rstest;
+use serde::{Serialize, de::DeserializeOwned};
+
+fn round_trip<T: Serialize + DeserializeOwned>(value: &T) -> T {
+    let json = serde_json::to_string(value).expect("value serializes");
+    serde_json::from_str(&json).expect("serialized value deserializes")
+}
+
+#[rstest]
+fn configurations_round_trip() {
+    let params = HnswParams::new(8, 32)
+        .expect("valid parameters")
+        .with_rng_seed(7)
+        .with_max_level(5)
+        .with_distance_cache_ttl(Some(Duration::from_millis(250)))
+        .with_base_layer_connections(24)
+        .expect("valid M0")
+        .with_layer_overrides(&[(1, 4, 16)])
+        .expect("valid overrides")
+        .with_level_distribution(&[0.75, 0.25])
+        .expect("valid level distribution")
+        .with_adjacency_storage(AdjacencyStorage::Arena);
+    let config = HierarchyConfig::new(NonZeroUsize::new(4).expect("non-zero"));
+
+    assert_eq!(round_trip(&params), params);
+    assert_eq!(
+        round_trip(&config).min_cluster_size(),
+        config.min_cluster_size()
+    );
+}
+
+#[rstest]
+fn invalid_hnsw_params_are_rejected() {
+    let mut json = serde_json::to_value(HnswParams::default()).expect("params serialize");
+    json["ef_construction"] = 4.into();
+
+    let error = serde_json::from_value::<HnswParams>(json).expect_err("ef below M must fail");
+
+    assert!(error.to_string().contains("ef_construction"), "{error}");
+}
+
+#[rstest]
+fn edges_round_trip_and_canonicalize() {
+    let candidate = CandidateEdge::new(5, 2, 0.75, 9);
+    assert_eq!(round_trip(&candidate), candidate);
+
+    let edge: MstEdge =
+        serde_json::from_str(r#"{"source":6,"target":1,"weight":0.5,"sequence":3}"#)
+            .expect("edge deserializes");
+    assert_eq!(edge, MstEdge::new(1, 6, 0.5, 3));
+}
+
+#[rstest]
+fn pipeline_results_round_trip() {
+    let near = (0..12).map(|i| i as f32 * 0.1);
+    let far = (0..12).map(|i| 50.0 + i as f32 * 0.1);
+    let source = Dummy::new(near.chain(far).collect());
+    let result = ChutoroBuilder::new()
+        .with_min_cluster_size(3)
+        .with_seed(11)
+        .build()
+        .expect("configuration must be valid")
+        .run(&source)
+        .expect("run must succeed");
+
+    assert_eq!(round_trip(&result), result);
+}
+
+#[rstest]
+#[case::gap(r#"{"assignments":[0,2],"cluster_count":2}"#)]
+#[case::membership_length(
+    r#"{"assignments":[0],"membership":{"probabilities":[],"outlier_scores":[]}}"#
+)]
+#[case::score_range(
+    r#"{"assignments":[0],"membership":{"probabilities":[1.5],"outlier_scores":[0.0]}}"#
+)]
+fn invalid_results_are_rejected(#[case] json: &str) {
+    assert!(serde_json::from_str::<ClusteringResult>(json).is_err());
+}
+
+#[rstest]
+fn manual_results_deserialize_without_reports() {
+    let result: ClusteringResult =
+        serde_json::from_str(r#"{"assignments":[0,1,0]}"#).expect("result deserializes");
+
+    assert_eq!(result.cluster_count(), 2);
+    assert_eq!(result.assignments()[1], ClusterId::new(1));
+    assert!(result.seeds().is_none());
+}
+
+#[rstest]
+fn error_codes_use_their_stable_identifiers() {
+    let codes = (
+        ChutoroErrorCode::InvalidSample,
+        DataSourceErrorCode::EmptyData,
+        HnswErrorCode::GraphInvariantViolation,
+        MstErrorCode::NonFiniteWeight,
+        HierarchyErrorCode::MinClusterSizeTooLarge,
+    );
+
+    let json = serde_json::to_string(&codes).expect("codes serialize");
+
+    assert_eq!(
+        json,
+        concat!(
+            r#"["CHUTORO_INVALID_SAMPLE","DATA_SOURCE_EMPTY","GRAPH_INVARIANT_VIOLATION","#,
+            r#""NON_FINITE_WEIGHT","MIN_CLUSTER_SIZE_TOO_LARGE"]"#
+        )
+    );
+    assert_eq!(round_trip(&codes), codes);
+    assert!(serde_json::from_str::<ChutoroErrorCode>(r#""CHUTORO_NOPE""#).is_err());
+}
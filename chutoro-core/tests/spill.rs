@@ -0,0 +1,88 @@
+//! Tests for spilling the MST input to disk.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use std::fs;
+use std::num::NonZeroUsize;
+
+use chutoro_core::{ChutoroBuilder, ChutoroError, DefaultMstStage, EdgeBudget};
+use common::Dummy;
+use rstest::{fixture, rstest};
+use tempfile::TempDir;
+
+#[fixture]
+fn source() -> Dummy {
+    Dummy::new(
+        (0..60)
+            .map(|point| (point % 3) as f32 * 100.0 + (point / 3) as f32 * 0.5)
+            .collect(),
+    )
+}
+
+#[fixture]
+fn spill_dir() -> TempDir {
+    tempfile::tempdir().expect("tempdir")
+}
+
+fn builder() -> ChutoroBuilder {
+    ChutoroBuilder::new().with_min_cluster_size(5)
+}
+
+#[rstest]
+#[case::streamed(None)]
+#[case::budgeted(Some(EdgeBudget::new(NonZeroUsize::new(400).expect("non-zero"))))]
+fn spilled_runs_match_in_memory_runs(
+    source: Dummy,
+    spill_dir: TempDir,
+    #[case] budget: Option<EdgeBudget>,
+) {
+    let configure = |builder: ChutoroBuilder| match budget {
+        Some(budget) => builder.with_edge_budget(budget),
+        None => builder,
+    };
+    let in_memory = configure(builder())
+        .build()
+        .expect("configuration must be valid")
+        .run(&source)
+        .expect("in-memory run must succeed");
+    let spilled = configure(builder().with_weighted_edge_spill_directory(spill_dir.path()))
+        .build()
+        .expect("configuration must be valid")
+        .run(&source)
+        .expect("spilled run must succeed");
+
+    assert_eq!(spilled.assignments(), in_memory.assignments());
+    assert_eq!(spilled.cluster_count(), 3);
+    let leftovers = fs::read_dir(spill_dir.path())
+        .expect("read spill dir")
+        .count();
+    assert_eq!(leftovers, 0, "spill files must be removed after the run");
+}
+
+#[rstest]
+fn rejects_missing_spill_directory(spill_dir: TempDir) {
+    let missing = spill_dir.path().join("absent");
+
+    let err = builder()
+        .with_weighted_edge_spill_directory(&missing)
+        .build()
+        .expect_err("a missing directory must be rejected");
+
+    assert!(
+        matches!(&err, ChutoroError::Spill { path, .. } if **path == *missing),
+        "{err:?}"
+    );
+}
+
+#[rstest]
+fn rejects_spilling_with_a_custom_mst_stage(spill_dir: TempDir) {
+    let err = builder()
+        .with_weighted_edge_spill_directory(spill_dir.path())
+        .with_mst_stage(DefaultMstStage)
+        .build()
+        .expect_err("a custom MST stage must be rejected");
+
+    assert!(matches!(err, ChutoroError::Spill { .. }), "{err:?}");
+    assert_eq!(err.code().as_str(), "CHUTORO_SPILL_FAILURE");
+}
@@ -0,0 +1,151 @@
+//! Tests for `ClusteringResult::stability_report`.
+
+mod common;
+
+use chutoro_core::{ClusterId, ClusteringQualityError, ClusteringResult};
+use rstest::rstest;
+
+fn labelled(labels: &[u64]) -> ClusteringResult {
+    ClusteringResult::from_assignments(labels.iter().copied().map(ClusterId::new).collect())
+}
+
+#[rstest]
+fn relabelled_runs_agree_completely() {
+    let reference = labelled(&[0, 0, 1, 1, 2, 2]);
+    let others = [labelled(&[2, 2, 0, 0, 1, 1]), labelled(&[1, 1, 2, 2, 0, 0])];
+
+    let report = reference
+        .stability_report(&others)
+        .expect("lengths must match");
+
+    assert_eq!(report.runs(), 3);
+    let pairs: Vec<_> = report
+        .agreements()
+        .iter()
+        .map(|agreement| (agreement.left, agreement.right))
+        .collect();
+    assert_eq!(pairs, [(0, 1), (0, 2), (1, 2)]);
+    assert_eq!(report.mean_ari(), Some(1.0));
+    assert_eq!(report.mean_nmi(), Some(1.0));
+    assert_eq!(report.min_ari(), Some(1.0));
+    assert!(report.clusters().iter().all(|cluster| {
+        cluster.size == 2 && cluster.frequency == 1.0 && cluster.mean_jaccard == 1.0
+    }));
+}
+
+#[rstest]
+fn persistence_tracks_each_cluster() {
+    let reference = labelled(&[0, 0, 0, 0, 1, 1, 1, 1]);
+    // The first cluster survives both runs; the second splits in one.
+    let others = [
+        labelled(&[0, 0, 0, 0, 1, 1, 1, 1]),
+        labelled(&[0, 0, 0, 0, 1, 2, 3, 4]),
+    ];
+
+    let report = reference
+        .stability_report(&others)
+        .expect("lengths must match");
+
+    let [stable, fragile] = report.clusters() else {
+        panic!("expected two clusters, got {:?}", report.clusters());
+    };
+    assert_eq!((stable.cluster, stable.size), (ClusterId::new(0), 4));
+    assert_eq!(stable.frequency, 1.0);
+    assert_eq!(fragile.cluster, ClusterId::new(1));
+    assert_eq!(fragile.frequency, 0.5);
+    assert_eq!(fragile.mean_jaccard, (1.0 + 0.25) / 2.0);
+    assert!(report.min_ari() < report.mean_ari());
+}
+
+#[rstest]
+fn a_single_run_has_no_pairs() {
+    let report = labelled(&[0, 1, 1])
+        .stability_report(&[])
+        .expect("nothing is compared");
+
+    assert_eq!(report.runs(), 1);
+    assert_eq!(report.agreements(), []);
+    assert_eq!(report.mean_ari(), None);
+    assert!(
+        report
+            .clusters()
+            .iter()
+            .all(|cluster| cluster.frequency == 1.0)
+    );
+}
+
+#[rstest]
+fn mismatched_lengths_are_rejected() {
+    let err = labelled(&[0, 0, 1])
+        .stability_report(&[labelled(&[0, 1])])
+        .expect_err("lengths differ");
+
+    assert_eq!(
+        err,
+        ClusteringQualityError::LabelLengthMismatch {
+            ground_truth_len: 3,
+            predicted_len: 2,
+        }
+    );
+}
+
+#[cfg(feature = "cpu")]
+mod runs {
+    use super::*;
+    use chutoro_core::ChutoroBuilder;
+    use common::Dummy;
+
+    fn run(source: &Dummy, seed: u64) -> ClusteringResult {
+        ChutoroBuilder::new()
+            .with_min_cluster_size(5)
+            .with_seed(seed)
+            .build()
+            .expect("configuration must be valid")
+            .run(source)
+            .expect("run must succeed")
+    }
+
+    #[rstest]
+    fn separated_groups_are_stable_across_seeds() {
+        let near = (0..20).map(|i| i as f32 * 0.1);
+        let far = (0..20).map(|i| 100.0 + i as f32 * 0.1);
+        let source = Dummy::new(near.chain(far).collect());
+        let reference = run(&source, 0);
+        let others: Vec<_> = (1..5).map(|seed| run(&source, seed)).collect();
+
+        let report = reference
+            .stability_report(&others)
+            .expect("runs cover the same points");
+
+        assert_eq!(report.runs(), 5);
+        assert_eq!(report.agreements().len(), 10);
+        assert_eq!(report.min_ari(), Some(1.0));
+        assert!(!report.clusters().is_empty());
+        assert!(
+            report
+                .clusters()
+                .iter()
+                .all(|cluster| cluster.frequency == 1.0)
+        );
+    }
+
+    #[rstest]
+    fn noise_is_not_reported_as_a_cluster() {
+        let near = (0..15).map(|i| i as f32 * 0.1);
+        let far = (0..15).map(|i| 10.0 + i as f32 * 0.1);
+        let source = Dummy::new(near.chain(far).chain([-30.0, 500.0]).collect());
+        let reference = run(&source, 0);
+        let noise = reference.noise_label().expect("outliers must be noise");
+
+        let report = reference
+            .stability_report(&[run(&source, 1)])
+            .expect("runs cover the same points");
+
+        assert!(
+            report
+                .clusters()
+                .iter()
+                .all(|cluster| cluster.cluster != noise)
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! Tests for observing intermediate pipeline artefacts via the builder hook.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use chutoro_core::{
+    ChutoroBuilder, ClusterId, ClusteringResult, CondensedChild, HierarchyStage, MstEdge, Result,
+    StageArtefact, StageContext,
+};
+use common::Dummy;
+use rstest::{fixture, rstest};
+
+/// Two well-separated groups of 10 points each.
+#[fixture]
+fn groups() -> Dummy {
+    let near = (0..10).map(|i| i as f32 * 0.1);
+    let far = (0..10).map(|i| 40.0 + i as f32 * 0.1);
+    Dummy::new(near.chain(far).collect())
+}
+
+/// Owned summary of one artefact, recorded by the hook.
+#[derive(Clone, Debug, PartialEq)]
+enum Seen {
+    Index { points: usize, harvested: usize },
+    Harvest(usize),
+    Mst(usize),
+    CondensedTree { points: Vec<usize>, roots: usize },
+}
+
+fn summarise(artefact: StageArtefact<'_>) -> Seen {
+    match artefact {
+        StageArtefact::Index { index, harvest } => Seen::Index {
+            points: index.len(),
+            harvested: harvest.len(),
+        },
+        StageArtefact::Harvest(edges) => Seen::Harvest(edges.len()),
+        StageArtefact::Mst(edges) => Seen::Mst(edges.len()),
+        StageArtefact::CondensedTree(tree) => {
+            let mut points: Vec<usize> = tree
+                .rows()
+                .filter_map(|row| match row.child() {
+                    CondensedChild::Point(point) => Some(point),
+                    CondensedChild::Cluster(_) => None,
+                })
+                .collect();
+            points.sort_unstable();
+            Seen::CondensedTree {
+                points,
+                roots: tree.roots().len(),
+            }
+        }
+        other => panic!("unexpected artefact {other:?}"),
+    }
+}
+
+fn recorded_run(builder: ChutoroBuilder, source: &Dummy) -> Vec<Seen> {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&seen);
+    builder
+        .with_min_cluster_size(3)
+        .on_stage_complete(move |artefact: StageArtefact<'_>| {
+            sink.lock()
+                .expect("hook lock is healthy")
+                .push(summarise(artefact));
+        })
+        .build()
+        .expect("configuration must be valid")
+        .run(source)
+        .expect("run must succeed");
+    seen.lock().expect("hook lock is healthy").clone()
+}
+
+#[rstest]
+fn hook_sees_every_stage_in_order(groups: Dummy) {
+    let seen = recorded_run(ChutoroBuilder::new(), &groups);
+
+    assert_eq!(seen.len(), 4);
+    assert!(matches!(seen[0], Seen::Index { points: 20, harvested } if harvested > 0));
+    assert!(matches!(seen[1], Seen::Harvest(edges) if edges > 0));
+    assert_eq!(seen[2], Seen::Mst(19));
+    assert_eq!(
+        seen[3],
+        Seen::CondensedTree {
+            points: (0..20).collect(),
+            roots: 1,
+        }
+    );
+}
+
+#[rstest]
+fn repaired_forests_include_bridge_edges() {
+    // Far-apart points rarely share harvest edges with a tiny fan-out, but
+    // repair must always deliver a spanning tree to the hook.
+    let source = Dummy::new((0..16).map(|i| (i * i) as f32).collect());
+    let seen = recorded_run(ChutoroBuilder::new().with_connect_components(true), &source);
+
+    assert_eq!(seen[2], Seen::Mst(15));
+}
+
+#[derive(Debug)]
+struct AllOneCluster;
+
+impl HierarchyStage for AllOneCluster {
+    fn extract(&self, context: &StageContext<'_>, _edges: &[MstEdge]) -> Result<ClusteringResult> {
+        Ok(ClusteringResult::from_assignments(vec![
+            ClusterId::new(0);
+            context.len()
+        ]))
+    }
+}
+
+#[rstest]
+fn custom_hierarchy_stages_produce_no_condensed_tree(groups: Dummy) {
+    let seen = recorded_run(
+        ChutoroBuilder::new().with_hierarchy_stage(AllOneCluster),
+        &groups,
+    );
+
+    assert_eq!(seen.len(), 3);
+    assert!(
+        !seen
+            .iter()
+            .any(|entry| matches!(entry, Seen::CondensedTree { .. }))
+    );
+}
@@ -0,0 +1,224 @@
+//! Tests for triangle-inequality checks of sources that declare a metric.
+
+mod common;
+
+use std::num::NonZeroUsize;
+
+use chutoro_core::{
+    DataSource, DataSourceError, MetricClass, MetricDescriptor,
+    datasource::{TriangleViolation, check_triangle_inequality},
+};
+use common::Dummy;
+use rstest::rstest;
+
+/// Points on a line compared by squared distance, which breaks the triangle
+/// inequality, under an optional declared class.
+struct Squared {
+    points: Vec<f32>,
+    class: Option<MetricClass>,
+}
+
+impl Squared {
+    fn new(class: Option<MetricClass>) -> Self {
+        let near = (0..20).map(|i| i as f32 * 0.5);
+        let far = (0..20).map(|i| 100.0 + i as f32 * 0.5);
+        Self {
+            points: near.chain(far).collect(),
+            class,
+        }
+    }
+}
+
+impl DataSource for Squared {
+    fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    fn name(&self) -> &str {
+        "squared"
+    }
+
+    fn metric_descriptor(&self) -> MetricDescriptor {
+        let descriptor = MetricDescriptor::new("squared-euclidean");
+        match self.class {
+            Some(class) => descriptor.with_class(class),
+            None => descriptor,
+        }
+    }
+
+    fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+        Ok((self.points[i] - self.points[j]).powi(2))
+    }
+}
+
+#[rstest]
+fn metrics_satisfy_the_inequality() {
+    let source = Dummy::new((0..30).map(|i| (i * i) as f32).collect());
+
+    let report = check_triangle_inequality(&source, 128, 4).expect("distances must evaluate");
+
+    assert!(report.is_valid());
+    assert_eq!(report.sampled_triples(), 128);
+}
+
+#[rstest]
+fn violations_name_the_triple() {
+    let source = Squared::new(None);
+
+    let report = check_triangle_inequality(&source, 128, 4).expect("distances must evaluate");
+
+    assert!(!report.is_valid());
+    for violation in report.violations() {
+        let TriangleViolation {
+            first,
+            middle,
+            third,
+            direct,
+            detour,
+        } = *violation;
+        assert!(first.max(middle).max(third) < source.len());
+        assert_ne!(first, middle);
+        assert_ne!(middle, third);
+        assert_ne!(first, third);
+        assert!(direct > detour);
+    }
+}
+
+#[rstest]
+#[case::empty(Vec::new())]
+#[case::pair(vec![0.0, 1.0])]
+fn sources_without_triples_are_vacuously_valid(#[case] points: Vec<f32>) {
+    let report =
+        check_triangle_inequality(&Dummy::new(points), 16, 0).expect("nothing is evaluated");
+
+    assert!(report.is_valid());
+    assert_eq!(report.sampled_triples(), 0);
+}
+
+#[rstest]
+fn samples_follow_the_seed() {
+    let source = Squared::new(None);
+    let check = |seed| check_triangle_inequality(&source, 32, seed).expect("distances evaluate");
+
+    assert_eq!(check(3), check(3));
+    assert_ne!(check(3), check(4));
+}
+
+#[rstest]
+fn violations_describe_themselves() {
+    let violation = TriangleViolation {
+        first: 0,
+        middle: 1,
+        third: 2,
+        direct: 4.0,
+        detour: 2.0,
+    };
+
+    assert_eq!(
+        violation.to_string(),
+        "d(0, 2) = 4 exceeds d(0, 1) + d(1, 2) = 2"
+    );
+}
+
+#[rstest]
+#[case::metric(MetricClass::Metric, true)]
+#[case::pseudo_metric(MetricClass::PseudoMetric, true)]
+#[case::non_metric(MetricClass::NonMetric, false)]
+fn classes_declare_the_inequality(#[case] class: MetricClass, #[case] expected: bool) {
+    assert_eq!(class.satisfies_triangle_inequality(), expected);
+}
+
+#[rstest]
+fn classes_distinguish_descriptors() {
+    let plain = MetricDescriptor::new("euclidean");
+    let declared = plain.clone().with_class(MetricClass::Metric);
+
+    assert_eq!(plain.class(), None);
+    assert_eq!(declared.class(), Some(MetricClass::Metric));
+    assert_eq!(declared.as_str(), plain.as_str());
+    assert_ne!(declared, plain);
+}
+
+#[cfg(feature = "cpu")]
+mod runs {
+    use super::*;
+    use chutoro_core::{ChutoroBuilder, ClusteringResult, Warning, WarningCode};
+
+    fn run(source: &Squared, triples: Option<usize>) -> ClusteringResult {
+        let mut builder = ChutoroBuilder::new().with_min_cluster_size(3).with_seed(5);
+        if let Some(triples) = triples.and_then(NonZeroUsize::new) {
+            builder = builder.with_triangle_check(triples);
+        }
+        builder
+            .build()
+            .expect("configuration must be valid")
+            .run(source)
+            .expect("run must succeed")
+    }
+
+    fn triangle_warnings(result: &ClusteringResult) -> Vec<Warning> {
+        result
+            .warnings()
+            .iter()
+            .copied()
+            .filter(|warning| warning.code() == WarningCode::TriangleInequalityViolated)
+            .collect()
+    }
+
+    #[rstest]
+    #[case::metric(MetricClass::Metric)]
+    #[case::pseudo_metric(MetricClass::PseudoMetric)]
+    fn declared_metrics_that_break_the_inequality_warn(#[case] class: MetricClass) {
+        let result = run(&Squared::new(Some(class)), Some(256));
+
+        let [
+            Warning::TriangleInequalityViolated {
+                violations,
+                sampled,
+            },
+        ] = triangle_warnings(&result)[..]
+        else {
+            panic!("expected one triangle warning, got {:?}", result.warnings());
+        };
+        assert_eq!(sampled, 256);
+        assert!(violations > 0 && violations <= sampled);
+    }
+
+    #[rstest]
+    fn triangle_warnings_survive_persistence() {
+        let result = run(&Squared::new(Some(MetricClass::Metric)), Some(64));
+
+        let restored =
+            ClusteringResult::from_bytes(&result.to_bytes()).expect("result must decode");
+
+        assert_eq!(triangle_warnings(&restored), triangle_warnings(&result));
+        assert_eq!(triangle_warnings(&restored).len(), 1);
+    }
+
+    #[rstest]
+    #[case::undeclared(None, Some(256))]
+    #[case::non_metric(Some(MetricClass::NonMetric), Some(256))]
+    #[case::disabled(Some(MetricClass::Metric), None)]
+    fn other_runs_are_not_checked(
+        #[case] class: Option<MetricClass>,
+        #[case] triples: Option<usize>,
+    ) {
+        let result = run(&Squared::new(class), triples);
+
+        assert_eq!(triangle_warnings(&result), []);
+    }
+
+    #[rstest]
+    fn checks_do_not_change_the_clustering() {
+        let source = Squared::new(Some(MetricClass::Metric));
+
+        let checked = run(&source, Some(64));
+        let unchecked = run(&source, None);
+
+        assert_eq!(checked.assignments(), unchecked.assignments());
+        assert_eq!(
+            checked.distance_evaluations(),
+            unchecked.distance_evaluations()
+        );
+    }
+}
@@ -0,0 +1,133 @@
+//! Tests for the sampled metric checks of `datasource::validate_source`.
+
+mod common;
+
+use chutoro_core::{
+    DataSource, DataSourceError,
+    datasource::{Violation, ViolationKind, validate_source},
+};
+use common::{Dummy, truncated::Truncated};
+use rstest::rstest;
+
+/// Eight points whose distances come from a function of their indices.
+struct Metric(fn(usize, usize) -> f32);
+
+impl DataSource for Metric {
+    fn len(&self) -> usize {
+        8
+    }
+
+    fn name(&self) -> &str {
+        "metric"
+    }
+
+    fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+        Ok((self.0)(i, j))
+    }
+}
+
+fn gap(i: usize, j: usize) -> f32 {
+    i.abs_diff(j) as f32
+}
+
+#[rstest]
+fn metrics_are_valid() {
+    let source = Dummy::new((0..20).map(|i| i as f32 * 0.5).collect());
+
+    let report = validate_source(&source, 64, 3).expect("distances must evaluate");
+
+    assert!(report.is_valid());
+    assert_eq!(report.sampled_pairs(), 64);
+    assert_eq!(report.violations(), &[]);
+}
+
+#[rstest]
+#[case::non_finite(|i, j| if i == j { 0.0 } else { f32::INFINITY }, ViolationKind::NonFinite)]
+#[case::negative(|i, j| -gap(i, j), ViolationKind::Negative)]
+#[case::identity(|i, j| gap(i, j) + 1.0, ViolationKind::Identity)]
+#[case::asymmetric(|i, j| if i < j { gap(i, j) } else { gap(i, j) * 2.0 }, ViolationKind::Asymmetric)]
+fn each_broken_property_is_reported_alone(
+    #[case] distance: fn(usize, usize) -> f32,
+    #[case] kind: ViolationKind,
+) {
+    let report = validate_source(&Metric(distance), 32, 5).expect("distances must evaluate");
+
+    assert!(!report.is_valid());
+    assert_eq!(report.count(kind), 32);
+    assert!(
+        report
+            .violations()
+            .iter()
+            .all(|violation| violation.kind() == kind)
+    );
+}
+
+#[rstest]
+fn violations_name_the_offending_points() {
+    let source = Dummy::new(vec![0.0, 1.0, 2.0, f32::NAN]);
+
+    let report = validate_source(&source, 32, 11).expect("distances must evaluate");
+
+    assert!(report.count(ViolationKind::NonFinite) > 0);
+    assert!(report.violations().iter().all(|violation| matches!(
+        violation,
+        Violation::NonFinite { left, right, distance }
+            if (*left == 3 || *right == 3) && distance.is_nan()
+    )));
+}
+
+#[rstest]
+fn rounding_errors_are_tolerated() {
+    let source = Metric(|i, j| {
+        let skew = if i < j { 1.0 } else { 1.0 + 1e-7 };
+        gap(i, j) * skew
+    });
+
+    assert!(
+        validate_source(&source, 32, 1)
+            .expect("distances must evaluate")
+            .is_valid()
+    );
+}
+
+#[rstest]
+fn samples_follow_the_seed() {
+    let source = Metric(|i, j| if i < j { gap(i, j) } else { gap(i, j) + 0.5 });
+    let validate = |seed| validate_source(&source, 4, seed).expect("distances must evaluate");
+
+    assert_eq!(validate(9), validate(9));
+    assert_ne!(validate(9), validate(10));
+}
+
+#[rstest]
+#[case::empty(Vec::new())]
+#[case::single(vec![1.0])]
+fn sources_without_pairs_are_vacuously_valid(#[case] data: Vec<f32>) {
+    let report = validate_source(&Dummy::new(data), 16, 0).expect("nothing is evaluated");
+
+    assert!(report.is_valid());
+    assert_eq!(report.sampled_pairs(), 0);
+}
+
+#[rstest]
+fn source_errors_are_returned() {
+    let err = validate_source(&Truncated, 64, 0).expect_err("out-of-range points must fail");
+
+    assert!(matches!(err, DataSourceError::OutOfBounds { index: 3 }));
+}
+
+#[rstest]
+fn violations_describe_themselves() {
+    let violation = Violation::Asymmetric {
+        left: 1,
+        right: 4,
+        forward: 3.0,
+        backward: 6.0,
+    };
+
+    assert_eq!(violation.kind(), ViolationKind::Asymmetric);
+    assert_eq!(
+        violation.to_string(),
+        "d(1, 4) = 3 differs from d(4, 1) = 6"
+    );
+}
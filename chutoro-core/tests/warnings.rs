@@ -0,0 +1,162 @@
+//! Tests for the non-fatal warnings recorded on clustering results.
+#![cfg(feature = "cpu")]
+
+mod common;
+
+use std::num::NonZeroUsize;
+
+use chutoro_core::{
+    CandidateEdge, ChutoroBuilder, ClusteringResult, DataSource, DistanceCacheConfig,
+    DistancePolicy, EdgeHarvest, HnswParams, SampleSpec, Warning, WarningCode,
+};
+use common::Dummy;
+use rstest::{fixture, rstest};
+
+/// Two well-separated groups of 20 points each.
+#[fixture]
+fn groups() -> Dummy {
+    let near = (0..20).map(|i| i as f32 * 0.1);
+    let far = (0..20).map(|i| 100.0 + i as f32 * 0.1);
+    Dummy::new(near.chain(far).collect())
+}
+
+fn run(builder: ChutoroBuilder, source: &Dummy) -> ClusteringResult {
+    builder
+        .with_min_cluster_size(3)
+        .build()
+        .expect("configuration must be valid")
+        .run(source)
+        .expect("run must succeed")
+}
+
+fn codes(result: &ClusteringResult) -> Vec<WarningCode> {
+    result.warnings().iter().map(Warning::code).collect()
+}
+
+#[rstest]
+fn clean_runs_raise_no_warnings(groups: Dummy) {
+    let result = run(ChutoroBuilder::new(), &groups);
+
+    assert_eq!(result.warnings(), &[]);
+}
+
+#[rstest]
+#[case::clamp(DistancePolicy::ClampToMax, WarningCode::NonFiniteDistancesClamped)]
+#[case::skip(DistancePolicy::SkipEdge, WarningCode::NonFiniteEdgesSkipped)]
+fn lenient_policies_warn_with_the_replacement_count(
+    #[case] policy: DistancePolicy,
+    #[case] expected: WarningCode,
+) {
+    let mut values: Vec<f32> = (0..20).map(|i| i as f32 * 0.1).collect();
+    values.push(f32::NAN);
+    let source = Dummy::new(values);
+
+    let result = run(ChutoroBuilder::new().with_distance_policy(policy), &source);
+
+    let report = result.distance_policy().expect("lenient runs report");
+    // Skipping isolates the NaN point, so a connectivity warning may follow.
+    let Some(warning) = result.warnings().first() else {
+        panic!("lenient runs over NaN distances must warn");
+    };
+    assert_eq!(warning.code(), expected);
+    assert!(matches!(
+        warning,
+        Warning::NonFiniteDistancesClamped { count } | Warning::NonFiniteEdgesSkipped { count }
+            if *count == report.total()
+    ));
+}
+
+#[rstest]
+fn small_distance_caches_warn_of_pressure(groups: Dummy) {
+    let cache = DistanceCacheConfig::new(NonZeroUsize::new(4).expect("literal is non-zero"));
+    let params = HnswParams::default().with_distance_cache_config(cache);
+
+    let result = run(ChutoroBuilder::new().with_hnsw_params(params), &groups);
+
+    let Some(Warning::DistanceCachePressure {
+        evictions,
+        capacity,
+    }) = result.warnings().first()
+    else {
+        panic!("expected cache pressure, got {:?}", result.warnings());
+    };
+    assert_eq!(*capacity, 4);
+    assert!(*evictions >= 4);
+}
+
+#[rstest]
+fn disconnected_graphs_warn_of_their_components() {
+    let source = Dummy::new(vec![0.0, 1.0, 2.0, 3.0, 20.0, 21.0, 22.0, 23.0]);
+    // Every point's three nearest neighbours lie in its own group of four.
+    let edges = (0..source.len())
+        .flat_map(|point| {
+            let group = point / 4 * 4;
+            (group..group + 4)
+                .filter(move |&other| other != point)
+                .map(move |other| (point, other))
+        })
+        .enumerate()
+        .map(|(sequence, (point, other))| {
+            let distance = source.distance(point, other).expect("in range");
+            CandidateEdge::new(point, other, distance, sequence as u64)
+        })
+        .collect();
+    let chutoro = ChutoroBuilder::new()
+        .with_min_cluster_size(2)
+        .build()
+        .expect("configuration must be valid");
+
+    let result = chutoro
+        .cluster_from_knn_graph(source.len(), EdgeHarvest::new(edges))
+        .expect("graph must cluster");
+
+    assert_eq!(
+        result.warnings(),
+        &[Warning::DisconnectedComponents { components: 2 }]
+    );
+    assert_eq!(
+        result.warnings()[0].to_string(),
+        "the spanning forest has 2 components; clusters never span them"
+    );
+}
+
+#[rstest]
+#[case::report_only(false)]
+#[case::repair(true)]
+fn connectivity_warnings_match_the_report(#[case] repair: bool) {
+    let source = Dummy::new(
+        (0..24)
+            .map(|i| (i % 6) as f32 + (i / 6) as f32 * 50.0)
+            .collect(),
+    );
+
+    let result = run(
+        ChutoroBuilder::new().with_connect_components(repair),
+        &source,
+    );
+
+    let report = result.connectivity().expect("CPU runs report connectivity");
+    let expected = match (report.is_connected(), repair) {
+        (true, _) => vec![],
+        (false, false) => vec![WarningCode::DisconnectedComponents],
+        (false, true) => vec![WarningCode::ComponentsBridged],
+    };
+    assert_eq!(codes(&result), expected);
+}
+
+#[rstest]
+fn warnings_survive_persistence(groups: Dummy) {
+    let cache = DistanceCacheConfig::new(NonZeroUsize::new(4).expect("literal is non-zero"));
+    let params = HnswParams::default().with_distance_cache_config(cache);
+    let result = run(
+        ChutoroBuilder::new()
+            .with_hnsw_params(params)
+            .with_sample(SampleSpec::Fraction(0.5), 7),
+        &groups,
+    );
+    assert!(codes(&result).contains(&WarningCode::DistanceCachePressure));
+
+    let restored = ClusteringResult::from_bytes(&result.to_bytes()).expect("result must decode");
+
+    assert_eq!(restored.warnings(), result.warnings());
+}
@@ -1,5 +1,5 @@
 //! Errors emitted by dense matrix ingestion flows.
-use arrow_schema::{ArrowError, DataType};
+use arrow_schema::{ArrowError, DataType, Schema};
 use thiserror::Error;
 
 /// Variants cover the errors encountered when materializing dense matrices
@@ -7,13 +7,20 @@ use thiserror::Error;
 #[derive(Debug, Error)]
 pub enum DenseMatrixProviderError {
     /// A referenced column does not exist in the Arrow schema.
-    #[error("column `{column}` not found in Parquet schema")]
+    #[error("column `{column}` not found in schema")]
     ColumnNotFound {
         /// Name of the column that was missing from the schema.
         column: String,
+        /// Names of the columns the schema does contain, in schema order.
+        available: Vec<String>,
     },
+    /// No feature columns were requested.
+    #[error("at least one feature column is required")]
+    NoColumns,
     /// The Arrow column has an unexpected data type.
-    #[error("column `{column}` must be a FixedSizeList<Float32, _> but found {actual:?}")]
+    #[error(
+        "column `{column}` must be a FixedSizeList of floats or a float column but found {actual:?}"
+    )]
     InvalidColumnType {
         /// Name of the offending column.
         column: String,
@@ -31,12 +38,15 @@ pub enum DenseMatrixProviderError {
         /// Indicates whether the nested child field was nullable.
         nullable_child: bool,
     },
-    /// Fixed-size list columns must contain 32-bit floating point child values.
-    #[error("FixedSizeList child type must be Float32 but found {actual:?}")]
+    /// Fixed-size list columns must contain floating point child values.
+    #[error("FixedSizeList child type must be Float16, Float32, or Float64 but found {actual:?}")]
     InvalidListValueType {
         /// Actual child type discovered in the Arrow schema.
         actual: DataType,
     },
+    /// `Float64` values were found but lossy narrowing was not enabled.
+    #[error("Float64 values lose precision as f32; enable DenseIngestOptions::with_lossy_f64")]
+    LossyNarrowingDisabled,
     /// Fixed-size list column declared an invalid dimension.
     #[error("invalid FixedSizeList dimension {actual}")]
     InvalidDimension {
@@ -83,6 +93,46 @@ pub enum DenseMatrixProviderError {
         /// Dimension reported by the current batch.
         actual: usize,
     },
+    /// An `ndarray` view passed to `DenseMatrixProvider::try_from_ndarray`
+    /// did not store its rows contiguously.
+    #[error("ndarray view must be contiguous in standard (C-order) layout")]
+    NonContiguousArray,
+    /// A [`crate::MatrixLayout`] stride is shorter than the row or column it
+    /// steps over.
+    #[error("stride {stride} is shorter than the {minimum} contiguous elements it steps over")]
+    StrideTooShort {
+        /// Stride requested by the layout.
+        stride: usize,
+        /// Length of each contiguous row or column.
+        minimum: usize,
+    },
+    /// A buffer passed to `DenseMatrixProvider::from_parts_with_layout` ends
+    /// before the last element its layout describes.
+    #[error("layout needs at least {expected} values but {actual} were supplied")]
+    InsufficientValues {
+        /// Minimum buffer length the layout requires.
+        expected: usize,
+        /// Length of the buffer supplied.
+        actual: usize,
+    },
+    /// A vector passed to [`crate::FeatureScaling::apply`] had the wrong
+    /// number of features.
+    #[error("scaling expects {expected} features but the vector has {actual}")]
+    ScalingDimensionMismatch {
+        /// Number of features the scaling was fitted for.
+        expected: usize,
+        /// Length of the vector supplied.
+        actual: usize,
+    },
+    /// [`crate::Quantization::Product`] asked for a number of subspaces that
+    /// does not divide the row dimension.
+    #[error("{subspaces} subspaces do not evenly divide dimension {dimension}")]
+    InvalidSubspaceCount {
+        /// Dimensionality of each row.
+        dimension: usize,
+        /// Number of subspaces requested.
+        subspaces: usize,
+    },
     /// Wrapper around Arrow-specific ingestion failures.
     #[error("arrow error: {0}")]
     Arrow(#[from] ArrowError),
@@ -93,3 +143,18 @@ pub enum DenseMatrixProviderError {
     #[error("i/o error: {0}")]
     Io(#[from] std::io::Error),
 }
+
+impl DenseMatrixProviderError {
+    /// Reports that `column` is missing from `schema`, listing the columns it
+    /// does contain.
+    pub(crate) fn column_not_found(column: &str, schema: &Schema) -> Self {
+        Self::ColumnNotFound {
+            column: column.to_owned(),
+            available: schema
+                .fields()
+                .iter()
+                .map(|field| field.name().clone())
+                .collect(),
+        }
+    }
+}
@@ -1,8 +1,120 @@
 //! Helpers for ingesting fixed-size list arrays into dense buffers.
-use arrow_array::{Array, FixedSizeListArray, Float32Array};
+//!
+//! Rows may also be assembled from several feature columns, each either a
+//! `FixedSizeList<F, D>` or a scalar `F`, concatenated in order. `F` may be
+//! `Float16`, `Float32`, or `Float64`; values are converted to `f32` as they
+//! are copied.
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Float16Type, Float32Type, Float64Type};
+use arrow_array::{Array, ArrayRef, FixedSizeListArray};
 use arrow_schema::{DataType, Field};
 
 use crate::errors::DenseMatrixProviderError;
+use crate::options::DenseIngestOptions;
+
+/// Layout of a feature column contributing values to each dense row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ColumnShape {
+    /// A `FixedSizeList<F, D>` column contributing `D` values per row.
+    List(usize),
+    /// A float column contributing one value per row.
+    Scalar,
+}
+
+impl ColumnShape {
+    pub(crate) fn width(self) -> usize {
+        match self {
+            Self::List(dimension) => dimension,
+            Self::Scalar => 1,
+        }
+    }
+}
+
+/// A feature column from one record batch.
+pub(crate) struct FeatureColumn<'a> {
+    pub(crate) name: &'a str,
+    pub(crate) array: &'a ArrayRef,
+    pub(crate) shape: ColumnShape,
+}
+
+pub(crate) fn validate_feature_field(
+    field: &Field,
+    column: &str,
+    options: DenseIngestOptions,
+) -> Result<ColumnShape, DenseMatrixProviderError> {
+    check_narrowing(field.data_type(), options)?;
+    if !is_float(field.data_type()) {
+        return validate_fixed_size_list_field(field, column).map(ColumnShape::List);
+    }
+    if field.is_nullable() {
+        return Err(DenseMatrixProviderError::NullableField {
+            column: column.to_owned(),
+            nullable_child: false,
+        });
+    }
+    Ok(ColumnShape::Scalar)
+}
+
+/// Appends `rows` dense rows built by concatenating `columns` in order.
+pub(crate) fn append_feature_columns(
+    columns: &[FeatureColumn<'_>],
+    rows: usize,
+    start_row: usize,
+    out: &mut Vec<f32>,
+) -> Result<(), DenseMatrixProviderError> {
+    if let [column] = columns {
+        return append_feature_column(column, start_row, out);
+    }
+    let mut flattened = Vec::with_capacity(columns.len());
+    for column in columns {
+        let mut values = Vec::new();
+        append_feature_column(column, start_row, &mut values)?;
+        flattened.push((column.shape.width(), values));
+    }
+    let dimension: usize = flattened.iter().map(|(width, _)| width).sum();
+    let additional = rows
+        .checked_mul(dimension)
+        .ok_or(DenseMatrixProviderError::CapacityOverflow { rows, dimension })?;
+    out.reserve(additional);
+    for row in 0..rows {
+        for (width, values) in &flattened {
+            let start = row * width;
+            out.extend_from_slice(&values[start..start + width]);
+        }
+    }
+    Ok(())
+}
+
+fn append_feature_column(
+    column: &FeatureColumn<'_>,
+    start_row: usize,
+    out: &mut Vec<f32>,
+) -> Result<(), DenseMatrixProviderError> {
+    let invalid_type = || DenseMatrixProviderError::InvalidColumnType {
+        column: column.name.to_owned(),
+        actual: column.array.data_type().clone(),
+    };
+    match column.shape {
+        ColumnShape::List(dimension) => {
+            let list = column
+                .array
+                .as_any()
+                .downcast_ref::<FixedSizeListArray>()
+                .ok_or_else(invalid_type)?;
+            append_fixed_size_list_values(list, Some(dimension), start_row, out)?;
+        }
+        ColumnShape::Scalar => {
+            let floats = column.array;
+            if let Some(row) = (0..floats.len()).find(|&row| floats.is_null(row)) {
+                return Err(DenseMatrixProviderError::NullRow {
+                    row: start_row + row,
+                });
+            }
+            extend_as_f32(floats.as_ref(), out).ok_or_else(invalid_type)?;
+        }
+    }
+    Ok(())
+}
 
 pub(crate) fn validate_fixed_size_list_field(
     field: &Field,
@@ -16,7 +128,7 @@ pub(crate) fn validate_fixed_size_list_field(
                     nullable_child: child.is_nullable(),
                 });
             }
-            if child.data_type() != &DataType::Float32 {
+            if !is_float(child.data_type()) {
                 return Err(DenseMatrixProviderError::InvalidListValueType {
                     actual: child.data_type().clone(),
                 });
@@ -52,7 +164,7 @@ pub(crate) fn validate_fixed_size_list(
     array: &FixedSizeListArray,
 ) -> Result<usize, DenseMatrixProviderError> {
     let value_type = array.value_type();
-    if value_type != DataType::Float32 {
+    if !is_float(&value_type) {
         return Err(DenseMatrixProviderError::InvalidListValueType { actual: value_type });
     }
     usize::try_from(array.value_length()).map_err(|_| DenseMatrixProviderError::InvalidDimension {
@@ -76,12 +188,7 @@ pub(crate) fn copy_list_values(
         if array.is_null(row_index) {
             return Err(DenseMatrixProviderError::NullRow { row: absolute_row });
         }
-        let row = array.value(row_index);
-        let floats = row.as_any().downcast_ref::<Float32Array>().ok_or_else(|| {
-            DenseMatrixProviderError::InvalidListValueType {
-                actual: row.data_type().clone(),
-            }
-        })?;
+        let floats = array.value(row_index);
         if floats.len() != dimension {
             return Err(DenseMatrixProviderError::InvalidRowLength {
                 row: absolute_row,
@@ -98,10 +205,62 @@ pub(crate) fn copy_list_values(
                 value_index,
             });
         }
-        let values = floats.values().as_ref();
-        let start = floats.offset();
-        let end = start + dimension;
-        out.extend_from_slice(&values[start..end]);
+        extend_as_f32(floats.as_ref(), out).ok_or_else(|| {
+            DenseMatrixProviderError::InvalidListValueType {
+                actual: floats.data_type().clone(),
+            }
+        })?;
+    }
+    Ok(())
+}
+
+/// Rejects `Float64` values, bare or in a list, unless narrowing is enabled.
+pub(crate) fn check_narrowing(
+    data_type: &DataType,
+    options: DenseIngestOptions,
+) -> Result<(), DenseMatrixProviderError> {
+    let element = match data_type {
+        DataType::FixedSizeList(child, _) => child.data_type(),
+        other => other,
+    };
+    if element == &DataType::Float64 && !options.lossy_f64() {
+        return Err(DenseMatrixProviderError::LossyNarrowingDisabled);
     }
     Ok(())
 }
+
+fn is_float(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Float16 | DataType::Float32 | DataType::Float64
+    )
+}
+
+/// Appends every value of a float array to `out`, converting to `f32`.
+///
+/// Returns `None` when `values` is not a `Float16`, `Float32`, or `Float64`
+/// array. Null slots are copied as their backing value, so callers must reject
+/// nulls first.
+fn extend_as_f32(values: &dyn Array, out: &mut Vec<f32>) -> Option<()> {
+    match values.data_type() {
+        DataType::Float16 => out.extend(
+            values
+                .as_primitive_opt::<Float16Type>()?
+                .values()
+                .iter()
+                .map(|value| value.to_f32()),
+        ),
+        DataType::Float32 => {
+            out.extend_from_slice(values.as_primitive_opt::<Float32Type>()?.values());
+        }
+        DataType::Float64 => out.extend(
+            values
+                .as_primitive_opt::<Float64Type>()?
+                .values()
+                .iter()
+                .map(|&value| value as f32),
+        ),
+        _ => return None,
+    }
+    Some(())
+}
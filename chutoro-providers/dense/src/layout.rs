@@ -0,0 +1,191 @@
+//! Construction from flat buffers in BLAS-style layouts.
+//!
+//! Numerical code often holds matrices in column-major order, or pads each
+//! row or column to an aligned leading dimension. [`MatrixLayout`] describes
+//! such a buffer so [`DenseMatrixProvider::from_parts_with_layout`] can copy
+//! it into the provider's packed row-major storage in one pass, without the
+//! caller reshaping it first.
+
+use crate::errors::DenseMatrixProviderError;
+use crate::provider::DenseMatrixProvider;
+
+/// Order in which the elements of a matrix are stored.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Layout {
+    /// Each row is stored contiguously, one row after another (C order).
+    #[default]
+    RowMajor,
+    /// Each column is stored contiguously, one column after another
+    /// (Fortran order).
+    ColMajor,
+}
+
+/// Shape and memory layout of a flat matrix buffer.
+///
+/// The stride is the distance, in elements, between the starts of
+/// consecutive rows of a row-major buffer or consecutive columns of a
+/// column-major one: the leading dimension in BLAS terms. It defaults to the
+/// packed length of a row or column, and may be larger to skip padding.
+///
+/// # Examples
+/// ```
+/// use chutoro_providers_dense::{Layout, MatrixLayout};
+///
+/// let layout = MatrixLayout::new(3, 2, Layout::ColMajor).with_stride(4);
+/// assert_eq!(layout.stride(), 4);
+/// assert_eq!(MatrixLayout::new(3, 2, Layout::ColMajor).stride(), 3);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MatrixLayout {
+    rows: usize,
+    dimension: usize,
+    order: Layout,
+    stride: Option<usize>,
+}
+
+impl MatrixLayout {
+    /// Describes a packed matrix of `rows` points with `dimension` features
+    /// stored in `order`.
+    #[must_use]
+    pub const fn new(rows: usize, dimension: usize, order: Layout) -> Self {
+        Self {
+            rows,
+            dimension,
+            order,
+            stride: None,
+        }
+    }
+
+    /// Sets the distance between the starts of consecutive rows (row-major)
+    /// or columns (column-major).
+    #[must_use]
+    pub const fn with_stride(mut self, stride: usize) -> Self {
+        self.stride = Some(stride);
+        self
+    }
+
+    /// Returns the number of rows.
+    #[rustfmt::skip]
+    #[must_use]
+    pub const fn rows(&self) -> usize { self.rows }
+
+    /// Returns the number of features per row.
+    #[rustfmt::skip]
+    #[must_use]
+    pub const fn dimension(&self) -> usize { self.dimension }
+
+    /// Returns the element order.
+    #[rustfmt::skip]
+    #[must_use]
+    pub const fn order(&self) -> Layout { self.order }
+
+    /// Returns the stride, defaulting to the packed length of a row or
+    /// column.
+    #[must_use]
+    pub const fn stride(&self) -> usize {
+        match self.stride {
+            Some(stride) => stride,
+            None => self.contiguous_len(),
+        }
+    }
+
+    /// Returns the number of elements stored contiguously: a row's features
+    /// in row-major order, a column's rows in column-major order.
+    const fn contiguous_len(&self) -> usize {
+        match self.order {
+            Layout::RowMajor => self.dimension,
+            Layout::ColMajor => self.rows,
+        }
+    }
+
+    /// Returns the number of contiguous runs: rows in row-major order,
+    /// columns in column-major order.
+    const fn run_count(&self) -> usize {
+        match self.order {
+            Layout::RowMajor => self.rows,
+            Layout::ColMajor => self.dimension,
+        }
+    }
+
+    /// Returns the smallest buffer length that holds every element.
+    fn required_len(&self) -> Result<usize, DenseMatrixProviderError> {
+        let (runs, run_len) = (self.run_count(), self.contiguous_len());
+        if runs == 0 || run_len == 0 {
+            return Ok(0);
+        }
+        let stride = self.stride();
+        if stride < run_len {
+            return Err(DenseMatrixProviderError::StrideTooShort {
+                stride,
+                minimum: run_len,
+            });
+        }
+        (runs - 1)
+            .checked_mul(stride)
+            .and_then(|offset| offset.checked_add(run_len))
+            .ok_or(DenseMatrixProviderError::CapacityOverflow {
+                rows: self.rows,
+                dimension: self.dimension,
+            })
+    }
+}
+
+impl DenseMatrixProvider {
+    /// Copies a matrix stored in `values` as described by `layout`.
+    ///
+    /// Packed row-major buffers are kept as they are; other layouts are
+    /// copied once into packed row-major order. Elements beyond the last row
+    /// or column, and any padding between them, are ignored.
+    ///
+    /// # Errors
+    /// Returns [`DenseMatrixProviderError::StrideTooShort`] when the stride
+    /// is shorter than a row (row-major) or column (column-major),
+    /// [`DenseMatrixProviderError::InsufficientValues`] when `values` ends
+    /// before the last element, and
+    /// [`DenseMatrixProviderError::CapacityOverflow`] when the layout spans
+    /// more elements than can be addressed.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::DataSource;
+    /// use chutoro_providers_dense::{DenseMatrixProvider, Layout, MatrixLayout};
+    ///
+    /// // Two points, (0, 3) and (4, 0), as the columns of a padded buffer.
+    /// let values = vec![0.0, 4.0, -1.0, 3.0, 0.0, -1.0];
+    /// let layout = MatrixLayout::new(2, 2, Layout::ColMajor).with_stride(3);
+    /// let provider = DenseMatrixProvider::from_parts_with_layout("blas", values, layout)?;
+    ///
+    /// assert_eq!(provider.data(), &[0.0, 3.0, 4.0, 0.0]);
+    /// assert_eq!(provider.distance(0, 1)?, 5.0);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_parts_with_layout(
+        name: impl Into<String>,
+        mut values: Vec<f32>,
+        layout: MatrixLayout,
+    ) -> Result<Self, DenseMatrixProviderError> {
+        let required = layout.required_len()?;
+        if values.len() < required {
+            return Err(DenseMatrixProviderError::InsufficientValues {
+                expected: required,
+                actual: values.len(),
+            });
+        }
+        let (rows, dimension, stride) = (layout.rows, layout.dimension, layout.stride());
+        let packed = match layout.order {
+            Layout::RowMajor if stride == dimension => {
+                values.truncate(rows * dimension);
+                values
+            }
+            Layout::RowMajor => (0..rows)
+                .flat_map(|row| &values[row * stride..row * stride + dimension])
+                .copied()
+                .collect(),
+            Layout::ColMajor => (0..rows)
+                .flat_map(|row| (0..dimension).map(move |column| column * stride + row))
+                .map(|index| values[index])
+                .collect(),
+        };
+        Ok(Self::from_parts(name, rows, dimension, packed))
+    }
+}
@@ -1,5 +1,15 @@
 //! Dense providers for f32 vectors backed by contiguous storage.
 //!
+//! Rows load from Arrow arrays, Parquet files, Arrow IPC streams, any Arrow
+//! record-batch reader, flat buffers in row- or column-major order with any
+//! stride, or, with the `polars` and `ndarray` features, Polars data frames
+//! and `ndarray` matrices.
+//! Feature values may be `Float16`, `Float32`, or, with
+//! [`DenseIngestOptions::with_lossy_f64`], `Float64`; all are stored as
+//! `f32`. Large matrices can be compressed with
+//! [`DenseMatrixProvider::quantize`]. With the `predict` feature, a
+//! `DensePredictor` labels Arrow batches of new vectors against a finished
+//! clustering.
 #![cfg_attr(
     all(feature = "nightly_portable_simd", nightly),
     feature(portable_simd)
@@ -7,12 +17,29 @@
 
 mod errors;
 mod ingest;
+mod layout;
+#[cfg(feature = "ndarray")]
+mod ndarray_matrix;
+mod normalization;
+mod options;
+#[cfg(feature = "polars")]
+mod polars;
+#[cfg(feature = "predict")]
+mod predict;
 mod provider;
+mod quantization;
 mod simd;
 mod source;
+mod stream;
 
 pub use errors::DenseMatrixProviderError;
+pub use layout::{Layout, MatrixLayout};
+pub use normalization::{FeatureScaling, Normalization};
+pub use options::DenseIngestOptions;
+#[cfg(feature = "predict")]
+pub use predict::{DensePredictor, PredictError};
 pub use provider::DenseMatrixProvider;
+pub use quantization::{Quantization, QuantizedMatrixProvider};
 pub use source::DenseSource;
 
 #[cfg(test)]
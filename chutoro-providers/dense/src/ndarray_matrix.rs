@@ -0,0 +1,65 @@
+//! Conversions from `ndarray` matrices.
+//!
+//! Each row of a two-dimensional array becomes one point. Views in standard
+//! (C-order) layout are copied with a single `memcpy`; other layouts, such as
+//! transposed or sliced views, are copied element by element in row order.
+use ndarray::ArrayView2;
+
+use crate::errors::DenseMatrixProviderError;
+use crate::provider::DenseMatrixProvider;
+
+impl DenseMatrixProvider {
+    /// Copies a two-dimensional `ndarray` view, one row per point.
+    ///
+    /// Any memory layout is accepted. Use [`Self::try_from_ndarray`] to
+    /// insist on contiguous rows and avoid the strided copy.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_providers_dense::DenseMatrixProvider;
+    /// use ndarray::array;
+    ///
+    /// let matrix = array![[0.0_f32, 1.0], [2.0, 3.0]];
+    /// let provider = DenseMatrixProvider::from_ndarray("matrix", matrix.t());
+    /// assert_eq!(provider.data(), &[0.0, 2.0, 1.0, 3.0]);
+    /// ```
+    #[must_use]
+    pub fn from_ndarray(name: impl Into<String>, matrix: ArrayView2<'_, f32>) -> Self {
+        let (rows, dimension) = matrix.dim();
+        let values = matrix
+            .as_slice()
+            .map_or_else(|| matrix.iter().copied().collect(), <[f32]>::to_vec);
+        Self::from_parts(name, rows, dimension, values)
+    }
+
+    /// Copies a two-dimensional `ndarray` view whose rows are contiguous.
+    ///
+    /// # Errors
+    /// Returns [`DenseMatrixProviderError::NonContiguousArray`] when `matrix`
+    /// is not in standard (C-order) layout.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_providers_dense::{DenseMatrixProvider, DenseMatrixProviderError};
+    /// use ndarray::array;
+    ///
+    /// let matrix = array![[0.0_f32, 1.0], [2.0, 3.0]];
+    /// let provider = DenseMatrixProvider::try_from_ndarray("matrix", matrix.view())?;
+    /// assert_eq!(provider.data(), &[0.0, 1.0, 2.0, 3.0]);
+    /// assert!(matches!(
+    ///     DenseMatrixProvider::try_from_ndarray("matrix", matrix.t()),
+    ///     Err(DenseMatrixProviderError::NonContiguousArray)
+    /// ));
+    /// # Ok::<(), DenseMatrixProviderError>(())
+    /// ```
+    pub fn try_from_ndarray(
+        name: impl Into<String>,
+        matrix: ArrayView2<'_, f32>,
+    ) -> Result<Self, DenseMatrixProviderError> {
+        let (rows, dimension) = matrix.dim();
+        let values = matrix
+            .as_slice()
+            .ok_or(DenseMatrixProviderError::NonContiguousArray)?;
+        Ok(Self::from_parts(name, rows, dimension, values.to_vec()))
+    }
+}
@@ -0,0 +1,167 @@
+//! Per-feature scaling applied to dense matrices after ingestion.
+//!
+//! Euclidean distances are dominated by whichever features have the widest
+//! range, so unscaled inputs can silently ruin a clustering. A
+//! [`Normalization`] fits per-feature statistics over every row, and the
+//! resulting [`FeatureScaling`] is kept so later query vectors can be mapped
+//! into the same space.
+
+use crate::errors::DenseMatrixProviderError;
+
+/// How [`FeatureScaling::fit`] rescales each feature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Normalization {
+    /// Standardize each feature to zero mean and unit (population) variance.
+    ZScore,
+    /// Rescale each feature so its observed range maps onto `[0, 1]`.
+    MinMax,
+}
+
+/// Fitted per-feature statistics mapping each value `x` to
+/// `(x - offset) / scale`.
+///
+/// Features with no spread (a constant column) use a scale of `1`, so they
+/// map to zero instead of dividing by zero.
+///
+/// # Examples
+/// ```
+/// use chutoro_providers_dense::{FeatureScaling, Normalization};
+///
+/// let scaling = FeatureScaling::fit(Normalization::MinMax, &[0.0, 10.0, 4.0, 30.0], 2);
+/// let mut query = [2.0, 20.0];
+/// scaling.apply(&mut query)?;
+/// assert_eq!(query, [0.5, 0.5]);
+/// # Ok::<(), chutoro_providers_dense::DenseMatrixProviderError>(())
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct FeatureScaling {
+    method: Normalization,
+    offsets: Vec<f32>,
+    scales: Vec<f32>,
+}
+
+impl FeatureScaling {
+    /// Fits `method` over the row-major `values`, each row holding
+    /// `dimension` features.
+    ///
+    /// Statistics are accumulated in `f64`. An empty matrix yields the
+    /// identity transform.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_providers_dense::{FeatureScaling, Normalization};
+    ///
+    /// let scaling = FeatureScaling::fit(Normalization::ZScore, &[1.0, 3.0], 1);
+    /// assert_eq!(scaling.offsets(), &[2.0]);
+    /// assert_eq!(scaling.scales(), &[1.0]);
+    /// ```
+    #[must_use]
+    pub fn fit(method: Normalization, values: &[f32], dimension: usize) -> Self {
+        let (offsets, scales) = (0..dimension)
+            .map(|feature| {
+                let column = values.iter().skip(feature).step_by(dimension.max(1));
+                let (offset, spread) = match method {
+                    Normalization::ZScore => mean_and_deviation(column),
+                    Normalization::MinMax => min_and_range(column),
+                };
+                let scale = if spread > 0.0 && spread.is_finite() {
+                    spread
+                } else {
+                    1.0
+                };
+                (offset as f32, scale as f32)
+            })
+            .unzip();
+        Self {
+            method,
+            offsets,
+            scales,
+        }
+    }
+
+    /// Returns the normalization the statistics were fitted for.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn method(&self) -> Normalization { self.method }
+
+    /// Returns the number of features the scaling expects.
+    #[must_use]
+    pub fn dimension(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// Returns the per-feature offsets (mean or minimum).
+    #[must_use]
+    pub fn offsets(&self) -> &[f32] {
+        &self.offsets
+    }
+
+    /// Returns the per-feature scales (standard deviation or range).
+    #[must_use]
+    pub fn scales(&self) -> &[f32] {
+        &self.scales
+    }
+
+    /// Scales `vector` in place, for example a query embedding that must be
+    /// compared with a normalized provider.
+    ///
+    /// # Errors
+    /// Returns [`DenseMatrixProviderError::ScalingDimensionMismatch`] when
+    /// `vector` does not have [`Self::dimension`] features.
+    pub fn apply(&self, vector: &mut [f32]) -> Result<(), DenseMatrixProviderError> {
+        if vector.len() != self.dimension() {
+            return Err(DenseMatrixProviderError::ScalingDimensionMismatch {
+                expected: self.dimension(),
+                actual: vector.len(),
+            });
+        }
+        self.apply_rows(vector);
+        Ok(())
+    }
+
+    /// Scales every row of a row-major matrix in place.
+    pub(crate) fn apply_rows(&self, values: &mut [f32]) {
+        for row in values.chunks_exact_mut(self.dimension().max(1)) {
+            for ((value, offset), scale) in row.iter_mut().zip(&self.offsets).zip(&self.scales) {
+                *value = (*value - offset) / scale;
+            }
+        }
+    }
+
+    /// Reverses [`Self::apply_rows`], restoring the original feature values.
+    pub(crate) fn invert_rows(&self, values: &mut [f32]) {
+        for row in values.chunks_exact_mut(self.dimension().max(1)) {
+            for ((value, offset), scale) in row.iter_mut().zip(&self.offsets).zip(&self.scales) {
+                *value = *value * scale + offset;
+            }
+        }
+    }
+}
+
+/// Returns the mean and population standard deviation using Welford's
+/// update, which stays stable when the mean dwarfs the spread.
+fn mean_and_deviation<'a>(column: impl Iterator<Item = &'a f32>) -> (f64, f64) {
+    let (count, mean, squares) = column.fold((0_u64, 0.0_f64, 0.0_f64), |acc, &value| {
+        let (count, mean, squares) = acc;
+        let value = f64::from(value);
+        let count = count + 1;
+        let delta = value - mean;
+        let mean = mean + delta / count as f64;
+        (count, mean, squares + delta * (value - mean))
+    });
+    if count == 0 {
+        return (0.0, 1.0);
+    }
+    (mean, (squares / count as f64).sqrt())
+}
+
+fn min_and_range<'a>(column: impl Iterator<Item = &'a f32>) -> (f64, f64) {
+    let bounds = column.fold(None, |bounds: Option<(f32, f32)>, &value| {
+        Some(bounds.map_or((value, value), |(low, high)| {
+            (low.min(value), high.max(value))
+        }))
+    });
+    bounds.map_or((0.0, 1.0), |(low, high)| {
+        (f64::from(low), f64::from(high) - f64::from(low))
+    })
+}
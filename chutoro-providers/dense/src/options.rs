@@ -0,0 +1,33 @@
+//! Options controlling how feature values are converted during ingestion.
+
+/// Ingestion options for [`crate::DenseMatrixProvider`].
+///
+/// `Float16` and `Float32` values are always accepted because widening to
+/// `f32` is exact. `Float64` values lose precision when narrowed, so they are
+/// rejected unless [`Self::with_lossy_f64`] opts in.
+///
+/// # Examples
+/// ```
+/// use chutoro_providers_dense::DenseIngestOptions;
+///
+/// let options = DenseIngestOptions::default().with_lossy_f64(true);
+/// assert!(options.lossy_f64());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DenseIngestOptions {
+    lossy_f64: bool,
+}
+
+impl DenseIngestOptions {
+    /// Allows `Float64` columns, narrowing each value to the nearest `f32`.
+    #[must_use]
+    pub const fn with_lossy_f64(mut self, allow: bool) -> Self {
+        self.lossy_f64 = allow;
+        self
+    }
+
+    /// Returns whether `Float64` values may be narrowed to `f32`.
+    #[rustfmt::skip]
+    #[must_use]
+    pub const fn lossy_f64(&self) -> bool { self.lossy_f64 }
+}
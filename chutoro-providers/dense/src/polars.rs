@@ -0,0 +1,200 @@
+//! Ingestion from Polars data frames.
+//!
+//! Polars stores columns as chunks of Arrow-compatible arrays. Each chunk is
+//! handed to `arrow-rs` through the Arrow C Data Interface, which shares the
+//! chunk's buffers instead of copying them, so the only copy is the one into
+//! the provider's row-major buffer. Columns whose chunk boundaries differ are
+//! rechunked first, which copies them once.
+use arrow_array::ffi::{FFI_ArrowArray, FFI_ArrowSchema, from_ffi_and_data_type};
+use arrow_array::{ArrayRef, make_array};
+use arrow_schema::{DataType, Field};
+use polars_arrow::ffi::{export_array_to_c, export_field_to_c};
+use polars_core::prelude::{CompatLevel, DataFrame, PlSmallStr, Series};
+
+use crate::errors::DenseMatrixProviderError;
+use crate::ingest::{ColumnShape, FeatureColumn, append_feature_columns, validate_feature_field};
+use crate::options::DenseIngestOptions;
+use crate::provider::DenseMatrixProvider;
+
+impl DenseMatrixProvider {
+    /// Loads data from one column of a Polars [`DataFrame`].
+    ///
+    /// The column is an `Array(F, D)` or an `F` column, where `F` is
+    /// `Float32`.
+    ///
+    /// # Errors
+    /// Returns the errors of [`Self::try_from_polars_columns`].
+    pub fn try_from_polars(
+        name: impl Into<String>,
+        frame: &DataFrame,
+        column: &str,
+    ) -> Result<Self, DenseMatrixProviderError> {
+        Self::try_from_polars_columns(name, frame, &[column])
+    }
+
+    /// Loads data from several columns of a Polars [`DataFrame`],
+    /// concatenating them per row as described in
+    /// [`Self::try_from_parquet_columns`].
+    ///
+    /// Polars does not record nullability in its schema, so null rows and
+    /// values are rejected as they are found rather than up front.
+    ///
+    /// # Errors
+    /// Returns [`DenseMatrixProviderError::NoColumns`] when `columns` is
+    /// empty, [`DenseMatrixProviderError::ColumnNotFound`] when a column is
+    /// missing from `frame`, [`DenseMatrixProviderError::Arrow`] when a chunk
+    /// cannot be exchanged with `arrow-rs`, and the usual type, null, and
+    /// dimension errors for each column.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_providers_dense::DenseMatrixProvider;
+    /// use polars_core::prelude::*;
+    ///
+    /// let frame = df!("x" => [0.0_f32, 1.0], "y" => [2.0_f32, 3.0])?;
+    ///
+    /// let provider = DenseMatrixProvider::try_from_polars_columns("frame", &frame, &["x", "y"])?;
+    /// assert_eq!(provider.data(), &[0.0, 2.0, 1.0, 3.0]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_from_polars_columns(
+        name: impl Into<String>,
+        frame: &DataFrame,
+        columns: &[&str],
+    ) -> Result<Self, DenseMatrixProviderError> {
+        Self::try_from_polars_columns_with_options(
+            name,
+            frame,
+            columns,
+            DenseIngestOptions::default(),
+        )
+    }
+
+    /// Loads data from several columns of a Polars [`DataFrame`], applying
+    /// `options` as described in [`Self::try_from_parquet_columns_with_options`].
+    ///
+    /// # Errors
+    /// Returns the errors of [`Self::try_from_polars_columns`], plus
+    /// [`DenseMatrixProviderError::LossyNarrowingDisabled`] when a column
+    /// holds `Float64` values and `options` does not allow narrowing.
+    pub fn try_from_polars_columns_with_options(
+        name: impl Into<String>,
+        frame: &DataFrame,
+        columns: &[&str],
+        options: DenseIngestOptions,
+    ) -> Result<Self, DenseMatrixProviderError> {
+        if columns.is_empty() {
+            return Err(DenseMatrixProviderError::NoColumns);
+        }
+        let series = aligned_series(frame, columns)?;
+        let data_types = series
+            .iter()
+            .map(import_data_type)
+            .collect::<Result<Vec<_>, _>>()?;
+        let shapes = columns
+            .iter()
+            .zip(&data_types)
+            .map(|(&column, data_type)| validate_polars_field(column, data_type, options))
+            .collect::<Result<Vec<_>, _>>()?;
+        let dimension = shapes.iter().map(|shape| shape.width()).sum();
+        let chunks = series.first().map_or(0, |series| series.n_chunks());
+        let mut values = Vec::new();
+        let mut rows = 0_usize;
+        for chunk in 0..chunks {
+            let arrays = series
+                .iter()
+                .zip(&data_types)
+                .map(|(series, data_type)| import_chunk(series, chunk, data_type))
+                .collect::<Result<Vec<_>, _>>()?;
+            let features: Vec<_> = columns
+                .iter()
+                .zip(&arrays)
+                .zip(&shapes)
+                .map(|((name, array), &shape)| FeatureColumn { name, array, shape })
+                .collect();
+            let chunk_rows = arrays.first().map_or(0, |array| array.len());
+            append_feature_columns(&features, chunk_rows, rows, &mut values)?;
+            rows += chunk_rows;
+        }
+        Ok(Self::from_parts(name, rows, dimension, values))
+    }
+}
+
+/// Looks up each requested column, rechunking them all when their chunk
+/// boundaries differ so chunk `i` of every column covers the same rows.
+fn aligned_series(
+    frame: &DataFrame,
+    columns: &[&str],
+) -> Result<Vec<Series>, DenseMatrixProviderError> {
+    let series = columns
+        .iter()
+        .map(|&column| {
+            frame
+                .column(column)
+                .map(|found| found.as_materialized_series().clone())
+                .map_err(|_| DenseMatrixProviderError::ColumnNotFound {
+                    column: column.to_owned(),
+                    available: frame
+                        .get_column_names()
+                        .into_iter()
+                        .map(ToString::to_string)
+                        .collect(),
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let aligned = series
+        .windows(2)
+        .all(|pair| pair[0].chunk_lengths().eq(pair[1].chunk_lengths()));
+    if aligned {
+        Ok(series)
+    } else {
+        Ok(series.iter().map(|series| series.rechunk()).collect())
+    }
+}
+
+/// Validates a column's layout from its Arrow type.
+///
+/// Fields are declared non-nullable because Polars does not track
+/// nullability; the row copy rejects any nulls it finds instead.
+fn validate_polars_field(
+    column: &str,
+    data_type: &DataType,
+    options: DenseIngestOptions,
+) -> Result<ColumnShape, DenseMatrixProviderError> {
+    let data_type = match data_type {
+        DataType::FixedSizeList(child, width) => {
+            DataType::FixedSizeList(child.as_ref().clone().with_nullable(false).into(), *width)
+        }
+        other => other.clone(),
+    };
+    validate_feature_field(&Field::new(column, data_type, false), column, options)
+}
+
+/// Returns the Arrow type `series` exports its chunks as.
+fn import_data_type(series: &Series) -> Result<DataType, DenseMatrixProviderError> {
+    let field = series
+        .dtype()
+        .to_arrow_field(PlSmallStr::EMPTY, CompatLevel::newest());
+    let mut exported = export_field_to_c(&field);
+    // SAFETY: both crates define this struct as the `#[repr(C)]` layout of
+    // the Arrow C Data Interface. `from_raw` moves it out and leaves a
+    // released one behind, so Polars' drop glue does nothing.
+    let schema = unsafe { FFI_ArrowSchema::from_raw((&raw mut exported).cast()) };
+    Ok(Field::try_from(&schema)?.data_type().clone())
+}
+
+/// Shares one chunk of `series` with `arrow-rs` without copying its buffers.
+fn import_chunk(
+    series: &Series,
+    chunk: usize,
+    data_type: &DataType,
+) -> Result<ArrayRef, DenseMatrixProviderError> {
+    let mut exported = export_array_to_c(series.to_arrow(chunk, CompatLevel::newest()));
+    // SAFETY: as in `import_data_type`; `arrow-rs` takes over releasing the
+    // shared buffers when the imported array is dropped.
+    let array = unsafe { FFI_ArrowArray::from_raw((&raw mut exported).cast()) };
+    // SAFETY: the chunk was exported as `data_type` by the same Polars
+    // version that reported it.
+    let data = unsafe { from_ffi_and_data_type(array, data_type.clone()) }?;
+    Ok(make_array(data))
+}
@@ -0,0 +1,234 @@
+//! Scoring Arrow batches of new vectors against a finished clustering.
+//!
+//! Scoring services receive new data as record batches and want labels back
+//! in the same form. Crossing a language boundary once per point dominates
+//! the cost of a nearest-neighbour search, so a whole batch is read into one
+//! buffer, appended behind the training rows, and searched against the
+//! retained HNSW index with [`Chutoro::predict`].
+use std::sync::Arc;
+
+use arrow_array::{
+    ArrayRef, Float32Array, RecordBatch, RecordBatchIterator, StructArray, UInt64Array,
+};
+use arrow_schema::{DataType, Field};
+use chutoro_core::{
+    Chutoro, ChutoroError, ClusteringResult, DataSource, DataSourceError, MetricDescriptor,
+    Prediction,
+};
+use thiserror::Error;
+
+use crate::errors::DenseMatrixProviderError;
+use crate::options::DenseIngestOptions;
+use crate::provider::DenseMatrixProvider;
+use crate::simd;
+
+/// Errors raised by [`DensePredictor::predict_batch_arrow`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum PredictError {
+    /// The query column could not be read as dense vectors.
+    #[error(transparent)]
+    Ingest(#[from] DenseMatrixProviderError),
+    /// The query vectors have a different width from the training rows.
+    #[error("query vectors have {actual} features but the training rows have {expected}")]
+    DimensionMismatch {
+        /// Width of the training rows.
+        expected: usize,
+        /// Width of the query vectors.
+        actual: usize,
+    },
+    /// The search against the retained index failed.
+    #[error(transparent)]
+    Search(#[from] ChutoroError),
+}
+
+/// A clustering of dense rows, ready to label new vectors.
+///
+/// The [`Chutoro`] must have been built with the prebuilt index that
+/// clustered `training`, so new vectors are searched against it rather than
+/// re-indexed.
+///
+/// # Examples
+/// ```
+/// use std::sync::Arc;
+///
+/// use arrow_array::{
+///     Array, ArrayRef, FixedSizeListArray, Float32Array, RecordBatch, UInt64Array,
+/// };
+/// use arrow_schema::{DataType, Field, Schema};
+/// use chutoro_core::{ChutoroBuilder, CpuHnsw, HnswParams};
+/// use chutoro_providers_dense::{DenseMatrixProvider, DensePredictor};
+///
+/// fn vectors(values: Vec<f32>) -> FixedSizeListArray {
+///     let item = Arc::new(Field::new("item", DataType::Float32, false));
+///     FixedSizeListArray::new(item, 1, Arc::new(Float32Array::from(values)), None)
+/// }
+///
+/// let training = DenseMatrixProvider::try_from_fixed_size_list(
+///     "train",
+///     &vectors(vec![0.0, 0.1, 0.2, 10.0, 10.1, 10.2]),
+/// )?;
+/// let (index, harvest) = CpuHnsw::build_with_edges(&training, HnswParams::new(2, 8)?)?;
+/// let chutoro = ChutoroBuilder::new()
+///     .with_min_cluster_size(2)
+///     .with_prebuilt_index(Arc::new(index), harvest)
+///     .build()?;
+/// let clustering = chutoro.run(&training)?;
+///
+/// let queries = vectors(vec![0.05, 10.05]);
+/// let schema = Arc::new(Schema::new(vec![Field::new(
+///     "embedding",
+///     queries.data_type().clone(),
+///     false,
+/// )]));
+/// let batch = RecordBatch::try_new(schema, vec![Arc::new(queries) as ArrayRef])?;
+///
+/// let predictor = DensePredictor::new(&chutoro, &training, &clustering);
+/// let predictions = predictor.predict_batch_arrow(&batch, "embedding")?;
+/// let labels = predictions
+///     .column_by_name("label")
+///     .and_then(|column| column.as_any().downcast_ref::<UInt64Array>())
+///     .expect("labels are UInt64");
+/// assert_ne!(labels.value(0), labels.value(1));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct DensePredictor<'a> {
+    chutoro: &'a Chutoro,
+    training: &'a DenseMatrixProvider,
+    clustering: &'a ClusteringResult,
+}
+
+impl<'a> DensePredictor<'a> {
+    /// Pairs the training rows with the clustering `chutoro` produced for
+    /// them.
+    #[must_use]
+    pub fn new(
+        chutoro: &'a Chutoro,
+        training: &'a DenseMatrixProvider,
+        clustering: &'a ClusteringResult,
+    ) -> Self {
+        Self {
+            chutoro,
+            training,
+            clustering,
+        }
+    }
+
+    /// Predicts a label and strength for each row of `column` in `batch`.
+    ///
+    /// The column takes the layouts accepted by
+    /// [`DenseMatrixProvider::try_from_record_batch_reader`], and vectors are
+    /// scaled like the training rows when those were normalized. The result
+    /// is a struct array with a nullable `label` column of `UInt64`, null for
+    /// noise, and a `strength` column of `Float32`, one row per input row.
+    ///
+    /// # Errors
+    /// Returns [`PredictError::Ingest`] when the column is missing or not a
+    /// float vector column, [`PredictError::DimensionMismatch`] when its width
+    /// differs from the training rows, and [`PredictError::Search`] when the
+    /// [`Chutoro`] has no prebuilt index covering the training rows.
+    pub fn predict_batch_arrow(
+        &self,
+        batch: &RecordBatch,
+        column: &str,
+    ) -> Result<StructArray, PredictError> {
+        let reader = RecordBatchIterator::new([Ok(batch.clone())], batch.schema());
+        let options = DenseIngestOptions::default().with_lossy_f64(true);
+        let queries = DenseMatrixProvider::try_from_record_batch_reader(
+            "queries",
+            reader,
+            &[column],
+            options,
+        )?;
+        let source = QueriedRows::new(self.training, queries)?;
+        let trained = self.training.len();
+        let predictions = self
+            .chutoro
+            .predict(&source, self.clustering, trained..source.len())?;
+        Ok(into_struct_array(&predictions))
+    }
+}
+
+/// Training rows followed by a batch of query rows.
+struct QueriedRows<'a> {
+    training: &'a DenseMatrixProvider,
+    queries: Vec<f32>,
+    query_rows: usize,
+    dimension: usize,
+}
+
+impl<'a> QueriedRows<'a> {
+    fn new(
+        training: &'a DenseMatrixProvider,
+        queries: DenseMatrixProvider,
+    ) -> Result<Self, PredictError> {
+        let (expected, actual) = (training.dimension(), queries.dimension());
+        if queries.len() > 0 && actual != expected {
+            return Err(PredictError::DimensionMismatch { expected, actual });
+        }
+        let mut values = queries.data().to_vec();
+        if let Some(scaling) = training.scaling() {
+            scaling.apply_rows(&mut values);
+        }
+        Ok(Self {
+            training,
+            queries: values,
+            query_rows: queries.len(),
+            dimension: expected,
+        })
+    }
+
+    fn row(&self, index: usize) -> Result<&[f32], DataSourceError> {
+        let (values, row) = match index.checked_sub(self.training.len()) {
+            Some(query) => (self.queries.as_slice(), query),
+            None => (self.training.data(), index),
+        };
+        let start = row.saturating_mul(self.dimension);
+        values
+            .get(start..start.saturating_add(self.dimension))
+            .ok_or(DataSourceError::OutOfBounds { index })
+    }
+}
+
+impl DataSource for QueriedRows<'_> {
+    fn len(&self) -> usize {
+        self.training.len() + self.query_rows
+    }
+
+    fn name(&self) -> &str {
+        self.training.name()
+    }
+
+    fn metric_descriptor(&self) -> MetricDescriptor {
+        self.training.metric_descriptor()
+    }
+
+    fn dimension_hint(&self) -> Option<usize> {
+        Some(self.dimension)
+    }
+
+    fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+        let (a, b) = (self.row(i)?, self.row(j)?);
+        Ok(simd::euclidean_distance(simd::RowSlice::new(a), simd::RowSlice::new(b)).get())
+    }
+}
+
+/// Packs predictions into `label` and `strength` columns.
+fn into_struct_array(predictions: &[Prediction]) -> StructArray {
+    let labels: UInt64Array = predictions
+        .iter()
+        .map(|prediction| prediction.label().map(|label| label.get()))
+        .collect();
+    let strengths = Float32Array::from_iter_values(predictions.iter().map(Prediction::strength));
+    StructArray::from(vec![
+        (
+            Arc::new(Field::new("label", DataType::UInt64, true)),
+            Arc::new(labels) as ArrayRef,
+        ),
+        (
+            Arc::new(Field::new("strength", DataType::Float32, false)),
+            Arc::new(strengths) as ArrayRef,
+        ),
+    ])
+}
@@ -0,0 +1,13 @@
+//! Keys that let deduplicating runs find exactly repeated rows.
+
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// Hashes the bit pattern of every value in `row`, so rows share a key only
+/// when each value matches exactly.
+pub(super) fn row_key(row: &[f32]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for value in row {
+        value.to_bits().hash(&mut hasher);
+    }
+    hasher.finish()
+}
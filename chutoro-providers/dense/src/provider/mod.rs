@@ -0,0 +1,387 @@
+//! Dense matrix provider implementation and ingestion utilities.
+
+mod dedupe;
+mod scaling;
+
+use std::{fs::File, path::Path};
+
+use arrow_array::{Array, FixedSizeListArray};
+
+use chutoro_core::{DataSource, DataSourceError, IdMap, MetricClass, MetricDescriptor, RowIdError};
+use parquet::arrow::{ProjectionMask, arrow_reader::ParquetRecordBatchReaderBuilder};
+use parquet::file::reader::ChunkReader;
+
+use crate::errors::DenseMatrixProviderError;
+use crate::ingest::{append_fixed_size_list_values, check_narrowing};
+use crate::normalization::FeatureScaling;
+use crate::options::DenseIngestOptions;
+use crate::quantization::{Quantization, QuantizedMatrixProvider};
+use crate::simd;
+
+/// Dense matrix provider backed by a contiguous row-major buffer.
+#[derive(Debug)]
+pub struct DenseMatrixProvider {
+    name: String,
+    rows: usize,
+    dimension: usize,
+    values: Vec<f32>,
+    scaling: Option<FeatureScaling>,
+    ids: Option<IdMap>,
+}
+
+/// The fields of a [`DenseMatrixProvider`], moved out for re-encoding.
+pub(crate) struct DenseParts {
+    pub(crate) name: String,
+    pub(crate) rows: usize,
+    pub(crate) dimension: usize,
+    pub(crate) values: Vec<f32>,
+    pub(crate) scaling: Option<FeatureScaling>,
+    pub(crate) ids: Option<IdMap>,
+}
+
+impl DenseMatrixProvider {
+    /// Metric identifier reported by [`DataSource::metric_descriptor`]; dense
+    /// rows are always compared by Euclidean distance.
+    pub const METRIC: &'static str = "euclidean";
+
+    /// Creates a provider from a contiguous matrix.
+    pub(crate) fn from_parts(
+        name: impl Into<String>,
+        rows: usize,
+        dimension: usize,
+        values: Vec<f32>,
+    ) -> Self {
+        debug_assert_eq!(values.len(), rows.saturating_mul(dimension));
+        Self {
+            name: name.into(),
+            rows,
+            dimension,
+            values,
+            scaling: None,
+            ids: None,
+        }
+    }
+
+    /// Returns the dimensionality of each row.
+    #[must_use]
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    /// Returns the underlying row-major matrix.
+    #[must_use]
+    pub fn data(&self) -> &[f32] {
+        &self.values
+    }
+
+    /// Attaches one identifier per row, reported through
+    /// [`DataSource::row_ids`] and carried over by [`Self::quantize`].
+    ///
+    /// # Errors
+    /// Returns [`RowIdError::LengthMismatch`] when `ids` does not hold one
+    /// entry per row and [`RowIdError::Duplicate`] when an identifier repeats.
+    pub fn with_ids(mut self, ids: Vec<String>) -> Result<Self, RowIdError> {
+        self.ids = Some(IdMap::for_rows(ids, self.rows)?);
+        Ok(self)
+    }
+
+    /// Compresses every row with `quantization`, trading distance accuracy
+    /// for memory.
+    ///
+    /// Normalise first: the returned provider keeps the fitted
+    /// [`FeatureScaling`] for query vectors but cannot be rescaled.
+    ///
+    /// # Errors
+    /// Returns [`DenseMatrixProviderError::InvalidSubspaceCount`] when product
+    /// quantization's subspace count does not divide the dimension.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use arrow_array::{ArrayRef, FixedSizeListArray, Float32Array};
+    /// use arrow_schema::{DataType, Field};
+    /// use chutoro_core::DataSource;
+    /// use chutoro_providers_dense::{DenseMatrixProvider, Quantization};
+    ///
+    /// let child = Arc::new(Field::new("item", DataType::Float32, false));
+    /// let values: ArrayRef = Arc::new(Float32Array::from(vec![0.0, 0.0, 3.0, 4.0]));
+    /// let array = FixedSizeListArray::new(child, 2, values, None);
+    /// let quantized = DenseMatrixProvider::try_from_fixed_size_list("demo", &array)?
+    ///     .quantize(Quantization::Int8)?;
+    ///
+    /// assert_eq!(quantized.distance(0, 1)?, 5.0);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn quantize(
+        self,
+        quantization: Quantization,
+    ) -> Result<QuantizedMatrixProvider, DenseMatrixProviderError> {
+        QuantizedMatrixProvider::encode(self, quantization)
+    }
+
+    /// Splits the provider into its name, shape, values, scaling, and row
+    /// identifiers.
+    pub(crate) fn into_parts(self) -> DenseParts {
+        DenseParts {
+            name: self.name,
+            rows: self.rows,
+            dimension: self.dimension,
+            values: self.values,
+            scaling: self.scaling,
+            ids: self.ids,
+        }
+    }
+
+    /// Loads data from an Arrow [`FixedSizeListArray`] of `Float16` or
+    /// `Float32` values.
+    pub fn try_from_fixed_size_list(
+        name: impl Into<String>,
+        array: &FixedSizeListArray,
+    ) -> Result<Self, DenseMatrixProviderError> {
+        Self::try_from_fixed_size_list_with_options(name, array, DenseIngestOptions::default())
+    }
+
+    /// Loads data from an Arrow [`FixedSizeListArray`], applying `options`.
+    ///
+    /// # Errors
+    /// Returns [`DenseMatrixProviderError::LossyNarrowingDisabled`] for
+    /// `Float64` values unless `options` allows narrowing, and the usual type,
+    /// nullability, and dimension errors otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use arrow_array::{ArrayRef, FixedSizeListArray, Float64Array};
+    /// use arrow_schema::{DataType, Field};
+    /// use chutoro_providers_dense::{DenseIngestOptions, DenseMatrixProvider};
+    ///
+    /// let child = Arc::new(Field::new("item", DataType::Float64, false));
+    /// let values: ArrayRef = Arc::new(Float64Array::from(vec![0.5, 1.5, 2.5, 3.5]));
+    /// let array = FixedSizeListArray::new(child, 2, values, None);
+    ///
+    /// assert!(DenseMatrixProvider::try_from_fixed_size_list("demo", &array).is_err());
+    /// let options = DenseIngestOptions::default().with_lossy_f64(true);
+    /// let provider =
+    ///     DenseMatrixProvider::try_from_fixed_size_list_with_options("demo", &array, options)?;
+    /// assert_eq!(provider.data(), &[0.5, 1.5, 2.5, 3.5]);
+    /// # Ok::<(), chutoro_providers_dense::DenseMatrixProviderError>(())
+    /// ```
+    pub fn try_from_fixed_size_list_with_options(
+        name: impl Into<String>,
+        array: &FixedSizeListArray,
+        options: DenseIngestOptions,
+    ) -> Result<Self, DenseMatrixProviderError> {
+        check_narrowing(array.data_type(), options)?;
+        let mut values = Vec::new();
+        let dimension = append_fixed_size_list_values(array, None, 0, &mut values)?;
+        Ok(Self::from_parts(name, array.len(), dimension, values))
+    }
+
+    /// Loads data from a Parquet column containing `FixedSizeList<F, D>` or
+    /// `F` rows, where `F` is `Float16` or `Float32`.
+    pub fn try_from_parquet_path(
+        name: impl Into<String>,
+        path: impl AsRef<Path>,
+        column: &str,
+    ) -> Result<Self, DenseMatrixProviderError> {
+        Self::try_from_parquet_columns(name, path, &[column])
+    }
+
+    /// Loads data from several Parquet columns, concatenating them per row.
+    ///
+    /// Each column is either a `FixedSizeList<F, D>` contributing `D` values
+    /// or an `F` column contributing one, so embeddings split across columns
+    /// are reassembled in the order given. The resulting dimension is the sum
+    /// of the column widths. `F` may be `Float16` or `Float32`; use
+    /// [`Self::try_from_parquet_columns_with_options`] to accept `Float64`.
+    ///
+    /// # Errors
+    /// Returns [`DenseMatrixProviderError::NoColumns`] when `columns` is
+    /// empty, [`DenseMatrixProviderError::ColumnNotFound`] when a column is
+    /// missing, and the usual type, nullability, and dimension errors for each
+    /// column.
+    pub fn try_from_parquet_columns(
+        name: impl Into<String>,
+        path: impl AsRef<Path>,
+        columns: &[&str],
+    ) -> Result<Self, DenseMatrixProviderError> {
+        Self::try_from_parquet_columns_with_options(
+            name,
+            path,
+            columns,
+            DenseIngestOptions::default(),
+        )
+    }
+
+    /// Loads data from several Parquet columns, applying `options`.
+    ///
+    /// # Errors
+    /// Returns the errors of [`Self::try_from_parquet_columns`], plus
+    /// [`DenseMatrixProviderError::LossyNarrowingDisabled`] when a column
+    /// holds `Float64` values and `options` does not allow narrowing.
+    pub fn try_from_parquet_columns_with_options(
+        name: impl Into<String>,
+        path: impl AsRef<Path>,
+        columns: &[&str],
+        options: DenseIngestOptions,
+    ) -> Result<Self, DenseMatrixProviderError> {
+        let file = File::open(path)?;
+        Self::try_from_parquet_reader_columns_with_options(name, file, columns, options)
+    }
+
+    /// Loads data from a Parquet reader.
+    pub fn try_from_parquet_reader<R>(
+        name: impl Into<String>,
+        reader: R,
+        column: &str,
+    ) -> Result<Self, DenseMatrixProviderError>
+    where
+        R: ChunkReader + Send + 'static,
+    {
+        Self::try_from_parquet_reader_columns(name, reader, &[column])
+    }
+
+    /// Loads data from several columns of a Parquet reader, concatenating
+    /// them per row as described in [`Self::try_from_parquet_columns`].
+    pub fn try_from_parquet_reader_columns<R>(
+        name: impl Into<String>,
+        reader: R,
+        columns: &[&str],
+    ) -> Result<Self, DenseMatrixProviderError>
+    where
+        R: ChunkReader + Send + 'static,
+    {
+        Self::try_from_parquet_reader_columns_with_options(
+            name,
+            reader,
+            columns,
+            DenseIngestOptions::default(),
+        )
+    }
+
+    /// Loads data from several columns of a Parquet reader, applying
+    /// `options` as described in [`Self::try_from_parquet_columns_with_options`].
+    pub fn try_from_parquet_reader_columns_with_options<R>(
+        name: impl Into<String>,
+        reader: R,
+        columns: &[&str],
+        options: DenseIngestOptions,
+    ) -> Result<Self, DenseMatrixProviderError>
+    where
+        R: ChunkReader + Send + 'static,
+    {
+        if columns.is_empty() {
+            return Err(DenseMatrixProviderError::NoColumns);
+        }
+        let builder = ParquetRecordBatchReaderBuilder::try_new(reader)?;
+        // Check names against the full schema: once projected, the reader's
+        // schema no longer lists the columns a caller could have meant.
+        let schema = builder.schema();
+        if let Some(&missing) = columns
+            .iter()
+            .find(|&&column| schema.index_of(column).is_err())
+        {
+            return Err(DenseMatrixProviderError::column_not_found(missing, schema));
+        }
+        let mask = ProjectionMask::columns(builder.parquet_schema(), columns.iter().copied());
+        let reader = builder.with_projection(mask).build()?;
+        Self::try_from_record_batch_reader(name, reader, columns, options)
+    }
+
+    fn matrix(&self) -> simd::RowMajorMatrix<'_> {
+        simd::RowMajorMatrix::new(
+            simd::MatrixValues::new(&self.values),
+            simd::RowCount::new(self.rows),
+            simd::Dimension::new(self.dimension),
+        )
+    }
+
+    fn row_slice(&self, index: usize) -> Result<&[f32], DataSourceError> {
+        if index >= self.rows {
+            return Err(DataSourceError::OutOfBounds { index });
+        }
+        let start = index
+            .checked_mul(self.dimension)
+            .ok_or(DataSourceError::OutOfBounds { index })?;
+        let end = start
+            .checked_add(self.dimension)
+            .ok_or(DataSourceError::OutOfBounds { index })?;
+        if end > self.values.len() {
+            return Err(DataSourceError::OutOfBounds { index });
+        }
+        Ok(&self.values[start..end])
+    }
+}
+
+impl DataSource for DenseMatrixProvider {
+    fn len(&self) -> usize {
+        self.rows
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn metric_descriptor(&self) -> MetricDescriptor {
+        MetricDescriptor::new(Self::METRIC).with_class(MetricClass::Metric)
+    }
+
+    fn dimension_hint(&self) -> Option<usize> {
+        Some(self.dimension)
+    }
+
+    fn row_ids(&self) -> Option<&IdMap> {
+        self.ids.as_ref()
+    }
+
+    fn dedupe_key(&self, index: usize) -> Option<u64> {
+        self.row_slice(index).ok().map(dedupe::row_key)
+    }
+
+    fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+        let a = self.row_slice(i)?;
+        let b = self.row_slice(j)?;
+        Ok(simd::euclidean_distance(simd::RowSlice::new(a), simd::RowSlice::new(b)).get())
+    }
+
+    #[expect(clippy::float_arithmetic, reason = "vector arithmetic")]
+    fn distance_f64(&self, i: usize, j: usize) -> Result<f64, DataSourceError> {
+        let a = self.row_slice(i)?;
+        let b = self.row_slice(j)?;
+        let sum = a
+            .iter()
+            .zip(b)
+            .map(|(&x, &y)| {
+                let d = f64::from(x) - f64::from(y);
+                d * d
+            })
+            .sum::<f64>();
+        Ok(sum.sqrt())
+    }
+
+    fn batch_distances_into(
+        &self,
+        query: usize,
+        targets: &[usize],
+        out: &mut [f32],
+    ) -> Result<(), DataSourceError> {
+        simd::euclidean_distance_query_batch(self.matrix(), query, targets, out)
+    }
+
+    fn distance_batch(
+        &self,
+        pairs: &[(usize, usize)],
+        out: &mut [f32],
+    ) -> Result<(), DataSourceError> {
+        if pairs.len() != out.len() {
+            return Err(DataSourceError::OutputLengthMismatch {
+                out: out.len(),
+                expected: pairs.len(),
+            });
+        }
+        let mut out_buffer = simd::DistanceBuffer::new(out);
+        simd::euclidean_distance_batch_raw_pairs(self.matrix(), pairs, &mut out_buffer)
+    }
+}
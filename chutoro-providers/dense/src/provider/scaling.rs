@@ -0,0 +1,51 @@
+//! Feature scaling applied to a [`DenseMatrixProvider`]'s rows in place.
+
+use crate::normalization::{FeatureScaling, Normalization};
+
+use super::DenseMatrixProvider;
+
+impl DenseMatrixProvider {
+    /// Scales every feature with `method`, keeping the fitted statistics.
+    ///
+    /// Statistics are fitted over all rows as loaded. Calling this again
+    /// undoes the previous scaling before refitting, so the stored
+    /// [`FeatureScaling`] always maps raw feature values. Apply it to query
+    /// vectors with [`FeatureScaling::apply`] before comparing them with rows.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use arrow_array::{ArrayRef, FixedSizeListArray, Float32Array};
+    /// use arrow_schema::{DataType, Field};
+    /// use chutoro_providers_dense::{DenseMatrixProvider, Normalization};
+    ///
+    /// let child = Arc::new(Field::new("item", DataType::Float32, false));
+    /// let values: ArrayRef = Arc::new(Float32Array::from(vec![0.0, 100.0, 2.0, 300.0]));
+    /// let array = FixedSizeListArray::new(child, 2, values, None);
+    /// let provider = DenseMatrixProvider::try_from_fixed_size_list("demo", &array)?
+    ///     .with_normalization(Normalization::MinMax);
+    /// assert_eq!(provider.data(), &[0.0, 0.0, 1.0, 1.0]);
+    ///
+    /// let mut query = [1.0, 200.0];
+    /// provider.scaling().expect("scaling was fitted").apply(&mut query)?;
+    /// assert_eq!(query, [0.5, 0.5]);
+    /// # Ok::<(), chutoro_providers_dense::DenseMatrixProviderError>(())
+    /// ```
+    #[must_use]
+    pub fn with_normalization(mut self, method: Normalization) -> Self {
+        if let Some(previous) = self.scaling.take() {
+            previous.invert_rows(&mut self.values);
+        }
+        let scaling = FeatureScaling::fit(method, &self.values, self.dimension);
+        scaling.apply_rows(&mut self.values);
+        self.scaling = Some(scaling);
+        self
+    }
+
+    /// Returns the scaling fitted by [`Self::with_normalization`], if any.
+    #[must_use]
+    pub fn scaling(&self) -> Option<&FeatureScaling> {
+        self.scaling.as_ref()
+    }
+}
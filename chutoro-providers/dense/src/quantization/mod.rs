@@ -0,0 +1,281 @@
+//! Compressed row storage for large embedding matrices.
+//!
+//! A [`QuantizedMatrixProvider`] replaces each `f32` row with a short byte
+//! code and keeps only the small codebook needed to interpret it. Scalar
+//! quantization stores one byte per feature, a 4× saving; product
+//! quantization stores one byte per subspace, which for 1536-d embeddings
+//! split into 96 subspaces is a 64× saving. Distances are approximate, so
+//! clusterings may differ slightly from the `f32` provider's.
+
+mod product;
+mod scalar;
+
+use std::{num::NonZeroUsize, thread};
+
+use chutoro_core::{DataSource, DataSourceError, IdMap};
+
+use crate::errors::DenseMatrixProviderError;
+use crate::normalization::FeatureScaling;
+use crate::provider::{DenseMatrixProvider, DenseParts};
+
+use self::{product::ProductCodebook, scalar::ScalarCodebook};
+
+/// How [`crate::DenseMatrixProvider::quantize`] compresses each row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Quantization {
+    /// One byte per feature, mapping each feature's observed range onto 256
+    /// evenly spaced levels.
+    ///
+    /// Cuts memory by about 4×; each value is off by at most half a level.
+    Int8,
+    /// One byte per subspace: rows are split into `subspaces` equal slices
+    /// and each slice is replaced by the nearest of 256 centroids learnt with
+    /// k-means.
+    ///
+    /// Cuts memory by `4 × dimension / subspaces`, at a larger accuracy cost
+    /// than [`Self::Int8`]. `subspaces` must divide the dimension.
+    Product {
+        /// Number of slices each row is split into.
+        subspaces: NonZeroUsize,
+    },
+}
+
+/// The codebook that turns row codes back into distances.
+#[derive(Debug)]
+enum Codebook {
+    Scalar(ScalarCodebook),
+    Product(ProductCodebook),
+}
+
+impl Codebook {
+    fn code_width(&self) -> usize {
+        match self {
+            Self::Scalar(codebook) => codebook.dimension(),
+            Self::Product(codebook) => codebook.subspaces(),
+        }
+    }
+
+    fn encode(&self, row: &[f32], codes: &mut [u8]) {
+        match self {
+            Self::Scalar(codebook) => codebook.encode(row, codes),
+            Self::Product(codebook) => codebook.encode(row, codes),
+        }
+    }
+
+    fn squared_distance(&self, left: &[u8], right: &[u8]) -> f32 {
+        match self {
+            Self::Scalar(codebook) => codebook.squared_distance(left, right),
+            Self::Product(codebook) => codebook.squared_distance(left, right),
+        }
+    }
+
+    fn squared_query_distance(&self, query: &[f32], codes: &[u8]) -> f32 {
+        match self {
+            Self::Scalar(codebook) => codebook.squared_query_distance(query, codes),
+            Self::Product(codebook) => codebook.squared_query_distance(query, codes),
+        }
+    }
+
+    fn decode(&self, codes: &[u8]) -> Vec<f32> {
+        match self {
+            Self::Scalar(codebook) => codebook.decode(codes),
+            Self::Product(codebook) => codebook.decode(codes),
+        }
+    }
+
+    fn bytes(&self) -> usize {
+        match self {
+            Self::Scalar(codebook) => codebook.bytes(),
+            Self::Product(codebook) => codebook.bytes(),
+        }
+    }
+}
+
+/// A dense matrix stored as quantized row codes.
+///
+/// Built by [`crate::DenseMatrixProvider::quantize`]. Distances between rows
+/// are computed from the codes without decompressing the matrix;
+/// [`Self::distance_to`] compares a full-precision query against a row's
+/// code, which is more accurate than quantizing the query as well.
+#[derive(Debug)]
+pub struct QuantizedMatrixProvider {
+    name: String,
+    rows: usize,
+    dimension: usize,
+    quantization: Quantization,
+    codebook: Codebook,
+    codes: Vec<u8>,
+    scaling: Option<FeatureScaling>,
+    ids: Option<IdMap>,
+}
+
+impl QuantizedMatrixProvider {
+    /// Learns a codebook for `matrix` and encodes every row.
+    pub(crate) fn encode(
+        matrix: DenseMatrixProvider,
+        quantization: Quantization,
+    ) -> Result<Self, DenseMatrixProviderError> {
+        let DenseParts {
+            name,
+            rows,
+            dimension,
+            values,
+            scaling,
+            ids,
+        } = matrix.into_parts();
+        let codebook = match quantization {
+            Quantization::Int8 => Codebook::Scalar(ScalarCodebook::fit(&values, dimension)),
+            Quantization::Product { subspaces } => {
+                Codebook::Product(ProductCodebook::fit(&values, dimension, subspaces)?)
+            }
+        };
+        let codes = encode_rows(&values, dimension, codebook.code_width(), |row, code| {
+            codebook.encode(row, code);
+        });
+        Ok(Self {
+            name,
+            rows,
+            dimension,
+            quantization,
+            codebook,
+            codes,
+            scaling,
+            ids,
+        })
+    }
+
+    /// Returns the dimensionality of each row before quantization.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn dimension(&self) -> usize { self.dimension }
+
+    /// Returns the scheme the rows were quantized with.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn quantization(&self) -> Quantization { self.quantization }
+
+    /// Returns the scaling fitted before quantization, if any.
+    ///
+    /// Apply it to query vectors before calling [`Self::distance_to`].
+    #[must_use]
+    pub fn scaling(&self) -> Option<&FeatureScaling> {
+        self.scaling.as_ref()
+    }
+
+    /// Returns the bytes held by the row codes and codebook.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use arrow_array::{ArrayRef, FixedSizeListArray, Float32Array};
+    /// use arrow_schema::{DataType, Field};
+    /// use chutoro_providers_dense::{DenseMatrixProvider, Quantization};
+    ///
+    /// let child = Arc::new(Field::new("item", DataType::Float32, false));
+    /// let values: ArrayRef = Arc::new(Float32Array::from(vec![0.5; 64 * 1024]));
+    /// let array = FixedSizeListArray::new(child, 64, values, None);
+    /// let quantized = DenseMatrixProvider::try_from_fixed_size_list("demo", &array)?
+    ///     .quantize(Quantization::Int8)?;
+    ///
+    /// // 1024 rows of 64 one-byte codes, plus a range per feature.
+    /// assert_eq!(quantized.storage_bytes(), 1024 * 64 + 64 * 8);
+    /// # Ok::<(), chutoro_providers_dense::DenseMatrixProviderError>(())
+    /// ```
+    #[must_use]
+    pub fn storage_bytes(&self) -> usize {
+        self.codes.len() + self.codebook.bytes()
+    }
+
+    /// Returns the approximate `f32` row reconstructed from its code.
+    ///
+    /// # Errors
+    /// Returns [`DataSourceError::OutOfBounds`] when `index` is not a row.
+    pub fn decode_row(&self, index: usize) -> Result<Vec<f32>, DataSourceError> {
+        Ok(self.codebook.decode(self.row_codes(index)?))
+    }
+
+    /// Returns the Euclidean distance from a full-precision `query` to row
+    /// `index`.
+    ///
+    /// Only the row is approximated (asymmetric distance computation), so the
+    /// result is closer to the exact distance than comparing two quantized
+    /// rows would be.
+    ///
+    /// # Errors
+    /// Returns [`DataSourceError::DimensionMismatch`] when `query` does not
+    /// have [`Self::dimension`] features and
+    /// [`DataSourceError::OutOfBounds`] when `index` is not a row.
+    pub fn distance_to(&self, query: &[f32], index: usize) -> Result<f32, DataSourceError> {
+        if query.len() != self.dimension {
+            return Err(DataSourceError::DimensionMismatch {
+                left: query.len(),
+                right: self.dimension,
+            });
+        }
+        let codes = self.row_codes(index)?;
+        Ok(self.codebook.squared_query_distance(query, codes).sqrt())
+    }
+
+    fn row_codes(&self, index: usize) -> Result<&[u8], DataSourceError> {
+        if index >= self.rows {
+            return Err(DataSourceError::OutOfBounds { index });
+        }
+        let width = self.codebook.code_width();
+        let start = index * width;
+        self.codes
+            .get(start..start + width)
+            .ok_or(DataSourceError::OutOfBounds { index })
+    }
+}
+
+impl DataSource for QuantizedMatrixProvider {
+    fn len(&self) -> usize {
+        self.rows
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn row_ids(&self) -> Option<&IdMap> {
+        self.ids.as_ref()
+    }
+
+    fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+        let left = self.row_codes(i)?;
+        let right = self.row_codes(j)?;
+        Ok(self.codebook.squared_distance(left, right).sqrt())
+    }
+}
+
+/// Encodes each `dimension`-wide row of `values` into `width` bytes,
+/// splitting the rows evenly across the available cores.
+fn encode_rows(
+    values: &[f32],
+    dimension: usize,
+    width: usize,
+    encode: impl Fn(&[f32], &mut [u8]) + Sync,
+) -> Vec<u8> {
+    if dimension == 0 || width == 0 {
+        return Vec::new();
+    }
+    let rows = values.len() / dimension;
+    let mut codes = vec![0; rows * width];
+    let workers = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let chunk_rows = rows.div_ceil(workers).max(1);
+    let encode = &encode;
+    thread::scope(|scope| {
+        for (rows, codes) in values
+            .chunks(chunk_rows * dimension)
+            .zip(codes.chunks_mut(chunk_rows * width))
+        {
+            scope.spawn(move || {
+                rows.chunks_exact(dimension)
+                    .zip(codes.chunks_exact_mut(width))
+                    .for_each(|(row, code)| encode(row, code));
+            });
+        }
+    });
+    codes
+}
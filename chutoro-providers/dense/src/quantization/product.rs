@@ -0,0 +1,227 @@
+//! Product quantization: one byte per subspace.
+//!
+//! Rows are split into equal slices, and each slice position (a subspace) gets
+//! its own 256-centroid codebook learnt with Lloyd's k-means on an evenly
+//! strided sample of rows. A row is stored as the index of the nearest
+//! centroid in every subspace.
+
+use std::{num::NonZeroUsize, ops::Range, thread};
+
+use crate::errors::DenseMatrixProviderError;
+
+/// Centroids per subspace, the most a one-byte code can address.
+const CENTROIDS: usize = 256;
+/// Most rows sampled to train the codebooks.
+const TRAINING_ROWS: usize = CENTROIDS * 16;
+/// Most Lloyd iterations per subspace.
+const ITERATIONS: usize = 12;
+
+/// Per-subspace centroids, stored subspace-major.
+#[derive(Debug)]
+pub(super) struct ProductCodebook {
+    subspaces: usize,
+    width: usize,
+    centroids: usize,
+    values: Vec<f32>,
+}
+
+impl ProductCodebook {
+    /// Learns a codebook for the row-major `values` split into `subspaces`
+    /// slices.
+    ///
+    /// # Errors
+    /// Returns [`DenseMatrixProviderError::InvalidSubspaceCount`] when
+    /// `subspaces` does not divide a non-zero `dimension`.
+    pub(super) fn fit(
+        values: &[f32],
+        dimension: usize,
+        subspaces: NonZeroUsize,
+    ) -> Result<Self, DenseMatrixProviderError> {
+        let subspaces = subspaces.get();
+        if dimension == 0 || !dimension.is_multiple_of(subspaces) {
+            return Err(DenseMatrixProviderError::InvalidSubspaceCount {
+                dimension,
+                subspaces,
+            });
+        }
+        let width = dimension / subspaces;
+        let rows = values.len() / dimension;
+        let training: Vec<&[f32]> = values
+            .chunks_exact(dimension)
+            .step_by(rows.div_ceil(TRAINING_ROWS).max(1))
+            .collect();
+        let centroids = training.len().clamp(1, CENTROIDS);
+        let workers = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        let per_worker = subspaces.div_ceil(workers);
+        let training = &training;
+        let values = thread::scope(|scope| {
+            let handles: Vec<_> = (0..subspaces)
+                .step_by(per_worker)
+                .map(|first| {
+                    let owned = first..(first + per_worker).min(subspaces);
+                    scope.spawn(move || train_subspaces(training, owned, width, centroids))
+                })
+                .collect();
+            let mut values = Vec::with_capacity(subspaces * centroids * width);
+            for handle in handles {
+                match handle.join() {
+                    Ok(trained) => values.extend(trained),
+                    Err(panic) => std::panic::resume_unwind(panic),
+                }
+            }
+            values
+        });
+        Ok(Self {
+            subspaces,
+            width,
+            centroids,
+            values,
+        })
+    }
+
+    pub(super) fn subspaces(&self) -> usize {
+        self.subspaces
+    }
+
+    pub(super) fn encode(&self, row: &[f32], codes: &mut [u8]) {
+        for (subspace, (code, slice)) in codes
+            .iter_mut()
+            .zip(row.chunks_exact(self.width))
+            .enumerate()
+        {
+            let start = subspace * self.centroids * self.width;
+            let centroids = &self.values[start..start + self.centroids * self.width];
+            *code = nearest(centroids, self.width, slice);
+        }
+    }
+
+    pub(super) fn squared_distance(&self, left: &[u8], right: &[u8]) -> f32 {
+        left.iter()
+            .zip(right)
+            .enumerate()
+            .map(|(subspace, (&left, &right))| {
+                squared_euclidean(
+                    self.centroid(subspace, left),
+                    self.centroid(subspace, right),
+                )
+            })
+            .sum()
+    }
+
+    pub(super) fn squared_query_distance(&self, query: &[f32], codes: &[u8]) -> f32 {
+        query
+            .chunks_exact(self.width)
+            .zip(codes)
+            .enumerate()
+            .map(|(subspace, (slice, &code))| {
+                squared_euclidean(slice, self.centroid(subspace, code))
+            })
+            .sum()
+    }
+
+    pub(super) fn decode(&self, codes: &[u8]) -> Vec<f32> {
+        codes
+            .iter()
+            .enumerate()
+            .flat_map(|(subspace, &code)| self.centroid(subspace, code))
+            .copied()
+            .collect()
+    }
+
+    pub(super) fn bytes(&self) -> usize {
+        self.values.len() * size_of::<f32>()
+    }
+
+    fn centroid(&self, subspace: usize, code: u8) -> &[f32] {
+        let start = (subspace * self.centroids + usize::from(code)) * self.width;
+        &self.values[start..start + self.width]
+    }
+}
+
+/// Trains each subspace in `subspaces`, returning their centroids in order.
+fn train_subspaces(
+    training: &[&[f32]],
+    subspaces: Range<usize>,
+    width: usize,
+    centroids: usize,
+) -> Vec<f32> {
+    subspaces
+        .flat_map(|subspace| train_subspace(training, subspace * width, width, centroids))
+        .collect()
+}
+
+/// Runs Lloyd's k-means on the `width` features of each training row that
+/// start at `offset`, returning `centroids` centroids back to back.
+///
+/// Centroids start at evenly spaced training rows, so training is
+/// deterministic; a centroid that loses all its points keeps its position.
+fn train_subspace(training: &[&[f32]], offset: usize, width: usize, centroids: usize) -> Vec<f32> {
+    let points: Vec<&[f32]> = training
+        .iter()
+        .map(|row| &row[offset..offset + width])
+        .collect();
+    if points.is_empty() {
+        return vec![0.0; centroids * width];
+    }
+    let mut values: Vec<f32> = (0..centroids)
+        .flat_map(|centroid| points[centroid * points.len() / centroids])
+        .copied()
+        .collect();
+    let mut assignments = vec![u8::MAX; points.len()];
+    for _ in 0..ITERATIONS {
+        let mut changed = false;
+        for (assignment, point) in assignments.iter_mut().zip(&points) {
+            let nearest = nearest(&values, width, point);
+            changed |= *assignment != nearest;
+            *assignment = nearest;
+        }
+        if !changed {
+            break;
+        }
+        move_to_means(&mut values, width, &points, &assignments);
+    }
+    values
+}
+
+/// Moves each centroid to the mean of the points assigned to it, leaving
+/// centroids without points in place.
+fn move_to_means(values: &mut [f32], width: usize, points: &[&[f32]], assignments: &[u8]) {
+    let centroids = values.len() / width;
+    let mut sums = vec![0.0_f64; centroids * width];
+    let mut counts = vec![0_usize; centroids];
+    for (&assignment, point) in assignments.iter().zip(points) {
+        let centroid = usize::from(assignment);
+        counts[centroid] += 1;
+        let sum = &mut sums[centroid * width..(centroid + 1) * width];
+        for (total, &value) in sum.iter_mut().zip(*point) {
+            *total += f64::from(value);
+        }
+    }
+    let updates = values
+        .chunks_exact_mut(width)
+        .zip(sums.chunks_exact(width))
+        .zip(counts)
+        .filter(|(_, count)| *count > 0);
+    for ((centroid, sum), count) in updates {
+        for (value, total) in centroid.iter_mut().zip(sum) {
+            *value = (total / count as f64) as f32;
+        }
+    }
+}
+
+/// Returns the index of the centroid in `centroids` closest to `point`.
+fn nearest(centroids: &[f32], width: usize, point: &[f32]) -> u8 {
+    let mut best = (0, f32::INFINITY);
+    for (index, centroid) in centroids.chunks_exact(width).enumerate() {
+        let distance = squared_euclidean(centroid, point);
+        if distance < best.1 {
+            best = (index, distance);
+        }
+    }
+    // At most `CENTROIDS` centroids exist, so the index fits in a byte.
+    best.0 as u8
+}
+
+fn squared_euclidean(left: &[f32], right: &[f32]) -> f32 {
+    left.iter().zip(right).map(|(a, b)| (a - b) * (a - b)).sum()
+}
@@ -0,0 +1,95 @@
+//! Scalar quantization: one byte per feature.
+//!
+//! Each feature's observed range is split into 255 equal steps, and a value
+//! is stored as the index of its nearest level. The per-feature offset cancels
+//! out of row-to-row distances, so two codes are compared by their level
+//! differences alone.
+
+/// Per-feature ranges mapping each code `c` to `low + c × step`.
+#[derive(Debug)]
+pub(super) struct ScalarCodebook {
+    lows: Vec<f32>,
+    steps: Vec<f32>,
+}
+
+impl ScalarCodebook {
+    /// Fits one range per feature over the row-major `values`.
+    ///
+    /// Non-finite values are ignored when fitting; constant features get a
+    /// unit step so every value maps to level zero.
+    pub(super) fn fit(values: &[f32], dimension: usize) -> Self {
+        let mut lows = vec![f32::INFINITY; dimension];
+        let mut highs = vec![f32::NEG_INFINITY; dimension];
+        for row in values.chunks_exact(dimension.max(1)) {
+            let features = row.iter().zip(lows.iter_mut().zip(&mut highs));
+            for (value, (low, high)) in features.filter(|(value, _)| value.is_finite()) {
+                *low = low.min(*value);
+                *high = high.max(*value);
+            }
+        }
+        let (lows, steps): (Vec<f32>, Vec<f32>) = lows
+            .into_iter()
+            .zip(highs)
+            .map(|(low, high)| {
+                let step = (high - low) / f32::from(u8::MAX);
+                if step > 0.0 && step.is_finite() {
+                    (low, step)
+                } else if low.is_finite() {
+                    (low, 1.0)
+                } else {
+                    (0.0, 1.0)
+                }
+            })
+            .unzip();
+        Self { lows, steps }
+    }
+
+    pub(super) fn dimension(&self) -> usize {
+        self.lows.len()
+    }
+
+    pub(super) fn encode(&self, row: &[f32], codes: &mut [u8]) {
+        for (((code, value), low), step) in
+            codes.iter_mut().zip(row).zip(&self.lows).zip(&self.steps)
+        {
+            // Saturating float-to-int casts clamp out-of-range values and map
+            // NaN to level zero.
+            *code = ((value - low) / step).round() as u8;
+        }
+    }
+
+    pub(super) fn squared_distance(&self, left: &[u8], right: &[u8]) -> f32 {
+        left.iter()
+            .zip(right)
+            .zip(&self.steps)
+            .map(|((&left, &right), step)| {
+                let difference = f32::from(i16::from(left) - i16::from(right)) * step;
+                difference * difference
+            })
+            .sum()
+    }
+
+    pub(super) fn squared_query_distance(&self, query: &[f32], codes: &[u8]) -> f32 {
+        query
+            .iter()
+            .zip(codes)
+            .zip(self.lows.iter().zip(&self.steps))
+            .map(|((value, &code), (low, step))| {
+                let difference = value - (low + f32::from(code) * step);
+                difference * difference
+            })
+            .sum()
+    }
+
+    pub(super) fn decode(&self, codes: &[u8]) -> Vec<f32> {
+        codes
+            .iter()
+            .zip(self.lows.iter().zip(&self.steps))
+            .map(|(&code, (low, step))| low + f32::from(code) * step)
+            .collect()
+    }
+
+    pub(super) fn bytes(&self) -> usize {
+        (self.lows.len() + self.steps.len()) * size_of::<f32>()
+    }
+}
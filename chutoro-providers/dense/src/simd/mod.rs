@@ -116,6 +116,43 @@ pub(crate) fn euclidean_distance_batch_raw_pairs(
     Ok(())
 }
 
+/// Computes Euclidean distances from the `query` row to each `targets` row,
+/// writing them straight into `out`.
+///
+/// The targets are packed for the query-to-points kernel when the selected
+/// backend vectorises and there is more than one target; otherwise each pair
+/// is scored on its own. Every index is validated first, so `out` is left
+/// untouched on error.
+pub(crate) fn euclidean_distance_query_batch(
+    matrix: RowMajorMatrix<'_>,
+    query: usize,
+    targets: &[usize],
+    out: &mut [f32],
+) -> Result<(), DataSourceError> {
+    if targets.len() != out.len() {
+        return Err(DataSourceError::OutputLengthMismatch {
+            out: out.len(),
+            expected: targets.len(),
+        });
+    }
+    let rows = matrix.rows().get();
+    validate_raw_row_index(query, rows)?;
+    for target in targets.iter().copied() {
+        validate_raw_row_index(target, rows)?;
+    }
+
+    let query_row = matrix.row(RowIndex::new(query))?;
+    if should_pack_query_points(matrix.dimension().get(), targets.len()) {
+        let indices: Vec<RowIndex> = targets.iter().copied().map(RowIndex::new).collect();
+        let point_view = DensePointView::from_row_indices(matrix, &indices)?;
+        return euclidean_distance_query_points(query_row, &point_view, out);
+    }
+    for (target, slot) in targets.iter().copied().zip(out.iter_mut()) {
+        *slot = euclidean_distance(query_row, matrix.row(RowIndex::new(target))?).get();
+    }
+    Ok(())
+}
+
 fn collect_euclidean_distance_batch_from_raw_pairs(
     matrix: RowMajorMatrix<'_>,
     pairs: &[(usize, usize)],
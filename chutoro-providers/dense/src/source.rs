@@ -93,6 +93,10 @@ impl DataSource for DenseSource {
         &self.name
     }
 
+    fn dimension_hint(&self) -> Option<usize> {
+        self.data.first().map(Vec::len)
+    }
+
     #[expect(clippy::float_arithmetic, reason = "vector arithmetic")]
     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
         let a = self
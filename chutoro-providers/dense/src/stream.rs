@@ -0,0 +1,191 @@
+//! Ingestion from streams of Arrow record batches.
+//!
+//! Parquet files, Arrow IPC streams pushed by other processes, and any other
+//! [`RecordBatchReader`] share one loader: the requested feature columns are
+//! resolved against the stream's schema once, then every batch is appended
+//! to the row-major buffer as it arrives, so the stream never needs to be
+//! materialised as a file first.
+use std::io::Read;
+
+use arrow_array::RecordBatchReader;
+use arrow_ipc::reader::StreamReader;
+use arrow_schema::Schema;
+
+use crate::errors::DenseMatrixProviderError;
+use crate::ingest::{ColumnShape, FeatureColumn, append_feature_columns, validate_feature_field};
+use crate::options::DenseIngestOptions;
+use crate::provider::DenseMatrixProvider;
+
+impl DenseMatrixProvider {
+    /// Loads data from one column of an Arrow IPC stream.
+    ///
+    /// The column is a `FixedSizeList<F, D>` or an `F` column, where `F` is
+    /// `Float16` or `Float32`.
+    ///
+    /// # Errors
+    /// Returns [`DenseMatrixProviderError::Arrow`] when the stream cannot be
+    /// decoded, plus the errors of [`Self::try_from_ipc_stream_columns`].
+    pub fn try_from_ipc_stream<R: Read>(
+        name: impl Into<String>,
+        reader: R,
+        column: &str,
+    ) -> Result<Self, DenseMatrixProviderError> {
+        Self::try_from_ipc_stream_columns(name, reader, &[column])
+    }
+
+    /// Loads data from several columns of an Arrow IPC stream, concatenating
+    /// them per row as described in [`Self::try_from_parquet_columns`].
+    ///
+    /// Processes that already hold Arrow data, such as `pyarrow` or Spark
+    /// writers, can push it over a socket or pipe in the IPC streaming format
+    /// without writing Parquet to disk. Batches are decoded and copied as
+    /// they arrive.
+    ///
+    /// # Errors
+    /// Returns [`DenseMatrixProviderError::Arrow`] when the stream cannot be
+    /// decoded, plus the errors of [`Self::try_from_record_batch_reader`].
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use arrow_array::{ArrayRef, Float32Array, RecordBatch};
+    /// use arrow_ipc::writer::StreamWriter;
+    /// use arrow_schema::{DataType, Field, Schema};
+    /// use chutoro_providers_dense::DenseMatrixProvider;
+    ///
+    /// let schema = Arc::new(Schema::new(vec![
+    ///     Field::new("x", DataType::Float32, false),
+    ///     Field::new("y", DataType::Float32, false),
+    /// ]));
+    /// let x: ArrayRef = Arc::new(Float32Array::from(vec![0.0, 1.0]));
+    /// let y: ArrayRef = Arc::new(Float32Array::from(vec![2.0, 3.0]));
+    /// let batch = RecordBatch::try_new(Arc::clone(&schema), vec![x, y])?;
+    ///
+    /// let mut bytes = Vec::new();
+    /// let mut writer = StreamWriter::try_new(&mut bytes, &schema)?;
+    /// writer.write(&batch)?;
+    /// writer.finish()?;
+    ///
+    /// let provider =
+    ///     DenseMatrixProvider::try_from_ipc_stream_columns("pushed", bytes.as_slice(), &["x", "y"])?;
+    /// assert_eq!(provider.data(), &[0.0, 2.0, 1.0, 3.0]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_from_ipc_stream_columns<R: Read>(
+        name: impl Into<String>,
+        reader: R,
+        columns: &[&str],
+    ) -> Result<Self, DenseMatrixProviderError> {
+        Self::try_from_ipc_stream_columns_with_options(
+            name,
+            reader,
+            columns,
+            DenseIngestOptions::default(),
+        )
+    }
+
+    /// Loads data from several columns of an Arrow IPC stream, applying
+    /// `options` as described in [`Self::try_from_parquet_columns_with_options`].
+    ///
+    /// # Errors
+    /// Returns the errors of [`Self::try_from_ipc_stream_columns`], plus
+    /// [`DenseMatrixProviderError::LossyNarrowingDisabled`] when a column
+    /// holds `Float64` values and `options` does not allow narrowing.
+    pub fn try_from_ipc_stream_columns_with_options<R: Read>(
+        name: impl Into<String>,
+        reader: R,
+        columns: &[&str],
+        options: DenseIngestOptions,
+    ) -> Result<Self, DenseMatrixProviderError> {
+        if columns.is_empty() {
+            return Err(DenseMatrixProviderError::NoColumns);
+        }
+        let reader = StreamReader::try_new(reader, None)?;
+        Self::try_from_record_batch_reader(name, reader, columns, options)
+    }
+
+    /// Loads data from several columns of any [`RecordBatchReader`].
+    ///
+    /// This is the loader behind the Parquet and IPC constructors. Use it
+    /// directly for record batches from other transports, such as batches
+    /// decoded from an Arrow Flight stream, wrapped in a
+    /// [`arrow_array::RecordBatchIterator`].
+    ///
+    /// # Errors
+    /// Returns [`DenseMatrixProviderError::NoColumns`] when `columns` is
+    /// empty, [`DenseMatrixProviderError::ColumnNotFound`] when a column is
+    /// missing from the reader's schema, [`DenseMatrixProviderError::Arrow`]
+    /// when the reader yields an error, and the usual type, nullability, and
+    /// dimension errors for each column.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use arrow_array::{ArrayRef, Float32Array, RecordBatch, RecordBatchIterator};
+    /// use arrow_schema::{DataType, Field, Schema};
+    /// use chutoro_providers_dense::{DenseIngestOptions, DenseMatrixProvider};
+    ///
+    /// let schema = Arc::new(Schema::new(vec![Field::new("x", DataType::Float32, false)]));
+    /// let x: ArrayRef = Arc::new(Float32Array::from(vec![4.0, 5.0]));
+    /// let batch = RecordBatch::try_new(Arc::clone(&schema), vec![x])?;
+    /// let reader = RecordBatchIterator::new([Ok(batch)], schema);
+    ///
+    /// let provider = DenseMatrixProvider::try_from_record_batch_reader(
+    ///     "batches",
+    ///     reader,
+    ///     &["x"],
+    ///     DenseIngestOptions::default(),
+    /// )?;
+    /// assert_eq!(provider.data(), &[4.0, 5.0]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_from_record_batch_reader<R: RecordBatchReader>(
+        name: impl Into<String>,
+        reader: R,
+        columns: &[&str],
+        options: DenseIngestOptions,
+    ) -> Result<Self, DenseMatrixProviderError> {
+        if columns.is_empty() {
+            return Err(DenseMatrixProviderError::NoColumns);
+        }
+        let layout = resolve_columns(&reader.schema(), columns, options)?;
+        let dimension = layout.iter().map(|(_, shape)| shape.width()).sum();
+        let mut values = Vec::new();
+        let mut rows = 0_usize;
+        for batch in reader {
+            let batch = batch?;
+            let features: Vec<_> = columns
+                .iter()
+                .zip(&layout)
+                .map(|(name, &(index, shape))| FeatureColumn {
+                    name,
+                    array: batch.column(index),
+                    shape,
+                })
+                .collect();
+            append_feature_columns(&features, batch.num_rows(), rows, &mut values)?;
+            rows += batch.num_rows();
+        }
+        Ok(Self::from_parts(name, rows, dimension, values))
+    }
+}
+
+/// Locates each requested column in `schema` and validates its layout.
+fn resolve_columns(
+    schema: &Schema,
+    columns: &[&str],
+    options: DenseIngestOptions,
+) -> Result<Vec<(usize, ColumnShape)>, DenseMatrixProviderError> {
+    columns
+        .iter()
+        .map(|&column| {
+            let index = schema
+                .index_of(column)
+                .map_err(|_| DenseMatrixProviderError::column_not_found(column, schema))?;
+            let shape = validate_feature_field(schema.field(index), column, options)?;
+            Ok((index, shape))
+        })
+        .collect()
+}
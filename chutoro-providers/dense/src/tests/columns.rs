@@ -0,0 +1,135 @@
+//! Tests for loading dense rows from several Parquet columns. Covers list and
+//! scalar concatenation across batches, column order, and the validation
+//! errors raised for missing, nullable, or mistyped columns.
+
+use super::{DenseMatrixProvider, DenseMatrixProviderError, support::*};
+use arrow_array::{ArrayRef, Float32Array, Int32Array, RecordBatch};
+use arrow_schema::{DataType, Field, Schema};
+use chutoro_core::DataSource;
+use rstest::rstest;
+use std::sync::Arc;
+
+fn split_schema(scalar_type: DataType, scalar_nullable: bool) -> Arc<Schema> {
+    Arc::new(Schema::new(vec![
+        feature_field(3, false, false),
+        Field::new("extra", scalar_type, scalar_nullable),
+    ]))
+}
+
+fn split_batch(rows: &[Vec<f32>], extra: Vec<f32>) -> RecordBatch {
+    let list = build_list_array(rows, 3, false);
+    RecordBatch::try_new(
+        split_schema(DataType::Float32, false),
+        vec![
+            Arc::new(list) as ArrayRef,
+            Arc::new(Float32Array::from(extra)),
+        ],
+    )
+    .expect("batch")
+}
+
+#[rstest]
+fn concatenates_columns_per_row_across_batches() {
+    let bytes = write_parquet_batches(&[
+        split_batch(&[vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]], vec![7.0, 8.0]),
+        split_batch(&[vec![9.0, 10.0, 11.0]], vec![12.0]),
+    ]);
+    let provider =
+        DenseMatrixProvider::try_from_parquet_reader_columns("demo", bytes, &["features", "extra"])
+            .expect("columns must load");
+    assert_eq!(provider.len(), 3);
+    assert_eq!(provider.dimension(), 4);
+    assert_eq!(
+        provider.data(),
+        &[
+            1.0, 2.0, 3.0, 7.0, 4.0, 5.0, 6.0, 8.0, 9.0, 10.0, 11.0, 12.0
+        ]
+    );
+}
+
+#[rstest]
+fn honours_requested_column_order() {
+    let bytes = write_parquet_batches(&[split_batch(&[vec![1.0, 2.0, 3.0]], vec![4.0])]);
+    let provider =
+        DenseMatrixProvider::try_from_parquet_reader_columns("demo", bytes, &["extra", "features"])
+            .expect("columns must load");
+    assert_eq!(provider.data(), &[4.0, 1.0, 2.0, 3.0]);
+}
+
+#[rstest]
+fn loads_single_scalar_column() {
+    let bytes =
+        write_parquet_batches(&[split_batch(&[vec![0.0; 3], vec![0.0; 3]], vec![1.0, 4.0])]);
+    let provider = DenseMatrixProvider::try_from_parquet_reader("demo", bytes, "extra")
+        .expect("scalar column must load");
+    assert_eq!(provider.dimension(), 1);
+    let distance = provider.distance(0, 1).expect("distance");
+    assert!((distance - 3.0).abs() < 1.0e-5_f32);
+}
+
+#[rstest]
+fn rejects_empty_column_list() {
+    let bytes = write_parquet_batches(&[split_batch(&[vec![1.0, 2.0, 3.0]], vec![4.0])]);
+    let err = DenseMatrixProvider::try_from_parquet_reader_columns("demo", bytes, &[])
+        .expect_err("no columns must fail");
+    assert!(matches!(err, DenseMatrixProviderError::NoColumns));
+}
+
+#[rstest]
+fn reports_missing_column_by_name() {
+    let bytes = write_parquet_batches(&[split_batch(&[vec![1.0, 2.0, 3.0]], vec![4.0])]);
+    let err =
+        DenseMatrixProvider::try_from_parquet_reader_columns("demo", bytes, &["features", "gone"])
+            .expect_err("missing column must fail");
+    assert!(matches!(
+        err,
+        DenseMatrixProviderError::ColumnNotFound { column, available }
+            if column == "gone" && available == ["features", "extra"]
+    ));
+}
+
+#[rstest]
+fn rejects_nullable_scalar_column() {
+    let list = build_list_array(&[vec![1.0, 2.0, 3.0]], 3, false);
+    let batch = RecordBatch::try_new(
+        split_schema(DataType::Float32, true),
+        vec![
+            Arc::new(list) as ArrayRef,
+            Arc::new(Float32Array::from(vec![Some(4.0)])),
+        ],
+    )
+    .expect("batch");
+    let err = DenseMatrixProvider::try_from_parquet_reader_columns(
+        "demo",
+        write_parquet_batches(&[batch]),
+        &["features", "extra"],
+    )
+    .expect_err("nullable scalar must fail");
+    assert!(matches!(
+        err,
+        DenseMatrixProviderError::NullableField { column, nullable_child: false } if column == "extra"
+    ));
+}
+
+#[rstest]
+fn rejects_non_float_scalar_column() {
+    let list = build_list_array(&[vec![1.0, 2.0, 3.0]], 3, false);
+    let batch = RecordBatch::try_new(
+        split_schema(DataType::Int32, false),
+        vec![
+            Arc::new(list) as ArrayRef,
+            Arc::new(Int32Array::from(vec![4])),
+        ],
+    )
+    .expect("batch");
+    let err = DenseMatrixProvider::try_from_parquet_reader_columns(
+        "demo",
+        write_parquet_batches(&[batch]),
+        &["features", "extra"],
+    )
+    .expect_err("integer column must fail");
+    assert!(matches!(
+        err,
+        DenseMatrixProviderError::InvalidColumnType { column, .. } if column == "extra"
+    ));
+}
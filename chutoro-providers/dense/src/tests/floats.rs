@@ -0,0 +1,130 @@
+//! Tests for `Float16` and `Float64` feature values. Covers widening of half
+//! precision lists and scalars, the `Float64` opt-in, and narrowing to `f32`
+//! across the Arrow and Parquet ingestion paths.
+
+use super::{DenseMatrixProvider, DenseMatrixProviderError, support::*};
+use crate::DenseIngestOptions;
+use arrow_array::types::{ArrowPrimitiveType, Float16Type};
+use arrow_array::{ArrayRef, FixedSizeListArray, Float16Array, Float64Array, RecordBatch};
+use arrow_schema::{DataType, Field, Schema};
+use bytes::Bytes;
+use rstest::rstest;
+use std::sync::Arc;
+
+type F16 = <Float16Type as ArrowPrimitiveType>::Native;
+
+fn float_list(values: ArrayRef, dimension: i32) -> FixedSizeListArray {
+    let child = Arc::new(Field::new("item", values.data_type().clone(), false));
+    FixedSizeListArray::new(child, dimension, values, None)
+}
+
+fn f16_values(values: &[f32]) -> ArrayRef {
+    Arc::new(Float16Array::from(
+        values
+            .iter()
+            .copied()
+            .map(F16::from_f32)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+fn parquet_of(columns: Vec<(&str, ArrayRef)>) -> Bytes {
+    let fields: Vec<Field> = columns
+        .iter()
+        .map(|(name, array)| Field::new(*name, array.data_type().clone(), false))
+        .collect();
+    let arrays = columns.into_iter().map(|(_, array)| array).collect();
+    let batch =
+        RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays).expect("batch must build");
+    write_parquet_batches(&[batch])
+}
+
+fn lossy() -> DenseIngestOptions {
+    DenseIngestOptions::default().with_lossy_f64(true)
+}
+
+#[rstest]
+fn widens_float16_lists_from_arrow() {
+    let array = float_list(f16_values(&[0.5, 1.5, -2.0, 4.0]), 2);
+    let provider =
+        DenseMatrixProvider::try_from_fixed_size_list("demo", &array).expect("f16 must load");
+    assert_eq!(provider.dimension(), 2);
+    assert_eq!(provider.data(), &[0.5, 1.5, -2.0, 4.0]);
+}
+
+#[rstest]
+fn widens_float16_list_and_scalar_columns_from_parquet() {
+    let bytes = parquet_of(vec![
+        (
+            "features",
+            Arc::new(float_list(f16_values(&[1.0, 2.0, 3.0, 4.0]), 2)),
+        ),
+        ("weight", f16_values(&[0.25, 0.75])),
+    ]);
+    let provider = DenseMatrixProvider::try_from_parquet_reader_columns(
+        "demo",
+        bytes,
+        &["features", "weight"],
+    )
+    .expect("f16 columns must load");
+    assert_eq!(provider.dimension(), 3);
+    assert_eq!(provider.data(), &[1.0, 2.0, 0.25, 3.0, 4.0, 0.75]);
+}
+
+#[rstest]
+#[case::list(true)]
+#[case::scalar(false)]
+fn float64_parquet_columns_require_opt_in(#[case] as_list: bool) {
+    let doubles: ArrayRef = Arc::new(Float64Array::from(vec![0.1, 0.2]));
+    let column: ArrayRef = if as_list {
+        Arc::new(float_list(doubles, 2))
+    } else {
+        doubles
+    };
+    let bytes = parquet_of(vec![("features", column)]);
+
+    let err = DenseMatrixProvider::try_from_parquet_reader("demo", bytes.clone(), "features")
+        .expect_err("f64 must be rejected by default");
+    assert!(matches!(
+        err,
+        DenseMatrixProviderError::LossyNarrowingDisabled
+    ));
+
+    let provider = DenseMatrixProvider::try_from_parquet_reader_columns_with_options(
+        "demo",
+        bytes,
+        &["features"],
+        lossy(),
+    )
+    .expect("f64 must load once narrowing is allowed");
+    let expected: Vec<f32> = [0.1_f64, 0.2].iter().map(|&v| v as f32).collect();
+    assert_eq!(provider.data(), expected.as_slice());
+}
+
+#[rstest]
+fn float64_arrow_lists_require_opt_in() {
+    let array = float_list(Arc::new(Float64Array::from(vec![1.0, 2.0])), 1);
+    assert!(matches!(
+        DenseMatrixProvider::try_from_fixed_size_list("demo", &array),
+        Err(DenseMatrixProviderError::LossyNarrowingDisabled)
+    ));
+    let provider =
+        DenseMatrixProvider::try_from_fixed_size_list_with_options("demo", &array, lossy())
+            .expect("f64 must load once narrowing is allowed");
+    assert_eq!(provider.data(), &[1.0, 2.0]);
+}
+
+#[rstest]
+fn rejects_integer_children_even_with_lossy_options() {
+    let field = Arc::new(Field::new("item", DataType::Int64, false));
+    let values: ArrayRef = Arc::new(arrow_array::Int64Array::from(vec![1, 2]));
+    let array = FixedSizeListArray::new(field, 2, values, None);
+    let err = DenseMatrixProvider::try_from_fixed_size_list_with_options("demo", &array, lossy())
+        .expect_err("integers are not feature values");
+    assert!(matches!(
+        err,
+        DenseMatrixProviderError::InvalidListValueType {
+            actual: DataType::Int64
+        }
+    ));
+}
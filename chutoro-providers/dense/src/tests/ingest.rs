@@ -48,7 +48,7 @@ fn matrix_provider_parquet_missing_column() {
         .expect_err("missing column");
     assert!(matches!(
         err,
-        DenseMatrixProviderError::ColumnNotFound { column } if column == "unknown"
+        DenseMatrixProviderError::ColumnNotFound { column, .. } if column == "unknown"
     ));
 }
 
@@ -0,0 +1,137 @@
+//! Tests for loading dense rows from Arrow IPC streams and record-batch
+//! readers. Covers multi-batch streams, column concatenation, narrowing
+//! options, and the errors raised for malformed streams and missing columns.
+
+use super::{DenseMatrixProvider, DenseMatrixProviderError, support::*};
+use crate::DenseIngestOptions;
+use arrow_array::{ArrayRef, Float64Array, RecordBatch, RecordBatchIterator};
+use arrow_ipc::writer::StreamWriter;
+use arrow_schema::{ArrowError, DataType, Field, Schema};
+use chutoro_core::DataSource;
+use rstest::rstest;
+use std::sync::Arc;
+
+fn ipc_stream(batches: &[RecordBatch]) -> Vec<u8> {
+    let schema = batches.first().expect("at least one batch").schema();
+    let mut bytes = Vec::new();
+    let mut writer = StreamWriter::try_new(&mut bytes, &schema).expect("writer");
+    for batch in batches {
+        writer.write(batch).expect("write batch");
+    }
+    writer.finish().expect("finish");
+    drop(writer);
+    bytes
+}
+
+fn feature_batch(rows: &[Vec<f32>]) -> RecordBatch {
+    let schema = Arc::new(Schema::new(vec![feature_field(3, false, false)]));
+    let list = build_list_array(rows, 3, false);
+    RecordBatch::try_new(schema, vec![Arc::new(list) as ArrayRef]).expect("batch")
+}
+
+fn wide_batch() -> RecordBatch {
+    let schema = Arc::new(Schema::new(vec![
+        feature_field(3, false, false),
+        Field::new("extra", DataType::Float64, false),
+    ]));
+    let list = build_list_array(&[vec![1.0, 2.0, 3.0]], 3, false);
+    RecordBatch::try_new(
+        schema,
+        vec![
+            Arc::new(list) as ArrayRef,
+            Arc::new(Float64Array::from(vec![4.0])),
+        ],
+    )
+    .expect("batch")
+}
+
+#[rstest]
+fn loads_every_batch_of_a_stream() {
+    let bytes = ipc_stream(&[
+        feature_batch(&[vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]),
+        feature_batch(&[vec![7.0, 8.0, 9.0]]),
+    ]);
+
+    let provider = DenseMatrixProvider::try_from_ipc_stream("pushed", bytes.as_slice(), "features")
+        .expect("stream must load");
+
+    assert_eq!(provider.len(), 3);
+    assert_eq!(provider.dimension(), 3);
+    assert_eq!(
+        provider.data(),
+        &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]
+    );
+}
+
+#[rstest]
+fn concatenates_columns_when_narrowing_is_allowed() {
+    let bytes = ipc_stream(&[wide_batch()]);
+    let columns = ["features", "extra"];
+
+    let rejected =
+        DenseMatrixProvider::try_from_ipc_stream_columns("pushed", bytes.as_slice(), &columns);
+    let provider = DenseMatrixProvider::try_from_ipc_stream_columns_with_options(
+        "pushed",
+        bytes.as_slice(),
+        &columns,
+        DenseIngestOptions::default().with_lossy_f64(true),
+    )
+    .expect("narrowing is allowed");
+
+    assert!(matches!(
+        rejected,
+        Err(DenseMatrixProviderError::LossyNarrowingDisabled)
+    ));
+    assert_eq!(provider.data(), &[1.0, 2.0, 3.0, 4.0]);
+}
+
+#[rstest]
+#[case::missing_column(&["absent"])]
+#[case::no_columns(&[])]
+fn rejects_unusable_column_requests(#[case] columns: &[&str]) {
+    let bytes = ipc_stream(&[feature_batch(&[vec![1.0, 2.0, 3.0]])]);
+
+    let err = DenseMatrixProvider::try_from_ipc_stream_columns("pushed", bytes.as_slice(), columns)
+        .expect_err("request must fail");
+
+    assert!(matches!(
+        err,
+        DenseMatrixProviderError::ColumnNotFound { .. } | DenseMatrixProviderError::NoColumns
+    ));
+}
+
+#[rstest]
+#[case::not_ipc(b"PAR1 definitely not an ipc stream".to_vec())]
+#[case::truncated({
+    let bytes = ipc_stream(&[feature_batch(&[vec![1.0, 2.0, 3.0]])]);
+    bytes[..bytes.len() / 2].to_vec()
+})]
+fn malformed_streams_surface_arrow_errors(#[case] bytes: Vec<u8>) {
+    let err = DenseMatrixProvider::try_from_ipc_stream("pushed", bytes.as_slice(), "features")
+        .expect_err("malformed stream must fail");
+
+    assert!(matches!(err, DenseMatrixProviderError::Arrow(_)), "{err:?}");
+}
+
+#[rstest]
+fn reader_errors_stop_ingestion() {
+    let first = feature_batch(&[vec![1.0, 2.0, 3.0]]);
+    let schema = first.schema();
+    let batches = [
+        Ok(first),
+        Err(ArrowError::IpcError("connection reset".into())),
+    ];
+
+    let err = DenseMatrixProvider::try_from_record_batch_reader(
+        "flight",
+        RecordBatchIterator::new(batches, schema),
+        &["features"],
+        DenseIngestOptions::default(),
+    )
+    .expect_err("reader error must fail");
+
+    assert!(matches!(
+        err,
+        DenseMatrixProviderError::Arrow(ArrowError::IpcError(_))
+    ));
+}
@@ -0,0 +1,81 @@
+//! Tests for building dense providers from row- and column-major buffers
+//! with arbitrary strides.
+
+use super::{DenseMatrixProvider, DenseMatrixProviderError};
+use crate::{Layout, MatrixLayout};
+use chutoro_core::DataSource;
+use rstest::rstest;
+
+/// The points (1, 2, 3) and (4, 5, 6) in every supported layout.
+#[rstest]
+#[case::packed_rows(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], MatrixLayout::new(2, 3, Layout::RowMajor))]
+#[case::padded_rows(
+    vec![1.0, 2.0, 3.0, 0.0, 4.0, 5.0, 6.0],
+    MatrixLayout::new(2, 3, Layout::RowMajor).with_stride(4)
+)]
+#[case::packed_columns(
+    vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0],
+    MatrixLayout::new(2, 3, Layout::ColMajor)
+)]
+#[case::padded_columns(
+    vec![1.0, 4.0, 0.0, 2.0, 5.0, 0.0, 3.0, 6.0, 0.0],
+    MatrixLayout::new(2, 3, Layout::ColMajor).with_stride(3)
+)]
+fn every_layout_loads_the_same_rows(#[case] values: Vec<f32>, #[case] layout: MatrixLayout) {
+    let provider = DenseMatrixProvider::from_parts_with_layout("layout", values, layout)
+        .expect("layout must be valid");
+
+    assert_eq!(provider.len(), 2);
+    assert_eq!(provider.dimension(), 3);
+    assert_eq!(provider.data(), &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+}
+
+#[rstest]
+fn trailing_values_are_ignored() {
+    let values = vec![3.0, 4.0, 0.0, 0.0, 99.0];
+    let layout = MatrixLayout::new(2, 2, Layout::RowMajor);
+
+    let provider = DenseMatrixProvider::from_parts_with_layout("layout", values, layout)
+        .expect("layout must be valid");
+
+    assert_eq!(provider.data(), &[3.0, 4.0, 0.0, 0.0]);
+    assert_eq!(provider.distance(0, 1).expect("rows exist"), 5.0);
+}
+
+#[rstest]
+#[case::row_stride(
+    MatrixLayout::new(2, 3, Layout::RowMajor).with_stride(2),
+    DenseMatrixProviderError::StrideTooShort { stride: 2, minimum: 3 }
+)]
+#[case::column_stride(
+    MatrixLayout::new(4, 1, Layout::ColMajor).with_stride(3),
+    DenseMatrixProviderError::StrideTooShort { stride: 3, minimum: 4 }
+)]
+#[case::short_buffer(
+    MatrixLayout::new(2, 3, Layout::ColMajor).with_stride(4),
+    DenseMatrixProviderError::InsufficientValues { expected: 10, actual: 6 }
+)]
+#[case::overflow(
+    MatrixLayout::new(usize::MAX, 2, Layout::RowMajor),
+    DenseMatrixProviderError::CapacityOverflow { rows: usize::MAX, dimension: 2 }
+)]
+fn invalid_layouts_are_rejected(
+    #[case] layout: MatrixLayout,
+    #[case] expected: DenseMatrixProviderError,
+) {
+    let err = DenseMatrixProvider::from_parts_with_layout("layout", vec![0.0; 6], layout)
+        .expect_err("layout must be rejected");
+
+    assert_eq!(err.to_string(), expected.to_string());
+}
+
+#[rstest]
+#[case::no_rows(MatrixLayout::new(0, 3, Layout::ColMajor).with_stride(0))]
+#[case::no_columns(MatrixLayout::new(3, 0, Layout::RowMajor))]
+fn empty_layouts_need_no_values(#[case] layout: MatrixLayout) {
+    let provider = DenseMatrixProvider::from_parts_with_layout("empty", Vec::new(), layout)
+        .expect("empty layouts hold no elements");
+
+    assert!(provider.data().is_empty());
+    assert_eq!(provider.dimension(), layout.dimension());
+}
@@ -1,8 +1,21 @@
-//! Dense provider test suite covering errors, ingestion, providers, sources, and shared fixtures.
+//! Dense provider test suite covering multi-column loading, float widths, errors, ingestion, IPC streams, strided layouts, ndarray matrices, Polars frames, normalization, prediction, quantization, providers, query batches, sources, and shared fixtures.
 pub(crate) use super::{DenseMatrixProvider, DenseMatrixProviderError, DenseSource};
 
+mod columns;
 mod errors;
+mod floats;
 mod ingest;
+mod ipc;
+mod layout;
+#[cfg(feature = "ndarray")]
+mod ndarray_matrix;
+mod normalization;
+#[cfg(feature = "polars")]
+mod polars;
+#[cfg(feature = "predict")]
+mod predict;
 mod provider;
+mod quantization;
+mod query_batch;
 mod source;
 mod support;
@@ -0,0 +1,62 @@
+//! Tests for building dense providers from `ndarray` matrices. Covers
+//! standard, transposed, and sliced layouts, and the contiguity check.
+
+use super::{DenseMatrixProvider, DenseMatrixProviderError};
+use chutoro_core::DataSource;
+use ndarray::{Array2, ArrayView2, array, s};
+use rstest::{fixture, rstest};
+
+#[fixture]
+fn matrix() -> Array2<f32> {
+    array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]
+}
+
+#[rstest]
+fn standard_layout_loads_with_either_constructor(matrix: Array2<f32>) {
+    let copied = DenseMatrixProvider::from_ndarray("matrix", matrix.view());
+    let checked = DenseMatrixProvider::try_from_ndarray("matrix", matrix.view())
+        .expect("standard layout is contiguous");
+
+    for provider in [copied, checked] {
+        assert_eq!(provider.len(), 2);
+        assert_eq!(provider.dimension(), 3);
+        assert_eq!(provider.data(), &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    }
+}
+
+fn transposed(matrix: &Array2<f32>) -> ArrayView2<'_, f32> {
+    matrix.t()
+}
+
+fn without_first_column(matrix: &Array2<f32>) -> ArrayView2<'_, f32> {
+    matrix.slice(s![.., 1..])
+}
+
+#[rstest]
+#[case::transposed(transposed, &[1.0, 4.0, 2.0, 5.0, 3.0, 6.0])]
+#[case::column_slice(without_first_column, &[2.0, 3.0, 5.0, 6.0])]
+fn strided_views_copy_in_row_order(
+    matrix: Array2<f32>,
+    #[case] strided: fn(&Array2<f32>) -> ArrayView2<'_, f32>,
+    #[case] expected: &[f32],
+) {
+    let view = strided(&matrix);
+
+    let provider = DenseMatrixProvider::from_ndarray("matrix", view);
+    let err = DenseMatrixProvider::try_from_ndarray("matrix", view)
+        .expect_err("strided views are not contiguous");
+
+    assert_eq!(provider.data(), expected);
+    assert!(matches!(err, DenseMatrixProviderError::NonContiguousArray));
+}
+
+#[rstest]
+fn empty_matrices_load_as_empty_providers() {
+    let matrix = Array2::<f32>::zeros((0, 4));
+
+    let provider = DenseMatrixProvider::try_from_ndarray("empty", matrix.view())
+        .expect("empty matrices are contiguous");
+
+    assert!(provider.is_empty());
+    assert_eq!(provider.dimension(), 4);
+}
@@ -0,0 +1,97 @@
+//! Tests for per-feature normalization of dense providers. Covers z-score and
+//! min-max fitting, constant features, refitting, and applying the stored
+//! statistics to query vectors.
+
+use super::{DenseMatrixProvider, DenseMatrixProviderError, support::*};
+use crate::{FeatureScaling, Normalization};
+use chutoro_core::DataSource;
+use rstest::rstest;
+
+fn provider(rows: &[[f32; 3]]) -> DenseMatrixProvider {
+    DenseMatrixProvider::try_from_fixed_size_list("demo", &build_array(rows)).expect("valid matrix")
+}
+
+fn assert_close(actual: &[f32], expected: &[f32]) {
+    assert_eq!(actual.len(), expected.len());
+    for (left, right) in actual.iter().zip(expected) {
+        assert!((left - right).abs() < 1.0e-5, "{actual:?} != {expected:?}");
+    }
+}
+
+#[rstest]
+fn z_score_centres_and_scales_each_feature() {
+    let provider =
+        provider(&[[1.0, 10.0, 5.0], [3.0, 30.0, 5.0]]).with_normalization(Normalization::ZScore);
+
+    assert_close(provider.data(), &[-1.0, -1.0, 0.0, 1.0, 1.0, 0.0]);
+    let scaling = provider.scaling().expect("scaling must be stored");
+    assert_eq!(scaling.method(), Normalization::ZScore);
+    assert_close(scaling.offsets(), &[2.0, 20.0, 5.0]);
+    assert_close(scaling.scales(), &[1.0, 10.0, 1.0]);
+}
+
+#[rstest]
+fn min_max_maps_each_range_onto_unit_interval() {
+    let provider = provider(&[[0.0, -5.0, 2.0], [4.0, 5.0, 2.0], [2.0, 0.0, 2.0]])
+        .with_normalization(Normalization::MinMax);
+
+    assert_close(
+        provider.data(),
+        &[0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.5, 0.5, 0.0],
+    );
+}
+
+#[rstest]
+fn normalization_equalizes_feature_influence_on_distance() {
+    let provider = provider(&[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1000.0, 0.0]])
+        .with_normalization(Normalization::MinMax);
+
+    let near = provider.distance(0, 1).expect("distance must succeed");
+    let far = provider.distance(0, 2).expect("distance must succeed");
+    assert!((near - far).abs() < 1.0e-5);
+}
+
+#[rstest]
+fn refitting_starts_from_the_original_values() {
+    let once =
+        provider(&[[1.0, 10.0, 0.0], [3.0, 50.0, 4.0]]).with_normalization(Normalization::MinMax);
+    let twice = provider(&[[1.0, 10.0, 0.0], [3.0, 50.0, 4.0]])
+        .with_normalization(Normalization::ZScore)
+        .with_normalization(Normalization::MinMax);
+
+    assert_close(twice.data(), once.data());
+    assert_eq!(twice.scaling(), once.scaling());
+}
+
+#[rstest]
+fn stored_scaling_transforms_queries_like_rows() {
+    let provider =
+        provider(&[[1.0, 10.0, 5.0], [3.0, 30.0, 9.0]]).with_normalization(Normalization::ZScore);
+    let scaling = provider.scaling().expect("scaling must be stored");
+
+    let mut query = [3.0, 30.0, 9.0];
+    scaling
+        .apply(&mut query)
+        .expect("query must match dimension");
+    assert_close(&query, &provider.data()[3..]);
+}
+
+#[rstest]
+fn applying_scaling_rejects_mismatched_dimension() {
+    let scaling = FeatureScaling::fit(Normalization::ZScore, &[1.0, 2.0, 3.0, 4.0], 2);
+    let err = scaling
+        .apply(&mut [1.0, 2.0, 3.0])
+        .expect_err("dimension mismatch must fail");
+    assert!(matches!(
+        err,
+        DenseMatrixProviderError::ScalingDimensionMismatch {
+            expected: 2,
+            actual: 3
+        }
+    ));
+}
+
+#[rstest]
+fn unnormalized_providers_have_no_scaling() {
+    assert!(provider(&[[1.0, 2.0, 3.0]]).scaling().is_none());
+}
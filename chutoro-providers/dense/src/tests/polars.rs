@@ -0,0 +1,110 @@
+//! Tests for loading dense rows from Polars data frames. Covers `Array`
+//! columns, column concatenation across mismatched chunks, narrowing, nulls,
+//! and the errors raised for unusable column requests.
+
+use super::{DenseMatrixProvider, DenseMatrixProviderError};
+use crate::DenseIngestOptions;
+use chutoro_core::DataSource;
+use polars_core::prelude::*;
+use rstest::rstest;
+
+fn array_series(name: &str, rows: &[[f32; 3]]) -> Series {
+    let lists: Vec<Series> = rows
+        .iter()
+        .map(|row| Series::new(PlSmallStr::EMPTY, row.as_slice()))
+        .collect();
+    Series::new(name.into(), lists)
+        .cast(&DataType::Array(Box::new(DataType::Float32), 3))
+        .expect("lists of three floats cast to an array")
+}
+
+fn frame(columns: Vec<Series>) -> DataFrame {
+    DataFrame::new(columns.into_iter().map(Column::from).collect()).expect("frame")
+}
+
+#[rstest]
+fn loads_array_columns_from_every_chunk() {
+    let mut features = array_series("features", &[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    features
+        .append(&array_series("features", &[[7.0, 8.0, 9.0]]))
+        .expect("append");
+    assert_eq!(features.n_chunks(), 2);
+
+    let provider =
+        DenseMatrixProvider::try_from_polars("frame", &frame(vec![features]), "features")
+            .expect("array column must load");
+
+    assert_eq!(provider.len(), 3);
+    assert_eq!(provider.dimension(), 3);
+    assert_eq!(
+        provider.data(),
+        &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]
+    );
+}
+
+#[rstest]
+fn concatenates_columns_with_different_chunking() {
+    let features = array_series("features", &[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    let mut extra = Series::new("extra".into(), [10.0_f32]);
+    extra
+        .append(&Series::new("extra".into(), [20.0_f32]))
+        .expect("append");
+
+    let provider = DenseMatrixProvider::try_from_polars_columns(
+        "frame",
+        &frame(vec![features, extra]),
+        &["features", "extra"],
+    )
+    .expect("columns must load");
+
+    assert_eq!(provider.dimension(), 4);
+    assert_eq!(provider.data(), &[1.0, 2.0, 3.0, 10.0, 4.0, 5.0, 6.0, 20.0]);
+}
+
+#[rstest]
+fn narrows_float64_only_when_allowed() {
+    let frame = frame(vec![Series::new("x".into(), [0.5_f64, 1.5])]);
+
+    let rejected = DenseMatrixProvider::try_from_polars("frame", &frame, "x");
+    let provider = DenseMatrixProvider::try_from_polars_columns_with_options(
+        "frame",
+        &frame,
+        &["x"],
+        DenseIngestOptions::default().with_lossy_f64(true),
+    )
+    .expect("narrowing is allowed");
+
+    assert!(matches!(
+        rejected,
+        Err(DenseMatrixProviderError::LossyNarrowingDisabled)
+    ));
+    assert_eq!(provider.data(), &[0.5, 1.5]);
+}
+
+#[rstest]
+fn rejects_null_rows() {
+    let frame = frame(vec![Series::new("x".into(), [Some(1.0_f32), None])]);
+
+    let err = DenseMatrixProvider::try_from_polars("frame", &frame, "x")
+        .expect_err("null rows must be rejected");
+
+    assert!(matches!(err, DenseMatrixProviderError::NullRow { row: 1 }));
+}
+
+#[rstest]
+#[case::missing_column(&["absent"])]
+#[case::no_columns(&[])]
+#[case::integer_column(&["ids"])]
+fn rejects_unusable_column_requests(#[case] columns: &[&str]) {
+    let frame = frame(vec![Series::new("ids".into(), [1_i64, 2])]);
+
+    let err = DenseMatrixProvider::try_from_polars_columns("frame", &frame, columns)
+        .expect_err("request must fail");
+
+    assert!(matches!(
+        err,
+        DenseMatrixProviderError::ColumnNotFound { .. }
+            | DenseMatrixProviderError::NoColumns
+            | DenseMatrixProviderError::InvalidColumnType { .. }
+    ));
+}
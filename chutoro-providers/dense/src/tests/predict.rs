@@ -0,0 +1,140 @@
+//! Tests for `DensePredictor`. Covers labelling query batches against the
+//! blobs they were drawn near, scaling queries like normalized training rows,
+//! and the errors for mismatched widths and missing indexes.
+
+use super::{DenseMatrixProvider, support::*};
+use crate::{DensePredictor, Normalization, PredictError};
+use arrow_array::{Array, ArrayRef, Float32Array, RecordBatch, StructArray, UInt64Array};
+use arrow_schema::{DataType, Field, Schema};
+use chutoro_core::{Chutoro, ChutoroBuilder, ClusteringResult, CpuHnsw, HnswParams};
+use rstest::rstest;
+use std::sync::Arc;
+
+/// Two tight blobs of five points each, far apart.
+const TRAINING: [[f32; 3]; 10] = [
+    [0.0, 0.0, 0.0],
+    [0.1, 0.0, 0.0],
+    [0.0, 0.1, 0.0],
+    [0.0, 0.0, 0.1],
+    [0.1, 0.1, 0.0],
+    [10.0, 10.0, 10.0],
+    [10.1, 10.0, 10.0],
+    [10.0, 10.1, 10.0],
+    [10.0, 10.0, 10.1],
+    [10.1, 10.1, 10.0],
+];
+
+fn fit(training: &DenseMatrixProvider) -> (Chutoro, ClusteringResult) {
+    let params = HnswParams::new(4, 16)
+        .expect("valid params")
+        .with_rng_seed(7);
+    let (index, harvest) = CpuHnsw::build_with_edges(training, params).expect("index builds");
+    let chutoro = ChutoroBuilder::new()
+        .with_min_cluster_size(3)
+        .with_prebuilt_index(Arc::new(index), harvest)
+        .build()
+        .expect("valid configuration");
+    let clustering = chutoro.run(training).expect("training run succeeds");
+    (chutoro, clustering)
+}
+
+fn batch(rows: &[[f32; 3]]) -> RecordBatch {
+    let array = build_array(rows);
+    let schema = Schema::new(vec![Field::new(
+        "embedding",
+        array.data_type().clone(),
+        false,
+    )]);
+    RecordBatch::try_new(Arc::new(schema), vec![Arc::new(array) as ArrayRef]).expect("valid batch")
+}
+
+fn columns(predictions: &StructArray) -> (&UInt64Array, &Float32Array) {
+    let labels = predictions
+        .column_by_name("label")
+        .and_then(|column| column.as_any().downcast_ref::<UInt64Array>())
+        .expect("labels are UInt64");
+    let strengths = predictions
+        .column_by_name("strength")
+        .and_then(|column| column.as_any().downcast_ref::<Float32Array>())
+        .expect("strengths are Float32");
+    (labels, strengths)
+}
+
+#[rstest]
+fn queries_take_the_label_of_their_blob() {
+    let training = DenseMatrixProvider::try_from_fixed_size_list("train", &build_array(&TRAINING))
+        .expect("valid matrix");
+    let (chutoro, clustering) = fit(&training);
+    let predictor = DensePredictor::new(&chutoro, &training, &clustering);
+
+    let queries = batch(&[[0.05, 0.05, 0.0], [10.05, 10.05, 10.0], [0.0, 0.05, 0.05]]);
+    let predictions = predictor
+        .predict_batch_arrow(&queries, "embedding")
+        .expect("prediction succeeds");
+
+    let (labels, strengths) = columns(&predictions);
+    assert_eq!(predictions.len(), 3);
+    let assignments = clustering.assignments();
+    assert_eq!(labels.value(0), assignments[0].get());
+    assert_eq!(labels.value(1), assignments[5].get());
+    assert_eq!(labels.value(2), labels.value(0));
+    assert_ne!(labels.value(0), labels.value(1));
+    assert!(strengths.values().iter().all(|&strength| strength > 0.0));
+}
+
+#[rstest]
+fn queries_are_scaled_like_normalized_training_rows() {
+    let training = DenseMatrixProvider::try_from_fixed_size_list("train", &build_array(&TRAINING))
+        .expect("valid matrix")
+        .with_normalization(Normalization::MinMax);
+    let (chutoro, clustering) = fit(&training);
+    let predictor = DensePredictor::new(&chutoro, &training, &clustering);
+
+    let predictions = predictor
+        .predict_batch_arrow(&batch(&[[10.05, 10.05, 10.0]]), "embedding")
+        .expect("prediction succeeds");
+
+    let (labels, _) = columns(&predictions);
+    assert_eq!(labels.value(0), clustering.assignments()[5].get());
+}
+
+#[rstest]
+fn query_width_must_match_training_rows() {
+    let training = DenseMatrixProvider::try_from_fixed_size_list("train", &build_array(&TRAINING))
+        .expect("valid matrix");
+    let (chutoro, clustering) = fit(&training);
+    let predictor = DensePredictor::new(&chutoro, &training, &clustering);
+    let values: ArrayRef = Arc::new(Float32Array::from(vec![0.0, 10.0]));
+    let schema = Schema::new(vec![Field::new("x", DataType::Float32, false)]);
+    let queries = RecordBatch::try_new(Arc::new(schema), vec![values]).expect("valid batch");
+
+    let err = predictor
+        .predict_batch_arrow(&queries, "x")
+        .expect_err("width mismatch must fail");
+
+    assert!(matches!(
+        err,
+        PredictError::DimensionMismatch {
+            expected: 3,
+            actual: 1
+        }
+    ));
+}
+
+#[rstest]
+fn prediction_requires_a_prebuilt_index() {
+    let training = DenseMatrixProvider::try_from_fixed_size_list("train", &build_array(&TRAINING))
+        .expect("valid matrix");
+    let chutoro = ChutoroBuilder::new()
+        .with_min_cluster_size(3)
+        .build()
+        .expect("valid configuration");
+    let clustering = chutoro.run(&training).expect("training run succeeds");
+    let predictor = DensePredictor::new(&chutoro, &training, &clustering);
+
+    let err = predictor
+        .predict_batch_arrow(&batch(&[[0.0, 0.0, 0.0]]), "embedding")
+        .expect_err("missing index must fail");
+
+    assert!(matches!(err, PredictError::Search(_)));
+}
@@ -9,7 +9,7 @@
 use super::{DenseMatrixProvider, DenseMatrixProviderError, support::*};
 use arrow_array::{ArrayRef, FixedSizeListArray};
 use arrow_schema::{DataType, Field};
-use chutoro_core::DataSource;
+use chutoro_core::{DataSource, MetricClass};
 use rstest::rstest;
 use std::sync::Arc;
 
@@ -23,6 +23,10 @@ fn matrix_provider_from_fixed_size_list() {
     assert_eq!(provider.data(), &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
     let distance = provider.distance(0, 1).expect("distance should work");
     assert!((distance - (27.0_f32).sqrt()).abs() < 1.0e-5_f32);
+    assert_eq!(
+        provider.metric_descriptor().class(),
+        Some(MetricClass::Metric)
+    );
 }
 
 #[rstest]
@@ -187,6 +191,19 @@ fn matrix_provider_distance_batch_preserves_output_on_error() {
     assert_eq!(out, vec![10.0_f32, 20.0_f32]);
 }
 
+#[rstest]
+fn matrix_provider_distance_f64_keeps_sub_f32_differences() {
+    let array = build_array(&[[0.0, 0.0, 0.0], [3000.0, 0.001, 0.0]]);
+    let provider =
+        DenseMatrixProvider::try_from_fixed_size_list("demo", &array).expect("valid matrix");
+    let single = provider.distance(0, 1).expect("distance should work");
+    let double = provider.distance_f64(0, 1).expect("distance should work");
+    assert_eq!(single, 3000.0_f32);
+    assert!(double > 3000.0_f64);
+    let expected = 3000.0_f64.hypot(f64::from(0.001_f32));
+    assert!((double - expected).abs() < 1.0e-12_f64);
+}
+
 #[rstest]
 fn matrix_provider_distance_out_of_bounds() {
     let array = build_array(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
@@ -296,3 +313,35 @@ fn matrix_provider_rejects_non_float_children() {
         DenseMatrixProviderError::InvalidListValueType { .. }
     ));
 }
+
+#[rstest]
+fn row_ids_survive_quantization() {
+    let array = build_array(&[[0.0, 0.0, 0.0], [3.0, 4.0, 0.0]]);
+    let provider = DenseMatrixProvider::try_from_fixed_size_list("demo", &array)
+        .expect("valid matrix")
+        .with_ids(vec!["left".into(), "right".into()])
+        .expect("one identifier per row");
+    assert_eq!(
+        provider.row_ids().and_then(|ids| ids.index_of("right")),
+        Some(1)
+    );
+
+    let quantized = provider
+        .quantize(crate::Quantization::Int8)
+        .expect("int8 quantization always succeeds");
+
+    assert_eq!(quantized.row_ids().and_then(|ids| ids.id(0)), Some("left"));
+}
+
+#[rstest]
+fn row_ids_must_cover_every_row() {
+    let array = build_array(&[[0.0, 0.0, 0.0], [3.0, 4.0, 0.0]]);
+    let result = DenseMatrixProvider::try_from_fixed_size_list("demo", &array)
+        .expect("valid matrix")
+        .with_ids(vec!["only".into()]);
+
+    assert!(matches!(
+        result,
+        Err(chutoro_core::RowIdError::LengthMismatch { ids: 1, rows: 2 })
+    ));
+}
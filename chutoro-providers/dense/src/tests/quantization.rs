@@ -0,0 +1,185 @@
+//! Tests for quantized storage. Covers scalar and product quantization
+//! accuracy, the memory saving, asymmetric query distances, carried-over
+//! scaling, and the documented clustering-quality tolerance.
+
+use std::num::NonZeroUsize;
+
+use super::{DenseMatrixProvider, DenseMatrixProviderError, support::*};
+use crate::{Normalization, Quantization};
+use chutoro_core::{ChutoroBuilder, DataSource, adjusted_rand_index};
+use rstest::rstest;
+
+const PRODUCT: Quantization = Quantization::Product {
+    subspaces: NonZeroUsize::new(4).expect("literal is non-zero"),
+};
+
+/// `clusters` Gaussian blobs of `per_cluster` rows each, with centres in
+/// `[-10, 10]` and unit variance per feature.
+fn blobs(clusters: usize, per_cluster: usize, dimension: usize) -> Vec<Vec<f32>> {
+    let mut state = 0x9e37_79b9_u32;
+    let mut uniform = move || {
+        state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        ((state >> 8) as f32 + 0.5) / (1 << 24) as f32
+    };
+    let centres: Vec<Vec<f32>> = (0..clusters)
+        .map(|_| (0..dimension).map(|_| uniform() * 20.0 - 10.0).collect())
+        .collect();
+    let mut rows = Vec::with_capacity(clusters * per_cluster);
+    for centre in &centres {
+        for _ in 0..per_cluster {
+            rows.push(
+                centre
+                    .iter()
+                    .map(|value| {
+                        // Box-Muller transform.
+                        let radius = (-2.0 * uniform().ln()).sqrt();
+                        value + radius * (std::f32::consts::TAU * uniform()).cos()
+                    })
+                    .collect(),
+            );
+        }
+    }
+    rows
+}
+
+fn provider(rows: &[Vec<f32>]) -> DenseMatrixProvider {
+    let dimension = rows.first().map_or(0, Vec::len);
+    DenseMatrixProvider::try_from_fixed_size_list(
+        "blobs",
+        &build_list_array(rows, dimension, false),
+    )
+    .expect("valid matrix")
+}
+
+fn labels(source: &(impl DataSource + Sync)) -> Vec<usize> {
+    ChutoroBuilder::new()
+        .with_min_cluster_size(20)
+        .build()
+        .expect("configuration must be valid")
+        .run(source)
+        .expect("run must succeed")
+        .assignments()
+        .iter()
+        .map(|id| id.get() as usize)
+        .collect()
+}
+
+#[rstest]
+fn int8_distances_stay_within_one_step_per_feature() {
+    let rows = blobs(3, 20, 16);
+    let exact = provider(&rows);
+    let quantized = provider(&rows)
+        .quantize(Quantization::Int8)
+        .expect("int8 quantization must succeed");
+
+    // Each coordinate difference is off by at most one level of its
+    // feature's range.
+    let bound = (0..16)
+        .map(|feature| {
+            let (low, high) = rows.iter().fold((f32::MAX, f32::MIN), |(low, high), row| {
+                (low.min(row[feature]), high.max(row[feature]))
+            });
+            ((high - low) / 255.0).powi(2)
+        })
+        .sum::<f32>()
+        .sqrt();
+    for (i, j) in [(0, 1), (0, 30), (17, 59), (42, 43)] {
+        let approximate = quantized.distance(i, j).expect("in range");
+        let reference = exact.distance(i, j).expect("in range");
+        assert!(
+            (approximate - reference).abs() <= bound,
+            "({i}, {j}): {approximate} vs {reference}"
+        );
+    }
+}
+
+#[rstest]
+fn int8_cuts_memory_by_about_four_times() {
+    let rows = blobs(2, 200, 1536);
+    let quantized = provider(&rows)
+        .quantize(Quantization::Int8)
+        .expect("int8 quantization must succeed");
+
+    let dense_bytes = rows.len() * 1536 * size_of::<f32>();
+    let ratio = dense_bytes as f64 / quantized.storage_bytes() as f64;
+    assert!(ratio > 3.9, "compression ratio {ratio}");
+    assert_eq!(quantized.dimension(), 1536);
+    assert_eq!(quantized.dimension_hint(), None);
+}
+
+#[rstest]
+#[case::int8(Quantization::Int8, 0.95)]
+#[case::product(PRODUCT, 0.9)]
+fn clustering_stays_within_tolerance(#[case] quantization: Quantization, #[case] min_ari: f64) {
+    let rows = blobs(4, 100, 8);
+    let reference = labels(&provider(&rows));
+    let quantized = provider(&rows)
+        .quantize(quantization)
+        .expect("quantization must succeed");
+
+    let ari = adjusted_rand_index(&reference, &labels(&quantized)).expect("same length");
+    assert!(ari >= min_ari, "{quantization:?}: ARI {ari}");
+}
+
+#[rstest]
+#[case::int8(Quantization::Int8)]
+#[case::product(PRODUCT)]
+fn query_distances_approximate_only_the_row(#[case] quantization: Quantization) {
+    let rows = blobs(2, 30, 32);
+    let quantized = provider(&rows)
+        .quantize(quantization)
+        .expect("quantization must succeed");
+    let query: Vec<f32> = rows[5].iter().map(|value| value + 0.25).collect();
+
+    let decoded = quantized.decode_row(40).expect("in range");
+    let expected = query
+        .iter()
+        .zip(&decoded)
+        .map(|(a, b)| (a - b) * (a - b))
+        .sum::<f32>()
+        .sqrt();
+    let actual = quantized.distance_to(&query, 40).expect("valid query");
+    assert!((actual - expected).abs() < 1.0e-3, "{actual} vs {expected}");
+    assert_eq!(quantized.quantization(), quantization);
+}
+
+#[rstest]
+fn query_distances_check_the_dimension() {
+    let quantized = provider(&blobs(1, 4, 8))
+        .quantize(Quantization::Int8)
+        .expect("int8 quantization must succeed");
+
+    let err = quantized
+        .distance_to(&[0.0; 3], 0)
+        .expect_err("short query must fail");
+    assert!(matches!(
+        err,
+        chutoro_core::DataSourceError::DimensionMismatch { left: 3, right: 8 }
+    ));
+}
+
+#[rstest]
+fn product_quantization_rejects_uneven_subspaces() {
+    let err = provider(&blobs(1, 4, 10))
+        .quantize(PRODUCT)
+        .expect_err("4 does not divide 10");
+
+    assert!(matches!(
+        err,
+        DenseMatrixProviderError::InvalidSubspaceCount {
+            dimension: 10,
+            subspaces: 4
+        }
+    ));
+}
+
+#[rstest]
+fn quantization_keeps_the_fitted_scaling() {
+    let quantized = provider(&blobs(1, 4, 8))
+        .with_normalization(Normalization::ZScore)
+        .quantize(Quantization::Int8)
+        .expect("int8 quantization must succeed");
+
+    let scaling = quantized.scaling().expect("scaling must be kept");
+    assert_eq!(scaling.method(), Normalization::ZScore);
+}
@@ -0,0 +1,110 @@
+//! Tests for `DenseMatrixProvider::batch_distances_into`.
+//!
+//! The query-centric batch packs its targets for the vectorised kernel, so
+//! each case checks it against one scalar `distance` call per target, within
+//! `1.0e-5_f32`, and that failures leave the output untouched.
+
+use super::DenseMatrixProvider;
+use chutoro_core::{DataSource, DataSourceError};
+use rstest::rstest;
+
+fn provider(rows: &[Vec<f32>]) -> DenseMatrixProvider {
+    let dimension = rows
+        .first()
+        .map(Vec::len)
+        .expect("rows must include at least one vector");
+    let flat_values: Vec<f32> = rows.iter().flat_map(|row| row.iter().copied()).collect();
+    DenseMatrixProvider::from_parts("query-batch", rows.len(), dimension, flat_values)
+}
+
+#[rstest]
+#[case::single_target(vec![vec![1.0, 0.0, 1.0], vec![0.0, 1.0, 0.0]], 0, vec![1])]
+#[case::odd_dimension(
+    vec![
+        vec![1.0, 3.0, 5.0, 7.0, 9.0],
+        vec![2.0, 4.0, 6.0, 8.0, 10.0],
+        vec![0.5, 1.5, 2.5, 3.5, 4.5],
+    ],
+    2,
+    vec![0, 1, 2],
+)]
+#[case::repeated_targets(
+    vec![vec![0.0, 0.0, 0.0, 0.0], vec![1.0, 1.0, 1.0, 1.0], vec![2.0, 2.0, 2.0, 2.0]],
+    0,
+    vec![1, 1, 2, 0],
+)]
+#[case::more_targets_than_lanes(
+    (0..20_u8).map(|row| vec![f32::from(row), f32::from(row) * 0.5, 1.0]).collect(),
+    3,
+    (0..20).rev().collect(),
+)]
+#[case::non_finite_canonicalises_to_nan(
+    vec![vec![1.0, f32::INFINITY, 3.0], vec![1.0, 2.0, 3.0], vec![0.0, 0.0, 0.0]],
+    1,
+    vec![0, 2],
+)]
+fn batch_distances_into_matches_scalar_distance(
+    #[case] rows: Vec<Vec<f32>>,
+    #[case] query: usize,
+    #[case] targets: Vec<usize>,
+) {
+    let provider = provider(&rows);
+    let mut out = vec![0.0_f32; targets.len()];
+    provider
+        .batch_distances_into(query, &targets, &mut out)
+        .expect("batch distances should succeed");
+
+    for (target, actual) in targets.iter().copied().zip(out) {
+        let expected = provider
+            .distance(query, target)
+            .expect("scalar distance should succeed");
+        if expected.is_nan() {
+            assert!(
+                actual.is_nan(),
+                "target {target}: actual={actual}, expected=NaN"
+            );
+        } else {
+            assert!(
+                (actual - expected).abs() <= 1.0e-5_f32,
+                "target {target}: actual={actual}, expected={expected}",
+            );
+        }
+    }
+}
+
+#[rstest]
+#[case::query_out_of_bounds(7, vec![0, 1], 7)]
+#[case::target_out_of_bounds(0, vec![1, 9], 9)]
+fn batch_distances_into_preserves_output_on_error(
+    #[case] query: usize,
+    #[case] targets: Vec<usize>,
+    #[case] index: usize,
+) {
+    let provider = provider(&[vec![1.0, 2.0], vec![3.0, 4.0]]);
+    let mut out = vec![10.0_f32, 20.0_f32];
+
+    let err = provider
+        .batch_distances_into(query, &targets, &mut out)
+        .expect_err("out-of-bounds index must fail");
+
+    assert_eq!(err, DataSourceError::OutOfBounds { index });
+    assert_eq!(out, vec![10.0_f32, 20.0_f32]);
+}
+
+#[rstest]
+fn batch_distances_into_rejects_length_mismatch() {
+    let provider = provider(&[vec![1.0, 2.0], vec![3.0, 4.0]]);
+    let mut out = vec![0.0_f32; 1];
+
+    let err = provider
+        .batch_distances_into(0, &[0, 1], &mut out)
+        .expect_err("mismatched output must fail");
+
+    assert_eq!(
+        err,
+        DataSourceError::OutputLengthMismatch {
+            out: 1,
+            expected: 2
+        }
+    );
+}
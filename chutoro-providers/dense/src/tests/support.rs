@@ -89,12 +89,9 @@ pub(crate) fn try_from_record_batches(
 
     for batch in batches {
         let schema = batch.schema();
-        let index =
-            schema
-                .index_of(column)
-                .map_err(|_| DenseMatrixProviderError::ColumnNotFound {
-                    column: column.to_owned(),
-                })?;
+        let index = schema
+            .index_of(column)
+            .map_err(|_| DenseMatrixProviderError::column_not_found(column, &schema))?;
         let field = schema.field(index);
         let width = validate_fixed_size_list_field(field, column)?;
         if let Some(expected) = dimension {
@@ -175,3 +172,16 @@ fn fixed_size_list_from_values(
         None,
     )
 }
+
+pub(crate) fn write_parquet_batches(batches: &[RecordBatch]) -> Bytes {
+    let schema = batches.first().expect("at least one batch").schema();
+    let mut buffer = Vec::new();
+    {
+        let mut writer = ArrowWriter::try_new(&mut buffer, schema, None).expect("writer");
+        for batch in batches {
+            writer.write(batch).expect("write batch");
+        }
+        writer.close().expect("close");
+    }
+    Bytes::from(buffer)
+}
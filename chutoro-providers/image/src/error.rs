@@ -0,0 +1,38 @@
+//! Errors raised while loading images into an [`crate::ImageFolderProvider`].
+
+use std::io;
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+/// Errors produced when constructing an [`crate::ImageFolderProvider`].
+#[derive(Debug, Error)]
+pub enum ImageProviderError {
+    /// A directory could not be listed or an image could not be read.
+    #[error("failed to read `{path}`: {source}")]
+    Io {
+        /// Path that triggered the failure.
+        path: PathBuf,
+        /// Underlying operating system error.
+        #[source]
+        source: io::Error,
+    },
+    /// A file with an image extension could not be decoded.
+    #[error("failed to decode `{path}`: {source}")]
+    Decode {
+        /// Path of the undecodable image.
+        path: PathBuf,
+        /// Underlying decoder error.
+        #[source]
+        source: image::ImageError,
+    },
+    /// The directory holds no files with a supported image extension.
+    #[error("no supported images found under `{dir}`")]
+    NoImages {
+        /// Directory that was searched.
+        dir: PathBuf,
+    },
+    /// [`crate::ImageFeature::Pixels`] was requested with a side of zero.
+    #[error("pixel thumbnails need a side of at least one pixel")]
+    ZeroPixelSide,
+}
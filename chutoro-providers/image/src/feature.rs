@@ -0,0 +1,190 @@
+//! Per-image features: perceptual hashes and downsampled pixels.
+//!
+//! Every feature starts from a greyscale copy of the image shrunk with a
+//! triangle filter, which discards the resolution, compression artefacts,
+//! and colour shifts that distinguish near-duplicates.
+
+use std::f32::consts::PI;
+
+use image::imageops::{self, FilterType};
+use image::{DynamicImage, GrayImage};
+
+/// The feature extracted from each image, which also fixes the distance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageFeature {
+    /// A 64-bit difference hash compared by Hamming distance.
+    ///
+    /// Each bit records whether a pixel of a 9×8 thumbnail is brighter than
+    /// its right-hand neighbour. Cheap, and robust to scaling and
+    /// recompression.
+    DHash,
+    /// A 64-bit perceptual hash compared by Hamming distance.
+    ///
+    /// Each bit records whether one of the 64 lowest-frequency DCT
+    /// coefficients of a 32×32 thumbnail exceeds their median. Slower than
+    /// [`Self::DHash`] but more tolerant of brightness and contrast changes.
+    PHash,
+    /// A `side`×`side` greyscale thumbnail with values in `[0, 1]`, compared
+    /// by Euclidean distance.
+    Pixels {
+        /// Width and height of the thumbnail in pixels.
+        side: u32,
+    },
+}
+
+impl ImageFeature {
+    /// Returns the metric identifier reported by the provider.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_providers_image::ImageFeature;
+    ///
+    /// assert_eq!(ImageFeature::PHash.metric(), "hamming");
+    /// assert_eq!(ImageFeature::Pixels { side: 8 }.metric(), "euclidean");
+    /// ```
+    #[must_use]
+    pub const fn metric(self) -> &'static str {
+        match self {
+            Self::DHash | Self::PHash => "hamming",
+            Self::Pixels { .. } => "euclidean",
+        }
+    }
+}
+
+/// Features of every loaded image, in path order.
+#[derive(Debug)]
+pub(crate) enum Features {
+    Hashes(Vec<u64>),
+    Pixels { dimension: usize, values: Vec<f32> },
+}
+
+/// One image's feature, before the features are gathered into [`Features`].
+pub(crate) enum Extracted {
+    Hash(u64),
+    Pixels(Vec<f32>),
+}
+
+pub(crate) fn extract(image: &DynamicImage, feature: ImageFeature) -> Extracted {
+    let grey = image.to_luma8();
+    match feature {
+        ImageFeature::DHash => Extracted::Hash(difference_hash(&grey)),
+        ImageFeature::PHash => Extracted::Hash(perceptual_hash(&grey)),
+        ImageFeature::Pixels { side } => Extracted::Pixels(thumbnail(&grey, side)),
+    }
+}
+
+fn difference_hash(grey: &GrayImage) -> u64 {
+    let small = imageops::resize(grey, 9, 8, FilterType::Triangle);
+    let mut hash = 0_u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            let brighter = small.get_pixel(x, y)[0] > small.get_pixel(x + 1, y)[0];
+            hash = (hash << 1) | u64::from(brighter);
+        }
+    }
+    hash
+}
+
+fn perceptual_hash(grey: &GrayImage) -> u64 {
+    const SIDE: usize = 32;
+    const KEEP: usize = 8;
+    let small = imageops::resize(grey, SIDE as u32, SIDE as u32, FilterType::Triangle);
+    let pixels: Vec<f32> = small.pixels().map(|pixel| f32::from(pixel[0])).collect();
+    // Only the lowest KEEP frequencies on each axis are needed, so the
+    // separable DCT-II is evaluated for those alone.
+    let basis: Vec<f32> = (0..KEEP)
+        .flat_map(|frequency| {
+            (0..SIDE).map(move |x| {
+                (PI * (2 * x + 1) as f32 * frequency as f32 / (2 * SIDE) as f32).cos()
+            })
+        })
+        .collect();
+    let rows: Vec<f32> = pixels
+        .chunks_exact(SIDE)
+        .flat_map(|row| {
+            basis
+                .chunks_exact(SIDE)
+                .map(|cosines| dot(row, cosines))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    let coefficients: Vec<f32> = basis
+        .chunks_exact(SIDE)
+        .flat_map(|cosines| {
+            let rows = &rows;
+            (0..KEEP).map(move |u| {
+                (0..SIDE)
+                    .map(|y| rows[y * KEEP + u] * cosines[y])
+                    .sum::<f32>()
+            })
+        })
+        .collect();
+    // The DC term measures overall brightness, so it is left out of the
+    // median that each coefficient is compared against.
+    let mut ac = coefficients[1..].to_vec();
+    ac.sort_by(f32::total_cmp);
+    let median = ac[ac.len() / 2];
+    coefficients.iter().fold(0_u64, |hash, &value| {
+        (hash << 1) | u64::from(value > median)
+    })
+}
+
+fn thumbnail(grey: &GrayImage, side: u32) -> Vec<f32> {
+    imageops::resize(grey, side, side, FilterType::Triangle)
+        .pixels()
+        .map(|pixel| f32::from(pixel[0]) / 255.0)
+        .collect()
+}
+
+fn dot(left: &[f32], right: &[f32]) -> f32 {
+    left.iter().zip(right).map(|(a, b)| a * b).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    //! Unit tests for the hash bit layouts.
+
+    use super::*;
+    use image::Luma;
+
+    fn horizontal_gradient(width: u32, height: u32, rising: bool) -> GrayImage {
+        GrayImage::from_fn(width, height, |x, _| {
+            let level = (x * 255 / (width - 1)) as u8;
+            Luma([if rising { level } else { 255 - level }])
+        })
+    }
+
+    #[test]
+    fn difference_hash_sets_a_bit_per_falling_step() {
+        assert_eq!(difference_hash(&GrayImage::new(40, 30)), 0);
+        assert_eq!(
+            difference_hash(&horizontal_gradient(90, 80, false)),
+            u64::MAX
+        );
+        assert_eq!(difference_hash(&horizontal_gradient(90, 80, true)), 0);
+    }
+
+    #[test]
+    fn perceptual_hash_ignores_scale() {
+        // Smoothly interpolated noise spreads energy over every low
+        // frequency, like a photograph and unlike a plain gradient.
+        let mut state = 0x2545_f491_u32;
+        let coarse = GrayImage::from_fn(8, 8, |_, _| {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            Luma([(state >> 24) as u8])
+        });
+        let large = imageops::resize(&coarse, 256, 128, FilterType::Triangle);
+        let small = imageops::resize(&large, 64, 32, FilterType::Triangle);
+
+        let distance = (perceptual_hash(&large) ^ perceptual_hash(&small)).count_ones();
+
+        assert!(distance <= 4, "hashes differ in {distance} bits");
+    }
+
+    #[test]
+    fn thumbnails_scale_pixels_to_unit_range() {
+        let values = thumbnail(&GrayImage::from_pixel(10, 10, Luma([255])), 4);
+
+        assert_eq!(values, vec![1.0; 16]);
+    }
+}
@@ -0,0 +1,18 @@
+//! Image folder provider for perceptual-hash clustering.
+//!
+//! [`ImageFolderProvider`] walks a directory, reduces every image to a compact
+//! feature, and implements [`chutoro_core::DataSource`] over those features.
+//! Perceptual hashes are compared by Hamming distance, so resized,
+//! recompressed, or lightly edited copies of an image land a few bits apart
+//! and cluster together. Downsampled greyscale pixels are compared by
+//! Euclidean distance instead.
+
+mod error;
+mod feature;
+mod provider;
+mod walk;
+
+pub use error::ImageProviderError;
+pub use feature::ImageFeature;
+pub use provider::ImageFolderProvider;
+pub use walk::image_paths;
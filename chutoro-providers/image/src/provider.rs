@@ -0,0 +1,235 @@
+//! The [`ImageFolderProvider`] data source.
+
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use chutoro_core::{DataSource, DataSourceError, MetricClass, MetricDescriptor};
+use image::ImageReader;
+
+use crate::error::ImageProviderError;
+use crate::feature::{Extracted, Features, ImageFeature, extract};
+use crate::walk::image_paths;
+
+/// Images from a directory, reduced to hashes or thumbnails.
+///
+/// Points are the images in lexicographic path order; [`Self::paths`] maps a
+/// point index back to its file.
+#[derive(Debug)]
+pub struct ImageFolderProvider {
+    name: String,
+    paths: Vec<PathBuf>,
+    feature: ImageFeature,
+    features: Features,
+}
+
+impl ImageFolderProvider {
+    /// Loads every image under `dir`, recursing into subdirectories.
+    ///
+    /// Files are selected by extension; BMP, GIF, JPEG, PNG, and WebP images
+    /// are supported, and other files are ignored. Images are decoded in
+    /// parallel across the available cores.
+    ///
+    /// # Errors
+    /// Returns [`ImageProviderError::NoImages`] when `dir` holds no supported
+    /// images, [`ImageProviderError::Io`] when a directory or file cannot be
+    /// read, [`ImageProviderError::Decode`] when an image is corrupt, and
+    /// [`ImageProviderError::ZeroPixelSide`] for a zero-sized thumbnail.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::DataSource;
+    /// use chutoro_providers_image::{ImageFeature, ImageFolderProvider};
+    /// use image::{GrayImage, Luma};
+    ///
+    /// let dir = tempfile::tempdir()?;
+    /// let gradient = GrayImage::from_fn(64, 64, |x, _| Luma([(x * 4) as u8]));
+    /// gradient.save(dir.path().join("a.png"))?;
+    /// gradient.save(dir.path().join("b.png"))?;
+    ///
+    /// let provider = ImageFolderProvider::try_from_dir("photos", dir.path(), ImageFeature::DHash)?;
+    /// assert_eq!(provider.len(), 2);
+    /// assert_eq!(provider.distance(0, 1)?, 0.0);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_from_dir(
+        name: impl Into<String>,
+        dir: impl AsRef<Path>,
+        feature: ImageFeature,
+    ) -> Result<Self, ImageProviderError> {
+        let dir = dir.as_ref();
+        let paths = image_paths(dir)?;
+        if paths.is_empty() {
+            return Err(ImageProviderError::NoImages {
+                dir: dir.to_path_buf(),
+            });
+        }
+        Self::try_from_paths(name, paths, feature)
+    }
+
+    /// Loads the images at `paths`, in the order given.
+    ///
+    /// # Errors
+    /// Returns the errors of [`Self::try_from_dir`] for each path.
+    pub fn try_from_paths(
+        name: impl Into<String>,
+        paths: Vec<PathBuf>,
+        feature: ImageFeature,
+    ) -> Result<Self, ImageProviderError> {
+        if feature == (ImageFeature::Pixels { side: 0 }) {
+            return Err(ImageProviderError::ZeroPixelSide);
+        }
+        let features = gather(load_all(&paths, feature)?, feature);
+        Ok(Self {
+            name: name.into(),
+            paths,
+            feature,
+            features,
+        })
+    }
+
+    /// Returns the path of every image, indexed by point.
+    #[must_use]
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    /// Returns the feature extracted from each image.
+    #[must_use]
+    pub fn feature(&self) -> ImageFeature {
+        self.feature
+    }
+
+    /// Returns the perceptual hash of every image, or `None` when the
+    /// provider holds pixel thumbnails.
+    #[must_use]
+    pub fn hashes(&self) -> Option<&[u64]> {
+        match &self.features {
+            Features::Hashes(hashes) => Some(hashes),
+            Features::Pixels { .. } => None,
+        }
+    }
+}
+
+/// Decodes and reduces every image, splitting the paths evenly across the
+/// available cores and keeping the results in path order.
+fn load_all(
+    paths: &[PathBuf],
+    feature: ImageFeature,
+) -> Result<Vec<Extracted>, ImageProviderError> {
+    let workers = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let chunk = paths.len().div_ceil(workers).max(1);
+    thread::scope(|scope| {
+        let handles: Vec<_> = paths
+            .chunks(chunk)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|path| load(path, feature))
+                        .collect::<Result<Vec<_>, _>>()
+                })
+            })
+            .collect();
+        let mut extracted = Vec::with_capacity(paths.len());
+        for handle in handles {
+            match handle.join() {
+                Ok(chunk) => extracted.extend(chunk?),
+                Err(panic) => std::panic::resume_unwind(panic),
+            }
+        }
+        Ok(extracted)
+    })
+}
+
+fn load(path: &Path, feature: ImageFeature) -> Result<Extracted, ImageProviderError> {
+    let io_error = |source| ImageProviderError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    let image = ImageReader::open(path)
+        .map_err(io_error)?
+        .with_guessed_format()
+        .map_err(io_error)?
+        .decode()
+        .map_err(|source| ImageProviderError::Decode {
+            path: path.to_path_buf(),
+            source,
+        })?;
+    Ok(extract(&image, feature))
+}
+
+fn gather(extracted: Vec<Extracted>, feature: ImageFeature) -> Features {
+    match feature {
+        ImageFeature::DHash | ImageFeature::PHash => Features::Hashes(
+            extracted
+                .into_iter()
+                .filter_map(|item| match item {
+                    Extracted::Hash(hash) => Some(hash),
+                    Extracted::Pixels(_) => None,
+                })
+                .collect(),
+        ),
+        ImageFeature::Pixels { side } => {
+            let side = side as usize;
+            let values = extracted
+                .into_iter()
+                .filter_map(|item| match item {
+                    Extracted::Pixels(values) => Some(values),
+                    Extracted::Hash(_) => None,
+                })
+                .flatten()
+                .collect();
+            Features::Pixels {
+                dimension: side * side,
+                values,
+            }
+        }
+    }
+}
+
+impl DataSource for ImageFolderProvider {
+    fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn metric_descriptor(&self) -> MetricDescriptor {
+        // Distinct images can share a hash or downscaled pixels.
+        MetricDescriptor::new(self.feature.metric()).with_class(MetricClass::PseudoMetric)
+    }
+
+    fn dimension_hint(&self) -> Option<usize> {
+        match &self.features {
+            Features::Hashes(_) => None,
+            Features::Pixels { dimension, .. } => Some(*dimension),
+        }
+    }
+
+    fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+        match &self.features {
+            Features::Hashes(hashes) => {
+                let left = hashes
+                    .get(i)
+                    .ok_or(DataSourceError::OutOfBounds { index: i })?;
+                let right = hashes
+                    .get(j)
+                    .ok_or(DataSourceError::OutOfBounds { index: j })?;
+                Ok((left ^ right).count_ones() as f32)
+            }
+            Features::Pixels { dimension, values } => {
+                let row = |index: usize| {
+                    values
+                        .get(index * dimension..(index + 1) * dimension)
+                        .ok_or(DataSourceError::OutOfBounds { index })
+                };
+                let (left, right) = (row(i)?, row(j)?);
+                let squared: f32 = left.iter().zip(right).map(|(a, b)| (a - b) * (a - b)).sum();
+                Ok(squared.sqrt())
+            }
+        }
+    }
+}
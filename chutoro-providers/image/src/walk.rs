@@ -0,0 +1,45 @@
+//! Directory traversal that collects the images a provider will load.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use image::ImageFormat;
+
+use crate::error::ImageProviderError;
+
+/// Lists every file under `dir` whose extension names a supported image
+/// format, recursing into subdirectories, in lexicographic path order.
+///
+/// Symbolic links to directories are not followed, so link cycles cannot
+/// trap the walk; symbolic links to files are listed like the files
+/// themselves. [`crate::ImageFolderProvider::try_from_dir`] loads exactly
+/// these files, so callers can use the list to fingerprint a dataset.
+///
+/// # Errors
+/// Returns [`ImageProviderError::Io`] when a directory cannot be read.
+pub fn image_paths(dir: &Path) -> Result<Vec<PathBuf>, ImageProviderError> {
+    let io_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |source| ImageProviderError::Io { path, source }
+    };
+    let mut paths = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        for entry in fs::read_dir(&current).map_err(io_error(&current))? {
+            let entry = entry.map_err(io_error(&current))?;
+            let path = entry.path();
+            let file_type = entry.file_type().map_err(io_error(&path))?;
+            if file_type.is_dir() {
+                pending.push(path);
+            } else if is_supported_image(&path) {
+                paths.push(path);
+            }
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+fn is_supported_image(path: &Path) -> bool {
+    ImageFormat::from_path(path).is_ok_and(|format| format.reading_enabled())
+}
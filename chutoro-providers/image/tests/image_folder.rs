@@ -0,0 +1,163 @@
+//! Integration tests for loading image folders and clustering duplicates.
+
+use std::fs;
+use std::path::Path;
+
+use chutoro_core::{ChutoroBuilder, DataSource, MetricClass};
+use chutoro_providers_image::{ImageFeature, ImageFolderProvider, ImageProviderError};
+use image::imageops::{self, FilterType};
+use image::{GrayImage, Luma};
+use rstest::{fixture, rstest};
+use tempfile::TempDir;
+
+/// A distinctive image for each `seed`: seeded noise on an 8×8 grid,
+/// smoothly interpolated up to `side` pixels, so unrelated images share no
+/// structure while each one survives downscaling.
+fn pattern(seed: u32, side: u32) -> GrayImage {
+    let mut state = seed.wrapping_mul(0x9e37_79b9) | 1;
+    let coarse = GrayImage::from_fn(8, 8, |_, _| {
+        state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        Luma([(state >> 24) as u8])
+    });
+    imageops::resize(&coarse, side, side, FilterType::Triangle)
+}
+
+fn save(image: &GrayImage, path: &Path) {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).expect("create directories");
+    }
+    image.save(path).expect("save image");
+}
+
+/// Three originals, each with two rescaled copies, plus an unrelated file.
+#[fixture]
+fn duplicates() -> TempDir {
+    let dir = tempfile::tempdir().expect("tempdir");
+    for seed in 0..3 {
+        let original = pattern(seed + 1, 128);
+        save(
+            &original,
+            &dir.path().join(format!("set{seed}/original.png")),
+        );
+        for (copy, side) in [(0, 96), (1, 64)] {
+            let resized = imageops::resize(&original, side, side, FilterType::Triangle);
+            save(
+                &resized,
+                &dir.path().join(format!("set{seed}/copy{copy}.png")),
+            );
+        }
+    }
+    fs::write(dir.path().join("notes.txt"), "not an image").expect("write notes");
+    dir
+}
+
+#[rstest]
+fn walks_subdirectories_in_path_order(duplicates: TempDir) {
+    let provider =
+        ImageFolderProvider::try_from_dir("photos", duplicates.path(), ImageFeature::DHash)
+            .expect("folder must load");
+
+    let names: Vec<_> = provider
+        .paths()
+        .iter()
+        .map(|path| path.strip_prefix(duplicates.path()).expect("inside dir"))
+        .map(|path| path.to_string_lossy().replace('\\', "/"))
+        .collect();
+
+    assert_eq!(provider.len(), 9);
+    assert_eq!(
+        names[..3],
+        ["set0/copy0.png", "set0/copy1.png", "set0/original.png"]
+    );
+    assert_eq!(provider.metric_descriptor().as_str(), "hamming");
+    assert_eq!(
+        provider.metric_descriptor().class(),
+        Some(MetricClass::PseudoMetric)
+    );
+}
+
+#[rstest]
+#[case::dhash(ImageFeature::DHash)]
+#[case::phash(ImageFeature::PHash)]
+#[case::pixels(ImageFeature::Pixels { side: 8 })]
+fn copies_are_closer_than_other_images(duplicates: TempDir, #[case] feature: ImageFeature) {
+    let provider = ImageFolderProvider::try_from_dir("photos", duplicates.path(), feature)
+        .expect("folder must load");
+
+    let within = provider.distance(0, 2).expect("copy to original");
+    let across = provider.distance(0, 3).expect("copy to another set");
+
+    assert!(within < across, "{feature:?}: {within} >= {across}");
+}
+
+#[rstest]
+fn duplicates_cluster_together(duplicates: TempDir) {
+    let provider =
+        ImageFolderProvider::try_from_dir("photos", duplicates.path(), ImageFeature::PHash)
+            .expect("folder must load");
+    let result = ChutoroBuilder::new()
+        .with_min_cluster_size(2)
+        .build()
+        .expect("configuration must be valid")
+        .run(&provider)
+        .expect("run must succeed");
+
+    let labels = result.assignments();
+    for set in labels.chunks(3) {
+        assert!(set.iter().all(|label| *label == set[0]), "{labels:?}");
+    }
+    assert_eq!(result.cluster_count(), 3);
+}
+
+#[rstest]
+fn pixel_features_expose_no_hashes(duplicates: TempDir) {
+    let provider = ImageFolderProvider::try_from_dir(
+        "photos",
+        duplicates.path(),
+        ImageFeature::Pixels { side: 4 },
+    )
+    .expect("folder must load");
+
+    assert!(provider.hashes().is_none());
+    assert_eq!(provider.metric_descriptor().as_str(), "euclidean");
+    assert_eq!(
+        provider.metric_descriptor().class(),
+        Some(MetricClass::PseudoMetric)
+    );
+}
+
+#[rstest]
+fn rejects_folders_without_images() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    fs::write(dir.path().join("readme.md"), "# empty").expect("write");
+
+    let err = ImageFolderProvider::try_from_dir("empty", dir.path(), ImageFeature::DHash)
+        .expect_err("no images must fail");
+
+    assert!(matches!(err, ImageProviderError::NoImages { .. }));
+}
+
+#[rstest]
+fn rejects_corrupt_images(duplicates: TempDir) {
+    fs::write(duplicates.path().join("broken.png"), b"not really a png").expect("write");
+
+    let err = ImageFolderProvider::try_from_dir("photos", duplicates.path(), ImageFeature::DHash)
+        .expect_err("corrupt images must fail");
+
+    assert!(
+        matches!(&err, ImageProviderError::Decode { path, .. } if path.ends_with("broken.png")),
+        "{err:?}"
+    );
+}
+
+#[rstest]
+fn rejects_zero_sized_thumbnails(duplicates: TempDir) {
+    let err = ImageFolderProvider::try_from_dir(
+        "photos",
+        duplicates.path(),
+        ImageFeature::Pixels { side: 0 },
+    )
+    .expect_err("zero side must fail");
+
+    assert!(matches!(err, ImageProviderError::ZeroPixelSide));
+}
@@ -0,0 +1,229 @@
+//! Levenshtein distance kernels shared by the text providers.
+//!
+//! The classic kernel fills the dynamic-programming table one cell at a time.
+//! The bit-parallel kernel implements Myers' algorithm in the blocked form
+//! described by Hyyrö: each column of the table is held as vertical deltas in
+//! 64-row words, so one text character advances 64 rows with a handful of
+//! word operations. An optional maximum distance lets either kernel stop as
+//! soon as the result is known to reach it.
+
+use std::collections::HashMap;
+
+use strsim::levenshtein;
+
+/// Rows of the dynamic-programming table held per machine word.
+const BLOCK_ROWS: usize = 64;
+
+/// Characters with a dense entry in the match-mask table.
+const ASCII: usize = 128;
+
+/// Algorithm used to compute Levenshtein distances.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum LevenshteinKernel {
+    /// `strsim`'s dynamic-programming implementation, touching every cell of
+    /// the table.
+    #[default]
+    Classic,
+    /// Myers' bit-parallel algorithm, advancing 64 table rows per word
+    /// operation. Much faster on long lines.
+    BitParallel,
+}
+
+/// Levenshtein distance configuration for the text providers.
+///
+/// # Examples
+/// ```
+/// use chutoro_providers_text::{Levenshtein, LevenshteinKernel};
+///
+/// let exact = Levenshtein::new(LevenshteinKernel::BitParallel);
+/// assert_eq!(exact.distance("kitten", "sitting"), 3);
+///
+/// let banded = exact.with_max_distance(2);
+/// assert_eq!(banded.distance("kitten", "sitting"), 2);
+/// assert_eq!(banded.distance("kitten", "mitten"), 1);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Levenshtein {
+    kernel: LevenshteinKernel,
+    max_distance: Option<usize>,
+}
+
+impl Levenshtein {
+    /// Computes exact distances with `kernel`.
+    #[must_use]
+    pub const fn new(kernel: LevenshteinKernel) -> Self {
+        Self {
+            kernel,
+            max_distance: None,
+        }
+    }
+
+    /// Clamps distances to `max_distance`, letting the kernel stop as soon
+    /// as the true distance is known to reach it.
+    ///
+    /// Clamping keeps the triangle inequality, so the result is still a
+    /// metric, but every pair at least `max_distance` apart becomes equally
+    /// far. Choose a limit above the edit distances that separate clusters.
+    #[must_use]
+    pub const fn with_max_distance(mut self, max_distance: usize) -> Self {
+        self.max_distance = Some(max_distance);
+        self
+    }
+
+    /// Returns the configured kernel.
+    #[rustfmt::skip]
+    #[must_use]
+    pub const fn kernel(&self) -> LevenshteinKernel { self.kernel }
+
+    /// Returns the distance limit, if any.
+    #[rustfmt::skip]
+    #[must_use]
+    pub const fn max_distance(&self) -> Option<usize> { self.max_distance }
+
+    /// Returns the Levenshtein distance between `left` and `right` in
+    /// Unicode scalar values, clamped to the maximum distance if one is set.
+    #[must_use]
+    pub fn distance(&self, left: &str, right: &str) -> usize {
+        let max = self.max_distance.unwrap_or(usize::MAX);
+        match self.kernel {
+            LevenshteinKernel::Classic => levenshtein(left, right).min(max),
+            LevenshteinKernel::BitParallel => bit_parallel(left, right, max),
+        }
+    }
+}
+
+/// Computes the distance with Myers' algorithm, returning `max` as soon as
+/// the distance cannot fall below it.
+fn bit_parallel(left: &str, right: &str, max: usize) -> usize {
+    let left: Vec<char> = left.chars().collect();
+    let right: Vec<char> = right.chars().collect();
+    let (pattern, text) = trim_common_affixes(&left, &right);
+    let (pattern, text) = if pattern.len() <= text.len() {
+        (pattern, text)
+    } else {
+        (text, pattern)
+    };
+    if pattern.is_empty() || text.len() - pattern.len() >= max {
+        return text.len().min(max);
+    }
+
+    let masks = MatchMasks::new(pattern);
+    let mut blocks = vec![Block::default(); masks.blocks];
+    let last_row = 1_u64 << ((pattern.len() - 1) % BLOCK_ROWS);
+    let mut score = pattern.len();
+    for (column, &character) in text.iter().enumerate() {
+        let eq = masks.get(character);
+        let mut carry = 1;
+        let last = blocks.len() - 1;
+        for (index, block) in blocks.iter_mut().enumerate() {
+            let bottom = if index == last { last_row } else { 1 << 63 };
+            carry = block.advance(eq[index], carry, bottom);
+        }
+        score = score.wrapping_add_signed(isize::from(carry));
+        let remaining = text.len() - column - 1;
+        if score.saturating_sub(remaining) >= max {
+            return max;
+        }
+    }
+    score.min(max)
+}
+
+/// Drops the prefix and suffix both strings share, which never add edits.
+fn trim_common_affixes<'a>(left: &'a [char], right: &'a [char]) -> (&'a [char], &'a [char]) {
+    let prefix = left.iter().zip(right).take_while(|(a, b)| a == b).count();
+    let (left, right) = (&left[prefix..], &right[prefix..]);
+    let suffix = left
+        .iter()
+        .rev()
+        .zip(right.iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    (&left[..left.len() - suffix], &right[..right.len() - suffix])
+}
+
+/// Vertical deltas of one 64-row block of the current table column.
+#[derive(Clone, Copy, Debug)]
+struct Block {
+    /// Rows whose value is one more than the row above.
+    positive: u64,
+    /// Rows whose value is one less than the row above.
+    negative: u64,
+}
+
+impl Default for Block {
+    fn default() -> Self {
+        // Column zero counts up from the empty prefix, one per row.
+        Self {
+            positive: u64::MAX,
+            negative: 0,
+        }
+    }
+}
+
+impl Block {
+    /// Advances the block by one text character whose matches are `eq`,
+    /// given the horizontal delta `carry_in` entering its top row, and
+    /// returns the horizontal delta at the `bottom` row.
+    fn advance(&mut self, eq: u64, carry_in: i8, bottom: u64) -> i8 {
+        let (pv, mv) = (self.positive, self.negative);
+        let carry_negative = u64::from(carry_in < 0);
+        let xv = eq | mv;
+        let eq = eq | carry_negative;
+        let xh = ((eq & pv).wrapping_add(pv) ^ pv) | eq;
+        let ph = mv | !(xh | pv);
+        let mh = pv & xh;
+        let carry_out = i8::from(ph & bottom != 0) - i8::from(mh & bottom != 0);
+        let ph = (ph << 1) | u64::from(carry_in > 0);
+        let mh = (mh << 1) | carry_negative;
+        self.positive = mh | !(xv | ph);
+        self.negative = ph & xv;
+        carry_out
+    }
+}
+
+/// For each character, the rows of the pattern that hold it, one word per
+/// block.
+struct MatchMasks {
+    blocks: usize,
+    ascii: Vec<u64>,
+    other: HashMap<char, Vec<u64>>,
+    absent: Vec<u64>,
+}
+
+impl MatchMasks {
+    fn new(pattern: &[char]) -> Self {
+        let blocks = pattern.len().div_ceil(BLOCK_ROWS);
+        let mut masks = Self {
+            blocks,
+            ascii: vec![0; ASCII * blocks],
+            other: HashMap::new(),
+            absent: vec![0; blocks],
+        };
+        for (row, &character) in pattern.iter().enumerate() {
+            let (block, bit) = (row / BLOCK_ROWS, row % BLOCK_ROWS);
+            masks.get_mut(character)[block] |= 1 << bit;
+        }
+        masks
+    }
+
+    fn get(&self, character: char) -> &[u64] {
+        let code = character as usize;
+        if code < ASCII {
+            return &self.ascii[code * self.blocks..(code + 1) * self.blocks];
+        }
+        self.other
+            .get(&character)
+            .map_or(&self.absent, Vec::as_slice)
+    }
+
+    fn get_mut(&mut self, character: char) -> &mut [u64] {
+        let code = character as usize;
+        if code < ASCII {
+            return &mut self.ascii[code * self.blocks..(code + 1) * self.blocks];
+        }
+        self.other
+            .entry(character)
+            .or_insert_with(|| vec![0; self.blocks])
+    }
+}
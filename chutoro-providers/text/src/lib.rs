@@ -1,10 +1,27 @@
 //! Text provider for line-based UTF-8 sources implementing [`DataSource`].
-use std::io::BufRead;
+//!
+//! [`TextProvider`] holds every line in memory; [`MappedTextProvider`] reads
+//! lines lazily from a memory-mapped file for inputs too large for that. Both
+//! compute distances with a configurable [`Levenshtein`] kernel.
+//! [`MinHashTextSource`] instead keeps a MinHash signature per line and
+//! estimates Jaccard distances between shingle sets, for corpora where edit
+//! distance is too slow.
+use std::{
+    hash::{BuildHasher, BuildHasherDefault, DefaultHasher},
+    io::BufRead,
+};
 
-use chutoro_core::{DataSource, DataSourceError};
-use strsim::levenshtein;
+use chutoro_core::{DataSource, DataSourceError, IdMap, MetricClass, MetricDescriptor, RowIdError};
 use thiserror::Error;
 
+mod levenshtein;
+mod mapped;
+mod minhash;
+
+pub use levenshtein::{Levenshtein, LevenshteinKernel};
+pub use mapped::MappedTextProvider;
+pub use minhash::{MinHashConfig, MinHashTextSource, Shingling};
+
 /// Errors produced when constructing a [`TextProvider`].
 #[derive(Debug, Error)]
 pub enum TextProviderError {
@@ -14,6 +31,12 @@ pub enum TextProviderError {
     /// Reading from the input failed.
     #[error("failed to read text source: {0}")]
     Io(#[from] std::io::Error),
+    /// A line of a mapped file is not valid UTF-8.
+    #[error("line {line} of the text source is not valid UTF-8")]
+    InvalidUtf8 {
+        /// Zero-based index of the offending line.
+        line: usize,
+    },
 }
 
 /// UTF-8 text provider that reports Levenshtein distances between lines.
@@ -21,6 +44,8 @@ pub enum TextProviderError {
 pub struct TextProvider {
     data: Vec<String>,
     name: String,
+    ids: Option<IdMap>,
+    levenshtein: Levenshtein,
 }
 
 impl TextProvider {
@@ -49,6 +74,8 @@ impl TextProvider {
         Ok(Self {
             data: lines,
             name: name.into(),
+            ids: None,
+            levenshtein: Levenshtein::default(),
         })
     }
 
@@ -80,6 +107,59 @@ impl TextProvider {
         Self::new(name, lines)
     }
 
+    /// Attaches one identifier per line, reported through
+    /// [`DataSource::row_ids`].
+    ///
+    /// # Errors
+    /// Returns [`RowIdError::LengthMismatch`] when `ids` does not hold one
+    /// entry per line and [`RowIdError::Duplicate`] when an identifier repeats.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::DataSource;
+    /// use chutoro_providers_text::TextProvider;
+    ///
+    /// let provider = TextProvider::new("demo", vec!["kitten".into(), "sitting".into()])
+    ///     .expect("provider must build")
+    ///     .with_ids(vec!["cat-1".into(), "cat-2".into()])
+    ///     .expect("identifiers are unique");
+    /// let ids = provider.row_ids().expect("identifiers were attached");
+    /// assert_eq!(ids.index_of("cat-2"), Some(1));
+    /// ```
+    pub fn with_ids(mut self, ids: Vec<String>) -> Result<Self, RowIdError> {
+        self.ids = Some(IdMap::for_rows(ids, self.data.len())?);
+        Ok(self)
+    }
+
+    /// Computes distances with `levenshtein` instead of the classic kernel.
+    ///
+    /// [`LevenshteinKernel::BitParallel`] returns the same distances much
+    /// faster on long lines, such as log messages, and
+    /// [`Levenshtein::with_max_distance`] clamps distances so dissimilar
+    /// lines are rejected early.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::DataSource;
+    /// use chutoro_providers_text::{Levenshtein, LevenshteinKernel, TextProvider};
+    ///
+    /// let provider = TextProvider::new("demo", vec!["kitten".into(), "sitting".into()])
+    ///     .expect("provider must build")
+    ///     .with_levenshtein(Levenshtein::new(LevenshteinKernel::BitParallel));
+    /// assert_eq!(provider.distance(0, 1).expect("indices are in range"), 3.0);
+    /// ```
+    #[must_use]
+    pub fn with_levenshtein(mut self, levenshtein: Levenshtein) -> Self {
+        self.levenshtein = levenshtein;
+        self
+    }
+
+    /// Returns the Levenshtein configuration used for distances.
+    #[must_use]
+    pub fn levenshtein(&self) -> Levenshtein {
+        self.levenshtein
+    }
+
     /// Returns the stored UTF-8 lines.
     #[must_use]
     pub fn lines(&self) -> &[String] {
@@ -116,6 +196,19 @@ impl DataSource for TextProvider {
         &self.name
     }
 
+    fn metric_descriptor(&self) -> MetricDescriptor {
+        MetricDescriptor::new("levenshtein").with_class(MetricClass::Metric)
+    }
+
+    fn row_ids(&self) -> Option<&IdMap> {
+        self.ids.as_ref()
+    }
+
+    fn dedupe_key(&self, index: usize) -> Option<u64> {
+        let line = self.data.get(index)?;
+        Some(BuildHasherDefault::<DefaultHasher>::default().hash_one(line))
+    }
+
     #[expect(
         clippy::cast_precision_loss,
         reason = "Distances are exposed as f32 to match the DataSource API."
@@ -129,7 +222,7 @@ impl DataSource for TextProvider {
             .data
             .get(j)
             .ok_or(DataSourceError::OutOfBounds { index: j })?;
-        let distance = levenshtein(left, right);
+        let distance = self.levenshtein.distance(left, right);
         Ok(distance as f32)
     }
 }
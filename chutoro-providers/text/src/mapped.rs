@@ -0,0 +1,217 @@
+//! Memory-mapped text provider for files too large to load into memory.
+//!
+//! [`MappedTextProvider`] maps the file and keeps only a sparse line index:
+//! the byte offset of every 64th line. Reading a line seeks to its chunk and
+//! skips the remaining newlines, and a small LRU keeps recently decoded lines
+//! so the repeated lookups of an HNSW search do not decode them again. Resident
+//! memory is therefore the index, the cache, and whichever pages the operating
+//! system keeps mapped, rather than the whole file.
+
+use std::{
+    fs::File,
+    hash::{BuildHasher, BuildHasherDefault, DefaultHasher},
+    num::NonZeroUsize,
+    path::Path,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use chutoro_core::{DataSource, DataSourceError, MetricClass, MetricDescriptor};
+use lru::LruCache;
+use memmap2::Mmap;
+
+use crate::{Levenshtein, TextProviderError};
+
+/// Lines per index chunk; the index stores one offset per chunk.
+const CHUNK_LINES: usize = 64;
+
+/// Decoded lines cached by default.
+const DEFAULT_CACHED_LINES: NonZeroUsize = match NonZeroUsize::new(4096) {
+    Some(lines) => lines,
+    None => panic!("the default cache holds at least one line"),
+};
+
+/// UTF-8 text provider that reads lines lazily from a memory-mapped file and
+/// reports Levenshtein distances between them.
+///
+/// Lines follow the same rules as [`crate::TextProvider::try_from_reader`]:
+/// one point per line, with trailing carriage returns and newlines trimmed.
+#[derive(Debug)]
+pub struct MappedTextProvider {
+    name: String,
+    map: Mmap,
+    chunks: Vec<usize>,
+    len: usize,
+    cache: Mutex<LruCache<usize, Arc<str>>>,
+    levenshtein: Levenshtein,
+}
+
+impl MappedTextProvider {
+    /// Maps the file at `path` and indexes its lines.
+    ///
+    /// Opening scans the file once to count lines, record chunk offsets, and
+    /// check that every line is valid UTF-8; no line is kept in memory. The
+    /// file must not be modified or truncated while the provider is alive.
+    ///
+    /// # Errors
+    /// Returns [`TextProviderError::Io`] if the file cannot be opened or
+    /// mapped, [`TextProviderError::EmptyInput`] if it holds no lines, and
+    /// [`TextProviderError::InvalidUtf8`] if a line is not valid UTF-8.
+    ///
+    /// # Examples
+    /// ```
+    /// use chutoro_core::DataSource;
+    /// use chutoro_providers_text::MappedTextProvider;
+    ///
+    /// let path = std::env::temp_dir().join("chutoro-mapped-doc.txt");
+    /// std::fs::write(&path, "kitten\nsitting\n")?;
+    /// let provider = MappedTextProvider::try_from_path("demo", &path)?;
+    /// assert_eq!(provider.len(), 2);
+    /// assert_eq!(provider.distance(0, 1)?, 3.0);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_from_path(
+        name: impl Into<String>,
+        path: impl AsRef<Path>,
+    ) -> Result<Self, TextProviderError> {
+        let file = File::open(path)?;
+        // SAFETY: the map is only read, and callers are told not to modify
+        // the file while the provider is alive; decoding re-checks UTF-8 so a
+        // concurrent edit cannot produce an invalid `str`.
+        let map = unsafe { Mmap::map(&file) }?;
+        let (chunks, len) = index_lines(&map)?;
+        Ok(Self {
+            name: name.into(),
+            map,
+            chunks,
+            len,
+            cache: Mutex::new(LruCache::new(DEFAULT_CACHED_LINES)),
+            levenshtein: Levenshtein::default(),
+        })
+    }
+
+    /// Replaces the decoded-line cache with one holding up to `lines` lines.
+    ///
+    /// The default of 4,096 lines suits HNSW builds, which revisit a point's
+    /// neighbourhood many times in quick succession; pair it with the HNSW
+    /// distance cache so repeated edit distances are not recomputed either.
+    #[must_use]
+    pub fn with_cache_capacity(mut self, lines: NonZeroUsize) -> Self {
+        self.cache = Mutex::new(LruCache::new(lines));
+        self
+    }
+
+    /// Returns how many decoded lines the cache holds at most.
+    #[must_use]
+    pub fn cache_capacity(&self) -> NonZeroUsize {
+        self.lock_cache().cap()
+    }
+
+    /// Computes distances with `levenshtein`, as
+    /// [`crate::TextProvider::with_levenshtein`] does.
+    #[must_use]
+    pub fn with_levenshtein(mut self, levenshtein: Levenshtein) -> Self {
+        self.levenshtein = levenshtein;
+        self
+    }
+
+    /// Returns the Levenshtein configuration used for distances.
+    #[must_use]
+    pub fn levenshtein(&self) -> Levenshtein {
+        self.levenshtein
+    }
+
+    /// Returns the line at `index`, decoding it if it is not cached.
+    ///
+    /// # Errors
+    /// Returns [`DataSourceError::OutOfBounds`] when `index` is not a line.
+    pub fn line(&self, index: usize) -> Result<Arc<str>, DataSourceError> {
+        if let Some(line) = self.lock_cache().get(&index) {
+            return Ok(Arc::clone(line));
+        }
+        let bytes = self
+            .line_bytes(index)
+            .ok_or(DataSourceError::OutOfBounds { index })?;
+        let line: Arc<str> = Arc::from(String::from_utf8_lossy(bytes));
+        self.lock_cache().put(index, Arc::clone(&line));
+        Ok(line)
+    }
+
+    fn line_bytes(&self, index: usize) -> Option<&[u8]> {
+        if index >= self.len {
+            return None;
+        }
+        let start = *self.chunks.get(index / CHUNK_LINES)?;
+        let line = self
+            .map
+            .get(start..)?
+            .split(|&byte| byte == b'\n')
+            .nth(index % CHUNK_LINES)?;
+        Some(trim_line_ending(line))
+    }
+
+    fn lock_cache(&self) -> std::sync::MutexGuard<'_, LruCache<usize, Arc<str>>> {
+        // Recover from a poisoned lock: every cache update is a single call,
+        // so a panic elsewhere cannot leave it half-applied.
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl DataSource for MappedTextProvider {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn metric_descriptor(&self) -> MetricDescriptor {
+        MetricDescriptor::new("levenshtein").with_class(MetricClass::Metric)
+    }
+
+    fn dedupe_key(&self, index: usize) -> Option<u64> {
+        let line = self.line_bytes(index)?;
+        Some(BuildHasherDefault::<DefaultHasher>::default().hash_one(line))
+    }
+
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "Distances are exposed as f32 to match the DataSource API."
+    )]
+    fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+        let left = self.line(i)?;
+        let right = self.line(j)?;
+        Ok(self.levenshtein.distance(&left, &right) as f32)
+    }
+}
+
+/// Returns the offset of every [`CHUNK_LINES`]-th line and the line count,
+/// checking that each line is valid UTF-8.
+fn index_lines(bytes: &[u8]) -> Result<(Vec<usize>, usize), TextProviderError> {
+    let mut chunks = Vec::new();
+    let mut offset = 0;
+    let mut len: usize = 0;
+    for line in bytes.split_inclusive(|&byte| byte == b'\n') {
+        if len.is_multiple_of(CHUNK_LINES) {
+            chunks.push(offset);
+        }
+        if std::str::from_utf8(line).is_err() {
+            return Err(TextProviderError::InvalidUtf8 { line: len });
+        }
+        offset += line.len();
+        len += 1;
+    }
+    if len == 0 {
+        return Err(TextProviderError::EmptyInput);
+    }
+    Ok((chunks, len))
+}
+
+/// Strips trailing carriage returns and newlines, as
+/// [`str::trim_end_matches`] does for in-memory lines.
+fn trim_line_ending(mut line: &[u8]) -> &[u8] {
+    while let [rest @ .., b'\r' | b'\n'] = line {
+        line = rest;
+    }
+    line
+}
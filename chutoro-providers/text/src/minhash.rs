@@ -0,0 +1,320 @@
+//! MinHash text source for near-duplicate detection at scale.
+//!
+//! Each line is split into a set of shingles, either runs of tokens or runs of
+//! characters, and reduced to a fixed-length MinHash signature when the source
+//! is built. The fraction of signature slots two lines disagree on estimates
+//! their Jaccard distance, so a distance costs one pass over two signatures
+//! however long the lines are, and the text itself is not retained.
+
+use std::{io::BufRead, num::NonZeroUsize};
+
+use chutoro_core::{DataSource, DataSourceError, IdMap, MetricClass, MetricDescriptor, RowIdError};
+
+use crate::TextProviderError;
+
+/// Signature slots used unless configured otherwise.
+const DEFAULT_SIGNATURE_LEN: NonZeroUsize = match NonZeroUsize::new(128) {
+    Some(len) => len,
+    None => panic!("the default signature has at least one slot"),
+};
+
+/// SplitMix64 increment, used to space the per-slot salts.
+const SALT_INCREMENT: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// Byte hashed between the tokens of a shingle so `"ab c"` and `"a bc"`
+/// differ.
+const TOKEN_SEPARATOR: u8 = 0x1f;
+
+/// How lines are split into the sets compared by [`MinHashTextSource`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Shingling {
+    /// Runs of `n` consecutive whitespace-separated tokens.
+    Tokens(NonZeroUsize),
+    /// Runs of `n` consecutive characters.
+    Characters(NonZeroUsize),
+}
+
+/// Parameters of the MinHash signatures built by [`MinHashTextSource`].
+///
+/// # Examples
+/// ```
+/// use std::num::NonZeroUsize;
+///
+/// use chutoro_providers_text::{MinHashConfig, Shingling};
+///
+/// let words = NonZeroUsize::new(2).expect("literal is non-zero");
+/// let config = MinHashConfig::new(Shingling::Tokens(words))
+///     .with_signature_len(NonZeroUsize::new(256).expect("literal is non-zero"))
+///     .with_seed(7);
+/// assert_eq!(config.signature_len().get(), 256);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MinHashConfig {
+    shingling: Shingling,
+    signature_len: NonZeroUsize,
+    seed: u64,
+}
+
+impl MinHashConfig {
+    /// Builds 128-slot signatures over `shingling` with seed zero.
+    #[must_use]
+    pub const fn new(shingling: Shingling) -> Self {
+        Self {
+            shingling,
+            signature_len: DEFAULT_SIGNATURE_LEN,
+            seed: 0,
+        }
+    }
+
+    /// Sets the number of signature slots. The standard error of an
+    /// estimated distance shrinks with the square root of this length.
+    #[must_use]
+    pub const fn with_signature_len(mut self, signature_len: NonZeroUsize) -> Self {
+        self.signature_len = signature_len;
+        self
+    }
+
+    /// Sets the seed from which the slot hash functions are derived.
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Returns how lines are shingled.
+    #[rustfmt::skip]
+    #[must_use]
+    pub const fn shingling(&self) -> Shingling { self.shingling }
+
+    /// Returns the number of signature slots.
+    #[rustfmt::skip]
+    #[must_use]
+    pub const fn signature_len(&self) -> NonZeroUsize { self.signature_len }
+
+    /// Returns the hash seed.
+    #[rustfmt::skip]
+    #[must_use]
+    pub const fn seed(&self) -> u64 { self.seed }
+
+    fn salts(&self) -> Vec<u64> {
+        (1..=self.signature_len.get() as u64)
+            .map(|slot| splitmix64(self.seed.wrapping_add(slot.wrapping_mul(SALT_INCREMENT))))
+            .collect()
+    }
+}
+
+/// Text source that reports estimated Jaccard distances between the shingle
+/// sets of its lines.
+///
+/// Lines with no shingles, such as empty lines, are at distance zero from
+/// each other and one from every other line.
+#[derive(Debug)]
+pub struct MinHashTextSource {
+    name: String,
+    config: MinHashConfig,
+    signatures: Vec<u64>,
+    len: usize,
+    ids: Option<IdMap>,
+}
+
+impl MinHashTextSource {
+    /// Computes a signature for each of `lines`.
+    ///
+    /// # Errors
+    /// Returns [`TextProviderError::EmptyInput`] when `lines` is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use chutoro_core::DataSource;
+    /// use chutoro_providers_text::{MinHashConfig, MinHashTextSource, Shingling};
+    ///
+    /// let trigrams = Shingling::Characters(NonZeroUsize::new(3).expect("literal is non-zero"));
+    /// let source = MinHashTextSource::new(
+    ///     "docs",
+    ///     ["the quick brown fox", "the quick brown fox", "lorem ipsum"],
+    ///     MinHashConfig::new(trigrams),
+    /// )?;
+    /// assert_eq!(source.distance(0, 1)?, 0.0);
+    /// assert!(source.distance(0, 2)? > 0.9);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn new<S: AsRef<str>>(
+        name: impl Into<String>,
+        lines: impl IntoIterator<Item = S>,
+        config: MinHashConfig,
+    ) -> Result<Self, TextProviderError> {
+        let salts = config.salts();
+        let mut signatures = Vec::new();
+        for line in lines {
+            append_signature(line.as_ref(), config.shingling, &salts, &mut signatures);
+        }
+        Self::from_signatures(name.into(), config, signatures)
+    }
+
+    /// Computes a signature for each line read from `reader`, trimming line
+    /// endings as [`crate::TextProvider::try_from_reader`] does. Each line is
+    /// discarded once hashed, so the text is never held in memory.
+    ///
+    /// # Errors
+    /// Returns [`TextProviderError::EmptyInput`] if `reader` produced no lines
+    /// and [`TextProviderError::Io`] if reading fails.
+    pub fn try_from_reader(
+        name: impl Into<String>,
+        mut reader: impl BufRead,
+        config: MinHashConfig,
+    ) -> Result<Self, TextProviderError> {
+        let salts = config.salts();
+        let mut signatures = Vec::new();
+        let mut buffer = String::new();
+        while reader.read_line(&mut buffer)? > 0 {
+            let line = buffer.trim_end_matches(['\r', '\n']);
+            append_signature(line, config.shingling, &salts, &mut signatures);
+            buffer.clear();
+        }
+        Self::from_signatures(name.into(), config, signatures)
+    }
+
+    /// Attaches one identifier per line, reported through
+    /// [`DataSource::row_ids`].
+    ///
+    /// # Errors
+    /// Returns [`RowIdError::LengthMismatch`] when `ids` does not hold one
+    /// entry per line and [`RowIdError::Duplicate`] when an identifier repeats.
+    pub fn with_ids(mut self, ids: Vec<String>) -> Result<Self, RowIdError> {
+        self.ids = Some(IdMap::for_rows(ids, self.len)?);
+        Ok(self)
+    }
+
+    /// Returns the signature parameters.
+    #[rustfmt::skip]
+    #[must_use]
+    pub fn config(&self) -> MinHashConfig { self.config }
+
+    fn from_signatures(
+        name: String,
+        config: MinHashConfig,
+        signatures: Vec<u64>,
+    ) -> Result<Self, TextProviderError> {
+        let len = signatures.len() / config.signature_len.get();
+        if len == 0 {
+            return Err(TextProviderError::EmptyInput);
+        }
+        Ok(Self {
+            name,
+            config,
+            signatures,
+            len,
+            ids: None,
+        })
+    }
+
+    /// Returns the signature of the line at `index`.
+    #[must_use]
+    pub fn signature(&self, index: usize) -> Option<&[u64]> {
+        let slots = self.config.signature_len.get();
+        let start = index.checked_mul(slots)?;
+        self.signatures.get(start..start.checked_add(slots)?)
+    }
+}
+
+impl DataSource for MinHashTextSource {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn metric_descriptor(&self) -> MetricDescriptor {
+        MetricDescriptor::new("minhash-jaccard").with_class(MetricClass::Metric)
+    }
+
+    fn row_ids(&self) -> Option<&IdMap> {
+        self.ids.as_ref()
+    }
+
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "Signatures hold far fewer than 2^24 slots."
+    )]
+    fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+        let left = self
+            .signature(i)
+            .ok_or(DataSourceError::OutOfBounds { index: i })?;
+        let right = self
+            .signature(j)
+            .ok_or(DataSourceError::OutOfBounds { index: j })?;
+        let differing = left.iter().zip(right).filter(|(a, b)| a != b).count();
+        Ok(differing as f32 / left.len() as f32)
+    }
+}
+
+/// Appends the MinHash signature of `line` to `signatures`.
+fn append_signature(line: &str, shingling: Shingling, salts: &[u64], signatures: &mut Vec<u64>) {
+    let start = signatures.len();
+    signatures.resize(start + salts.len(), u64::MAX);
+    let signature = &mut signatures[start..];
+    for shingle in shingle_hashes(line, shingling) {
+        for (slot, &salt) in signature.iter_mut().zip(salts) {
+            *slot = (*slot).min(splitmix64(shingle ^ salt));
+        }
+    }
+}
+
+/// Hashes each shingle of `line`. A non-empty line shorter than one shingle
+/// forms a single shingle of its own.
+fn shingle_hashes(line: &str, shingling: Shingling) -> Vec<u64> {
+    match shingling {
+        Shingling::Tokens(n) => {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            windows(tokens.len(), n.get())
+                .map(|(start, end)| token_hash(&tokens[start..end]))
+                .collect()
+        }
+        Shingling::Characters(n) => {
+            let mut bounds: Vec<usize> = line.char_indices().map(|(offset, _)| offset).collect();
+            let characters = bounds.len();
+            bounds.push(line.len());
+            windows(characters, n.get())
+                .map(|(start, end)| fnv1a(line[bounds[start]..bounds[end]].bytes()))
+                .collect()
+        }
+    }
+}
+
+/// Yields the `[start, end)` ranges of every run of `n` items among `len`,
+/// or the whole range when it is non-empty but shorter than `n`.
+fn windows(len: usize, n: usize) -> impl Iterator<Item = (usize, usize)> {
+    let width = n.min(len);
+    let count = if len == 0 { 0 } else { len - width + 1 };
+    (0..count).map(move |start| (start, start + width))
+}
+
+fn token_hash(tokens: &[&str]) -> u64 {
+    let bytes = tokens.iter().enumerate().flat_map(|(index, token)| {
+        let separator = (index > 0).then_some(TOKEN_SEPARATOR);
+        separator.into_iter().chain(token.bytes())
+    });
+    fnv1a(bytes)
+}
+
+/// Hashes bytes with 64-bit FNV-1a, which is stable across platforms and
+/// Rust releases, unlike the standard library's hasher.
+fn fnv1a(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    bytes.into_iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Applies one SplitMix64 step, mixing a shingle hash with a slot's salt.
+fn splitmix64(mut state: u64) -> u64 {
+    state = state.wrapping_add(SALT_INCREMENT);
+    let mut z = state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
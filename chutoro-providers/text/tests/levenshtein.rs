@@ -0,0 +1,89 @@
+//! Tests for the Levenshtein kernels, checked against `strsim`.
+use chutoro_core::DataSource;
+use chutoro_providers_text::{Levenshtein, LevenshteinKernel, TextProvider};
+use rstest::rstest;
+use strsim::levenshtein;
+
+const ALPHABET: [char; 6] = ['a', 'b', 'c', 'é', '字', ' '];
+
+/// Generates `count` strings of up to `max_len` characters from a small
+/// alphabet with a fixed linear congruential generator.
+fn strings(count: usize, max_len: usize, seed: u64) -> Vec<String> {
+    let mut state = seed;
+    let mut next = move |bound: usize| {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (state >> 33) as usize % bound
+    };
+    (0..count)
+        .map(|_| {
+            let len = next(max_len + 1);
+            (0..len).map(|_| ALPHABET[next(ALPHABET.len())]).collect()
+        })
+        .collect()
+}
+
+#[rstest]
+#[case::single_block(20, 1)]
+#[case::two_blocks(130, 2)]
+#[case::many_blocks(300, 3)]
+fn bit_parallel_matches_strsim(#[case] max_len: usize, #[case] seed: u64) {
+    let kernel = Levenshtein::new(LevenshteinKernel::BitParallel);
+    let lines = strings(24, max_len, seed);
+
+    for left in &lines {
+        for right in &lines {
+            assert_eq!(
+                kernel.distance(left, right),
+                levenshtein(left, right),
+                "{left:?} vs {right:?}"
+            );
+        }
+    }
+}
+
+#[rstest]
+#[case("kitten", "sitting", 3)]
+#[case("", "abc", 3)]
+#[case("abc", "", 3)]
+#[case("prefix-same-suffix", "prefix-diff-suffix", 4)]
+#[case("naïve", "naive", 1)]
+fn bit_parallel_handles_edge_cases(
+    #[case] left: &str,
+    #[case] right: &str,
+    #[case] expected: usize,
+) {
+    let kernel = Levenshtein::new(LevenshteinKernel::BitParallel);
+
+    assert_eq!(kernel.distance(left, right), expected);
+}
+
+#[rstest]
+fn max_distance_clamps_both_kernels() {
+    let lines = strings(16, 150, 9);
+    for kernel in [LevenshteinKernel::Classic, LevenshteinKernel::BitParallel] {
+        let banded = Levenshtein::new(kernel).with_max_distance(20);
+        for left in &lines {
+            for right in &lines {
+                let expected = levenshtein(left, right).min(20);
+                assert_eq!(banded.distance(left, right), expected, "{kernel:?}");
+            }
+        }
+    }
+}
+
+#[rstest]
+fn providers_use_the_selected_kernel() {
+    let lines = vec!["kitten".repeat(20), "sitting".repeat(20)];
+    let classic = TextProvider::new("demo", lines.clone()).expect("provider must build");
+    let banded = Levenshtein::new(LevenshteinKernel::BitParallel).with_max_distance(10);
+    let fast = TextProvider::new("demo", lines)
+        .expect("provider must build")
+        .with_levenshtein(banded);
+
+    assert_eq!(classic.levenshtein(), Levenshtein::default());
+    assert_eq!(fast.levenshtein(), banded);
+    assert_eq!(classic.distance(0, 1).expect("in range"), 60.0);
+    assert_eq!(fast.distance(0, 1).expect("in range"), 10.0);
+}
@@ -0,0 +1,104 @@
+//! Integration tests covering the memory-mapped text provider.
+use std::{io::Cursor, num::NonZeroUsize};
+
+use chutoro_core::{DataSource, DataSourceError, MetricClass};
+use chutoro_providers_text::{
+    Levenshtein, LevenshteinKernel, MappedTextProvider, TextProvider, TextProviderError,
+};
+use rstest::rstest;
+use tempfile::NamedTempFile;
+
+fn mapped(contents: &[u8]) -> Result<MappedTextProvider, TextProviderError> {
+    let file = NamedTempFile::new().expect("temporary file must be created");
+    std::fs::write(file.path(), contents).expect("temporary file must be written");
+    MappedTextProvider::try_from_path("demo", file.path())
+}
+
+/// Two hundred numbered lines, enough to span several index chunks.
+fn numbered_lines() -> String {
+    (0..200).map(|line| format!("line {line}\n")).collect()
+}
+
+#[rstest]
+#[case("alpha\nbeta\n".to_owned())]
+#[case("carriage\r\nreturn\r\n".to_owned())]
+#[case("lonely".to_owned())]
+#[case("\n\nnaïve\n".to_owned())]
+#[case(numbered_lines())]
+fn lines_match_the_in_memory_provider(#[case] raw: String) {
+    let expected = TextProvider::try_from_reader("demo", Cursor::new(raw.as_str()))
+        .expect("in-memory provider must build");
+
+    let provider = mapped(raw.as_bytes()).expect("mapped provider must build");
+
+    assert_eq!(provider.len(), expected.len());
+    for (index, line) in expected.lines().iter().enumerate() {
+        assert_eq!(&*provider.line(index).expect("line must exist"), line);
+    }
+}
+
+#[rstest]
+fn distances_match_the_in_memory_provider() {
+    let raw = numbered_lines();
+    let expected = TextProvider::try_from_reader("demo", Cursor::new(raw.as_str()))
+        .expect("in-memory provider must build");
+    let capacity = NonZeroUsize::new(2).expect("literal is non-zero");
+    let kernel = Levenshtein::new(LevenshteinKernel::BitParallel);
+
+    let provider = mapped(raw.as_bytes())
+        .expect("mapped provider must build")
+        .with_cache_capacity(capacity)
+        .with_levenshtein(kernel);
+
+    assert_eq!(provider.cache_capacity(), capacity);
+    assert_eq!(provider.levenshtein(), kernel);
+    assert_eq!(provider.metric_descriptor(), expected.metric_descriptor());
+    assert_eq!(
+        provider.metric_descriptor().class(),
+        Some(MetricClass::Metric)
+    );
+    for (left, right) in [(0, 1), (63, 64), (150, 7), (199, 0)] {
+        assert_eq!(
+            provider
+                .distance(left, right)
+                .expect("distance must succeed"),
+            expected
+                .distance(left, right)
+                .expect("distance must succeed"),
+        );
+    }
+}
+
+#[rstest]
+fn rejects_empty_files() {
+    let err = mapped(b"").expect_err("empty file must fail");
+
+    assert!(matches!(err, TextProviderError::EmptyInput));
+}
+
+#[rstest]
+fn rejects_invalid_utf8_with_the_line_index() {
+    let err = mapped(b"fine\nalso fine\nbad \xff byte\n").expect_err("invalid UTF-8 must fail");
+
+    assert!(matches!(err, TextProviderError::InvalidUtf8 { line: 2 }));
+}
+
+#[rstest]
+fn rejects_missing_files() {
+    let err = MappedTextProvider::try_from_path("demo", "/nonexistent/chutoro.txt")
+        .expect_err("missing file must fail");
+
+    assert!(matches!(err, TextProviderError::Io(_)));
+}
+
+#[rstest]
+fn distance_bounds_check() {
+    let provider = mapped(b"a\nb\n").expect("mapped provider must build");
+
+    let err = provider
+        .distance(0, 2)
+        .expect_err("distance must check bounds");
+
+    assert!(matches!(err, DataSourceError::OutOfBounds { index: 2 }));
+    assert_eq!(provider.name(), "demo");
+}
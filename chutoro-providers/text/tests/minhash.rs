@@ -0,0 +1,140 @@
+//! Tests for the MinHash text source.
+use std::{io::Cursor, num::NonZeroUsize};
+
+use chutoro_core::{ChutoroBuilder, DataSource, DataSourceError, MetricClass};
+use chutoro_providers_text::{MinHashConfig, MinHashTextSource, Shingling, TextProviderError};
+use rstest::rstest;
+
+fn non_zero(value: usize) -> NonZeroUsize {
+    NonZeroUsize::new(value).expect("value is non-zero")
+}
+
+fn words(n: usize) -> MinHashConfig {
+    MinHashConfig::new(Shingling::Tokens(non_zero(n)))
+}
+
+/// Joins `w{start}` to `w{end - 1}` into one line.
+fn numbered_words(start: usize, end: usize) -> String {
+    (start..end)
+        .map(|word| format!("w{word}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[rstest]
+fn estimates_track_the_true_jaccard_distance() {
+    // Forty shared words out of eighty: Jaccard distance 0.5.
+    let lines = [numbered_words(0, 60), numbered_words(20, 80)];
+    let config = words(1).with_signature_len(non_zero(1024));
+
+    let source = MinHashTextSource::new("docs", &lines, config).expect("source must build");
+
+    let distance = source.distance(0, 1).expect("indices are in range");
+    assert!((distance - 0.5).abs() < 0.05, "estimated {distance}");
+    assert_eq!(source.distance(0, 0).expect("index is in range"), 0.0);
+}
+
+#[rstest]
+#[case::identical("the quick brown fox", "the quick brown fox", 0.0)]
+#[case::disjoint("alpha beta gamma", "delta epsilon zeta", 1.0)]
+#[case::both_empty("", "", 0.0)]
+#[case::one_empty("", "alpha", 1.0)]
+#[case::shorter_than_a_shingle("alpha", "alpha", 0.0)]
+fn exact_cases_are_reported_exactly(
+    #[case] left: &str,
+    #[case] right: &str,
+    #[case] expected: f32,
+) {
+    let source =
+        MinHashTextSource::new("docs", [left, right], words(2)).expect("source must build");
+
+    assert_eq!(
+        source.distance(0, 1).expect("indices are in range"),
+        expected
+    );
+}
+
+#[rstest]
+fn character_shingles_tolerate_small_edits() {
+    let trigrams =
+        MinHashConfig::new(Shingling::Characters(non_zero(3))).with_signature_len(non_zero(256));
+    let lines = [
+        "connection reset by peer at 10.0.0.1",
+        "connection reset by peer at 10.0.0.2",
+        "disk quota exceeded for user alice",
+    ];
+
+    let source = MinHashTextSource::new("logs", lines, trigrams).expect("source must build");
+
+    let near = source.distance(0, 1).expect("indices are in range");
+    let far = source.distance(0, 2).expect("indices are in range");
+    assert!(near < 0.2, "near duplicates are {near} apart");
+    assert!(far > 0.8, "unrelated lines are {far} apart");
+}
+
+#[rstest]
+fn signatures_follow_the_seed() {
+    let lines = ["alpha beta", "beta gamma"];
+    let first = MinHashTextSource::new("docs", lines, words(1)).expect("source must build");
+    let again = MinHashTextSource::new("docs", lines, words(1)).expect("source must build");
+    let reseeded =
+        MinHashTextSource::new("docs", lines, words(1).with_seed(9)).expect("source must build");
+
+    assert_eq!(first.signature(0), again.signature(0));
+    assert_ne!(first.signature(0), reseeded.signature(0));
+    assert_eq!(first.signature(0).map(<[u64]>::len), Some(128));
+    assert_eq!(first.signature(2), None);
+}
+
+#[rstest]
+fn reader_lines_match_in_memory_lines() {
+    let raw = "alpha beta\r\ngamma delta\n";
+    let read = MinHashTextSource::try_from_reader("docs", Cursor::new(raw), words(1))
+        .expect("source must build");
+    let direct = MinHashTextSource::new("docs", ["alpha beta", "gamma delta"], words(1))
+        .expect("source must build");
+
+    assert_eq!(read.len(), 2);
+    assert_eq!(read.signature(1), direct.signature(1));
+    assert_eq!(read.metric_descriptor().class(), Some(MetricClass::Metric));
+    assert_eq!(read.metric_descriptor().as_str(), "minhash-jaccard");
+}
+
+#[rstest]
+fn empty_input_and_bad_indices_are_rejected() {
+    let empty = MinHashTextSource::try_from_reader("docs", Cursor::new(""), words(1))
+        .expect_err("empty input must fail");
+    let source = MinHashTextSource::new("docs", ["alpha"], words(1)).expect("source must build");
+
+    assert!(matches!(empty, TextProviderError::EmptyInput));
+    assert!(matches!(
+        source.distance(0, 1),
+        Err(DataSourceError::OutOfBounds { index: 1 })
+    ));
+}
+
+#[rstest]
+fn near_duplicate_documents_cluster_together() {
+    let lines: Vec<String> = (0..3)
+        .flat_map(|topic| {
+            (0..6).map(move |variant| {
+                let body = numbered_words(topic * 100, topic * 100 + 40);
+                format!("{body} edit{variant}")
+            })
+        })
+        .collect();
+    let source = MinHashTextSource::new("docs", &lines, words(2)).expect("source must build");
+
+    let result = ChutoroBuilder::new()
+        .with_min_cluster_size(3)
+        .build()
+        .expect("configuration must be valid")
+        .run(&source)
+        .expect("run must succeed");
+
+    let labels = result.assignments();
+    assert_eq!(result.cluster_count(), 3);
+    for topic in labels.chunks(6) {
+        assert!(topic.iter().all(|label| *label == topic[0]));
+    }
+}
@@ -0,0 +1,49 @@
+//! Checks the text source against the exact-neighbour oracle.
+use std::num::NonZeroUsize;
+
+use chutoro_core::{
+    CpuHnsw, HnswParams,
+    oracles::{ExactKnnGraph, exact_neighbours, recall_at_k},
+};
+use chutoro_providers_text::TextProvider;
+use rstest::{fixture, rstest};
+
+/// Two groups of similar words, plus an outlier.
+#[fixture]
+fn words() -> TextProvider {
+    let lines = [
+        "kitten", "sitten", "bitten", "apple", "apply", "ample", "zebra",
+    ];
+    TextProvider::new("words", lines.map(str::to_owned).to_vec()).expect("provider must build")
+}
+
+#[rstest]
+fn nearest_words_are_one_edit_away(words: TextProvider) {
+    let graph = ExactKnnGraph::build(&words, 2).expect("distances must succeed");
+
+    let nearest = |point: usize| -> Vec<usize> {
+        let neighbours = graph.neighbours(point).expect("point must exist");
+        neighbours.iter().map(|neighbour| neighbour.id).collect()
+    };
+    assert_eq!(nearest(0), [1, 2]);
+    assert_eq!(nearest(4), [3, 5]);
+    assert!(
+        graph.neighbours(6).expect("zebra must exist")[0].distance > 1.0,
+        "zebra has no close neighbour"
+    );
+}
+
+#[rstest]
+fn hnsw_search_recovers_the_exact_neighbours(words: TextProvider) {
+    let params = HnswParams::new(4, 16).expect("params must be valid");
+    let index = CpuHnsw::build(&words, params).expect("index must build");
+    let ef = NonZeroUsize::new(16).expect("literal is non-zero");
+
+    for query in 0..words.lines().len() {
+        let expected = exact_neighbours(&words, query, 3).expect("distances must succeed");
+        let observed = index
+            .search(&words, query, ef)
+            .expect("search must succeed");
+        assert_eq!(recall_at_k(&expected, &observed, 3), 1.0, "query {query}");
+    }
+}
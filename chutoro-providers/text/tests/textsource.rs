@@ -1,7 +1,7 @@
 //! Integration tests covering the text-backed [`DataSource`] implementation.
 use std::io::Cursor;
 
-use chutoro_core::{DataSource, DataSourceError};
+use chutoro_core::{DataSource, DataSourceError, RowIdError};
 use chutoro_providers_text::{TextProvider, TextProviderError};
 use rstest::rstest;
 
@@ -101,3 +101,22 @@ fn data_source_reports_metadata() {
         .expect("distance calculation must succeed");
     assert_eq!(distance, 4.0);
 }
+
+#[rstest]
+fn duplicate_row_ids_are_rejected() {
+    let provider =
+        TextProvider::new("demo", vec!["a".into(), "b".into()]).expect("provider must build");
+
+    let err = provider
+        .with_ids(vec!["same".into(), "same".into()])
+        .expect_err("identifiers repeat");
+
+    assert!(matches!(
+        err,
+        RowIdError::Duplicate {
+            first: 0,
+            second: 1,
+            ..
+        }
+    ));
+}
@@ -0,0 +1,43 @@
+//! Runs Chutoro clustering from async services without blocking the runtime.
+//!
+//! [`chutoro_core::Chutoro::run`] is CPU-bound and holds its thread for the
+//! whole run, which stalls the worker threads of an async runtime such as the
+//! one driving an Axum or Tonic service. [`cluster_async`] moves the run onto
+//! Tokio's blocking thread pool and returns a [`ClusterTask`] that resolves to
+//! the result, publishes coarse [`ClusterProgress`] on a watch channel, and
+//! cancels the run when asked to or when dropped.
+//!
+//! # Examples
+//! ```no_run
+//! use std::sync::Arc;
+//!
+//! use chutoro_core::{ChutoroBuilder, DataSource, DataSourceError};
+//! use chutoro_tokio::{ClusterTaskError, cluster_async};
+//!
+//! struct Line(Vec<f32>);
+//!
+//! impl DataSource for Line {
+//!     fn len(&self) -> usize { self.0.len() }
+//!     fn name(&self) -> &str { "line" }
+//!     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+//!         Ok((self.0[i] - self.0[j]).abs())
+//!     }
+//! }
+//!
+//! async fn handler() -> Result<usize, ClusterTaskError> {
+//!     let source = Arc::new(Line(vec![0.0, 1.0, 2.0, 10.0, 11.0, 12.0]));
+//!     let task = cluster_async(ChutoroBuilder::new().with_min_cluster_size(2), source);
+//!     let mut progress = task.progress();
+//!     tokio::spawn(async move {
+//!         while progress.changed().await.is_ok() {
+//!             println!("clustering: {:?}", *progress.borrow());
+//!         }
+//!     });
+//!     Ok(task.await?.cluster_count())
+//! }
+//! ```
+
+mod source;
+mod task;
+
+pub use task::{ClusterProgress, ClusterTask, ClusterTaskError, cluster_async};
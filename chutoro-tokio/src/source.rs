@@ -0,0 +1,90 @@
+//! A data source adapter that stops evaluating distances once cancelled.
+//!
+//! The pipeline has no cancellation points of its own, but every stage that
+//! does real work asks the source for distances. Failing those requests makes
+//! a cancelled run unwind through its ordinary error paths within one
+//! distance evaluation per worker thread.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use chutoro_core::{DataSource, DataSourceError, IdMap, MetricDescriptor};
+
+/// Forwards to the wrapped source until `cancelled` is set, then fails every
+/// request with [`DataSourceError::Cancelled`].
+pub(crate) struct CancellableSource<'a, D> {
+    source: &'a D,
+    cancelled: &'a AtomicBool,
+}
+
+impl<'a, D: DataSource> CancellableSource<'a, D> {
+    pub(crate) const fn new(source: &'a D, cancelled: &'a AtomicBool) -> Self {
+        Self { source, cancelled }
+    }
+
+    fn check(&self) -> Result<(), DataSourceError> {
+        if self.cancelled.load(Ordering::Acquire) {
+            Err(DataSourceError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<D: DataSource> DataSource for CancellableSource<'_, D> {
+    fn len(&self) -> usize {
+        self.source.len()
+    }
+
+    fn name(&self) -> &str {
+        self.source.name()
+    }
+
+    fn metric_descriptor(&self) -> MetricDescriptor {
+        self.source.metric_descriptor()
+    }
+
+    fn dimension_hint(&self) -> Option<usize> {
+        self.source.dimension_hint()
+    }
+
+    fn row_ids(&self) -> Option<&IdMap> {
+        self.source.row_ids()
+    }
+
+    fn dedupe_key(&self, index: usize) -> Option<u64> {
+        self.source.dedupe_key(index)
+    }
+
+    fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+        self.check()?;
+        self.source.distance(i, j)
+    }
+
+    fn batch_distances(
+        &self,
+        query: usize,
+        candidates: &[usize],
+    ) -> Result<Vec<f32>, DataSourceError> {
+        self.check()?;
+        self.source.batch_distances(query, candidates)
+    }
+
+    fn batch_distances_into(
+        &self,
+        query: usize,
+        targets: &[usize],
+        out: &mut [f32],
+    ) -> Result<(), DataSourceError> {
+        self.check()?;
+        self.source.batch_distances_into(query, targets, out)
+    }
+
+    fn distance_batch(
+        &self,
+        pairs: &[(usize, usize)],
+        out: &mut [f32],
+    ) -> Result<(), DataSourceError> {
+        self.check()?;
+        self.source.distance_batch(pairs, out)
+    }
+}
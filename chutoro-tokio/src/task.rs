@@ -0,0 +1,216 @@
+//! Spawning clustering runs onto the blocking pool and awaiting them.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    task::{Context, Poll},
+};
+
+use chutoro_core::{ChutoroBuilder, ChutoroError, ClusteringResult, DataSource, StageArtefact};
+use thiserror::Error;
+use tokio::sync::{oneshot, watch};
+
+use crate::source::CancellableSource;
+
+/// How far a [`ClusterTask`] has progressed.
+///
+/// Stages are reported as they complete, so a task that has just built its
+/// HNSW index reports [`Self::IndexBuilt`] while it harvests edges. Sampled
+/// runs report the stages of the clustered sample.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ClusterProgress {
+    /// Waiting for a thread on the blocking pool.
+    Queued,
+    /// The pipeline has started.
+    Running,
+    /// The HNSW index is built or a prebuilt one was adopted.
+    IndexBuilt,
+    /// Candidate edges are weighted and ready for MST construction.
+    EdgesHarvested,
+    /// The minimum spanning forest is built.
+    ForestBuilt,
+    /// The built-in hierarchy stage extracted the clusters. Custom hierarchy
+    /// stages do not report this stage.
+    HierarchyExtracted,
+}
+
+impl ClusterProgress {
+    fn after(artefact: &StageArtefact<'_>) -> Option<Self> {
+        match artefact {
+            StageArtefact::Index { .. } => Some(Self::IndexBuilt),
+            StageArtefact::Harvest(_) => Some(Self::EdgesHarvested),
+            StageArtefact::Mst(_) => Some(Self::ForestBuilt),
+            StageArtefact::CondensedTree(_) => Some(Self::HierarchyExtracted),
+            _ => None,
+        }
+    }
+}
+
+/// Error returned when a [`ClusterTask`] does not produce a result.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum ClusterTaskError {
+    /// The task was cancelled before the run finished.
+    #[error("clustering was cancelled")]
+    Cancelled,
+    /// The builder configuration was invalid or the run failed.
+    #[error(transparent)]
+    Run(#[from] ChutoroError),
+    /// The thread running the pipeline panicked.
+    #[error("clustering thread panicked")]
+    Panicked,
+}
+
+/// A clustering run executing on Tokio's blocking thread pool.
+///
+/// Awaiting the task yields the run's result. Dropping it cancels the run,
+/// so a service whose request is abandoned stops paying for the clustering.
+#[derive(Debug)]
+#[must_use = "dropping a ClusterTask cancels the run"]
+pub struct ClusterTask {
+    result: oneshot::Receiver<Result<ClusteringResult, ClusterTaskError>>,
+    progress: watch::Receiver<ClusterProgress>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl ClusterTask {
+    /// Returns a receiver that observes the task's progress.
+    ///
+    /// The channel closes when the run ends, so `changed()` returning an
+    /// error means the task is about to resolve.
+    #[must_use]
+    pub fn progress(&self) -> watch::Receiver<ClusterProgress> {
+        self.progress.clone()
+    }
+
+    /// Asks the run to stop.
+    ///
+    /// The run stops at its next distance evaluation, and the task resolves
+    /// to [`ClusterTaskError::Cancelled`] unless it has already resolved.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    /// Returns whether [`Self::cancel`] has been called.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+}
+
+impl Future for ClusterTask {
+    type Output = Result<ClusteringResult, ClusterTaskError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // The sender is only dropped without sending if the pipeline panics.
+        Pin::new(&mut self.result)
+            .poll(cx)
+            .map(|outcome| outcome.unwrap_or(Err(ClusterTaskError::Panicked)))
+    }
+}
+
+impl Drop for ClusterTask {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+/// Runs the pipeline configured by `builder` over `source` on Tokio's
+/// blocking thread pool.
+///
+/// The builder is validated on the pool, so configuration errors resolve the
+/// task to [`ClusterTaskError::Run`]. Any stage hook already registered on
+/// the builder keeps receiving artefacts; the task registers its own hook in
+/// front of it to publish progress.
+///
+/// # Panics
+/// Panics when called outside a Tokio runtime.
+///
+/// # Examples
+/// ```no_run
+/// use std::sync::Arc;
+///
+/// use chutoro_core::{ChutoroBuilder, DataSource, DataSourceError};
+/// use chutoro_tokio::{ClusterTaskError, cluster_async};
+///
+/// struct Line(Vec<f32>);
+///
+/// impl DataSource for Line {
+///     fn len(&self) -> usize { self.0.len() }
+///     fn name(&self) -> &str { "line" }
+///     fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+///         Ok((self.0[i] - self.0[j]).abs())
+///     }
+/// }
+///
+/// # async fn example() -> Result<(), ClusterTaskError> {
+/// let source = Arc::new(Line(vec![0.0, 1.0, 10.0, 11.0]));
+/// let task = cluster_async(ChutoroBuilder::new().with_min_cluster_size(2), source);
+/// let result = tokio::select! {
+///     result = task => result?,
+///     () = tokio::time::sleep(std::time::Duration::from_secs(30)) => {
+///         // Dropping the task cancelled the run.
+///         return Err(ClusterTaskError::Cancelled);
+///     }
+/// };
+/// assert_eq!(result.assignments().len(), 4);
+/// # Ok(())
+/// # }
+/// ```
+pub fn cluster_async<D>(builder: ChutoroBuilder, source: Arc<D>) -> ClusterTask
+where
+    D: DataSource + Send + Sync + 'static,
+{
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let (progress_tx, progress) = watch::channel(ClusterProgress::Queued);
+    let (result_tx, result) = oneshot::channel();
+    let flag = Arc::clone(&cancelled);
+    // The join handle is not needed: the outcome, or a panic, reaches the
+    // task through the oneshot channel.
+    drop(tokio::task::spawn_blocking(move || {
+        let outcome = run(builder, source.as_ref(), &flag, progress_tx);
+        // A closed receiver means the task was dropped and nobody is waiting.
+        result_tx.send(outcome).ok();
+    }));
+    ClusterTask {
+        result,
+        progress,
+        cancelled,
+    }
+}
+
+fn run<D: DataSource + Sync>(
+    builder: ChutoroBuilder,
+    source: &D,
+    cancelled: &AtomicBool,
+    progress: watch::Sender<ClusterProgress>,
+) -> Result<ClusteringResult, ClusterTaskError> {
+    if cancelled.load(Ordering::Acquire) {
+        return Err(ClusterTaskError::Cancelled);
+    }
+    progress.send_replace(ClusterProgress::Running);
+    let previous = builder.stage_hook().cloned();
+    let chutoro = builder
+        .on_stage_complete(move |artefact: StageArtefact<'_>| {
+            if let Some(stage) = ClusterProgress::after(&artefact) {
+                progress.send_replace(stage);
+            }
+            if let Some(hook) = &previous {
+                hook.on_stage_complete(artefact);
+            }
+        })
+        .build()?;
+
+    let outcome = chutoro.run(&CancellableSource::new(source, cancelled));
+    // A run cancelled late may still finish; report the cancellation anyway
+    // so callers see one outcome for every cancelled task.
+    if cancelled.load(Ordering::Acquire) {
+        return Err(ClusterTaskError::Cancelled);
+    }
+    Ok(outcome?)
+}
@@ -0,0 +1,152 @@
+//! Tests for running clustering on the blocking pool from async code.
+
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use chutoro_core::{ChutoroBuilder, ChutoroError, DataSource, DataSourceError, StageArtefact};
+use chutoro_tokio::{ClusterProgress, ClusterTaskError, cluster_async};
+use rstest::{fixture, rstest};
+
+/// Points on a line in two well-separated groups, optionally slowed down to
+/// keep a run busy while the test acts on it.
+struct Line {
+    points: Vec<f32>,
+    delay: Duration,
+    calls: AtomicUsize,
+}
+
+impl DataSource for Line {
+    fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    fn name(&self) -> &str {
+        "line"
+    }
+
+    fn distance(&self, i: usize, j: usize) -> Result<f32, DataSourceError> {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        std::thread::sleep(self.delay);
+        let a = self
+            .points
+            .get(i)
+            .ok_or(DataSourceError::OutOfBounds { index: i })?;
+        let b = self
+            .points
+            .get(j)
+            .ok_or(DataSourceError::OutOfBounds { index: j })?;
+        Ok((a - b).abs())
+    }
+}
+
+fn line(count: usize, delay: Duration) -> Arc<Line> {
+    let near = (0..count).map(|i| i as f32 * 0.1);
+    let far = (0..count).map(|i| 100.0 + i as f32 * 0.1);
+    Arc::new(Line {
+        points: near.chain(far).collect(),
+        delay,
+        calls: AtomicUsize::new(0),
+    })
+}
+
+#[fixture]
+fn builder() -> ChutoroBuilder {
+    ChutoroBuilder::new().with_min_cluster_size(3).with_seed(5)
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn resolves_to_the_blocking_result(builder: ChutoroBuilder) {
+    let source = line(20, Duration::ZERO);
+    let expected = builder
+        .clone()
+        .build()
+        .expect("configuration must be valid")
+        .run(source.as_ref())
+        .expect("blocking run must succeed");
+
+    let result = cluster_async(builder, source)
+        .await
+        .expect("async run must succeed");
+
+    assert_eq!(result.assignments(), expected.assignments());
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn publishes_progress_and_keeps_existing_hooks(builder: ChutoroBuilder) {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&seen);
+    let builder = builder.on_stage_complete(move |artefact: StageArtefact<'_>| {
+        if let StageArtefact::Mst(edges) = artefact {
+            sink.lock().expect("lock is healthy").push(edges.len());
+        }
+    });
+
+    let task = cluster_async(builder, line(20, Duration::ZERO));
+    let mut progress = task.progress();
+    let watcher = tokio::spawn(async move {
+        let mut stages = vec![*progress.borrow_and_update()];
+        while progress.changed().await.is_ok() {
+            stages.push(*progress.borrow_and_update());
+        }
+        stages
+    });
+    task.await.expect("run must succeed");
+    let stages = watcher.await.expect("watcher must finish");
+
+    assert_eq!(stages.last(), Some(&ClusterProgress::HierarchyExtracted));
+    assert_eq!(*seen.lock().expect("lock is healthy"), [39]);
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn cancelled_tasks_resolve_to_cancelled(builder: ChutoroBuilder) {
+    let task = cluster_async(builder, line(20, Duration::ZERO));
+
+    task.cancel();
+
+    assert!(task.is_cancelled());
+    assert_eq!(task.await, Err(ClusterTaskError::Cancelled));
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn dropped_tasks_stop_evaluating_distances(builder: ChutoroBuilder) {
+    let source = line(200, Duration::from_millis(1));
+    let task = cluster_async(builder, Arc::clone(&source));
+    let mut progress = task.progress();
+    progress
+        .wait_for(|stage| *stage == ClusterProgress::Running)
+        .await
+        .expect("run must start");
+
+    drop(task);
+    // Let in-flight evaluations finish before sampling the count.
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let stopped_at = source.calls.load(Ordering::Relaxed);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    assert_eq!(source.calls.load(Ordering::Relaxed), stopped_at);
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn invalid_configurations_fail_the_task() {
+    let task = cluster_async(
+        ChutoroBuilder::new().with_min_cluster_size(0),
+        line(5, Duration::ZERO),
+    );
+
+    assert_eq!(
+        task.await,
+        Err(ClusterTaskError::Run(ChutoroError::InvalidMinClusterSize {
+            got: 0
+        }))
+    );
+}
@@ -0,0 +1,71 @@
+#!/usr/bin/env -S uv run python
+# /// script
+# requires-python = ">=3.13"
+# dependencies = ["h5py==3.14.0", "numpy==2.3.3"]
+# ///
+"""Write the ann-benchmarks HDF5 fixture read by the chutoro-benches tests.
+
+The file is written the way ann-benchmarks' ``write_output`` writes its
+published datasets, with h5py's default (earliest) file format, so the
+reader is checked against the library rather than a hand-assembled image.
+"""
+
+from __future__ import annotations
+
+import argparse
+from pathlib import Path
+
+import h5py
+import numpy as np
+
+DEFAULT_OUTPUT = Path("chutoro-benches/tests/fixtures/ann/grid-2-euclidean.hdf5")
+DEPTH = 20
+
+
+def grid() -> tuple[np.ndarray, np.ndarray]:
+    """Return a 10x10 grid of training points and five off-grid queries."""
+    train = np.array(
+        [[x, y] for y in range(10) for x in range(10)], dtype=np.float32
+    )
+    test = np.array(
+        [[0.3, 0.6], [2.0, 1.9], [3.7, 3.2], [5.4, 4.5], [7.1, 5.8]],
+        dtype=np.float32,
+    )
+    return train, test
+
+
+def ground_truth(
+    train: np.ndarray, test: np.ndarray
+) -> tuple[np.ndarray, np.ndarray]:
+    """Return each query's nearest training rows and their distances."""
+    distances = np.linalg.norm(test[:, None, :] - train[None, :, :], axis=2)
+    order = np.lexsort((np.broadcast_to(np.arange(len(train)), distances.shape), distances))
+    neighbours = order[:, :DEPTH]
+    return neighbours, np.take_along_axis(distances, neighbours, axis=1)
+
+
+def write(path: Path) -> None:
+    """Write the fixture to ``path``."""
+    train, test = grid()
+    neighbours, distances = ground_truth(train, test)
+    path.parent.mkdir(parents=True, exist_ok=True)
+    with h5py.File(path, "w") as output:
+        output.attrs["type"] = "dense"
+        output.attrs["distance"] = "euclidean"
+        output.attrs["dimension"] = train.shape[1]
+        output.attrs["point_type"] = "float"
+        output.create_dataset("train", data=train)
+        output.create_dataset("test", data=test)
+        output.create_dataset("neighbors", data=neighbours.astype(int))
+        output.create_dataset("distances", data=distances.astype(np.float32))
+
+
+def main() -> None:
+    """Parse the output path and write the fixture."""
+    parser = argparse.ArgumentParser(description=__doc__)
+    parser.add_argument("output", nargs="?", type=Path, default=DEFAULT_OUTPUT)
+    write(parser.parse_args().output)
+
+
+if __name__ == "__main__":
+    main()